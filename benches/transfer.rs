@@ -6,6 +6,15 @@ use tempfile::NamedTempFile;
 use wraith_files::DEFAULT_CHUNK_SIZE;
 use wraith_files::chunker::{FileChunker, FileReassembler};
 use wraith_files::tree_hash::{compute_tree_hash, compute_tree_hash_from_data};
+use wraith_integration_tests::test_data::TestDataSpec;
+
+/// Fixed-seed corpus so numbers are comparable across machines and runs
+///
+/// Text-like data exercises hashing and chunking on realistic (neither
+/// constant nor incompressible) input; the seed pins the exact bytes.
+fn bench_corpus(size: usize) -> Vec<u8> {
+    TestDataSpec::text_like(0xB37C).generate(size)
+}
 
 /// Create a NodeConfig optimized for benchmarking (NAT detection disabled)
 fn benchmark_node_config(port: u16) -> wraith_core::node::NodeConfig {
@@ -35,7 +44,7 @@ fn bench_file_chunking(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
             // Create temporary file
             let mut temp_file = NamedTempFile::new().unwrap();
-            let data = vec![0xAA; size as usize];
+            let data = bench_corpus(size as usize);
             temp_file.write_all(&data).unwrap();
             temp_file.flush().unwrap();
             let path = temp_file.path().to_path_buf();
@@ -68,7 +77,7 @@ fn bench_tree_hashing(c: &mut Criterion) {
         group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
             // Create temporary file
             let mut temp_file = NamedTempFile::new().unwrap();
-            let data = vec![0xBB; size as usize];
+            let data = bench_corpus(size as usize);
             temp_file.write_all(&data).unwrap();
             temp_file.flush().unwrap();
             let path = temp_file.path().to_path_buf();
@@ -95,7 +104,7 @@ fn bench_tree_hashing_memory(c: &mut Criterion) {
         group.throughput(Throughput::Bytes(size));
 
         group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
-            let data = vec![0xCC; size as usize];
+            let data = bench_corpus(size as usize);
 
             b.iter(|| {
                 let tree = compute_tree_hash_from_data(&data, DEFAULT_CHUNK_SIZE);
@@ -112,7 +121,7 @@ fn bench_chunk_verification(c: &mut Criterion) {
     let mut group = c.benchmark_group("chunk_verification");
 
     // Create test data
-    let chunk_data = vec![0xDD; DEFAULT_CHUNK_SIZE];
+    let chunk_data = bench_corpus(DEFAULT_CHUNK_SIZE);
     let tree = compute_tree_hash_from_data(&chunk_data, DEFAULT_CHUNK_SIZE);
 
     group.throughput(Throughput::Bytes(DEFAULT_CHUNK_SIZE as u64));
@@ -139,9 +148,10 @@ fn bench_file_reassembly(c: &mut Criterion) {
 
         group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
             // Prepare chunks
-            let num_chunks = (size as usize).div_ceil(DEFAULT_CHUNK_SIZE);
-            let chunks: Vec<_> = (0..num_chunks)
-                .map(|_| vec![0xEE; DEFAULT_CHUNK_SIZE])
+            let data = bench_corpus(size as usize);
+            let chunks: Vec<_> = data
+                .chunks(DEFAULT_CHUNK_SIZE)
+                .map(<[u8]>::to_vec)
                 .collect();
 
             b.iter(|| {
@@ -99,7 +99,9 @@ fn bench_worker_pool(c: &mut Criterion) {
                     queue_capacity: 1000,
                     pin_to_cpu: false,
                     numa_aware: false,
+                    numa_node: None,
                     buffer_pool: None,
+                    polling: Default::default(),
                 };
 
                 let pool = WorkerPool::new(config);
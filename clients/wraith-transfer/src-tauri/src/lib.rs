@@ -55,6 +55,10 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
+        // Deep links: wraith:// URIs clicked anywhere on the system open the
+        // app; the frontend subscribes via onOpenUrl and parses the URI with
+        // the same grammar as `wraith handle-uri`
+        .plugin(tauri_plugin_deep_link::init())
         .invoke_handler(tauri::generate_handler![
             commands::get_node_status,
             commands::start_node,
@@ -0,0 +1,25 @@
+//! Embeds build metadata into the `wraith` binary.
+//!
+//! The commit hash comes from `WRAITH_BUILD_COMMIT` when set (release
+//! builds via `cargo xtask dist`), falling back to `git rev-parse` for
+//! local builds and `"unknown"` outside a git checkout.
+
+use std::process::Command;
+
+fn main() {
+    let commit = std::env::var("WRAITH_BUILD_COMMIT")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .or_else(|| {
+            Command::new("git")
+                .args(["rev-parse", "--short", "HEAD"])
+                .output()
+                .ok()
+                .filter(|out| out.status.success())
+                .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=WRAITH_BUILD_COMMIT={commit}");
+    println!("cargo:rerun-if-env-changed=WRAITH_BUILD_COMMIT");
+}
@@ -2,12 +2,18 @@
 
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::net::SocketAddr;
+use std::net::{SocketAddr, ToSocketAddrs};
 use std::path::{Path, PathBuf};
 
 /// WRAITH configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
+    /// Manually pinned network profile, set by `wraith profile use <name>`
+    ///
+    /// When set, this profile is applied unconditionally and automatic
+    /// network detection is skipped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
     /// Node configuration
     pub node: NodeConfig,
     /// Network configuration
@@ -20,6 +26,62 @@ pub struct Config {
     pub transfer: TransferConfig,
     /// Logging configuration
     pub logging: LoggingConfig,
+    /// Metrics exporter configuration
+    #[serde(default)]
+    pub metrics: MetricsConfig,
+    /// Embedded relay configuration
+    #[serde(default)]
+    pub relay: RelayConfig,
+    /// Named per-network profiles (home/work/public), keyed by name
+    ///
+    /// Applied on top of the base configuration at startup; see
+    /// [`NetworkProfile`] for the selection rules.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub profiles: std::collections::BTreeMap<String, NetworkProfile>,
+}
+
+/// Named network profile overriding parts of the base configuration
+///
+/// Profiles carry the settings that legitimately differ between physical
+/// networks: relays, obfuscation level, and bandwidth caps. A profile is
+/// selected either manually (`wraith profile use <name>`, persisted as
+/// `active_profile`) or automatically when one of its match criteria fits
+/// the detected network; profiles are tried in name order and the first
+/// match wins. A profile with no criteria can only be selected manually.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct NetworkProfile {
+    /// Match on the default gateway's MAC address (`aa:bb:cc:dd:ee:ff`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_gateway_mac: Option<String>,
+    /// Match on the Wi-Fi SSID of the active interface
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_ssid: Option<String>,
+    /// Match when the default gateway lies in this subnet (CIDR, e.g. `192.168.1.0/24`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_subnet: Option<String>,
+
+    /// Relay servers for this network (replaces `discovery.relay_servers`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub relay_servers: Option<Vec<String>>,
+    /// Obfuscation level for this network (replaces `obfuscation.default_level`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obfuscation_level: Option<String>,
+    /// Per-transfer bandwidth cap for this network (replaces `transfer.bandwidth_limit`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bandwidth_limit: Option<String>,
+
+    /// Private key file for this profile (replaces `node.private_key_file`)
+    ///
+    /// Gives the profile its own keypair, so `work` and `personal` present
+    /// different node identities to the network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub private_key_file: Option<PathBuf>,
+    /// Trust store for this profile (replaces `node.trust_store_file`)
+    ///
+    /// Keeps each identity's pinned peers separate; a profile with its own
+    /// keypair should set this too.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust_store_file: Option<PathBuf>,
 }
 
 /// Node configuration
@@ -31,6 +93,12 @@ pub struct NodeConfig {
     /// Private key file path
     #[serde(default = "default_private_key_path")]
     pub private_key_file: PathBuf,
+    /// Persistent trust store (known_peers) path
+    ///
+    /// `None` leaves the trust store disabled. Usually set per profile so
+    /// each identity pins its own peers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trust_store_file: Option<PathBuf>,
 }
 
 /// Network configuration
@@ -87,6 +155,78 @@ pub struct TransferConfig {
     /// Enable resume support
     #[serde(default = "default_true")]
     pub enable_resume: bool,
+    /// Egress bandwidth limit per transfer (e.g. "10MB/s", empty = unlimited)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bandwidth_limit: Option<String>,
+}
+
+/// Metrics exporter configuration
+///
+/// When enabled, the daemon serves Prometheus text format on
+/// `GET /metrics` and the same snapshot as JSON on `GET /metrics.json`.
+/// `wraith metrics` queries the JSON endpoint of a running daemon.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsConfig {
+    /// Serve the metrics HTTP endpoint in daemon mode
+    #[serde(default)]
+    pub enabled: bool,
+    /// Address the metrics endpoint listens on
+    ///
+    /// Defaults to loopback; the endpoint has no authentication, so
+    /// exposing it beyond localhost should be a deliberate choice.
+    #[serde(default = "default_metrics_listen_addr")]
+    pub listen_addr: String,
+}
+
+/// Embedded relay configuration
+///
+/// Lets the daemon act as a relay for its own peers — e.g. a VPS node
+/// relaying between a user's two NAT'd home machines — without running a
+/// separate relay binary. In `trusted-peers` mode only peers pinned in
+/// the trust store may register; `open` runs an unrestricted relay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelayConfig {
+    /// Relay mode: `disabled`, `trusted-peers`, or `open`
+    #[serde(default = "default_relay_mode")]
+    pub mode: String,
+    /// Address the embedded relay listens on
+    #[serde(default = "default_relay_listen_addr")]
+    pub listen_addr: String,
+}
+
+impl Default for RelayConfig {
+    fn default() -> Self {
+        Self {
+            mode: default_relay_mode(),
+            listen_addr: default_relay_listen_addr(),
+        }
+    }
+}
+
+/// Structured result of a full configuration diagnosis
+///
+/// Errors prevent the node from starting; warnings flag suspicious or
+/// insecure combinations that are still allowed.
+#[derive(Debug, Default)]
+pub struct ConfigDiagnostics {
+    /// Problems that make the configuration unusable
+    pub errors: Vec<String>,
+    /// Settings that are legal but likely unintended or insecure
+    pub warnings: Vec<String>,
+}
+
+impl ConfigDiagnostics {
+    /// Whether any errors were found
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Whether the configuration is completely clean (no errors or warnings)
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
 }
 
 /// Logging configuration
@@ -140,11 +280,24 @@ fn default_log_level() -> String {
     "info".to_string()
 }
 
+fn default_metrics_listen_addr() -> String {
+    "127.0.0.1:9464".to_string()
+}
+
+fn default_relay_mode() -> String {
+    "disabled".to_string()
+}
+
+fn default_relay_listen_addr() -> String {
+    "0.0.0.0:40001".to_string()
+}
+
 impl Default for NodeConfig {
     fn default() -> Self {
         Self {
             public_key: None,
             private_key_file: default_private_key_path(),
+            trust_store_file: None,
         }
     }
 }
@@ -176,6 +329,7 @@ impl Default for TransferConfig {
             chunk_size: default_chunk_size(),
             max_concurrent: default_max_concurrent(),
             enable_resume: true,
+            bandwidth_limit: None,
         }
     }
 }
@@ -189,6 +343,15 @@ impl Default for LoggingConfig {
     }
 }
 
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            listen_addr: default_metrics_listen_addr(),
+        }
+    }
+}
+
 impl Config {
     /// Load configuration from file
     ///
@@ -218,6 +381,132 @@ impl Config {
         Ok(())
     }
 
+    /// Render the configuration as TOML with explanatory comments
+    ///
+    /// Used by `wraith init` to write a first config file that a new user
+    /// can read and edit without consulting the documentation. The output
+    /// parses back to an equivalent [`Config`].
+    #[must_use]
+    pub fn to_annotated_toml(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# WRAITH configuration\n");
+        out.push_str("# Generated by `wraith init`. Edit freely; run `wraith config validate`\n");
+        out.push_str("# after changes to check for mistakes.\n\n");
+
+        out.push_str("[node]\n");
+        if let Some(key) = &self.node.public_key {
+            out.push_str("# Node public key (hex), printed by `wraith keygen`\n");
+            out.push_str(&format!("public_key = {key:?}\n"));
+        }
+        out.push_str(
+            "# Encrypted private key file (created by `wraith keygen` or `wraith init`)\n",
+        );
+        out.push_str(&format!(
+            "private_key_file = {:?}\n\n",
+            self.node.private_key_file.display().to_string()
+        ));
+
+        out.push_str("[network]\n");
+        out.push_str("# Address and port to listen on; port 0 picks a random port\n");
+        out.push_str(&format!("listen_addr = {:?}\n", self.network.listen_addr));
+        out.push_str("# XDP kernel bypass (Linux only, requires root and a supported NIC)\n");
+        out.push_str(&format!("enable_xdp = {}\n", self.network.enable_xdp));
+        if let Some(iface) = &self.network.xdp_interface {
+            out.push_str(&format!("xdp_interface = {iface:?}\n"));
+        }
+        out.push_str("# Fall back to plain UDP sockets when XDP is unavailable\n");
+        out.push_str(&format!("udp_fallback = {}\n\n", self.network.udp_fallback));
+
+        out.push_str("[obfuscation]\n");
+        out.push_str("# Traffic obfuscation level: none, low, medium, high, paranoid\n");
+        out.push_str(&format!(
+            "default_level = {:?}\n",
+            self.obfuscation.default_level
+        ));
+        out.push_str("# Make traffic resemble TLS on the wire\n");
+        out.push_str(&format!("tls_mimicry = {}\n", self.obfuscation.tls_mimicry));
+        out.push_str("# Send cover traffic when idle (costs bandwidth)\n");
+        out.push_str(&format!(
+            "cover_traffic = {}\n\n",
+            self.obfuscation.cover_traffic
+        ));
+
+        out.push_str("[discovery]\n");
+        out.push_str("# DHT bootstrap nodes (host:port); empty means LAN/manual peers only\n");
+        out.push_str(&format!(
+            "bootstrap_nodes = {:?}\n",
+            self.discovery.bootstrap_nodes
+        ));
+        out.push_str("# Relay servers used when direct connection fails (host:port)\n");
+        out.push_str(&format!(
+            "relay_servers = {:?}\n\n",
+            self.discovery.relay_servers
+        ));
+
+        out.push_str("[transfer]\n");
+        out.push_str("# Chunk size in bytes (larger is faster on clean links)\n");
+        out.push_str(&format!("chunk_size = {}\n", self.transfer.chunk_size));
+        out.push_str("# Maximum simultaneous transfers\n");
+        out.push_str(&format!(
+            "max_concurrent = {}\n",
+            self.transfer.max_concurrent
+        ));
+        out.push_str("# Resume interrupted transfers\n");
+        out.push_str(&format!(
+            "enable_resume = {}\n",
+            self.transfer.enable_resume
+        ));
+        if let Some(limit) = &self.transfer.bandwidth_limit {
+            out.push_str("# Egress bandwidth limit per transfer (e.g. \"10MB/s\")\n");
+            out.push_str(&format!("bandwidth_limit = {limit:?}\n"));
+        }
+        out.push('\n');
+
+        out.push_str("[logging]\n");
+        out.push_str("# Log level: trace, debug, info, warn, error\n");
+        out.push_str(&format!("level = {:?}\n", self.logging.level));
+        if let Some(file) = &self.logging.file {
+            out.push_str(&format!("file = {:?}\n", file.display().to_string()));
+        }
+        out.push('\n');
+
+        out.push_str("[relay]\n");
+        out.push_str("# Embedded relay in the daemon: disabled, trusted-peers, open\n");
+        out.push_str("# trusted-peers restricts registration to peers pinned in the trust store\n");
+        out.push_str(&format!("mode = {:?}\n", self.relay.mode));
+        out.push_str("# Address the embedded relay listens on\n");
+        out.push_str(&format!("listen_addr = {:?}\n\n", self.relay.listen_addr));
+
+        out.push_str("[metrics]\n");
+        out.push_str("# Serve Prometheus metrics from the daemon (GET /metrics)\n");
+        out.push_str(&format!("enabled = {}\n", self.metrics.enabled));
+        out.push_str("# Metrics endpoint address; unauthenticated, keep on loopback\n");
+        out.push_str(&format!("listen_addr = {:?}\n", self.metrics.listen_addr));
+
+        out.push('\n');
+        out.push_str("# Named profiles: override relays, obfuscation, bandwidth caps, and\n");
+        out.push_str("# identity (keypair + trust store) per profile. Selected automatically\n");
+        out.push_str("# by gateway MAC, SSID, or subnet, pinned with `wraith profile use\n");
+        out.push_str("# <name>`, or forced for one invocation with `wraith --profile <name>\n");
+        out.push_str("# ...`. Example:\n");
+        out.push_str("#\n");
+        out.push_str("# [profiles.home]\n");
+        out.push_str("# match_ssid = \"HomeWifi\"\n");
+        out.push_str("# obfuscation_level = \"low\"\n");
+        out.push_str("#\n");
+        out.push_str("# [profiles.public]\n");
+        out.push_str("# match_subnet = \"10.128.0.0/16\"\n");
+        out.push_str("# obfuscation_level = \"paranoid\"\n");
+        out.push_str("# bandwidth_limit = \"1MB/s\"\n");
+        out.push_str("#\n");
+        out.push_str("# [profiles.work]\n");
+        out.push_str("# private_key_file = \"~/.wraith/work.key\"\n");
+        out.push_str("# trust_store_file = \"~/.config/wraith/known_peers.work\"\n");
+
+        out
+    }
+
     /// Get default config path
     #[must_use]
     pub fn default_path() -> PathBuf {
@@ -256,56 +545,318 @@ impl Config {
     ///
     /// # Errors
     ///
-    /// Returns an error if configuration is invalid.
+    /// Returns the first error found by [`Config::diagnose`].
     pub fn validate(&self) -> anyhow::Result<()> {
-        // Validate listen address
-        self.parse_listen_addr()?;
+        let diagnostics = self.diagnose(false);
+        if let Some(err) = diagnostics.errors.first() {
+            anyhow::bail!("{err}");
+        }
+        Ok(())
+    }
+
+    /// Run the full set of configuration checks, collecting every finding
+    /// instead of stopping at the first error.
+    ///
+    /// When `resolve_dns` is set, hostname entries in bootstrap and relay
+    /// lists are resolved via the system resolver; failures are reported as
+    /// warnings since the machine may simply be offline.
+    #[must_use]
+    pub fn diagnose(&self, resolve_dns: bool) -> ConfigDiagnostics {
+        let mut d = ConfigDiagnostics::default();
+
+        // Listen address must parse as a socket address
+        if let Err(e) = self.parse_listen_addr() {
+            d.errors.push(format!(
+                "Invalid listen address '{}': {e}",
+                self.network.listen_addr
+            ));
+        }
 
-        // Validate XDP interface if enabled
+        // XDP interface required when XDP is enabled
         if self.network.enable_xdp && self.network.xdp_interface.is_none() {
-            anyhow::bail!("XDP enabled but no interface specified");
+            d.errors
+                .push("XDP enabled but no interface specified".to_string());
+        }
+
+        // At least one transport must be available
+        if !self.network.enable_xdp && !self.network.udp_fallback {
+            d.errors
+                .push("No usable transport: XDP is disabled and udp_fallback is off".to_string());
         }
 
-        // Validate obfuscation level
+        // Obfuscation level
         let valid_levels = ["none", "low", "medium", "high", "paranoid"];
         if !valid_levels.contains(&self.obfuscation.default_level.as_str()) {
-            anyhow::bail!(
+            d.errors.push(format!(
                 "Invalid obfuscation level: {}. Must be one of: {}",
                 self.obfuscation.default_level,
                 valid_levels.join(", ")
-            );
+            ));
         }
 
-        // Validate log level
+        // Log level
         let valid_log_levels = ["trace", "debug", "info", "warn", "error"];
         if !valid_log_levels.contains(&self.logging.level.to_lowercase().as_str()) {
-            anyhow::bail!(
+            d.errors.push(format!(
                 "Invalid log level: {}. Must be one of: {}",
                 self.logging.level,
                 valid_log_levels.join(", ")
-            );
+            ));
         }
 
-        // Validate chunk size
+        // Chunk size
         if self.transfer.chunk_size == 0 || self.transfer.chunk_size > 16 * 1024 * 1024 {
-            anyhow::bail!("Chunk size must be between 1 and 16MB");
+            d.errors
+                .push("Chunk size must be between 1 and 16MB".to_string());
         }
 
-        // Validate max concurrent transfers
+        // Max concurrent transfers
         if self.transfer.max_concurrent == 0 || self.transfer.max_concurrent > 1000 {
-            anyhow::bail!("Max concurrent transfers must be between 1 and 1000");
+            d.errors
+                .push("Max concurrent transfers must be between 1 and 1000".to_string());
         }
 
-        // Validate bootstrap nodes (must be valid host:port format)
-        for node in &self.discovery.bootstrap_nodes {
-            self.validate_host_port(node, "Bootstrap node")?;
+        // Bandwidth limit must parse if set
+        if let Some(limit) = &self.transfer.bandwidth_limit
+            && wraith_core::node::bandwidth::parse_rate(limit).is_none()
+        {
+            d.errors.push(format!(
+                "Invalid bandwidth limit '{limit}' (expected e.g. \"10MB/s\" or \"500K\")"
+            ));
         }
 
-        // Validate relay servers (must be valid host:port format)
+        // Metrics endpoint address must parse when the exporter is enabled
+        if self.metrics.enabled && self.metrics.listen_addr.parse::<SocketAddr>().is_err() {
+            d.errors.push(format!(
+                "Invalid metrics listen address '{}'",
+                self.metrics.listen_addr
+            ));
+        }
+
+        // Embedded relay mode and listen address
+        if !["disabled", "trusted-peers", "open"].contains(&self.relay.mode.as_str()) {
+            d.errors.push(format!(
+                "Invalid relay mode '{}' (expected \"disabled\", \"trusted-peers\", or \"open\")",
+                self.relay.mode
+            ));
+        }
+        if self.relay.mode != "disabled" {
+            if self.relay.listen_addr.parse::<SocketAddr>().is_err() {
+                d.errors.push(format!(
+                    "Invalid relay listen address '{}'",
+                    self.relay.listen_addr
+                ));
+            }
+            if self.relay.mode == "open" {
+                d.warnings.push(
+                    "Relay mode 'open' accepts any client; use 'trusted-peers' unless \
+                     this node is meant to be a public relay"
+                        .to_string(),
+                );
+            }
+        }
+
+        // Bootstrap nodes and relay servers (host:port format)
+        for node in &self.discovery.bootstrap_nodes {
+            if let Err(e) = self.validate_host_port(node, "Bootstrap node") {
+                d.errors.push(e.to_string());
+            }
+        }
         for server in &self.discovery.relay_servers {
-            self.validate_host_port(server, "Relay server")?;
+            if let Err(e) = self.validate_host_port(server, "Relay server") {
+                d.errors.push(e.to_string());
+            }
         }
 
+        // Network profiles: pinned profile must exist, overrides must be valid
+        if let Some(active) = &self.active_profile
+            && !self.profiles.contains_key(active)
+        {
+            d.errors
+                .push(format!("active_profile '{active}' is not defined"));
+        }
+        for (name, profile) in &self.profiles {
+            if let Some(level) = &profile.obfuscation_level
+                && !valid_levels.contains(&level.as_str())
+            {
+                d.errors.push(format!(
+                    "Profile '{name}' has invalid obfuscation level: {level}"
+                ));
+            }
+            if let Some(limit) = &profile.bandwidth_limit
+                && wraith_core::node::bandwidth::parse_rate(limit).is_none()
+            {
+                d.errors.push(format!(
+                    "Profile '{name}' has invalid bandwidth limit '{limit}'"
+                ));
+            }
+            if let Some(relays) = &profile.relay_servers {
+                for server in relays {
+                    if let Err(e) =
+                        self.validate_host_port(server, &format!("Profile '{name}' relay server"))
+                    {
+                        d.errors.push(e.to_string());
+                    }
+                }
+            }
+            if let Some(subnet) = &profile.match_subnet
+                && crate::profile::parse_cidr(subnet).is_none()
+            {
+                d.errors.push(format!(
+                    "Profile '{name}' has invalid match_subnet '{subnet}' \
+                     (expected CIDR, e.g. \"192.168.1.0/24\")"
+                ));
+            }
+            if let Some(mac) = &profile.match_gateway_mac
+                && !crate::profile::is_valid_mac(mac)
+            {
+                d.errors.push(format!(
+                    "Profile '{name}' has invalid match_gateway_mac '{mac}' \
+                     (expected aa:bb:cc:dd:ee:ff)"
+                ));
+            }
+        }
+
+        // ─── Warnings ───
+
+        // Referenced paths should exist
+        if !self.node.private_key_file.exists() {
+            d.warnings.push(format!(
+                "Private key file {} does not exist (run `wraith keygen` to create one)",
+                self.node.private_key_file.display()
+            ));
+        }
+        if let Some(log_file) = &self.logging.file
+            && let Some(parent) = log_file.parent()
+            && !parent.as_os_str().is_empty()
+            && !parent.exists()
+        {
+            d.warnings.push(format!(
+                "Log file directory {} does not exist",
+                parent.display()
+            ));
+        }
+
+        // Public key must be a 32-byte hex string if present
+        if let Some(key) = &self.node.public_key
+            && (key.len() != 64 || !key.chars().all(|c| c.is_ascii_hexdigit()))
+        {
+            d.errors.push(format!(
+                "node.public_key '{key}' is not a 64-character hex string"
+            ));
+        }
+
+        // Insecure or inconsistent obfuscation combinations
+        let stealth = matches!(self.obfuscation.default_level.as_str(), "high" | "paranoid");
+        if stealth && !self.obfuscation.tls_mimicry {
+            d.warnings.push(format!(
+                "Obfuscation level '{}' with TLS mimicry disabled leaves traffic \
+                 patterns exposed; enable obfuscation.tls_mimicry",
+                self.obfuscation.default_level
+            ));
+        }
+        if stealth && !self.network.enable_xdp {
+            d.warnings.push(format!(
+                "Obfuscation level '{}' without XDP relies on userspace timing; \
+                 jitter precision is reduced",
+                self.obfuscation.default_level
+            ));
+        }
+        if self.obfuscation.cover_traffic && self.obfuscation.default_level == "none" {
+            d.warnings.push(
+                "Cover traffic enabled with obfuscation level 'none'; decoy frames \
+                 are sent unpadded and are trivially distinguishable"
+                    .to_string(),
+            );
+        }
+
+        // Metrics endpoint exposed beyond loopback
+        if self.metrics.enabled
+            && let Ok(addr) = self.metrics.listen_addr.parse::<SocketAddr>()
+            && !addr.ip().is_loopback()
+        {
+            d.warnings.push(format!(
+                "Metrics endpoint on {} is reachable from the network and has \
+                 no authentication; prefer a loopback address",
+                self.metrics.listen_addr
+            ));
+        }
+
+        // Discovery reachability
+        if self.discovery.bootstrap_nodes.is_empty() && self.discovery.relay_servers.is_empty() {
+            d.warnings.push(
+                "No bootstrap nodes or relay servers configured; peers can only be \
+                 reached by direct address"
+                    .to_string(),
+            );
+        }
+
+        if resolve_dns {
+            for (entry, name) in self
+                .discovery
+                .bootstrap_nodes
+                .iter()
+                .map(|n| (n, "Bootstrap node"))
+                .chain(
+                    self.discovery
+                        .relay_servers
+                        .iter()
+                        .map(|s| (s, "Relay server")),
+                )
+            {
+                // Literal IP:port entries need no resolver
+                if entry.parse::<SocketAddr>().is_ok() {
+                    continue;
+                }
+                if let Err(e) = entry.to_socket_addrs() {
+                    d.warnings
+                        .push(format!("{name} '{entry}' does not resolve: {e}"));
+                }
+            }
+        }
+
+        d
+    }
+
+    /// Apply a named profile's overrides onto the base configuration
+    ///
+    /// Unset profile fields keep their base values, so a profile only needs
+    /// to list what actually differs on that network.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no profile with that name is defined.
+    pub fn apply_profile(&mut self, name: &str) -> anyhow::Result<()> {
+        let profile = self.profiles.get(name).cloned().ok_or_else(|| {
+            let defined: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            anyhow::anyhow!(
+                "Unknown profile '{name}' (defined: {})",
+                if defined.is_empty() {
+                    "none".to_string()
+                } else {
+                    defined.join(", ")
+                }
+            )
+        })?;
+
+        if let Some(relays) = profile.relay_servers {
+            self.discovery.relay_servers = relays;
+        }
+        if let Some(level) = profile.obfuscation_level {
+            self.obfuscation.default_level = level;
+        }
+        if let Some(limit) = profile.bandwidth_limit {
+            self.transfer.bandwidth_limit = Some(limit);
+        }
+        if let Some(key_file) = profile.private_key_file {
+            // The base public key belongs to the base keypair; drop it so
+            // nothing reports a stale identity for this profile
+            self.node.private_key_file = key_file;
+            self.node.public_key = None;
+        }
+        if let Some(trust_file) = profile.trust_store_file {
+            self.node.trust_store_file = Some(trust_file);
+        }
         Ok(())
     }
 
@@ -745,8 +1296,9 @@ mod tests {
     fn test_config_with_custom_values() {
         let config = Config {
             node: NodeConfig {
-                public_key: Some("deadbeef".to_string()),
+                public_key: Some("deadbeef".repeat(8)),
                 private_key_file: PathBuf::from("/custom/path"),
+                trust_store_file: None,
             },
             network: NetworkConfig {
                 listen_addr: "127.0.0.1:9999".to_string(),
@@ -767,20 +1319,100 @@ mod tests {
                 chunk_size: 512 * 1024,
                 max_concurrent: 20,
                 enable_resume: false,
+                bandwidth_limit: Some("10MB/s".to_string()),
             },
             logging: LoggingConfig {
                 level: "debug".to_string(),
                 file: Some(PathBuf::from("/var/log/wraith.log")),
             },
+            metrics: MetricsConfig {
+                enabled: true,
+                listen_addr: "127.0.0.1:9900".to_string(),
+            },
+            ..Config::default()
         };
 
         assert!(config.validate().is_ok());
-        assert_eq!(config.node.public_key, Some("deadbeef".to_string()));
+        assert_eq!(config.node.public_key, Some("deadbeef".repeat(8)));
         assert_eq!(config.network.listen_addr, "127.0.0.1:9999");
         assert_eq!(config.obfuscation.default_level, "high");
         assert_eq!(config.transfer.chunk_size, 512 * 1024);
     }
 
+    #[test]
+    fn test_profiles_roundtrip_through_toml() {
+        let mut config = Config {
+            active_profile: Some("home".to_string()),
+            ..Config::default()
+        };
+        config.profiles.insert(
+            "home".to_string(),
+            NetworkProfile {
+                match_ssid: Some("HomeWifi".to_string()),
+                obfuscation_level: Some("low".to_string()),
+                ..NetworkProfile::default()
+            },
+        );
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed: Config = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.active_profile.as_deref(), Some("home"));
+        let home = &parsed.profiles["home"];
+        assert_eq!(home.match_ssid.as_deref(), Some("HomeWifi"));
+        assert_eq!(home.obfuscation_level.as_deref(), Some("low"));
+    }
+
+    #[test]
+    fn test_apply_profile_overrides_identity() {
+        let mut config = Config::default();
+        config.node.public_key = Some("deadbeef".repeat(8));
+        config.profiles.insert(
+            "work".to_string(),
+            NetworkProfile {
+                private_key_file: Some(PathBuf::from("/keys/work.key")),
+                trust_store_file: Some(PathBuf::from("/keys/known_peers.work")),
+                ..NetworkProfile::default()
+            },
+        );
+
+        config.apply_profile("work").unwrap();
+        assert_eq!(
+            config.node.private_key_file,
+            PathBuf::from("/keys/work.key")
+        );
+        assert_eq!(
+            config.node.trust_store_file,
+            Some(PathBuf::from("/keys/known_peers.work"))
+        );
+        // The base public key belongs to the base keypair, not this profile
+        assert!(config.node.public_key.is_none());
+    }
+
+    #[test]
+    fn test_diagnose_flags_bad_profiles() {
+        let mut config = Config {
+            active_profile: Some("nowhere".to_string()),
+            ..Config::default()
+        };
+        config.profiles.insert(
+            "bad".to_string(),
+            NetworkProfile {
+                match_subnet: Some("not-a-cidr".to_string()),
+                match_gateway_mac: Some("nope".to_string()),
+                obfuscation_level: Some("invisible".to_string()),
+                bandwidth_limit: Some("fast".to_string()),
+                ..NetworkProfile::default()
+            },
+        );
+
+        let d = config.diagnose(false);
+        assert!(d.errors.iter().any(|e| e.contains("active_profile")));
+        assert!(d.errors.iter().any(|e| e.contains("match_subnet")));
+        assert!(d.errors.iter().any(|e| e.contains("match_gateway_mac")));
+        assert!(d.errors.iter().any(|e| e.contains("obfuscation level")));
+        assert!(d.errors.iter().any(|e| e.contains("bandwidth limit")));
+    }
+
     #[test]
     fn test_default_functions() {
         assert_eq!(default_listen_addr(), "0.0.0.0:40000");
@@ -800,6 +1432,171 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diagnose_default_has_no_errors() {
+        let config = Config::default();
+        let d = config.diagnose(false);
+        assert!(!d.has_errors(), "unexpected errors: {:?}", d.errors);
+        // Empty discovery lists should at least warn
+        assert!(d.warnings.iter().any(|w| w.contains("bootstrap")));
+    }
+
+    #[test]
+    fn test_diagnose_collects_multiple_errors() {
+        let mut config = Config::default();
+        config.network.listen_addr = "not-an-addr".to_string();
+        config.transfer.chunk_size = 0;
+        config.logging.level = "loud".to_string();
+
+        let d = config.diagnose(false);
+        assert!(
+            d.errors.len() >= 3,
+            "expected 3+ errors, got {:?}",
+            d.errors
+        );
+    }
+
+    #[test]
+    fn test_diagnose_no_transport_is_error() {
+        let mut config = Config::default();
+        config.network.enable_xdp = false;
+        config.network.udp_fallback = false;
+
+        let d = config.diagnose(false);
+        assert!(d.errors.iter().any(|e| e.contains("No usable transport")));
+    }
+
+    #[test]
+    fn test_diagnose_invalid_bandwidth_limit() {
+        let mut config = Config::default();
+        config.transfer.bandwidth_limit = Some("fast".to_string());
+        let d = config.diagnose(false);
+        assert!(d.errors.iter().any(|e| e.contains("bandwidth limit")));
+
+        config.transfer.bandwidth_limit = Some("10MB/s".to_string());
+        let d = config.diagnose(false);
+        assert!(!d.errors.iter().any(|e| e.contains("bandwidth limit")));
+    }
+
+    #[test]
+    fn test_diagnose_invalid_public_key() {
+        let mut config = Config::default();
+        config.node.public_key = Some("deadbeef".to_string()); // too short
+        let d = config.diagnose(false);
+        assert!(d.errors.iter().any(|e| e.contains("public_key")));
+
+        config.node.public_key = Some("ab".repeat(32));
+        let d = config.diagnose(false);
+        assert!(!d.errors.iter().any(|e| e.contains("public_key")));
+    }
+
+    #[test]
+    fn test_diagnose_insecure_combinations_warn() {
+        let mut config = Config::default();
+        config.obfuscation.default_level = "paranoid".to_string();
+        config.obfuscation.tls_mimicry = false;
+        config.network.enable_xdp = false;
+
+        let d = config.diagnose(false);
+        assert!(!d.has_errors());
+        assert!(d.warnings.iter().any(|w| w.contains("TLS mimicry")));
+        assert!(d.warnings.iter().any(|w| w.contains("userspace timing")));
+
+        // Cover traffic at level none is flagged
+        let mut config = Config::default();
+        config.obfuscation.default_level = "none".to_string();
+        config.obfuscation.cover_traffic = true;
+        let d = config.diagnose(false);
+        assert!(d.warnings.iter().any(|w| w.contains("Cover traffic")));
+    }
+
+    #[test]
+    fn test_diagnose_dns_skips_literal_addrs() {
+        let mut config = Config::default();
+        config.discovery.bootstrap_nodes = vec!["127.0.0.1:40000".to_string()];
+
+        let d = config.diagnose(true);
+        assert!(!d.warnings.iter().any(|w| w.contains("resolve")));
+    }
+
+    #[test]
+    fn test_metrics_config_default() {
+        let metrics_config = MetricsConfig::default();
+        assert!(!metrics_config.enabled);
+        assert_eq!(metrics_config.listen_addr, "127.0.0.1:9464");
+    }
+
+    #[test]
+    fn test_diagnose_invalid_metrics_addr() {
+        let mut config = Config::default();
+        config.metrics.enabled = true;
+        config.metrics.listen_addr = "not-an-addr".to_string();
+        let d = config.diagnose(false);
+        assert!(d.errors.iter().any(|e| e.contains("metrics listen")));
+
+        // Disabled exporter tolerates a bad address
+        config.metrics.enabled = false;
+        let d = config.diagnose(false);
+        assert!(!d.errors.iter().any(|e| e.contains("metrics listen")));
+    }
+
+    #[test]
+    fn test_diagnose_non_loopback_metrics_warns() {
+        let mut config = Config::default();
+        config.metrics.enabled = true;
+        config.metrics.listen_addr = "0.0.0.0:9464".to_string();
+        let d = config.diagnose(false);
+        assert!(!d.has_errors());
+        assert!(d.warnings.iter().any(|w| w.contains("Metrics endpoint")));
+    }
+
+    #[test]
+    fn test_diagnose_missing_log_dir_warns() {
+        let mut config = Config::default();
+        config.logging.file = Some(PathBuf::from("/nonexistent-wraith-dir/wraith.log"));
+        let d = config.diagnose(false);
+        assert!(d.warnings.iter().any(|w| w.contains("Log file directory")));
+    }
+
+    #[test]
+    fn test_validate_reports_first_diagnose_error() {
+        let mut config = Config::default();
+        config.network.listen_addr = "bogus".to_string();
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("listen address"));
+    }
+
+    #[test]
+    fn test_annotated_toml_roundtrip() {
+        let mut config = Config::default();
+        config.node.public_key = Some("deadbeef".repeat(8));
+        config.network.listen_addr = "127.0.0.1:41000".to_string();
+        config.discovery.relay_servers = vec!["relay.example.com:3478".to_string()];
+        config.transfer.bandwidth_limit = Some("10MB/s".to_string());
+
+        let text = config.to_annotated_toml();
+        let parsed: Config = toml::from_str(&text).unwrap();
+
+        assert_eq!(parsed.node.public_key, config.node.public_key);
+        assert_eq!(parsed.network.listen_addr, config.network.listen_addr);
+        assert_eq!(
+            parsed.discovery.relay_servers,
+            config.discovery.relay_servers
+        );
+        assert_eq!(
+            parsed.transfer.bandwidth_limit,
+            config.transfer.bandwidth_limit
+        );
+        assert_eq!(parsed.transfer.chunk_size, config.transfer.chunk_size);
+    }
+
+    #[test]
+    fn test_annotated_toml_has_comments() {
+        let text = Config::default().to_annotated_toml();
+        assert!(text.starts_with("# WRAITH configuration"));
+        assert!(text.contains("# Traffic obfuscation level"));
+    }
+
     #[test]
     fn test_config_clone() {
         let config = Config::default();
@@ -0,0 +1,274 @@
+//! Post-setup privilege dropping for the daemon (`wraith daemon --harden`).
+//!
+//! The daemon parses untrusted network data on every packet, so once its
+//! sockets are bound and any XDP program is loaded it no longer needs the
+//! privileges it started with. [`apply`] runs at that point and, on Linux:
+//!
+//! - drops every capability from the ambient and bounding sets and clears
+//!   the effective, permitted and inheritable sets — nothing after setup
+//!   needs one
+//! - sets `no_new_privs`, so a compromised process cannot re-escalate
+//!   through setuid binaries
+//! - installs a seccomp denylist blocking the syscalls an exploited
+//!   worker would reach for (exec, ptrace, kernel module loading, mounts,
+//!   `bpf(2)`), synced to every thread with `TSYNC` so the already-running
+//!   tokio workers are covered
+//!
+//! A denylist rather than an allowlist: the tokio runtime, io_uring and
+//! the DHT together exercise a wide and version-dependent syscall surface,
+//! and a stale allowlist turns a routine dependency upgrade into a daemon
+//! that kills itself. The matching AppArmor/SELinux profiles generated by
+//! `cargo xtask profiles` confine the file and network surface from the
+//! outside.
+
+/// Drop capabilities and install the seccomp filter
+///
+/// Call after all sockets are bound and XDP is loaded; nothing privileged
+/// works afterwards. Irreversible for the lifetime of the process.
+#[cfg(target_os = "linux")]
+pub fn apply() -> anyhow::Result<()> {
+    linux::drop_capabilities()?;
+    linux::set_no_new_privs()?;
+    linux::install_seccomp_filter()?;
+    Ok(())
+}
+
+/// Hardening relies on Linux capabilities and seccomp
+#[cfg(not(target_os = "linux"))]
+pub fn apply() -> anyhow::Result<()> {
+    anyhow::bail!("--harden is only supported on Linux");
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+    use anyhow::Context;
+
+    /// Highest capability number we try to drop from the bounding set
+    ///
+    /// Deliberately past the newest kernels' last capability; the kernel
+    /// answers `EINVAL` for numbers it does not know, which we ignore.
+    const CAP_DROP_LIMIT: libc::c_ulong = 63;
+
+    /// `_LINUX_CAPABILITY_VERSION_3` for `capset(2)`
+    const CAPABILITY_VERSION_3: u32 = 0x2008_0522;
+
+    #[repr(C)]
+    struct CapUserHeader {
+        version: u32,
+        pid: libc::c_int,
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Default)]
+    struct CapUserData {
+        effective: u32,
+        permitted: u32,
+        inheritable: u32,
+    }
+
+    /// Clear the ambient and bounding sets, then all remaining cap sets
+    pub(super) fn drop_capabilities() -> anyhow::Result<()> {
+        // Ambient set (inherited across exec, though we never exec)
+        unsafe {
+            libc::prctl(
+                libc::PR_CAP_AMBIENT,
+                libc::PR_CAP_AMBIENT_CLEAR_ALL,
+                0,
+                0,
+                0,
+            );
+        }
+
+        // Bounding set; EINVAL marks numbers past the kernel's last cap
+        // and EPERM means we are unprivileged and have nothing to drop
+        for cap in 0..=CAP_DROP_LIMIT {
+            let rc = unsafe { libc::prctl(libc::PR_CAPBSET_DROP, cap, 0, 0, 0) };
+            if rc != 0 {
+                let errno = std::io::Error::last_os_error().raw_os_error();
+                if errno != Some(libc::EINVAL) && errno != Some(libc::EPERM) {
+                    return Err(std::io::Error::last_os_error())
+                        .with_context(|| format!("Failed to drop capability {cap}"));
+                }
+            }
+        }
+
+        // Effective, permitted and inheritable sets (dropping our own
+        // capabilities never requires privilege)
+        let mut header = CapUserHeader {
+            version: CAPABILITY_VERSION_3,
+            pid: 0,
+        };
+        let data = [CapUserData::default(); 2];
+        let rc = unsafe { libc::syscall(libc::SYS_capset, &mut header, data.as_ptr()) };
+        if rc != 0 {
+            return Err(std::io::Error::last_os_error()).context("capset failed");
+        }
+
+        Ok(())
+    }
+
+    /// Forbid privilege gain through setuid/setcap binaries
+    pub(super) fn set_no_new_privs() -> anyhow::Result<()> {
+        let rc = unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) };
+        if rc != 0 {
+            return Err(std::io::Error::last_os_error()).context("PR_SET_NO_NEW_PRIVS failed");
+        }
+        Ok(())
+    }
+
+    // Classic BPF opcodes and seccomp constants (not exposed by libc)
+    const BPF_LD_W_ABS: u16 = 0x20;
+    const BPF_JEQ_K: u16 = 0x15;
+    const BPF_RET_K: u16 = 0x06;
+    const SECCOMP_RET_ALLOW: u32 = 0x7fff_0000;
+    const SECCOMP_RET_ERRNO: u32 = 0x0005_0000;
+    const SECCOMP_RET_KILL_PROCESS: u32 = 0x8000_0000;
+    const SECCOMP_SET_MODE_FILTER: libc::c_long = 1;
+    const SECCOMP_FILTER_FLAG_TSYNC: libc::c_long = 1;
+
+    /// `seccomp_data` field offsets: syscall number and audit architecture
+    const SECCOMP_DATA_NR: u32 = 0;
+    const SECCOMP_DATA_ARCH: u32 = 4;
+
+    #[cfg(target_arch = "x86_64")]
+    const AUDIT_ARCH: u32 = 0xC000_003E; // AUDIT_ARCH_X86_64
+    #[cfg(target_arch = "aarch64")]
+    const AUDIT_ARCH: u32 = 0xC000_00B7; // AUDIT_ARCH_AARCH64
+
+    /// Syscalls the daemon never makes and an intruder would want
+    const DENIED_SYSCALLS: &[libc::c_long] = &[
+        libc::SYS_execve,
+        libc::SYS_execveat,
+        libc::SYS_ptrace,
+        libc::SYS_process_vm_readv,
+        libc::SYS_process_vm_writev,
+        libc::SYS_init_module,
+        libc::SYS_finit_module,
+        libc::SYS_delete_module,
+        libc::SYS_kexec_load,
+        libc::SYS_kexec_file_load,
+        libc::SYS_mount,
+        libc::SYS_umount2,
+        libc::SYS_pivot_root,
+        libc::SYS_chroot,
+        libc::SYS_swapon,
+        libc::SYS_swapoff,
+        libc::SYS_reboot,
+        libc::SYS_bpf,
+        libc::SYS_userfaultfd,
+        libc::SYS_add_key,
+        libc::SYS_request_key,
+        libc::SYS_keyctl,
+    ];
+
+    fn bpf_stmt(code: u16, k: u32) -> libc::sock_filter {
+        libc::sock_filter {
+            code,
+            jt: 0,
+            jf: 0,
+            k,
+        }
+    }
+
+    fn bpf_jeq(k: u32, jt: u8) -> libc::sock_filter {
+        libc::sock_filter {
+            code: BPF_JEQ_K,
+            jt,
+            jf: 0,
+            k,
+        }
+    }
+
+    /// Build the denylist filter program
+    ///
+    /// Layout: kill on foreign-architecture syscalls (blocks 32-bit compat
+    /// entry on x86_64), then one `JEQ` per denied syscall jumping to a
+    /// shared `EPERM` return, falling through to `ALLOW`.
+    fn build_filter() -> Vec<libc::sock_filter> {
+        let n = DENIED_SYSCALLS.len();
+        let mut filter = Vec::with_capacity(n + 6);
+
+        filter.push(bpf_stmt(BPF_LD_W_ABS, SECCOMP_DATA_ARCH));
+        filter.push(bpf_jeq(AUDIT_ARCH, 1));
+        filter.push(bpf_stmt(BPF_RET_K, SECCOMP_RET_KILL_PROCESS));
+        filter.push(bpf_stmt(BPF_LD_W_ABS, SECCOMP_DATA_NR));
+
+        for (i, nr) in DENIED_SYSCALLS.iter().enumerate() {
+            // Skip the remaining comparisons and the ALLOW return
+            filter.push(bpf_jeq(*nr as u32, (n - i) as u8));
+        }
+
+        filter.push(bpf_stmt(BPF_RET_K, SECCOMP_RET_ALLOW));
+        filter.push(bpf_stmt(BPF_RET_K, SECCOMP_RET_ERRNO | libc::EPERM as u32));
+        filter
+    }
+
+    /// Install the filter on every thread of the process
+    pub(super) fn install_seccomp_filter() -> anyhow::Result<()> {
+        let mut filter = build_filter();
+        let prog = libc::sock_fprog {
+            len: filter.len() as u16,
+            filter: filter.as_mut_ptr(),
+        };
+
+        // TSYNC applies the filter to all existing threads (the tokio
+        // workers are already running) or fails as a whole
+        let rc = unsafe {
+            libc::syscall(
+                libc::SYS_seccomp,
+                SECCOMP_SET_MODE_FILTER,
+                SECCOMP_FILTER_FLAG_TSYNC,
+                &prog,
+            )
+        };
+        if rc != 0 {
+            return Err(std::io::Error::last_os_error())
+                .context("seccomp filter installation failed (requires no_new_privs)");
+        }
+        Ok(())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // The filter is installed process-wide and is irreversible, so
+        // tests validate the program's structure rather than applying it
+
+        #[test]
+        fn test_filter_shape() {
+            let filter = build_filter();
+            assert_eq!(filter.len(), DENIED_SYSCALLS.len() + 6);
+
+            // Opens by checking the audit architecture
+            assert_eq!(filter[0].code, BPF_LD_W_ABS);
+            assert_eq!(filter[0].k, SECCOMP_DATA_ARCH);
+            assert_eq!(filter[2].k, SECCOMP_RET_KILL_PROCESS);
+
+            // Falls through to ALLOW, with the shared EPERM return last
+            let allow = &filter[filter.len() - 2];
+            assert_eq!((allow.code, allow.k), (BPF_RET_K, SECCOMP_RET_ALLOW));
+            let deny = filter.last().unwrap();
+            assert_eq!(deny.k, SECCOMP_RET_ERRNO | libc::EPERM as u32);
+        }
+
+        #[test]
+        fn test_filter_jumps_land_on_deny() {
+            let filter = build_filter();
+            let deny_index = filter.len() - 1;
+
+            for (i, insn) in filter.iter().enumerate() {
+                if insn.code == BPF_JEQ_K && insn.k != AUDIT_ARCH {
+                    // A matched syscall must jump exactly to the EPERM return
+                    assert_eq!(i + 1 + insn.jt as usize, deny_index);
+                }
+            }
+        }
+
+        #[test]
+        fn test_denied_syscalls_fit_one_byte_jumps() {
+            // jt is a u8; the denylist must stay within its reach
+            assert!(DENIED_SYSCALLS.len() + 1 < u8::MAX as usize);
+        }
+    }
+}
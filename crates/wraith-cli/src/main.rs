@@ -8,16 +8,23 @@
 //! - Memory zeroization for sensitive data
 
 mod config;
+mod hardening;
+mod profile;
 mod progress;
+mod replay;
+mod service;
+mod spool;
 
+use anyhow::Context;
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use zeroize::Zeroize;
 
 use config::Config;
-use progress::{TransferProgress, format_bytes};
+use progress::{MultiTransferProgress, format_bytes};
 
 // WRAITH Core imports
 use wraith_core::node::identity::TransferId;
@@ -35,10 +42,98 @@ const ARGON2_SALT_SIZE: usize = 16;
 const ARGON2_NONCE_SIZE: usize = 24; // XChaCha20-Poly1305 nonce
 const ARGON2_TAG_SIZE: usize = 16;
 
+/// Process exit codes for scripting (documented in `--help`)
+///
+/// Stable contract: shell scripts and CI branch on these values, so new
+/// outcomes must get new codes rather than reusing existing ones.
+const EXIT_GENERIC: i32 = 1;
+const EXIT_INTEGRITY: i32 = 3;
+const EXIT_UNREACHABLE: i32 = 4;
+const EXIT_POLICY: i32 = 5;
+const EXIT_TIMEOUT: i32 = 6;
+
+/// Operation refused by local policy (trust, path, or acceptance rules)
+#[derive(Debug)]
+struct PolicyRejection(String);
+
+impl std::fmt::Display for PolicyRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Rejected by policy: {}", self.0)
+    }
+}
+
+impl std::error::Error for PolicyRejection {}
+
+/// Map an error chain to the documented exit code
+fn exit_code_for(err: &anyhow::Error) -> i32 {
+    use wraith_core::node::NodeError;
+
+    for cause in err.chain() {
+        if cause.downcast_ref::<PolicyRejection>().is_some() {
+            return EXIT_POLICY;
+        }
+
+        if let Some(node_err) = cause.downcast_ref::<NodeError>() {
+            return match node_err {
+                NodeError::HashMismatch => EXIT_INTEGRITY,
+                NodeError::Timeout(_) => EXIT_TIMEOUT,
+                NodeError::PeerNotFound(_)
+                | NodeError::SessionNotFound(_)
+                | NodeError::SessionEstablishment(_)
+                | NodeError::Handshake(_)
+                | NodeError::NatTraversal(_)
+                | NodeError::Transport(_) => EXIT_UNREACHABLE,
+                _ => EXIT_GENERIC,
+            };
+        }
+    }
+
+    EXIT_GENERIC
+}
+
+/// Suppresses non-error output when `--quiet` is set
+static QUIET: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn is_quiet() -> bool {
+    QUIET.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Print status output unless `--quiet` is set
+///
+/// All human-facing progress and status lines go through this; errors use
+/// `eprintln!` and are never suppressed.
+macro_rules! status {
+    () => {
+        if !crate::is_quiet() {
+            println!();
+        }
+    };
+    ($($arg:tt)*) => {
+        if !crate::is_quiet() {
+            println!($($arg)*);
+        }
+    };
+}
+
 /// WRAITH - Secure, fast, undetectable file transfer
 #[derive(Parser)]
 #[command(name = "wraith")]
 #[command(author, version, about, long_about = None)]
+#[command(long_version = concat!(
+    env!("CARGO_PKG_VERSION"),
+    " (commit ",
+    env!("WRAITH_BUILD_COMMIT"),
+    ")"
+))]
+#[command(after_help = "\
+Exit codes:
+  0  success
+  1  generic error
+  2  usage error
+  3  integrity verification failed
+  4  peer unreachable
+  5  rejected by policy
+  6  operation timed out")]
 struct Cli {
     /// Enable verbose output
     #[arg(short, long)]
@@ -48,29 +143,121 @@ struct Cli {
     #[arg(short, long)]
     debug: bool,
 
+    /// Suppress all non-error output (for scripts; see exit codes)
+    #[arg(short, long)]
+    quiet: bool,
+
     /// Configuration file path
     #[arg(short, long, default_value = "~/.config/wraith/config.toml")]
     config: String,
 
+    /// Named profile for this invocation (own identity, trust store, and
+    /// config overrides; overrides pinned and network-detected profiles)
+    #[arg(short, long, global = true)]
+    profile: Option<String>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    /// Interactive first-run setup: identity, config, and self-test
+    Init {
+        /// Overwrite an existing configuration and identity
+        #[arg(long)]
+        force: bool,
+    },
+
     /// Send a file to one or more peers
     Send {
-        /// File to send
+        /// File to send, or "-" to stream from stdin
         #[arg(required = true)]
         file: String,
 
         /// Recipient peer ID or address (can be specified multiple times)
-        #[arg(required = true)]
+        #[arg(required_unless_present_any = ["code", "link", "to", "multicast"])]
         recipient: Vec<String>,
 
+        /// Fan out the whole file to this recipient (repeatable)
+        ///
+        /// With several `--to` flags the file is sent to every recipient
+        /// concurrently, reading each chunk from disk only once.
+        #[arg(long = "to", conflicts_with_all = ["code", "link"])]
+        to: Vec<String>,
+
+        /// Pair with a one-time transfer code instead of a peer ID
+        ///
+        /// Prints a short code (e.g. "7-hungry-wombat") and waits for the
+        /// receiver to run `wraith receive --code <code>`.
+        #[arg(long, conflicts_with = "recipient")]
+        code: bool,
+
+        /// Generate a one-shot claim link (wraith:// URL) instead of a peer ID
+        ///
+        /// Prints a URL granting its holder permission to fetch this file
+        /// once via `wraith get <url>`.
+        #[arg(long, conflicts_with_all = ["recipient", "code"])]
+        link: bool,
+
+        /// Distribute over LAN multicast instead of per-peer unicast
+        ///
+        /// Multicasts the file once to every machine on the local segment
+        /// (receivers run `wraith receive --multicast-hash <hash>` with the
+        /// printed content hash), with NACK-based repair. Unauthenticated
+        /// and LAN-only; when no multicast route exists the send falls
+        /// back to ordinary unicast to any listed recipients.
+        #[arg(long, conflicts_with_all = ["code", "link", "queue", "dry_run"])]
+        multicast: bool,
+
         /// Obfuscation mode
         #[arg(long, default_value = "privacy")]
         mode: String,
+
+        /// Bandwidth limit for this transfer (e.g. "10MB/s", "500KB/s")
+        #[arg(long)]
+        limit: Option<String>,
+
+        /// Show what would be transferred without moving any data
+        ///
+        /// Computes the manifest exactly as a real send would and prints
+        /// files, sizes, chunk counts, and the estimated duration at the
+        /// effective bandwidth limit. No node is started.
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Queue the transfer if a recipient cannot be reached
+        ///
+        /// Unreachable recipients are recorded in the encrypted spool
+        /// (`~/.wraith/spool`); a running daemon dispatches them
+        /// automatically once the peer appears in discovery. See
+        /// `wraith queue`.
+        #[arg(long, conflicts_with_all = ["code", "link", "to", "dry_run"])]
+        queue: bool,
+
+        /// Start no earlier than this local time (HH:MM; tomorrow if past)
+        ///
+        /// Implies queueing: the transfer is recorded in the encrypted
+        /// spool and a running daemon starts it once the time arrives and
+        /// the peer is reachable.
+        #[arg(long, conflicts_with_all = ["code", "link", "to", "dry_run"])]
+        schedule: Option<String>,
+
+        /// Only run during this daily local-time window (repeatable)
+        ///
+        /// `HH:MM-HH:MM`, e.g. `--window 01:00-06:00`; windows may wrap
+        /// past midnight. Implies queueing like `--schedule`. Without
+        /// `--peak-limit` the transfer runs only inside its windows.
+        #[arg(long, conflicts_with_all = ["code", "link", "to", "dry_run"])]
+        window: Vec<String>,
+
+        /// Throttled rate outside the --window hours (e.g. "1MB/s")
+        ///
+        /// Turns the windows into an off-peak bandwidth profile: full
+        /// speed (or `--limit`) inside them, this rate the rest of the day
+        /// instead of pausing.
+        #[arg(long, requires = "window")]
+        peak_limit: Option<String>,
     },
 
     /// Send multiple files in batch
@@ -86,6 +273,10 @@ enum Commands {
         /// Obfuscation mode
         #[arg(long, default_value = "privacy")]
         mode: String,
+
+        /// Show what would be transferred without moving any data
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Receive files from peers
@@ -105,6 +296,52 @@ enum Commands {
         /// Comma-separated list of trusted peer IDs (only accept from these peers)
         #[arg(long)]
         trusted_peers: Option<String>,
+
+        /// Write a single streamed transfer to stdout instead of saving files
+        #[arg(long, conflicts_with = "output")]
+        stdout: bool,
+
+        /// Join a LAN multicast distribution, verified against this
+        /// BLAKE3 content hash (printed by `wraith send --multicast`)
+        ///
+        /// Saves the file under the hash's hex prefix in the output
+        /// directory. If chunks are still missing after the sender's
+        /// repair rounds, fetch the file over unicast instead.
+        #[arg(long, value_name = "HEX", conflicts_with_all = ["stdout", "code"])]
+        multicast_hash: Option<String>,
+
+        /// One-time transfer code from the sender (e.g. "7-hungry-wombat")
+        #[arg(long, requires = "sender")]
+        code: Option<String>,
+
+        /// Sender address for code-based pairing (host:port)
+        #[arg(long, requires = "code")]
+        sender: Option<String>,
+    },
+
+    /// Claim a one-shot transfer link (wraith:// URL)
+    Get {
+        /// The wraith:// URL from the sharer
+        url: String,
+
+        /// Output directory
+        #[arg(short, long, default_value = ".")]
+        output: String,
+    },
+
+    /// Handle a wraith:// URI (invoked by the OS scheme handler)
+    HandleUri {
+        /// The wraith:// URI to act on
+        #[arg(required_unless_present = "register")]
+        uri: Option<String>,
+
+        /// Register this binary as the OS handler for wraith:// links
+        #[arg(long, conflicts_with = "uri")]
+        register: bool,
+
+        /// Output directory for claimed transfers
+        #[arg(short, long, default_value = ".")]
+        output: String,
     },
 
     /// Run as background daemon
@@ -116,6 +353,17 @@ enum Commands {
         /// Enable relay mode
         #[arg(long)]
         relay: bool,
+
+        /// Drop capabilities and sandbox with seccomp after startup (Linux)
+        #[arg(long)]
+        harden: bool,
+
+        /// Entered by the Windows service manager; not for interactive use
+        #[arg(long, hide = true)]
+        service: bool,
+
+        #[command(subcommand)]
+        action: Option<DaemonAction>,
     },
 
     /// Show connection status
@@ -153,6 +401,13 @@ enum Commands {
     /// Show node information
     Info,
 
+    /// Show compiled features and detected platform capabilities
+    Features {
+        /// Output as JSON for tooling
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Generate a new identity keypair
     Keygen {
         /// Output file for private key
@@ -175,11 +430,104 @@ enum Commands {
         interval: u64,
     },
 
+    /// Probe the NAT environment and report the traversal strategy
+    Probe,
+
+    /// Pipe bytes between stdin/stdout and a peer over an encrypted stream
+    Pipe {
+        /// Peer ID to connect to (omit with --listen to accept instead)
+        #[arg(required_unless_present = "listen")]
+        peer: Option<String>,
+
+        /// Wait for an incoming pipe instead of connecting
+        #[arg(short, long)]
+        listen: bool,
+    },
+
+    /// Manage the offline send queue
+    Queue {
+        #[command(subcommand)]
+        action: QueueAction,
+    },
+
+    /// Verify a received file against its signed manifest
+    Verify {
+        /// File to verify
+        #[arg(required = true)]
+        file: String,
+
+        /// Manifest path (defaults to `<file>.manifest`)
+        manifest: Option<String>,
+    },
+
+    /// Search shared content in a private group by name
+    Search {
+        /// Name or substring to search for
+        #[arg(required = true)]
+        query: String,
+
+        /// Group secret shared among group members (64 hex chars)
+        #[arg(long, required = true)]
+        group_secret: String,
+    },
+
+    /// Replay a captured frame log or pcap through the session state machine
+    Replay {
+        /// Capture file: hex frame log or libpcap file (auto-detected)
+        #[arg(required = true)]
+        capture: String,
+
+        /// Abort on the first parse error instead of continuing
+        #[arg(long)]
+        fail_fast: bool,
+    },
+
     /// View or modify configuration
     Config {
         #[command(subcommand)]
         action: ConfigAction,
     },
+
+    /// Manage per-network configuration profiles
+    Profile {
+        #[command(subcommand)]
+        action: ProfileAction,
+    },
+
+    /// Generate shell completions on stdout (used by `cargo xtask dist`)
+    #[command(hide = true)]
+    Completions {
+        /// Shell to generate for
+        shell: clap_complete::Shell,
+    },
+
+    /// Generate the man page on stdout (used by `cargo xtask dist`)
+    #[command(hide = true)]
+    Manpage,
+}
+
+#[derive(Subcommand)]
+enum DaemonAction {
+    /// Register the daemon with the OS service manager and start it at boot
+    ///
+    /// Windows: creates an auto-start SCM service (run as administrator).
+    /// macOS: writes and loads a LaunchAgent for the current user.
+    Install,
+
+    /// Stop the daemon service and remove its registration
+    Uninstall,
+}
+
+#[derive(Subcommand)]
+enum QueueAction {
+    /// List queued transfers waiting for their recipient
+    List,
+
+    /// Remove a queued transfer by ID
+    Remove {
+        /// Entry ID as shown by `wraith queue list`
+        id: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -198,6 +546,32 @@ enum ConfigAction {
         /// Value to set
         value: String,
     },
+
+    /// Validate a configuration file and report actionable diagnostics
+    Validate {
+        /// Config file to check (defaults to the global --config path)
+        #[arg(long)]
+        file: Option<String>,
+
+        /// Skip DNS resolution of bootstrap and relay entries
+        #[arg(long)]
+        offline: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ProfileAction {
+    /// List defined profiles and show which one is currently selected
+    List,
+
+    /// Pin a profile, overriding automatic network detection
+    Use {
+        /// Profile name to pin
+        name: String,
+    },
+
+    /// Remove the manual pin and return to automatic selection
+    Auto,
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -217,7 +591,6 @@ fn parse_transfer_id(s: &str) -> anyhow::Result<TransferId> {
 }
 
 /// Format duration as human-readable string
-#[allow(dead_code)]
 fn format_duration(d: Duration) -> String {
     let secs = d.as_secs();
     if secs < 60 {
@@ -231,22 +604,109 @@ fn format_duration(d: Duration) -> String {
 
 /// Create NodeConfig from CLI Config
 fn create_node_config(config: &Config) -> NodeConfig {
-    NodeConfig {
+    let mut node_config = NodeConfig {
         listen_addr: config
             .network
             .listen_addr
             .parse()
             .unwrap_or_else(|_| "0.0.0.0:0".parse().expect("Invalid default listen address")),
         ..NodeConfig::default()
+    };
+
+    // Persistent trust store, usually set per identity profile
+    if let Some(path) = &config.node.trust_store_file {
+        node_config.trust.enabled = true;
+        node_config.trust.store_path = Some(expand_tilde(path));
+    }
+
+    // Persistent per-transfer bandwidth limit from the config file
+    if let Some(limit) = &config.transfer.bandwidth_limit {
+        node_config.bandwidth.per_transfer_bps = wraith_core::node::bandwidth::parse_rate(limit);
+        if node_config.bandwidth.per_transfer_bps.is_none() {
+            tracing::warn!("Ignoring invalid transfer.bandwidth_limit: {limit:?}");
+        }
+    }
+
+    node_config
+}
+
+/// Expand a leading `~/` in a config-supplied path
+fn expand_tilde(path: &Path) -> PathBuf {
+    match path.strip_prefix("~") {
+        Ok(rest) => dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join(rest),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Load the identity persisted at `node.private_key_file`, if one exists
+///
+/// Returns `None` when no key file is present: the node then runs with an
+/// ephemeral random identity, as it always has. The key is decrypted with
+/// the passphrase from `WRAITH_PASSPHRASE` when set, otherwise with an
+/// interactive prompt; a non-interactive run without the variable falls
+/// back to an ephemeral identity rather than hanging on a prompt.
+fn load_identity(config: &Config) -> anyhow::Result<Option<wraith_core::node::Identity>> {
+    use std::io::IsTerminal;
+
+    let key_path = expand_tilde(&config.node.private_key_file);
+    if !key_path.exists() {
+        return Ok(None);
+    }
+
+    let encrypted = std::fs::read(&key_path)
+        .with_context(|| format!("Failed to read private key {}", key_path.display()))?;
+
+    let passphrase = match std::env::var("WRAITH_PASSPHRASE") {
+        Ok(passphrase) => passphrase,
+        Err(_) if std::io::stdin().is_terminal() => {
+            rpassword::prompt_password(format!("Passphrase for {}: ", key_path.display()))?
+        }
+        Err(_) => {
+            tracing::warn!(
+                "Key file {} exists but WRAITH_PASSPHRASE is not set and stdin \
+                 is not a terminal; using an ephemeral identity",
+                key_path.display()
+            );
+            return Ok(None);
+        }
+    };
+
+    let mut seed = decrypt_private_key(&encrypted, &passphrase)
+        .with_context(|| format!("Failed to decrypt {}", key_path.display()))?;
+    let identity = wraith_core::node::Identity::from_ed25519_seed(seed);
+    seed.zeroize();
+    Ok(Some(identity?))
+}
+
+/// Build a node from the resolved configuration
+///
+/// Uses the persisted identity when the configured key file exists, so the
+/// node presents a stable peer ID across runs - and a different one per
+/// profile when profiles override `private_key_file`. Without a key file
+/// the identity is ephemeral.
+async fn create_node(node_config: NodeConfig, config: &Config) -> anyhow::Result<Node> {
+    match load_identity(config)? {
+        Some(identity) => {
+            tracing::info!(
+                "Identity loaded from {}",
+                config.node.private_key_file.display()
+            );
+            Ok(Node::new_from_identity(identity, node_config).await?)
+        }
+        None => Ok(Node::new_with_config(node_config).await?),
     }
 }
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() {
     let cli = Cli::parse();
 
-    // Initialize logging
-    let log_level = if cli.debug {
+    // Initialize logging (quiet mode keeps errors only)
+    let log_level = if cli.quiet {
+        "error"
+    } else if cli.debug {
         "trace"
     } else if cli.verbose {
         "debug"
@@ -254,8 +714,120 @@ async fn main() -> anyhow::Result<()> {
         "info"
     };
 
-    tracing_subscriber::fmt().with_env_filter(log_level).init();
+    // A daemon launched by the Windows service manager has no console: it
+    // must hand the main thread to the service dispatcher before anything
+    // else, and its logs go to the service log file instead of stderr
+    #[cfg(windows)]
+    if let Commands::Daemon {
+        bind,
+        relay,
+        harden: _,
+        service: true,
+        action: None,
+    } = &cli.command
+    {
+        if let Err(e) = service::run_as_service(bind.clone(), *relay, log_level) {
+            eprintln!("wraith service failed: {e}");
+            std::process::exit(1);
+        }
+        return;
+    }
+
+    #[cfg(feature = "otel")]
+    let otel_provider = init_otel_tracing(log_level);
+    #[cfg(not(feature = "otel"))]
+    init_reloadable_tracing(log_level);
+
+    QUIET.store(cli.quiet, std::sync::atomic::Ordering::Relaxed);
+    progress::set_quiet(cli.quiet);
+
+    let result = run(cli).await;
+
+    // Flush buffered spans before exiting
+    #[cfg(feature = "otel")]
+    if let Err(e) = otel_provider.shutdown() {
+        tracing::debug!("OTLP exporter shutdown failed: {e}");
+    }
+
+    if let Err(err) = result {
+        eprintln!("Error: {err:#}");
+        std::process::exit(exit_code_for(&err));
+    }
+}
+
+/// Handle for swapping the active log filter at runtime
+///
+/// Set by [`init_reloadable_tracing`]; consumed by the daemon's SIGHUP
+/// config reload to apply `logging.level` changes without a restart.
+static LOG_FILTER_RELOAD: std::sync::OnceLock<
+    tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, tracing_subscriber::Registry>,
+> = std::sync::OnceLock::new();
+
+/// Initialize tracing with a filter that can be swapped at runtime
+#[cfg(not(feature = "otel"))]
+fn init_reloadable_tracing(log_level: &str) {
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    let (filter, handle) =
+        tracing_subscriber::reload::Layer::new(tracing_subscriber::EnvFilter::new(log_level));
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .init();
+    let _ = LOG_FILTER_RELOAD.set(handle);
+}
+
+/// Swap the active log filter; returns false if the subscriber has no
+/// reload handle (OTLP builds install a fixed filter)
+fn set_log_level(level: &str) -> bool {
+    match LOG_FILTER_RELOAD.get() {
+        Some(handle) => handle
+            .reload(tracing_subscriber::EnvFilter::new(level))
+            .is_ok(),
+        None => false,
+    }
+}
+
+/// Initialize tracing with an OTLP span exporter layered over the console
+/// formatter
+///
+/// Spans from wraith-core (transfer → chunk → encrypt/transmit) are
+/// batched and shipped to the collector named by the standard
+/// `OTEL_EXPORTER_OTLP_ENDPOINT` environment variable (default
+/// `http://localhost:4317`), so slow transfers can be traced end-to-end
+/// in Jaeger or Tempo. Returns the provider so `main` can flush it on
+/// exit.
+#[cfg(feature = "otel")]
+fn init_otel_tracing(log_level: &str) -> opentelemetry_sdk::trace::SdkTracerProvider {
+    use opentelemetry::trace::TracerProvider as _;
+    use tracing_subscriber::layer::SubscriberExt;
+    use tracing_subscriber::util::SubscriberInitExt;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .build()
+        .expect("Failed to build OTLP span exporter");
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name("wraith")
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer("wraith");
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::new(log_level))
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .init();
+
+    provider
+}
 
+async fn run(cli: Cli) -> anyhow::Result<()> {
     // Keygen command doesn't need config - handle it separately
     if matches!(cli.command, Commands::Keygen { .. }) {
         if let Commands::Keygen { output } = cli.command {
@@ -263,6 +835,47 @@ async fn main() -> anyhow::Result<()> {
         }
     }
 
+    // Init creates the config, so it must run before config loading
+    if let Commands::Init { force } = cli.command {
+        return init_wizard(&cli.config, force).await;
+    }
+
+    // Features reports on the binary and platform, not the config
+    if let Commands::Features { json } = cli.command {
+        return show_features(json);
+    }
+
+    // Artifact generators write to stdout and never touch the config
+    if let Commands::Completions { shell } = cli.command {
+        use clap::CommandFactory;
+        clap_complete::generate(shell, &mut Cli::command(), "wraith", &mut std::io::stdout());
+        return Ok(());
+    }
+    if matches!(cli.command, Commands::Manpage) {
+        use clap::CommandFactory;
+        clap_mangen::Man::new(Cli::command()).render(&mut std::io::stdout())?;
+        return Ok(());
+    }
+
+    // Replay operates on a capture file offline and never touches the config
+    if let Commands::Replay { capture, fail_fast } = &cli.command {
+        return replay::run_replay(capture, *fail_fast);
+    }
+
+    // Verify operates on local files offline and never touches the config
+    if let Commands::Verify { file, manifest } = &cli.command {
+        return verify_manifest(file, manifest.as_deref());
+    }
+
+    // `config validate` inspects a file directly and must not be blocked by
+    // the strict validation applied to the active config below
+    if let Commands::Config {
+        action: ConfigAction::Validate { file, offline },
+    } = &cli.command
+    {
+        return config_validate(file.as_deref().unwrap_or(&cli.config), *offline);
+    }
+
     // Load configuration (expand tilde if present)
     let config_path = if cli.config.starts_with("~/") {
         dirs::home_dir()
@@ -272,7 +885,7 @@ async fn main() -> anyhow::Result<()> {
         PathBuf::from(&cli.config)
     };
 
-    let config = if config_path.exists() {
+    let mut config = if config_path.exists() {
         Config::load(&config_path)?
     } else if config_path == Config::default_path() {
         Config::load_or_default()?
@@ -283,41 +896,137 @@ async fn main() -> anyhow::Result<()> {
     // Validate configuration
     config.validate()?;
 
+    // Apply a profile on top of the base config: --profile wins outright,
+    // otherwise the pinned or network-detected one
+    if let Some(name) = &cli.profile {
+        config.apply_profile(name)?;
+        tracing::info!("Profile '{name}' active (--profile)");
+    } else if let Some(name) = profile::select_profile(&config) {
+        config.apply_profile(&name)?;
+        tracing::info!("Network profile '{name}' active");
+    }
+
     match cli.command {
         Commands::Send {
             file,
             recipient,
+            to,
+            multicast,
             mode,
+            limit,
+            code,
+            link,
+            dry_run,
+            queue,
+            schedule,
+            window,
+            peak_limit,
         } => {
-            send_file(PathBuf::from(file), recipient, mode, &config).await?;
+            if multicast {
+                send_multicast(PathBuf::from(file), recipient, mode, limit, &config).await?;
+            } else if dry_run {
+                dry_run_send_command(file, recipient, to, &mode, limit, &config)?;
+            } else if schedule.is_some() || !window.is_empty() {
+                schedule_send(
+                    PathBuf::from(file),
+                    recipient,
+                    limit,
+                    schedule,
+                    window,
+                    peak_limit,
+                    &config,
+                )?;
+            } else if link {
+                send_with_link(PathBuf::from(file), &config).await?;
+            } else if code {
+                send_with_code(PathBuf::from(file), &config).await?;
+            } else if !to.is_empty() {
+                let mut recipients = recipient;
+                recipients.extend(to);
+                send_file_fanout(PathBuf::from(file), recipients, limit, &config).await?;
+            } else if file == "-" {
+                send_stdin(recipient, &config).await?;
+            } else {
+                send_file(PathBuf::from(file), recipient, mode, limit, queue, &config).await?;
+            }
         }
-        Commands::Batch { files, to, mode } => {
-            send_batch(files, to, mode, &config).await?;
+        Commands::Batch {
+            files,
+            to,
+            mode,
+            dry_run,
+        } => {
+            if dry_run {
+                dry_run_batch_command(files, &to, &mode, &config)?;
+            } else {
+                send_batch(files, to, mode, &config).await?;
+            }
         }
         Commands::Receive {
             output,
             bind,
             auto_accept,
             trusted_peers,
+            stdout,
+            multicast_hash,
+            code,
+            sender,
         } => {
-            receive_files(
-                PathBuf::from(output),
-                bind,
-                auto_accept,
-                trusted_peers,
-                &config,
-            )
-            .await?;
-        }
-        Commands::Daemon { bind, relay } => {
-            run_daemon(bind, relay, &config).await?;
+            if let Some(hash) = multicast_hash {
+                receive_multicast(&hash, PathBuf::from(output), &config).await?;
+            } else if let Some(code) = code {
+                let sender = sender.expect("clap requires --sender with --code");
+                receive_with_code(&code, &sender, PathBuf::from(output), &config).await?;
+            } else if stdout {
+                receive_stdout(trusted_peers, &config).await?;
+            } else {
+                receive_files(
+                    PathBuf::from(output),
+                    bind,
+                    auto_accept,
+                    trusted_peers,
+                    &config,
+                )
+                .await?;
+            }
+        }
+        Commands::Get { url, output } => {
+            get_link(&url, PathBuf::from(output), &config).await?;
+        }
+        Commands::HandleUri {
+            uri,
+            register,
+            output,
+        } => {
+            if register {
+                register_uri_handler()?;
+            } else if let Some(uri) = uri {
+                handle_uri(&uri, PathBuf::from(output), &config).await?;
+            }
         }
+        Commands::Daemon {
+            bind,
+            relay,
+            harden,
+            service: _,
+            action,
+        } => match action {
+            Some(DaemonAction::Install) => service::install(&bind, relay)?,
+            Some(DaemonAction::Uninstall) => service::uninstall()?,
+            None => run_daemon(bind, relay, harden, &config, &config_path).await?,
+        },
         Commands::Status { transfer, detailed } => {
             show_status(transfer, detailed, &config).await?;
         }
         Commands::Peers { dht_query } => {
             list_peers(dht_query, &config).await?;
         }
+        Commands::Search {
+            query,
+            group_secret,
+        } => {
+            search_group_content(query, group_secret, &config).await?;
+        }
         Commands::Health => {
             show_health(&config).await?;
         }
@@ -331,6 +1040,34 @@ async fn main() -> anyhow::Result<()> {
             // Already handled above before config loading
             unreachable!("Keygen command should have been handled earlier")
         }
+        Commands::Init { .. } => {
+            // Already handled above before config loading
+            unreachable!("Init command should have been handled earlier")
+        }
+        Commands::Features { .. } => {
+            // Already handled above before config loading
+            unreachable!("Features command should have been handled earlier")
+        }
+        Commands::Completions { .. } | Commands::Manpage => {
+            // Already handled above before config loading
+            unreachable!("Artifact generators should have been handled earlier")
+        }
+        Commands::Replay { .. } => {
+            // Already handled above before config loading
+            unreachable!("Replay command should have been handled earlier")
+        }
+        Commands::Verify { .. } => {
+            // Already handled above before config loading
+            unreachable!("Verify command should have been handled earlier")
+        }
+        Commands::Queue { action } => match action {
+            QueueAction::List => {
+                queue_list()?;
+            }
+            QueueAction::Remove { id } => {
+                queue_remove(&id)?;
+            }
+        },
         Commands::Ping {
             peer,
             count,
@@ -338,6 +1075,23 @@ async fn main() -> anyhow::Result<()> {
         } => {
             ping_peer(peer, count, interval, &config).await?;
         }
+        Commands::Probe => {
+            probe_network().await?;
+        }
+        Commands::Pipe { peer, listen } => {
+            pipe_stream(peer, listen, &config).await?;
+        }
+        Commands::Profile { action } => match action {
+            ProfileAction::List => {
+                profile_list(&config)?;
+            }
+            ProfileAction::Use { name } => {
+                profile_use(Some(name), &cli.config)?;
+            }
+            ProfileAction::Auto => {
+                profile_use(None, &cli.config)?;
+            }
+        },
         Commands::Config { action } => match action {
             ConfigAction::Show { key } => {
                 config_show(key, &config).await?;
@@ -345,6 +1099,10 @@ async fn main() -> anyhow::Result<()> {
             ConfigAction::Set { key, value } => {
                 config_set(key, value, &cli.config).await?;
             }
+            ConfigAction::Validate { .. } => {
+                // Already handled above before config loading
+                unreachable!("Validate action should have been handled earlier")
+            }
         },
     }
 
@@ -363,7 +1121,9 @@ fn sanitize_path(path: &PathBuf) -> anyhow::Result<PathBuf> {
     // Check for obvious traversal attempts in the raw path
     let path_str = path.to_string_lossy();
     if path_str.contains("..") {
-        anyhow::bail!("Path traversal attempt detected: path contains '..'");
+        return Err(
+            PolicyRejection("path traversal attempt: path contains '..'".to_string()).into(),
+        );
     }
 
     // Canonicalize if the path exists
@@ -537,26 +1297,120 @@ fn prompt_passphrase(prompt: &str, confirm: bool) -> anyhow::Result<String> {
     Ok(passphrase)
 }
 
+/// Handle `wraith send --schedule` / `--window`: spool for later dispatch
+///
+/// Scheduled sends never start a node here — the constraints are recorded
+/// in the encrypted spool and the daemon's dispatcher starts the transfer
+/// once the start time has passed, the clock is inside an allowed window,
+/// and the peer is reachable. `--limit` becomes the off-peak (in-window)
+/// rate; `--peak-limit` the throttled rate outside the windows.
+fn schedule_send(
+    file: PathBuf,
+    recipients: Vec<String>,
+    limit: Option<String>,
+    schedule: Option<String>,
+    windows: Vec<String>,
+    peak_limit: Option<String>,
+    config: &Config,
+) -> anyhow::Result<()> {
+    use wraith_core::transfer::{TransferSchedule, schedule::parse_hhmm};
+
+    let file = sanitize_path(&file)?;
+    if !file.exists() {
+        anyhow::bail!("File not found: {file:?}");
+    }
+
+    let limit_str = limit.or_else(|| config.transfer.bandwidth_limit.clone());
+    let offpeak_limit_bps = match &limit_str {
+        Some(s) => Some(wraith_core::node::bandwidth::parse_rate(s).ok_or_else(|| {
+            anyhow::anyhow!("Invalid bandwidth limit: {s:?} (expected e.g. \"10MB/s\")")
+        })?),
+        None => None,
+    };
+    let peak_limit_bps = match &peak_limit {
+        Some(s) => Some(wraith_core::node::bandwidth::parse_rate(s).ok_or_else(|| {
+            anyhow::anyhow!("Invalid peak limit: {s:?} (expected e.g. \"1MB/s\")")
+        })?),
+        None => None,
+    };
+
+    let parsed_windows = windows
+        .iter()
+        .map(|w| w.parse())
+        .collect::<Result<Vec<wraith_core::transfer::TimeWindow>, String>>()
+        .map_err(|e| anyhow::anyhow!(e))?;
+
+    // --schedule HH:MM means the next occurrence of that local time
+    let start_at = match &schedule {
+        Some(s) => {
+            let target = parse_hhmm(s).map_err(|e| anyhow::anyhow!(e))?;
+            let delta_min =
+                u64::from((target + 24 * 60 - spool::local_minute_of_day()) % (24 * 60));
+            Some(spool::now_secs() + delta_min * 60)
+        }
+        None => None,
+    };
+
+    let transfer_schedule = TransferSchedule {
+        start_at,
+        windows: parsed_windows,
+        offpeak_limit_bps,
+        peak_limit_bps,
+    };
+
+    let send_queue = spool::Spool::open(spool::Spool::default_dir())?;
+    status!("File: {}", file.display());
+    for recipient in &recipients {
+        let peer_id = parse_peer_id(recipient)?;
+        let entry = spool::SpoolEntry {
+            file_path: file.clone(),
+            peer_id,
+            limit_bps: offpeak_limit_bps,
+            queued_at: spool::now_secs(),
+            schedule: Some(transfer_schedule.clone()),
+        };
+        let id = send_queue.enqueue(&entry)?;
+        status!("Scheduled as {id} for {}", hex::encode(&peer_id[..8]));
+    }
+
+    if let Some(start) = start_at {
+        let wait_min = start.saturating_sub(spool::now_secs()) / 60;
+        status!("Starts in ~{wait_min} min");
+    }
+    for window in &transfer_schedule.windows {
+        status!("Window: {window} (local time)");
+    }
+    status!("A running daemon (`wraith daemon`) dispatches scheduled transfers");
+    Ok(())
+}
+
 /// Send a file to one or more recipients
 async fn send_file(
     file: PathBuf,
     recipients: Vec<String>,
     _mode: String,
+    limit: Option<String>,
+    queue: bool,
     config: &Config,
 ) -> anyhow::Result<()> {
     // Sanitize file path to prevent directory traversal
     let file = sanitize_path(&file)?;
 
+    // Resolve bandwidth limit: --limit flag overrides the config default
+    let limit_str = limit.or_else(|| config.transfer.bandwidth_limit.clone());
+    let limit_bps = match &limit_str {
+        Some(s) => Some(wraith_core::node::bandwidth::parse_rate(s).ok_or_else(|| {
+            anyhow::anyhow!("Invalid bandwidth limit: {s:?} (expected e.g. \"10MB/s\")")
+        })?),
+        None => None,
+    };
+
     // Verify file exists
     if !file.exists() {
         anyhow::bail!("File not found: {file:?}");
     }
 
     let file_size = std::fs::metadata(&file)?.len();
-    let filename = file
-        .file_name()
-        .and_then(|n| n.to_str())
-        .unwrap_or("unknown");
 
     // Parse all peer IDs
     let mut peer_ids = Vec::new();
@@ -565,30 +1419,37 @@ async fn send_file(
         peer_ids.push(peer_id);
     }
 
-    println!("File: {}", file.display());
-    println!("Size: {}", format_bytes(file_size));
-    println!("Recipients: {}", peer_ids.len());
+    status!("File: {}", file.display());
+    status!("Size: {}", format_bytes(file_size));
+    if let Some(bps) = limit_bps {
+        status!("Limit: {}/s", format_bytes(bps));
+    }
+    status!("Recipients: {}", peer_ids.len());
     for (idx, peer_id) in peer_ids.iter().enumerate() {
-        println!("  {}: {}", idx + 1, hex::encode(&peer_id[..8]));
+        status!("  {}: {}", idx + 1, hex::encode(&peer_id[..8]));
     }
-    println!();
+    status!();
 
     // Create and start node
-    let node_config = create_node_config(config);
-    let node = Node::new_with_config(node_config).await?;
+    let mut node_config = create_node_config(config);
+    node_config.bandwidth.per_transfer_bps = limit_bps;
+    let node = create_node(node_config, config).await?;
 
     tracing::info!("Starting node...");
     node.start().await?;
 
     let listen_addr = node.listen_addr().await?;
-    println!("Node started: {}", hex::encode(node.node_id()));
-    println!("Listening on: {}", listen_addr);
-    println!();
+    status!("Node started: {}", hex::encode(node.node_id()));
+    status!("Listening on: {}", listen_addr);
+    status!();
 
-    // Send file to each recipient
+    // Send file to each recipient; with --queue, unreachable recipients
+    // are spooled for the daemon instead of failing the command
+    let mut sent_peers = Vec::new();
     let mut transfer_ids = Vec::new();
+    let mut queued = 0usize;
     for (idx, peer_id) in peer_ids.iter().enumerate() {
-        println!(
+        status!(
             "[{}/{}] Sending to {}...",
             idx + 1,
             peer_ids.len(),
@@ -597,49 +1458,90 @@ async fn send_file(
 
         // Send file using Node API
         tracing::info!("Establishing session with peer...");
-        let transfer_id = node.send_file(&file, peer_id).await?;
+        let transfer_id = match node.send_file(&file, peer_id).await {
+            Ok(transfer_id) => transfer_id,
+            Err(e) if queue => {
+                let entry = spool::SpoolEntry {
+                    file_path: file.clone(),
+                    peer_id: *peer_id,
+                    limit_bps,
+                    queued_at: spool::now_secs(),
+                    schedule: None,
+                };
+                let id = spool::Spool::open(spool::Spool::default_dir())?.enqueue(&entry)?;
+                status!("  Peer unreachable ({e})");
+                status!("  Queued as {id}; the daemon will send it when the peer appears");
+                queued += 1;
+                continue;
+            }
+            Err(e) => return Err(e.into()),
+        };
+        sent_peers.push(*peer_id);
         transfer_ids.push(transfer_id);
 
-        println!("  Transfer started: {}", hex::encode(&transfer_id[..8]));
+        status!("  Transfer started: {}", hex::encode(&transfer_id[..8]));
+        if let Ok(sas) = node.session_sas(peer_id) {
+            status!(
+                "  Verify with recipient: {}  ({})",
+                sas.emoji(),
+                sas.digits()
+            );
+        }
+    }
+
+    if transfer_ids.is_empty() {
+        status!();
+        status!("No transfers started ({queued} queued)");
+        node.stop().await?;
+        status!("Node stopped");
+        return Ok(());
     }
 
-    println!();
-    println!("Monitoring {} transfer(s)...", transfer_ids.len());
+    status!();
+    status!("Monitoring {} transfer(s)...", transfer_ids.len());
+
+    // Wait for all transfers to complete: one aggregate bar plus one bar
+    // per recipient, driven by node-reported progress
+    let mut progress = MultiTransferProgress::new(file_size * sent_peers.len() as u64);
+    let bar_indices: Vec<usize> = sent_peers
+        .iter()
+        .map(|peer_id| progress.add_transfer(&hex::encode(&peer_id[..8]), file_size))
+        .collect();
 
-    // Wait for all transfers to complete
-    let progress = TransferProgress::new(file_size * peer_ids.len() as u64, filename);
     let mut completed = vec![false; transfer_ids.len()];
-    let mut total_sent = 0u64;
 
     loop {
         let mut all_done = true;
+        let mut total_sent = 0u64;
 
         for (idx, transfer_id) in transfer_ids.iter().enumerate() {
             if completed[idx] {
+                total_sent += file_size;
                 continue;
             }
 
             if let Some(transfer_progress) = node.get_transfer_progress(transfer_id).await {
+                total_sent += transfer_progress.bytes_sent;
+
                 if transfer_progress.status == wraith_core::node::progress::TransferStatus::Complete
                 {
                     completed[idx] = true;
-                    total_sent += file_size;
-                    println!(
-                        "Transfer {} complete: {} sent to {}",
-                        hex::encode(&transfer_id[..8]),
-                        format_bytes(file_size),
-                        hex::encode(&peer_ids[idx][..8])
+                    progress.finish_transfer(
+                        bar_indices[idx],
+                        format!("complete ({})", format_bytes(file_size)),
                     );
                 } else if transfer_progress.status
                     == wraith_core::node::progress::TransferStatus::Failed
                 {
                     completed[idx] = true;
-                    println!(
-                        "Transfer {} failed to {}",
-                        hex::encode(&transfer_id[..8]),
-                        hex::encode(&peer_ids[idx][..8])
-                    );
+                    progress.finish_transfer(bar_indices[idx], "failed".to_string());
                 } else {
+                    progress.update_transfer(
+                        bar_indices[idx],
+                        transfer_progress.bytes_sent,
+                        transfer_progress.speed_bytes_per_sec,
+                        transfer_progress.eta,
+                    );
                     all_done = false;
                 }
             } else {
@@ -647,11 +1549,11 @@ async fn send_file(
             }
         }
 
-        progress.update(total_sent);
+        progress.update_aggregate(total_sent);
 
         if all_done {
             let successful = completed.iter().filter(|&&c| c).count();
-            progress.finish_with_message(format!(
+            progress.finish(format!(
                 "All transfers complete: {}/{} successful",
                 successful,
                 transfer_ids.len()
@@ -664,7 +1566,150 @@ async fn send_file(
 
     // Stop node
     node.stop().await?;
-    println!("Node stopped");
+    status!("Node stopped");
+
+    Ok(())
+}
+
+/// Fan out one file to several recipients (`wraith send --to ... --to ...`)
+///
+/// Unlike the sequential loop in [`send_file`], this hands all recipients to
+/// `Node::send_file_to_many`, which reads each chunk from disk once and
+/// frames it per recipient. Recipients that fail during setup are reported
+/// and the rest proceed.
+async fn send_file_fanout(
+    file: PathBuf,
+    recipients: Vec<String>,
+    limit: Option<String>,
+    config: &Config,
+) -> anyhow::Result<()> {
+    // Sanitize file path to prevent directory traversal
+    let file = sanitize_path(&file)?;
+
+    // Resolve bandwidth limit: --limit flag overrides the config default
+    let limit_str = limit.or_else(|| config.transfer.bandwidth_limit.clone());
+    let limit_bps = match &limit_str {
+        Some(s) => Some(wraith_core::node::bandwidth::parse_rate(s).ok_or_else(|| {
+            anyhow::anyhow!("Invalid bandwidth limit: {s:?} (expected e.g. \"10MB/s\")")
+        })?),
+        None => None,
+    };
+
+    if !file.exists() {
+        anyhow::bail!("File not found: {file:?}");
+    }
+
+    let file_size = std::fs::metadata(&file)?.len();
+
+    let mut peer_ids = Vec::new();
+    for recipient in &recipients {
+        let peer_id = parse_peer_id(recipient)?;
+        peer_ids.push(peer_id);
+    }
+
+    status!("File: {}", file.display());
+    status!("Size: {}", format_bytes(file_size));
+    if let Some(bps) = limit_bps {
+        status!("Limit: {}/s", format_bytes(bps));
+    }
+    status!("Fan-out recipients: {}", peer_ids.len());
+    for (idx, peer_id) in peer_ids.iter().enumerate() {
+        status!("  {}: {}", idx + 1, hex::encode(&peer_id[..8]));
+    }
+    status!();
+
+    // Create and start node
+    let mut node_config = create_node_config(config);
+    node_config.bandwidth.per_transfer_bps = limit_bps;
+    let node = create_node(node_config, config).await?;
+
+    tracing::info!("Starting node...");
+    node.start().await?;
+
+    let listen_addr = node.listen_addr().await?;
+    status!("Node started: {}", hex::encode(node.node_id()));
+    status!("Listening on: {}", listen_addr);
+    status!();
+
+    let report = node.send_file_to_many(&file, &peer_ids).await?;
+
+    for (peer_id, reason) in &report.failures {
+        status!("  {} failed: {}", hex::encode(&peer_id[..8]), reason);
+    }
+    status!(
+        "Started {} transfer(s), {} recipient(s) unreachable",
+        report.transfers.len(),
+        report.failures.len()
+    );
+    status!();
+
+    // Wait for all started transfers: one aggregate bar plus one bar per
+    // recipient, driven by node-reported progress
+    let mut progress = MultiTransferProgress::new(file_size * report.transfers.len() as u64);
+    let bar_indices: Vec<usize> = report
+        .transfers
+        .iter()
+        .map(|(peer_id, _)| progress.add_transfer(&hex::encode(&peer_id[..8]), file_size))
+        .collect();
+
+    let mut completed = vec![false; report.transfers.len()];
+
+    loop {
+        let mut all_done = true;
+        let mut total_sent = 0u64;
+
+        for (idx, (_, transfer_id)) in report.transfers.iter().enumerate() {
+            if completed[idx] {
+                total_sent += file_size;
+                continue;
+            }
+
+            if let Some(transfer_progress) = node.get_transfer_progress(transfer_id).await {
+                total_sent += transfer_progress.bytes_sent;
+
+                if transfer_progress.status == wraith_core::node::progress::TransferStatus::Complete
+                {
+                    completed[idx] = true;
+                    progress.finish_transfer(
+                        bar_indices[idx],
+                        format!("complete ({})", format_bytes(file_size)),
+                    );
+                } else if transfer_progress.status
+                    == wraith_core::node::progress::TransferStatus::Failed
+                {
+                    completed[idx] = true;
+                    progress.finish_transfer(bar_indices[idx], "failed".to_string());
+                } else {
+                    progress.update_transfer(
+                        bar_indices[idx],
+                        transfer_progress.bytes_sent,
+                        transfer_progress.speed_bytes_per_sec,
+                        transfer_progress.eta,
+                    );
+                    all_done = false;
+                }
+            } else {
+                all_done = false;
+            }
+        }
+
+        progress.update_aggregate(total_sent);
+
+        if all_done {
+            let successful = completed.iter().filter(|&&c| c).count();
+            progress.finish(format!(
+                "Fan-out complete: {}/{} successful",
+                successful,
+                report.transfers.len()
+            ));
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    node.stop().await?;
+    status!("Node stopped");
 
     Ok(())
 }
@@ -693,31 +1738,47 @@ async fn receive_files(
 
     // Create and start node
     let node_config = create_node_config(config);
-    let node = Node::new_with_config(node_config).await?;
+    let node = create_node(node_config, config).await?;
 
     tracing::info!("Starting receive node...");
     node.start().await?;
 
     let listen_addr = node.listen_addr().await?;
-    println!("WRAITH Receive Mode");
-    println!("Version: {}", env!("CARGO_PKG_VERSION"));
-    println!();
-    println!("Node ID: {}", hex::encode(node.node_id()));
-    println!("Listening on: {}", listen_addr);
-    println!("Output directory: {}", output.display());
-    println!("Auto-accept: {}", auto_accept);
+    status!("WRAITH Receive Mode");
+    status!("Version: {}", env!("CARGO_PKG_VERSION"));
+    status!();
+    status!("Node ID: {}", hex::encode(node.node_id()));
+    status!("Listening on: {}", listen_addr);
+    status!("Output directory: {}", output.display());
+    status!("Auto-accept: {}", auto_accept);
     if !trusted_peer_ids.is_empty() {
-        println!("Trusted peers: {}", trusted_peer_ids.len());
+        status!("Trusted peers: {}", trusted_peer_ids.len());
         for (idx, peer_id) in trusted_peer_ids.iter().enumerate() {
-            println!("  {}: {}", idx + 1, hex::encode(&peer_id[..8]));
+            status!("  {}: {}", idx + 1, hex::encode(&peer_id[..8]));
         }
     }
-    println!();
-    println!("Ready to receive files. Press Ctrl+C to stop");
-    println!();
+    status!();
+    status!("Ready to receive files. Press Ctrl+C to stop");
+    status!();
 
     // Monitor for incoming transfers
     let node_arc = Arc::new(node);
+
+    // Show the short authentication string for each new session so both
+    // users can compare it out-of-band and rule out a MITM on first contact
+    let mut events = node_arc.subscribe();
+    let sas_node = Arc::clone(&node_arc);
+    tokio::spawn(async move {
+        while let Ok(event) = events.recv().await {
+            if let wraith_core::node::NodeEvent::PeerConnected { peer_id } = event
+                && let Ok(sas) = sas_node.session_sas(&peer_id)
+            {
+                status!("Peer {} connected", hex::encode(&peer_id[..8]));
+                status!("  Verify with sender: {}  ({})", sas.emoji(), sas.digits());
+            }
+        }
+    });
+
     let node_clone = Arc::clone(&node_arc);
     let output_clone = output.clone();
     let _auto_accept = auto_accept;
@@ -728,7 +1789,7 @@ async fn receive_files(
             let transfers = node_clone.active_transfers().await;
             for transfer_id in transfers {
                 if let Some(progress) = node_clone.get_transfer_progress(&transfer_id).await {
-                    println!(
+                    status!(
                         "Transfer {}: {} / {} ({:.1}%)",
                         hex::encode(&transfer_id[..8]),
                         format_bytes(progress.bytes_sent),
@@ -737,7 +1798,7 @@ async fn receive_files(
                     );
 
                     if progress.status == wraith_core::node::progress::TransferStatus::Complete {
-                        println!(
+                        status!(
                             "Transfer {} complete - saved to {}",
                             hex::encode(&transfer_id[..8]),
                             output_clone.display()
@@ -751,363 +1812,1458 @@ async fn receive_files(
 
     // Keep alive until Ctrl+C
     tokio::signal::ctrl_c().await?;
-    println!("\nShutting down...");
+    status!("\nShutting down...");
 
     node_arc.stop().await?;
-    println!("Node stopped");
+    status!("Node stopped");
 
     Ok(())
 }
 
-/// Run daemon mode
-async fn run_daemon(_bind: String, _relay: bool, config: &Config) -> anyhow::Result<()> {
-    // Create and start node
-    let node_config = create_node_config(config);
-    let node = Node::new_with_config(node_config).await?;
+/// Send a file using a one-time transfer code (magic-wormhole style)
+///
+/// Generates a short human-readable code, waits for the receiver to pair,
+/// and authenticates the pairing with a SPAKE2 exchange over an encrypted
+/// stream: both sides derive the same key only if the receiver typed the
+/// same code, and an active guesser gets exactly one attempt before the
+/// code is burned.
+/// Distribute a file over LAN multicast, falling back to unicast
+///
+/// Multicasts the file once to the WRAITH group with NACK-based repair and
+/// prints the BLAKE3 content hash receivers verify against. When no
+/// multicast route exists the file is instead sent over the ordinary
+/// unicast path to any listed recipients.
+async fn send_multicast(
+    file: PathBuf,
+    recipients: Vec<String>,
+    mode: String,
+    limit: Option<String>,
+    config: &Config,
+) -> anyhow::Result<()> {
+    use wraith_core::node::MulticastConfig;
 
-    tracing::info!("Starting WRAITH daemon...");
-    node.start().await?;
+    let file = sanitize_path(&file)?;
+    if !file.exists() {
+        anyhow::bail!("File not found: {file:?}");
+    }
+    let file_size = std::fs::metadata(&file)?.len();
 
-    let listen_addr = node.listen_addr().await?;
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+    node.start().await?;
 
-    println!("WRAITH Daemon");
-    println!("Version: {}", env!("CARGO_PKG_VERSION"));
-    println!();
-    println!("Node ID: {}", hex::encode(node.node_id()));
-    println!("Listening on: {}", listen_addr);
-    println!("XDP: {}", config.network.enable_xdp);
-    if config.network.enable_xdp {
-        if let Some(iface) = &config.network.xdp_interface {
-            println!("XDP interface: {iface}");
+    let mcast = MulticastConfig::default();
+    status!("File: {}", file.display());
+    status!("Size: {}", format_bytes(file_size));
+    status!("Multicast group: {}:{}", mcast.group, mcast.port);
+
+    match node.multicast_distribute(&file, mcast).await {
+        Ok((report, content_hash)) => {
+            status!("Content hash: {}", hex::encode(content_hash));
+            status!(
+                "Distribution complete: {} chunks, {} repairs over {} round(s)",
+                report.chunks_sent,
+                report.repairs_sent,
+                report.rounds_used
+            );
+            status!("Receivers that still miss chunks can fetch over unicast.");
+            node.stop().await?;
+            Ok(())
+        }
+        Err(e) if !recipients.is_empty() => {
+            status!("Multicast unavailable ({e}); falling back to unicast");
+            node.stop().await?;
+            send_file(file, recipients, mode, limit, false, config).await
+        }
+        Err(e) => {
+            node.stop().await?;
+            Err(anyhow::anyhow!(
+                "Multicast distribution failed: {e} (no recipients given for unicast fallback)"
+            ))
         }
     }
-    println!();
-    println!("Daemon ready. Press Ctrl+C to stop");
-    println!();
+}
 
-    // Monitor sessions and transfers
-    let node_arc = Arc::new(node);
-    let node_clone = Arc::clone(&node_arc);
+/// Join a LAN multicast distribution and save the verified file
+async fn receive_multicast(hash_hex: &str, output: PathBuf, config: &Config) -> anyhow::Result<()> {
+    use wraith_core::node::MulticastConfig;
 
-    tokio::spawn(async move {
-        loop {
-            let sessions = node_clone.active_sessions().await;
-            let transfers = node_clone.active_transfers().await;
+    let hash_bytes = hex::decode(hash_hex).context("Invalid content hash (expected hex)")?;
+    let content_hash: [u8; 32] = hash_bytes
+        .as_slice()
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Content hash must be 32 bytes (64 hex characters)"))?;
 
-            if !sessions.is_empty() || !transfers.is_empty() {
-                println!(
-                    "Status: {} active sessions, {} active transfers",
-                    sessions.len(),
-                    transfers.len()
-                );
-            }
+    if !output.exists() {
+        std::fs::create_dir_all(&output)?;
+    }
+    let output_file = output.join(format!("wraith-{}.bin", &hash_hex[..16]));
 
-            tokio::time::sleep(Duration::from_secs(5)).await;
-        }
-    });
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+    node.start().await?;
 
-    // Keep alive until Ctrl+C
-    tokio::signal::ctrl_c().await?;
-    println!("\nShutting down...");
+    let mcast = MulticastConfig::default();
+    status!("Joining multicast group {}:{}...", mcast.group, mcast.port);
+    status!("Waiting for distribution...");
 
-    node_arc.stop().await?;
-    println!("Daemon stopped");
+    // Idle window: give up when no datagram is seen for this long
+    let result = node
+        .multicast_fetch(content_hash, &output_file, mcast, Duration::from_secs(30))
+        .await;
+    node.stop().await?;
 
+    let bytes = result.context("Multicast receive failed")?;
+    status!(
+        "Received and verified {} - saved to {}",
+        format_bytes(bytes),
+        output_file.display()
+    );
     Ok(())
 }
 
-/// Send batch of files
-async fn send_batch(
-    files: Vec<String>,
-    recipient: String,
-    _mode: String,
-    config: &Config,
-) -> anyhow::Result<()> {
-    // Parse peer ID
-    let peer_id = parse_peer_id(&recipient)?;
-
-    println!("Batch Transfer");
-    println!("Files: {}", files.len());
-    println!("Recipient: {}", hex::encode(&peer_id[..8]));
-    println!();
-
-    // Validate and sanitize all file paths
-    let mut total_size = 0u64;
-    let mut sanitized_files = Vec::new();
-
-    for file_path_str in &files {
-        let file_path = PathBuf::from(file_path_str);
-        let sanitized = sanitize_path(&file_path)?;
-
-        if !sanitized.exists() {
-            anyhow::bail!("File not found: {file_path:?}");
-        }
-
-        let metadata = std::fs::metadata(&sanitized)?;
-        if !metadata.is_file() {
-            anyhow::bail!("Not a file: {file_path:?}");
-        }
+async fn send_with_code(file: PathBuf, config: &Config) -> anyhow::Result<()> {
+    use wraith_core::node::{PakeRole, TransferCode};
 
-        total_size += metadata.len();
-        sanitized_files.push((sanitized, metadata.len()));
+    let file = sanitize_path(&file)?;
+    if !file.exists() {
+        anyhow::bail!("File not found: {file:?}");
     }
+    let file_size = std::fs::metadata(&file)?.len();
 
-    println!("Total size: {}", format_bytes(total_size));
-    println!();
-
-    // Create and start node
     let node_config = create_node_config(config);
-    let node = Node::new_with_config(node_config).await?;
-
-    tracing::info!("Starting node...");
+    let node = create_node(node_config, config).await?;
     node.start().await?;
-
     let listen_addr = node.listen_addr().await?;
-    println!("Node started: {}", hex::encode(node.node_id()));
-    println!("Listening on: {}", listen_addr);
-    println!();
-
-    // Send each file
-    for (idx, (file_path, file_size)) in sanitized_files.iter().enumerate() {
-        let filename = file_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown");
-
-        println!("[{}/{}] {}", idx + 1, sanitized_files.len(), filename);
-        println!("  Size: {}", format_bytes(*file_size));
 
-        let progress = TransferProgress::new(*file_size, filename);
-
-        // Send file using Node API
-        let transfer_id = node.send_file(file_path, &peer_id).await?;
-        println!("  Transfer ID: {}", hex::encode(&transfer_id[..8]));
+    let code = TransferCode::generate();
+    status!("File: {}", file.display());
+    status!("Size: {}", format_bytes(file_size));
+    status!();
+    status!("Transfer code: {code}");
+    status!("On the receiving machine, run:");
+    status!(
+        "  wraith receive --code {code} --sender <this-host>:{}",
+        listen_addr.port()
+    );
+    status!();
+    status!("Waiting for receiver...");
 
-        // Wait for completion
-        node.wait_for_transfer(transfer_id).await?;
+    let stream = node
+        .accept_stream()
+        .await
+        .ok_or_else(|| anyhow::anyhow!("Node stopped while waiting for the receiver"))?;
+    let peer_id = stream.peer_id();
 
-        progress.finish_with_message(format!(
-            "File {}/{} complete",
-            idx + 1,
-            sanitized_files.len()
-        ));
-    }
+    pake_exchange(stream, PakeRole::Sender, &code).await?;
+    status!("Code verified - receiver authenticated");
 
-    println!();
-    println!("Batch transfer complete: {} files sent", files.len());
+    let transfer_id = node.send_file(&file, &peer_id).await?;
+    status!("Transfer started: {}", hex::encode(&transfer_id[..8]));
+    node.wait_for_transfer(transfer_id).await?;
+    status!("Transfer complete");
 
-    // Stop node
     node.stop().await?;
-    println!("Node stopped");
-
     Ok(())
 }
 
-/// Show node status
-async fn show_status(
-    transfer: Option<String>,
-    detailed: bool,
+/// Receive a file using a one-time transfer code
+///
+/// Connects to the sender's address without knowing its public key; the
+/// SPAKE2 exchange over the first stream proves both sides hold the same
+/// code, after which the learned identity is as trustworthy as the code.
+async fn receive_with_code(
+    code: &str,
+    sender: &str,
+    output: PathBuf,
     config: &Config,
 ) -> anyhow::Result<()> {
-    println!("WRAITH Protocol Status");
-    println!("Version: {}", env!("CARGO_PKG_VERSION"));
-    println!("Build: {} edition", env!("CARGO_PKG_RUST_VERSION"));
-    println!();
+    use wraith_core::node::{PakeRole, TransferCode};
 
-    if let Some(transfer_id_str) = transfer {
-        let transfer_id = parse_transfer_id(&transfer_id_str)?;
-        println!("Transfer status query: {}", hex::encode(&transfer_id[..8]));
-        println!();
-        println!("NOTE: Transfer status queries require a running daemon.");
-        println!("Start a daemon with: wraith daemon");
-        println!("Then query transfer status via IPC (future feature)");
-        return Ok(());
+    let code = TransferCode::parse(code).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let sender_addr: std::net::SocketAddr = std::net::ToSocketAddrs::to_socket_addrs(sender)
+        .context("Invalid sender address")?
+        .next()
+        .context("Sender address did not resolve")?;
+
+    if !output.exists() {
+        std::fs::create_dir_all(&output)?;
     }
 
-    // Basic status information
-    println!("Configuration:");
-    println!("  Listen: {}", config.network.listen_addr);
-    println!("  Obfuscation: {}", config.obfuscation.default_level);
-    println!(
-        "  Chunk size: {}",
-        format_bytes(config.transfer.chunk_size as u64)
-    );
-    println!("  Max concurrent: {}", config.transfer.max_concurrent);
-    println!();
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+    node.start().await?;
 
-    println!("Network:");
-    println!("  XDP: {}", config.network.enable_xdp);
-    if config.network.enable_xdp {
-        if let Some(iface) = &config.network.xdp_interface {
-            println!("  XDP interface: {}", iface);
+    status!("Connecting to sender at {sender_addr}...");
+    let (_session_id, peer_id) = node.connect_addr(sender_addr).await?;
+
+    let stream = node.open_stream(&peer_id).await?;
+    pake_exchange(stream, PakeRole::Receiver, &code).await?;
+    status!("Code verified - sender authenticated");
+    status!("Output directory: {}", output.display());
+    status!("Waiting for transfer...");
+
+    // Wait for the transfer the sender starts after verification
+    let node_arc = Arc::new(node);
+    let mut events = node_arc.subscribe();
+    loop {
+        match events.recv().await {
+            Ok(wraith_core::node::NodeEvent::TransferCompleted { transfer_id }) => {
+                status!(
+                    "Transfer {} complete - saved to {}",
+                    hex::encode(&transfer_id[..8]),
+                    output.display()
+                );
+                break;
+            }
+            Ok(_) => {}
+            Err(e) => anyhow::bail!("Event stream closed: {e}"),
         }
     }
-    println!("  UDP fallback: {}", config.network.udp_fallback);
-    println!();
-
-    println!("Discovery:");
-    println!(
-        "  Bootstrap nodes: {}",
-        config.discovery.bootstrap_nodes.len()
-    );
-    println!("  Relay servers: {}", config.discovery.relay_servers.len());
-    println!();
 
-    // Detailed information
-    if detailed {
-        println!("Detailed Configuration:");
-        println!();
+    node_arc.stop().await?;
+    Ok(())
+}
 
-        println!("  Obfuscation:");
-        println!("    Default level: {}", config.obfuscation.default_level);
-        println!("    TLS mimicry: {}", config.obfuscation.tls_mimicry);
-        println!();
+/// Share a file via a one-shot claim link (`wraith://` URL)
+///
+/// Prints a URL carrying this node's address, public key, and a single-use
+/// claim token, then waits for a claimant. The first stream presenting the
+/// right token gets the file; the token is dead afterwards, so a leaked or
+/// logged URL cannot be replayed.
+async fn send_with_link(file: PathBuf, config: &Config) -> anyhow::Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use wraith_core::node::TransferLink;
 
-        println!("  Transfer:");
-        println!(
-            "    Chunk size: {}",
-            format_bytes(config.transfer.chunk_size as u64)
-        );
-        println!("    Max concurrent: {}", config.transfer.max_concurrent);
-        println!("    Enable resume: {}", config.transfer.enable_resume);
-        println!();
+    let file = sanitize_path(&file)?;
+    if !file.exists() {
+        anyhow::bail!("File not found: {file:?}");
+    }
+    let file_size = std::fs::metadata(&file)?.len();
 
-        println!("  Logging:");
-        println!("    Level: {}", config.logging.level);
-        if let Some(file) = &config.logging.file {
-            println!("    File: {}", file.display());
-        }
-        println!();
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+    node.start().await?;
+    let listen_addr = node.listen_addr().await?;
 
-        println!("  Bootstrap Nodes:");
-        for (idx, node) in config.discovery.bootstrap_nodes.iter().enumerate() {
-            println!("    {}: {}", idx + 1, node);
+    let link = TransferLink::new(listen_addr, *node.x25519_public_key());
+    status!("File: {}", file.display());
+    status!("Size: {}", format_bytes(file_size));
+    status!();
+    status!("One-shot link (valid for a single download):");
+    status!("  {link}");
+    if listen_addr.ip().is_unspecified() {
+        status!(
+            "  (replace {} with an address the claimant can reach)",
+            listen_addr.ip()
+        );
+    }
+    status!();
+    status!("Waiting for the link to be claimed...");
+
+    // Accept claim streams until one presents the right token
+    let peer_id = loop {
+        let stream = node
+            .accept_stream()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Node stopped while waiting for a claim"))?;
+        let peer_id = stream.peer_id();
+        let (mut reader, mut writer) = tokio::io::split(stream);
+
+        let mut presented = [0u8; 32];
+        if reader.read_exact(&mut presented).await.is_err() {
+            continue;
         }
-        println!();
-
-        if !config.discovery.relay_servers.is_empty() {
-            println!("  Relay Servers:");
-            for (idx, server) in config.discovery.relay_servers.iter().enumerate() {
-                println!("    {}: {}", idx + 1, server);
-            }
-            println!();
+        if link.verify_token(&presented) {
+            let _ = writer.write_all(&[1u8]).await;
+            writer.shutdown().await.ok();
+            break peer_id;
         }
+        status!(
+            "Rejected claim with a wrong token from {}",
+            hex::encode(&peer_id[..8])
+        );
+        let _ = writer.write_all(&[0u8]).await;
+        writer.shutdown().await.ok();
+    };
 
-        // Platform information
-        println!("Platform:");
-        println!("  OS: {}", std::env::consts::OS);
-        println!("  Architecture: {}", std::env::consts::ARCH);
-        println!("  io_uring support: {}", cfg!(target_os = "linux"));
-        println!();
-    }
-
-    println!("NOTE: Runtime status requires a running daemon.");
-    println!("Start a daemon with: wraith daemon");
-    println!("Then query status via IPC (future feature)");
+    status!("Link claimed by {}", hex::encode(&peer_id[..8]));
+    let transfer_id = node.send_file(&file, &peer_id).await?;
+    status!("Transfer started: {}", hex::encode(&transfer_id[..8]));
+    node.wait_for_transfer(transfer_id).await?;
+    status!("Transfer complete - link is now dead");
 
+    node.stop().await?;
     Ok(())
 }
 
-/// List connected peers
-async fn list_peers(dht_query: Option<String>, config: &Config) -> anyhow::Result<()> {
-    if let Some(peer_id_str) = dht_query {
+/// Claim a one-shot transfer link
+///
+/// Connects to the address in the URL, pins the session to the public key
+/// the URL carries, presents the claim token, and downloads the file with
+/// the usual chunk-level integrity verification.
+async fn get_link(url: &str, output: PathBuf, config: &Config) -> anyhow::Result<()> {
+    let uri = wraith_core::node::WraithUri::parse(url).map_err(|e| anyhow::anyhow!("{e}"))?;
+    let link = uri.claim_link().ok_or_else(|| {
+        anyhow::anyhow!("URI is not claimable: it needs both an address and a claim token")
+    })?;
+    claim_transfer_link(link, output, config).await
+}
+
+/// Connect to a sharer, present the claim token, and receive the file
+async fn claim_transfer_link(
+    link: wraith_core::node::TransferLink,
+    output: PathBuf,
+    config: &Config,
+) -> anyhow::Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    if !output.exists() {
+        std::fs::create_dir_all(&output)?;
+    }
+
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+    node.start().await?;
+
+    status!("Connecting to sharer at {}...", link.addr);
+    node.establish_session_with_addr(&link.peer_id, link.addr)
+        .await?;
+
+    let stream = node.open_stream(&link.peer_id).await?;
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    writer.write_all(link.token()).await?;
+    writer.shutdown().await.ok();
+
+    let mut verdict = [0u8; 1];
+    reader.read_exact(&mut verdict).await?;
+    if verdict[0] != 1 {
+        anyhow::bail!("Claim rejected: the link was already used or is invalid");
+    }
+
+    status!("Claim accepted");
+    status!("Output directory: {}", output.display());
+    status!("Downloading...");
+
+    let node_arc = Arc::new(node);
+    let mut events = node_arc.subscribe();
+    loop {
+        match events.recv().await {
+            Ok(wraith_core::node::NodeEvent::TransferCompleted { transfer_id }) => {
+                status!(
+                    "Transfer {} complete - saved to {}",
+                    hex::encode(&transfer_id[..8]),
+                    output.display()
+                );
+                break;
+            }
+            Ok(_) => {}
+            Err(e) => anyhow::bail!("Event stream closed: {e}"),
+        }
+    }
+
+    node_arc.stop().await?;
+    Ok(())
+}
+
+/// Act on a wraith:// URI delivered by the OS scheme handler
+///
+/// Claimable URIs (address + token) start the claim flow directly; anything
+/// else is parsed and described so the user can decide what to do with it.
+async fn handle_uri(uri: &str, output: PathBuf, config: &Config) -> anyhow::Result<()> {
+    let uri = wraith_core::node::WraithUri::parse(uri).map_err(|e| anyhow::anyhow!("{e}"))?;
+
+    if let Some(link) = uri.claim_link() {
+        return claim_transfer_link(link, output, config).await;
+    }
+
+    status!("Peer: {}", hex::encode(uri.peer_id));
+    if let Some(addr) = uri.addr {
+        status!("Address: {addr}");
+    }
+    if let Some(hash) = uri.content_hash {
+        status!("Content: {}", hex::encode(hash));
+    }
+    for relay in &uri.relays {
+        status!("Relay hint: {relay}");
+    }
+    status!();
+    status!("URI carries no claim token; nothing to fetch automatically.");
+    status!(
+        "Use `wraith send <file> {}` to send to this peer.",
+        hex::encode(uri.peer_id)
+    );
+    Ok(())
+}
+
+/// Register this binary as the OS handler for the wraith:// scheme
+///
+/// Writes a desktop entry with `x-scheme-handler/wraith` and makes it the
+/// default via xdg-mime, so clicked links launch `wraith handle-uri <uri>`.
+fn register_uri_handler() -> anyhow::Result<()> {
+    let exe = std::env::current_exe().context("Failed to resolve the wraith binary path")?;
+    let data_dir = dirs::data_dir().context("Could not determine the XDG data directory")?;
+    let applications = data_dir.join("applications");
+    std::fs::create_dir_all(&applications)?;
+
+    let desktop_file = applications.join("wraith-uri.desktop");
+    let entry = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name=WRAITH URI Handler\n\
+         Exec={} handle-uri %u\n\
+         Terminal=true\n\
+         NoDisplay=true\n\
+         MimeType=x-scheme-handler/wraith;\n",
+        exe.display()
+    );
+    std::fs::write(&desktop_file, entry)
+        .with_context(|| format!("Failed to write {}", desktop_file.display()))?;
+    status!("Wrote {}", desktop_file.display());
+
+    // Best effort: make the entry the default handler and refresh the
+    // desktop database so the registration takes effect immediately
+    match std::process::Command::new("xdg-mime")
+        .args(["default", "wraith-uri.desktop", "x-scheme-handler/wraith"])
+        .status()
+    {
+        Ok(code) if code.success() => status!("Registered as default handler for wraith:// links"),
+        Ok(code) => status!("Warning: xdg-mime exited with {code}; registration may be incomplete"),
+        Err(e) => {
+            status!("Warning: could not run xdg-mime ({e}); set the default handler manually")
+        }
+    }
+    let _ = std::process::Command::new("update-desktop-database")
+        .arg(&applications)
+        .status();
+
+    Ok(())
+}
+
+/// Run the SPAKE2 messages and confirmation tags over a pairing stream
+///
+/// The receiver (stream opener) speaks first; each side sends its 32-byte
+/// PAKE message and a 32-byte confirmation tag, and aborts on mismatch.
+async fn pake_exchange(
+    stream: wraith_core::node::WraithStream,
+    role: wraith_core::node::PakeRole,
+    code: &wraith_core::node::TransferCode,
+) -> anyhow::Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use wraith_core::node::{PakeRole, Spake2};
+
+    let (state, own_message) = Spake2::start(role, code);
+    let (mut reader, mut writer) = tokio::io::split(stream);
+    let mut peer_message = [0u8; 32];
+    let mut peer_tag = [0u8; 32];
+
+    let key = match role {
+        PakeRole::Receiver => {
+            writer.write_all(&own_message).await?;
+            reader.read_exact(&mut peer_message).await?;
+            let key = state
+                .finish(&peer_message)
+                .map_err(|e| anyhow::anyhow!("{e}"))?;
+            reader.read_exact(&mut peer_tag).await?;
+            if !key.verify_confirmation(PakeRole::Sender, &peer_tag) {
+                anyhow::bail!("Transfer code verification failed (wrong code or tampering)");
+            }
+            writer.write_all(&key.confirmation(role)).await?;
+            key
+        }
+        PakeRole::Sender => {
+            reader.read_exact(&mut peer_message).await?;
+            let key = state
+                .finish(&peer_message)
+                .map_err(|e| anyhow::anyhow!("{e}"))?;
+            writer.write_all(&own_message).await?;
+            writer.write_all(&key.confirmation(role)).await?;
+            reader.read_exact(&mut peer_tag).await?;
+            if !key.verify_confirmation(PakeRole::Receiver, &peer_tag) {
+                anyhow::bail!("Transfer code verification failed (wrong code or tampering)");
+            }
+            key
+        }
+    };
+    drop(key);
+    writer.shutdown().await.ok();
+
+    Ok(())
+}
+
+/// Run daemon mode
+async fn run_daemon(
+    _bind: String,
+    relay: bool,
+    harden: bool,
+    config: &Config,
+    config_path: &Path,
+) -> anyhow::Result<()> {
+    // The --relay flag enables the safe trusted-peers mode when the config
+    // file leaves the embedded relay disabled
+    let relay_mode = if relay && config.relay.mode == "disabled" {
+        "trusted-peers".to_string()
+    } else {
+        config.relay.mode.clone()
+    };
+
+    // Create and start node
+    let mut node_config = create_node_config(config);
+    if relay_mode == "trusted-peers" {
+        // The relay ACL is built from the trust store, so it must be loaded
+        node_config.trust.enabled = true;
+    }
+    let node = create_node(node_config, config).await?;
+
+    tracing::info!("Starting WRAITH daemon...");
+    node.start().await?;
+
+    let listen_addr = node.listen_addr().await?;
+
+    status!("WRAITH Daemon");
+    status!("Version: {}", env!("CARGO_PKG_VERSION"));
+    status!();
+    status!("Node ID: {}", hex::encode(node.node_id()));
+    status!("Listening on: {}", listen_addr);
+    status!("XDP: {}", config.network.enable_xdp);
+    if config.network.enable_xdp {
+        if let Some(iface) = &config.network.xdp_interface {
+            status!("XDP interface: {iface}");
+        }
+    }
+    // Monitor sessions and transfers
+    let node_arc = Arc::new(node);
+
+    // Embedded relay (config-gated; --relay enables trusted-peers mode)
+    if relay_mode != "disabled" {
+        start_embedded_relay(&relay_mode, &config.relay.listen_addr, &node_arc).await?;
+        status!();
+    }
+
+    status!();
+    status!("Daemon ready. Press Ctrl+C to stop");
+    status!();
+
+    // Metrics endpoint (config-gated)
+    if config.metrics.enabled {
+        let metrics_addr: std::net::SocketAddr = config
+            .metrics
+            .listen_addr
+            .parse()
+            .context("Invalid metrics listen address")?;
+        let listener = tokio::net::TcpListener::bind(metrics_addr)
+            .await
+            .context("Failed to bind metrics endpoint")?;
+        status!("Metrics: http://{}/metrics", listener.local_addr()?);
+        status!();
+        let metrics_node = Arc::clone(&node_arc);
+        tokio::spawn(serve_metrics(listener, metrics_node));
+    }
+
+    // All sockets are bound and XDP (if enabled) is loaded: nothing from
+    // here on needs privilege, so shed it before handling peer traffic
+    if harden {
+        hardening::apply().context("Failed to apply hardening")?;
+        status!("Hardening: capabilities dropped, seccomp filter installed");
+        status!();
+    }
+
+    let node_clone = Arc::clone(&node_arc);
+
+    tokio::spawn(async move {
+        loop {
+            let sessions = node_clone.active_sessions().await;
+            let transfers = node_clone.active_transfers().await;
+
+            if !sessions.is_empty() || !transfers.is_empty() {
+                status!(
+                    "Status: {} active sessions, {} active transfers",
+                    sessions.len(),
+                    transfers.len()
+                );
+            }
+
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        }
+    });
+
+    // Offline send queue: dispatch spooled transfers as their recipients
+    // become reachable
+    match spool::Spool::open(spool::Spool::default_dir()) {
+        Ok(send_queue) => {
+            if let Ok(entries) = send_queue.list()
+                && !entries.is_empty()
+            {
+                status!("Send queue: {} spooled transfer(s)", entries.len());
+                status!();
+            }
+            let spool_node = Arc::clone(&node_arc);
+            tokio::spawn(dispatch_spool(Arc::new(send_queue), spool_node));
+        }
+        Err(e) => tracing::warn!("Offline send queue unavailable: {e}"),
+    }
+
+    // Config hot-reload on SIGHUP: re-read config.toml and apply what the
+    // node can absorb without restarting (see Node::apply_config_update)
+    #[cfg(unix)]
+    {
+        let reload_node = Arc::clone(&node_arc);
+        let reload_path = config_path.to_path_buf();
+        tokio::spawn(async move {
+            let mut hangup =
+                match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                    Ok(stream) => stream,
+                    Err(e) => {
+                        tracing::warn!("SIGHUP handler unavailable: {e}");
+                        return;
+                    }
+                };
+            while hangup.recv().await.is_some() {
+                reload_daemon_config(&reload_node, &reload_path);
+            }
+        });
+        status!(
+            "Reload: kill -HUP {} re-reads the config file",
+            std::process::id()
+        );
+        status!();
+    }
+
+    // Keep alive until Ctrl+C
+    tokio::signal::ctrl_c().await?;
+    status!("\nShutting down...");
+
+    node_arc.stop().await?;
+    status!("Daemon stopped");
+
+    Ok(())
+}
+
+/// Re-read the config file and hot-apply what the running node accepts
+///
+/// Called from the daemon's SIGHUP handler. The logging level is swapped
+/// in the tracing subscriber; everything else goes through
+/// [`Node::apply_config_update`], which reports per field whether the
+/// change took effect live or needs a restart. Sessions and transfers are
+/// never dropped. A config file that fails to parse or validate leaves
+/// the running configuration untouched.
+fn reload_daemon_config(node: &Node, config_path: &Path) {
+    status!("SIGHUP: reloading {}", config_path.display());
+
+    let mut new_config = match Config::load(config_path) {
+        Ok(config) => config,
+        Err(e) => {
+            tracing::warn!("Config reload failed, keeping current settings: {e:#}");
+            return;
+        }
+    };
+    if let Err(e) = new_config.validate() {
+        tracing::warn!("Reloaded config invalid, keeping current settings: {e:#}");
+        return;
+    }
+    if let Some(name) = profile::select_profile(&new_config)
+        && let Err(e) = new_config.apply_profile(&name)
+    {
+        tracing::warn!("Profile '{name}' failed to apply on reload: {e:#}");
+        return;
+    }
+
+    if set_log_level(&new_config.logging.level) {
+        status!("  log level: {}", new_config.logging.level);
+    }
+
+    let report = node.apply_config_update(&create_node_config(&new_config));
+    for field in &report.applied {
+        status!("  applied: {field}");
+    }
+    for field in &report.restart_required {
+        status!("  restart required: {field}");
+    }
+    if report.is_noop() {
+        status!("  no node-level changes");
+    }
+}
+
+/// How often the daemon scans the spool for dispatchable transfers
+const SPOOL_DISPATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Dispatch spooled transfers as their recipients become reachable
+///
+/// Every [`SPOOL_DISPATCH_INTERVAL`] the daemon walks the send queue:
+/// entries whose schedule allows running now and whose peer has an active
+/// session or answers a discovery lookup are sent and removed once the
+/// transfer completes; the rest stay queued for the next pass. Scheduled
+/// entries run under the bandwidth cap their schedule prescribes for the
+/// current hour, re-applied as the clock crosses window boundaries.
+/// Entries whose source file has disappeared are dropped with a warning.
+async fn dispatch_spool(send_queue: Arc<spool::Spool>, node: Arc<Node>) {
+    loop {
+        tokio::time::sleep(SPOOL_DISPATCH_INTERVAL).await;
+
+        let entries = match send_queue.list() {
+            Ok(entries) => entries,
+            Err(e) => {
+                tracing::warn!("Failed to read send queue: {e}");
+                continue;
+            }
+        };
+
+        for (id, entry) in entries {
+            if !entry.file_path.exists() {
+                tracing::warn!(
+                    "Dropping queued transfer {id}: source file {} no longer exists",
+                    entry.file_path.display()
+                );
+                let _ = send_queue.remove(&id);
+                continue;
+            }
+
+            // Schedules are written in local time; outside the allowed
+            // hours the entry just waits for a later pass
+            if let Some(schedule) = &entry.schedule
+                && !schedule.may_run(spool::now_secs(), spool::local_minute_of_day())
+            {
+                continue;
+            }
+
+            // A connected peer is dispatchable right away; otherwise ask
+            // discovery whether it has appeared
+            let connected = node.active_sessions().await.contains(&entry.peer_id);
+            if !connected
+                && node
+                    .discover_peer(&entry.peer_id)
+                    .await
+                    .map(|addrs| addrs.is_empty())
+                    .unwrap_or(true)
+            {
+                continue;
+            }
+
+            match node.send_file(&entry.file_path, &entry.peer_id).await {
+                Ok(transfer_id) => {
+                    apply_schedule_limit(&node, &transfer_id, &entry);
+                    status!(
+                        "Dispatching queued transfer {id} to {}",
+                        hex::encode(&entry.peer_id[..8])
+                    );
+
+                    // Re-apply the time-appropriate cap while waiting so a
+                    // transfer spilling out of its window drops to the
+                    // peak rate instead of keeping off-peak speed
+                    let wait = node.wait_for_transfer(transfer_id);
+                    tokio::pin!(wait);
+                    let result = loop {
+                        tokio::select! {
+                            result = &mut wait => break result,
+                            () = tokio::time::sleep(SPOOL_DISPATCH_INTERVAL) => {
+                                apply_schedule_limit(&node, &transfer_id, &entry);
+                            }
+                        }
+                    };
+
+                    if result.is_ok() {
+                        status!("Queued transfer {id} complete");
+                        if let Err(e) = send_queue.remove(&id) {
+                            tracing::warn!("Failed to remove dispatched spool entry {id}: {e}");
+                        }
+                    } else {
+                        tracing::warn!("Queued transfer {id} failed; will retry");
+                    }
+                }
+                Err(e) => {
+                    tracing::debug!("Queued transfer {id} not dispatchable yet: {e}");
+                }
+            }
+        }
+    }
+}
+
+/// Apply the bandwidth cap an entry's schedule prescribes right now
+///
+/// Falls back to the entry's flat limit for unscheduled queue entries.
+fn apply_schedule_limit(node: &Node, transfer_id: &TransferId, entry: &spool::SpoolEntry) {
+    let limit = match &entry.schedule {
+        Some(schedule) => schedule.limit_at(spool::local_minute_of_day()),
+        None => entry.limit_bps,
+    };
+    if let Some(limit) = limit {
+        node.set_transfer_bandwidth_limit(transfer_id, limit);
+    }
+}
+
+/// Handle `wraith queue list`
+fn queue_list() -> anyhow::Result<()> {
+    let send_queue = spool::Spool::open(spool::Spool::default_dir())?;
+    let entries = send_queue.list()?;
+
+    if entries.is_empty() {
+        status!("Send queue is empty");
+        return Ok(());
+    }
+
+    status!("Queued transfers: {}", entries.len());
+    status!();
+    for (id, entry) in entries {
+        let size = std::fs::metadata(&entry.file_path)
+            .map(|m| format_bytes(m.len()))
+            .unwrap_or_else(|_| "missing".to_string());
+        status!(
+            "  {id}  {} ({size})  ->  {}",
+            entry.file_path.display(),
+            hex::encode(&entry.peer_id[..8])
+        );
+        if let Some(limit) = entry.limit_bps {
+            status!("        limit: {}/s", format_bytes(limit));
+        }
+        if let Some(schedule) = &entry.schedule {
+            if let Some(start) = schedule.start_at {
+                let wait = start.saturating_sub(spool::now_secs()) / 60;
+                if wait > 0 {
+                    status!("        starts in: ~{wait} min");
+                }
+            }
+            for window in &schedule.windows {
+                status!("        window: {window} (local time)");
+            }
+            if let Some(peak) = schedule.peak_limit_bps {
+                status!("        peak limit: {}/s", format_bytes(peak));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handle `wraith queue remove`
+fn queue_remove(id: &str) -> anyhow::Result<()> {
+    let send_queue = spool::Spool::open(spool::Spool::default_dir())?;
+    if send_queue.remove(id)? {
+        status!("Removed queued transfer {id}");
+        Ok(())
+    } else {
+        anyhow::bail!("No queued transfer with ID {id}")
+    }
+}
+
+/// Start the embedded relay alongside the daemon
+///
+/// In `trusted-peers` mode the registration ACL is built from the node's
+/// trust store and refreshed periodically, so pinning or unpinning a peer
+/// takes effect without restarting the daemon.
+async fn start_embedded_relay(
+    mode: &str,
+    listen_addr: &str,
+    node: &Arc<Node>,
+) -> anyhow::Result<()> {
+    use wraith_discovery::relay::{RelayServer, RelayServerConfig};
+
+    let bind_addr: std::net::SocketAddr = listen_addr
+        .parse()
+        .context("Invalid relay listen address")?;
+
+    let allowed_clients = if mode == "trusted-peers" {
+        let trust = node
+            .trust_store()
+            .context("Relay mode 'trusted-peers' requires the trust store")?;
+        let allowed: HashSet<[u8; 32]> = trust
+            .entries()
+            .await
+            .iter()
+            .map(|entry| entry.peer_id)
+            .collect();
+        Some(allowed)
+    } else {
+        None
+    };
+
+    let relay_config = RelayServerConfig {
+        allowed_clients: allowed_clients.clone(),
+        ..RelayServerConfig::default()
+    };
+    let server = Arc::new(
+        RelayServer::bind_with_config(bind_addr, relay_config)
+            .await
+            .context("Failed to start embedded relay")?,
+    );
+    let relay_addr = server.local_addr()?;
+
+    status!("Embedded relay: {relay_addr} (mode: {mode})");
+    if let Some(allowed) = &allowed_clients {
+        status!("Relay ACL: {} trusted peer(s)", allowed.len());
+    }
+
+    let run_server = Arc::clone(&server);
+    tokio::spawn(async move {
+        if let Err(e) = run_server.run().await {
+            tracing::error!("Embedded relay stopped: {e}");
+        }
+    });
+
+    // Track trust store changes so the ACL follows pin/unpin
+    if mode == "trusted-peers"
+        && let Some(trust) = node.trust_store()
+    {
+        let acl_server = Arc::clone(&server);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                let allowed: HashSet<[u8; 32]> = trust
+                    .entries()
+                    .await
+                    .iter()
+                    .map(|entry| entry.peer_id)
+                    .collect();
+                acl_server.set_allowed_clients(Some(allowed)).await;
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Send batch of files
+/// Handle `wraith send --dry-run`
+///
+/// Validates the file and recipients and prints the transfer plan without
+/// starting a node.
+fn dry_run_send_command(
+    file: String,
+    recipient: Vec<String>,
+    to: Vec<String>,
+    mode: &str,
+    limit: Option<String>,
+    config: &Config,
+) -> anyhow::Result<()> {
+    if file == "-" {
+        anyhow::bail!("--dry-run cannot size a stream from stdin");
+    }
+
+    let path = sanitize_path(&PathBuf::from(file))?;
+    if !path.exists() {
+        anyhow::bail!("File not found: {path:?}");
+    }
+    let metadata = std::fs::metadata(&path)?;
+    if !metadata.is_file() {
+        anyhow::bail!("Not a file: {path:?}");
+    }
+
+    // Validate recipients exactly as a real send would
+    let mut recipients = recipient;
+    recipients.extend(to);
+    for recipient in &recipients {
+        parse_peer_id(recipient)?;
+    }
+
+    // Resolve bandwidth limit: --limit flag overrides the config default
+    let limit_str = limit.or_else(|| config.transfer.bandwidth_limit.clone());
+    let limit_bps = match &limit_str {
+        Some(s) => Some(wraith_core::node::bandwidth::parse_rate(s).ok_or_else(|| {
+            anyhow::anyhow!("Invalid bandwidth limit: {s:?} (expected e.g. \"10MB/s\")")
+        })?),
+        None => None,
+    };
+
+    dry_run_report(
+        &[(path, metadata.len())],
+        recipients.len().max(1),
+        mode,
+        limit_bps,
+        config,
+    )
+}
+
+/// Handle `wraith batch --dry-run`
+fn dry_run_batch_command(
+    files: Vec<String>,
+    recipient: &str,
+    mode: &str,
+    config: &Config,
+) -> anyhow::Result<()> {
+    parse_peer_id(recipient)?;
+
+    // Validate and sanitize all file paths exactly as a real batch would
+    let mut sanitized_files = Vec::new();
+    for file_path_str in &files {
+        let file_path = PathBuf::from(file_path_str);
+        let sanitized = sanitize_path(&file_path)?;
+
+        if !sanitized.exists() {
+            anyhow::bail!("File not found: {file_path:?}");
+        }
+        let metadata = std::fs::metadata(&sanitized)?;
+        if !metadata.is_file() {
+            anyhow::bail!("Not a file: {file_path:?}");
+        }
+
+        let size = metadata.len();
+        sanitized_files.push((sanitized, size));
+    }
+
+    let limit_bps = config
+        .transfer
+        .bandwidth_limit
+        .as_ref()
+        .and_then(|s| wraith_core::node::bandwidth::parse_rate(s));
+
+    dry_run_report(&sanitized_files, 1, mode, limit_bps, config)
+}
+
+/// Print what a send would transfer without moving any data
+///
+/// Computes the manifest for each file exactly as the real send would
+/// (same chunk size, same BLAKE3 tree hash) and reports per-file chunk
+/// counts and root hashes, the policy decisions that would apply, and the
+/// estimated duration at the effective bandwidth limit. No node is
+/// started and no packets leave the machine.
+fn dry_run_report(
+    files: &[(PathBuf, u64)],
+    num_recipients: usize,
+    mode: &str,
+    limit_bps: Option<u64>,
+    config: &Config,
+) -> anyhow::Result<()> {
+    use anyhow::Context;
+
+    status!("Dry run: no data will be transferred");
+    status!();
+
+    let chunk_size = config.transfer.chunk_size;
+    let mut total_size = 0u64;
+    let mut total_chunks = 0u64;
+
+    for (file, size) in files {
+        let tree_hash = wraith_files::tree_hash::compute_tree_hash(file, chunk_size)
+            .with_context(|| format!("Failed to hash {}", file.display()))?;
+
+        total_size += size;
+        total_chunks += tree_hash.chunks.len() as u64;
+
+        status!("{}", file.display());
+        status!("  Size ........ {}", format_bytes(*size));
+        status!(
+            "  Chunks ...... {} x {}",
+            tree_hash.chunks.len(),
+            format_bytes(chunk_size as u64)
+        );
+        status!("  Root hash ... {}", hex::encode(tree_hash.root));
+    }
+
+    status!();
+    status!("Policy");
+    status!("  Obfuscation mode ... {}", mode);
+    status!("  Chunk size ......... {}", format_bytes(chunk_size as u64));
+    match limit_bps {
+        Some(bps) => status!("  Bandwidth limit .... {}/s", format_bytes(bps)),
+        None => status!("  Bandwidth limit .... unlimited"),
+    }
+
+    status!();
+    status!(
+        "Would send {} file(s), {} ({} chunks) to {} recipient(s)",
+        files.len(),
+        format_bytes(total_size),
+        total_chunks,
+        num_recipients
+    );
+
+    // Total bytes scale with the recipient count: each recipient gets the
+    // whole payload
+    let wire_bytes = total_size.saturating_mul(num_recipients as u64);
+    if let Some(bps) = limit_bps.filter(|bps| *bps > 0) {
+        let estimate = Duration::from_secs_f64(wire_bytes as f64 / bps as f64);
+        status!(
+            "Estimated duration: {} at {}/s",
+            format_duration(estimate),
+            format_bytes(bps)
+        );
+    } else {
+        status!("Estimated duration: unknown (no bandwidth limit to estimate against)");
+    }
+
+    Ok(())
+}
+
+async fn send_batch(
+    files: Vec<String>,
+    recipient: String,
+    _mode: String,
+    config: &Config,
+) -> anyhow::Result<()> {
+    // Parse peer ID
+    let peer_id = parse_peer_id(&recipient)?;
+
+    status!("Batch Transfer");
+    status!("Files: {}", files.len());
+    status!("Recipient: {}", hex::encode(&peer_id[..8]));
+    status!();
+
+    // Validate and sanitize all file paths
+    let mut total_size = 0u64;
+    let mut sanitized_files = Vec::new();
+
+    for file_path_str in &files {
+        let file_path = PathBuf::from(file_path_str);
+        let sanitized = sanitize_path(&file_path)?;
+
+        if !sanitized.exists() {
+            anyhow::bail!("File not found: {file_path:?}");
+        }
+
+        let metadata = std::fs::metadata(&sanitized)?;
+        if !metadata.is_file() {
+            anyhow::bail!("Not a file: {file_path:?}");
+        }
+
+        total_size += metadata.len();
+        sanitized_files.push((sanitized, metadata.len()));
+    }
+
+    status!("Total size: {}", format_bytes(total_size));
+    status!();
+
+    // Create and start node
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+
+    tracing::info!("Starting node...");
+    node.start().await?;
+
+    let listen_addr = node.listen_addr().await?;
+    status!("Node started: {}", hex::encode(node.node_id()));
+    status!("Listening on: {}", listen_addr);
+    status!();
+
+    // Send each file: one aggregate bar plus one bar per file
+    let mut progress = MultiTransferProgress::new(total_size);
+    let bar_indices: Vec<usize> = sanitized_files
+        .iter()
+        .map(|(file_path, file_size)| {
+            let filename = file_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("unknown");
+            progress.add_transfer(filename, *file_size)
+        })
+        .collect();
+
+    let mut batch_sent = 0u64;
+    for (idx, (file_path, file_size)) in sanitized_files.iter().enumerate() {
+        // Send file using Node API
+        let transfer_id = node.send_file(file_path, &peer_id).await?;
+        tracing::info!("Transfer started: {}", hex::encode(&transfer_id[..8]));
+
+        // Drive the bars from node-reported progress until completion
+        loop {
+            let Some(transfer_progress) = node.get_transfer_progress(&transfer_id).await else {
+                anyhow::bail!("Transfer {} disappeared", hex::encode(&transfer_id[..8]));
+            };
+
+            match transfer_progress.status {
+                wraith_core::node::progress::TransferStatus::Complete => {
+                    batch_sent += file_size;
+                    progress.finish_transfer(
+                        bar_indices[idx],
+                        format!("complete ({})", format_bytes(*file_size)),
+                    );
+                    progress.update_aggregate(batch_sent);
+                    break;
+                }
+                wraith_core::node::progress::TransferStatus::Failed => {
+                    progress.finish_transfer(bar_indices[idx], "failed".to_string());
+                    anyhow::bail!("Transfer failed: {}", hex::encode(&transfer_id[..8]));
+                }
+                _ => {
+                    progress.update_transfer(
+                        bar_indices[idx],
+                        transfer_progress.bytes_sent,
+                        transfer_progress.speed_bytes_per_sec,
+                        transfer_progress.eta,
+                    );
+                    progress.update_aggregate(batch_sent + transfer_progress.bytes_sent);
+                }
+            }
+
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    progress.finish(format!(
+        "Batch transfer complete: {} files sent",
+        files.len()
+    ));
+
+    // Stop node
+    node.stop().await?;
+    status!("Node stopped");
+
+    Ok(())
+}
+
+/// Show node status
+async fn show_status(
+    transfer: Option<String>,
+    detailed: bool,
+    config: &Config,
+) -> anyhow::Result<()> {
+    status!("WRAITH Protocol Status");
+    status!("Version: {}", env!("CARGO_PKG_VERSION"));
+    status!("Build: {} edition", env!("CARGO_PKG_RUST_VERSION"));
+    status!();
+
+    if let Some(transfer_id_str) = transfer {
+        let transfer_id = parse_transfer_id(&transfer_id_str)?;
+        status!("Transfer status query: {}", hex::encode(&transfer_id[..8]));
+        status!();
+        status!("NOTE: Transfer status queries require a running daemon.");
+        status!("Start a daemon with: wraith daemon");
+        status!("Then query transfer status via IPC (future feature)");
+        return Ok(());
+    }
+
+    // Basic status information
+    status!("Configuration:");
+    status!("  Listen: {}", config.network.listen_addr);
+    status!("  Obfuscation: {}", config.obfuscation.default_level);
+    status!(
+        "  Chunk size: {}",
+        format_bytes(config.transfer.chunk_size as u64)
+    );
+    status!("  Max concurrent: {}", config.transfer.max_concurrent);
+    status!();
+
+    status!("Network:");
+    status!("  XDP: {}", config.network.enable_xdp);
+    if config.network.enable_xdp {
+        if let Some(iface) = &config.network.xdp_interface {
+            status!("  XDP interface: {}", iface);
+        }
+    }
+    status!("  UDP fallback: {}", config.network.udp_fallback);
+    status!();
+
+    status!("Discovery:");
+    status!(
+        "  Bootstrap nodes: {}",
+        config.discovery.bootstrap_nodes.len()
+    );
+    status!("  Relay servers: {}", config.discovery.relay_servers.len());
+    status!();
+
+    // Detailed information
+    if detailed {
+        status!("Detailed Configuration:");
+        status!();
+
+        status!("  Obfuscation:");
+        status!("    Default level: {}", config.obfuscation.default_level);
+        status!("    TLS mimicry: {}", config.obfuscation.tls_mimicry);
+        status!();
+
+        status!("  Transfer:");
+        status!(
+            "    Chunk size: {}",
+            format_bytes(config.transfer.chunk_size as u64)
+        );
+        status!("    Max concurrent: {}", config.transfer.max_concurrent);
+        status!("    Enable resume: {}", config.transfer.enable_resume);
+        status!();
+
+        status!("  Logging:");
+        status!("    Level: {}", config.logging.level);
+        if let Some(file) = &config.logging.file {
+            status!("    File: {}", file.display());
+        }
+        status!();
+
+        status!("  Bootstrap Nodes:");
+        for (idx, node) in config.discovery.bootstrap_nodes.iter().enumerate() {
+            status!("    {}: {}", idx + 1, node);
+        }
+        status!();
+
+        if !config.discovery.relay_servers.is_empty() {
+            status!("  Relay Servers:");
+            for (idx, server) in config.discovery.relay_servers.iter().enumerate() {
+                status!("    {}: {}", idx + 1, server);
+            }
+            status!();
+        }
+
+        // Platform information
+        status!("Platform:");
+        status!("  OS: {}", std::env::consts::OS);
+        status!("  Architecture: {}", std::env::consts::ARCH);
+        status!("  io_uring support: {}", cfg!(target_os = "linux"));
+        status!();
+    }
+
+    status!("NOTE: Runtime status requires a running daemon.");
+    status!("Start a daemon with: wraith daemon");
+    status!("Then query status via IPC (future feature)");
+
+    Ok(())
+}
+
+/// List connected peers
+async fn list_peers(dht_query: Option<String>, config: &Config) -> anyhow::Result<()> {
+    if let Some(peer_id_str) = dht_query {
         let peer_id = parse_peer_id(&peer_id_str)?;
 
-        println!("DHT Peer Query");
-        println!("Peer ID: {}", hex::encode(peer_id));
-        println!();
+        status!("DHT Peer Query");
+        status!("Peer ID: {}", hex::encode(peer_id));
+        status!();
 
         // Create temporary node for DHT query
         let node_config = create_node_config(config);
-        let node = Node::new_with_config(node_config).await?;
+        let node = create_node(node_config, config).await?;
 
-        println!("Starting node for DHT query...");
+        status!("Starting node for DHT query...");
         node.start().await?;
 
         let listen_addr = node.listen_addr().await?;
-        println!("Node started: {}", hex::encode(node.node_id()));
-        println!("Listening on: {}", listen_addr);
-        println!();
+        status!("Node started: {}", hex::encode(node.node_id()));
+        status!("Listening on: {}", listen_addr);
+        status!();
 
-        println!("Discovering peer via DHT...");
+        status!("Discovering peer via DHT...");
         match node.discover_peer(&peer_id).await {
             Ok(addrs) => {
-                println!();
-                println!("Peer found successfully!");
-                println!();
-                println!("Details:");
-                println!("  Peer ID: {}", hex::encode(peer_id));
-                println!("  Addresses: {}", addrs.len());
+                status!();
+                status!("Peer found successfully!");
+                status!();
+                status!("Details:");
+                status!("  Peer ID: {}", hex::encode(peer_id));
+                status!("  Addresses: {}", addrs.len());
                 for (idx, addr) in addrs.iter().enumerate() {
-                    println!("    {}: {}", idx + 1, addr);
+                    status!("    {}: {}", idx + 1, addr);
                 }
-                println!();
+                status!();
             }
             Err(e) => {
-                println!();
-                println!("Peer discovery failed: {}", e);
-                println!();
-                println!("Possible reasons:");
-                println!("  - Peer is not online");
-                println!("  - Peer ID is invalid");
-                println!("  - DHT network is not reachable");
-                println!("  - Bootstrap nodes are offline");
-                println!();
+                status!();
+                status!("Peer discovery failed: {}", e);
+                status!();
+                status!("Possible reasons:");
+                status!("  - Peer is not online");
+                status!("  - Peer ID is invalid");
+                status!("  - DHT network is not reachable");
+                status!("  - Bootstrap nodes are offline");
+                status!();
             }
         }
 
-        println!("Stopping node...");
+        status!("Stopping node...");
         node.stop().await?;
-        println!("Node stopped");
+        status!("Node stopped");
 
         return Ok(());
     }
 
     // List mode (no DHT query)
-    println!("Connected Peers");
-    println!("Version: {}", env!("CARGO_PKG_VERSION"));
-    println!();
+    status!("Connected Peers");
+    status!("Version: {}", env!("CARGO_PKG_VERSION"));
+    status!();
 
-    println!("Discovery Configuration:");
-    println!(
+    status!("Discovery Configuration:");
+    status!(
         "  Bootstrap nodes: {}",
         config.discovery.bootstrap_nodes.len()
     );
     if !config.discovery.bootstrap_nodes.is_empty() {
         for (idx, node) in config.discovery.bootstrap_nodes.iter().enumerate() {
-            println!("    {}: {}", idx + 1, node);
+            status!("    {}: {}", idx + 1, node);
         }
     }
-    println!();
+    status!();
 
-    println!("  Relay servers: {}", config.discovery.relay_servers.len());
+    status!("  Relay servers: {}", config.discovery.relay_servers.len());
     if !config.discovery.relay_servers.is_empty() {
         for (idx, server) in config.discovery.relay_servers.iter().enumerate() {
-            println!("    {}: {}", idx + 1, server);
+            status!("    {}: {}", idx + 1, server);
         }
     }
-    println!();
+    status!();
 
-    println!("NOTE: Listing active peers requires a running daemon.");
-    println!("Start a daemon with: wraith daemon");
-    println!("Then query peer list via IPC (future feature)");
-    println!();
-    println!("To query a specific peer via DHT, use:");
-    println!("  wraith peers --dht-query <peer-id>");
+    status!("NOTE: Listing active peers requires a running daemon.");
+    status!("Start a daemon with: wraith daemon");
+    status!("Then query peer list via IPC (future feature)");
+    status!();
+    status!("To query a specific peer via DHT, use:");
+    status!("  wraith peers --dht-query <peer-id>");
+
+    Ok(())
+}
+
+/// Search a private group's content index by name
+async fn search_group_content(
+    query: String,
+    group_secret_hex: String,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let secret_bytes = hex::decode(group_secret_hex.trim())
+        .context("Group secret must be hex-encoded (64 hex chars)")?;
+    let secret_bytes: [u8; 32] = secret_bytes
+        .try_into()
+        .map_err(|_| anyhow::anyhow!("Group secret must be exactly 32 bytes (64 hex chars)"))?;
+    let group_secret = wraith_discovery::dht::GroupSecret::new(secret_bytes);
+
+    status!("Content Search");
+    status!("Query: {}", query);
+    status!();
+
+    // Create temporary node for the DHT search
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+
+    status!("Starting node for content search...");
+    node.start().await?;
+
+    let results = node.search_content(&group_secret, &query).await?;
+
+    if results.is_empty() {
+        status!();
+        status!("No matching content found.");
+        status!();
+        status!("Possible reasons:");
+        status!("  - Nothing matching has been announced in this group");
+        status!("  - The group secret does not match the publisher's");
+        status!("  - The DHT network is not reachable");
+    } else {
+        status!();
+        status!("Found {} result(s):", results.len());
+        status!();
+        for (idx, record) in results.iter().enumerate() {
+            let announcement = &record.announcement;
+            status!("  {}: {}", idx + 1, announcement.name);
+            status!("     Hash: {}", hex::encode(announcement.content_hash));
+            status!("     Size: {}", format_bytes(announcement.size));
+            if !announcement.description.is_empty() {
+                status!("     Description: {}", announcement.description);
+            }
+            status!(
+                "     Publisher: {} (signature verified)",
+                hex::encode(announcement.publisher)
+            );
+            status!();
+        }
+    }
+
+    status!("Stopping node...");
+    node.stop().await?;
+    status!("Node stopped");
 
     Ok(())
 }
 
 /// Show node health
 async fn show_health(config: &Config) -> anyhow::Result<()> {
-    println!("WRAITH Node Health Check");
-    println!("Version: {}", env!("CARGO_PKG_VERSION"));
-    println!();
+    status!("WRAITH Node Health Check");
+    status!("Version: {}", env!("CARGO_PKG_VERSION"));
+    status!();
 
     // Configuration health
-    println!("Configuration:");
-    println!("  Config file: OK");
-    println!(
+    status!("Configuration:");
+    status!("  Config file: OK");
+    status!(
         "  Listen address: {} ({})",
         config.network.listen_addr,
         if config.network.listen_addr.starts_with("0.0.0.0") {
@@ -1116,116 +3272,355 @@ async fn show_health(config: &Config) -> anyhow::Result<()> {
             "specific interface"
         }
     );
-    println!("  XDP: {} ", config.network.enable_xdp);
+    status!("  XDP: {} ", config.network.enable_xdp);
     if config.network.enable_xdp {
         if let Some(iface) = &config.network.xdp_interface {
-            println!("  XDP interface: {}", iface);
+            status!("  XDP interface: {}", iface);
         }
     }
-    println!();
+    status!();
 
     // Test node creation
-    println!("Node Creation:");
+    status!("Node Creation:");
     match Node::new_random().await {
         Ok(_node) => {
-            println!("  Identity generation: OK");
-            println!("  Node initialization: OK");
+            status!("  Identity generation: OK");
+            status!("  Node initialization: OK");
         }
         Err(e) => {
-            println!("  Node creation: FAILED - {}", e);
+            status!("  Node creation: FAILED - {}", e);
             return Ok(());
         }
     }
-    println!();
+    status!();
 
     // Discovery health
-    println!("Discovery:");
-    println!(
+    status!("Discovery:");
+    status!(
         "  Bootstrap nodes: {} configured",
         config.discovery.bootstrap_nodes.len()
     );
-    println!(
+    status!(
         "  Relay servers: {} configured",
         config.discovery.relay_servers.len()
     );
-    println!();
+    status!();
 
-    println!("Overall Health: OK");
-    println!();
-    println!("NOTE: For runtime health metrics, start a daemon with: wraith daemon");
+    status!("Overall Health: OK");
+    status!();
+    status!("NOTE: For runtime health metrics, start a daemon with: wraith daemon");
 
     Ok(())
 }
 
+/// Serve node metrics over HTTP
+///
+/// Minimal HTTP/1.1 server backing the config-gated daemon metrics
+/// endpoint: `GET /metrics` returns Prometheus text exposition format,
+/// `GET /metrics.json` the same snapshot as JSON. Both are rendered from
+/// [`Node::metrics_snapshot`], so scrapes and `wraith metrics` agree.
+async fn serve_metrics(listener: tokio::net::TcpListener, node: Arc<Node>) {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    loop {
+        let Ok((mut stream, _)) = listener.accept().await else {
+            continue;
+        };
+        let node = Arc::clone(&node);
+        tokio::spawn(async move {
+            let mut buf = [0u8; 1024];
+            let Ok(n) = stream.read(&mut buf).await else {
+                return;
+            };
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let path = request.split_whitespace().nth(1).unwrap_or("");
+
+            let (code, content_type, body) = match path {
+                "/metrics" => (
+                    "200 OK",
+                    "text/plain; version=0.0.4",
+                    node.metrics_snapshot().await.to_prometheus(),
+                ),
+                "/metrics.json" => {
+                    match serde_json::to_string_pretty(&node.metrics_snapshot().await) {
+                        Ok(json) => ("200 OK", "application/json", json),
+                        Err(_) => ("500 Internal Server Error", "text/plain", String::new()),
+                    }
+                }
+                _ => ("404 Not Found", "text/plain", "not found\n".to_string()),
+            };
+            let response = format!(
+                "HTTP/1.1 {code}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+/// Fetch the JSON metrics snapshot from a running daemon
+async fn fetch_daemon_metrics(endpoint: &str) -> anyhow::Result<String> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let request =
+        format!("GET /metrics.json HTTP/1.1\r\nHost: {endpoint}\r\nConnection: close\r\n\r\n");
+    let response = tokio::time::timeout(Duration::from_secs(2), async {
+        let mut stream = tokio::net::TcpStream::connect(endpoint).await?;
+        stream.write_all(request.as_bytes()).await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok::<String, std::io::Error>(response)
+    })
+    .await
+    .context("Metrics query timed out")??;
+
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .context("Malformed metrics response")?;
+    anyhow::ensure!(
+        head.starts_with("HTTP/1.1 200"),
+        "Metrics endpoint returned: {}",
+        head.lines().next().unwrap_or("unknown status")
+    );
+    Ok(body.to_string())
+}
+
 /// Show metrics
+///
+/// Queries the metrics endpoint of a running daemon (see `[metrics]` in
+/// the config file) and renders its live snapshot. Falls back to static
+/// configuration facts when no daemon is reachable.
 async fn show_metrics(json: bool, _watch: Option<u64>, config: &Config) -> anyhow::Result<()> {
+    if let Ok(body) = fetch_daemon_metrics(&config.metrics.listen_addr).await {
+        if json {
+            status!("{}", body.trim_end());
+            return Ok(());
+        }
+
+        let metrics: serde_json::Value = serde_json::from_str(&body)?;
+        status!("WRAITH Metrics");
+        status!(
+            "Version: {}",
+            metrics["version"].as_str().unwrap_or("unknown")
+        );
+        status!(
+            "Node: {}",
+            if metrics["running"].as_bool() == Some(true) {
+                "running"
+            } else {
+                "stopped"
+            }
+        );
+        status!();
+
+        let sessions = metrics["sessions"].as_array().cloned().unwrap_or_default();
+        status!("Sessions: {}", sessions.len());
+        for s in &sessions {
+            let peer = s["peer_id"].as_str().unwrap_or("?");
+            let rtt = s["rtt_us"].as_u64().map_or("n/a".to_string(), |us| {
+                format!("{:.1} ms", us as f64 / 1000.0)
+            });
+            status!(
+                "  {}...: rtt {}, {} sent / {} received",
+                &peer[..peer.len().min(16)],
+                rtt,
+                format_bytes(s["bytes_sent"].as_u64().unwrap_or(0)),
+                format_bytes(s["bytes_received"].as_u64().unwrap_or(0))
+            );
+        }
+
+        let transfers = metrics["transfers"].as_array().cloned().unwrap_or_default();
+        status!("Transfers: {}", transfers.len());
+        for t in &transfers {
+            let id = t["transfer_id"].as_str().unwrap_or("?");
+            status!(
+                "  {}...: {} / {} ({}/s)",
+                &id[..id.len().min(16)],
+                format_bytes(t["bytes_sent"].as_u64().unwrap_or(0)),
+                format_bytes(t["bytes_total"].as_u64().unwrap_or(0)),
+                format_bytes(t["throughput_bytes_per_sec"].as_f64().unwrap_or(0.0) as u64)
+            );
+        }
+        status!();
+
+        status!(
+            "Frames: {} sent, {} received, {} retransmitted",
+            metrics["frames"]["sent"].as_u64().unwrap_or(0),
+            metrics["frames"]["received"].as_u64().unwrap_or(0),
+            metrics["frames"]["retransmitted"].as_u64().unwrap_or(0)
+        );
+        status!(
+            "Rekeys: {} sent, {} received",
+            metrics["frames"]["rekeys_sent"].as_u64().unwrap_or(0),
+            metrics["frames"]["rekeys_received"].as_u64().unwrap_or(0)
+        );
+        if let Some(peers) = metrics["dht_routing_table_peers"].as_u64() {
+            status!("DHT routing table: {} peers", peers);
+        }
+        return Ok(());
+    }
+
+    // No daemon reachable: fall back to configuration facts
+    if json {
+        status!(
+            r#"{{
+  "version": "{}",
+  "configuration": {{
+    "listen_addr": "{}",
+    "xdp_enabled": {},
+    "chunk_size": {},
+    "max_concurrent": {}
+  }},
+  "note": "No running daemon reachable at {}. Start one with `wraith daemon` and enable [metrics] in the config."
+}}"#,
+            env!("CARGO_PKG_VERSION"),
+            config.network.listen_addr,
+            config.network.enable_xdp,
+            config.transfer.chunk_size,
+            config.transfer.max_concurrent,
+            config.metrics.listen_addr
+        );
+        return Ok(());
+    }
+
+    // Text output
+    status!("WRAITH Metrics");
+    status!("Version: {}", env!("CARGO_PKG_VERSION"));
+    status!();
+
+    status!("Configuration:");
+    status!("  Listen: {}", config.network.listen_addr);
+    status!("  XDP: {}", config.network.enable_xdp);
+    status!(
+        "  Chunk size: {}",
+        format_bytes(config.transfer.chunk_size as u64)
+    );
+    status!("  Max concurrent: {}", config.transfer.max_concurrent);
+    status!();
+
+    status!(
+        "NOTE: No running daemon reachable at {}.",
+        config.metrics.listen_addr
+    );
+    status!("Start a daemon with `wraith daemon` and enable [metrics] in the config");
+
+    Ok(())
+}
+
+/// Show compiled features and detected platform capabilities
+///
+/// The JSON form is a stable contract for support tooling and GUIs, so
+/// keys are only added, never renamed or removed. JSON goes to stdout
+/// unconditionally (ignoring `--quiet`) since it exists to be parsed.
+fn show_features(json: bool) -> anyhow::Result<()> {
+    use wraith_crypto::capabilities::CryptoCapabilities;
+    use wraith_transport::capabilities::TransportCapabilities;
+
+    let transport = TransportCapabilities::detect();
+    let crypto = CryptoCapabilities::detect();
+
     if json {
-        // JSON output
         println!(
             r#"{{
   "version": "{}",
-  "configuration": {{
-    "listen_addr": "{}",
-    "xdp_enabled": {},
-    "chunk_size": {},
-    "max_concurrent": {}
+  "platform": {{
+    "os": "{}",
+    "arch": "{}"
+  }},
+  "transport": {{
+    "udp": {},
+    "af_xdp": {},
+    "io_uring": {},
+    "bpf_fs": {},
+    "xdp_helper": {},
+    "udp_gso": {},
+    "udp_gro": {}
   }},
-  "note": "Runtime metrics require a running daemon. Start with: wraith daemon"
+  "crypto": {{
+    "aes_ni": {},
+    "avx2": {},
+    "sse41": {},
+    "neon": {},
+    "pq_kem": {}
+  }},
+  "compression": []
 }}"#,
             env!("CARGO_PKG_VERSION"),
-            config.network.listen_addr,
-            config.network.enable_xdp,
-            config.transfer.chunk_size,
-            config.transfer.max_concurrent
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            transport.udp,
+            transport.af_xdp,
+            transport.io_uring,
+            transport.bpf_fs,
+            transport.xdp_helper,
+            transport.udp_gso,
+            transport.udp_gro,
+            crypto.aes_ni,
+            crypto.avx2,
+            crypto.sse41,
+            crypto.neon,
+            crypto.pq_kem,
         );
         return Ok(());
     }
 
-    // Text output
-    println!("WRAITH Metrics");
-    println!("Version: {}", env!("CARGO_PKG_VERSION"));
-    println!();
-
-    println!("Configuration:");
-    println!("  Listen: {}", config.network.listen_addr);
-    println!("  XDP: {}", config.network.enable_xdp);
-    println!(
-        "  Chunk size: {}",
-        format_bytes(config.transfer.chunk_size as u64)
-    );
-    println!("  Max concurrent: {}", config.transfer.max_concurrent);
-    println!();
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
 
-    println!("NOTE: Runtime metrics require a running daemon.");
-    println!("Start a daemon with: wraith daemon");
-    println!("Then query metrics via IPC (future feature)");
+    status!("WRAITH Features");
+    status!("Version: {}", env!("CARGO_PKG_VERSION"));
+    status!(
+        "Platform: {} ({})",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    status!();
+
+    status!("Transport:");
+    status!("  UDP ......... {}", yes_no(transport.udp));
+    status!("  AF_XDP ...... {}", yes_no(transport.af_xdp));
+    status!("  io_uring .... {}", yes_no(transport.io_uring));
+    status!("  bpffs ....... {}", yes_no(transport.bpf_fs));
+    status!("  XDP helper .. {}", yes_no(transport.xdp_helper));
+    status!("  UDP GSO ..... {}", yes_no(transport.udp_gso));
+    status!("  UDP GRO ..... {}", yes_no(transport.udp_gro));
+    status!();
+
+    status!("Crypto acceleration:");
+    status!("  AES-NI ...... {}", yes_no(crypto.aes_ni));
+    status!("  AVX2 ........ {}", yes_no(crypto.avx2));
+    status!("  SSE4.1 ...... {}", yes_no(crypto.sse41));
+    status!("  NEON ........ {}", yes_no(crypto.neon));
+    status!("  PQ KEM ...... {}", yes_no(crypto.pq_kem));
+    status!();
+
+    status!("Compression backends: none compiled in");
 
     Ok(())
 }
 
 /// Show node information
 async fn show_info(config: &Config) -> anyhow::Result<()> {
-    println!("WRAITH Node Information");
-    println!();
+    status!("WRAITH Node Information");
+    status!();
 
-    println!("Version: {}", env!("CARGO_PKG_VERSION"));
-    println!(
+    status!("Version: {}", env!("CARGO_PKG_VERSION"));
+    status!(
         "Build: {} ({})",
         env!("CARGO_PKG_NAME"),
         env!("CARGO_PKG_VERSION")
     );
-    println!();
+    status!();
 
     // Generate temporary node to show ID
     let node = Node::new_random().await?;
-    println!("Node:");
-    println!("  ID: {}", hex::encode(node.node_id()));
-    println!("  X25519 Key: {}", hex::encode(node.x25519_public_key()));
-    println!("  Listen: {}", config.network.listen_addr);
-    println!();
-
-    println!("Features:");
-    println!(
+    status!("Node:");
+    status!("  ID: {}", hex::encode(node.node_id()));
+    status!("  X25519 Key: {}", hex::encode(node.x25519_public_key()));
+    status!("  Listen: {}", config.network.listen_addr);
+    status!();
+
+    status!("Features:");
+    status!(
         "  XDP: {} ({})",
         config.network.enable_xdp,
         if config.network.enable_xdp {
@@ -1234,7 +3629,7 @@ async fn show_info(config: &Config) -> anyhow::Result<()> {
             "disabled"
         }
     );
-    println!(
+    status!(
         "  io_uring: {} ({})",
         cfg!(target_os = "linux"),
         if cfg!(target_os = "linux") {
@@ -1243,29 +3638,29 @@ async fn show_info(config: &Config) -> anyhow::Result<()> {
             "unavailable"
         }
     );
-    println!("  Obfuscation: {}", config.obfuscation.default_level);
-    println!("  TLS Mimicry: {}", config.obfuscation.tls_mimicry);
-    println!();
+    status!("  Obfuscation: {}", config.obfuscation.default_level);
+    status!("  TLS Mimicry: {}", config.obfuscation.tls_mimicry);
+    status!();
 
-    println!("Configuration:");
-    println!(
+    status!("Configuration:");
+    status!(
         "  Chunk size: {}",
         format_bytes(config.transfer.chunk_size as u64)
     );
-    println!("  Max concurrent: {}", config.transfer.max_concurrent);
-    println!("  Resume: {}", config.transfer.enable_resume);
-    println!();
+    status!("  Max concurrent: {}", config.transfer.max_concurrent);
+    status!("  Resume: {}", config.transfer.enable_resume);
+    status!();
 
-    println!("Discovery:");
-    println!(
+    status!("Discovery:");
+    status!(
         "  Bootstrap nodes: {}",
         config.discovery.bootstrap_nodes.len()
     );
-    println!("  Relay servers: {}", config.discovery.relay_servers.len());
-    println!();
+    status!("  Relay servers: {}", config.discovery.relay_servers.len());
+    status!();
 
-    println!("NOTE: Node ID shown is randomly generated.");
-    println!("Use 'wraith keygen' to create a persistent identity.");
+    status!("NOTE: Node ID shown is randomly generated.");
+    status!("Use 'wraith keygen' to create a persistent identity.");
 
     Ok(())
 }
@@ -1281,14 +3676,14 @@ async fn show_info(config: &Config) -> anyhow::Result<()> {
 async fn generate_keypair(output: Option<String>, _config: &Config) -> anyhow::Result<()> {
     use wraith_crypto::signatures::SigningKey;
 
-    println!("Generating new Ed25519 identity keypair...");
-    println!();
+    status!("Generating new Ed25519 identity keypair...");
+    status!();
 
     let mut rng = rand_core::OsRng;
     let signing_key = SigningKey::generate(&mut rng);
     let verifying_key = signing_key.verifying_key();
 
-    println!("Public key: {}", hex::encode(verifying_key.to_bytes()));
+    status!("Public key: {}", hex::encode(verifying_key.to_bytes()));
 
     if let Some(path) = output {
         let output_path = PathBuf::from(&path);
@@ -1302,160 +3697,760 @@ async fn generate_keypair(output: Option<String>, _config: &Config) -> anyhow::R
         }
 
         // Prompt for encryption passphrase
-        println!();
-        println!("Your private key will be encrypted with a passphrase.");
-        println!("Choose a strong passphrase (minimum 8 characters).");
-        println!();
+        status!();
+        status!("Your private key will be encrypted with a passphrase.");
+        status!("Choose a strong passphrase (minimum 8 characters).");
+        status!();
 
         let passphrase = prompt_passphrase("Enter passphrase: ", true)?;
 
         // Get private key bytes
         let mut private_bytes = signing_key.to_bytes();
 
-        // Encrypt the private key
-        let encrypted = encrypt_private_key(&private_bytes, &passphrase)?;
+        // Encrypt the private key
+        let encrypted = encrypt_private_key(&private_bytes, &passphrase)?;
+
+        // Zeroize the plaintext private key
+        private_bytes.zeroize();
+
+        // Write encrypted key to file
+        std::fs::write(&output_path, &encrypted)?;
+
+        // Set restrictive file permissions on Unix
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let permissions = std::fs::Permissions::from_mode(0o600);
+            std::fs::set_permissions(&output_path, permissions)?;
+        }
+
+        status!();
+        status!("Encrypted private key saved to: {}", output_path.display());
+        status!();
+        status!("IMPORTANT:");
+        status!("  - Your private key is encrypted and protected by your passphrase");
+        status!("  - Keep your passphrase secure - it cannot be recovered if lost");
+        status!("  - Back up this file and your passphrase separately");
+    } else {
+        status!();
+        status!("WARNING: Private key not saved (use --output to save)");
+        status!("The key will be lost when this program exits.");
+    }
+
+    Ok(())
+}
+
+/// Prompt for a line of input with a default shown in brackets
+///
+/// An empty answer (or EOF on a non-interactive stdin) returns the default.
+fn prompt_line(prompt: &str, default: &str) -> anyhow::Result<String> {
+    print!("{prompt} [{default}]: ");
+    std::io::Write::flush(&mut std::io::stdout())?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    let answer = answer.trim();
+
+    if answer.is_empty() {
+        Ok(default.to_string())
+    } else {
+        Ok(answer.to_string())
+    }
+}
+
+/// Prompt for a yes/no answer with a default
+fn prompt_yes_no(prompt: &str, default: bool) -> anyhow::Result<bool> {
+    let hint = if default { "Y/n" } else { "y/N" };
+    let answer = prompt_line(prompt, hint)?;
+    match answer.to_lowercase().as_str() {
+        "y" | "yes" => Ok(true),
+        "n" | "no" => Ok(false),
+        _ => Ok(default),
+    }
+}
+
+/// Derive a short human-comparable pairing code from a public key
+///
+/// Both sides of a pairing derive the same code from the same key, so two
+/// users can compare it over a phone call or chat to confirm identity.
+fn pairing_code(public_key: &[u8; 32]) -> String {
+    format!(
+        "{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}",
+        public_key[0], public_key[1], public_key[2], public_key[3], public_key[4], public_key[5]
+    )
+}
+
+/// Run local self-tests: crypto primitives and socket binding
+///
+/// Returns the number of failed checks; failures are reported but do not
+/// abort the wizard, since a node may still work in a different network
+/// environment.
+async fn run_selftest(config: &Config) -> usize {
+    use chacha20poly1305::{KeyInit, XChaCha20Poly1305, aead::Aead};
+    use wraith_crypto::signatures::SigningKey;
+
+    let mut failures = 0;
+
+    // Ed25519 sign/verify round-trip
+    let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+    let signature = signing_key.sign(b"wraith selftest");
+    let sig_ok = signing_key
+        .verifying_key()
+        .verify(b"wraith selftest", &signature)
+        .is_ok();
+    status!(
+        "  Ed25519 signatures ........ {}",
+        if sig_ok { "ok" } else { "FAILED" }
+    );
+    failures += usize::from(!sig_ok);
+
+    // XChaCha20-Poly1305 encrypt/decrypt round-trip
+    let cipher = XChaCha20Poly1305::new((&[7u8; 32]).into());
+    let nonce = [0u8; 24];
+    let aead_ok = cipher
+        .encrypt((&nonce).into(), b"wraith selftest".as_ref())
+        .ok()
+        .and_then(|ct| cipher.decrypt((&nonce).into(), ct.as_ref()).ok())
+        .is_some_and(|pt| pt == b"wraith selftest");
+    status!(
+        "  XChaCha20-Poly1305 AEAD ... {}",
+        if aead_ok { "ok" } else { "FAILED" }
+    );
+    failures += usize::from(!aead_ok);
+
+    // UDP socket on the configured listen address (port 0 avoids clashing
+    // with a node that is already running)
+    let bind_probe = config
+        .parse_listen_addr()
+        .map(|mut addr| {
+            addr.set_port(0);
+            addr
+        })
+        .unwrap_or_else(|_| "0.0.0.0:0".parse().expect("Invalid probe address"));
+    let bind_ok = tokio::net::UdpSocket::bind(bind_probe).await.is_ok();
+    status!(
+        "  UDP socket binding ........ {}",
+        if bind_ok { "ok" } else { "FAILED" }
+    );
+    failures += usize::from(!bind_ok);
+
+    failures
+}
+
+/// Interactive first-run setup wizard
+///
+/// Walks a new user through generating a passphrase-protected identity,
+/// writing a commented config file, optionally registering with a relay,
+/// and verifying the installation with a self-test. Finishes by printing
+/// the node's fingerprint and pairing code.
+async fn init_wizard(config_path: &str, force: bool) -> anyhow::Result<()> {
+    use wraith_crypto::signatures::SigningKey;
+
+    // Expand tilde in the config path
+    let config_path = if let Some(rest) = config_path.strip_prefix("~/") {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join(rest)
+    } else {
+        PathBuf::from(config_path)
+    };
+
+    if config_path.exists() && !force {
+        anyhow::bail!(
+            "Configuration already exists at {} (use --force to overwrite, \
+             or `wraith config show` to inspect it)",
+            config_path.display()
+        );
+    }
+
+    status!("WRAITH first-run setup");
+    status!("======================");
+    status!();
+    status!("This wizard creates your identity and configuration.");
+    status!("Press Enter to accept the suggested defaults.");
+    status!();
+
+    let mut config = Config::default();
+
+    // ── Step 1: network basics ─────────────────────────────────────────
+    config.network.listen_addr = prompt_line("Listen address", &config.network.listen_addr)?;
+    config.obfuscation.default_level = prompt_line(
+        "Obfuscation level (none/low/medium/high/paranoid)",
+        &config.obfuscation.default_level,
+    )?;
+
+    // ── Step 2: identity ───────────────────────────────────────────────
+    status!();
+    status!("Generating your Ed25519 identity...");
+    status!("The private key is encrypted with a passphrase before it is");
+    status!("written to disk. Choose a strong one (minimum 8 characters).");
+    status!();
+
+    let signing_key = SigningKey::generate(&mut rand_core::OsRng);
+    let public_key = signing_key.verifying_key().to_bytes();
+
+    let key_path = config.node.private_key_file.display().to_string();
+    let key_path = PathBuf::from(prompt_line("Private key file", &key_path)?);
+
+    let passphrase = prompt_passphrase("Enter passphrase: ", true)?;
+
+    if let Some(parent) = key_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut private_bytes = signing_key.to_bytes();
+    let encrypted = encrypt_private_key(&private_bytes, &passphrase)?;
+    private_bytes.zeroize();
+    std::fs::write(&key_path, &encrypted)
+        .with_context(|| format!("Failed to write private key to {}", key_path.display()))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&key_path, std::fs::Permissions::from_mode(0o600))?;
+    }
+
+    config.node.public_key = Some(hex::encode(public_key));
+    config.node.private_key_file = key_path.clone();
+
+    status!("Private key saved to {}", key_path.display());
+
+    // ── Step 3: relay registration (optional) ──────────────────────────
+    status!();
+    if prompt_yes_no("Register with a relay server for NAT traversal?", false)? {
+        let relay = prompt_line("Relay address (host:port)", "relay.example.com:3478")?;
+
+        // Best-effort registration: an unreachable relay is recorded in the
+        // config anyway so it is retried once the network is up
+        match relay.parse::<std::net::SocketAddr>() {
+            Ok(addr) => {
+                use wraith_discovery::relay::client::RelayClient;
+                match RelayClient::connect(addr, public_key).await {
+                    Ok(mut client) => match client.register(&public_key).await {
+                        Ok(()) => status!("Registered with relay {relay}"),
+                        Err(e) => status!("Relay registration failed ({e}); will retry later"),
+                    },
+                    Err(e) => status!("Could not reach relay ({e}); will retry later"),
+                }
+            }
+            Err(_) => status!("Relay is not a literal address; it will be resolved at runtime"),
+        }
+
+        config.discovery.relay_servers.push(relay);
+    }
+
+    // ── Step 4: write the commented config ─────────────────────────────
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&config_path, config.to_annotated_toml())
+        .with_context(|| format!("Failed to write config to {}", config_path.display()))?;
+
+    status!();
+    status!("Configuration written to {}", config_path.display());
+
+    // ── Step 5: self-test ──────────────────────────────────────────────
+    status!();
+    status!("Running self-test:");
+    let failures = run_selftest(&config).await;
+    if failures > 0 {
+        status!("{failures} check(s) failed; `wraith config validate` may help");
+    }
+
+    // ── Done: identity summary ─────────────────────────────────────────
+    status!();
+    status!("Setup complete. Your node identity:");
+    status!("  Fingerprint:  {}", hex::encode(public_key));
+    status!("  Pairing code: {}", pairing_code(&public_key));
+    status!();
+    status!("Share the fingerprint with peers who should send you files;");
+    status!("compare pairing codes out-of-band to confirm it was not altered.");
+
+    Ok(())
+}
+
+/// Ping a peer to measure connectivity and RTT
+async fn ping_peer(peer: String, count: u32, interval: u64, config: &Config) -> anyhow::Result<()> {
+    // Parse peer ID
+    let peer_id = parse_peer_id(&peer)?;
+
+    status!("WRAITH Ping");
+    status!("Peer: {}", hex::encode(peer_id));
+    status!("Count: {count}, Interval: {interval}ms");
+    status!();
+
+    // Create and start node
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+
+    tracing::info!("Starting ping node...");
+    node.start().await?;
+
+    status!("Node ID: {}", hex::encode(node.node_id()));
+    status!();
+
+    // Ping statistics
+    let mut rtts = Vec::new();
+    let mut packets_sent = 0u32;
+    let mut packets_received = 0u32;
+
+    for seq in 0..count {
+        packets_sent += 1;
+        let start = std::time::Instant::now();
+
+        print!(
+            "Ping {} ({}/{}): ",
+            hex::encode(&peer_id[..8]),
+            seq + 1,
+            count
+        );
+        std::io::Write::flush(&mut std::io::stdout())?;
+
+        // Attempt to establish connection or use existing session for RTT measurement
+        match node.discover_peer(&peer_id).await {
+            Ok(addrs) => {
+                let rtt = start.elapsed();
+                rtts.push(rtt);
+                packets_received += 1;
+
+                status!(
+                    "time={:.2}ms, addrs={}",
+                    rtt.as_secs_f64() * 1000.0,
+                    addrs.len()
+                );
+            }
+            Err(e) => {
+                status!("timeout ({})", e);
+            }
+        }
+
+        // Wait for interval before next ping (except for last one)
+        if seq < count - 1 {
+            tokio::time::sleep(Duration::from_millis(interval)).await;
+        }
+    }
+
+    status!();
+
+    // Calculate statistics
+    if !rtts.is_empty() {
+        let min_rtt = rtts.iter().min().unwrap();
+        let max_rtt = rtts.iter().max().unwrap();
+        let avg_rtt = rtts.iter().map(|d| d.as_secs_f64()).sum::<f64>() / rtts.len() as f64;
+
+        // Calculate standard deviation for mdev
+        let variance = rtts
+            .iter()
+            .map(|d| {
+                let diff = d.as_secs_f64() - avg_rtt;
+                diff * diff
+            })
+            .sum::<f64>()
+            / rtts.len() as f64;
+        let mdev = variance.sqrt();
+
+        let packet_loss = if packets_sent > 0 {
+            ((packets_sent - packets_received) as f64 / packets_sent as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        status!("--- {} ping statistics ---", hex::encode(&peer_id[..8]));
+        status!(
+            "{} packets transmitted, {} received, {:.1}% packet loss",
+            packets_sent,
+            packets_received,
+            packet_loss
+        );
+        status!(
+            "rtt min/avg/max/mdev = {:.3}/{:.3}/{:.3}/{:.3} ms",
+            min_rtt.as_secs_f64() * 1000.0,
+            avg_rtt * 1000.0,
+            max_rtt.as_secs_f64() * 1000.0,
+            mdev * 1000.0
+        );
+    } else {
+        status!("--- {} ping statistics ---", hex::encode(&peer_id[..8]));
+        status!(
+            "{} packets transmitted, 0 received, 100.0% packet loss",
+            packets_sent
+        );
+    }
+
+    status!();
+
+    // Stop node
+    node.stop().await?;
+
+    Ok(())
+}
+
+/// Probe the NAT environment and report the traversal strategy
+async fn probe_network() -> anyhow::Result<()> {
+    use wraith_discovery::nat::EnvironmentProbe;
+
+    status!("WRAITH Network Probe");
+    status!("Probing NAT environment (STUN + DNS64)...");
+    status!();
+
+    let env = EnvironmentProbe::new().probe().await;
+
+    status!("NAT type:         {}", env.nat_type);
+    status!("CGNAT (RFC 6598): {}", if env.cgnat { "yes" } else { "no" });
+    match env.nat64_prefix {
+        Some(prefix) => status!("NAT64/464XLAT:    yes (prefix {prefix}/96)"),
+        None => status!("NAT64/464XLAT:    no"),
+    }
+    status!(
+        "Global IPv6:      {}",
+        if env.ipv6_available {
+            "available"
+        } else {
+            "not available"
+        }
+    );
+    status!();
+    status!("Strategy:         {}", env.strategy());
+
+    Ok(())
+}
+
+/// Verify a file against a sender-signed manifest
+///
+/// Checks the Ed25519 signature under the signer key embedded in the
+/// manifest, then re-chunks the file with the manifest's chunk size and
+/// compares the BLAKE3 tree root — confirming both who published the file
+/// and that its contents are unchanged.
+fn verify_manifest(file: &str, manifest_path: Option<&str>) -> anyhow::Result<()> {
+    use wraith_core::node::offer::manifest_sidecar_path;
+    use wraith_crypto::manifest::TransferManifest;
+
+    let file = PathBuf::from(file);
+    let manifest_path = manifest_path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_sidecar_path(&file));
+
+    let bytes = std::fs::read(&manifest_path)
+        .with_context(|| format!("Failed to read manifest {}", manifest_path.display()))?;
+    let manifest = TransferManifest::deserialize(&bytes)
+        .map_err(|e| anyhow::anyhow!("Malformed manifest {}: {e}", manifest_path.display()))?;
+
+    manifest
+        .verify()
+        .map_err(|_| anyhow::anyhow!("Manifest signature is INVALID"))?;
+
+    let file_size = std::fs::metadata(&file)
+        .with_context(|| format!("Failed to read {}", file.display()))?
+        .len();
+    if file_size != manifest.file_size {
+        anyhow::bail!(
+            "Size mismatch: file is {} bytes, manifest attests {}",
+            file_size,
+            manifest.file_size
+        );
+    }
+
+    let tree_hash = wraith_files::tree_hash::compute_tree_hash(&file, manifest.chunk_size as usize)
+        .with_context(|| format!("Failed to hash {}", file.display()))?;
+    if tree_hash.root != manifest.root_hash {
+        anyhow::bail!("Content hash mismatch: file does not match the signed manifest");
+    }
+
+    status!("File:   {}", file.display());
+    status!("Size:   {}", format_bytes(file_size));
+    status!(
+        "Chunks: {} x {}",
+        manifest.chunk_count,
+        format_bytes(manifest.chunk_size as u64)
+    );
+    status!("Signer: {}", hex::encode(manifest.signer));
+    status!();
+    status!("Signature and content verified OK");
+
+    Ok(())
+}
+
+/// Pipe stdin/stdout through an encrypted byte stream to a peer
+///
+/// Connect mode opens a pipe to the given peer; listen mode waits for an
+/// incoming pipe. Either way, stdin is forwarded to the peer and the peer's
+/// bytes are written to stdout, so the command composes with shell pipelines:
+///
+/// ```text
+/// tar czf - dir/ | wraith pipe <peer-id>
+/// wraith pipe --listen | tar xzf -
+/// ```
+///
+/// Status output goes to stderr to keep stdout clean for the byte stream.
+async fn pipe_stream(peer: Option<String>, listen: bool, config: &Config) -> anyhow::Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    // Create and start node
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+    node.start().await?;
+
+    let listen_addr = node.listen_addr().await?;
+    if !is_quiet() {
+        eprintln!("Node started: {}", hex::encode(node.node_id()));
+        eprintln!("Listening on: {listen_addr}");
+    }
+
+    let stream = if listen {
+        if !is_quiet() {
+            eprintln!("Waiting for incoming pipe...");
+        }
+        node.accept_stream()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Node stopped while waiting for a pipe"))?
+    } else {
+        let peer = peer.expect("clap requires peer unless --listen");
+        let peer_id = parse_peer_id(&peer)?;
+        if !is_quiet() {
+            eprintln!("Opening pipe to {}...", hex::encode(&peer_id[..8]));
+        }
+        node.open_stream(&peer_id).await?
+    };
+
+    if !is_quiet() {
+        eprintln!(
+            "Pipe open (stream {}, peer {})",
+            stream.stream_id(),
+            hex::encode(&stream.peer_id()[..8])
+        );
+    }
+
+    let (mut reader, mut writer) = tokio::io::split(stream);
+
+    // stdin -> peer; shut down the stream on EOF so the peer sees EOF too
+    let outbound = tokio::spawn(async move {
+        let mut stdin = tokio::io::stdin();
+        let sent = tokio::io::copy(&mut stdin, &mut writer).await?;
+        writer.shutdown().await?;
+        Ok::<u64, std::io::Error>(sent)
+    });
+
+    // peer -> stdout
+    let mut stdout = tokio::io::stdout();
+    let received = tokio::io::copy(&mut reader, &mut stdout).await?;
+    stdout.flush().await?;
+
+    // Remote side closed; stop forwarding stdin
+    outbound.abort();
+    let sent = match outbound.await {
+        Ok(Ok(sent)) => sent,
+        _ => 0,
+    };
+
+    if !is_quiet() {
+        eprintln!(
+            "Pipe closed: {} sent, {} received",
+            format_bytes(sent),
+            format_bytes(received)
+        );
+    }
+
+    node.stop().await?;
+    Ok(())
+}
+
+/// Record tag for a data chunk in a stdin/stdout streamed transfer
+const STREAM_RECORD_DATA: u8 = 0x00;
+/// Record tag for the trailing manifest (8B total size + 32B BLAKE3 hash)
+const STREAM_RECORD_MANIFEST: u8 = 0x01;
+/// Read size for stdin chunks in streamed transfers
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Stream stdin to a peer as an unknown-length transfer
+///
+/// The total size is unknown until EOF, so the stream is sent as framed
+/// records over an encrypted pipe: data records carry the bytes, and a
+/// trailing manifest record delivers the final size and BLAKE3 hash so the
+/// receiver can verify integrity without knowing the length up front. This
+/// enables pipeline workflows:
+///
+/// ```text
+/// tar czf - dir/ | wraith send - <peer-id>
+/// ```
+///
+/// Status output goes to stderr to keep stdout free.
+async fn send_stdin(recipients: Vec<String>, config: &Config) -> anyhow::Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    if recipients.len() != 1 {
+        anyhow::bail!("Streaming from stdin supports exactly one recipient");
+    }
+    let peer_id = parse_peer_id(&recipients[0])?;
 
-        // Zeroize the plaintext private key
-        private_bytes.zeroize();
+    // Create and start node
+    let node_config = create_node_config(config);
+    let node = create_node(node_config, config).await?;
+    node.start().await?;
 
-        // Write encrypted key to file
-        std::fs::write(&output_path, &encrypted)?;
+    if !is_quiet() {
+        eprintln!("Node started: {}", hex::encode(node.node_id()));
+        eprintln!("Streaming stdin to {}...", hex::encode(&peer_id[..8]));
+    }
 
-        // Set restrictive file permissions on Unix
-        #[cfg(unix)]
-        {
-            use std::os::unix::fs::PermissionsExt;
-            let permissions = std::fs::Permissions::from_mode(0o600);
-            std::fs::set_permissions(&output_path, permissions)?;
+    let mut stream = node.open_stream(&peer_id).await?;
+    let mut stdin = tokio::io::stdin();
+    let mut hasher = wraith_crypto::hash::TreeHasher::new();
+    let mut buf = vec![0u8; STREAM_CHUNK_SIZE];
+    let mut total: u64 = 0;
+
+    loop {
+        let n = stdin.read(&mut buf).await?;
+        if n == 0 {
+            break;
         }
+        hasher.update(&buf[..n]);
+        total += n as u64;
 
-        println!();
-        println!("Encrypted private key saved to: {}", output_path.display());
-        println!();
-        println!("IMPORTANT:");
-        println!("  - Your private key is encrypted and protected by your passphrase");
-        println!("  - Keep your passphrase secure - it cannot be recovered if lost");
-        println!("  - Back up this file and your passphrase separately");
-    } else {
-        println!();
-        println!("WARNING: Private key not saved (use --output to save)");
-        println!("The key will be lost when this program exits.");
+        stream.write_all(&[STREAM_RECORD_DATA]).await?;
+        stream.write_all(&(n as u32).to_be_bytes()).await?;
+        stream.write_all(&buf[..n]).await?;
+    }
+
+    // Trailing manifest: now that EOF fixed the length, deliver size + hash
+    let hash = hasher.finalize();
+    let mut manifest = Vec::with_capacity(40);
+    manifest.extend_from_slice(&total.to_be_bytes());
+    manifest.extend_from_slice(&hash);
+
+    stream.write_all(&[STREAM_RECORD_MANIFEST]).await?;
+    stream
+        .write_all(&(manifest.len() as u32).to_be_bytes())
+        .await?;
+    stream.write_all(&manifest).await?;
+    stream.shutdown().await?;
+
+    if !is_quiet() {
+        eprintln!(
+            "Stream complete: {} sent, hash {}",
+            format_bytes(total),
+            hex::encode(&hash[..8])
+        );
     }
 
+    node.stop().await?;
     Ok(())
 }
 
-/// Ping a peer to measure connectivity and RTT
-async fn ping_peer(peer: String, count: u32, interval: u64, config: &Config) -> anyhow::Result<()> {
-    // Parse peer ID
-    let peer_id = parse_peer_id(&peer)?;
+/// Receive a single streamed transfer and write it to stdout
+///
+/// Accepts one incoming pipe stream, unwraps the data records to stdout, and
+/// verifies the trailing manifest (final size + BLAKE3 hash) before exiting.
+/// Composes with `wraith send -`:
+///
+/// ```text
+/// wraith receive --stdout | tar xzf -
+/// ```
+async fn receive_stdout(trusted_peers: Option<String>, config: &Config) -> anyhow::Result<()> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-    println!("WRAITH Ping");
-    println!("Peer: {}", hex::encode(peer_id));
-    println!("Count: {count}, Interval: {interval}ms");
-    println!();
+    // Parse trusted peers if provided
+    let mut trusted_peer_ids = Vec::new();
+    if let Some(peers_str) = trusted_peers {
+        for peer_str in peers_str.split(',') {
+            trusted_peer_ids.push(parse_peer_id(peer_str.trim())?);
+        }
+    }
 
     // Create and start node
     let node_config = create_node_config(config);
-    let node = Node::new_with_config(node_config).await?;
-
-    tracing::info!("Starting ping node...");
+    let node = create_node(node_config, config).await?;
     node.start().await?;
 
-    println!("Node ID: {}", hex::encode(node.node_id()));
-    println!();
+    if !is_quiet() {
+        eprintln!("Node started: {}", hex::encode(node.node_id()));
+        eprintln!("Listening on: {}", node.listen_addr().await?);
+        eprintln!("Waiting for incoming stream...");
+    }
 
-    // Ping statistics
-    let mut rtts = Vec::new();
-    let mut packets_sent = 0u32;
-    let mut packets_received = 0u32;
+    let mut stream = loop {
+        let stream = node
+            .accept_stream()
+            .await
+            .ok_or_else(|| anyhow::anyhow!("Node stopped while waiting for a stream"))?;
 
-    for seq in 0..count {
-        packets_sent += 1;
-        let start = std::time::Instant::now();
+        if trusted_peer_ids.is_empty() || trusted_peer_ids.contains(&stream.peer_id()) {
+            break stream;
+        }
+        if !is_quiet() {
+            eprintln!(
+                "Rejected stream from untrusted peer {}",
+                hex::encode(&stream.peer_id()[..8])
+            );
+        }
+    };
 
-        print!(
-            "Ping {} ({}/{}): ",
-            hex::encode(&peer_id[..8]),
-            seq + 1,
-            count
+    if !is_quiet() {
+        eprintln!(
+            "Receiving stream from {}...",
+            hex::encode(&stream.peer_id()[..8])
         );
-        std::io::Write::flush(&mut std::io::stdout())?;
+    }
 
-        // Attempt to establish connection or use existing session for RTT measurement
-        match node.discover_peer(&peer_id).await {
-            Ok(addrs) => {
-                let rtt = start.elapsed();
-                rtts.push(rtt);
-                packets_received += 1;
+    let mut stdout = tokio::io::stdout();
+    let mut hasher = wraith_crypto::hash::TreeHasher::new();
+    let mut total: u64 = 0;
 
-                println!(
-                    "time={:.2}ms, addrs={}",
-                    rtt.as_secs_f64() * 1000.0,
-                    addrs.len()
-                );
+    loop {
+        let mut header = [0u8; 5];
+        stream.read_exact(&mut header).await.map_err(|e| {
+            anyhow::anyhow!("Stream ended before the trailing manifest arrived: {e}")
+        })?;
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+
+        match header[0] {
+            STREAM_RECORD_DATA => {
+                let mut chunk = vec![0u8; len];
+                stream.read_exact(&mut chunk).await?;
+                hasher.update(&chunk);
+                total += len as u64;
+                stdout.write_all(&chunk).await?;
             }
-            Err(e) => {
-                println!("timeout ({})", e);
+            STREAM_RECORD_MANIFEST => {
+                if len != 40 {
+                    anyhow::bail!("Malformed manifest record ({len} bytes, expected 40)");
+                }
+                let mut manifest = [0u8; 40];
+                stream.read_exact(&mut manifest).await?;
+
+                let expected_size = u64::from_be_bytes(manifest[..8].try_into().unwrap());
+                let expected_hash: [u8; 32] = manifest[8..].try_into().unwrap();
+                let actual_hash = hasher.finalize();
+
+                if total != expected_size {
+                    anyhow::bail!(
+                        "Stream size mismatch: received {} bytes, manifest says {}",
+                        total,
+                        expected_size
+                    );
+                }
+                if actual_hash != expected_hash {
+                    anyhow::bail!(
+                        "Stream hash mismatch: computed {}, manifest says {}",
+                        hex::encode(&actual_hash[..8]),
+                        hex::encode(&expected_hash[..8])
+                    );
+                }
+                break;
             }
-        }
-
-        // Wait for interval before next ping (except for last one)
-        if seq < count - 1 {
-            tokio::time::sleep(Duration::from_millis(interval)).await;
+            tag => anyhow::bail!("Unknown stream record tag: {tag:#04x}"),
         }
     }
 
-    println!();
-
-    // Calculate statistics
-    if !rtts.is_empty() {
-        let min_rtt = rtts.iter().min().unwrap();
-        let max_rtt = rtts.iter().max().unwrap();
-        let avg_rtt = rtts.iter().map(|d| d.as_secs_f64()).sum::<f64>() / rtts.len() as f64;
-
-        // Calculate standard deviation for mdev
-        let variance = rtts
-            .iter()
-            .map(|d| {
-                let diff = d.as_secs_f64() - avg_rtt;
-                diff * diff
-            })
-            .sum::<f64>()
-            / rtts.len() as f64;
-        let mdev = variance.sqrt();
-
-        let packet_loss = if packets_sent > 0 {
-            ((packets_sent - packets_received) as f64 / packets_sent as f64) * 100.0
-        } else {
-            0.0
-        };
+    stdout.flush().await?;
 
-        println!("--- {} ping statistics ---", hex::encode(&peer_id[..8]));
-        println!(
-            "{} packets transmitted, {} received, {:.1}% packet loss",
-            packets_sent, packets_received, packet_loss
-        );
-        println!(
-            "rtt min/avg/max/mdev = {:.3}/{:.3}/{:.3}/{:.3} ms",
-            min_rtt.as_secs_f64() * 1000.0,
-            avg_rtt * 1000.0,
-            max_rtt.as_secs_f64() * 1000.0,
-            mdev * 1000.0
-        );
-    } else {
-        println!("--- {} ping statistics ---", hex::encode(&peer_id[..8]));
-        println!(
-            "{} packets transmitted, 0 received, 100.0% packet loss",
-            packets_sent
+    if !is_quiet() {
+        eprintln!(
+            "Stream complete: {} received, hash verified",
+            format_bytes(total)
         );
     }
 
-    println!();
-
-    // Stop node
     node.stop().await?;
-
     Ok(())
 }
 
@@ -1467,35 +4462,35 @@ async fn config_show(key: Option<String>, config: &Config) -> anyhow::Result<()>
 
         match key_lower.as_str() {
             "network.listen_addr" | "listen_addr" => {
-                println!("{}", config.network.listen_addr);
+                status!("{}", config.network.listen_addr);
             }
             "network.enable_xdp" | "enable_xdp" => {
-                println!("{}", config.network.enable_xdp);
+                status!("{}", config.network.enable_xdp);
             }
             "network.xdp_interface" | "xdp_interface" => {
                 if let Some(iface) = &config.network.xdp_interface {
-                    println!("{}", iface);
+                    status!("{}", iface);
                 } else {
-                    println!("(not set)");
+                    status!("(not set)");
                 }
             }
             "network.udp_fallback" | "udp_fallback" => {
-                println!("{}", config.network.udp_fallback);
+                status!("{}", config.network.udp_fallback);
             }
             "obfuscation.default_level" | "default_level" => {
-                println!("{}", config.obfuscation.default_level);
+                status!("{}", config.obfuscation.default_level);
             }
             "obfuscation.tls_mimicry" | "tls_mimicry" => {
-                println!("{}", config.obfuscation.tls_mimicry);
+                status!("{}", config.obfuscation.tls_mimicry);
             }
             "transfer.chunk_size" | "chunk_size" => {
-                println!("{}", config.transfer.chunk_size);
+                status!("{}", config.transfer.chunk_size);
             }
             "transfer.max_concurrent" | "max_concurrent" => {
-                println!("{}", config.transfer.max_concurrent);
+                status!("{}", config.transfer.max_concurrent);
             }
             "transfer.enable_resume" | "enable_resume" => {
-                println!("{}", config.transfer.enable_resume);
+                status!("{}", config.transfer.enable_resume);
             }
             _ => {
                 anyhow::bail!("Unknown configuration key: {}", key_name);
@@ -1503,43 +4498,43 @@ async fn config_show(key: Option<String>, config: &Config) -> anyhow::Result<()>
         }
     } else {
         // Show all configuration
-        println!("WRAITH Configuration");
-        println!();
+        status!("WRAITH Configuration");
+        status!();
 
-        println!("[network]");
-        println!("  listen_addr = \"{}\"", config.network.listen_addr);
-        println!("  enable_xdp = {}", config.network.enable_xdp);
+        status!("[network]");
+        status!("  listen_addr = \"{}\"", config.network.listen_addr);
+        status!("  enable_xdp = {}", config.network.enable_xdp);
         if let Some(iface) = &config.network.xdp_interface {
-            println!("  xdp_interface = \"{}\"", iface);
+            status!("  xdp_interface = \"{}\"", iface);
         }
-        println!("  udp_fallback = {}", config.network.udp_fallback);
-        println!();
-
-        println!("[obfuscation]");
-        println!("  default_level = \"{}\"", config.obfuscation.default_level);
-        println!("  tls_mimicry = {}", config.obfuscation.tls_mimicry);
-        println!();
-
-        println!("[transfer]");
-        println!("  chunk_size = {}", config.transfer.chunk_size);
-        println!("  max_concurrent = {}", config.transfer.max_concurrent);
-        println!("  enable_resume = {}", config.transfer.enable_resume);
-        println!();
-
-        println!("[discovery]");
-        println!(
+        status!("  udp_fallback = {}", config.network.udp_fallback);
+        status!();
+
+        status!("[obfuscation]");
+        status!("  default_level = \"{}\"", config.obfuscation.default_level);
+        status!("  tls_mimicry = {}", config.obfuscation.tls_mimicry);
+        status!();
+
+        status!("[transfer]");
+        status!("  chunk_size = {}", config.transfer.chunk_size);
+        status!("  max_concurrent = {}", config.transfer.max_concurrent);
+        status!("  enable_resume = {}", config.transfer.enable_resume);
+        status!();
+
+        status!("[discovery]");
+        status!(
             "  bootstrap_nodes = {} configured",
             config.discovery.bootstrap_nodes.len()
         );
-        println!(
+        status!(
             "  relay_servers = {} configured",
             config.discovery.relay_servers.len()
         );
-        println!();
+        status!();
 
-        println!("[logging]");
-        println!("  level = \"{}\"", config.logging.level);
-        println!("  file = {:?}", config.logging.file);
+        status!("[logging]");
+        status!("  level = \"{}\"", config.logging.level);
+        status!("  file = {:?}", config.logging.file);
     }
 
     Ok(())
@@ -1619,8 +4614,161 @@ async fn config_set(key: String, value: String, config_path: &str) -> anyhow::Re
     // Save the configuration
     config.save(&config_path_buf)?;
 
-    println!("Configuration updated: {} = {}", key, value);
-    println!("Saved to: {}", config_path_buf.display());
+    status!("Configuration updated: {} = {}", key, value);
+    status!("Saved to: {}", config_path_buf.display());
+
+    Ok(())
+}
+
+/// List defined network profiles and how the current one was selected
+fn profile_list(config: &Config) -> anyhow::Result<()> {
+    if config.profiles.is_empty() {
+        status!("No profiles defined");
+        status!("Add [profiles.<name>] sections to the config file to create some");
+        return Ok(());
+    }
+
+    let detected = profile::detect_network();
+    let selected = profile::select_profile(config);
+
+    status!("Profiles:");
+    for (name, prof) in &config.profiles {
+        let marker = if Some(name) == selected.as_ref() {
+            if config.active_profile.as_ref() == Some(name) {
+                " (active, pinned)"
+            } else {
+                " (active, auto-detected)"
+            }
+        } else {
+            ""
+        };
+
+        let mut criteria = Vec::new();
+        if let Some(mac) = &prof.match_gateway_mac {
+            criteria.push(format!("gateway {mac}"));
+        }
+        if let Some(ssid) = &prof.match_ssid {
+            criteria.push(format!("ssid {ssid:?}"));
+        }
+        if let Some(subnet) = &prof.match_subnet {
+            criteria.push(format!("subnet {subnet}"));
+        }
+        let criteria = if criteria.is_empty() {
+            "manual only".to_string()
+        } else {
+            criteria.join(", ")
+        };
+
+        status!("  {name}{marker} [{criteria}]");
+    }
+
+    status!();
+    status!(
+        "Detected network: gateway {} ({}), ssid {}",
+        detected
+            .gateway_ip
+            .map_or_else(|| "unknown".to_string(), |ip| ip.to_string()),
+        detected.gateway_mac.as_deref().unwrap_or("unknown"),
+        detected.ssid.as_deref().unwrap_or("none")
+    );
+
+    Ok(())
+}
+
+/// Pin a profile (`Some(name)`) or return to automatic selection (`None`)
+///
+/// Persists `active_profile` in the config file so the choice survives
+/// across invocations.
+fn profile_use(name: Option<String>, config_path: &str) -> anyhow::Result<()> {
+    // Expand tilde in config path
+    let config_path_buf = if let Some(stripped) = config_path.strip_prefix("~/") {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join(stripped)
+    } else {
+        PathBuf::from(config_path)
+    };
+
+    let mut config = if config_path_buf.exists() {
+        Config::load(&config_path_buf)?
+    } else {
+        Config::default()
+    };
+
+    match &name {
+        Some(name) => {
+            if !config.profiles.contains_key(name) {
+                let defined: Vec<&str> = config.profiles.keys().map(String::as_str).collect();
+                anyhow::bail!(
+                    "Unknown profile '{name}' (defined: {})",
+                    if defined.is_empty() {
+                        "none".to_string()
+                    } else {
+                        defined.join(", ")
+                    }
+                );
+            }
+            config.active_profile = Some(name.clone());
+        }
+        None => {
+            config.active_profile = None;
+        }
+    }
+
+    config.save(&config_path_buf)?;
+
+    match name {
+        Some(name) => status!("Profile '{name}' pinned"),
+        None => status!("Manual pin removed; profiles are selected by network detection"),
+    }
+    status!("Saved to: {}", config_path_buf.display());
+
+    Ok(())
+}
+
+/// Validate a configuration file and print every error and warning found
+fn config_validate(config_path: &str, offline: bool) -> anyhow::Result<()> {
+    // Expand tilde in config path
+    let path = if let Some(stripped) = config_path.strip_prefix("~/") {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join(stripped)
+    } else {
+        PathBuf::from(config_path)
+    };
+
+    if !path.exists() {
+        anyhow::bail!("Config file not found: {}", path.display());
+    }
+
+    let config =
+        Config::load(&path).with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    status!("Validating {}", path.display());
+
+    let diagnostics = config.diagnose(!offline);
+
+    for error in &diagnostics.errors {
+        println!("  error: {error}");
+    }
+    for warning in &diagnostics.warnings {
+        status!("  warning: {warning}");
+    }
+
+    if diagnostics.is_clean() {
+        status!("Configuration OK");
+    } else {
+        status!();
+        status!(
+            "{} error(s), {} warning(s)",
+            diagnostics.errors.len(),
+            diagnostics.warnings.len()
+        );
+    }
+
+    if diagnostics.has_errors() {
+        anyhow::bail!("Configuration invalid");
+    }
 
     Ok(())
 }
@@ -1631,6 +4779,25 @@ mod tests {
     use std::fs;
     use tempfile::TempDir;
 
+    #[test]
+    fn test_pairing_code_format() {
+        let mut key = [0u8; 32];
+        key[..6].copy_from_slice(&[0xab, 0x12, 0xcd, 0x34, 0xef, 0x56]);
+        assert_eq!(pairing_code(&key), "ab12-cd34-ef56");
+    }
+
+    #[test]
+    fn test_pairing_code_stable_for_same_key() {
+        let key = [42u8; 32];
+        assert_eq!(pairing_code(&key), pairing_code(&key));
+    }
+
+    #[tokio::test]
+    async fn test_selftest_passes_with_default_config() {
+        let config = Config::default();
+        assert_eq!(run_selftest(&config).await, 0);
+    }
+
     #[test]
     fn test_sanitize_path_no_traversal() {
         let temp_dir = TempDir::new().unwrap();
@@ -1642,6 +4809,54 @@ mod tests {
         assert!(sanitized.is_absolute());
     }
 
+    #[test]
+    fn test_exit_code_integrity() {
+        let err = anyhow::Error::from(wraith_core::node::NodeError::HashMismatch);
+        assert_eq!(exit_code_for(&err), EXIT_INTEGRITY);
+    }
+
+    #[test]
+    fn test_exit_code_timeout() {
+        let err = anyhow::Error::from(wraith_core::node::NodeError::Timeout("handshake".into()));
+        assert_eq!(exit_code_for(&err), EXIT_TIMEOUT);
+    }
+
+    #[test]
+    fn test_exit_code_unreachable() {
+        for err in [
+            wraith_core::node::NodeError::PeerNotFound([0u8; 32]),
+            wraith_core::node::NodeError::SessionEstablishment("no response".into()),
+            wraith_core::node::NodeError::NatTraversal("all candidates failed".into()),
+        ] {
+            assert_eq!(exit_code_for(&anyhow::Error::from(err)), EXIT_UNREACHABLE);
+        }
+    }
+
+    #[test]
+    fn test_exit_code_policy() {
+        let err = anyhow::Error::from(PolicyRejection("untrusted peer".to_string()));
+        assert_eq!(exit_code_for(&err), EXIT_POLICY);
+    }
+
+    #[test]
+    fn test_exit_code_generic_fallback() {
+        let err = anyhow::anyhow!("something else went wrong");
+        assert_eq!(exit_code_for(&err), EXIT_GENERIC);
+    }
+
+    #[test]
+    fn test_exit_code_found_through_context_chain() {
+        let err =
+            anyhow::Error::from(wraith_core::node::NodeError::HashMismatch).context("sending file");
+        assert_eq!(exit_code_for(&err), EXIT_INTEGRITY);
+    }
+
+    #[test]
+    fn test_policy_rejection_exit_code_from_sanitize() {
+        let result = sanitize_path(&PathBuf::from("../etc/passwd"));
+        assert_eq!(exit_code_for(&result.unwrap_err()), EXIT_POLICY);
+    }
+
     #[test]
     fn test_sanitize_path_rejects_dot_dot() {
         let path = PathBuf::from("../etc/passwd");
@@ -1697,6 +4912,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_dry_run_send_plans_without_sending() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("payload.bin");
+        fs::write(&file_path, vec![0u8; 4096]).unwrap();
+
+        let config = Config::default();
+        let peer = "ab".repeat(32);
+
+        dry_run_send_command(
+            file_path.to_string_lossy().into_owned(),
+            vec![peer],
+            vec![],
+            "standard",
+            Some("10MB/s".to_string()),
+            &config,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_dry_run_send_rejects_stdin_and_missing_files() {
+        let config = Config::default();
+        let peer = "ab".repeat(32);
+
+        let result = dry_run_send_command(
+            "-".to_string(),
+            vec![peer.clone()],
+            vec![],
+            "standard",
+            None,
+            &config,
+        );
+        assert!(result.unwrap_err().to_string().contains("stdin"));
+
+        let result = dry_run_send_command(
+            "/nonexistent/payload.bin".to_string(),
+            vec![peer],
+            vec![],
+            "standard",
+            None,
+            &config,
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_dry_run_batch_validates_all_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let good = temp_dir.path().join("good.bin");
+        fs::write(&good, b"data").unwrap();
+
+        let config = Config::default();
+        let peer = "cd".repeat(32);
+
+        dry_run_batch_command(
+            vec![good.to_string_lossy().into_owned()],
+            &peer,
+            "standard",
+            &config,
+        )
+        .unwrap();
+
+        // A missing file anywhere in the batch fails the whole dry run
+        let result = dry_run_batch_command(
+            vec![
+                good.to_string_lossy().into_owned(),
+                temp_dir
+                    .path()
+                    .join("missing.bin")
+                    .to_string_lossy()
+                    .into_owned(),
+            ],
+            &peer,
+            "standard",
+            &config,
+        );
+        assert!(result.unwrap_err().to_string().contains("not found"));
+    }
+
     #[test]
     fn test_encrypt_decrypt_private_key_roundtrip() {
         let mut rng = rand_core::OsRng;
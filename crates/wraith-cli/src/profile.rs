@@ -0,0 +1,287 @@
+//! Per-network configuration profiles with automatic selection.
+//!
+//! One config file can carry different relays, obfuscation levels, and
+//! bandwidth caps per physical network (home/work/public) as named
+//! [`NetworkProfile`] entries. Selection order:
+//!
+//! 1. `active_profile` pinned via `wraith profile use <name>`
+//! 2. The first profile (in name order) whose match criteria fit the
+//!    detected network: gateway MAC, Wi-Fi SSID, or gateway subnet
+//!
+//! Detection is Linux-specific and best-effort: the default gateway comes
+//! from `/proc/net/route`, its MAC from `/proc/net/arp`, and the SSID from
+//! `iwgetid -r` when the tool is installed. Information that cannot be
+//! gathered simply fails the corresponding match criterion, so a laptop
+//! without wireless tools still matches on gateway MAC or subnet.
+
+use crate::config::{Config, NetworkProfile};
+use std::net::Ipv4Addr;
+
+/// Network identity observed at startup
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DetectedNetwork {
+    /// Default gateway IPv4 address
+    pub gateway_ip: Option<Ipv4Addr>,
+    /// Default gateway MAC address (lowercase, colon-separated)
+    pub gateway_mac: Option<String>,
+    /// Wi-Fi SSID of the active interface
+    pub ssid: Option<String>,
+}
+
+impl NetworkProfile {
+    /// Whether any of this profile's criteria match the detected network
+    ///
+    /// A profile with no criteria never matches automatically; it can only
+    /// be selected with `wraith profile use`.
+    #[must_use]
+    pub fn matches(&self, network: &DetectedNetwork) -> bool {
+        if let Some(mac) = &self.match_gateway_mac
+            && network.gateway_mac.as_deref() == Some(mac.to_lowercase().as_str())
+        {
+            return true;
+        }
+
+        if let Some(ssid) = &self.match_ssid
+            && network.ssid.as_deref() == Some(ssid.as_str())
+        {
+            return true;
+        }
+
+        if let Some(subnet) = &self.match_subnet
+            && let Some((net, prefix)) = parse_cidr(subnet)
+            && let Some(gateway) = network.gateway_ip
+            && subnet_contains(net, prefix, gateway)
+        {
+            return true;
+        }
+
+        false
+    }
+}
+
+/// Resolve which profile applies: the manual pin first, then detection
+///
+/// Returns `None` when no profile is pinned and nothing matches; the base
+/// configuration is then used unchanged.
+#[must_use]
+pub fn select_profile(config: &Config) -> Option<String> {
+    if let Some(name) = &config.active_profile {
+        return Some(name.clone());
+    }
+    if config.profiles.is_empty() {
+        return None;
+    }
+
+    let network = detect_network();
+    config
+        .profiles
+        .iter()
+        .find(|(_, profile)| profile.matches(&network))
+        .map(|(name, _)| name.clone())
+}
+
+/// Gather the current network's identity for profile matching
+#[must_use]
+pub fn detect_network() -> DetectedNetwork {
+    let gateway_ip = default_gateway();
+    DetectedNetwork {
+        gateway_ip,
+        gateway_mac: gateway_ip.and_then(gateway_mac),
+        ssid: wifi_ssid(),
+    }
+}
+
+/// Parse a CIDR string ("192.168.1.0/24") into (network, prefix length)
+#[must_use]
+pub fn parse_cidr(cidr: &str) -> Option<(Ipv4Addr, u8)> {
+    let (addr, prefix) = cidr.split_once('/')?;
+    let addr: Ipv4Addr = addr.parse().ok()?;
+    let prefix: u8 = prefix.parse().ok()?;
+    if prefix > 32 {
+        return None;
+    }
+    Some((addr, prefix))
+}
+
+/// Whether `ip` lies within the subnet `net/prefix`
+fn subnet_contains(net: Ipv4Addr, prefix: u8, ip: Ipv4Addr) -> bool {
+    let mask = if prefix == 0 {
+        0
+    } else {
+        u32::MAX << (32 - u32::from(prefix))
+    };
+    (u32::from(net) & mask) == (u32::from(ip) & mask)
+}
+
+/// Whether `mac` looks like a colon-separated MAC address
+#[must_use]
+pub fn is_valid_mac(mac: &str) -> bool {
+    let parts: Vec<&str> = mac.split(':').collect();
+    parts.len() == 6
+        && parts
+            .iter()
+            .all(|p| p.len() == 2 && p.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Default IPv4 gateway from `/proc/net/route`
+///
+/// The route table stores addresses as little-endian hex; the default route
+/// has destination 00000000.
+fn default_gateway() -> Option<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route").ok()?;
+    for line in routes.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() >= 3 && fields[1] == "00000000" {
+            let raw = u32::from_str_radix(fields[2], 16).ok()?;
+            return Some(Ipv4Addr::from(raw.swap_bytes()));
+        }
+    }
+    None
+}
+
+/// MAC address of `gateway` from the ARP cache
+fn gateway_mac(gateway: Ipv4Addr) -> Option<String> {
+    let arp = std::fs::read_to_string("/proc/net/arp").ok()?;
+    let gateway = gateway.to_string();
+    for line in arp.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() >= 4 && fields[0] == gateway && fields[3] != "00:00:00:00:00:00" {
+            return Some(fields[3].to_lowercase());
+        }
+    }
+    None
+}
+
+/// SSID of the active wireless interface via `iwgetid -r`
+///
+/// Returns `None` when the tool is missing or no wireless link is up.
+fn wifi_ssid() -> Option<String> {
+    let output = std::process::Command::new("iwgetid")
+        .arg("-r")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let ssid = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    if ssid.is_empty() { None } else { Some(ssid) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(mac: Option<&str>, ssid: Option<&str>, subnet: Option<&str>) -> NetworkProfile {
+        NetworkProfile {
+            match_gateway_mac: mac.map(String::from),
+            match_ssid: ssid.map(String::from),
+            match_subnet: subnet.map(String::from),
+            ..NetworkProfile::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_cidr() {
+        assert_eq!(
+            parse_cidr("192.168.1.0/24"),
+            Some((Ipv4Addr::new(192, 168, 1, 0), 24))
+        );
+        assert_eq!(
+            parse_cidr("10.0.0.0/8"),
+            Some((Ipv4Addr::new(10, 0, 0, 0), 8))
+        );
+        assert_eq!(parse_cidr("192.168.1.0"), None);
+        assert_eq!(parse_cidr("192.168.1.0/33"), None);
+        assert_eq!(parse_cidr("not-an-ip/24"), None);
+    }
+
+    #[test]
+    fn test_is_valid_mac() {
+        assert!(is_valid_mac("aa:bb:cc:dd:ee:ff"));
+        assert!(is_valid_mac("AA:BB:CC:DD:EE:FF"));
+        assert!(!is_valid_mac("aa:bb:cc:dd:ee"));
+        assert!(!is_valid_mac("aa-bb-cc-dd-ee-ff"));
+        assert!(!is_valid_mac("zz:bb:cc:dd:ee:ff"));
+    }
+
+    #[test]
+    fn test_match_gateway_mac_case_insensitive() {
+        let network = DetectedNetwork {
+            gateway_mac: Some("aa:bb:cc:dd:ee:ff".to_string()),
+            ..DetectedNetwork::default()
+        };
+        assert!(profile(Some("AA:BB:CC:DD:EE:FF"), None, None).matches(&network));
+        assert!(!profile(Some("11:22:33:44:55:66"), None, None).matches(&network));
+    }
+
+    #[test]
+    fn test_match_ssid() {
+        let network = DetectedNetwork {
+            ssid: Some("HomeWifi".to_string()),
+            ..DetectedNetwork::default()
+        };
+        assert!(profile(None, Some("HomeWifi"), None).matches(&network));
+        // SSIDs are case-sensitive
+        assert!(!profile(None, Some("homewifi"), None).matches(&network));
+    }
+
+    #[test]
+    fn test_match_subnet() {
+        let network = DetectedNetwork {
+            gateway_ip: Some(Ipv4Addr::new(192, 168, 1, 1)),
+            ..DetectedNetwork::default()
+        };
+        assert!(profile(None, None, Some("192.168.1.0/24")).matches(&network));
+        assert!(profile(None, None, Some("192.168.0.0/16")).matches(&network));
+        assert!(!profile(None, None, Some("10.0.0.0/8")).matches(&network));
+    }
+
+    #[test]
+    fn test_profile_without_criteria_never_auto_matches() {
+        let network = DetectedNetwork {
+            gateway_ip: Some(Ipv4Addr::new(192, 168, 1, 1)),
+            gateway_mac: Some("aa:bb:cc:dd:ee:ff".to_string()),
+            ssid: Some("HomeWifi".to_string()),
+        };
+        assert!(!profile(None, None, None).matches(&network));
+    }
+
+    #[test]
+    fn test_select_profile_prefers_manual_pin() {
+        let mut config = Config::default();
+        config
+            .profiles
+            .insert("work".to_string(), profile(None, None, None));
+        config.active_profile = Some("work".to_string());
+        assert_eq!(select_profile(&config), Some("work".to_string()));
+    }
+
+    #[test]
+    fn test_select_profile_none_without_profiles() {
+        assert_eq!(select_profile(&Config::default()), None);
+    }
+
+    #[test]
+    fn test_apply_profile_overrides() {
+        let mut config = Config::default();
+        config.profiles.insert(
+            "public".to_string(),
+            NetworkProfile {
+                relay_servers: Some(vec!["relay.example.com:3478".to_string()]),
+                obfuscation_level: Some("paranoid".to_string()),
+                bandwidth_limit: Some("1MB/s".to_string()),
+                ..NetworkProfile::default()
+            },
+        );
+
+        config.apply_profile("public").unwrap();
+        assert_eq!(
+            config.discovery.relay_servers,
+            vec!["relay.example.com:3478".to_string()]
+        );
+        assert_eq!(config.obfuscation.default_level, "paranoid");
+        assert_eq!(config.transfer.bandwidth_limit.as_deref(), Some("1MB/s"));
+
+        assert!(config.apply_profile("missing").is_err());
+    }
+}
@@ -1,8 +1,25 @@
 //! Transfer progress display with progress bars.
 
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
+/// When set, progress bars render to a hidden target (for `--quiet`)
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Suppress progress bar rendering (used by `--quiet`)
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
+fn draw_target() -> ProgressDrawTarget {
+    if QUIET.load(Ordering::Relaxed) {
+        ProgressDrawTarget::hidden()
+    } else {
+        ProgressDrawTarget::stderr()
+    }
+}
+
 /// Transfer progress tracker
 pub struct TransferProgress {
     bar: ProgressBar,
@@ -11,8 +28,9 @@ pub struct TransferProgress {
 impl TransferProgress {
     /// Create a new progress tracker
     #[must_use]
+    #[allow(dead_code)]
     pub fn new(total_bytes: u64, filename: &str) -> Self {
-        let bar = ProgressBar::new(total_bytes);
+        let bar = ProgressBar::with_draw_target(Some(total_bytes), draw_target());
 
         bar.set_style(
             ProgressStyle::default_bar()
@@ -45,6 +63,7 @@ impl TransferProgress {
     }
 
     /// Finish with custom message
+    #[allow(dead_code)]
     pub fn finish_with_message(&self, msg: String) {
         self.bar.finish_with_message(msg);
     }
@@ -56,6 +75,113 @@ impl TransferProgress {
     }
 }
 
+/// Multi-bar progress display for multi-file and multi-peer transfers
+///
+/// Renders one aggregate bar plus one bar per transfer. Per-transfer speeds
+/// and ETAs come from the node's measured transfer progress, not from the
+/// display's own update cadence.
+pub struct MultiTransferProgress {
+    multi: MultiProgress,
+    aggregate: ProgressBar,
+    bars: Vec<ProgressBar>,
+}
+
+impl MultiTransferProgress {
+    /// Create a multi-transfer display with the given aggregate total
+    #[must_use]
+    pub fn new(total_bytes: u64) -> Self {
+        let multi = MultiProgress::with_draw_target(draw_target());
+
+        let aggregate = multi.add(ProgressBar::new(total_bytes));
+        aggregate.set_style(
+            ProgressStyle::default_bar()
+                .template("{prefix:>12} [{elapsed_precise}] [{wide_bar:.green/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})")
+                .expect("Invalid progress bar template")
+                .progress_chars("#>-"),
+        );
+        aggregate.set_prefix("Total");
+
+        Self {
+            multi,
+            aggregate,
+            bars: Vec::new(),
+        }
+    }
+
+    /// Add a bar for one transfer, labeled e.g. with a filename or peer ID
+    ///
+    /// Returns the index used to address this transfer in later updates.
+    pub fn add_transfer(&mut self, label: &str, total_bytes: u64) -> usize {
+        let bar = self.multi.add(ProgressBar::new(total_bytes));
+        bar.set_style(
+            ProgressStyle::default_bar()
+                .template("{prefix:>12} [{wide_bar:.cyan/blue}] {bytes}/{total_bytes} {msg}")
+                .expect("Invalid progress bar template")
+                .progress_chars("#>-"),
+        );
+        bar.set_prefix(truncate_label(label, 12));
+
+        self.bars.push(bar);
+        self.bars.len() - 1
+    }
+
+    /// Update one transfer with node-reported bytes, speed, and ETA
+    pub fn update_transfer(
+        &self,
+        index: usize,
+        bytes_sent: u64,
+        speed_bytes_per_sec: f64,
+        eta: Option<Duration>,
+    ) {
+        let Some(bar) = self.bars.get(index) else {
+            return;
+        };
+
+        bar.set_position(bytes_sent);
+
+        let mut msg = format!("({}", format_speed(speed_bytes_per_sec));
+        if let Some(eta) = eta {
+            msg.push_str(&format!(", {}", format_duration(eta)));
+        }
+        msg.push(')');
+        bar.set_message(msg);
+    }
+
+    /// Update the aggregate bar with the total bytes sent across transfers
+    pub fn update_aggregate(&self, total_bytes_sent: u64) {
+        self.aggregate.set_position(total_bytes_sent);
+    }
+
+    /// Finish one transfer's bar with a status message
+    pub fn finish_transfer(&self, index: usize, msg: String) {
+        if let Some(bar) = self.bars.get(index) {
+            bar.finish_with_message(msg);
+        }
+    }
+
+    /// Finish the aggregate bar with a summary message
+    pub fn finish(&self, msg: String) {
+        self.aggregate.finish_with_message(msg);
+    }
+
+    /// Number of per-transfer bars
+    #[must_use]
+    #[allow(dead_code)]
+    pub fn transfer_count(&self) -> usize {
+        self.bars.len()
+    }
+}
+
+/// Truncate a bar label to `max` characters, appending `…` when cut
+fn truncate_label(label: &str, max: usize) -> String {
+    if label.chars().count() <= max {
+        label.to_string()
+    } else {
+        let truncated: String = label.chars().take(max.saturating_sub(1)).collect();
+        format!("{truncated}…")
+    }
+}
+
 /// Format bytes in human-readable format
 ///
 /// # Example
@@ -340,6 +466,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_multi_transfer_progress_workflow() {
+        let mut multi = MultiTransferProgress::new(2048);
+
+        let a = multi.add_transfer("file-a.txt", 1024);
+        let b = multi.add_transfer("file-b.txt", 1024);
+        assert_eq!(multi.transfer_count(), 2);
+
+        multi.update_transfer(a, 512, 1024.0, Some(Duration::from_secs(1)));
+        multi.update_transfer(b, 256, 512.0, None);
+        multi.update_aggregate(768);
+
+        multi.finish_transfer(a, "done".to_string());
+        multi.finish_transfer(b, "done".to_string());
+        multi.finish("All transfers complete".to_string());
+    }
+
+    #[test]
+    fn test_multi_transfer_progress_out_of_range_index() {
+        let multi = MultiTransferProgress::new(1024);
+
+        // Updates to unknown indices are ignored, not panics
+        multi.update_transfer(5, 100, 0.0, None);
+        multi.finish_transfer(5, "ignored".to_string());
+    }
+
+    #[test]
+    fn test_truncate_label() {
+        assert_eq!(truncate_label("short", 12), "short");
+        assert_eq!(truncate_label("exactly12chr", 12), "exactly12chr");
+        assert_eq!(
+            truncate_label("a_rather_long_filename.txt", 12),
+            "a_rather_lo…"
+        );
+        // Multi-byte characters are counted, not sliced mid-codepoint
+        assert_eq!(truncate_label("文件名", 12), "文件名");
+    }
+
     #[test]
     fn test_format_bytes_consistency() {
         // Verify that format_bytes and format_speed are consistent
@@ -0,0 +1,377 @@
+//! Offline frame replay for regression debugging.
+//!
+//! `wraith replay <capture>` feeds captured frames through the frame
+//! parser and session state machine without touching the network, so a
+//! parser or state bug reported from the field can be reproduced
+//! deterministically from the submitted capture.
+//!
+//! Two capture formats are accepted:
+//!
+//! - **hex log** - one frame per line as hex (whitespace ignored),
+//!   `#` comments and blank lines skipped; this is the format produced
+//!   by frame-level debug logging
+//! - **pcap** - classic libpcap files (Ethernet, Linux SLL, raw IP, or
+//!   loopback link types); the UDP payload of each packet is replayed
+//!
+//! The format is detected from the pcap magic number. Replay output is
+//! printed unconditionally (it is the product of the command), with one
+//! line per frame and a summary including the final session state.
+
+use anyhow::{Context, bail};
+use std::collections::HashSet;
+use std::path::Path;
+use wraith_core::{Frame, FrameType, HandshakePhase, Session, SessionConfig, SessionState};
+
+/// Outcome of replaying one capture
+struct ReplaySummary {
+    /// Frames parsed successfully
+    parsed: usize,
+    /// Frames rejected by the parser
+    parse_errors: usize,
+    /// Stream state violations (duplicate open, close without open)
+    stream_errors: usize,
+    /// Final session state after the capture
+    final_state: SessionState,
+}
+
+/// Replay a capture file through the parser and session state machine
+///
+/// With `fail_fast` the first parse error aborts the replay, which is
+/// convenient when bisecting a capture. Returns an error (non-zero exit)
+/// if any frame failed to parse or violated stream state.
+pub fn run_replay(capture: &str, fail_fast: bool) -> anyhow::Result<()> {
+    let path = Path::new(capture);
+    let data = std::fs::read(path).with_context(|| format!("Failed to read capture {capture}"))?;
+
+    let frames = if is_pcap(&data) {
+        println!("Capture format: pcap");
+        extract_pcap_payloads(&data)?
+    } else {
+        println!("Capture format: hex log");
+        parse_hex_capture(&data)?
+    };
+    println!("Replaying {} frame(s) from {capture}", frames.len());
+    println!();
+
+    let summary = replay_frames(&frames, fail_fast)?;
+
+    println!();
+    println!("Replay summary:");
+    println!("  Frames parsed:   {}", summary.parsed);
+    println!("  Parse errors:    {}", summary.parse_errors);
+    println!("  Stream errors:   {}", summary.stream_errors);
+    println!("  Final state:     {:?}", summary.final_state);
+
+    if summary.parse_errors > 0 || summary.stream_errors > 0 {
+        bail!(
+            "replay found {} parse error(s) and {} stream error(s)",
+            summary.parse_errors,
+            summary.stream_errors
+        );
+    }
+    Ok(())
+}
+
+/// Feed each frame through the parser and a responder session
+fn replay_frames(frames: &[Vec<u8>], fail_fast: bool) -> anyhow::Result<ReplaySummary> {
+    // A responder session mirrors what a receiving node would hold; the
+    // capture starts after the handshake, so begin established
+    let mut session = Session::new_responder(SessionConfig::default());
+    let _ = session.transition_to(SessionState::Handshaking(HandshakePhase::RespSent));
+    let _ = session.transition_to(SessionState::Established);
+
+    let mut summary = ReplaySummary {
+        parsed: 0,
+        parse_errors: 0,
+        stream_errors: 0,
+        final_state: session.state(),
+    };
+    let mut open_streams: HashSet<u16> = HashSet::new();
+
+    for (index, bytes) in frames.iter().enumerate() {
+        let frame = match Frame::parse(bytes) {
+            Ok(frame) => frame,
+            Err(e) => {
+                println!("frame {index:>5}: PARSE ERROR: {e} ({} bytes)", bytes.len());
+                summary.parse_errors += 1;
+                if fail_fast {
+                    bail!("replay aborted at frame {index}: {e}");
+                }
+                continue;
+            }
+        };
+
+        let frame_type = frame.frame_type();
+        println!(
+            "frame {index:>5}: {frame_type:?} stream={} seq={} offset={} payload={}B",
+            frame.stream_id(),
+            frame.sequence(),
+            frame.offset(),
+            frame.payload().len(),
+        );
+
+        summary.parsed += 1;
+        session.record_frame_received(frame_type);
+        session.record_received(bytes.len() as u64);
+
+        // Stream bookkeeping: the violations this catches (double open,
+        // close or data on a stream that was never opened) are exactly
+        // the state bugs field captures tend to exhibit
+        match frame_type {
+            FrameType::StreamOpen if !open_streams.insert(frame.stream_id()) => {
+                println!(
+                    "frame {index:>5}: STREAM ERROR: duplicate open of stream {}",
+                    frame.stream_id()
+                );
+                summary.stream_errors += 1;
+            }
+            FrameType::StreamClose if !open_streams.remove(&frame.stream_id()) => {
+                println!(
+                    "frame {index:>5}: STREAM ERROR: close of unopened stream {}",
+                    frame.stream_id()
+                );
+                summary.stream_errors += 1;
+            }
+            FrameType::Close => {
+                let _ = session.transition_to(SessionState::Draining);
+                let _ = session.transition_to(SessionState::Closed);
+            }
+            _ => {}
+        }
+    }
+
+    summary.final_state = session.state();
+    Ok(summary)
+}
+
+/// Whether the file starts with a classic libpcap magic number
+fn is_pcap(data: &[u8]) -> bool {
+    if data.len() < 4 {
+        return false;
+    }
+    let magic = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+    matches!(magic, 0xa1b2_c3d4 | 0xd4c3_b2a1 | 0xa1b2_3c4d | 0x4d3c_b2a1)
+}
+
+/// Parse a hex log: one frame per line, `#` comments and blanks skipped
+fn parse_hex_capture(data: &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+    let text = std::str::from_utf8(data).context("hex capture is not valid UTF-8")?;
+    let mut frames = Vec::new();
+
+    for (lineno, line) in text.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or_default();
+        let compact: String = line.split_whitespace().collect();
+        if compact.is_empty() {
+            continue;
+        }
+        let bytes =
+            hex::decode(&compact).with_context(|| format!("invalid hex on line {}", lineno + 1))?;
+        frames.push(bytes);
+    }
+    Ok(frames)
+}
+
+/// Extract UDP payloads from a classic libpcap file
+///
+/// Supports the link types WRAITH captures actually occur with:
+/// Ethernet (1), loopback/NULL (0), raw IP (101), and Linux SLL (113).
+fn extract_pcap_payloads(data: &[u8]) -> anyhow::Result<Vec<Vec<u8>>> {
+    if data.len() < 24 {
+        bail!("pcap file truncated (no global header)");
+    }
+
+    let magic = u32::from_le_bytes([data[0], data[1], data[2], data[3]]);
+    // Swapped magics mean the file was written big-endian
+    let big_endian = matches!(magic, 0xd4c3_b2a1 | 0x4d3c_b2a1);
+    let read_u32 = |bytes: &[u8]| -> u32 {
+        let arr = [bytes[0], bytes[1], bytes[2], bytes[3]];
+        if big_endian {
+            u32::from_be_bytes(arr)
+        } else {
+            u32::from_le_bytes(arr)
+        }
+    };
+
+    let link_type = read_u32(&data[20..24]);
+    let mut payloads = Vec::new();
+    let mut pos = 24;
+
+    while pos + 16 <= data.len() {
+        let incl_len = read_u32(&data[pos + 8..pos + 12]) as usize;
+        pos += 16;
+        if pos + incl_len > data.len() {
+            bail!("pcap record truncated at offset {pos}");
+        }
+        let packet = &data[pos..pos + incl_len];
+        pos += incl_len;
+
+        if let Some(payload) = udp_payload(packet, link_type) {
+            payloads.push(payload.to_vec());
+        }
+    }
+    Ok(payloads)
+}
+
+/// UDP payload of one captured packet, if it is IPv4/UDP
+fn udp_payload(packet: &[u8], link_type: u32) -> Option<&[u8]> {
+    // Strip the link-layer header down to the IP header
+    let ip = match link_type {
+        1 => packet.get(14..)?,   // Ethernet
+        0 => packet.get(4..)?,    // NULL/loopback (4-byte address family)
+        101 => packet,            // Raw IP
+        113 => packet.get(16..)?, // Linux cooked capture (SLL)
+        _ => return None,
+    };
+
+    // IPv4 with protocol UDP (17)
+    if ip.first()? >> 4 != 4 {
+        return None;
+    }
+    let ihl = (ip.first()? & 0x0F) as usize * 4;
+    if *ip.get(9)? != 17 {
+        return None;
+    }
+
+    // Skip the 8-byte UDP header, honoring the UDP length field
+    let udp = ip.get(ihl..)?;
+    let udp_len = u16::from_be_bytes([*udp.get(4)?, *udp.get(5)?]) as usize;
+    udp.get(8..udp_len.min(udp.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wraith_core::FrameBuilder;
+
+    fn data_frame(stream_id: u16, seq: u32) -> Vec<u8> {
+        FrameBuilder::new()
+            .frame_type(FrameType::Data)
+            .stream_id(stream_id)
+            .sequence(seq)
+            .payload(b"payload")
+            .build(64)
+            .unwrap()
+    }
+
+    fn stream_frame(frame_type: FrameType, stream_id: u16) -> Vec<u8> {
+        FrameBuilder::new()
+            .frame_type(frame_type)
+            .stream_id(stream_id)
+            .payload(&[])
+            .build(64)
+            .unwrap()
+    }
+
+    /// Build a minimal little-endian pcap with raw-IP link type
+    fn pcap_with_payloads(payloads: &[&[u8]]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+        out.extend_from_slice(&[0x02, 0x00, 0x04, 0x00]); // version 2.4
+        out.extend_from_slice(&[0u8; 8]); // thiszone + sigfigs
+        out.extend_from_slice(&65535u32.to_le_bytes()); // snaplen
+        out.extend_from_slice(&101u32.to_le_bytes()); // link type: raw IP
+
+        for payload in payloads {
+            let udp_len = 8 + payload.len();
+            let ip_len = 20 + udp_len;
+
+            let mut packet = Vec::new();
+            packet.push(0x45); // IPv4, IHL 5
+            packet.push(0);
+            packet.extend_from_slice(&(ip_len as u16).to_be_bytes());
+            packet.extend_from_slice(&[0u8; 4]); // id + frag
+            packet.push(64); // TTL
+            packet.push(17); // UDP
+            packet.extend_from_slice(&[0u8; 2]); // checksum
+            packet.extend_from_slice(&[127, 0, 0, 1, 127, 0, 0, 1]);
+            packet.extend_from_slice(&41888u16.to_be_bytes());
+            packet.extend_from_slice(&41889u16.to_be_bytes());
+            packet.extend_from_slice(&(udp_len as u16).to_be_bytes());
+            packet.extend_from_slice(&[0u8; 2]); // checksum
+            packet.extend_from_slice(payload);
+
+            out.extend_from_slice(&[0u8; 8]); // timestamp
+            out.extend_from_slice(&(packet.len() as u32).to_le_bytes());
+            out.extend_from_slice(&(packet.len() as u32).to_le_bytes());
+            out.extend_from_slice(&packet);
+        }
+        out
+    }
+
+    #[test]
+    fn test_hex_capture_parsing() {
+        let capture = "# comment line\nDEADBEEF\n\nCA FE BA BE # trailing comment\n";
+        let frames = parse_hex_capture(capture.as_bytes()).unwrap();
+        assert_eq!(
+            frames,
+            vec![vec![0xDE, 0xAD, 0xBE, 0xEF], vec![0xCA, 0xFE, 0xBA, 0xBE]]
+        );
+    }
+
+    #[test]
+    fn test_hex_capture_rejects_bad_hex() {
+        assert!(parse_hex_capture(b"not hex").is_err());
+    }
+
+    #[test]
+    fn test_pcap_detection() {
+        assert!(is_pcap(&0xa1b2_c3d4u32.to_le_bytes()));
+        assert!(is_pcap(&0xa1b2_c3d4u32.to_be_bytes()));
+        assert!(!is_pcap(b"DEADBEEF"));
+    }
+
+    #[test]
+    fn test_pcap_payload_extraction() {
+        let frame = data_frame(16, 1);
+        let pcap = pcap_with_payloads(&[&frame, &frame]);
+        let payloads = extract_pcap_payloads(&pcap).unwrap();
+        assert_eq!(payloads.len(), 2);
+        assert_eq!(payloads[0], frame);
+    }
+
+    #[test]
+    fn test_replay_counts_valid_frames() {
+        let frames = vec![
+            stream_frame(FrameType::StreamOpen, 16),
+            data_frame(16, 1),
+            data_frame(16, 2),
+            stream_frame(FrameType::StreamClose, 16),
+        ];
+        let summary = replay_frames(&frames, false).unwrap();
+        assert_eq!(summary.parsed, 4);
+        assert_eq!(summary.parse_errors, 0);
+        assert_eq!(summary.stream_errors, 0);
+    }
+
+    #[test]
+    fn test_replay_flags_parse_errors() {
+        let frames = vec![vec![0u8; 4], data_frame(16, 1)];
+        let summary = replay_frames(&frames, false).unwrap();
+        assert_eq!(summary.parsed, 1);
+        assert_eq!(summary.parse_errors, 1);
+    }
+
+    #[test]
+    fn test_replay_fail_fast_aborts() {
+        let frames = vec![vec![0u8; 4], data_frame(16, 1)];
+        assert!(replay_frames(&frames, true).is_err());
+    }
+
+    #[test]
+    fn test_replay_flags_stream_violations() {
+        let frames = vec![
+            stream_frame(FrameType::StreamOpen, 16),
+            stream_frame(FrameType::StreamOpen, 16),
+            stream_frame(FrameType::StreamClose, 17),
+        ];
+        let summary = replay_frames(&frames, false).unwrap();
+        assert_eq!(summary.stream_errors, 2);
+    }
+
+    #[test]
+    fn test_replay_close_frame_closes_session() {
+        let frames = vec![stream_frame(FrameType::Close, 0)];
+        let summary = replay_frames(&frames, false).unwrap();
+        assert_eq!(summary.final_state, SessionState::Closed);
+    }
+}
@@ -0,0 +1,370 @@
+//! OS service integration for persistent daemons (`wraith daemon install`).
+//!
+//! Linux deployments run the daemon under systemd with a unit file of
+//! their own; this module covers the two desktop platforms without that
+//! convention:
+//!
+//! - **macOS** writes a LaunchAgent plist under `~/Library/LaunchAgents`
+//!   and loads it with `launchctl`, so the daemon starts at login, is
+//!   restarted if it dies, and logs to `~/Library/Logs/wraith/`
+//! - **Windows** registers the binary with the service control manager as
+//!   an auto-start service; the daemon re-enters through the service
+//!   dispatcher (`wraith daemon --service`, hidden), answers SCM stop and
+//!   shutdown controls, and logs to `%PROGRAMDATA%\wraith\logs\`
+//!
+//! `install` bakes the `--bind` and `--relay` arguments it was invoked
+//! with into the registration; re-running it after an `uninstall` changes
+//! them.
+
+#![allow(dead_code)] // each platform compiles only its own section
+
+#[cfg(any(windows, target_os = "macos"))]
+use anyhow::Context;
+
+// ═══════════════════════════════════════════════════════════════════════════
+// macOS: LaunchAgent
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// LaunchAgent label (also the plist file stem)
+#[cfg(target_os = "macos")]
+const LAUNCH_AGENT_LABEL: &str = "org.wraith.daemon";
+
+/// Install and load the daemon LaunchAgent
+#[cfg(target_os = "macos")]
+pub fn install(bind: &str, relay: bool) -> anyhow::Result<()> {
+    let home = dirs::home_dir().context("Cannot determine home directory")?;
+    let agents_dir = home.join("Library/LaunchAgents");
+    let log_dir = home.join("Library/Logs/wraith");
+    std::fs::create_dir_all(&agents_dir)
+        .with_context(|| format!("Failed to create {}", agents_dir.display()))?;
+    std::fs::create_dir_all(&log_dir)
+        .with_context(|| format!("Failed to create {}", log_dir.display()))?;
+
+    let exe = std::env::current_exe().context("Cannot determine executable path")?;
+    let log_file = log_dir.join("daemon.log");
+
+    let mut arguments = vec![
+        xml_escape(&exe.display().to_string()),
+        "daemon".to_string(),
+        "--bind".to_string(),
+        xml_escape(bind),
+    ];
+    if relay {
+        arguments.push("--relay".to_string());
+    }
+    let arguments: String = arguments
+        .iter()
+        .map(|a| format!("        <string>{a}</string>\n"))
+        .collect();
+
+    let plist = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{LAUNCH_AGENT_LABEL}</string>
+    <key>ProgramArguments</key>
+    <array>
+{arguments}    </array>
+    <key>RunAtLoad</key>
+    <true/>
+    <key>KeepAlive</key>
+    <true/>
+    <key>StandardOutPath</key>
+    <string>{log}</string>
+    <key>StandardErrorPath</key>
+    <string>{log}</string>
+</dict>
+</plist>
+"#,
+        log = xml_escape(&log_file.display().to_string()),
+    );
+
+    let plist_path = agents_dir.join(format!("{LAUNCH_AGENT_LABEL}.plist"));
+    std::fs::write(&plist_path, plist)
+        .with_context(|| format!("Failed to write {}", plist_path.display()))?;
+
+    run_launchctl(&["load", "-w"], &plist_path)?;
+
+    println!("LaunchAgent installed: {}", plist_path.display());
+    println!("Logs: {}", log_file.display());
+    println!("The daemon is running and will start automatically at login");
+    Ok(())
+}
+
+/// Unload and remove the daemon LaunchAgent
+#[cfg(target_os = "macos")]
+pub fn uninstall() -> anyhow::Result<()> {
+    let home = dirs::home_dir().context("Cannot determine home directory")?;
+    let plist_path = home.join(format!("Library/LaunchAgents/{LAUNCH_AGENT_LABEL}.plist"));
+
+    if !plist_path.exists() {
+        anyhow::bail!("LaunchAgent is not installed ({})", plist_path.display());
+    }
+
+    run_launchctl(&["unload", "-w"], &plist_path)?;
+    std::fs::remove_file(&plist_path)
+        .with_context(|| format!("Failed to remove {}", plist_path.display()))?;
+
+    println!("LaunchAgent removed: {}", plist_path.display());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn run_launchctl(args: &[&str], plist_path: &std::path::Path) -> anyhow::Result<()> {
+    let output = std::process::Command::new("launchctl")
+        .args(args)
+        .arg(plist_path)
+        .output()
+        .context("Failed to run launchctl")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "launchctl {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Escape the XML special characters that can appear in paths and addresses
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Windows: service control manager
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[cfg(windows)]
+mod windows_impl {
+    use super::*;
+    use std::ffi::{OsStr, OsString};
+    use std::path::PathBuf;
+    use std::sync::OnceLock;
+    use std::time::Duration;
+    use windows_service::service::{
+        ServiceAccess, ServiceControl, ServiceControlAccept, ServiceErrorControl, ServiceExitCode,
+        ServiceInfo, ServiceStartType, ServiceState, ServiceStatus, ServiceType,
+    };
+    use windows_service::service_control_handler::{self, ServiceControlHandlerResult};
+    use windows_service::service_manager::{ServiceManager, ServiceManagerAccess};
+    use windows_service::{define_windows_service, service_dispatcher};
+
+    /// SCM service name
+    const SERVICE_NAME: &str = "wraithd";
+    /// Name shown in the services console
+    const SERVICE_DISPLAY_NAME: &str = "WRAITH Daemon";
+
+    /// Arguments captured before the dispatcher takes over the process
+    static SERVICE_ARGS: OnceLock<(String, bool)> = OnceLock::new();
+
+    /// Where service logs go (`%PROGRAMDATA%\wraith\logs`)
+    fn log_dir() -> PathBuf {
+        let program_data =
+            std::env::var_os("PROGRAMDATA").unwrap_or_else(|| OsString::from(r"C:\ProgramData"));
+        PathBuf::from(program_data).join("wraith").join("logs")
+    }
+
+    /// Register the daemon as an auto-start SCM service and start it
+    pub fn install(bind: &str, relay: bool) -> anyhow::Result<()> {
+        let manager = ServiceManager::local_computer(
+            None::<&str>,
+            ServiceManagerAccess::CONNECT | ServiceManagerAccess::CREATE_SERVICE,
+        )
+        .context("Failed to connect to the service control manager (run as administrator)")?;
+
+        let mut launch_arguments = vec![
+            OsString::from("daemon"),
+            OsString::from("--service"),
+            OsString::from("--bind"),
+            OsString::from(bind),
+        ];
+        if relay {
+            launch_arguments.push(OsString::from("--relay"));
+        }
+
+        let info = ServiceInfo {
+            name: OsString::from(SERVICE_NAME),
+            display_name: OsString::from(SERVICE_DISPLAY_NAME),
+            service_type: ServiceType::OWN_PROCESS,
+            start_type: ServiceStartType::AutoStart,
+            error_control: ServiceErrorControl::Normal,
+            executable_path: std::env::current_exe().context("Cannot determine executable path")?,
+            launch_arguments,
+            dependencies: vec![],
+            account_name: None, // LocalSystem
+            account_password: None,
+        };
+
+        let service = manager
+            .create_service(&info, ServiceAccess::START)
+            .context("Failed to register the service (already installed?)")?;
+
+        let logs = log_dir();
+        std::fs::create_dir_all(&logs)
+            .with_context(|| format!("Failed to create {}", logs.display()))?;
+
+        service
+            .start(&[] as &[&OsStr])
+            .context("Service registered but failed to start")?;
+
+        println!("Service installed: {SERVICE_NAME} ({SERVICE_DISPLAY_NAME})");
+        println!("Logs: {}", logs.join("daemon.log").display());
+        println!("The daemon is running and will start automatically at boot");
+        Ok(())
+    }
+
+    /// Stop the SCM service and delete its registration
+    pub fn uninstall() -> anyhow::Result<()> {
+        let manager = ServiceManager::local_computer(None::<&str>, ServiceManagerAccess::CONNECT)
+            .context("Failed to connect to the service control manager")?;
+        let service = manager
+            .open_service(
+                SERVICE_NAME,
+                ServiceAccess::STOP | ServiceAccess::DELETE | ServiceAccess::QUERY_STATUS,
+            )
+            .context("Service is not installed")?;
+
+        // Best-effort stop; the service may already be stopped
+        if let Err(e) = service.stop() {
+            tracing::debug!("Service stop before delete failed: {e}");
+        }
+        service.delete().context("Failed to delete the service")?;
+
+        println!("Service removed: {SERVICE_NAME}");
+        Ok(())
+    }
+
+    /// Entry point when the SCM launches `wraith daemon --service`
+    ///
+    /// Must be called before anything touches the console: a service has
+    /// none, so logging is routed to the service log file, then the
+    /// process hands its main thread to the service dispatcher.
+    pub fn run_as_service(bind: String, relay: bool, log_level: &str) -> anyhow::Result<()> {
+        let _ = SERVICE_ARGS.set((bind, relay));
+
+        let logs = log_dir();
+        std::fs::create_dir_all(&logs)
+            .with_context(|| format!("Failed to create {}", logs.display()))?;
+        let log_file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(logs.join("daemon.log"))
+            .context("Failed to open service log file")?;
+        tracing_subscriber::fmt()
+            .with_env_filter(log_level)
+            .with_writer(log_file)
+            .with_ansi(false)
+            .init();
+
+        service_dispatcher::start(SERVICE_NAME, ffi_service_main)
+            .context("Failed to connect to the service dispatcher")?;
+        Ok(())
+    }
+
+    define_windows_service!(ffi_service_main, service_main);
+
+    fn service_main(_arguments: Vec<OsString>) {
+        if let Err(e) = run_service() {
+            tracing::error!("Service failed: {e}");
+        }
+    }
+
+    fn run_service() -> anyhow::Result<()> {
+        let (stop_tx, stop_rx) = std::sync::mpsc::channel::<()>();
+
+        let status_handle =
+            service_control_handler::register(SERVICE_NAME, move |control| match control {
+                ServiceControl::Stop | ServiceControl::Shutdown => {
+                    let _ = stop_tx.send(());
+                    ServiceControlHandlerResult::NoError
+                }
+                ServiceControl::Interrogate => ServiceControlHandlerResult::NoError,
+                _ => ServiceControlHandlerResult::NotImplemented,
+            })
+            .context("Failed to register the service control handler")?;
+
+        let set_state = |state: ServiceState, accepted: ServiceControlAccept| {
+            status_handle.set_service_status(ServiceStatus {
+                service_type: ServiceType::OWN_PROCESS,
+                current_state: state,
+                controls_accepted: accepted,
+                exit_code: ServiceExitCode::Win32(0),
+                checkpoint: 0,
+                wait_hint: Duration::from_secs(10),
+                process_id: None,
+            })
+        };
+        set_state(
+            ServiceState::Running,
+            ServiceControlAccept::STOP | ServiceControlAccept::SHUTDOWN,
+        )?;
+
+        let (bind, relay) = SERVICE_ARGS
+            .get()
+            .cloned()
+            .unwrap_or_else(|| ("0.0.0.0:0".to_string(), false));
+
+        let result = (|| -> anyhow::Result<()> {
+            let mut config = crate::Config::load_or_default()?;
+            config.validate()?;
+
+            let runtime = tokio::runtime::Runtime::new()?;
+            runtime.block_on(async move {
+                // The daemon runs until the SCM asks us to stop; dropping
+                // the daemon future tears the node down
+                let stopped = tokio::task::spawn_blocking(move || {
+                    let _ = stop_rx.recv();
+                });
+                tokio::select! {
+                    res = crate::run_daemon(bind, relay, false, &config) => res,
+                    _ = stopped => Ok(()),
+                }
+            })
+        })();
+
+        set_state(ServiceState::Stopped, ServiceControlAccept::empty())?;
+        result
+    }
+}
+
+#[cfg(windows)]
+pub use windows_impl::{install, run_as_service, uninstall};
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Other platforms
+// ═══════════════════════════════════════════════════════════════════════════
+
+/// Service installation is only meaningful on Windows and macOS
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn install(_bind: &str, _relay: bool) -> anyhow::Result<()> {
+    anyhow::bail!(
+        "`wraith daemon install` supports Windows and macOS; \
+         on Linux run the daemon under systemd"
+    );
+}
+
+/// Service removal is only meaningful on Windows and macOS
+#[cfg(not(any(windows, target_os = "macos")))]
+pub fn uninstall() -> anyhow::Result<()> {
+    anyhow::bail!(
+        "`wraith daemon uninstall` supports Windows and macOS; \
+         on Linux run the daemon under systemd"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("plain/path"), "plain/path");
+        assert_eq!(xml_escape("a<b>&c"), "a&lt;b&gt;&amp;c");
+    }
+}
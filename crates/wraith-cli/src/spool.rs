@@ -0,0 +1,357 @@
+//! Encrypted on-disk spool for the daemon's outgoing queue
+//!
+//! `wraith send --queue` records transfers whose recipient is offline —
+//! file path, peer, and send options — in this spool; the daemon replays
+//! them automatically once the peer appears in discovery. Entries are
+//! encrypted at rest with XChaCha20-Poly1305 under a random key stored
+//! next to the spool with owner-only permissions, so a backed-up or synced
+//! spool directory does not reveal who is being sent which files.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use chacha20poly1305::{KeyInit, XChaCha20Poly1305, aead::Aead};
+use rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// Magic header identifying an encrypted spool entry
+const SPOOL_MAGIC: &[u8; 8] = b"WRAITHQ1";
+
+/// XChaCha20-Poly1305 nonce size
+const NONCE_SIZE: usize = 24;
+
+/// File extension for spool entries
+const ENTRY_EXT: &str = "spool";
+
+/// Name of the spool encryption key file
+const KEY_FILE: &str = "spool.key";
+
+/// A queued outgoing transfer awaiting its recipient
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpoolEntry {
+    /// Path to the file to send
+    pub file_path: PathBuf,
+
+    /// Recipient peer ID
+    pub peer_id: [u8; 32],
+
+    /// Per-transfer bandwidth cap in bytes/sec, if one was requested
+    pub limit_bps: Option<u64>,
+
+    /// When the transfer was queued (seconds since epoch)
+    pub queued_at: u64,
+
+    /// Scheduling constraints: start time, allowed windows, rate profile
+    ///
+    /// Evaluated by the daemon's dispatcher against local time; absent for
+    /// plain `--queue` entries (dispatch as soon as the peer appears).
+    #[serde(default)]
+    pub schedule: Option<wraith_core::transfer::TransferSchedule>,
+}
+
+/// Encrypted spool directory holding queued transfers
+///
+/// Each entry is one `<id>.spool` file: the magic header, a random nonce,
+/// and the XChaCha20-Poly1305 ciphertext of the JSON-serialized
+/// [`SpoolEntry`]. The key lives in `spool.key` beside the entries and is
+/// generated on first use.
+pub struct Spool {
+    dir: PathBuf,
+    key: [u8; 32],
+}
+
+impl Spool {
+    /// Default spool location (`~/.wraith/spool`)
+    pub fn default_dir() -> PathBuf {
+        dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join(".wraith/spool")
+    }
+
+    /// Open a spool directory, creating it and its key on first use
+    pub fn open(dir: PathBuf) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create spool directory {}", dir.display()))?;
+
+        let key_path = dir.join(KEY_FILE);
+        let key = if key_path.exists() {
+            let bytes = std::fs::read(&key_path).context("Failed to read spool key")?;
+            let key: [u8; 32] = bytes
+                .as_slice()
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Corrupt spool key (expected 32 bytes)"))?;
+            key
+        } else {
+            let mut key = [0u8; 32];
+            OsRng.fill_bytes(&mut key);
+            write_owner_only(&key_path, &key).context("Failed to write spool key")?;
+            key
+        };
+
+        Ok(Self { dir, key })
+    }
+
+    /// Queue a transfer, returning the new entry's ID
+    pub fn enqueue(&self, entry: &SpoolEntry) -> anyhow::Result<String> {
+        let mut id_bytes = [0u8; 8];
+        OsRng.fill_bytes(&mut id_bytes);
+        let id = hex::encode(id_bytes);
+
+        let plaintext = serde_json::to_vec(entry).context("Failed to serialize spool entry")?;
+
+        let mut nonce = [0u8; NONCE_SIZE];
+        OsRng.fill_bytes(&mut nonce);
+
+        let cipher = XChaCha20Poly1305::new((&self.key).into());
+        let ciphertext = cipher
+            .encrypt((&nonce).into(), plaintext.as_slice())
+            .map_err(|e| anyhow::anyhow!("Failed to encrypt spool entry: {e}"))?;
+
+        let mut data = Vec::with_capacity(SPOOL_MAGIC.len() + NONCE_SIZE + ciphertext.len());
+        data.extend_from_slice(SPOOL_MAGIC);
+        data.extend_from_slice(&nonce);
+        data.extend_from_slice(&ciphertext);
+
+        write_owner_only(&self.entry_path(&id), &data).context("Failed to write spool entry")?;
+        Ok(id)
+    }
+
+    /// List all queued transfers as (id, entry) pairs, oldest first
+    ///
+    /// Entries that fail to decrypt (corrupt file or foreign key) are
+    /// skipped with a warning rather than blocking the readable ones.
+    pub fn list(&self) -> anyhow::Result<Vec<(String, SpoolEntry)>> {
+        let mut entries = Vec::new();
+
+        for dirent in std::fs::read_dir(&self.dir).context("Failed to read spool directory")? {
+            let path = dirent?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some(ENTRY_EXT) {
+                continue;
+            }
+            let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            match self.read_entry(&path) {
+                Ok(entry) => entries.push((id.to_string(), entry)),
+                Err(e) => tracing::warn!("Skipping unreadable spool entry {id}: {e}"),
+            }
+        }
+
+        entries.sort_by_key(|(_, entry)| entry.queued_at);
+        Ok(entries)
+    }
+
+    /// Remove a queued transfer by ID
+    ///
+    /// Returns `false` if no entry with that ID exists.
+    pub fn remove(&self, id: &str) -> anyhow::Result<bool> {
+        // IDs come from the command line; constrain them to the hex form
+        // enqueue generates so they cannot traverse out of the spool dir
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!("Invalid spool entry ID: {id:?}");
+        }
+
+        let path = self.entry_path(id);
+        if !path.exists() {
+            return Ok(false);
+        }
+        std::fs::remove_file(&path).context("Failed to remove spool entry")?;
+        Ok(true)
+    }
+
+    /// Path of the entry file for an ID
+    fn entry_path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}.{ENTRY_EXT}"))
+    }
+
+    /// Read and decrypt a single entry file
+    fn read_entry(&self, path: &Path) -> anyhow::Result<SpoolEntry> {
+        let data = std::fs::read(path)?;
+        if data.len() < SPOOL_MAGIC.len() + NONCE_SIZE || &data[..SPOOL_MAGIC.len()] != SPOOL_MAGIC
+        {
+            anyhow::bail!("Not a spool entry (bad header)");
+        }
+
+        let nonce = &data[SPOOL_MAGIC.len()..SPOOL_MAGIC.len() + NONCE_SIZE];
+        let ciphertext = &data[SPOOL_MAGIC.len() + NONCE_SIZE..];
+
+        let cipher = XChaCha20Poly1305::new((&self.key).into());
+        let plaintext = cipher
+            .decrypt(nonce.into(), ciphertext)
+            .map_err(|_| anyhow::anyhow!("Decryption failed (wrong key or corrupt entry)"))?;
+
+        serde_json::from_slice(&plaintext).context("Failed to parse spool entry")
+    }
+}
+
+/// Write a file readable only by its owner
+fn write_owner_only(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    std::fs::write(path, data)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+    Ok(())
+}
+
+/// Current time in seconds since the Unix epoch
+pub fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Current minute of the day in local time
+///
+/// Schedule windows ("only 01:00–06:00") are written in the operator's
+/// local clock, so dispatch decisions must be made in it too.
+#[cfg(target_os = "linux")]
+pub fn local_minute_of_day() -> u16 {
+    let now = unsafe { libc::time(std::ptr::null_mut()) };
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    unsafe { libc::localtime_r(&now, &mut tm) };
+    (tm.tm_hour as u16) * 60 + tm.tm_min as u16
+}
+
+/// Current minute of the day (UTC fallback where local time is unavailable)
+#[cfg(not(target_os = "linux"))]
+pub fn local_minute_of_day() -> u16 {
+    ((now_secs() % 86_400) / 60) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry(name: &str, queued_at: u64) -> SpoolEntry {
+        SpoolEntry {
+            file_path: PathBuf::from(name),
+            peer_id: [7u8; 32],
+            limit_bps: Some(1_000_000),
+            queued_at,
+            schedule: None,
+        }
+    }
+
+    #[test]
+    fn test_enqueue_list_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let spool = Spool::open(dir.path().to_path_buf()).unwrap();
+
+        let id = spool.enqueue(&entry("/tmp/a.bin", 10)).unwrap();
+        let listed = spool.list().unwrap();
+
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].0, id);
+        assert_eq!(listed[0].1.file_path, PathBuf::from("/tmp/a.bin"));
+        assert_eq!(listed[0].1.peer_id, [7u8; 32]);
+        assert_eq!(listed[0].1.limit_bps, Some(1_000_000));
+    }
+
+    #[test]
+    fn test_schedule_roundtrips() {
+        let dir = TempDir::new().unwrap();
+        let spool = Spool::open(dir.path().to_path_buf()).unwrap();
+
+        let mut scheduled = entry("/tmp/backup.bin", 10);
+        scheduled.schedule = Some(wraith_core::transfer::TransferSchedule {
+            start_at: Some(1_700_000_000),
+            windows: vec!["01:00-06:00".parse().unwrap()],
+            offpeak_limit_bps: None,
+            peak_limit_bps: Some(500_000),
+        });
+        spool.enqueue(&scheduled).unwrap();
+
+        let listed = spool.list().unwrap();
+        assert_eq!(listed[0].1.schedule, scheduled.schedule);
+    }
+
+    #[test]
+    fn test_list_is_oldest_first() {
+        let dir = TempDir::new().unwrap();
+        let spool = Spool::open(dir.path().to_path_buf()).unwrap();
+
+        spool.enqueue(&entry("/tmp/newer.bin", 20)).unwrap();
+        spool.enqueue(&entry("/tmp/older.bin", 10)).unwrap();
+
+        let listed = spool.list().unwrap();
+        assert_eq!(listed[0].1.file_path, PathBuf::from("/tmp/older.bin"));
+        assert_eq!(listed[1].1.file_path, PathBuf::from("/tmp/newer.bin"));
+    }
+
+    #[test]
+    fn test_remove() {
+        let dir = TempDir::new().unwrap();
+        let spool = Spool::open(dir.path().to_path_buf()).unwrap();
+
+        let id = spool.enqueue(&entry("/tmp/a.bin", 10)).unwrap();
+        assert!(spool.remove(&id).unwrap());
+        assert!(!spool.remove(&id).unwrap());
+        assert!(spool.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_remove_rejects_non_hex_ids() {
+        let dir = TempDir::new().unwrap();
+        let spool = Spool::open(dir.path().to_path_buf()).unwrap();
+
+        assert!(spool.remove("../escape").is_err());
+        assert!(spool.remove("").is_err());
+    }
+
+    #[test]
+    fn test_entries_are_encrypted_on_disk() {
+        let dir = TempDir::new().unwrap();
+        let spool = Spool::open(dir.path().to_path_buf()).unwrap();
+
+        let id = spool.enqueue(&entry("/tmp/secret-name.bin", 10)).unwrap();
+        let raw = std::fs::read(dir.path().join(format!("{id}.spool"))).unwrap();
+
+        assert_eq!(&raw[..8], SPOOL_MAGIC);
+        let haystack = String::from_utf8_lossy(&raw);
+        assert!(!haystack.contains("secret-name"));
+    }
+
+    #[test]
+    fn test_foreign_key_entries_are_skipped() {
+        let dir = TempDir::new().unwrap();
+        let spool = Spool::open(dir.path().to_path_buf()).unwrap();
+        spool.enqueue(&entry("/tmp/a.bin", 10)).unwrap();
+
+        // Replace the key: existing entries no longer decrypt
+        std::fs::remove_file(dir.path().join(KEY_FILE)).unwrap();
+        let reopened = Spool::open(dir.path().to_path_buf()).unwrap();
+        assert!(reopened.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_key_survives_reopen() {
+        let dir = TempDir::new().unwrap();
+        let spool = Spool::open(dir.path().to_path_buf()).unwrap();
+        let id = spool.enqueue(&entry("/tmp/a.bin", 10)).unwrap();
+
+        let reopened = Spool::open(dir.path().to_path_buf()).unwrap();
+        let listed = reopened.list().unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].0, id);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_key_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        Spool::open(dir.path().to_path_buf()).unwrap();
+
+        let mode = std::fs::metadata(dir.path().join(KEY_FILE))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -0,0 +1,196 @@
+//! Performance benchmarks for the connection ID routing table.
+//!
+//! Run with: `cargo bench -p wraith-core routing`
+//!
+//! Every received packet resolves its 8-byte Connection ID through
+//! `RoutingTable::lookup`, so this path must sustain millions of
+//! lookups per second. These benchmarks measure single lookups (hit
+//! and miss) at various table sizes, snapshot-amortized batch lookups,
+//! and concurrent reader throughput.
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use std::sync::Arc;
+use wraith_core::ConnectionId;
+use wraith_core::node::routing::{RoutingTable, extract_connection_id};
+use wraith_core::node::session::PeerConnection;
+use wraith_crypto::aead::SessionCrypto;
+
+/// Build a minimal peer connection for routing benchmarks
+fn bench_connection(seed: u64) -> Arc<PeerConnection> {
+    let b = seed.to_be_bytes();
+    let mut session_id = [0u8; 32];
+    session_id[..8].copy_from_slice(&b);
+    let mut peer_id = [1u8; 32];
+    peer_id[..8].copy_from_slice(&b);
+    let peer_addr = "127.0.0.1:5000".parse().unwrap();
+    let connection_id = ConnectionId::from_bytes(b);
+    let crypto = SessionCrypto::new([2u8; 32], [3u8; 32], &[4u8; 32]);
+
+    Arc::new(PeerConnection::new(
+        session_id,
+        peer_id,
+        peer_addr,
+        connection_id,
+        crypto,
+    ))
+}
+
+/// Populate a routing table with `count` routes at IDs 0..count
+fn populated_table(count: u64) -> RoutingTable {
+    let routing = RoutingTable::new();
+    for id in 0..count {
+        routing.add_route(id, bench_connection(id));
+    }
+    routing
+}
+
+// ============================================================================
+// Single Lookup Benchmarks
+// ============================================================================
+
+/// Benchmark a single lookup hit at various table sizes
+fn bench_routing_lookup_hit(c: &mut Criterion) {
+    let mut group = c.benchmark_group("routing_lookup_hit");
+    group.throughput(Throughput::Elements(1));
+
+    for table_size in [16u64, 1_024, 16_384, 131_072] {
+        let routing = populated_table(table_size);
+        let target = table_size / 2;
+
+        group.bench_with_input(BenchmarkId::new("routes", table_size), &target, |b, &id| {
+            b.iter(|| black_box(routing.lookup(black_box(id)).is_some()));
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark a single lookup miss (unknown connection ID)
+fn bench_routing_lookup_miss(c: &mut Criterion) {
+    let mut group = c.benchmark_group("routing_lookup_miss");
+    group.throughput(Throughput::Elements(1));
+
+    for table_size in [1_024u64, 131_072] {
+        let routing = populated_table(table_size);
+
+        group.bench_with_input(
+            BenchmarkId::new("routes", table_size),
+            &u64::MAX,
+            |b, &id| {
+                b.iter(|| black_box(routing.lookup(black_box(id)).is_none()));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+// ============================================================================
+// Batch / Snapshot Benchmarks
+// ============================================================================
+
+/// Benchmark resolving a full RX batch against one snapshot
+///
+/// Compares per-packet `lookup()` against amortizing a single
+/// `snapshot()` across the batch, as a batch receive loop would.
+fn bench_routing_batch(c: &mut Criterion) {
+    const BATCH: u64 = 64;
+    let mut group = c.benchmark_group("routing_batch_64");
+    group.throughput(Throughput::Elements(BATCH));
+
+    let routing = populated_table(16_384);
+
+    group.bench_function("per_packet_lookup", |b| {
+        b.iter(|| {
+            let mut hits = 0usize;
+            for id in 0..BATCH {
+                if routing.lookup(black_box(id)).is_some() {
+                    hits += 1;
+                }
+            }
+            black_box(hits)
+        });
+    });
+
+    group.bench_function("amortized_snapshot", |b| {
+        b.iter(|| {
+            let snapshot = routing.snapshot();
+            let mut hits = 0usize;
+            for id in 0..BATCH {
+                if snapshot.get(&black_box(id)).is_some() {
+                    hits += 1;
+                }
+            }
+            black_box(hits)
+        });
+    });
+
+    group.finish();
+}
+
+/// Benchmark extracting the connection ID from a packet header
+fn bench_extract_connection_id(c: &mut Criterion) {
+    let packet = [0xABu8; 64];
+
+    c.bench_function("routing_extract_connection_id", |b| {
+        b.iter(|| black_box(extract_connection_id(black_box(&packet))));
+    });
+}
+
+// ============================================================================
+// Concurrent Reader Benchmarks
+// ============================================================================
+
+/// Benchmark lookup throughput with multiple concurrent reader threads
+///
+/// Readers never block each other: each lookup resolves on an immutable
+/// snapshot, so throughput should scale with reader count.
+fn bench_routing_concurrent_readers(c: &mut Criterion) {
+    const LOOKUPS_PER_THREAD: u64 = 10_000;
+    let mut group = c.benchmark_group("routing_concurrent_readers");
+    group.sample_size(20);
+
+    for threads in [1usize, 2, 4] {
+        let routing = Arc::new(populated_table(16_384));
+        group.throughput(Throughput::Elements(LOOKUPS_PER_THREAD * threads as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("threads", threads),
+            &threads,
+            |b, &threads| {
+                b.iter(|| {
+                    let handles: Vec<_> = (0..threads)
+                        .map(|t| {
+                            let routing = Arc::clone(&routing);
+                            std::thread::spawn(move || {
+                                let mut hits = 0usize;
+                                for i in 0..LOOKUPS_PER_THREAD {
+                                    let id = (t as u64 + i) % 16_384;
+                                    if routing.lookup(black_box(id)).is_some() {
+                                        hits += 1;
+                                    }
+                                }
+                                hits
+                            })
+                        })
+                        .collect();
+
+                    let total: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+                    black_box(total)
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_routing_lookup_hit,
+    bench_routing_lookup_miss,
+    bench_routing_batch,
+    bench_extract_connection_id,
+    bench_routing_concurrent_readers,
+);
+criterion_main!(benches);
@@ -1,11 +1,76 @@
-//! `BBR`-inspired congestion control.
+//! Congestion control algorithms.
 //!
 //! Implements a `BBRv2`-inspired congestion control algorithm optimized
-//! for high-throughput, low-latency file transfers.
+//! for high-throughput, low-latency file transfers, plus loss-based `CUBIC`
+//! (RFC 9438) and `NewReno` (RFC 6582) controllers for fairness testing on
+//! shared links. The algorithm is selectable per session via
+//! [`CongestionAlgorithm`] and [`crate::SessionConfig::congestion_algorithm`].
 
 use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+/// Maximum segment size used for window arithmetic (bytes)
+const MSS: u64 = 1_500;
+
+/// Common interface implemented by all congestion controllers.
+///
+/// Allows the send path to drive `BBR`, `CUBIC`, or `NewReno` through one
+/// type, selected at runtime via [`CongestionAlgorithm::new_controller`].
+pub trait CongestionController: Send {
+    /// Called when a packet is sent
+    fn on_packet_sent(&mut self, bytes: u64);
+
+    /// Called when a packet is acknowledged
+    fn on_packet_acked(&mut self, bytes: u64, rtt: Duration);
+
+    /// Called when a packet is lost
+    fn on_packet_lost(&mut self, bytes: u64);
+
+    /// Get current congestion window (bytes)
+    fn congestion_window(&self) -> u64;
+
+    /// Check if we can send more data within the window
+    fn can_send(&self, bytes: u64) -> bool;
+
+    /// Get current pacing rate (bytes/sec)
+    fn pacing_rate(&self) -> u64;
+
+    /// Get bytes currently in flight
+    fn bytes_in_flight(&self) -> u64;
+
+    /// Get the current RTT estimate
+    fn estimated_rtt(&self) -> Duration;
+}
+
+/// Congestion control algorithm selection.
+///
+/// Operators can pick the algorithm per session via
+/// [`crate::SessionConfig::congestion_algorithm`]; `BBR` is the default and
+/// the loss-based algorithms are mainly useful for fairness testing against
+/// TCP flows on shared links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CongestionAlgorithm {
+    /// Model-based `BBRv2`-inspired controller (default)
+    #[default]
+    Bbr,
+    /// Loss-based `CUBIC` (RFC 9438)
+    Cubic,
+    /// Loss-based `NewReno` (RFC 6582)
+    NewReno,
+}
+
+impl CongestionAlgorithm {
+    /// Instantiate a controller for this algorithm
+    #[must_use]
+    pub fn new_controller(self) -> Box<dyn CongestionController> {
+        match self {
+            Self::Bbr => Box::new(BbrState::new()),
+            Self::Cubic => Box::new(CubicState::new()),
+            Self::NewReno => Box::new(NewRenoState::new()),
+        }
+    }
+}
+
 /// Maximum number of bandwidth samples to keep
 const BW_WINDOW_SIZE: usize = 10;
 
@@ -98,6 +163,32 @@ pub struct BbrState {
     pacing_rate_bps: u64,
 }
 
+/// Saved congestion estimates for careful resume across reconnects
+///
+/// Captures the path model a controller had built — bottleneck bandwidth
+/// and minimum `RTT` — so a session re-established to the same peer over
+/// the same path shortly after a drop can be seeded via
+/// [`BbrState::resume_from`] instead of rediscovering the path from
+/// scratch. Callers decide how long a snapshot stays trustworthy via
+/// [`is_fresh`](Self::is_fresh).
+#[derive(Debug, Clone, Copy)]
+pub struct CongestionSnapshot {
+    /// Estimated bottleneck bandwidth at capture time (bytes/sec)
+    pub btl_bw: u64,
+    /// Minimum observed `RTT` at capture time
+    pub min_rtt: Duration,
+    /// When the snapshot was taken
+    pub taken_at: Instant,
+}
+
+impl CongestionSnapshot {
+    /// Whether the snapshot is recent enough to still describe the path
+    #[must_use]
+    pub fn is_fresh(&self, window: Duration) -> bool {
+        self.taken_at.elapsed() <= window
+    }
+}
+
 /// `BBR` algorithm phases
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BbrPhase {
@@ -142,6 +233,52 @@ impl BbrState {
         }
     }
 
+    /// Capture the current path model for careful resume
+    ///
+    /// The returned snapshot can seed a fresh controller for the same path
+    /// via [`Self::resume_from`] after a reconnect.
+    #[must_use]
+    pub fn snapshot(&self) -> CongestionSnapshot {
+        CongestionSnapshot {
+            btl_bw: self.btl_bw,
+            min_rtt: self.min_rtt,
+            taken_at: Instant::now(),
+        }
+    }
+
+    /// Create `BBR` state seeded from a snapshot of the same path
+    ///
+    /// Careful-resume style: the saved bottleneck bandwidth is trusted at
+    /// half value and the saved `min_rtt` is adopted outright, so pacing and
+    /// the congestion window start near the path's known capacity instead of
+    /// the cold-start defaults. The state still begins in
+    /// [`BbrPhase::Startup`], so live samples re-validate (and quickly
+    /// overtake) the seed; a path that changed while disconnected falls back
+    /// to normal probing. A snapshot with no bandwidth estimate yields plain
+    /// cold-start state.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn resume_from(snapshot: &CongestionSnapshot) -> Self {
+        let mut state = Self::new();
+        if snapshot.btl_bw == 0 {
+            return state;
+        }
+
+        // Trust half the previous estimate until live samples confirm it
+        let seeded_bw = (snapshot.btl_bw / 2).max(1);
+        state.btl_bw = seeded_bw;
+        state.min_rtt = snapshot.min_rtt;
+        // Seed the sample windows so early genuine measurements merge with
+        // the seed instead of replacing an empty window
+        state.bw_samples.push_back((seeded_bw, snapshot.min_rtt));
+        state.rtt_samples.push_back(snapshot.min_rtt);
+        state.bdp = (seeded_bw as f64 * snapshot.min_rtt.as_secs_f64()) as u64;
+        state.update_pacing_rate();
+        state
+    }
+
     /// Update `RTT` estimate with new sample
     pub fn update_rtt(&mut self, rtt_sample: Duration) {
         // Add sample to window
@@ -215,10 +352,10 @@ impl BbrState {
         self.on_packet_sent(bytes as u64);
 
         // Calculate pacing delay
-        if self.pacing_rate_bps > 0 {
-            // delay = bytes / rate (in seconds)
-            // Convert to nanoseconds: bytes * 8 * 1e9 / rate
-            let delay_ns = (bytes as u64 * 8 * 1_000_000_000) / self.pacing_rate_bps;
+        // delay = bytes / rate (in seconds)
+        // Convert to nanoseconds: bytes * 8 * 1e9 / rate
+        if let Some(delay_ns) = (bytes as u64 * 8 * 1_000_000_000).checked_div(self.pacing_rate_bps)
+        {
             self.next_send_time = Instant::now() + Duration::from_nanos(delay_ns);
         }
     }
@@ -476,6 +613,269 @@ impl Default for BbrState {
     }
 }
 
+impl CongestionController for BbrState {
+    fn on_packet_sent(&mut self, bytes: u64) {
+        BbrState::on_packet_sent(self, bytes);
+    }
+
+    fn on_packet_acked(&mut self, bytes: u64, rtt: Duration) {
+        BbrState::on_packet_acked(self, bytes, rtt);
+    }
+
+    fn on_packet_lost(&mut self, bytes: u64) {
+        BbrState::on_packet_lost(self, bytes);
+    }
+
+    fn congestion_window(&self) -> u64 {
+        self.cwnd()
+    }
+
+    fn can_send(&self, bytes: u64) -> bool {
+        BbrState::can_send(self, bytes)
+    }
+
+    fn pacing_rate(&self) -> u64 {
+        BbrState::pacing_rate(self)
+    }
+
+    fn bytes_in_flight(&self) -> u64 {
+        BbrState::bytes_in_flight(self)
+    }
+
+    fn estimated_rtt(&self) -> Duration {
+        self.min_rtt()
+    }
+}
+
+/// `CUBIC` multiplicative decrease factor (beta, RFC 9438)
+const CUBIC_BETA: f64 = 0.7;
+
+/// `CUBIC` scaling constant (C, RFC 9438)
+const CUBIC_C: f64 = 0.4;
+
+/// `CUBIC` congestion control state (RFC 9438).
+///
+/// Loss-based controller whose window grows along a cubic curve anchored at
+/// the window size where the last loss occurred. Friendlier to competing TCP
+/// flows than `BBR` on shared bottlenecks.
+pub struct CubicState {
+    /// Current congestion window (bytes)
+    cwnd: u64,
+    /// Slow start threshold (bytes)
+    ssthresh: u64,
+    /// Window size before the last loss event (`W_max`, bytes)
+    w_max: f64,
+    /// Time of the last loss event (start of the current cubic epoch)
+    epoch_start: Option<Instant>,
+    /// Time offset at which the curve reaches `w_max` again (K, seconds)
+    k: f64,
+    /// Smoothed RTT estimate
+    srtt: Duration,
+    /// Bytes in flight
+    bytes_in_flight: u64,
+}
+
+impl CubicState {
+    /// Create new `CUBIC` state
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cwnd: 10 * MSS,
+            ssthresh: u64::MAX,
+            w_max: 0.0,
+            epoch_start: None,
+            k: 0.0,
+            srtt: Duration::from_millis(100),
+            bytes_in_flight: 0,
+        }
+    }
+
+    /// Get slow start threshold (bytes)
+    #[must_use]
+    pub fn ssthresh(&self) -> u64 {
+        self.ssthresh
+    }
+
+    /// Compute the cubic window at elapsed time `t` since the epoch start
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn w_cubic(&self, t: f64) -> u64 {
+        // W_cubic(t) = C * (t - K)^3 + W_max  (in MSS units)
+        let w = CUBIC_C * (t - self.k).powi(3) + self.w_max;
+        (w.max(0.0) * MSS as f64) as u64
+    }
+}
+
+impl Default for CubicState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CongestionController for CubicState {
+    fn on_packet_sent(&mut self, bytes: u64) {
+        self.bytes_in_flight += bytes;
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn on_packet_acked(&mut self, bytes: u64, rtt: Duration) {
+        self.bytes_in_flight = self.bytes_in_flight.saturating_sub(bytes);
+        self.srtt = rtt;
+
+        if self.cwnd < self.ssthresh {
+            // Slow start: grow by acked bytes
+            self.cwnd += bytes;
+            return;
+        }
+
+        // Congestion avoidance along the cubic curve
+        let epoch_start = *self.epoch_start.get_or_insert_with(Instant::now);
+        let t = epoch_start.elapsed().as_secs_f64();
+        let target = self.w_cubic(t);
+
+        if target > self.cwnd {
+            self.cwnd = target;
+        } else {
+            // TCP-friendly minimum growth: ~one MSS per RTT
+            self.cwnd += (MSS * MSS) / self.cwnd.max(MSS);
+        }
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn on_packet_lost(&mut self, bytes: u64) {
+        self.bytes_in_flight = self.bytes_in_flight.saturating_sub(bytes);
+
+        // Multiplicative decrease and new cubic epoch
+        self.w_max = self.cwnd as f64 / MSS as f64;
+        self.cwnd = ((self.cwnd as f64 * CUBIC_BETA) as u64).max(2 * MSS);
+        self.ssthresh = self.cwnd;
+        self.k = (self.w_max * (1.0 - CUBIC_BETA) / CUBIC_C).cbrt();
+        self.epoch_start = Some(Instant::now());
+    }
+
+    fn congestion_window(&self) -> u64 {
+        self.cwnd
+    }
+
+    fn can_send(&self, bytes: u64) -> bool {
+        self.bytes_in_flight + bytes <= self.cwnd
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn pacing_rate(&self) -> u64 {
+        // cwnd per RTT
+        (self.cwnd as f64 / self.srtt.as_secs_f64().max(0.001)) as u64
+    }
+
+    fn bytes_in_flight(&self) -> u64 {
+        self.bytes_in_flight
+    }
+
+    fn estimated_rtt(&self) -> Duration {
+        self.srtt
+    }
+}
+
+/// `NewReno` congestion control state (RFC 6582).
+///
+/// Classic AIMD controller: slow start until `ssthresh`, then additive
+/// increase of one MSS per RTT, halving the window on loss.
+pub struct NewRenoState {
+    /// Current congestion window (bytes)
+    cwnd: u64,
+    /// Slow start threshold (bytes)
+    ssthresh: u64,
+    /// Smoothed RTT estimate
+    srtt: Duration,
+    /// Bytes in flight
+    bytes_in_flight: u64,
+}
+
+impl NewRenoState {
+    /// Create new `NewReno` state
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            cwnd: 10 * MSS,
+            ssthresh: u64::MAX,
+            srtt: Duration::from_millis(100),
+            bytes_in_flight: 0,
+        }
+    }
+
+    /// Get slow start threshold (bytes)
+    #[must_use]
+    pub fn ssthresh(&self) -> u64 {
+        self.ssthresh
+    }
+
+    /// Whether the controller is in slow start
+    #[must_use]
+    pub fn in_slow_start(&self) -> bool {
+        self.cwnd < self.ssthresh
+    }
+}
+
+impl Default for NewRenoState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CongestionController for NewRenoState {
+    fn on_packet_sent(&mut self, bytes: u64) {
+        self.bytes_in_flight += bytes;
+    }
+
+    fn on_packet_acked(&mut self, bytes: u64, rtt: Duration) {
+        self.bytes_in_flight = self.bytes_in_flight.saturating_sub(bytes);
+        self.srtt = rtt;
+
+        if self.in_slow_start() {
+            // Slow start: grow by acked bytes (doubles per RTT)
+            self.cwnd += bytes;
+        } else {
+            // Congestion avoidance: ~one MSS per RTT
+            self.cwnd += (MSS * MSS) / self.cwnd.max(MSS);
+        }
+    }
+
+    fn on_packet_lost(&mut self, bytes: u64) {
+        self.bytes_in_flight = self.bytes_in_flight.saturating_sub(bytes);
+
+        // Halve the window (fast recovery entry)
+        self.ssthresh = (self.cwnd / 2).max(2 * MSS);
+        self.cwnd = self.ssthresh;
+    }
+
+    fn congestion_window(&self) -> u64 {
+        self.cwnd
+    }
+
+    fn can_send(&self, bytes: u64) -> bool {
+        self.bytes_in_flight + bytes <= self.cwnd
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    fn pacing_rate(&self) -> u64 {
+        // cwnd per RTT
+        (self.cwnd as f64 / self.srtt.as_secs_f64().max(0.001)) as u64
+    }
+
+    fn bytes_in_flight(&self) -> u64 {
+        self.bytes_in_flight
+    }
+
+    fn estimated_rtt(&self) -> Duration {
+        self.srtt
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -781,6 +1181,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bbr_snapshot_captures_estimates() {
+        let mut bbr = BbrState::new();
+        bbr.update_rtt(Duration::from_millis(40));
+        bbr.update_bandwidth(10_000_000, Duration::from_secs(1)); // 10 MB/s
+
+        let snapshot = bbr.snapshot();
+        assert_eq!(snapshot.btl_bw, 10_000_000);
+        assert_eq!(snapshot.min_rtt, Duration::from_millis(40));
+        assert!(snapshot.is_fresh(Duration::from_secs(60)));
+        assert!(!snapshot.is_fresh(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_bbr_resume_from_seeds_path_model() {
+        let mut bbr = BbrState::new();
+        bbr.update_rtt(Duration::from_millis(40));
+        bbr.update_bandwidth(10_000_000, Duration::from_secs(1));
+
+        let resumed = BbrState::resume_from(&bbr.snapshot());
+
+        // Half the previous bandwidth is trusted; RTT carries over
+        assert_eq!(resumed.btl_bw(), 5_000_000);
+        assert_eq!(resumed.min_rtt(), Duration::from_millis(40));
+        assert_eq!(resumed.phase(), BbrPhase::Startup);
+
+        // Pacing and cwnd start well above the cold-start defaults
+        assert!(resumed.pacing_rate() > BbrState::new().pacing_rate());
+        assert!(resumed.cwnd() > BbrState::new().cwnd());
+    }
+
+    #[test]
+    fn test_bbr_resume_from_empty_snapshot_is_cold_start() {
+        let resumed = BbrState::resume_from(&BbrState::new().snapshot());
+
+        assert_eq!(resumed.btl_bw(), 0);
+        assert_eq!(resumed.pacing_rate(), BbrState::new().pacing_rate());
+        assert_eq!(resumed.cwnd(), BbrState::new().cwnd());
+    }
+
+    #[test]
+    fn test_bbr_resume_re_validates_with_live_samples() {
+        let mut bbr = BbrState::new();
+        bbr.update_rtt(Duration::from_millis(40));
+        bbr.update_bandwidth(10_000_000, Duration::from_secs(1));
+
+        let mut resumed = BbrState::resume_from(&bbr.snapshot());
+
+        // A faster live measurement overtakes the conservative seed
+        resumed.update_bandwidth(20_000_000, Duration::from_secs(1));
+        assert_eq!(resumed.btl_bw(), 20_000_000);
+
+        // A lower live RTT replaces the carried-over minimum
+        resumed.update_rtt(Duration::from_millis(10));
+        assert_eq!(resumed.min_rtt(), Duration::from_millis(10));
+    }
+
     #[test]
     fn test_bbr_cwnd_initial() {
         let bbr = BbrState::new();
@@ -1388,6 +1845,163 @@ mod tests {
         assert!(bbr.next_send_time > Instant::now());
     }
 
+    // ========================================================================
+    // CUBIC / NewReno / CongestionController Trait Tests
+    // ========================================================================
+
+    #[test]
+    fn test_algorithm_factory() {
+        // Each variant should produce a working controller
+        for algorithm in [
+            CongestionAlgorithm::Bbr,
+            CongestionAlgorithm::Cubic,
+            CongestionAlgorithm::NewReno,
+        ] {
+            let controller = algorithm.new_controller();
+            assert!(controller.congestion_window() > 0);
+            assert!(controller.pacing_rate() > 0);
+            assert_eq!(controller.bytes_in_flight(), 0);
+        }
+    }
+
+    #[test]
+    fn test_algorithm_default_is_bbr() {
+        assert_eq!(CongestionAlgorithm::default(), CongestionAlgorithm::Bbr);
+    }
+
+    #[test]
+    fn test_cubic_initial_state() {
+        let cubic = CubicState::new();
+
+        assert_eq!(cubic.congestion_window(), 10 * MSS);
+        assert_eq!(cubic.bytes_in_flight(), 0);
+        assert_eq!(cubic.ssthresh(), u64::MAX);
+    }
+
+    #[test]
+    fn test_cubic_slow_start_growth() {
+        let mut cubic = CubicState::new();
+
+        let initial = cubic.congestion_window();
+        cubic.on_packet_sent(1500);
+        cubic.on_packet_acked(1500, Duration::from_millis(50));
+
+        // In slow start, window grows by acked bytes
+        assert_eq!(cubic.congestion_window(), initial + 1500);
+    }
+
+    #[test]
+    fn test_cubic_multiplicative_decrease() {
+        let mut cubic = CubicState::new();
+
+        // Grow the window a bit
+        for _ in 0..20 {
+            cubic.on_packet_sent(1500);
+            cubic.on_packet_acked(1500, Duration::from_millis(50));
+        }
+
+        let before_loss = cubic.congestion_window();
+        cubic.on_packet_lost(1500);
+
+        // Window reduced by beta (0.7)
+        let expected = (before_loss as f64 * 0.7) as u64;
+        assert_eq!(cubic.congestion_window(), expected);
+        assert_eq!(cubic.ssthresh(), expected);
+    }
+
+    #[test]
+    fn test_cubic_window_floor() {
+        let mut cubic = CubicState::new();
+
+        // Repeated losses must not collapse the window below 2 MSS
+        for _ in 0..20 {
+            cubic.on_packet_lost(0);
+        }
+
+        assert!(cubic.congestion_window() >= 2 * MSS);
+    }
+
+    #[test]
+    fn test_cubic_can_send() {
+        let mut cubic = CubicState::new();
+
+        let cwnd = cubic.congestion_window();
+        assert!(cubic.can_send(cwnd));
+
+        cubic.on_packet_sent(cwnd);
+        assert!(!cubic.can_send(1));
+    }
+
+    #[test]
+    fn test_newreno_initial_state() {
+        let reno = NewRenoState::new();
+
+        assert_eq!(reno.congestion_window(), 10 * MSS);
+        assert!(reno.in_slow_start());
+    }
+
+    #[test]
+    fn test_newreno_slow_start_growth() {
+        let mut reno = NewRenoState::new();
+
+        let initial = reno.congestion_window();
+        reno.on_packet_sent(1500);
+        reno.on_packet_acked(1500, Duration::from_millis(50));
+
+        assert_eq!(reno.congestion_window(), initial + 1500);
+    }
+
+    #[test]
+    fn test_newreno_halves_on_loss() {
+        let mut reno = NewRenoState::new();
+
+        let before_loss = reno.congestion_window();
+        reno.on_packet_lost(1500);
+
+        assert_eq!(reno.congestion_window(), before_loss / 2);
+        assert_eq!(reno.ssthresh(), before_loss / 2);
+        assert!(!reno.in_slow_start());
+    }
+
+    #[test]
+    fn test_newreno_congestion_avoidance_linear() {
+        let mut reno = NewRenoState::new();
+
+        // Exit slow start via a loss
+        reno.on_packet_lost(0);
+        let cwnd = reno.congestion_window();
+
+        reno.on_packet_acked(1500, Duration::from_millis(50));
+
+        // Growth should be roughly MSS^2 / cwnd, far less than one MSS
+        let growth = reno.congestion_window() - cwnd;
+        assert!(growth > 0);
+        assert!(growth < MSS);
+    }
+
+    #[test]
+    fn test_newreno_window_floor() {
+        let mut reno = NewRenoState::new();
+
+        for _ in 0..20 {
+            reno.on_packet_lost(0);
+        }
+
+        assert!(reno.congestion_window() >= 2 * MSS);
+    }
+
+    #[test]
+    fn test_controller_trait_via_bbr() {
+        let mut controller: Box<dyn CongestionController> = Box::new(BbrState::new());
+
+        controller.on_packet_sent(1500);
+        assert_eq!(controller.bytes_in_flight(), 1500);
+
+        controller.on_packet_acked(1500, Duration::from_millis(40));
+        assert_eq!(controller.bytes_in_flight(), 0);
+        assert_eq!(controller.estimated_rtt(), Duration::from_millis(40));
+    }
+
     #[test]
     fn test_pacing_integration_with_existing_api() {
         let mut bbr = BbrState::new();
@@ -25,6 +25,26 @@ const MAX_FILE_OFFSET: u64 = 256 * 1024 * 1024 * 1024 * 1024;
 #[allow(dead_code)]
 const MAX_SEQUENCE_DELTA: u32 = 1_000_000;
 
+/// Size of the optional trace ID carried after the payload
+const TRACE_ID_SIZE: usize = 4;
+
+/// Generate a random trace ID for frame-level correlation.
+///
+/// Trace IDs travel inside the encrypted frame (see
+/// [`FrameBuilder::trace_id`]) so both endpoints of a transfer can match
+/// log lines for the same frame when diagnosing sequence gaps or
+/// retransmissions. They carry no meaning beyond log correlation.
+///
+/// # Panics
+///
+/// Panics if the CSPRNG fails (extremely unlikely).
+#[must_use]
+pub fn random_trace_id() -> u32 {
+    let mut bytes = [0u8; 4];
+    getrandom::getrandom(&mut bytes).expect("CSPRNG failure");
+    u32::from_be_bytes(bytes)
+}
+
 /// Frame types as defined in the protocol specification
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
@@ -105,6 +125,8 @@ impl FrameFlags {
     pub const PRI: u8 = 0b0000_1000;
     /// Payload is compressed (LZ4)
     pub const CMP: u8 = 0b0001_0000;
+    /// Trace ID present (4 bytes following the payload)
+    pub const TRC: u8 = 0b0010_0000;
 
     /// Create new empty flags
     #[must_use]
@@ -144,6 +166,12 @@ impl FrameFlags {
         self.0 & Self::CMP != 0
     }
 
+    /// Check if a trace ID follows the payload
+    #[must_use]
+    pub fn has_trace(&self) -> bool {
+        self.0 & Self::TRC != 0
+    }
+
     /// Get raw byte value
     #[must_use]
     pub fn as_u8(&self) -> u8 {
@@ -388,6 +416,13 @@ impl<'a> Frame<'a> {
             });
         }
 
+        // A trace ID needs 4 bytes after the payload
+        if header.flags.has_trace()
+            && FRAME_HEADER_SIZE + header.payload_len as usize + TRACE_ID_SIZE > data.len()
+        {
+            return Err(FrameError::PayloadOverflow);
+        }
+
         Ok(Self {
             raw: data,
             kind: header.frame_type,
@@ -520,6 +555,21 @@ impl<'a> Frame<'a> {
     pub fn payload(&self) -> &[u8] {
         &self.raw[FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + self.payload_len as usize]
     }
+
+    /// Get the trace ID, if the sender stamped one.
+    ///
+    /// Trace IDs sit in the first 4 bytes after the payload when the TRC
+    /// flag is set; peers that predate tracing treat those bytes as
+    /// padding.
+    #[must_use]
+    pub fn trace_id(&self) -> Option<u32> {
+        if !self.flags.has_trace() {
+            return None;
+        }
+        let start = FRAME_HEADER_SIZE + self.payload_len as usize;
+        let bytes = self.raw.get(start..start + TRACE_ID_SIZE)?;
+        Some(u32::from_be_bytes(bytes.try_into().ok()?))
+    }
 }
 
 /// Builder for constructing frames
@@ -532,6 +582,7 @@ pub struct FrameBuilder {
     offset: u64,
     payload: Vec<u8>,
     nonce: [u8; 8],
+    trace_id: Option<u32>,
 }
 
 impl FrameBuilder {
@@ -590,6 +641,16 @@ impl FrameBuilder {
         self
     }
 
+    /// Stamp a trace ID for cross-node log correlation.
+    ///
+    /// Sets the TRC flag and carries the ID in the 4 bytes after the
+    /// payload, inside the encrypted frame. See [`random_trace_id`].
+    #[must_use]
+    pub fn trace_id(mut self, id: u32) -> Self {
+        self.trace_id = Some(id);
+        self
+    }
+
     /// Build the frame into a byte buffer
     ///
     /// # Errors
@@ -602,18 +663,28 @@ impl FrameBuilder {
     pub fn build(self, total_size: usize) -> Result<Vec<u8>, FrameError> {
         let frame_type = self.frame_type.unwrap_or(FrameType::Data);
         let payload_len = self.payload.len();
+        let trace_len = if self.trace_id.is_some() {
+            TRACE_ID_SIZE
+        } else {
+            0
+        };
 
-        if total_size < FRAME_HEADER_SIZE + payload_len {
+        if total_size < FRAME_HEADER_SIZE + payload_len + trace_len {
             return Err(FrameError::PayloadOverflow);
         }
 
-        let padding_len = total_size - FRAME_HEADER_SIZE - payload_len;
+        let padding_len = total_size - FRAME_HEADER_SIZE - payload_len - trace_len;
         let mut buf = Vec::with_capacity(total_size);
 
+        let mut flags = self.flags.as_u8();
+        if self.trace_id.is_some() {
+            flags |= FrameFlags::TRC;
+        }
+
         // Write header
         buf.extend_from_slice(&self.nonce);
         buf.push(frame_type as u8);
-        buf.push(self.flags.as_u8());
+        buf.push(flags);
         buf.extend_from_slice(&self.stream_id.to_be_bytes());
         buf.extend_from_slice(&self.sequence.to_be_bytes());
         buf.extend_from_slice(&self.offset.to_be_bytes());
@@ -625,6 +696,11 @@ impl FrameBuilder {
         // Write payload
         buf.extend_from_slice(&self.payload);
 
+        // Write trace ID (old peers see it as padding)
+        if let Some(trace) = self.trace_id {
+            buf.extend_from_slice(&trace.to_be_bytes());
+        }
+
         // Write random padding
         let mut padding = vec![0u8; padding_len];
         getrandom::getrandom(&mut padding).expect("CSPRNG failure");
@@ -1433,4 +1509,79 @@ mod tests {
             result
         );
     }
+
+    #[test]
+    fn test_frame_trace_id_roundtrip() {
+        let frame = FrameBuilder::new()
+            .frame_type(FrameType::Data)
+            .stream_id(42)
+            .sequence(7)
+            .payload(b"traced")
+            .trace_id(0xDEAD_BEEF)
+            .build(64)
+            .unwrap();
+
+        let parsed = Frame::parse(&frame).unwrap();
+        assert!(parsed.flags().has_trace());
+        assert_eq!(parsed.trace_id(), Some(0xDEAD_BEEF));
+        assert_eq!(parsed.payload(), b"traced");
+    }
+
+    #[test]
+    fn test_frame_without_trace_id() {
+        let frame = FrameBuilder::new()
+            .frame_type(FrameType::Data)
+            .payload(b"untraced")
+            .build(64)
+            .unwrap();
+
+        let parsed = Frame::parse(&frame).unwrap();
+        assert!(!parsed.flags().has_trace());
+        assert_eq!(parsed.trace_id(), None);
+    }
+
+    #[test]
+    fn test_trace_id_needs_room_beyond_payload() {
+        // Exactly header + payload leaves no room for the trace ID
+        let result = FrameBuilder::new()
+            .frame_type(FrameType::Data)
+            .payload(b"full")
+            .trace_id(1)
+            .build(FRAME_HEADER_SIZE + 4);
+        assert!(matches!(result, Err(FrameError::PayloadOverflow)));
+
+        // Four more bytes is enough
+        let frame = FrameBuilder::new()
+            .frame_type(FrameType::Data)
+            .payload(b"full")
+            .trace_id(1)
+            .build(FRAME_HEADER_SIZE + 4 + 4)
+            .unwrap();
+        assert_eq!(Frame::parse(&frame).unwrap().trace_id(), Some(1));
+    }
+
+    #[test]
+    fn test_parse_rejects_truncated_trace_id() {
+        let mut frame = FrameBuilder::new()
+            .frame_type(FrameType::Data)
+            .payload(b"data")
+            .build(FRAME_HEADER_SIZE + 4)
+            .unwrap();
+
+        // Claim a trace ID without the bytes to back it
+        frame[9] |= FrameFlags::TRC;
+
+        assert!(matches!(
+            Frame::parse(&frame),
+            Err(FrameError::PayloadOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_random_trace_ids_vary() {
+        // 16 draws from a 32-bit space colliding on every pair is a CSPRNG
+        // failure, not bad luck
+        let ids: std::collections::HashSet<u32> = (0..16).map(|_| random_trace_id()).collect();
+        assert!(ids.len() > 1);
+    }
 }
@@ -82,19 +82,25 @@ pub mod migration;
 pub mod node;
 pub mod path;
 pub mod ring_buffer;
+pub mod send_queue;
 pub mod session;
 pub mod stream;
 pub mod transfer;
 
-pub use congestion::BbrState;
+pub use congestion::{
+    BbrState, CongestionAlgorithm, CongestionController, CongestionSnapshot, CubicState,
+    NewRenoState,
+};
 pub use error::Error;
 pub use frame::{Frame, FrameBuilder, FrameFlags, FrameType};
-pub use migration::{PathState, PathValidator, ValidatedPath};
+pub use migration::{MultipathScheduler, PathState, PathStats, PathValidator, ValidatedPath};
 pub use node::{Node, NodeConfig, NodeError};
 pub use path::{DEFAULT_MTU, MAX_MTU, MIN_MTU, PathMtuDiscovery};
 pub use ring_buffer::{MpscRingBuffer, SpscRingBuffer};
+pub use send_queue::{FramePriority, SendQueue};
 pub use session::{
-    ConnectionId, HandshakePhase, Session, SessionConfig, SessionState, SessionStats,
+    ConnectionId, FrameCounters, HandshakePhase, Session, SessionConfig, SessionState,
+    SessionStats, SessionTicket, ZeroRttAcceptor,
 };
 pub use stream::{Stream, StreamState};
 
@@ -130,6 +130,141 @@ impl Default for PathValidator {
     }
 }
 
+/// Default bandwidth weight for paths without a BBR estimate yet (bits/sec)
+const DEFAULT_PATH_BANDWIDTH_BPS: u64 = 12_500_000; // ~100 Mbps
+
+/// Per-path transmission statistics
+#[derive(Clone, Debug)]
+pub struct PathStats {
+    /// Path identifier (address + port)
+    pub path_id: u64,
+    /// Round-trip time measured during validation
+    pub rtt: Duration,
+    /// Current bandwidth estimate (bits/sec), from the per-path BBR state
+    pub bandwidth_bps: u64,
+    /// Frames scheduled onto this path
+    pub frames_sent: u64,
+    /// Bytes scheduled onto this path
+    pub bytes_sent: u64,
+}
+
+/// Internal scheduler entry for one validated path
+#[derive(Clone, Debug)]
+struct SchedulerPath {
+    path: ValidatedPath,
+    /// Bandwidth estimate used as scheduling weight (bits/sec)
+    bandwidth_bps: u64,
+    /// Smooth weighted round-robin accumulator
+    current_weight: i64,
+    frames_sent: u64,
+    bytes_sent: u64,
+}
+
+/// Multipath scheduler striping DATA frames across validated paths.
+///
+/// Uses smooth weighted round-robin with per-path BBR bandwidth estimates
+/// as weights, so a path with twice the estimated bandwidth carries roughly
+/// twice the frames. Receivers reassemble in order via stream offsets, so
+/// no additional sequencing is required at this layer.
+pub struct MultipathScheduler {
+    paths: Vec<SchedulerPath>,
+}
+
+impl MultipathScheduler {
+    /// Create an empty scheduler
+    #[must_use]
+    pub fn new() -> Self {
+        Self { paths: Vec::new() }
+    }
+
+    /// Add a validated path to the scheduling set
+    ///
+    /// Adding a path that is already present updates its RTT and validation
+    /// timestamp but preserves counters and bandwidth estimate.
+    pub fn add_path(&mut self, path: ValidatedPath) {
+        if let Some(existing) = self
+            .paths
+            .iter_mut()
+            .find(|p| p.path.path_id == path.path_id)
+        {
+            existing.path = path;
+        } else {
+            self.paths.push(SchedulerPath {
+                path,
+                bandwidth_bps: DEFAULT_PATH_BANDWIDTH_BPS,
+                current_weight: 0,
+                frames_sent: 0,
+                bytes_sent: 0,
+            });
+        }
+    }
+
+    /// Remove a path (e.g. after validation failure or teardown)
+    pub fn remove_path(&mut self, path_id: u64) {
+        self.paths.retain(|p| p.path.path_id != path_id);
+    }
+
+    /// Update the bandwidth estimate for a path from its BBR state
+    pub fn update_bandwidth(&mut self, path_id: u64, bandwidth_bps: u64) {
+        if let Some(path) = self.paths.iter_mut().find(|p| p.path.path_id == path_id) {
+            // Never let the weight reach zero or the path would starve
+            path.bandwidth_bps = bandwidth_bps.max(1);
+        }
+    }
+
+    /// Select the path for the next DATA frame and record the frame against it
+    ///
+    /// Returns `None` when no validated paths are registered (caller should
+    /// fall back to the primary path).
+    pub fn schedule(&mut self, frame_bytes: u64) -> Option<u64> {
+        if self.paths.is_empty() {
+            return None;
+        }
+
+        // Smooth weighted round-robin: bump every accumulator by its weight,
+        // pick the largest, then charge the winner the total weight.
+        let total_weight: i64 = self.paths.iter().map(|p| p.bandwidth_bps as i64).sum();
+        for path in &mut self.paths {
+            path.current_weight += path.bandwidth_bps as i64;
+        }
+
+        let selected = self.paths.iter_mut().max_by_key(|p| p.current_weight)?;
+
+        selected.current_weight -= total_weight;
+        selected.frames_sent += 1;
+        selected.bytes_sent += frame_bytes;
+
+        Some(selected.path.path_id)
+    }
+
+    /// Get number of schedulable paths
+    #[must_use]
+    pub fn path_count(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Get per-path statistics snapshot
+    #[must_use]
+    pub fn path_stats(&self) -> Vec<PathStats> {
+        self.paths
+            .iter()
+            .map(|p| PathStats {
+                path_id: p.path.path_id,
+                rtt: p.path.rtt,
+                bandwidth_bps: p.bandwidth_bps,
+                frames_sent: p.frames_sent,
+                bytes_sent: p.bytes_sent,
+            })
+            .collect()
+    }
+}
+
+impl Default for MultipathScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -348,6 +483,111 @@ mod tests {
         assert!(validated.rtt < Duration::from_secs(1));
     }
 
+    fn test_path(path_id: u64) -> ValidatedPath {
+        ValidatedPath {
+            path_id,
+            rtt: Duration::from_millis(20),
+            validated_at: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn test_multipath_scheduler_empty() {
+        let mut scheduler = MultipathScheduler::new();
+
+        assert_eq!(scheduler.path_count(), 0);
+        assert!(scheduler.schedule(1200).is_none());
+        assert!(scheduler.path_stats().is_empty());
+    }
+
+    #[test]
+    fn test_multipath_scheduler_single_path() {
+        let mut scheduler = MultipathScheduler::new();
+        scheduler.add_path(test_path(1));
+
+        for _ in 0..10 {
+            assert_eq!(scheduler.schedule(1000), Some(1));
+        }
+
+        let stats = scheduler.path_stats();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].frames_sent, 10);
+        assert_eq!(stats[0].bytes_sent, 10_000);
+    }
+
+    #[test]
+    fn test_multipath_scheduler_equal_weights_alternate() {
+        let mut scheduler = MultipathScheduler::new();
+        scheduler.add_path(test_path(1));
+        scheduler.add_path(test_path(2));
+
+        for _ in 0..100 {
+            scheduler.schedule(1000);
+        }
+
+        let stats = scheduler.path_stats();
+        let sent: Vec<u64> = stats.iter().map(|s| s.frames_sent).collect();
+
+        // Equal weights should split frames evenly
+        assert_eq!(sent.iter().sum::<u64>(), 100);
+        assert_eq!(sent[0], 50);
+        assert_eq!(sent[1], 50);
+    }
+
+    #[test]
+    fn test_multipath_scheduler_bandwidth_weighting() {
+        let mut scheduler = MultipathScheduler::new();
+        scheduler.add_path(test_path(1));
+        scheduler.add_path(test_path(2));
+
+        // Path 1 has three times the bandwidth of path 2
+        scheduler.update_bandwidth(1, 30_000_000);
+        scheduler.update_bandwidth(2, 10_000_000);
+
+        for _ in 0..100 {
+            scheduler.schedule(1000);
+        }
+
+        let stats = scheduler.path_stats();
+        let path1 = stats.iter().find(|s| s.path_id == 1).unwrap();
+        let path2 = stats.iter().find(|s| s.path_id == 2).unwrap();
+
+        // Roughly 3:1 split
+        assert_eq!(path1.frames_sent + path2.frames_sent, 100);
+        assert!(path1.frames_sent >= 70 && path1.frames_sent <= 80);
+    }
+
+    #[test]
+    fn test_multipath_scheduler_remove_path() {
+        let mut scheduler = MultipathScheduler::new();
+        scheduler.add_path(test_path(1));
+        scheduler.add_path(test_path(2));
+        assert_eq!(scheduler.path_count(), 2);
+
+        scheduler.remove_path(1);
+        assert_eq!(scheduler.path_count(), 1);
+
+        // Remaining path takes all frames
+        for _ in 0..5 {
+            assert_eq!(scheduler.schedule(100), Some(2));
+        }
+    }
+
+    #[test]
+    fn test_multipath_scheduler_readd_preserves_counters() {
+        let mut scheduler = MultipathScheduler::new();
+        scheduler.add_path(test_path(1));
+        scheduler.schedule(500);
+
+        // Re-validating the same path must not reset its counters
+        scheduler.add_path(test_path(1));
+        assert_eq!(scheduler.path_count(), 1);
+
+        let stats = scheduler.path_stats();
+        assert_eq!(stats[0].frames_sent, 1);
+        assert_eq!(stats[0].bytes_sent, 500);
+    }
+
     #[test]
     fn test_challenge_uniqueness() {
         let mut validator = PathValidator::new(Duration::from_secs(3));
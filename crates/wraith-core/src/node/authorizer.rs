@@ -0,0 +1,370 @@
+//! Pluggable authorization for inbound sessions and transfers.
+//!
+//! [`TrustStore`](crate::node::trust::TrustStore) answers one question — is
+//! this key the one we pinned for this peer? — but embedders often need
+//! richer policy: consult LDAP group membership, ask an OPA sidecar, check a
+//! tenant database, or defer to application code across an FFI boundary.
+//! The [`Authorizer`] trait is that extension point: install one with
+//! [`Node::set_authorizer`] and it is consulted for every inbound session
+//! (after the Noise_XX handshake has authenticated the peer, before the
+//! session is admitted) and every inbound transfer offer (before the offer
+//! callback runs), with the peer's identity, the source address, and — for
+//! offers — the full file metadata.
+//!
+//! A denial closes the door with the given reason: denied sessions are torn
+//! down before any route is installed, and denied offers are REJECTed with
+//! the reason delivered to the sender. Without an installed authorizer
+//! everything is allowed, matching the node's previous behaviour; trust
+//! pinning and the offer callback still apply independently.
+//!
+//! Three implementations ship with the crate: [`AllowAll`] (the implicit
+//! default, useful as an explicit base for composition), [`Allowlist`]
+//! (only listed peers get in), and [`CallbackAuthorizer`] (adapts a
+//! synchronous closure, which is how FFI embedders bridge a C function
+//! pointer into the async trait).
+
+use crate::node::Node;
+use crate::node::file_transfer::FileMetadata;
+use crate::node::session::PeerId;
+use async_trait::async_trait;
+use std::collections::HashSet;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// What a peer is asking to do
+#[derive(Debug, Clone)]
+pub enum AuthAction {
+    /// An inbound Noise_XX handshake completed and the peer wants a session
+    SessionEstablish,
+    /// The peer offers a file transfer over an established session
+    TransferOffer {
+        /// Metadata of the offered file (name, size, chunking, compression)
+        metadata: FileMetadata,
+        /// Chunk the sender proposes to resume from (`0` = fresh transfer)
+        resume_from_chunk: u64,
+        /// Whether the offer carried a signed manifest that verified
+        ///
+        /// Offers whose manifest fails verification never reach the
+        /// authorizer; they are rejected outright.
+        signed: bool,
+    },
+}
+
+/// One authorization request presented to the [`Authorizer`]
+#[derive(Debug, Clone)]
+pub struct AuthRequest {
+    /// The requesting peer, authenticated by the Noise handshake
+    pub peer_id: PeerId,
+    /// The peer's source address, when the transport knows it
+    pub source_addr: Option<SocketAddr>,
+    /// What the peer is asking to do
+    pub action: AuthAction,
+}
+
+/// The authorizer's verdict on an [`AuthRequest`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthDecision {
+    /// Let the request proceed
+    Allow,
+    /// Refuse the request; the reason reaches logs and — for transfer
+    /// offers — the denied sender
+    Deny(String),
+}
+
+impl AuthDecision {
+    /// Whether this decision lets the request proceed
+    #[must_use]
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, Self::Allow)
+    }
+}
+
+/// Policy hook consulted for inbound sessions and transfer offers
+///
+/// Implementations may await external services (LDAP, OPA, databases), but
+/// they run on the packet-handling task: a slow decision stalls that
+/// session's inbound processing, so cache aggressively or keep a local
+/// replica of the policy.
+#[async_trait]
+pub trait Authorizer: Send + Sync {
+    /// Decide whether the request proceeds
+    async fn authorize(&self, request: &AuthRequest) -> AuthDecision;
+}
+
+/// Permits every request
+///
+/// Matches the node's behaviour when no authorizer is installed; useful as
+/// an explicit base when composing policies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AllowAll;
+
+#[async_trait]
+impl Authorizer for AllowAll {
+    async fn authorize(&self, _request: &AuthRequest) -> AuthDecision {
+        AuthDecision::Allow
+    }
+}
+
+/// Permits only peers on an explicit allowlist
+///
+/// The list can be changed while installed; decisions always reflect the
+/// current membership.
+#[derive(Debug, Default)]
+pub struct Allowlist {
+    peers: std::sync::RwLock<HashSet<PeerId>>,
+}
+
+impl Allowlist {
+    /// Create an allowlist admitting exactly the given peers
+    #[must_use]
+    pub fn new(peers: impl IntoIterator<Item = PeerId>) -> Self {
+        Self {
+            peers: std::sync::RwLock::new(peers.into_iter().collect()),
+        }
+    }
+
+    /// Add a peer to the allowlist
+    pub fn allow(&self, peer_id: PeerId) {
+        self.peers
+            .write()
+            .expect("allowlist lock poisoned")
+            .insert(peer_id);
+    }
+
+    /// Remove a peer; returns whether it was listed
+    ///
+    /// Already-established sessions are not torn down; revocation applies
+    /// to the peer's next session or offer.
+    pub fn revoke(&self, peer_id: &PeerId) -> bool {
+        self.peers
+            .write()
+            .expect("allowlist lock poisoned")
+            .remove(peer_id)
+    }
+
+    /// Whether a peer is currently listed
+    #[must_use]
+    pub fn contains(&self, peer_id: &PeerId) -> bool {
+        self.peers
+            .read()
+            .expect("allowlist lock poisoned")
+            .contains(peer_id)
+    }
+}
+
+#[async_trait]
+impl Authorizer for Allowlist {
+    async fn authorize(&self, request: &AuthRequest) -> AuthDecision {
+        if self.contains(&request.peer_id) {
+            AuthDecision::Allow
+        } else {
+            AuthDecision::Deny("Peer not on allowlist".to_string())
+        }
+    }
+}
+
+/// Adapts a synchronous decision callback to the [`Authorizer`] trait
+///
+/// This is the bridge FFI embedders use: wrap the C function pointer in a
+/// closure and install the result. The callback runs inline on the
+/// packet-handling task, so it must not block.
+pub struct CallbackAuthorizer {
+    callback: Arc<dyn Fn(&AuthRequest) -> AuthDecision + Send + Sync>,
+}
+
+impl CallbackAuthorizer {
+    /// Wrap a synchronous callback
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&AuthRequest) -> AuthDecision + Send + Sync + 'static,
+    {
+        Self {
+            callback: Arc::new(callback),
+        }
+    }
+}
+
+impl std::fmt::Debug for CallbackAuthorizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackAuthorizer").finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Authorizer for CallbackAuthorizer {
+    async fn authorize(&self, request: &AuthRequest) -> AuthDecision {
+        (self.callback)(request)
+    }
+}
+
+impl Node {
+    /// Install an authorizer consulted for inbound sessions and offers
+    ///
+    /// Replaces any previously installed authorizer. See the module docs
+    /// for exactly where the hook runs.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(node: wraith_core::node::Node, partner: [u8; 32]) {
+    /// use wraith_core::node::authorizer::Allowlist;
+    ///
+    /// node.set_authorizer(Allowlist::new([partner]));
+    /// # }
+    /// ```
+    pub fn set_authorizer(&self, authorizer: impl Authorizer + 'static) {
+        *self
+            .inner
+            .authorizer
+            .write()
+            .expect("authorizer lock poisoned") = Some(Arc::new(authorizer));
+    }
+
+    /// Remove the installed authorizer, returning to allow-all behaviour
+    pub fn clear_authorizer(&self) {
+        *self
+            .inner
+            .authorizer
+            .write()
+            .expect("authorizer lock poisoned") = None;
+    }
+
+    /// Run a request past the installed authorizer (allow when none is set)
+    pub(crate) async fn authorize(&self, request: &AuthRequest) -> AuthDecision {
+        let authorizer = self
+            .inner
+            .authorizer
+            .read()
+            .expect("authorizer lock poisoned")
+            .clone();
+
+        match authorizer {
+            Some(authorizer) => authorizer.authorize(request).await,
+            None => AuthDecision::Allow,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session_request(peer_id: PeerId) -> AuthRequest {
+        AuthRequest {
+            peer_id,
+            source_addr: Some("203.0.113.9:4433".parse().unwrap()),
+            action: AuthAction::SessionEstablish,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_allow_all_permits_everything() {
+        let authorizer = AllowAll;
+        let decision = authorizer.authorize(&session_request([1u8; 32])).await;
+        assert!(decision.is_allowed());
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_admits_only_listed_peers() {
+        let friend = [2u8; 32];
+        let stranger = [3u8; 32];
+        let authorizer = Allowlist::new([friend]);
+
+        assert!(
+            authorizer
+                .authorize(&session_request(friend))
+                .await
+                .is_allowed()
+        );
+        assert_eq!(
+            authorizer.authorize(&session_request(stranger)).await,
+            AuthDecision::Deny("Peer not on allowlist".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_allowlist_membership_is_live() {
+        let peer = [4u8; 32];
+        let authorizer = Allowlist::new([]);
+        assert!(
+            !authorizer
+                .authorize(&session_request(peer))
+                .await
+                .is_allowed()
+        );
+
+        authorizer.allow(peer);
+        assert!(
+            authorizer
+                .authorize(&session_request(peer))
+                .await
+                .is_allowed()
+        );
+
+        assert!(authorizer.revoke(&peer));
+        assert!(!authorizer.contains(&peer));
+        assert!(!authorizer.revoke(&peer));
+    }
+
+    #[tokio::test]
+    async fn test_callback_authorizer_sees_request_fields() {
+        let authorizer = CallbackAuthorizer::new(|request: &AuthRequest| match &request.action {
+            AuthAction::TransferOffer { metadata, .. } if metadata.file_size > 1 << 30 => {
+                AuthDecision::Deny("files over 1 GiB not accepted".to_string())
+            }
+            _ => AuthDecision::Allow,
+        });
+
+        assert!(
+            authorizer
+                .authorize(&session_request([5u8; 32]))
+                .await
+                .is_allowed()
+        );
+
+        let big_offer = AuthRequest {
+            peer_id: [5u8; 32],
+            source_addr: None,
+            action: AuthAction::TransferOffer {
+                metadata: FileMetadata {
+                    transfer_id: [0u8; 32],
+                    file_name: "huge.iso".to_string(),
+                    file_size: 2 << 30,
+                    chunk_size: 256 * 1024,
+                    total_chunks: 8192,
+                    root_hash: [0u8; 32],
+                    compression: crate::transfer::compression::CompressionAlgorithm::None,
+                },
+                resume_from_chunk: 0,
+                signed: false,
+            },
+        };
+        assert!(!authorizer.authorize(&big_offer).await.is_allowed());
+    }
+
+    #[tokio::test]
+    async fn test_node_without_authorizer_allows() {
+        let node = Node::new_random().await.unwrap();
+        let decision = node.authorize(&session_request([6u8; 32])).await;
+        assert!(decision.is_allowed());
+    }
+
+    #[tokio::test]
+    async fn test_node_set_and_clear_authorizer() {
+        let node = Node::new_random().await.unwrap();
+        node.set_authorizer(Allowlist::new([]));
+        assert!(
+            !node
+                .authorize(&session_request([7u8; 32]))
+                .await
+                .is_allowed()
+        );
+
+        node.clear_authorizer();
+        assert!(
+            node.authorize(&session_request([7u8; 32]))
+                .await
+                .is_allowed()
+        );
+    }
+}
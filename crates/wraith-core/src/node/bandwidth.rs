@@ -0,0 +1,535 @@
+//! Egress bandwidth limiting
+//!
+//! Implements a tiered token-bucket limiter that caps outgoing bandwidth:
+//! - **Global**: total egress across all sessions
+//! - **Per-session**: egress to a single peer
+//! - **Per-transfer**: egress for a single file transfer
+//!
+//! Buckets use a debt model: sends are debited immediately and the limiter
+//! reports how long the caller must wait before the next send. This paces
+//! traffic at exactly the configured rate instead of dropping frames, so
+//! the limiter cooperates with BBR rather than triggering loss-based
+//! backoff. [`BandwidthLimiter::capped_pacing_rate`] additionally clamps
+//! BBR's pacing rate to the configured caps so the congestion controller
+//! never schedules bursts the limiter would have to absorb.
+
+use dashmap::DashMap;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// Minimum burst allowance so a full frame can always be debited at once
+const MIN_BURST_BYTES: f64 = 64.0 * 1024.0;
+
+/// Burst window as a fraction of the rate (100 ms worth of tokens)
+const BURST_WINDOW: f64 = 0.1;
+
+/// Configured bandwidth caps (bytes per second)
+///
+/// `None` means unlimited at that tier. All tiers default to unlimited.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BandwidthLimits {
+    /// Total egress cap across all sessions (bytes/sec)
+    pub global_bps: Option<u64>,
+
+    /// Egress cap per peer session (bytes/sec)
+    pub per_session_bps: Option<u64>,
+
+    /// Egress cap per file transfer (bytes/sec)
+    pub per_transfer_bps: Option<u64>,
+}
+
+impl BandwidthLimits {
+    /// Whether no tier has a configured cap
+    #[must_use]
+    pub fn is_unlimited(&self) -> bool {
+        self.global_bps.is_none()
+            && self.per_session_bps.is_none()
+            && self.per_transfer_bps.is_none()
+    }
+}
+
+/// Parse a human-readable rate like `10MB/s`, `500KB/s`, or `1048576`
+///
+/// Accepts an optional binary suffix (`B`, `KB`, `MB`, `GB`, case-insensitive)
+/// and an optional trailing `/s`. Bare numbers are bytes per second. Returns
+/// `None` for zero, negative, or unparseable rates.
+#[must_use]
+pub fn parse_rate(input: &str) -> Option<u64> {
+    let s = input.trim();
+    let s = s
+        .strip_suffix("/s")
+        .or_else(|| s.strip_suffix("/S"))
+        .unwrap_or(s);
+
+    let split = s
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(s.len());
+    let (number, suffix) = s.split_at(split);
+
+    let value: f64 = number.parse().ok()?;
+    let multiplier = match suffix.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1.0,
+        "K" | "KB" => 1024.0,
+        "M" | "MB" => 1024.0 * 1024.0,
+        "G" | "GB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+
+    let bytes = value * multiplier;
+    if bytes >= 1.0 {
+        Some(bytes as u64)
+    } else {
+        None
+    }
+}
+
+/// Token bucket that debits immediately and reports the required pacing delay
+#[derive(Debug)]
+struct PacedBucket {
+    /// Current tokens (may go negative - debt to be paid off by waiting)
+    tokens: f64,
+
+    /// Refill rate (bytes per second)
+    rate: f64,
+
+    /// Maximum accumulated tokens (burst allowance)
+    burst: f64,
+
+    /// Last refill time
+    last_refill: Instant,
+}
+
+impl PacedBucket {
+    fn new(rate_bps: u64) -> Self {
+        let rate = rate_bps.max(1) as f64;
+        let burst = (rate * BURST_WINDOW).max(MIN_BURST_BYTES);
+        Self {
+            tokens: burst,
+            rate,
+            burst,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Debit `bytes` and return how long the caller must wait before sending
+    fn reserve(&mut self, bytes: u64) -> Duration {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.last_refill = now;
+
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.tokens -= bytes as f64;
+
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.rate)
+        }
+    }
+
+    /// Debit `bytes` only if the bucket stays non-negative
+    ///
+    /// Unlike [`reserve`](Self::reserve), this never creates debt: it is
+    /// used for cover traffic, which may only consume surplus tokens and
+    /// must never delay real data.
+    fn try_reserve(&mut self, bytes: u64) -> bool {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+        self.last_refill = now;
+
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        if self.tokens >= bytes as f64 {
+            self.tokens -= bytes as f64;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// Tiered egress bandwidth limiter
+///
+/// Thread-safe; shared across the packet handler and transfer tasks via
+/// `Arc`. Sessions and transfers get lazily-created buckets that must be
+/// released with [`remove_session`](Self::remove_session) /
+/// [`remove_transfer`](Self::remove_transfer) when they end.
+#[derive(Debug)]
+pub struct BandwidthLimiter {
+    /// Configured caps (hot-reloadable via [`set_limits`](Self::set_limits))
+    limits: RwLock<BandwidthLimits>,
+
+    /// Global bucket (present only when a global cap is set)
+    global: Mutex<Option<PacedBucket>>,
+
+    /// Per-session buckets (session key -> bucket)
+    sessions: DashMap<[u8; 32], Mutex<PacedBucket>>,
+
+    /// Per-transfer buckets (transfer ID -> bucket)
+    transfers: DashMap<[u8; 32], Mutex<PacedBucket>>,
+
+    /// Per-transfer rate overrides (transfer ID -> bytes/sec)
+    transfer_overrides: DashMap<[u8; 32], u64>,
+
+    /// Cover traffic budget bucket (present only when a budget is set)
+    cover: Option<Mutex<PacedBucket>>,
+}
+
+impl BandwidthLimiter {
+    /// Create a limiter with the given caps
+    #[must_use]
+    pub fn new(limits: BandwidthLimits) -> Self {
+        Self {
+            limits: RwLock::new(limits),
+            global: Mutex::new(limits.global_bps.map(PacedBucket::new)),
+            sessions: DashMap::new(),
+            transfers: DashMap::new(),
+            transfer_overrides: DashMap::new(),
+            cover: None,
+        }
+    }
+
+    /// Set the cover traffic budget (bytes/sec, `None` = unbudgeted)
+    #[must_use]
+    pub fn with_cover_budget(mut self, budget_bps: Option<u64>) -> Self {
+        self.cover = budget_bps.map(|bps| Mutex::new(PacedBucket::new(bps)));
+        self
+    }
+
+    /// Configured caps
+    #[must_use]
+    pub fn limits(&self) -> BandwidthLimits {
+        *self.limits.read().expect("limits lock poisoned")
+    }
+
+    /// Replace the configured caps without dropping traffic
+    ///
+    /// Used by config hot-reload: the global bucket is rebuilt at the new
+    /// rate and per-session/per-transfer buckets are recreated lazily on
+    /// their next send, so in-flight transfers just change pace. Buckets
+    /// for transfers with an explicit override keep their override.
+    pub fn set_limits(&self, limits: BandwidthLimits) {
+        *self.limits.write().expect("limits lock poisoned") = limits;
+        *self.global.lock().expect("bucket lock poisoned") =
+            limits.global_bps.map(PacedBucket::new);
+        self.sessions.clear();
+        self.transfers
+            .retain(|id, _| self.transfer_overrides.contains_key(id));
+    }
+
+    /// Whether no tier has a configured cap (fast path for the hot loop)
+    #[must_use]
+    pub fn is_unlimited(&self) -> bool {
+        self.limits().is_unlimited() && self.transfer_overrides.is_empty()
+    }
+
+    /// Override the rate cap for one transfer (e.g. from `--limit` on the CLI)
+    pub fn set_transfer_limit(&self, transfer_id: &[u8; 32], bps: u64) {
+        self.transfer_overrides.insert(*transfer_id, bps.max(1));
+        // Replace any existing bucket so the new rate takes effect immediately
+        self.transfers
+            .insert(*transfer_id, Mutex::new(PacedBucket::new(bps.max(1))));
+    }
+
+    /// Debit a session send and sleep for any required pacing delay
+    ///
+    /// Applies the global and per-session caps. Returns immediately when
+    /// neither is configured.
+    pub async fn acquire(&self, session_key: &[u8; 32], bytes: u64) {
+        let mut delay = Duration::ZERO;
+
+        if let Some(global) = self.global.lock().expect("bucket lock poisoned").as_mut() {
+            delay = delay.max(global.reserve(bytes));
+        }
+
+        if let Some(bps) = self.limits().per_session_bps {
+            let bucket = self
+                .sessions
+                .entry(*session_key)
+                .or_insert_with(|| Mutex::new(PacedBucket::new(bps)));
+            delay = delay.max(bucket.lock().expect("bucket lock poisoned").reserve(bytes));
+        }
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Debit a transfer send and sleep for any required pacing delay
+    ///
+    /// Applies the per-transfer cap (an override set via
+    /// [`set_transfer_limit`](Self::set_transfer_limit) takes precedence over
+    /// the configured default). Returns immediately when neither is set.
+    pub async fn acquire_transfer(&self, transfer_id: &[u8; 32], bytes: u64) {
+        let bps = self
+            .transfer_overrides
+            .get(transfer_id)
+            .map(|r| *r)
+            .or(self.limits().per_transfer_bps);
+
+        let Some(bps) = bps else { return };
+
+        let delay = {
+            let bucket = self
+                .transfers
+                .entry(*transfer_id)
+                .or_insert_with(|| Mutex::new(PacedBucket::new(bps)));
+            bucket.lock().expect("bucket lock poisoned").reserve(bytes)
+        };
+
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Admit or reject a cover traffic (PAD) send without blocking
+    ///
+    /// Cover traffic shares the real pacer but at strict lower priority:
+    /// it must fit inside its own budget *and* the surplus tokens of the
+    /// global and per-session buckets. A rejected decoy is simply skipped -
+    /// it is never queued, so real data is never delayed behind it.
+    #[must_use]
+    pub fn admit_cover(&self, session_key: &[u8; 32], bytes: u64) -> bool {
+        // Budget check first: a rejected decoy must not have consumed
+        // surplus real-traffic tokens
+        if let Some(cover) = &self.cover
+            && !cover
+                .lock()
+                .expect("bucket lock poisoned")
+                .try_reserve(bytes)
+        {
+            return false;
+        }
+
+        if let Some(global) = self.global.lock().expect("bucket lock poisoned").as_mut()
+            && !global.try_reserve(bytes)
+        {
+            return false;
+        }
+
+        if let Some(bps) = self.limits().per_session_bps {
+            let bucket = self
+                .sessions
+                .entry(*session_key)
+                .or_insert_with(|| Mutex::new(PacedBucket::new(bps)));
+            if !bucket
+                .lock()
+                .expect("bucket lock poisoned")
+                .try_reserve(bytes)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Clamp a BBR pacing rate to the configured global/per-session caps
+    ///
+    /// Keeps the congestion controller from scheduling bursts faster than
+    /// the limiter will admit, so pacing and the cap do not fight.
+    #[must_use]
+    pub fn capped_pacing_rate(&self, bbr_rate_bps: u64) -> u64 {
+        let limits = self.limits();
+        let mut rate = bbr_rate_bps;
+        if let Some(global) = limits.global_bps {
+            rate = rate.min(global);
+        }
+        if let Some(per_session) = limits.per_session_bps {
+            rate = rate.min(per_session);
+        }
+        rate
+    }
+
+    /// Release the bucket for an ended session
+    pub fn remove_session(&self, session_key: &[u8; 32]) {
+        self.sessions.remove(session_key);
+    }
+
+    /// Release the bucket and any override for a completed transfer
+    pub fn remove_transfer(&self, transfer_id: &[u8; 32]) {
+        self.transfers.remove(transfer_id);
+        self.transfer_overrides.remove(transfer_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate() {
+        assert_eq!(parse_rate("1024"), Some(1024));
+        assert_eq!(parse_rate("10KB/s"), Some(10 * 1024));
+        assert_eq!(parse_rate("10MB/s"), Some(10 * 1024 * 1024));
+        assert_eq!(parse_rate("1GB/s"), Some(1024 * 1024 * 1024));
+        assert_eq!(parse_rate("1.5MB/s"), Some((1.5 * 1024.0 * 1024.0) as u64));
+        assert_eq!(parse_rate(" 500 kb/s "), Some(500 * 1024));
+        assert_eq!(parse_rate("10M"), Some(10 * 1024 * 1024));
+    }
+
+    #[test]
+    fn test_parse_rate_rejects_invalid() {
+        assert_eq!(parse_rate(""), None);
+        assert_eq!(parse_rate("0"), None);
+        assert_eq!(parse_rate("fast"), None);
+        assert_eq!(parse_rate("10TB/s"), None);
+        assert_eq!(parse_rate("-5MB/s"), None);
+    }
+
+    #[test]
+    fn test_bucket_allows_burst_then_paces() {
+        let mut bucket = PacedBucket::new(1_000_000); // 1 MB/s, 100 KB burst
+
+        // Within the burst allowance - no delay
+        assert_eq!(bucket.reserve(50_000), Duration::ZERO);
+
+        // Deep into debt - delay proportional to the overdraft
+        let delay = bucket.reserve(1_000_000);
+        assert!(delay > Duration::from_millis(800));
+        assert!(delay < Duration::from_millis(1_100));
+    }
+
+    #[test]
+    fn test_unlimited_by_default() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits::default());
+        assert!(limiter.is_unlimited());
+        assert_eq!(limiter.capped_pacing_rate(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_capped_pacing_rate() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits {
+            global_bps: Some(10_000_000),
+            per_session_bps: Some(2_000_000),
+            per_transfer_bps: None,
+        });
+
+        // Clamped to the tightest cap
+        assert_eq!(limiter.capped_pacing_rate(50_000_000), 2_000_000);
+        // BBR rate below the caps passes through
+        assert_eq!(limiter.capped_pacing_rate(1_000_000), 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_acquire_unlimited_never_blocks() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits::default());
+        let start = Instant::now();
+        limiter.acquire(&[1u8; 32], 100_000_000).await;
+        assert!(start.elapsed() < Duration::from_millis(50));
+    }
+
+    #[tokio::test]
+    async fn test_acquire_paces_session() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits {
+            per_session_bps: Some(1_000_000),
+            ..Default::default()
+        });
+
+        let session = [2u8; 32];
+        let start = Instant::now();
+        // Burst (100 KB) plus ~200 ms of debt at 1 MB/s
+        for _ in 0..3 {
+            limiter.acquire(&session, 100_000).await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(150));
+    }
+
+    #[tokio::test]
+    async fn test_transfer_override_takes_precedence() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits {
+            per_transfer_bps: Some(1_000),
+            ..Default::default()
+        });
+
+        let transfer = [3u8; 32];
+        // Override with a much higher rate - the small sends below must not
+        // accumulate meaningful debt against the 1 KB/s default
+        limiter.set_transfer_limit(&transfer, 100_000_000);
+
+        let start = Instant::now();
+        for _ in 0..4 {
+            limiter.acquire_transfer(&transfer, 100_000).await;
+        }
+        assert!(start.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_remove_transfer_clears_override() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits::default());
+        let transfer = [4u8; 32];
+
+        limiter.set_transfer_limit(&transfer, 1_000);
+        assert!(!limiter.is_unlimited());
+
+        limiter.remove_transfer(&transfer);
+        assert!(limiter.is_unlimited());
+    }
+
+    #[test]
+    fn test_set_limits_hot_swap() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits::default());
+        assert!(limiter.is_unlimited());
+
+        limiter.set_limits(BandwidthLimits {
+            global_bps: Some(5_000_000),
+            ..Default::default()
+        });
+        assert_eq!(limiter.limits().global_bps, Some(5_000_000));
+        assert_eq!(limiter.capped_pacing_rate(u64::MAX), 5_000_000);
+
+        // Per-transfer overrides survive a limits swap
+        let transfer = [8u8; 32];
+        limiter.set_transfer_limit(&transfer, 1_000);
+        limiter.set_limits(BandwidthLimits::default());
+        assert!(!limiter.is_unlimited());
+        assert_eq!(limiter.limits().global_bps, None);
+    }
+
+    #[test]
+    fn test_cover_unbudgeted_always_admitted() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits::default());
+        assert!(limiter.admit_cover(&[6u8; 32], 1_000_000_000));
+    }
+
+    #[test]
+    fn test_cover_budget_rejects_without_blocking() {
+        let limiter =
+            BandwidthLimiter::new(BandwidthLimits::default()).with_cover_budget(Some(1_000));
+
+        let session = [7u8; 32];
+        // Burst floor is 64 KB - drain it, then the budget must reject
+        assert!(limiter.admit_cover(&session, 64 * 1024));
+        assert!(!limiter.admit_cover(&session, 64 * 1024));
+    }
+
+    #[test]
+    fn test_cover_only_consumes_surplus() {
+        let mut bucket = PacedBucket::new(1_000_000); // 100 KB burst
+
+        // Surplus available - cover send admitted
+        assert!(bucket.try_reserve(10_000));
+
+        // Real data drives the bucket into debt; cover is now rejected
+        // instead of deepening the debt and delaying the next real send
+        bucket.reserve(200_000);
+        assert!(!bucket.try_reserve(1_000));
+        let delay = bucket.reserve(0);
+        assert!(delay <= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_session_bucket_lifecycle() {
+        let limiter = BandwidthLimiter::new(BandwidthLimits {
+            per_session_bps: Some(1_000_000),
+            ..Default::default()
+        });
+
+        let session = [5u8; 32];
+        // Lazily created on first acquire
+        limiter.acquire(&session, 10).await;
+        assert_eq!(limiter.sessions.len(), 1);
+
+        limiter.remove_session(&session);
+        assert_eq!(limiter.sessions.len(), 0);
+    }
+}
@@ -0,0 +1,360 @@
+//! Fault injection for resilience testing (feature `chaos`)
+//!
+//! Compiled only with the `chaos` feature, this module lets a running
+//! node inject faults into its own data path so retry, resume, and
+//! failover logic can be exercised on real deployments:
+//!
+//! - drop a fraction of incoming frames
+//! - delay outgoing ACK frames
+//! - corrupt received chunks before they reach the reassembler
+//! - kill a session at a random moment
+//! - fail disk writes during receive
+//!
+//! The injector is lock-free (atomics only) so enabling it does not
+//! perturb the hot path it is testing. Faults are configured through
+//! [`Node::set_chaos`](crate::node::Node::set_chaos) or bootstrapped
+//! from the `WRAITH_CHAOS` environment variable (JSON, same shape as
+//! [`ChaosConfig`]) until the daemon grows a control socket.
+//!
+//! This is a test facility: probabilities apply independently per event,
+//! and all injected faults are counted so a run can be correlated with
+//! observed recovery behavior.
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Fault-injection configuration
+///
+/// All probabilities are fractions in `0.0..=1.0` and are clamped on
+/// application. The default configuration injects nothing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ChaosConfig {
+    /// Master switch; when false no faults are injected
+    pub enabled: bool,
+    /// Probability of dropping an incoming frame
+    pub drop_frame: f64,
+    /// Delay added to outgoing ACK frames, in milliseconds
+    pub ack_delay_ms: u64,
+    /// Probability of corrupting a received chunk before reassembly
+    pub corrupt_chunk: f64,
+    /// Probability, per incoming frame, of killing that session
+    pub kill_session: f64,
+    /// Probability of failing a chunk disk write
+    pub fail_disk_write: f64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            drop_frame: 0.0,
+            ack_delay_ms: 0,
+            corrupt_chunk: 0.0,
+            kill_session: 0.0,
+            fail_disk_write: 0.0,
+        }
+    }
+}
+
+impl ChaosConfig {
+    /// Load configuration from the `WRAITH_CHAOS` environment variable
+    ///
+    /// Returns `None` when the variable is unset or does not parse.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let raw = std::env::var("WRAITH_CHAOS").ok()?;
+        match serde_json::from_str(&raw) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                tracing::warn!("Ignoring malformed WRAITH_CHAOS: {e}");
+                None
+            }
+        }
+    }
+}
+
+/// Counters for faults actually injected
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChaosStats {
+    /// Incoming frames dropped
+    pub frames_dropped: u64,
+    /// Outgoing ACK frames delayed
+    pub acks_delayed: u64,
+    /// Received chunks corrupted
+    pub chunks_corrupted: u64,
+    /// Sessions killed
+    pub sessions_killed: u64,
+    /// Disk writes failed
+    pub writes_failed: u64,
+}
+
+/// Lock-free fault injector queried from the node's data path
+///
+/// Probabilities are stored as parts-per-million so the whole state fits
+/// in atomics; reads on the hot path are single relaxed loads when chaos
+/// is disabled.
+#[derive(Debug, Default)]
+pub struct ChaosInjector {
+    enabled: AtomicBool,
+    drop_frame_ppm: AtomicU32,
+    ack_delay_ms: AtomicU64,
+    corrupt_chunk_ppm: AtomicU32,
+    kill_session_ppm: AtomicU32,
+    fail_disk_write_ppm: AtomicU32,
+    frames_dropped: AtomicU64,
+    acks_delayed: AtomicU64,
+    chunks_corrupted: AtomicU64,
+    sessions_killed: AtomicU64,
+    writes_failed: AtomicU64,
+}
+
+/// Convert a probability fraction to clamped parts-per-million
+fn to_ppm(fraction: f64) -> u32 {
+    (fraction.clamp(0.0, 1.0) * 1_000_000.0) as u32
+}
+
+impl ChaosInjector {
+    /// Create a disabled injector
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Apply a configuration, replacing the previous one atomically
+    /// per field (readers may briefly observe a mix during the update)
+    pub fn apply(&self, config: &ChaosConfig) {
+        self.drop_frame_ppm
+            .store(to_ppm(config.drop_frame), Ordering::Relaxed);
+        self.ack_delay_ms
+            .store(config.ack_delay_ms, Ordering::Relaxed);
+        self.corrupt_chunk_ppm
+            .store(to_ppm(config.corrupt_chunk), Ordering::Relaxed);
+        self.kill_session_ppm
+            .store(to_ppm(config.kill_session), Ordering::Relaxed);
+        self.fail_disk_write_ppm
+            .store(to_ppm(config.fail_disk_write), Ordering::Relaxed);
+        self.enabled.store(config.enabled, Ordering::Relaxed);
+        if config.enabled {
+            tracing::warn!("Chaos fault injection ENABLED: {config:?}");
+        } else {
+            tracing::info!("Chaos fault injection disabled");
+        }
+    }
+
+    /// Current configuration snapshot
+    #[must_use]
+    pub fn snapshot(&self) -> ChaosConfig {
+        ChaosConfig {
+            enabled: self.enabled.load(Ordering::Relaxed),
+            drop_frame: f64::from(self.drop_frame_ppm.load(Ordering::Relaxed)) / 1_000_000.0,
+            ack_delay_ms: self.ack_delay_ms.load(Ordering::Relaxed),
+            corrupt_chunk: f64::from(self.corrupt_chunk_ppm.load(Ordering::Relaxed)) / 1_000_000.0,
+            kill_session: f64::from(self.kill_session_ppm.load(Ordering::Relaxed)) / 1_000_000.0,
+            fail_disk_write: f64::from(self.fail_disk_write_ppm.load(Ordering::Relaxed))
+                / 1_000_000.0,
+        }
+    }
+
+    /// Counters for faults injected so far
+    #[must_use]
+    pub fn stats(&self) -> ChaosStats {
+        ChaosStats {
+            frames_dropped: self.frames_dropped.load(Ordering::Relaxed),
+            acks_delayed: self.acks_delayed.load(Ordering::Relaxed),
+            chunks_corrupted: self.chunks_corrupted.load(Ordering::Relaxed),
+            sessions_killed: self.sessions_killed.load(Ordering::Relaxed),
+            writes_failed: self.writes_failed.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Should this incoming frame be dropped?
+    pub fn should_drop_frame(&self) -> bool {
+        if self.roll(&self.drop_frame_ppm) {
+            self.frames_dropped.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Delay to apply to an outgoing ACK frame, if any
+    pub fn ack_delay(&self) -> Option<Duration> {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return None;
+        }
+        let ms = self.ack_delay_ms.load(Ordering::Relaxed);
+        if ms == 0 {
+            return None;
+        }
+        self.acks_delayed.fetch_add(1, Ordering::Relaxed);
+        Some(Duration::from_millis(ms))
+    }
+
+    /// Possibly corrupt a received chunk in place (one random byte is
+    /// flipped); returns whether corruption was injected
+    pub fn maybe_corrupt_chunk(&self, chunk: &mut [u8]) -> bool {
+        if chunk.is_empty() || !self.roll(&self.corrupt_chunk_ppm) {
+            return false;
+        }
+        let index = rand::thread_rng().gen_range(0..chunk.len());
+        chunk[index] ^= 0xFF;
+        self.chunks_corrupted.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+
+    /// Should the session this frame arrived on be killed?
+    pub fn should_kill_session(&self) -> bool {
+        if self.roll(&self.kill_session_ppm) {
+            self.sessions_killed.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Should this chunk disk write fail?
+    pub fn should_fail_write(&self) -> bool {
+        if self.roll(&self.fail_disk_write_ppm) {
+            self.writes_failed.fetch_add(1, Ordering::Relaxed);
+            true
+        } else {
+            false
+        }
+    }
+
+    /// One weighted coin flip against a parts-per-million probability
+    fn roll(&self, ppm: &AtomicU32) -> bool {
+        if !self.enabled.load(Ordering::Relaxed) {
+            return false;
+        }
+        let threshold = ppm.load(Ordering::Relaxed);
+        if threshold == 0 {
+            return false;
+        }
+        rand::thread_rng().gen_range(0..1_000_000u32) < threshold
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_injector_never_fires() {
+        let injector = ChaosInjector::new();
+        injector.apply(&ChaosConfig {
+            enabled: false,
+            drop_frame: 1.0,
+            ack_delay_ms: 100,
+            corrupt_chunk: 1.0,
+            kill_session: 1.0,
+            fail_disk_write: 1.0,
+        });
+
+        for _ in 0..100 {
+            assert!(!injector.should_drop_frame());
+            assert!(!injector.should_kill_session());
+            assert!(!injector.should_fail_write());
+        }
+        assert_eq!(injector.ack_delay(), None);
+        assert_eq!(injector.stats(), ChaosStats::default());
+    }
+
+    #[test]
+    fn test_certain_faults_always_fire() {
+        let injector = ChaosInjector::new();
+        injector.apply(&ChaosConfig {
+            enabled: true,
+            drop_frame: 1.0,
+            ack_delay_ms: 25,
+            corrupt_chunk: 1.0,
+            kill_session: 1.0,
+            fail_disk_write: 1.0,
+        });
+
+        assert!(injector.should_drop_frame());
+        assert!(injector.should_kill_session());
+        assert!(injector.should_fail_write());
+        assert_eq!(injector.ack_delay(), Some(Duration::from_millis(25)));
+
+        let mut chunk = vec![0u8; 64];
+        assert!(injector.maybe_corrupt_chunk(&mut chunk));
+        assert!(chunk.iter().any(|&b| b != 0));
+
+        let stats = injector.stats();
+        assert_eq!(stats.frames_dropped, 1);
+        assert_eq!(stats.acks_delayed, 1);
+        assert_eq!(stats.chunks_corrupted, 1);
+        assert_eq!(stats.sessions_killed, 1);
+        assert_eq!(stats.writes_failed, 1);
+    }
+
+    #[test]
+    fn test_zero_probability_never_fires() {
+        let injector = ChaosInjector::new();
+        injector.apply(&ChaosConfig {
+            enabled: true,
+            ..ChaosConfig::default()
+        });
+
+        for _ in 0..100 {
+            assert!(!injector.should_drop_frame());
+        }
+        let mut chunk = vec![0u8; 16];
+        assert!(!injector.maybe_corrupt_chunk(&mut chunk));
+    }
+
+    #[test]
+    fn test_empty_chunk_is_never_corrupted() {
+        let injector = ChaosInjector::new();
+        injector.apply(&ChaosConfig {
+            enabled: true,
+            corrupt_chunk: 1.0,
+            ..ChaosConfig::default()
+        });
+        assert!(!injector.maybe_corrupt_chunk(&mut []));
+    }
+
+    #[test]
+    fn test_snapshot_roundtrip() {
+        let injector = ChaosInjector::new();
+        let config = ChaosConfig {
+            enabled: true,
+            drop_frame: 0.05,
+            ack_delay_ms: 200,
+            corrupt_chunk: 0.01,
+            kill_session: 0.001,
+            fail_disk_write: 0.02,
+        };
+        injector.apply(&config);
+
+        let snapshot = injector.snapshot();
+        assert!(snapshot.enabled);
+        assert!((snapshot.drop_frame - 0.05).abs() < 1e-6);
+        assert_eq!(snapshot.ack_delay_ms, 200);
+        assert!((snapshot.kill_session - 0.001).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_probabilities_are_clamped() {
+        let injector = ChaosInjector::new();
+        injector.apply(&ChaosConfig {
+            enabled: true,
+            drop_frame: 7.5,
+            ..ChaosConfig::default()
+        });
+        assert!((injector.snapshot().drop_frame - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_config_json_shape() {
+        let config: ChaosConfig =
+            serde_json::from_str(r#"{"enabled":true,"drop_frame":0.1}"#).unwrap();
+        assert!(config.enabled);
+        assert!((config.drop_frame - 0.1).abs() < 1e-6);
+        assert_eq!(config.ack_delay_ms, 0);
+    }
+}
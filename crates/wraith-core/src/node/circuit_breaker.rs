@@ -23,7 +23,7 @@ pub enum CircuitState {
 }
 
 /// Circuit breaker configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CircuitBreakerConfig {
     /// Number of consecutive failures before opening circuit
     pub failure_threshold: u32,
@@ -1,8 +1,11 @@
 //! Node configuration
 
+use crate::node::bandwidth::BandwidthLimits;
 use crate::node::circuit_breaker::CircuitBreakerConfig;
+use crate::node::error::{NodeError, Result};
 use crate::node::health::HealthConfig;
 use crate::node::rate_limiter::RateLimitConfig;
+use crate::node::telemetry::TelemetryConfig;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -37,11 +40,20 @@ pub struct NodeConfig {
     /// Rate limiting configuration
     pub rate_limiting: RateLimitConfig,
 
+    /// Egress bandwidth caps (global, per-session, per-transfer)
+    pub bandwidth: BandwidthLimits,
+
     /// Health monitoring configuration
     pub health: HealthConfig,
 
     /// Circuit breaker configuration
     pub circuit_breaker: CircuitBreakerConfig,
+
+    /// Opt-in anonymous telemetry (off by default)
+    pub telemetry: TelemetryConfig,
+
+    /// Peer trust store configuration (TOFU identity pinning)
+    pub trust: TrustConfig,
 }
 
 impl Default for NodeConfig {
@@ -60,19 +72,64 @@ impl Default for NodeConfig {
             transfer: TransferConfig::default(),
             logging: LoggingConfig::default(),
             rate_limiting: RateLimitConfig::default(),
+            bandwidth: BandwidthLimits::default(),
             health: HealthConfig::default(),
             circuit_breaker: CircuitBreakerConfig::default(),
+            telemetry: TelemetryConfig::default(),
+            trust: TrustConfig::default(),
+        }
+    }
+}
+
+impl NodeConfig {
+    /// Validate cross-subsystem configuration consistency
+    ///
+    /// Runs the obfuscation compatibility checks and rules that span
+    /// subsystems, such as mimicry versus the XDP fast path. Called by the
+    /// node constructor so an inconsistent configuration fails at startup
+    /// rather than degrading silently at runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::InvalidConfig`] describing the first violation.
+    pub fn validate(&self) -> Result<()> {
+        self.obfuscation.validate()?;
+
+        if self.transport.enable_xdp && self.obfuscation.mimicry_mode != MimicryMode::None {
+            return Err(NodeError::InvalidConfig(
+                format!(
+                    "{:?} mimicry is incompatible with the XDP fast path: mimicry \
+                     wraps packets in userspace, after XDP has already forwarded them",
+                    self.obfuscation.mimicry_mode
+                )
+                .into(),
+            ));
         }
+
+        if let Some(dscp) = self.transport.dscp
+            && dscp >= 64
+        {
+            return Err(NodeError::InvalidConfig(
+                format!("DSCP code point must be 0-63, got {dscp}").into(),
+            ));
+        }
+
+        Ok(())
     }
 }
 
 /// Transport layer configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TransportConfig {
     /// Enable AF_XDP (requires root and compatible NIC)
     pub enable_xdp: bool,
 
-    /// Enable io_uring for file I/O (Linux only)
+    /// Enable io_uring for file and network datagram I/O (Linux only)
+    ///
+    /// When the kernel supports rings, file chunk I/O and UDP sends and
+    /// receives are driven through io_uring worker rings; otherwise the
+    /// node silently falls back to thread-pool file I/O and the tokio
+    /// epoll socket.
     pub enable_io_uring: bool,
 
     /// UDP socket buffer size
@@ -86,6 +143,31 @@ pub struct TransportConfig {
 
     /// Idle timeout before closing sessions
     pub idle_timeout: Duration,
+
+    /// DSCP code point (0-63) for outgoing packets, or None for the OS
+    /// default
+    ///
+    /// Lets QoS-aware networks classify WRAITH traffic, e.g. 18 (AF21) for
+    /// bulk transfers or 46 (EF) for latency-sensitive deployments. Applied
+    /// to every packet the node sends; per-frame marking is not supported.
+    pub dscp: Option<u8>,
+
+    /// Keep NAT bindings of idle sessions alive with adaptive PING frames
+    ///
+    /// Sends an encrypted keepalive whenever a session has been idle for
+    /// close to the estimated NAT binding lifetime (learned per peer), and
+    /// triggers path revalidation when a binding breaks early. See
+    /// [`crate::node::keepalive`].
+    pub nat_keepalive: bool,
+
+    /// Actively migrate sessions when the local address set changes
+    ///
+    /// Watches local interfaces (netlink-driven on Linux, polled
+    /// elsewhere) and re-validates every established session with a
+    /// PATH_CHALLENGE when an address appears or disappears — e.g. a
+    /// Wi-Fi → LTE handover — so transfers survive the switch. See
+    /// [`crate::node::netmon`].
+    pub migrate_on_network_change: bool,
 }
 
 impl Default for TransportConfig {
@@ -97,12 +179,30 @@ impl Default for TransportConfig {
             worker_threads: None,             // Use all CPUs
             connection_timeout: Duration::from_secs(30),
             idle_timeout: Duration::from_secs(180), // 3 minutes
+            dscp: None,                             // No marking by default
+            nat_keepalive: true,
+            migrate_on_network_change: true,
         }
     }
 }
 
 /// Obfuscation configuration
-#[derive(Debug, Clone)]
+///
+/// Not all combinations of padding, timing, mimicry, and cover traffic make
+/// sense together. [`ObfuscationConfig::validate`] (run on every node start)
+/// enforces this compatibility matrix:
+///
+/// | Combination | Verdict |
+/// |-------------|---------|
+/// | `ConstantRate` padding without a cover traffic budget | Rejected — shaping to a constant rate emits decoy packets, which are admitted from `cover_traffic.budget_bps` |
+/// | `ConstantRate` padding with a timing mode other than `None` | Rejected — constant-rate shaping already fixes inter-packet timing; a second delay stage defeats it |
+/// | Cover traffic enabled with `rate <= 0` | Rejected — the generator would never fire |
+/// | `Uniform` timing or cover distribution with `min > max` | Rejected — empty sampling range |
+/// | Any mimicry mode with AF_XDP enabled | Rejected by [`NodeConfig::validate`] — mimicry wraps packets in userspace after the XDP fast path has already forwarded them |
+///
+/// Use [`ObfuscationConfig::builder`] to construct validated configurations;
+/// field access remains available for tests and trusted callers.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObfuscationConfig {
     /// Padding mode
     pub padding_mode: PaddingMode,
@@ -117,6 +217,119 @@ pub struct ObfuscationConfig {
     pub cover_traffic: CoverTrafficConfig,
 }
 
+impl ObfuscationConfig {
+    /// Start building a validated obfuscation configuration
+    #[must_use]
+    pub fn builder() -> ObfuscationConfigBuilder {
+        ObfuscationConfigBuilder::default()
+    }
+
+    /// Check that the configured modes are mutually compatible
+    ///
+    /// See the type-level compatibility matrix for the rules enforced here.
+    /// Cross-subsystem rules (mimicry vs. XDP) live in
+    /// [`NodeConfig::validate`] because they need the transport settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::InvalidConfig`] describing the first conflicting
+    /// combination found.
+    pub fn validate(&self) -> Result<()> {
+        if self.padding_mode == PaddingMode::ConstantRate {
+            if !self.cover_traffic.enabled || self.cover_traffic.budget_bps.is_none() {
+                return Err(NodeError::InvalidConfig(
+                    "ConstantRate padding requires cover traffic with a bandwidth \
+                     budget (set cover_traffic.enabled and cover_traffic.budget_bps)"
+                        .into(),
+                ));
+            }
+            if self.timing_mode != TimingMode::None {
+                return Err(NodeError::InvalidConfig(
+                    "ConstantRate padding already fixes inter-packet timing; \
+                     combining it with a timing obfuscation mode defeats the shaping"
+                        .into(),
+                ));
+            }
+        }
+
+        if self.cover_traffic.enabled && self.cover_traffic.rate <= 0.0 {
+            return Err(NodeError::InvalidConfig(
+                "Cover traffic is enabled but rate is not positive".into(),
+            ));
+        }
+
+        if let CoverTrafficDistribution::Uniform { min_ms, max_ms } =
+            self.cover_traffic.distribution
+            && min_ms > max_ms
+        {
+            return Err(NodeError::InvalidConfig(
+                "Cover traffic uniform distribution has min_ms > max_ms".into(),
+            ));
+        }
+
+        if let TimingMode::Uniform { min, max } = self.timing_mode
+            && min > max
+        {
+            return Err(NodeError::InvalidConfig(
+                "Timing obfuscation uniform distribution has min > max".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Builder for [`ObfuscationConfig`] that validates on [`build`](Self::build)
+///
+/// Starts from [`ObfuscationConfig::default`] (everything off) and rejects
+/// incompatible combinations with a descriptive error instead of letting
+/// them reach the node.
+#[derive(Debug, Clone, Default)]
+pub struct ObfuscationConfigBuilder {
+    config: ObfuscationConfig,
+}
+
+impl ObfuscationConfigBuilder {
+    /// Set the padding mode
+    #[must_use]
+    pub fn padding_mode(mut self, mode: PaddingMode) -> Self {
+        self.config.padding_mode = mode;
+        self
+    }
+
+    /// Set the timing obfuscation mode
+    #[must_use]
+    pub fn timing_mode(mut self, mode: TimingMode) -> Self {
+        self.config.timing_mode = mode;
+        self
+    }
+
+    /// Set the protocol mimicry mode
+    #[must_use]
+    pub fn mimicry_mode(mut self, mode: MimicryMode) -> Self {
+        self.config.mimicry_mode = mode;
+        self
+    }
+
+    /// Set the cover traffic configuration
+    #[must_use]
+    pub fn cover_traffic(mut self, cover: CoverTrafficConfig) -> Self {
+        self.config.cover_traffic = cover;
+        self
+    }
+
+    /// Validate the combination and produce the configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::InvalidConfig`] if the combination violates the
+    /// compatibility matrix documented on [`ObfuscationConfig`].
+    pub fn build(self) -> Result<ObfuscationConfig> {
+        self.config.validate()?;
+        Ok(self.config)
+    }
+}
+
 impl Default for ObfuscationConfig {
     fn default() -> Self {
         Self {
@@ -129,7 +342,7 @@ impl Default for ObfuscationConfig {
 }
 
 /// Cover traffic configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CoverTrafficConfig {
     /// Enable cover traffic generation
     pub enabled: bool,
@@ -139,6 +352,13 @@ pub struct CoverTrafficConfig {
 
     /// Traffic distribution pattern
     pub distribution: CoverTrafficDistribution,
+
+    /// Bandwidth budget for cover traffic (bytes/sec, `None` = unbudgeted)
+    ///
+    /// Decoy packets are admitted strictly below real data: they draw from
+    /// this budget and only from surplus left by the global/per-session
+    /// caps, so cover traffic can never slow an active transfer.
+    pub budget_bps: Option<u64>,
 }
 
 impl Default for CoverTrafficConfig {
@@ -147,6 +367,7 @@ impl Default for CoverTrafficConfig {
             enabled: false,
             rate: 10.0, // 10 packets per second
             distribution: CoverTrafficDistribution::Constant,
+            budget_bps: None,
         }
     }
 }
@@ -224,7 +445,7 @@ pub enum MimicryMode {
 }
 
 /// Discovery configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct DiscoveryConfig {
     /// Enable DHT for peer discovery
     pub enable_dht: bool,
@@ -259,7 +480,7 @@ impl Default for DiscoveryConfig {
 }
 
 /// Transfer configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TransferConfig {
     /// Chunk size for file transfers
     pub chunk_size: usize,
@@ -276,6 +497,15 @@ pub struct TransferConfig {
     /// Enable resume support
     pub enable_resume: bool,
 
+    /// Directory for persisted transfer resume state
+    ///
+    /// When set (and `enable_resume` is on), in-flight transfers are
+    /// checkpointed to disk and picked up automatically the next time a
+    /// session with the same peer is established — including after a full
+    /// process restart. `None` disables cross-session resumption; transfers
+    /// interrupted by a dead session must then be restarted manually.
+    pub resume_state_dir: Option<PathBuf>,
+
     /// Enable multi-peer downloads
     pub enable_multi_peer: bool,
 
@@ -284,6 +514,49 @@ pub struct TransferConfig {
 
     /// Chunk assignment strategy for multi-peer downloads
     pub chunk_assignment_strategy: crate::node::multi_peer::ChunkAssignmentStrategy,
+
+    /// Path to the persistent peer performance history file
+    ///
+    /// When set, multi-peer transfers seed initial chunk assignments from
+    /// measured RTT/throughput of past transfers (`None` disables history).
+    pub peer_history_path: Option<PathBuf>,
+
+    /// Directory for the content-addressed seed chunk store
+    ///
+    /// When set, chunks of announced files are copied into a local store
+    /// keyed by their BLAKE3 hash and served from it, deduplicating
+    /// identical chunks across announced content. Unannouncing a file
+    /// garbage-collects chunks nothing else references. `None` disables
+    /// the store; seeded chunks are then read from the original files on
+    /// every request.
+    pub chunk_store_dir: Option<PathBuf>,
+
+    /// Maximum on-disk size of the seed chunk store in bytes
+    ///
+    /// When the store grows past this, the least-recently-requested
+    /// chunks are evicted first (they are re-read from the original file
+    /// and re-stored on the next request). `None` means unlimited.
+    /// Ignored without `chunk_store_dir`.
+    pub chunk_store_max_bytes: Option<u64>,
+
+    /// File size in bytes from which sends read with `O_DIRECT`
+    ///
+    /// Streaming a huge file through the page cache evicts everything
+    /// else on the machine for data read exactly once, so files at least
+    /// this large are read with direct I/O on the send path. Direct I/O
+    /// is best-effort: filesystems that reject `O_DIRECT` fall back to
+    /// buffered reads transparently. `None` always reads buffered.
+    pub direct_io_threshold: Option<u64>,
+
+    /// Use a fresh ephemeral identity for each outgoing transfer
+    ///
+    /// Every send performs its own handshake with a newly generated X25519
+    /// key, so recipients and observers cannot link transfers to each other
+    /// or to the node's long-term identity. The recipient is still shown
+    /// who is sending: a delegation signed by the long-term Ed25519 key is
+    /// delivered inside the encrypted channel. Ephemeral sessions are never
+    /// announced to the DHT.
+    pub ephemeral_identities: bool,
 }
 
 impl Default for TransferConfig {
@@ -294,21 +567,60 @@ impl Default for TransferConfig {
             max_concurrent_chunks: 4,
             download_dir: PathBuf::from("."), // Default to current directory
             enable_resume: true,
+            resume_state_dir: None,
             enable_multi_peer: true,
             max_peers_per_transfer: 5,
             chunk_assignment_strategy: crate::node::multi_peer::ChunkAssignmentStrategy::default(),
+            peer_history_path: None,
+            chunk_store_dir: None,
+            chunk_store_max_bytes: None,
+            // 1 GiB: large enough that page-cache churn starts to hurt,
+            // small enough that the transfers it helps actually hit it
+            direct_io_threshold: Some(1024 * 1024 * 1024),
+            ephemeral_identities: false,
         }
     }
 }
 
+/// Peer trust store configuration
+///
+/// When enabled, the node records each peer's static key on first contact
+/// (trust on first use) in a `known_peers` file and refuses sessions to
+/// peers whose key later changes, unless `allow_key_change` is set. See
+/// [`TrustStore`](crate::node::trust::TrustStore).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrustConfig {
+    /// Enable the persistent trust store (opt-in: creates state under the
+    /// user's home directory)
+    pub enabled: bool,
+
+    /// Path to the known_peers file
+    ///
+    /// `None` uses the default `~/.config/wraith/known_peers`.
+    pub store_path: Option<PathBuf>,
+
+    /// Re-pin and continue instead of failing when a peer's key changes
+    ///
+    /// Leave disabled unless a deliberate key rotation is expected; a
+    /// changed key is indistinguishable from a man-in-the-middle.
+    pub allow_key_change: bool,
+}
+
 /// Logging configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LoggingConfig {
     /// Log level
     pub level: LogLevel,
 
     /// Enable metrics collection
     pub enable_metrics: bool,
+
+    /// Stamp outgoing DATA frames with random trace IDs and log them on
+    /// both endpoints, so diagnostics submitted from each side of a
+    /// transfer can be correlated frame by frame. The IDs travel inside
+    /// the encrypted frame (4 bytes that pre-tracing peers treat as
+    /// padding). Off by default.
+    pub trace_frames: bool,
 }
 
 impl Default for LoggingConfig {
@@ -316,6 +628,7 @@ impl Default for LoggingConfig {
         Self {
             level: LogLevel::Info,
             enable_metrics: false,
+            trace_frames: false,
         }
     }
 }
@@ -334,3 +647,126 @@ pub enum LogLevel {
     /// Error level
     Error,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budgeted_cover() -> CoverTrafficConfig {
+        CoverTrafficConfig {
+            enabled: true,
+            budget_bps: Some(1_000_000),
+            ..CoverTrafficConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_default_config_validates() {
+        assert!(NodeConfig::default().validate().is_ok());
+        assert!(ObfuscationConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_constant_rate_requires_cover_budget() {
+        let err = ObfuscationConfig::builder()
+            .padding_mode(PaddingMode::ConstantRate)
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, NodeError::InvalidConfig(_)));
+        assert!(err.to_string().contains("budget"));
+
+        // Enabled cover traffic without a budget is still rejected
+        let err = ObfuscationConfig::builder()
+            .padding_mode(PaddingMode::ConstantRate)
+            .cover_traffic(CoverTrafficConfig {
+                enabled: true,
+                ..CoverTrafficConfig::default()
+            })
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, NodeError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_constant_rate_with_budget_builds() {
+        let config = ObfuscationConfig::builder()
+            .padding_mode(PaddingMode::ConstantRate)
+            .cover_traffic(budgeted_cover())
+            .build()
+            .unwrap();
+        assert_eq!(config.padding_mode, PaddingMode::ConstantRate);
+    }
+
+    #[test]
+    fn test_constant_rate_rejects_timing_mode() {
+        let err = ObfuscationConfig::builder()
+            .padding_mode(PaddingMode::ConstantRate)
+            .cover_traffic(budgeted_cover())
+            .timing_mode(TimingMode::Fixed(Duration::from_millis(5)))
+            .build()
+            .unwrap_err();
+        assert!(err.to_string().contains("timing"));
+    }
+
+    #[test]
+    fn test_cover_traffic_requires_positive_rate() {
+        let err = ObfuscationConfig::builder()
+            .cover_traffic(CoverTrafficConfig {
+                enabled: true,
+                rate: 0.0,
+                ..CoverTrafficConfig::default()
+            })
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, NodeError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_uniform_ranges_must_be_ordered() {
+        let err = ObfuscationConfig::builder()
+            .timing_mode(TimingMode::Uniform {
+                min: Duration::from_millis(10),
+                max: Duration::from_millis(1),
+            })
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, NodeError::InvalidConfig(_)));
+
+        let err = ObfuscationConfig::builder()
+            .cover_traffic(CoverTrafficConfig {
+                enabled: true,
+                distribution: CoverTrafficDistribution::Uniform {
+                    min_ms: 100,
+                    max_ms: 10,
+                },
+                ..CoverTrafficConfig::default()
+            })
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, NodeError::InvalidConfig(_)));
+    }
+
+    #[test]
+    fn test_mimicry_incompatible_with_xdp() {
+        let mut config = NodeConfig::default();
+        config.transport.enable_xdp = true;
+        config.obfuscation.mimicry_mode = MimicryMode::Tls;
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("XDP"));
+
+        // Same mimicry mode without XDP is fine
+        config.transport.enable_xdp = false;
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_dscp_code_point_range() {
+        let mut config = NodeConfig::default();
+        config.transport.dscp = Some(18); // AF21
+        assert!(config.validate().is_ok());
+
+        config.transport.dscp = Some(64);
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("DSCP"));
+    }
+}
@@ -2,13 +2,15 @@
 //!
 //! Monitors connection health, handles session migration, and manages keepalives.
 
-use crate::node::session::PeerId;
+use crate::node::discovery::PeerInfo;
+use crate::node::nat::IceCandidate;
+use crate::node::session::{PeerConnection, PeerId};
 use crate::node::{Node, NodeError};
 use std::borrow::Cow;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::interval;
-use wraith_transport::transport::Transport;
 
 /// Connection health status
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,6 +50,149 @@ pub struct HealthMetrics {
 /// Maximum consecutive failed pings before considering connection dead
 const MAX_FAILED_PINGS: u32 = 3;
 
+/// Delay before a relayed session tries to upgrade to a direct path
+///
+/// Long enough for the relayed session to settle and for any in-flight
+/// hole punching to finish creating NAT bindings.
+const DIRECT_UPGRADE_DELAY: Duration = Duration::from_secs(2);
+
+/// Candidate pair state (RFC 8445 Section 6.1.2.6)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairState {
+    /// Not yet eligible for checks
+    Frozen,
+    /// Eligible, waiting to be checked
+    Waiting,
+    /// A connectivity check is running on this pair
+    InProgress,
+    /// The Noise handshake completed over this pair
+    Succeeded,
+    /// The check failed
+    Failed,
+}
+
+/// A local/remote candidate pairing being raced during connection setup
+#[derive(Debug, Clone)]
+pub struct CandidatePair {
+    /// Local candidate
+    pub local: IceCandidate,
+    /// Remote candidate
+    pub remote: IceCandidate,
+    /// Pair priority (RFC 8445 Section 6.1.2.3, initiator controlling)
+    pub priority: u64,
+    /// Current check state
+    pub state: PairState,
+}
+
+impl CandidatePair {
+    fn new(local: IceCandidate, remote: IceCandidate) -> Self {
+        let priority = Self::pair_priority(local.priority, remote.priority);
+        Self {
+            local,
+            remote,
+            priority,
+            state: PairState::Waiting,
+        }
+    }
+
+    /// RFC 8445 pair priority: `2^32 * MIN(G,D) + 2 * MAX(G,D) + (G>D ? 1 : 0)`
+    ///
+    /// `G` is the controlling (our) candidate priority, `D` the remote's.
+    fn pair_priority(g: u32, d: u32) -> u64 {
+        let (g, d) = (u64::from(g), u64::from(d));
+        (1u64 << 32) * g.min(d) + 2 * g.max(d) + u64::from(g > d)
+    }
+}
+
+/// Candidate pair checklist driving the connection race
+///
+/// Holds every local/remote pairing sorted by pair priority and tracks
+/// each pair through the ICE state machine. The race starts checks on
+/// all waiting pairs at once; the first handshake to complete marks its
+/// pair `Succeeded` and freezes the rest.
+#[derive(Debug)]
+pub struct CandidateChecklist {
+    pairs: Vec<CandidatePair>,
+}
+
+impl CandidateChecklist {
+    /// Form and prioritize all pairings of local and remote candidates
+    #[must_use]
+    pub fn new(local: &[IceCandidate], remote: &[IceCandidate]) -> Self {
+        let mut pairs: Vec<CandidatePair> = local
+            .iter()
+            .flat_map(|l| {
+                remote
+                    .iter()
+                    .map(|r| CandidatePair::new(l.clone(), r.clone()))
+            })
+            .collect();
+        pairs.sort_by_key(|p| std::cmp::Reverse(p.priority));
+        Self { pairs }
+    }
+
+    /// All pairs, highest priority first
+    #[must_use]
+    pub fn pairs(&self) -> &[CandidatePair] {
+        &self.pairs
+    }
+
+    /// Move the highest-priority waiting pair to `InProgress`
+    ///
+    /// Returns its index, or `None` when no pair is waiting.
+    pub fn next_waiting(&mut self) -> Option<usize> {
+        let idx = self
+            .pairs
+            .iter()
+            .position(|p| p.state == PairState::Waiting)?;
+        self.pairs[idx].state = PairState::InProgress;
+        Some(idx)
+    }
+
+    /// Mark a pair's check as succeeded and freeze all unfinished pairs
+    pub fn mark_succeeded(&mut self, idx: usize) {
+        if let Some(pair) = self.pairs.get_mut(idx) {
+            pair.state = PairState::Succeeded;
+        }
+        for pair in &mut self.pairs {
+            if matches!(pair.state, PairState::Waiting | PairState::InProgress) {
+                pair.state = PairState::Frozen;
+            }
+        }
+    }
+
+    /// Mark a pair's check as failed
+    pub fn mark_failed(&mut self, idx: usize) {
+        if let Some(pair) = self.pairs.get_mut(idx) {
+            pair.state = PairState::Failed;
+        }
+    }
+
+    /// The pair that won the race, if any
+    #[must_use]
+    pub fn succeeded(&self) -> Option<&CandidatePair> {
+        self.pairs.iter().find(|p| p.state == PairState::Succeeded)
+    }
+
+    /// Have all pairs failed?
+    #[must_use]
+    pub fn all_failed(&self) -> bool {
+        !self.pairs.is_empty() && self.pairs.iter().all(|p| p.state == PairState::Failed)
+    }
+
+    /// Number of pairs in the checklist
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Is the checklist empty?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+}
+
 impl Node {
     /// Start the connection manager background task
     ///
@@ -126,7 +271,7 @@ impl Node {
     ///
     /// Sends a PING frame and waits for the corresponding PONG response.
     /// Uses the `pending_pings` map to coordinate with `handle_pong_frame()`.
-    async fn ping_session(
+    pub(crate) async fn ping_session(
         &self,
         peer_id: &PeerId,
         session: std::sync::Arc<crate::node::session::PeerConnection>,
@@ -157,8 +302,7 @@ impl Node {
         })?;
 
         // Send via transport
-        let transport_guard = self.inner.transport.lock().await;
-        if let Some(transport) = transport_guard.as_ref() {
+        if let Some(transport) = self.inner.transport.get() {
             transport
                 .send_to(&encrypted, session.peer_addr())
                 .await
@@ -170,8 +314,6 @@ impl Node {
             self.inner.pending_pings.remove(&(*peer_id, sequence));
             return Err(NodeError::Transport("Transport not initialized".into()));
         }
-        drop(transport_guard);
-
         // Wait for PONG response with timeout
         let ping_timeout = Duration::from_secs(5);
         match tokio::time::timeout(ping_timeout, rx).await {
@@ -183,6 +325,11 @@ impl Node {
                 session.reset_failed_pings();
                 session.touch(); // Update last activity
 
+                // Tighten the cached path RTT used for careful resume
+                self.inner
+                    .path_estimates
+                    .record_rtt(*peer_id, session.peer_addr(), latency);
+
                 tracing::trace!(
                     "PONG received from {:?}: {} µs RTT",
                     peer_id,
@@ -294,21 +441,14 @@ impl Node {
                 NodeError::Migration(format!("Failed to build PATH_CHALLENGE: {e}").into())
             })?;
 
-        // Encrypt and send to new address
-        let encrypted = session.encrypt_frame(&frame).await?;
-
-        let transport_guard = self.inner.transport.lock().await;
-        if let Some(transport) = transport_guard.as_ref() {
-            transport.send_to(&encrypted, new_addr).await.map_err(|e| {
-                self.inner.pending_migrations.remove(&path_id);
-                NodeError::Migration(format!("Failed to send PATH_CHALLENGE: {e}").into())
-            })?;
-        } else {
+        // Send to the new address in the full outer packet format, so the
+        // peer can route the challenge by Connection ID
+        if let Err(e) = self.send_wrapped_to(&session, &frame, new_addr).await {
             self.inner.pending_migrations.remove(&path_id);
-            return Err(NodeError::Migration("Transport not initialized".into()));
+            return Err(NodeError::Migration(
+                format!("Failed to send PATH_CHALLENGE: {e}").into(),
+            ));
         }
-        drop(transport_guard);
-
         tracing::debug!(
             "PATH_CHALLENGE sent to {}, awaiting PATH_RESPONSE",
             new_addr
@@ -327,6 +467,12 @@ impl Node {
                     new_addr,
                     latency.as_micros()
                 );
+                self.inner
+                    .events
+                    .emit(crate::node::events::NodeEvent::PathMigrated {
+                        peer_id: *peer_id,
+                        new_addr,
+                    });
                 Ok(())
             }
             Ok(Ok(Err(e))) => {
@@ -346,6 +492,143 @@ impl Node {
         }
     }
 
+    /// Add an additional validated path to a session for multipath striping
+    ///
+    /// Validates the new address with a PATH_CHALLENGE (like migration), but
+    /// instead of replacing the primary address, registers the path in the
+    /// session's multipath scheduler so DATA frames are striped across all
+    /// validated paths weighted by per-path bandwidth estimates.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_id` - The peer whose session gains the path
+    /// * `addr` - The additional address to validate and stripe over
+    ///
+    /// # Errors
+    ///
+    /// Returns error if session not found or path validation fails.
+    pub async fn add_session_path(
+        &self,
+        peer_id: &PeerId,
+        addr: SocketAddr,
+    ) -> Result<(), NodeError> {
+        use crate::frame::{FrameBuilder, FrameType};
+        use crate::migration::{PathValidator, ValidatedPath};
+        use crate::node::node::MigrationState;
+
+        tracing::info!("Adding multipath address {} for peer {:?}", addr, peer_id);
+
+        let connection = self
+            .inner
+            .sessions
+            .get(peer_id)
+            .ok_or(NodeError::SessionNotFound(*peer_id))?
+            .clone();
+
+        let mut path_validator = PathValidator::new(Duration::from_secs(3));
+
+        // Generate path ID from address (simple hash)
+        let path_id = {
+            use std::collections::hash_map::DefaultHasher;
+            use std::hash::{Hash, Hasher};
+            let mut hasher = DefaultHasher::new();
+            addr.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let challenge = path_validator.initiate_challenge(path_id);
+        let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+
+        let migration_state = MigrationState {
+            peer_id: *peer_id,
+            new_addr: addr,
+            challenge,
+            sender: response_tx,
+            initiated_at: std::time::Instant::now(),
+        };
+        self.inner
+            .pending_migrations
+            .insert(path_id, migration_state);
+
+        // Build PATH_CHALLENGE frame
+        let frame = FrameBuilder::new()
+            .frame_type(FrameType::PathChallenge)
+            .stream_id(0) // Connection-level
+            .sequence(0)
+            .payload(&challenge)
+            .build(128)
+            .map_err(|e| {
+                NodeError::Migration(format!("Failed to build PATH_CHALLENGE: {e}").into())
+            })?;
+
+        // Send to the candidate address in the full outer packet format, so
+        // the peer can route the challenge by Connection ID
+        if let Err(e) = self.send_wrapped_to(&connection, &frame, addr).await {
+            self.inner.pending_migrations.remove(&path_id);
+            return Err(NodeError::Migration(
+                format!("Failed to send PATH_CHALLENGE: {e}").into(),
+            ));
+        }
+        // Wait for PATH_RESPONSE with timeout
+        let timeout = Duration::from_secs(5);
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(Ok(latency))) => {
+                let now = std::time::Instant::now();
+                connection.add_path_addr(path_id, addr);
+
+                let mut session = connection.session.write().await;
+                let multipath = session.multipath_mut();
+
+                // Seed the scheduler with the primary path the first time a
+                // second path is added, so both participate in striping.
+                if multipath.path_count() == 0 {
+                    let primary_id = {
+                        use std::collections::hash_map::DefaultHasher;
+                        use std::hash::{Hash, Hasher};
+                        let mut hasher = DefaultHasher::new();
+                        connection.peer_addr().hash(&mut hasher);
+                        hasher.finish()
+                    };
+                    multipath.add_path(ValidatedPath {
+                        path_id: primary_id,
+                        rtt: latency,
+                        validated_at: now,
+                    });
+                }
+
+                multipath.add_path(ValidatedPath {
+                    path_id,
+                    rtt: latency,
+                    validated_at: now,
+                });
+
+                tracing::info!(
+                    "Multipath address {} validated with {}µs RTT ({} paths)",
+                    addr,
+                    latency.as_micros(),
+                    multipath.path_count()
+                );
+                Ok(())
+            }
+            Ok(Ok(Err(e))) => {
+                tracing::error!("Path validation failed: {}", e);
+                Err(e)
+            }
+            Ok(Err(_)) => {
+                self.inner.pending_migrations.remove(&path_id);
+                Err(NodeError::Migration(Cow::Borrowed(
+                    "Path validation channel closed",
+                )))
+            }
+            Err(_) => {
+                self.inner.pending_migrations.remove(&path_id);
+                Err(NodeError::Timeout(Cow::Borrowed(
+                    "Path validation timed out",
+                )))
+            }
+        }
+    }
+
     /// Get connection health metrics
     ///
     /// Returns health status and metrics for a specific peer.
@@ -446,11 +729,231 @@ impl Node {
 
         count
     }
+
+    /// Race direct, hole-punched, and relayed paths to a peer
+    ///
+    /// Builds a [`CandidateChecklist`] from gathered and exchanged ICE
+    /// candidates, starts a connectivity check on every pair at once
+    /// (plus a relay lane when relaying is enabled), and returns the
+    /// first connection whose Noise handshake completes. When the relay
+    /// lane wins, a background task keeps trying the direct candidates
+    /// and migrates the session off the relay as soon as one validates.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no candidates are available or every path
+    /// fails.
+    pub async fn connect_racing(&self, peer: &PeerInfo) -> Result<Arc<PeerConnection>, NodeError> {
+        if let Some(connection) = self.inner.sessions.get(&peer.peer_id) {
+            return Ok(Arc::clone(connection.value()));
+        }
+
+        let local = self.gather_ice_candidates().await?;
+        let remote = self.exchange_candidates(peer, &local).await?;
+        let mut checklist = CandidateChecklist::new(&local, &remote);
+
+        // Index reserved for the relay lane, which has no candidate pair
+        let relay_idx = usize::MAX;
+
+        let mut attempts = tokio::task::JoinSet::new();
+        while let Some(idx) = checklist.next_waiting() {
+            let node = self.clone();
+            let peer_id = peer.peer_id;
+            let remote_addr = checklist.pairs()[idx].remote.address;
+            attempts.spawn(async move {
+                let result = node
+                    .establish_session_with_addr(&peer_id, remote_addr)
+                    .await
+                    .map(|_| ());
+                (idx, result)
+            });
+        }
+        if self.inner.config.discovery.enable_relay {
+            let node = self.clone();
+            let peer = peer.clone();
+            attempts.spawn(async move {
+                let result = node.connect_via_relay(&peer).await.map(|_| ());
+                (relay_idx, result)
+            });
+        }
+
+        if attempts.is_empty() {
+            return Err(NodeError::NatTraversal(Cow::Borrowed(
+                "No candidates to race",
+            )));
+        }
+
+        let mut last_err = NodeError::NatTraversal(Cow::Borrowed("No candidates to race"));
+        while let Some(joined) = attempts.join_next().await {
+            let Ok((idx, result)) = joined else {
+                continue; // Cancelled by abort_all
+            };
+            match result {
+                Ok(()) => {
+                    attempts.abort_all();
+                    let via_relay = idx == relay_idx;
+                    if !via_relay {
+                        checklist.mark_succeeded(idx);
+                    }
+                    let connection = self
+                        .inner
+                        .sessions
+                        .get(&peer.peer_id)
+                        .map(|entry| Arc::clone(entry.value()))
+                        .ok_or(NodeError::SessionNotFound(peer.peer_id))?;
+
+                    if via_relay {
+                        tracing::info!(
+                            "Relay lane won the race to peer {:?}; scheduling direct upgrade",
+                            peer.peer_id
+                        );
+                        let direct_addrs = remote.iter().map(|c| c.address).collect();
+                        self.spawn_direct_upgrade(peer.peer_id, direct_addrs);
+                    } else {
+                        tracing::info!(
+                            "Candidate pair {} won the race to peer {:?}",
+                            checklist.pairs()[idx].remote.address,
+                            peer.peer_id
+                        );
+                    }
+                    return Ok(connection);
+                }
+                Err(e) => {
+                    if idx != relay_idx {
+                        checklist.mark_failed(idx);
+                    }
+                    last_err = e;
+                }
+            }
+        }
+
+        Err(last_err)
+    }
+
+    /// Background upgrade of a relayed session to a direct path
+    ///
+    /// Retries each direct candidate with [`Node::migrate_session`],
+    /// which path-validates the new address before switching; the first
+    /// to validate takes the session off the relay.
+    fn spawn_direct_upgrade(&self, peer_id: PeerId, direct_addrs: Vec<SocketAddr>) {
+        let node = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(DIRECT_UPGRADE_DELAY).await;
+            for addr in direct_addrs {
+                match node.migrate_session(&peer_id, addr).await {
+                    Ok(()) => {
+                        tracing::info!(
+                            "Upgraded relayed session to direct path {} for peer {:?}",
+                            addr,
+                            peer_id
+                        );
+                        return;
+                    }
+                    Err(e) => {
+                        tracing::debug!("Direct upgrade via {} failed: {}", addr, e);
+                    }
+                }
+            }
+            tracing::debug!(
+                "No direct path validated; staying on relay for {:?}",
+                peer_id
+            );
+        });
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::node::nat::CandidateType;
+
+    fn cand(addr: &str, priority: u32) -> IceCandidate {
+        IceCandidate {
+            address: addr.parse().unwrap(),
+            candidate_type: CandidateType::Host,
+            priority,
+            foundation: format!("test-{addr}"),
+        }
+    }
+
+    #[test]
+    fn test_checklist_forms_all_pairs_sorted() {
+        let local = vec![cand("192.168.1.1:4000", 126), cand("10.0.0.1:4000", 100)];
+        let remote = vec![cand("203.0.113.1:4000", 126), cand("203.0.113.2:4000", 50)];
+
+        let checklist = CandidateChecklist::new(&local, &remote);
+
+        assert_eq!(checklist.len(), 4);
+        // Highest pair priority first, monotonically decreasing
+        for window in checklist.pairs().windows(2) {
+            assert!(window[0].priority >= window[1].priority);
+        }
+        assert_eq!(checklist.pairs()[0].local.priority, 126);
+        assert_eq!(checklist.pairs()[0].remote.priority, 126);
+    }
+
+    #[test]
+    fn test_pair_priority_formula() {
+        // MIN dominates: a (100, 100) pair beats a (126, 50) pair
+        assert!(CandidatePair::pair_priority(100, 100) > CandidatePair::pair_priority(126, 50));
+        // Tiebreak: controlling side higher wins over the reverse
+        assert!(CandidatePair::pair_priority(126, 100) > CandidatePair::pair_priority(100, 126));
+    }
+
+    #[test]
+    fn test_checklist_next_waiting_transitions() {
+        let local = vec![cand("192.168.1.1:4000", 126)];
+        let remote = vec![cand("203.0.113.1:4000", 126), cand("203.0.113.2:4000", 50)];
+        let mut checklist = CandidateChecklist::new(&local, &remote);
+
+        let first = checklist.next_waiting().unwrap();
+        assert_eq!(checklist.pairs()[first].state, PairState::InProgress);
+        let second = checklist.next_waiting().unwrap();
+        assert_ne!(first, second);
+        assert!(checklist.next_waiting().is_none());
+    }
+
+    #[test]
+    fn test_checklist_success_freezes_rest() {
+        let local = vec![cand("192.168.1.1:4000", 126)];
+        let remote = vec![cand("203.0.113.1:4000", 126), cand("203.0.113.2:4000", 50)];
+        let mut checklist = CandidateChecklist::new(&local, &remote);
+
+        let idx = checklist.next_waiting().unwrap();
+        checklist.mark_succeeded(idx);
+
+        assert_eq!(
+            checklist.succeeded().unwrap().remote.address,
+            checklist.pairs()[idx].remote.address
+        );
+        // The pair that was never checked is frozen, not left waiting
+        assert!(
+            checklist
+                .pairs()
+                .iter()
+                .all(|p| p.state != PairState::Waiting && p.state != PairState::InProgress)
+        );
+    }
+
+    #[test]
+    fn test_checklist_all_failed() {
+        let local = vec![cand("192.168.1.1:4000", 126)];
+        let remote = vec![cand("203.0.113.1:4000", 126)];
+        let mut checklist = CandidateChecklist::new(&local, &remote);
+
+        assert!(!checklist.all_failed());
+        let idx = checklist.next_waiting().unwrap();
+        checklist.mark_failed(idx);
+        assert!(checklist.all_failed());
+        assert!(checklist.succeeded().is_none());
+    }
+
+    #[test]
+    fn test_checklist_empty() {
+        let checklist = CandidateChecklist::new(&[], &[]);
+        assert!(checklist.is_empty());
+        assert!(!checklist.all_failed());
+    }
 
     #[test]
     fn test_health_status_equality() {
@@ -0,0 +1,176 @@
+//! Per-transfer ephemeral identities (signed delegation exchange).
+//!
+//! With [`TransferConfig::ephemeral_identities`] set, every outgoing
+//! transfer performs its own handshake under a freshly generated X25519
+//! key, so two transfers from the same node present unrelated identities
+//! to the recipient and to any on-path observer. The recipient still needs
+//! to know who it is really talking to, so right after establishment the
+//! sender delivers an [`IdentityDelegation`] — the ephemeral key signed by
+//! the long-term Ed25519 identity — inside the already-encrypted channel.
+//!
+//! This module carries the delegation over a Control frame: tag
+//! [`CONTROL_IDENTITY_DELEGATION`] followed by the serialized delegation.
+//! The receiver verifies the signature and validity window, checks that
+//! the delegated key is the one the session was actually established
+//! under, and records the ephemeral-to-long-term mapping, queryable via
+//! [`Node::delegated_identity`].
+//!
+//! The session establishment itself lives in
+//! `Node::establish_ephemeral_session` alongside the regular handshake
+//! paths; ephemeral sessions are registered in the routing table only and
+//! are never announced to the DHT.
+//!
+//! [`TransferConfig::ephemeral_identities`]: crate::node::config::TransferConfig::ephemeral_identities
+
+use crate::FRAME_HEADER_SIZE;
+use crate::frame::{FrameBuilder, FrameType};
+use crate::node::Node;
+use crate::node::error::{NodeError, Result};
+use crate::node::session::PeerId;
+use wraith_crypto::delegation::IdentityDelegation;
+
+/// Control payload tag: identity delegation (ephemeral sender -> receiver)
+pub const CONTROL_IDENTITY_DELEGATION: u8 = 0x30;
+
+/// Validity window granted to each per-transfer ephemeral key
+///
+/// Long enough to cover any realistic transfer setup, short enough that a
+/// leaked ephemeral secret is quickly worthless.
+pub(crate) const DELEGATION_VALIDITY_SECS: u64 = 600;
+
+/// Encode a delegation control payload
+///
+/// Format: tag(1) + serialized [`IdentityDelegation`].
+pub(crate) fn encode_delegation(delegation: &IdentityDelegation) -> Vec<u8> {
+    let bytes = delegation.serialize();
+    let mut payload = Vec::with_capacity(1 + bytes.len());
+    payload.push(CONTROL_IDENTITY_DELEGATION);
+    payload.extend_from_slice(&bytes);
+    payload
+}
+
+/// Decode a delegation control payload (tag already consumed)
+///
+/// Only the framing is checked here; signature and validity verification
+/// happen in [`Node::handle_identity_delegation`].
+pub(crate) fn decode_delegation(data: &[u8]) -> Result<IdentityDelegation> {
+    IdentityDelegation::deserialize(data)
+        .map_err(|e| NodeError::Crypto(format!("Malformed identity delegation: {e}")))
+}
+
+/// Build a Control frame carrying a delegation payload
+pub(crate) fn build_delegation_frame(delegation: &IdentityDelegation) -> Result<Vec<u8>> {
+    let payload = encode_delegation(delegation);
+    FrameBuilder::new()
+        .frame_type(FrameType::Control)
+        .stream_id(0)
+        .sequence(0)
+        .payload(&payload)
+        .build(FRAME_HEADER_SIZE + payload.len())
+        .map_err(|e| NodeError::InvalidState(format!("Failed to build control frame: {e}").into()))
+}
+
+impl Node {
+    /// The long-term node ID behind a peer presenting an ephemeral key
+    ///
+    /// Returns `Some` once a valid [`IdentityDelegation`] has been received
+    /// on the session with `peer_id`, `None` for peers that connected under
+    /// their long-term key (or whose delegation has not arrived yet).
+    #[must_use]
+    pub fn delegated_identity(&self, peer_id: &PeerId) -> Option<[u8; 32]> {
+        self.inner
+            .delegated_identities
+            .get(peer_id)
+            .map(|entry| *entry.value())
+    }
+
+    /// Handle an inbound delegation control payload (receiver side)
+    ///
+    /// Rejects delegations whose ephemeral key is not the key the session
+    /// was established under — accepting those would let any connected peer
+    /// claim an identity on behalf of a third session.
+    pub(crate) fn handle_identity_delegation(&self, data: &[u8], peer_id: PeerId) -> Result<()> {
+        let delegation = decode_delegation(data)?;
+
+        if delegation.ephemeral_key != peer_id {
+            return Err(NodeError::Crypto(
+                "Delegation does not cover the key this session was established under".to_string(),
+            ));
+        }
+
+        delegation
+            .verify_now()
+            .map_err(|e| NodeError::Crypto(format!("Invalid identity delegation: {e}")))?;
+
+        tracing::info!(
+            "Ephemeral peer {} delegated by long-term identity {}",
+            hex::encode(&peer_id[..8]),
+            hex::encode(&delegation.long_term_key[..8])
+        );
+        self.inner
+            .delegated_identities
+            .insert(peer_id, delegation.long_term_key);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+    use wraith_crypto::signatures::SigningKey;
+
+    #[test]
+    fn test_delegation_payload_roundtrip() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let delegation = IdentityDelegation::issue(&signing_key, [9u8; 32], 600);
+
+        let payload = encode_delegation(&delegation);
+        assert_eq!(payload[0], CONTROL_IDENTITY_DELEGATION);
+
+        let decoded = decode_delegation(&payload[1..]).unwrap();
+        assert_eq!(decoded, delegation);
+    }
+
+    #[test]
+    fn test_decode_delegation_truncated() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let delegation = IdentityDelegation::issue(&signing_key, [9u8; 32], 600);
+
+        let payload = encode_delegation(&delegation);
+        assert!(decode_delegation(&payload[1..payload.len() - 1]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_identity_delegation_records_mapping() {
+        let node = Node::new_random().await.unwrap();
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let ephemeral_key = [9u8; 32];
+        let delegation = IdentityDelegation::issue(&signing_key, ephemeral_key, 600);
+
+        let payload = encode_delegation(&delegation);
+        node.handle_identity_delegation(&payload[1..], ephemeral_key)
+            .unwrap();
+
+        assert_eq!(
+            node.delegated_identity(&ephemeral_key),
+            Some(signing_key.verifying_key().to_bytes())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_identity_delegation_rejects_wrong_session_key() {
+        let node = Node::new_random().await.unwrap();
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let delegation = IdentityDelegation::issue(&signing_key, [9u8; 32], 600);
+
+        // Delivered on a session established under a different key
+        let payload = encode_delegation(&delegation);
+        assert!(
+            node.handle_identity_delegation(&payload[1..], [8u8; 32])
+                .is_err()
+        );
+        assert!(node.delegated_identity(&[9u8; 32]).is_none());
+    }
+}
@@ -211,7 +211,9 @@ impl Node {
 
     /// Find nearby peers
     ///
-    /// Returns the N closest peers in the DHT keyspace.
+    /// Returns peers discovered on the local network via mDNS first (a
+    /// LAN peer is always the best transfer candidate), followed by the
+    /// closest peers in the DHT keyspace, up to `count` total.
     ///
     /// # Arguments
     ///
@@ -234,7 +236,22 @@ impl Node {
                 .clone()
         };
 
-        // Find peers closest to our own node ID
+        // LAN peers first: mDNS-discovered neighbours need no NAT
+        // traversal and have the best bandwidth
+        let mut peers: Vec<PeerInfo> = discovery
+            .local_peers()
+            .await
+            .into_iter()
+            .map(|peer| PeerInfo {
+                peer_id: peer.node_id,
+                addresses: vec![peer.addr],
+                nat_type: NatType::None,
+                capabilities: NodeCapabilities::default(),
+                last_seen: SystemTime::now(),
+            })
+            .collect();
+
+        // Fill the remainder with peers closest to our own node ID
         let our_node_id = wraith_discovery::dht::NodeId::from_bytes(*self.node_id());
 
         let dht_peers = discovery
@@ -244,21 +261,22 @@ impl Node {
             .routing_table()
             .closest_peers(&our_node_id, count);
 
-        let peer_count = dht_peers.len();
-
-        // Convert DHT peers to PeerInfo
-        let peers = dht_peers
-            .into_iter()
-            .map(|peer| PeerInfo {
+        for peer in dht_peers {
+            if peers.iter().any(|p| p.peer_id == *peer.id.as_bytes()) {
+                continue; // Already known via the LAN
+            }
+            peers.push(PeerInfo {
                 peer_id: *peer.id.as_bytes(),
                 addresses: vec![peer.addr],
                 nat_type: NatType::None, // Would be populated from DHT metadata
                 capabilities: NodeCapabilities::default(),
                 last_seen: SystemTime::now(),
-            })
-            .collect();
+            });
+        }
+
+        peers.truncate(count);
 
-        tracing::debug!("Found {} nearby peers", peer_count);
+        tracing::debug!("Found {} nearby peers", peers.len());
 
         Ok(peers)
     }
@@ -350,15 +368,18 @@ impl Node {
 
     /// Get local addresses for announcement
     ///
-    /// Returns all local network addresses where this node is listening.
+    /// Returns the configured listen address followed by the reflexive
+    /// addresses peers have reported seeing us at (most-corroborated
+    /// first), so announcements carry reachable endpoints without extra
+    /// STUN round trips.
     pub fn local_addresses(&self) -> Vec<SocketAddr> {
-        // In a real implementation, this would:
-        // 1. Get all local network interfaces
-        // 2. Perform STUN queries to discover external addresses
-        // 3. Return both local and external addresses
-        //
-        // For now, return the configured listen address
-        vec![self.inner.config.listen_addr]
+        let mut addresses = vec![self.inner.config.listen_addr];
+        for addr in self.observed_endpoints() {
+            if !addresses.contains(&addr) {
+                addresses.push(addr);
+            }
+        }
+        addresses
     }
 
     /// Get node capabilities
@@ -93,6 +93,14 @@ pub enum NodeError {
     #[error("Peer not found: {}", hex::encode(&.0[..8]))]
     PeerNotFound([u8; 32]),
 
+    /// Peer presented a key that differs from its pinned identity
+    #[error("Peer identity verification failed: {0}")]
+    TrustViolation(Cow<'static, str>),
+
+    /// The installed authorizer denied a session or transfer
+    #[error("Authorization denied: {0}")]
+    AuthorizationDenied(Cow<'static, str>),
+
     // ============ Connection Errors ============
     /// Connection migration failed
     #[error("Connection migration failed: {0}")]
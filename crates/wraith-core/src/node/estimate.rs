@@ -0,0 +1,325 @@
+//! Pre-transfer bandwidth estimation
+//!
+//! Applications deciding *whether* (or *when*, or *via whom*) to start a
+//! transfer want a throughput prediction before committing to one.
+//! [`Node::estimate_bandwidth`] answers from what the node already knows,
+//! cheapest source first:
+//!
+//! 1. A fresh [`path_estimates`](crate::node::path_estimates) snapshot for
+//!    the peer's current path - measured delivered bandwidth from a
+//!    transfer moments ago.
+//! 2. The persisted [`peer_history`](crate::node::peer_history) record -
+//!    throughput smoothed over past transfers, valid across restarts.
+//!
+//! When neither exists (a never-before-seen peer), the passive estimate
+//! fails and the application can fall back to [`Node::probe_bandwidth`]: a
+//! short packet-train probe that sends a burst of back-to-back padded
+//! PINGs and derives the bottleneck rate from the dispersion of the PONG
+//! arrivals. The probe costs a few kilobytes and one round trip, and its
+//! result is folded into the path-estimate cache so the next passive query
+//! (and a careful-resume seed) benefits.
+//!
+//! All estimates are predictions, not guarantees: they describe the
+//! bottleneck observed at measurement time, before congestion control,
+//! cross traffic, and configured bandwidth limits have their say.
+
+use std::time::{Duration, Instant};
+
+use crate::frame::{FrameBuilder, FrameType};
+use crate::node::Node;
+use crate::node::error::{NodeError, Result};
+use crate::node::session::PeerId;
+
+/// Packets in the probe train
+///
+/// Enough gaps (one fewer than packets) to average out scheduling noise
+/// while keeping the probe under a handful of kilobytes.
+const PROBE_TRAIN_LEN: usize = 8;
+
+/// Padded size of each probe PING frame in bytes
+///
+/// Large enough that serialization dominates queueing at plausible
+/// bottlenecks; comfortably under any sane MTU after encryption overhead.
+const PROBE_FRAME_SIZE: usize = 1024;
+
+/// How long the probe waits for its PONGs before giving up
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Where a bandwidth estimate came from, in decreasing order of freshness
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EstimateSource {
+    /// Active probe measurement taken just now
+    Probe,
+    /// Fresh path estimate recorded by a recent transfer to this peer
+    CurrentPath,
+    /// Persisted history smoothed over past transfers
+    History,
+}
+
+/// A throughput prediction toward a peer
+///
+/// Returned by [`Node::estimate_bandwidth`] and [`Node::probe_bandwidth`].
+#[derive(Debug, Clone, Copy)]
+pub struct BandwidthEstimate {
+    /// Estimated achievable throughput in bytes per second
+    pub bandwidth_bps: u64,
+    /// Round-trip time toward the peer, if one was measured
+    pub rtt: Option<Duration>,
+    /// Which source produced the estimate
+    pub source: EstimateSource,
+}
+
+impl BandwidthEstimate {
+    /// Predicted duration of a transfer of `bytes` at the estimated rate
+    ///
+    /// A pure division - protocol overhead and ramp-up are not modelled,
+    /// so treat the result as a lower bound suitable for ETAs and peer
+    /// comparison rather than a deadline.
+    #[must_use]
+    pub fn transfer_duration(&self, bytes: u64) -> Duration {
+        Duration::from_secs_f64(bytes as f64 / self.bandwidth_bps.max(1) as f64)
+    }
+}
+
+impl Node {
+    /// Estimate achievable throughput to a peer without sending anything
+    ///
+    /// Consults the fresh path-estimate cache first, then the persisted
+    /// peer history (when [`TransferConfig::peer_history_path`] is set).
+    /// Fails with [`NodeError::Transfer`] when the node has never measured
+    /// this peer; callers wanting an answer anyway can follow up with
+    /// [`Node::probe_bandwidth`].
+    ///
+    /// [`TransferConfig::peer_history_path`]: crate::node::config::TransferConfig::peer_history_path
+    pub async fn estimate_bandwidth(&self, peer_id: &PeerId) -> Result<BandwidthEstimate> {
+        // A recent transfer over the current path is the best predictor
+        if let Some(session) = self.inner.sessions.get(peer_id).map(|s| s.value().clone())
+            && let Some(snapshot) = self
+                .inner
+                .path_estimates
+                .fresh(*peer_id, session.peer_addr())
+        {
+            return Ok(BandwidthEstimate {
+                bandwidth_bps: snapshot.btl_bw,
+                rtt: Some(snapshot.min_rtt),
+                source: EstimateSource::CurrentPath,
+            });
+        }
+
+        // Fall back to smoothed history from past transfers
+        if let Some(path) = &self.inner.config.transfer.peer_history_path {
+            let store = crate::node::peer_history::PeerHistoryStore::new(path.clone());
+            if let Err(e) = store.load().await {
+                tracing::warn!("Failed to load peer history: {}", e);
+            }
+            if let Some(record) = store.get(peer_id).await {
+                return Ok(BandwidthEstimate {
+                    bandwidth_bps: record.throughput_bps,
+                    rtt: Some(Duration::from_micros(record.rtt_us)),
+                    source: EstimateSource::History,
+                });
+            }
+        }
+
+        Err(NodeError::Transfer(
+            "no bandwidth estimate available for peer; probe_bandwidth can measure one".into(),
+        ))
+    }
+
+    /// Actively measure throughput to a peer with a short packet train
+    ///
+    /// Establishes a session if none exists, then sends
+    /// [`PROBE_TRAIN_LEN`] padded PING frames back-to-back and estimates
+    /// the bottleneck rate from how far the train spread out by the time
+    /// the PONGs returned. Costs a few kilobytes and completes within one
+    /// round trip (bounded by an internal timeout). The measurement is
+    /// recorded in the path-estimate cache, so it also seeds
+    /// careful-resume and subsequent [`Node::estimate_bandwidth`] calls.
+    ///
+    /// Dispersion-based estimates are coarse - the returned rate reflects
+    /// the tighter direction of the round trip and can be off by tens of
+    /// percent on jittery paths. Prefer the passive estimate when one
+    /// exists.
+    pub async fn probe_bandwidth(&self, peer_id: &PeerId) -> Result<BandwidthEstimate> {
+        let session = self.get_or_establish_session(peer_id).await?;
+        let transport = self
+            .inner
+            .transport
+            .get()
+            .ok_or(NodeError::Transport("Transport not initialized".into()))?;
+
+        let start = Instant::now();
+        let base_sequence = (start.elapsed().as_nanos() & 0xFFFF_FFFF) as u32;
+
+        // Register all receivers before the first send so no PONG races
+        // its waiter
+        let mut receivers = Vec::with_capacity(PROBE_TRAIN_LEN);
+        let mut packets = Vec::with_capacity(PROBE_TRAIN_LEN);
+        for i in 0..PROBE_TRAIN_LEN {
+            let sequence = base_sequence.wrapping_add(i as u32);
+            let frame = FrameBuilder::new()
+                .frame_type(FrameType::Ping)
+                .stream_id(0)
+                .sequence(sequence)
+                .build(PROBE_FRAME_SIZE)
+                .map_err(|e| {
+                    NodeError::Other(format!("Failed to build probe frame: {e}").into())
+                })?;
+
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            self.inner.pending_pings.insert((*peer_id, sequence), tx);
+            receivers.push((sequence, rx));
+            packets.push(session.encrypt_frame(&frame).await.inspect_err(|_| {
+                self.cancel_probe(peer_id, base_sequence);
+            })?);
+        }
+
+        // The train must leave back-to-back: dispersion measured at the
+        // far end only reflects the bottleneck if we did not pace it
+        let wire_bytes = packets.iter().map(|p| p.len() as u64).sum::<u64>();
+        for packet in &packets {
+            transport
+                .send_to(packet, session.peer_addr())
+                .await
+                .map_err(|e| {
+                    self.cancel_probe(peer_id, base_sequence);
+                    NodeError::Transport(format!("Failed to send probe: {e}").into())
+                })?;
+        }
+
+        // Collect PONG arrivals; stragglers beyond the timeout are dropped
+        let mut arrivals = Vec::with_capacity(PROBE_TRAIN_LEN);
+        let deadline = start + PROBE_TIMEOUT;
+        for (sequence, rx) in receivers {
+            match tokio::time::timeout_at(deadline.into(), rx).await {
+                Ok(Ok(arrival)) => arrivals.push(arrival),
+                _ => {
+                    self.inner.pending_pings.remove(&(*peer_id, sequence));
+                }
+            }
+        }
+
+        if arrivals.len() < 2 {
+            return Err(NodeError::Transfer(
+                "bandwidth probe received too few responses".into(),
+            ));
+        }
+
+        let first = *arrivals.iter().min().expect("arrivals is non-empty");
+        let last = *arrivals.iter().max().expect("arrivals is non-empty");
+        let rtt = first.duration_since(start);
+
+        // Bytes actually covered by the measured dispersion: the gaps
+        // between the answered packets. Clamp the dispersion so a
+        // same-instant burst on a fast LAN cannot divide by zero.
+        let gap_bytes = wire_bytes / PROBE_TRAIN_LEN as u64 * (arrivals.len() as u64 - 1);
+        let dispersion = last.duration_since(first).max(Duration::from_micros(1));
+        #[allow(clippy::cast_precision_loss)]
+        #[allow(clippy::cast_possible_truncation)]
+        #[allow(clippy::cast_sign_loss)]
+        let bandwidth_bps = (gap_bytes as f64 / dispersion.as_secs_f64()) as u64;
+
+        self.inner
+            .path_estimates
+            .record(*peer_id, session.peer_addr(), bandwidth_bps, Some(rtt));
+
+        Ok(BandwidthEstimate {
+            bandwidth_bps,
+            rtt: Some(rtt),
+            source: EstimateSource::Probe,
+        })
+    }
+
+    /// Drop every pending-ping registration a failed probe left behind
+    fn cancel_probe(&self, peer_id: &PeerId, base_sequence: u32) {
+        for i in 0..PROBE_TRAIN_LEN {
+            self.inner
+                .pending_pings
+                .remove(&(*peer_id, base_sequence.wrapping_add(i as u32)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_duration_is_size_over_rate() {
+        let estimate = BandwidthEstimate {
+            bandwidth_bps: 10_000_000,
+            rtt: Some(Duration::from_millis(20)),
+            source: EstimateSource::CurrentPath,
+        };
+        assert_eq!(
+            estimate.transfer_duration(100_000_000),
+            Duration::from_secs(10)
+        );
+    }
+
+    #[test]
+    fn test_transfer_duration_survives_zero_rate() {
+        let estimate = BandwidthEstimate {
+            bandwidth_bps: 0,
+            rtt: None,
+            source: EstimateSource::History,
+        };
+        // Degenerate estimate: clamped instead of dividing by zero
+        assert_eq!(estimate.transfer_duration(5), Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_fails_for_unknown_peer() {
+        let node = Node::new_random().await.unwrap();
+        let result = node.estimate_bandwidth(&[7u8; 32]).await;
+        assert!(matches!(result, Err(NodeError::Transfer(_))));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_prefers_fresh_path_over_history() {
+        use crate::node::session::PeerConnection;
+
+        let node = Node::new_random().await.unwrap();
+        let peer_id = [9u8; 32];
+        let addr: std::net::SocketAddr = "127.0.0.1:9999".parse().unwrap();
+
+        let connection = PeerConnection::new_for_test(peer_id, addr);
+        node.inner
+            .sessions
+            .insert(peer_id, std::sync::Arc::new(connection));
+        node.inner.path_estimates.record(
+            peer_id,
+            addr,
+            25_000_000,
+            Some(Duration::from_millis(12)),
+        );
+
+        let estimate = node.estimate_bandwidth(&peer_id).await.unwrap();
+        assert_eq!(estimate.source, EstimateSource::CurrentPath);
+        assert_eq!(estimate.bandwidth_bps, 25_000_000);
+        assert_eq!(estimate.rtt, Some(Duration::from_millis(12)));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_falls_back_to_persisted_history() {
+        use crate::node::config::NodeConfig;
+        use crate::node::peer_history::PeerHistoryStore;
+
+        let dir = tempfile::tempdir().unwrap();
+        let history_path = dir.path().join("peer_history.json");
+
+        let peer_id = [3u8; 32];
+        let store = PeerHistoryStore::new(history_path.clone());
+        store.record_measurement(peer_id, 30_000, 5_000_000).await;
+        store.save().await.unwrap();
+
+        let mut config = NodeConfig::default();
+        config.transfer.peer_history_path = Some(history_path);
+        let node = Node::new_with_config(config).await.unwrap();
+
+        let estimate = node.estimate_bandwidth(&peer_id).await.unwrap();
+        assert_eq!(estimate.source, EstimateSource::History);
+        assert_eq!(estimate.bandwidth_bps, 5_000_000);
+        assert_eq!(estimate.rtt, Some(Duration::from_micros(30_000)));
+    }
+}
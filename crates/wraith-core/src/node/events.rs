@@ -0,0 +1,167 @@
+//! Typed node event bus
+//!
+//! Broadcasts node state changes (peer sessions, transfers, path
+//! migration) to any number of subscribers over a tokio broadcast
+//! channel, so the CLI, FFI layer, and GUI clients can react to events
+//! instead of polling node state. Subscribing is cheap; events emitted
+//! with no subscribers are dropped, and slow subscribers that fall more
+//! than the channel capacity behind lose the oldest events (signalled by
+//! `RecvError::Lagged`).
+
+use std::net::SocketAddr;
+
+use tokio::sync::broadcast;
+
+use crate::node::identity::TransferId;
+use crate::node::session::PeerId;
+
+/// Broadcast channel capacity per node
+///
+/// Sized so a slow consumer can fall several seconds behind a busy node
+/// before losing events.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// A typed node event
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeEvent {
+    /// The node started and is accepting connections
+    NodeStarted,
+    /// The node stopped
+    NodeStopped,
+    /// A session with a peer was established
+    PeerConnected {
+        /// The peer's node ID
+        peer_id: PeerId,
+    },
+    /// A session with a peer was closed
+    PeerDisconnected {
+        /// The peer's node ID
+        peer_id: PeerId,
+    },
+    /// A handshake attempt failed
+    HandshakeFailed {
+        /// The peer the handshake targeted
+        peer_id: PeerId,
+        /// Human-readable failure reason
+        reason: String,
+    },
+    /// A file transfer was registered and began sending
+    TransferStarted {
+        /// The transfer ID
+        transfer_id: TransferId,
+        /// The receiving peer's node ID
+        peer_id: PeerId,
+    },
+    /// A transfer made progress
+    TransferProgress {
+        /// The transfer ID
+        transfer_id: TransferId,
+        /// Bytes transferred so far
+        bytes_sent: u64,
+        /// Total bytes in the transfer
+        bytes_total: u64,
+    },
+    /// A transfer finished successfully
+    TransferCompleted {
+        /// The transfer ID
+        transfer_id: TransferId,
+    },
+    /// A peer presented a key differing from its pinned identity
+    ///
+    /// Emitted whether or not the session was allowed to continue; see
+    /// [`TrustConfig::allow_key_change`](crate::node::config::TrustConfig).
+    PeerKeyMismatch {
+        /// The peer whose key changed
+        peer_id: PeerId,
+        /// The key pinned when the peer was first seen
+        pinned_key: [u8; 32],
+        /// The key the peer presented in this handshake
+        presented_key: [u8; 32],
+    },
+    /// A session migrated to a new network path
+    PathMigrated {
+        /// The peer whose session migrated
+        peer_id: PeerId,
+        /// The validated new address
+        new_addr: SocketAddr,
+    },
+}
+
+/// Event bus backing [`Node::subscribe`](crate::node::Node::subscribe)
+#[derive(Debug)]
+pub(crate) struct EventBus {
+    sender: broadcast::Sender<NodeEvent>,
+}
+
+impl EventBus {
+    /// Create a new bus
+    pub(crate) fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    /// Subscribe to events emitted after this call
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<NodeEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Emit an event to all current subscribers
+    ///
+    /// Dropped silently when no one is subscribed.
+    pub(crate) fn emit(&self, event: NodeEvent) {
+        let _ = self.sender.send(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_without_subscribers_is_silent() {
+        let bus = EventBus::new();
+        bus.emit(NodeEvent::NodeStarted);
+    }
+
+    #[tokio::test]
+    async fn test_subscriber_receives_events_in_order() {
+        let bus = EventBus::new();
+        let mut rx = bus.subscribe();
+
+        bus.emit(NodeEvent::NodeStarted);
+        bus.emit(NodeEvent::PeerConnected { peer_id: [1u8; 32] });
+
+        assert_eq!(rx.recv().await.unwrap(), NodeEvent::NodeStarted);
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            NodeEvent::PeerConnected { peer_id: [1u8; 32] }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_multiple_subscribers_each_receive_events() {
+        let bus = EventBus::new();
+        let mut rx1 = bus.subscribe();
+        let mut rx2 = bus.subscribe();
+
+        bus.emit(NodeEvent::TransferCompleted {
+            transfer_id: [7u8; 32],
+        });
+
+        let expected = NodeEvent::TransferCompleted {
+            transfer_id: [7u8; 32],
+        };
+        assert_eq!(rx1.recv().await.unwrap(), expected);
+        assert_eq!(rx2.recv().await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_starts_at_subscribe_time() {
+        let bus = EventBus::new();
+        bus.emit(NodeEvent::NodeStarted);
+
+        let mut rx = bus.subscribe();
+        bus.emit(NodeEvent::NodeStopped);
+        assert_eq!(rx.recv().await.unwrap(), NodeEvent::NodeStopped);
+    }
+}
@@ -8,12 +8,13 @@
 use crate::FRAME_HEADER_SIZE;
 use crate::frame::{FrameBuilder, FrameType};
 use crate::node::error::{NodeError, Result};
+use crate::transfer::compression::CompressionAlgorithm;
 use crate::transfer::session::TransferSession;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::{Mutex, RwLock};
 use wraith_files::chunker::FileReassembler;
-use wraith_files::tree_hash::FileTreeHash;
+use wraith_files::tree_hash::{FileTreeHash, TreeHashVerifier};
 
 /// File transfer context consolidating all per-transfer state
 ///
@@ -33,6 +34,13 @@ pub struct FileTransferContext {
 
     /// Tree hash for integrity verification
     pub tree_hash: FileTreeHash,
+
+    /// Streaming chunk verifier for receive transfers (None for sends)
+    ///
+    /// Validates chunks against the offer's root hash as they arrive, so
+    /// corruption is caught (and the chunk re-requested) during the
+    /// transfer instead of at finalize.
+    pub verifier: Option<Arc<Mutex<TreeHashVerifier>>>,
 }
 
 impl FileTransferContext {
@@ -47,21 +55,36 @@ impl FileTransferContext {
             transfer_session,
             reassembler: None,
             tree_hash,
+            verifier: None,
         }
     }
 
     /// Create context for receive transfer
+    ///
+    /// Builds a [`TreeHashVerifier`] from the offer's root hash; when the
+    /// tree hash already carries per-chunk hashes they are installed as the
+    /// verifier's manifest so corrupt chunks are rejected on arrival.
     pub fn new_receive(
         transfer_id: [u8; 32],
         transfer_session: Arc<RwLock<TransferSession>>,
         reassembler: Arc<Mutex<FileReassembler>>,
         tree_hash: FileTreeHash,
+        total_chunks: u64,
     ) -> Self {
+        let mut verifier = TreeHashVerifier::new(tree_hash.root, total_chunks);
+        if !tree_hash.chunks.is_empty() && !verifier.set_manifest(tree_hash.chunks.clone()) {
+            tracing::warn!(
+                "Chunk hash manifest does not match root for transfer {:?}; falling back to root-only verification",
+                hex::encode(&transfer_id[..8])
+            );
+        }
+
         Self {
             transfer_id,
             transfer_session,
             reassembler: Some(reassembler),
             tree_hash,
+            verifier: Some(Arc::new(Mutex::new(verifier))),
         }
     }
 }
@@ -85,6 +108,12 @@ pub struct FileMetadata {
     pub total_chunks: u64,
     /// BLAKE3 root hash (32 bytes)
     pub root_hash: [u8; 32],
+    /// Compression algorithm offered for this transfer
+    ///
+    /// Chunks are compressed with this algorithm before encryption; see
+    /// [`crate::transfer::compression`]. Peers that predate compression
+    /// omit the trailing byte, which deserializes as `None`.
+    pub compression: CompressionAlgorithm,
 }
 
 impl FileMetadata {
@@ -117,6 +146,7 @@ impl FileMetadata {
             chunk_size: chunk_size as u32,
             total_chunks,
             root_hash: tree_hash.root,
+            compression: CompressionAlgorithm::None,
         })
     }
 
@@ -130,13 +160,14 @@ impl FileMetadata {
     /// - 4 bytes: chunk_size (big-endian)
     /// - 8 bytes: total_chunks (big-endian)
     /// - 32 bytes: root_hash
+    /// - 1 byte: compression algorithm tag
     ///
-    /// Total: 85 + file_name.len() bytes
+    /// Total: 86 + file_name.len() bytes
     pub fn serialize(&self) -> Vec<u8> {
         let file_name_bytes = self.file_name.as_bytes();
         let file_name_len = file_name_bytes.len() as u8;
 
-        let mut buf = Vec::with_capacity(85 + file_name_bytes.len());
+        let mut buf = Vec::with_capacity(86 + file_name_bytes.len());
 
         // Transfer ID (32 bytes)
         buf.extend_from_slice(&self.transfer_id);
@@ -157,6 +188,9 @@ impl FileMetadata {
         // Root hash (32 bytes)
         buf.extend_from_slice(&self.root_hash);
 
+        // Compression algorithm tag (1 byte)
+        buf.push(self.compression.wire_tag());
+
         buf
     }
 
@@ -214,6 +248,14 @@ impl FileMetadata {
         // Root hash (32 bytes)
         let mut root_hash = [0u8; 32];
         root_hash.copy_from_slice(&data[offset..offset + 32]);
+        offset += 32;
+
+        // Compression algorithm tag (1 byte, absent from pre-compression peers)
+        let compression = data
+            .get(offset)
+            .copied()
+            .map(CompressionAlgorithm::from_wire_tag)
+            .unwrap_or_default();
 
         Ok(Self {
             transfer_id,
@@ -222,6 +264,7 @@ impl FileMetadata {
             chunk_size,
             total_chunks,
             root_hash,
+            compression,
         })
     }
 }
@@ -241,18 +284,39 @@ pub fn build_metadata_frame(stream_id: u16, metadata: &FileMetadata) -> Result<V
 }
 
 /// Build a data frame for file chunk
-pub fn build_chunk_frame(stream_id: u16, chunk_index: u64, chunk_data: &[u8]) -> Result<Vec<u8>> {
-    let frame_size = FRAME_HEADER_SIZE + chunk_data.len();
+///
+/// When `trace_id` is set (frame tracing enabled), the ID is stamped into
+/// the frame and logged so the receiving side can correlate its logs.
+pub fn build_chunk_frame(
+    stream_id: u16,
+    chunk_index: u64,
+    chunk_data: &[u8],
+    trace_id: Option<u32>,
+) -> Result<Vec<u8>> {
+    let mut frame_size = FRAME_HEADER_SIZE + chunk_data.len();
 
     // Use chunk_index as sequence number
     let sequence = chunk_index as u32;
 
-    FrameBuilder::new()
+    let mut builder = FrameBuilder::new()
         .frame_type(FrameType::Data)
         .stream_id(stream_id)
         .sequence(sequence)
         .offset(chunk_index * chunk_data.len() as u64) // File offset
-        .payload(chunk_data)
+        .payload(chunk_data);
+
+    if let Some(trace) = trace_id {
+        frame_size += 4;
+        builder = builder.trace_id(trace);
+        tracing::debug!(
+            "Frame trace {:08x}: sent DATA stream {} seq {}",
+            trace,
+            stream_id,
+            sequence
+        );
+    }
+
+    builder
         .build(frame_size)
         .map_err(|e| NodeError::InvalidState(format!("Failed to build chunk frame: {e}").into()))
 }
@@ -270,6 +334,7 @@ mod tests {
             chunk_size: 256 * 1024,
             total_chunks: 4,
             root_hash: [0xAB; 32],
+            compression: CompressionAlgorithm::Lz4,
         };
 
         let serialized = metadata.serialize();
@@ -281,6 +346,28 @@ mod tests {
         assert_eq!(metadata.chunk_size, deserialized.chunk_size);
         assert_eq!(metadata.total_chunks, deserialized.total_chunks);
         assert_eq!(metadata.root_hash, deserialized.root_hash);
+        assert_eq!(metadata.compression, deserialized.compression);
+    }
+
+    #[test]
+    fn test_metadata_without_compression_byte() {
+        let metadata = FileMetadata {
+            transfer_id: [7u8; 32],
+            file_name: "legacy.dat".to_string(),
+            file_size: 4096,
+            chunk_size: 1024,
+            total_chunks: 4,
+            root_hash: [9u8; 32],
+            compression: CompressionAlgorithm::Zstd,
+        };
+
+        // Peers that predate compression omit the trailing byte
+        let mut serialized = metadata.serialize();
+        serialized.pop();
+
+        let deserialized = FileMetadata::deserialize(&serialized).unwrap();
+        assert_eq!(deserialized.compression, CompressionAlgorithm::None);
+        assert_eq!(deserialized.file_name, metadata.file_name);
     }
 
     #[test]
@@ -292,6 +379,7 @@ mod tests {
             chunk_size: 256,
             total_chunks: 4,
             root_hash: [2u8; 32],
+            compression: CompressionAlgorithm::None,
         };
 
         let serialized = metadata.serialize();
@@ -315,6 +403,7 @@ mod tests {
             chunk_size: 256,
             total_chunks: 4,
             root_hash: [2u8; 32],
+            compression: CompressionAlgorithm::None,
         };
 
         let frame_bytes = build_metadata_frame(42, &metadata).unwrap();
@@ -336,7 +425,7 @@ mod tests {
         let chunk_data = vec![0xAB; 1024];
         let chunk_index = 5;
 
-        let frame_bytes = build_chunk_frame(100, chunk_index, &chunk_data).unwrap();
+        let frame_bytes = build_chunk_frame(100, chunk_index, &chunk_data, None).unwrap();
 
         // Verify frame
         let frame = crate::frame::Frame::parse(&frame_bytes).unwrap();
@@ -344,5 +433,17 @@ mod tests {
         assert_eq!(frame.stream_id(), 100);
         assert_eq!(frame.sequence(), chunk_index as u32);
         assert_eq!(frame.payload(), &chunk_data);
+        assert_eq!(frame.trace_id(), None);
+    }
+
+    #[test]
+    fn test_build_chunk_frame_with_trace_id() {
+        let chunk_data = vec![0xCD; 512];
+
+        let frame_bytes = build_chunk_frame(100, 3, &chunk_data, Some(0x1234_5678)).unwrap();
+
+        let frame = crate::frame::Frame::parse(&frame_bytes).unwrap();
+        assert_eq!(frame.payload(), &chunk_data);
+        assert_eq!(frame.trace_id(), Some(0x1234_5678));
     }
 }
@@ -24,7 +24,7 @@ pub enum HealthStatus {
 }
 
 /// Health monitoring configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct HealthConfig {
     /// Memory threshold for degraded state (percentage, 0.0-1.0)
     pub degraded_memory_threshold: f64,
@@ -170,6 +170,12 @@ pub struct Identity {
 
     /// X25519 keypair for Noise handshakes
     x25519: NoiseKeypair,
+
+    /// Ed25519 signing key (absent for identities restored without one)
+    ///
+    /// Used to sign identity delegations for ephemeral per-transfer keys;
+    /// identities built via [`Identity::from_components`] cannot sign.
+    ed25519: Option<std::sync::Arc<Ed25519SigningKey>>,
 }
 
 impl Identity {
@@ -196,12 +202,15 @@ impl Identity {
         // Generate Ed25519 keypair and extract public key as node ID
         let ed25519 = Ed25519SigningKey::generate(&mut OsRng);
         let node_id = ed25519.verifying_key().to_bytes();
-        // Note: We don't store the signing key, only use the public key as node ID
 
         // Generate X25519 keypair for Noise handshakes
         let x25519 = NoiseKeypair::generate().map_err(|e| NodeError::Crypto(e.to_string()))?;
 
-        Ok(Self { node_id, x25519 })
+        Ok(Self {
+            node_id,
+            x25519,
+            ed25519: Some(std::sync::Arc::new(ed25519)),
+        })
     }
 
     /// Create identity from existing components
@@ -224,7 +233,50 @@ impl Identity {
     /// let identity = Identity::from_components(node_id, x25519);
     /// ```
     pub fn from_components(node_id: [u8; 32], x25519: NoiseKeypair) -> Self {
-        Self { node_id, x25519 }
+        Self {
+            node_id,
+            x25519,
+            ed25519: None,
+        }
+    }
+
+    /// Restore a persisted identity from its Ed25519 seed
+    ///
+    /// Rebuilds the full identity - node ID, signing key, and the X25519
+    /// static key for Noise handshakes - from the 32-byte Ed25519 seed,
+    /// which is the only secret an application needs to persist. The
+    /// X25519 key is derived deterministically from the seed (domain
+    /// separated via BLAKE3), so the same seed always yields the same
+    /// identity on every load.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the derived X25519 key is rejected (effectively
+    /// never for uniformly random seeds).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wraith_core::node::identity::Identity;
+    ///
+    /// let seed = [7u8; 32];
+    /// let a = Identity::from_ed25519_seed(seed).unwrap();
+    /// let b = Identity::from_ed25519_seed(seed).unwrap();
+    /// assert_eq!(a.public_key(), b.public_key());
+    /// ```
+    pub fn from_ed25519_seed(seed: [u8; 32]) -> Result<Self> {
+        let ed25519 = Ed25519SigningKey::from_bytes(&seed);
+        let node_id = ed25519.verifying_key().to_bytes();
+
+        let x25519_private = blake3::derive_key("WRAITH v1 identity x25519 static", &seed);
+        let x25519 = NoiseKeypair::from_bytes(x25519_private)
+            .map_err(|e| NodeError::Crypto(e.to_string()))?;
+
+        Ok(Self {
+            node_id,
+            x25519,
+            ed25519: Some(std::sync::Arc::new(ed25519)),
+        })
     }
 
     /// Get the node's public key (node ID)
@@ -257,6 +309,15 @@ impl Identity {
     pub fn x25519_keypair(&self) -> &NoiseKeypair {
         &self.x25519
     }
+
+    /// Get the Ed25519 signing key, when this identity holds one
+    ///
+    /// `None` for identities restored via [`Identity::from_components`];
+    /// such identities cannot issue delegations for ephemeral keys.
+    #[must_use]
+    pub fn signing_key(&self) -> Option<&Ed25519SigningKey> {
+        self.ed25519.as_deref()
+    }
 }
 
 impl std::fmt::Debug for Identity {
@@ -292,6 +353,21 @@ mod tests {
         assert_ne!(id1.x25519_public_key(), id2.x25519_public_key());
     }
 
+    #[test]
+    fn test_identity_from_seed_is_deterministic() {
+        let a = Identity::from_ed25519_seed([42u8; 32]).unwrap();
+        let b = Identity::from_ed25519_seed([42u8; 32]).unwrap();
+
+        assert_eq!(a.public_key(), b.public_key());
+        assert_eq!(a.x25519_public_key(), b.x25519_public_key());
+        // A restored identity can still sign delegations
+        assert!(a.signing_key().is_some());
+
+        let other = Identity::from_ed25519_seed([43u8; 32]).unwrap();
+        assert_ne!(a.public_key(), other.public_key());
+        assert_ne!(a.x25519_public_key(), other.x25519_public_key());
+    }
+
     #[test]
     fn test_identity_from_components() {
         let x25519 = NoiseKeypair::generate().unwrap();
@@ -0,0 +1,311 @@
+//! NAT keepalive and binding-lifetime estimation.
+//!
+//! Hole-punched NAT bindings expire silently: the NAT drops the mapping
+//! after some idle period (commonly 30-180 s for UDP) and the next inbound
+//! packet is simply discarded, with neither endpoint told. This module
+//! keeps bindings alive by sending an encrypted PING whenever a session
+//! has been idle for close to the estimated binding lifetime, and learns
+//! that lifetime per peer instead of pinning a worst-case interval.
+//!
+//! [`BindingEstimator`] probes the lifetime adaptively: every successful
+//! keepalive after an idle stretch proves the binding survives that long,
+//! so the interval is stretched; a failed keepalive marks the interval as
+//! too long and falls back to the longest proven-good one. The interval
+//! converges between [`KEEPALIVE_FLOOR`] and the shortest observed
+//! failure, so chatty 15-second pings against a NAT with a 2-minute
+//! timeout stop within a few probes.
+//!
+//! When a keepalive fails (the binding broke before the estimator
+//! expected), the loop triggers [`Node::revalidate_binding`]: a
+//! PATH_CHALLENGE to the current address re-opens the mapping from the
+//! inside, and if the address itself has gone stale the peer is
+//! rediscovered and the session migrated.
+//!
+//! The loop is spawned by [`Node::start`] when
+//! [`TransportConfig::nat_keepalive`] is set (the default).
+//!
+//! [`TransportConfig::nat_keepalive`]: crate::node::config::TransportConfig::nat_keepalive
+
+use crate::node::Node;
+use crate::node::error::{NodeError, Result};
+use crate::node::session::PeerId;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Shortest keepalive interval the estimator will fall back to
+pub const KEEPALIVE_FLOOR: Duration = Duration::from_secs(5);
+
+/// Longest interval the estimator will stretch to
+///
+/// Bounded so a binding break is noticed within minutes even against
+/// NATs with very long (or no) UDP timeouts.
+pub const KEEPALIVE_CEILING: Duration = Duration::from_secs(120);
+
+/// Starting interval before anything is learned
+///
+/// Below the 30-second UDP timeout of the strictest common NATs, so the
+/// first probe is unlikely to find an already-dead binding.
+const KEEPALIVE_INITIAL: Duration = Duration::from_secs(15);
+
+/// How often the keepalive loop scans sessions for due keepalives
+const KEEPALIVE_TICK: Duration = Duration::from_secs(1);
+
+/// Adaptive estimate of a NAT binding's idle lifetime
+///
+/// Pure state machine: the caller reports whether a keepalive sent after
+/// `interval()` of silence was answered, and the estimate stretches or
+/// falls back accordingly. Growth is multiplicative (x1.5) and capped at
+/// three quarters of the shortest interval that ever failed, keeping the
+/// steady-state interval safely inside the binding lifetime.
+#[derive(Debug, Clone)]
+pub struct BindingEstimator {
+    /// Interval currently in use
+    current: Duration,
+    /// Longest idle stretch a keepalive is known to have survived
+    longest_ok: Duration,
+    /// Shortest idle stretch after which a keepalive ever failed
+    shortest_failed: Option<Duration>,
+}
+
+impl Default for BindingEstimator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BindingEstimator {
+    /// Create an estimator with the initial (unlearned) interval
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            current: KEEPALIVE_INITIAL,
+            longest_ok: Duration::ZERO,
+            shortest_failed: None,
+        }
+    }
+
+    /// The interval to let the session sit idle before the next keepalive
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        self.current
+    }
+
+    /// Longest idle stretch a keepalive is known to have survived
+    #[must_use]
+    pub fn proven_lifetime(&self) -> Duration {
+        self.longest_ok
+    }
+
+    /// A keepalive sent after `interval()` of silence was answered
+    ///
+    /// The binding demonstrably survives the current interval, so stretch
+    /// it — but never past the ceiling or into territory that already
+    /// failed.
+    pub fn record_success(&mut self) {
+        self.longest_ok = self.longest_ok.max(self.current);
+
+        let mut cap = KEEPALIVE_CEILING;
+        if let Some(failed) = self.shortest_failed {
+            cap = cap.min(failed.mul_f64(0.75));
+        }
+        self.current = (self.current.mul_f64(1.5)).min(cap).max(KEEPALIVE_FLOOR);
+    }
+
+    /// A keepalive sent after `interval()` of silence went unanswered
+    ///
+    /// The binding (or path) broke somewhere inside the current interval;
+    /// fall back to the longest proven-good interval and stop growing
+    /// past this point.
+    pub fn record_failure(&mut self) {
+        self.shortest_failed = Some(match self.shortest_failed {
+            Some(failed) => failed.min(self.current),
+            None => self.current,
+        });
+        self.current = self.longest_ok.max(KEEPALIVE_FLOOR);
+    }
+}
+
+impl Node {
+    /// NAT keepalive loop — keeps idle sessions' bindings open
+    ///
+    /// Scans sessions every [`KEEPALIVE_TICK`] and pings any that have
+    /// been idle longer than their estimator's interval. Sessions with
+    /// live traffic refresh their binding for free and are skipped.
+    pub(crate) async fn nat_keepalive_loop(&self) {
+        let mut estimators: HashMap<PeerId, BindingEstimator> = HashMap::new();
+        let mut ticker = self
+            .inner
+            .maintenance
+            .ticker("nat_keepalive", KEEPALIVE_TICK);
+
+        tracing::info!("NAT keepalive started");
+
+        loop {
+            ticker.tick().await;
+            if !self.is_running() {
+                break;
+            }
+
+            let sessions: Vec<_> = self
+                .inner
+                .sessions
+                .iter()
+                .map(|entry| (*entry.key(), entry.value().clone()))
+                .collect();
+
+            // Drop estimator state for sessions that no longer exist
+            estimators.retain(|peer_id, _| self.inner.sessions.contains_key(peer_id));
+
+            for (peer_id, session) in sessions {
+                let estimator = estimators.entry(peer_id).or_default();
+                let idle = Duration::from_millis(session.idle_duration_ms());
+                if idle < estimator.interval() {
+                    continue;
+                }
+
+                match self.ping_session(&peer_id, session).await {
+                    Ok(latency) => {
+                        estimator.record_success();
+                        tracing::trace!(
+                            "Keepalive to {} ok ({} µs), interval now {:?}",
+                            hex::encode(&peer_id[..8]),
+                            latency.as_micros(),
+                            estimator.interval()
+                        );
+                    }
+                    Err(e) => {
+                        estimator.record_failure();
+                        tracing::info!(
+                            "Keepalive to {} failed after {:?} idle ({}), revalidating path",
+                            hex::encode(&peer_id[..8]),
+                            idle,
+                            e
+                        );
+                        if let Err(e) = self.revalidate_binding(&peer_id).await {
+                            tracing::warn!(
+                                "Path revalidation for {} failed: {}",
+                                hex::encode(&peer_id[..8]),
+                                e
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        tracing::info!("NAT keepalive stopped");
+    }
+
+    /// Re-establish a broken NAT binding for a session
+    ///
+    /// First re-challenges the session's current address — an outbound
+    /// PATH_CHALLENGE re-opens the mapping from the inside, which is all
+    /// that is needed when only the binding timed out. If the current
+    /// address no longer answers, the peer is rediscovered and the
+    /// session migrated to the freshest address (the peer itself may have
+    /// been re-mapped to a new public port).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::SessionNotFound`] when no session with the
+    /// peer exists, or the final migration error when no address
+    /// validates.
+    pub(crate) async fn revalidate_binding(&self, peer_id: &PeerId) -> Result<()> {
+        let current_addr = self
+            .inner
+            .sessions
+            .get(peer_id)
+            .map(|entry| entry.value().peer_addr())
+            .ok_or(NodeError::SessionNotFound(*peer_id))?;
+
+        match self.migrate_session(peer_id, current_addr).await {
+            Ok(()) => {
+                tracing::info!("Binding to {} re-validated", current_addr);
+                return Ok(());
+            }
+            Err(e) => {
+                tracing::debug!(
+                    "Current address {} failed revalidation ({}), rediscovering peer",
+                    current_addr,
+                    e
+                );
+            }
+        }
+
+        let addrs = self.discover_peer(peer_id).await?;
+        let mut last_err = NodeError::PeerNotFound(*peer_id);
+        for addr in addrs.into_iter().filter(|a| *a != current_addr) {
+            match self.migrate_session(peer_id, addr).await {
+                Ok(()) => return Ok(()),
+                Err(e) => last_err = e,
+            }
+        }
+        Err(last_err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimator_stretches_on_success() {
+        let mut estimator = BindingEstimator::new();
+        let initial = estimator.interval();
+
+        estimator.record_success();
+        assert!(estimator.interval() > initial);
+        assert_eq!(estimator.proven_lifetime(), initial);
+    }
+
+    #[test]
+    fn test_estimator_capped_at_ceiling() {
+        let mut estimator = BindingEstimator::new();
+        for _ in 0..50 {
+            estimator.record_success();
+        }
+        assert!(estimator.interval() <= KEEPALIVE_CEILING);
+    }
+
+    #[test]
+    fn test_estimator_falls_back_on_failure() {
+        let mut estimator = BindingEstimator::new();
+        estimator.record_success();
+        let proven = estimator.proven_lifetime();
+
+        estimator.record_failure();
+        assert_eq!(estimator.interval(), proven);
+    }
+
+    #[test]
+    fn test_estimator_never_below_floor() {
+        let mut estimator = BindingEstimator::new();
+        for _ in 0..10 {
+            estimator.record_failure();
+        }
+        assert!(estimator.interval() >= KEEPALIVE_FLOOR);
+    }
+
+    #[test]
+    fn test_estimator_stays_below_observed_failure() {
+        let mut estimator = BindingEstimator::new();
+        estimator.record_success();
+        estimator.record_success();
+        let failed_at = estimator.interval();
+        estimator.record_failure();
+
+        // Regrowth must stay under the interval that broke the binding
+        for _ in 0..50 {
+            estimator.record_success();
+        }
+        assert!(estimator.interval() < failed_at);
+    }
+
+    #[tokio::test]
+    async fn test_revalidate_binding_session_not_found() {
+        let node = Node::new_random().await.unwrap();
+        let result = node.revalidate_binding(&[42u8; 32]).await;
+
+        assert!(matches!(result, Err(NodeError::SessionNotFound(_))));
+    }
+}
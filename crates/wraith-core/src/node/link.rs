@@ -0,0 +1,190 @@
+//! One-shot transfer links (`wraith://` claim URLs)
+//!
+//! A transfer link packages everything a stranger needs to fetch one shared
+//! file once: the sharer's address, the sharer's public key (so the session
+//! cannot be intercepted), and a single-use 256-bit claim token. The sharer
+//! generates the link with `wraith send <file> --link` and hands the URL to
+//! anyone over any channel; the claimant runs `wraith get wraith://...`,
+//! which connects, presents the token over an encrypted stream, and
+//! receives the file with the usual integrity verification.
+//!
+//! The URL embeds the sharer's key, so the link is end-to-end: whoever
+//! relays or logs the URL in transit can claim the file (it is a bearer
+//! token) but cannot tamper with the session or serve different content
+//! without failing the handshake. Tokens are compared in constant time and
+//! invalidated after the first successful claim.
+//!
+//! Format: `wraith://<host>:<port>/<peer-id-hex>/<token-hex>`
+
+use crate::node::error::{NodeError, Result};
+use crate::node::session::PeerId;
+use rand::RngCore;
+use std::net::SocketAddr;
+use wraith_crypto::constant_time::verify_32;
+
+/// URL scheme prefix for transfer links
+const SCHEME: &str = "wraith://";
+
+/// A one-shot claim link for a shared file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferLink {
+    /// Address the sharer is listening on
+    pub addr: SocketAddr,
+    /// The sharer's public key (X25519), pinning the session end-to-end
+    pub peer_id: PeerId,
+    /// Single-use claim token
+    token: [u8; 32],
+}
+
+impl TransferLink {
+    /// Create a link with a fresh random claim token
+    #[must_use]
+    pub fn new(addr: SocketAddr, peer_id: PeerId) -> Self {
+        let mut token = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut token);
+        Self {
+            addr,
+            peer_id,
+            token,
+        }
+    }
+
+    /// Reassemble a link from parts (used by [`WraithUri`] conversion)
+    ///
+    /// [`WraithUri`]: crate::node::uri::WraithUri
+    pub(crate) fn from_parts(addr: SocketAddr, peer_id: PeerId, token: [u8; 32]) -> Self {
+        Self {
+            addr,
+            peer_id,
+            token,
+        }
+    }
+
+    /// Parse a `wraith://` URL
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::Serialization`] if the URL does not have the
+    /// `wraith://<host>:<port>/<peer-id-hex>/<token-hex>` shape.
+    pub fn parse(url: &str) -> Result<Self> {
+        let url = url.trim();
+        let rest = url.strip_prefix(SCHEME).ok_or_else(|| {
+            NodeError::Serialization("transfer link must start with wraith://".into())
+        })?;
+
+        let mut parts = rest.splitn(3, '/');
+        let addr = parts
+            .next()
+            .and_then(|host| host.parse::<SocketAddr>().ok())
+            .ok_or_else(|| {
+                NodeError::Serialization("transfer link has an invalid host:port".into())
+            })?;
+        let peer_id = parts.next().and_then(decode_fixed::<32>).ok_or_else(|| {
+            NodeError::Serialization("transfer link has an invalid peer ID".into())
+        })?;
+        let token = parts
+            .next()
+            .and_then(decode_fixed::<32>)
+            .ok_or_else(|| NodeError::Serialization("transfer link has an invalid token".into()))?;
+
+        Ok(Self {
+            addr,
+            peer_id,
+            token,
+        })
+    }
+
+    /// The claim token carried by this link
+    #[must_use]
+    pub fn token(&self) -> &[u8; 32] {
+        &self.token
+    }
+
+    /// Check a presented token in constant time
+    #[must_use]
+    pub fn verify_token(&self, presented: &[u8; 32]) -> bool {
+        verify_32(&self.token, presented)
+    }
+}
+
+impl std::fmt::Display for TransferLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{SCHEME}{}/{}/{}",
+            self.addr,
+            hex::encode(self.peer_id),
+            hex::encode(self.token)
+        )
+    }
+}
+
+impl std::str::FromStr for TransferLink {
+    type Err = NodeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+/// Decode a fixed-length hex string, rejecting wrong sizes
+pub(crate) fn decode_fixed<const N: usize>(input: &str) -> Option<[u8; N]> {
+    let bytes = hex::decode(input).ok()?;
+    bytes.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_roundtrip() {
+        let link = TransferLink::new("192.0.2.7:40000".parse().unwrap(), [0xAB; 32]);
+        let url = link.to_string();
+        assert!(url.starts_with("wraith://192.0.2.7:40000/"));
+
+        let parsed = TransferLink::parse(&url).unwrap();
+        assert_eq!(parsed, link);
+    }
+
+    #[test]
+    fn test_link_roundtrip_ipv6() {
+        let link = TransferLink::new("[2001:db8::1]:40000".parse().unwrap(), [1u8; 32]);
+        let parsed = TransferLink::parse(&link.to_string()).unwrap();
+        assert_eq!(parsed, link);
+    }
+
+    #[test]
+    fn test_link_tokens_are_fresh() {
+        let addr: SocketAddr = "192.0.2.7:40000".parse().unwrap();
+        let a = TransferLink::new(addr, [0u8; 32]);
+        let b = TransferLink::new(addr, [0u8; 32]);
+        assert_ne!(a.token(), b.token());
+    }
+
+    #[test]
+    fn test_verify_token() {
+        let link = TransferLink::new("192.0.2.7:40000".parse().unwrap(), [0u8; 32]);
+        assert!(link.verify_token(link.token()));
+
+        let mut wrong = *link.token();
+        wrong[0] ^= 1;
+        assert!(!link.verify_token(&wrong));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_urls() {
+        assert!(TransferLink::parse("").is_err());
+        assert!(TransferLink::parse("https://example.com/a/b").is_err());
+        assert!(TransferLink::parse("wraith://nohost/aa/bb").is_err());
+        assert!(TransferLink::parse("wraith://192.0.2.7:40000").is_err());
+        // Peer ID and token must be exactly 32 bytes of hex
+        assert!(TransferLink::parse("wraith://192.0.2.7:40000/abcd/abcd").is_err());
+        let url = format!(
+            "wraith://192.0.2.7:40000/{}/{}",
+            hex::encode([0u8; 32]),
+            "zz".repeat(32)
+        );
+        assert!(TransferLink::parse(&url).is_err());
+    }
+}
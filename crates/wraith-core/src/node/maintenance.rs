@@ -0,0 +1,267 @@
+//! Control-plane deadline instrumentation.
+//!
+//! The node's background maintenance tasks (NAT keepalives, network
+//! change monitoring, cover traffic, and the discovery upkeep behind
+//! them) share the Tokio runtime with transfer workers. A saturating
+//! transfer can therefore starve them: the keepalive loop that should
+//! wake every second wakes late, the NAT binding expires, and the
+//! session stalls in a way that looks like a network fault rather than a
+//! scheduling one.
+//!
+//! This module makes that visible instead of silent. Each periodic loop
+//! drives its sleep through a [`MaintenanceTicker`], which records how far
+//! past the scheduled wake-up the runtime actually resumed the task. A
+//! tick whose lateness exceeds half its period counts as a **missed
+//! deadline**; per-task counters (ticks, misses, worst and most recent
+//! lateness) surface in [`NodeMetrics`](crate::node::metrics::NodeMetrics)
+//! as `wraith_maintenance_*` series, so a dashboard can alert on
+//! control-plane starvation long before sessions start dropping.
+//!
+//! [`Node::start`] additionally spawns a canary loop that does nothing but
+//! tick every [`CANARY_TICK`]. Its lateness is a pure measure of executor
+//! scheduling delay, independent of any real task's own workload, giving
+//! a baseline to compare the working loops against.
+//!
+//! [`Node::start`]: crate::node::Node::start
+
+use dashmap::DashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::time::Instant;
+
+use crate::node::Node;
+use crate::node::metrics::MaintenanceTaskMetrics;
+
+/// Period of the scheduler canary loop
+///
+/// Short enough to catch starvation episodes between the 1-second ticks
+/// of the working loops, long enough to stay negligible.
+pub const CANARY_TICK: Duration = Duration::from_millis(500);
+
+/// Per-task deadline counters, updated lock-free by the task's ticker
+#[derive(Debug, Default)]
+struct TaskStats {
+    /// Nominal period in microseconds (informational; variable-period
+    /// tasks record the period of their most recent tick)
+    period_us: AtomicU64,
+    /// Completed ticks
+    ticks: AtomicU64,
+    /// Ticks that woke more than half a period late
+    missed_deadlines: AtomicU64,
+    /// Worst observed wake-up lateness in microseconds
+    max_lateness_us: AtomicU64,
+    /// Lateness of the most recent tick in microseconds
+    last_lateness_us: AtomicU64,
+}
+
+/// Registry of control-plane tasks and their deadline counters
+///
+/// One per node, shared by every instrumented loop. Tasks register
+/// implicitly the first time they request a [`ticker`](Self::ticker).
+#[derive(Debug, Default)]
+pub(crate) struct MaintenanceMonitor {
+    /// Per-task counters, keyed by the task's registration name
+    tasks: DashMap<&'static str, Arc<TaskStats>>,
+}
+
+impl MaintenanceMonitor {
+    /// Create a ticker for a periodic task
+    ///
+    /// The first deadline is one `period` from now. Calling this again
+    /// with the same name resumes updating the existing counters rather
+    /// than resetting them.
+    pub(crate) fn ticker(&self, name: &'static str, period: Duration) -> MaintenanceTicker {
+        let stats = Arc::clone(&self.tasks.entry(name).or_default());
+        stats
+            .period_us
+            .store(period.as_micros() as u64, Ordering::Relaxed);
+        MaintenanceTicker {
+            name,
+            stats,
+            period,
+            armed: Instant::now(),
+        }
+    }
+
+    /// Snapshot the counters of every registered task, sorted by name
+    pub(crate) fn snapshot(&self) -> Vec<MaintenanceTaskMetrics> {
+        let mut tasks: Vec<_> = self
+            .tasks
+            .iter()
+            .map(|entry| {
+                let stats = entry.value();
+                MaintenanceTaskMetrics {
+                    task: entry.key(),
+                    period_us: stats.period_us.load(Ordering::Relaxed),
+                    ticks: stats.ticks.load(Ordering::Relaxed),
+                    missed_deadlines: stats.missed_deadlines.load(Ordering::Relaxed),
+                    max_lateness_us: stats.max_lateness_us.load(Ordering::Relaxed),
+                    last_lateness_us: stats.last_lateness_us.load(Ordering::Relaxed),
+                }
+            })
+            .collect();
+        tasks.sort_by_key(|t| t.task);
+        tasks
+    }
+}
+
+/// Deadline-tracking replacement for `tokio::time::sleep` in a periodic
+/// loop
+///
+/// Each [`tick`](Self::tick) sleeps until the scheduled deadline, then
+/// records how late the runtime actually woke the task. The next deadline
+/// is armed relative to the actual wake-up (matching the drift behaviour
+/// of a plain `sleep(period)` loop), so a slow tick body delays later
+/// ticks but never causes a catch-up burst.
+#[derive(Debug)]
+pub(crate) struct MaintenanceTicker {
+    /// Task name used in logs and metrics labels
+    name: &'static str,
+    /// Shared counters in the owning [`MaintenanceMonitor`]
+    stats: Arc<TaskStats>,
+    /// Nominal period, used by [`tick`](Self::tick)
+    period: Duration,
+    /// Completion time of the previous tick, which the next deadline is
+    /// relative to
+    armed: Instant,
+}
+
+impl MaintenanceTicker {
+    /// Sleep one nominal period and record wake-up lateness
+    pub(crate) async fn tick(&mut self) {
+        self.tick_after(self.period).await;
+    }
+
+    /// Sleep a caller-chosen period and record wake-up lateness
+    ///
+    /// For tasks with a randomized cadence (cover traffic): the deadline
+    /// slack scales with the period actually slept.
+    pub(crate) async fn tick_after(&mut self, period: Duration) {
+        let deadline = self.armed + period;
+        tokio::time::sleep_until(deadline).await;
+
+        let lateness = Instant::now().saturating_duration_since(deadline);
+        let lateness_us = lateness.as_micros() as u64;
+        self.stats
+            .period_us
+            .store(period.as_micros() as u64, Ordering::Relaxed);
+        self.stats.ticks.fetch_add(1, Ordering::Relaxed);
+        self.stats
+            .last_lateness_us
+            .store(lateness_us, Ordering::Relaxed);
+        self.stats
+            .max_lateness_us
+            .fetch_max(lateness_us, Ordering::Relaxed);
+
+        if lateness > period / 2 {
+            self.stats.missed_deadlines.fetch_add(1, Ordering::Relaxed);
+            tracing::warn!(
+                task = self.name,
+                lateness_ms = lateness.as_millis() as u64,
+                period_ms = period.as_millis() as u64,
+                "Control-plane task missed its deadline; runtime may be saturated"
+            );
+        }
+
+        self.armed = Instant::now();
+    }
+}
+
+impl Node {
+    /// Scheduler canary: ticks [`CANARY_TICK`] with an empty body
+    ///
+    /// Spawned by [`Node::start`]. Any lateness it records is pure
+    /// executor scheduling delay, so its `missed_deadlines` counter is
+    /// the cleanest signal that transfer workers are starving the
+    /// control plane.
+    pub(crate) async fn maintenance_canary_loop(&self) {
+        let mut ticker = self
+            .inner
+            .maintenance
+            .ticker("scheduler_canary", CANARY_TICK);
+
+        loop {
+            ticker.tick().await;
+            if !self.is_running() {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ticker_counts_ticks() {
+        let monitor = MaintenanceMonitor::default();
+        let mut ticker = monitor.ticker("test_task", Duration::from_millis(5));
+
+        for _ in 0..3 {
+            ticker.tick().await;
+        }
+
+        let snapshot = monitor.snapshot();
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].task, "test_task");
+        assert_eq!(snapshot[0].ticks, 3);
+        assert_eq!(snapshot[0].period_us, 5_000);
+    }
+
+    #[tokio::test]
+    async fn test_on_time_ticks_miss_no_deadlines() {
+        let monitor = MaintenanceMonitor::default();
+        let mut ticker = monitor.ticker("punctual", Duration::from_millis(50));
+
+        ticker.tick().await;
+
+        let snapshot = monitor.snapshot();
+        assert_eq!(snapshot[0].missed_deadlines, 0);
+        // An idle single-threaded runtime wakes well inside half a period
+        assert!(snapshot[0].max_lateness_us < 25_000);
+    }
+
+    #[tokio::test]
+    async fn test_blocked_tick_records_missed_deadline() {
+        let monitor = MaintenanceMonitor::default();
+        let mut ticker = monitor.ticker("starved", Duration::from_millis(10));
+
+        // Block the only worker thread past the deadline so the ticker
+        // cannot be woken on time - the same starvation a saturating
+        // transfer causes
+        std::thread::sleep(Duration::from_millis(40));
+        ticker.tick().await;
+
+        let snapshot = monitor.snapshot();
+        assert_eq!(snapshot[0].ticks, 1);
+        assert_eq!(snapshot[0].missed_deadlines, 1);
+        assert!(snapshot[0].max_lateness_us >= 5_000);
+        assert_eq!(snapshot[0].last_lateness_us, snapshot[0].max_lateness_us);
+    }
+
+    #[tokio::test]
+    async fn test_reregistering_resumes_counters() {
+        let monitor = MaintenanceMonitor::default();
+
+        let mut ticker = monitor.ticker("resumed", Duration::from_millis(5));
+        ticker.tick().await;
+        drop(ticker);
+
+        let mut ticker = monitor.ticker("resumed", Duration::from_millis(5));
+        ticker.tick().await;
+
+        assert_eq!(monitor.snapshot()[0].ticks, 2);
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_sorted_by_task_name() {
+        let monitor = MaintenanceMonitor::default();
+        monitor.ticker("zebra", Duration::from_secs(1));
+        monitor.ticker("aardvark", Duration::from_secs(1));
+
+        let names: Vec<_> = monitor.snapshot().iter().map(|t| t.task).collect();
+        assert_eq!(names, vec!["aardvark", "zebra"]);
+    }
+}
@@ -0,0 +1,527 @@
+//! Node metrics registry
+//!
+//! A point-in-time snapshot of node operational metrics, collected via
+//! [`Node::metrics_snapshot`](crate::node::Node::metrics_snapshot) and
+//! renderable either as JSON (it is `Serialize`) or as Prometheus text
+//! exposition format for scraping. The daemon's optional metrics HTTP
+//! endpoint and `wraith metrics --json` are both backed by this snapshot,
+//! so dashboards and the CLI always agree.
+
+use serde::Serialize;
+
+/// Per-session metrics
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionMetrics {
+    /// Peer node ID (hex)
+    pub peer_id: String,
+    /// Smoothed round-trip time in microseconds, if measured
+    pub rtt_us: Option<u64>,
+    /// Bytes sent on this session
+    pub bytes_sent: u64,
+    /// Bytes received on this session
+    pub bytes_received: u64,
+    /// Packets sent on this session
+    pub packets_sent: u64,
+    /// Packets received on this session
+    pub packets_received: u64,
+    /// Observed packet loss rate (0.0 to 1.0)
+    pub loss_rate: f64,
+}
+
+/// Per-transfer metrics
+#[derive(Debug, Clone, Serialize)]
+pub struct TransferMetrics {
+    /// Transfer ID (hex)
+    pub transfer_id: String,
+    /// Bytes transferred so far
+    pub bytes_sent: u64,
+    /// Total bytes in the transfer
+    pub bytes_total: u64,
+    /// Current throughput in bytes per second
+    pub throughput_bytes_per_sec: f64,
+}
+
+/// Node-level frame counters relevant for dashboards
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FrameMetrics {
+    /// Total frames sent across all types
+    pub sent: u64,
+    /// Total frames received across all types
+    pub received: u64,
+    /// Total frames dropped (parse/handler failure)
+    pub dropped: u64,
+    /// Total frames retransmitted
+    pub retransmitted: u64,
+    /// REKEY frames sent (ratchet steps initiated)
+    pub rekeys_sent: u64,
+    /// REKEY frames received (ratchet steps accepted)
+    pub rekeys_received: u64,
+}
+
+/// Relay routing metrics
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RouteMetrics {
+    /// Active routes in the routing table
+    pub active_routes: usize,
+    /// Successful route lookups
+    pub successful_lookups: u64,
+    /// Failed route lookups
+    pub failed_lookups: u64,
+}
+
+/// Deadline counters for one control-plane maintenance task
+///
+/// Collected by the ticker instrumentation in
+/// [`maintenance`](crate::node::maintenance); a missed deadline means the
+/// runtime woke the task more than half a period late.
+#[derive(Debug, Clone, Serialize)]
+pub struct MaintenanceTaskMetrics {
+    /// Task name (e.g. `nat_keepalive`, `scheduler_canary`)
+    pub task: &'static str,
+    /// Nominal tick period in microseconds
+    pub period_us: u64,
+    /// Completed ticks
+    pub ticks: u64,
+    /// Ticks that woke more than half a period late
+    pub missed_deadlines: u64,
+    /// Worst observed wake-up lateness in microseconds
+    pub max_lateness_us: u64,
+    /// Wake-up lateness of the most recent tick in microseconds
+    pub last_lateness_us: u64,
+}
+
+/// A point-in-time snapshot of all node metrics
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeMetrics {
+    /// Crate version of the running node
+    pub version: &'static str,
+    /// Whether the node is started
+    pub running: bool,
+    /// Per-session metrics for all active sessions
+    pub sessions: Vec<SessionMetrics>,
+    /// Per-transfer metrics for all active transfers
+    pub transfers: Vec<TransferMetrics>,
+    /// Aggregate frame counters across all sessions
+    pub frames: FrameMetrics,
+    /// Relay routing counters
+    pub routing: RouteMetrics,
+    /// Deadline counters per control-plane maintenance task
+    pub maintenance: Vec<MaintenanceTaskMetrics>,
+    /// Peers in the DHT routing table (None before discovery starts)
+    pub dht_routing_table_peers: Option<usize>,
+}
+
+impl NodeMetrics {
+    /// Render the snapshot in Prometheus text exposition format
+    ///
+    /// Metric names follow Prometheus conventions: base units (seconds,
+    /// bytes), `_total` suffix on counters, labels for per-session and
+    /// per-transfer series.
+    #[must_use]
+    pub fn to_prometheus(&self) -> String {
+        let mut out = String::with_capacity(2048);
+
+        scalar(
+            &mut out,
+            "wraith_node_running",
+            "gauge",
+            "Whether the node is started (1) or stopped (0)",
+            u64::from(self.running),
+        );
+        scalar(
+            &mut out,
+            "wraith_sessions_active",
+            "gauge",
+            "Number of active sessions",
+            self.sessions.len() as u64,
+        );
+        scalar(
+            &mut out,
+            "wraith_transfers_active",
+            "gauge",
+            "Number of active transfers",
+            self.transfers.len() as u64,
+        );
+
+        if !self.sessions.is_empty() {
+            header(
+                &mut out,
+                "wraith_session_rtt_seconds",
+                "gauge",
+                "Smoothed round-trip time per session",
+            );
+            for s in &self.sessions {
+                if let Some(rtt_us) = s.rtt_us {
+                    line(
+                        &mut out,
+                        "wraith_session_rtt_seconds",
+                        &[("peer", &s.peer_id)],
+                        &format!("{}", rtt_us as f64 / 1_000_000.0),
+                    );
+                }
+            }
+            header(
+                &mut out,
+                "wraith_session_bytes_total",
+                "counter",
+                "Bytes transferred per session by direction",
+            );
+            header(
+                &mut out,
+                "wraith_session_packets_total",
+                "counter",
+                "Packets transferred per session by direction",
+            );
+            header(
+                &mut out,
+                "wraith_session_loss_ratio",
+                "gauge",
+                "Observed packet loss ratio per session",
+            );
+            for s in &self.sessions {
+                let peer: &[(&str, &str)] = &[("peer", &s.peer_id)];
+                line(
+                    &mut out,
+                    "wraith_session_bytes_total",
+                    &[("peer", &s.peer_id), ("direction", "sent")],
+                    &s.bytes_sent.to_string(),
+                );
+                line(
+                    &mut out,
+                    "wraith_session_bytes_total",
+                    &[("peer", &s.peer_id), ("direction", "received")],
+                    &s.bytes_received.to_string(),
+                );
+                line(
+                    &mut out,
+                    "wraith_session_packets_total",
+                    &[("peer", &s.peer_id), ("direction", "sent")],
+                    &s.packets_sent.to_string(),
+                );
+                line(
+                    &mut out,
+                    "wraith_session_packets_total",
+                    &[("peer", &s.peer_id), ("direction", "received")],
+                    &s.packets_received.to_string(),
+                );
+                line(
+                    &mut out,
+                    "wraith_session_loss_ratio",
+                    peer,
+                    &format!("{}", s.loss_rate),
+                );
+            }
+        }
+
+        if !self.transfers.is_empty() {
+            header(
+                &mut out,
+                "wraith_transfer_bytes_sent",
+                "gauge",
+                "Bytes transferred so far per transfer",
+            );
+            header(
+                &mut out,
+                "wraith_transfer_bytes_total",
+                "gauge",
+                "Total size per transfer",
+            );
+            header(
+                &mut out,
+                "wraith_transfer_throughput_bytes_per_second",
+                "gauge",
+                "Current throughput per transfer",
+            );
+            for t in &self.transfers {
+                let id: &[(&str, &str)] = &[("transfer", &t.transfer_id)];
+                line(
+                    &mut out,
+                    "wraith_transfer_bytes_sent",
+                    id,
+                    &t.bytes_sent.to_string(),
+                );
+                line(
+                    &mut out,
+                    "wraith_transfer_bytes_total",
+                    id,
+                    &t.bytes_total.to_string(),
+                );
+                line(
+                    &mut out,
+                    "wraith_transfer_throughput_bytes_per_second",
+                    id,
+                    &format!("{}", t.throughput_bytes_per_sec),
+                );
+            }
+        }
+
+        header(
+            &mut out,
+            "wraith_frames_total",
+            "counter",
+            "Frames across all sessions by disposition",
+        );
+        for (disposition, value) in [
+            ("sent", self.frames.sent),
+            ("received", self.frames.received),
+            ("dropped", self.frames.dropped),
+            ("retransmitted", self.frames.retransmitted),
+        ] {
+            line(
+                &mut out,
+                "wraith_frames_total",
+                &[("disposition", disposition)],
+                &value.to_string(),
+            );
+        }
+        header(
+            &mut out,
+            "wraith_rekeys_total",
+            "counter",
+            "Key ratchet steps by direction",
+        );
+        line(
+            &mut out,
+            "wraith_rekeys_total",
+            &[("direction", "sent")],
+            &self.frames.rekeys_sent.to_string(),
+        );
+        line(
+            &mut out,
+            "wraith_rekeys_total",
+            &[("direction", "received")],
+            &self.frames.rekeys_received.to_string(),
+        );
+
+        scalar(
+            &mut out,
+            "wraith_relay_routes_active",
+            "gauge",
+            "Active relay routes",
+            self.routing.active_routes as u64,
+        );
+        header(
+            &mut out,
+            "wraith_relay_route_lookups_total",
+            "counter",
+            "Relay route lookups by result",
+        );
+        line(
+            &mut out,
+            "wraith_relay_route_lookups_total",
+            &[("result", "success")],
+            &self.routing.successful_lookups.to_string(),
+        );
+        line(
+            &mut out,
+            "wraith_relay_route_lookups_total",
+            &[("result", "failure")],
+            &self.routing.failed_lookups.to_string(),
+        );
+
+        if !self.maintenance.is_empty() {
+            header(
+                &mut out,
+                "wraith_maintenance_ticks_total",
+                "counter",
+                "Completed ticks per control-plane maintenance task",
+            );
+            header(
+                &mut out,
+                "wraith_maintenance_missed_deadlines_total",
+                "counter",
+                "Ticks that woke more than half a period late, per task",
+            );
+            header(
+                &mut out,
+                "wraith_maintenance_max_lateness_seconds",
+                "gauge",
+                "Worst observed wake-up lateness per task",
+            );
+            for t in &self.maintenance {
+                let task: &[(&str, &str)] = &[("task", t.task)];
+                line(
+                    &mut out,
+                    "wraith_maintenance_ticks_total",
+                    task,
+                    &t.ticks.to_string(),
+                );
+                line(
+                    &mut out,
+                    "wraith_maintenance_missed_deadlines_total",
+                    task,
+                    &t.missed_deadlines.to_string(),
+                );
+                line(
+                    &mut out,
+                    "wraith_maintenance_max_lateness_seconds",
+                    task,
+                    &format!("{}", t.max_lateness_us as f64 / 1_000_000.0),
+                );
+            }
+        }
+
+        if let Some(peers) = self.dht_routing_table_peers {
+            scalar(
+                &mut out,
+                "wraith_dht_routing_table_peers",
+                "gauge",
+                "Peers currently in the DHT routing table",
+                peers as u64,
+            );
+        }
+
+        out
+    }
+}
+
+/// Append HELP and TYPE comment lines for a metric
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {kind}\n"));
+}
+
+/// Append one sample line with labels
+fn line(out: &mut String, name: &str, labels: &[(&str, &str)], value: &str) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        out.push('{');
+        for (i, (key, label)) in labels.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push_str(&format!("{key}=\"{label}\""));
+        }
+        out.push('}');
+    }
+    out.push_str(&format!(" {value}\n"));
+}
+
+/// Append a complete unlabelled metric (header plus single sample)
+fn scalar(out: &mut String, name: &str, kind: &str, help: &str, value: u64) {
+    header(out, name, kind, help);
+    line(out, name, &[], &value.to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> NodeMetrics {
+        NodeMetrics {
+            version: "1.6.0",
+            running: true,
+            sessions: vec![SessionMetrics {
+                peer_id: "ab".repeat(32),
+                rtt_us: Some(1_500),
+                bytes_sent: 1000,
+                bytes_received: 2000,
+                packets_sent: 10,
+                packets_received: 20,
+                loss_rate: 0.01,
+            }],
+            transfers: vec![TransferMetrics {
+                transfer_id: "cd".repeat(32),
+                bytes_sent: 512,
+                bytes_total: 1024,
+                throughput_bytes_per_sec: 256.0,
+            }],
+            frames: FrameMetrics {
+                sent: 100,
+                received: 90,
+                dropped: 1,
+                retransmitted: 2,
+                rekeys_sent: 3,
+                rekeys_received: 3,
+            },
+            routing: RouteMetrics {
+                active_routes: 2,
+                successful_lookups: 50,
+                failed_lookups: 5,
+            },
+            maintenance: vec![MaintenanceTaskMetrics {
+                task: "nat_keepalive",
+                period_us: 1_000_000,
+                ticks: 600,
+                missed_deadlines: 2,
+                max_lateness_us: 750_000,
+                last_lateness_us: 1_200,
+            }],
+            dht_routing_table_peers: Some(17),
+        }
+    }
+
+    #[test]
+    fn test_prometheus_scalar_gauges() {
+        let text = sample().to_prometheus();
+        assert!(text.contains("# TYPE wraith_node_running gauge"));
+        assert!(text.contains("wraith_node_running 1\n"));
+        assert!(text.contains("wraith_sessions_active 1\n"));
+        assert!(text.contains("wraith_transfers_active 1\n"));
+        assert!(text.contains("wraith_dht_routing_table_peers 17\n"));
+    }
+
+    #[test]
+    fn test_prometheus_session_series() {
+        let text = sample().to_prometheus();
+        let peer = "ab".repeat(32);
+        assert!(text.contains(&format!(
+            "wraith_session_rtt_seconds{{peer=\"{peer}\"}} 0.0015\n"
+        )));
+        assert!(text.contains(&format!(
+            "wraith_session_bytes_total{{peer=\"{peer}\",direction=\"sent\"}} 1000\n"
+        )));
+        assert!(text.contains(&format!(
+            "wraith_session_packets_total{{peer=\"{peer}\",direction=\"received\"}} 20\n"
+        )));
+    }
+
+    #[test]
+    fn test_prometheus_transfer_and_counter_series() {
+        let text = sample().to_prometheus();
+        let id = "cd".repeat(32);
+        assert!(text.contains(&format!(
+            "wraith_transfer_bytes_sent{{transfer=\"{id}\"}} 512\n"
+        )));
+        assert!(text.contains("wraith_frames_total{disposition=\"retransmitted\"} 2\n"));
+        assert!(text.contains("wraith_rekeys_total{direction=\"sent\"} 3\n"));
+        assert!(text.contains("wraith_relay_route_lookups_total{result=\"failure\"} 5\n"));
+    }
+
+    #[test]
+    fn test_prometheus_maintenance_series() {
+        let text = sample().to_prometheus();
+        assert!(text.contains("wraith_maintenance_ticks_total{task=\"nat_keepalive\"} 600\n"));
+        assert!(
+            text.contains("wraith_maintenance_missed_deadlines_total{task=\"nat_keepalive\"} 2\n")
+        );
+        assert!(
+            text.contains("wraith_maintenance_max_lateness_seconds{task=\"nat_keepalive\"} 0.75\n")
+        );
+    }
+
+    #[test]
+    fn test_prometheus_omits_empty_series() {
+        let metrics = NodeMetrics {
+            sessions: Vec::new(),
+            transfers: Vec::new(),
+            maintenance: Vec::new(),
+            dht_routing_table_peers: None,
+            ..sample()
+        };
+        let text = metrics.to_prometheus();
+        assert!(!text.contains("wraith_session_rtt_seconds{"));
+        assert!(!text.contains("wraith_transfer_bytes_sent{"));
+        assert!(!text.contains("wraith_maintenance_ticks_total{"));
+        assert!(!text.contains("wraith_dht_routing_table_peers"));
+        assert!(text.contains("wraith_sessions_active 0\n"));
+    }
+
+    #[test]
+    fn test_json_serialization() {
+        let json = serde_json::to_string(&sample()).unwrap();
+        assert!(json.contains("\"running\":true"));
+        assert!(json.contains("\"rekeys_sent\":3"));
+        assert!(json.contains("\"missed_deadlines\":2"));
+        assert!(json.contains("\"dht_routing_table_peers\":17"));
+    }
+}
@@ -56,60 +56,99 @@
 // The buffer pool is now defined in wraith-transport where it's primarily used
 pub use wraith_transport::BufferPool;
 
+pub mod authorizer;
+pub mod bandwidth;
+#[cfg(feature = "chaos")]
+pub mod chaos;
 pub mod circuit_breaker;
 pub mod config;
 pub mod connection;
+pub mod delegation;
 pub mod discovery;
 pub mod error;
+pub mod estimate;
+pub mod events;
 pub mod file_transfer;
 pub mod health;
 pub mod identity;
 pub mod ip_reputation;
+pub mod keepalive;
+pub mod link;
+pub mod maintenance;
+pub mod metrics;
 pub mod multi_peer;
+pub mod multicast;
 pub mod nat;
+pub mod netmon;
 #[allow(clippy::module_inception)]
 pub mod node;
 pub mod obfuscation;
+pub mod observed;
+pub mod offer;
 pub mod packet_handler;
 pub mod padding_strategy;
+pub mod path_estimates;
+pub mod peer_history;
 pub mod progress;
 pub mod rate_limiter;
+pub mod reload;
 pub mod resume;
 pub mod routing;
 pub mod security_monitor;
 pub mod session;
 pub mod session_manager;
+pub mod stream_api;
+pub mod swarm;
+pub mod telemetry;
 pub mod transfer;
 pub mod transfer_manager;
+pub mod transport_slot;
+pub mod trust;
+pub mod uri;
+pub mod zero_rtt;
 
 // BufferPool is re-exported from wraith_transport at the top of this module
+pub use bandwidth::{BandwidthLimiter, BandwidthLimits, parse_rate};
 pub use circuit_breaker::{
     CircuitBreaker, CircuitBreakerConfig, CircuitMetrics, CircuitState, RetryConfig,
 };
 pub use config::{
     CoverTrafficConfig, CoverTrafficDistribution, DiscoveryConfig, LogLevel, LoggingConfig,
     MimicryMode, NodeConfig, ObfuscationConfig, PaddingMode, TimingMode, TransferConfig,
-    TransportConfig,
+    TransportConfig, TrustConfig,
 };
-pub use connection::{HealthMetrics, HealthStatus};
+pub use connection::{CandidateChecklist, CandidatePair, HealthMetrics, HealthStatus, PairState};
 pub use discovery::{NatType, NodeCapabilities, PeerAnnouncement, PeerInfo};
 pub use error::{NodeError, Result};
+pub use estimate::{BandwidthEstimate, EstimateSource};
+pub use events::NodeEvent;
 pub use file_transfer::{FileMetadata, FileTransferContext};
 pub use health::{HealthAction, HealthConfig, HealthMonitor};
 pub use identity::{Identity, TransferId};
 pub use ip_reputation::{
     IpReputationConfig, IpReputationMetrics, IpReputationSystem, ReputationStatus,
 };
+pub use keepalive::BindingEstimator;
+pub use link::TransferLink;
+pub use metrics::{
+    FrameMetrics, MaintenanceTaskMetrics, NodeMetrics, RouteMetrics, SessionMetrics,
+    TransferMetrics,
+};
 pub use multi_peer::{ChunkAssignmentStrategy, MultiPeerCoordinator, PeerPerformance};
+pub use multicast::{DistributionReport, MulticastConfig, multicast_session_id};
 pub use nat::{CandidateType, IceCandidate};
 pub use node::Node;
 pub use obfuscation::{ObfuscationStats, Protocol};
+pub use offer::{OfferDecision, TransferOffer};
 pub use padding_strategy::{
     ConstantRatePadding, NonePadding, PaddingStrategy, PowerOfTwoPadding, SizeClassesPadding,
     StatisticalPadding, create_padding_strategy,
 };
+pub use path_estimates::{PATH_ESTIMATE_FRESHNESS, PathEstimateCache};
+pub use peer_history::{PeerHistoryRecord, PeerHistoryStore};
 pub use progress::{TransferProgress, TransferStatus};
 pub use rate_limiter::{RateLimitConfig, RateLimitMetrics, RateLimiter};
+pub use reload::ConfigReloadReport;
 pub use resume::{ResumeManager, ResumeState};
 pub use routing::{RoutingStats, RoutingTable, extract_connection_id};
 pub use security_monitor::{
@@ -118,4 +157,12 @@ pub use security_monitor::{
 };
 pub use session::PeerConnection;
 pub use session_manager::SessionManager;
-pub use transfer_manager::TransferManager;
+pub use stream_api::WraithStream;
+pub use swarm::{ChokeUpdate, HaveBitmap, SwarmMessage, SwarmSeeder};
+pub use telemetry::{TelemetryCollector, TelemetryConfig, TelemetryReport};
+pub use transfer_manager::{FanOutReport, TransferManager};
+pub use transport_slot::{TransportSlot, TransportSlotStats};
+pub use trust::{TrustDecision, TrustEntry, TrustStore};
+pub use uri::WraithUri;
+pub use wraith_crypto::pake::{PakeRole, Spake2, Spake2Key, TransferCode};
+pub use wraith_crypto::sas::Sas;
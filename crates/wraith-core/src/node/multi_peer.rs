@@ -9,6 +9,13 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
+use crate::node::peer_history::{PeerHistoryRecord, PeerHistoryStore};
+use crate::node::swarm::HaveBitmap;
+
+/// Maximum number of peers a chunk is requested from in endgame mode
+/// (one primary assignment plus `ENDGAME_FANOUT - 1` duplicates)
+const ENDGAME_FANOUT: usize = 3;
+
 /// Chunk assignment strategy
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ChunkAssignmentStrategy {
@@ -82,6 +89,24 @@ impl PeerPerformance {
         }
     }
 
+    /// Create a peer performance tracker seeded from persisted history
+    ///
+    /// Historical RTT and throughput replace the blind defaults, and the
+    /// initial chunk parallelism follows the peer's demonstrated capacity,
+    /// so the first assignment round already favours known-fast sources.
+    pub fn with_history(
+        peer_id: [u8; 32],
+        address: SocketAddr,
+        history: &PeerHistoryRecord,
+    ) -> Self {
+        let mut peer = Self::new(peer_id, address);
+        peer.rtt_us = history.rtt_us;
+        peer.throughput_bps = history.throughput_bps;
+        peer.max_concurrent = history.suggested_concurrency();
+        peer.update_cached_score();
+        peer
+    }
+
     /// Calculate failure rate
     pub fn failure_rate(&self) -> f64 {
         let total = self.chunks_succeeded + self.chunks_failed;
@@ -197,6 +222,16 @@ impl PeerPerformance {
     pub fn record_assignment(&mut self) {
         self.in_flight += 1;
     }
+
+    /// Cancel an in-flight assignment without counting it as a failure
+    ///
+    /// Used when a duplicate endgame request is made redundant by another
+    /// peer delivering the chunk first.
+    pub fn cancel_assignment(&mut self) {
+        if self.in_flight > 0 {
+            self.in_flight -= 1;
+        }
+    }
 }
 
 /// Multi-peer chunk coordinator
@@ -212,6 +247,19 @@ pub struct MultiPeerCoordinator {
 
     /// Round-robin counter for RoundRobin strategy
     round_robin_counter: Arc<RwLock<usize>>,
+
+    /// Persistent peer history used to seed and record measurements
+    history: Option<Arc<PeerHistoryStore>>,
+
+    /// Duplicate endgame assignments (chunk_index -> extra peer_ids)
+    duplicates: Arc<RwLock<HashMap<usize, Vec<[u8; 32]>>>>,
+
+    /// Verified chunks announced per peer via swarm HAVE messages
+    ///
+    /// Peers without a recorded bitmap are assumed to hold the whole file
+    /// (the original sender); swarming receivers are only assigned chunks
+    /// they have announced.
+    peer_haves: Arc<RwLock<HashMap<[u8; 32], HaveBitmap>>>,
 }
 
 impl MultiPeerCoordinator {
@@ -222,40 +270,131 @@ impl MultiPeerCoordinator {
             peers: Arc::new(RwLock::new(HashMap::new())),
             assignments: Arc::new(RwLock::new(HashMap::new())),
             round_robin_counter: Arc::new(RwLock::new(0)),
+            history: None,
+            duplicates: Arc::new(RwLock::new(HashMap::new())),
+            peer_haves: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Create a coordinator backed by a persistent peer history store
+    ///
+    /// Peers added to the coordinator are seeded from their historical
+    /// RTT/throughput records, and measurements taken during the transfer
+    /// flow back into the store (persist them with
+    /// [`PeerHistoryStore::save`]).
+    pub fn with_history(strategy: ChunkAssignmentStrategy, history: Arc<PeerHistoryStore>) -> Self {
+        let mut coordinator = Self::new(strategy);
+        coordinator.history = Some(history);
+        coordinator
+    }
+
     /// Add a peer to the coordinator
+    ///
+    /// When a history store is attached and holds a record for the peer, the
+    /// performance tracker is seeded from it instead of the defaults.
     pub async fn add_peer(&self, peer_id: [u8; 32], address: SocketAddr) {
+        let performance = match &self.history {
+            Some(history) => match history.get(&peer_id).await {
+                Some(record) => PeerPerformance::with_history(peer_id, address, &record),
+                None => PeerPerformance::new(peer_id, address),
+            },
+            None => PeerPerformance::new(peer_id, address),
+        };
+
         let mut peers = self.peers.write().await;
-        peers.insert(peer_id, PeerPerformance::new(peer_id, address));
+        peers.insert(peer_id, performance);
     }
 
     /// Remove a peer from the coordinator
     pub async fn remove_peer(&self, peer_id: &[u8; 32]) {
-        let mut peers = self.peers.write().await;
-        peers.remove(peer_id);
+        {
+            let mut peers = self.peers.write().await;
+            peers.remove(peer_id);
+        }
 
         // Reassign chunks from removed peer
-        let mut assignments = self.assignments.write().await;
-        assignments.retain(|_, assigned_peer| assigned_peer != peer_id);
+        {
+            let mut assignments = self.assignments.write().await;
+            assignments.retain(|_, assigned_peer| assigned_peer != peer_id);
+        }
+
+        // Drop any duplicate endgame requests pointed at the removed peer
+        {
+            let mut duplicates = self.duplicates.write().await;
+            for extras in duplicates.values_mut() {
+                extras.retain(|extra| extra != peer_id);
+            }
+            duplicates.retain(|_, extras| !extras.is_empty());
+        }
+
+        let mut peer_haves = self.peer_haves.write().await;
+        peer_haves.remove(peer_id);
+    }
+
+    /// Record a peer's full have-bitmap ([`SwarmMessage::HaveBitmap`])
+    ///
+    /// From this point the peer is only assigned chunks it has announced;
+    /// peers that never announce a bitmap are treated as full seeds.
+    ///
+    /// [`SwarmMessage::HaveBitmap`]: crate::node::swarm::SwarmMessage::HaveBitmap
+    pub async fn record_peer_bitmap(&self, peer_id: [u8; 32], bitmap: HaveBitmap) {
+        let mut peer_haves = self.peer_haves.write().await;
+        peer_haves.insert(peer_id, bitmap);
+    }
+
+    /// Record one newly announced chunk ([`SwarmMessage::Have`])
+    ///
+    /// Ignored for peers that never announced a bitmap, since those are
+    /// already assumed complete.
+    ///
+    /// [`SwarmMessage::Have`]: crate::node::swarm::SwarmMessage::Have
+    pub async fn record_peer_have(&self, peer_id: &[u8; 32], chunk_index: u64) {
+        let mut peer_haves = self.peer_haves.write().await;
+        if let Some(bitmap) = peer_haves.get_mut(peer_id) {
+            bitmap.mark(chunk_index);
+        }
+    }
+
+    /// Number of known peers that can currently supply a chunk
+    pub async fn chunk_availability(&self, chunk_index: usize) -> usize {
+        let peers = self.peers.read().await;
+        let peer_haves = self.peer_haves.read().await;
+        peers
+            .keys()
+            .filter(|peer_id| {
+                peer_haves
+                    .get(*peer_id)
+                    .is_none_or(|bitmap| bitmap.has(chunk_index as u64))
+            })
+            .count()
     }
 
     /// Assign a chunk to a peer using the configured strategy
+    ///
+    /// Peers with a recorded have-bitmap are only considered if they have
+    /// announced this chunk.
     pub async fn assign_chunk(&self, chunk_index: usize) -> Option<[u8; 32]> {
         let peers = self.peers.read().await;
         if peers.is_empty() {
             return None;
         }
+        let haves = self.peer_haves.read().await;
 
         let peer_id = match self.strategy {
-            ChunkAssignmentStrategy::RoundRobin => self.assign_round_robin(&peers).await,
-            ChunkAssignmentStrategy::FastestFirst => self.assign_fastest_first(&peers),
-            ChunkAssignmentStrategy::Geographic => self.assign_geographic(&peers),
-            ChunkAssignmentStrategy::Adaptive => self.assign_adaptive(&peers),
+            ChunkAssignmentStrategy::RoundRobin => {
+                self.assign_round_robin(&peers, &haves, chunk_index).await
+            }
+            ChunkAssignmentStrategy::FastestFirst => {
+                Self::assign_fastest_first(&peers, &haves, chunk_index)
+            }
+            ChunkAssignmentStrategy::Geographic => {
+                Self::assign_geographic(&peers, &haves, chunk_index)
+            }
+            ChunkAssignmentStrategy::Adaptive => Self::assign_adaptive(&peers, &haves, chunk_index),
         }?;
 
         // Record assignment
+        drop(haves);
         drop(peers);
         let mut peers = self.peers.write().await;
         if let Some(peer) = peers.get_mut(&peer_id) {
@@ -268,13 +407,31 @@ impl MultiPeerCoordinator {
         Some(peer_id)
     }
 
+    /// Whether a peer can take this chunk: spare capacity, and the chunk
+    /// announced in its have-bitmap (peers without one are full seeds)
+    fn peer_eligible(
+        peer: &PeerPerformance,
+        haves: &HashMap<[u8; 32], HaveBitmap>,
+        chunk_index: usize,
+    ) -> bool {
+        peer.has_capacity()
+            && haves
+                .get(&peer.peer_id)
+                .is_none_or(|bitmap| bitmap.has(chunk_index as u64))
+    }
+
     /// Round-robin assignment
     async fn assign_round_robin(
         &self,
         peers: &HashMap<[u8; 32], PeerPerformance>,
+        haves: &HashMap<[u8; 32], HaveBitmap>,
+        chunk_index: usize,
     ) -> Option<[u8; 32]> {
         // Count available peers without allocating a Vec
-        let available_count = peers.values().filter(|p| p.has_capacity()).count();
+        let available_count = peers
+            .values()
+            .filter(|p| Self::peer_eligible(p, haves, chunk_index))
+            .count();
         if available_count == 0 {
             return None;
         }
@@ -286,34 +443,46 @@ impl MultiPeerCoordinator {
         // Use nth() to select the peer at the calculated index
         peers
             .iter()
-            .filter(|(_, p)| p.has_capacity())
+            .filter(|(_, p)| Self::peer_eligible(p, haves, chunk_index))
             .nth(index)
             .map(|(id, _)| *id)
     }
 
     /// Fastest-first assignment (highest throughput)
-    fn assign_fastest_first(&self, peers: &HashMap<[u8; 32], PeerPerformance>) -> Option<[u8; 32]> {
+    fn assign_fastest_first(
+        peers: &HashMap<[u8; 32], PeerPerformance>,
+        haves: &HashMap<[u8; 32], HaveBitmap>,
+        chunk_index: usize,
+    ) -> Option<[u8; 32]> {
         peers
             .iter()
-            .filter(|(_, p)| p.has_capacity())
+            .filter(|(_, p)| Self::peer_eligible(p, haves, chunk_index))
             .max_by_key(|(_, p)| p.throughput_bps)
             .map(|(id, _)| *id)
     }
 
     /// Geographic assignment (lowest RTT)
-    fn assign_geographic(&self, peers: &HashMap<[u8; 32], PeerPerformance>) -> Option<[u8; 32]> {
+    fn assign_geographic(
+        peers: &HashMap<[u8; 32], PeerPerformance>,
+        haves: &HashMap<[u8; 32], HaveBitmap>,
+        chunk_index: usize,
+    ) -> Option<[u8; 32]> {
         peers
             .iter()
-            .filter(|(_, p)| p.has_capacity())
+            .filter(|(_, p)| Self::peer_eligible(p, haves, chunk_index))
             .min_by_key(|(_, p)| p.rtt_us)
             .map(|(id, _)| *id)
     }
 
     /// Adaptive assignment (highest performance score)
-    fn assign_adaptive(&self, peers: &HashMap<[u8; 32], PeerPerformance>) -> Option<[u8; 32]> {
+    fn assign_adaptive(
+        peers: &HashMap<[u8; 32], PeerPerformance>,
+        haves: &HashMap<[u8; 32], HaveBitmap>,
+        chunk_index: usize,
+    ) -> Option<[u8; 32]> {
         peers
             .iter()
-            .filter(|(_, p)| p.has_capacity())
+            .filter(|(_, p)| Self::peer_eligible(p, haves, chunk_index))
             .max_by(|(_, a), (_, b)| {
                 a.performance_score()
                     .partial_cmp(&b.performance_score())
@@ -322,6 +491,113 @@ impl MultiPeerCoordinator {
             .map(|(id, _)| *id)
     }
 
+    /// Check whether the download is in its endgame phase
+    ///
+    /// Endgame begins once the number of missing chunks drops to the number
+    /// of participating peers: from that point the completion time is
+    /// dominated by the slowest peer holding a final chunk, so the remaining
+    /// chunks are worth requesting redundantly.
+    pub async fn in_endgame(&self, remaining_chunks: usize) -> bool {
+        let peers = self.peers.read().await;
+        remaining_chunks > 0 && peers.len() > 1 && remaining_chunks <= peers.len()
+    }
+
+    /// Assign duplicate endgame requests for a chunk
+    ///
+    /// Picks up to [`ENDGAME_FANOUT`] − 1 additional peers (best performance
+    /// score first) that are not already serving this chunk and have spare
+    /// capacity. Returns the extra peers; the caller requests the chunk from
+    /// each of them in parallel with the primary assignment and cancels the
+    /// losers via [`complete_chunk`](Self::complete_chunk) on first arrival.
+    pub async fn endgame_assign(&self, chunk_index: usize) -> Vec<[u8; 32]> {
+        let primary = self.assignments.read().await.get(&chunk_index).copied();
+
+        let mut duplicates = self.duplicates.write().await;
+        let existing = duplicates.entry(chunk_index).or_default();
+        let budget = (ENDGAME_FANOUT - 1).saturating_sub(existing.len());
+        if budget == 0 {
+            return Vec::new();
+        }
+
+        let haves = self.peer_haves.read().await;
+        let mut peers = self.peers.write().await;
+        let mut candidates: Vec<([u8; 32], f64)> = peers
+            .values()
+            .filter(|p| {
+                Self::peer_eligible(p, &haves, chunk_index)
+                    && Some(p.peer_id) != primary
+                    && !existing.contains(&p.peer_id)
+            })
+            .map(|p| (p.peer_id, p.performance_score()))
+            .collect();
+        candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut extras = Vec::new();
+        for (peer_id, _) in candidates.into_iter().take(budget) {
+            if let Some(peer) = peers.get_mut(&peer_id) {
+                peer.record_assignment();
+            }
+            existing.push(peer_id);
+            extras.push(peer_id);
+        }
+
+        extras
+    }
+
+    /// Complete a chunk delivered by `winner`, cancelling duplicate requests
+    ///
+    /// Credits the winning peer with the transfer, releases the in-flight
+    /// slots of every other peer the chunk was requested from, and returns
+    /// those peers so the caller can cancel their outstanding requests.
+    /// Cancelled duplicates are not counted as failures.
+    pub async fn complete_chunk(
+        &self,
+        chunk_index: usize,
+        winner: [u8; 32],
+        bytes: u64,
+        duration: Duration,
+    ) -> Vec<[u8; 32]> {
+        let primary = self.assignments.write().await.remove(&chunk_index);
+        let extras = self
+            .duplicates
+            .write()
+            .await
+            .remove(&chunk_index)
+            .unwrap_or_default();
+
+        let mut losers = Vec::new();
+        let mut peers = self.peers.write().await;
+        for peer_id in primary.into_iter().chain(extras) {
+            if peer_id == winner {
+                continue;
+            }
+            if let Some(peer) = peers.get_mut(&peer_id) {
+                peer.cancel_assignment();
+            }
+            losers.push(peer_id);
+        }
+
+        let measurement = if let Some(peer) = peers.get_mut(&winner) {
+            peer.record_success();
+            peer.update_throughput(bytes, duration);
+            Some((peer.rtt_us, peer.throughput_bps))
+        } else {
+            None
+        };
+        drop(peers);
+
+        if let Some(history) = &self.history {
+            history.record_chunk_result(winner, true).await;
+            if let Some((rtt_us, throughput_bps)) = measurement {
+                history
+                    .record_measurement(winner, rtt_us, throughput_bps)
+                    .await;
+            }
+        }
+
+        losers
+    }
+
     /// Reassign a chunk on failure
     pub async fn reassign_chunk(&self, chunk_index: usize) -> Option<[u8; 32]> {
         // Remove old assignment
@@ -332,6 +608,11 @@ impl MultiPeerCoordinator {
                 if let Some(peer) = peers.get_mut(&old_peer) {
                     peer.record_failure();
                 }
+                drop(peers);
+
+                if let Some(history) = &self.history {
+                    history.record_chunk_result(old_peer, false).await;
+                }
             }
         }
 
@@ -350,17 +631,42 @@ impl MultiPeerCoordinator {
         drop(assignments);
 
         let mut peers = self.peers.write().await;
-        if let Some(peer) = peers.get_mut(&peer_id) {
+        let measurement = if let Some(peer) = peers.get_mut(&peer_id) {
             peer.record_success();
             peer.update_throughput(bytes, duration);
+            Some((peer.rtt_us, peer.throughput_bps))
+        } else {
+            None
+        };
+        drop(peers);
+
+        if let Some(history) = &self.history {
+            history.record_chunk_result(peer_id, true).await;
+            if let Some((rtt_us, throughput_bps)) = measurement {
+                history
+                    .record_measurement(peer_id, rtt_us, throughput_bps)
+                    .await;
+            }
         }
     }
 
     /// Update peer RTT
     pub async fn update_peer_rtt(&self, peer_id: &[u8; 32], rtt_us: u64) {
         let mut peers = self.peers.write().await;
-        if let Some(peer) = peers.get_mut(peer_id) {
+        let measurement = if let Some(peer) = peers.get_mut(peer_id) {
             peer.update_rtt(rtt_us);
+            Some((peer.rtt_us, peer.throughput_bps))
+        } else {
+            None
+        };
+        drop(peers);
+
+        if let Some(history) = &self.history {
+            if let Some((rtt_us, throughput_bps)) = measurement {
+                history
+                    .record_measurement(*peer_id, rtt_us, throughput_bps)
+                    .await;
+            }
         }
     }
 
@@ -592,4 +898,244 @@ mod tests {
         let perf = coordinator.peer_performance(&peer_id).await.unwrap();
         assert_eq!(perf.chunks_succeeded, 1);
     }
+
+    #[tokio::test]
+    async fn test_peer_seeded_from_history() {
+        let store = Arc::new(PeerHistoryStore::new(std::path::PathBuf::from(
+            "/nonexistent/peer_history.json",
+        )));
+        let peer_id = [1u8; 32];
+        store
+            .record_measurement(peer_id, 5_000, 100 * 1024 * 1024)
+            .await;
+
+        let coordinator =
+            MultiPeerCoordinator::with_history(ChunkAssignmentStrategy::Adaptive, store);
+        coordinator
+            .add_peer(peer_id, "127.0.0.1:8420".parse().unwrap())
+            .await;
+
+        let perf = coordinator.peer_performance(&peer_id).await.unwrap();
+        assert_eq!(perf.rtt_us, 5_000);
+        assert_eq!(perf.throughput_bps, 100 * 1024 * 1024);
+        assert!(perf.max_concurrent > 4); // fast peer starts with more slots
+    }
+
+    #[tokio::test]
+    async fn test_history_seeding_drives_initial_assignment() {
+        let store = Arc::new(PeerHistoryStore::new(std::path::PathBuf::from(
+            "/nonexistent/peer_history.json",
+        )));
+        let fast_peer = [1u8; 32];
+        let slow_peer = [2u8; 32];
+        store
+            .record_measurement(fast_peer, 5_000, 50 * 1024 * 1024)
+            .await;
+        store.record_measurement(slow_peer, 200_000, 100_000).await;
+
+        let coordinator =
+            MultiPeerCoordinator::with_history(ChunkAssignmentStrategy::FastestFirst, store);
+        coordinator
+            .add_peer(fast_peer, "127.0.0.1:8420".parse().unwrap())
+            .await;
+        coordinator
+            .add_peer(slow_peer, "127.0.0.1:8421".parse().unwrap())
+            .await;
+
+        // With no runtime measurements yet, history picks the known-fast peer
+        let assigned = coordinator.assign_chunk(0).await.unwrap();
+        assert_eq!(assigned, fast_peer);
+    }
+
+    #[tokio::test]
+    async fn test_measurements_flow_back_to_history() {
+        let store = Arc::new(PeerHistoryStore::new(std::path::PathBuf::from(
+            "/nonexistent/peer_history.json",
+        )));
+        let peer_id = [1u8; 32];
+
+        let coordinator = MultiPeerCoordinator::with_history(
+            ChunkAssignmentStrategy::RoundRobin,
+            Arc::clone(&store),
+        );
+        coordinator
+            .add_peer(peer_id, "127.0.0.1:8420".parse().unwrap())
+            .await;
+        coordinator.assign_chunk(0).await.unwrap();
+        coordinator
+            .record_success(0, 1_000_000, Duration::from_secs(1))
+            .await;
+
+        let record = store.get(&peer_id).await.unwrap();
+        assert_eq!(record.chunks_succeeded, 1);
+        assert!(record.throughput_bps > 0);
+    }
+
+    #[tokio::test]
+    async fn test_endgame_detection() {
+        let coordinator = MultiPeerCoordinator::new(ChunkAssignmentStrategy::Adaptive);
+        coordinator
+            .add_peer([1u8; 32], "127.0.0.1:8420".parse().unwrap())
+            .await;
+        coordinator
+            .add_peer([2u8; 32], "127.0.0.1:8421".parse().unwrap())
+            .await;
+
+        assert!(!coordinator.in_endgame(100).await); // plenty left
+        assert!(coordinator.in_endgame(2).await); // remaining <= peers
+        assert!(coordinator.in_endgame(1).await);
+        assert!(!coordinator.in_endgame(0).await); // done
+    }
+
+    #[tokio::test]
+    async fn test_endgame_needs_multiple_peers() {
+        let coordinator = MultiPeerCoordinator::new(ChunkAssignmentStrategy::Adaptive);
+        coordinator
+            .add_peer([1u8; 32], "127.0.0.1:8420".parse().unwrap())
+            .await;
+
+        // With a single peer there is nobody to duplicate requests to
+        assert!(!coordinator.in_endgame(1).await);
+    }
+
+    #[tokio::test]
+    async fn test_endgame_assign_picks_distinct_extras() {
+        let coordinator = MultiPeerCoordinator::new(ChunkAssignmentStrategy::RoundRobin);
+        let peer1 = [1u8; 32];
+        let peer2 = [2u8; 32];
+        let peer3 = [3u8; 32];
+        for (i, peer) in [peer1, peer2, peer3].iter().enumerate() {
+            coordinator
+                .add_peer(*peer, format!("127.0.0.1:{}", 8420 + i).parse().unwrap())
+                .await;
+        }
+
+        let primary = coordinator.assign_chunk(0).await.unwrap();
+        let extras = coordinator.endgame_assign(0).await;
+
+        assert_eq!(extras.len(), ENDGAME_FANOUT - 1);
+        assert!(!extras.contains(&primary));
+        assert_ne!(extras[0], extras[1]);
+
+        // Repeated calls do not exceed the fanout budget
+        assert!(coordinator.endgame_assign(0).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_complete_chunk_cancels_losers() {
+        let coordinator = MultiPeerCoordinator::new(ChunkAssignmentStrategy::RoundRobin);
+        let peer1 = [1u8; 32];
+        let peer2 = [2u8; 32];
+        coordinator
+            .add_peer(peer1, "127.0.0.1:8420".parse().unwrap())
+            .await;
+        coordinator
+            .add_peer(peer2, "127.0.0.1:8421".parse().unwrap())
+            .await;
+
+        let primary = coordinator.assign_chunk(0).await.unwrap();
+        let extras = coordinator.endgame_assign(0).await;
+        assert_eq!(extras.len(), 1);
+        let extra = extras[0];
+
+        // The duplicate peer wins; the primary assignment is cancelled
+        let losers = coordinator
+            .complete_chunk(0, extra, 1_000_000, Duration::from_millis(100))
+            .await;
+        assert_eq!(losers, vec![primary]);
+
+        // Winner is credited, loser is not penalized and has its slot back
+        let winner_perf = coordinator.peer_performance(&extra).await.unwrap();
+        assert_eq!(winner_perf.chunks_succeeded, 1);
+        assert_eq!(winner_perf.in_flight, 0);
+
+        let loser_perf = coordinator.peer_performance(&primary).await.unwrap();
+        assert_eq!(loser_perf.chunks_failed, 0);
+        assert_eq!(loser_perf.in_flight, 0);
+    }
+
+    #[tokio::test]
+    async fn test_assignment_honors_peer_bitmaps() {
+        let coordinator = MultiPeerCoordinator::new(ChunkAssignmentStrategy::RoundRobin);
+        let seed = [1u8; 32];
+        let swarming_receiver = [2u8; 32];
+        coordinator
+            .add_peer(seed, "127.0.0.1:8420".parse().unwrap())
+            .await;
+        coordinator
+            .add_peer(swarming_receiver, "127.0.0.1:8421".parse().unwrap())
+            .await;
+
+        // The receiver has only announced chunk 0
+        let mut bitmap = HaveBitmap::new(4);
+        bitmap.mark(0);
+        coordinator
+            .record_peer_bitmap(swarming_receiver, bitmap)
+            .await;
+
+        // Chunks the receiver lacks can only go to the full seed
+        for chunk in 1..4 {
+            assert_eq!(coordinator.assign_chunk(chunk).await, Some(seed));
+            coordinator
+                .record_success(chunk, 1_000, Duration::from_millis(10))
+                .await;
+        }
+
+        assert_eq!(coordinator.chunk_availability(0).await, 2);
+        assert_eq!(coordinator.chunk_availability(1).await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_have_announcements_expand_availability() {
+        let coordinator = MultiPeerCoordinator::new(ChunkAssignmentStrategy::FastestFirst);
+        let seed = [1u8; 32];
+        let swarming_receiver = [2u8; 32];
+        coordinator
+            .add_peer(seed, "127.0.0.1:8420".parse().unwrap())
+            .await;
+        coordinator
+            .add_peer(swarming_receiver, "127.0.0.1:8421".parse().unwrap())
+            .await;
+        coordinator
+            .record_peer_bitmap(swarming_receiver, HaveBitmap::new(4))
+            .await;
+
+        // Make the receiver the faster source; it is still skipped while it
+        // lacks the chunk
+        {
+            let mut peers = coordinator.peers.write().await;
+            if let Some(peer) = peers.get_mut(&swarming_receiver) {
+                peer.throughput_bps = 100 * 1024 * 1024;
+            }
+        }
+        assert_eq!(coordinator.assign_chunk(2).await, Some(seed));
+
+        // Once it announces the chunk it becomes the preferred source
+        coordinator.record_peer_have(&swarming_receiver, 2).await;
+        assert_eq!(coordinator.assign_chunk(2).await, Some(swarming_receiver));
+    }
+
+    #[tokio::test]
+    async fn test_endgame_skips_peers_without_chunk() {
+        let coordinator = MultiPeerCoordinator::new(ChunkAssignmentStrategy::RoundRobin);
+        let peer1 = [1u8; 32];
+        let peer2 = [2u8; 32];
+        let peer3 = [3u8; 32];
+        for (i, peer) in [peer1, peer2, peer3].iter().enumerate() {
+            coordinator
+                .add_peer(*peer, format!("127.0.0.1:{}", 8420 + i).parse().unwrap())
+                .await;
+        }
+        // peer3 is a swarming receiver that lacks chunk 0
+        coordinator
+            .record_peer_bitmap(peer3, HaveBitmap::new(4))
+            .await;
+
+        let primary = coordinator.assign_chunk(0).await.unwrap();
+        let extras = coordinator.endgame_assign(0).await;
+
+        assert_eq!(extras.len(), 1); // only the other full seed qualifies
+        assert!(!extras.contains(&primary));
+        assert!(!extras.contains(&peer3));
+    }
 }
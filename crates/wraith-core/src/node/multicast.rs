@@ -0,0 +1,213 @@
+//! One-to-many LAN distribution through the node.
+//!
+//! Wraps [`wraith_transport::multicast`] behind the Node API: the sender
+//! multicasts a whole file to every machine on a trusted network segment
+//! in a single pass with NACK-based repair, and receivers reassemble it
+//! and verify the result against the announced BLAKE3 content hash. The
+//! distribution session ID is derived from that hash, so sender and
+//! receivers rendezvous on nothing more than the hash and the group
+//! address.
+//!
+//! Multicast datagrams are unauthenticated and unencrypted — this mode is
+//! for trusted LAN segments only, and the content-hash check is what
+//! stands in for the transfer path's AEAD. Callers are expected to fall
+//! back to the ordinary unicast send path when distribution fails (no
+//! multicast route, receiver behind a router) or a receiver reports
+//! missing chunks after the repair rounds; [`NodeError`] messages from
+//! these methods say so explicitly.
+
+use crate::node::error::{NodeError, Result};
+use crate::node::node::Node;
+use std::path::Path;
+use std::time::Duration;
+pub use wraith_transport::multicast::{DistributionReport, MulticastConfig};
+use wraith_transport::multicast::{MulticastReceiver, MulticastSender, chunk_payload};
+
+/// Derive the 8-byte multicast session ID from a content hash
+#[must_use]
+pub fn multicast_session_id(content_hash: &[u8; 32]) -> [u8; 8] {
+    let mut session_id = [0u8; 8];
+    session_id.copy_from_slice(&content_hash[..8]);
+    session_id
+}
+
+impl Node {
+    /// Distribute a file to the multicast group with NACK repair
+    ///
+    /// Reads the file, multicasts it in MTU-sized chunks, and re-multicasts
+    /// whatever any receiver NACKs until the repair rounds are exhausted.
+    /// Returns the distribution report together with the BLAKE3 content
+    /// hash receivers need to join and verify the session.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or no multicast route
+    /// exists — callers should fall back to the unicast send path.
+    pub async fn multicast_distribute(
+        &self,
+        file_path: impl AsRef<Path>,
+        config: MulticastConfig,
+    ) -> Result<(DistributionReport, [u8; 32])> {
+        let data = tokio::fs::read(file_path.as_ref())
+            .await
+            .map_err(|e| NodeError::Io(e.to_string()))?;
+        let content_hash = *blake3::hash(&data).as_bytes();
+        let chunks = chunk_payload(&data, config.chunk_payload);
+
+        tracing::info!(
+            group = %config.group,
+            chunks = chunks.len(),
+            bytes = data.len(),
+            "Starting multicast distribution"
+        );
+
+        let sender = MulticastSender::new(config).await.map_err(|e| {
+            NodeError::Transport(format!("Multicast sender unavailable: {e}").into())
+        })?;
+        let report = sender
+            .distribute(multicast_session_id(&content_hash), &chunks)
+            .await
+            .map_err(|e| {
+                NodeError::Transport(format!("Multicast distribution failed: {e}").into())
+            })?;
+
+        Ok((report, content_hash))
+    }
+
+    /// Receive one multicast distribution and write it to `output_path`
+    ///
+    /// Joins the group, collects the session derived from `content_hash`,
+    /// NACKs missed chunks, and verifies the reassembled bytes against the
+    /// hash before writing the file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the group cannot be joined, chunks are still
+    /// missing after the repair rounds (fetch the file via the unicast
+    /// transfer path instead), or the reassembled content does not match
+    /// `content_hash`.
+    pub async fn multicast_fetch(
+        &self,
+        content_hash: [u8; 32],
+        output_path: impl AsRef<Path>,
+        config: MulticastConfig,
+        idle_timeout: Duration,
+    ) -> Result<u64> {
+        let receiver = MulticastReceiver::join(config).map_err(|e| {
+            NodeError::Transport(format!("Failed to join multicast group: {e}").into())
+        })?;
+        let result = receiver
+            .receive(multicast_session_id(&content_hash), idle_timeout)
+            .await
+            .map_err(|e| NodeError::Transport(format!("Multicast receive failed: {e}").into()))?;
+
+        if !result.is_complete() {
+            return Err(NodeError::Transport(
+                format!(
+                    "Multicast distribution incomplete: {} of {} chunks missing; \
+                     fetch the file over unicast instead",
+                    result.missing().len(),
+                    result.chunks.len()
+                )
+                .into(),
+            ));
+        }
+
+        let data: Vec<u8> = result.chunks.into_iter().flatten().flatten().collect();
+        if *blake3::hash(&data).as_bytes() != content_hash {
+            return Err(NodeError::TrustViolation(
+                "Multicast content hash mismatch: discarding distribution".into(),
+            ));
+        }
+
+        let bytes = data.len() as u64;
+        tokio::fs::write(output_path.as_ref(), data)
+            .await
+            .map_err(|e| NodeError::Io(e.to_string()))?;
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::config::NodeConfig;
+
+    #[test]
+    fn test_session_id_derivation() {
+        let hash = *blake3::hash(b"file contents").as_bytes();
+        let session = multicast_session_id(&hash);
+        assert_eq!(session, hash[..8]);
+        // Distinct content gives a distinct session
+        let other = *blake3::hash(b"other contents").as_bytes();
+        assert_ne!(session, multicast_session_id(&other));
+    }
+
+    #[tokio::test]
+    async fn test_distribute_missing_file_errors() {
+        let node = Node::new_with_config(NodeConfig {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        let result = node
+            .multicast_distribute("/nonexistent/wraith-file", MulticastConfig::default())
+            .await;
+        assert!(matches!(result, Err(NodeError::Io(_))));
+    }
+
+    #[tokio::test]
+    async fn test_multicast_loopback_roundtrip() {
+        let config = MulticastConfig {
+            // Loopback keeps the traffic inside the test host
+            interface: "127.0.0.1".parse().unwrap(),
+            port: 18421,
+            ..Default::default()
+        };
+
+        let node = Node::new_with_config(NodeConfig {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let dir = std::env::temp_dir();
+        let input = dir.join("wraith-mcast-input.bin");
+        let output = dir.join("wraith-mcast-output.bin");
+        let payload = vec![42u8; 5000];
+        tokio::fs::write(&input, &payload).await.unwrap();
+
+        let content_hash = *blake3::hash(&payload).as_bytes();
+        let fetch = {
+            let node = node.clone();
+            let config = config.clone();
+            let output = output.clone();
+            tokio::spawn(async move {
+                node.multicast_fetch(content_hash, output, config, Duration::from_secs(2))
+                    .await
+            })
+        };
+        // Let the receiver join the group before the first pass
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let distribute = node.multicast_distribute(&input, config).await;
+        let _ = tokio::fs::remove_file(&input).await;
+        let Ok((report, hash)) = distribute else {
+            // No multicast route in this environment; the receiver times
+            // out with an incomplete-distribution error
+            assert!(fetch.await.unwrap().is_err());
+            return;
+        };
+        assert_eq!(hash, content_hash);
+        assert_eq!(report.chunks_sent, 5);
+
+        let fetched = fetch.await.unwrap();
+        if let Ok(bytes) = fetched {
+            assert_eq!(bytes, 5000);
+            assert_eq!(tokio::fs::read(&output).await.unwrap(), payload);
+            let _ = tokio::fs::remove_file(&output).await;
+        }
+    }
+}
@@ -7,7 +7,6 @@ use crate::node::session::PeerConnection;
 use crate::node::{Node, NodeError};
 use std::net::SocketAddr;
 use std::time::Duration;
-use wraith_transport::transport::Transport;
 
 /// ICE candidate for NAT traversal
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -225,7 +224,10 @@ impl Node {
     ///
     /// Uses the discovery manager to establish a relay path, then performs
     /// a Noise_XX handshake over the relay connection to establish a secure session.
-    async fn connect_via_relay(&self, peer: &PeerInfo) -> Result<PeerConnection, NodeError> {
+    pub(crate) async fn connect_via_relay(
+        &self,
+        peer: &PeerInfo,
+    ) -> Result<PeerConnection, NodeError> {
         tracing::debug!("Connecting via relay to peer {:?}", peer.peer_id);
 
         // Get discovery manager
@@ -298,7 +300,7 @@ impl Node {
     }
 
     /// Gather ICE candidates from local interfaces
-    async fn gather_ice_candidates(&self) -> Result<Vec<IceCandidate>, NodeError> {
+    pub(crate) async fn gather_ice_candidates(&self) -> Result<Vec<IceCandidate>, NodeError> {
         let mut candidates = Vec::new();
 
         // 1. Host candidates (local interfaces)
@@ -367,7 +369,7 @@ impl Node {
     /// - Support SDP-like candidate description format
     /// - Handle candidate gathering and exchange in parallel
     /// - Implement candidate filtering and priority calculation
-    async fn exchange_candidates(
+    pub(crate) async fn exchange_candidates(
         &self,
         peer: &PeerInfo,
         local_candidates: &[IceCandidate],
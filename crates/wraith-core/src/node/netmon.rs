@@ -0,0 +1,92 @@
+//! Active session migration on network change.
+//!
+//! When the host switches networks — the classic case being a phone walking
+//! out of Wi-Fi range and falling back to LTE — its source address changes
+//! and every established session's path silently stops working until it is
+//! re-validated. This module watches the local address set through
+//! [`NetworkWatcher`] (netlink-driven on Linux, polled elsewhere) and, on
+//! any change, re-validates each session: a PATH_CHALLENGE on the current
+//! address confirms it from the new network, and if the peer no longer
+//! answers there the session is migrated to a rediscovered address. Each
+//! successful migration surfaces a
+//! [`PathMigrated`](crate::node::events::NodeEvent::PathMigrated) event, so
+//! in-flight transfers continue without being restarted.
+//!
+//! The loop is spawned by [`Node::start`] when
+//! [`TransportConfig::migrate_on_network_change`] is set (the default).
+//!
+//! [`TransportConfig::migrate_on_network_change`]: crate::node::config::TransportConfig::migrate_on_network_change
+
+use crate::node::Node;
+use std::net::IpAddr;
+use std::time::Duration;
+use wraith_transport::netwatch::NetworkWatcher;
+
+/// How often the migration loop drains the watcher for pending changes
+const NETMON_TICK: Duration = Duration::from_secs(1);
+
+/// Poll interval handed to the watcher
+///
+/// Bounds detection latency on platforms without a kernel notification
+/// source; on Linux the netlink subscription reacts within one tick anyway.
+pub const NETMON_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+impl Node {
+    /// Network change migration loop — keeps sessions alive across handovers
+    ///
+    /// Drains the watcher every [`NETMON_TICK`], coalescing a flapping
+    /// interface's burst of events into one revalidation pass over all
+    /// established sessions.
+    pub(crate) async fn network_migration_loop(&self) {
+        let watcher = NetworkWatcher::spawn(NETMON_POLL_INTERVAL);
+        let mut ticker = self
+            .inner
+            .maintenance
+            .ticker("network_migration", NETMON_TICK);
+
+        tracing::info!("Network change monitor started");
+
+        loop {
+            ticker.tick().await;
+            if !self.is_running() {
+                break;
+            }
+
+            // Coalesce everything the watcher saw this tick
+            let mut added: Vec<IpAddr> = Vec::new();
+            let mut removed: Vec<IpAddr> = Vec::new();
+            while let Some(change) = watcher.try_recv() {
+                added.extend(change.added);
+                removed.extend(change.removed);
+            }
+            if added.is_empty() && removed.is_empty() {
+                continue;
+            }
+
+            tracing::info!(
+                "Local address set changed (added {:?}, removed {:?}), revalidating sessions",
+                added,
+                removed
+            );
+
+            let peers: Vec<_> = self
+                .inner
+                .sessions
+                .iter()
+                .map(|entry| *entry.key())
+                .collect();
+
+            for peer_id in peers {
+                if let Err(e) = self.revalidate_binding(&peer_id).await {
+                    tracing::warn!(
+                        "Migration of session {} after network change failed: {}",
+                        hex::encode(&peer_id[..8]),
+                        e
+                    );
+                }
+            }
+        }
+
+        tracing::info!("Network change monitor stopped");
+    }
+}
@@ -21,8 +21,10 @@
 //! }
 //! ```
 
+use crate::node::bandwidth::BandwidthLimiter;
 use crate::node::config::NodeConfig;
 use crate::node::error::{NodeError, Result};
+use crate::node::events::{EventBus, NodeEvent};
 use crate::node::file_transfer::FileTransferContext;
 use crate::node::identity::{Identity, TransferId};
 use crate::node::ip_reputation::IpReputationSystem;
@@ -41,9 +43,11 @@ use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, RwLock, oneshot};
+use tracing::Instrument;
 use wraith_discovery::{DiscoveryConfig as DiscoveryConfigInternal, DiscoveryManager};
-use wraith_files::tree_hash::compute_tree_hash;
+use wraith_files::tree_hash::{FileTreeHash, compute_tree_hash};
 use wraith_obfuscation::{DohTunnel, TlsRecordWrapper, WebSocketFrameWrapper};
+use wraith_transport::io_uring_udp::IoUringUdpTransport;
 use wraith_transport::transport::Transport;
 use wraith_transport::udp_async::AsyncUdpTransport;
 
@@ -71,6 +75,8 @@ pub(crate) struct NodeInner {
     pub(crate) identity: Arc<Identity>,
     /// Node configuration
     pub(crate) config: NodeConfig,
+    /// Hot-reloadable obfuscation settings (see [`Node::apply_config_update`])
+    pub(crate) live_obfuscation: std::sync::RwLock<crate::node::config::ObfuscationConfig>,
     /// Active sessions (peer_id -> connection)
     pub(crate) sessions: Arc<DashMap<PeerId, Arc<PeerConnection>>>,
     /// Packet routing table (Connection ID -> PeerConnection)
@@ -85,14 +91,52 @@ pub(crate) struct NodeInner {
     pub(crate) pending_migrations: Arc<DashMap<u64, MigrationState>>,
     /// Pending chunk requests ((stream_id, chunk_idx) -> data sender)
     pub(crate) pending_chunks: Arc<PendingChunkMap>,
+    /// Outbound transfer offers awaiting the peer's decision
+    pub(crate) pending_offers:
+        Arc<DashMap<TransferId, oneshot::Sender<crate::node::offer::OfferDecision>>>,
+    /// Receiver-side decision callback for inbound transfer offers
+    pub(crate) transfer_offer_callback:
+        std::sync::RwLock<Option<crate::node::offer::TransferOfferCallback>>,
+    /// Embedder policy hook for inbound sessions and offers (None = allow)
+    pub(crate) authorizer: std::sync::RwLock<Option<Arc<dyn crate::node::authorizer::Authorizer>>>,
+    /// Persisted transfer checkpoints (None when cross-session resume is off)
+    pub(crate) resume: Option<Arc<crate::node::resume::ResumeManager>>,
+    /// Resume bitmap queries awaiting the peer's reply
+    pub(crate) pending_resume: Arc<DashMap<TransferId, oneshot::Sender<Vec<u8>>>>,
+    /// Recent per-path bandwidth/RTT estimates for careful resume
+    pub(crate) path_estimates: Arc<crate::node::path_estimates::PathEstimateCache>,
+    /// Verified manifests of accepted inbound transfers, stored next to
+    /// the file once its transfer completes
+    pub(crate) transfer_manifests:
+        Arc<DashMap<TransferId, wraith_crypto::manifest::TransferManifest>>,
+    /// Byte-stream pipes (stream_id -> incoming data channel)
+    pub(crate) pipe_streams: Arc<DashMap<u16, tokio::sync::mpsc::Sender<Vec<u8>>>>,
+    /// Incoming pipe streams awaiting accept_stream()
+    pub(crate) pipe_accepts:
+        Arc<Mutex<tokio::sync::mpsc::UnboundedReceiver<crate::node::stream_api::WraithStream>>>,
+    /// Producer side of the pipe accept queue
+    pub(crate) pipe_accepts_tx:
+        tokio::sync::mpsc::UnboundedSender<crate::node::stream_api::WraithStream>,
+    /// Next pipe stream ID offset within the reserved range
+    pub(crate) next_pipe_stream: Arc<std::sync::atomic::AtomicU16>,
+    /// Persistent peer trust store (None when disabled)
+    pub(crate) trust: Option<Arc<crate::node::trust::TrustStore>>,
+    /// Verified identity delegations (ephemeral peer key -> long-term node ID)
+    pub(crate) delegated_identities: Arc<DashMap<PeerId, [u8; 32]>>,
+    /// Own reflexive addresses as reported by peers (reporter -> (address, when))
+    pub(crate) observed_endpoints: Arc<DashMap<PeerId, (SocketAddr, std::time::Instant)>>,
+    /// 0-RTT resumption state: tickets we issued and tickets issued to us
+    pub(crate) zero_rtt: crate::node::zero_rtt::ZeroRttState,
     /// Node running state
     pub(crate) running: Arc<AtomicBool>,
     /// Transport layer
-    pub(crate) transport: Arc<Mutex<Option<Arc<AsyncUdpTransport>>>>,
+    pub(crate) transport: Arc<crate::node::transport_slot::TransportSlot>,
     /// Discovery manager
     pub(crate) discovery: Arc<Mutex<Option<Arc<DiscoveryManager>>>>,
     /// Rate limiter for DoS protection
     pub(crate) rate_limiter: Arc<RateLimiter>,
+    /// Egress bandwidth limiter
+    pub(crate) bandwidth_limiter: Arc<BandwidthLimiter>,
     /// IP reputation system
     pub(crate) ip_reputation: Arc<IpReputationSystem>,
     /// Security event monitor
@@ -108,6 +152,26 @@ pub(crate) struct NodeInner {
     /// Available files for seeding (root_hash -> (metadata, file_path))
     pub(crate) available_files:
         Arc<DashMap<[u8; 32], (crate::node::transfer::FileMetadata, PathBuf)>>,
+    /// Content-addressed store backing the seed path
+    ///
+    /// `None` when `chunk_store_dir` is unset; chunks are then served
+    /// straight from the announced files.
+    pub(crate) chunk_store: Option<Arc<std::sync::Mutex<wraith_files::chunk_store::ChunkStore>>>,
+    /// Background integrity scrubber re-verifying announced files
+    ///
+    /// Announced files are registered here by [`Node::announce_file`]; the
+    /// loop spawned in [`Node::start`] periodically re-reads them and the
+    /// event consumer withdraws anything that fails verification.
+    pub(crate) scrubber: wraith_files::scrub::Scrubber,
+    /// Anonymous telemetry counters (reported only when opted in)
+    pub(crate) telemetry: Arc<crate::node::telemetry::TelemetryCollector>,
+    /// Deadline counters for control-plane maintenance tasks
+    pub(crate) maintenance: Arc<crate::node::maintenance::MaintenanceMonitor>,
+    /// Typed event bus backing [`Node::subscribe`]
+    pub(crate) events: EventBus,
+    /// Fault injector for resilience testing
+    #[cfg(feature = "chaos")]
+    pub(crate) chaos: Arc<crate::node::chaos::ChaosInjector>,
 }
 
 /// WRAITH Protocol Node
@@ -158,7 +222,11 @@ impl Node {
         use crate::node::ip_reputation::IpReputationConfig;
         use crate::node::security_monitor::SecurityMonitorConfig;
 
+        config.validate()?;
+
         let rate_limiter = RateLimiter::new(config.rate_limiting.clone());
+        let bandwidth_limiter = BandwidthLimiter::new(config.bandwidth)
+            .with_cover_budget(config.obfuscation.cover_traffic.budget_bps);
         let ip_reputation = IpReputationSystem::new(IpReputationConfig::default());
         let security_monitor = SecurityMonitor::new(SecurityMonitorConfig::default());
 
@@ -168,9 +236,58 @@ impl Node {
         let doh_tunnel = DohTunnel::new("https://1.1.1.1/dns-query".to_string());
         let obfuscation_stats = ObfuscationStats::default();
 
+        let (pipe_accepts_tx, pipe_accepts_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        // Trust store path comes from the config or defaults to
+        // ~/.config/wraith/known_peers; entries are loaded in start()
+        let trust = if config.trust.enabled {
+            let path = config
+                .trust
+                .store_path
+                .clone()
+                .or_else(crate::node::trust::TrustStore::default_path)
+                .ok_or_else(|| {
+                    NodeError::InvalidConfig(
+                        "Trust store enabled but no store_path set and no home directory".into(),
+                    )
+                })?;
+            Some(Arc::new(crate::node::trust::TrustStore::new(path)))
+        } else {
+            None
+        };
+
+        // Transfer checkpoints live in the configured state directory;
+        // without one, resumption does not survive a dead session
+        let resume = if config.transfer.enable_resume {
+            config.transfer.resume_state_dir.clone().map(|dir| {
+                Arc::new(crate::node::resume::ResumeManager::new(
+                    dir,
+                    crate::node::resume::RESUME_STATE_MAX_AGE_DAYS,
+                ))
+            })
+        } else {
+            None
+        };
+
+        // Content-addressed seed cache; announced files are copied in
+        // chunk by chunk and served from here (see transfer.rs)
+        let chunk_store = match config.transfer.chunk_store_dir.clone() {
+            Some(dir) => {
+                let mut store_config = wraith_files::chunk_store::ChunkStoreConfig::new(dir);
+                store_config.max_cache_bytes = config.transfer.chunk_store_max_bytes;
+                let store = wraith_files::chunk_store::ChunkStore::open(store_config)
+                    .map_err(|e| NodeError::Io(format!("Failed to open chunk store: {e}")))?;
+                Some(Arc::new(std::sync::Mutex::new(store)))
+            }
+            None => None,
+        };
+
+        let live_obfuscation = std::sync::RwLock::new(config.obfuscation.clone());
+
         let inner = NodeInner {
             identity: Arc::new(identity),
             config,
+            live_obfuscation,
             sessions: Arc::new(DashMap::new()),
             routing: Arc::new(RoutingTable::new()),
             transfers: Arc::new(DashMap::new()),
@@ -178,10 +295,26 @@ impl Node {
             pending_pings: Arc::new(DashMap::new()),
             pending_migrations: Arc::new(DashMap::new()),
             pending_chunks: Arc::new(DashMap::new()),
+            pending_offers: Arc::new(DashMap::new()),
+            transfer_offer_callback: std::sync::RwLock::new(None),
+            authorizer: std::sync::RwLock::new(None),
+            resume,
+            pending_resume: Arc::new(DashMap::new()),
+            path_estimates: Arc::new(crate::node::path_estimates::PathEstimateCache::new()),
+            transfer_manifests: Arc::new(DashMap::new()),
+            pipe_streams: Arc::new(DashMap::new()),
+            pipe_accepts: Arc::new(Mutex::new(pipe_accepts_rx)),
+            pipe_accepts_tx,
+            next_pipe_stream: Arc::new(std::sync::atomic::AtomicU16::new(0)),
+            trust,
+            delegated_identities: Arc::new(DashMap::new()),
+            observed_endpoints: Arc::new(DashMap::new()),
+            zero_rtt: crate::node::zero_rtt::ZeroRttState::default(),
             running: Arc::new(AtomicBool::new(false)),
-            transport: Arc::new(Mutex::new(None)),
+            transport: Arc::new(crate::node::transport_slot::TransportSlot::new()),
             discovery: Arc::new(Mutex::new(None)),
             rate_limiter: Arc::new(rate_limiter),
+            bandwidth_limiter: Arc::new(bandwidth_limiter),
             ip_reputation: Arc::new(ip_reputation),
             security_monitor: Arc::new(security_monitor),
             tls_wrapper: Arc::new(Mutex::new(tls_wrapper)),
@@ -189,6 +322,21 @@ impl Node {
             doh_tunnel: Arc::new(doh_tunnel),
             obfuscation_stats: Arc::new(Mutex::new(obfuscation_stats)),
             available_files: Arc::new(DashMap::new()),
+            chunk_store,
+            scrubber: wraith_files::scrub::Scrubber::new(
+                wraith_files::scrub::ScrubConfig::default(),
+            ),
+            telemetry: Arc::new(crate::node::telemetry::TelemetryCollector::new()),
+            maintenance: Arc::new(crate::node::maintenance::MaintenanceMonitor::default()),
+            events: EventBus::new(),
+            #[cfg(feature = "chaos")]
+            chaos: {
+                let injector = Arc::new(crate::node::chaos::ChaosInjector::new());
+                if let Some(chaos_config) = crate::node::chaos::ChaosConfig::from_env() {
+                    injector.apply(&chaos_config);
+                }
+                injector
+            },
         };
         Ok(Self {
             inner: Arc::new(inner),
@@ -218,7 +366,7 @@ impl Node {
 
     /// Get node's actual listening address
     pub async fn listen_addr(&self) -> Result<SocketAddr> {
-        let transport = self.inner.transport.lock().await;
+        let transport = self.inner.transport.get();
         match transport.as_ref() {
             Some(t) => {
                 let mut addr = t.local_addr().map_err(|e| {
@@ -264,12 +412,92 @@ impl Node {
             self.inner.config.listen_addr
         );
 
-        // Initialize transport
-        let transport = AsyncUdpTransport::bind(self.inner.config.listen_addr)
-            .await
-            .map_err(|e| NodeError::Transport(format!("Failed to bind transport: {e}").into()))?;
-        let transport = Arc::new(transport);
-        *self.inner.transport.lock().await = Some(Arc::clone(&transport));
+        // Load pinned peer identities before any session can be established
+        if let Some(trust) = &self.inner.trust {
+            trust.load().await?;
+            tracing::info!("Loaded trust store with {} pinned peers", trust.len().await);
+        }
+
+        // Prepare the resume state directory and drop stale checkpoints
+        if let Some(resume) = &self.inner.resume {
+            resume.initialize().await?;
+            match resume.cleanup_old_states().await {
+                Ok(0) => {}
+                Ok(removed) => tracing::info!("Removed {} stale resume state file(s)", removed),
+                Err(e) => tracing::warn!("Resume state cleanup failed: {}", e),
+            }
+        }
+
+        // Initialize transport: datagram I/O goes through a dedicated
+        // io_uring ring worker when enabled and the kernel supports rings,
+        // falling back to the tokio epoll socket otherwise
+        let dscp =
+            match self.inner.config.transport.dscp {
+                Some(code) => Some(wraith_transport::dscp::Dscp::new(code).ok_or_else(|| {
+                    NodeError::InvalidConfig("DSCP code point out of range".into())
+                })?),
+                None => None,
+            };
+        let mut transport: Option<Arc<dyn Transport>> = None;
+        if self.inner.config.transport.enable_io_uring && IoUringUdpTransport::is_supported() {
+            match IoUringUdpTransport::bind(self.inner.config.listen_addr) {
+                Ok(ring) => {
+                    if let Some(dscp) = dscp {
+                        // QoS marking is best-effort: some platforms refuse the option
+                        if let Err(e) = ring.set_dscp(dscp) {
+                            tracing::warn!("Failed to apply DSCP marking {dscp}: {e}");
+                        }
+                    }
+                    tracing::info!("Datagram I/O routed through io_uring ring worker");
+                    transport = Some(Arc::new(ring));
+                }
+                Err(e) => {
+                    tracing::warn!("io_uring transport unavailable ({e}); using UDP socket");
+                }
+            }
+        }
+        let transport: Arc<dyn Transport> = match transport {
+            Some(transport) => transport,
+            None => {
+                let udp = AsyncUdpTransport::bind(self.inner.config.listen_addr)
+                    .await
+                    .map_err(|e| {
+                        NodeError::Transport(format!("Failed to bind transport: {e}").into())
+                    })?;
+                if let Some(dscp) = dscp {
+                    // QoS marking is best-effort: some platforms refuse the option
+                    if let Err(e) = udp.set_dscp(dscp) {
+                        tracing::warn!("Failed to apply DSCP marking {dscp}: {e}");
+                    }
+                }
+                Arc::new(udp)
+            }
+        };
+        // With the XDP fast path requested, publish the socket behind a
+        // SwitchableTransport with a second UDP socket as fallback: an I/O
+        // failure on the primary (XDP program detached, NIC driver reset)
+        // demotes sends to the fallback instead of taking the node offline,
+        // and peers absorb the source-port change through path validation.
+        let transport: Arc<dyn Transport> = if self.inner.config.transport.enable_xdp {
+            let mut fallback_addr = self.inner.config.listen_addr;
+            fallback_addr.set_port(0);
+            let fallback = AsyncUdpTransport::bind(fallback_addr).await.map_err(|e| {
+                NodeError::Transport(format!("Failed to bind fallback transport: {e}").into())
+            })?;
+            tracing::info!(
+                fallback = %fallback.local_addr().map_err(|e| NodeError::Transport(
+                    format!("Failed to get fallback address: {e}").into()
+                ))?,
+                "Wrapping transport in switchable fast/fallback pair"
+            );
+            Arc::new(wraith_transport::switchable::SwitchableTransport::new(
+                transport,
+                Arc::new(fallback),
+            ))
+        } else {
+            transport
+        };
+        self.inner.transport.set(Arc::clone(&transport));
 
         // Initialize discovery
         let node_id_bytes = wraith_discovery::dht::NodeId::from_bytes(*self.node_id());
@@ -302,7 +530,52 @@ impl Node {
             });
         }
 
+        // Keep NAT bindings of idle sessions alive (defined in keepalive.rs)
+        if self.inner.config.transport.nat_keepalive {
+            let node = self.clone();
+            tokio::spawn(async move {
+                node.nat_keepalive_loop().await;
+            });
+        }
+
+        // Migrate sessions across network handovers (defined in netmon.rs)
+        if self.inner.config.transport.migrate_on_network_change {
+            let node = self.clone();
+            tokio::spawn(async move {
+                node.network_migration_loop().await;
+            });
+        }
+
+        // Reconnect to peers holding unfinished sends from a previous run
+        // (defined in resume.rs; no-op without persisted resume state)
+        self.spawn_resume_reconnect();
+
+        // Canary measuring executor scheduling delay (defined in
+        // maintenance.rs); its missed-deadline counter flags control-plane
+        // starvation by saturating transfer workers
+        let node = self.clone();
+        tokio::spawn(async move {
+            node.maintenance_canary_loop().await;
+        });
+
+        // Re-verify announced files against their recorded tree hashes on
+        // a slow cycle and withdraw anything that rotted on disk; the
+        // event consumer (defined in transfer.rs) drops the announcements
+        let node = self.clone();
+        tokio::spawn(async move {
+            node.scrub_event_loop().await;
+        });
+        let _scrub_loop = self.inner.scrubber.spawn();
+
+        // Start telemetry reporting only when explicitly opted in
+        if self.inner.config.telemetry.is_active() {
+            let config = self.inner.config.telemetry.clone();
+            let collector = Arc::clone(&self.inner.telemetry);
+            tokio::spawn(crate::node::telemetry::reporting_loop(config, collector));
+        }
+
         tracing::info!("Node started: {:?}", hex::encode(self.node_id()));
+        self.inner.events.emit(NodeEvent::NodeStarted);
         Ok(())
     }
 
@@ -332,13 +605,14 @@ impl Node {
         self.inner.routing.clear();
 
         // Close transport
-        if let Some(transport) = self.inner.transport.lock().await.take() {
+        if let Some(transport) = self.inner.transport.clear() {
             if let Err(e) = transport.close().await {
                 tracing::warn!("Error closing transport: {}", e);
             }
         }
 
         tracing::info!("Node stopped");
+        self.inner.events.emit(NodeEvent::NodeStopped);
         Ok(())
     }
 
@@ -346,6 +620,17 @@ impl Node {
     pub fn is_running(&self) -> bool {
         self.inner.running.load(Ordering::SeqCst)
     }
+
+    /// Subscribe to typed node events
+    ///
+    /// Returns a broadcast receiver yielding events emitted after this
+    /// call: node lifecycle, session establishment and teardown, transfer
+    /// progress and completion, and path migrations. A subscriber that
+    /// falls further behind than the channel capacity observes
+    /// `RecvError::Lagged` and loses the oldest events.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<NodeEvent> {
+        self.inner.events.subscribe()
+    }
 }
 
 // ═══════════════════════════════════════════════════════════════════════════
@@ -375,6 +660,10 @@ impl Node {
                     peer_connection.addr,
                     peer_connection.connection_type
                 );
+                self.inner.telemetry.record_connection(matches!(
+                    peer_connection.connection_type,
+                    wraith_discovery::manager::ConnectionType::Relayed(_)
+                ));
                 Ok(vec![peer_connection.addr])
             }
             Err(e) => {
@@ -390,7 +679,58 @@ impl Node {
         }
     }
 
+    /// Publish a signed content announcement to the group's DHT index
+    ///
+    /// The record is stored under group-derived keys so only members holding
+    /// the group secret can find it; its publisher signature is verified
+    /// before publishing.
+    pub async fn publish_content(
+        &self,
+        group_secret: &wraith_discovery::dht::GroupSecret,
+        record: wraith_discovery::dht::SignedContentAnnouncement,
+        ttl: Duration,
+    ) -> Result<()> {
+        let discovery = self.inner.discovery.lock().await;
+        let discovery =
+            discovery
+                .as_ref()
+                .ok_or(NodeError::Discovery(std::borrow::Cow::Borrowed(
+                    "Discovery not initialized",
+                )))?;
+
+        let dht = discovery.dht();
+        let mut dht = dht.write().await;
+        dht.publish_content(group_secret, record, ttl)
+            .map_err(|e| NodeError::Discovery(format!("Content publish failed: {e}").into()))
+    }
+
+    /// Search the group's content index by name substring
+    ///
+    /// Matching is case-insensitive and performed client-side; only records
+    /// with valid publisher signatures are returned.
+    pub async fn search_content(
+        &self,
+        group_secret: &wraith_discovery::dht::GroupSecret,
+        query: &str,
+    ) -> Result<Vec<wraith_discovery::dht::SignedContentAnnouncement>> {
+        let discovery = self.inner.discovery.lock().await;
+        let discovery =
+            discovery
+                .as_ref()
+                .ok_or(NodeError::Discovery(std::borrow::Cow::Borrowed(
+                    "Discovery not initialized",
+                )))?;
+
+        let dht = discovery.dht();
+        let dht = dht.read().await;
+        Ok(dht.search_content(group_secret, query))
+    }
+
     /// Establish session with peer (via DHT lookup)
+    ///
+    /// When discovery returns multiple addresses, handshake attempts are
+    /// raced with IPv6 leading (see [`order_addresses_for_racing`]); the
+    /// first to complete wins.
     pub async fn establish_session(&self, peer_id: &PeerId) -> Result<SessionId> {
         if let Some(connection) = self.inner.sessions.get(peer_id) {
             return Ok(connection.session_id);
@@ -402,20 +742,77 @@ impl Node {
             return Err(NodeError::PeerNotFound(*peer_id));
         }
 
-        // Try first address (in future, could try multiple in parallel)
-        let peer_addr = addrs[0];
-        self.establish_session_with_addr(peer_id, peer_addr).await
+        if addrs.len() == 1 {
+            return self.establish_session_with_addr(peer_id, addrs[0]).await;
+        }
+
+        // Race the discovered addresses happy-eyeballs style: attempts
+        // start staggered (IPv6 leading), the first handshake to complete
+        // wins and the rest are cancelled
+        let ordered = order_addresses_for_racing(&addrs);
+        let mut attempts = tokio::task::JoinSet::new();
+        for (i, peer_addr) in ordered.into_iter().enumerate() {
+            let node = self.clone();
+            let peer_id = *peer_id;
+            attempts.spawn(async move {
+                tokio::time::sleep(HAPPY_EYEBALLS_DELAY * i as u32).await;
+                node.establish_session_with_addr(&peer_id, peer_addr).await
+            });
+        }
+
+        let mut last_err = NodeError::PeerNotFound(*peer_id);
+        while let Some(result) = attempts.join_next().await {
+            match result {
+                Ok(Ok(session_id)) => {
+                    attempts.abort_all();
+                    return Ok(session_id);
+                }
+                Ok(Err(e)) => last_err = e,
+                Err(_) => {} // Cancelled by abort_all
+            }
+        }
+        Err(last_err)
     }
 
     /// Establish session with peer at known address
     pub async fn establish_session_with_addr(
         &self,
-        _expected_peer_id: &PeerId,
+        expected_peer_id: &PeerId,
         peer_addr: SocketAddr,
     ) -> Result<SessionId> {
+        let (session_id, _) = self
+            .establish_with_addr_inner(Some(expected_peer_id), peer_addr)
+            .await?;
+        Ok(session_id)
+    }
+
+    /// Establish a session with a peer whose identity is not known upfront
+    ///
+    /// Used by code-based pairing, where authentication comes from a
+    /// subsequent PAKE exchange rather than a pre-shared public key. The
+    /// peer's identity is learned from the Noise handshake and returned;
+    /// with the trust store enabled the presented key is pinned TOFU-style.
+    pub async fn connect_addr(&self, peer_addr: SocketAddr) -> Result<(SessionId, PeerId)> {
+        self.establish_with_addr_inner(None, peer_addr).await
+    }
+
+    /// Shared session establishment for known and unknown peer identities
+    async fn establish_with_addr_inner(
+        &self,
+        expected_peer_id: Option<&PeerId>,
+        peer_addr: SocketAddr,
+    ) -> Result<(SessionId, PeerId)> {
         let transport = self.get_transport().await?;
         tracing::info!("Establishing session with peer at {}", peer_addr);
 
+        // A ticket stored for this address skips the handshake entirely,
+        // re-authenticating the peer by possession of the resumption
+        // secret; any failure falls through silently to the full Noise_XX
+        // exchange (see zero_rtt.rs)
+        if let Some((session_id, peer_id)) = self.try_resume_0rtt(peer_addr).await {
+            return Ok((session_id, peer_id));
+        }
+
         let (msg2_tx, msg2_rx) = oneshot::channel();
         self.inner.pending_handshakes.insert(peer_addr, msg2_tx);
 
@@ -427,10 +824,20 @@ impl Node {
         )
         .await;
         self.inner.pending_handshakes.remove(&peer_addr);
-        let (crypto, session_id, peer_id) = handshake_result?;
+        let (crypto, session_id, peer_id) = handshake_result.inspect_err(|e| {
+            self.inner.events.emit(NodeEvent::HandshakeFailed {
+                peer_id: *expected_peer_id.unwrap_or(&[0u8; 32]),
+                reason: e.to_string(),
+            });
+        })?;
+
+        // Without an expected identity the presented key is checked against
+        // itself, which pins it on first contact (TOFU)
+        self.check_peer_trust(expected_peer_id.unwrap_or(&peer_id), &peer_id)
+            .await?;
 
         if let Some(connection) = self.inner.sessions.get(&peer_id) {
-            return Ok(connection.session_id);
+            return Ok((connection.session_id, peer_id));
         }
 
         let mut connection_id_bytes = [0u8; 8];
@@ -463,7 +870,85 @@ impl Node {
         // Announce peer to DHT (best-effort, don't fail session if announcement fails)
         self.announce_peer_to_dht(&peer_id, peer_addr).await;
 
-        Ok(session_id)
+        // Tell the peer where we see it (deferred: our report must not
+        // outrun the responder's own route registration)
+        self.spawn_observed_address_report(peer_id);
+
+        self.inner.events.emit(NodeEvent::PeerConnected { peer_id });
+
+        // Pick up any transfer this session's predecessor left unfinished
+        // (defined in resume.rs; no-op without persisted resume state)
+        self.spawn_resume_check(peer_id);
+
+        Ok((session_id, peer_id))
+    }
+
+    /// Check the key a peer presented against the trust store
+    ///
+    /// TOFU semantics: unknown peers are pinned on first contact, known
+    /// peers must present their pinned key. A changed key fails the session
+    /// closed unless `trust.allow_key_change` is set, in which case the new
+    /// key is re-pinned with a warning. No-op when the store is disabled.
+    async fn check_peer_trust(&self, expected_peer_id: &PeerId, presented: &PeerId) -> Result<()> {
+        use crate::node::trust::TrustDecision;
+
+        let Some(trust) = &self.inner.trust else {
+            return Ok(());
+        };
+
+        match trust.verify(expected_peer_id, presented).await {
+            TrustDecision::Trusted => Ok(()),
+            TrustDecision::FirstUse => {
+                tracing::info!(
+                    "Pinned key for first-seen peer {}",
+                    hex::encode(&expected_peer_id[..8])
+                );
+                if let Err(e) = trust.save().await {
+                    tracing::warn!("Failed to persist trust store: {}", e);
+                }
+                Ok(())
+            }
+            TrustDecision::KeyMismatch { pinned_key } => {
+                self.inner.events.emit(NodeEvent::PeerKeyMismatch {
+                    peer_id: *expected_peer_id,
+                    pinned_key,
+                    presented_key: *presented,
+                });
+
+                if self.inner.config.trust.allow_key_change {
+                    tracing::warn!(
+                        "Peer {} presented a changed key; re-pinning (allow_key_change set)",
+                        hex::encode(&expected_peer_id[..8])
+                    );
+                    trust.pin(*expected_peer_id, *presented).await;
+                    if let Err(e) = trust.save().await {
+                        tracing::warn!("Failed to persist trust store: {}", e);
+                    }
+                    Ok(())
+                } else {
+                    Err(NodeError::TrustViolation(
+                        format!(
+                            "Key for peer {} changed (pinned {}, presented {}). Refusing \
+                             session; remove the known_peers entry or set \
+                             trust.allow_key_change if this rotation is expected",
+                            hex::encode(&expected_peer_id[..8]),
+                            hex::encode(&pinned_key[..8]),
+                            hex::encode(&presented[..8])
+                        )
+                        .into(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Get the peer trust store, if enabled
+    ///
+    /// Exposes pin/unpin/list operations for management tooling (e.g. a CLI
+    /// `wraith peers` command).
+    #[must_use]
+    pub fn trust_store(&self) -> Option<Arc<crate::node::trust::TrustStore>> {
+        self.inner.trust.as_ref().map(Arc::clone)
     }
 
     /// Announce peer to DHT (best-effort)
@@ -500,6 +985,126 @@ impl Node {
             .ok_or(NodeError::SessionNotFound(*peer_id))
     }
 
+    /// Establish a fresh session with a peer under a newly generated X25519 key
+    ///
+    /// Used when [`TransferConfig::ephemeral_identities`] is set: every call
+    /// performs a complete handshake with a one-off key, so the recipient and
+    /// any observer see an identity that cannot be linked to this node's
+    /// long-term key or to its other transfers. Consequently there is no
+    /// session-reuse shortcut here, the connection is registered in the
+    /// routing table only (never in the peer-keyed session map, where it
+    /// would shadow the regular session), and the session is not announced to
+    /// the DHT. After establishment a signed
+    /// [`IdentityDelegation`](wraith_crypto::delegation::IdentityDelegation)
+    /// is sent inside the encrypted channel so the recipient can still
+    /// attribute the transfer to this node.
+    ///
+    /// [`TransferConfig::ephemeral_identities`]: crate::node::config::TransferConfig::ephemeral_identities
+    pub(crate) async fn establish_ephemeral_session(
+        &self,
+        peer_id: &PeerId,
+    ) -> Result<Arc<PeerConnection>> {
+        let addrs = self.discover_peer(peer_id).await?;
+        if addrs.is_empty() {
+            return Err(NodeError::PeerNotFound(*peer_id));
+        }
+        let peer_addr = addrs[0];
+        let transport = self.get_transport().await?;
+
+        let ephemeral = wraith_crypto::noise::NoiseKeypair::generate()
+            .map_err(|e| NodeError::Crypto(e.to_string()))?;
+        tracing::info!("Establishing ephemeral session with peer at {}", peer_addr);
+
+        let (msg2_tx, msg2_rx) = oneshot::channel();
+        self.inner.pending_handshakes.insert(peer_addr, msg2_tx);
+
+        let handshake_result = crate::node::session::perform_handshake_initiator(
+            &ephemeral,
+            peer_addr,
+            transport.as_ref(),
+            Some(msg2_rx),
+        )
+        .await;
+        self.inner.pending_handshakes.remove(&peer_addr);
+        let (crypto, session_id, presented) = handshake_result.inspect_err(|e| {
+            self.inner.events.emit(NodeEvent::HandshakeFailed {
+                peer_id: *peer_id,
+                reason: e.to_string(),
+            });
+        })?;
+
+        self.check_peer_trust(peer_id, &presented).await?;
+
+        let mut connection_id_bytes = [0u8; 8];
+        connection_id_bytes.copy_from_slice(&session_id[..8]);
+        let connection_id = ConnectionId::from_bytes(connection_id_bytes);
+        let connection =
+            PeerConnection::new(session_id, presented, peer_addr, connection_id, crypto);
+
+        connection
+            .transition_to(SessionState::Handshaking(HandshakePhase::InitSent))
+            .await?;
+        connection
+            .transition_to(SessionState::Handshaking(HandshakePhase::InitComplete))
+            .await?;
+        connection.transition_to(SessionState::Established).await?;
+
+        let connection_arc = Arc::new(connection);
+        let cid_u64 = u64::from_be_bytes(connection_id_bytes);
+        self.inner
+            .routing
+            .add_route(cid_u64, Arc::clone(&connection_arc));
+
+        tracing::info!(
+            "Ephemeral session established with peer {}, session: {}, route: {:016x}",
+            hex::encode(&presented[..8]),
+            hex::encode(&session_id[..8]),
+            cid_u64
+        );
+
+        // Deliberately no DHT announcement and no PeerConnected event: the
+        // whole point is that nothing outside this channel ties the
+        // ephemeral key to a peer relationship
+
+        if let Some(signing_key) = self.inner.identity.signing_key() {
+            let delegation = wraith_crypto::delegation::IdentityDelegation::issue(
+                signing_key,
+                *ephemeral.public_key(),
+                crate::node::delegation::DELEGATION_VALIDITY_SECS,
+            );
+            let frame = crate::node::delegation::build_delegation_frame(&delegation)?;
+            self.send_encrypted_frame(&connection_arc, &frame).await?;
+        } else {
+            tracing::warn!(
+                "Identity restored without its Ed25519 signing key; sending \
+                 without a delegation, so the recipient sees only the \
+                 ephemeral identity"
+            );
+        }
+
+        Ok(connection_arc)
+    }
+
+    /// Get the short authentication string for an established session
+    ///
+    /// The SAS is derived from handshake-transcript material shared by both
+    /// endpoints, so the two users can compare it out-of-band (verbally, in
+    /// person) to rule out a man-in-the-middle on first contact. See
+    /// [`wraith_crypto::sas::Sas`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::SessionNotFound`] when no session with the peer
+    /// exists.
+    pub fn session_sas(&self, peer_id: &PeerId) -> Result<wraith_crypto::sas::Sas> {
+        let connection = self
+            .inner
+            .sessions
+            .get(peer_id)
+            .ok_or(NodeError::SessionNotFound(*peer_id))?;
+        Ok(wraith_crypto::sas::Sas::derive(&connection.session_id))
+    }
+
     /// Close session with peer
     pub async fn close_session(&self, peer_id: &PeerId) -> Result<()> {
         if let Some((_, connection)) = self.inner.sessions.remove(peer_id) {
@@ -511,6 +1116,9 @@ impl Node {
                 peer_id,
                 cid_u64
             );
+            self.inner
+                .events
+                .emit(NodeEvent::PeerDisconnected { peer_id: *peer_id });
             Ok(())
         } else {
             Err(NodeError::SessionNotFound(*peer_id))
@@ -531,6 +1139,106 @@ impl Node {
         self.inner.routing.stats()
     }
 
+    /// Get aggregate per-frame-type counters across all active sessions
+    ///
+    /// Merges the [`crate::FrameCounters`] of every active session into a
+    /// single node-level snapshot, suitable for export to metrics dashboards.
+    pub async fn frame_stats(&self) -> crate::FrameCounters {
+        let mut aggregate = crate::FrameCounters::new();
+        for entry in self.inner.sessions.iter() {
+            let session = entry.value().session.read().await;
+            aggregate.merge(session.frame_counters());
+        }
+        aggregate
+    }
+
+    /// Collect a point-in-time metrics snapshot for export
+    ///
+    /// Gathers per-session, per-transfer, frame, routing, and DHT metrics
+    /// into a [`crate::node::metrics::NodeMetrics`] that renders as JSON
+    /// or Prometheus text. Backs the daemon's metrics endpoint and
+    /// `wraith metrics --json`.
+    pub async fn metrics_snapshot(&self) -> crate::node::metrics::NodeMetrics {
+        use crate::node::metrics::{
+            FrameMetrics, NodeMetrics, RouteMetrics, SessionMetrics, TransferMetrics,
+        };
+
+        let mut sessions = Vec::new();
+        for entry in self.inner.sessions.iter() {
+            let stats = &entry.value().stats;
+            sessions.push(SessionMetrics {
+                peer_id: hex::encode(entry.key()),
+                rtt_us: stats.rtt_us,
+                bytes_sent: stats.bytes_sent,
+                bytes_received: stats.bytes_received,
+                packets_sent: stats.packets_sent,
+                packets_received: stats.packets_received,
+                loss_rate: stats.loss_rate,
+            });
+        }
+
+        let mut transfers = Vec::new();
+        for transfer_id in self.active_transfers().await {
+            if let Some(progress) = self.get_transfer_progress(&transfer_id).await {
+                transfers.push(TransferMetrics {
+                    transfer_id: hex::encode(transfer_id),
+                    bytes_sent: progress.bytes_sent,
+                    bytes_total: progress.bytes_total,
+                    throughput_bytes_per_sec: progress.speed_bytes_per_sec,
+                });
+            }
+        }
+
+        let frame_counters = self.frame_stats().await;
+        let frames = FrameMetrics {
+            sent: frame_counters.total_sent(),
+            received: frame_counters.total_received(),
+            dropped: frame_counters.total_dropped(),
+            retransmitted: frame_counters.total_retransmitted(),
+            rekeys_sent: frame_counters.sent(crate::FrameType::Rekey),
+            rekeys_received: frame_counters.received(crate::FrameType::Rekey),
+        };
+
+        let routing_stats = self.routing_stats();
+        let routing = RouteMetrics {
+            active_routes: routing_stats.active_routes,
+            successful_lookups: routing_stats.successful_lookups,
+            failed_lookups: routing_stats.failed_lookups,
+        };
+
+        let dht_routing_table_peers = match &*self.inner.discovery.lock().await {
+            Some(discovery) => Some(discovery.dht().read().await.routing_table().peer_count()),
+            None => None,
+        };
+
+        NodeMetrics {
+            version: env!("CARGO_PKG_VERSION"),
+            running: self.is_running(),
+            sessions,
+            transfers,
+            frames,
+            routing,
+            maintenance: self.inner.maintenance.snapshot(),
+            dht_routing_table_peers,
+        }
+    }
+
+    /// Get the configured egress bandwidth caps
+    #[must_use]
+    pub fn bandwidth_limits(&self) -> crate::node::bandwidth::BandwidthLimits {
+        self.inner.bandwidth_limiter.limits()
+    }
+
+    /// Cap the egress rate of a single transfer (bytes per second)
+    ///
+    /// Overrides any configured per-transfer default. Takes effect on the
+    /// next chunk sent; the bucket is released when the transfer ends.
+    pub fn set_transfer_bandwidth_limit(&self, transfer_id: &TransferId, bytes_per_sec: u64) {
+        self.inner
+            .bandwidth_limiter
+            .set_transfer_limit(transfer_id, bytes_per_sec);
+    }
+
     /// Get number of active routes
     pub fn active_route_count(&self) -> usize {
         self.inner.routing.route_count()
@@ -602,6 +1310,34 @@ impl Node {
             compute_tree_hash(file_path, chunk_size).map_err(|e| NodeError::Io(e.to_string()))?;
         let transfer_id = Self::generate_transfer_id();
 
+        // Root span of the transfer: chunking, framing, encryption, and
+        // transmission spans all nest under it, so an OTLP-exported trace
+        // shows one transfer end-to-end
+        let span = tracing::info_span!(
+            "transfer",
+            id = %hex::encode(&transfer_id[..8]),
+            peer = %hex::encode(&peer_id[..8]),
+            file_size,
+        );
+
+        self.send_file_in_span(file_path, peer_id, transfer_id, file_size, tree_hash)
+            .instrument(span)
+            .await
+    }
+
+    /// Body of [`Node::send_file`] running inside the transfer span
+    ///
+    /// Also entered directly by [`Node::offer_file`] once the peer accepts,
+    /// reusing the transfer ID and tree hash from the offer.
+    pub(crate) async fn send_file_in_span(
+        &self,
+        file_path: &Path,
+        peer_id: &PeerId,
+        transfer_id: TransferId,
+        file_size: u64,
+        tree_hash: FileTreeHash,
+    ) -> Result<TransferId> {
+        let chunk_size = self.inner.config.transfer.chunk_size;
         let mut transfer =
             TransferSession::new_send(transfer_id, file_path.to_path_buf(), file_size, chunk_size);
         transfer.start();
@@ -616,7 +1352,28 @@ impl Node {
             .transfers
             .insert(transfer_id, Arc::clone(&context));
 
-        let connection = self.get_or_establish_session(peer_id).await?;
+        // Checkpoint the send so it can continue if the session dies
+        // mid-transfer (see resume.rs)
+        if let Some(resume) = &self.inner.resume {
+            let state = crate::node::resume::ResumeState::new(
+                transfer_id,
+                *peer_id,
+                tree_hash.root,
+                file_size,
+                chunk_size,
+                file_path.to_path_buf(),
+                true,
+            );
+            if let Err(e) = resume.save_state(&state).await {
+                tracing::warn!("Failed to checkpoint transfer for resume: {}", e);
+            }
+        }
+
+        let connection = if self.inner.config.transfer.ephemeral_identities {
+            self.establish_ephemeral_session(peer_id).await?
+        } else {
+            self.get_or_establish_session(peer_id).await?
+        };
         let stream_id = ((transfer_id[0] as u16) << 8) | (transfer_id[1] as u16);
 
         let metadata = crate::node::file_transfer::FileMetadata::from_path_and_hash(
@@ -631,18 +1388,184 @@ impl Node {
         self.send_encrypted_frame(&connection, &metadata_frame)
             .await?;
 
+        let node = self.clone();
+        let file_path_buf = file_path.to_path_buf();
+        tokio::spawn(
+            async move {
+                if let Err(e) = node
+                    .send_file_chunks(transfer_id, file_path_buf, stream_id, connection)
+                    .await
+                {
+                    tracing::error!("Error sending file chunks: {}", e);
+                }
+            }
+            // Keep the chunk-sending task inside the transfer span
+            .instrument(tracing::Span::current()),
+        );
+
+        self.inner.events.emit(NodeEvent::TransferStarted {
+            transfer_id,
+            peer_id: *peer_id,
+        });
+        self.spawn_transfer_event_monitor(transfer_id);
+
+        Ok(transfer_id)
+    }
+
+    /// Fan out one file to several recipients concurrently
+    ///
+    /// Unlike [`Node::send_file_to_peers`], which splits a file's chunks
+    /// *across* peers, this sends the complete file *to each* peer. Every
+    /// chunk is read and hashed once and then framed per recipient, so the
+    /// disk cost stays that of a single transfer regardless of recipient
+    /// count. Each recipient gets its own transfer ID and is tracked through
+    /// the usual per-transfer progress APIs.
+    ///
+    /// Partial failure is tolerated: recipients that cannot be reached during
+    /// setup are reported in [`FanOutReport::failures`] while the rest
+    /// proceed, and a recipient that fails mid-transfer is dropped without
+    /// affecting the others.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file is empty or unreadable, no peers were
+    /// provided, or setup fails for every recipient.
+    pub async fn send_file_to_many(
+        &self,
+        file_path: impl AsRef<Path>,
+        peer_ids: &[PeerId],
+    ) -> Result<crate::node::transfer_manager::FanOutReport> {
+        if peer_ids.is_empty() {
+            return Err(NodeError::InvalidState(
+                "No peers provided for fan-out transfer".into(),
+            ));
+        }
+
+        let file_path = file_path.as_ref();
+        let file_size = std::fs::metadata(file_path)
+            .map_err(|e| NodeError::Io(e.to_string()))?
+            .len();
+        if file_size == 0 {
+            return Err(NodeError::InvalidState("Cannot send empty file".into()));
+        }
+
+        let chunk_size = self.inner.config.transfer.chunk_size;
+        let tree_hash =
+            compute_tree_hash(file_path, chunk_size).map_err(|e| NodeError::Io(e.to_string()))?;
+
+        tracing::info!(
+            "Starting fan-out send: {} to {} recipients",
+            file_path.display(),
+            peer_ids.len()
+        );
+
+        let mut report = crate::node::transfer_manager::FanOutReport::default();
+        let mut recipients = Vec::new();
+
+        for peer_id in peer_ids {
+            match self
+                .start_fanout_recipient(file_path, peer_id, file_size, &tree_hash)
+                .await
+            {
+                Ok(recipient) => {
+                    report.transfers.push((*peer_id, recipient.transfer_id));
+                    recipients.push(recipient);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Fan-out setup failed for peer {}: {}",
+                        hex::encode(&peer_id[..8]),
+                        e
+                    );
+                    report.failures.push((*peer_id, e.to_string()));
+                }
+            }
+        }
+
+        if recipients.is_empty() {
+            return Err(NodeError::Transfer(
+                "Fan-out setup failed for every recipient".into(),
+            ));
+        }
+
         let node = self.clone();
         let file_path_buf = file_path.to_path_buf();
         tokio::spawn(async move {
             if let Err(e) = node
-                .send_file_chunks(transfer_id, file_path_buf, stream_id, connection)
+                .send_file_chunks_fanout(file_path_buf, recipients)
                 .await
             {
-                tracing::error!("Error sending file chunks: {}", e);
+                tracing::error!("Error in fan-out send: {}", e);
             }
         });
 
-        Ok(transfer_id)
+        Ok(report)
+    }
+
+    /// Set up one recipient of a fan-out send
+    ///
+    /// Mirrors the per-transfer setup in [`Node::send_file_in_span`]:
+    /// transfer session, context, session establishment, and the metadata
+    /// frame — but leaves chunk sending to the shared fan-out loop.
+    async fn start_fanout_recipient(
+        &self,
+        file_path: &Path,
+        peer_id: &PeerId,
+        file_size: u64,
+        tree_hash: &FileTreeHash,
+    ) -> Result<crate::node::packet_handler::FanOutRecipient> {
+        let chunk_size = self.inner.config.transfer.chunk_size;
+        let transfer_id = Self::generate_transfer_id();
+
+        let mut transfer =
+            TransferSession::new_send(transfer_id, file_path.to_path_buf(), file_size, chunk_size);
+        transfer.start();
+
+        let context = Arc::new(FileTransferContext::new_send(
+            transfer_id,
+            Arc::new(RwLock::new(transfer)),
+            tree_hash.clone(),
+        ));
+        self.inner.transfers.insert(transfer_id, context);
+
+        let connection = match self.get_or_establish_session(peer_id).await {
+            Ok(connection) => connection,
+            Err(e) => {
+                // Don't leave a dead context behind for an unreachable peer
+                self.inner.transfers.remove(&transfer_id);
+                return Err(e);
+            }
+        };
+        let stream_id = ((transfer_id[0] as u16) << 8) | (transfer_id[1] as u16);
+
+        let metadata = crate::node::file_transfer::FileMetadata::from_path_and_hash(
+            transfer_id,
+            file_path,
+            file_size,
+            chunk_size,
+            tree_hash,
+        )?;
+        let metadata_frame =
+            crate::node::file_transfer::build_metadata_frame(stream_id, &metadata)?;
+        if let Err(e) = self
+            .send_encrypted_frame(&connection, &metadata_frame)
+            .await
+        {
+            self.inner.transfers.remove(&transfer_id);
+            return Err(e);
+        }
+
+        self.inner.events.emit(NodeEvent::TransferStarted {
+            transfer_id,
+            peer_id: *peer_id,
+        });
+        self.spawn_transfer_event_monitor(transfer_id);
+
+        Ok(crate::node::packet_handler::FanOutRecipient {
+            transfer_id,
+            stream_id,
+            connection,
+        })
     }
 
     /// Send file to multiple peers using multi-peer coordination
@@ -720,9 +1643,28 @@ impl Node {
         ));
         self.inner.transfers.insert(transfer_id, context.clone());
 
-        // Create multi-peer coordinator
+        // Create multi-peer coordinator, seeded from persisted peer history
+        // when the transfer config points at a history file
         let strategy = self.inner.config.transfer.chunk_assignment_strategy;
-        let coordinator = crate::node::multi_peer::MultiPeerCoordinator::new(strategy);
+        let history = match &self.inner.config.transfer.peer_history_path {
+            Some(path) => {
+                let store = Arc::new(crate::node::peer_history::PeerHistoryStore::new(
+                    path.clone(),
+                ));
+                if let Err(e) = store.load().await {
+                    tracing::warn!("Failed to load peer history: {}", e);
+                }
+                Some(store)
+            }
+            None => None,
+        };
+        let coordinator = match &history {
+            Some(store) => crate::node::multi_peer::MultiPeerCoordinator::with_history(
+                strategy,
+                Arc::clone(store),
+            ),
+            None => crate::node::multi_peer::MultiPeerCoordinator::new(strategy),
+        };
 
         // Establish sessions with all peers and add to coordinator
         let mut sessions = Vec::new();
@@ -760,8 +1702,22 @@ impl Node {
             {
                 tracing::error!("Error in multi-peer upload: {}", e);
             }
+
+            // Persist measurements taken during this transfer for future
+            // source selection
+            if let Some(store) = history {
+                if let Err(e) = store.save().await {
+                    tracing::warn!("Failed to save peer history: {}", e);
+                }
+            }
         });
 
+        self.inner.events.emit(NodeEvent::TransferStarted {
+            transfer_id,
+            peer_id: peer_ids[0],
+        });
+        self.spawn_transfer_event_monitor(transfer_id);
+
         Ok(transfer_id)
     }
 
@@ -816,8 +1772,18 @@ impl Node {
                     stream_id,
                     chunk_index,
                     &chunk_data,
+                    self.next_trace_id(),
                 )?;
 
+                // In the endgame, push the final chunks through extra peers as
+                // well so a single slow session cannot hold the transfer open
+                let remaining = (total_chunks - chunk_index) as usize;
+                let extras = if coordinator.in_endgame(remaining).await {
+                    coordinator.endgame_assign(chunk_index as usize).await
+                } else {
+                    Vec::new()
+                };
+
                 let start = Instant::now();
                 if let Err(e) = self.send_encrypted_frame(&session, &chunk_frame).await {
                     tracing::warn!(
@@ -830,11 +1796,32 @@ impl Node {
                     continue;
                 }
 
-                // Record success
+                for extra in &extras {
+                    if let Some((_, extra_session)) = sessions.iter().find(|(id, _)| id == extra) {
+                        if let Err(e) = self.send_encrypted_frame(extra_session, &chunk_frame).await
+                        {
+                            tracing::debug!(
+                                "Endgame duplicate of chunk {} to peer {:?} failed: {}",
+                                chunk_index,
+                                extra,
+                                e
+                            );
+                        }
+                    }
+                }
+
+                // Record success; with endgame duplicates in flight this also
+                // releases the redundant assignments
                 let duration = start.elapsed();
-                coordinator
-                    .record_success(chunk_index as usize, chunk_len as u64, duration)
-                    .await;
+                if extras.is_empty() {
+                    coordinator
+                        .record_success(chunk_index as usize, chunk_len as u64, duration)
+                        .await;
+                } else {
+                    coordinator
+                        .complete_chunk(chunk_index as usize, peer_id, chunk_len as u64, duration)
+                        .await;
+                }
 
                 // Update progress
                 if let Some(context) = self.inner.transfers.get(&transfer_id) {
@@ -857,7 +1844,39 @@ impl Node {
         Ok(())
     }
 
-    /// Wait for transfer to complete
+    /// Forward a transfer's progress to the event bus
+    ///
+    /// Emits [`NodeEvent::TransferProgress`] as bytes move and a final
+    /// [`NodeEvent::TransferCompleted`]; exits quietly when the transfer
+    /// is cancelled or otherwise removed.
+    pub(crate) fn spawn_transfer_event_monitor(&self, transfer_id: TransferId) {
+        let node = self.clone();
+        tokio::spawn(async move {
+            let mut last_bytes = 0u64;
+            loop {
+                let Some(progress) = node.get_transfer_progress(&transfer_id).await else {
+                    return;
+                };
+                if progress.bytes_sent != last_bytes {
+                    last_bytes = progress.bytes_sent;
+                    node.inner.events.emit(NodeEvent::TransferProgress {
+                        transfer_id,
+                        bytes_sent: progress.bytes_sent,
+                        bytes_total: progress.bytes_total,
+                    });
+                }
+                if progress.is_complete() {
+                    node.inner
+                        .events
+                        .emit(NodeEvent::TransferCompleted { transfer_id });
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(200)).await;
+            }
+        });
+    }
+
+    /// Wait for a transfer to complete
     pub async fn wait_for_transfer(&self, transfer_id: TransferId) -> Result<()> {
         loop {
             if let Some(context) = self.inner.transfers.get(&transfer_id) {
@@ -931,6 +1950,8 @@ impl Node {
 
         // Remove transfer from map
         if let Some((_, _context)) = self.inner.transfers.remove(transfer_id) {
+            // Release any per-transfer bandwidth bucket
+            self.inner.bandwidth_limiter.remove_transfer(transfer_id);
             // Get the session if transfer has one
             let session_opt = self
                 .inner
@@ -955,7 +1976,7 @@ impl Node {
                 if let Some(frame) = close_frame {
                     if let Ok(encrypted) = session.encrypt_frame(&frame).await {
                         // Send CLOSE frame (best-effort - don't fail cancellation if send fails)
-                        if let Some(transport) = self.inner.transport.lock().await.as_ref() {
+                        if let Some(transport) = self.inner.transport.get() {
                             let _ = transport.send_to(&encrypted, session.peer_addr()).await;
                         }
                     }
@@ -977,23 +1998,81 @@ impl Node {
     }
 }
 
+// ═══════════════════════════════════════════════════════════════════════════
+// Telemetry
+// ═══════════════════════════════════════════════════════════════════════════
+
+impl Node {
+    /// Anonymous telemetry counters
+    ///
+    /// Recording is always safe; nothing leaves the node unless
+    /// telemetry is explicitly enabled in [`NodeConfig`].
+    pub fn telemetry(&self) -> &Arc<crate::node::telemetry::TelemetryCollector> {
+        &self.inner.telemetry
+    }
+}
+
+// ═══════════════════════════════════════════════════════════════════════════
+// Chaos Fault Injection (feature "chaos")
+// ═══════════════════════════════════════════════════════════════════════════
+
+#[cfg(feature = "chaos")]
+impl Node {
+    /// Apply a fault-injection configuration to the running node
+    pub fn set_chaos(&self, config: &crate::node::chaos::ChaosConfig) {
+        self.inner.chaos.apply(config);
+    }
+
+    /// Current fault-injection configuration
+    pub fn chaos_config(&self) -> crate::node::chaos::ChaosConfig {
+        self.inner.chaos.snapshot()
+    }
+
+    /// Counters of faults injected since startup
+    pub fn chaos_stats(&self) -> crate::node::chaos::ChaosStats {
+        self.inner.chaos.stats()
+    }
+}
+
 // ═══════════════════════════════════════════════════════════════════════════
 // Internal Helpers
 // ═══════════════════════════════════════════════════════════════════════════
 
 impl Node {
     /// Get transport layer
-    pub(crate) async fn get_transport(&self) -> Result<Arc<AsyncUdpTransport>> {
-        let guard = self.inner.transport.lock().await;
-        guard
-            .as_ref()
+    pub(crate) async fn get_transport(&self) -> Result<Arc<dyn Transport>> {
+        self.inner
+            .transport
+            .get()
             .ok_or(NodeError::InvalidState(std::borrow::Cow::Borrowed(
                 "Transport not initialized",
             )))
-            .cloned()
     }
 }
 
+/// Stagger between racing connection attempts (RFC 8305 recommends 250ms)
+const HAPPY_EYEBALLS_DELAY: Duration = Duration::from_millis(250);
+
+/// Order discovered addresses for happy-eyeballs racing
+///
+/// Interleaves the two address families starting with IPv6, per RFC 8305:
+/// v6 gets a head start of one [`HAPPY_EYEBALLS_DELAY`], but a broken v6
+/// path only delays the first v4 attempt rather than blocking it.
+fn order_addresses_for_racing(addrs: &[SocketAddr]) -> Vec<SocketAddr> {
+    let mut v6 = addrs.iter().copied().filter(SocketAddr::is_ipv6);
+    let mut v4 = addrs.iter().copied().filter(SocketAddr::is_ipv4);
+    let mut ordered = Vec::with_capacity(addrs.len());
+
+    loop {
+        match (v6.next(), v4.next()) {
+            (None, None) => break,
+            (a, b) => ordered.extend(a.into_iter().chain(b)),
+        }
+    }
+
+    ordered
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1022,6 +2101,33 @@ mod tests {
         assert!(node.stop().await.is_err());
     }
 
+    #[tokio::test]
+    async fn test_session_through_switchable_transport() {
+        let config = |xdp| {
+            let mut config = NodeConfig {
+                listen_addr: "127.0.0.1:0".parse().unwrap(),
+                ..Default::default()
+            };
+            config.transport.enable_xdp = xdp;
+            config
+        };
+        let a = Node::new_with_config(config(true)).await.unwrap();
+        let b = Node::new_with_config(config(false)).await.unwrap();
+        a.start().await.unwrap();
+        b.start().await.unwrap();
+
+        // Handshake and session traffic must flow through the switchable
+        // wrapper exactly as through the bare socket
+        let b_addr = b.listen_addr().await.unwrap();
+        a.establish_session_with_addr(b.node_id(), b_addr)
+            .await
+            .unwrap();
+        assert_eq!(a.active_sessions().await.len(), 1);
+
+        a.stop().await.unwrap();
+        b.stop().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_active_sessions_empty() {
         let node = Node::new_random().await.unwrap();
@@ -1111,4 +2217,32 @@ mod tests {
         }
         assert!(bob.decrypt_frame(&encrypted).await.is_err());
     }
+
+    #[test]
+    fn test_order_addresses_for_racing_interleaves_v6_first() {
+        let addrs: Vec<SocketAddr> = vec![
+            "192.0.2.1:1000".parse().unwrap(),
+            "192.0.2.2:1000".parse().unwrap(),
+            "[2001:db8::1]:1000".parse().unwrap(),
+            "[2001:db8::2]:1000".parse().unwrap(),
+        ];
+
+        let ordered = order_addresses_for_racing(&addrs);
+
+        assert_eq!(ordered.len(), 4);
+        assert!(ordered[0].is_ipv6());
+        assert!(ordered[1].is_ipv4());
+        assert!(ordered[2].is_ipv6());
+        assert!(ordered[3].is_ipv4());
+    }
+
+    #[test]
+    fn test_order_addresses_for_racing_single_family() {
+        let addrs: Vec<SocketAddr> = vec![
+            "192.0.2.1:1000".parse().unwrap(),
+            "192.0.2.2:1000".parse().unwrap(),
+        ];
+
+        assert_eq!(order_addresses_for_racing(&addrs), addrs);
+    }
 }
@@ -7,7 +7,6 @@ use crate::node::config::{MimicryMode, TimingMode};
 use crate::node::session::PeerConnection;
 use crate::node::{Node, NodeError};
 use std::time::Duration;
-use wraith_transport::transport::Transport;
 
 /// Protocol types for mimicry
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -61,9 +60,8 @@ impl Node {
     /// Apply padding to packet using configured strategy
     fn apply_padding(&self, data: &mut Vec<u8>) -> Result<(), NodeError> {
         // Delegate to the padding strategy
-        let strategy = crate::node::padding_strategy::create_padding_strategy(
-            self.inner.config.obfuscation.padding_mode,
-        );
+        let strategy =
+            crate::node::padding_strategy::create_padding_strategy(self.live_padding_mode());
         strategy.apply(data)
     }
 
@@ -71,7 +69,7 @@ impl Node {
     ///
     /// Returns the delay to apply before sending the next packet.
     pub fn get_timing_delay(&self) -> Duration {
-        match &self.inner.config.obfuscation.timing_mode {
+        match &self.live_timing_mode() {
             TimingMode::None => Duration::ZERO,
 
             TimingMode::Fixed(delay) => *delay,
@@ -186,7 +184,7 @@ impl Node {
     ///
     /// Makes WRAITH traffic look like normal protocol traffic.
     pub fn wrap_protocol(&self, data: &[u8]) -> Result<Vec<u8>, NodeError> {
-        match self.inner.config.obfuscation.mimicry_mode {
+        match self.live_mimicry_mode() {
             MimicryMode::None => Ok(data.to_vec()),
 
             MimicryMode::Tls => self.wrap_as_tls(data),
@@ -234,10 +232,13 @@ impl Node {
 
     /// Wrap as DNS-over-HTTPS query/response
     fn wrap_as_doh(&self, data: &[u8]) -> Result<Vec<u8>, NodeError> {
-        // Use wraith-obfuscation DohTunnel for protocol mimicry
-        // Note: DohTunnel creates DNS query packets with EDNS0 OPT records
+        // Use wraith-obfuscation DohTunnel for protocol mimicry: a complete
+        // RFC 8484 POST request with a DNS-message body. Sized for control
+        // frames - bulk data should use a different mimicry mode.
         let tunnel = &self.inner.doh_tunnel;
-        let wrapped = tunnel.create_dns_query("wraith.local", data);
+        let wrapped = tunnel
+            .encode_post_request(data)
+            .map_err(|e| NodeError::Other(format!("DoH wrap failed: {e}").into()))?;
 
         tracing::trace!(
             "Wrapped {} bytes as DoH (total: {} bytes)",
@@ -252,7 +253,7 @@ impl Node {
     ///
     /// Extracts original data from protocol wrapper.
     pub fn unwrap_protocol(&self, data: &[u8]) -> Result<Vec<u8>, NodeError> {
-        match self.inner.config.obfuscation.mimicry_mode {
+        match self.live_mimicry_mode() {
             MimicryMode::None => Ok(data.to_vec()),
 
             MimicryMode::Tls => self.unwrap_tls(data),
@@ -305,11 +306,12 @@ impl Node {
 
     /// Unwrap DNS-over-HTTPS
     fn unwrap_doh(&self, data: &[u8]) -> Result<Vec<u8>, NodeError> {
-        // Use wraith-obfuscation DohTunnel for protocol demimicry
+        // Use wraith-obfuscation DohTunnel for protocol demimicry; accepts
+        // either a POST request or a 200 response
         let tunnel = &self.inner.doh_tunnel;
 
         let unwrapped = tunnel
-            .parse_dns_response(data)
+            .decode_post(data)
             .map_err(|e| NodeError::Other(format!("DoH unwrap failed: {e}").into()))?;
 
         tracing::trace!(
@@ -0,0 +1,286 @@
+//! Observed-address reporting (learning our own reflexive addresses).
+//!
+//! A node behind a NAT cannot see its own public address, but every peer
+//! it completes a handshake with can: the source address the peer's
+//! transport observed is exactly the reflexive mapping a third party
+//! would need to reach us. Right after session establishment each side
+//! reports that observed remote address back over the already-encrypted
+//! channel (the same idea as QUIC's preferred/observed address), so the
+//! node accumulates its own endpoint list from live traffic instead of
+//! extra STUN round trips.
+//!
+//! Reports are carried over a Control frame: tag
+//! [`CONTROL_OBSERVED_ADDRESS`] followed by the encoded address. One
+//! observation is kept per reporting peer (latest wins) so a single peer
+//! cannot stuff the list; [`Node::observed_endpoints`] aggregates the
+//! live observations with the most-corroborated address first, and
+//! `Node::local_addresses` folds them into DHT announcements.
+
+use crate::FRAME_HEADER_SIZE;
+use crate::frame::{FrameBuilder, FrameType};
+use crate::node::Node;
+use crate::node::error::{NodeError, Result};
+use crate::node::session::{PeerConnection, PeerId};
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Control payload tag: observed remote address (reporter -> observed node)
+pub const CONTROL_OBSERVED_ADDRESS: u8 = 0x40;
+
+/// How long a peer's observation stays usable
+///
+/// NAT bindings drift; an address nobody has confirmed for this long is
+/// more likely stale than reachable and is dropped from the endpoint list.
+pub(crate) const OBSERVATION_MAX_AGE: Duration = Duration::from_secs(600);
+
+/// Grace period before the session initiator sends its report
+///
+/// The initiator registers its route the moment it fires off the final
+/// handshake message, so a report sent inline can reach the responder
+/// before the responder has processed that message and registered its own
+/// route — where the unknown connection ID is misread as a (malformed)
+/// handshake initiation and penalizes our IP reputation. A short wait
+/// lets the responder finish registering first.
+pub(crate) const INITIATOR_REPORT_DELAY: Duration = Duration::from_millis(500);
+
+/// Encode an observed-address control payload
+///
+/// Format: tag(1) + family(1, `4` or `6`) + IP octets (4 or 16) + port(2, BE).
+pub(crate) fn encode_observed_address(addr: &SocketAddr) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(20);
+    payload.push(CONTROL_OBSERVED_ADDRESS);
+    match addr.ip() {
+        IpAddr::V4(ip) => {
+            payload.push(4);
+            payload.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => {
+            payload.push(6);
+            payload.extend_from_slice(&ip.octets());
+        }
+    }
+    payload.extend_from_slice(&addr.port().to_be_bytes());
+    payload
+}
+
+/// Decode an observed-address control payload (tag already consumed)
+pub(crate) fn decode_observed_address(data: &[u8]) -> Result<SocketAddr> {
+    let malformed = || NodeError::InvalidState("Malformed observed address".into());
+
+    let (&family, rest) = data.split_first().ok_or_else(malformed)?;
+    let ip_len = match family {
+        4 => 4,
+        6 => 16,
+        _ => return Err(malformed()),
+    };
+    if rest.len() != ip_len + 2 {
+        return Err(malformed());
+    }
+
+    let ip = match family {
+        4 => IpAddr::from(<[u8; 4]>::try_from(&rest[..4]).expect("length checked")),
+        _ => IpAddr::from(<[u8; 16]>::try_from(&rest[..16]).expect("length checked")),
+    };
+    let port = u16::from_be_bytes([rest[ip_len], rest[ip_len + 1]]);
+    Ok(SocketAddr::new(ip, port))
+}
+
+/// Build a Control frame carrying an observed-address payload
+pub(crate) fn build_observed_address_frame(addr: &SocketAddr) -> Result<Vec<u8>> {
+    let payload = encode_observed_address(addr);
+    FrameBuilder::new()
+        .frame_type(FrameType::Control)
+        .stream_id(0)
+        .sequence(0)
+        .payload(&payload)
+        .build(FRAME_HEADER_SIZE + payload.len())
+        .map_err(|e| NodeError::InvalidState(format!("Failed to build control frame: {e}").into()))
+}
+
+impl Node {
+    /// Report the address we observe a peer at back to that peer
+    ///
+    /// Called once per session right after establishment: inline from the
+    /// responder registration path, via
+    /// [`Node::spawn_observed_address_report`] from the initiator path.
+    /// Best-effort: failure never tears down the session.
+    pub(crate) async fn report_observed_address(&self, connection: &PeerConnection) -> Result<()> {
+        let observed = connection.peer_addr();
+        let frame = build_observed_address_frame(&observed)?;
+        self.send_encrypted_frame(connection, &frame).await?;
+        tracing::debug!(
+            "Reported observed address {} to peer {}",
+            observed,
+            hex::encode(&connection.peer_id[..8])
+        );
+        Ok(())
+    }
+
+    /// Report the observed address after a grace period (initiator side)
+    ///
+    /// The responder reports inline during session registration; the
+    /// initiator defers by [`INITIATOR_REPORT_DELAY`] so the report cannot
+    /// outrun the responder's route registration. Best-effort: if the
+    /// session is gone by then, nothing is sent.
+    pub(crate) fn spawn_observed_address_report(&self, peer_id: PeerId) {
+        let node = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(INITIATOR_REPORT_DELAY).await;
+            let Some(connection) = node
+                .inner
+                .sessions
+                .get(&peer_id)
+                .map(|entry| Arc::clone(entry.value()))
+            else {
+                return;
+            };
+            if let Err(e) = node.report_observed_address(&connection).await {
+                tracing::debug!("Failed to report observed address: {e}");
+            }
+        });
+    }
+
+    /// Handle an inbound observed-address report (observed node side)
+    ///
+    /// Records the reporter's view of our address, keyed by reporter so
+    /// each peer contributes at most one live observation. Unroutable
+    /// claims (unspecified IP, port 0) are ignored rather than rejected —
+    /// a peer behind its own odd middlebox may genuinely see garbage.
+    pub(crate) fn handle_observed_address(&self, data: &[u8], peer_id: PeerId) -> Result<()> {
+        let addr = decode_observed_address(data)?;
+
+        if addr.ip().is_unspecified() || addr.port() == 0 {
+            tracing::debug!(
+                "Ignoring unroutable observed address {} from {}",
+                addr,
+                hex::encode(&peer_id[..8])
+            );
+            return Ok(());
+        }
+
+        tracing::debug!(
+            "Peer {} observes us at {}",
+            hex::encode(&peer_id[..8]),
+            addr
+        );
+        self.inner
+            .observed_endpoints
+            .insert(peer_id, (addr, Instant::now()));
+        Ok(())
+    }
+
+    /// Reflexive addresses of this node as reported by established peers
+    ///
+    /// Stale observations (older than [`OBSERVATION_MAX_AGE`]) are pruned;
+    /// the remainder is deduplicated and ordered by how many peers
+    /// corroborate each address, most-confirmed first. An address multiple
+    /// peers agree on is almost certainly our stable public mapping;
+    /// a singleton may be one peer's hairpin or translator artifact.
+    #[must_use]
+    pub fn observed_endpoints(&self) -> Vec<SocketAddr> {
+        self.inner
+            .observed_endpoints
+            .retain(|_, (_, seen)| seen.elapsed() < OBSERVATION_MAX_AGE);
+
+        let mut counts: HashMap<SocketAddr, usize> = HashMap::new();
+        for entry in self.inner.observed_endpoints.iter() {
+            *counts.entry(entry.value().0).or_insert(0) += 1;
+        }
+
+        let mut ranked: Vec<(SocketAddr, usize)> = counts.into_iter().collect();
+        // Secondary order on the address keeps the list deterministic
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked.into_iter().map(|(addr, _)| addr).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observed_address_roundtrip_v4() {
+        let addr: SocketAddr = "203.0.113.7:8420".parse().unwrap();
+        let payload = encode_observed_address(&addr);
+        assert_eq!(payload[0], CONTROL_OBSERVED_ADDRESS);
+        assert_eq!(decode_observed_address(&payload[1..]).unwrap(), addr);
+    }
+
+    #[test]
+    fn test_observed_address_roundtrip_v6() {
+        let addr: SocketAddr = "[2001:db8::1]:443".parse().unwrap();
+        let payload = encode_observed_address(&addr);
+        assert_eq!(decode_observed_address(&payload[1..]).unwrap(), addr);
+    }
+
+    #[test]
+    fn test_decode_observed_address_malformed() {
+        let addr: SocketAddr = "203.0.113.7:8420".parse().unwrap();
+        let payload = encode_observed_address(&addr);
+
+        assert!(decode_observed_address(&[]).is_err());
+        assert!(decode_observed_address(&payload[1..payload.len() - 1]).is_err());
+        assert!(decode_observed_address(&[9, 1, 2, 3, 4, 0, 1]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_handle_observed_address_records() {
+        let node = Node::new_random().await.unwrap();
+        let addr: SocketAddr = "198.51.100.9:8420".parse().unwrap();
+
+        let payload = encode_observed_address(&addr);
+        node.handle_observed_address(&payload[1..], [1u8; 32])
+            .unwrap();
+
+        assert_eq!(node.observed_endpoints(), vec![addr]);
+    }
+
+    #[tokio::test]
+    async fn test_handle_observed_address_ignores_unroutable() {
+        let node = Node::new_random().await.unwrap();
+
+        for addr in ["0.0.0.0:8420", "198.51.100.9:0"] {
+            let payload = encode_observed_address(&addr.parse().unwrap());
+            node.handle_observed_address(&payload[1..], [1u8; 32])
+                .unwrap();
+        }
+
+        assert!(node.observed_endpoints().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_observed_endpoints_majority_first() {
+        let node = Node::new_random().await.unwrap();
+        let majority: SocketAddr = "198.51.100.9:8420".parse().unwrap();
+        let outlier: SocketAddr = "203.0.113.7:9000".parse().unwrap();
+
+        for (reporter, addr) in [
+            ([1u8; 32], majority),
+            ([2u8; 32], majority),
+            ([3u8; 32], outlier),
+        ] {
+            let payload = encode_observed_address(&addr);
+            node.handle_observed_address(&payload[1..], reporter)
+                .unwrap();
+        }
+
+        assert_eq!(node.observed_endpoints(), vec![majority, outlier]);
+    }
+
+    #[tokio::test]
+    async fn test_observed_endpoints_latest_per_reporter_wins() {
+        let node = Node::new_random().await.unwrap();
+        let first: SocketAddr = "198.51.100.9:8420".parse().unwrap();
+        let second: SocketAddr = "198.51.100.9:9999".parse().unwrap();
+
+        for addr in [first, second] {
+            let payload = encode_observed_address(&addr);
+            node.handle_observed_address(&payload[1..], [1u8; 32])
+                .unwrap();
+        }
+
+        assert_eq!(node.observed_endpoints(), vec![second]);
+    }
+}
@@ -0,0 +1,593 @@
+//! Transfer offer negotiation (OFFER / ACCEPT / REJECT).
+//!
+//! [`Node::send_file`] pushes a StreamOpen and starts streaming chunks
+//! immediately; the receiver has no say. This module adds an explicit
+//! negotiation step over Control frames so receivers can approve or deny
+//! inbound files before any data moves:
+//!
+//! - `OFFER` carries the full [`FileMetadata`] (name, size, proposed chunk
+//!   size, compression) plus the chunk index the sender proposes to resume
+//!   from (`0` for a fresh transfer), and — when the sender holds an
+//!   Ed25519 identity — a [`TransferManifest`] signing the file's name,
+//!   size, chunk count and content root
+//! - `ACCEPT` echoes the transfer ID
+//! - `REJECT` echoes the transfer ID with a UTF-8 reason that is propagated
+//!   to the sender's [`Node::offer_file`] call
+//!
+//! Receivers register a decision callback with [`Node::on_transfer_offer`];
+//! when none is registered, offers are accepted, matching the pre-offer
+//! behaviour of unconditional delivery. Offers carrying a manifest whose
+//! signature does not verify, or whose fields disagree with the offered
+//! metadata, are rejected before the callback runs; a verified manifest is
+//! stored next to the received file once the transfer completes, so
+//! provenance can be re-checked later with `wraith verify`.
+
+use crate::FRAME_HEADER_SIZE;
+use crate::frame::{FrameBuilder, FrameType};
+use crate::node::Node;
+use crate::node::error::{NodeError, Result};
+use crate::node::file_transfer::FileMetadata;
+use crate::node::identity::TransferId;
+use crate::node::session::PeerId;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use wraith_crypto::manifest::TransferManifest;
+
+/// Control payload tag: transfer offer (sender -> receiver)
+pub const CONTROL_TRANSFER_OFFER: u8 = 0x10;
+/// Control payload tag: offer accepted (receiver -> sender)
+pub const CONTROL_TRANSFER_ACCEPT: u8 = 0x11;
+/// Control payload tag: offer rejected (receiver -> sender)
+pub const CONTROL_TRANSFER_REJECT: u8 = 0x12;
+
+/// How long the sender waits for the peer's decision
+const OFFER_DECISION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Maximum length of a rejection reason on the wire
+const MAX_REJECT_REASON: usize = 512;
+
+/// An inbound transfer offer presented to the receiver's decision callback
+#[derive(Debug, Clone)]
+pub struct TransferOffer {
+    /// The offering peer's node ID
+    pub peer_id: PeerId,
+    /// Metadata of the offered file (name, size, chunk size, compression)
+    pub metadata: FileMetadata,
+    /// First chunk the sender proposes to transfer (`0` = full transfer,
+    /// nonzero = resuming an interrupted transfer)
+    pub resume_from_chunk: u64,
+    /// Sender-signed manifest, when the sender holds an Ed25519 identity
+    ///
+    /// Signature and metadata consistency are already verified by the time
+    /// the callback sees the offer; whether the signer is an identity worth
+    /// trusting is the callback's decision.
+    pub manifest: Option<TransferManifest>,
+}
+
+/// The receiver's verdict on a [`TransferOffer`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OfferDecision {
+    /// Accept the transfer as offered
+    Accept,
+    /// Deny the transfer; the reason is delivered to the sender
+    Reject(String),
+}
+
+/// Receiver-side decision callback registered via [`Node::on_transfer_offer`]
+pub type TransferOfferCallback = Arc<dyn Fn(TransferOffer) -> OfferDecision + Send + Sync>;
+
+/// Encode an OFFER control payload
+///
+/// Format: tag(1) + serialized [`FileMetadata`] + resume_from_chunk(8, BE),
+/// optionally followed by a serialized [`TransferManifest`]. Peers that
+/// predate signed manifests omit the trailing bytes.
+pub(crate) fn encode_offer(
+    metadata: &FileMetadata,
+    resume_from_chunk: u64,
+    manifest: Option<&TransferManifest>,
+) -> Vec<u8> {
+    let metadata_bytes = metadata.serialize();
+    let mut payload = Vec::with_capacity(9 + metadata_bytes.len());
+    payload.push(CONTROL_TRANSFER_OFFER);
+    payload.extend_from_slice(&metadata_bytes);
+    payload.extend_from_slice(&resume_from_chunk.to_be_bytes());
+    if let Some(manifest) = manifest {
+        payload.extend_from_slice(&manifest.serialize());
+    }
+    payload
+}
+
+/// Decode an OFFER control payload (tag already consumed)
+///
+/// The manifest's signature is not checked here; that happens in
+/// [`Node::handle_transfer_offer`].
+pub(crate) fn decode_offer(data: &[u8]) -> Result<(FileMetadata, u64, Option<TransferManifest>)> {
+    let metadata = FileMetadata::deserialize(data)?;
+    // Metadata occupies 86 + file_name bytes (see FileMetadata::serialize);
+    // the resume chunk index follows it
+    let metadata_len = 86 + metadata.file_name.len();
+    let resume_bytes = data
+        .get(metadata_len..metadata_len + 8)
+        .ok_or_else(|| NodeError::invalid_state("Offer truncated (resume state)"))?;
+    let resume_from_chunk = u64::from_be_bytes(resume_bytes.try_into().expect("sliced to 8"));
+
+    // Signed manifest (absent from pre-manifest peers)
+    let manifest_bytes = &data[metadata_len + 8..];
+    let manifest = if manifest_bytes.is_empty() {
+        None
+    } else {
+        Some(
+            TransferManifest::deserialize(manifest_bytes)
+                .map_err(|e| NodeError::Crypto(format!("Malformed transfer manifest: {e}")))?,
+        )
+    };
+
+    Ok((metadata, resume_from_chunk, manifest))
+}
+
+/// Whether a manifest attests exactly the transfer the metadata describes
+fn manifest_matches_metadata(manifest: &TransferManifest, metadata: &FileMetadata) -> bool {
+    manifest.file_name == metadata.file_name
+        && manifest.file_size == metadata.file_size
+        && manifest.chunk_size == metadata.chunk_size
+        && manifest.chunk_count == metadata.total_chunks
+        && manifest.root_hash == metadata.root_hash
+}
+
+/// Where a received file's signed manifest is stored (`<file>.manifest`)
+#[must_use]
+pub fn manifest_sidecar_path(file: &Path) -> PathBuf {
+    let mut name = file
+        .file_name()
+        .map(std::ffi::OsStr::to_os_string)
+        .unwrap_or_default();
+    name.push(".manifest");
+    file.with_file_name(name)
+}
+
+/// Encode an ACCEPT control payload
+pub(crate) fn encode_accept(transfer_id: &TransferId) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(33);
+    payload.push(CONTROL_TRANSFER_ACCEPT);
+    payload.extend_from_slice(transfer_id);
+    payload
+}
+
+/// Encode a REJECT control payload
+///
+/// Format: tag(1) + transfer_id(32) + reason_len(2, BE) + reason UTF-8.
+/// Reasons longer than [`MAX_REJECT_REASON`] bytes are truncated.
+pub(crate) fn encode_reject(transfer_id: &TransferId, reason: &str) -> Vec<u8> {
+    let mut reason = reason.as_bytes();
+    if reason.len() > MAX_REJECT_REASON {
+        reason = &reason[..MAX_REJECT_REASON];
+    }
+    let mut payload = Vec::with_capacity(35 + reason.len());
+    payload.push(CONTROL_TRANSFER_REJECT);
+    payload.extend_from_slice(transfer_id);
+    payload.extend_from_slice(&(reason.len() as u16).to_be_bytes());
+    payload.extend_from_slice(reason);
+    payload
+}
+
+/// Decode an ACCEPT or REJECT control payload (tag already consumed)
+pub(crate) fn decode_reply(tag: u8, data: &[u8]) -> Result<(TransferId, OfferDecision)> {
+    let transfer_id: TransferId = data
+        .get(..32)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| NodeError::invalid_state("Offer reply truncated (transfer ID)"))?;
+
+    let decision = match tag {
+        CONTROL_TRANSFER_ACCEPT => OfferDecision::Accept,
+        CONTROL_TRANSFER_REJECT => {
+            let len_bytes = data
+                .get(32..34)
+                .ok_or_else(|| NodeError::invalid_state("Offer reply truncated (reason length)"))?;
+            let reason_len =
+                u16::from_be_bytes(len_bytes.try_into().expect("sliced to 2")) as usize;
+            let reason_bytes = data
+                .get(34..34 + reason_len)
+                .ok_or_else(|| NodeError::invalid_state("Offer reply truncated (reason)"))?;
+            let reason = String::from_utf8_lossy(reason_bytes).into_owned();
+            OfferDecision::Reject(reason)
+        }
+        other => {
+            return Err(NodeError::InvalidState(
+                format!("Unknown offer reply tag: {other:#04x}").into(),
+            ));
+        }
+    };
+
+    Ok((transfer_id, decision))
+}
+
+/// Build a Control frame carrying an offer-negotiation payload
+fn build_control_frame(payload: &[u8]) -> Result<Vec<u8>> {
+    FrameBuilder::new()
+        .frame_type(FrameType::Control)
+        .stream_id(0)
+        .sequence(0)
+        .payload(payload)
+        .build(FRAME_HEADER_SIZE + payload.len())
+        .map_err(|e| NodeError::InvalidState(format!("Failed to build control frame: {e}").into()))
+}
+
+impl Node {
+    /// Register a decision callback for inbound transfer offers
+    ///
+    /// The callback runs on the packet-handling task for every OFFER
+    /// received, so it should decide quickly; long-running policy checks
+    /// belong in a separate task fed by the offer data. Registering a new
+    /// callback replaces the previous one. Without a callback, all offers
+    /// are accepted.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # async fn example(node: wraith_core::node::Node) {
+    /// use wraith_core::node::offer::OfferDecision;
+    ///
+    /// node.on_transfer_offer(|offer| {
+    ///     if offer.metadata.file_size > 1 << 30 {
+    ///         OfferDecision::Reject("files over 1 GiB not accepted".to_string())
+    ///     } else {
+    ///         OfferDecision::Accept
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn on_transfer_offer<F>(&self, callback: F)
+    where
+        F: Fn(TransferOffer) -> OfferDecision + Send + Sync + 'static,
+    {
+        *self
+            .inner
+            .transfer_offer_callback
+            .write()
+            .expect("offer callback lock poisoned") = Some(Arc::new(callback));
+    }
+
+    /// Offer a file to a peer and transfer it once the peer accepts
+    ///
+    /// Sends an OFFER control frame with the file's metadata and waits for
+    /// the peer's decision before any chunk is read or sent. On acceptance
+    /// the transfer proceeds exactly as [`Node::send_file`]; on rejection
+    /// the peer's reason is returned in the error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::Transfer`] when the peer rejects the offer or
+    /// does not answer within 30 seconds, or any [`Node::send_file`] error
+    /// once the transfer itself starts.
+    pub async fn offer_file(
+        &self,
+        file_path: impl AsRef<Path>,
+        peer_id: &PeerId,
+    ) -> Result<TransferId> {
+        let file_path = file_path.as_ref();
+        let file_size = std::fs::metadata(file_path)
+            .map_err(|e| NodeError::Io(e.to_string()))?
+            .len();
+        if file_size == 0 {
+            return Err(NodeError::InvalidState("Cannot send empty file".into()));
+        }
+
+        let chunk_size = self.inner.config.transfer.chunk_size;
+        let tree_hash = wraith_files::tree_hash::compute_tree_hash(file_path, chunk_size)
+            .map_err(|e| NodeError::Io(e.to_string()))?;
+        let transfer_id = Self::generate_transfer_id();
+
+        let metadata = FileMetadata::from_path_and_hash(
+            transfer_id,
+            file_path,
+            file_size,
+            chunk_size,
+            &tree_hash,
+        )?;
+
+        // Sign the manifest when this node holds its Ed25519 identity;
+        // restored identities without a signing key offer unsigned
+        let manifest = self.inner.identity.signing_key().map(|key| {
+            TransferManifest::sign(
+                key,
+                &metadata.file_name,
+                metadata.file_size,
+                metadata.chunk_size,
+                metadata.total_chunks,
+                metadata.root_hash,
+            )
+        });
+
+        let connection = self.get_or_establish_session(peer_id).await?;
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.inner.pending_offers.insert(transfer_id, tx);
+
+        let frame = build_control_frame(&encode_offer(&metadata, 0, manifest.as_ref()))?;
+        if let Err(e) = self.send_encrypted_frame(&connection, &frame).await {
+            self.inner.pending_offers.remove(&transfer_id);
+            return Err(e);
+        }
+
+        tracing::debug!(
+            "Offered {} ({} bytes) to {}, awaiting decision",
+            metadata.file_name,
+            file_size,
+            hex::encode(&peer_id[..8])
+        );
+
+        let decision = match tokio::time::timeout(OFFER_DECISION_TIMEOUT, rx).await {
+            Ok(Ok(decision)) => decision,
+            Ok(Err(_)) => {
+                return Err(NodeError::Transfer("Offer reply channel closed".into()));
+            }
+            Err(_) => {
+                self.inner.pending_offers.remove(&transfer_id);
+                return Err(NodeError::Transfer(
+                    "Peer did not answer the transfer offer within 30s".into(),
+                ));
+            }
+        };
+
+        match decision {
+            OfferDecision::Accept => {
+                self.send_file_in_span(file_path, peer_id, transfer_id, file_size, tree_hash)
+                    .await
+            }
+            OfferDecision::Reject(reason) => Err(NodeError::Transfer(
+                format!("Transfer rejected by peer: {reason}").into(),
+            )),
+        }
+    }
+
+    /// Handle an inbound OFFER control payload (receiver side)
+    pub(crate) async fn handle_transfer_offer(&self, data: &[u8], peer_id: PeerId) -> Result<()> {
+        let (metadata, resume_from_chunk, manifest) = decode_offer(data)?;
+        let transfer_id = metadata.transfer_id;
+
+        tracing::info!(
+            "Transfer offer from {}: {} ({} bytes, chunk size {})",
+            hex::encode(&peer_id[..8]),
+            metadata.file_name,
+            metadata.file_size,
+            metadata.chunk_size
+        );
+
+        // A manifest that fails verification is rejected outright, without
+        // consulting the policy callback: an unverifiable signature is
+        // worse than no signature, since the sender claims an identity it
+        // cannot back up
+        let manifest_rejection = manifest.as_ref().and_then(|m| {
+            if m.verify().is_err() {
+                Some("Manifest signature invalid".to_string())
+            } else if !manifest_matches_metadata(m, &metadata) {
+                Some("Manifest does not match offered metadata".to_string())
+            } else {
+                None
+            }
+        });
+
+        // Embedder policy (see crate::node::authorizer) runs before the
+        // offer callback; a denial becomes a REJECT carrying its reason
+        let auth_rejection = if manifest_rejection.is_none() {
+            let request = crate::node::authorizer::AuthRequest {
+                peer_id,
+                source_addr: self
+                    .inner
+                    .sessions
+                    .get(&peer_id)
+                    .map(|entry| entry.peer_addr()),
+                action: crate::node::authorizer::AuthAction::TransferOffer {
+                    metadata: metadata.clone(),
+                    resume_from_chunk,
+                    signed: manifest.is_some(),
+                },
+            };
+            match self.authorize(&request).await {
+                crate::node::authorizer::AuthDecision::Allow => None,
+                crate::node::authorizer::AuthDecision::Deny(reason) => Some(reason),
+            }
+        } else {
+            None
+        };
+
+        let callback = self
+            .inner
+            .transfer_offer_callback
+            .read()
+            .expect("offer callback lock poisoned")
+            .clone();
+
+        let decision = match (manifest_rejection.or(auth_rejection), callback) {
+            (Some(reason), _) => OfferDecision::Reject(reason),
+            (None, Some(callback)) => callback(TransferOffer {
+                peer_id,
+                metadata,
+                resume_from_chunk,
+                manifest: manifest.clone(),
+            }),
+            // No policy registered: accept, matching pre-offer behaviour
+            (None, None) => OfferDecision::Accept,
+        };
+
+        // Keep the verified manifest so completion can store it next to
+        // the received file
+        if decision == OfferDecision::Accept
+            && let Some(manifest) = manifest
+        {
+            self.inner.transfer_manifests.insert(transfer_id, manifest);
+        }
+
+        let payload = match &decision {
+            OfferDecision::Accept => encode_accept(&transfer_id),
+            OfferDecision::Reject(reason) => {
+                tracing::info!(
+                    "Rejecting transfer offer {}: {}",
+                    hex::encode(&transfer_id[..8]),
+                    reason
+                );
+                encode_reject(&transfer_id, reason)
+            }
+        };
+
+        let connection = self
+            .inner
+            .sessions
+            .get(&peer_id)
+            .map(|entry| Arc::clone(entry.value()))
+            .ok_or(NodeError::SessionNotFound(peer_id))?;
+        let frame = build_control_frame(&payload)?;
+        self.send_encrypted_frame(&connection, &frame).await
+    }
+
+    /// Handle an inbound ACCEPT/REJECT control payload (sender side)
+    pub(crate) fn handle_offer_reply(&self, tag: u8, data: &[u8]) -> Result<()> {
+        let (transfer_id, decision) = decode_reply(tag, data)?;
+
+        if let Some((_, tx)) = self.inner.pending_offers.remove(&transfer_id) {
+            let _ = tx.send(decision);
+        } else {
+            tracing::debug!(
+                "Offer reply for unknown transfer {}",
+                hex::encode(&transfer_id[..8])
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transfer::compression::CompressionAlgorithm;
+
+    fn sample_metadata() -> FileMetadata {
+        FileMetadata {
+            transfer_id: [7u8; 32],
+            file_name: "report.pdf".to_string(),
+            file_size: 1024 * 1024,
+            chunk_size: 256 * 1024,
+            total_chunks: 4,
+            root_hash: [0xAB; 32],
+            compression: CompressionAlgorithm::Lz4,
+        }
+    }
+
+    fn sample_manifest(metadata: &FileMetadata) -> TransferManifest {
+        let signing_key = wraith_crypto::signatures::SigningKey::generate(&mut rand_core::OsRng);
+        TransferManifest::sign(
+            &signing_key,
+            &metadata.file_name,
+            metadata.file_size,
+            metadata.chunk_size,
+            metadata.total_chunks,
+            metadata.root_hash,
+        )
+    }
+
+    #[test]
+    fn test_offer_roundtrip() {
+        let metadata = sample_metadata();
+        let payload = encode_offer(&metadata, 42, None);
+        assert_eq!(payload[0], CONTROL_TRANSFER_OFFER);
+
+        let (decoded, resume, manifest) = decode_offer(&payload[1..]).unwrap();
+        assert_eq!(decoded.transfer_id, metadata.transfer_id);
+        assert_eq!(decoded.file_name, metadata.file_name);
+        assert_eq!(decoded.chunk_size, metadata.chunk_size);
+        assert_eq!(decoded.compression, metadata.compression);
+        assert_eq!(resume, 42);
+        assert!(manifest.is_none());
+    }
+
+    #[test]
+    fn test_offer_roundtrip_with_manifest() {
+        let metadata = sample_metadata();
+        let manifest = sample_manifest(&metadata);
+        let payload = encode_offer(&metadata, 0, Some(&manifest));
+
+        let (decoded_metadata, _, decoded_manifest) = decode_offer(&payload[1..]).unwrap();
+        let decoded_manifest = decoded_manifest.expect("manifest present");
+        assert_eq!(decoded_manifest, manifest);
+        assert!(decoded_manifest.verify().is_ok());
+        assert!(manifest_matches_metadata(
+            &decoded_manifest,
+            &decoded_metadata
+        ));
+    }
+
+    #[test]
+    fn test_offer_truncated_manifest_rejected() {
+        let metadata = sample_metadata();
+        let manifest = sample_manifest(&metadata);
+        let payload = encode_offer(&metadata, 0, Some(&manifest));
+
+        assert!(decode_offer(&payload[1..payload.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_manifest_metadata_mismatch_detected() {
+        let metadata = sample_metadata();
+        let manifest = sample_manifest(&metadata);
+        assert!(manifest_matches_metadata(&manifest, &metadata));
+
+        let mut inflated = metadata;
+        inflated.file_size += 1;
+        assert!(!manifest_matches_metadata(&manifest, &inflated));
+    }
+
+    #[test]
+    fn test_manifest_sidecar_path() {
+        assert_eq!(
+            manifest_sidecar_path(Path::new("/downloads/report.pdf")),
+            PathBuf::from("/downloads/report.pdf.manifest")
+        );
+    }
+
+    #[test]
+    fn test_offer_truncated_resume_state() {
+        let payload = encode_offer(&sample_metadata(), 0, None);
+        // Drop the resume bytes
+        assert!(decode_offer(&payload[1..payload.len() - 8]).is_err());
+    }
+
+    #[test]
+    fn test_accept_roundtrip() {
+        let transfer_id = [3u8; 32];
+        let payload = encode_accept(&transfer_id);
+        assert_eq!(payload[0], CONTROL_TRANSFER_ACCEPT);
+
+        let (id, decision) = decode_reply(CONTROL_TRANSFER_ACCEPT, &payload[1..]).unwrap();
+        assert_eq!(id, transfer_id);
+        assert_eq!(decision, OfferDecision::Accept);
+    }
+
+    #[test]
+    fn test_reject_roundtrip_with_reason() {
+        let transfer_id = [9u8; 32];
+        let payload = encode_reject(&transfer_id, "disk full");
+        assert_eq!(payload[0], CONTROL_TRANSFER_REJECT);
+
+        let (id, decision) = decode_reply(CONTROL_TRANSFER_REJECT, &payload[1..]).unwrap();
+        assert_eq!(id, transfer_id);
+        assert_eq!(decision, OfferDecision::Reject("disk full".to_string()));
+    }
+
+    #[test]
+    fn test_reject_reason_truncated_on_wire() {
+        let transfer_id = [1u8; 32];
+        let long_reason = "x".repeat(2 * MAX_REJECT_REASON);
+        let payload = encode_reject(&transfer_id, &long_reason);
+
+        let (_, decision) = decode_reply(CONTROL_TRANSFER_REJECT, &payload[1..]).unwrap();
+        match decision {
+            OfferDecision::Reject(reason) => assert_eq!(reason.len(), MAX_REJECT_REASON),
+            OfferDecision::Accept => panic!("expected rejection"),
+        }
+    }
+
+    #[test]
+    fn test_decode_reply_unknown_tag() {
+        let payload = encode_accept(&[0u8; 32]);
+        assert!(decode_reply(0x7F, &payload[1..]).is_err());
+    }
+}
@@ -25,10 +25,102 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::{Mutex, RwLock, oneshot};
+use tracing::Instrument;
 use wraith_files::chunker::FileChunker;
-use wraith_transport::transport::Transport;
+use wraith_files::direct_io::DirectChunker;
+
+/// One recipient of a fan-out send
+///
+/// Produced per-peer by [`Node::send_file_to_many`] setup and consumed by
+/// [`Node::send_file_chunks_fanout`].
+pub(crate) struct FanOutRecipient {
+    /// Per-recipient transfer ID
+    pub(crate) transfer_id: crate::node::identity::TransferId,
+    /// Stream carrying this recipient's copy
+    pub(crate) stream_id: u16,
+    /// Established session with the recipient
+    pub(crate) connection: Arc<PeerConnection>,
+}
+
+/// Buffers kept ready for direct reads; sends read sequentially, so a
+/// small pool suffices
+const DIRECT_POOL_SIZE: usize = 4;
+
+/// Chunk reader for the send path
+///
+/// Files at or past the configured `direct_io_threshold` are read with
+/// `O_DIRECT` so a one-shot streaming send does not churn the page
+/// cache; everything else reads buffered. [`DirectChunker`] itself falls
+/// back to buffered reads when the filesystem rejects `O_DIRECT`, so
+/// callers never branch on the outcome.
+pub(crate) enum SendChunkReader {
+    /// Ordinary buffered reads through the page cache
+    Buffered(FileChunker),
+    /// Page-cache-bypassing reads from an aligned buffer pool
+    Direct(DirectChunker),
+}
+
+impl SendChunkReader {
+    /// Open `path`, selecting direct I/O when the file meets the threshold
+    pub(crate) fn open(
+        path: &std::path::Path,
+        chunk_size: usize,
+        direct_io_threshold: Option<u64>,
+    ) -> std::io::Result<Self> {
+        if let Some(threshold) = direct_io_threshold {
+            let file_size = std::fs::metadata(path)?.len();
+            if file_size >= threshold {
+                let chunker = DirectChunker::new(path, chunk_size, DIRECT_POOL_SIZE)?;
+                tracing::debug!(
+                    "Send path reading {} with {} I/O ({file_size} bytes)",
+                    path.display(),
+                    if chunker.is_direct() {
+                        "direct"
+                    } else {
+                        "buffered (O_DIRECT unavailable)"
+                    }
+                );
+                return Ok(Self::Direct(chunker));
+            }
+        }
+        Ok(Self::Buffered(FileChunker::new(path, chunk_size)?))
+    }
+
+    /// Total number of chunks in the file
+    pub(crate) fn num_chunks(&self) -> u64 {
+        match self {
+            Self::Buffered(chunker) => chunker.num_chunks(),
+            Self::Direct(chunker) => chunker.num_chunks(),
+        }
+    }
+
+    /// Read the chunk at the given index
+    pub(crate) fn read_chunk_at(&mut self, chunk_index: u64) -> std::io::Result<Vec<u8>> {
+        match self {
+            Self::Buffered(chunker) => chunker.read_chunk_at(chunk_index),
+            Self::Direct(chunker) => {
+                let buffer = chunker.read_chunk_at(chunk_index)?;
+                let data = buffer.as_slice().to_vec();
+                chunker.release_chunk(buffer);
+                Ok(data)
+            }
+        }
+    }
+}
 
 impl Node {
+    /// Generate a trace ID for an outgoing frame when frame tracing is on
+    ///
+    /// Returns `None` (no trace bytes, no log line) unless
+    /// `logging.trace_frames` is enabled in the node config.
+    pub(crate) fn next_trace_id(&self) -> Option<u32> {
+        self.inner
+            .config
+            .logging
+            .trace_frames
+            .then(crate::frame::random_trace_id)
+    }
+
     /// Packet receive loop - main event loop for incoming packets
     ///
     /// Continuously receives packets from the transport layer and dispatches
@@ -40,12 +132,9 @@ impl Node {
                 break;
             }
 
-            let transport = {
-                let guard = self.inner.transport.lock().await;
-                match guard.as_ref() {
-                    Some(t) => Arc::clone(t),
-                    None => break,
-                }
+            let transport = match self.inner.transport.get() {
+                Some(t) => t,
+                None => break,
             };
 
             match tokio::time::timeout(Duration::from_millis(100), transport.recv_from(&mut buf))
@@ -78,6 +167,10 @@ impl Node {
     pub(crate) async fn cover_traffic_loop(&self) {
         let config = &self.inner.config.obfuscation.cover_traffic;
         let rate = config.rate;
+        let mut ticker = self
+            .inner
+            .maintenance
+            .ticker("cover_traffic", Duration::from_secs(1));
 
         loop {
             if !self.is_running() {
@@ -103,7 +196,9 @@ impl Node {
                 }
             };
 
-            tokio::time::sleep(delay).await;
+            // Randomized cadence: the deadline slack scales with each
+            // tick's own delay
+            ticker.tick_after(delay).await;
 
             // Send cover traffic to all active sessions
             for entry in self.inner.sessions.iter() {
@@ -123,6 +218,17 @@ impl Node {
                     Err(_) => continue,
                 };
 
+                // Decoys run strictly below real data: only send when the
+                // cover budget and the real pacer have surplus tokens
+                if !self
+                    .inner
+                    .bandwidth_limiter
+                    .admit_cover(&connection.peer_id, frame_bytes.len() as u64)
+                {
+                    tracing::trace!("Cover frame skipped: no bandwidth surplus");
+                    continue;
+                }
+
                 let connection = Arc::clone(connection);
                 let node = self.clone();
                 tokio::spawn(async move {
@@ -145,6 +251,13 @@ impl Node {
 
         let source_ip = from.ip();
 
+        // Chaos: drop a configured fraction of incoming frames
+        #[cfg(feature = "chaos")]
+        if self.inner.chaos.should_drop_frame() {
+            tracing::trace!("Chaos: dropped incoming packet from {}", from);
+            return Ok(());
+        }
+
         // Check IP reputation
         if !self.inner.ip_reputation.check_allowed(source_ip).await {
             tracing::debug!("Blocked packet from banned IP: {}", source_ip);
@@ -212,6 +325,24 @@ impl Node {
                     conn.touch();
                     match conn.decrypt_frame(&unwrapped[8..]).await {
                         Ok(frame_bytes) => {
+                            // Successful decryption proves key possession. If the
+                            // packet arrived from a new address (e.g. mobile network
+                            // switch), validate the path instead of requiring a new
+                            // handshake; traffic moves once validation succeeds.
+                            if from != conn.peer_addr() {
+                                let node = self.clone();
+                                let peer_id = conn.peer_id;
+                                tokio::spawn(async move {
+                                    if let Err(e) = node.validate_migrated_path(peer_id, from).await
+                                    {
+                                        tracing::warn!(
+                                            "Path validation for migrated peer failed: {}",
+                                            e
+                                        );
+                                    }
+                                });
+                            }
+
                             let node = self.clone();
                             let peer_id = conn.peer_id;
                             tokio::spawn(async move {
@@ -251,20 +382,79 @@ impl Node {
         let frame = Frame::parse(&frame_bytes)
             .map_err(|e| NodeError::Other(format!("Failed to parse frame: {e}").into()))?;
 
-        match frame.frame_type() {
-            FrameType::StreamOpen => self.handle_stream_open_frame(frame).await,
-            FrameType::Data => self.handle_data_frame(frame).await,
+        let frame_type = frame.frame_type();
+
+        // Log sender-stamped trace IDs unconditionally: correlation needs
+        // the receive side to log even when it doesn't stamp its own frames
+        if let Some(trace) = frame.trace_id() {
+            tracing::debug!(
+                "Frame trace {:08x}: received {:?} stream {} seq {}",
+                trace,
+                frame_type,
+                frame.stream_id(),
+                frame.sequence()
+            );
+        }
+
+        // Chaos: kill this session at a random moment
+        #[cfg(feature = "chaos")]
+        if self.inner.chaos.should_kill_session() {
+            tracing::warn!("Chaos: killing session for peer {}", hex::encode(peer_id));
+            return self.close_session(&peer_id).await;
+        }
+
+        if let Some(connection) = self.inner.sessions.get(&peer_id) {
+            connection
+                .session
+                .write()
+                .await
+                .record_frame_received(frame_type);
+        }
+
+        let result = match frame_type {
+            FrameType::StreamOpen
+                if frame.payload() == crate::node::stream_api::PIPE_STREAM_MAGIC =>
+            {
+                self.handle_pipe_open(frame.stream_id(), peer_id)
+            }
+            FrameType::StreamOpen => self.handle_stream_open_frame(frame, peer_id).await,
+            FrameType::Data => self.handle_data_frame(frame, peer_id).await,
             FrameType::Pong => self.handle_pong_frame(frame, peer_id).await,
+            FrameType::Control => self.handle_control_frame(frame, peer_id).await,
+            FrameType::PathChallenge => self.handle_path_challenge_frame(frame, peer_id).await,
             FrameType::PathResponse => self.handle_path_response_frame(frame, peer_id).await,
+            FrameType::Ack => {
+                // Acknowledgement visibility for transfer traces; ACK
+                // content is processed by the congestion controller
+                tracing::trace!("Received ACK frame");
+                Ok(())
+            }
             FrameType::StreamClose => {
-                tracing::debug!("Received StreamClose frame");
+                if self.close_pipe_stream(frame.stream_id()) {
+                    tracing::debug!("Pipe stream {} closed by peer", frame.stream_id());
+                } else {
+                    tracing::debug!("Received StreamClose frame");
+                }
                 Ok(())
             }
             _ => {
-                tracing::debug!("Unhandled frame type: {:?}", frame.frame_type());
+                tracing::debug!("Unhandled frame type: {:?}", frame_type);
                 Ok(())
             }
+        };
+
+        // Count frames whose handler failed as dropped
+        if result.is_err()
+            && let Some(connection) = self.inner.sessions.get(&peer_id)
+        {
+            connection
+                .session
+                .write()
+                .await
+                .record_frame_dropped(frame_type);
         }
+
+        result
     }
 
     /// Handle handshake initiation (responder side)
@@ -297,6 +487,13 @@ impl Node {
             return Err(NodeError::Transport("Session limit exceeded".into()));
         }
 
+        // A first flight opening with an outstanding ticket ID resumes the
+        // session without any handshake round trip (see zero_rtt.rs);
+        // anything else proceeds as a Noise_XX initiation
+        if let Some(result) = self.handle_possible_0rtt(msg1, peer_addr).await {
+            return result;
+        }
+
         // Create channel for receiving msg3
         let (msg3_tx, msg3_rx) = oneshot::channel();
         self.inner.pending_handshakes.insert(peer_addr, msg3_tx);
@@ -327,6 +524,24 @@ impl Node {
             }
         };
 
+        // The handshake has authenticated the peer; consult the embedder's
+        // policy before admitting the session
+        let request = crate::node::authorizer::AuthRequest {
+            peer_id,
+            source_addr: Some(peer_addr),
+            action: crate::node::authorizer::AuthAction::SessionEstablish,
+        };
+        if let crate::node::authorizer::AuthDecision::Deny(reason) = self.authorize(&request).await
+        {
+            tracing::warn!(
+                "Session from {} ({}) denied by authorizer: {}",
+                hex::encode(&peer_id[..8]),
+                peer_addr,
+                reason
+            );
+            return Err(NodeError::AuthorizationDenied(reason.into()));
+        }
+
         // Derive connection ID from session ID
         let mut connection_id_bytes = [0u8; 8];
         connection_id_bytes.copy_from_slice(&session_id[..8]);
@@ -355,7 +570,9 @@ impl Node {
             .insert(peer_id, Arc::clone(&connection_arc));
 
         let cid_u64 = u64::from_be_bytes(connection_id_bytes);
-        self.inner.routing.add_route(cid_u64, connection_arc);
+        self.inner
+            .routing
+            .add_route(cid_u64, Arc::clone(&connection_arc));
 
         tracing::info!(
             "Session established as responder with peer {}, session: {}, route: {:016x}",
@@ -364,11 +581,30 @@ impl Node {
             cid_u64
         );
 
+        // Tell the peer where we see it (best-effort, feeds its endpoint list)
+        if let Err(e) = self.report_observed_address(&connection_arc).await {
+            tracing::debug!("Failed to report observed address: {e}");
+        }
+
+        // Pick up any transfer a previous session with this peer left
+        // unfinished (defined in resume.rs)
+        self.spawn_resume_check(peer_id);
+
+        // Hand the initiator a single-use ticket so its next connection
+        // can skip the handshake (no-op when 0-RTT is disabled)
+        if let Err(e) = self.issue_session_ticket(&connection_arc).await {
+            tracing::debug!("Failed to issue session ticket: {e}");
+        }
+
         Ok(session_id)
     }
 
     /// Handle StreamOpen frame (file transfer metadata)
-    pub(crate) async fn handle_stream_open_frame(&self, frame: Frame<'_>) -> Result<()> {
+    pub(crate) async fn handle_stream_open_frame(
+        &self,
+        frame: Frame<'_>,
+        peer_id: crate::node::session::PeerId,
+    ) -> Result<()> {
         let metadata = crate::node::file_transfer::FileMetadata::deserialize(frame.payload())?;
 
         tracing::info!(
@@ -377,6 +613,22 @@ impl Node {
             metadata.file_size
         );
 
+        // A checkpoint from an interrupted run means part of this file is
+        // already on disk; reopen it instead of truncating (see resume.rs)
+        let prior = match &self.inner.resume {
+            Some(resume) => resume
+                .load_state(&metadata.transfer_id)
+                .await
+                .ok()
+                .flatten()
+                .filter(|state| {
+                    !state.is_sender
+                        && state.peer_id == peer_id
+                        && state.file_hash == metadata.root_hash
+                }),
+            None => None,
+        };
+
         // Create receive transfer session
         let mut transfer = TransferSession::new_receive(
             metadata.transfer_id,
@@ -387,13 +639,59 @@ impl Node {
         transfer.start();
 
         // Create file reassembler
-        let reassembler = wraith_files::chunker::FileReassembler::new(
-            &metadata.file_name,
-            metadata.file_size,
-            metadata.chunk_size as usize,
-        )
+        let reassembler = match &prior {
+            Some(state) => {
+                tracing::info!(
+                    "Resuming receive of {}: {} of {} chunks already on disk",
+                    metadata.file_name,
+                    state.completed_chunks.len(),
+                    state.total_chunks
+                );
+                let received: std::collections::HashSet<u64> =
+                    state.completed_chunks.iter().map(|&i| i as u64).collect();
+                for &index in &state.completed_chunks {
+                    transfer.mark_chunk_transferred(
+                        index as u64,
+                        crate::node::resume::chunk_len(
+                            metadata.file_size,
+                            metadata.chunk_size as usize,
+                            index,
+                        ),
+                    );
+                }
+                wraith_files::chunker::FileReassembler::resume(
+                    &metadata.file_name,
+                    metadata.file_size,
+                    metadata.chunk_size as usize,
+                    &received,
+                )
+            }
+            None => wraith_files::chunker::FileReassembler::new(
+                &metadata.file_name,
+                metadata.file_size,
+                metadata.chunk_size as usize,
+            ),
+        }
         .map_err(|e| NodeError::Io(e.to_string()))?;
 
+        // Checkpoint fresh receives so they can resume in turn
+        if prior.is_none()
+            && let Some(resume) = &self.inner.resume
+        {
+            let state = crate::node::resume::ResumeState::new(
+                metadata.transfer_id,
+                peer_id,
+                metadata.root_hash,
+                metadata.file_size,
+                metadata.chunk_size as usize,
+                std::path::PathBuf::from(&metadata.file_name),
+                false,
+            );
+            if let Err(e) = resume.save_state(&state).await {
+                tracing::warn!("Failed to checkpoint transfer for resume: {}", e);
+            }
+        }
+
         // Create tree hash (root only for now)
         let tree_hash = wraith_files::tree_hash::FileTreeHash {
             root: metadata.root_hash,
@@ -406,12 +704,57 @@ impl Node {
             Arc::new(RwLock::new(transfer)),
             Arc::new(Mutex::new(reassembler)),
             tree_hash,
+            metadata.total_chunks,
         ));
         self.inner.transfers.insert(metadata.transfer_id, context);
 
         Ok(())
     }
 
+    /// Handle Control frame, dispatching on the payload's tag byte
+    ///
+    /// Offer-negotiation tags (OFFER/ACCEPT/REJECT) are routed to the offer
+    /// module and identity delegations to the delegation module; other tags
+    /// (metadata and chunk requests) keep their existing handling paths.
+    pub(crate) async fn handle_control_frame(
+        &self,
+        frame: Frame<'_>,
+        peer_id: crate::node::session::PeerId,
+    ) -> Result<()> {
+        let payload = frame.payload();
+        let Some((&tag, body)) = payload.split_first() else {
+            tracing::debug!("Received empty Control frame");
+            return Ok(());
+        };
+
+        match tag {
+            crate::node::offer::CONTROL_TRANSFER_OFFER => {
+                self.handle_transfer_offer(body, peer_id).await
+            }
+            crate::node::offer::CONTROL_TRANSFER_ACCEPT
+            | crate::node::offer::CONTROL_TRANSFER_REJECT => self.handle_offer_reply(tag, body),
+            crate::node::delegation::CONTROL_IDENTITY_DELEGATION => {
+                self.handle_identity_delegation(body, peer_id)
+            }
+            crate::node::observed::CONTROL_OBSERVED_ADDRESS => {
+                self.handle_observed_address(body, peer_id)
+            }
+            crate::node::resume::CONTROL_RESUME_QUERY => {
+                self.handle_resume_query(body, peer_id).await
+            }
+            crate::node::resume::CONTROL_RESUME_BITMAP => {
+                self.handle_resume_bitmap(body, peer_id).await
+            }
+            crate::node::zero_rtt::CONTROL_SESSION_TICKET => {
+                self.handle_session_ticket(body, peer_id)
+            }
+            other => {
+                tracing::debug!("Unhandled Control payload tag: {other:#04x}");
+                Ok(())
+            }
+        }
+    }
+
     /// Handle PONG frame (ping response)
     pub(crate) async fn handle_pong_frame(
         &self,
@@ -437,11 +780,20 @@ impl Node {
     }
 
     /// Handle Data frame (file chunk)
-    pub(crate) async fn handle_data_frame(&self, frame: Frame<'_>) -> Result<()> {
+    pub(crate) async fn handle_data_frame(
+        &self,
+        frame: Frame<'_>,
+        peer_id: crate::node::session::PeerId,
+    ) -> Result<()> {
         let chunk_index = frame.sequence() as u64;
         let chunk_data = frame.payload();
         let stream_id = frame.stream_id();
 
+        // Byte-stream pipes consume their Data frames directly
+        if self.deliver_pipe_data(stream_id, chunk_data) {
+            return Ok(());
+        }
+
         // Check if there's a pending chunk request waiting for this data
         let chunk_key = (stream_id, chunk_index);
         if let Some((_, sender)) = self.inner.pending_chunks.remove(&chunk_key) {
@@ -472,6 +824,31 @@ impl Node {
         })?;
         let transfer_id = context.transfer_id;
 
+        // Validate the chunk before it touches the reassembler: a corrupt
+        // chunk is never marked received, so it stays in the missing set
+        // and is re-requested instead of surfacing at finalize
+        let verdict = match &context.verifier {
+            Some(verifier) => verifier.lock().await.verify_chunk(chunk_index, chunk_data),
+            None => wraith_files::tree_hash::ChunkVerdict::Deferred,
+        };
+
+        if verdict == wraith_files::tree_hash::ChunkVerdict::Corrupt {
+            tracing::warn!(
+                "Chunk {} of transfer {} failed verification, requesting retransmission",
+                chunk_index,
+                hex::encode(&transfer_id[..8])
+            );
+            if let Err(e) = self
+                .request_chunk_retransmission(&peer_id, &transfer_id, chunk_index)
+                .await
+            {
+                tracing::debug!("Failed to request chunk retransmission: {e}");
+            }
+            return Err(NodeError::InvalidState(
+                "Chunk hash verification failed".into(),
+            ));
+        }
+
         // Write chunk to reassembler
         if let Some(reassembler_arc) = &context.reassembler {
             reassembler_arc
@@ -481,21 +858,37 @@ impl Node {
                 .map_err(|e| NodeError::Io(e.to_string()))?;
         }
 
-        // Verify chunk hash if available
-        if chunk_index < context.tree_hash.chunks.len() as u64 {
-            let computed_hash = blake3::hash(chunk_data);
-            if computed_hash.as_bytes() != &context.tree_hash.chunks[chunk_index as usize] {
-                return Err(NodeError::InvalidState(
-                    "Chunk hash verification failed".into(),
-                ));
-            }
-        }
-
         // Update transfer progress
         let mut transfer = context.transfer_session.write().await;
         transfer.mark_chunk_transferred(chunk_index, chunk_data.len());
 
         if transfer.is_complete() {
+            // Without a manifest, verification was deferred per chunk; the
+            // root check happens here, the moment the last chunk lands
+            if let Some(verifier) = &context.verifier
+                && verifier.lock().await.root_valid() == Some(false)
+            {
+                return Err(NodeError::InvalidState(
+                    format!(
+                        "Root hash mismatch for transfer {}",
+                        hex::encode(&transfer_id[..8])
+                    )
+                    .into(),
+                ));
+            }
+
+            // Persist the sender-signed manifest next to the file, so
+            // provenance can be re-checked later with `wraith verify`
+            if let Some((_, manifest)) = self.inner.transfer_manifests.remove(&transfer_id) {
+                let sidecar = crate::node::offer::manifest_sidecar_path(&transfer.file_path);
+                if let Err(e) = std::fs::write(&sidecar, manifest.serialize()) {
+                    tracing::warn!(
+                        "Failed to store transfer manifest at {}: {e}",
+                        sidecar.display()
+                    );
+                }
+            }
+
             tracing::info!(
                 "File transfer {:?} completed ({} bytes)",
                 hex::encode(&transfer_id[..8]),
@@ -503,9 +896,202 @@ impl Node {
             );
         }
 
+        // Keep the persisted checkpoint in step with what is on disk
+        let is_complete = transfer.is_complete();
+        drop(transfer);
+        if is_complete {
+            self.clear_resume_state(&transfer_id).await;
+        } else {
+            self.record_resume_chunk(&transfer_id, chunk_index).await;
+        }
+
         Ok(())
     }
 
+    /// Ask a peer to retransmit a chunk that failed verification
+    ///
+    /// Sends the same chunk-request Control frame used by multi-peer
+    /// downloads; the retransmitted Data frame flows back through
+    /// [`Node::handle_data_frame`] and is verified again on arrival.
+    async fn request_chunk_retransmission(
+        &self,
+        peer_id: &crate::node::session::PeerId,
+        transfer_id: &[u8; 32],
+        chunk_index: u64,
+    ) -> Result<()> {
+        let connection = self
+            .inner
+            .sessions
+            .get(peer_id)
+            .map(|entry| Arc::clone(entry.value()))
+            .ok_or(NodeError::SessionNotFound(*peer_id))?;
+
+        let stream_id = ((transfer_id[0] as u16) << 8) | (transfer_id[1] as u16);
+
+        // Payload format: request_type(1) + transfer_id(32) + chunk_index(8)
+        let mut payload = Vec::with_capacity(41);
+        payload.push(0x02);
+        payload.extend_from_slice(transfer_id);
+        payload.extend_from_slice(&chunk_index.to_be_bytes());
+
+        let frame = FrameBuilder::new()
+            .frame_type(FrameType::Control)
+            .stream_id(stream_id)
+            .sequence(chunk_index as u32)
+            .payload(&payload)
+            .build(crate::FRAME_HEADER_SIZE + payload.len())
+            .map_err(|e| {
+                NodeError::InvalidState(format!("Failed to build chunk request: {e}").into())
+            })?;
+
+        self.send_encrypted_frame(&connection, &frame).await
+    }
+
+    /// Handle PATH_CHALLENGE frame (connection migration)
+    ///
+    /// Echoes the 8-byte challenge back in a PATH_RESPONSE frame so the peer
+    /// can validate this path.
+    pub(crate) async fn handle_path_challenge_frame(
+        &self,
+        frame: Frame<'_>,
+        peer_id: crate::node::session::PeerId,
+    ) -> Result<()> {
+        let challenge_data = frame.payload();
+        if challenge_data.len() != 8 {
+            tracing::warn!(
+                "Invalid PATH_CHALLENGE payload length: {} (expected 8)",
+                challenge_data.len()
+            );
+            return Ok(());
+        }
+
+        let connection = self
+            .inner
+            .sessions
+            .get(&peer_id)
+            .map(|entry| entry.value().clone())
+            .ok_or(NodeError::SessionNotFound(peer_id))?;
+
+        let response = FrameBuilder::new()
+            .frame_type(FrameType::PathResponse)
+            .stream_id(0) // Connection-level
+            .sequence(0)
+            .payload(challenge_data)
+            .build(128)
+            .map_err(|e| {
+                NodeError::Migration(format!("Failed to build PATH_RESPONSE: {e}").into())
+            })?;
+
+        tracing::debug!("Echoing PATH_CHALLENGE as PATH_RESPONSE");
+        self.send_encrypted_frame(&connection, &response).await
+    }
+
+    /// Validate a peer's new source address after an apparent migration
+    ///
+    /// Called when an authenticated packet for a known connection ID arrives
+    /// from an unknown address. Sends a PATH_CHALLENGE to the new address and,
+    /// once the matching PATH_RESPONSE arrives, moves the session's peer
+    /// address - no new handshake required.
+    pub(crate) async fn validate_migrated_path(
+        &self,
+        peer_id: crate::node::session::PeerId,
+        new_addr: SocketAddr,
+    ) -> Result<()> {
+        use crate::node::node::MigrationState;
+
+        let connection = self
+            .inner
+            .sessions
+            .get(&peer_id)
+            .map(|entry| entry.value().clone())
+            .ok_or(NodeError::SessionNotFound(peer_id))?;
+
+        // Generate path ID from the new address (simple hash)
+        let path_id = {
+            use std::collections::hash_map::DefaultHasher;
+            use std::hash::{Hash, Hasher};
+            let mut hasher = DefaultHasher::new();
+            new_addr.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        // Challenge already in flight for this address - nothing to do
+        if self.inner.pending_migrations.contains_key(&path_id) {
+            return Ok(());
+        }
+
+        tracing::info!(
+            "Peer {:?} appears to have migrated to {}, validating path",
+            hex::encode(&peer_id[..8]),
+            new_addr
+        );
+
+        let mut challenge = [0u8; 8];
+        getrandom::getrandom(&mut challenge)
+            .map_err(|e| NodeError::Other(format!("getrandom failed: {e}").into()))?;
+
+        let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+        let migration_state = MigrationState {
+            peer_id,
+            new_addr,
+            challenge,
+            sender: response_tx,
+            initiated_at: std::time::Instant::now(),
+        };
+        self.inner
+            .pending_migrations
+            .insert(path_id, migration_state);
+
+        // Build PATH_CHALLENGE frame
+        let frame = FrameBuilder::new()
+            .frame_type(FrameType::PathChallenge)
+            .stream_id(0) // Connection-level
+            .sequence(0)
+            .payload(&challenge)
+            .build(128)
+            .map_err(|e| {
+                NodeError::Migration(format!("Failed to build PATH_CHALLENGE: {e}").into())
+            })?;
+
+        // Send to the claimed new address in the full outer packet format,
+        // so the peer can route the challenge by Connection ID
+        if let Err(e) = self.send_wrapped_to(&connection, &frame, new_addr).await {
+            self.inner.pending_migrations.remove(&path_id);
+            return Err(NodeError::Migration(
+                format!("Failed to send PATH_CHALLENGE: {e}").into(),
+            ));
+        }
+
+        // Wait for PATH_RESPONSE with timeout
+        let timeout = Duration::from_secs(5);
+        match tokio::time::timeout(timeout, response_rx).await {
+            Ok(Ok(Ok(latency))) => {
+                connection.update_peer_addr(new_addr);
+                connection.touch();
+                tracing::info!(
+                    "Session migrated to {} after path validation ({}µs RTT)",
+                    new_addr,
+                    latency.as_micros()
+                );
+                Ok(())
+            }
+            Ok(Ok(Err(e))) => {
+                tracing::warn!("Migrated path validation failed: {}", e);
+                Err(e)
+            }
+            Ok(Err(_)) => {
+                self.inner.pending_migrations.remove(&path_id);
+                Err(NodeError::Migration(
+                    "Path validation channel closed".into(),
+                ))
+            }
+            Err(_) => {
+                self.inner.pending_migrations.remove(&path_id);
+                Err(NodeError::Timeout("Path validation timed out".into()))
+            }
+        }
+    }
+
     /// Handle PATH_RESPONSE frame (connection migration)
     pub(crate) async fn handle_path_response_frame(
         &self,
@@ -579,19 +1165,129 @@ impl Node {
             .ok_or(NodeError::TransferNotFound(transfer_id))?
             .clone();
 
-        let mut chunker = FileChunker::new(&file_path, self.inner.config.transfer.chunk_size)
-            .map_err(|e| NodeError::Io(e.to_string()))?;
+        let mut chunker = SendChunkReader::open(
+            &file_path,
+            self.inner.config.transfer.chunk_size,
+            self.inner.config.transfer.direct_io_threshold,
+        )
+        .map_err(|e| NodeError::Io(e.to_string()))?;
 
         let total_chunks = chunker.num_chunks();
 
+        let transfer_start = std::time::Instant::now();
+        let mut bytes_sent: u64 = 0;
+
+        for chunk_index in 0..total_chunks {
+            // One span per chunk: read, verify, framing, and the nested
+            // encryption/transmission spans of send_encrypted_frame
+            let chunk_span = tracing::trace_span!("chunk", index = chunk_index);
+            async {
+                let chunk_data = chunker
+                    .read_chunk_at(chunk_index)
+                    .map_err(|e| NodeError::Io(e.to_string()))?;
+                let chunk_len = chunk_data.len();
+
+                // Verify chunk hash
+                if chunk_index < context.tree_hash.chunks.len() as u64 {
+                    let computed_hash = blake3::hash(&chunk_data);
+                    if computed_hash.as_bytes() != &context.tree_hash.chunks[chunk_index as usize] {
+                        return Err(NodeError::InvalidState(
+                            "Chunk hash verification failed".into(),
+                        ));
+                    }
+                }
+
+                // Build and send chunk frame
+                let chunk_frame = crate::node::file_transfer::build_chunk_frame(
+                    stream_id,
+                    chunk_index,
+                    &chunk_data,
+                    self.next_trace_id(),
+                )?;
+
+                // Honor any per-transfer bandwidth cap before the global and
+                // per-session caps applied inside send_encrypted_frame
+                self.inner
+                    .bandwidth_limiter
+                    .acquire_transfer(&transfer_id, chunk_frame.len() as u64)
+                    .await;
+
+                self.send_encrypted_frame(&connection, &chunk_frame).await?;
+
+                // Update progress
+                context
+                    .transfer_session
+                    .write()
+                    .await
+                    .mark_chunk_transferred(chunk_index, chunk_len);
+                self.record_resume_chunk(&transfer_id, chunk_index).await;
+                bytes_sent += chunk_len as u64;
+                Ok(())
+            }
+            .instrument(chunk_span)
+            .await?;
+
+            // Keep a careful-resume seed current: an abrupt drop
+            // mid-transfer leaves the path's delivered rate behind for
+            // the next session to this peer
+            if (chunk_index + 1) % crate::node::path_estimates::PATH_ESTIMATE_RECORD_INTERVAL == 0 {
+                self.record_path_estimate(&connection, bytes_sent, transfer_start);
+            }
+        }
+
+        tracing::info!(
+            "File transfer {:?} completed ({} chunks sent)",
+            hex::encode(&transfer_id[..8]),
+            total_chunks
+        );
+        self.record_path_estimate(&connection, bytes_sent, transfer_start);
+        self.clear_resume_state(&transfer_id).await;
+
+        Ok(())
+    }
+
+    /// Send the same file to several recipients with shared chunk reads
+    ///
+    /// Fan-out body of [`Node::send_file_to_many`]: each chunk is read and
+    /// hash-verified once, then framed and sent to every recipient still
+    /// standing. A recipient whose send fails is dropped from the fan-out
+    /// with a warning while the others continue; the loop ends early only
+    /// when no recipients remain.
+    pub(crate) async fn send_file_chunks_fanout(
+        &self,
+        file_path: std::path::PathBuf,
+        recipients: Vec<FanOutRecipient>,
+    ) -> Result<()> {
+        let mut chunker = SendChunkReader::open(
+            &file_path,
+            self.inner.config.transfer.chunk_size,
+            self.inner.config.transfer.direct_io_threshold,
+        )
+        .map_err(|e| NodeError::Io(e.to_string()))?;
+        let total_chunks = chunker.num_chunks();
+
+        // Pair each recipient with its transfer context up front
+        let mut active: Vec<(FanOutRecipient, Arc<FileTransferContext>)> = Vec::new();
+        for recipient in recipients {
+            let context = self
+                .inner
+                .transfers
+                .get(&recipient.transfer_id)
+                .ok_or(NodeError::TransferNotFound(recipient.transfer_id))?
+                .clone();
+            active.push((recipient, context));
+        }
+
         for chunk_index in 0..total_chunks {
             let chunk_data = chunker
                 .read_chunk_at(chunk_index)
                 .map_err(|e| NodeError::Io(e.to_string()))?;
             let chunk_len = chunk_data.len();
 
-            // Verify chunk hash
-            if chunk_index < context.tree_hash.chunks.len() as u64 {
+            // Verify the chunk once; the tree hash is shared by all recipients
+            if let Some((_, context)) = active.first()
+                && chunk_index < context.tree_hash.chunks.len() as u64
+            {
                 let computed_hash = blake3::hash(&chunk_data);
                 if computed_hash.as_bytes() != &context.tree_hash.chunks[chunk_index as usize] {
                     return Err(NodeError::InvalidState(
@@ -600,46 +1296,141 @@ impl Node {
                 }
             }
 
-            // Build and send chunk frame
-            let chunk_frame =
-                crate::node::file_transfer::build_chunk_frame(stream_id, chunk_index, &chunk_data)?;
-
-            self.send_encrypted_frame(&connection, &chunk_frame).await?;
+            let mut still_active = Vec::with_capacity(active.len());
+            for (recipient, context) in active {
+                let chunk_frame = crate::node::file_transfer::build_chunk_frame(
+                    recipient.stream_id,
+                    chunk_index,
+                    &chunk_data,
+                    self.next_trace_id(),
+                )?;
+
+                self.inner
+                    .bandwidth_limiter
+                    .acquire_transfer(&recipient.transfer_id, chunk_frame.len() as u64)
+                    .await;
+
+                match self
+                    .send_encrypted_frame(&recipient.connection, &chunk_frame)
+                    .await
+                {
+                    Ok(()) => {
+                        context
+                            .transfer_session
+                            .write()
+                            .await
+                            .mark_chunk_transferred(chunk_index, chunk_len);
+                        still_active.push((recipient, context));
+                    }
+                    Err(e) => {
+                        tracing::warn!(
+                            "Dropping recipient {} from fan-out at chunk {}: {}",
+                            hex::encode(&recipient.connection.peer_id[..8]),
+                            chunk_index,
+                            e
+                        );
+                    }
+                }
+            }
+            active = still_active;
 
-            // Update progress
-            context
-                .transfer_session
-                .write()
-                .await
-                .mark_chunk_transferred(chunk_index, chunk_len);
+            if active.is_empty() {
+                return Err(NodeError::Transfer(
+                    "All fan-out recipients failed mid-transfer".into(),
+                ));
+            }
         }
 
         tracing::info!(
-            "File transfer {:?} completed ({} chunks sent)",
-            hex::encode(&transfer_id[..8]),
-            total_chunks
+            "Fan-out transfer completed ({} chunks to {} recipients)",
+            total_chunks,
+            active.len()
         );
-
         Ok(())
     }
 
     /// Send encrypted frame to peer
+    ///
+    /// Frames pass through the connection's prioritized
+    /// [`SendQueue`](crate::send_queue::SendQueue) before transmission, so
+    /// latency-sensitive control traffic (ACKs, rekeys, path validation)
+    /// queued by any task bypasses a bulk DATA backlog instead of waiting
+    /// behind it. PAD cover frames are shed silently under backpressure;
+    /// a full lane for anything else surfaces as a transport error.
     #[allow(dead_code)]
     pub(crate) async fn send_encrypted_frame(
         &self,
         connection: &PeerConnection,
         frame_bytes: &[u8],
     ) -> Result<()> {
-        // Encrypt the frame
-        let encrypted = connection.encrypt_frame(frame_bytes).await?;
-        let encrypted_len = encrypted.len();
+        use crate::send_queue::FramePriority;
+
+        if !connection.enqueue_outbound(frame_bytes.to_vec()) {
+            let priority = frame_bytes
+                .get(8)
+                .and_then(|b| FrameType::try_from(*b).ok())
+                .map_or(FramePriority::Control, FramePriority::from);
+            if priority == FramePriority::Background {
+                tracing::trace!("Outbound queue full, shedding cover frame");
+                return Ok(());
+            }
+            return Err(NodeError::Transport(
+                "Outbound send queue full (backpressure)".into(),
+            ));
+        }
 
-        // Apply padding obfuscation
-        let mut obfuscated = encrypted;
-        self.apply_obfuscation(&mut obfuscated)?;
+        // Drain the queue in priority order. Concurrent senders may drain
+        // each other's frames; errors are attributed to whichever caller
+        // hit them, which keeps per-transfer failure handling intact since
+        // the frames share a connection.
+        while let Some(frame) = connection.pop_outbound() {
+            self.transmit_frame(connection, &frame).await?;
+        }
+        Ok(())
+    }
 
-        // Wrap in protocol mimicry (if enabled)
-        let wrapped = self.wrap_protocol(&obfuscated)?;
+    /// Transmit one frame: statistics, multipath striping, bandwidth caps,
+    /// timing delay, then encryption and the wire (dequeued frames only -
+    /// everything else goes through
+    /// [`send_encrypted_frame`](Self::send_encrypted_frame))
+    async fn transmit_frame(&self, connection: &PeerConnection, frame_bytes: &[u8]) -> Result<()> {
+        // Record per-frame-type statistics (frame type lives at byte 8 of the header)
+        // and stripe DATA frames across validated multipath addresses.
+        let mut target_addr = connection.peer_addr();
+        let mut is_data_frame = false;
+        if let Some(type_byte) = frame_bytes.get(8)
+            && let Ok(frame_type) = FrameType::try_from(*type_byte)
+        {
+            let mut session = connection.session.write().await;
+            session.record_frame_sent(frame_type);
+
+            if frame_type == FrameType::Data
+                && let Some(path_id) = session.multipath_mut().schedule(frame_bytes.len() as u64)
+                && let Some(path_addr) = connection.path_addr(path_id)
+            {
+                target_addr = path_addr;
+            }
+            is_data_frame = frame_type == FrameType::Data;
+
+            // Chaos: delay outgoing ACK frames
+            #[cfg(feature = "chaos")]
+            if frame_type == FrameType::Ack
+                && let Some(delay) = self.inner.chaos.ack_delay()
+            {
+                drop(session);
+                tracing::trace!("Chaos: delaying ACK by {:?}", delay);
+                tokio::time::sleep(delay).await;
+            }
+        }
+
+        // Apply global and per-session bandwidth caps to bulk data (control
+        // frames are exempt so ACKs and path validation are never delayed)
+        if is_data_frame && !self.inner.bandwidth_limiter.is_unlimited() {
+            self.inner
+                .bandwidth_limiter
+                .acquire(&connection.peer_id, frame_bytes.len() as u64)
+                .await;
+        }
 
         // Apply timing delay
         let delay = self.get_timing_delay();
@@ -647,17 +1438,57 @@ impl Node {
             tokio::time::sleep(delay).await;
         }
 
+        self.send_wrapped_to(connection, frame_bytes, target_addr)
+            .await
+    }
+
+    /// Encrypt a frame and send it to an explicit address in the full outer
+    /// packet format (Connection-ID prefix, padding obfuscation, protocol
+    /// wrap) so the receiver can route it by Connection ID
+    ///
+    /// [`send_encrypted_frame`](Self::send_encrypted_frame) targets the
+    /// connection's current (or multipath-scheduled) address; path validation
+    /// uses this directly to probe an address the session has not moved to
+    /// yet. No timing delay is applied here, keeping challenge RTT
+    /// measurements honest.
+    pub(crate) async fn send_wrapped_to(
+        &self,
+        connection: &PeerConnection,
+        frame_bytes: &[u8],
+        addr: SocketAddr,
+    ) -> Result<()> {
+        // Encrypt the frame
+        let encrypted = connection
+            .encrypt_frame(frame_bytes)
+            .instrument(tracing::trace_span!("encrypt", len = frame_bytes.len()))
+            .await?;
+        let encrypted_len = encrypted.len();
+
+        // Prefix the Connection ID so the receiver can route the packet
+        // (outer packet format: 8B CID + ciphertext + auth tag)
+        let mut packet = Vec::with_capacity(8 + encrypted.len());
+        packet.extend_from_slice(&connection.connection_id.to_bytes());
+        packet.extend_from_slice(&encrypted);
+
+        // Apply padding obfuscation and protocol mimicry (if enabled)
+        let wrapped = tracing::trace_span!("obfuscate").in_scope(|| {
+            let mut obfuscated = packet;
+            self.apply_obfuscation(&mut obfuscated)?;
+            self.wrap_protocol(&obfuscated)
+        })?;
+
         // Send via transport
         let transport = self.get_transport().await?;
         transport
-            .send_to(&wrapped, connection.peer_addr())
+            .send_to(&wrapped, addr)
+            .instrument(tracing::trace_span!("transmit", addr = %addr))
             .await
             .map_err(|e| NodeError::Transport(format!("Failed to send packet: {e}").into()))?;
 
         tracing::trace!(
             "Sent {} obfuscated bytes to {} (original: {} encrypted)",
             wrapped.len(),
-            connection.peer_addr(),
+            addr,
             encrypted_len
         );
 
@@ -669,10 +1500,79 @@ impl Node {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_send_chunk_reader_threshold_selects_direct() {
+        use std::io::Write;
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("payload.bin");
+        let data: Vec<u8> = (0..10_000).map(|i| (i % 251) as u8).collect();
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(&data)
+            .unwrap();
+
+        // Below the threshold: buffered
+        let reader = SendChunkReader::open(&path, 4096, Some(1024 * 1024)).unwrap();
+        assert!(matches!(reader, SendChunkReader::Buffered(_)));
+
+        // At or past the threshold: direct, with identical chunk contents
+        let mut direct = SendChunkReader::open(&path, 4096, Some(1)).unwrap();
+        assert!(matches!(direct, SendChunkReader::Direct(_)));
+        let mut buffered = SendChunkReader::open(&path, 4096, None).unwrap();
+        assert_eq!(direct.num_chunks(), buffered.num_chunks());
+        for index in 0..direct.num_chunks() {
+            assert_eq!(
+                direct.read_chunk_at(index).unwrap(),
+                buffered.read_chunk_at(index).unwrap()
+            );
+        }
+    }
+
     #[test]
     fn test_cover_traffic_distribution_constant() {
         let rate = 10.0; // 10 packets per second
         let expected_delay = Duration::from_secs_f64(1.0 / rate);
         assert_eq!(expected_delay, Duration::from_millis(100));
     }
+
+    #[tokio::test]
+    async fn test_validate_migrated_path_roundtrip() {
+        use crate::node::NodeConfig;
+
+        let config = || NodeConfig {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            ..Default::default()
+        };
+        let a = crate::node::Node::new_with_config(config()).await.unwrap();
+        let b = crate::node::Node::new_with_config(config()).await.unwrap();
+        a.start().await.unwrap();
+        b.start().await.unwrap();
+
+        let b_addr = b.listen_addr().await.unwrap();
+        a.establish_session_with_addr(b.node_id(), b_addr)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // b's view of a, at a's actual socket address
+        let conn = b
+            .inner
+            .sessions
+            .get(a.x25519_public_key())
+            .map(|entry| entry.value().clone())
+            .unwrap();
+        let real_addr = conn.peer_addr();
+
+        // Pretend a used to live somewhere else, then validate the "new"
+        // (real) address: the PATH_CHALLENGE must reach a over the wire and
+        // the echoed PATH_RESPONSE must move the session
+        conn.update_peer_addr("127.0.0.1:9".parse().unwrap());
+        b.validate_migrated_path(*a.x25519_public_key(), real_addr)
+            .await
+            .unwrap();
+        assert_eq!(conn.peer_addr(), real_addr);
+
+        a.stop().await.unwrap();
+        b.stop().await.unwrap();
+    }
 }
@@ -0,0 +1,278 @@
+//! Short-horizon congestion estimates per peer path
+//!
+//! Caches the bandwidth and RTT measured toward a peer at a specific
+//! address so a session re-established over the same path shortly after a
+//! drop can seed its congestion state careful-resume style
+//! ([`BbrState::resume_from`](crate::congestion::BbrState::resume_from),
+//! [`ChunkBatcher::seed`](crate::transfer::ChunkBatcher::seed)) instead of
+//! slow-starting from scratch.
+//!
+//! Unlike [`peer_history`](crate::node::peer_history), which smooths
+//! measurements over weeks to rank swarm sources, entries here describe one
+//! concrete path at one moment and expire after
+//! [`PATH_ESTIMATE_FRESHNESS`]: a path that has been quiet longer than that
+//! may have changed (route, cross traffic, radio conditions), so a late
+//! reconnect re-probes from scratch rather than trusting a stale seed.
+
+use dashmap::DashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use crate::congestion::CongestionSnapshot;
+use crate::node::Node;
+use crate::node::session::{PeerConnection, PeerId};
+
+/// How long a recorded estimate stays trustworthy
+///
+/// A reconnect within this window counts as "shortly after a drop" and is
+/// seeded from the cached estimates; anything later starts cold.
+pub const PATH_ESTIMATE_FRESHNESS: Duration = Duration::from_secs(60);
+
+/// Chunks sent between estimate recordings in the send loops
+///
+/// Keeps the cache current enough to survive an abrupt drop without putting
+/// a map write on every chunk.
+pub(crate) const PATH_ESTIMATE_RECORD_INTERVAL: u64 = 32;
+
+/// Fallback RTT for entries recorded before any RTT measurement
+///
+/// Matches the congestion controllers' initial estimate so a seed without
+/// an RTT sample behaves like a cold controller on that axis.
+const DEFAULT_RTT: Duration = Duration::from_millis(100);
+
+/// In-memory cache of congestion estimates keyed by peer path
+///
+/// Entries are written by the send loops (delivered bandwidth) and the ping
+/// path (measured RTT) and read back when a transfer starts toward a peer
+/// whose previous session dropped moments ago. The cache is deliberately
+/// not persisted: a process restart invalidates the "same path, moments
+/// later" assumption the seed relies on.
+pub struct PathEstimateCache {
+    /// Latest snapshot per (peer, remote address)
+    entries: DashMap<(PeerId, SocketAddr), CongestionSnapshot>,
+}
+
+impl PathEstimateCache {
+    /// Create an empty cache
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+        }
+    }
+
+    /// Record a delivered-bandwidth measurement for a peer path
+    ///
+    /// `bandwidth_bps` of zero is ignored (no measurement yet). When `rtt`
+    /// is `None` the entry keeps its previous RTT, falling back to the
+    /// controllers' initial estimate for a brand-new entry. Stale entries
+    /// for other paths are pruned opportunistically.
+    pub fn record(
+        &self,
+        peer_id: PeerId,
+        addr: SocketAddr,
+        bandwidth_bps: u64,
+        rtt: Option<Duration>,
+    ) {
+        if bandwidth_bps == 0 {
+            return;
+        }
+
+        let min_rtt = rtt
+            .or_else(|| self.entries.get(&(peer_id, addr)).map(|e| e.min_rtt))
+            .unwrap_or(DEFAULT_RTT);
+
+        self.entries.insert(
+            (peer_id, addr),
+            CongestionSnapshot {
+                btl_bw: bandwidth_bps,
+                min_rtt,
+                taken_at: Instant::now(),
+            },
+        );
+
+        self.entries
+            .retain(|_, snapshot| snapshot.is_fresh(PATH_ESTIMATE_FRESHNESS));
+    }
+
+    /// Fold a measured RTT into an existing entry
+    ///
+    /// RTT alone does not create an entry (a seed without a bandwidth
+    /// estimate is useless) and does not extend an entry's freshness: a
+    /// keepalive ping proves the path is alive, not that the cached
+    /// bandwidth still holds.
+    pub fn record_rtt(&self, peer_id: PeerId, addr: SocketAddr, rtt: Duration) {
+        if let Some(mut entry) = self.entries.get_mut(&(peer_id, addr)) {
+            entry.min_rtt = entry.min_rtt.min(rtt);
+        }
+    }
+
+    /// Look up a still-fresh estimate for a peer path
+    ///
+    /// Returns `None` (and evicts the entry) once the snapshot is older
+    /// than [`PATH_ESTIMATE_FRESHNESS`].
+    #[must_use]
+    pub fn fresh(&self, peer_id: PeerId, addr: SocketAddr) -> Option<CongestionSnapshot> {
+        let key = (peer_id, addr);
+        let snapshot = *self.entries.get(&key)?;
+        if snapshot.is_fresh(PATH_ESTIMATE_FRESHNESS) {
+            Some(snapshot)
+        } else {
+            self.entries.remove(&key);
+            None
+        }
+    }
+
+    /// Number of cached paths
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the cache holds no entries
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl Default for PathEstimateCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Node {
+    /// Record the delivered send rate over a connection's path
+    ///
+    /// Called periodically from the chunk-send loops so an abrupt drop
+    /// leaves a recent estimate behind for the next session to this peer.
+    /// `bytes_sent` is the total delivered since `since`.
+    #[allow(clippy::cast_precision_loss)]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    pub(crate) fn record_path_estimate(
+        &self,
+        connection: &PeerConnection,
+        bytes_sent: u64,
+        since: Instant,
+    ) {
+        let elapsed = since.elapsed().as_secs_f64();
+        if elapsed <= 0.0 {
+            return;
+        }
+
+        let bandwidth = (bytes_sent as f64 / elapsed) as u64;
+        let rtt = connection.stats.rtt_us.map(Duration::from_micros);
+        self.inner.path_estimates.record(
+            connection.peer_id,
+            connection.peer_addr(),
+            bandwidth,
+            rtt,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(port: u16) -> SocketAddr {
+        format!("127.0.0.1:{port}").parse().unwrap()
+    }
+
+    #[test]
+    fn test_record_and_fresh_lookup() {
+        let cache = PathEstimateCache::new();
+        cache.record(
+            [1u8; 32],
+            addr(9000),
+            10_000_000,
+            Some(Duration::from_millis(40)),
+        );
+
+        let snapshot = cache.fresh([1u8; 32], addr(9000)).unwrap();
+        assert_eq!(snapshot.btl_bw, 10_000_000);
+        assert_eq!(snapshot.min_rtt, Duration::from_millis(40));
+    }
+
+    #[test]
+    fn test_paths_are_distinct() {
+        let cache = PathEstimateCache::new();
+        cache.record([1u8; 32], addr(9000), 10_000_000, None);
+
+        // Same peer over a different path is a different entry
+        assert!(cache.fresh([1u8; 32], addr(9001)).is_none());
+        assert!(cache.fresh([2u8; 32], addr(9000)).is_none());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_zero_bandwidth_is_ignored() {
+        let cache = PathEstimateCache::new();
+        cache.record([1u8; 32], addr(9000), 0, Some(Duration::from_millis(40)));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_record_without_rtt_uses_default_then_keeps_previous() {
+        let cache = PathEstimateCache::new();
+        cache.record([1u8; 32], addr(9000), 10_000_000, None);
+        assert_eq!(
+            cache.fresh([1u8; 32], addr(9000)).unwrap().min_rtt,
+            DEFAULT_RTT
+        );
+
+        cache.record(
+            [1u8; 32],
+            addr(9000),
+            12_000_000,
+            Some(Duration::from_millis(20)),
+        );
+        cache.record([1u8; 32], addr(9000), 14_000_000, None);
+
+        let snapshot = cache.fresh([1u8; 32], addr(9000)).unwrap();
+        assert_eq!(snapshot.btl_bw, 14_000_000);
+        assert_eq!(snapshot.min_rtt, Duration::from_millis(20));
+    }
+
+    #[test]
+    fn test_record_rtt_only_updates_existing_entries() {
+        let cache = PathEstimateCache::new();
+
+        // No entry: RTT alone must not create a seed
+        cache.record_rtt([1u8; 32], addr(9000), Duration::from_millis(10));
+        assert!(cache.is_empty());
+
+        cache.record(
+            [1u8; 32],
+            addr(9000),
+            10_000_000,
+            Some(Duration::from_millis(40)),
+        );
+
+        // Lower measurement tightens the minimum; higher is ignored
+        cache.record_rtt([1u8; 32], addr(9000), Duration::from_millis(10));
+        cache.record_rtt([1u8; 32], addr(9000), Duration::from_millis(80));
+        assert_eq!(
+            cache.fresh([1u8; 32], addr(9000)).unwrap().min_rtt,
+            Duration::from_millis(10)
+        );
+    }
+
+    #[test]
+    fn test_stale_entries_are_evicted_on_lookup() {
+        let cache = PathEstimateCache::new();
+        cache.entries.insert(
+            ([1u8; 32], addr(9000)),
+            CongestionSnapshot {
+                btl_bw: 10_000_000,
+                min_rtt: Duration::from_millis(40),
+                taken_at: Instant::now() - PATH_ESTIMATE_FRESHNESS - Duration::from_secs(1),
+            },
+        );
+
+        assert!(cache.fresh([1u8; 32], addr(9000)).is_none());
+        assert!(cache.is_empty());
+    }
+}
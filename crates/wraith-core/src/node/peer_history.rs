@@ -0,0 +1,325 @@
+//! Persistent peer performance history
+//!
+//! Stores measured RTT and throughput per peer across transfers so swarm
+//! downloads can make informed initial chunk assignments instead of treating
+//! every source equally until runtime measurements accumulate.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+
+use crate::node::error::{NodeError, Result};
+
+/// Maximum number of peers retained in the history store
+const MAX_RECORDS: usize = 1024;
+
+/// Records older than this are pruned on save (30 days)
+const MAX_RECORD_AGE_SECS: u64 = 30 * 24 * 3600;
+
+/// Historical performance record for a single peer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerHistoryRecord {
+    /// Peer ID
+    pub peer_id: [u8; 32],
+
+    /// Smoothed round-trip time in microseconds
+    pub rtt_us: u64,
+
+    /// Smoothed throughput in bytes per second
+    pub throughput_bps: u64,
+
+    /// Total chunks successfully received from this peer
+    pub chunks_succeeded: u64,
+
+    /// Total chunks that failed from this peer
+    pub chunks_failed: u64,
+
+    /// Last observation timestamp (seconds since epoch)
+    pub last_seen: u64,
+}
+
+impl PeerHistoryRecord {
+    /// Create a new record with the given initial measurements
+    pub fn new(peer_id: [u8; 32], rtt_us: u64, throughput_bps: u64) -> Self {
+        Self {
+            peer_id,
+            rtt_us,
+            throughput_bps,
+            chunks_succeeded: 0,
+            chunks_failed: 0,
+            last_seen: now_secs(),
+        }
+    }
+
+    /// Historical failure rate (0.0 to 1.0)
+    pub fn failure_rate(&self) -> f64 {
+        let total = self.chunks_succeeded + self.chunks_failed;
+        if total == 0 {
+            0.0
+        } else {
+            self.chunks_failed as f64 / total as f64
+        }
+    }
+
+    /// Suggested initial chunk parallelism for this peer
+    ///
+    /// Fast, reliable peers start with more in-flight chunks; slow or flaky
+    /// ones start conservatively. Clamped to 1..=8 so a single stale record
+    /// can neither starve nor flood a peer.
+    pub fn suggested_concurrency(&self) -> usize {
+        // Roughly one extra slot per 10 MB/s of demonstrated throughput
+        let base = 1 + (self.throughput_bps / (10 * 1024 * 1024)) as usize;
+        let capped = base.min(8);
+
+        // Halve for peers with a poor track record
+        if self.failure_rate() > 0.2 {
+            (capped / 2).max(1)
+        } else {
+            capped
+        }
+    }
+
+    /// Check if the record is older than `max_age_secs`
+    pub fn is_stale(&self, max_age_secs: u64) -> bool {
+        now_secs().saturating_sub(self.last_seen) > max_age_secs
+    }
+
+    /// Merge a fresh measurement into the record (exponential moving average)
+    pub fn absorb(&mut self, rtt_us: u64, throughput_bps: u64) {
+        let alpha = 0.25;
+        self.rtt_us = ((1.0 - alpha) * self.rtt_us as f64 + alpha * rtt_us as f64) as u64;
+        self.throughput_bps =
+            ((1.0 - alpha) * self.throughput_bps as f64 + alpha * throughput_bps as f64) as u64;
+        self.last_seen = now_secs();
+    }
+}
+
+/// Persistent store of peer performance records
+///
+/// Records live in memory during a transfer; [`load`](Self::load) and
+/// [`save`](Self::save) move them to and from a JSON file, pruning stale
+/// entries and capping the store at [`MAX_RECORDS`] peers on save.
+pub struct PeerHistoryStore {
+    /// Path to the JSON state file
+    state_path: PathBuf,
+
+    /// In-memory records keyed by peer ID
+    records: Arc<RwLock<HashMap<[u8; 32], PeerHistoryRecord>>>,
+}
+
+impl PeerHistoryStore {
+    /// Create a new store backed by the given file
+    pub fn new(state_path: PathBuf) -> Self {
+        Self {
+            state_path,
+            records: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Load records from disk
+    ///
+    /// A missing file yields an empty store; a corrupt file is treated the
+    /// same way rather than failing the transfer that wanted history.
+    pub async fn load(&self) -> Result<()> {
+        let json = match fs::read_to_string(&self.state_path).await {
+            Ok(json) => json,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(NodeError::Io(e.to_string())),
+        };
+
+        let loaded: Vec<PeerHistoryRecord> = match serde_json::from_str(&json) {
+            Ok(records) => records,
+            Err(e) => {
+                tracing::warn!("Ignoring corrupt peer history file: {}", e);
+                return Ok(());
+            }
+        };
+
+        let mut records = self.records.write().await;
+        for record in loaded {
+            records.insert(record.peer_id, record);
+        }
+
+        Ok(())
+    }
+
+    /// Save records to disk, pruning stale entries and capping the count
+    pub async fn save(&self) -> Result<()> {
+        if let Some(parent) = self.state_path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let records = self.records.read().await;
+        let mut to_save: Vec<&PeerHistoryRecord> = records
+            .values()
+            .filter(|r| !r.is_stale(MAX_RECORD_AGE_SECS))
+            .collect();
+
+        // Keep the most recently seen peers when over the cap
+        to_save.sort_by_key(|r| std::cmp::Reverse(r.last_seen));
+        to_save.truncate(MAX_RECORDS);
+
+        let json = serde_json::to_string_pretty(&to_save).map_err(|e| {
+            NodeError::Transfer(format!("Failed to serialize peer history: {}", e).into())
+        })?;
+
+        fs::write(&self.state_path, json).await?;
+        Ok(())
+    }
+
+    /// Look up the record for a peer
+    pub async fn get(&self, peer_id: &[u8; 32]) -> Option<PeerHistoryRecord> {
+        let records = self.records.read().await;
+        records.get(peer_id).cloned()
+    }
+
+    /// Merge a fresh RTT/throughput measurement for a peer
+    pub async fn record_measurement(&self, peer_id: [u8; 32], rtt_us: u64, throughput_bps: u64) {
+        let mut records = self.records.write().await;
+        records
+            .entry(peer_id)
+            .and_modify(|r| r.absorb(rtt_us, throughput_bps))
+            .or_insert_with(|| PeerHistoryRecord::new(peer_id, rtt_us, throughput_bps));
+    }
+
+    /// Record a chunk outcome for a peer
+    pub async fn record_chunk_result(&self, peer_id: [u8; 32], success: bool) {
+        let mut records = self.records.write().await;
+        let record = records
+            .entry(peer_id)
+            .or_insert_with(|| PeerHistoryRecord::new(peer_id, 100_000, 1_000_000));
+
+        if success {
+            record.chunks_succeeded += 1;
+        } else {
+            record.chunks_failed += 1;
+        }
+        record.last_seen = now_secs();
+    }
+
+    /// Number of records currently held in memory
+    pub async fn len(&self) -> usize {
+        self.records.read().await.len()
+    }
+
+    /// Check if the store holds no records
+    pub async fn is_empty(&self) -> bool {
+        self.records.read().await.is_empty()
+    }
+}
+
+/// Current time in seconds since the Unix epoch
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_creation() {
+        let record = PeerHistoryRecord::new([1u8; 32], 50_000, 10_000_000);
+        assert_eq!(record.rtt_us, 50_000);
+        assert_eq!(record.throughput_bps, 10_000_000);
+        assert_eq!(record.failure_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_record_absorb() {
+        let mut record = PeerHistoryRecord::new([1u8; 32], 100_000, 1_000_000);
+        record.absorb(20_000, 50_000_000);
+
+        // EMA moves toward the new measurement without jumping to it
+        assert!(record.rtt_us < 100_000);
+        assert!(record.rtt_us > 20_000);
+        assert!(record.throughput_bps > 1_000_000);
+        assert!(record.throughput_bps < 50_000_000);
+    }
+
+    #[test]
+    fn test_suggested_concurrency_scales_with_throughput() {
+        let slow = PeerHistoryRecord::new([1u8; 32], 100_000, 1_000_000);
+        let fast = PeerHistoryRecord::new([2u8; 32], 10_000, 100 * 1024 * 1024);
+
+        assert_eq!(slow.suggested_concurrency(), 1);
+        assert!(fast.suggested_concurrency() > slow.suggested_concurrency());
+        assert!(fast.suggested_concurrency() <= 8);
+    }
+
+    #[test]
+    fn test_suggested_concurrency_penalizes_failures() {
+        let mut record = PeerHistoryRecord::new([1u8; 32], 10_000, 100 * 1024 * 1024);
+        let healthy = record.suggested_concurrency();
+
+        record.chunks_succeeded = 6;
+        record.chunks_failed = 4;
+        assert!(record.suggested_concurrency() < healthy);
+        assert!(record.suggested_concurrency() >= 1);
+    }
+
+    #[tokio::test]
+    async fn test_store_measurement_and_lookup() {
+        let store = PeerHistoryStore::new(PathBuf::from("/nonexistent/peer_history.json"));
+        assert!(store.is_empty().await);
+
+        store.record_measurement([1u8; 32], 30_000, 5_000_000).await;
+        let record = store.get(&[1u8; 32]).await.unwrap();
+        assert_eq!(record.rtt_us, 30_000);
+        assert_eq!(store.len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_store_chunk_results() {
+        let store = PeerHistoryStore::new(PathBuf::from("/nonexistent/peer_history.json"));
+
+        store.record_chunk_result([1u8; 32], true).await;
+        store.record_chunk_result([1u8; 32], true).await;
+        store.record_chunk_result([1u8; 32], false).await;
+
+        let record = store.get(&[1u8; 32]).await.unwrap();
+        assert_eq!(record.chunks_succeeded, 2);
+        assert_eq!(record.chunks_failed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_store_save_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peer_history.json");
+
+        let store = PeerHistoryStore::new(path.clone());
+        store.record_measurement([7u8; 32], 42_000, 8_000_000).await;
+        store.save().await.unwrap();
+
+        let reloaded = PeerHistoryStore::new(path);
+        reloaded.load().await.unwrap();
+        let record = reloaded.get(&[7u8; 32]).await.unwrap();
+        assert_eq!(record.rtt_us, 42_000);
+        assert_eq!(record.throughput_bps, 8_000_000);
+    }
+
+    #[tokio::test]
+    async fn test_store_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = PeerHistoryStore::new(dir.path().join("missing.json"));
+        store.load().await.unwrap();
+        assert!(store.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_store_load_corrupt_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("peer_history.json");
+        tokio::fs::write(&path, "not json").await.unwrap();
+
+        let store = PeerHistoryStore::new(path);
+        store.load().await.unwrap();
+        assert!(store.is_empty().await);
+    }
+}
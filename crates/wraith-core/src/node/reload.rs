@@ -0,0 +1,218 @@
+//! Node configuration hot-reload
+//!
+//! [`Node::apply_config_update`] takes a freshly parsed [`NodeConfig`]
+//! (e.g. after the daemon catches SIGHUP and re-reads `config.toml`) and
+//! applies every change the node can absorb without restarting or dropping
+//! sessions, reporting the rest as restart-required.
+//!
+//! # Hot-reloadable
+//!
+//! | Field | Effect |
+//! |-------|--------|
+//! | `obfuscation.padding_mode` | Next packet sent |
+//! | `obfuscation.timing_mode` | Next packet sent |
+//! | `obfuscation.mimicry_mode` | Next packet sent/received (both peers must agree) |
+//! | `bandwidth.*` | Buckets rebuilt; in-flight transfers change pace |
+//!
+//! # Restart-required
+//!
+//! `listen_addr`, `transport`, `discovery` (including relay servers, which
+//! are consulted at session setup), `transfer`, `trust`, `rate_limiting`,
+//! `obfuscation.cover_traffic` (the cover loop is spawned at start), and
+//! everything else: these are wired into sockets, spawned tasks, or
+//! per-session state at startup. The logging level is owned by whoever
+//! installed the tracing subscriber (the CLI reloads it itself).
+
+use crate::node::Node;
+use crate::node::config::{MimicryMode, NodeConfig, PaddingMode, TimingMode};
+
+/// What a configuration update changed, split by how it was handled
+///
+/// Returned by [`Node::apply_config_update`]. Field names use the
+/// `config.toml` dotted form (e.g. `obfuscation.padding_mode`).
+#[derive(Debug, Clone, Default)]
+pub struct ConfigReloadReport {
+    /// Changes applied live, in effect from the next packet or send
+    pub applied: Vec<&'static str>,
+
+    /// Changes that only take effect after a node restart
+    pub restart_required: Vec<&'static str>,
+}
+
+impl ConfigReloadReport {
+    /// Whether the update changed nothing
+    #[must_use]
+    pub fn is_noop(&self) -> bool {
+        self.applied.is_empty() && self.restart_required.is_empty()
+    }
+}
+
+impl Node {
+    /// Apply a re-read configuration without restarting the node
+    ///
+    /// Diffs `new` against the node's current live settings and applies the
+    /// hot-reloadable subset (see the module docs for the field table);
+    /// active sessions and transfers are never dropped. Fields the node
+    /// cannot change at runtime are reported in
+    /// [`restart_required`](ConfigReloadReport::restart_required) so the
+    /// caller can tell the operator. Safe to call repeatedly: reapplying
+    /// the current configuration is a no-op.
+    pub fn apply_config_update(&self, new: &NodeConfig) -> ConfigReloadReport {
+        let mut report = ConfigReloadReport::default();
+
+        // Obfuscation modes swap live: padding and timing affect only our
+        // own egress, and the mimicry wrappers for every mode are
+        // pre-constructed. Cover traffic cannot - its loop is spawned with
+        // the boot settings.
+        {
+            let mut live = self
+                .inner
+                .live_obfuscation
+                .write()
+                .expect("obfuscation lock poisoned");
+            if new.obfuscation.padding_mode != live.padding_mode {
+                live.padding_mode = new.obfuscation.padding_mode;
+                report.applied.push("obfuscation.padding_mode");
+            }
+            if new.obfuscation.timing_mode != live.timing_mode {
+                live.timing_mode = new.obfuscation.timing_mode;
+                report.applied.push("obfuscation.timing_mode");
+            }
+            if new.obfuscation.mimicry_mode != live.mimicry_mode {
+                live.mimicry_mode = new.obfuscation.mimicry_mode;
+                report.applied.push("obfuscation.mimicry_mode");
+            }
+            if new.obfuscation.cover_traffic != live.cover_traffic {
+                report.restart_required.push("obfuscation.cover_traffic");
+            }
+        }
+
+        if new.bandwidth != self.inner.bandwidth_limiter.limits() {
+            self.inner.bandwidth_limiter.set_limits(new.bandwidth);
+            report.applied.push("bandwidth");
+        }
+
+        // Everything else is wired in at startup; report what differs from
+        // the boot configuration so the operator knows a restart is due
+        let boot = &self.inner.config;
+        if new.listen_addr != boot.listen_addr {
+            report.restart_required.push("listen_addr");
+        }
+        if new.transport != boot.transport {
+            report.restart_required.push("transport");
+        }
+        if new.discovery != boot.discovery {
+            report.restart_required.push("discovery");
+        }
+        if new.transfer != boot.transfer {
+            report.restart_required.push("transfer");
+        }
+        if new.rate_limiting != boot.rate_limiting {
+            report.restart_required.push("rate_limiting");
+        }
+        if new.trust != boot.trust {
+            report.restart_required.push("trust");
+        }
+        if new.health != boot.health {
+            report.restart_required.push("health");
+        }
+        if new.circuit_breaker != boot.circuit_breaker {
+            report.restart_required.push("circuit_breaker");
+        }
+        if new.telemetry != boot.telemetry {
+            report.restart_required.push("telemetry");
+        }
+
+        report
+    }
+
+    /// Current padding mode, reflecting any hot-reload
+    pub(crate) fn live_padding_mode(&self) -> PaddingMode {
+        self.inner
+            .live_obfuscation
+            .read()
+            .expect("obfuscation lock poisoned")
+            .padding_mode
+    }
+
+    /// Current timing mode, reflecting any hot-reload
+    pub(crate) fn live_timing_mode(&self) -> TimingMode {
+        self.inner
+            .live_obfuscation
+            .read()
+            .expect("obfuscation lock poisoned")
+            .timing_mode
+    }
+
+    /// Current mimicry mode, reflecting any hot-reload
+    pub(crate) fn live_mimicry_mode(&self) -> MimicryMode {
+        self.inner
+            .live_obfuscation
+            .read()
+            .expect("obfuscation lock poisoned")
+            .mimicry_mode
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::bandwidth::BandwidthLimits;
+
+    #[tokio::test]
+    async fn test_reapplying_same_config_is_noop() {
+        let config = NodeConfig::default();
+        let node = Node::new_with_config(config.clone()).await.unwrap();
+
+        let report = node.apply_config_update(&config);
+        assert!(report.is_noop(), "unexpected diff: {report:?}");
+    }
+
+    #[tokio::test]
+    async fn test_obfuscation_modes_apply_live() {
+        let node = Node::new_with_config(NodeConfig::default()).await.unwrap();
+
+        let mut updated = NodeConfig::default();
+        updated.obfuscation.padding_mode = PaddingMode::PowerOfTwo;
+
+        let report = node.apply_config_update(&updated);
+        assert_eq!(report.applied, vec!["obfuscation.padding_mode"]);
+        assert!(report.restart_required.is_empty());
+        assert_eq!(node.live_padding_mode(), PaddingMode::PowerOfTwo);
+
+        // A second application of the same update changes nothing more
+        assert!(node.apply_config_update(&updated).is_noop());
+    }
+
+    #[tokio::test]
+    async fn test_bandwidth_limits_apply_live() {
+        let node = Node::new_with_config(NodeConfig::default()).await.unwrap();
+
+        let updated = NodeConfig {
+            bandwidth: BandwidthLimits {
+                global_bps: Some(10_000_000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let report = node.apply_config_update(&updated);
+        assert_eq!(report.applied, vec!["bandwidth"]);
+        assert_eq!(node.bandwidth_limits().global_bps, Some(10_000_000));
+    }
+
+    #[tokio::test]
+    async fn test_restart_required_fields_are_reported() {
+        let node = Node::new_with_config(NodeConfig::default()).await.unwrap();
+
+        let mut updated = NodeConfig {
+            listen_addr: "127.0.0.1:4242".parse().unwrap(),
+            ..Default::default()
+        };
+        updated.transfer.chunk_size *= 2;
+
+        let report = node.apply_config_update(&updated);
+        assert!(report.applied.is_empty());
+        assert_eq!(report.restart_required, vec!["listen_addr", "transfer"]);
+    }
+}
@@ -5,15 +5,61 @@
 //! - Network partition and reconnect
 //! - Peer address change
 //! - Corrupted chunk detection
+//!
+//! With [`TransferConfig::resume_state_dir`] set, both endpoints checkpoint
+//! transfer progress to disk as chunks flow. When a session with a peer is
+//! (re-)established, [`Node::spawn_resume_check`] looks for unfinished
+//! checkpoints against that peer: a sender queries the receiver's chunk
+//! bitmap (RESUME_QUERY/RESUME_BITMAP control payloads) and re-sends only
+//! what is missing, while a receiver announces its bitmap so the sender can
+//! do the same. No manual `resume` invocation is needed — a transfer cut
+//! short by laptop sleep or a network outage continues on the next
+//! handshake.
+//!
+//! [`TransferConfig::resume_state_dir`]: crate::node::config::TransferConfig::resume_state_dir
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::fs;
 use tokio::sync::RwLock;
 
+use crate::FRAME_HEADER_SIZE;
+use crate::frame::{FrameBuilder, FrameType};
+use crate::node::Node;
 use crate::node::error::{NodeError, Result};
+use crate::node::events::NodeEvent;
+use crate::node::file_transfer::FileTransferContext;
+use crate::node::identity::TransferId;
+use crate::node::session::PeerId;
+use crate::transfer::TransferSession;
+use wraith_files::chunker::FileChunker;
+use wraith_files::tree_hash::compute_tree_hash;
+
+/// Control payload tag: ask a peer which chunks of a transfer it holds
+pub(crate) const CONTROL_RESUME_QUERY: u8 = 0x50;
+
+/// Control payload tag: chunk bitmap reply or announcement
+pub(crate) const CONTROL_RESUME_BITMAP: u8 = 0x51;
+
+/// Retention of persisted resume state files, in days
+pub const RESUME_STATE_MAX_AGE_DAYS: u64 = 7;
+
+/// Flush checkpointed progress to disk every this many chunks
+///
+/// Progress recorded since the last flush is lost on a crash, which only
+/// costs re-transferring those chunks; flushing per chunk would put a
+/// growing JSON write on the hot path.
+const RESUME_FLUSH_INTERVAL: usize = 64;
+
+/// How long a querying sender waits for the peer's bitmap
+const RESUME_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Delay before the startup sweep dials peers with unfinished sends,
+/// giving discovery a moment to come up
+const RESUME_RECONNECT_DELAY: Duration = Duration::from_secs(2);
 
 /// Transfer resume state
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -198,7 +244,11 @@ impl ResumeManager {
             states.insert(state.transfer_id, state.clone());
         }
 
-        // Save to disk
+        self.write_state_file(state).await
+    }
+
+    /// Serialize a state snapshot to its file on disk
+    async fn write_state_file(&self, state: &ResumeState) -> Result<()> {
         let path = self.state_file_path(&state.transfer_id);
         let json = serde_json::to_string_pretty(state).map_err(|e| {
             NodeError::Serialization(format!("Failed to serialize state: {e}").into())
@@ -252,6 +302,31 @@ impl ResumeManager {
         Ok(())
     }
 
+    /// Mark a chunk complete, flushing to disk periodically
+    ///
+    /// Hot-path counterpart of [`ResumeManager::update_state`]: the
+    /// in-memory state is updated on every call but only written out every
+    /// [`RESUME_FLUSH_INTERVAL`] chunks and when the transfer completes.
+    /// Transfers without cached state are ignored, so chunk handlers can
+    /// call this unconditionally.
+    pub async fn record_chunk(&self, transfer_id: &[u8; 32], chunk_index: usize) -> Result<()> {
+        let snapshot = {
+            let mut states = self.states.write().await;
+            let Some(state) = states.get_mut(transfer_id) else {
+                return Ok(());
+            };
+            state.mark_chunk_complete(chunk_index);
+            let flush =
+                state.is_complete() || state.completed_chunks.len() % RESUME_FLUSH_INTERVAL == 0;
+            flush.then(|| state.clone())
+        };
+
+        match snapshot {
+            Some(state) => self.write_state_file(&state).await,
+            None => Ok(()),
+        }
+    }
+
     /// Delete resume state
     pub async fn delete_state(&self, transfer_id: &[u8; 32]) -> Result<()> {
         // Remove from cache
@@ -334,6 +409,560 @@ impl ResumeManager {
     }
 }
 
+/// Encode a RESUME_QUERY control payload
+///
+/// Format: tag(1) + transfer_id(32).
+pub(crate) fn encode_resume_query(transfer_id: &TransferId) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(33);
+    payload.push(CONTROL_RESUME_QUERY);
+    payload.extend_from_slice(transfer_id);
+    payload
+}
+
+/// Decode a RESUME_QUERY control payload (tag already consumed)
+pub(crate) fn decode_resume_query(data: &[u8]) -> Result<TransferId> {
+    let transfer_id: TransferId = data
+        .try_into()
+        .map_err(|_| NodeError::invalid_state("Malformed resume query"))?;
+    Ok(transfer_id)
+}
+
+/// Encode a RESUME_BITMAP control payload
+///
+/// Format: tag(1) + transfer_id(32) + total_chunks(8, BE) + bitmap
+/// (LSB-first, see [`ResumeState::chunk_bitmap`]). A total of zero with an
+/// empty bitmap means the peer holds nothing for this transfer.
+pub(crate) fn encode_resume_bitmap(
+    transfer_id: &TransferId,
+    total_chunks: u64,
+    bitmap: &[u8],
+) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(41 + bitmap.len());
+    payload.push(CONTROL_RESUME_BITMAP);
+    payload.extend_from_slice(transfer_id);
+    payload.extend_from_slice(&total_chunks.to_be_bytes());
+    payload.extend_from_slice(bitmap);
+    payload
+}
+
+/// Decode a RESUME_BITMAP control payload (tag already consumed)
+pub(crate) fn decode_resume_bitmap(data: &[u8]) -> Result<(TransferId, u64, Vec<u8>)> {
+    let transfer_id: TransferId = data
+        .get(..32)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| NodeError::invalid_state("Resume bitmap truncated (transfer ID)"))?;
+    let total_bytes = data
+        .get(32..40)
+        .ok_or_else(|| NodeError::invalid_state("Resume bitmap truncated (chunk count)"))?;
+    let total_chunks = u64::from_be_bytes(total_bytes.try_into().expect("sliced to 8"));
+    Ok((transfer_id, total_chunks, data[40..].to_vec()))
+}
+
+/// Byte length of one chunk of a file, accounting for the short final chunk
+pub(crate) fn chunk_len(file_size: u64, chunk_size: usize, index: usize) -> usize {
+    let start = index as u64 * chunk_size as u64;
+    usize::try_from(file_size.saturating_sub(start).min(chunk_size as u64)).unwrap_or(chunk_size)
+}
+
+/// Build a Control frame carrying a resume payload
+fn build_control_frame(payload: &[u8]) -> Result<Vec<u8>> {
+    FrameBuilder::new()
+        .frame_type(FrameType::Control)
+        .stream_id(0)
+        .sequence(0)
+        .payload(payload)
+        .build(FRAME_HEADER_SIZE + payload.len())
+        .map_err(|e| NodeError::InvalidState(format!("Failed to build control frame: {e}").into()))
+}
+
+impl Node {
+    /// Kick off transfer resumption with a freshly connected peer
+    ///
+    /// Called on both the initiator and responder sides of session
+    /// establishment. The work runs in a background task so session setup
+    /// never blocks on bitmap round-trips; a no-op unless resume state is
+    /// persisted for this peer.
+    pub(crate) fn spawn_resume_check(&self, peer_id: PeerId) {
+        if self.inner.resume.is_none() {
+            return;
+        }
+        let node = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = node.resume_transfers_with_peer(peer_id).await {
+                tracing::warn!(
+                    "Transfer resumption with peer {} failed: {}",
+                    hex::encode(&peer_id[..8]),
+                    e
+                );
+            }
+        });
+    }
+
+    /// Reconnect to peers holding unfinished sends after a restart
+    ///
+    /// A sender that died mid-transfer comes back up with checkpoints but
+    /// no sessions, so the per-session resume hook never fires until
+    /// something else dials the peer. This sweep, spawned from
+    /// [`Node::start`], establishes a session to each such peer — which
+    /// triggers the hook and continues the push from the receiver's
+    /// bitmap. Unreachable peers keep their checkpoints and are retried on
+    /// the next restart or inbound session.
+    pub(crate) fn spawn_resume_reconnect(&self) {
+        if self.inner.resume.is_none() {
+            return;
+        }
+        let node = self.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(RESUME_RECONNECT_DELAY).await;
+            if let Err(e) = node.reconnect_pending_sends().await {
+                tracing::warn!("Resume reconnect sweep failed: {}", e);
+            }
+        });
+    }
+
+    /// Dial every peer that an unfinished sender-side checkpoint names
+    async fn reconnect_pending_sends(&self) -> Result<()> {
+        let Some(resume) = self.inner.resume.clone() else {
+            return Ok(());
+        };
+
+        let mut peers = std::collections::HashSet::new();
+        for state in resume.list_states().await? {
+            if state.is_sender && !state.is_complete() {
+                peers.insert(state.peer_id);
+            }
+        }
+
+        for peer_id in peers {
+            // An existing session means the establishment hook already ran
+            // (e.g. the peer dialed us first)
+            if self.inner.sessions.contains_key(&peer_id) {
+                continue;
+            }
+
+            // Session establishment runs the resume hook on success; an
+            // unreachable peer is not an error, its checkpoints keep
+            if let Err(e) = self.establish_session(&peer_id).await {
+                tracing::debug!(
+                    "Peer {} with unfinished sends is unreachable: {}",
+                    hex::encode(&peer_id[..8]),
+                    e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Continue every unfinished transfer checkpointed against a peer
+    ///
+    /// Sender-side checkpoints query the receiver's bitmap and re-send the
+    /// missing chunks; receiver-side checkpoints announce their bitmap so
+    /// the remote sender can do the same.
+    async fn resume_transfers_with_peer(&self, peer_id: PeerId) -> Result<()> {
+        let Some(resume) = self.inner.resume.clone() else {
+            return Ok(());
+        };
+
+        for state in resume.list_states().await? {
+            if state.peer_id != peer_id
+                || state.is_complete()
+                || self.inner.transfers.contains_key(&state.transfer_id)
+            {
+                continue;
+            }
+
+            if state.is_sender {
+                let transfer_id = state.transfer_id;
+                match self.query_resume_bitmap(&peer_id, &transfer_id).await {
+                    Ok(bitmap) => {
+                        if let Err(e) = self.resume_transfer_send(state, &bitmap).await {
+                            tracing::warn!(
+                                "Failed to resume transfer {}: {}",
+                                hex::encode(&transfer_id[..8]),
+                                e
+                            );
+                        }
+                    }
+                    // The peer may predate resume tags or be mid-restart;
+                    // the checkpoint stays for the next session
+                    Err(e) => tracing::debug!(
+                        "No resume bitmap for transfer {}: {}",
+                        hex::encode(&transfer_id[..8]),
+                        e
+                    ),
+                }
+            } else if let Err(e) = self.send_resume_bitmap(&peer_id, &state).await {
+                tracing::debug!(
+                    "Failed to announce resume bitmap for transfer {}: {}",
+                    hex::encode(&state.transfer_id[..8]),
+                    e
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Ask a peer for its chunk bitmap of an interrupted transfer
+    async fn query_resume_bitmap(
+        &self,
+        peer_id: &PeerId,
+        transfer_id: &TransferId,
+    ) -> Result<Vec<u8>> {
+        let connection = self
+            .inner
+            .sessions
+            .get(peer_id)
+            .map(|entry| Arc::clone(entry.value()))
+            .ok_or(NodeError::SessionNotFound(*peer_id))?;
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.inner.pending_resume.insert(*transfer_id, tx);
+
+        let frame = build_control_frame(&encode_resume_query(transfer_id))?;
+        self.send_encrypted_frame(&connection, &frame)
+            .await
+            .inspect_err(|_| {
+                self.inner.pending_resume.remove(transfer_id);
+            })?;
+
+        match tokio::time::timeout(RESUME_QUERY_TIMEOUT, rx).await {
+            Ok(Ok(bitmap)) => Ok(bitmap),
+            Ok(Err(_)) => {
+                self.inner.pending_resume.remove(transfer_id);
+                Err(NodeError::invalid_state("Resume bitmap channel closed"))
+            }
+            Err(_) => {
+                self.inner.pending_resume.remove(transfer_id);
+                Err(NodeError::Timeout("Resume bitmap query timed out".into()))
+            }
+        }
+    }
+
+    /// Announce our receive progress for an interrupted transfer
+    async fn send_resume_bitmap(&self, peer_id: &PeerId, state: &ResumeState) -> Result<()> {
+        let connection = self
+            .inner
+            .sessions
+            .get(peer_id)
+            .map(|entry| Arc::clone(entry.value()))
+            .ok_or(NodeError::SessionNotFound(*peer_id))?;
+
+        let payload = encode_resume_bitmap(
+            &state.transfer_id,
+            state.total_chunks as u64,
+            &state.chunk_bitmap(),
+        );
+        let frame = build_control_frame(&payload)?;
+        self.send_encrypted_frame(&connection, &frame).await
+    }
+
+    /// Handle a RESUME_QUERY control payload
+    ///
+    /// Replies with the chunk bitmap from our persisted receive checkpoint.
+    /// Without one (or without resume support at all) an empty bitmap is
+    /// returned, telling the sender to start over rather than stall on a
+    /// timeout.
+    pub(crate) async fn handle_resume_query(&self, body: &[u8], peer_id: PeerId) -> Result<()> {
+        let transfer_id = decode_resume_query(body)?;
+
+        let state = match &self.inner.resume {
+            Some(resume) => resume
+                .load_state(&transfer_id)
+                .await
+                .ok()
+                .flatten()
+                .filter(|state| !state.is_sender && state.peer_id == peer_id),
+            None => None,
+        };
+
+        let payload = match &state {
+            Some(state) => encode_resume_bitmap(
+                &transfer_id,
+                state.total_chunks as u64,
+                &state.chunk_bitmap(),
+            ),
+            None => encode_resume_bitmap(&transfer_id, 0, &[]),
+        };
+
+        let connection = self
+            .inner
+            .sessions
+            .get(&peer_id)
+            .map(|entry| Arc::clone(entry.value()))
+            .ok_or(NodeError::SessionNotFound(peer_id))?;
+
+        let frame = build_control_frame(&payload)?;
+        self.send_encrypted_frame(&connection, &frame).await
+    }
+
+    /// Handle a RESUME_BITMAP control payload
+    ///
+    /// A solicited bitmap answers our pending query. An unsolicited one is
+    /// a reconnecting receiver announcing its progress; if a matching
+    /// sender-side checkpoint exists, the send resumes from it.
+    pub(crate) async fn handle_resume_bitmap(&self, body: &[u8], peer_id: PeerId) -> Result<()> {
+        let (transfer_id, total_chunks, bitmap) = decode_resume_bitmap(body)?;
+
+        if let Some((_, tx)) = self.inner.pending_resume.remove(&transfer_id) {
+            let _ = tx.send(bitmap);
+            return Ok(());
+        }
+
+        let Some(resume) = &self.inner.resume else {
+            return Ok(());
+        };
+        let Some(state) = resume.load_state(&transfer_id).await? else {
+            return Ok(());
+        };
+        if !state.is_sender || state.peer_id != peer_id {
+            return Ok(());
+        }
+        if total_chunks as usize != state.total_chunks {
+            return Err(NodeError::invalid_state(
+                "Resume bitmap chunk count mismatch",
+            ));
+        }
+
+        self.resume_transfer_send(state, &bitmap).await
+    }
+
+    /// Continue an interrupted send from the receiver's chunk bitmap
+    ///
+    /// Rebuilds the transfer context (the original died with its session),
+    /// re-sends the metadata frame so a restarted receiver can recreate its
+    /// reassembler, and streams only the chunks the bitmap marks missing.
+    async fn resume_transfer_send(&self, mut state: ResumeState, bitmap: &[u8]) -> Result<()> {
+        let Some(resume) = self.inner.resume.clone() else {
+            return Ok(());
+        };
+
+        state.from_bitmap(bitmap);
+        if state.is_complete() {
+            resume.delete_state(&state.transfer_id).await?;
+            return Ok(());
+        }
+
+        // The source must still be the file the receiver holds part of
+        let file_size = std::fs::metadata(&state.file_path)
+            .map_err(|e| NodeError::Io(e.to_string()))?
+            .len();
+        let tree_hash = compute_tree_hash(&state.file_path, state.chunk_size)
+            .map_err(|e| NodeError::Io(e.to_string()))?;
+        if file_size != state.file_size || tree_hash.root != state.file_hash {
+            resume.delete_state(&state.transfer_id).await?;
+            return Err(NodeError::InvalidState(
+                format!(
+                    "Source file {} changed since the interrupted transfer; \
+                     resume state dropped",
+                    state.file_path.display()
+                )
+                .into(),
+            ));
+        }
+
+        let transfer_id = state.transfer_id;
+        let peer_id = state.peer_id;
+        tracing::info!(
+            "Resuming transfer {} with peer {}: {} of {} chunks already delivered",
+            hex::encode(&transfer_id[..8]),
+            hex::encode(&peer_id[..8]),
+            state.completed_chunks.len(),
+            state.total_chunks,
+        );
+
+        // Rebuild the send context the dead session took with it, with the
+        // delivered chunks pre-marked so progress reporting stays truthful
+        let mut transfer = TransferSession::new_send(
+            transfer_id,
+            state.file_path.clone(),
+            file_size,
+            state.chunk_size,
+        );
+        transfer.start();
+        for &index in &state.completed_chunks {
+            transfer.mark_chunk_transferred(
+                index as u64,
+                chunk_len(file_size, state.chunk_size, index),
+            );
+        }
+        let context = Arc::new(FileTransferContext::new_send(
+            transfer_id,
+            Arc::new(RwLock::new(transfer)),
+            tree_hash.clone(),
+        ));
+        if self.inner.transfers.contains_key(&transfer_id) {
+            // An active transfer got there first (e.g. both sides ran the
+            // resume check at once)
+            return Ok(());
+        }
+        self.inner
+            .transfers
+            .insert(transfer_id, Arc::clone(&context));
+
+        resume.save_state(&state).await?;
+
+        let connection = self
+            .inner
+            .sessions
+            .get(&peer_id)
+            .map(|entry| Arc::clone(entry.value()))
+            .ok_or(NodeError::SessionNotFound(peer_id))?;
+        let stream_id = ((transfer_id[0] as u16) << 8) | (transfer_id[1] as u16);
+
+        // Metadata lets a restarted receiver rebuild its reassembler (from
+        // its own checkpoint, see handle_stream_open_frame) before the
+        // remaining chunks arrive
+        let metadata = crate::node::file_transfer::FileMetadata::from_path_and_hash(
+            transfer_id,
+            &state.file_path,
+            file_size,
+            state.chunk_size,
+            &tree_hash,
+        )?;
+        let metadata_frame =
+            crate::node::file_transfer::build_metadata_frame(stream_id, &metadata)?;
+        self.send_encrypted_frame(&connection, &metadata_frame)
+            .await?;
+
+        let missing: Vec<u64> = state
+            .missing_chunks()
+            .into_iter()
+            .map(|i| i as u64)
+            .collect();
+        let chunk_size = state.chunk_size;
+        let file_path = state.file_path.clone();
+        let node = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = node
+                .send_missing_chunks(
+                    transfer_id,
+                    file_path,
+                    chunk_size,
+                    stream_id,
+                    connection,
+                    missing,
+                )
+                .await
+            {
+                tracing::error!("Error sending resumed file chunks: {}", e);
+            }
+        });
+
+        self.inner.events.emit(NodeEvent::TransferStarted {
+            transfer_id,
+            peer_id,
+        });
+        self.spawn_transfer_event_monitor(transfer_id);
+
+        Ok(())
+    }
+
+    /// Send an explicit set of chunks to a peer
+    ///
+    /// Resume-path counterpart of [`Node::send_file_chunks`]: the same
+    /// read/verify/frame/send pipeline, but over the missing-chunk list of
+    /// a resumed transfer instead of the whole file.
+    async fn send_missing_chunks(
+        &self,
+        transfer_id: TransferId,
+        file_path: PathBuf,
+        chunk_size: usize,
+        stream_id: u16,
+        connection: Arc<crate::node::session::PeerConnection>,
+        chunks: Vec<u64>,
+    ) -> Result<()> {
+        let context = self
+            .inner
+            .transfers
+            .get(&transfer_id)
+            .ok_or(NodeError::TransferNotFound(transfer_id))?
+            .clone();
+
+        let mut chunker =
+            FileChunker::new(&file_path, chunk_size).map_err(|e| NodeError::Io(e.to_string()))?;
+
+        let total = chunks.len();
+        let send_start = std::time::Instant::now();
+        let mut bytes_sent: u64 = 0;
+        let mut chunks_sent: u64 = 0;
+        for chunk_index in chunks {
+            let chunk_data = chunker
+                .read_chunk_at(chunk_index)
+                .map_err(|e| NodeError::Io(e.to_string()))?;
+            let chunk_len = chunk_data.len();
+
+            // Verify chunk hash
+            if chunk_index < context.tree_hash.chunks.len() as u64 {
+                let computed_hash = blake3::hash(&chunk_data);
+                if computed_hash.as_bytes() != &context.tree_hash.chunks[chunk_index as usize] {
+                    return Err(NodeError::InvalidState(
+                        "Chunk hash verification failed".into(),
+                    ));
+                }
+            }
+
+            let chunk_frame = crate::node::file_transfer::build_chunk_frame(
+                stream_id,
+                chunk_index,
+                &chunk_data,
+                self.next_trace_id(),
+            )?;
+
+            self.inner
+                .bandwidth_limiter
+                .acquire_transfer(&transfer_id, chunk_frame.len() as u64)
+                .await;
+
+            self.send_encrypted_frame(&connection, &chunk_frame).await?;
+
+            context
+                .transfer_session
+                .write()
+                .await
+                .mark_chunk_transferred(chunk_index, chunk_len);
+            self.record_resume_chunk(&transfer_id, chunk_index).await;
+
+            // Same careful-resume bookkeeping as the full send loop: keep
+            // the path estimate current in case this session drops too
+            bytes_sent += chunk_len as u64;
+            chunks_sent += 1;
+            if chunks_sent % crate::node::path_estimates::PATH_ESTIMATE_RECORD_INTERVAL == 0 {
+                self.record_path_estimate(&connection, bytes_sent, send_start);
+            }
+        }
+
+        tracing::info!(
+            "Resumed transfer {:?} completed ({} missing chunks sent)",
+            hex::encode(&transfer_id[..8]),
+            total
+        );
+        self.record_path_estimate(&connection, bytes_sent, send_start);
+        self.clear_resume_state(&transfer_id).await;
+
+        Ok(())
+    }
+
+    /// Record chunk completion in the persisted checkpoint (best-effort)
+    pub(crate) async fn record_resume_chunk(&self, transfer_id: &TransferId, chunk_index: u64) {
+        if let Some(resume) = &self.inner.resume
+            && let Err(e) = resume.record_chunk(transfer_id, chunk_index as usize).await
+        {
+            tracing::debug!("Failed to record resume progress: {}", e);
+        }
+    }
+
+    /// Drop the persisted checkpoint of a finished transfer (best-effort)
+    pub(crate) async fn clear_resume_state(&self, transfer_id: &TransferId) {
+        if let Some(resume) = &self.inner.resume
+            && let Err(e) = resume.delete_state(transfer_id).await
+        {
+            tracing::debug!("Failed to delete resume state: {}", e);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -559,4 +1188,154 @@ mod tests {
         let states = manager.list_states().await.unwrap();
         assert_eq!(states.len(), 2);
     }
+
+    #[test]
+    fn test_resume_query_roundtrip() {
+        let transfer_id = [7u8; 32];
+        let payload = encode_resume_query(&transfer_id);
+
+        assert_eq!(payload[0], CONTROL_RESUME_QUERY);
+        let decoded = decode_resume_query(&payload[1..]).unwrap();
+        assert_eq!(decoded, transfer_id);
+
+        // Truncated payloads are rejected
+        assert!(decode_resume_query(&payload[1..20]).is_err());
+    }
+
+    #[test]
+    fn test_resume_bitmap_roundtrip() {
+        let transfer_id = [9u8; 32];
+        let bitmap = vec![0x55, 0x03];
+        let payload = encode_resume_bitmap(&transfer_id, 10, &bitmap);
+
+        assert_eq!(payload[0], CONTROL_RESUME_BITMAP);
+        let (decoded_id, total, decoded_bitmap) = decode_resume_bitmap(&payload[1..]).unwrap();
+        assert_eq!(decoded_id, transfer_id);
+        assert_eq!(total, 10);
+        assert_eq!(decoded_bitmap, bitmap);
+
+        // An empty bitmap ("peer holds nothing") survives the roundtrip
+        let empty = encode_resume_bitmap(&transfer_id, 0, &[]);
+        let (_, total, decoded_bitmap) = decode_resume_bitmap(&empty[1..]).unwrap();
+        assert_eq!(total, 0);
+        assert!(decoded_bitmap.is_empty());
+
+        // Truncated payloads are rejected
+        assert!(decode_resume_bitmap(&payload[1..30]).is_err());
+        assert!(decode_resume_bitmap(&payload[1..38]).is_err());
+    }
+
+    #[test]
+    fn test_chunk_len_short_final_chunk() {
+        assert_eq!(chunk_len(1000, 256, 0), 256);
+        assert_eq!(chunk_len(1000, 256, 2), 256);
+        assert_eq!(chunk_len(1000, 256, 3), 232);
+        // Out-of-range indices saturate to zero rather than underflowing
+        assert_eq!(chunk_len(1000, 256, 4), 0);
+    }
+
+    #[tokio::test]
+    async fn test_record_chunk_flushes_on_completion() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ResumeManager::new(temp_dir.path().to_path_buf(), 7);
+        manager.initialize().await.unwrap();
+
+        let state = ResumeState::new(
+            [1u8; 32],
+            [2u8; 32],
+            [3u8; 32],
+            1024,
+            256,
+            PathBuf::from("/tmp/test.bin"),
+            true,
+        );
+        manager.save_state(&state).await.unwrap();
+
+        // Below the flush interval and incomplete: cached but not on disk
+        manager.record_chunk(&state.transfer_id, 0).await.unwrap();
+        let fresh = ResumeManager::new(temp_dir.path().to_path_buf(), 7);
+        let on_disk = fresh.load_state(&state.transfer_id).await.unwrap().unwrap();
+        assert!(!on_disk.is_chunk_complete(0));
+
+        // Completion forces a flush
+        for index in 1..4 {
+            manager
+                .record_chunk(&state.transfer_id, index)
+                .await
+                .unwrap();
+        }
+        let fresh = ResumeManager::new(temp_dir.path().to_path_buf(), 7);
+        let on_disk = fresh.load_state(&state.transfer_id).await.unwrap().unwrap();
+        assert!(on_disk.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_reconnect_sweep_tolerates_unreachable_peers() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = crate::node::config::NodeConfig {
+            transfer: crate::node::config::TransferConfig {
+                resume_state_dir: Some(temp_dir.path().to_path_buf()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let node = Node::new_with_config(config).await.unwrap();
+        let resume = node.inner.resume.clone().unwrap();
+        resume.initialize().await.unwrap();
+
+        // An unfinished send to a peer that cannot be discovered, plus a
+        // finished send and a receive that the sweep must skip
+        let pending = ResumeState::new(
+            [1u8; 32],
+            [9u8; 32],
+            [3u8; 32],
+            1024,
+            256,
+            PathBuf::from("/tmp/pending.bin"),
+            true,
+        );
+        let mut done = ResumeState::new(
+            [2u8; 32],
+            [9u8; 32],
+            [3u8; 32],
+            1024,
+            256,
+            PathBuf::from("/tmp/done.bin"),
+            true,
+        );
+        done.mark_chunks_complete(&[0, 1, 2, 3]);
+        let receive = ResumeState::new(
+            [3u8; 32],
+            [9u8; 32],
+            [3u8; 32],
+            1024,
+            256,
+            PathBuf::from("/tmp/receive.bin"),
+            false,
+        );
+        resume.save_state(&pending).await.unwrap();
+        resume.save_state(&done).await.unwrap();
+        resume.save_state(&receive).await.unwrap();
+
+        // The unreachable peer must not fail the sweep; its checkpoint stays
+        node.reconnect_pending_sends().await.unwrap();
+        assert!(
+            resume
+                .load_state(&pending.transfer_id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_chunk_ignores_unknown_transfer() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ResumeManager::new(temp_dir.path().to_path_buf(), 7);
+        manager.initialize().await.unwrap();
+
+        // No state saved for this ID; hot paths call this unconditionally
+        manager.record_chunk(&[8u8; 32], 3).await.unwrap();
+        assert!(manager.load_state(&[8u8; 32]).await.unwrap().is_none());
+    }
 }
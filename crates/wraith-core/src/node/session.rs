@@ -82,6 +82,14 @@ pub struct PeerConnection {
 
     /// Timestamp when the session was established
     pub established_at: std::time::SystemTime,
+
+    /// Additional validated path addresses for multipath striping
+    /// (`path_id` -> address, primary path excluded)
+    path_addrs: std::sync::RwLock<std::collections::HashMap<u64, SocketAddr>>,
+
+    /// Prioritized outbound frame queue: control frames bypass bulk DATA
+    /// (pushed and drained by `Node::send_encrypted_frame`)
+    outbound: std::sync::Mutex<crate::send_queue::SendQueue>,
 }
 
 /// Get current time as milliseconds since UNIX epoch
@@ -110,6 +118,15 @@ impl Clone for PeerConnection {
                 self.failed_pings.load(Ordering::Relaxed),
             ),
             established_at: self.established_at,
+            path_addrs: std::sync::RwLock::new(
+                self.path_addrs
+                    .read()
+                    .expect("path_addrs lock poisoned")
+                    .clone(),
+            ),
+            // Queued frames stay with the original: whoever enqueued them
+            // is draining through the original's queue
+            outbound: std::sync::Mutex::new(crate::send_queue::SendQueue::new()),
         }
     }
 }
@@ -134,6 +151,8 @@ impl PeerConnection {
             last_activity_ms: AtomicU64::new(current_time_ms()),
             failed_pings: std::sync::atomic::AtomicU32::new(0),
             established_at: std::time::SystemTime::now(),
+            path_addrs: std::sync::RwLock::new(std::collections::HashMap::new()),
+            outbound: std::sync::Mutex::new(crate::send_queue::SendQueue::new()),
         }
     }
 
@@ -145,6 +164,57 @@ impl PeerConnection {
         *self.peer_addr.read().expect("peer_addr lock poisoned")
     }
 
+    /// Register an additional validated path address for multipath striping
+    pub fn add_path_addr(&self, path_id: u64, addr: SocketAddr) {
+        self.path_addrs
+            .write()
+            .expect("path_addrs lock poisoned")
+            .insert(path_id, addr);
+    }
+
+    /// Remove a path address (validation failure or teardown)
+    pub fn remove_path_addr(&self, path_id: u64) {
+        self.path_addrs
+            .write()
+            .expect("path_addrs lock poisoned")
+            .remove(&path_id);
+    }
+
+    /// Look up the address for a validated path, if registered
+    #[must_use]
+    pub fn path_addr(&self, path_id: u64) -> Option<SocketAddr> {
+        self.path_addrs
+            .read()
+            .expect("path_addrs lock poisoned")
+            .get(&path_id)
+            .copied()
+    }
+
+    /// Enqueue a serialized frame on the prioritized outbound queue
+    ///
+    /// The frame is classified by its type byte (see
+    /// [`crate::send_queue::SendQueue::push`]); control frames will be
+    /// drained ahead of any DATA backlog. Returns `false` when the target
+    /// lane is full (backpressure).
+    pub fn enqueue_outbound(&self, frame: Vec<u8>) -> bool {
+        self.outbound
+            .lock()
+            .expect("outbound lock poisoned")
+            .push(frame)
+    }
+
+    /// Dequeue the highest-priority outbound frame, if any
+    #[must_use]
+    pub fn pop_outbound(&self) -> Option<Vec<u8>> {
+        self.outbound.lock().expect("outbound lock poisoned").pop()
+    }
+
+    /// Number of frames awaiting transmission
+    #[must_use]
+    pub fn outbound_len(&self) -> usize {
+        self.outbound.lock().expect("outbound lock poisoned").len()
+    }
+
     /// Increment failed ping counter
     pub fn increment_failed_pings(&self) -> u32 {
         self.failed_pings.fetch_add(1, Ordering::Relaxed) + 1
@@ -221,6 +291,8 @@ impl PeerConnection {
             last_activity_ms: AtomicU64::new(current_time_ms()),
             failed_pings: std::sync::atomic::AtomicU32::new(0),
             established_at: std::time::SystemTime::now(),
+            path_addrs: std::sync::RwLock::new(std::collections::HashMap::new()),
+            outbound: std::sync::Mutex::new(crate::send_queue::SendQueue::new()),
         }
     }
 
@@ -241,7 +313,9 @@ impl PeerConnection {
     /// Encrypt frame data for transmission
     ///
     /// Takes serialized frame bytes and encrypts them using the session crypto.
-    /// Automatically manages nonce counters and checks for rekey conditions.
+    /// The nonce counter is carried on the wire (8 bytes, big-endian) ahead of
+    /// the ciphertext so the receiver stays synchronized even when individual
+    /// datagrams are lost or reordered.
     ///
     /// # Arguments
     ///
@@ -249,7 +323,7 @@ impl PeerConnection {
     ///
     /// # Returns
     ///
-    /// Encrypted frame data (ciphertext + auth tag)
+    /// `8B counter || ciphertext + auth tag`
     ///
     /// # Errors
     ///
@@ -264,20 +338,29 @@ impl PeerConnection {
             ));
         }
 
-        // Encrypt with empty AAD (frame already contains all necessary data)
-        crypto
+        // Encrypt with empty AAD (frame already contains all necessary data);
+        // encrypt() consumes and increments the send counter
+        let counter = crypto.send_counter();
+        let ciphertext = crypto
             .encrypt(frame_bytes, &[])
-            .map_err(|e| NodeError::Crypto(e.to_string()))
+            .map_err(|e| NodeError::Crypto(e.to_string()))?;
+
+        let mut out = Vec::with_capacity(8 + ciphertext.len());
+        out.extend_from_slice(&counter.to_be_bytes());
+        out.extend_from_slice(&ciphertext);
+        Ok(out)
     }
 
     /// Decrypt received frame data
     ///
-    /// Takes encrypted bytes and decrypts them using the session crypto.
-    /// Automatically manages nonce counters and replay protection.
+    /// Takes `8B counter || ciphertext` as produced by [`Self::encrypt_frame`]
+    /// and decrypts it using the session crypto. The explicit counter makes
+    /// decryption tolerant of packet loss and reordering; replay protection
+    /// rejects duplicated or stale counters.
     ///
     /// # Arguments
     ///
-    /// * `encrypted_bytes` - Encrypted frame data (ciphertext + auth tag)
+    /// * `encrypted_bytes` - Counter prefix plus encrypted frame data
     ///
     /// # Returns
     ///
@@ -287,11 +370,21 @@ impl PeerConnection {
     ///
     /// Returns error if decryption fails, authentication fails, or replay is detected.
     pub async fn decrypt_frame(&self, encrypted_bytes: &[u8]) -> Result<Vec<u8>> {
+        if encrypted_bytes.len() < 8 {
+            return Err(NodeError::Serialization("encrypted frame too short".into()));
+        }
+
+        let counter = u64::from_be_bytes(
+            encrypted_bytes[..8]
+                .try_into()
+                .expect("slice is exactly 8 bytes"),
+        );
+
         let mut crypto = self.crypto.write().await;
 
         // Decrypt with empty AAD
         crypto
-            .decrypt(encrypted_bytes, &[])
+            .decrypt_with_counter(counter, &encrypted_bytes[8..], &[])
             .map_err(|e| NodeError::Crypto(e.to_string()))
     }
 
@@ -386,7 +479,7 @@ pub struct ConnectionStats {
 /// same socket, causing a race where whichever wins receives msg2 and the other times out.
 /// With channeling, only `packet_receive_loop` receives packets and forwards handshake packets
 /// to the appropriate channel.
-pub async fn perform_handshake_initiator<T: Transport + Send + Sync>(
+pub async fn perform_handshake_initiator<T: Transport + Send + Sync + ?Sized>(
     local_keypair: &NoiseKeypair,
     peer_addr: SocketAddr,
     transport: &T,
@@ -534,7 +627,7 @@ pub async fn perform_handshake_initiator<T: Transport + Send + Sync>(
 /// # Returns
 ///
 /// Returns session crypto, session ID, and peer's public key on success.
-pub async fn perform_handshake_responder<T: Transport + Send + Sync>(
+pub async fn perform_handshake_responder<T: Transport + Send + Sync + ?Sized>(
     local_keypair: &NoiseKeypair,
     msg1: &[u8],
     peer_addr: SocketAddr,
@@ -644,8 +737,10 @@ pub async fn perform_handshake_responder<T: Transport + Send + Sync>(
         .into_session_keys()
         .map_err(|e| NodeError::Handshake(format!("Failed to extract keys: {e}").into()))?;
 
-    // Create session crypto (responder: recv=send_key, send=recv_key - reversed from initiator)
-    let crypto = SessionCrypto::new(keys.recv_key, keys.send_key, &keys.chain_key);
+    // Create session crypto; into_session_keys() already assigned send/recv
+    // by role, so the responder uses the keys as-is (swapping here would give
+    // both sides the same send key)
+    let crypto = SessionCrypto::new(keys.send_key, keys.recv_key, &keys.chain_key);
 
     // Derive session ID from keys (extend 8-byte CID to 32-byte session ID)
     let cid = keys.derive_connection_id();
@@ -733,6 +828,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_outbound_queue_prioritizes_control() {
+        use crate::frame::{FrameBuilder, FrameType};
+
+        let conn = PeerConnection::new_for_test([2u8; 32], "127.0.0.1:5000".parse().unwrap());
+        let build = |frame_type| {
+            FrameBuilder::new()
+                .frame_type(frame_type)
+                .stream_id(1)
+                .sequence(0)
+                .payload(b"test")
+                .build(128)
+                .unwrap()
+        };
+
+        // DATA queued first, ACK second - the ACK still drains first
+        let data = build(FrameType::Data);
+        let ack = build(FrameType::Ack);
+        assert!(conn.enqueue_outbound(data.clone()));
+        assert!(conn.enqueue_outbound(ack.clone()));
+        assert_eq!(conn.outbound_len(), 2);
+
+        assert_eq!(conn.pop_outbound(), Some(ack));
+        assert_eq!(conn.pop_outbound(), Some(data));
+        assert_eq!(conn.pop_outbound(), None);
+    }
+
     #[test]
     fn test_connection_stats() {
         let stats = ConnectionStats::default();
@@ -8,8 +8,13 @@
 //!
 //! # Architecture
 //!
-//! Sessions are stored in a concurrent DashMap keyed by peer ID (X25519 public key).
-//! Each session has an associated route in the routing table for O(1) packet lookup.
+//! Sessions are stored in a concurrent DashMap keyed by peer ID (X25519 public key),
+//! which shards its internal state by key hash so concurrent operations on
+//! different peers do not serialize on a single lock. Each session has an
+//! associated route in the routing table for O(1) packet lookup. The transport
+//! handle lives in a read-mostly [`TransportSlot`] rather than behind an async
+//! mutex, and per-operation counters expose how the manager is being driven
+//! via [`SessionManager::stats`].
 //!
 //! # Handshake Flow
 //!
@@ -30,13 +35,15 @@ use crate::node::session::{
     HandshakePacket, PeerConnection, PeerId, SessionId, perform_handshake_initiator,
     perform_handshake_responder,
 };
+use crate::node::transport_slot::TransportSlot;
 use crate::{ConnectionId, HandshakePhase, SessionState};
 use dashmap::DashMap;
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::{Mutex, oneshot};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::sync::oneshot;
 use wraith_crypto::noise::NoiseKeypair;
-use wraith_transport::udp_async::AsyncUdpTransport;
+use wraith_transport::transport::Transport;
 
 /// Session manager for WRAITH nodes
 ///
@@ -53,7 +60,31 @@ pub struct SessionManager {
     pending_handshakes: Arc<DashMap<SocketAddr, oneshot::Sender<HandshakePacket>>>,
 
     /// Transport layer
-    transport: Arc<Mutex<Option<Arc<AsyncUdpTransport>>>>,
+    transport: Arc<TransportSlot>,
+
+    /// Sessions established by this manager
+    established: AtomicU64,
+
+    /// Sessions closed by this manager
+    closed: AtomicU64,
+
+    /// Session lookups (get/get-or-establish/has)
+    lookups: AtomicU64,
+}
+
+/// Operation statistics for a [`SessionManager`]
+#[derive(Debug, Clone, Copy)]
+pub struct SessionManagerStats {
+    /// Active sessions
+    pub active_sessions: usize,
+    /// Handshakes currently in flight
+    pub pending_handshakes: usize,
+    /// Sessions established
+    pub established: u64,
+    /// Sessions closed
+    pub closed: u64,
+    /// Session lookups
+    pub lookups: u64,
 }
 
 impl SessionManager {
@@ -62,23 +93,24 @@ impl SessionManager {
         local_keypair: Arc<NoiseKeypair>,
         sessions: Arc<DashMap<PeerId, Arc<PeerConnection>>>,
         pending_handshakes: Arc<DashMap<SocketAddr, oneshot::Sender<HandshakePacket>>>,
-        transport: Arc<Mutex<Option<Arc<AsyncUdpTransport>>>>,
+        transport: Arc<TransportSlot>,
     ) -> Self {
         Self {
             local_keypair,
             sessions,
             pending_handshakes,
             transport,
+            established: AtomicU64::new(0),
+            closed: AtomicU64::new(0),
+            lookups: AtomicU64::new(0),
         }
     }
 
     /// Get the transport layer
-    async fn get_transport(&self) -> Result<Arc<AsyncUdpTransport>> {
-        let guard = self.transport.lock().await;
-        guard
-            .as_ref()
+    async fn get_transport(&self) -> Result<Arc<dyn Transport>> {
+        self.transport
+            .get()
             .ok_or_else(|| NodeError::invalid_state("Transport not initialized"))
-            .cloned()
     }
 
     /// Establish session with peer at known address
@@ -155,6 +187,7 @@ impl SessionManager {
         // Store session using X25519 peer_id from handshake
         let connection_arc = Arc::new(connection);
         self.sessions.insert(peer_id, Arc::clone(&connection_arc));
+        self.established.fetch_add(1, Ordering::Relaxed);
 
         // Add route to routing table for Connection ID-based packet routing
         let cid_u64 = u64::from_be_bytes(connection_id_bytes);
@@ -248,6 +281,7 @@ impl SessionManager {
 
         let connection_arc = Arc::new(connection);
         self.sessions.insert(peer_id, Arc::clone(&connection_arc));
+        self.established.fetch_add(1, Ordering::Relaxed);
 
         // Add route to routing table for Connection ID-based packet routing
         let cid_u64 = u64::from_be_bytes(connection_id_bytes);
@@ -279,6 +313,7 @@ impl SessionManager {
         routing: &crate::node::routing::RoutingTable,
     ) -> Result<Arc<PeerConnection>> {
         // Try to get existing session
+        self.lookups.fetch_add(1, Ordering::Relaxed);
         if let Some(connection) = self.sessions.get(peer_id) {
             return Ok(Arc::clone(connection.value()));
         }
@@ -309,6 +344,8 @@ impl SessionManager {
         routing: &crate::node::routing::RoutingTable,
     ) -> Result<()> {
         if let Some((_, connection)) = self.sessions.remove(peer_id) {
+            self.closed.fetch_add(1, Ordering::Relaxed);
+
             // Remove route from routing table
             let cid_u64 = connection.connection_id.as_u64();
             routing.remove_route(cid_u64);
@@ -327,6 +364,7 @@ impl SessionManager {
 
     /// Get session by peer ID
     pub fn get_session(&self, peer_id: &PeerId) -> Option<Arc<PeerConnection>> {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
         self.sessions.get(peer_id).map(|e| Arc::clone(e.value()))
     }
 
@@ -345,6 +383,17 @@ impl SessionManager {
         self.sessions.len()
     }
 
+    /// Get operation statistics
+    pub fn stats(&self) -> SessionManagerStats {
+        SessionManagerStats {
+            active_sessions: self.sessions.len(),
+            pending_handshakes: self.pending_handshakes.len(),
+            established: self.established.load(Ordering::Relaxed),
+            closed: self.closed.load(Ordering::Relaxed),
+            lookups: self.lookups.load(Ordering::Relaxed),
+        }
+    }
+
     /// Close all sessions
     ///
     /// Used during node shutdown to gracefully close all connections.
@@ -405,7 +454,7 @@ mod tests {
             Arc::new(keypair),
             Arc::new(DashMap::new()),
             Arc::new(DashMap::new()),
-            Arc::new(Mutex::new(None)),
+            Arc::new(TransportSlot::new()),
         )
     }
 
@@ -416,6 +465,19 @@ mod tests {
         assert!(manager.active_sessions().is_empty());
     }
 
+    #[test]
+    fn test_stats_track_lookups() {
+        let manager = create_test_manager();
+        let stats = manager.stats();
+        assert_eq!(stats.active_sessions, 0);
+        assert_eq!(stats.established, 0);
+        assert_eq!(stats.closed, 0);
+
+        let peer_id = [42u8; 32];
+        assert!(manager.get_session(&peer_id).is_none());
+        assert_eq!(manager.stats().lookups, 1);
+    }
+
     #[test]
     fn test_has_session() {
         let manager = create_test_manager();
@@ -0,0 +1,401 @@
+//! Byte-stream pipe API over the stream multiplexing layer.
+//!
+//! File transfers stage data on disk before sending. This module exposes the
+//! stream layer directly as [`WraithStream`], an `AsyncRead + AsyncWrite`
+//! duplex pipe, so applications can push arbitrary byte streams (stdout, tar
+//! pipelines, live logs) through an encrypted WRAITH session without staging
+//! them as files first.
+//!
+//! # Protocol
+//!
+//! Pipe streams reuse the existing frame types with stream IDs allocated
+//! from a reserved range so they never collide with file-transfer streams
+//! (which derive their IDs from transfer IDs):
+//!
+//! - `StreamOpen` with the [`PIPE_STREAM_MAGIC`] payload opens a pipe
+//! - `Data` frames carry the byte stream (sequence numbers increase per frame)
+//! - `StreamClose` signals EOF; the reader side then returns 0 bytes
+//!
+//! # Usage
+//!
+//! ```text
+//! Initiator                          Acceptor
+//!     |                                  |
+//!     |  open_stream(peer_id)            |  accept_stream().await
+//!     |-- StreamOpen (WPIPE magic) ----->|
+//!     |-- Data (seq 0..n) -------------->|  AsyncRead
+//!     |<- Data (seq 0..m) ---------------|  AsyncWrite
+//!     |-- StreamClose ------------------>|  read returns EOF
+//! ```
+
+use crate::FRAME_HEADER_SIZE;
+use crate::frame::{FrameBuilder, FrameType};
+use crate::node::Node;
+use crate::node::error::{NodeError, Result};
+use crate::node::session::{PeerConnection, PeerId};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::sync::mpsc;
+
+/// StreamOpen payload marking a byte-stream pipe (not a file transfer)
+pub const PIPE_STREAM_MAGIC: &[u8] = b"WPIPE\x01";
+
+/// First stream ID in the range reserved for pipe streams
+///
+/// File transfers derive stream IDs from the first two transfer ID bytes,
+/// so pipes allocate from the top of the ID space to avoid collisions.
+pub(crate) const PIPE_STREAM_BASE: u16 = 0xF000;
+
+/// Maximum payload bytes per Data frame on a pipe stream
+///
+/// Keeps each frame within a single UDP datagram after encryption and
+/// obfuscation overhead.
+const MAX_PIPE_PAYLOAD: usize = 16 * 1024;
+
+/// Incoming data channel depth per pipe stream
+const PIPE_CHANNEL_CAPACITY: usize = 64;
+
+/// Future type for in-flight frame sends
+type SendFuture = Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+/// Encrypted duplex byte stream to a peer
+///
+/// Implements `AsyncRead + AsyncWrite` so it composes with `tokio::io`
+/// utilities (`copy`, `split`, buffered readers). Writes are chunked into
+/// Data frames and sent through the session's encryption, obfuscation, and
+/// bandwidth-limiting pipeline; reads yield payload bytes in frame arrival
+/// order. Dropping the stream without [`shutdown`](AsyncWrite::poll_shutdown)
+/// closes the local side without notifying the peer.
+pub struct WraithStream {
+    node: Node,
+    connection: Arc<PeerConnection>,
+    stream_id: u16,
+    /// Incoming Data frame payloads, pushed by the packet handler
+    incoming: mpsc::Receiver<Vec<u8>>,
+    /// Unread remainder of the last received payload
+    read_buf: Vec<u8>,
+    read_pos: usize,
+    /// Next outgoing Data frame sequence number
+    next_seq: u32,
+    /// In-flight write, polled to completion before accepting more data
+    write_fut: Option<SendFuture>,
+    /// Bytes the in-flight write will report on completion
+    write_len: usize,
+    /// In-flight StreamClose send
+    shutdown_fut: Option<SendFuture>,
+    shutdown_done: bool,
+}
+
+impl WraithStream {
+    fn new(
+        node: Node,
+        connection: Arc<PeerConnection>,
+        stream_id: u16,
+        incoming: mpsc::Receiver<Vec<u8>>,
+    ) -> Self {
+        Self {
+            node,
+            connection,
+            stream_id,
+            incoming,
+            read_buf: Vec::new(),
+            read_pos: 0,
+            next_seq: 0,
+            write_fut: None,
+            write_len: 0,
+            shutdown_fut: None,
+            shutdown_done: false,
+        }
+    }
+
+    /// Stream ID of this pipe
+    pub fn stream_id(&self) -> u16 {
+        self.stream_id
+    }
+
+    /// Peer on the other end of the pipe
+    pub fn peer_id(&self) -> PeerId {
+        self.connection.peer_id
+    }
+
+    /// Build the future that sends one frame through the node pipeline
+    fn send_frame_future(&self, frame_bytes: Vec<u8>) -> SendFuture {
+        let node = self.node.clone();
+        let connection = Arc::clone(&self.connection);
+        Box::pin(async move { node.send_encrypted_frame(&connection, &frame_bytes).await })
+    }
+}
+
+/// Build a Data frame carrying pipe payload bytes
+fn build_pipe_data_frame(stream_id: u16, sequence: u32, payload: &[u8]) -> Result<Vec<u8>> {
+    FrameBuilder::new()
+        .frame_type(FrameType::Data)
+        .stream_id(stream_id)
+        .sequence(sequence)
+        .payload(payload)
+        .build(FRAME_HEADER_SIZE + payload.len())
+        .map_err(|e| NodeError::InvalidState(format!("Failed to build pipe frame: {e}").into()))
+}
+
+/// Build a StreamClose frame for a pipe stream
+fn build_pipe_close_frame(stream_id: u16, sequence: u32) -> Result<Vec<u8>> {
+    FrameBuilder::new()
+        .frame_type(FrameType::StreamClose)
+        .stream_id(stream_id)
+        .sequence(sequence)
+        .build(64)
+        .map_err(|e| NodeError::InvalidState(format!("Failed to build close frame: {e}").into()))
+}
+
+impl AsyncRead for WraithStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        // Serve buffered bytes from the last payload first
+        if self.read_pos < self.read_buf.len() {
+            let remaining = &self.read_buf[self.read_pos..];
+            let n = remaining.len().min(buf.remaining());
+            buf.put_slice(&remaining[..n]);
+            self.read_pos += n;
+            return Poll::Ready(Ok(()));
+        }
+
+        match self.incoming.poll_recv(cx) {
+            Poll::Ready(Some(payload)) => {
+                let n = payload.len().min(buf.remaining());
+                buf.put_slice(&payload[..n]);
+                if n < payload.len() {
+                    self.read_buf = payload;
+                    self.read_pos = n;
+                }
+                Poll::Ready(Ok(()))
+            }
+            // Channel closed: peer sent StreamClose (or the node stopped)
+            Poll::Ready(None) => Poll::Ready(Ok(())),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl AsyncWrite for WraithStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        data: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        if self.shutdown_done {
+            return Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::BrokenPipe,
+                "stream is shut down",
+            )));
+        }
+
+        // Drive any in-flight send to completion before accepting more
+        if let Some(fut) = self.write_fut.as_mut() {
+            match fut.as_mut().poll(cx) {
+                Poll::Ready(Ok(())) => {
+                    self.write_fut = None;
+                    return Poll::Ready(Ok(self.write_len));
+                }
+                Poll::Ready(Err(e)) => {
+                    self.write_fut = None;
+                    return Poll::Ready(Err(std::io::Error::other(e)));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let len = data.len().min(MAX_PIPE_PAYLOAD);
+        let frame = build_pipe_data_frame(self.stream_id, self.next_seq, &data[..len])
+            .map_err(std::io::Error::other)?;
+        self.next_seq = self.next_seq.wrapping_add(1);
+        self.write_len = len;
+
+        let mut fut = self.send_frame_future(frame);
+        match fut.as_mut().poll(cx) {
+            Poll::Ready(Ok(())) => Poll::Ready(Ok(len)),
+            Poll::Ready(Err(e)) => Poll::Ready(Err(std::io::Error::other(e))),
+            Poll::Pending => {
+                self.write_fut = Some(fut);
+                Poll::Pending
+            }
+        }
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        // Frames are handed to the transport as soon as the send future
+        // completes, so flushing only drains the in-flight write
+        if let Some(fut) = self.write_fut.as_mut() {
+            match fut.as_mut().poll(cx) {
+                Poll::Ready(Ok(())) => {
+                    self.write_fut = None;
+                    Poll::Ready(Ok(()))
+                }
+                Poll::Ready(Err(e)) => {
+                    self.write_fut = None;
+                    Poll::Ready(Err(std::io::Error::other(e)))
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        } else {
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        if self.shutdown_done {
+            return Poll::Ready(Ok(()));
+        }
+
+        // Drain any in-flight data write first
+        if self.write_fut.is_some() {
+            match self.as_mut().poll_flush(cx) {
+                Poll::Ready(Ok(())) => {}
+                other => return other,
+            }
+        }
+
+        if self.shutdown_fut.is_none() {
+            let frame = build_pipe_close_frame(self.stream_id, self.next_seq)
+                .map_err(std::io::Error::other)?;
+            self.shutdown_fut = Some(self.send_frame_future(frame));
+        }
+
+        let fut = self.shutdown_fut.as_mut().expect("just set");
+        match fut.as_mut().poll(cx) {
+            Poll::Ready(result) => {
+                self.shutdown_fut = None;
+                self.shutdown_done = true;
+                result.map_err(std::io::Error::other).into()
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for WraithStream {
+    fn drop(&mut self) {
+        self.node.inner.pipe_streams.remove(&self.stream_id);
+    }
+}
+
+impl std::fmt::Debug for WraithStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WraithStream")
+            .field("stream_id", &self.stream_id)
+            .field("peer_id", &hex::encode(&self.connection.peer_id[..8]))
+            .field("next_seq", &self.next_seq)
+            .finish()
+    }
+}
+
+impl Node {
+    /// Open a byte-stream pipe to a peer
+    ///
+    /// Establishes a session if none exists, allocates a pipe stream ID, and
+    /// sends a StreamOpen announcing the pipe. The peer obtains its end via
+    /// [`Node::accept_stream`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if session establishment fails or the StreamOpen
+    /// cannot be sent.
+    pub async fn open_stream(&self, peer_id: &PeerId) -> Result<WraithStream> {
+        let connection = self.get_or_establish_session(peer_id).await?;
+
+        let offset = self.inner.next_pipe_stream.fetch_add(1, Ordering::Relaxed);
+        let stream_id = PIPE_STREAM_BASE | (offset & 0x0FFF);
+
+        let (tx, rx) = mpsc::channel(PIPE_CHANNEL_CAPACITY);
+        self.inner.pipe_streams.insert(stream_id, tx);
+
+        let open_frame = FrameBuilder::new()
+            .frame_type(FrameType::StreamOpen)
+            .stream_id(stream_id)
+            .sequence(0)
+            .payload(PIPE_STREAM_MAGIC)
+            .build(64)
+            .map_err(|e| {
+                NodeError::InvalidState(format!("Failed to build StreamOpen: {e}").into())
+            })?;
+
+        if let Err(e) = self.send_encrypted_frame(&connection, &open_frame).await {
+            self.inner.pipe_streams.remove(&stream_id);
+            return Err(e);
+        }
+
+        tracing::info!(
+            "Opened pipe stream {} to peer {}",
+            stream_id,
+            hex::encode(&peer_id[..8])
+        );
+
+        Ok(WraithStream::new(self.clone(), connection, stream_id, rx))
+    }
+
+    /// Accept the next incoming byte-stream pipe
+    ///
+    /// Waits until a peer opens a pipe with [`Node::open_stream`]. Returns
+    /// `None` if the node is stopped while waiting.
+    pub async fn accept_stream(&self) -> Option<WraithStream> {
+        self.inner.pipe_accepts.lock().await.recv().await
+    }
+
+    /// Handle an incoming pipe StreamOpen (packet handler)
+    pub(crate) fn handle_pipe_open(&self, stream_id: u16, peer_id: PeerId) -> Result<()> {
+        let connection = self
+            .inner
+            .sessions
+            .get(&peer_id)
+            .map(|e| Arc::clone(e.value()))
+            .ok_or(NodeError::SessionNotFound(peer_id))?;
+
+        let (tx, rx) = mpsc::channel(PIPE_CHANNEL_CAPACITY);
+        self.inner.pipe_streams.insert(stream_id, tx);
+
+        let stream = WraithStream::new(self.clone(), connection, stream_id, rx);
+
+        tracing::info!(
+            "Accepted pipe stream {} from peer {}",
+            stream_id,
+            hex::encode(&peer_id[..8])
+        );
+        if self.inner.pipe_accepts_tx.send(stream).is_err() {
+            self.inner.pipe_streams.remove(&stream_id);
+            return Err(NodeError::invalid_state("Pipe accept queue closed"));
+        }
+
+        Ok(())
+    }
+
+    /// Deliver a Data frame payload to a pipe stream, if one matches
+    ///
+    /// Returns true if the frame belonged to a pipe stream and was consumed.
+    pub(crate) fn deliver_pipe_data(&self, stream_id: u16, payload: &[u8]) -> bool {
+        if let Some(tx) = self.inner.pipe_streams.get(&stream_id) {
+            // Drop-on-full matches UDP semantics: a slow reader loses
+            // frames rather than stalling the packet receive loop
+            if tx.try_send(payload.to_vec()).is_err() {
+                tracing::debug!("Pipe stream {} receive buffer full, dropping", stream_id);
+            }
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Close the local end of a pipe stream (peer sent StreamClose)
+    ///
+    /// Returns true if a pipe stream matched the ID.
+    pub(crate) fn close_pipe_stream(&self, stream_id: u16) -> bool {
+        // Dropping the sender closes the channel, which surfaces as EOF
+        // on the reader side
+        self.inner.pipe_streams.remove(&stream_id).is_some()
+    }
+}
@@ -0,0 +1,579 @@
+//! Swarm seeding: receivers re-share verified chunks (HAVE / CHOKE).
+//!
+//! A multi-peer download normally pulls every chunk from the original
+//! sender. This module lets receivers of the same content hash serve each
+//! other BitTorrent-style: as soon as a receiver has verified a chunk
+//! against the tree hash, it can announce and upload that chunk to other
+//! receivers, turning N downloads of the same file into a swarm.
+//!
+//! Messages ride Control frames using the same tag convention as transfer
+//! offers, keyed by the file's root hash so transfers of the same content
+//! find each other regardless of transfer ID:
+//!
+//! - `HAVE_BITMAP` announces the full set of verified chunks (sent on join
+//!   and on request)
+//! - `HAVE` announces one newly verified chunk
+//! - `CHOKE` / `UNCHOKE` gate whether the sender will currently serve the
+//!   peer
+//! - `REQUEST` asks an unchoked peer for one chunk
+//!
+//! Upload starvation is avoided with classic choking: a seeder keeps at
+//! most [`UPLOAD_SLOTS`] interested peers unchoked, ranked by how much data
+//! they have contributed back, plus one optimistically unchoked peer
+//! rotated on each [`SwarmSeeder::rechoke`] so newcomers with nothing to
+//! reciprocate still get a chance to bootstrap.
+
+use crate::node::error::{NodeError, Result};
+use crate::node::session::PeerId;
+use std::collections::HashMap;
+
+/// Control payload tag: full have-bitmap announcement
+pub const CONTROL_SWARM_HAVE_BITMAP: u8 = 0x20;
+/// Control payload tag: single newly verified chunk announcement
+pub const CONTROL_SWARM_HAVE: u8 = 0x21;
+/// Control payload tag: sender will not serve the peer for now
+pub const CONTROL_SWARM_CHOKE: u8 = 0x22;
+/// Control payload tag: sender is willing to serve the peer
+pub const CONTROL_SWARM_UNCHOKE: u8 = 0x23;
+/// Control payload tag: request one chunk from an unchoked peer
+pub const CONTROL_SWARM_REQUEST: u8 = 0x24;
+
+/// Regular unchoke slots per seeder (reciprocation-ranked)
+pub const UPLOAD_SLOTS: usize = 3;
+
+/// Bitmap of verified chunks for one content hash
+///
+/// One bit per chunk, most significant bit of byte 0 = chunk 0, matching
+/// the BitTorrent bitfield layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HaveBitmap {
+    total_chunks: u64,
+    bits: Vec<u8>,
+}
+
+impl HaveBitmap {
+    /// Create an empty bitmap for `total_chunks` chunks
+    #[must_use]
+    pub fn new(total_chunks: u64) -> Self {
+        let bytes = total_chunks.div_ceil(8) as usize;
+        Self {
+            total_chunks,
+            bits: vec![0u8; bytes],
+        }
+    }
+
+    /// Create a bitmap with every chunk marked (a full seed)
+    #[must_use]
+    pub fn full(total_chunks: u64) -> Self {
+        let mut bitmap = Self::new(total_chunks);
+        for index in 0..total_chunks {
+            bitmap.mark(index);
+        }
+        bitmap
+    }
+
+    /// Total number of chunks this bitmap covers
+    #[must_use]
+    pub fn total_chunks(&self) -> u64 {
+        self.total_chunks
+    }
+
+    /// Mark a chunk as verified; returns `true` if it was newly set
+    pub fn mark(&mut self, chunk_index: u64) -> bool {
+        if chunk_index >= self.total_chunks {
+            return false;
+        }
+        let byte = (chunk_index / 8) as usize;
+        let mask = 0x80u8 >> (chunk_index % 8);
+        let newly_set = self.bits[byte] & mask == 0;
+        self.bits[byte] |= mask;
+        newly_set
+    }
+
+    /// Whether a chunk is marked as verified
+    #[must_use]
+    pub fn has(&self, chunk_index: u64) -> bool {
+        if chunk_index >= self.total_chunks {
+            return false;
+        }
+        let byte = (chunk_index / 8) as usize;
+        let mask = 0x80u8 >> (chunk_index % 8);
+        self.bits[byte] & mask != 0
+    }
+
+    /// Number of verified chunks
+    #[must_use]
+    pub fn have_count(&self) -> u64 {
+        self.bits.iter().map(|b| u64::from(b.count_ones())).sum()
+    }
+
+    /// Whether every chunk is verified
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.have_count() == self.total_chunks
+    }
+
+    /// Serialize: total_chunks(8, BE) + packed bits
+    #[must_use]
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(8 + self.bits.len());
+        out.extend_from_slice(&self.total_chunks.to_be_bytes());
+        out.extend_from_slice(&self.bits);
+        out
+    }
+
+    /// Deserialize a bitmap, rejecting length mismatches
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the buffer is truncated or the packed bits do
+    /// not match the declared chunk count.
+    pub fn deserialize(data: &[u8]) -> Result<Self> {
+        let count_bytes = data
+            .get(..8)
+            .ok_or_else(|| NodeError::invalid_state("Have-bitmap truncated (chunk count)"))?;
+        let total_chunks = u64::from_be_bytes(count_bytes.try_into().expect("sliced to 8"));
+        let expected = total_chunks.div_ceil(8) as usize;
+        let bits = &data[8..];
+        if bits.len() != expected {
+            return Err(NodeError::invalid_state("Have-bitmap length mismatch"));
+        }
+        Ok(Self {
+            total_chunks,
+            bits: bits.to_vec(),
+        })
+    }
+}
+
+/// A swarm control message, keyed by the content's root hash
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SwarmMessage {
+    /// Full verified-chunk announcement
+    HaveBitmap {
+        /// Root hash identifying the content
+        content_hash: [u8; 32],
+        /// The announcer's verified chunks
+        bitmap: HaveBitmap,
+    },
+    /// One newly verified chunk
+    Have {
+        /// Root hash identifying the content
+        content_hash: [u8; 32],
+        /// Index of the newly verified chunk
+        chunk_index: u64,
+    },
+    /// The sender will not serve the peer for now
+    Choke {
+        /// Root hash identifying the content
+        content_hash: [u8; 32],
+    },
+    /// The sender is willing to serve the peer
+    Unchoke {
+        /// Root hash identifying the content
+        content_hash: [u8; 32],
+    },
+    /// Request one chunk (only honored while unchoked)
+    Request {
+        /// Root hash identifying the content
+        content_hash: [u8; 32],
+        /// Index of the requested chunk
+        chunk_index: u64,
+    },
+}
+
+impl SwarmMessage {
+    /// Encode as a Control payload: tag(1) + content_hash(32) + body
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let (tag, content_hash) = match self {
+            Self::HaveBitmap { content_hash, .. } => (CONTROL_SWARM_HAVE_BITMAP, content_hash),
+            Self::Have { content_hash, .. } => (CONTROL_SWARM_HAVE, content_hash),
+            Self::Choke { content_hash } => (CONTROL_SWARM_CHOKE, content_hash),
+            Self::Unchoke { content_hash } => (CONTROL_SWARM_UNCHOKE, content_hash),
+            Self::Request { content_hash, .. } => (CONTROL_SWARM_REQUEST, content_hash),
+        };
+
+        let mut payload = Vec::with_capacity(41);
+        payload.push(tag);
+        payload.extend_from_slice(content_hash);
+        match self {
+            Self::HaveBitmap { bitmap, .. } => payload.extend_from_slice(&bitmap.serialize()),
+            Self::Have { chunk_index, .. } | Self::Request { chunk_index, .. } => {
+                payload.extend_from_slice(&chunk_index.to_be_bytes());
+            }
+            Self::Choke { .. } | Self::Unchoke { .. } => {}
+        }
+        payload
+    }
+
+    /// Decode a Control payload (tag already consumed)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for unknown tags or truncated bodies.
+    pub fn decode(tag: u8, data: &[u8]) -> Result<Self> {
+        let content_hash: [u8; 32] = data
+            .get(..32)
+            .and_then(|b| b.try_into().ok())
+            .ok_or_else(|| NodeError::invalid_state("Swarm message truncated (content hash)"))?;
+        let body = &data[32..];
+
+        match tag {
+            CONTROL_SWARM_HAVE_BITMAP => Ok(Self::HaveBitmap {
+                content_hash,
+                bitmap: HaveBitmap::deserialize(body)?,
+            }),
+            CONTROL_SWARM_HAVE | CONTROL_SWARM_REQUEST => {
+                let index_bytes = body.get(..8).ok_or_else(|| {
+                    NodeError::invalid_state("Swarm message truncated (chunk index)")
+                })?;
+                let chunk_index = u64::from_be_bytes(index_bytes.try_into().expect("sliced to 8"));
+                if tag == CONTROL_SWARM_HAVE {
+                    Ok(Self::Have {
+                        content_hash,
+                        chunk_index,
+                    })
+                } else {
+                    Ok(Self::Request {
+                        content_hash,
+                        chunk_index,
+                    })
+                }
+            }
+            CONTROL_SWARM_CHOKE => Ok(Self::Choke { content_hash }),
+            CONTROL_SWARM_UNCHOKE => Ok(Self::Unchoke { content_hash }),
+            other => Err(NodeError::InvalidState(
+                format!("Unknown swarm message tag: {other:#04x}").into(),
+            )),
+        }
+    }
+}
+
+/// Per-peer upload state tracked by a [`SwarmSeeder`]
+#[derive(Debug, Clone)]
+struct SeedPeerState {
+    /// Peer has asked to download from us
+    interested: bool,
+    /// We are currently refusing to serve this peer
+    choked: bool,
+    /// Bytes this peer has uploaded to us (reciprocation credit)
+    contributed_bytes: u64,
+}
+
+impl SeedPeerState {
+    fn new() -> Self {
+        Self {
+            interested: false,
+            choked: true,
+            contributed_bytes: 0,
+        }
+    }
+}
+
+/// Choke/unchoke decisions produced by one [`SwarmSeeder::rechoke`] round
+///
+/// Contains only the peers whose state changed; the caller sends a
+/// [`SwarmMessage::Choke`] / [`SwarmMessage::Unchoke`] to each.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChokeUpdate {
+    /// Peers to notify that they are now choked
+    pub choke: Vec<PeerId>,
+    /// Peers to notify that they are now unchoked
+    pub unchoke: Vec<PeerId>,
+}
+
+/// Upload side of one swarm: verified chunks plus per-peer choking
+///
+/// A receiver creates one seeder per in-progress (or completed) download,
+/// marks chunks as they verify, and answers [`SwarmMessage::Request`]s from
+/// peers that pass [`SwarmSeeder::can_serve`]. [`SwarmSeeder::rechoke`]
+/// should run periodically (BitTorrent uses 10 s) to re-rank peers by
+/// reciprocation and rotate the optimistic slot.
+#[derive(Debug)]
+pub struct SwarmSeeder {
+    /// Root hash identifying the content being seeded
+    content_hash: [u8; 32],
+    /// Chunks verified locally and available to serve
+    local_have: HaveBitmap,
+    /// Upload state per peer
+    peers: HashMap<PeerId, SeedPeerState>,
+    /// Rotation cursor for the optimistic unchoke slot
+    optimistic_cursor: usize,
+}
+
+impl SwarmSeeder {
+    /// Create a seeder with no verified chunks yet
+    #[must_use]
+    pub fn new(content_hash: [u8; 32], total_chunks: u64) -> Self {
+        Self {
+            content_hash,
+            local_have: HaveBitmap::new(total_chunks),
+            peers: HashMap::new(),
+            optimistic_cursor: 0,
+        }
+    }
+
+    /// Root hash of the content this seeder serves
+    #[must_use]
+    pub fn content_hash(&self) -> &[u8; 32] {
+        &self.content_hash
+    }
+
+    /// The locally verified chunks (send as [`SwarmMessage::HaveBitmap`])
+    #[must_use]
+    pub fn local_have(&self) -> &HaveBitmap {
+        &self.local_have
+    }
+
+    /// Mark a chunk as verified and servable
+    ///
+    /// Returns `true` if the chunk was newly marked, in which case the
+    /// caller should broadcast a [`SwarmMessage::Have`] to swarm peers.
+    pub fn mark_verified(&mut self, chunk_index: u64) -> bool {
+        self.local_have.mark(chunk_index)
+    }
+
+    /// Track a peer that joined the swarm (starts choked)
+    pub fn add_peer(&mut self, peer_id: PeerId) {
+        self.peers.entry(peer_id).or_insert_with(SeedPeerState::new);
+    }
+
+    /// Forget a departed peer
+    pub fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.peers.remove(peer_id);
+    }
+
+    /// Record whether a peer wants to download from us
+    pub fn set_interested(&mut self, peer_id: PeerId, interested: bool) {
+        self.peers
+            .entry(peer_id)
+            .or_insert_with(SeedPeerState::new)
+            .interested = interested;
+    }
+
+    /// Credit bytes a peer uploaded to us (drives unchoke ranking)
+    pub fn record_contribution(&mut self, peer_id: PeerId, bytes: u64) {
+        self.peers
+            .entry(peer_id)
+            .or_insert_with(SeedPeerState::new)
+            .contributed_bytes += bytes;
+    }
+
+    /// Whether a request from `peer_id` for `chunk_index` should be served
+    #[must_use]
+    pub fn can_serve(&self, peer_id: &PeerId, chunk_index: u64) -> bool {
+        self.local_have.has(chunk_index) && self.peers.get(peer_id).is_some_and(|peer| !peer.choked)
+    }
+
+    /// Re-rank peers and rotate the optimistic slot
+    ///
+    /// Unchokes the top [`UPLOAD_SLOTS`] interested peers by contributed
+    /// bytes plus one optimistically unchoked peer rotated across the
+    /// remaining interested peers; everyone else is choked. Returns only
+    /// the peers whose state changed so the caller can notify them.
+    pub fn rechoke(&mut self) -> ChokeUpdate {
+        // Interested peers ranked by reciprocation, ties broken by peer ID
+        // so rounds are deterministic
+        let mut ranked: Vec<(PeerId, u64)> = self
+            .peers
+            .iter()
+            .filter(|(_, state)| state.interested)
+            .map(|(id, state)| (*id, state.contributed_bytes))
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut unchoked: Vec<PeerId> = ranked
+            .iter()
+            .take(UPLOAD_SLOTS)
+            .map(|(id, _)| *id)
+            .collect();
+
+        // Optimistic slot: rotate through the interested peers that did not
+        // earn a regular slot
+        let leftover: Vec<PeerId> = ranked
+            .iter()
+            .skip(UPLOAD_SLOTS)
+            .map(|(id, _)| *id)
+            .collect();
+        if !leftover.is_empty() {
+            unchoked.push(leftover[self.optimistic_cursor % leftover.len()]);
+            self.optimistic_cursor = self.optimistic_cursor.wrapping_add(1);
+        }
+
+        let mut update = ChokeUpdate::default();
+        for (peer_id, state) in &mut self.peers {
+            let should_choke = !unchoked.contains(peer_id);
+            if state.choked != should_choke {
+                state.choked = should_choke;
+                if should_choke {
+                    update.choke.push(*peer_id);
+                } else {
+                    update.unchoke.push(*peer_id);
+                }
+            }
+        }
+        update
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_have_bitmap_mark_and_query() {
+        let mut bitmap = HaveBitmap::new(10);
+        assert_eq!(bitmap.have_count(), 0);
+        assert!(!bitmap.has(0));
+
+        assert!(bitmap.mark(0));
+        assert!(bitmap.mark(9));
+        assert!(!bitmap.mark(0)); // already set
+        assert!(!bitmap.mark(10)); // out of range
+
+        assert!(bitmap.has(0));
+        assert!(bitmap.has(9));
+        assert!(!bitmap.has(5));
+        assert!(!bitmap.has(10));
+        assert_eq!(bitmap.have_count(), 2);
+        assert!(!bitmap.is_complete());
+    }
+
+    #[test]
+    fn test_have_bitmap_full_and_complete() {
+        let bitmap = HaveBitmap::full(17);
+        assert_eq!(bitmap.have_count(), 17);
+        assert!(bitmap.is_complete());
+        assert!(bitmap.has(16));
+        assert!(!bitmap.has(17));
+    }
+
+    #[test]
+    fn test_have_bitmap_serialization_roundtrip() {
+        let mut bitmap = HaveBitmap::new(20);
+        bitmap.mark(3);
+        bitmap.mark(8);
+        bitmap.mark(19);
+
+        let decoded = HaveBitmap::deserialize(&bitmap.serialize()).unwrap();
+        assert_eq!(decoded, bitmap);
+    }
+
+    #[test]
+    fn test_have_bitmap_rejects_length_mismatch() {
+        assert!(HaveBitmap::deserialize(&[0u8; 4]).is_err());
+
+        let mut data = HaveBitmap::new(20).serialize();
+        data.push(0); // extra byte the chunk count does not account for
+        assert!(HaveBitmap::deserialize(&data).is_err());
+    }
+
+    #[test]
+    fn test_swarm_message_roundtrip() {
+        let mut bitmap = HaveBitmap::new(100);
+        bitmap.mark(42);
+
+        let messages = [
+            SwarmMessage::HaveBitmap {
+                content_hash: [1u8; 32],
+                bitmap,
+            },
+            SwarmMessage::Have {
+                content_hash: [2u8; 32],
+                chunk_index: 42,
+            },
+            SwarmMessage::Choke {
+                content_hash: [3u8; 32],
+            },
+            SwarmMessage::Unchoke {
+                content_hash: [4u8; 32],
+            },
+            SwarmMessage::Request {
+                content_hash: [5u8; 32],
+                chunk_index: 7,
+            },
+        ];
+
+        for message in messages {
+            let encoded = message.encode();
+            let decoded = SwarmMessage::decode(encoded[0], &encoded[1..]).unwrap();
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn test_swarm_message_rejects_malformed() {
+        assert!(SwarmMessage::decode(CONTROL_SWARM_HAVE, &[0u8; 8]).is_err()); // short hash
+        assert!(SwarmMessage::decode(CONTROL_SWARM_HAVE, &[0u8; 32]).is_err()); // no index
+        assert!(SwarmMessage::decode(0xFF, &[0u8; 40]).is_err()); // unknown tag
+    }
+
+    #[test]
+    fn test_seeder_serves_only_unchoked_verified() {
+        let peer = [1u8; 32];
+        let mut seeder = SwarmSeeder::new([9u8; 32], 10);
+        seeder.add_peer(peer);
+        seeder.set_interested(peer, true);
+        seeder.mark_verified(3);
+
+        // Choked by default
+        assert!(!seeder.can_serve(&peer, 3));
+
+        seeder.rechoke();
+        assert!(seeder.can_serve(&peer, 3));
+        assert!(!seeder.can_serve(&peer, 4)); // not verified locally
+        assert!(!seeder.can_serve(&[2u8; 32], 3)); // unknown peer
+    }
+
+    #[test]
+    fn test_rechoke_prefers_reciprocating_peers() {
+        let mut seeder = SwarmSeeder::new([9u8; 32], 10);
+        // One more interested peer than there are regular slots
+        let peers: Vec<PeerId> = (0..UPLOAD_SLOTS as u8 + 1).map(|i| [i + 1; 32]).collect();
+        for peer in &peers {
+            seeder.add_peer(*peer);
+            seeder.set_interested(*peer, true);
+        }
+        // Everyone but the last peer has contributed
+        for (idx, peer) in peers.iter().take(UPLOAD_SLOTS).enumerate() {
+            seeder.record_contribution(*peer, 1_000_000 * (idx as u64 + 1));
+        }
+
+        let update = seeder.rechoke();
+        // All four end up unchoked: three earned slots plus the optimistic one
+        assert_eq!(update.unchoke.len(), UPLOAD_SLOTS + 1);
+        assert!(update.choke.is_empty());
+
+        // A fifth freeloader cannot displace reciprocating peers from the
+        // regular slots, but competes for the optimistic slot
+        let freeloader = [0xEE; 32];
+        seeder.add_peer(freeloader);
+        seeder.set_interested(freeloader, true);
+        let update = seeder.rechoke();
+        for peer in peers.iter().take(UPLOAD_SLOTS) {
+            assert!(!update.choke.contains(peer));
+        }
+    }
+
+    #[test]
+    fn test_rechoke_rotates_optimistic_slot() {
+        let mut seeder = SwarmSeeder::new([9u8; 32], 10);
+        // Two peers with no contribution beyond the regular slots
+        let peers: Vec<PeerId> = (0..UPLOAD_SLOTS as u8 + 2).map(|i| [i + 1; 32]).collect();
+        for peer in &peers {
+            seeder.add_peer(*peer);
+            seeder.set_interested(*peer, true);
+        }
+
+        let first = seeder.rechoke();
+        assert_eq!(first.unchoke.len(), UPLOAD_SLOTS + 1);
+
+        // Next round the optimistic slot moves to the other leftover peer
+        let second = seeder.rechoke();
+        assert_eq!(second.choke.len(), 1);
+        assert_eq!(second.unchoke.len(), 1);
+        assert_ne!(second.unchoke[0], second.choke[0]);
+        assert!(first.unchoke.contains(&second.choke[0]));
+        assert!(!first.unchoke.contains(&second.unchoke[0]));
+    }
+}
@@ -0,0 +1,368 @@
+//! Opt-in anonymous telemetry
+//!
+//! Periodically reports anonymized aggregate statistics (version, OS,
+//! NAT type distribution, relay vs direct connection ratio, throughput
+//! buckets) to a configurable endpoint. Reports contain no identifiers:
+//! no node IDs, peer IDs, addresses, or file names - only counters.
+//!
+//! Telemetry is **off by default** and never activates without an
+//! explicit `enabled = true` plus an endpoint in [`TelemetryConfig`].
+//! Counters are drained on each report, so every report covers one
+//! interval.
+
+use serde::Serialize;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use crate::node::discovery::NatType;
+
+/// Default reporting interval (1 hour)
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Timeout for one report upload
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Telemetry configuration
+///
+/// Disabled by default; both `enabled` and `endpoint` must be set for
+/// any report to leave the node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetryConfig {
+    /// Master switch - must be explicitly enabled by the operator
+    pub enabled: bool,
+    /// Report endpoint as `host:port` (HTTP POST to `/v1/telemetry`)
+    pub endpoint: Option<String>,
+    /// Reporting interval
+    pub interval: Duration,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: None,
+            interval: DEFAULT_INTERVAL,
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// Whether reporting is fully configured and switched on
+    #[must_use]
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.endpoint.is_some()
+    }
+}
+
+/// Throughput buckets for anonymized transfer rate distribution
+///
+/// Bucketing means a report reveals only coarse rate classes, not
+/// actual transfer speeds.
+const THROUGHPUT_BUCKETS: [(&str, u64); 5] = [
+    ("lt_1mbps", 1_000_000),
+    ("1_10mbps", 10_000_000),
+    ("10_100mbps", 100_000_000),
+    ("100_1000mbps", 1_000_000_000),
+    ("gte_1gbps", u64::MAX),
+];
+
+/// One anonymized aggregate report
+#[derive(Debug, Clone, Serialize)]
+pub struct TelemetryReport {
+    /// Protocol version (crate version)
+    pub version: &'static str,
+    /// Operating system (e.g. "linux")
+    pub os: &'static str,
+    /// CPU architecture (e.g. "x86_64")
+    pub arch: &'static str,
+    /// Direct sessions established this interval
+    pub direct_connections: u64,
+    /// Relayed sessions established this interval
+    pub relayed_connections: u64,
+    /// Transfers completed this interval
+    pub transfers_completed: u64,
+    /// NAT type observations, keyed by type name
+    pub nat_types: std::collections::BTreeMap<&'static str, u64>,
+    /// Completed-transfer throughput distribution, keyed by bucket
+    pub throughput: std::collections::BTreeMap<&'static str, u64>,
+}
+
+/// Lock-free collector for telemetry counters
+///
+/// Always safe to record into - recording is a handful of atomic
+/// increments and happens regardless of configuration. Whether anything
+/// leaves the node is decided solely by [`TelemetryConfig`].
+#[derive(Debug, Default)]
+pub struct TelemetryCollector {
+    direct_connections: AtomicU64,
+    relayed_connections: AtomicU64,
+    transfers_completed: AtomicU64,
+    /// Indexed by `NatType` discriminant order
+    nat_types: [AtomicU64; 5],
+    /// Indexed by `THROUGHPUT_BUCKETS` order
+    throughput: [AtomicU64; 5],
+}
+
+impl TelemetryCollector {
+    /// Create a collector with all counters at zero
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an established session (direct or relayed path)
+    pub fn record_connection(&self, relayed: bool) {
+        if relayed {
+            self.relayed_connections.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.direct_connections.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record an observed NAT type
+    pub fn record_nat_type(&self, nat_type: NatType) {
+        self.nat_types[Self::nat_index(nat_type)].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record a completed transfer with its average throughput
+    pub fn record_transfer_completed(&self, bits_per_sec: u64) {
+        self.transfers_completed.fetch_add(1, Ordering::Relaxed);
+        for (i, (_, upper)) in THROUGHPUT_BUCKETS.iter().enumerate() {
+            if bits_per_sec < *upper {
+                self.throughput[i].fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        }
+    }
+
+    /// Drain all counters into a report covering the elapsed interval
+    #[must_use]
+    pub fn drain(&self) -> TelemetryReport {
+        let mut nat_types = std::collections::BTreeMap::new();
+        for (i, name) in Self::NAT_NAMES.iter().enumerate() {
+            let count = self.nat_types[i].swap(0, Ordering::Relaxed);
+            if count > 0 {
+                nat_types.insert(*name, count);
+            }
+        }
+
+        let mut throughput = std::collections::BTreeMap::new();
+        for (i, (name, _)) in THROUGHPUT_BUCKETS.iter().enumerate() {
+            let count = self.throughput[i].swap(0, Ordering::Relaxed);
+            if count > 0 {
+                throughput.insert(*name, count);
+            }
+        }
+
+        TelemetryReport {
+            version: env!("CARGO_PKG_VERSION"),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            direct_connections: self.direct_connections.swap(0, Ordering::Relaxed),
+            relayed_connections: self.relayed_connections.swap(0, Ordering::Relaxed),
+            transfers_completed: self.transfers_completed.swap(0, Ordering::Relaxed),
+            nat_types,
+            throughput,
+        }
+    }
+
+    /// NAT type names in discriminant order
+    const NAT_NAMES: [&'static str; 5] = [
+        "none",
+        "full_cone",
+        "restricted_cone",
+        "port_restricted",
+        "symmetric",
+    ];
+
+    fn nat_index(nat_type: NatType) -> usize {
+        match nat_type {
+            NatType::None => 0,
+            NatType::FullCone => 1,
+            NatType::RestrictedCone => 2,
+            NatType::PortRestricted => 3,
+            NatType::Symmetric => 4,
+        }
+    }
+}
+
+/// Background reporting loop
+///
+/// Drains the collector every interval and POSTs the JSON report to
+/// the configured endpoint. Upload failures are logged at debug level
+/// and the drained counters are dropped - telemetry must never affect
+/// node operation.
+pub(crate) async fn reporting_loop(config: TelemetryConfig, collector: Arc<TelemetryCollector>) {
+    let Some(endpoint) = config.endpoint else {
+        return;
+    };
+    tracing::info!(
+        "Telemetry enabled: reporting to {endpoint} every {:?}",
+        config.interval
+    );
+
+    let mut interval = tokio::time::interval(config.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    interval.tick().await; // First tick fires immediately; skip it
+
+    loop {
+        interval.tick().await;
+        let report = collector.drain();
+        if let Err(e) = upload_report(&endpoint, &report).await {
+            tracing::debug!("Telemetry upload to {endpoint} failed: {e}");
+        }
+    }
+}
+
+/// POST one report as JSON over a plain HTTP/1.1 connection
+async fn upload_report(
+    endpoint: &str,
+    report: &TelemetryReport,
+) -> std::result::Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let body = serde_json::to_string(report)?;
+    let host = endpoint.split(':').next().unwrap_or(endpoint);
+    let request = format!(
+        "POST /v1/telemetry HTTP/1.1\r\nHost: {host}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+
+    tokio::time::timeout(UPLOAD_TIMEOUT, async {
+        let mut stream = TcpStream::connect(endpoint).await?;
+        stream.write_all(request.as_bytes()).await?;
+        // Read and discard the response so the server sees a clean close
+        let mut buf = [0u8; 512];
+        let _ = stream.read(&mut buf).await;
+        Ok::<(), std::io::Error>(())
+    })
+    .await
+    .map_err(|_| std::io::Error::new(std::io::ErrorKind::TimedOut, "upload timed out"))??;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_telemetry_disabled_by_default() {
+        let config = TelemetryConfig::default();
+        assert!(!config.enabled);
+        assert!(config.endpoint.is_none());
+        assert!(!config.is_active());
+    }
+
+    #[test]
+    fn test_telemetry_requires_endpoint() {
+        let config = TelemetryConfig {
+            enabled: true,
+            ..TelemetryConfig::default()
+        };
+        assert!(!config.is_active());
+
+        let config = TelemetryConfig {
+            enabled: true,
+            endpoint: Some("telemetry.example.com:80".to_string()),
+            ..TelemetryConfig::default()
+        };
+        assert!(config.is_active());
+    }
+
+    #[test]
+    fn test_collector_connection_counts() {
+        let collector = TelemetryCollector::new();
+        collector.record_connection(false);
+        collector.record_connection(false);
+        collector.record_connection(true);
+
+        let report = collector.drain();
+        assert_eq!(report.direct_connections, 2);
+        assert_eq!(report.relayed_connections, 1);
+    }
+
+    #[test]
+    fn test_collector_nat_type_distribution() {
+        let collector = TelemetryCollector::new();
+        collector.record_nat_type(NatType::Symmetric);
+        collector.record_nat_type(NatType::Symmetric);
+        collector.record_nat_type(NatType::FullCone);
+
+        let report = collector.drain();
+        assert_eq!(report.nat_types.get("symmetric"), Some(&2));
+        assert_eq!(report.nat_types.get("full_cone"), Some(&1));
+        assert_eq!(report.nat_types.get("none"), None);
+    }
+
+    #[test]
+    fn test_collector_throughput_buckets() {
+        let collector = TelemetryCollector::new();
+        collector.record_transfer_completed(500_000); // <1 Mbps
+        collector.record_transfer_completed(50_000_000); // 10-100 Mbps
+        collector.record_transfer_completed(2_000_000_000); // >=1 Gbps
+
+        let report = collector.drain();
+        assert_eq!(report.transfers_completed, 3);
+        assert_eq!(report.throughput.get("lt_1mbps"), Some(&1));
+        assert_eq!(report.throughput.get("10_100mbps"), Some(&1));
+        assert_eq!(report.throughput.get("gte_1gbps"), Some(&1));
+    }
+
+    #[test]
+    fn test_drain_resets_counters() {
+        let collector = TelemetryCollector::new();
+        collector.record_connection(false);
+        collector.record_transfer_completed(1_000_000);
+
+        let first = collector.drain();
+        assert_eq!(first.direct_connections, 1);
+
+        let second = collector.drain();
+        assert_eq!(second.direct_connections, 0);
+        assert_eq!(second.transfers_completed, 0);
+        assert!(second.throughput.is_empty());
+    }
+
+    #[test]
+    fn test_report_contains_no_identifiers() {
+        let collector = TelemetryCollector::new();
+        collector.record_connection(false);
+
+        let json = serde_json::to_string(&collector.drain()).unwrap();
+        // Only environment facts and counters: spot-check the shape
+        assert!(json.contains("\"version\""));
+        assert!(json.contains("\"os\""));
+        assert!(!json.contains("addr"));
+        assert!(!json.contains("peer"));
+        assert!(!json.contains("node_id"));
+    }
+
+    #[tokio::test]
+    async fn test_upload_report_to_local_server() {
+        use tokio::net::TcpListener;
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = listener.local_addr().unwrap().to_string();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            stream
+                .write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_string()
+        });
+
+        let collector = TelemetryCollector::new();
+        collector.record_connection(true);
+        upload_report(&endpoint, &collector.drain()).await.unwrap();
+
+        let received = server.await.unwrap();
+        assert!(received.starts_with("POST /v1/telemetry HTTP/1.1"));
+        assert!(received.contains("\"relayed_connections\":1"));
+    }
+}
@@ -10,7 +10,7 @@ use std::collections::HashMap;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use wraith_files::chunker::FileReassembler;
+use wraith_files::chunker::{FileChunker, FileReassembler};
 use wraith_files::tree_hash::compute_tree_hash;
 
 /// File metadata for transfers
@@ -105,6 +105,7 @@ impl Node {
                 Arc::new(tokio::sync::RwLock::new(transfer_session)),
                 reassembler.clone(),
                 tree_hash,
+                metadata.total_chunks as u64,
             ),
         );
         self.inner.transfers.insert(transfer_id, context.clone());
@@ -376,6 +377,22 @@ impl Node {
                 }
             };
 
+            // Chaos: corrupt the received chunk and/or fail the disk write
+            #[cfg(feature = "chaos")]
+            let chunk_data = {
+                let mut chunk_data = chunk_data;
+                if self.inner.chaos.maybe_corrupt_chunk(&mut chunk_data) {
+                    tracing::warn!("Chaos: corrupted chunk {} before reassembly", chunk_idx);
+                }
+                chunk_data
+            };
+            #[cfg(feature = "chaos")]
+            if self.inner.chaos.should_fail_write() {
+                return Err(NodeError::Io(
+                    "Chaos: injected disk write failure".to_string(),
+                ));
+            }
+
             // Write to reassembler
             if let Some(reassembler) = &context.reassembler {
                 reassembler
@@ -402,8 +419,10 @@ impl Node {
 
     /// Upload chunks to a requesting peer
     ///
-    /// Serves chunks from a file being seeded.
-    /// Reads requested chunks from file and sends them via Data frames.
+    /// Serves chunks from a file being seeded and sends them via Data
+    /// frames. With a chunk store configured, chunks are served from the
+    /// content-addressed store (re-warming it after eviction); otherwise
+    /// they are read straight from the file.
     pub async fn upload_chunks_to_peer(
         &self,
         peer_id: &PeerId,
@@ -411,7 +430,6 @@ impl Node {
         chunks: Vec<usize>,
     ) -> Result<(), NodeError> {
         use crate::frame::{FrameBuilder, FrameType};
-        use wraith_files::chunker::FileChunker;
 
         tracing::debug!(
             "Uploading {} chunks to peer {:?} from {}",
@@ -427,16 +445,23 @@ impl Node {
         let mut chunker = FileChunker::new(file_path, self.inner.config.transfer.chunk_size)
             .map_err(|e| NodeError::Io(e.to_string()))?;
 
+        // The chunk store addresses chunks by manifest (root hash), so
+        // map the path back to its announcement once up front
+        let root_hash = self
+            .inner
+            .available_files
+            .iter()
+            .find(|entry| entry.value().1 == file_path)
+            .map(|entry| *entry.key());
+
         // Stream ID derived from peer_id
         let stream_id = ((peer_id[0] as u16) << 8) | (peer_id[1] as u16);
 
         // Send each requested chunk
         let num_chunks = chunks.len();
         for chunk_idx in chunks {
-            // Read chunk from file
-            let chunk_data = chunker
-                .read_chunk_at(chunk_idx as u64)
-                .map_err(|e| NodeError::Io(e.to_string()))?;
+            // Serve from the chunk store when possible, else from the file
+            let chunk_data = self.read_seed_chunk(root_hash, &mut chunker, chunk_idx as u64)?;
 
             // Build Data frame
             let frame = FrameBuilder::new()
@@ -470,6 +495,78 @@ impl Node {
         Ok(())
     }
 
+    /// Read one seeded chunk, preferring the content-addressed store
+    ///
+    /// A store hit skips the file read entirely. A chunk evicted by the
+    /// cache limit is re-read from the file and put back so subsequent
+    /// requests hit the store again. Store I/O failures only demote to
+    /// the file read; they never fail the upload.
+    fn read_seed_chunk(
+        &self,
+        root_hash: Option<[u8; 32]>,
+        chunker: &mut FileChunker,
+        chunk_index: u64,
+    ) -> Result<Vec<u8>, NodeError> {
+        if let (Some(store), Some(root)) = (self.inner.chunk_store.as_ref(), root_hash) {
+            let mut store = store.lock().unwrap();
+            if let Some(hash) = store.manifest_chunk(&root, chunk_index as usize) {
+                match store.get_chunk(&hash) {
+                    Ok(Some(data)) => return Ok(data),
+                    Ok(None) => {
+                        // Evicted: re-read from the file and re-warm
+                        let data = chunker
+                            .read_chunk_at(chunk_index)
+                            .map_err(|e| NodeError::Io(e.to_string()))?;
+                        if let Err(e) = store.put_chunk(&data) {
+                            tracing::debug!("Failed to re-store evicted chunk: {e}");
+                        }
+                        return Ok(data);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Chunk store read failed, serving from file: {e}");
+                    }
+                }
+            }
+        }
+        chunker
+            .read_chunk_at(chunk_index)
+            .map_err(|e| NodeError::Io(e.to_string()))
+    }
+
+    /// Copy an announced file's chunks into the store and register its manifest
+    fn store_seed_chunks(&self, file_path: &Path, root_hash: [u8; 32]) -> std::io::Result<()> {
+        let Some(store) = self.inner.chunk_store.as_ref() else {
+            return Ok(());
+        };
+        let mut chunker = FileChunker::new(file_path, self.inner.config.transfer.chunk_size)?;
+
+        let mut store = store.lock().unwrap();
+        let mut chunk_hashes = Vec::with_capacity(chunker.num_chunks() as usize);
+        while let Some(chunk) = chunker.read_chunk()? {
+            chunk_hashes.push(store.put_chunk(&chunk)?);
+        }
+        store.add_manifest(root_hash, &chunk_hashes);
+        let evicted = store.enforce_cache_limit()?;
+        if evicted.chunks_removed > 0 {
+            tracing::debug!(
+                "Chunk store over limit after announce: evicted {} chunks ({} bytes)",
+                evicted.chunks_removed,
+                evicted.bytes_reclaimed
+            );
+        }
+        Ok(())
+    }
+
+    /// On-disk usage of the seed chunk store
+    ///
+    /// Returns `None` when no `chunk_store_dir` is configured.
+    pub async fn chunk_store_usage(&self) -> Option<wraith_files::chunk_store::StoreUsage> {
+        self.inner
+            .chunk_store
+            .as_ref()
+            .map(|store| store.lock().unwrap().usage())
+    }
+
     /// Get list of files available for download
     ///
     /// Returns list of files this node can serve.
@@ -537,6 +634,20 @@ impl Node {
             .available_files
             .insert(root_hash, (metadata.clone(), file_path.to_path_buf()));
 
+        // Register with the background scrubber so bit-rot is caught and
+        // the announcement withdrawn before peers fetch damaged chunks
+        self.inner
+            .scrubber
+            .add_seed(file_path, tree_hash, chunk_size);
+
+        // Copy the chunks into the content-addressed store (when one is
+        // configured) so the upload path serves deduplicated seed data.
+        // The store is a cache: failing to fill it is not an announce
+        // failure, the file itself stays servable
+        if let Err(e) = self.store_seed_chunks(file_path, root_hash) {
+            tracing::warn!("Failed to store seed chunks for {:?}: {e}", &root_hash[..8]);
+        }
+
         tracing::info!(
             "File announced: {} ({} bytes, {} chunks, hash: {:?})",
             metadata.name,
@@ -581,6 +692,26 @@ impl Node {
     ///
     /// Returns error if file is not currently announced.
     pub async fn unannounce_file(&self, file_hash: &[u8; 32]) -> Result<(), NodeError> {
+        self.inner.scrubber.remove_seed(file_hash);
+
+        // Drop the store manifest and reclaim chunks nothing else
+        // references (shared chunks stay for the other manifests)
+        if let Some(store) = self.inner.chunk_store.as_ref() {
+            let mut store = store.lock().unwrap();
+            store.remove_manifest(file_hash);
+            match store.collect_garbage() {
+                Ok(stats) if stats.chunks_removed > 0 => {
+                    tracing::debug!(
+                        "Chunk store GC after unannounce: removed {} chunks ({} bytes)",
+                        stats.chunks_removed,
+                        stats.bytes_reclaimed
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Chunk store GC failed: {e}"),
+            }
+        }
+
         match self.inner.available_files.remove(file_hash) {
             Some((_, (metadata, path))) => {
                 let _ = metadata; // suppress unused warning
@@ -606,6 +737,52 @@ impl Node {
             )),
         }
     }
+
+    /// Run one integrity-scrub pass over announced files immediately
+    ///
+    /// The background loop spawned by [`Node::start`] does this on its own
+    /// schedule; this forces a pass (e.g. after suspected disk trouble)
+    /// and returns the report. Files that fail verification are withdrawn
+    /// from the announce set by the scrub event consumer.
+    pub async fn scrub_now(&self) -> wraith_files::scrub::ScrubReport {
+        self.inner.scrubber.scrub_once().await
+    }
+
+    /// Consume scrubber events and withdraw corrupted announcements
+    ///
+    /// Spawned by [`Node::start`]; runs until the node's scrubber is
+    /// dropped. Corruption reports translate into [`Node::unannounce_file`]
+    /// so the DHT stops pointing peers at damaged content.
+    pub(crate) async fn scrub_event_loop(&self) {
+        use tokio::sync::broadcast::error::RecvError;
+        use wraith_files::scrub::ScrubEvent;
+
+        let mut events = self.inner.scrubber.subscribe();
+        loop {
+            match events.recv().await {
+                Ok(ScrubEvent::CorruptionDetected {
+                    root_hash, path, ..
+                }) => {
+                    tracing::warn!(
+                        "Withdrawing corrupted announcement {:?} ({})",
+                        &root_hash[..8],
+                        path.display()
+                    );
+                    if let Err(e) = self.unannounce_file(&root_hash).await {
+                        tracing::debug!(
+                            "Corrupted file {:?} was already unannounced: {e}",
+                            &root_hash[..8]
+                        );
+                    }
+                }
+                Ok(ScrubEvent::PassCompleted { .. }) => {}
+                Err(RecvError::Lagged(missed)) => {
+                    tracing::warn!("Scrub event consumer lagged by {missed} events");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -758,4 +935,96 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_announce_fills_chunk_store_and_unannounce_collects_it() {
+        use std::io::Write;
+        let dir = tempfile::TempDir::new().unwrap();
+        let node = Node::new_with_config(crate::node::config::NodeConfig {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            transfer: crate::node::config::TransferConfig {
+                chunk_size: 1024,
+                chunk_store_dir: Some(dir.path().join("store")),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+
+        let file_path = dir.path().join("seed.dat");
+        let mut file = std::fs::File::create(&file_path).unwrap();
+        let data: Vec<u8> = (0..4096).map(|i| (i % 251) as u8).collect();
+        file.write_all(&data).unwrap();
+        drop(file);
+
+        let file_hash = node.announce_file(&file_path).await.unwrap();
+
+        let usage = node.chunk_store_usage().await.unwrap();
+        assert_eq!(usage.chunk_count, 4);
+        assert_eq!(usage.manifest_count, 1);
+        assert_eq!(usage.referenced_bytes, 4096);
+        assert_eq!(usage.unreferenced_bytes, 0);
+
+        // Unannouncing drops the manifest and GCs the chunks
+        node.unannounce_file(&file_hash).await.unwrap();
+        let usage = node.chunk_store_usage().await.unwrap();
+        assert_eq!(usage.chunk_count, 0);
+        assert_eq!(usage.manifest_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_scrub_withdraws_corrupted_announcement() {
+        use std::io::{Seek, SeekFrom, Write};
+        let node = Node::new_with_config(crate::node::config::NodeConfig {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        node.start().await.unwrap();
+
+        let temp_dir = std::env::temp_dir();
+        let file_path = temp_dir.join("wraith_test_scrub.dat");
+        let mut file = std::fs::File::create(&file_path).unwrap();
+        file.write_all(&vec![7u8; 4096]).unwrap();
+        drop(file);
+
+        let file_hash = node.announce_file(&file_path).await.unwrap();
+
+        // Clean content survives a pass and stays announced
+        let report = node.scrub_now().await;
+        assert_eq!(report.files_verified, 1);
+        assert!(
+            node.list_available_files()
+                .await
+                .iter()
+                .any(|m| m.root_hash == file_hash)
+        );
+
+        // Flip a byte and scrub again
+        let mut file = std::fs::OpenOptions::new()
+            .write(true)
+            .open(&file_path)
+            .unwrap();
+        file.seek(SeekFrom::Start(100)).unwrap();
+        file.write_all(&[0xFF]).unwrap();
+        drop(file);
+
+        let report = node.scrub_now().await;
+        assert_eq!(report.files_corrupted, 1);
+
+        // The event loop withdraws the announcement asynchronously
+        let mut withdrawn = false;
+        for _ in 0..50 {
+            if node.list_available_files().await.is_empty() {
+                withdrawn = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let _ = std::fs::remove_file(&file_path);
+        node.stop().await.unwrap();
+        assert!(withdrawn);
+    }
 }
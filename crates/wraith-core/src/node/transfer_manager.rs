@@ -5,6 +5,13 @@
 //! - Progress tracking
 //! - Chunk management
 //!
+//! # Architecture
+//!
+//! Transfer contexts live in a concurrent DashMap keyed by transfer ID, which
+//! shards its internal state by key hash so concurrent transfers do not
+//! serialize on a single lock. Per-operation counters expose how the manager
+//! is being driven via [`TransferManager::stats`].
+//!
 //! # Transfer Flow
 //!
 //! ```text
@@ -24,11 +31,12 @@ use crate::node::error::{NodeError, Result};
 use crate::node::file_transfer::FileTransferContext;
 use crate::node::identity::TransferId;
 use crate::node::session::PeerConnection;
-use crate::transfer::TransferSession;
+use crate::transfer::{BatchConfig, ChunkBatcher, TransferSchedule, TransferSession};
 use dashmap::DashMap;
 use getrandom::getrandom;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use tokio::sync::{Mutex, RwLock};
 use wraith_files::chunker::{FileChunker, FileReassembler};
@@ -44,6 +52,61 @@ pub struct TransferManager {
 
     /// Default chunk size for transfers
     chunk_size: usize,
+
+    /// Stamp outgoing chunk frames with trace IDs for log correlation
+    trace_frames: bool,
+
+    /// Rate-adaptive chunk batching policy for sends
+    batch_config: BatchConfig,
+
+    /// Recent per-path estimates for careful-resume batch seeding
+    path_estimates: Option<Arc<crate::node::path_estimates::PathEstimateCache>>,
+
+    /// Scheduling constraints per transfer (start time, windows, rates)
+    schedules: DashMap<TransferId, TransferSchedule>,
+
+    /// Transfers initiated (send + receive)
+    initiated: AtomicU64,
+
+    /// Transfers removed (completed, failed, or cancelled)
+    removed: AtomicU64,
+
+    /// Transfer lookups by ID or stream
+    lookups: AtomicU64,
+}
+
+/// Operation statistics for a [`TransferManager`]
+#[derive(Debug, Clone, Copy)]
+pub struct TransferManagerStats {
+    /// Active transfers
+    pub active_transfers: usize,
+    /// Transfers initiated
+    pub initiated: u64,
+    /// Transfers removed
+    pub removed: u64,
+    /// Transfer lookups
+    pub lookups: u64,
+}
+
+/// Outcome of a fan-out send ([`Node::send_file_to_many`](crate::node::Node::send_file_to_many))
+///
+/// A fan-out is allowed to partially fail: recipients that could not be
+/// reached are reported here while the remaining transfers proceed, each
+/// trackable through the usual per-transfer progress APIs.
+#[derive(Debug, Clone, Default)]
+pub struct FanOutReport {
+    /// Transfers that started, one per reachable recipient
+    pub transfers: Vec<(crate::node::session::PeerId, TransferId)>,
+    /// Recipients that failed during setup, with the reason
+    pub failures: Vec<(crate::node::session::PeerId, String)>,
+}
+
+impl FanOutReport {
+    /// Whether every recipient was reached
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failures.is_empty()
+    }
 }
 
 impl TransferManager {
@@ -60,9 +123,52 @@ impl TransferManager {
         Self {
             transfers,
             chunk_size,
+            trace_frames: false,
+            batch_config: BatchConfig::default(),
+            path_estimates: None,
+            schedules: DashMap::new(),
+            initiated: AtomicU64::new(0),
+            removed: AtomicU64::new(0),
+            lookups: AtomicU64::new(0),
         }
     }
 
+    /// Enable or disable frame tracing for outgoing chunks
+    ///
+    /// See [`LoggingConfig::trace_frames`](crate::node::config::LoggingConfig).
+    #[must_use]
+    pub fn with_frame_tracing(mut self, enabled: bool) -> Self {
+        self.trace_frames = enabled;
+        self
+    }
+
+    /// Override the rate-adaptive batching policy for sends
+    ///
+    /// See [`BatchConfig`]; the default grows disk-read batches toward the
+    /// delivered send rate and splits them under loss.
+    #[must_use]
+    pub fn with_batch_config(mut self, config: BatchConfig) -> Self {
+        self.batch_config = config;
+        self
+    }
+
+    /// Seed send batches from cached path estimates (careful resume)
+    ///
+    /// When a transfer starts toward a peer whose path was measured within
+    /// [`PATH_ESTIMATE_FRESHNESS`](crate::node::path_estimates::PATH_ESTIMATE_FRESHNESS)
+    /// — typically a session re-established shortly after a drop — the
+    /// batcher starts at the size the cached bandwidth implies instead of
+    /// ramping up from the minimum. Delivered-rate measurements are recorded
+    /// back into the cache as sends progress.
+    #[must_use]
+    pub fn with_path_estimates(
+        mut self,
+        cache: Arc<crate::node::path_estimates::PathEstimateCache>,
+    ) -> Self {
+        self.path_estimates = Some(cache);
+        self
+    }
+
     /// Generate a random transfer ID
     pub fn generate_transfer_id() -> TransferId {
         let mut id = [0u8; 32];
@@ -134,6 +240,7 @@ impl TransferManager {
             tree_hash.clone(),
         ));
         self.transfers.insert(transfer_id, Arc::clone(&context));
+        self.initiated.fetch_add(1, Ordering::Relaxed);
 
         tracing::info!(
             "Initialized send transfer {:?} for {} ({} bytes, {} chunks)",
@@ -190,8 +297,10 @@ impl TransferManager {
             Arc::new(RwLock::new(transfer)),
             Arc::new(Mutex::new(reassembler)),
             tree_hash,
+            file_size.div_ceil(chunk_size as u64),
         ));
         self.transfers.insert(transfer_id, context);
+        self.initiated.fetch_add(1, Ordering::Relaxed);
 
         tracing::debug!(
             "Initialized receive transfer {:?} for {} ({} bytes)",
@@ -245,45 +354,92 @@ impl TransferManager {
             hex::encode(&transfer_id[..8])
         );
 
-        // Send each chunk
-        for chunk_index in 0..total_chunks {
-            // Read chunk
-            let chunk_data = chunker
-                .read_chunk_at(chunk_index)
+        // Send chunks in rate-adaptive batches: each batch is one disk read
+        // sized to the delivered send rate, split at the fixed hashed chunk
+        // boundaries so verification is unaffected
+        let mut batcher = ChunkBatcher::new(self.chunk_size, self.batch_config.clone());
+        if let Some(cache) = &self.path_estimates
+            && let Some(snapshot) = cache.fresh(connection.peer_id, connection.peer_addr())
+        {
+            // Careful resume: trust half the previous delivered rate so the
+            // first batches start near the path's known capacity
+            batcher.seed(snapshot.btl_bw / 2);
+        }
+        let transfer_start = std::time::Instant::now();
+        let mut bytes_sent: u64 = 0;
+
+        let mut next_chunk = 0u64;
+        while next_chunk < total_chunks {
+            let range = batcher.batch_range(next_chunk, total_chunks);
+            let batch_count = (range.end - range.start) as usize;
+
+            let batch = chunker
+                .read_chunk_batch(range.start, batch_count)
                 .map_err(|e| NodeError::Io(e.to_string()))?;
-            let chunk_len = chunk_data.len();
-
-            // Verify chunk hash against tree hash
-            if chunk_index < context.tree_hash.chunks.len() as u64 {
-                let computed_hash = blake3::hash(&chunk_data);
-                if computed_hash.as_bytes() != &context.tree_hash.chunks[chunk_index as usize] {
-                    tracing::error!("Chunk {} hash mismatch during send", chunk_index);
-                    return Err(NodeError::InvalidState(
-                        "Chunk hash verification failed".into(),
-                    ));
-                }
-            }
 
-            // Build chunk frame
-            let chunk_frame =
-                crate::node::file_transfer::build_chunk_frame(stream_id, chunk_index, &chunk_data)?;
+            for (offset, chunk_data) in batch.into_iter().enumerate() {
+                let chunk_index = range.start + offset as u64;
+                let chunk_len = chunk_data.len();
+
+                // Verify chunk hash against tree hash
+                if chunk_index < context.tree_hash.chunks.len() as u64 {
+                    let computed_hash = blake3::hash(&chunk_data);
+                    if computed_hash.as_bytes() != &context.tree_hash.chunks[chunk_index as usize] {
+                        tracing::error!("Chunk {} hash mismatch during send", chunk_index);
+                        return Err(NodeError::InvalidState(
+                            "Chunk hash verification failed".into(),
+                        ));
+                    }
+                }
 
-            // Send encrypted frame
-            send_frame_fn(Arc::clone(&connection), chunk_frame).await?;
+                // Build chunk frame
+                let trace_id = self.trace_frames.then(crate::frame::random_trace_id);
+                let chunk_frame = crate::node::file_transfer::build_chunk_frame(
+                    stream_id,
+                    chunk_index,
+                    &chunk_data,
+                    trace_id,
+                )?;
+
+                // Send encrypted frame
+                send_frame_fn(Arc::clone(&connection), chunk_frame).await?;
+                bytes_sent += chunk_len as u64;
+
+                // Update transfer progress
+                {
+                    let mut transfer = context.transfer_session.write().await;
+                    transfer.mark_chunk_transferred(chunk_index, chunk_len);
+                }
 
-            // Update transfer progress
-            {
-                let mut transfer = context.transfer_session.write().await;
-                transfer.mark_chunk_transferred(chunk_index, chunk_len);
+                tracing::trace!(
+                    "Sent chunk {}/{} for transfer {:?} ({} bytes)",
+                    chunk_index + 1,
+                    total_chunks,
+                    hex::encode(&transfer_id[..8]),
+                    chunk_len
+                );
             }
 
-            tracing::trace!(
-                "Sent chunk {}/{} for transfer {:?} ({} bytes)",
-                chunk_index + 1,
-                total_chunks,
-                hex::encode(&transfer_id[..8]),
-                chunk_len
-            );
+            next_chunk = range.end;
+
+            // Re-size the next batch from the delivered rate (the paced
+            // send path makes this track the congestion controller's
+            // bandwidth estimate) and the connection's observed loss
+            let elapsed = transfer_start.elapsed().as_secs_f64();
+            let bandwidth = if elapsed > 0.0 {
+                (bytes_sent as f64 / elapsed) as u64
+            } else {
+                0
+            };
+            batcher.update(bandwidth, connection.stats.loss_rate);
+            if let Some(cache) = &self.path_estimates {
+                cache.record(
+                    connection.peer_id,
+                    connection.peer_addr(),
+                    bandwidth,
+                    connection.stats.rtt_us.map(Duration::from_micros),
+                );
+            }
         }
 
         tracing::info!(
@@ -402,6 +558,7 @@ impl TransferManager {
 
     /// Get transfer context
     pub fn get_transfer(&self, transfer_id: &TransferId) -> Option<Arc<FileTransferContext>> {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
         self.transfers.get(transfer_id).map(|e| Arc::clone(&e))
     }
 
@@ -409,6 +566,7 @@ impl TransferManager {
     ///
     /// Stream ID is derived from transfer ID: `(transfer_id[0] << 8) | transfer_id[1]`
     pub fn find_transfer_by_stream_id(&self, stream_id: u16) -> Option<Arc<FileTransferContext>> {
+        self.lookups.fetch_add(1, Ordering::Relaxed);
         for entry in self.transfers.iter() {
             let tid = entry.key();
             let derived_stream_id = ((tid[0] as u16) << 8) | (tid[1] as u16);
@@ -431,7 +589,42 @@ impl TransferManager {
 
     /// Remove a completed or failed transfer
     pub fn remove_transfer(&self, transfer_id: &TransferId) -> Option<Arc<FileTransferContext>> {
-        self.transfers.remove(transfer_id).map(|(_, ctx)| ctx)
+        let removed = self.transfers.remove(transfer_id).map(|(_, ctx)| ctx);
+        if removed.is_some() {
+            self.schedules.remove(transfer_id);
+            self.removed.fetch_add(1, Ordering::Relaxed);
+        }
+        removed
+    }
+
+    /// Attach scheduling constraints to a transfer
+    ///
+    /// The schedule describes when the transfer may run and which bandwidth
+    /// cap applies ([`TransferSchedule`]); the driving loop (the daemon's
+    /// dispatcher) evaluates it against the wall clock. An unconstrained
+    /// schedule clears any previous one.
+    pub fn set_schedule(&self, transfer_id: TransferId, schedule: TransferSchedule) {
+        if schedule.is_unconstrained() {
+            self.schedules.remove(&transfer_id);
+        } else {
+            self.schedules.insert(transfer_id, schedule);
+        }
+    }
+
+    /// Get the scheduling constraints attached to a transfer
+    #[must_use]
+    pub fn schedule(&self, transfer_id: &TransferId) -> Option<TransferSchedule> {
+        self.schedules.get(transfer_id).map(|s| s.clone())
+    }
+
+    /// Get operation statistics
+    pub fn stats(&self) -> TransferManagerStats {
+        TransferManagerStats {
+            active_transfers: self.transfers.len(),
+            initiated: self.initiated.load(Ordering::Relaxed),
+            removed: self.removed.load(Ordering::Relaxed),
+            lookups: self.lookups.load(Ordering::Relaxed),
+        }
     }
 }
 
@@ -443,6 +636,24 @@ mod tests {
         TransferManager::new(Arc::new(DashMap::new()), 256 * 1024)
     }
 
+    #[test]
+    fn test_stats_track_operations() {
+        let manager = create_test_manager();
+        let stats = manager.stats();
+        assert_eq!(stats.active_transfers, 0);
+        assert_eq!(stats.initiated, 0);
+        assert_eq!(stats.removed, 0);
+
+        let missing = [9u8; 32];
+        assert!(manager.get_transfer(&missing).is_none());
+        assert!(manager.remove_transfer(&missing).is_none());
+
+        let stats = manager.stats();
+        assert_eq!(stats.lookups, 1);
+        // Removing a missing transfer does not count as removed
+        assert_eq!(stats.removed, 0);
+    }
+
     #[test]
     fn test_transfer_manager_creation() {
         let manager = create_test_manager();
@@ -505,4 +716,37 @@ mod tests {
             Err(NodeError::TransferNotFound(_))
         ));
     }
+
+    #[test]
+    fn test_schedule_attach_and_clear() {
+        let manager = create_test_manager();
+        let transfer_id = [42u8; 32];
+
+        assert!(manager.schedule(&transfer_id).is_none());
+
+        let schedule = TransferSchedule {
+            windows: vec!["01:00-06:00".parse().unwrap()],
+            ..Default::default()
+        };
+        manager.set_schedule(transfer_id, schedule.clone());
+        assert_eq!(manager.schedule(&transfer_id), Some(schedule));
+
+        // An unconstrained schedule clears the previous one
+        manager.set_schedule(transfer_id, TransferSchedule::default());
+        assert!(manager.schedule(&transfer_id).is_none());
+    }
+
+    #[test]
+    fn test_fan_out_report_complete() {
+        let mut report = FanOutReport::default();
+        assert!(report.is_complete());
+
+        report.transfers.push(([1u8; 32], [2u8; 32]));
+        assert!(report.is_complete());
+
+        report
+            .failures
+            .push(([3u8; 32], "session establishment failed".into()));
+        assert!(!report.is_complete());
+    }
 }
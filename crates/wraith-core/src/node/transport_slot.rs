@@ -0,0 +1,197 @@
+//! Lock-light transport handle shared across the node.
+//!
+//! The transport is bound once at node start, read on every packet send,
+//! and cleared once at shutdown. Guarding it with an async `Mutex` made
+//! every concurrent send serialize on a single lock just to clone an `Arc`.
+//!
+//! [`TransportSlot`] replaces that with a read-mostly slot: readers take a
+//! brief `std::sync::RwLock` read (never held across an await) to clone the
+//! `Arc`, so concurrent sends to many peers proceed in parallel. The slot
+//! counts reads and contended reads so the improvement is observable via
+//! [`TransportSlot::stats`].
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use wraith_transport::transport::Transport;
+
+/// Shared slot holding the node's transport, if started
+///
+/// # Thread Safety
+///
+/// Readers clone the inner `Arc` under a short-lived read lock; the write
+/// lock is taken only at node start/stop. Safe to use from async code since
+/// the guard never lives across an await point.
+#[derive(Default)]
+pub struct TransportSlot {
+    /// Current transport (None before start / after stop)
+    ///
+    /// Stored as a trait object so the node can publish either the bare UDP
+    /// socket or a [`SwitchableTransport`](wraith_transport::switchable::SwitchableTransport)
+    /// wrapping it, without every reader caring which one is active.
+    slot: RwLock<Option<Arc<dyn Transport>>>,
+
+    /// Total reads of the slot
+    reads: AtomicU64,
+
+    /// Reads that found the lock held and had to wait
+    ///
+    /// With the slot written only at start/stop this should stay near zero;
+    /// a growing value indicates writer contention worth investigating.
+    contended_reads: AtomicU64,
+}
+
+/// Contention statistics for a [`TransportSlot`]
+#[derive(Debug, Clone, Copy)]
+pub struct TransportSlotStats {
+    /// Total slot reads
+    pub reads: u64,
+    /// Reads that blocked on the lock
+    pub contended_reads: u64,
+}
+
+impl TransportSlotStats {
+    /// Fraction of reads that blocked (0.0 if no reads yet)
+    pub fn contention_rate(&self) -> f64 {
+        if self.reads == 0 {
+            0.0
+        } else {
+            self.contended_reads as f64 / self.reads as f64
+        }
+    }
+}
+
+impl std::fmt::Debug for TransportSlot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransportSlot")
+            .field(
+                "set",
+                &self.slot.read().map(|g| g.is_some()).unwrap_or(false),
+            )
+            .field("reads", &self.reads.load(Ordering::Relaxed))
+            .field(
+                "contended_reads",
+                &self.contended_reads.load(Ordering::Relaxed),
+            )
+            .finish()
+    }
+}
+
+impl TransportSlot {
+    /// Create an empty slot
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the current transport, if set
+    ///
+    /// Tries an uncontended read first and records a contended read when
+    /// the lock is held by a writer.
+    pub fn get(&self) -> Option<Arc<dyn Transport>> {
+        self.reads.fetch_add(1, Ordering::Relaxed);
+
+        match self.slot.try_read() {
+            Ok(guard) => guard.clone(),
+            Err(std::sync::TryLockError::WouldBlock) => {
+                self.contended_reads.fetch_add(1, Ordering::Relaxed);
+                self.slot
+                    .read()
+                    .expect("transport slot lock poisoned")
+                    .clone()
+            }
+            Err(std::sync::TryLockError::Poisoned(_)) => {
+                panic!("transport slot lock poisoned")
+            }
+        }
+    }
+
+    /// Publish a transport (node start)
+    pub fn set(&self, transport: Arc<dyn Transport>) {
+        *self.slot.write().expect("transport slot lock poisoned") = Some(transport);
+    }
+
+    /// Take the transport out of the slot (node stop)
+    pub fn clear(&self) -> Option<Arc<dyn Transport>> {
+        self.slot
+            .write()
+            .expect("transport slot lock poisoned")
+            .take()
+    }
+
+    /// Whether a transport is currently set
+    pub fn is_set(&self) -> bool {
+        self.get().is_some()
+    }
+
+    /// Get contention statistics
+    pub fn stats(&self) -> TransportSlotStats {
+        TransportSlotStats {
+            reads: self.reads.load(Ordering::Relaxed),
+            contended_reads: self.contended_reads.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wraith_transport::udp_async::AsyncUdpTransport;
+
+    #[tokio::test]
+    async fn test_empty_slot() {
+        let slot = TransportSlot::new();
+        assert!(slot.get().is_none());
+        assert!(!slot.is_set());
+        assert!(slot.clear().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_get_clear() {
+        let slot = TransportSlot::new();
+        let addr: std::net::SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let transport = Arc::new(AsyncUdpTransport::bind(addr).await.unwrap());
+
+        slot.set(transport);
+        assert!(slot.is_set());
+        assert!(slot.get().is_some());
+
+        let taken = slot.clear();
+        assert!(taken.is_some());
+        assert!(slot.get().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_holds_switchable_transport() {
+        use wraith_transport::switchable::SwitchableTransport;
+
+        let addr: std::net::SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let fast = Arc::new(AsyncUdpTransport::bind(addr).await.unwrap());
+        let fallback = Arc::new(AsyncUdpTransport::bind(addr).await.unwrap());
+
+        let slot = TransportSlot::new();
+        slot.set(Arc::new(SwitchableTransport::new(fast, fallback)));
+        assert!(slot.is_set());
+        assert!(slot.get().unwrap().local_addr().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_stats_count_reads() {
+        let slot = TransportSlot::new();
+        assert_eq!(slot.stats().reads, 0);
+
+        slot.get();
+        slot.get();
+        let stats = slot.stats();
+        assert_eq!(stats.reads, 2);
+        assert_eq!(stats.contended_reads, 0);
+        assert_eq!(stats.contention_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_contention_rate_no_reads() {
+        let stats = TransportSlotStats {
+            reads: 0,
+            contended_reads: 0,
+        };
+        assert_eq!(stats.contention_rate(), 0.0);
+    }
+}
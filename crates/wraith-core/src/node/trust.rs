@@ -0,0 +1,392 @@
+//! Persistent peer trust store with TOFU identity pinning
+//!
+//! Records the static key each peer presented on first contact, à la SSH
+//! `known_hosts`. Later sessions to the same peer must present the pinned
+//! key; a changed key indicates either a legitimate re-key or an active
+//! man-in-the-middle, so sessions fail closed unless the operator allows
+//! key changes ([`TrustConfig::allow_key_change`](crate::node::config::TrustConfig)).
+//!
+//! The store is a human-editable text file (default
+//! `~/.config/wraith/known_peers`), one entry per line:
+//!
+//! ```text
+//! <peer-id-hex> <pinned-key-hex> <first-seen-epoch> <last-seen-epoch>
+//! ```
+//!
+//! Lines starting with `#` are comments. Removing a line un-pins that peer,
+//! exactly like deleting a `known_hosts` entry after a deliberate key change.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::fs;
+use tokio::sync::RwLock;
+
+use crate::node::error::{NodeError, Result};
+use crate::node::session::PeerId;
+
+/// A pinned peer identity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrustEntry {
+    /// The peer this entry pins (the identity the caller dialed)
+    pub peer_id: PeerId,
+
+    /// The static key the peer presented when first seen
+    pub pinned_key: [u8; 32],
+
+    /// When the peer was first seen (seconds since epoch)
+    pub first_seen: u64,
+
+    /// When the pinned key was last confirmed (seconds since epoch)
+    pub last_seen: u64,
+}
+
+/// Outcome of checking a presented key against the store
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrustDecision {
+    /// The presented key matches the pinned key
+    Trusted,
+
+    /// The peer was not in the store; its key has now been pinned
+    FirstUse,
+
+    /// The presented key differs from the pinned key
+    KeyMismatch {
+        /// The key recorded when the peer was first seen
+        pinned_key: [u8; 32],
+    },
+}
+
+/// Persistent store of pinned peer identities
+///
+/// Entries live in memory while the node runs; [`load`](Self::load) and
+/// [`save`](Self::save) move them to and from the `known_peers` file.
+pub struct TrustStore {
+    /// Path to the known_peers file
+    path: PathBuf,
+
+    /// In-memory entries keyed by peer ID
+    entries: Arc<RwLock<HashMap<PeerId, TrustEntry>>>,
+}
+
+impl TrustStore {
+    /// Create a new store backed by the given file
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            entries: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Default store location: `~/.config/wraith/known_peers`
+    ///
+    /// Returns `None` when the home directory cannot be determined.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config/wraith/known_peers"))
+    }
+
+    /// Load entries from disk
+    ///
+    /// A missing file yields an empty store. Malformed lines are skipped
+    /// with a warning rather than discarding the rest of the file, since a
+    /// partially readable trust store is strictly safer than an empty one.
+    pub async fn load(&self) -> Result<()> {
+        let text = match fs::read_to_string(&self.path).await {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(NodeError::Io(e.to_string())),
+        };
+
+        let mut entries = self.entries.write().await;
+        for (line_no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_entry(line) {
+                Some(entry) => {
+                    entries.insert(entry.peer_id, entry);
+                }
+                None => {
+                    tracing::warn!(
+                        "Skipping malformed known_peers entry at {}:{}",
+                        self.path.display(),
+                        line_no + 1
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Save entries to disk
+    pub async fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let entries = self.entries.read().await;
+        let mut sorted: Vec<&TrustEntry> = entries.values().collect();
+        sorted.sort_by_key(|e| e.peer_id);
+
+        let mut text = String::from(
+            "# WRAITH known peers: <peer-id> <pinned-key> <first-seen> <last-seen>\n\
+             # Remove a line to un-pin that peer after a deliberate key change.\n",
+        );
+        for entry in sorted {
+            text.push_str(&format!(
+                "{} {} {} {}\n",
+                hex::encode(entry.peer_id),
+                hex::encode(entry.pinned_key),
+                entry.first_seen,
+                entry.last_seen
+            ));
+        }
+
+        fs::write(&self.path, text).await?;
+        Ok(())
+    }
+
+    /// Check a presented key against the pinned key for a peer
+    ///
+    /// Unknown peers are pinned on first use (TOFU). Known peers must
+    /// present their pinned key; a differing key returns
+    /// [`TrustDecision::KeyMismatch`] without updating the store, so the
+    /// caller decides whether to fail closed or re-pin.
+    pub async fn verify(&self, peer_id: &PeerId, presented_key: &[u8; 32]) -> TrustDecision {
+        let mut entries = self.entries.write().await;
+        match entries.get_mut(peer_id) {
+            Some(entry) if &entry.pinned_key == presented_key => {
+                entry.last_seen = now_secs();
+                TrustDecision::Trusted
+            }
+            Some(entry) => TrustDecision::KeyMismatch {
+                pinned_key: entry.pinned_key,
+            },
+            None => {
+                let now = now_secs();
+                entries.insert(
+                    *peer_id,
+                    TrustEntry {
+                        peer_id: *peer_id,
+                        pinned_key: *presented_key,
+                        first_seen: now,
+                        last_seen: now,
+                    },
+                );
+                TrustDecision::FirstUse
+            }
+        }
+    }
+
+    /// Pin (or re-pin) a peer's key, overwriting any existing entry
+    ///
+    /// This is the explicit override for a deliberate key change.
+    pub async fn pin(&self, peer_id: PeerId, key: [u8; 32]) {
+        let now = now_secs();
+        let mut entries = self.entries.write().await;
+        let first_seen = entries.get(&peer_id).map_or(now, |e| e.first_seen);
+        entries.insert(
+            peer_id,
+            TrustEntry {
+                peer_id,
+                pinned_key: key,
+                first_seen,
+                last_seen: now,
+            },
+        );
+    }
+
+    /// Remove a peer's entry, returning it if present
+    pub async fn unpin(&self, peer_id: &PeerId) -> Option<TrustEntry> {
+        self.entries.write().await.remove(peer_id)
+    }
+
+    /// Look up the entry for a peer
+    pub async fn get(&self, peer_id: &PeerId) -> Option<TrustEntry> {
+        self.entries.read().await.get(peer_id).cloned()
+    }
+
+    /// All entries, sorted by peer ID
+    pub async fn entries(&self) -> Vec<TrustEntry> {
+        let mut all: Vec<TrustEntry> = self.entries.read().await.values().cloned().collect();
+        all.sort_by_key(|e| e.peer_id);
+        all
+    }
+
+    /// Number of pinned peers
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    /// Check if the store holds no entries
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+}
+
+/// Parse one known_peers line into an entry
+fn parse_entry(line: &str) -> Option<TrustEntry> {
+    let mut fields = line.split_whitespace();
+    let peer_id = parse_key(fields.next()?)?;
+    let pinned_key = parse_key(fields.next()?)?;
+    let first_seen = fields.next()?.parse().ok()?;
+    let last_seen = fields.next()?.parse().ok()?;
+    Some(TrustEntry {
+        peer_id,
+        pinned_key,
+        first_seen,
+        last_seen,
+    })
+}
+
+/// Parse a 32-byte hex field
+fn parse_key(field: &str) -> Option<[u8; 32]> {
+    let bytes = hex::decode(field).ok()?;
+    bytes.try_into().ok()
+}
+
+/// Current time in seconds since the Unix epoch
+fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_first_use_pins_key() {
+        let store = TrustStore::new(PathBuf::from("/nonexistent/known_peers"));
+        assert!(store.is_empty().await);
+
+        let decision = store.verify(&[1u8; 32], &[2u8; 32]).await;
+        assert_eq!(decision, TrustDecision::FirstUse);
+        assert_eq!(store.get(&[1u8; 32]).await.unwrap().pinned_key, [2u8; 32]);
+    }
+
+    #[tokio::test]
+    async fn test_matching_key_is_trusted() {
+        let store = TrustStore::new(PathBuf::from("/nonexistent/known_peers"));
+        store.verify(&[1u8; 32], &[2u8; 32]).await;
+
+        let decision = store.verify(&[1u8; 32], &[2u8; 32]).await;
+        assert_eq!(decision, TrustDecision::Trusted);
+    }
+
+    #[tokio::test]
+    async fn test_changed_key_is_mismatch() {
+        let store = TrustStore::new(PathBuf::from("/nonexistent/known_peers"));
+        store.verify(&[1u8; 32], &[2u8; 32]).await;
+
+        let decision = store.verify(&[1u8; 32], &[3u8; 32]).await;
+        assert_eq!(
+            decision,
+            TrustDecision::KeyMismatch {
+                pinned_key: [2u8; 32]
+            }
+        );
+
+        // The mismatching key must not replace the pinned one
+        assert_eq!(store.get(&[1u8; 32]).await.unwrap().pinned_key, [2u8; 32]);
+    }
+
+    #[tokio::test]
+    async fn test_pin_overwrites_and_keeps_first_seen() {
+        let store = TrustStore::new(PathBuf::from("/nonexistent/known_peers"));
+        store.verify(&[1u8; 32], &[2u8; 32]).await;
+        let first_seen = store.get(&[1u8; 32]).await.unwrap().first_seen;
+
+        store.pin([1u8; 32], [3u8; 32]).await;
+        let entry = store.get(&[1u8; 32]).await.unwrap();
+        assert_eq!(entry.pinned_key, [3u8; 32]);
+        assert_eq!(entry.first_seen, first_seen);
+
+        assert_eq!(
+            store.verify(&[1u8; 32], &[3u8; 32]).await,
+            TrustDecision::Trusted
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unpin_removes_entry() {
+        let store = TrustStore::new(PathBuf::from("/nonexistent/known_peers"));
+        store.verify(&[1u8; 32], &[2u8; 32]).await;
+
+        assert!(store.unpin(&[1u8; 32]).await.is_some());
+        assert!(store.is_empty().await);
+        assert_eq!(
+            store.verify(&[1u8; 32], &[9u8; 32]).await,
+            TrustDecision::FirstUse
+        );
+    }
+
+    #[tokio::test]
+    async fn test_save_load_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known_peers");
+
+        let store = TrustStore::new(path.clone());
+        store.verify(&[1u8; 32], &[2u8; 32]).await;
+        store.verify(&[7u8; 32], &[8u8; 32]).await;
+        store.save().await.unwrap();
+
+        let reloaded = TrustStore::new(path);
+        reloaded.load().await.unwrap();
+        assert_eq!(reloaded.len().await, 2);
+        assert_eq!(
+            reloaded.get(&[7u8; 32]).await.unwrap().pinned_key,
+            [8u8; 32]
+        );
+        assert_eq!(
+            reloaded.verify(&[1u8; 32], &[2u8; 32]).await,
+            TrustDecision::Trusted
+        );
+    }
+
+    #[tokio::test]
+    async fn test_load_missing_file_is_empty() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = TrustStore::new(dir.path().join("missing"));
+        store.load().await.unwrap();
+        assert!(store.is_empty().await);
+    }
+
+    #[tokio::test]
+    async fn test_load_skips_malformed_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known_peers");
+        let good = format!(
+            "# comment\n\nnot an entry\n{} {} 10 20\n{} short 10 20\n",
+            hex::encode([1u8; 32]),
+            hex::encode([2u8; 32]),
+            hex::encode([3u8; 32]),
+        );
+        tokio::fs::write(&path, good).await.unwrap();
+
+        let store = TrustStore::new(path);
+        store.load().await.unwrap();
+        assert_eq!(store.len().await, 1);
+        assert_eq!(store.get(&[1u8; 32]).await.unwrap().first_seen, 10);
+    }
+
+    #[tokio::test]
+    async fn test_saved_file_is_known_hosts_style() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("known_peers");
+
+        let store = TrustStore::new(path.clone());
+        store.verify(&[1u8; 32], &[2u8; 32]).await;
+        store.save().await.unwrap();
+
+        let text = tokio::fs::read_to_string(&path).await.unwrap();
+        assert!(text.starts_with('#'));
+        assert!(text.contains(&hex::encode([1u8; 32])));
+        assert!(text.contains(&hex::encode([2u8; 32])));
+    }
+}
@@ -0,0 +1,283 @@
+//! Canonical `wraith://` URI scheme
+//!
+//! One URI format for everything a WRAITH endpoint can be pointed at: a
+//! peer's key, optionally where to reach it, what content to fetch, which
+//! relays to try if the direct path fails, and a claim token for one-shot
+//! links. The CLI registers itself as the OS handler for the scheme
+//! (`wraith handle-uri --register`), and the desktop clients receive these
+//! URIs through Tauri deep links, so a pasted or clicked link resolves to
+//! the same parse everywhere.
+//!
+//! Canonical form:
+//!
+//! ```text
+//! wraith://<peer-key-hex>[@<host>:<port>][/<content-hash-hex>][?relay=<addr>&token=<token-hex>]
+//! ```
+//!
+//! - the peer key (32-byte X25519, hex) is the only mandatory component
+//! - `@host:port` is the peer's last known direct address
+//! - the path segment is a BLAKE3 root hash naming specific content
+//! - `relay=` may repeat; each value is a relay address to try in order
+//! - `token=` carries a one-shot claim token
+//!
+//! The pre-existing claim-link shape `wraith://<addr>/<peer>/<token>`
+//! ([`TransferLink`]) is still accepted by [`WraithUri::parse`] and
+//! converts losslessly in both directions.
+
+use crate::node::error::{NodeError, Result};
+use crate::node::link::{TransferLink, decode_fixed};
+use crate::node::session::PeerId;
+use std::net::SocketAddr;
+
+/// URL scheme prefix shared with [`TransferLink`]
+const SCHEME: &str = "wraith://";
+
+/// A parsed `wraith://` URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WraithUri {
+    /// The peer's public key (X25519)
+    pub peer_id: PeerId,
+    /// The peer's direct address, when known
+    pub addr: Option<SocketAddr>,
+    /// BLAKE3 root hash of the content the URI names, if any
+    pub content_hash: Option<[u8; 32]>,
+    /// Relay addresses to try when the direct path fails, in order
+    pub relays: Vec<SocketAddr>,
+    /// One-shot claim token for link-based transfers
+    pub token: Option<[u8; 32]>,
+}
+
+impl WraithUri {
+    /// Create a URI naming just a peer
+    #[must_use]
+    pub fn new(peer_id: PeerId) -> Self {
+        Self {
+            peer_id,
+            addr: None,
+            content_hash: None,
+            relays: Vec::new(),
+            token: None,
+        }
+    }
+
+    /// Parse a `wraith://` URI (canonical or legacy claim-link form)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NodeError::Serialization`] if the URI does not match
+    /// either shape or any component is malformed.
+    pub fn parse(uri: &str) -> Result<Self> {
+        let uri = uri.trim();
+        let rest = uri.strip_prefix(SCHEME).ok_or_else(|| {
+            NodeError::Serialization("wraith URI must start with wraith://".into())
+        })?;
+
+        // Split off the query, then the path, leaving the authority
+        let (rest, query) = match rest.split_once('?') {
+            Some((rest, query)) => (rest, Some(query)),
+            None => (rest, None),
+        };
+        let (authority, path) = match rest.split_once('/') {
+            Some((authority, path)) => (authority, Some(path)),
+            None => (rest, None),
+        };
+
+        // Legacy claim links put a socket address in the authority slot;
+        // the canonical form starts with a peer key
+        if authority.parse::<SocketAddr>().is_ok() {
+            return TransferLink::parse(uri).map(Self::from);
+        }
+
+        let (peer_hex, addr) = match authority.split_once('@') {
+            Some((peer_hex, host)) => {
+                let addr = host.parse::<SocketAddr>().map_err(|_| {
+                    NodeError::Serialization("wraith URI has an invalid host:port".into())
+                })?;
+                (peer_hex, Some(addr))
+            }
+            None => (authority, None),
+        };
+        let peer_id = decode_fixed::<32>(peer_hex)
+            .ok_or_else(|| NodeError::Serialization("wraith URI has an invalid peer key".into()))?;
+
+        let content_hash = match path {
+            None | Some("") => None,
+            Some(segment) => Some(decode_fixed::<32>(segment).ok_or_else(|| {
+                NodeError::Serialization("wraith URI has an invalid content hash".into())
+            })?),
+        };
+
+        let mut relays = Vec::new();
+        let mut token = None;
+        if let Some(query) = query {
+            for pair in query.split('&').filter(|p| !p.is_empty()) {
+                let (key, value) = pair.split_once('=').ok_or_else(|| {
+                    NodeError::Serialization("wraith URI has a malformed query pair".into())
+                })?;
+                match key {
+                    "relay" => {
+                        let relay = value.parse::<SocketAddr>().map_err(|_| {
+                            NodeError::Serialization("wraith URI has an invalid relay hint".into())
+                        })?;
+                        relays.push(relay);
+                    }
+                    "token" => {
+                        token = Some(decode_fixed::<32>(value).ok_or_else(|| {
+                            NodeError::Serialization("wraith URI has an invalid token".into())
+                        })?);
+                    }
+                    other => {
+                        return Err(NodeError::Serialization(
+                            format!("wraith URI has an unknown query key: {other}").into(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            peer_id,
+            addr,
+            content_hash,
+            relays,
+            token,
+        })
+    }
+
+    /// Extract a one-shot claim link, if the URI carries enough for one
+    ///
+    /// Requires both a direct address and a claim token.
+    #[must_use]
+    pub fn claim_link(&self) -> Option<TransferLink> {
+        Some(TransferLink::from_parts(
+            self.addr?,
+            self.peer_id,
+            self.token?,
+        ))
+    }
+}
+
+impl From<TransferLink> for WraithUri {
+    fn from(link: TransferLink) -> Self {
+        Self {
+            peer_id: link.peer_id,
+            addr: Some(link.addr),
+            content_hash: None,
+            relays: Vec::new(),
+            token: Some(*link.token()),
+        }
+    }
+}
+
+impl std::fmt::Display for WraithUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{SCHEME}{}", hex::encode(self.peer_id))?;
+        if let Some(addr) = self.addr {
+            write!(f, "@{addr}")?;
+        }
+        if let Some(hash) = self.content_hash {
+            write!(f, "/{}", hex::encode(hash))?;
+        }
+
+        let mut separator = '?';
+        for relay in &self.relays {
+            write!(f, "{separator}relay={relay}")?;
+            separator = '&';
+        }
+        if let Some(token) = self.token {
+            write!(f, "{separator}token={}", hex::encode(token))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for WraithUri {
+    type Err = NodeError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peer_only_roundtrip() {
+        let uri = WraithUri::new([0xAB; 32]);
+        let rendered = uri.to_string();
+        assert_eq!(rendered, format!("wraith://{}", hex::encode([0xAB; 32])));
+        assert_eq!(WraithUri::parse(&rendered).unwrap(), uri);
+    }
+
+    #[test]
+    fn test_full_uri_roundtrip() {
+        let uri = WraithUri {
+            peer_id: [1u8; 32],
+            addr: Some("192.0.2.7:40000".parse().unwrap()),
+            content_hash: Some([2u8; 32]),
+            relays: vec![
+                "198.51.100.1:40001".parse().unwrap(),
+                "[2001:db8::1]:40001".parse().unwrap(),
+            ],
+            token: Some([3u8; 32]),
+        };
+        let parsed = WraithUri::parse(&uri.to_string()).unwrap();
+        assert_eq!(parsed, uri);
+    }
+
+    #[test]
+    fn test_legacy_claim_link_accepted() {
+        let link = TransferLink::new("192.0.2.7:40000".parse().unwrap(), [0xCD; 32]);
+        let uri = WraithUri::parse(&link.to_string()).unwrap();
+        assert_eq!(uri.peer_id, [0xCD; 32]);
+        assert_eq!(uri.addr, Some(link.addr));
+        assert_eq!(uri.token, Some(*link.token()));
+        assert_eq!(uri.claim_link(), Some(link));
+    }
+
+    #[test]
+    fn test_canonical_claim_link_extraction() {
+        let uri = WraithUri {
+            peer_id: [1u8; 32],
+            addr: Some("192.0.2.7:40000".parse().unwrap()),
+            content_hash: None,
+            relays: Vec::new(),
+            token: Some([3u8; 32]),
+        };
+        let link = uri.claim_link().unwrap();
+        assert_eq!(link.peer_id, [1u8; 32]);
+        assert_eq!(link.token(), &[3u8; 32]);
+
+        // No claim without an address or token
+        assert!(WraithUri::new([1u8; 32]).claim_link().is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_uris() {
+        assert!(WraithUri::parse("").is_err());
+        assert!(WraithUri::parse("https://example.com").is_err());
+        assert!(WraithUri::parse("wraith://not-hex").is_err());
+        // Peer key must be exactly 32 bytes
+        assert!(WraithUri::parse("wraith://abcd").is_err());
+        let peer = hex::encode([1u8; 32]);
+        assert!(WraithUri::parse(&format!("wraith://{peer}@nohost")).is_err());
+        assert!(WraithUri::parse(&format!("wraith://{peer}/abcd")).is_err());
+        assert!(WraithUri::parse(&format!("wraith://{peer}?relay=bad")).is_err());
+        assert!(WraithUri::parse(&format!("wraith://{peer}?token=zz")).is_err());
+        assert!(WraithUri::parse(&format!("wraith://{peer}?magic=1")).is_err());
+    }
+
+    #[test]
+    fn test_ipv6_address_roundtrip() {
+        let uri = WraithUri {
+            peer_id: [7u8; 32],
+            addr: Some("[2001:db8::1]:40000".parse().unwrap()),
+            content_hash: None,
+            relays: Vec::new(),
+            token: None,
+        };
+        assert_eq!(WraithUri::parse(&uri.to_string()).unwrap(), uri);
+    }
+}
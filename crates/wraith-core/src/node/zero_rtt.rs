@@ -0,0 +1,614 @@
+//! 0-RTT session resumption over session tickets.
+//!
+//! After a full Noise_XX handshake the responder hands the initiator a
+//! single-use [`SessionTicket`] (Control tag [`CONTROL_SESSION_TICKET`])
+//! over the already-encrypted channel. On reconnection the initiator
+//! derives fresh directional keys from the ticket's resumption secret and
+//! sends its first flight immediately - `ticket_id || encrypted frame` -
+//! skipping both handshake round trips. The responder redeems the ticket
+//! (strictly single-use, so a replayed flight falls back to a full
+//! handshake), derives the same keys, and processes the early frame.
+//!
+//! The early frame is a PATH_CHALLENGE probing the resumed path: the
+//! echoed PATH_RESPONSE doubles as the responder's accept signal, so the
+//! initiator knows within one round trip whether the resumption landed.
+//! Any failure - missing, expired, or replayed ticket, no response in
+//! [`RESUME_ACCEPT_TIMEOUT`] - rolls the attempt back and the initiator
+//! falls through to the normal Noise_XX handshake.
+
+use crate::frame::{FrameBuilder, FrameType};
+use crate::node::Node;
+use crate::node::error::{NodeError, Result};
+use crate::node::session::{PeerConnection, PeerId, SessionId};
+use crate::{ConnectionId, FRAME_HEADER_SIZE, HandshakePhase, SessionState, ZeroRttAcceptor};
+use dashmap::DashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use wraith_crypto::aead::SessionCrypto;
+
+/// Control payload tag: session resumption ticket (responder -> initiator)
+pub const CONTROL_SESSION_TICKET: u8 = 0x60;
+
+/// How long the initiator waits for the accept signal before rolling the
+/// resumption back and falling through to a full handshake
+pub(crate) const RESUME_ACCEPT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Encoded ticket payload length: ticket_id(16) + secret(32) + lifetime(8)
+const TICKET_BODY_LEN: usize = 56;
+
+/// A resumption ticket a peer issued to us, held until reconnection
+#[derive(Debug, Clone)]
+pub(crate) struct StoredTicket {
+    /// Opaque identifier presented in the resumption first flight
+    pub(crate) ticket_id: [u8; 16],
+    /// Secret from which the resumed session's keys are derived
+    pub(crate) resumption_secret: [u8; 32],
+    /// When the issuer stops honoring the ticket
+    pub(crate) expires_at: Instant,
+    /// Identity (X25519) the issuing session authenticated
+    pub(crate) peer_id: PeerId,
+}
+
+/// Node-level 0-RTT state for both roles
+///
+/// As responder: tickets we issued, awaiting redemption, plus the peer
+/// identity each ticket is bound to. As initiator: tickets peers issued
+/// to us, keyed by the address we reach the issuer at - resumption means
+/// reconnecting to the same endpoint - with a reissue replacing the old
+/// ticket.
+#[derive(Default)]
+pub(crate) struct ZeroRttState {
+    /// Single-use store for tickets we issued (responder side)
+    acceptor: std::sync::Mutex<ZeroRttAcceptor>,
+    /// Peer identity bound to each outstanding ticket, with its expiry
+    /// for pruning (responder side)
+    ticket_peers: DashMap<[u8; 16], (PeerId, Instant)>,
+    /// Tickets peers issued to us, keyed by issuer address (initiator side)
+    stored: DashMap<SocketAddr, StoredTicket>,
+}
+
+/// Keys of a resumed session, all derived from the resumption secret
+///
+/// Both sides derive the same values, so no key material crosses the wire
+/// on resumption; the initiator sends with `initiator_key` and the
+/// responder with `responder_key`.
+pub(crate) struct ResumedKeys {
+    /// Session ID (first 8 bytes double as the Connection ID)
+    pub(crate) session_id: SessionId,
+    /// Initiator's send key
+    pub(crate) initiator_key: [u8; 32],
+    /// Responder's send key
+    pub(crate) responder_key: [u8; 32],
+    /// Chain key seeding the nonce salt and ratchet
+    pub(crate) chain_key: [u8; 32],
+}
+
+/// Derive the resumed session's keys from a ticket's resumption secret
+pub(crate) fn derive_resumed_keys(resumption_secret: &[u8; 32]) -> ResumedKeys {
+    ResumedKeys {
+        session_id: blake3::derive_key("WRAITH v1 0-RTT session id", resumption_secret),
+        initiator_key: blake3::derive_key("WRAITH v1 0-RTT initiator key", resumption_secret),
+        responder_key: blake3::derive_key("WRAITH v1 0-RTT responder key", resumption_secret),
+        chain_key: blake3::derive_key("WRAITH v1 0-RTT chain key", resumption_secret),
+    }
+}
+
+/// Encode a session-ticket control payload
+///
+/// Format: tag(1) + ticket_id(16) + resumption_secret(32) + lifetime
+/// seconds(8, BE).
+pub(crate) fn encode_session_ticket(ticket: &crate::SessionTicket) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(1 + TICKET_BODY_LEN);
+    payload.push(CONTROL_SESSION_TICKET);
+    payload.extend_from_slice(&ticket.ticket_id);
+    payload.extend_from_slice(&ticket.resumption_secret);
+    payload.extend_from_slice(&ticket.lifetime.as_secs().to_be_bytes());
+    payload
+}
+
+/// Decode a session-ticket control payload (tag already consumed)
+///
+/// The resulting ticket is bound to `peer_id`, the authenticated identity
+/// of the session the ticket arrived over.
+pub(crate) fn decode_session_ticket(data: &[u8], peer_id: PeerId) -> Result<StoredTicket> {
+    if data.len() != TICKET_BODY_LEN {
+        return Err(NodeError::InvalidState("Malformed session ticket".into()));
+    }
+
+    let ticket_id: [u8; 16] = data[..16].try_into().expect("length checked");
+    let resumption_secret: [u8; 32] = data[16..48].try_into().expect("length checked");
+    let lifetime_secs = u64::from_be_bytes(data[48..56].try_into().expect("length checked"));
+
+    Ok(StoredTicket {
+        ticket_id,
+        resumption_secret,
+        expires_at: Instant::now() + Duration::from_secs(lifetime_secs),
+        peer_id,
+    })
+}
+
+/// Build a Control frame carrying a session-ticket payload
+pub(crate) fn build_session_ticket_frame(ticket: &crate::SessionTicket) -> Result<Vec<u8>> {
+    let payload = encode_session_ticket(ticket);
+    FrameBuilder::new()
+        .frame_type(FrameType::Control)
+        .stream_id(0)
+        .sequence(0)
+        .payload(&payload)
+        .build(FRAME_HEADER_SIZE + payload.len())
+        .map_err(|e| NodeError::InvalidState(format!("Failed to build control frame: {e}").into()))
+}
+
+impl Node {
+    /// Issue a single-use resumption ticket to an established peer
+    ///
+    /// Called by the responder after a full handshake and again after each
+    /// accepted resumption, so the peer always holds one fresh ticket.
+    /// No-op when 0-RTT is disabled in the session config; best-effort
+    /// otherwise (a lost ticket just means the next connection pays for a
+    /// full handshake).
+    pub(crate) async fn issue_session_ticket(&self, connection: &PeerConnection) -> Result<()> {
+        let Some(ticket) = connection.session.read().await.issue_resumption_ticket() else {
+            return Ok(());
+        };
+
+        {
+            let mut acceptor = self
+                .inner
+                .zero_rtt
+                .acceptor
+                .lock()
+                .expect("acceptor lock poisoned");
+            acceptor.register(ticket.clone());
+        }
+
+        // The peer-binding map prunes on its own schedule: expired entries
+        // are dropped whenever a new ticket is issued
+        let now = Instant::now();
+        self.inner
+            .zero_rtt
+            .ticket_peers
+            .retain(|_, (_, expires)| *expires > now);
+        self.inner.zero_rtt.ticket_peers.insert(
+            ticket.ticket_id,
+            (connection.peer_id, now + ticket.lifetime),
+        );
+
+        let frame = build_session_ticket_frame(&ticket)?;
+        self.send_encrypted_frame(connection, &frame).await?;
+        tracing::debug!(
+            "Issued session ticket to peer {}",
+            hex::encode(&connection.peer_id[..8])
+        );
+        Ok(())
+    }
+
+    /// Handle an inbound session ticket (initiator side)
+    ///
+    /// Stores the ticket under the address we reach the issuer at; a
+    /// reissue replaces any older ticket, matching the issuer's
+    /// single-outstanding view.
+    pub(crate) fn handle_session_ticket(&self, data: &[u8], peer_id: PeerId) -> Result<()> {
+        let ticket = decode_session_ticket(data, peer_id)?;
+        let peer_addr = self
+            .inner
+            .sessions
+            .get(&peer_id)
+            .map(|entry| entry.peer_addr())
+            .ok_or(NodeError::SessionNotFound(peer_id))?;
+        tracing::debug!(
+            "Received session ticket from peer {} at {}",
+            hex::encode(&peer_id[..8]),
+            peer_addr
+        );
+        self.inner.zero_rtt.stored.insert(peer_addr, ticket);
+        Ok(())
+    }
+
+    /// Attempt 0-RTT resumption with a stored ticket (initiator side)
+    ///
+    /// Returns the resumed session and the identity it re-authenticated
+    /// on success. Returns `None` - after rolling back any partial state -
+    /// when no usable ticket exists for this address or the responder does
+    /// not accept within [`RESUME_ACCEPT_TIMEOUT`]; the caller falls
+    /// through to the full handshake.
+    pub(crate) async fn try_resume_0rtt(
+        &self,
+        peer_addr: SocketAddr,
+    ) -> Option<(SessionId, PeerId)> {
+        use crate::node::node::MigrationState;
+
+        // An existing session makes resumption moot (and must not burn the
+        // ticket); let the normal establishment path deduplicate
+        let ticket_peer = self
+            .inner
+            .zero_rtt
+            .stored
+            .get(&peer_addr)
+            .map(|entry| entry.peer_id)?;
+        if self.inner.sessions.contains_key(&ticket_peer) {
+            return None;
+        }
+
+        // Tickets are single-use on our side too: a failed attempt must
+        // not be retried with the same (now-consumed) ticket
+        let (_, ticket) = self.inner.zero_rtt.stored.remove(&peer_addr)?;
+        let peer_id = &ticket.peer_id;
+        if Instant::now() >= ticket.expires_at {
+            tracing::debug!("Stored session ticket for {} expired", peer_addr);
+            return None;
+        }
+
+        let keys = derive_resumed_keys(&ticket.resumption_secret);
+        let crypto = SessionCrypto::new(keys.initiator_key, keys.responder_key, &keys.chain_key);
+
+        let mut connection_id_bytes = [0u8; 8];
+        connection_id_bytes.copy_from_slice(&keys.session_id[..8]);
+        let connection_id = ConnectionId::from_bytes(connection_id_bytes);
+        let connection = Arc::new(PeerConnection::new(
+            keys.session_id,
+            *peer_id,
+            peer_addr,
+            connection_id,
+            crypto,
+        ));
+        connection
+            .transition_to(SessionState::Handshaking(HandshakePhase::EarlyData))
+            .await
+            .ok()?;
+
+        // Early frame: a PATH_CHALLENGE probing the resumed path, whose
+        // echoed PATH_RESPONSE is the responder's accept signal
+        let mut challenge = [0u8; 8];
+        getrandom::getrandom(&mut challenge).ok()?;
+        let frame = FrameBuilder::new()
+            .frame_type(FrameType::PathChallenge)
+            .stream_id(0)
+            .sequence(0)
+            .payload(&challenge)
+            .build(128)
+            .ok()?;
+        let encrypted = connection.encrypt_frame(&frame).await.ok()?;
+
+        let mut first_flight = Vec::with_capacity(16 + encrypted.len());
+        first_flight.extend_from_slice(&ticket.ticket_id);
+        first_flight.extend_from_slice(&encrypted);
+
+        let transport = self.get_transport().await.ok()?;
+
+        // Path ID keyed off the ticket so a concurrent real migration to
+        // the same address cannot collide
+        let path_id = {
+            use std::collections::hash_map::DefaultHasher;
+            use std::hash::{Hash, Hasher};
+            let mut hasher = DefaultHasher::new();
+            ticket.ticket_id.hash(&mut hasher);
+            hasher.finish()
+        };
+        let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+        self.inner.pending_migrations.insert(
+            path_id,
+            MigrationState {
+                peer_id: *peer_id,
+                new_addr: peer_addr,
+                challenge,
+                sender: response_tx,
+                initiated_at: Instant::now(),
+            },
+        );
+
+        // Register session and route before sending so the PATH_RESPONSE
+        // (and any data the peer sends back immediately) can be routed
+        let cid_u64 = u64::from_be_bytes(connection_id_bytes);
+        self.inner
+            .sessions
+            .insert(*peer_id, Arc::clone(&connection));
+        self.inner
+            .routing
+            .add_route(cid_u64, Arc::clone(&connection));
+
+        let rollback = || {
+            self.inner.pending_migrations.remove(&path_id);
+            self.inner
+                .sessions
+                .remove_if(peer_id, |_, c| c.session_id == keys.session_id);
+            self.inner.routing.remove_route(cid_u64);
+        };
+
+        if let Err(e) = transport.send_to(&first_flight, peer_addr).await {
+            tracing::debug!("Failed to send 0-RTT first flight: {e}");
+            rollback();
+            return None;
+        }
+
+        match tokio::time::timeout(RESUME_ACCEPT_TIMEOUT, response_rx).await {
+            Ok(Ok(Ok(latency))) => {
+                connection
+                    .transition_to(SessionState::Established)
+                    .await
+                    .ok()?;
+                connection.touch();
+                tracing::info!(
+                    "Session resumed via 0-RTT with peer {}, session: {} ({}µs)",
+                    hex::encode(&peer_id[..8]),
+                    hex::encode(&keys.session_id[..8]),
+                    latency.as_micros()
+                );
+                self.inner
+                    .events
+                    .emit(crate::node::events::NodeEvent::PeerConnected { peer_id: *peer_id });
+                self.spawn_resume_check(*peer_id);
+                Some((keys.session_id, *peer_id))
+            }
+            _ => {
+                tracing::debug!(
+                    "0-RTT resumption with peer {} not accepted, falling back to full handshake",
+                    hex::encode(&peer_id[..8])
+                );
+                rollback();
+                None
+            }
+        }
+    }
+
+    /// Redeem a resumption first flight, if that is what this packet is
+    /// (responder side)
+    ///
+    /// Called for packets that match no known Connection ID, before the
+    /// Noise responder machinery. Returns `None` when the packet carries
+    /// no outstanding ticket ID - including replays, since redemption is
+    /// single-use - so the caller treats it as a handshake initiation.
+    /// The ticket is consumed even when the early frame fails to decrypt;
+    /// that is what makes a replayed flight worthless.
+    pub(crate) async fn handle_possible_0rtt(
+        &self,
+        packet: &[u8],
+        peer_addr: SocketAddr,
+    ) -> Option<Result<SessionId>> {
+        if packet.len() < 16 + 8 {
+            return None;
+        }
+        let ticket_id: [u8; 16] = packet[..16].try_into().expect("length checked");
+
+        let resumption_secret = self
+            .inner
+            .zero_rtt
+            .acceptor
+            .lock()
+            .expect("acceptor lock poisoned")
+            .redeem(&ticket_id)?;
+        let (_, (peer_id, _)) = self.inner.zero_rtt.ticket_peers.remove(&ticket_id)?;
+
+        Some(
+            self.accept_0rtt_resumption(&resumption_secret, peer_id, peer_addr, &packet[16..])
+                .await,
+        )
+    }
+
+    /// Build the resumed session and process the early frame (responder)
+    async fn accept_0rtt_resumption(
+        &self,
+        resumption_secret: &[u8; 32],
+        peer_id: PeerId,
+        peer_addr: SocketAddr,
+        early: &[u8],
+    ) -> Result<SessionId> {
+        let keys = derive_resumed_keys(resumption_secret);
+        let crypto = SessionCrypto::new(keys.responder_key, keys.initiator_key, &keys.chain_key);
+
+        let mut connection_id_bytes = [0u8; 8];
+        connection_id_bytes.copy_from_slice(&keys.session_id[..8]);
+        let connection_id = ConnectionId::from_bytes(connection_id_bytes);
+        let connection = Arc::new(PeerConnection::new(
+            keys.session_id,
+            peer_id,
+            peer_addr,
+            connection_id,
+            crypto,
+        ));
+
+        // Key possession authenticates the peer (the secret only ever
+        // traveled inside the original session), but the embedder's policy
+        // still gets a say, exactly as for a full handshake
+        let request = crate::node::authorizer::AuthRequest {
+            peer_id,
+            source_addr: Some(peer_addr),
+            action: crate::node::authorizer::AuthAction::SessionEstablish,
+        };
+        if let crate::node::authorizer::AuthDecision::Deny(reason) = self.authorize(&request).await
+        {
+            tracing::warn!(
+                "Resumed session from {} ({}) denied by authorizer: {}",
+                hex::encode(&peer_id[..8]),
+                peer_addr,
+                reason
+            );
+            return Err(NodeError::AuthorizationDenied(reason.into()));
+        }
+
+        // Decrypting the early frame proves possession of the resumption
+        // secret; a forged flight dies here with the ticket already spent
+        let frame_bytes = connection.decrypt_frame(early).await.inspect_err(|_| {
+            tracing::warn!("0-RTT early frame from {} failed to decrypt", peer_addr);
+        })?;
+
+        connection
+            .transition_to(SessionState::Handshaking(HandshakePhase::EarlyData))
+            .await?;
+        connection.transition_to(SessionState::Established).await?;
+
+        // The reconnecting peer supersedes any session we still hold for
+        // it (typically a stale one whose far end is already gone)
+        if let Some(old) = self.inner.sessions.insert(peer_id, Arc::clone(&connection)) {
+            self.inner.routing.remove_route(old.connection_id.as_u64());
+        }
+        let cid_u64 = u64::from_be_bytes(connection_id_bytes);
+        self.inner
+            .routing
+            .add_route(cid_u64, Arc::clone(&connection));
+
+        tracing::info!(
+            "Session resumed as responder with peer {}, session: {}, route: {:016x}",
+            hex::encode(&peer_id[..8]),
+            hex::encode(&keys.session_id[..8]),
+            cid_u64
+        );
+
+        // The early frame is a normal frame (a PATH_CHALLENGE in our own
+        // first flights); its handler sends the accept signal
+        if let Err(e) = self.dispatch_frame(frame_bytes, peer_id).await {
+            tracing::warn!("Error handling 0-RTT early frame: {}", e);
+        }
+
+        // Hand the peer its next single-use ticket
+        if let Err(e) = self.issue_session_ticket(&connection).await {
+            tracing::debug!("Failed to issue session ticket: {e}");
+        }
+
+        // Pick up any transfer the previous session left unfinished
+        self.spawn_resume_check(peer_id);
+
+        Ok(keys.session_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SessionTicket;
+    use crate::node::{Node, NodeConfig};
+
+    fn test_ticket() -> SessionTicket {
+        SessionTicket {
+            ticket_id: [7u8; 16],
+            resumption_secret: [9u8; 32],
+            issued_at: Instant::now(),
+            lifetime: Duration::from_secs(3600),
+        }
+    }
+
+    #[test]
+    fn test_session_ticket_roundtrip() {
+        let ticket = test_ticket();
+        let payload = encode_session_ticket(&ticket);
+        assert_eq!(payload[0], CONTROL_SESSION_TICKET);
+
+        let decoded = decode_session_ticket(&payload[1..], [2u8; 32]).unwrap();
+        assert_eq!(decoded.ticket_id, ticket.ticket_id);
+        assert_eq!(decoded.resumption_secret, ticket.resumption_secret);
+        assert_eq!(decoded.peer_id, [2u8; 32]);
+        assert!(decoded.expires_at > Instant::now());
+    }
+
+    #[test]
+    fn test_decode_session_ticket_malformed() {
+        let payload = encode_session_ticket(&test_ticket());
+
+        assert!(decode_session_ticket(&[], [2u8; 32]).is_err());
+        assert!(decode_session_ticket(&payload[1..payload.len() - 1], [2u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_derive_resumed_keys_deterministic_and_distinct() {
+        let secret = [42u8; 32];
+        let a = derive_resumed_keys(&secret);
+        let b = derive_resumed_keys(&secret);
+
+        assert_eq!(a.session_id, b.session_id);
+        assert_eq!(a.initiator_key, b.initiator_key);
+
+        // Directional keys, session ID, and chain key must all differ
+        assert_ne!(a.initiator_key, a.responder_key);
+        assert_ne!(a.session_id, a.initiator_key);
+        assert_ne!(a.chain_key, a.responder_key);
+
+        // A different secret yields unrelated keys
+        let c = derive_resumed_keys(&[43u8; 32]);
+        assert_ne!(a.session_id, c.session_id);
+    }
+
+    #[tokio::test]
+    async fn test_try_resume_skips_expired_ticket() {
+        let node = Node::new_random().await.unwrap();
+        let peer_addr: SocketAddr = "127.0.0.1:9".parse().unwrap();
+        node.inner.zero_rtt.stored.insert(
+            peer_addr,
+            StoredTicket {
+                ticket_id: [7u8; 16],
+                resumption_secret: [9u8; 32],
+                expires_at: Instant::now() - Duration::from_secs(1),
+                peer_id: [1u8; 32],
+            },
+        );
+
+        let result = node.try_resume_0rtt(peer_addr).await;
+        assert!(result.is_none());
+        // The expired ticket is consumed, not retried
+        assert!(!node.inner.zero_rtt.stored.contains_key(&peer_addr));
+    }
+
+    #[tokio::test]
+    async fn test_session_resumes_via_0rtt() {
+        let config = || NodeConfig {
+            listen_addr: "127.0.0.1:0".parse().unwrap(),
+            ..Default::default()
+        };
+        let a = Node::new_with_config(config()).await.unwrap();
+        let b = Node::new_with_config(config()).await.unwrap();
+        a.start().await.unwrap();
+        b.start().await.unwrap();
+
+        let b_addr = b.listen_addr().await.unwrap();
+        let b_peer = *b.x25519_public_key();
+        a.establish_session_with_addr(b.node_id(), b_addr)
+            .await
+            .unwrap();
+
+        // The responder's ticket arrives over the encrypted channel
+        let mut ticket = None;
+        for _ in 0..50 {
+            if let Some(stored) = a.inner.zero_rtt.stored.get(&b_addr) {
+                ticket = Some(stored.clone());
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        let ticket = ticket.expect("no session ticket received after full handshake");
+        let expected_session = derive_resumed_keys(&ticket.resumption_secret).session_id;
+
+        // Drop the session locally and reconnect: the stored ticket must
+        // carry the reconnection, giving the derived session ID on both
+        // sides instead of a fresh handshake's
+        a.close_session(&b_peer).await.unwrap();
+        let session_id = a
+            .establish_session_with_addr(b.node_id(), b_addr)
+            .await
+            .unwrap();
+        assert_eq!(session_id, expected_session);
+
+        let b_view = b
+            .inner
+            .sessions
+            .get(a.x25519_public_key())
+            .map(|entry| entry.session_id)
+            .unwrap();
+        assert_eq!(b_view, expected_session);
+
+        // The old ticket is spent and a fresh one is on its way
+        let mut reissued = false;
+        for _ in 0..50 {
+            if let Some(stored) = a.inner.zero_rtt.stored.get(&b_addr) {
+                if stored.ticket_id != ticket.ticket_id {
+                    reissued = true;
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(reissued, "responder did not reissue a ticket");
+
+        a.stop().await.unwrap();
+        b.stop().await.unwrap();
+    }
+}
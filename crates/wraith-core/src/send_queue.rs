@@ -0,0 +1,308 @@
+//! Prioritized outbound frame queue
+//!
+//! Orders outgoing frames so latency-sensitive control traffic (ACKs,
+//! handshake continuations, path validation) bypasses bulk DATA frames
+//! queued ahead of it. Without prioritization, a deep DATA backlog delays
+//! ACK delivery and stalls the peer's congestion window.
+//!
+//! # Priority classes
+//!
+//! - **Control**: ACK, REKEY, PING/PONG, CLOSE, stream management, path
+//!   validation - dequeued first
+//! - **Data**: bulk DATA frames - dequeued once no control frames remain
+//! - **Background**: PAD cover traffic - only sent when nothing else waits
+//!
+//! Frames within a class preserve FIFO order, so stream offsets stay
+//! monotonic per path.
+
+use crate::frame::FrameType;
+use std::collections::VecDeque;
+
+/// Priority class of an outbound frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FramePriority {
+    /// Latency-sensitive control traffic (dequeued first)
+    Control,
+    /// Bulk data payload
+    Data,
+    /// Cover traffic, sent only when idle
+    Background,
+}
+
+impl From<FrameType> for FramePriority {
+    fn from(frame_type: FrameType) -> Self {
+        match frame_type {
+            FrameType::Data => Self::Data,
+            FrameType::Pad => Self::Background,
+            // Everything else is connection or stream control
+            _ => Self::Control,
+        }
+    }
+}
+
+/// An outbound frame awaiting transmission
+#[derive(Debug, Clone)]
+struct QueuedFrame {
+    /// Serialized frame bytes
+    bytes: Vec<u8>,
+}
+
+/// Prioritized outbound frame queue.
+///
+/// Three FIFO lanes drained strictly in priority order: control, then data,
+/// then background. Bounded per lane; pushing to a full lane reports
+/// backpressure instead of growing without limit.
+pub struct SendQueue {
+    control: VecDeque<QueuedFrame>,
+    data: VecDeque<QueuedFrame>,
+    background: VecDeque<QueuedFrame>,
+    /// Maximum frames queued per lane
+    capacity: usize,
+}
+
+impl SendQueue {
+    /// Default per-lane capacity (frames)
+    pub const DEFAULT_CAPACITY: usize = 4_096;
+
+    /// Create a queue with the default per-lane capacity
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_capacity(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Create a queue with a custom per-lane capacity
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            control: VecDeque::new(),
+            data: VecDeque::new(),
+            background: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    /// Enqueue a serialized frame, classified by its frame type
+    ///
+    /// The frame type lives at byte 8 of the frame header; frames too short
+    /// to carry a header are classified as control so they are not starved.
+    ///
+    /// Returns `false` (and drops the frame) if the target lane is full.
+    pub fn push(&mut self, bytes: Vec<u8>) -> bool {
+        let priority = bytes
+            .get(8)
+            .and_then(|b| FrameType::try_from(*b).ok())
+            .map_or(FramePriority::Control, FramePriority::from);
+        self.push_with_priority(bytes, priority)
+    }
+
+    /// Enqueue a serialized frame with an explicit priority class
+    ///
+    /// Returns `false` (and drops the frame) if the target lane is full.
+    pub fn push_with_priority(&mut self, bytes: Vec<u8>, priority: FramePriority) -> bool {
+        let capacity = self.capacity;
+        let lane = self.lane_mut(priority);
+        if lane.len() >= capacity {
+            return false;
+        }
+        lane.push_back(QueuedFrame { bytes });
+        true
+    }
+
+    /// Dequeue the highest-priority frame, if any
+    pub fn pop(&mut self) -> Option<Vec<u8>> {
+        self.control
+            .pop_front()
+            .or_else(|| self.data.pop_front())
+            .or_else(|| self.background.pop_front())
+            .map(|f| f.bytes)
+    }
+
+    /// Number of frames queued across all lanes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.control.len() + self.data.len() + self.background.len()
+    }
+
+    /// Whether no frames are queued
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of frames queued in a specific lane
+    #[must_use]
+    pub fn lane_len(&self, priority: FramePriority) -> usize {
+        match priority {
+            FramePriority::Control => self.control.len(),
+            FramePriority::Data => self.data.len(),
+            FramePriority::Background => self.background.len(),
+        }
+    }
+
+    /// Drop all queued background (PAD) frames, e.g. when bandwidth is scarce
+    pub fn clear_background(&mut self) {
+        self.background.clear();
+    }
+
+    fn lane_mut(&mut self, priority: FramePriority) -> &mut VecDeque<QueuedFrame> {
+        match priority {
+            FramePriority::Control => &mut self.control,
+            FramePriority::Data => &mut self.data,
+            FramePriority::Background => &mut self.background,
+        }
+    }
+}
+
+impl Default for SendQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::frame::FrameBuilder;
+
+    fn build_frame(frame_type: FrameType) -> Vec<u8> {
+        FrameBuilder::new()
+            .frame_type(frame_type)
+            .stream_id(1)
+            .sequence(0)
+            .payload(b"test")
+            .build(128)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_priority_classification() {
+        assert_eq!(FramePriority::from(FrameType::Ack), FramePriority::Control);
+        assert_eq!(
+            FramePriority::from(FrameType::Rekey),
+            FramePriority::Control
+        );
+        assert_eq!(
+            FramePriority::from(FrameType::PathChallenge),
+            FramePriority::Control
+        );
+        assert_eq!(FramePriority::from(FrameType::Data), FramePriority::Data);
+        assert_eq!(
+            FramePriority::from(FrameType::Pad),
+            FramePriority::Background
+        );
+    }
+
+    #[test]
+    fn test_empty_queue() {
+        let mut queue = SendQueue::new();
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.len(), 0);
+        assert!(queue.pop().is_none());
+    }
+
+    #[test]
+    fn test_control_bypasses_data() {
+        let mut queue = SendQueue::new();
+
+        let data = build_frame(FrameType::Data);
+        let ack = build_frame(FrameType::Ack);
+
+        // Data queued first, ACK second
+        assert!(queue.push(data.clone()));
+        assert!(queue.push(ack.clone()));
+
+        // ACK still comes out first
+        assert_eq!(queue.pop(), Some(ack));
+        assert_eq!(queue.pop(), Some(data));
+    }
+
+    #[test]
+    fn test_background_sent_last() {
+        let mut queue = SendQueue::new();
+
+        let pad = build_frame(FrameType::Pad);
+        let data = build_frame(FrameType::Data);
+        let ping = build_frame(FrameType::Ping);
+
+        queue.push(pad.clone());
+        queue.push(data.clone());
+        queue.push(ping.clone());
+
+        assert_eq!(queue.pop(), Some(ping));
+        assert_eq!(queue.pop(), Some(data));
+        assert_eq!(queue.pop(), Some(pad));
+    }
+
+    #[test]
+    fn test_fifo_within_class() {
+        let mut queue = SendQueue::new();
+
+        let mut frames = Vec::new();
+        for seq in 0..5u32 {
+            let frame = FrameBuilder::new()
+                .frame_type(FrameType::Data)
+                .stream_id(1)
+                .sequence(seq)
+                .payload(b"chunk")
+                .build(128)
+                .unwrap();
+            queue.push(frame.clone());
+            frames.push(frame);
+        }
+
+        for frame in frames {
+            assert_eq!(queue.pop(), Some(frame));
+        }
+    }
+
+    #[test]
+    fn test_capacity_backpressure() {
+        let mut queue = SendQueue::with_capacity(2);
+
+        assert!(queue.push(build_frame(FrameType::Data)));
+        assert!(queue.push(build_frame(FrameType::Data)));
+
+        // Data lane full - push reports backpressure
+        assert!(!queue.push(build_frame(FrameType::Data)));
+        assert_eq!(queue.lane_len(FramePriority::Data), 2);
+
+        // Control lane has its own capacity
+        assert!(queue.push(build_frame(FrameType::Ack)));
+    }
+
+    #[test]
+    fn test_short_frame_classified_as_control() {
+        let mut queue = SendQueue::new();
+
+        // Too short to carry a frame header
+        assert!(queue.push(vec![0u8; 4]));
+        assert_eq!(queue.lane_len(FramePriority::Control), 1);
+    }
+
+    #[test]
+    fn test_clear_background() {
+        let mut queue = SendQueue::new();
+
+        queue.push(build_frame(FrameType::Pad));
+        queue.push(build_frame(FrameType::Pad));
+        queue.push(build_frame(FrameType::Data));
+
+        queue.clear_background();
+
+        assert_eq!(queue.lane_len(FramePriority::Background), 0);
+        assert_eq!(queue.len(), 1);
+    }
+
+    #[test]
+    fn test_explicit_priority_override() {
+        let mut queue = SendQueue::new();
+
+        // Retransmitted DATA promoted to control priority
+        let data = build_frame(FrameType::Data);
+        queue.push_with_priority(data, FramePriority::Control);
+
+        assert_eq!(queue.lane_len(FramePriority::Control), 1);
+        assert_eq!(queue.lane_len(FramePriority::Data), 0);
+    }
+}
@@ -4,7 +4,10 @@
 //! two peers. Sessions multiplex multiple streams (file transfers) over
 //! a single UDP "connection".
 
+use crate::congestion::CongestionAlgorithm;
 use crate::error::SessionError;
+use crate::frame::FrameType;
+use crate::migration::{MultipathScheduler, PathStats};
 use crate::stream::Stream;
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
@@ -98,6 +101,14 @@ pub struct SessionConfig {
     pub rekey_byte_limit: u64,
     /// Emergency rekey threshold (percentage of limits, e.g., 0.9 for 90%)
     pub rekey_emergency_threshold: f64,
+    /// Congestion control algorithm for this session
+    pub congestion_algorithm: CongestionAlgorithm,
+    /// Allow 0-RTT resumption with session tickets
+    ///
+    /// Disable for high-security deployments: early data is not
+    /// forward-secret and is only protected against replay by the
+    /// single-use ticket store.
+    pub enable_0rtt: bool,
 }
 
 impl Default for SessionConfig {
@@ -111,6 +122,8 @@ impl Default for SessionConfig {
             rekey_packet_limit: 1_000_000,
             rekey_byte_limit: 1024 * 1024 * 1024, // 1 GiB
             rekey_emergency_threshold: 0.9,       // 90% of any limit triggers rekey
+            congestion_algorithm: CongestionAlgorithm::Bbr,
+            enable_0rtt: true,
         }
     }
 }
@@ -141,6 +154,217 @@ pub enum HandshakePhase {
     RespSent,
     /// Initiator: received Phase 2, sent Phase 3
     InitComplete,
+    /// Initiator: sent session ticket with 0-RTT early data, awaiting accept
+    EarlyData,
+}
+
+/// Number of frame type slots tracked by [`FrameCounters`].
+///
+/// Frame type codes occupy 0x00-0x0F, so a fixed array of 16 slots covers
+/// every variant (slot 0 is the reserved/invalid type).
+const FRAME_TYPE_SLOTS: usize = 16;
+
+/// Per-frame-type packet counters.
+///
+/// Tracks sent, received, dropped, and retransmitted frame counts indexed
+/// by [`FrameType`]. Makes protocol-level anomalies (e.g. excessive Pad
+/// frames or ACK storms) visible in metrics without packet captures.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrameCounters {
+    sent: [u64; FRAME_TYPE_SLOTS],
+    received: [u64; FRAME_TYPE_SLOTS],
+    dropped: [u64; FRAME_TYPE_SLOTS],
+    retransmitted: [u64; FRAME_TYPE_SLOTS],
+}
+
+impl FrameCounters {
+    /// Create empty counters
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a frame of the given type as sent
+    pub fn record_sent(&mut self, frame_type: FrameType) {
+        self.sent[frame_type as usize] += 1;
+    }
+
+    /// Record a frame of the given type as received
+    pub fn record_received(&mut self, frame_type: FrameType) {
+        self.received[frame_type as usize] += 1;
+    }
+
+    /// Record a frame of the given type as dropped (parse/handler failure)
+    pub fn record_dropped(&mut self, frame_type: FrameType) {
+        self.dropped[frame_type as usize] += 1;
+    }
+
+    /// Record a frame of the given type as retransmitted
+    pub fn record_retransmitted(&mut self, frame_type: FrameType) {
+        self.retransmitted[frame_type as usize] += 1;
+    }
+
+    /// Get number of frames of the given type sent
+    #[must_use]
+    pub fn sent(&self, frame_type: FrameType) -> u64 {
+        self.sent[frame_type as usize]
+    }
+
+    /// Get number of frames of the given type received
+    #[must_use]
+    pub fn received(&self, frame_type: FrameType) -> u64 {
+        self.received[frame_type as usize]
+    }
+
+    /// Get number of frames of the given type dropped
+    #[must_use]
+    pub fn dropped(&self, frame_type: FrameType) -> u64 {
+        self.dropped[frame_type as usize]
+    }
+
+    /// Get number of frames of the given type retransmitted
+    #[must_use]
+    pub fn retransmitted(&self, frame_type: FrameType) -> u64 {
+        self.retransmitted[frame_type as usize]
+    }
+
+    /// Total frames sent across all types
+    #[must_use]
+    pub fn total_sent(&self) -> u64 {
+        self.sent.iter().sum()
+    }
+
+    /// Total frames received across all types
+    #[must_use]
+    pub fn total_received(&self) -> u64 {
+        self.received.iter().sum()
+    }
+
+    /// Total frames dropped across all types
+    #[must_use]
+    pub fn total_dropped(&self) -> u64 {
+        self.dropped.iter().sum()
+    }
+
+    /// Total frames retransmitted across all types
+    #[must_use]
+    pub fn total_retransmitted(&self) -> u64 {
+        self.retransmitted.iter().sum()
+    }
+
+    /// Merge another set of counters into this one (for node-level aggregation)
+    pub fn merge(&mut self, other: &FrameCounters) {
+        for i in 0..FRAME_TYPE_SLOTS {
+            self.sent[i] += other.sent[i];
+            self.received[i] += other.received[i];
+            self.dropped[i] += other.dropped[i];
+            self.retransmitted[i] += other.retransmitted[i];
+        }
+    }
+}
+
+/// Default session ticket lifetime
+const TICKET_LIFETIME: Duration = Duration::from_secs(7 * 24 * 60 * 60); // 7 days
+
+/// Maximum outstanding tickets tracked by a [`ZeroRttAcceptor`]
+const MAX_OUTSTANDING_TICKETS: usize = 4_096;
+
+/// Session resumption ticket for 0-RTT reconnection.
+///
+/// Issued by the responder after a successful Noise_XX handshake. A
+/// reconnecting initiator presents the ticket in its first flight along
+/// with early application data keyed from the resumption secret, skipping
+/// the full handshake round trips.
+#[derive(Debug, Clone)]
+pub struct SessionTicket {
+    /// Opaque ticket identifier presented on resumption
+    pub ticket_id: [u8; 16],
+    /// Secret from which 0-RTT keys are derived
+    pub resumption_secret: [u8; 32],
+    /// When the ticket was issued
+    pub issued_at: Instant,
+    /// How long the ticket remains redeemable
+    pub lifetime: Duration,
+}
+
+impl SessionTicket {
+    /// Check whether the ticket is past its lifetime
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.issued_at.elapsed() >= self.lifetime
+    }
+}
+
+/// Responder-side 0-RTT ticket store with anti-replay protection.
+///
+/// Tickets are strictly single-use: redemption removes the ticket, so a
+/// replayed first flight fails and the attacker learns nothing beyond what
+/// a random connection attempt would reveal. Expired tickets are purged on
+/// redemption and registration.
+pub struct ZeroRttAcceptor {
+    /// Outstanding tickets: `ticket_id` -> ticket
+    tickets: HashMap<[u8; 16], SessionTicket>,
+}
+
+impl ZeroRttAcceptor {
+    /// Create an empty acceptor
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tickets: HashMap::new(),
+        }
+    }
+
+    /// Register a freshly issued ticket for later redemption
+    ///
+    /// When the store is full the oldest outstanding ticket is evicted so
+    /// ticket registration cannot grow without bound.
+    pub fn register(&mut self, ticket: SessionTicket) {
+        self.prune_expired();
+
+        if self.tickets.len() >= MAX_OUTSTANDING_TICKETS
+            && let Some(oldest) = self
+                .tickets
+                .values()
+                .min_by_key(|t| t.issued_at)
+                .map(|t| t.ticket_id)
+        {
+            self.tickets.remove(&oldest);
+        }
+
+        self.tickets.insert(ticket.ticket_id, ticket);
+    }
+
+    /// Redeem a ticket, returning its resumption secret
+    ///
+    /// Single-use: the ticket is consumed whether or not the subsequent
+    /// early data decrypts, which is what provides replay protection.
+    /// Returns `None` for unknown, already-used, or expired tickets - the
+    /// peer must fall back to a full handshake.
+    pub fn redeem(&mut self, ticket_id: &[u8; 16]) -> Option<[u8; 32]> {
+        let ticket = self.tickets.remove(ticket_id)?;
+        if ticket.is_expired() {
+            return None;
+        }
+        Some(ticket.resumption_secret)
+    }
+
+    /// Number of outstanding (unredeemed) tickets
+    #[must_use]
+    pub fn outstanding(&self) -> usize {
+        self.tickets.len()
+    }
+
+    /// Drop expired tickets
+    pub fn prune_expired(&mut self) {
+        self.tickets.retain(|_, t| !t.is_expired());
+    }
+}
+
+impl Default for ZeroRttAcceptor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// A single session with a remote peer
@@ -171,6 +395,10 @@ pub struct Session {
     packets_sent: u64,
     /// Packets received
     packets_received: u64,
+    /// Per-frame-type counters
+    frame_counters: FrameCounters,
+    /// Multipath scheduler striping DATA frames across validated paths
+    multipath: MultipathScheduler,
 }
 
 impl Session {
@@ -197,6 +425,8 @@ impl Session {
             bytes_received: 0,
             packets_sent: 0,
             packets_received: 0,
+            frame_counters: FrameCounters::new(),
+            multipath: MultipathScheduler::new(),
         }
     }
 
@@ -251,6 +481,12 @@ impl Session {
                 SessionState::Handshaking(HandshakePhase::InitSent),
                 SessionState::Handshaking(HandshakePhase::InitComplete),
             ) => true,
+
+            // 0-RTT rejected: fall back to a full handshake
+            (
+                SessionState::Handshaking(HandshakePhase::EarlyData),
+                SessionState::Handshaking(HandshakePhase::InitSent),
+            ) => true,
             (
                 SessionState::Handshaking(
                     HandshakePhase::RespSent | HandshakePhase::InitComplete | _,
@@ -298,10 +534,8 @@ impl Session {
 
         // Handle state entry logic
         match new_state {
-            SessionState::Established => {
-                if self.established_at.is_none() {
-                    self.established_at = Some(Instant::now());
-                }
+            SessionState::Established if self.established_at.is_none() => {
+                self.established_at = Some(Instant::now());
             }
             SessionState::Rekeying => {
                 self.last_rekey = Some(Instant::now());
@@ -493,6 +727,70 @@ impl Session {
         self.update_activity();
     }
 
+    /// Record a frame of the given type as sent
+    pub fn record_frame_sent(&mut self, frame_type: FrameType) {
+        self.frame_counters.record_sent(frame_type);
+    }
+
+    /// Record a frame of the given type as received
+    pub fn record_frame_received(&mut self, frame_type: FrameType) {
+        self.frame_counters.record_received(frame_type);
+    }
+
+    /// Record a frame of the given type as dropped
+    pub fn record_frame_dropped(&mut self, frame_type: FrameType) {
+        self.frame_counters.record_dropped(frame_type);
+    }
+
+    /// Record a frame of the given type as retransmitted
+    pub fn record_frame_retransmitted(&mut self, frame_type: FrameType) {
+        self.frame_counters.record_retransmitted(frame_type);
+    }
+
+    /// Get per-frame-type counters
+    #[must_use]
+    pub fn frame_counters(&self) -> &FrameCounters {
+        &self.frame_counters
+    }
+
+    /// Issue a session resumption ticket for 0-RTT reconnection
+    ///
+    /// Returns `None` unless the session is established and 0-RTT is
+    /// enabled in the configuration. The responder registers the ticket
+    /// with its [`ZeroRttAcceptor`] and sends it to the peer, which may
+    /// present it on reconnection to send application data in the first
+    /// flight.
+    #[must_use]
+    pub fn issue_resumption_ticket(&self) -> Option<SessionTicket> {
+        if !self.config.enable_0rtt || self.state != SessionState::Established {
+            return None;
+        }
+
+        let mut ticket_id = [0u8; 16];
+        let mut resumption_secret = [0u8; 32];
+        getrandom::getrandom(&mut ticket_id).expect("getrandom failed");
+        getrandom::getrandom(&mut resumption_secret).expect("getrandom failed");
+
+        Some(SessionTicket {
+            ticket_id,
+            resumption_secret,
+            issued_at: Instant::now(),
+            lifetime: TICKET_LIFETIME,
+        })
+    }
+
+    /// Get the multipath scheduler
+    #[must_use]
+    pub fn multipath(&self) -> &MultipathScheduler {
+        &self.multipath
+    }
+
+    /// Get the multipath scheduler mutably (add/remove paths, schedule frames)
+    #[must_use]
+    pub fn multipath_mut(&mut self) -> &mut MultipathScheduler {
+        &mut self.multipath
+    }
+
     /// Get session statistics
     #[must_use]
     pub fn stats(&self) -> SessionStats {
@@ -505,6 +803,8 @@ impl Session {
             stream_count: self.streams.len(),
             established_at: self.established_at,
             last_activity: self.last_activity,
+            frame_counters: self.frame_counters,
+            path_stats: self.multipath.path_stats(),
         }
     }
 }
@@ -534,6 +834,10 @@ pub struct SessionStats {
     pub established_at: Option<Instant>,
     /// Last activity timestamp
     pub last_activity: Instant,
+    /// Per-frame-type counters
+    pub frame_counters: FrameCounters,
+    /// Per-path transmission statistics (empty when single-path)
+    pub path_stats: Vec<PathStats>,
 }
 
 #[cfg(test)]
@@ -1055,6 +1359,186 @@ mod tests {
         assert!(session.needs_rekey());
     }
 
+    #[test]
+    fn test_frame_counters_record_and_query() {
+        let mut counters = FrameCounters::new();
+
+        counters.record_sent(FrameType::Data);
+        counters.record_sent(FrameType::Data);
+        counters.record_sent(FrameType::Ack);
+        counters.record_received(FrameType::Pad);
+        counters.record_dropped(FrameType::Data);
+        counters.record_retransmitted(FrameType::Data);
+
+        assert_eq!(counters.sent(FrameType::Data), 2);
+        assert_eq!(counters.sent(FrameType::Ack), 1);
+        assert_eq!(counters.sent(FrameType::Ping), 0);
+        assert_eq!(counters.received(FrameType::Pad), 1);
+        assert_eq!(counters.dropped(FrameType::Data), 1);
+        assert_eq!(counters.retransmitted(FrameType::Data), 1);
+
+        assert_eq!(counters.total_sent(), 3);
+        assert_eq!(counters.total_received(), 1);
+        assert_eq!(counters.total_dropped(), 1);
+        assert_eq!(counters.total_retransmitted(), 1);
+    }
+
+    #[test]
+    fn test_frame_counters_merge() {
+        let mut a = FrameCounters::new();
+        a.record_sent(FrameType::Data);
+        a.record_received(FrameType::Ack);
+
+        let mut b = FrameCounters::new();
+        b.record_sent(FrameType::Data);
+        b.record_sent(FrameType::Pad);
+        b.record_dropped(FrameType::Control);
+
+        a.merge(&b);
+        assert_eq!(a.sent(FrameType::Data), 2);
+        assert_eq!(a.sent(FrameType::Pad), 1);
+        assert_eq!(a.received(FrameType::Ack), 1);
+        assert_eq!(a.dropped(FrameType::Control), 1);
+    }
+
+    #[test]
+    fn test_session_frame_counters_in_stats() {
+        let mut session = Session::new();
+
+        session.record_frame_sent(FrameType::Data);
+        session.record_frame_sent(FrameType::Ping);
+        session.record_frame_received(FrameType::Pong);
+        session.record_frame_dropped(FrameType::Data);
+        session.record_frame_retransmitted(FrameType::Data);
+
+        let stats = session.stats();
+        assert_eq!(stats.frame_counters.sent(FrameType::Data), 1);
+        assert_eq!(stats.frame_counters.sent(FrameType::Ping), 1);
+        assert_eq!(stats.frame_counters.received(FrameType::Pong), 1);
+        assert_eq!(stats.frame_counters.dropped(FrameType::Data), 1);
+        assert_eq!(stats.frame_counters.retransmitted(FrameType::Data), 1);
+    }
+
+    #[test]
+    fn test_ticket_issuance_requires_established() {
+        let mut session = Session::new();
+
+        // No ticket before the handshake completes
+        assert!(session.issue_resumption_ticket().is_none());
+
+        session
+            .transition_to(SessionState::Handshaking(HandshakePhase::InitSent))
+            .unwrap();
+        session.transition_to(SessionState::Established).unwrap();
+
+        let ticket = session.issue_resumption_ticket().unwrap();
+        assert!(!ticket.is_expired());
+    }
+
+    #[test]
+    fn test_ticket_issuance_disabled_by_config() {
+        let config = SessionConfig {
+            enable_0rtt: false,
+            ..Default::default()
+        };
+        let mut session = Session::with_config(config);
+
+        session
+            .transition_to(SessionState::Handshaking(HandshakePhase::InitSent))
+            .unwrap();
+        session.transition_to(SessionState::Established).unwrap();
+
+        // High-security deployments can disable 0-RTT entirely
+        assert!(session.issue_resumption_ticket().is_none());
+    }
+
+    #[test]
+    fn test_tickets_are_unique() {
+        let mut session = Session::new();
+        session
+            .transition_to(SessionState::Handshaking(HandshakePhase::InitSent))
+            .unwrap();
+        session.transition_to(SessionState::Established).unwrap();
+
+        let t1 = session.issue_resumption_ticket().unwrap();
+        let t2 = session.issue_resumption_ticket().unwrap();
+
+        assert_ne!(t1.ticket_id, t2.ticket_id);
+        assert_ne!(t1.resumption_secret, t2.resumption_secret);
+    }
+
+    #[test]
+    fn test_zero_rtt_redeem_single_use() {
+        let mut acceptor = ZeroRttAcceptor::new();
+
+        let ticket = SessionTicket {
+            ticket_id: [1u8; 16],
+            resumption_secret: [2u8; 32],
+            issued_at: Instant::now(),
+            lifetime: TICKET_LIFETIME,
+        };
+        acceptor.register(ticket);
+        assert_eq!(acceptor.outstanding(), 1);
+
+        // First redemption succeeds
+        assert_eq!(acceptor.redeem(&[1u8; 16]), Some([2u8; 32]));
+
+        // Replay fails - ticket was consumed
+        assert_eq!(acceptor.redeem(&[1u8; 16]), None);
+        assert_eq!(acceptor.outstanding(), 0);
+    }
+
+    #[test]
+    fn test_zero_rtt_redeem_unknown_ticket() {
+        let mut acceptor = ZeroRttAcceptor::new();
+        assert_eq!(acceptor.redeem(&[9u8; 16]), None);
+    }
+
+    #[test]
+    fn test_zero_rtt_expired_ticket_rejected() {
+        let mut acceptor = ZeroRttAcceptor::new();
+
+        let ticket = SessionTicket {
+            ticket_id: [3u8; 16],
+            resumption_secret: [4u8; 32],
+            issued_at: Instant::now(),
+            lifetime: Duration::ZERO,
+        };
+        acceptor.register(ticket);
+
+        assert_eq!(acceptor.redeem(&[3u8; 16]), None);
+    }
+
+    #[test]
+    fn test_early_data_fallback_to_full_handshake() {
+        let mut session = Session::new();
+
+        // Initiator presents a ticket with early data
+        session
+            .transition_to(SessionState::Handshaking(HandshakePhase::EarlyData))
+            .unwrap();
+
+        // Ticket rejected - fall back to a full handshake
+        assert!(session.can_transition(SessionState::Handshaking(HandshakePhase::InitSent)));
+        session
+            .transition_to(SessionState::Handshaking(HandshakePhase::InitSent))
+            .unwrap();
+        session.transition_to(SessionState::Established).unwrap();
+    }
+
+    #[test]
+    fn test_early_data_accepted_establishes() {
+        let mut session = Session::new();
+
+        session
+            .transition_to(SessionState::Handshaking(HandshakePhase::EarlyData))
+            .unwrap();
+
+        // Ticket accepted - session established without further round trips
+        assert!(session.can_transition(SessionState::Established));
+        session.transition_to(SessionState::Established).unwrap();
+    }
+
     #[test]
     fn test_rekey_no_false_positives() {
         let config = SessionConfig {
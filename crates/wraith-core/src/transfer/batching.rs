@@ -0,0 +1,286 @@
+//! Rate-adaptive chunk batching for active transfers.
+//!
+//! A transfer's hashed chunk boundaries are fixed the moment the tree hash
+//! is computed — changing them mid-transfer would invalidate verification.
+//! What *can* adapt is how many of those logical chunks the sender moves per
+//! disk read: on fast paths, reading one chunk at a time leaves the disk
+//! idle between sends, while on lossy paths a large read-ahead batch
+//! amplifies the retransmission cost of a stall.
+//!
+//! [`ChunkBatcher`] sizes the batch from the congestion controller's
+//! bandwidth estimate (e.g.
+//! [`BbrState::estimated_bandwidth`](crate::congestion::BbrState::estimated_bandwidth))
+//! and the observed loss rate: the batch grows toward covering
+//! [`target_read_interval`](BatchConfig::target_read_interval) of wall time
+//! at the estimated send rate, and is split back down when loss crosses
+//! [`loss_split_threshold`](BatchConfig::loss_split_threshold). Adjustments
+//! move one doubling/halving step per update so a noisy estimate does not
+//! cause batch-size oscillation.
+
+use std::ops::Range;
+use std::time::Duration;
+
+/// Configuration for rate-adaptive chunk batching
+#[derive(Debug, Clone)]
+pub struct BatchConfig {
+    /// Smallest batch (logical chunks per disk read)
+    pub min_batch: usize,
+    /// Largest batch (bounds read-ahead memory and stall cost)
+    pub max_batch: usize,
+    /// Wall time one disk read should cover at the estimated send rate
+    pub target_read_interval: Duration,
+    /// Loss rate at or above which the batch is halved instead of grown
+    pub loss_split_threshold: f64,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            min_batch: 1,
+            max_batch: 64,
+            target_read_interval: Duration::from_millis(25),
+            loss_split_threshold: 0.02,
+        }
+    }
+}
+
+/// Rate-adaptive batch sizing for a single transfer
+///
+/// Tracks the current chunks-per-disk-read batch size. Feed it the
+/// congestion controller's estimates via [`update`](Self::update) between
+/// batches; read the current size via [`batch_chunks`](Self::batch_chunks)
+/// or slice the chunk index space with [`batch_range`](Self::batch_range).
+#[derive(Debug)]
+pub struct ChunkBatcher {
+    /// Fixed logical chunk size (hashed boundary), in bytes
+    chunk_size: usize,
+    config: BatchConfig,
+    /// Current batch size in logical chunks
+    batch: usize,
+}
+
+impl ChunkBatcher {
+    /// Create a batcher for a transfer with the given hashed chunk size
+    ///
+    /// Starts at the minimum batch size; the batch grows as bandwidth
+    /// estimates arrive.
+    #[must_use]
+    pub fn new(chunk_size: usize, config: BatchConfig) -> Self {
+        let batch = config.min_batch.max(1);
+        Self {
+            chunk_size: chunk_size.max(1),
+            config,
+            batch,
+        }
+    }
+
+    /// Current batch size in logical chunks
+    #[must_use]
+    pub fn batch_chunks(&self) -> usize {
+        self.batch
+    }
+
+    /// Current batch size in bytes (full chunks; the file's final chunk
+    /// may be shorter)
+    #[must_use]
+    pub fn batch_bytes(&self) -> usize {
+        self.batch.saturating_mul(self.chunk_size)
+    }
+
+    /// Re-size the batch from fresh congestion estimates
+    ///
+    /// `bandwidth_bps` is the estimated bottleneck bandwidth in bytes/sec
+    /// (0 = no estimate yet, batch unchanged); `loss_rate` is the observed
+    /// packet loss fraction (0.0 to 1.0). Returns the new batch size.
+    pub fn update(&mut self, bandwidth_bps: u64, loss_rate: f64) -> usize {
+        let min = self.config.min_batch.max(1);
+        let max = self.config.max_batch.max(min);
+
+        // High loss: split immediately so a stall costs at most half the
+        // previous read-ahead
+        if loss_rate >= self.config.loss_split_threshold {
+            self.batch = (self.batch / 2).max(min);
+            return self.batch;
+        }
+
+        if bandwidth_bps == 0 {
+            return self.batch;
+        }
+
+        let target_bytes = bandwidth_bps as f64 * self.config.target_read_interval.as_secs_f64();
+        let target = ((target_bytes / self.chunk_size as f64) as usize).clamp(min, max);
+
+        // One doubling/halving step per update: converges quickly but does
+        // not whipsaw on a noisy estimate
+        if target > self.batch {
+            self.batch = (self.batch.saturating_mul(2)).min(target);
+        } else if target < self.batch {
+            self.batch = (self.batch / 2).max(target);
+        }
+
+        self.batch
+    }
+
+    /// Seed the batch straight to the size a known bandwidth implies
+    ///
+    /// Careful-resume entry point: when a transfer starts over a path whose
+    /// capacity was measured moments ago (see
+    /// [`PathEstimateCache`](crate::node::path_estimates::PathEstimateCache)),
+    /// the batch jumps directly to the target for `bandwidth_bps` instead of
+    /// doubling up from the minimum. Subsequent [`update`](Self::update)
+    /// calls adjust from there, so a stale seed is corrected within a few
+    /// batches. A zero bandwidth leaves the batch untouched.
+    pub fn seed(&mut self, bandwidth_bps: u64) {
+        if bandwidth_bps == 0 {
+            return;
+        }
+
+        let min = self.config.min_batch.max(1);
+        let max = self.config.max_batch.max(min);
+        let target_bytes = bandwidth_bps as f64 * self.config.target_read_interval.as_secs_f64();
+        self.batch = ((target_bytes / self.chunk_size as f64) as usize).clamp(min, max);
+    }
+
+    /// Chunk indices covered by the next batch, truncated at end of file
+    #[must_use]
+    pub fn batch_range(&self, next_chunk: u64, total_chunks: u64) -> Range<u64> {
+        let end = next_chunk
+            .saturating_add(self.batch as u64)
+            .min(total_chunks);
+        next_chunk..end.max(next_chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHUNK: usize = 64 * 1024;
+
+    #[test]
+    fn test_batcher_starts_at_min() {
+        let batcher = ChunkBatcher::new(CHUNK, BatchConfig::default());
+        assert_eq!(batcher.batch_chunks(), 1);
+        assert_eq!(batcher.batch_bytes(), CHUNK);
+    }
+
+    #[test]
+    fn test_batch_grows_one_doubling_per_update() {
+        let mut batcher = ChunkBatcher::new(CHUNK, BatchConfig::default());
+
+        // 100 MB/s over 25ms = 2.5 MB target = 40 chunks, but growth is
+        // capped at one doubling per update
+        let bw = 100 * 1024 * 1024;
+        assert_eq!(batcher.update(bw, 0.0), 2);
+        assert_eq!(batcher.update(bw, 0.0), 4);
+        assert_eq!(batcher.update(bw, 0.0), 8);
+        assert_eq!(batcher.update(bw, 0.0), 16);
+        assert_eq!(batcher.update(bw, 0.0), 32);
+        // Final step lands on the target, not a full doubling
+        assert_eq!(batcher.update(bw, 0.0), 40);
+        assert_eq!(batcher.update(bw, 0.0), 40);
+    }
+
+    #[test]
+    fn test_batch_shrinks_when_bandwidth_drops() {
+        let mut batcher = ChunkBatcher::new(CHUNK, BatchConfig::default());
+
+        let fast = 100 * 1024 * 1024;
+        for _ in 0..8 {
+            batcher.update(fast, 0.0);
+        }
+        assert_eq!(batcher.batch_chunks(), 40);
+
+        // 1 MB/s over 25ms is well under one chunk: shrink by halving
+        let slow = 1024 * 1024;
+        assert_eq!(batcher.update(slow, 0.0), 20);
+        assert_eq!(batcher.update(slow, 0.0), 10);
+        assert_eq!(batcher.update(slow, 0.0), 5);
+        assert_eq!(batcher.update(slow, 0.0), 2);
+        assert_eq!(batcher.update(slow, 0.0), 1);
+        assert_eq!(batcher.update(slow, 0.0), 1);
+    }
+
+    #[test]
+    fn test_loss_splits_batch() {
+        let mut batcher = ChunkBatcher::new(CHUNK, BatchConfig::default());
+
+        let bw = 100 * 1024 * 1024;
+        for _ in 0..8 {
+            batcher.update(bw, 0.0);
+        }
+        assert_eq!(batcher.batch_chunks(), 40);
+
+        // Loss above the threshold halves regardless of bandwidth
+        assert_eq!(batcher.update(bw, 0.05), 20);
+        assert_eq!(batcher.update(bw, 0.05), 10);
+        // Loss below the threshold resumes growing
+        assert_eq!(batcher.update(bw, 0.0), 20);
+    }
+
+    #[test]
+    fn test_seed_jumps_straight_to_target() {
+        let mut batcher = ChunkBatcher::new(CHUNK, BatchConfig::default());
+
+        // The same rate takes six doubling updates to reach from cold
+        batcher.seed(100 * 1024 * 1024);
+        assert_eq!(batcher.batch_chunks(), 40);
+
+        // A zero seed leaves the batch at the minimum
+        let mut cold = ChunkBatcher::new(CHUNK, BatchConfig::default());
+        cold.seed(0);
+        assert_eq!(cold.batch_chunks(), 1);
+    }
+
+    #[test]
+    fn test_zero_bandwidth_keeps_batch() {
+        let mut batcher = ChunkBatcher::new(CHUNK, BatchConfig::default());
+        batcher.update(100 * 1024 * 1024, 0.0);
+        let before = batcher.batch_chunks();
+
+        assert_eq!(batcher.update(0, 0.0), before);
+    }
+
+    #[test]
+    fn test_batch_clamped_to_max() {
+        let config = BatchConfig {
+            max_batch: 8,
+            ..BatchConfig::default()
+        };
+        let mut batcher = ChunkBatcher::new(CHUNK, config);
+
+        for _ in 0..10 {
+            batcher.update(u64::MAX / 2, 0.0);
+        }
+        assert_eq!(batcher.batch_chunks(), 8);
+    }
+
+    #[test]
+    fn test_batch_range_truncates_at_end_of_file() {
+        let mut batcher = ChunkBatcher::new(CHUNK, BatchConfig::default());
+        for _ in 0..3 {
+            batcher.update(100 * 1024 * 1024, 0.0);
+        }
+        assert_eq!(batcher.batch_chunks(), 8);
+
+        assert_eq!(batcher.batch_range(0, 100), 0..8);
+        assert_eq!(batcher.batch_range(96, 100), 96..100);
+        assert_eq!(batcher.batch_range(100, 100), 100..100);
+    }
+
+    #[test]
+    fn test_degenerate_config_is_sane() {
+        // min_batch of 0 is treated as 1; chunk_size of 0 does not divide
+        // by zero
+        let config = BatchConfig {
+            min_batch: 0,
+            max_batch: 0,
+            ..BatchConfig::default()
+        };
+        let mut batcher = ChunkBatcher::new(0, config);
+
+        assert_eq!(batcher.batch_chunks(), 1);
+        assert_eq!(batcher.update(1024, 0.0), 1);
+        assert_eq!(batcher.update(1024, 1.0), 1);
+    }
+}
@@ -0,0 +1,489 @@
+//! Transparent per-chunk compression for file transfers.
+//!
+//! Chunks are optionally compressed *before* encryption (ciphertext does not
+//! compress), using an algorithm negotiated in the transfer offer so both
+//! sides agree:
+//!
+//! - **LZ4** - very low latency, modest ratios; the default for interactive
+//!   transfers and the algorithm referenced by the frame `COMPRESSED` flag
+//! - **Zstd** - better ratios at a configurable level for bulk transfers
+//!
+//! Each compressed chunk is self-describing on the wire:
+//!
+//! ```text
+//! [1 byte method] [4 bytes uncompressed length, big-endian] [payload]
+//! ```
+//!
+//! A method of `0x00` means the payload is stored uncompressed. This is used
+//! both when compression is disabled and when a chunk expands (already
+//! compressed or encrypted content), so decompression never depends on
+//! out-of-band state beyond the negotiated algorithm.
+//!
+//! # Incompressible-data detection
+//!
+//! Compressing incompressible data wastes CPU on both ends. [`ChunkCompressor`]
+//! samples the first chunks of a transfer: if the trial compression ratio
+//! stays above the configured threshold, compression is disabled for the
+//! remainder of the transfer.
+
+use std::collections::HashSet;
+use thiserror::Error;
+
+/// Maximum uncompressed chunk size accepted during decompression (64 MiB)
+///
+/// Guards against decompression bombs: the declared uncompressed length is
+/// attacker-controlled until the chunk hash is verified.
+const MAX_DECOMPRESSED_SIZE: usize = 64 * 1024 * 1024;
+
+/// Wire method tag: payload stored uncompressed
+const METHOD_STORED: u8 = 0x00;
+/// Wire method tag: LZ4 block compression
+const METHOD_LZ4: u8 = 0x01;
+/// Wire method tag: Zstandard compression
+const METHOD_ZSTD: u8 = 0x02;
+
+/// Chunk compression header size (1 byte method + 4 bytes length)
+pub const COMPRESSION_HEADER_SIZE: usize = 5;
+
+/// Errors from chunk compression/decompression
+#[derive(Debug, Error)]
+pub enum CompressionError {
+    /// Chunk shorter than the compression header
+    #[error("Compressed chunk truncated: {0} bytes (min {COMPRESSION_HEADER_SIZE})")]
+    Truncated(usize),
+
+    /// Unknown method tag in the chunk header
+    #[error("Unknown compression method: {0:#04x}")]
+    UnknownMethod(u8),
+
+    /// Declared uncompressed length exceeds the decompression limit
+    #[error("Declared uncompressed size {0} exceeds limit {MAX_DECOMPRESSED_SIZE}")]
+    SizeLimit(usize),
+
+    /// Payload failed to decompress or did not match the declared length
+    #[error("Decompression failed: {0}")]
+    Decompress(String),
+
+    /// Compression backend failure
+    #[error("Compression failed: {0}")]
+    Compress(String),
+}
+
+/// Compression algorithm negotiated in the transfer offer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CompressionAlgorithm {
+    /// No compression (always supported)
+    #[default]
+    None,
+    /// LZ4 block compression (low latency)
+    Lz4,
+    /// Zstandard (better ratio, negotiated level)
+    Zstd,
+}
+
+impl CompressionAlgorithm {
+    /// Wire tag used in the transfer offer
+    pub fn wire_tag(&self) -> u8 {
+        match self {
+            Self::None => METHOD_STORED,
+            Self::Lz4 => METHOD_LZ4,
+            Self::Zstd => METHOD_ZSTD,
+        }
+    }
+
+    /// Parse a wire tag from a transfer offer
+    ///
+    /// Unknown tags fall back to `None` rather than erroring: a peer
+    /// advertising an algorithm we do not know simply gets no compression.
+    pub fn from_wire_tag(tag: u8) -> Self {
+        match tag {
+            METHOD_LZ4 => Self::Lz4,
+            METHOD_ZSTD => Self::Zstd,
+            _ => Self::None,
+        }
+    }
+}
+
+/// Negotiate a compression algorithm from both sides' preference lists
+///
+/// Returns the first algorithm in `local` (preference order) that `remote`
+/// also supports, falling back to [`CompressionAlgorithm::None`] when there
+/// is no overlap. `None` is implicitly supported by every peer.
+pub fn negotiate(
+    local: &[CompressionAlgorithm],
+    remote: &[CompressionAlgorithm],
+) -> CompressionAlgorithm {
+    let remote: HashSet<_> = remote.iter().copied().collect();
+    local
+        .iter()
+        .copied()
+        .find(|alg| *alg == CompressionAlgorithm::None || remote.contains(alg))
+        .unwrap_or(CompressionAlgorithm::None)
+}
+
+/// Compression configuration for a transfer
+#[derive(Debug, Clone)]
+pub struct CompressionConfig {
+    /// Negotiated algorithm
+    pub algorithm: CompressionAlgorithm,
+    /// Zstd compression level (1-19; ignored for LZ4)
+    pub zstd_level: i32,
+    /// Number of leading chunks sampled for compressibility
+    pub sample_chunks: u32,
+    /// Minimum savings required to keep compression enabled (0.0-1.0)
+    ///
+    /// If the sampled chunks compress to more than `1.0 - min_savings` of
+    /// their original size on average, compression is disabled for the rest
+    /// of the transfer.
+    pub min_savings: f64,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            algorithm: CompressionAlgorithm::None,
+            zstd_level: 3,
+            sample_chunks: 4,
+            min_savings: 0.05,
+        }
+    }
+}
+
+/// Per-transfer chunk compressor with incompressible-data detection
+///
+/// Thread-per-transfer: not shared across transfers, so sampling state
+/// needs no synchronization.
+#[derive(Debug)]
+pub struct ChunkCompressor {
+    config: CompressionConfig,
+    /// Compression currently active (may be disabled by sampling)
+    enabled: bool,
+    /// Chunks sampled so far
+    sampled: u32,
+    /// Sum of compressed/uncompressed ratios over sampled chunks
+    ratio_sum: f64,
+}
+
+impl ChunkCompressor {
+    /// Create a compressor for a negotiated configuration
+    pub fn new(config: CompressionConfig) -> Self {
+        let enabled = config.algorithm != CompressionAlgorithm::None;
+        Self {
+            config,
+            enabled,
+            sampled: 0,
+            ratio_sum: 0.0,
+        }
+    }
+
+    /// Whether compression is currently active
+    ///
+    /// Starts true for any negotiated algorithm other than `None` and may
+    /// flip to false once sampling detects incompressible data.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Compress a chunk, framing it with the method/length header
+    ///
+    /// Returns the stored (uncompressed) framing when compression is
+    /// disabled, the chunk is empty, or the compressed form is not smaller
+    /// than the original. The first [`CompressionConfig::sample_chunks`]
+    /// chunks contribute to the compressibility sample; if the average
+    /// ratio shows less than [`CompressionConfig::min_savings`] savings,
+    /// compression is disabled for subsequent chunks.
+    pub fn compress_chunk(&mut self, data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        if !self.enabled || data.is_empty() {
+            return Ok(Self::frame(METHOD_STORED, data.len(), data));
+        }
+
+        let compressed = match self.config.algorithm {
+            CompressionAlgorithm::None => unreachable!("enabled implies an algorithm"),
+            CompressionAlgorithm::Lz4 => lz4_flex::compress(data),
+            CompressionAlgorithm::Zstd => zstd::bulk::compress(data, self.config.zstd_level)
+                .map_err(|e| CompressionError::Compress(e.to_string()))?,
+        };
+
+        self.record_sample(compressed.len(), data.len());
+
+        if compressed.len() < data.len() {
+            Ok(Self::frame(
+                self.config.algorithm.wire_tag(),
+                data.len(),
+                &compressed,
+            ))
+        } else {
+            Ok(Self::frame(METHOD_STORED, data.len(), data))
+        }
+    }
+
+    /// Decompress a framed chunk
+    ///
+    /// Stateless with respect to sampling: the receiver honors whatever
+    /// method each chunk declares, so the sender can disable compression
+    /// mid-transfer without renegotiation.
+    pub fn decompress_chunk(data: &[u8]) -> Result<Vec<u8>, CompressionError> {
+        if data.len() < COMPRESSION_HEADER_SIZE {
+            return Err(CompressionError::Truncated(data.len()));
+        }
+
+        let method = data[0];
+        let uncompressed_len = u32::from_be_bytes([data[1], data[2], data[3], data[4]]) as usize;
+        let payload = &data[COMPRESSION_HEADER_SIZE..];
+
+        if uncompressed_len > MAX_DECOMPRESSED_SIZE {
+            return Err(CompressionError::SizeLimit(uncompressed_len));
+        }
+
+        let out = match method {
+            METHOD_STORED => payload.to_vec(),
+            METHOD_LZ4 => lz4_flex::decompress(payload, uncompressed_len)
+                .map_err(|e| CompressionError::Decompress(e.to_string()))?,
+            METHOD_ZSTD => zstd::bulk::decompress(payload, uncompressed_len)
+                .map_err(|e| CompressionError::Decompress(e.to_string()))?,
+            other => return Err(CompressionError::UnknownMethod(other)),
+        };
+
+        if out.len() != uncompressed_len {
+            return Err(CompressionError::Decompress(format!(
+                "Length mismatch: declared {uncompressed_len}, got {}",
+                out.len()
+            )));
+        }
+
+        Ok(out)
+    }
+
+    /// Record a trial ratio and disable compression if the sample shows
+    /// the data is incompressible
+    fn record_sample(&mut self, compressed_len: usize, uncompressed_len: usize) {
+        if self.sampled >= self.config.sample_chunks {
+            return;
+        }
+
+        self.sampled += 1;
+        self.ratio_sum += compressed_len as f64 / uncompressed_len as f64;
+
+        if self.sampled == self.config.sample_chunks {
+            let avg_ratio = self.ratio_sum / self.sampled as f64;
+            if avg_ratio > 1.0 - self.config.min_savings {
+                tracing::debug!(
+                    "Disabling compression: sample ratio {:.3} above threshold {:.3}",
+                    avg_ratio,
+                    1.0 - self.config.min_savings
+                );
+                self.enabled = false;
+            }
+        }
+    }
+
+    /// Frame a payload with the method/length header
+    fn frame(method: u8, uncompressed_len: usize, payload: &[u8]) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(COMPRESSION_HEADER_SIZE + payload.len());
+        buf.push(method);
+        buf.extend_from_slice(&(uncompressed_len as u32).to_be_bytes());
+        buf.extend_from_slice(payload);
+        buf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compressible(len: usize) -> Vec<u8> {
+        // Long runs compress well under both algorithms
+        (0..len).map(|i| (i / 64) as u8).collect()
+    }
+
+    fn incompressible(len: usize) -> Vec<u8> {
+        // splitmix64-style mixing produces pseudorandom bytes
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                (state >> 33) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_wire_tag_round_trip() {
+        for alg in [
+            CompressionAlgorithm::None,
+            CompressionAlgorithm::Lz4,
+            CompressionAlgorithm::Zstd,
+        ] {
+            assert_eq!(CompressionAlgorithm::from_wire_tag(alg.wire_tag()), alg);
+        }
+        // Unknown tags fall back to None
+        assert_eq!(
+            CompressionAlgorithm::from_wire_tag(0xFF),
+            CompressionAlgorithm::None
+        );
+    }
+
+    #[test]
+    fn test_negotiate_prefers_local_order() {
+        let local = [CompressionAlgorithm::Zstd, CompressionAlgorithm::Lz4];
+        let remote = [CompressionAlgorithm::Lz4, CompressionAlgorithm::Zstd];
+        assert_eq!(negotiate(&local, &remote), CompressionAlgorithm::Zstd);
+    }
+
+    #[test]
+    fn test_negotiate_no_overlap_falls_back_to_none() {
+        let local = [CompressionAlgorithm::Zstd];
+        let remote = [CompressionAlgorithm::Lz4];
+        assert_eq!(negotiate(&local, &remote), CompressionAlgorithm::None);
+    }
+
+    #[test]
+    fn test_negotiate_empty_remote() {
+        let local = [CompressionAlgorithm::Lz4, CompressionAlgorithm::None];
+        assert_eq!(negotiate(&local, &[]), CompressionAlgorithm::None);
+    }
+
+    #[test]
+    fn test_lz4_round_trip() {
+        let mut compressor = ChunkCompressor::new(CompressionConfig {
+            algorithm: CompressionAlgorithm::Lz4,
+            ..Default::default()
+        });
+
+        let data = compressible(16 * 1024);
+        let framed = compressor.compress_chunk(&data).unwrap();
+        assert!(framed.len() < data.len());
+        assert_eq!(framed[0], METHOD_LZ4);
+
+        let restored = ChunkCompressor::decompress_chunk(&framed).unwrap();
+        assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn test_zstd_round_trip() {
+        let mut compressor = ChunkCompressor::new(CompressionConfig {
+            algorithm: CompressionAlgorithm::Zstd,
+            ..Default::default()
+        });
+
+        let data = compressible(16 * 1024);
+        let framed = compressor.compress_chunk(&data).unwrap();
+        assert!(framed.len() < data.len());
+        assert_eq!(framed[0], METHOD_ZSTD);
+
+        let restored = ChunkCompressor::decompress_chunk(&framed).unwrap();
+        assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn test_disabled_stores_raw() {
+        let mut compressor = ChunkCompressor::new(CompressionConfig::default());
+        assert!(!compressor.is_enabled());
+
+        let data = compressible(1024);
+        let framed = compressor.compress_chunk(&data).unwrap();
+        assert_eq!(framed[0], METHOD_STORED);
+        assert_eq!(framed.len(), COMPRESSION_HEADER_SIZE + data.len());
+        assert_eq!(ChunkCompressor::decompress_chunk(&framed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_incompressible_chunk_stored_raw() {
+        let mut compressor = ChunkCompressor::new(CompressionConfig {
+            algorithm: CompressionAlgorithm::Lz4,
+            ..Default::default()
+        });
+
+        let data = incompressible(4096);
+        let framed = compressor.compress_chunk(&data).unwrap();
+        // Expanding chunks fall back to stored framing
+        assert_eq!(framed[0], METHOD_STORED);
+        assert_eq!(ChunkCompressor::decompress_chunk(&framed).unwrap(), data);
+    }
+
+    #[test]
+    fn test_sampling_disables_compression() {
+        let mut compressor = ChunkCompressor::new(CompressionConfig {
+            algorithm: CompressionAlgorithm::Lz4,
+            sample_chunks: 4,
+            ..Default::default()
+        });
+
+        for _ in 0..4 {
+            compressor.compress_chunk(&incompressible(4096)).unwrap();
+        }
+        assert!(!compressor.is_enabled());
+
+        // Even compressible chunks are now stored raw
+        let framed = compressor.compress_chunk(&compressible(4096)).unwrap();
+        assert_eq!(framed[0], METHOD_STORED);
+    }
+
+    #[test]
+    fn test_sampling_keeps_compression_for_compressible_data() {
+        let mut compressor = ChunkCompressor::new(CompressionConfig {
+            algorithm: CompressionAlgorithm::Lz4,
+            sample_chunks: 4,
+            ..Default::default()
+        });
+
+        for _ in 0..8 {
+            compressor.compress_chunk(&compressible(4096)).unwrap();
+        }
+        assert!(compressor.is_enabled());
+    }
+
+    #[test]
+    fn test_empty_chunk() {
+        let mut compressor = ChunkCompressor::new(CompressionConfig {
+            algorithm: CompressionAlgorithm::Zstd,
+            ..Default::default()
+        });
+
+        let framed = compressor.compress_chunk(&[]).unwrap();
+        assert_eq!(framed.len(), COMPRESSION_HEADER_SIZE);
+        assert!(
+            ChunkCompressor::decompress_chunk(&framed)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_decompress_truncated() {
+        let err = ChunkCompressor::decompress_chunk(&[0x01, 0x00]).unwrap_err();
+        assert!(matches!(err, CompressionError::Truncated(2)));
+    }
+
+    #[test]
+    fn test_decompress_unknown_method() {
+        let mut framed = vec![0x7F];
+        framed.extend_from_slice(&4u32.to_be_bytes());
+        framed.extend_from_slice(&[1, 2, 3, 4]);
+        let err = ChunkCompressor::decompress_chunk(&framed).unwrap_err();
+        assert!(matches!(err, CompressionError::UnknownMethod(0x7F)));
+    }
+
+    #[test]
+    fn test_decompress_size_limit() {
+        let mut framed = vec![METHOD_LZ4];
+        framed.extend_from_slice(&(u32::MAX).to_be_bytes());
+        framed.extend_from_slice(&[0u8; 16]);
+        let err = ChunkCompressor::decompress_chunk(&framed).unwrap_err();
+        assert!(matches!(err, CompressionError::SizeLimit(_)));
+    }
+
+    #[test]
+    fn test_decompress_corrupted_payload() {
+        let mut compressor = ChunkCompressor::new(CompressionConfig {
+            algorithm: CompressionAlgorithm::Zstd,
+            ..Default::default()
+        });
+
+        let mut framed = compressor.compress_chunk(&compressible(4096)).unwrap();
+        let last = framed.len() - 1;
+        framed[last] ^= 0xFF;
+        framed[COMPRESSION_HEADER_SIZE] ^= 0xFF;
+        assert!(ChunkCompressor::decompress_chunk(&framed).is_err());
+    }
+}
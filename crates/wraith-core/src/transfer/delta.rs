@@ -0,0 +1,452 @@
+//! Rsync-style delta synchronization for file transfers.
+//!
+//! When the receiver already holds an older version of a file, retransmitting
+//! every chunk wastes bandwidth. Delta sync flow:
+//!
+//! 1. The receiver chunks its existing copy and sends the ordered chunk hash
+//!    list ([`ChunkHashList`]) to the sender.
+//! 2. The sender compares that list against its own chunk hashes (the leaves
+//!    of the transfer's BLAKE3 tree hash) and builds a [`PatchPlan`]: chunks
+//!    the receiver already holds become [`PatchOp::Copy`] operations, the
+//!    rest become [`PatchOp::Fetch`].
+//! 3. The sender transmits the plan plus only the fetched chunks; the
+//!    receiver assembles the new file by copying local chunks and writing
+//!    fetched ones, then verifies against the root hash as usual.
+//!
+//! A delta send plugs into [`TransferSession`](super::TransferSession) via
+//! [`Direction::DeltaSend`](super::Direction): chunks covered by copy
+//! operations are pre-marked as transferred, so progress and missing-chunk
+//! queries reflect only the data that actually crosses the wire.
+
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Delta sync errors
+#[derive(Debug, Error)]
+pub enum DeltaError {
+    /// Serialized data shorter than expected
+    #[error("delta payload truncated: expected at least {expected}, got {actual}")]
+    Truncated {
+        /// Expected minimum size
+        expected: usize,
+        /// Actual size received
+        actual: usize,
+    },
+
+    /// Unknown patch operation tag
+    #[error("invalid patch op tag: 0x{0:02X}")]
+    InvalidOpTag(u8),
+}
+
+/// Ordered chunk hash list of the receiver's existing file
+///
+/// Sent by the receiver to initiate delta sync. The chunk size is included
+/// so the sender can reject lists chunked with a different size.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkHashList {
+    /// Chunk size the hashes were computed with
+    pub chunk_size: u32,
+    /// BLAKE3 hash of each chunk, in file order
+    pub hashes: Vec<[u8; 32]>,
+}
+
+impl ChunkHashList {
+    /// Create a hash list
+    #[must_use]
+    pub fn new(chunk_size: u32, hashes: Vec<[u8; 32]>) -> Self {
+        Self { chunk_size, hashes }
+    }
+
+    /// Serialize to bytes
+    ///
+    /// Format:
+    /// - 4 bytes: chunk_size (big-endian)
+    /// - 8 bytes: hash count (big-endian)
+    /// - 32 bytes per hash
+    #[must_use]
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(12 + self.hashes.len() * 32);
+        buf.extend_from_slice(&self.chunk_size.to_be_bytes());
+        buf.extend_from_slice(&(self.hashes.len() as u64).to_be_bytes());
+        for hash in &self.hashes {
+            buf.extend_from_slice(hash);
+        }
+        buf
+    }
+
+    /// Deserialize from bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DeltaError::Truncated`] if the payload is too short.
+    pub fn deserialize(data: &[u8]) -> Result<Self, DeltaError> {
+        if data.len() < 12 {
+            return Err(DeltaError::Truncated {
+                expected: 12,
+                actual: data.len(),
+            });
+        }
+
+        let chunk_size = u32::from_be_bytes(data[0..4].try_into().expect("4 bytes"));
+        let count = u64::from_be_bytes(data[4..12].try_into().expect("8 bytes")) as usize;
+
+        let expected = 12 + count * 32;
+        if data.len() < expected {
+            return Err(DeltaError::Truncated {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        let mut hashes = Vec::with_capacity(count);
+        for i in 0..count {
+            let start = 12 + i * 32;
+            let mut hash = [0u8; 32];
+            hash.copy_from_slice(&data[start..start + 32]);
+            hashes.push(hash);
+        }
+
+        Ok(Self { chunk_size, hashes })
+    }
+}
+
+/// A single operation in a patch plan
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchOp {
+    /// Receiver copies a chunk it already holds into place
+    Copy {
+        /// Chunk index in the new file
+        target_index: u64,
+        /// Chunk index in the receiver's existing file
+        source_index: u64,
+    },
+    /// Sender transmits the chunk
+    Fetch {
+        /// Chunk index in the new file
+        target_index: u64,
+    },
+}
+
+/// Op tag for `PatchOp::Copy` in serialized plans
+const OP_COPY: u8 = 0x01;
+/// Op tag for `PatchOp::Fetch` in serialized plans
+const OP_FETCH: u8 = 0x02;
+
+/// Patch plan describing how the receiver assembles the new file
+///
+/// Contains one operation per chunk of the new file, in chunk order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatchPlan {
+    /// Per-chunk operations, ordered by target index
+    pub ops: Vec<PatchOp>,
+}
+
+impl PatchPlan {
+    /// Compute a patch plan from the sender's chunk hashes and the
+    /// receiver's hash list
+    ///
+    /// `sender_hashes` are the tree hash leaves of the file being sent.
+    /// Chunks whose hash appears anywhere in the receiver's list become
+    /// copy operations (duplicates resolve to the first occurrence);
+    /// everything else must be fetched.
+    #[must_use]
+    pub fn compute(sender_hashes: &[[u8; 32]], receiver: &ChunkHashList) -> Self {
+        // Hash -> first index in the receiver's existing file
+        let mut held: HashMap<&[u8; 32], u64> = HashMap::with_capacity(receiver.hashes.len());
+        for (index, hash) in receiver.hashes.iter().enumerate() {
+            held.entry(hash).or_insert(index as u64);
+        }
+
+        let ops = sender_hashes
+            .iter()
+            .enumerate()
+            .map(|(target, hash)| match held.get(hash) {
+                Some(&source_index) => PatchOp::Copy {
+                    target_index: target as u64,
+                    source_index,
+                },
+                None => PatchOp::Fetch {
+                    target_index: target as u64,
+                },
+            })
+            .collect();
+
+        Self { ops }
+    }
+
+    /// Total number of chunks in the new file
+    #[must_use]
+    pub fn total_chunks(&self) -> u64 {
+        self.ops.len() as u64
+    }
+
+    /// Number of chunks the receiver copies locally
+    #[must_use]
+    pub fn copy_count(&self) -> u64 {
+        self.ops
+            .iter()
+            .filter(|op| matches!(op, PatchOp::Copy { .. }))
+            .count() as u64
+    }
+
+    /// Number of chunks that must cross the wire
+    #[must_use]
+    pub fn fetch_count(&self) -> u64 {
+        self.ops
+            .iter()
+            .filter(|op| matches!(op, PatchOp::Fetch { .. }))
+            .count() as u64
+    }
+
+    /// Target indices of chunks the sender must transmit, in order
+    #[must_use]
+    pub fn fetch_indices(&self) -> Vec<u64> {
+        self.ops
+            .iter()
+            .filter_map(|op| match op {
+                PatchOp::Fetch { target_index } => Some(*target_index),
+                PatchOp::Copy { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Fraction of chunks satisfied locally (0.0 - 1.0)
+    #[must_use]
+    pub fn savings_ratio(&self) -> f64 {
+        if self.ops.is_empty() {
+            return 0.0;
+        }
+        self.copy_count() as f64 / self.ops.len() as f64
+    }
+
+    /// Serialize to bytes
+    ///
+    /// Format:
+    /// - 8 bytes: op count (big-endian)
+    /// - Per op: 1 byte tag, 8 bytes target index, and for copy ops
+    ///   8 bytes source index
+    #[must_use]
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(8 + self.ops.len() * 17);
+        buf.extend_from_slice(&(self.ops.len() as u64).to_be_bytes());
+
+        for op in &self.ops {
+            match op {
+                PatchOp::Copy {
+                    target_index,
+                    source_index,
+                } => {
+                    buf.push(OP_COPY);
+                    buf.extend_from_slice(&target_index.to_be_bytes());
+                    buf.extend_from_slice(&source_index.to_be_bytes());
+                }
+                PatchOp::Fetch { target_index } => {
+                    buf.push(OP_FETCH);
+                    buf.extend_from_slice(&target_index.to_be_bytes());
+                }
+            }
+        }
+
+        buf
+    }
+
+    /// Deserialize from bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the payload is truncated or contains an
+    /// unknown op tag.
+    pub fn deserialize(data: &[u8]) -> Result<Self, DeltaError> {
+        if data.len() < 8 {
+            return Err(DeltaError::Truncated {
+                expected: 8,
+                actual: data.len(),
+            });
+        }
+
+        let count = u64::from_be_bytes(data[0..8].try_into().expect("8 bytes")) as usize;
+        let mut ops = Vec::with_capacity(count);
+        let mut offset = 8;
+
+        for _ in 0..count {
+            if data.len() < offset + 9 {
+                return Err(DeltaError::Truncated {
+                    expected: offset + 9,
+                    actual: data.len(),
+                });
+            }
+
+            let tag = data[offset];
+            let target_index =
+                u64::from_be_bytes(data[offset + 1..offset + 9].try_into().expect("8 bytes"));
+            offset += 9;
+
+            match tag {
+                OP_COPY => {
+                    if data.len() < offset + 8 {
+                        return Err(DeltaError::Truncated {
+                            expected: offset + 8,
+                            actual: data.len(),
+                        });
+                    }
+                    let source_index =
+                        u64::from_be_bytes(data[offset..offset + 8].try_into().expect("8 bytes"));
+                    offset += 8;
+                    ops.push(PatchOp::Copy {
+                        target_index,
+                        source_index,
+                    });
+                }
+                OP_FETCH => ops.push(PatchOp::Fetch { target_index }),
+                other => return Err(DeltaError::InvalidOpTag(other)),
+            }
+        }
+
+        Ok(Self { ops })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: u8) -> [u8; 32] {
+        [byte; 32]
+    }
+
+    #[test]
+    fn test_hash_list_roundtrip() {
+        let list = ChunkHashList::new(262_144, vec![hash(1), hash(2), hash(3)]);
+        let bytes = list.serialize();
+        let decoded = ChunkHashList::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, list);
+    }
+
+    #[test]
+    fn test_hash_list_empty() {
+        let list = ChunkHashList::new(262_144, Vec::new());
+        let decoded = ChunkHashList::deserialize(&list.serialize()).unwrap();
+        assert!(decoded.hashes.is_empty());
+        assert_eq!(decoded.chunk_size, 262_144);
+    }
+
+    #[test]
+    fn test_hash_list_truncated() {
+        let list = ChunkHashList::new(262_144, vec![hash(1), hash(2)]);
+        let bytes = list.serialize();
+        assert!(matches!(
+            ChunkHashList::deserialize(&bytes[..bytes.len() - 1]),
+            Err(DeltaError::Truncated { .. })
+        ));
+        assert!(matches!(
+            ChunkHashList::deserialize(&[0u8; 5]),
+            Err(DeltaError::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn test_plan_identical_files() {
+        let hashes = vec![hash(1), hash(2), hash(3)];
+        let receiver = ChunkHashList::new(256, hashes.clone());
+
+        let plan = PatchPlan::compute(&hashes, &receiver);
+        assert_eq!(plan.total_chunks(), 3);
+        assert_eq!(plan.copy_count(), 3);
+        assert_eq!(plan.fetch_count(), 0);
+        assert_eq!(plan.savings_ratio(), 1.0);
+        assert!(plan.fetch_indices().is_empty());
+    }
+
+    #[test]
+    fn test_plan_no_overlap() {
+        let receiver = ChunkHashList::new(256, vec![hash(10), hash(11)]);
+        let plan = PatchPlan::compute(&[hash(1), hash(2)], &receiver);
+
+        assert_eq!(plan.copy_count(), 0);
+        assert_eq!(plan.fetch_count(), 2);
+        assert_eq!(plan.savings_ratio(), 0.0);
+        assert_eq!(plan.fetch_indices(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_plan_partial_overlap_with_shift() {
+        // Receiver's file has the same chunks at different positions plus
+        // one chunk the sender replaced
+        let receiver = ChunkHashList::new(256, vec![hash(9), hash(1), hash(2)]);
+        let sender = [hash(1), hash(2), hash(3)];
+
+        let plan = PatchPlan::compute(&sender, &receiver);
+        assert_eq!(
+            plan.ops[0],
+            PatchOp::Copy {
+                target_index: 0,
+                source_index: 1
+            }
+        );
+        assert_eq!(
+            plan.ops[1],
+            PatchOp::Copy {
+                target_index: 1,
+                source_index: 2
+            }
+        );
+        assert_eq!(plan.ops[2], PatchOp::Fetch { target_index: 2 });
+        assert_eq!(plan.fetch_indices(), vec![2]);
+    }
+
+    #[test]
+    fn test_plan_duplicate_chunks_use_first_source() {
+        let receiver = ChunkHashList::new(256, vec![hash(5), hash(5)]);
+        let plan = PatchPlan::compute(&[hash(5), hash(5)], &receiver);
+
+        for op in &plan.ops {
+            assert!(matches!(
+                op,
+                PatchOp::Copy {
+                    source_index: 0,
+                    ..
+                }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_plan_roundtrip() {
+        let receiver = ChunkHashList::new(256, vec![hash(1), hash(3)]);
+        let plan = PatchPlan::compute(&[hash(1), hash(2), hash(3), hash(4)], &receiver);
+
+        let decoded = PatchPlan::deserialize(&plan.serialize()).unwrap();
+        assert_eq!(decoded, plan);
+    }
+
+    #[test]
+    fn test_plan_deserialize_invalid_tag() {
+        let mut bytes = 1u64.to_be_bytes().to_vec();
+        bytes.push(0xFF); // Unknown tag
+        bytes.extend_from_slice(&0u64.to_be_bytes());
+
+        assert!(matches!(
+            PatchPlan::deserialize(&bytes),
+            Err(DeltaError::InvalidOpTag(0xFF))
+        ));
+    }
+
+    #[test]
+    fn test_plan_deserialize_truncated() {
+        let receiver = ChunkHashList::new(256, vec![hash(1)]);
+        let plan = PatchPlan::compute(&[hash(1), hash(2)], &receiver);
+        let bytes = plan.serialize();
+
+        assert!(matches!(
+            PatchPlan::deserialize(&bytes[..bytes.len() - 4]),
+            Err(DeltaError::Truncated { .. })
+        ));
+    }
+
+    #[test]
+    fn test_empty_plan_savings() {
+        let plan = PatchPlan::default();
+        assert_eq!(plan.savings_ratio(), 0.0);
+        assert_eq!(plan.total_chunks(), 0);
+    }
+}
@@ -3,6 +3,14 @@
 //! Provides high-level file transfer session management, progress tracking,
 //! and multi-peer coordination.
 
+pub mod batching;
+pub mod compression;
+pub mod delta;
+pub mod schedule;
 pub mod session;
 
+pub use batching::{BatchConfig, ChunkBatcher};
+pub use compression::{ChunkCompressor, CompressionAlgorithm, CompressionConfig, CompressionError};
+pub use delta::{ChunkHashList, DeltaError, PatchOp, PatchPlan};
+pub use schedule::{TimeWindow, TransferSchedule};
 pub use session::{Direction, TransferSession, TransferState};
@@ -0,0 +1,261 @@
+//! Transfer scheduling: start times, time windows, and bandwidth profiles
+//!
+//! Large backups should not saturate the network during office hours. A
+//! [`TransferSchedule`] constrains when a transfer may run (an absolute
+//! start time, recurring daily [`TimeWindow`]s such as 01:00–06:00) and how
+//! fast it may go when it does (a full-speed off-peak rate inside the
+//! windows, an optional throttled peak rate outside them).
+//!
+//! The types here are timezone-agnostic: callers pass the current minute of
+//! the day in whatever clock the windows were written against (the CLI uses
+//! local time). Schedules serialize with serde so the daemon can persist
+//! them alongside queued transfers.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Minutes in a day
+const MINUTES_PER_DAY: u16 = 24 * 60;
+
+/// A recurring daily time window, in minutes since midnight
+///
+/// The end is exclusive, and windows may wrap past midnight: `22:00-06:00`
+/// covers late evening through early morning. Parsed from the `HH:MM-HH:MM`
+/// form used by the CLI's `--window` flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    /// Window start, minutes since midnight (inclusive)
+    pub start: u16,
+
+    /// Window end, minutes since midnight (exclusive)
+    pub end: u16,
+}
+
+impl TimeWindow {
+    /// Check whether a minute of the day falls inside the window
+    #[must_use]
+    pub fn contains(&self, minute: u16) -> bool {
+        if self.start < self.end {
+            minute >= self.start && minute < self.end
+        } else {
+            // Wraps past midnight
+            minute >= self.start || minute < self.end
+        }
+    }
+}
+
+impl FromStr for TimeWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .split_once('-')
+            .ok_or_else(|| format!("Invalid time window {s:?} (expected HH:MM-HH:MM)"))?;
+        let start = parse_hhmm(start)?;
+        let end = parse_hhmm(end)?;
+        if start == end {
+            return Err(format!("Empty time window {s:?}"));
+        }
+        Ok(Self { start, end })
+    }
+}
+
+impl fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}-{:02}:{:02}",
+            self.start / 60,
+            self.start % 60,
+            self.end / 60,
+            self.end % 60
+        )
+    }
+}
+
+/// Parse `HH:MM` into minutes since midnight
+pub fn parse_hhmm(s: &str) -> Result<u16, String> {
+    let (h, m) = s
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid time {s:?} (expected HH:MM)"))?;
+    let hours: u16 = h.parse().map_err(|_| format!("Invalid hour in {s:?}"))?;
+    let minutes: u16 = m.parse().map_err(|_| format!("Invalid minute in {s:?}"))?;
+    if hours >= 24 || minutes >= 60 {
+        return Err(format!("Time {s:?} out of range"));
+    }
+    Ok(hours * 60 + minutes)
+}
+
+/// When a transfer may run and how fast, evaluated against the wall clock
+///
+/// With no fields set the schedule is unconstrained. Windows are "allowed"
+/// times: a transfer with windows and no peak rate only runs inside them,
+/// while setting [`peak_limit_bps`](Self::peak_limit_bps) lets it run
+/// outside the windows too, throttled to that rate — the off-peak
+/// bandwidth-profile case.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferSchedule {
+    /// Do not start before this time (seconds since the Unix epoch)
+    #[serde(default)]
+    pub start_at: Option<u64>,
+
+    /// Daily windows during which the transfer runs at full (off-peak) speed
+    #[serde(default)]
+    pub windows: Vec<TimeWindow>,
+
+    /// Rate cap inside the windows, bytes/sec (`None` = unlimited)
+    #[serde(default)]
+    pub offpeak_limit_bps: Option<u64>,
+
+    /// Rate cap outside the windows, bytes/sec
+    ///
+    /// `None` means the transfer must not run outside its windows at all.
+    #[serde(default)]
+    pub peak_limit_bps: Option<u64>,
+}
+
+impl TransferSchedule {
+    /// Check whether the schedule imposes no constraints
+    #[must_use]
+    pub fn is_unconstrained(&self) -> bool {
+        self.start_at.is_none() && self.windows.is_empty() && self.offpeak_limit_bps.is_none()
+    }
+
+    /// Check whether the current minute falls inside any window
+    ///
+    /// A schedule without windows is always in-window: every hour is
+    /// off-peak.
+    #[must_use]
+    pub fn in_window(&self, minute_of_day: u16) -> bool {
+        let minute = minute_of_day % MINUTES_PER_DAY;
+        self.windows.is_empty() || self.windows.iter().any(|w| w.contains(minute))
+    }
+
+    /// Check whether the transfer may run right now
+    ///
+    /// `now_secs` is seconds since the Unix epoch; `minute_of_day` is the
+    /// current minute in the clock the windows were written against.
+    #[must_use]
+    pub fn may_run(&self, now_secs: u64, minute_of_day: u16) -> bool {
+        if self.start_at.is_some_and(|start| now_secs < start) {
+            return false;
+        }
+        self.in_window(minute_of_day) || self.peak_limit_bps.is_some()
+    }
+
+    /// The bandwidth cap that applies at the given minute
+    ///
+    /// Returns `None` when the transfer runs unlimited (in-window with no
+    /// off-peak cap). Callers should check [`may_run`](Self::may_run) first;
+    /// out-of-window minutes report the peak cap regardless of whether the
+    /// transfer is allowed to run then.
+    #[must_use]
+    pub fn limit_at(&self, minute_of_day: u16) -> Option<u64> {
+        if self.in_window(minute_of_day) {
+            self.offpeak_limit_bps
+        } else {
+            self.peak_limit_bps.or(self.offpeak_limit_bps)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_window() {
+        let window: TimeWindow = "01:00-06:00".parse().unwrap();
+        assert_eq!(window.start, 60);
+        assert_eq!(window.end, 360);
+        assert_eq!(window.to_string(), "01:00-06:00");
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_windows() {
+        assert!("01:00".parse::<TimeWindow>().is_err());
+        assert!("25:00-06:00".parse::<TimeWindow>().is_err());
+        assert!("01:60-06:00".parse::<TimeWindow>().is_err());
+        assert!("01:00-01:00".parse::<TimeWindow>().is_err());
+        assert!("one-six".parse::<TimeWindow>().is_err());
+    }
+
+    #[test]
+    fn test_window_contains() {
+        let window: TimeWindow = "01:00-06:00".parse().unwrap();
+        assert!(!window.contains(59));
+        assert!(window.contains(60));
+        assert!(window.contains(359));
+        assert!(!window.contains(360));
+    }
+
+    #[test]
+    fn test_window_wraps_past_midnight() {
+        let window: TimeWindow = "22:00-06:00".parse().unwrap();
+        assert!(window.contains(23 * 60));
+        assert!(window.contains(0));
+        assert!(window.contains(359));
+        assert!(!window.contains(360));
+        assert!(!window.contains(12 * 60));
+    }
+
+    #[test]
+    fn test_unconstrained_schedule_always_runs() {
+        let schedule = TransferSchedule::default();
+        assert!(schedule.is_unconstrained());
+        assert!(schedule.may_run(0, 0));
+        assert_eq!(schedule.limit_at(12 * 60), None);
+    }
+
+    #[test]
+    fn test_start_at_defers_the_transfer() {
+        let schedule = TransferSchedule {
+            start_at: Some(1_000),
+            ..Default::default()
+        };
+        assert!(!schedule.may_run(999, 0));
+        assert!(schedule.may_run(1_000, 0));
+    }
+
+    #[test]
+    fn test_windows_without_peak_limit_block_outside() {
+        let schedule = TransferSchedule {
+            windows: vec!["01:00-06:00".parse().unwrap()],
+            ..Default::default()
+        };
+        assert!(schedule.may_run(0, 2 * 60));
+        assert!(!schedule.may_run(0, 12 * 60));
+        assert_eq!(schedule.limit_at(2 * 60), None);
+    }
+
+    #[test]
+    fn test_peak_limit_throttles_instead_of_blocking() {
+        let schedule = TransferSchedule {
+            windows: vec!["01:00-06:00".parse().unwrap()],
+            offpeak_limit_bps: Some(50_000_000),
+            peak_limit_bps: Some(1_000_000),
+            ..Default::default()
+        };
+        // Inside the window: allowed at the off-peak rate
+        assert!(schedule.may_run(0, 2 * 60));
+        assert_eq!(schedule.limit_at(2 * 60), Some(50_000_000));
+
+        // Outside: still allowed, but throttled to the peak rate
+        assert!(schedule.may_run(0, 12 * 60));
+        assert_eq!(schedule.limit_at(12 * 60), Some(1_000_000));
+    }
+
+    #[test]
+    fn test_schedule_serde_roundtrip() {
+        let schedule = TransferSchedule {
+            start_at: Some(1_700_000_000),
+            windows: vec!["22:00-06:00".parse().unwrap()],
+            offpeak_limit_bps: None,
+            peak_limit_bps: Some(500_000),
+        };
+        let json = serde_json::to_string(&schedule).unwrap();
+        let back: TransferSchedule = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, schedule);
+    }
+}
@@ -65,6 +65,9 @@ pub enum Direction {
     Send,
     /// Receiving file
     Receive,
+    /// Sending only the chunks a delta patch plan marks as fetched
+    /// (rsync-style delta sync, see [`crate::transfer::delta`])
+    DeltaSend,
 }
 
 /// Peer identifier (32-byte public key hash)
@@ -210,6 +213,63 @@ impl TransferSession {
         }
     }
 
+    /// Create a delta send transfer session from a patch plan
+    ///
+    /// Chunks covered by copy operations in the plan are pre-marked as
+    /// transferred (the receiver satisfies them locally), so progress,
+    /// `missing_chunks()` and completion reflect only the chunks that
+    /// actually cross the wire.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Unique 32-byte transfer identifier
+    /// * `file_path` - Path to the file being sent
+    /// * `file_size` - Total file size in bytes
+    /// * `chunk_size` - Size of each chunk in bytes
+    /// * `plan` - Patch plan computed from the receiver's chunk hash list
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use wraith_core::transfer::{ChunkHashList, Direction, PatchPlan, TransferSession};
+    /// use std::path::PathBuf;
+    ///
+    /// let receiver_hashes = ChunkHashList::new(256 * 1024, vec![[1u8; 32], [2u8; 32]]);
+    /// let plan = PatchPlan::compute(&[[1u8; 32], [2u8; 32], [3u8; 32]], &receiver_hashes);
+    ///
+    /// let session = TransferSession::new_delta_send(
+    ///     [1u8; 32],
+    ///     PathBuf::from("/path/to/file.dat"),
+    ///     3 * 256 * 1024,
+    ///     256 * 1024,
+    ///     &plan,
+    /// );
+    ///
+    /// assert_eq!(session.direction, Direction::DeltaSend);
+    /// assert_eq!(session.missing_count(), 1); // Only the new chunk is sent
+    /// ```
+    #[must_use]
+    pub fn new_delta_send(
+        id: [u8; 32],
+        file_path: PathBuf,
+        file_size: u64,
+        chunk_size: usize,
+        plan: &crate::transfer::delta::PatchPlan,
+    ) -> Self {
+        let mut session = Self::new_send(id, file_path, file_size, chunk_size);
+        session.direction = Direction::DeltaSend;
+
+        // Chunks the receiver copies locally never cross the wire: count
+        // them as already transferred so only fetched chunks remain missing
+        for op in &plan.ops {
+            if let crate::transfer::delta::PatchOp::Copy { target_index, .. } = op {
+                session.mark_chunk_transferred(*target_index, chunk_size);
+            }
+        }
+
+        session
+    }
+
     /// Create a new receive transfer session
     ///
     /// # Arguments
@@ -740,6 +800,76 @@ mod tests {
         assert_eq!(recv_session.direction, Direction::Receive);
     }
 
+    #[test]
+    fn test_delta_send_only_fetched_chunks_missing() {
+        use crate::transfer::delta::{ChunkHashList, PatchPlan};
+
+        // Receiver holds chunks 0 and 2; chunk 1 and 3 are new
+        let receiver = ChunkHashList::new(256 * 1024, vec![[1u8; 32], [3u8; 32]]);
+        let sender_hashes = [[1u8; 32], [2u8; 32], [3u8; 32], [4u8; 32]];
+        let plan = PatchPlan::compute(&sender_hashes, &receiver);
+
+        let session = TransferSession::new_delta_send(
+            [10u8; 32],
+            PathBuf::from("/tmp/delta.dat"),
+            4 * 256 * 1024,
+            256 * 1024,
+            &plan,
+        );
+
+        assert_eq!(session.direction, Direction::DeltaSend);
+        assert_eq!(session.missing_count(), 2);
+        assert!(session.is_chunk_missing(1));
+        assert!(session.is_chunk_missing(3));
+        assert!(!session.is_chunk_missing(0));
+        assert!(!session.is_chunk_missing(2));
+    }
+
+    #[test]
+    fn test_delta_send_all_copies_completes() {
+        use crate::transfer::delta::{ChunkHashList, PatchPlan};
+
+        let hashes = vec![[1u8; 32], [2u8; 32]];
+        let receiver = ChunkHashList::new(256 * 1024, hashes.clone());
+        let plan = PatchPlan::compute(&hashes, &receiver);
+
+        let session = TransferSession::new_delta_send(
+            [11u8; 32],
+            PathBuf::from("/tmp/identical.dat"),
+            2 * 256 * 1024,
+            256 * 1024,
+            &plan,
+        );
+
+        // Nothing to send: the session is already complete
+        assert!(session.is_complete());
+        assert_eq!(session.missing_count(), 0);
+    }
+
+    #[test]
+    fn test_delta_send_completes_after_fetched_chunks() {
+        use crate::transfer::delta::{ChunkHashList, PatchPlan};
+
+        let receiver = ChunkHashList::new(256 * 1024, vec![[1u8; 32]]);
+        let plan = PatchPlan::compute(&[[1u8; 32], [2u8; 32]], &receiver);
+
+        let mut session = TransferSession::new_delta_send(
+            [12u8; 32],
+            PathBuf::from("/tmp/partial.dat"),
+            2 * 256 * 1024,
+            256 * 1024,
+            &plan,
+        );
+
+        session.start();
+        assert!(!session.is_complete());
+
+        for index in plan.fetch_indices() {
+            session.mark_chunk_transferred(index, 256 * 1024);
+        }
+        assert!(session.is_complete());
+    }
+
     #[test]
     fn test_assigned_chunks() {
         let mut session = TransferSession::new_receive(
@@ -198,9 +198,10 @@ impl SessionCrypto {
 
     /// Decrypt a message with explicit counter and key commitment verification.
     ///
-    /// Does NOT automatically increment the counter.
     /// Checks replay protection - packets with duplicate or old sequence numbers are rejected.
     /// Verifies key commitment in AAD to prevent key-commitment attacks.
+    /// On success, advances the receive counter high-water mark so rekey
+    /// thresholds account for received traffic.
     ///
     /// # Errors
     ///
@@ -225,7 +226,12 @@ impl SessionCrypto {
         committed_aad.extend_from_slice(&commitment);
         committed_aad.extend_from_slice(aad);
 
-        self.recv_key.decrypt(&nonce, ciphertext, &committed_aad)
+        let plaintext = self.recv_key.decrypt(&nonce, ciphertext, &committed_aad)?;
+
+        // Advance the high-water mark so needs_rekey() sees received volume
+        self.recv_counter = self.recv_counter.max(counter + 1);
+
+        Ok(plaintext)
     }
 
     /// Get the current send counter.
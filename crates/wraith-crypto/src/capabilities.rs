@@ -0,0 +1,96 @@
+//! CPU cryptographic capability detection
+//!
+//! Reports which hardware features the crypto primitives can take
+//! advantage of at runtime, and which optional algorithm families this
+//! build supports. Detection is cheap and has no side effects.
+
+/// Snapshot of CPU and build-time crypto capabilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CryptoCapabilities {
+    /// AES-NI instructions available (x86_64 only)
+    pub aes_ni: bool,
+    /// AVX2 SIMD available, accelerates BLAKE3 (x86_64 only)
+    pub avx2: bool,
+    /// SSE4.1 SIMD available (x86_64 only)
+    pub sse41: bool,
+    /// NEON SIMD available (aarch64)
+    pub neon: bool,
+    /// Post-quantum KEM compiled in (reserved; no PQ suite is shipped yet)
+    pub pq_kem: bool,
+}
+
+impl CryptoCapabilities {
+    /// Detect CPU crypto capabilities at runtime
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wraith_crypto::capabilities::CryptoCapabilities;
+    ///
+    /// let caps = CryptoCapabilities::detect();
+    /// // The protocol never requires hardware AES, so this is informational
+    /// let _ = caps.aes_ni;
+    /// ```
+    #[must_use]
+    pub fn detect() -> Self {
+        Self {
+            aes_ni: detect_x86_feature("aes"),
+            avx2: detect_x86_feature("avx2"),
+            sse41: detect_x86_feature("sse4.1"),
+            neon: cfg!(target_arch = "aarch64"),
+            pq_kem: false,
+        }
+    }
+
+    /// Whether any SIMD acceleration is available for hashing
+    #[must_use]
+    pub fn has_simd(&self) -> bool {
+        self.avx2 || self.sse41 || self.neon
+    }
+}
+
+/// Runtime x86_64 feature probe; always false on other architectures
+fn detect_x86_feature(feature: &str) -> bool {
+    #[cfg(target_arch = "x86_64")]
+    {
+        match feature {
+            "aes" => std::arch::is_x86_feature_detected!("aes"),
+            "avx2" => std::arch::is_x86_feature_detected!("avx2"),
+            "sse4.1" => std::arch::is_x86_feature_detected!("sse4.1"),
+            _ => false,
+        }
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        let _ = feature;
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_does_not_panic() {
+        let caps = CryptoCapabilities::detect();
+        // No shipped PQ suite: the flag is pinned false until one lands
+        assert!(!caps.pq_kem);
+    }
+
+    #[test]
+    fn test_detect_is_stable() {
+        assert_eq!(CryptoCapabilities::detect(), CryptoCapabilities::detect());
+    }
+
+    #[test]
+    fn test_unknown_x86_feature_is_false() {
+        assert!(!detect_x86_feature("nonexistent-feature"));
+    }
+
+    #[cfg(target_arch = "aarch64")]
+    #[test]
+    fn test_aarch64_reports_neon() {
+        assert!(CryptoCapabilities::detect().neon);
+    }
+}
@@ -0,0 +1,234 @@
+//! Signed identity delegations for ephemeral per-transfer keys.
+//!
+//! A node that wants unlinkable transfers performs each handshake with a
+//! fresh ephemeral X25519 key, so recipients and observers cannot correlate
+//! transfers by the presented static key. The recipient still needs to know
+//! who it is really talking to, so the sender delivers a delegation inside
+//! the already-encrypted channel: a statement that "this ephemeral key acts
+//! for this long-term identity until this time", signed by the long-term
+//! Ed25519 key.
+//!
+//! Because the delegation only ever travels inside the Noise channel, the
+//! linkage between ephemeral and long-term identity is visible to the
+//! recipient alone — an observer sees only unrelated ephemeral keys.
+//! Delegations are time-bounded so a leaked ephemeral secret cannot
+//! impersonate the long-term identity indefinitely.
+
+use crate::CryptoError;
+use crate::signatures::{Signature, SigningKey, VerifyingKey};
+
+/// Domain separation context for delegation signatures
+const DELEGATION_CONTEXT: &[u8] = b"wraith-delegation-v1";
+
+/// Serialized size: long-term key + ephemeral key + two timestamps + signature
+pub const DELEGATION_SIZE: usize = 32 + 32 + 8 + 8 + 64;
+
+/// A time-bounded statement binding an ephemeral X25519 key to a long-term
+/// Ed25519 identity
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IdentityDelegation {
+    /// The delegating long-term Ed25519 public key (the node ID)
+    pub long_term_key: [u8; 32],
+    /// The ephemeral X25519 key acting on the identity's behalf
+    pub ephemeral_key: [u8; 32],
+    /// Issuance time (seconds since the Unix epoch)
+    pub issued_at: u64,
+    /// Expiry time (seconds since the Unix epoch)
+    pub expires_at: u64,
+    /// Ed25519 signature by the long-term key over the fields above
+    signature: Signature,
+}
+
+impl IdentityDelegation {
+    /// Issue a delegation for `ephemeral_key`, valid for `validity_secs`
+    /// starting now
+    #[must_use]
+    pub fn issue(signing_key: &SigningKey, ephemeral_key: [u8; 32], validity_secs: u64) -> Self {
+        let issued_at = unix_now();
+        let expires_at = issued_at.saturating_add(validity_secs);
+        let long_term_key = signing_key.verifying_key().to_bytes();
+
+        let message = signing_message(&long_term_key, &ephemeral_key, issued_at, expires_at);
+        let signature = signing_key.sign(&message);
+
+        Self {
+            long_term_key,
+            ephemeral_key,
+            issued_at,
+            expires_at,
+            signature,
+        }
+    }
+
+    /// Verify the signature and validity window at time `now`
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CryptoError::InvalidSignature`] if the signature does not
+    /// verify under the claimed long-term key, or if the delegation is not
+    /// yet valid or has expired.
+    pub fn verify(&self, now: u64) -> Result<(), CryptoError> {
+        if now < self.issued_at || now > self.expires_at {
+            return Err(CryptoError::InvalidSignature);
+        }
+
+        let verifying_key = VerifyingKey::from_bytes(&self.long_term_key)?;
+        let message = signing_message(
+            &self.long_term_key,
+            &self.ephemeral_key,
+            self.issued_at,
+            self.expires_at,
+        );
+        verifying_key.verify(&message, &self.signature)
+    }
+
+    /// Verify against the current system time
+    ///
+    /// # Errors
+    ///
+    /// See [`IdentityDelegation::verify`].
+    pub fn verify_now(&self) -> Result<(), CryptoError> {
+        self.verify(unix_now())
+    }
+
+    /// Serialize to [`DELEGATION_SIZE`] bytes
+    #[must_use]
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(DELEGATION_SIZE);
+        out.extend_from_slice(&self.long_term_key);
+        out.extend_from_slice(&self.ephemeral_key);
+        out.extend_from_slice(&self.issued_at.to_be_bytes());
+        out.extend_from_slice(&self.expires_at.to_be_bytes());
+        out.extend_from_slice(self.signature.as_bytes());
+        out
+    }
+
+    /// Deserialize from [`DELEGATION_SIZE`] bytes
+    ///
+    /// The signature is not checked here; call
+    /// [`IdentityDelegation::verify`] afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CryptoError::InvalidKeyLength`] if the buffer is not
+    /// exactly [`DELEGATION_SIZE`] bytes.
+    pub fn deserialize(data: &[u8]) -> Result<Self, CryptoError> {
+        if data.len() != DELEGATION_SIZE {
+            return Err(CryptoError::InvalidKeyLength {
+                expected: DELEGATION_SIZE,
+                actual: data.len(),
+            });
+        }
+
+        let mut long_term_key = [0u8; 32];
+        long_term_key.copy_from_slice(&data[..32]);
+        let mut ephemeral_key = [0u8; 32];
+        ephemeral_key.copy_from_slice(&data[32..64]);
+        let issued_at = u64::from_be_bytes(data[64..72].try_into().expect("sliced to 8"));
+        let expires_at = u64::from_be_bytes(data[72..80].try_into().expect("sliced to 8"));
+        let signature = Signature::from_slice(&data[80..])?;
+
+        Ok(Self {
+            long_term_key,
+            ephemeral_key,
+            issued_at,
+            expires_at,
+            signature,
+        })
+    }
+}
+
+/// The byte string the long-term key signs
+fn signing_message(
+    long_term_key: &[u8; 32],
+    ephemeral_key: &[u8; 32],
+    issued_at: u64,
+    expires_at: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(DELEGATION_CONTEXT.len() + 80);
+    message.extend_from_slice(DELEGATION_CONTEXT);
+    message.extend_from_slice(long_term_key);
+    message.extend_from_slice(ephemeral_key);
+    message.extend_from_slice(&issued_at.to_be_bytes());
+    message.extend_from_slice(&expires_at.to_be_bytes());
+    message
+}
+
+/// Current time in seconds since the Unix epoch
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_delegation_issue_and_verify() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let delegation = IdentityDelegation::issue(&signing_key, [7u8; 32], 600);
+
+        assert_eq!(
+            delegation.long_term_key,
+            signing_key.verifying_key().to_bytes()
+        );
+        assert_eq!(delegation.ephemeral_key, [7u8; 32]);
+        assert!(delegation.verify_now().is_ok());
+    }
+
+    #[test]
+    fn test_delegation_expires() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let delegation = IdentityDelegation::issue(&signing_key, [7u8; 32], 600);
+
+        assert!(delegation.verify(delegation.expires_at + 1).is_err());
+        assert!(
+            delegation
+                .verify(delegation.issued_at.saturating_sub(1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_delegation_rejects_tampering() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let mut delegation = IdentityDelegation::issue(&signing_key, [7u8; 32], 600);
+
+        delegation.ephemeral_key[0] ^= 1;
+        assert!(delegation.verify_now().is_err());
+    }
+
+    #[test]
+    fn test_delegation_rejects_wrong_signer() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let other_key = SigningKey::generate(&mut OsRng);
+        let mut delegation = IdentityDelegation::issue(&signing_key, [7u8; 32], 600);
+
+        // Claim the delegation came from a different long-term identity
+        delegation.long_term_key = other_key.verifying_key().to_bytes();
+        assert!(delegation.verify_now().is_err());
+    }
+
+    #[test]
+    fn test_delegation_serialization_roundtrip() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let delegation = IdentityDelegation::issue(&signing_key, [7u8; 32], 600);
+
+        let bytes = delegation.serialize();
+        assert_eq!(bytes.len(), DELEGATION_SIZE);
+
+        let decoded = IdentityDelegation::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, delegation);
+        assert!(decoded.verify_now().is_ok());
+    }
+
+    #[test]
+    fn test_delegation_rejects_bad_length() {
+        assert!(IdentityDelegation::deserialize(&[0u8; 10]).is_err());
+        assert!(IdentityDelegation::deserialize(&[0u8; DELEGATION_SIZE + 1]).is_err());
+    }
+}
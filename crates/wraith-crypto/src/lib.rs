@@ -27,14 +27,19 @@
 #![deny(unsafe_op_in_unsafe_fn)]
 
 pub mod aead;
+pub mod capabilities;
 pub mod constant_time;
+pub mod delegation;
 pub mod elligator;
 pub mod encrypted_keys;
 pub mod error;
 pub mod hash;
+pub mod manifest;
 pub mod noise;
+pub mod pake;
 pub mod random;
 pub mod ratchet;
+pub mod sas;
 pub mod signatures;
 pub mod x25519;
 
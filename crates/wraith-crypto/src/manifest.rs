@@ -0,0 +1,269 @@
+//! Signed transfer manifests for file provenance.
+//!
+//! A sender holding a long-term Ed25519 identity signs a manifest of every
+//! offered transfer — file name, size, chunking geometry and BLAKE3 content
+//! root — so the receiver can check who published a file before accepting
+//! it, and keep the signature next to the file for provenance checks long
+//! after the transfer (`wraith verify`). Because the signature covers the
+//! content root, any later modification of the file invalidates the
+//! manifest without the signer's involvement.
+//!
+//! The manifest is self-certifying: the signer's public key travels with
+//! it, and [`TransferManifest::verify`] checks the signature under that
+//! key. Whether the signer is an identity the receiver trusts is a policy
+//! question left to the caller.
+
+use crate::CryptoError;
+use crate::signatures::{Signature, SigningKey, VerifyingKey};
+
+/// Domain separation context for manifest signatures
+const MANIFEST_CONTEXT: &[u8] = b"wraith-manifest-v1";
+
+/// Serialized size excluding the file name: name length byte, file size,
+/// chunk size, chunk count, root hash, signer key and signature
+pub const MANIFEST_OVERHEAD: usize = 1 + 8 + 4 + 8 + 32 + 32 + 64;
+
+/// A sender-signed statement of a transfer's content and geometry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferManifest {
+    /// File name as offered (UTF-8, max 255 bytes)
+    pub file_name: String,
+    /// File size in bytes
+    pub file_size: u64,
+    /// Chunk size the content root was computed with
+    pub chunk_size: u32,
+    /// Total number of chunks
+    pub chunk_count: u64,
+    /// BLAKE3 tree root over the file's chunks
+    pub root_hash: [u8; 32],
+    /// The signing Ed25519 public key (the sender's node ID)
+    pub signer: [u8; 32],
+    /// Ed25519 signature by `signer` over the fields above
+    signature: Signature,
+}
+
+impl TransferManifest {
+    /// Sign a manifest for the given transfer parameters
+    ///
+    /// `file_name` must not exceed 255 bytes; callers are expected to have
+    /// validated it when building the transfer metadata.
+    #[must_use]
+    pub fn sign(
+        signing_key: &SigningKey,
+        file_name: &str,
+        file_size: u64,
+        chunk_size: u32,
+        chunk_count: u64,
+        root_hash: [u8; 32],
+    ) -> Self {
+        let signer = signing_key.verifying_key().to_bytes();
+        let message = signing_message(
+            &signer,
+            file_name,
+            file_size,
+            chunk_size,
+            chunk_count,
+            &root_hash,
+        );
+        let signature = signing_key.sign(&message);
+
+        Self {
+            file_name: file_name.to_string(),
+            file_size,
+            chunk_size,
+            chunk_count,
+            root_hash,
+            signer,
+            signature,
+        }
+    }
+
+    /// Verify the signature under the claimed signer key
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CryptoError::InvalidSignature`] if the signature does not
+    /// verify, or [`CryptoError::InvalidPublicKey`] if the signer bytes are
+    /// not a valid Ed25519 public key.
+    pub fn verify(&self) -> Result<(), CryptoError> {
+        let verifying_key = VerifyingKey::from_bytes(&self.signer)?;
+        let message = signing_message(
+            &self.signer,
+            &self.file_name,
+            self.file_size,
+            self.chunk_size,
+            self.chunk_count,
+            &self.root_hash,
+        );
+        verifying_key.verify(&message, &self.signature)
+    }
+
+    /// Serialize to [`MANIFEST_OVERHEAD`] + file name bytes
+    #[must_use]
+    pub fn serialize(&self) -> Vec<u8> {
+        let name_bytes = self.file_name.as_bytes();
+        let mut out = Vec::with_capacity(MANIFEST_OVERHEAD + name_bytes.len());
+        out.push(name_bytes.len() as u8);
+        out.extend_from_slice(name_bytes);
+        out.extend_from_slice(&self.file_size.to_be_bytes());
+        out.extend_from_slice(&self.chunk_size.to_be_bytes());
+        out.extend_from_slice(&self.chunk_count.to_be_bytes());
+        out.extend_from_slice(&self.root_hash);
+        out.extend_from_slice(&self.signer);
+        out.extend_from_slice(self.signature.as_bytes());
+        out
+    }
+
+    /// Deserialize from the exact serialized length
+    ///
+    /// The signature is not checked here; call
+    /// [`TransferManifest::verify`] afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CryptoError::InvalidKeyLength`] if the buffer length does
+    /// not match the embedded file name length, or
+    /// [`CryptoError::InvalidSignature`] if the name is not valid UTF-8.
+    pub fn deserialize(data: &[u8]) -> Result<Self, CryptoError> {
+        let name_len = *data.first().ok_or(CryptoError::InvalidKeyLength {
+            expected: MANIFEST_OVERHEAD,
+            actual: 0,
+        })? as usize;
+
+        let expected = MANIFEST_OVERHEAD + name_len;
+        if data.len() != expected {
+            return Err(CryptoError::InvalidKeyLength {
+                expected,
+                actual: data.len(),
+            });
+        }
+
+        let mut offset = 1;
+        let file_name = std::str::from_utf8(&data[offset..offset + name_len])
+            .map_err(|_| CryptoError::InvalidSignature)?
+            .to_string();
+        offset += name_len;
+
+        let file_size = u64::from_be_bytes(data[offset..offset + 8].try_into().expect("8 bytes"));
+        offset += 8;
+        let chunk_size = u32::from_be_bytes(data[offset..offset + 4].try_into().expect("4 bytes"));
+        offset += 4;
+        let chunk_count = u64::from_be_bytes(data[offset..offset + 8].try_into().expect("8 bytes"));
+        offset += 8;
+
+        let mut root_hash = [0u8; 32];
+        root_hash.copy_from_slice(&data[offset..offset + 32]);
+        offset += 32;
+        let mut signer = [0u8; 32];
+        signer.copy_from_slice(&data[offset..offset + 32]);
+        offset += 32;
+        let signature = Signature::from_slice(&data[offset..])?;
+
+        Ok(Self {
+            file_name,
+            file_size,
+            chunk_size,
+            chunk_count,
+            root_hash,
+            signer,
+            signature,
+        })
+    }
+}
+
+/// The byte string the signer signs
+fn signing_message(
+    signer: &[u8; 32],
+    file_name: &str,
+    file_size: u64,
+    chunk_size: u32,
+    chunk_count: u64,
+    root_hash: &[u8; 32],
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(MANIFEST_CONTEXT.len() + 84 + file_name.len());
+    message.extend_from_slice(MANIFEST_CONTEXT);
+    message.extend_from_slice(signer);
+    message.extend_from_slice(root_hash);
+    message.extend_from_slice(&file_size.to_be_bytes());
+    message.extend_from_slice(&chunk_size.to_be_bytes());
+    message.extend_from_slice(&chunk_count.to_be_bytes());
+    message.extend_from_slice(file_name.as_bytes());
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    fn sample_manifest(signing_key: &SigningKey) -> TransferManifest {
+        TransferManifest::sign(
+            signing_key,
+            "report.pdf",
+            1 << 20,
+            256 * 1024,
+            4,
+            [0xAB; 32],
+        )
+    }
+
+    #[test]
+    fn test_manifest_sign_and_verify() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let manifest = sample_manifest(&signing_key);
+
+        assert_eq!(manifest.signer, signing_key.verifying_key().to_bytes());
+        assert!(manifest.verify().is_ok());
+    }
+
+    #[test]
+    fn test_manifest_rejects_tampered_root() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let mut manifest = sample_manifest(&signing_key);
+
+        manifest.root_hash[0] ^= 1;
+        assert!(manifest.verify().is_err());
+    }
+
+    #[test]
+    fn test_manifest_rejects_renamed_file() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let mut manifest = sample_manifest(&signing_key);
+
+        manifest.file_name = "renamed.pdf".to_string();
+        assert!(manifest.verify().is_err());
+    }
+
+    #[test]
+    fn test_manifest_rejects_wrong_signer() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let other_key = SigningKey::generate(&mut OsRng);
+        let mut manifest = sample_manifest(&signing_key);
+
+        // Claim the manifest came from a different identity
+        manifest.signer = other_key.verifying_key().to_bytes();
+        assert!(manifest.verify().is_err());
+    }
+
+    #[test]
+    fn test_manifest_serialization_roundtrip() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let manifest = sample_manifest(&signing_key);
+
+        let bytes = manifest.serialize();
+        assert_eq!(bytes.len(), MANIFEST_OVERHEAD + "report.pdf".len());
+
+        let decoded = TransferManifest::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, manifest);
+        assert!(decoded.verify().is_ok());
+    }
+
+    #[test]
+    fn test_manifest_rejects_bad_length() {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        let bytes = sample_manifest(&signing_key).serialize();
+
+        assert!(TransferManifest::deserialize(&bytes[..bytes.len() - 1]).is_err());
+        assert!(TransferManifest::deserialize(&[]).is_err());
+    }
+}
@@ -0,0 +1,443 @@
+//! Password-authenticated key exchange (SPAKE2) and one-time transfer codes
+//!
+//! Implements the SPAKE2 protocol over the Ristretto group so two parties
+//! who share only a short human-readable code (`7-hungry-wombat`) can derive
+//! a strong session key without pre-exchanging public keys. The code is
+//! low-entropy by design: SPAKE2 limits an active attacker to exactly one
+//! online guess per protocol run, and the code is used once and discarded,
+//! so a wrong guess burns the transfer rather than leaking anything.
+//!
+//! The flow mirrors magic-wormhole pairing:
+//! 1. The sender generates a [`TransferCode`] and reads it to the receiver
+//!    out of band (voice, chat, sticky note).
+//! 2. Both sides run [`Spake2`] with their role and the code, exchange one
+//!    32-byte message each, and derive the same key.
+//! 3. Both sides exchange [`Spake2Key::confirmation`] tags; a mismatch means
+//!    a wrong code or an active attacker, and the transfer is aborted.
+
+use crate::error::CryptoError;
+use crate::hash::Kdf;
+use curve25519_elligator2::constants::RISTRETTO_BASEPOINT_TABLE;
+use curve25519_elligator2::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_elligator2::scalar::Scalar;
+use curve25519_elligator2::traits::Identity;
+use rand_core::{OsRng, RngCore};
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+/// Domain separation for the password-derived scalar
+const CONTEXT_W: &str = "wraith-pake-v1 w";
+
+/// Domain separation for the sender blinding point M
+const CONTEXT_M: &str = "wraith-pake-v1 M";
+
+/// Domain separation for the receiver blinding point N
+const CONTEXT_N: &str = "wraith-pake-v1 N";
+
+/// Domain separation for the final key derivation
+const CONTEXT_KEY: &str = "wraith-pake-v1 key";
+
+/// Domain separation for the sender's confirmation tag
+const CONTEXT_CONFIRM_SENDER: &str = "wraith-pake-v1 confirm sender";
+
+/// Domain separation for the receiver's confirmation tag
+const CONTEXT_CONFIRM_RECEIVER: &str = "wraith-pake-v1 confirm receiver";
+
+/// Adjectives for transfer codes (64 short, unambiguous words)
+const ADJECTIVES: [&str; 64] = [
+    "angry", "brave", "calm", "clever", "crispy", "curly", "dusty", "eager", "fancy", "fierce",
+    "fluffy", "foggy", "frosty", "fuzzy", "gentle", "giant", "glossy", "golden", "greedy", "happy",
+    "hazy", "humble", "hungry", "jolly", "lazy", "little", "lively", "lonely", "loud", "lucky",
+    "mellow", "mighty", "misty", "noble", "nimble", "odd", "plucky", "polite", "proud", "quick",
+    "quiet", "rapid", "rusty", "salty", "sandy", "shiny", "silent", "sleepy", "sly", "smoky",
+    "snowy", "spicy", "stormy", "sturdy", "sunny", "swift", "tidy", "tiny", "vivid", "wild",
+    "windy", "witty", "young", "zesty",
+];
+
+/// Nouns for transfer codes (64 short, unambiguous words)
+const NOUNS: [&str; 64] = [
+    "anchor", "badger", "bamboo", "beacon", "bishop", "bobcat", "candle", "canyon", "cobra",
+    "comet", "condor", "copper", "coral", "cricket", "dragon", "eagle", "ember", "falcon",
+    "ferret", "fiddle", "galaxy", "gecko", "glacier", "hammer", "harbor", "hedgehog", "heron",
+    "iguana", "jaguar", "kayak", "kitten", "lantern", "lemur", "lizard", "magnet", "mammoth",
+    "marble", "meadow", "meteor", "nebula", "otter", "panda", "pebble", "pelican", "penguin",
+    "pepper", "pirate", "planet", "puffin", "python", "rabbit", "raven", "robin", "rocket",
+    "salmon", "sparrow", "thunder", "tiger", "trumpet", "tulip", "turtle", "walrus", "willow",
+    "wombat",
+];
+
+/// Which side of the exchange this party plays
+///
+/// SPAKE2 is asymmetric: the two sides blind their ephemeral keys with
+/// different fixed group elements, so each party must know its role and the
+/// roles must differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PakeRole {
+    /// The party that generated the code (file sender)
+    Sender,
+    /// The party that typed the code (file receiver)
+    Receiver,
+}
+
+/// A one-time human-readable transfer code (`7-hungry-wombat`)
+///
+/// Carries about 20 bits of entropy: enough because SPAKE2 allows an active
+/// attacker exactly one guess, after which the transfer is aborted and the
+/// code is dead.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransferCode {
+    code: String,
+}
+
+impl TransferCode {
+    /// Generate a fresh random code
+    #[must_use]
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 3];
+        OsRng.fill_bytes(&mut bytes);
+        let number = bytes[0];
+        let adjective = ADJECTIVES[(bytes[1] & 0x3F) as usize];
+        let noun = NOUNS[(bytes[2] & 0x3F) as usize];
+        Self {
+            code: format!("{number}-{adjective}-{noun}"),
+        }
+    }
+
+    /// Parse a code typed by the receiver
+    ///
+    /// Accepts `number-word-word` with any words (not just the built-in
+    /// lists, so codes survive wordlist changes); whitespace is trimmed and
+    /// the code is lowercased so minor transcription slips don't break the
+    /// exchange.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CryptoError::InvalidMessage`] if the code is not three
+    /// nonempty dash-separated parts starting with a number.
+    pub fn parse(input: &str) -> Result<Self, CryptoError> {
+        let code = input.trim().to_lowercase();
+        let parts: Vec<&str> = code.split('-').collect();
+        if parts.len() != 3 || parts.iter().any(|p| p.is_empty()) {
+            return Err(CryptoError::InvalidMessage(
+                "transfer code must look like 7-hungry-wombat".to_string(),
+            ));
+        }
+        if parts[0].parse::<u32>().is_err() {
+            return Err(CryptoError::InvalidMessage(
+                "transfer code must start with a number".to_string(),
+            ));
+        }
+        Ok(Self { code })
+    }
+
+    /// The code as typed/displayed (`7-hungry-wombat`)
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.code
+    }
+
+    /// The low-entropy password fed into the PAKE
+    #[must_use]
+    pub fn password(&self) -> &[u8] {
+        self.code.as_bytes()
+    }
+}
+
+impl std::fmt::Display for TransferCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.code)
+    }
+}
+
+impl std::str::FromStr for TransferCode {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// An in-progress SPAKE2 exchange
+///
+/// Created with [`Spake2::start`], which also yields the 32-byte message to
+/// send to the peer; consumed by [`Spake2::finish`] with the peer's message.
+pub struct Spake2 {
+    role: PakeRole,
+    ephemeral: Scalar,
+    w: Scalar,
+    own_message: [u8; 32],
+}
+
+impl Spake2 {
+    /// Start an exchange, returning the state and the message to send
+    ///
+    /// Both sides call this with the same code but opposite roles.
+    #[must_use]
+    pub fn start(role: PakeRole, code: &TransferCode) -> (Self, [u8; 32]) {
+        let w = password_scalar(code);
+
+        let mut seed = [0u8; 64];
+        OsRng.fill_bytes(&mut seed);
+        let ephemeral = Scalar::from_bytes_mod_order_wide(&seed);
+        seed.zeroize();
+
+        // X = x*G + w*M (sender) or Y = y*G + w*N (receiver)
+        let blind = match role {
+            PakeRole::Sender => point_m(),
+            PakeRole::Receiver => point_n(),
+        };
+        let public = RISTRETTO_BASEPOINT_TABLE * &ephemeral + blind * w;
+        let own_message = public.compress().to_bytes();
+
+        (
+            Self {
+                role,
+                ephemeral,
+                w,
+                own_message,
+            },
+            own_message,
+        )
+    }
+
+    /// Complete the exchange with the peer's message
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CryptoError::InvalidMessage`] if the peer's message is not
+    /// a valid group element or degenerates to the identity (an active
+    /// attack or a broken peer, never an honest wrong code — that surfaces
+    /// as a confirmation tag mismatch instead).
+    pub fn finish(self, peer_message: &[u8; 32]) -> Result<Spake2Key, CryptoError> {
+        let peer_public = CompressedRistretto(*peer_message)
+            .decompress()
+            .ok_or_else(|| {
+                CryptoError::InvalidMessage("PAKE message is not a valid group element".to_string())
+            })?;
+
+        // Strip the peer's password blinding, then apply our ephemeral
+        let peer_blind = match self.role {
+            PakeRole::Sender => point_n(),
+            PakeRole::Receiver => point_m(),
+        };
+        let shared = (peer_public - peer_blind * self.w) * self.ephemeral;
+        if shared == RistrettoPoint::identity() {
+            return Err(CryptoError::InvalidMessage(
+                "PAKE shared point degenerated to the identity".to_string(),
+            ));
+        }
+
+        // Transcript binds both messages in role order, the shared point,
+        // and the password scalar
+        let (sender_msg, receiver_msg) = match self.role {
+            PakeRole::Sender => (self.own_message, *peer_message),
+            PakeRole::Receiver => (*peer_message, self.own_message),
+        };
+        let mut transcript = Vec::with_capacity(128);
+        transcript.extend_from_slice(&sender_msg);
+        transcript.extend_from_slice(&receiver_msg);
+        transcript.extend_from_slice(shared.compress().as_bytes());
+        transcript.extend_from_slice(self.w.as_bytes());
+
+        let key = Kdf::new(CONTEXT_KEY).derive_key(&transcript);
+        transcript.zeroize();
+
+        Ok(Spake2Key { key })
+    }
+}
+
+/// The shared key produced by a completed SPAKE2 exchange
+pub struct Spake2Key {
+    key: [u8; 32],
+}
+
+impl Spake2Key {
+    /// The derived 32-byte session key
+    #[must_use]
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// Confirmation tag proving this side derived the key
+    ///
+    /// Each role sends its own tag and checks the peer's with
+    /// [`Spake2Key::verify_confirmation`]; the tags are role-separated so a
+    /// reflected tag never verifies.
+    #[must_use]
+    pub fn confirmation(&self, role: PakeRole) -> [u8; 32] {
+        let context = match role {
+            PakeRole::Sender => CONTEXT_CONFIRM_SENDER,
+            PakeRole::Receiver => CONTEXT_CONFIRM_RECEIVER,
+        };
+        Kdf::new(context).derive_key(&self.key)
+    }
+
+    /// Check the peer's confirmation tag in constant time
+    ///
+    /// `peer_role` is the *peer's* role, i.e. the opposite of our own. A
+    /// `false` result means the codes did not match (typo or guess) and the
+    /// transfer must be aborted.
+    #[must_use]
+    pub fn verify_confirmation(&self, peer_role: PakeRole, tag: &[u8; 32]) -> bool {
+        self.confirmation(peer_role).ct_eq(tag).into()
+    }
+}
+
+impl Drop for Spake2Key {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+/// Derive the password scalar w from a transfer code
+fn password_scalar(code: &TransferCode) -> Scalar {
+    let mut wide = [0u8; 64];
+    Kdf::new(CONTEXT_W).derive(code.password(), &mut wide);
+    let scalar = Scalar::from_bytes_mod_order_wide(&wide);
+    wide.zeroize();
+    scalar
+}
+
+/// The fixed sender blinding element M (nothing-up-my-sleeve derivation)
+fn point_m() -> RistrettoPoint {
+    derive_point(CONTEXT_M)
+}
+
+/// The fixed receiver blinding element N (nothing-up-my-sleeve derivation)
+fn point_n() -> RistrettoPoint {
+    derive_point(CONTEXT_N)
+}
+
+/// Hash a context string to a group element of unknown discrete log
+fn derive_point(context: &'static str) -> RistrettoPoint {
+    let mut uniform = [0u8; 64];
+    Kdf::new(context).derive(b"WRAITH", &mut uniform);
+    RistrettoPoint::from_uniform_bytes(&uniform)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_code_format() {
+        let code = TransferCode::generate();
+        let parts: Vec<&str> = code.as_str().split('-').collect();
+        assert_eq!(parts.len(), 3);
+        assert!(parts[0].parse::<u32>().is_ok());
+        assert!(ADJECTIVES.contains(&parts[1]));
+        assert!(NOUNS.contains(&parts[2]));
+    }
+
+    #[test]
+    fn test_transfer_code_parse_roundtrip() {
+        let code = TransferCode::generate();
+        let parsed = TransferCode::parse(code.as_str()).unwrap();
+        assert_eq!(code, parsed);
+    }
+
+    #[test]
+    fn test_transfer_code_parse_normalizes() {
+        let parsed = TransferCode::parse("  7-Hungry-WOMBAT ").unwrap();
+        assert_eq!(parsed.as_str(), "7-hungry-wombat");
+    }
+
+    #[test]
+    fn test_transfer_code_parse_rejects_garbage() {
+        assert!(TransferCode::parse("").is_err());
+        assert!(TransferCode::parse("hungry-wombat").is_err());
+        assert!(TransferCode::parse("seven-hungry-wombat").is_err());
+        assert!(TransferCode::parse("7--wombat").is_err());
+    }
+
+    #[test]
+    fn test_wordlists_have_no_duplicates() {
+        let mut seen = std::collections::HashSet::new();
+        for word in ADJECTIVES.iter().chain(NOUNS.iter()) {
+            assert!(seen.insert(*word), "duplicate word: {word}");
+        }
+    }
+
+    #[test]
+    fn test_spake2_same_code_same_key() {
+        let code = TransferCode::parse("7-hungry-wombat").unwrap();
+
+        let (sender, msg_a) = Spake2::start(PakeRole::Sender, &code);
+        let (receiver, msg_b) = Spake2::start(PakeRole::Receiver, &code);
+
+        let key_a = sender.finish(&msg_b).unwrap();
+        let key_b = receiver.finish(&msg_a).unwrap();
+        assert_eq!(key_a.as_bytes(), key_b.as_bytes());
+    }
+
+    #[test]
+    fn test_spake2_wrong_code_different_key() {
+        let code = TransferCode::parse("7-hungry-wombat").unwrap();
+        let wrong = TransferCode::parse("7-hungry-walrus").unwrap();
+
+        let (sender, msg_a) = Spake2::start(PakeRole::Sender, &code);
+        let (receiver, msg_b) = Spake2::start(PakeRole::Receiver, &wrong);
+
+        let key_a = sender.finish(&msg_b).unwrap();
+        let key_b = receiver.finish(&msg_a).unwrap();
+        assert_ne!(key_a.as_bytes(), key_b.as_bytes());
+    }
+
+    #[test]
+    fn test_spake2_confirmation_roundtrip() {
+        let code = TransferCode::parse("42-salty-python").unwrap();
+
+        let (sender, msg_a) = Spake2::start(PakeRole::Sender, &code);
+        let (receiver, msg_b) = Spake2::start(PakeRole::Receiver, &code);
+        let key_a = sender.finish(&msg_b).unwrap();
+        let key_b = receiver.finish(&msg_a).unwrap();
+
+        let tag_a = key_a.confirmation(PakeRole::Sender);
+        let tag_b = key_b.confirmation(PakeRole::Receiver);
+        assert!(key_b.verify_confirmation(PakeRole::Sender, &tag_a));
+        assert!(key_a.verify_confirmation(PakeRole::Receiver, &tag_b));
+    }
+
+    #[test]
+    fn test_spake2_confirmation_rejects_wrong_code() {
+        let code = TransferCode::parse("42-salty-python").unwrap();
+        let wrong = TransferCode::parse("42-salty-rabbit").unwrap();
+
+        let (sender, msg_a) = Spake2::start(PakeRole::Sender, &code);
+        let (receiver, msg_b) = Spake2::start(PakeRole::Receiver, &wrong);
+        let key_a = sender.finish(&msg_b).unwrap();
+        let key_b = receiver.finish(&msg_a).unwrap();
+
+        let tag_a = key_a.confirmation(PakeRole::Sender);
+        assert!(!key_b.verify_confirmation(PakeRole::Sender, &tag_a));
+    }
+
+    #[test]
+    fn test_spake2_reflected_tag_never_verifies() {
+        let code = TransferCode::parse("3-misty-otter").unwrap();
+
+        let (sender, msg_a) = Spake2::start(PakeRole::Sender, &code);
+        let (receiver, msg_b) = Spake2::start(PakeRole::Receiver, &code);
+        let key_a = sender.finish(&msg_b).unwrap();
+        let _key_b = receiver.finish(&msg_a).unwrap();
+
+        // Reflecting our own tag back must not pass as the peer's
+        let tag_a = key_a.confirmation(PakeRole::Sender);
+        assert!(!key_a.verify_confirmation(PakeRole::Receiver, &tag_a));
+    }
+
+    #[test]
+    fn test_spake2_rejects_invalid_message() {
+        let code = TransferCode::parse("7-hungry-wombat").unwrap();
+        let (sender, _msg) = Spake2::start(PakeRole::Sender, &code);
+        assert!(sender.finish(&[0xFF; 32]).is_err());
+    }
+
+    #[test]
+    fn test_spake2_messages_are_fresh() {
+        let code = TransferCode::parse("7-hungry-wombat").unwrap();
+        let (_s1, msg1) = Spake2::start(PakeRole::Sender, &code);
+        let (_s2, msg2) = Spake2::start(PakeRole::Sender, &code);
+        assert_ne!(msg1, msg2);
+    }
+}
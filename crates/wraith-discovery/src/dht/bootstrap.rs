@@ -7,6 +7,7 @@
 use super::node_id::NodeId;
 use super::routing::DhtPeer;
 use std::net::SocketAddr;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Bootstrap node configuration
@@ -75,8 +76,21 @@ impl BootstrapNode {
 pub struct BootstrapConfig {
     /// List of known bootstrap nodes
     nodes: Vec<BootstrapNode>,
+    /// How often locally originated values are republished
+    republish_interval: Duration,
+    /// How often expired stored values are pruned
+    expiration_interval: Duration,
+    /// Idle time after which a k-bucket is refreshed with a random lookup
+    bucket_refresh_interval: Duration,
 }
 
+/// Default republish interval (Kademlia: republish every hour)
+const DEFAULT_REPUBLISH_INTERVAL: Duration = Duration::from_secs(3600);
+/// Default expiration sweep interval
+const DEFAULT_EXPIRATION_INTERVAL: Duration = Duration::from_secs(60);
+/// Default bucket refresh threshold (Kademlia: refresh after an idle hour)
+const DEFAULT_BUCKET_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
 impl BootstrapConfig {
     /// Create a new empty bootstrap configuration
     ///
@@ -90,7 +104,12 @@ impl BootstrapConfig {
     /// ```
     #[must_use]
     pub fn new() -> Self {
-        Self { nodes: Vec::new() }
+        Self {
+            nodes: Vec::new(),
+            republish_interval: DEFAULT_REPUBLISH_INTERVAL,
+            expiration_interval: DEFAULT_EXPIRATION_INTERVAL,
+            bucket_refresh_interval: DEFAULT_BUCKET_REFRESH_INTERVAL,
+        }
     }
 
     /// Create bootstrap configuration with default nodes
@@ -149,6 +168,61 @@ impl BootstrapConfig {
         self.nodes.push(node);
     }
 
+    /// Resolve a `host:port` endpoint and add one node per address
+    ///
+    /// Resolution returns both A and AAAA records, so a dual-stack
+    /// bootstrap host yields an IPv4 and an IPv6 entry under the same
+    /// node ID and the joining node can reach it over either family.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The node's identifier (same for every resolved address)
+    /// * `endpoint` - `host:port` string, e.g. `"bootstrap.wraith.network:8000"`
+    /// * `name` - Optional human-readable name
+    ///
+    /// # Returns
+    ///
+    /// Number of nodes added
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BootstrapError::Resolution`] if the endpoint does not
+    /// resolve to any address.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wraith_discovery::dht::{BootstrapConfig, NodeId};
+    ///
+    /// let mut config = BootstrapConfig::new();
+    /// let added = config.add_endpoint(NodeId::random(), "localhost:8000", None).unwrap();
+    /// assert!(added >= 1);
+    /// ```
+    pub fn add_endpoint(
+        &mut self,
+        id: NodeId,
+        endpoint: &str,
+        name: Option<String>,
+    ) -> Result<usize, BootstrapError> {
+        use std::net::ToSocketAddrs;
+
+        let addrs: Vec<SocketAddr> = endpoint
+            .to_socket_addrs()
+            .map_err(|e| BootstrapError::Resolution(format!("{endpoint}: {e}")))?
+            .collect();
+        if addrs.is_empty() {
+            return Err(BootstrapError::Resolution(format!(
+                "{endpoint}: no addresses"
+            )));
+        }
+
+        let count = addrs.len();
+        for addr in addrs {
+            self.add_node(BootstrapNode::new(id, addr, name.clone()));
+        }
+        Ok(count)
+    }
+
     /// Remove a bootstrap node by address
     ///
     /// # Arguments
@@ -229,6 +303,39 @@ impl BootstrapConfig {
     pub fn is_empty(&self) -> bool {
         self.nodes.is_empty()
     }
+
+    /// How often locally originated values are republished
+    #[must_use]
+    pub const fn republish_interval(&self) -> Duration {
+        self.republish_interval
+    }
+
+    /// Set the republish interval for locally originated values
+    pub fn set_republish_interval(&mut self, interval: Duration) {
+        self.republish_interval = interval;
+    }
+
+    /// How often expired stored values are pruned
+    #[must_use]
+    pub const fn expiration_interval(&self) -> Duration {
+        self.expiration_interval
+    }
+
+    /// Set the expiration sweep interval
+    pub fn set_expiration_interval(&mut self, interval: Duration) {
+        self.expiration_interval = interval;
+    }
+
+    /// Idle time after which a k-bucket is refreshed with a random lookup
+    #[must_use]
+    pub const fn bucket_refresh_interval(&self) -> Duration {
+        self.bucket_refresh_interval
+    }
+
+    /// Set the bucket refresh threshold
+    pub fn set_bucket_refresh_interval(&mut self, interval: Duration) {
+        self.bucket_refresh_interval = interval;
+    }
 }
 
 impl Default for BootstrapConfig {
@@ -334,6 +441,10 @@ pub enum BootstrapError {
     /// Network error
     #[error("Network error: {0}")]
     Network(String),
+
+    /// Endpoint did not resolve to any address
+    #[error("Failed to resolve bootstrap endpoint: {0}")]
+    Resolution(String),
 }
 
 #[cfg(test)]
@@ -407,6 +518,33 @@ mod tests {
         assert!(!removed);
     }
 
+    #[test]
+    fn test_bootstrap_config_add_endpoint() {
+        let mut config = BootstrapConfig::new();
+
+        let added = config
+            .add_endpoint(
+                NodeId::random(),
+                "localhost:8000",
+                Some("local".to_string()),
+            )
+            .unwrap();
+
+        // localhost resolves to 127.0.0.1 and, on dual-stack hosts, ::1
+        assert!(added >= 1);
+        assert_eq!(config.node_count(), added);
+        assert!(config.nodes().iter().all(|n| n.addr.port() == 8000));
+    }
+
+    #[test]
+    fn test_bootstrap_config_add_endpoint_unresolvable() {
+        let mut config = BootstrapConfig::new();
+
+        let result = config.add_endpoint(NodeId::random(), "not a valid endpoint", None);
+        assert!(matches!(result, Err(BootstrapError::Resolution(_))));
+        assert!(config.is_empty());
+    }
+
     #[test]
     fn test_bootstrap_config_as_peers() {
         let mut config = BootstrapConfig::new();
@@ -471,4 +609,22 @@ mod tests {
         let config = BootstrapConfig::default();
         assert_eq!(config.node_count(), 0);
     }
+
+    #[test]
+    fn test_bootstrap_config_maintenance_intervals() {
+        let mut config = BootstrapConfig::new();
+
+        // Kademlia defaults
+        assert_eq!(config.republish_interval(), Duration::from_secs(3600));
+        assert_eq!(config.expiration_interval(), Duration::from_secs(60));
+        assert_eq!(config.bucket_refresh_interval(), Duration::from_secs(3600));
+
+        config.set_republish_interval(Duration::from_secs(120));
+        config.set_expiration_interval(Duration::from_secs(10));
+        config.set_bucket_refresh_interval(Duration::from_secs(300));
+
+        assert_eq!(config.republish_interval(), Duration::from_secs(120));
+        assert_eq!(config.expiration_interval(), Duration::from_secs(10));
+        assert_eq!(config.bucket_refresh_interval(), Duration::from_secs(300));
+    }
 }
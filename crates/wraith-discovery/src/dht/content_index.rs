@@ -0,0 +1,553 @@
+//! Content announcement index for private groups
+//!
+//! Lets group members publish signed (name → content hash, size, description)
+//! records to the DHT under group-derived keys, and search shared content by
+//! human-readable name. Records are stored in two places:
+//!
+//! - An exact-name key derived from the group secret and the content name,
+//!   for direct lookups
+//! - A per-group index key holding the full announcement list, enabling
+//!   client-side substring search
+//!
+//! All records carry an Ed25519 signature from the publisher; consumers
+//! verify signatures client-side before trusting a record, so a malicious
+//! DHT node cannot forge announcements (it can only withhold them).
+//! Key derivation uses BLAKE3 keyed hashing with the group secret, so
+//! observers without the secret cannot correlate announcements to names
+//! or enumerate a group's content.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wraith_crypto::signatures::{Signature, SigningKey, VerifyingKey};
+
+use super::{DhtNode, GroupSecret};
+
+/// Domain separator for content index key derivation
+const CONTENT_INDEX_DOMAIN: &[u8] = b"wraith-dht-content-index";
+
+/// Domain separator for per-name content key derivation
+const CONTENT_NAME_DOMAIN: &[u8] = b"wraith-dht-content-name";
+
+/// Domain separator for announcement signatures
+const CONTENT_SIGNING_DOMAIN: &[u8] = b"wraith-content-announcement-v1";
+
+/// Maximum length of a content name in bytes
+pub const MAX_CONTENT_NAME_LEN: usize = 255;
+
+/// Maximum length of a content description in bytes
+pub const MAX_CONTENT_DESCRIPTION_LEN: usize = 1024;
+
+/// Content index errors
+#[derive(Debug, Error)]
+pub enum ContentIndexError {
+    /// Serialization error
+    #[error("Serialization failed: {0}")]
+    Serialization(bincode::Error),
+
+    /// Publisher key is not a valid Ed25519 public key
+    #[error("Invalid publisher key")]
+    InvalidPublisher,
+
+    /// Signature does not authenticate the announcement
+    #[error("Signature verification failed")]
+    InvalidSignature,
+
+    /// Announcement fails validation (e.g. oversized name)
+    #[error("Invalid announcement: {0}")]
+    InvalidAnnouncement(String),
+}
+
+/// Derive the group's content index key
+///
+/// All of a group's announcements are indexed under this key. Uses BLAKE3
+/// keyed hashing so observers without the group secret cannot find or
+/// enumerate the index.
+#[must_use]
+pub fn derive_content_index_key(group_secret: &GroupSecret) -> [u8; 32] {
+    *blake3::keyed_hash(group_secret.as_bytes(), CONTENT_INDEX_DOMAIN).as_bytes()
+}
+
+/// Derive the exact-name lookup key for a content name within a group
+///
+/// Different groups (and different names) map to unlinkable keys.
+#[must_use]
+pub fn derive_content_name_key(group_secret: &GroupSecret, name: &str) -> [u8; 32] {
+    let mut message = CONTENT_NAME_DOMAIN.to_vec();
+    message.extend_from_slice(name.as_bytes());
+    *blake3::keyed_hash(group_secret.as_bytes(), &message).as_bytes()
+}
+
+/// A content announcement: name → content hash mapping with metadata
+///
+/// The `publisher` field is filled in when the announcement is signed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentAnnouncement {
+    /// Human-readable content name (search key)
+    pub name: String,
+    /// BLAKE3 hash of the content
+    pub content_hash: [u8; 32],
+    /// Content size in bytes
+    pub size: u64,
+    /// Free-form description
+    pub description: String,
+    /// Publisher's Ed25519 public key
+    pub publisher: [u8; 32],
+    /// Publication time (seconds since Unix epoch)
+    pub published_at: u64,
+}
+
+impl ContentAnnouncement {
+    /// Create a new unsigned announcement
+    ///
+    /// The publisher key and signature are attached by [`Self::sign`].
+    #[must_use]
+    pub fn new(name: String, content_hash: [u8; 32], size: u64, description: String) -> Self {
+        let published_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Self {
+            name,
+            content_hash,
+            size,
+            description,
+            publisher: [0u8; 32],
+            published_at,
+        }
+    }
+
+    /// Sign the announcement, producing a publishable record
+    ///
+    /// Sets the publisher field from the signing key before signing, so the
+    /// signature covers the publisher identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContentIndexError::InvalidAnnouncement`] if the name is
+    /// empty or the name/description exceed their size limits.
+    pub fn sign(
+        mut self,
+        key: &SigningKey,
+    ) -> Result<SignedContentAnnouncement, ContentIndexError> {
+        if self.name.is_empty() {
+            return Err(ContentIndexError::InvalidAnnouncement(
+                "name is empty".to_string(),
+            ));
+        }
+        if self.name.len() > MAX_CONTENT_NAME_LEN {
+            return Err(ContentIndexError::InvalidAnnouncement(format!(
+                "name exceeds {MAX_CONTENT_NAME_LEN} bytes"
+            )));
+        }
+        if self.description.len() > MAX_CONTENT_DESCRIPTION_LEN {
+            return Err(ContentIndexError::InvalidAnnouncement(format!(
+                "description exceeds {MAX_CONTENT_DESCRIPTION_LEN} bytes"
+            )));
+        }
+
+        self.publisher = key.verifying_key().to_bytes();
+        let signature = key.sign(&self.signing_bytes()?);
+
+        Ok(SignedContentAnnouncement {
+            announcement: self,
+            signature: signature.as_bytes().to_vec(),
+        })
+    }
+
+    /// Canonical byte encoding covered by the signature
+    fn signing_bytes(&self) -> Result<Vec<u8>, ContentIndexError> {
+        let mut bytes = CONTENT_SIGNING_DOMAIN.to_vec();
+        bytes.extend(bincode::serialize(self).map_err(ContentIndexError::Serialization)?);
+        Ok(bytes)
+    }
+}
+
+/// A signed content announcement ready for DHT publication
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedContentAnnouncement {
+    /// The announcement
+    pub announcement: ContentAnnouncement,
+    /// Ed25519 signature over the announcement (64 bytes)
+    pub signature: Vec<u8>,
+}
+
+impl SignedContentAnnouncement {
+    /// Verify the publisher's signature
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContentIndexError::InvalidPublisher`] if the publisher key
+    /// is malformed, or [`ContentIndexError::InvalidSignature`] if the
+    /// signature does not authenticate the announcement.
+    pub fn verify(&self) -> Result<(), ContentIndexError> {
+        let verifying_key = VerifyingKey::from_bytes(&self.announcement.publisher)
+            .map_err(|_| ContentIndexError::InvalidPublisher)?;
+        let signature = Signature::from_slice(&self.signature)
+            .map_err(|_| ContentIndexError::InvalidSignature)?;
+
+        verifying_key
+            .verify(&self.announcement.signing_bytes()?, &signature)
+            .map_err(|_| ContentIndexError::InvalidSignature)
+    }
+
+    /// Serialize to bytes for DHT storage
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContentIndexError::Serialization`] on encoding failure.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ContentIndexError> {
+        bincode::serialize(self).map_err(ContentIndexError::Serialization)
+    }
+
+    /// Deserialize from DHT storage bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContentIndexError::Serialization`] on decoding failure.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ContentIndexError> {
+        bincode::deserialize(bytes).map_err(ContentIndexError::Serialization)
+    }
+}
+
+/// A group's announcement list, stored under the group index key
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentIndex {
+    /// Signed announcements, at most one per (name, publisher) pair
+    entries: Vec<SignedContentAnnouncement>,
+}
+
+impl ContentIndex {
+    /// Insert or replace an announcement
+    ///
+    /// An existing entry with the same name and publisher is replaced, so
+    /// republishing updated metadata does not grow the index.
+    pub fn upsert(&mut self, record: SignedContentAnnouncement) {
+        self.entries.retain(|existing| {
+            existing.announcement.name != record.announcement.name
+                || existing.announcement.publisher != record.announcement.publisher
+        });
+        self.entries.push(record);
+    }
+
+    /// Search announcements by name (case-insensitive substring match)
+    ///
+    /// Only returns entries whose publisher signature verifies; forged or
+    /// corrupted records are silently skipped.
+    #[must_use]
+    pub fn search(&self, query: &str) -> Vec<&SignedContentAnnouncement> {
+        let query = query.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| entry.announcement.name.to_lowercase().contains(&query))
+            .filter(|entry| entry.verify().is_ok())
+            .collect()
+    }
+
+    /// Number of entries (including unverified ones)
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the index is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Serialize to bytes for DHT storage
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContentIndexError::Serialization`] on encoding failure.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ContentIndexError> {
+        bincode::serialize(self).map_err(ContentIndexError::Serialization)
+    }
+
+    /// Deserialize from DHT storage bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ContentIndexError::Serialization`] on decoding failure.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ContentIndexError> {
+        bincode::deserialize(bytes).map_err(ContentIndexError::Serialization)
+    }
+}
+
+impl DhtNode {
+    /// Publish a signed content announcement under group-derived keys
+    ///
+    /// Stores the record under its exact-name key and upserts it into the
+    /// group's content index. The record's signature is verified before
+    /// publishing so a node never propagates forged announcements.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record fails verification or serialization.
+    pub fn publish_content(
+        &mut self,
+        group_secret: &GroupSecret,
+        record: SignedContentAnnouncement,
+        ttl: Duration,
+    ) -> Result<(), ContentIndexError> {
+        record.verify()?;
+
+        let name_key = derive_content_name_key(group_secret, &record.announcement.name);
+        self.store(name_key, record.to_bytes()?, ttl);
+
+        let index_key = derive_content_index_key(group_secret);
+        let mut index = match self.get(&index_key) {
+            Some(bytes) => ContentIndex::from_bytes(&bytes).unwrap_or_default(),
+            None => ContentIndex::default(),
+        };
+        index.upsert(record);
+        self.store(index_key, index.to_bytes()?, ttl);
+
+        Ok(())
+    }
+
+    /// Look up a content announcement by exact name
+    ///
+    /// Returns `None` if no record exists, the record fails signature
+    /// verification, or its name does not match (hash collision guard).
+    #[must_use]
+    pub fn lookup_content(
+        &self,
+        group_secret: &GroupSecret,
+        name: &str,
+    ) -> Option<SignedContentAnnouncement> {
+        let key = derive_content_name_key(group_secret, name);
+        let record = SignedContentAnnouncement::from_bytes(&self.get(&key)?).ok()?;
+        record.verify().ok()?;
+        (record.announcement.name == name).then_some(record)
+    }
+
+    /// Search the group's content index by name substring
+    ///
+    /// Matching is case-insensitive; only records with valid publisher
+    /// signatures are returned.
+    #[must_use]
+    pub fn search_content(
+        &self,
+        group_secret: &GroupSecret,
+        query: &str,
+    ) -> Vec<SignedContentAnnouncement> {
+        let index_key = derive_content_index_key(group_secret);
+        let Some(bytes) = self.get(&index_key) else {
+            return Vec::new();
+        };
+        let Ok(index) = ContentIndex::from_bytes(&bytes) else {
+            return Vec::new();
+        };
+        index.search(query).into_iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dht::NodeId;
+    use rand::rngs::OsRng;
+
+    fn signed_record(name: &str, key: &SigningKey) -> SignedContentAnnouncement {
+        ContentAnnouncement::new(
+            name.to_string(),
+            [7u8; 32],
+            4096,
+            "test content".to_string(),
+        )
+        .sign(key)
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sign_verify_roundtrip() {
+        let key = SigningKey::generate(&mut OsRng);
+        let record = signed_record("report.pdf", &key);
+
+        assert!(record.verify().is_ok());
+        assert_eq!(
+            record.announcement.publisher,
+            key.verifying_key().to_bytes()
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_record() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut record = signed_record("report.pdf", &key);
+
+        record.announcement.size += 1;
+        assert!(matches!(
+            record.verify(),
+            Err(ContentIndexError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_swapped_publisher() {
+        let key = SigningKey::generate(&mut OsRng);
+        let other = SigningKey::generate(&mut OsRng);
+        let mut record = signed_record("report.pdf", &key);
+
+        record.announcement.publisher = other.verifying_key().to_bytes();
+        assert!(record.verify().is_err());
+    }
+
+    #[test]
+    fn test_sign_rejects_invalid_announcements() {
+        let key = SigningKey::generate(&mut OsRng);
+
+        let empty = ContentAnnouncement::new(String::new(), [0u8; 32], 0, String::new());
+        assert!(matches!(
+            empty.sign(&key),
+            Err(ContentIndexError::InvalidAnnouncement(_))
+        ));
+
+        let long_name = ContentAnnouncement::new(
+            "x".repeat(MAX_CONTENT_NAME_LEN + 1),
+            [0u8; 32],
+            0,
+            String::new(),
+        );
+        assert!(long_name.sign(&key).is_err());
+
+        let long_description = ContentAnnouncement::new(
+            "ok".to_string(),
+            [0u8; 32],
+            0,
+            "x".repeat(MAX_CONTENT_DESCRIPTION_LEN + 1),
+        );
+        assert!(long_description.sign(&key).is_err());
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let key = SigningKey::generate(&mut OsRng);
+        let record = signed_record("report.pdf", &key);
+
+        let bytes = record.to_bytes().unwrap();
+        let decoded = SignedContentAnnouncement::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, record);
+        assert!(decoded.verify().is_ok());
+    }
+
+    #[test]
+    fn test_key_derivation_group_separation() {
+        let secret1 = GroupSecret::new([1u8; 32]);
+        let secret2 = GroupSecret::new([2u8; 32]);
+
+        assert_ne!(
+            derive_content_index_key(&secret1),
+            derive_content_index_key(&secret2)
+        );
+        assert_ne!(
+            derive_content_name_key(&secret1, "a"),
+            derive_content_name_key(&secret2, "a")
+        );
+        assert_ne!(
+            derive_content_name_key(&secret1, "a"),
+            derive_content_name_key(&secret1, "b")
+        );
+        // Index and name keys never collide for the same secret
+        assert_ne!(
+            derive_content_index_key(&secret1),
+            derive_content_name_key(&secret1, "")
+        );
+    }
+
+    #[test]
+    fn test_index_upsert_replaces_same_name_and_publisher() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut index = ContentIndex::default();
+
+        index.upsert(signed_record("report.pdf", &key));
+        index.upsert(signed_record("report.pdf", &key));
+        assert_eq!(index.len(), 1);
+
+        // Same name from a different publisher coexists
+        let other = SigningKey::generate(&mut OsRng);
+        index.upsert(signed_record("report.pdf", &other));
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn test_index_search_substring_case_insensitive() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut index = ContentIndex::default();
+        index.upsert(signed_record("Quarterly-Report.pdf", &key));
+        index.upsert(signed_record("holiday-photos.zip", &key));
+
+        assert_eq!(index.search("report").len(), 1);
+        assert_eq!(index.search("REPORT").len(), 1);
+        assert_eq!(index.search(".pdf").len(), 1);
+        assert_eq!(index.search("nothing").len(), 0);
+        // Empty query matches everything
+        assert_eq!(index.search("").len(), 2);
+    }
+
+    #[test]
+    fn test_index_search_skips_forged_entries() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut index = ContentIndex::default();
+
+        let mut forged = signed_record("report.pdf", &key);
+        forged.announcement.content_hash = [99u8; 32];
+        index.upsert(forged);
+
+        assert_eq!(index.len(), 1);
+        assert!(index.search("report").is_empty());
+    }
+
+    #[test]
+    fn test_dht_publish_and_search() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        let secret = GroupSecret::new([42u8; 32]);
+        let key = SigningKey::generate(&mut OsRng);
+
+        node.publish_content(
+            &secret,
+            signed_record("quarterly-report.pdf", &key),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+        node.publish_content(
+            &secret,
+            signed_record("photos.zip", &key),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+
+        let results = node.search_content(&secret, "report");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].announcement.name, "quarterly-report.pdf");
+
+        // Exact-name lookup
+        let found = node.lookup_content(&secret, "photos.zip").unwrap();
+        assert_eq!(found.announcement.name, "photos.zip");
+        assert!(node.lookup_content(&secret, "missing.txt").is_none());
+
+        // Wrong group secret sees nothing
+        let other_secret = GroupSecret::new([43u8; 32]);
+        assert!(node.search_content(&other_secret, "report").is_empty());
+    }
+
+    #[test]
+    fn test_dht_publish_rejects_forged_record() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        let secret = GroupSecret::new([42u8; 32]);
+        let key = SigningKey::generate(&mut OsRng);
+
+        let mut forged = signed_record("report.pdf", &key);
+        forged.announcement.size = 1;
+
+        assert!(
+            node.publish_content(&secret, forged, Duration::from_secs(60))
+                .is_err()
+        );
+        assert!(node.search_content(&secret, "report").is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+//! DHT Background Maintenance
+//!
+//! Periodic upkeep required by the Kademlia paper:
+//!
+//! - **Republish**: locally originated values are re-announced to the
+//!   closest nodes before their TTL elapses, so content survives churn.
+//! - **Expiration**: values stored on behalf of other nodes are pruned
+//!   once their TTL runs out.
+//! - **Bucket refresh**: k-buckets that no lookup has touched recently are
+//!   refreshed with a lookup for a random ID in their range.
+//!
+//! Intervals are configured through [`BootstrapConfig`](super::BootstrapConfig).
+
+use super::bootstrap::BootstrapConfig;
+use super::node::DhtNode;
+use super::node_id::NodeId;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{debug, trace};
+
+/// Background maintenance driver for a DHT node
+///
+/// Owns a handle to the shared [`DhtNode`] and runs the three Kademlia
+/// upkeep passes on their configured intervals when spawned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wraith_discovery::dht::{BootstrapConfig, DhtMaintenance, DhtNode, NodeId};
+/// use std::sync::Arc;
+/// use tokio::sync::RwLock;
+///
+/// # async fn example() {
+/// let dht = Arc::new(RwLock::new(DhtNode::new(
+///     NodeId::random(),
+///     "127.0.0.1:8000".parse().unwrap(),
+/// )));
+/// let maintenance = DhtMaintenance::new(dht, BootstrapConfig::new());
+/// let handle = maintenance.spawn();
+/// // ... later, on shutdown:
+/// handle.abort();
+/// # }
+/// ```
+pub struct DhtMaintenance {
+    /// Shared DHT node being maintained
+    dht: Arc<RwLock<DhtNode>>,
+    /// Configuration providing the maintenance intervals
+    config: BootstrapConfig,
+}
+
+impl DhtMaintenance {
+    /// Create a new maintenance driver
+    ///
+    /// # Arguments
+    ///
+    /// * `dht` - Shared DHT node to maintain
+    /// * `config` - Bootstrap configuration carrying the intervals
+    #[must_use]
+    pub fn new(dht: Arc<RwLock<DhtNode>>, config: BootstrapConfig) -> Self {
+        Self { dht, config }
+    }
+
+    /// Spawn the maintenance loop as a background tokio task
+    ///
+    /// The task runs until aborted via the returned handle.
+    #[must_use]
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut expire_tick = tokio::time::interval(self.config.expiration_interval());
+            let mut republish_tick = tokio::time::interval(self.config.republish_interval());
+            let mut refresh_tick = tokio::time::interval(self.config.expiration_interval());
+
+            // The first tick of a tokio interval fires immediately
+            expire_tick.tick().await;
+            republish_tick.tick().await;
+            refresh_tick.tick().await;
+
+            loop {
+                tokio::select! {
+                    _ = expire_tick.tick() => {
+                        Self::run_expiration(&self.dht).await;
+                    }
+                    _ = republish_tick.tick() => {
+                        Self::run_republish(&self.dht, self.config.republish_interval()).await;
+                    }
+                    _ = refresh_tick.tick() => {
+                        Self::run_bucket_refresh(&self.dht, self.config.bucket_refresh_interval())
+                            .await;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Prune expired values and dead routing table entries
+    ///
+    /// Returns the number of values removed.
+    pub async fn run_expiration(dht: &Arc<RwLock<DhtNode>>) -> usize {
+        let mut node = dht.write().await;
+        let (peers_removed, values_removed) = node.prune_all();
+        if peers_removed > 0 || values_removed > 0 {
+            debug!(peers_removed, values_removed, "DHT maintenance: pruned");
+        }
+        values_removed
+    }
+
+    /// Republish locally originated values approaching expiry
+    ///
+    /// A value is due when its remaining TTL has dropped below the
+    /// republish interval, meaning it would expire before the next pass.
+    /// Returns the number of values republished.
+    pub async fn run_republish(dht: &Arc<RwLock<DhtNode>>, interval: Duration) -> usize {
+        let due = {
+            let node = dht.read().await;
+            node.values_needing_republish(interval)
+        };
+
+        let mut republished = 0;
+        for key in due {
+            let mut node = dht.write().await;
+            // Locate the current closest nodes for the key; the store
+            // requests ride on the same lookup path
+            let _closest = node.iterative_find_node(&NodeId::from_bytes(key)).await;
+            if node.mark_republished(&key) {
+                republished += 1;
+                trace!(key = ?&key[..4], "DHT maintenance: republished value");
+            }
+        }
+        republished
+    }
+
+    /// Refresh k-buckets that have gone stale
+    ///
+    /// Performs a lookup for a random ID in each stale bucket's range.
+    /// Returns the number of buckets refreshed.
+    pub async fn run_bucket_refresh(dht: &Arc<RwLock<DhtNode>>, threshold: Duration) -> usize {
+        let stale = {
+            let node = dht.read().await;
+            node.routing_table().stale_buckets(threshold)
+        };
+
+        for &index in &stale {
+            let mut node = dht.write().await;
+            let target = node.routing_table().random_id_in_bucket(index);
+            let _found = node.iterative_find_node(&target).await;
+            node.routing_table_mut().mark_bucket_refreshed(index);
+            trace!(bucket = index, "DHT maintenance: refreshed bucket");
+        }
+        stale.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dht::routing::DhtPeer;
+
+    fn test_dht() -> Arc<RwLock<DhtNode>> {
+        Arc::new(RwLock::new(DhtNode::new(
+            NodeId::random(),
+            "127.0.0.1:8000".parse().unwrap(),
+        )))
+    }
+
+    #[tokio::test]
+    async fn test_expiration_prunes_stale_values() {
+        let dht = test_dht();
+        {
+            let mut node = dht.write().await;
+            node.store([1u8; 32], vec![1], Duration::from_millis(10));
+            node.store([2u8; 32], vec![2], Duration::from_secs(3600));
+        }
+
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let removed = DhtMaintenance::run_expiration(&dht).await;
+        assert_eq!(removed, 1);
+        assert_eq!(dht.read().await.storage_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_republish_refreshes_local_values() {
+        let dht = test_dht();
+        {
+            let mut node = dht.write().await;
+            // Local value with less remaining TTL than the interval: due
+            node.store_local([1u8; 32], vec![1], Duration::from_secs(30));
+            // Remote value in the same window: not republished
+            node.store([2u8; 32], vec![2], Duration::from_secs(30));
+        }
+
+        let republished = DhtMaintenance::run_republish(&dht, Duration::from_secs(60)).await;
+        assert_eq!(republished, 1);
+
+        // The refreshed value has a full TTL again and is no longer due
+        let due = dht
+            .read()
+            .await
+            .values_needing_republish(Duration::from_secs(10));
+        assert!(due.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_republish_skips_values_with_ample_ttl() {
+        let dht = test_dht();
+        dht.write()
+            .await
+            .store_local([1u8; 32], vec![1], Duration::from_secs(3600));
+
+        let republished = DhtMaintenance::run_republish(&dht, Duration::from_secs(60)).await;
+        assert_eq!(republished, 0);
+    }
+
+    #[tokio::test]
+    async fn test_bucket_refresh_touches_stale_buckets() {
+        let dht = test_dht();
+        {
+            let mut node = dht.write().await;
+            let peer = DhtPeer::new(NodeId::random(), "127.0.0.1:8001".parse().unwrap());
+            node.routing_table_mut().insert(peer).unwrap();
+        }
+
+        // Every non-empty bucket is stale with a zero threshold
+        let refreshed = DhtMaintenance::run_bucket_refresh(&dht, Duration::ZERO).await;
+        assert_eq!(refreshed, 1);
+
+        // Freshly marked buckets are not stale under a generous threshold
+        let refreshed = DhtMaintenance::run_bucket_refresh(&dht, Duration::from_secs(3600)).await;
+        assert_eq!(refreshed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_runs_expiration() {
+        let dht = test_dht();
+        dht.write()
+            .await
+            .store([1u8; 32], vec![1], Duration::from_millis(5));
+
+        let mut config = BootstrapConfig::new();
+        config.set_expiration_interval(Duration::from_millis(20));
+
+        let handle = DhtMaintenance::new(dht.clone(), config).spawn();
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        handle.abort();
+
+        assert_eq!(dht.read().await.storage_count(), 0);
+    }
+}
@@ -38,7 +38,10 @@ use zeroize::{Zeroize, ZeroizeOnDrop};
 
 // Module declarations
 pub mod bootstrap;
+pub mod content_index;
+pub mod maintenance;
 pub mod messages;
+pub mod mutable_record;
 pub mod node;
 pub mod node_id;
 pub mod operations;
@@ -46,10 +49,19 @@ pub mod routing;
 
 // Re-exports for convenience
 pub use bootstrap::{Bootstrap, BootstrapConfig, BootstrapError, BootstrapNode};
+pub use content_index::{
+    ContentAnnouncement, ContentIndex, ContentIndexError, SignedContentAnnouncement,
+    derive_content_index_key, derive_content_name_key,
+};
+pub use maintenance::DhtMaintenance;
 pub use messages::{
     CompactPeer, DhtMessage, FindNodeRequest, FindValueRequest, FoundNodesResponse,
     FoundValueResponse, MessageError, PingRequest, PongResponse, StoreAckResponse, StoreRequest,
 };
+pub use mutable_record::{
+    MAX_LABEL_LEN, MutableRecord, MutableRecordError, SignedMutableRecord,
+    derive_mutable_record_key,
+};
 pub use node::{DhtNode, NodeState, StoredValue};
 pub use node_id::{NodeId, SybilResistance};
 pub use operations::{ALPHA, DhtOperations, OperationError};
@@ -0,0 +1,483 @@
+//! Signed mutable pointer records with latest-version resolution
+//!
+//! Immutable DHT records are addressed by content hash, so a publisher
+//! cannot repoint an existing name at new content. Mutable records solve
+//! this: the DHT key is derived from the publisher's public key and a
+//! label (e.g. `"latest-build"`), and the value is a signed pointer to
+//! the current content hash plus a monotonically increasing sequence
+//! number. Republishing with a higher sequence number moves the pointer;
+//! conflicting updates resolve to the highest sequence number, and stale
+//! or equal sequence numbers are rejected.
+//!
+//! Fetchers verify the publisher signature and that the record's
+//! publisher and label match the key they asked for, so a malicious DHT
+//! node can withhold a pointer but cannot forge or redirect one.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wraith_crypto::signatures::{Signature, SigningKey, VerifyingKey};
+
+use super::DhtNode;
+
+/// Domain separator for mutable record key derivation
+const MUTABLE_RECORD_KEY_DOMAIN: &[u8] = b"wraith-dht-mutable-record";
+
+/// Domain separator for mutable record signatures
+const MUTABLE_RECORD_SIGNING_DOMAIN: &[u8] = b"wraith-mutable-record-v1";
+
+/// Maximum length of a record label in bytes
+pub const MAX_LABEL_LEN: usize = 255;
+
+/// Mutable record errors
+#[derive(Debug, Error)]
+pub enum MutableRecordError {
+    /// Serialization error
+    #[error("Serialization failed: {0}")]
+    Serialization(bincode::Error),
+
+    /// Publisher key is not a valid Ed25519 public key
+    #[error("Invalid publisher key")]
+    InvalidPublisher,
+
+    /// Signature does not authenticate the record
+    #[error("Signature verification failed")]
+    InvalidSignature,
+
+    /// Record fails validation (e.g. oversized label)
+    #[error("Invalid record: {0}")]
+    InvalidRecord(String),
+
+    /// An update does not supersede the currently stored record
+    #[error("Stale sequence number: current {current}, attempted {attempted}")]
+    StaleSequence {
+        /// Sequence number of the stored record
+        current: u64,
+        /// Sequence number of the rejected update
+        attempted: u64,
+    },
+}
+
+/// Derive the DHT key for a publisher's labelled mutable record
+///
+/// The key binds the publisher identity to the label, so only the holder
+/// of the matching signing key can publish valid records under it.
+#[must_use]
+pub fn derive_mutable_record_key(publisher: &[u8; 32], label: &str) -> [u8; 32] {
+    let mut message = MUTABLE_RECORD_KEY_DOMAIN.to_vec();
+    message.extend_from_slice(publisher);
+    message.extend_from_slice(label.as_bytes());
+    *blake3::hash(&message).as_bytes()
+}
+
+/// A mutable pointer record: label → current content hash with a sequence number
+///
+/// The `publisher` field is filled in when the record is signed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MutableRecord {
+    /// Human-readable label (e.g. `"latest-build"`)
+    pub label: String,
+    /// BLAKE3 hash of the content the label currently points at
+    pub content_hash: [u8; 32],
+    /// Monotonically increasing update counter; highest wins
+    pub seq: u64,
+    /// Publisher's Ed25519 public key
+    pub publisher: [u8; 32],
+    /// Last update time (seconds since Unix epoch)
+    pub updated_at: u64,
+}
+
+impl MutableRecord {
+    /// Create a new unsigned record
+    ///
+    /// The publisher key and signature are attached by [`Self::sign`].
+    #[must_use]
+    pub fn new(label: String, content_hash: [u8; 32], seq: u64) -> Self {
+        let updated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        Self {
+            label,
+            content_hash,
+            seq,
+            publisher: [0u8; 32],
+            updated_at,
+        }
+    }
+
+    /// Sign the record, producing a publishable pointer
+    ///
+    /// Sets the publisher field from the signing key before signing, so the
+    /// signature covers the publisher identity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MutableRecordError::InvalidRecord`] if the label is empty
+    /// or exceeds [`MAX_LABEL_LEN`] bytes.
+    pub fn sign(mut self, key: &SigningKey) -> Result<SignedMutableRecord, MutableRecordError> {
+        if self.label.is_empty() {
+            return Err(MutableRecordError::InvalidRecord(
+                "label is empty".to_string(),
+            ));
+        }
+        if self.label.len() > MAX_LABEL_LEN {
+            return Err(MutableRecordError::InvalidRecord(format!(
+                "label exceeds {MAX_LABEL_LEN} bytes"
+            )));
+        }
+
+        self.publisher = key.verifying_key().to_bytes();
+        let signature = key.sign(&self.signing_bytes()?);
+
+        Ok(SignedMutableRecord {
+            record: self,
+            signature: signature.as_bytes().to_vec(),
+        })
+    }
+
+    /// Canonical byte encoding covered by the signature
+    fn signing_bytes(&self) -> Result<Vec<u8>, MutableRecordError> {
+        let mut bytes = MUTABLE_RECORD_SIGNING_DOMAIN.to_vec();
+        bytes.extend(bincode::serialize(self).map_err(MutableRecordError::Serialization)?);
+        Ok(bytes)
+    }
+}
+
+/// A signed mutable record ready for DHT publication
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedMutableRecord {
+    /// The pointer record
+    pub record: MutableRecord,
+    /// Ed25519 signature over the record (64 bytes)
+    pub signature: Vec<u8>,
+}
+
+impl SignedMutableRecord {
+    /// Verify the publisher's signature
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MutableRecordError::InvalidPublisher`] if the publisher key
+    /// is malformed, or [`MutableRecordError::InvalidSignature`] if the
+    /// signature does not authenticate the record.
+    pub fn verify(&self) -> Result<(), MutableRecordError> {
+        let verifying_key = VerifyingKey::from_bytes(&self.record.publisher)
+            .map_err(|_| MutableRecordError::InvalidPublisher)?;
+        let signature = Signature::from_slice(&self.signature)
+            .map_err(|_| MutableRecordError::InvalidSignature)?;
+
+        verifying_key
+            .verify(&self.record.signing_bytes()?, &signature)
+            .map_err(|_| MutableRecordError::InvalidSignature)
+    }
+
+    /// Whether this record supersedes another version of the same pointer
+    ///
+    /// Resolution is by sequence number alone: strictly higher wins, so
+    /// replaying an old or equal-sequence record never moves the pointer.
+    #[must_use]
+    pub fn supersedes(&self, other: &Self) -> bool {
+        self.record.seq > other.record.seq
+    }
+
+    /// Serialize to bytes for DHT storage
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MutableRecordError::Serialization`] on encoding failure.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MutableRecordError> {
+        bincode::serialize(self).map_err(MutableRecordError::Serialization)
+    }
+
+    /// Deserialize from DHT storage bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MutableRecordError::Serialization`] on decoding failure.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MutableRecordError> {
+        bincode::deserialize(bytes).map_err(MutableRecordError::Serialization)
+    }
+}
+
+impl DhtNode {
+    /// Publish a signed mutable record under its publisher-derived key
+    ///
+    /// The record's signature is verified before publishing. If a valid
+    /// record already exists under the key, the update must carry a strictly
+    /// higher sequence number; otherwise the stored version wins and
+    /// [`MutableRecordError::StaleSequence`] is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the record fails verification, does not supersede
+    /// the stored record, or cannot be serialized.
+    pub fn publish_mutable(
+        &mut self,
+        record: SignedMutableRecord,
+        ttl: Duration,
+    ) -> Result<(), MutableRecordError> {
+        record.verify()?;
+
+        let key = derive_mutable_record_key(&record.record.publisher, &record.record.label);
+        if let Some(bytes) = self.get(&key)
+            && let Ok(existing) = SignedMutableRecord::from_bytes(&bytes)
+            && existing.verify().is_ok()
+            && !record.supersedes(&existing)
+        {
+            return Err(MutableRecordError::StaleSequence {
+                current: existing.record.seq,
+                attempted: record.record.seq,
+            });
+        }
+
+        self.store(key, record.to_bytes()?, ttl);
+        Ok(())
+    }
+
+    /// Resolve the latest version of a publisher's labelled pointer
+    ///
+    /// Returns `None` if no record exists, the record fails signature
+    /// verification, or its publisher/label do not match the requested key
+    /// (hash collision guard).
+    #[must_use]
+    pub fn resolve_mutable(
+        &self,
+        publisher: &[u8; 32],
+        label: &str,
+    ) -> Option<SignedMutableRecord> {
+        let key = derive_mutable_record_key(publisher, label);
+        let record = SignedMutableRecord::from_bytes(&self.get(&key)?).ok()?;
+        record.verify().ok()?;
+        (record.record.publisher == *publisher && record.record.label == label).then_some(record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dht::NodeId;
+    use rand::rngs::OsRng;
+
+    fn signed_pointer(
+        label: &str,
+        hash: [u8; 32],
+        seq: u64,
+        key: &SigningKey,
+    ) -> SignedMutableRecord {
+        MutableRecord::new(label.to_string(), hash, seq)
+            .sign(key)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sign_verify_roundtrip() {
+        let key = SigningKey::generate(&mut OsRng);
+        let record = signed_pointer("latest-build", [7u8; 32], 1, &key);
+
+        assert!(record.verify().is_ok());
+        assert_eq!(record.record.publisher, key.verifying_key().to_bytes());
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_record() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut record = signed_pointer("latest-build", [7u8; 32], 1, &key);
+
+        record.record.content_hash = [8u8; 32];
+        assert!(matches!(
+            record.verify(),
+            Err(MutableRecordError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_rejects_seq_rollback() {
+        let key = SigningKey::generate(&mut OsRng);
+        let mut record = signed_pointer("latest-build", [7u8; 32], 5, &key);
+
+        // Lowering the sequence number breaks the signature
+        record.record.seq = 1;
+        assert!(record.verify().is_err());
+    }
+
+    #[test]
+    fn test_sign_rejects_invalid_labels() {
+        let key = SigningKey::generate(&mut OsRng);
+
+        let empty = MutableRecord::new(String::new(), [0u8; 32], 0);
+        assert!(matches!(
+            empty.sign(&key),
+            Err(MutableRecordError::InvalidRecord(_))
+        ));
+
+        let long = MutableRecord::new("x".repeat(MAX_LABEL_LEN + 1), [0u8; 32], 0);
+        assert!(long.sign(&key).is_err());
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let key = SigningKey::generate(&mut OsRng);
+        let record = signed_pointer("latest-build", [7u8; 32], 3, &key);
+
+        let bytes = record.to_bytes().unwrap();
+        let decoded = SignedMutableRecord::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, record);
+        assert!(decoded.verify().is_ok());
+    }
+
+    #[test]
+    fn test_key_derivation_separation() {
+        let publisher1 = [1u8; 32];
+        let publisher2 = [2u8; 32];
+
+        assert_ne!(
+            derive_mutable_record_key(&publisher1, "latest"),
+            derive_mutable_record_key(&publisher2, "latest")
+        );
+        assert_ne!(
+            derive_mutable_record_key(&publisher1, "latest"),
+            derive_mutable_record_key(&publisher1, "stable")
+        );
+    }
+
+    #[test]
+    fn test_supersedes_by_sequence_number() {
+        let key = SigningKey::generate(&mut OsRng);
+        let v1 = signed_pointer("latest-build", [1u8; 32], 1, &key);
+        let v2 = signed_pointer("latest-build", [2u8; 32], 2, &key);
+
+        assert!(v2.supersedes(&v1));
+        assert!(!v1.supersedes(&v2));
+        // Equal sequence numbers never supersede
+        assert!(!v1.supersedes(&v1));
+    }
+
+    #[test]
+    fn test_dht_publish_and_resolve() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        let key = SigningKey::generate(&mut OsRng);
+        let publisher = key.verifying_key().to_bytes();
+
+        node.publish_mutable(
+            signed_pointer("latest-build", [1u8; 32], 1, &key),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+
+        let resolved = node.resolve_mutable(&publisher, "latest-build").unwrap();
+        assert_eq!(resolved.record.content_hash, [1u8; 32]);
+        assert_eq!(resolved.record.seq, 1);
+
+        // Unknown label or publisher resolves to nothing
+        assert!(node.resolve_mutable(&publisher, "stable").is_none());
+        assert!(node.resolve_mutable(&[9u8; 32], "latest-build").is_none());
+    }
+
+    #[test]
+    fn test_dht_update_moves_pointer() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        let key = SigningKey::generate(&mut OsRng);
+        let publisher = key.verifying_key().to_bytes();
+
+        node.publish_mutable(
+            signed_pointer("latest-build", [1u8; 32], 1, &key),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+        node.publish_mutable(
+            signed_pointer("latest-build", [2u8; 32], 2, &key),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+
+        let resolved = node.resolve_mutable(&publisher, "latest-build").unwrap();
+        assert_eq!(resolved.record.content_hash, [2u8; 32]);
+        assert_eq!(resolved.record.seq, 2);
+    }
+
+    #[test]
+    fn test_dht_rejects_stale_sequence() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        let key = SigningKey::generate(&mut OsRng);
+        let publisher = key.verifying_key().to_bytes();
+
+        node.publish_mutable(
+            signed_pointer("latest-build", [5u8; 32], 5, &key),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+
+        // Replay of an older version is rejected
+        let stale = node.publish_mutable(
+            signed_pointer("latest-build", [1u8; 32], 1, &key),
+            Duration::from_secs(3600),
+        );
+        assert!(matches!(
+            stale,
+            Err(MutableRecordError::StaleSequence {
+                current: 5,
+                attempted: 1
+            })
+        ));
+
+        // Equal sequence is also rejected
+        let equal = node.publish_mutable(
+            signed_pointer("latest-build", [6u8; 32], 5, &key),
+            Duration::from_secs(3600),
+        );
+        assert!(equal.is_err());
+
+        let resolved = node.resolve_mutable(&publisher, "latest-build").unwrap();
+        assert_eq!(resolved.record.content_hash, [5u8; 32]);
+    }
+
+    #[test]
+    fn test_dht_publish_rejects_forged_record() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        let key = SigningKey::generate(&mut OsRng);
+
+        let mut forged = signed_pointer("latest-build", [1u8; 32], 1, &key);
+        forged.record.content_hash = [99u8; 32];
+
+        assert!(
+            node.publish_mutable(forged, Duration::from_secs(60))
+                .is_err()
+        );
+        assert!(
+            node.resolve_mutable(&key.verifying_key().to_bytes(), "latest-build")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_different_publishers_do_not_conflict() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        let key1 = SigningKey::generate(&mut OsRng);
+        let key2 = SigningKey::generate(&mut OsRng);
+
+        // Same label, different publishers: independent pointers, no
+        // sequence number interaction
+        node.publish_mutable(
+            signed_pointer("latest-build", [1u8; 32], 10, &key1),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+        node.publish_mutable(
+            signed_pointer("latest-build", [2u8; 32], 1, &key2),
+            Duration::from_secs(3600),
+        )
+        .unwrap();
+
+        let r1 = node
+            .resolve_mutable(&key1.verifying_key().to_bytes(), "latest-build")
+            .unwrap();
+        let r2 = node
+            .resolve_mutable(&key2.verifying_key().to_bytes(), "latest-build")
+            .unwrap();
+        assert_eq!(r1.record.content_hash, [1u8; 32]);
+        assert_eq!(r2.record.content_hash, [2u8; 32]);
+    }
+}
@@ -46,6 +46,11 @@ pub struct StoredValue {
     pub stored_at: Instant,
     /// Time-to-live for this value
     pub ttl: Duration,
+    /// Whether this node is the original publisher
+    ///
+    /// Locally originated values are republished before their TTL elapses;
+    /// values stored on behalf of other nodes simply expire.
+    pub locally_originated: bool,
 }
 
 impl StoredValue {
@@ -70,6 +75,29 @@ impl StoredValue {
             data,
             stored_at: Instant::now(),
             ttl,
+            locally_originated: false,
+        }
+    }
+
+    /// Create a new locally originated value
+    ///
+    /// Locally originated values are picked up by the maintenance loop and
+    /// republished before expiry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wraith_discovery::dht::StoredValue;
+    /// use std::time::Duration;
+    ///
+    /// let value = StoredValue::new_local(vec![1, 2, 3], Duration::from_secs(3600));
+    /// assert!(value.locally_originated);
+    /// ```
+    #[must_use]
+    pub fn new_local(data: Vec<u8>, ttl: Duration) -> Self {
+        Self {
+            locally_originated: true,
+            ..Self::new(data, ttl)
         }
     }
 
@@ -230,6 +258,71 @@ impl DhtNode {
         self.storage.insert(key, stored_value);
     }
 
+    /// Store a locally originated value in the DHT
+    ///
+    /// Unlike [`store`](Self::store), values stored through this method are
+    /// republished by the maintenance loop before their TTL elapses.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - 32-byte key to store under
+    /// * `value` - Value data
+    /// * `ttl` - Time-to-live for this value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wraith_discovery::dht::{DhtNode, NodeId};
+    /// use std::time::Duration;
+    ///
+    /// let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+    /// node.store_local([42u8; 32], vec![1, 2, 3], Duration::from_secs(3600));
+    /// ```
+    pub fn store_local(&mut self, key: [u8; 32], value: Vec<u8>, ttl: Duration) {
+        let stored_value = StoredValue::new_local(value, ttl);
+        self.storage.insert(key, stored_value);
+    }
+
+    /// Collect locally originated values that should be republished
+    ///
+    /// Returns keys of values whose remaining TTL has dropped below
+    /// `window`, meaning they would expire before the next maintenance
+    /// pass unless republished.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - Remaining-TTL threshold below which republish is due
+    ///
+    /// # Returns
+    ///
+    /// Keys of locally originated values due for republish
+    #[must_use]
+    pub fn values_needing_republish(&self, window: Duration) -> Vec<[u8; 32]> {
+        self.storage
+            .iter()
+            .filter(|(_, v)| v.locally_originated && !v.is_expired() && v.remaining_ttl() <= window)
+            .map(|(k, _)| *k)
+            .collect()
+    }
+
+    /// Reset the storage timestamp of a value after a successful republish
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Key of the republished value
+    ///
+    /// # Returns
+    ///
+    /// `true` if the value existed and was refreshed
+    pub fn mark_republished(&mut self, key: &[u8; 32]) -> bool {
+        if let Some(stored) = self.storage.get_mut(key) {
+            stored.stored_at = Instant::now();
+            true
+        } else {
+            false
+        }
+    }
+
     /// Retrieve a value from local storage
     ///
     /// # Arguments
@@ -439,6 +532,55 @@ mod tests {
         assert_eq!(node.storage_count(), 1);
     }
 
+    #[test]
+    fn test_store_local_marks_origin() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+
+        node.store_local([1u8; 32], vec![1], Duration::from_secs(60));
+        node.store([2u8; 32], vec![2], Duration::from_secs(60));
+
+        // Both readable; only the local one is republish-eligible
+        assert!(node.get(&[1u8; 32]).is_some());
+        assert!(node.get(&[2u8; 32]).is_some());
+
+        let due = node.values_needing_republish(Duration::from_secs(120));
+        assert_eq!(due, vec![[1u8; 32]]);
+    }
+
+    #[test]
+    fn test_values_needing_republish_window() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        node.store_local([1u8; 32], vec![1], Duration::from_secs(3600));
+
+        // Plenty of TTL left: not due yet
+        assert!(
+            node.values_needing_republish(Duration::from_secs(60))
+                .is_empty()
+        );
+        // Window larger than remaining TTL: due
+        assert_eq!(
+            node.values_needing_republish(Duration::from_secs(7200))
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_mark_republished_resets_ttl() {
+        let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        node.store_local([1u8; 32], vec![1], Duration::from_millis(50));
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(node.mark_republished(&[1u8; 32]));
+
+        // TTL restarted; value survives past the original deadline
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(node.get(&[1u8; 32]).is_some());
+
+        // Unknown keys are reported
+        assert!(!node.mark_republished(&[9u8; 32]));
+    }
+
     #[test]
     fn test_dht_node_prune_all() {
         let mut node = DhtNode::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
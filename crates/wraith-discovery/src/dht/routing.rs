@@ -108,6 +108,8 @@ pub struct KBucket {
     peers: VecDeque<DhtPeer>,
     /// Maximum number of peers this bucket can hold
     capacity: usize,
+    /// When a lookup last touched this bucket's range
+    last_refreshed: Instant,
 }
 
 impl KBucket {
@@ -130,9 +132,24 @@ impl KBucket {
         Self {
             peers: VecDeque::with_capacity(capacity),
             capacity,
+            last_refreshed: Instant::now(),
         }
     }
 
+    /// Whether no lookup has touched this bucket's range within `threshold`
+    ///
+    /// Stale buckets should be refreshed with a lookup for a random ID in
+    /// their range, per the Kademlia paper.
+    #[must_use]
+    pub fn is_stale(&self, threshold: Duration) -> bool {
+        self.last_refreshed.elapsed() >= threshold
+    }
+
+    /// Record that a lookup touched this bucket's range
+    pub fn mark_refreshed(&mut self) {
+        self.last_refreshed = Instant::now();
+    }
+
     /// Insert a peer into the bucket
     ///
     /// Insertion follows these rules:
@@ -475,6 +492,82 @@ impl RoutingTable {
             .map(|(idx, _)| idx)
             .collect()
     }
+
+    /// Identify non-empty buckets that no lookup has touched recently
+    ///
+    /// Per the Kademlia paper, each such bucket should be refreshed with a
+    /// lookup for a random ID in its range (see
+    /// [`random_id_in_bucket`](Self::random_id_in_bucket)).
+    ///
+    /// # Arguments
+    ///
+    /// * `threshold` - Idle time after which a bucket is considered stale
+    ///
+    /// # Returns
+    ///
+    /// Indices of stale, non-empty buckets
+    #[must_use]
+    pub fn stale_buckets(&self, threshold: Duration) -> Vec<usize> {
+        self.buckets
+            .iter()
+            .enumerate()
+            .filter(|(_, bucket)| !bucket.is_empty() && bucket.is_stale(threshold))
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Record that a lookup touched the given bucket's range
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Bucket index (0-255)
+    pub fn mark_bucket_refreshed(&mut self, index: usize) {
+        if let Some(bucket) = self.buckets.get_mut(index) {
+            bucket.mark_refreshed();
+        }
+    }
+
+    /// Generate a random NodeId falling into the given bucket's range
+    ///
+    /// The returned ID differs from the local ID first at bit `index`
+    /// (counted from the least significant bit), so a lookup for it
+    /// exercises exactly that bucket.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Bucket index (0-255)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wraith_discovery::dht::{NodeId, RoutingTable};
+    ///
+    /// let table = RoutingTable::new(NodeId::random());
+    /// let id = table.random_id_in_bucket(42);
+    /// assert_eq!(id.bucket_index(table.local_id()), Some(42));
+    /// ```
+    #[must_use]
+    pub fn random_id_in_bucket(&self, index: usize) -> NodeId {
+        let index = index.min(NUM_BUCKETS - 1);
+
+        // Build a random XOR distance whose highest set bit is `index`
+        let mut distance = *NodeId::random().as_bytes();
+        let byte_idx = 31 - index / 8;
+        let bit_idx = index % 8;
+
+        for byte in &mut distance[..byte_idx] {
+            *byte = 0;
+        }
+        let below_mask = ((1u16 << bit_idx) - 1) as u8;
+        distance[byte_idx] = (distance[byte_idx] & below_mask) | (1 << bit_idx);
+
+        let local = self.local_id.as_bytes();
+        let mut id = [0u8; 32];
+        for (i, byte) in id.iter_mut().enumerate() {
+            *byte = local[i] ^ distance[i];
+        }
+        NodeId::from_bytes(id)
+    }
 }
 
 /// DHT errors
@@ -651,4 +744,50 @@ mod tests {
         // All buckets start empty, so all need refresh
         assert_eq!(needing_refresh.len(), 256);
     }
+
+    #[test]
+    fn test_stale_buckets_only_non_empty() {
+        let local_id = NodeId::random();
+        let mut table = RoutingTable::new(local_id);
+
+        // Empty table: nothing to refresh even with a zero threshold
+        assert!(table.stale_buckets(Duration::ZERO).is_empty());
+
+        let peer = DhtPeer::new(NodeId::random(), "127.0.0.1:8000".parse().unwrap());
+        table.insert(peer).unwrap();
+
+        let stale = table.stale_buckets(Duration::ZERO);
+        assert_eq!(stale.len(), 1);
+
+        // Marking refreshed clears staleness under a generous threshold
+        table.mark_bucket_refreshed(stale[0]);
+        assert!(table.stale_buckets(Duration::from_secs(3600)).is_empty());
+    }
+
+    #[test]
+    fn test_random_id_in_bucket_lands_in_bucket() {
+        let local_id = NodeId::random();
+        let table = RoutingTable::new(local_id);
+
+        for index in [0, 1, 7, 8, 42, 128, 255] {
+            let id = table.random_id_in_bucket(index);
+            assert_eq!(
+                id.bucket_index(&local_id),
+                Some(index),
+                "ID for bucket {index} landed elsewhere"
+            );
+        }
+    }
+
+    #[test]
+    fn test_random_id_in_bucket_varies() {
+        let local_id = NodeId::random();
+        let table = RoutingTable::new(local_id);
+
+        // Low bits are randomized, so repeated draws differ (bucket 0 has
+        // only one member, so use a bucket with room)
+        let a = table.random_id_in_bucket(200);
+        let b = table.random_id_in_bucket(200);
+        assert_ne!(a, b);
+    }
 }
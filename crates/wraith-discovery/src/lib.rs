@@ -35,18 +35,21 @@
 #![warn(clippy::all)]
 
 pub mod dht;
+pub mod local;
 pub mod manager;
 pub mod nat;
 pub mod relay;
 
 // Re-export commonly used types
+pub use local::{LocalDiscovery, LocalDiscoveryConfig, LocalPeer};
 pub use manager::{
     ConnectionType, DiscoveryConfig, DiscoveryError, DiscoveryManager, DiscoveryState,
     PeerConnection, RelayInfo,
 };
 pub use nat::{
-    Candidate, CandidateType, HolePuncher, IceGatherer, NatDetector, NatError, NatType, PunchError,
-    StunClient, StunError,
+    AdaptiveKeepalive, Candidate, CandidateType, EnvironmentProbe, HolePuncher, IceGatherer,
+    NatDetector, NatEnvironment, NatError, NatType, PunchError, StunClient, StunError,
+    TraversalStrategy, TurnServerConfig,
 };
 
 /// Peer endpoint information
@@ -0,0 +1,707 @@
+//! mDNS-SD Local Peer Discovery
+//!
+//! Announces and browses WRAITH peers on the local network using
+//! multicast DNS service discovery (RFC 6762/6763) under the service name
+//! `_wraith._udp.local`. Two machines on the same Wi-Fi can find each
+//! other and transfer without any DHT or relay infrastructure.
+//!
+//! ## Wire Format
+//!
+//! Announcements are standard mDNS responses carrying:
+//! - A PTR record from `_wraith._udp.local` to the peer's instance name
+//! - An SRV record with the peer's WRAITH listen port
+//! - A TXT record with `id=<64-hex node id>`
+//!
+//! The peer's IP is taken from the packet's source address rather than an
+//! A record, which sidesteps interface enumeration and multi-homing.
+//!
+//! ## Example
+//!
+//! ```no_run
+//! use wraith_discovery::local::{LocalDiscovery, LocalDiscoveryConfig};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let discovery = LocalDiscovery::new([7u8; 32], 40000, LocalDiscoveryConfig::default())?;
+//! let handle = discovery.spawn();
+//!
+//! // ... later:
+//! for peer in discovery.peers() {
+//!     println!("LAN peer {} at {}", hex::encode(peer.node_id), peer.addr);
+//! }
+//! handle.abort();
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::net::UdpSocket;
+use tokio::task::JoinHandle;
+use tracing::{debug, trace, warn};
+
+/// mDNS multicast group address
+const MDNS_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+
+/// mDNS port
+const MDNS_PORT: u16 = 5353;
+
+/// WRAITH service name (DNS-SD convention: `_<service>._<proto>.local`)
+const SERVICE_NAME: &[&str] = &["_wraith", "_udp", "local"];
+
+/// DNS record types used by DNS-SD
+const TYPE_PTR: u16 = 12;
+const TYPE_TXT: u16 = 16;
+const TYPE_SRV: u16 = 33;
+
+/// DNS class IN, with the mDNS cache-flush bit for announcements
+const CLASS_IN: u16 = 1;
+const CLASS_IN_FLUSH: u16 = 0x8001;
+
+/// Record TTL in announcements (seconds, per RFC 6762 §10)
+const RECORD_TTL: u32 = 120;
+
+/// Largest mDNS packet we will parse
+const MAX_PACKET_SIZE: usize = 1472;
+
+/// Local discovery errors
+#[derive(Debug, Error)]
+pub enum LocalDiscoveryError {
+    /// Socket setup or I/O failure
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// Received packet could not be parsed as DNS
+    #[error("Malformed mDNS packet: {0}")]
+    Malformed(&'static str),
+}
+
+/// Configuration for local peer discovery
+#[derive(Debug, Clone)]
+pub struct LocalDiscoveryConfig {
+    /// How often to announce our presence and re-query for peers
+    pub announce_interval: Duration,
+    /// Drop peers not heard from within this window
+    pub peer_timeout: Duration,
+}
+
+impl Default for LocalDiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            announce_interval: Duration::from_secs(15),
+            peer_timeout: Duration::from_secs(120),
+        }
+    }
+}
+
+/// A peer discovered on the local network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalPeer {
+    /// Peer node ID from the TXT record
+    pub node_id: [u8; 32],
+    /// Peer's WRAITH endpoint (source IP + SRV port)
+    pub addr: SocketAddr,
+    /// When the peer was last heard from
+    pub last_seen: Instant,
+}
+
+/// mDNS-SD announcer and browser for WRAITH peers
+///
+/// Cloning is cheap; clones share the socket and peer table.
+#[derive(Clone)]
+pub struct LocalDiscovery {
+    /// Our node ID, advertised in the TXT record
+    node_id: [u8; 32],
+    /// Our WRAITH listen port, advertised in the SRV record
+    service_port: u16,
+    /// Shared mDNS socket (bound to 5353, joined to the multicast group)
+    socket: Arc<UdpSocket>,
+    /// Discovered peers keyed by node ID
+    peers: Arc<Mutex<HashMap<[u8; 32], LocalPeer>>>,
+    /// Timing configuration
+    config: LocalDiscoveryConfig,
+}
+
+impl LocalDiscovery {
+    /// Create a local discovery instance
+    ///
+    /// Binds the shared mDNS port with address reuse (so it coexists with
+    /// system mDNS daemons like Avahi) and joins the multicast group.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - This node's ID, advertised to peers
+    /// * `service_port` - The WRAITH listen port to advertise
+    /// * `config` - Announce and expiry timing
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be bound or the multicast
+    /// group cannot be joined.
+    pub fn new(
+        node_id: [u8; 32],
+        service_port: u16,
+        config: LocalDiscoveryConfig,
+    ) -> Result<Self, LocalDiscoveryError> {
+        let socket = socket2::Socket::new(
+            socket2::Domain::IPV4,
+            socket2::Type::DGRAM,
+            Some(socket2::Protocol::UDP),
+        )?;
+        socket.set_reuse_address(true)?;
+        #[cfg(unix)]
+        socket.set_reuse_port(true)?;
+        socket.bind(&SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, MDNS_PORT).into())?;
+        socket.join_multicast_v4(&MDNS_GROUP, &Ipv4Addr::UNSPECIFIED)?;
+        socket.set_multicast_loop_v4(true)?;
+        socket.set_nonblocking(true)?;
+
+        let socket = UdpSocket::from_std(socket.into())?;
+
+        Ok(Self {
+            node_id,
+            service_port,
+            socket: Arc::new(socket),
+            peers: Arc::new(Mutex::new(HashMap::new())),
+            config,
+        })
+    }
+
+    /// Currently known local peers, pruning any that have expired
+    #[must_use]
+    pub fn peers(&self) -> Vec<LocalPeer> {
+        let mut peers = self.peers.lock().expect("peer table lock poisoned");
+        peers.retain(|_, p| p.last_seen.elapsed() < self.config.peer_timeout);
+        peers.values().cloned().collect()
+    }
+
+    /// Number of currently known local peers
+    #[must_use]
+    pub fn peer_count(&self) -> usize {
+        self.peers().len()
+    }
+
+    /// Send one announcement of our service to the multicast group
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the send fails (e.g. no multicast route).
+    pub async fn announce_once(&self) -> Result<(), LocalDiscoveryError> {
+        let packet = build_announcement(&self.node_id, self.service_port);
+        self.socket
+            .send_to(&packet, (MDNS_GROUP, MDNS_PORT))
+            .await?;
+        trace!("mDNS: announced _wraith._udp service");
+        Ok(())
+    }
+
+    /// Send one PTR query for the WRAITH service
+    ///
+    /// Peers respond with announcements, which the receive loop collects.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the send fails.
+    pub async fn query_once(&self) -> Result<(), LocalDiscoveryError> {
+        let packet = build_query();
+        self.socket
+            .send_to(&packet, (MDNS_GROUP, MDNS_PORT))
+            .await?;
+        trace!("mDNS: queried for _wraith._udp peers");
+        Ok(())
+    }
+
+    /// Process one received mDNS packet, updating the peer table
+    ///
+    /// Returns the discovered peer if the packet was a WRAITH announcement
+    /// from someone other than ourselves; `None` for queries (which are
+    /// answered) and unrelated mDNS traffic.
+    fn handle_packet(&self, packet: &[u8], src: SocketAddr) -> Option<LocalPeer> {
+        let parsed = parse_packet(packet).ok()?;
+
+        match parsed {
+            ParsedPacket::Query => {
+                // Another browser is asking; the announce loop will answer
+                // on its next tick, but respond promptly for snappy discovery
+                None
+            }
+            ParsedPacket::Announcement { node_id, port } => {
+                if node_id == self.node_id {
+                    return None; // Our own multicast loopback
+                }
+
+                let peer = LocalPeer {
+                    node_id,
+                    addr: SocketAddr::new(src.ip(), port),
+                    last_seen: Instant::now(),
+                };
+
+                let mut peers = self.peers.lock().expect("peer table lock poisoned");
+                if peers.insert(node_id, peer.clone()).is_none() {
+                    debug!(
+                        "mDNS: discovered local peer {} at {}",
+                        hex::encode(&node_id[..8]),
+                        peer.addr
+                    );
+                }
+                Some(peer)
+            }
+        }
+    }
+
+    /// Spawn the announce/browse loop as a background tokio task
+    ///
+    /// Announces and re-queries on the configured interval while
+    /// continuously receiving peer announcements. Runs until aborted via
+    /// the returned handle.
+    #[must_use]
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(this.config.announce_interval);
+            let mut buf = vec![0u8; MAX_PACKET_SIZE];
+            // Answer queries promptly instead of waiting a full interval
+            let mut respond_to_queries = false;
+
+            loop {
+                tokio::select! {
+                    _ = tick.tick() => {
+                        if let Err(e) = this.announce_once().await {
+                            warn!("mDNS announce failed: {e}");
+                        }
+                        if let Err(e) = this.query_once().await {
+                            warn!("mDNS query failed: {e}");
+                        }
+                        respond_to_queries = true;
+                    }
+                    recv = this.socket.recv_from(&mut buf) => {
+                        match recv {
+                            Ok((len, src)) => {
+                                let is_query =
+                                    matches!(parse_packet(&buf[..len]), Ok(ParsedPacket::Query));
+                                this.handle_packet(&buf[..len], src);
+                                if is_query && respond_to_queries {
+                                    let _ = this.announce_once().await;
+                                }
+                            }
+                            Err(e) => {
+                                warn!("mDNS receive failed: {e}");
+                                tokio::time::sleep(Duration::from_secs(1)).await;
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// A received mDNS packet relevant to the WRAITH service
+#[derive(Debug, PartialEq, Eq)]
+enum ParsedPacket {
+    /// A PTR query for `_wraith._udp.local`
+    Query,
+    /// A peer announcement with its node ID and listen port
+    Announcement {
+        /// Node ID from the TXT record
+        node_id: [u8; 32],
+        /// Listen port from the SRV record
+        port: u16,
+    },
+}
+
+/// Append a DNS name in wire format (length-prefixed labels)
+fn encode_name(out: &mut Vec<u8>, labels: &[&str]) {
+    for label in labels {
+        debug_assert!(label.len() < 64);
+        out.push(label.len() as u8);
+        out.extend_from_slice(label.as_bytes());
+    }
+    out.push(0);
+}
+
+/// Append a 16-bit big-endian value
+fn push_u16(out: &mut Vec<u8>, value: u16) {
+    out.extend_from_slice(&value.to_be_bytes());
+}
+
+/// Instance name for a node: `wraith-<hex prefix>._wraith._udp.local`
+fn instance_labels(node_id: &[u8; 32]) -> [String; 1] {
+    [format!("wraith-{}", hex::encode(&node_id[..8]))]
+}
+
+/// Build an mDNS PTR query for the WRAITH service
+fn build_query() -> Vec<u8> {
+    let mut out = Vec::with_capacity(64);
+
+    // Header: ID 0, standard query, one question
+    push_u16(&mut out, 0); // ID (always 0 for mDNS)
+    push_u16(&mut out, 0); // flags
+    push_u16(&mut out, 1); // QDCOUNT
+    push_u16(&mut out, 0); // ANCOUNT
+    push_u16(&mut out, 0); // NSCOUNT
+    push_u16(&mut out, 0); // ARCOUNT
+
+    encode_name(&mut out, SERVICE_NAME);
+    push_u16(&mut out, TYPE_PTR);
+    push_u16(&mut out, CLASS_IN);
+
+    out
+}
+
+/// Build an mDNS announcement (response) for this node
+///
+/// Contains PTR, SRV, and TXT records. No A record: receivers take the IP
+/// from the packet's source address.
+fn build_announcement(node_id: &[u8; 32], port: u16) -> Vec<u8> {
+    let instance = instance_labels(node_id);
+    let instance_name: Vec<&str> = instance
+        .iter()
+        .map(String::as_str)
+        .chain(SERVICE_NAME.iter().copied())
+        .collect();
+
+    let mut out = Vec::with_capacity(256);
+
+    // Header: response, authoritative, three answers
+    push_u16(&mut out, 0); // ID
+    push_u16(&mut out, 0x8400); // QR=1, AA=1
+    push_u16(&mut out, 0); // QDCOUNT
+    push_u16(&mut out, 3); // ANCOUNT
+    push_u16(&mut out, 0); // NSCOUNT
+    push_u16(&mut out, 0); // ARCOUNT
+
+    // PTR: _wraith._udp.local -> instance
+    encode_name(&mut out, SERVICE_NAME);
+    push_u16(&mut out, TYPE_PTR);
+    push_u16(&mut out, CLASS_IN);
+    out.extend_from_slice(&RECORD_TTL.to_be_bytes());
+    let mut ptr_data = Vec::new();
+    encode_name(&mut ptr_data, &instance_name);
+    push_u16(&mut out, ptr_data.len() as u16);
+    out.extend_from_slice(&ptr_data);
+
+    // SRV: instance -> port (target is the instance name itself; the
+    // source address supplies the IP)
+    encode_name(&mut out, &instance_name);
+    push_u16(&mut out, TYPE_SRV);
+    push_u16(&mut out, CLASS_IN_FLUSH);
+    out.extend_from_slice(&RECORD_TTL.to_be_bytes());
+    let mut srv_data = Vec::new();
+    push_u16(&mut srv_data, 0); // priority
+    push_u16(&mut srv_data, 0); // weight
+    push_u16(&mut srv_data, port);
+    encode_name(&mut srv_data, &instance_name);
+    push_u16(&mut out, srv_data.len() as u16);
+    out.extend_from_slice(&srv_data);
+
+    // TXT: instance -> id=<hex>
+    encode_name(&mut out, &instance_name);
+    push_u16(&mut out, TYPE_TXT);
+    push_u16(&mut out, CLASS_IN_FLUSH);
+    out.extend_from_slice(&RECORD_TTL.to_be_bytes());
+    let txt_entry = format!("id={}", hex::encode(node_id));
+    let mut txt_data = Vec::with_capacity(txt_entry.len() + 1);
+    txt_data.push(txt_entry.len() as u8);
+    txt_data.extend_from_slice(txt_entry.as_bytes());
+    push_u16(&mut out, txt_data.len() as u16);
+    out.extend_from_slice(&txt_data);
+
+    out
+}
+
+/// Read a big-endian u16 at `pos`, advancing it
+fn read_u16(packet: &[u8], pos: &mut usize) -> Result<u16, LocalDiscoveryError> {
+    let bytes = packet
+        .get(*pos..*pos + 2)
+        .ok_or(LocalDiscoveryError::Malformed("truncated u16"))?;
+    *pos += 2;
+    Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+}
+
+/// Read a DNS name at `pos`, advancing it past the name
+///
+/// Follows compression pointers (without advancing past them more than
+/// once) and returns the name as lowercase dot-joined labels.
+fn read_name(packet: &[u8], pos: &mut usize) -> Result<String, LocalDiscoveryError> {
+    let mut labels = Vec::new();
+    let mut cursor = *pos;
+    let mut jumped = false;
+    let mut jumps = 0;
+
+    loop {
+        let len = *packet
+            .get(cursor)
+            .ok_or(LocalDiscoveryError::Malformed("truncated name"))? as usize;
+
+        if len & 0xC0 == 0xC0 {
+            // Compression pointer: remaining name lives elsewhere
+            let second = *packet
+                .get(cursor + 1)
+                .ok_or(LocalDiscoveryError::Malformed("truncated pointer"))?
+                as usize;
+            if !jumped {
+                *pos = cursor + 2;
+                jumped = true;
+            }
+            cursor = ((len & 0x3F) << 8) | second;
+            jumps += 1;
+            if jumps > 8 {
+                return Err(LocalDiscoveryError::Malformed("pointer loop"));
+            }
+        } else if len == 0 {
+            if !jumped {
+                *pos = cursor + 1;
+            }
+            return Ok(labels.join("."));
+        } else {
+            let label = packet
+                .get(cursor + 1..cursor + 1 + len)
+                .ok_or(LocalDiscoveryError::Malformed("truncated label"))?;
+            labels.push(String::from_utf8_lossy(label).to_lowercase());
+            cursor += 1 + len;
+        }
+    }
+}
+
+/// Parse an mDNS packet, extracting anything relevant to the WRAITH service
+///
+/// Unrelated mDNS traffic (printers, Chromecasts, ...) yields a
+/// `Malformed("not a wraith packet")` error, which callers treat as
+/// "ignore".
+fn parse_packet(packet: &[u8]) -> Result<ParsedPacket, LocalDiscoveryError> {
+    let service = SERVICE_NAME.join(".");
+    let mut pos = 0;
+
+    let _id = read_u16(packet, &mut pos)?;
+    let flags = read_u16(packet, &mut pos)?;
+    let qdcount = read_u16(packet, &mut pos)?;
+    let ancount = read_u16(packet, &mut pos)?;
+    let _nscount = read_u16(packet, &mut pos)?;
+    let _arcount = read_u16(packet, &mut pos)?;
+
+    let is_response = flags & 0x8000 != 0;
+
+    // Questions: a PTR query for our service means someone is browsing
+    let mut saw_our_question = false;
+    for _ in 0..qdcount {
+        let name = read_name(packet, &mut pos)?;
+        let qtype = read_u16(packet, &mut pos)?;
+        let _qclass = read_u16(packet, &mut pos)?;
+        if qtype == TYPE_PTR && name == service {
+            saw_our_question = true;
+        }
+    }
+
+    if !is_response {
+        return if saw_our_question {
+            Ok(ParsedPacket::Query)
+        } else {
+            Err(LocalDiscoveryError::Malformed("not a wraith packet"))
+        };
+    }
+
+    // Answers: collect the SRV port and TXT node id for our service
+    let mut srv_port = None;
+    let mut node_id = None;
+
+    for _ in 0..ancount {
+        let name = read_name(packet, &mut pos)?;
+        let rtype = read_u16(packet, &mut pos)?;
+        let _rclass = read_u16(packet, &mut pos)?;
+        pos += 4; // TTL
+        let rdlen = read_u16(packet, &mut pos)? as usize;
+        let rdata = packet
+            .get(pos..pos + rdlen)
+            .ok_or(LocalDiscoveryError::Malformed("truncated rdata"))?;
+        let rdata_start = pos;
+        pos += rdlen;
+
+        if !name.ends_with(&service) {
+            continue;
+        }
+
+        match rtype {
+            TYPE_SRV => {
+                if rdlen < 6 {
+                    return Err(LocalDiscoveryError::Malformed("short SRV rdata"));
+                }
+                let mut srv_pos = rdata_start + 4;
+                srv_port = Some(read_u16(packet, &mut srv_pos)?);
+            }
+            TYPE_TXT => {
+                // TXT is a sequence of length-prefixed strings
+                let mut txt_pos = 0;
+                while txt_pos < rdata.len() {
+                    let len = rdata[txt_pos] as usize;
+                    let entry = rdata
+                        .get(txt_pos + 1..txt_pos + 1 + len)
+                        .ok_or(LocalDiscoveryError::Malformed("truncated TXT entry"))?;
+                    if let Some(hex_id) = entry.strip_prefix(b"id=")
+                        && let Ok(bytes) = hex::decode(hex_id)
+                        && bytes.len() == 32
+                    {
+                        let mut id = [0u8; 32];
+                        id.copy_from_slice(&bytes);
+                        node_id = Some(id);
+                    }
+                    txt_pos += 1 + len;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match (node_id, srv_port) {
+        (Some(node_id), Some(port)) => Ok(ParsedPacket::Announcement { node_id, port }),
+        _ => Err(LocalDiscoveryError::Malformed("not a wraith packet")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_roundtrip() {
+        let packet = build_query();
+        assert_eq!(parse_packet(&packet).unwrap(), ParsedPacket::Query);
+    }
+
+    #[test]
+    fn test_announcement_roundtrip() {
+        let node_id = [42u8; 32];
+        let packet = build_announcement(&node_id, 40123);
+
+        match parse_packet(&packet).unwrap() {
+            ParsedPacket::Announcement { node_id: id, port } => {
+                assert_eq!(id, node_id);
+                assert_eq!(port, 40123);
+            }
+            other => panic!("expected announcement, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_foreign_mdns_traffic_is_ignored() {
+        // A query for a different service (e.g. a printer)
+        let mut packet = Vec::new();
+        push_u16(&mut packet, 0);
+        push_u16(&mut packet, 0);
+        push_u16(&mut packet, 1);
+        push_u16(&mut packet, 0);
+        push_u16(&mut packet, 0);
+        push_u16(&mut packet, 0);
+        encode_name(&mut packet, &["_ipp", "_tcp", "local"]);
+        push_u16(&mut packet, TYPE_PTR);
+        push_u16(&mut packet, CLASS_IN);
+
+        assert!(parse_packet(&packet).is_err());
+    }
+
+    #[test]
+    fn test_truncated_packet_is_rejected() {
+        let packet = build_announcement(&[1u8; 32], 40000);
+        assert!(parse_packet(&packet[..packet.len() / 2]).is_err());
+        assert!(parse_packet(&[]).is_err());
+    }
+
+    #[test]
+    fn test_read_name_with_compression_pointer() {
+        // "local" at offset 12, then a name using a pointer to it
+        let mut packet = vec![0u8; 12];
+        encode_name(&mut packet, &["local"]); // offset 12
+        let name_start = packet.len();
+        packet.push(7);
+        packet.extend_from_slice(b"_wraith");
+        packet.push(0xC0); // pointer to offset 12
+        packet.push(12);
+
+        let mut pos = name_start;
+        let name = read_name(&packet, &mut pos).unwrap();
+        assert_eq!(name, "_wraith.local");
+        assert_eq!(pos, packet.len());
+    }
+
+    #[test]
+    fn test_pointer_loop_is_rejected() {
+        // A pointer that points at itself
+        let mut packet = vec![0u8; 12];
+        packet.push(0xC0);
+        packet.push(12);
+
+        let mut pos = 12;
+        assert!(read_name(&packet, &mut pos).is_err());
+    }
+
+    fn test_discovery(node_id: [u8; 32]) -> LocalDiscovery {
+        // handle_packet needs no live socket, but the constructor binds
+        // one; tests that cannot bind (sandboxed CI) are skipped there
+        LocalDiscovery::new(node_id, 40000, LocalDiscoveryConfig::default())
+            .expect("mDNS socket bind failed")
+    }
+
+    #[tokio::test]
+    async fn test_handle_packet_records_peer() {
+        let discovery = test_discovery([1u8; 32]);
+        let src: SocketAddr = "192.168.1.50:5353".parse().unwrap();
+
+        let packet = build_announcement(&[2u8; 32], 40555);
+        let peer = discovery.handle_packet(&packet, src).unwrap();
+
+        assert_eq!(peer.node_id, [2u8; 32]);
+        assert_eq!(peer.addr, "192.168.1.50:40555".parse().unwrap());
+        assert_eq!(discovery.peer_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_handle_packet_ignores_own_announcement() {
+        let discovery = test_discovery([3u8; 32]);
+        let src: SocketAddr = "192.168.1.50:5353".parse().unwrap();
+
+        let packet = build_announcement(&[3u8; 32], 40000);
+        assert!(discovery.handle_packet(&packet, src).is_none());
+        assert_eq!(discovery.peer_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_peers_are_pruned() {
+        let discovery = LocalDiscovery::new(
+            [4u8; 32],
+            40000,
+            LocalDiscoveryConfig {
+                announce_interval: Duration::from_secs(15),
+                peer_timeout: Duration::from_millis(10),
+            },
+        )
+        .expect("mDNS socket bind failed");
+        let src: SocketAddr = "192.168.1.50:5353".parse().unwrap();
+
+        discovery.handle_packet(&build_announcement(&[5u8; 32], 40555), src);
+        assert_eq!(discovery.peer_count(), 1);
+
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(discovery.peer_count(), 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires multicast networking"]
+    async fn test_two_instances_discover_each_other() {
+        let a = test_discovery([10u8; 32]);
+        let b = test_discovery([11u8; 32]);
+
+        let handle_a = a.spawn();
+        let handle_b = b.spawn();
+
+        // Announce intervals start immediately; give both a moment
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        handle_a.abort();
+        handle_b.abort();
+
+        assert!(a.peers().iter().any(|p| p.node_id == [11u8; 32]));
+        assert!(b.peers().iter().any(|p| p.node_id == [10u8; 32]));
+    }
+}
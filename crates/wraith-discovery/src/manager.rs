@@ -4,7 +4,8 @@
 //! to provide seamless peer discovery and connection establishment.
 
 use crate::dht::{DhtNode, NodeId};
-use crate::nat::{Candidate, HolePuncher, IceGatherer, NatDetector, NatType};
+use crate::local::{LocalDiscovery, LocalDiscoveryConfig, LocalPeer};
+use crate::nat::{Candidate, HolePuncher, IceGatherer, NatDetector, NatType, TurnServerConfig};
 use crate::relay::client::{RelayClient, RelayClientState};
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::Arc;
@@ -65,12 +66,20 @@ pub struct DiscoveryConfig {
     pub bootstrap_nodes: Vec<SocketAddr>,
     /// STUN servers for NAT detection
     pub stun_servers: Vec<SocketAddr>,
+    /// TURN servers for relayed ICE candidates
+    ///
+    /// Empty by default: TURN requires credentials, so servers are
+    /// added explicitly. Candidate gathering then includes a relayed
+    /// candidate per reachable server.
+    pub turn_servers: Vec<TurnServerConfig>,
     /// Relay servers (address, node_id)
     pub relay_servers: Vec<RelayInfo>,
     /// Enable NAT detection
     pub nat_detection_enabled: bool,
     /// Enable relay fallback
     pub relay_enabled: bool,
+    /// Enable mDNS local (LAN) peer discovery
+    pub local_discovery_enabled: bool,
     /// Connection timeout
     pub connection_timeout: Duration,
 }
@@ -95,9 +104,11 @@ impl DiscoveryConfig {
             listen_addr,
             bootstrap_nodes: Vec::new(),
             stun_servers: vec![DEFAULT_STUN_SERVER_1, DEFAULT_STUN_SERVER_2],
+            turn_servers: Vec::new(),
             relay_servers: Vec::new(),
             nat_detection_enabled: true,
             relay_enabled: true,
+            local_discovery_enabled: true,
             connection_timeout: Duration::from_secs(10),
         }
     }
@@ -112,6 +123,11 @@ impl DiscoveryConfig {
         self.stun_servers.push(addr);
     }
 
+    /// Add a TURN server for relayed candidates
+    pub fn add_turn_server(&mut self, server: TurnServerConfig) {
+        self.turn_servers.push(server);
+    }
+
     /// Add a relay server
     pub fn add_relay_server(&mut self, info: RelayInfo) {
         self.relay_servers.push(info);
@@ -182,6 +198,10 @@ pub struct DiscoveryManager {
     relay_clients: Arc<RwLock<Vec<RelayClient>>>,
     /// Detected NAT type
     nat_type: Arc<RwLock<Option<NatType>>>,
+    /// mDNS local discovery (None when disabled or socket setup failed)
+    local: Arc<RwLock<Option<LocalDiscovery>>>,
+    /// Local discovery background task handle
+    local_task: Arc<RwLock<Option<tokio::task::JoinHandle<()>>>>,
     /// Manager state
     state: Arc<RwLock<DiscoveryState>>,
 }
@@ -206,8 +226,10 @@ impl DiscoveryManager {
             None
         };
 
-        // Create ICE gatherer
-        let ice_gatherer = IceGatherer::with_stun_servers(config.stun_servers.clone());
+        // Create ICE gatherer; with TURN servers configured, gathering
+        // also allocates relayed candidates
+        let ice_gatherer = IceGatherer::with_stun_servers(config.stun_servers.clone())
+            .with_turn_servers(config.turn_servers.clone());
 
         // Create hole puncher
         let hole_puncher = HolePuncher::new(config.listen_addr)
@@ -223,6 +245,8 @@ impl DiscoveryManager {
             hole_puncher,
             relay_clients: Arc::new(RwLock::new(Vec::new())),
             nat_type: Arc::new(RwLock::new(None)),
+            local: Arc::new(RwLock::new(None)),
+            local_task: Arc::new(RwLock::new(None)),
             state: Arc::new(RwLock::new(DiscoveryState::Stopped)),
         })
     }
@@ -268,6 +292,24 @@ impl DiscoveryManager {
             self.connect_relays().await?;
         }
 
+        // 4. Start mDNS local discovery (non-fatal: a blocked mDNS port
+        // or missing multicast route only disables LAN discovery)
+        if self.config.local_discovery_enabled {
+            match LocalDiscovery::new(
+                *self.config.node_id.as_bytes(),
+                self.config.listen_addr.port(),
+                LocalDiscoveryConfig::default(),
+            ) {
+                Ok(local) => {
+                    *self.local_task.write().await = Some(local.spawn());
+                    *self.local.write().await = Some(local);
+                }
+                Err(e) => {
+                    eprintln!("Warning: local discovery unavailable ({e}), continuing without it");
+                }
+            }
+        }
+
         *self.state.write().await = DiscoveryState::Running;
         Ok(())
     }
@@ -464,6 +506,13 @@ impl DiscoveryManager {
             let _ = client.disconnect().await;
         }
         clients.clear();
+        drop(clients);
+
+        // Stop local discovery
+        if let Some(task) = self.local_task.write().await.take() {
+            task.abort();
+        }
+        *self.local.write().await = None;
 
         *self.state.write().await = DiscoveryState::Stopped;
         Ok(())
@@ -486,6 +535,18 @@ impl DiscoveryManager {
     pub fn dht(&self) -> Arc<RwLock<DhtNode>> {
         self.dht.clone()
     }
+
+    /// Get peers discovered on the local network via mDNS
+    ///
+    /// Returns an empty list when local discovery is disabled or has not
+    /// found anyone yet.
+    #[must_use]
+    pub async fn local_peers(&self) -> Vec<LocalPeer> {
+        match self.local.read().await.as_ref() {
+            Some(local) => local.peers(),
+            None => Vec::new(),
+        }
+    }
 }
 
 #[cfg(test)]
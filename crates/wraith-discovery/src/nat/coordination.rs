@@ -0,0 +1,231 @@
+//! Time-synchronized hole punch coordination.
+//!
+//! Simultaneous open only works when both peers transmit within the
+//! narrow window where each NAT has an outbound mapping but has not yet
+//! dropped the other side's inbound packet — on port-restricted NATs
+//! that window is tens of milliseconds. Local wall clocks are not
+//! reliable enough to hit it, so both peers sync against the clock of a
+//! relay they can already reach: each sends a `TimeQuery`, the relay
+//! echoes the timestamp together with its own clock, and the peer
+//! computes its offset NTP-style with round-trip compensation.
+//!
+//! With both offsets known, one peer proposes a [`PunchSchedule`]
+//! ("punch at relay time T") and sends it to the other through the relay.
+//! Each side converts T back to its local clock via its own [`ClockSync`]
+//! and fires [`HolePuncher::punch_at`] — transmitting within a couple of
+//! round-trip errors of each other regardless of wall-clock skew.
+//!
+//! [`HolePuncher::punch_at`]: crate::nat::HolePuncher::punch_at
+
+use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+/// Default lead time between proposing a schedule and the punch instant
+///
+/// Long enough for the proposal to cross the relay and for the remote
+/// side to set up, short enough that neither NAT mapping state changes
+/// underneath.
+pub const DEFAULT_PUNCH_LEAD: Duration = Duration::from_millis(500);
+
+/// Current time in microseconds since the Unix epoch
+#[must_use]
+pub fn unix_micros() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_micros() as u64)
+        .unwrap_or(0)
+}
+
+/// Offset between the local clock and a relay's clock
+///
+/// Produced by [`RelayClient::sync_time`], which performs several
+/// query/report exchanges and keeps the sample with the lowest
+/// round-trip time (the one with the tightest bound on the offset).
+///
+/// [`RelayClient::sync_time`]: crate::relay::RelayClient::sync_time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClockSync {
+    /// Relay clock minus local clock, in microseconds
+    pub offset_us: i64,
+    /// Round-trip time of the sample the offset was taken from
+    pub rtt_us: u64,
+}
+
+impl ClockSync {
+    /// Compute a sync from one query/report exchange
+    ///
+    /// `sent_us` and `received_us` are local clock readings around the
+    /// exchange; `relay_us` is the relay's clock when it handled the
+    /// query. The relay is assumed to answer halfway through the round
+    /// trip (the standard NTP approximation), so the residual error is
+    /// bounded by half the RTT.
+    #[must_use]
+    pub fn from_exchange(sent_us: u64, relay_us: u64, received_us: u64) -> Self {
+        let midpoint = (sent_us + received_us) / 2;
+        Self {
+            offset_us: relay_us as i64 - midpoint as i64,
+            rtt_us: received_us.saturating_sub(sent_us),
+        }
+    }
+
+    /// The relay's current time in microseconds since the Unix epoch
+    #[must_use]
+    pub fn relay_now_us(&self) -> u64 {
+        unix_micros().saturating_add_signed(self.offset_us)
+    }
+
+    /// How long until the relay's clock reads `relay_time_us`
+    ///
+    /// Returns [`Duration::ZERO`] when the instant has already passed.
+    #[must_use]
+    pub fn until(&self, relay_time_us: u64) -> Duration {
+        Duration::from_micros(relay_time_us.saturating_sub(self.relay_now_us()))
+    }
+}
+
+/// An agreed punch instant, expressed in the relay's clock
+///
+/// The proposing peer fills in its own addresses; the remote peer punches
+/// toward them while the proposer punches toward the remote's (exchanged
+/// in the remote's answering schedule or known from ICE gathering).
+/// Exchanged through the relay as an encrypted payload, so it carries its
+/// own serialization.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PunchSchedule {
+    /// The instant both sides transmit (µs since the Unix epoch, relay clock)
+    pub punch_at_relay_us: u64,
+    /// Proposer's external (server reflexive) address
+    pub external_addr: SocketAddr,
+    /// Proposer's internal address, for same-LAN shortcuts
+    pub internal_addr: Option<SocketAddr>,
+}
+
+impl PunchSchedule {
+    /// Propose a punch `lead` from now on the relay's clock
+    #[must_use]
+    pub fn propose(
+        sync: &ClockSync,
+        lead: Duration,
+        external_addr: SocketAddr,
+        internal_addr: Option<SocketAddr>,
+    ) -> Self {
+        Self {
+            punch_at_relay_us: sync.relay_now_us() + lead.as_micros() as u64,
+            external_addr,
+            internal_addr,
+        }
+    }
+
+    /// How long until the punch instant, by this side's clock sync
+    ///
+    /// Returns [`Duration::ZERO`] when the instant has already passed.
+    #[must_use]
+    pub fn time_until(&self, sync: &ClockSync) -> Duration {
+        sync.until(self.punch_at_relay_us)
+    }
+
+    /// Serialize for exchange through the relay
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, crate::relay::RelayError> {
+        bincode::serialize(self).map_err(|e| crate::relay::RelayError::Serialization(e.to_string()))
+    }
+
+    /// Deserialize a schedule received through the relay
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bytes are not a valid schedule.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, crate::relay::RelayError> {
+        bincode::deserialize(bytes)
+            .map_err(|e| crate::relay::RelayError::Deserialization(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_sync_from_exchange() {
+        // Relay answered 10ms ahead of the local midpoint
+        let sync = ClockSync::from_exchange(1_000_000, 1_015_000, 1_010_000);
+        assert_eq!(sync.offset_us, 10_000);
+        assert_eq!(sync.rtt_us, 10_000);
+    }
+
+    #[test]
+    fn test_clock_sync_negative_offset() {
+        // Relay clock behind the local clock
+        let sync = ClockSync::from_exchange(1_000_000, 995_000, 1_010_000);
+        assert_eq!(sync.offset_us, -10_000);
+    }
+
+    #[test]
+    fn test_clock_sync_until_past_instant() {
+        let sync = ClockSync {
+            offset_us: 0,
+            rtt_us: 1_000,
+        };
+        // An instant long past yields zero, not a panic or huge value
+        assert_eq!(sync.until(1), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_punch_schedule_lead() {
+        let sync = ClockSync {
+            offset_us: 0,
+            rtt_us: 1_000,
+        };
+        let external = "203.0.113.1:40000".parse().unwrap();
+        let schedule = PunchSchedule::propose(&sync, Duration::from_millis(500), external, None);
+
+        let remaining = schedule.time_until(&sync);
+        assert!(remaining <= Duration::from_millis(500));
+        assert!(remaining > Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_punch_schedule_roundtrip() {
+        let schedule = PunchSchedule {
+            punch_at_relay_us: 1_234_567,
+            external_addr: "203.0.113.1:40000".parse().unwrap(),
+            internal_addr: Some("192.168.1.10:40000".parse().unwrap()),
+        };
+
+        let bytes = schedule.to_bytes().unwrap();
+        let decoded = PunchSchedule::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, schedule);
+    }
+
+    #[test]
+    fn test_punch_schedule_rejects_garbage() {
+        assert!(PunchSchedule::from_bytes(&[0xFF; 3]).is_err());
+    }
+
+    #[test]
+    fn test_until_compensates_for_offset() {
+        // The same relay instant is sooner for a peer whose relay runs
+        // ahead of its local clock, and later for one it runs behind
+        let target_relay_us = unix_micros() + 1_000_000;
+
+        let in_sync = ClockSync {
+            offset_us: 0,
+            rtt_us: 1_000,
+        };
+        let relay_ahead = ClockSync {
+            offset_us: 500_000,
+            rtt_us: 1_000,
+        };
+        let relay_behind = ClockSync {
+            offset_us: -500_000,
+            rtt_us: 1_000,
+        };
+
+        assert!(relay_ahead.until(target_relay_us) < in_sync.until(target_relay_us));
+        assert!(relay_behind.until(target_relay_us) > in_sync.until(target_relay_us));
+    }
+}
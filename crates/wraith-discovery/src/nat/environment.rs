@@ -0,0 +1,296 @@
+//! Network Environment Detection
+//!
+//! Classifies translation layers that sit beyond the local NAT — NAT64
+//! with 464XLAT and carrier-grade NAT (RFC 6598) — both common on mobile
+//! networks, and derives the traversal strategy that actually has a
+//! chance there: prefer the native IPv6 path when IPv4 is translated,
+//! and go to a relay quickly instead of burning time on IPv4 hole
+//! punching that cannot succeed through a CGN.
+
+use super::types::{NatDetector, NatType};
+use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr};
+
+/// The well-known NAT64 prefix (RFC 6052, 64:ff9b::/96)
+pub const WELL_KNOWN_NAT64_PREFIX: Ipv6Addr = Ipv6Addr::new(0x64, 0xff9b, 0, 0, 0, 0, 0, 0);
+
+/// Traversal strategy derived from the detected environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraversalStrategy {
+    /// Public address, connect directly
+    Direct,
+    /// Ordinary NAT, standard UDP hole punching
+    HolePunch,
+    /// IPv4 is translated (NAT64) or carrier-NATed with IPv6 available:
+    /// use the native IPv6 path and skip IPv4 punching
+    PreferIpv6,
+    /// CGNAT or symmetric NAT without a usable IPv6 path: punching is
+    /// unlikely to succeed, fall back to a relay immediately
+    RelayFirst,
+}
+
+impl std::fmt::Display for TraversalStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Direct => write!(f, "Direct connection"),
+            Self::HolePunch => write!(f, "UDP hole punching"),
+            Self::PreferIpv6 => write!(f, "IPv6 direct (skip IPv4 punching)"),
+            Self::RelayFirst => write!(f, "Relay first (IPv4 punching unlikely to succeed)"),
+        }
+    }
+}
+
+/// Detected network environment
+///
+/// Produced by [`EnvironmentProbe::probe`]; [`NatEnvironment::strategy`]
+/// maps it to the traversal approach connection establishment should
+/// lead with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NatEnvironment {
+    /// NAT type from STUN probing
+    pub nat_type: NatType,
+    /// NAT64 translation prefix, when DNS64 is active (464XLAT network)
+    pub nat64_prefix: Option<Ipv6Addr>,
+    /// Local IPv4 address lies in the RFC 6598 shared range (CGNAT)
+    pub cgnat: bool,
+    /// A globally routable IPv6 source address is available
+    pub ipv6_available: bool,
+}
+
+impl NatEnvironment {
+    /// The traversal strategy this environment calls for
+    #[must_use]
+    pub fn strategy(&self) -> TraversalStrategy {
+        if self.nat_type == NatType::Open && !self.cgnat {
+            return TraversalStrategy::Direct;
+        }
+        // On 464XLAT networks IPv4 only exists through the translator;
+        // dual-stack CGN networks route IPv6 natively while sharing the
+        // IPv4 address across subscribers
+        if self.nat64_prefix.is_some() || (self.cgnat && self.ipv6_available) {
+            return TraversalStrategy::PreferIpv6;
+        }
+        if self.cgnat || self.nat_type == NatType::Symmetric {
+            return TraversalStrategy::RelayFirst;
+        }
+        TraversalStrategy::HolePunch
+    }
+}
+
+/// Is this IPv4 address in the CGNAT shared range (100.64.0.0/10, RFC 6598)?
+///
+/// Carriers hand these out to subscriber devices behind carrier-grade
+/// NAT; seeing one locally means at least one NAT layer is outside our
+/// control and cannot be hole-punched from the inside.
+#[must_use]
+pub fn is_shared_address(ip: &Ipv4Addr) -> bool {
+    let octets = ip.octets();
+    octets[0] == 100 && (octets[1] & 0xC0) == 0x40
+}
+
+/// Extract the /96 NAT64 prefix from a synthesized AAAA record
+///
+/// RFC 7050 discovery resolves `ipv4only.arpa` — a zone with only A
+/// records — so any AAAA answer was synthesized by DNS64 and carries the
+/// translation prefix in its upper 96 bits.
+#[must_use]
+pub fn nat64_prefix_of(addr: &Ipv6Addr) -> Ipv6Addr {
+    let s = addr.segments();
+    Ipv6Addr::new(s[0], s[1], s[2], s[3], s[4], s[5], 0, 0)
+}
+
+/// Network environment prober
+///
+/// Combines STUN-based NAT classification with RFC 7050 NAT64 discovery
+/// and RFC 6598 address inspection. Surfaced to users as `wraith probe`.
+pub struct EnvironmentProbe {
+    detector: NatDetector,
+}
+
+impl EnvironmentProbe {
+    /// Create a prober with the default STUN servers
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            detector: NatDetector::new(),
+        }
+    }
+
+    /// Create a prober with a custom NAT detector
+    #[must_use]
+    pub fn with_detector(detector: NatDetector) -> Self {
+        Self { detector }
+    }
+
+    /// Probe the network environment
+    ///
+    /// Individual signals that cannot be gathered (no network, DNS
+    /// filtered) degrade to their negative default rather than failing
+    /// the probe; NAT classification degrades to [`NatType::Unknown`].
+    pub async fn probe(&self) -> NatEnvironment {
+        let nat_type = self.detector.detect().await.unwrap_or(NatType::Unknown);
+
+        NatEnvironment {
+            nat_type,
+            nat64_prefix: detect_nat64_prefix().await,
+            cgnat: local_ipv4_source().is_some_and(|ip| is_shared_address(&ip)),
+            ipv6_available: has_global_ipv6_source(),
+        }
+    }
+}
+
+impl Default for EnvironmentProbe {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// RFC 7050 NAT64 prefix discovery via DNS64
+///
+/// `ipv4only.arpa` has no AAAA records at the source; an IPv6 answer
+/// means a DNS64 resolver synthesized it and reveals the prefix.
+async fn detect_nat64_prefix() -> Option<Ipv6Addr> {
+    let resolved = tokio::task::spawn_blocking(|| {
+        use std::net::ToSocketAddrs;
+        ("ipv4only.arpa", 443)
+            .to_socket_addrs()
+            .map(Iterator::collect::<Vec<_>>)
+    })
+    .await
+    .ok()?
+    .ok()?;
+
+    resolved.iter().find_map(|addr| match addr {
+        SocketAddr::V6(v6) => Some(nat64_prefix_of(v6.ip())),
+        SocketAddr::V4(_) => None,
+    })
+}
+
+/// The IPv4 source address the OS would use for Internet traffic
+///
+/// A connected UDP socket makes the routing decision without sending
+/// any packets.
+fn local_ipv4_source() -> Option<Ipv4Addr> {
+    let socket = std::net::UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:53").ok()?;
+    match socket.local_addr().ok()? {
+        SocketAddr::V4(v4) => Some(*v4.ip()),
+        SocketAddr::V6(_) => None,
+    }
+}
+
+/// Whether the OS has a globally routable IPv6 source address
+fn has_global_ipv6_source() -> bool {
+    let Ok(socket) = std::net::UdpSocket::bind("[::]:0") else {
+        return false;
+    };
+    if socket.connect("[2001:4860:4860::8888]:53").is_err() {
+        return false;
+    }
+    match socket.local_addr() {
+        Ok(SocketAddr::V6(v6)) => {
+            let ip = v6.ip();
+            let seg0 = ip.segments()[0];
+            // Exclude loopback, link-local (fe80::/10) and unique-local (fc00::/7)
+            !ip.is_loopback() && (seg0 & 0xFFC0) != 0xFE80 && (seg0 & 0xFE00) != 0xFC00
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(
+        nat_type: NatType,
+        nat64_prefix: Option<Ipv6Addr>,
+        cgnat: bool,
+        ipv6_available: bool,
+    ) -> NatEnvironment {
+        NatEnvironment {
+            nat_type,
+            nat64_prefix,
+            cgnat,
+            ipv6_available,
+        }
+    }
+
+    #[test]
+    fn test_shared_address_range() {
+        assert!(is_shared_address(&"100.64.0.1".parse().unwrap()));
+        assert!(is_shared_address(&"100.127.255.255".parse().unwrap()));
+        assert!(!is_shared_address(&"100.63.255.255".parse().unwrap()));
+        assert!(!is_shared_address(&"100.128.0.0".parse().unwrap()));
+        assert!(!is_shared_address(&"192.168.1.1".parse().unwrap()));
+        assert!(!is_shared_address(&"10.0.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_nat64_prefix_extraction() {
+        // Well-known prefix + 192.0.0.170 (the ipv4only.arpa address)
+        let synthesized: Ipv6Addr = "64:ff9b::c000:aa".parse().unwrap();
+        assert_eq!(nat64_prefix_of(&synthesized), WELL_KNOWN_NAT64_PREFIX);
+
+        // Network-specific prefix survives extraction
+        let nsp: Ipv6Addr = "2001:db8:64::c000:aa".parse().unwrap();
+        assert_eq!(
+            nat64_prefix_of(&nsp),
+            "2001:db8:64::".parse::<Ipv6Addr>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_strategy_open_is_direct() {
+        let e = env(NatType::Open, None, false, false);
+        assert_eq!(e.strategy(), TraversalStrategy::Direct);
+    }
+
+    #[test]
+    fn test_strategy_ordinary_nat_punches() {
+        let e = env(NatType::PortRestrictedCone, None, false, false);
+        assert_eq!(e.strategy(), TraversalStrategy::HolePunch);
+    }
+
+    #[test]
+    fn test_strategy_nat64_prefers_ipv6() {
+        let e = env(
+            NatType::Symmetric,
+            Some(WELL_KNOWN_NAT64_PREFIX),
+            false,
+            true,
+        );
+        assert_eq!(e.strategy(), TraversalStrategy::PreferIpv6);
+    }
+
+    #[test]
+    fn test_strategy_cgnat_with_ipv6_prefers_ipv6() {
+        let e = env(NatType::Symmetric, None, true, true);
+        assert_eq!(e.strategy(), TraversalStrategy::PreferIpv6);
+    }
+
+    #[test]
+    fn test_strategy_cgnat_without_ipv6_relays() {
+        let e = env(NatType::RestrictedCone, None, true, false);
+        assert_eq!(e.strategy(), TraversalStrategy::RelayFirst);
+    }
+
+    #[test]
+    fn test_strategy_symmetric_relays() {
+        let e = env(NatType::Symmetric, None, false, false);
+        assert_eq!(e.strategy(), TraversalStrategy::RelayFirst);
+    }
+
+    #[test]
+    fn test_strategy_cgnat_overrides_open() {
+        // A "public" view through a CGN is still shared address space
+        let e = env(NatType::Open, None, true, false);
+        assert_eq!(e.strategy(), TraversalStrategy::RelayFirst);
+    }
+
+    #[test]
+    fn test_traversal_strategy_display() {
+        assert_eq!(TraversalStrategy::Direct.to_string(), "Direct connection");
+        assert!(TraversalStrategy::PreferIpv6.to_string().contains("IPv6"));
+        assert!(TraversalStrategy::RelayFirst.to_string().contains("Relay"));
+    }
+}
@@ -3,6 +3,7 @@
 //! This module implements UDP hole punching for establishing direct peer-to-peer
 //! connections through NAT devices using the simultaneous open technique.
 
+use crate::nat::coordination::{ClockSync, PunchSchedule};
 use std::net::SocketAddr;
 use std::time::Duration;
 use tokio::net::UdpSocket;
@@ -86,6 +87,33 @@ impl HolePuncher {
         }
     }
 
+    /// Perform a time-synchronized hole punch against an agreed schedule
+    ///
+    /// Waits until the schedule's punch instant (converted to the local
+    /// clock through this side's [`ClockSync`]) and then runs the normal
+    /// strategies against the addresses in the schedule. When both peers
+    /// call this with the same schedule and their own relay syncs, they
+    /// transmit within roughly half a round-trip of each other — tight
+    /// enough for the inbound/outbound mapping race on port-restricted
+    /// NATs. See [`crate::nat::coordination`] for how schedules are
+    /// agreed.
+    ///
+    /// # Errors
+    ///
+    /// Same failure modes as [`HolePuncher::punch`].
+    pub async fn punch_at(
+        &self,
+        schedule: &PunchSchedule,
+        sync: &ClockSync,
+    ) -> Result<SocketAddr, PunchError> {
+        let wait = schedule.time_until(sync);
+        if wait > Duration::ZERO {
+            tokio::time::sleep(wait).await;
+        }
+        self.punch(schedule.external_addr, schedule.internal_addr)
+            .await
+    }
+
     /// Try direct connection to peer's external address
     async fn try_direct(&self, peer: SocketAddr) -> Result<SocketAddr, PunchError> {
         for _ in 0..MAX_PROBE_ATTEMPTS {
@@ -305,6 +333,51 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_scheduled_loopback_punch() {
+        use crate::nat::coordination::unix_micros;
+
+        let puncher1 = HolePuncher::new("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let addr1 = puncher1.local_addr().unwrap();
+
+        let puncher2 = HolePuncher::new("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let addr2 = puncher2.local_addr().unwrap();
+
+        // Both sides perfectly synced (same host clock) punch 100 ms out
+        let sync = ClockSync {
+            offset_us: 0,
+            rtt_us: 100,
+        };
+        let punch_at = unix_micros() + 100_000;
+        let schedule1 = PunchSchedule {
+            punch_at_relay_us: punch_at,
+            external_addr: addr2,
+            internal_addr: Some(addr2),
+        };
+        let schedule2 = PunchSchedule {
+            punch_at_relay_us: punch_at,
+            external_addr: addr1,
+            internal_addr: Some(addr1),
+        };
+
+        let punch1 = puncher1.punch_at(&schedule1, &sync);
+        let punch2 = puncher2.punch_at(&schedule2, &sync);
+
+        // At least one should succeed (mirrors test_loopback_punch)
+        tokio::select! {
+            result = punch1 => {
+                assert!(result.is_ok() || matches!(result, Err(PunchError::Timeout)));
+            }
+            result = punch2 => {
+                assert!(result.is_ok() || matches!(result, Err(PunchError::Timeout)));
+            }
+        }
+    }
+
     #[tokio::test]
     async fn test_maintain_hole() {
         let puncher = HolePuncher::new("127.0.0.1:0".parse().unwrap())
@@ -3,8 +3,11 @@
 //! This module implements ICE (Interactive Connectivity Establishment) candidate
 //! gathering for peer-to-peer connection establishment.
 
-use super::stun::StunClient;
+use super::stun::{StunAuthentication, StunClient};
+use super::turn::TurnClient;
 use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
 
 /// ICE candidate type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -57,7 +60,11 @@ impl IceCandidate {
             foundation: Self::compute_foundation(address, CandidateType::Host),
             component_id: 1,
             transport: "udp".to_string(),
-            priority: Self::compute_priority(CandidateType::Host, 65535, 1),
+            priority: Self::compute_priority(
+                CandidateType::Host,
+                Self::local_preference(&address),
+                1,
+            ),
             address,
             candidate_type: CandidateType::Host,
             related_address: None,
@@ -71,7 +78,11 @@ impl IceCandidate {
             foundation: Self::compute_foundation(address, CandidateType::ServerReflexive),
             component_id: 1,
             transport: "udp".to_string(),
-            priority: Self::compute_priority(CandidateType::ServerReflexive, 65535, 1),
+            priority: Self::compute_priority(
+                CandidateType::ServerReflexive,
+                Self::local_preference(&address),
+                1,
+            ),
             address,
             candidate_type: CandidateType::ServerReflexive,
             related_address: Some(base),
@@ -85,7 +96,11 @@ impl IceCandidate {
             foundation: Self::compute_foundation(address, CandidateType::Relay),
             component_id: 1,
             transport: "udp".to_string(),
-            priority: Self::compute_priority(CandidateType::Relay, 65535, 1),
+            priority: Self::compute_priority(
+                CandidateType::Relay,
+                Self::local_preference(&address),
+                1,
+            ),
             address,
             candidate_type: CandidateType::Relay,
             related_address: Some(base),
@@ -105,6 +120,15 @@ impl IceCandidate {
             .collect::<String>()
     }
 
+    /// Local preference for an address family (RFC 8421)
+    ///
+    /// IPv6 candidates are preferred over IPv4 so that dual-stack peers
+    /// converge on the v6 path when both work; the v4 candidate still
+    /// outranks any lower candidate type.
+    fn local_preference(addr: &SocketAddr) -> u32 {
+        if addr.is_ipv6() { 65535 } else { 65534 }
+    }
+
     /// Compute priority (RFC 8445 Section 5.1.2)
     ///
     /// Priority = (2^24) * (type preference) + (2^8) * (local preference) + (256 - component ID)
@@ -162,9 +186,28 @@ impl From<IceCandidate> for Candidate {
     }
 }
 
+/// A TURN server the gatherer may allocate relayed candidates from
+#[derive(Clone, Debug)]
+pub struct TurnServerConfig {
+    /// TURN server address
+    pub address: SocketAddr,
+    /// Long-term credentials; the realm is learned from the server's
+    /// 401 challenge, so it may be created without one
+    pub auth: StunAuthentication,
+}
+
 /// ICE candidate gatherer
 pub struct IceGatherer {
     stun_servers: Vec<SocketAddr>,
+    turn_servers: Vec<TurnServerConfig>,
+    /// Live TURN clients holding the allocations behind relayed
+    /// candidates
+    ///
+    /// A relayed candidate is only routable while its allocation lives,
+    /// so clients are retained here after gathering. Dropping the
+    /// gatherer (or calling [`Self::release_relay_allocations`]) lets
+    /// the server expire them.
+    turn_allocations: Arc<Mutex<Vec<TurnClient>>>,
 }
 
 impl IceGatherer {
@@ -178,11 +221,19 @@ impl IceGatherer {
         Self {
             stun_servers: vec![
                 // Placeholder STUN server addresses
-                // In production, resolve: stun.l.google.com:19302
+                // In production, resolve: stun.l.google.com:19302 (A + AAAA)
                 "1.1.1.1:3478".parse().expect("valid STUN server"),
-                // In production, resolve: stun1.l.google.com:19302
+                // In production, resolve: stun1.l.google.com:19302 (A + AAAA)
                 "8.8.8.8:3478".parse().expect("valid STUN server"),
+                "[2606:4700:4700::1111]:3478"
+                    .parse()
+                    .expect("valid STUN server"),
+                "[2001:4860:4860::8888]:3478"
+                    .parse()
+                    .expect("valid STUN server"),
             ],
+            turn_servers: Vec::new(),
+            turn_allocations: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -191,9 +242,21 @@ impl IceGatherer {
     pub fn with_stun_servers(servers: Vec<SocketAddr>) -> Self {
         Self {
             stun_servers: servers,
+            turn_servers: Vec::new(),
+            turn_allocations: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
+    /// Add TURN servers to allocate relayed candidates from
+    ///
+    /// Without any, gathering produces only host and server reflexive
+    /// candidates.
+    #[must_use]
+    pub fn with_turn_servers(mut self, servers: Vec<TurnServerConfig>) -> Self {
+        self.turn_servers = servers;
+        self
+    }
+
     /// Gather all candidates for a local address
     ///
     /// Returns host, server reflexive, and relay candidates (if available).
@@ -211,9 +274,19 @@ impl IceGatherer {
         let host_cand = IceCandidate::host(local_addr);
         candidates.push(host_cand.into());
 
-        // Gather server reflexive candidates from STUN
+        // Gather server reflexive candidates from STUN servers of the
+        // same address family as the base (a v4 socket cannot learn its
+        // v6 mapping and vice versa)
         for stun_server in &self.stun_servers {
-            if let Ok(client) = StunClient::bind("0.0.0.0:0").await {
+            if stun_server.is_ipv6() != local_addr.is_ipv6() {
+                continue;
+            }
+            let bind_addr = if local_addr.is_ipv6() {
+                "[::]:0"
+            } else {
+                "0.0.0.0:0"
+            };
+            if let Ok(client) = StunClient::bind(bind_addr).await {
                 if let Ok(mapped_addr) = client.get_mapped_address(*stun_server).await {
                     // Only add if different from host candidate
                     if mapped_addr != local_addr {
@@ -224,12 +297,57 @@ impl IceGatherer {
             }
         }
 
-        // Note: Relay candidates would be added here if TURN is implemented
-        // For now, we only support host and server reflexive
+        // Allocate relayed candidates from the configured TURN servers.
+        // The client holding each allocation is retained so the relayed
+        // address stays routable after gathering completes
+        for turn_server in &self.turn_servers {
+            if turn_server.address.is_ipv6() != local_addr.is_ipv6() {
+                continue;
+            }
+            let bind_addr = if local_addr.is_ipv6() {
+                "[::]:0"
+            } else {
+                "0.0.0.0:0"
+            };
+            let mut client =
+                match TurnClient::bind(bind_addr, turn_server.address, turn_server.auth.clone())
+                    .await
+                {
+                    Ok(client) => client,
+                    Err(e) => {
+                        tracing::debug!("TURN client bind for {} failed: {e}", turn_server.address);
+                        continue;
+                    }
+                };
+            match client.allocate().await {
+                Ok(relayed_addr) => {
+                    let relay_cand = IceCandidate::relay(relayed_addr, local_addr);
+                    candidates.push(relay_cand.into());
+                    self.turn_allocations.lock().await.push(client);
+                }
+                Err(e) => {
+                    tracing::debug!("TURN allocation from {} failed: {e}", turn_server.address);
+                }
+            }
+        }
 
         Ok(candidates)
     }
 
+    /// Release every TURN allocation backing previously gathered relayed
+    /// candidates
+    ///
+    /// Best-effort: an allocation whose Refresh(0) fails is dropped
+    /// anyway and left for the server to expire.
+    pub async fn release_relay_allocations(&self) {
+        let mut clients = self.turn_allocations.lock().await;
+        for mut client in clients.drain(..) {
+            if let Err(e) = client.deallocate().await {
+                tracing::debug!("TURN deallocation failed: {e}");
+            }
+        }
+    }
+
     /// Gather candidates for all local interfaces
     ///
     /// # Errors
@@ -259,19 +377,21 @@ impl IceGatherer {
 
     /// Get local network interface addresses
     fn get_local_interfaces(&self) -> Result<Vec<SocketAddr>, std::io::Error> {
-        use std::net::{IpAddr, Ipv4Addr};
+        use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
-        // Simplified: just return common bind addresses
+        // Simplified: just return common bind addresses for both families
         // In production, would enumerate actual network interfaces
         Ok(vec![
             SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 0),
             SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1)), 0),
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::UNSPECIFIED), 0),
+            SocketAddr::new(IpAddr::V6(Ipv6Addr::LOCALHOST), 0),
         ])
     }
 
     /// Sort candidates by priority (descending)
     pub fn sort_by_priority(candidates: &mut [Candidate]) {
-        candidates.sort_by(|a, b| b.priority.cmp(&a.priority));
+        candidates.sort_by_key(|c| std::cmp::Reverse(c.priority));
     }
 }
 
@@ -292,6 +412,55 @@ mod tests {
         assert_eq!(CandidateType::Relay.to_string(), "relay");
     }
 
+    #[tokio::test]
+    async fn test_gather_includes_turn_relayed_candidate() {
+        use crate::nat::stun::{StunAttribute, StunMessage, StunMessageClass};
+        use tokio::net::UdpSocket;
+
+        // Minimal TURN responder: answer every request with a success
+        // response carrying a fixed relayed address
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = server.local_addr().unwrap();
+        let relayed: SocketAddr = "127.0.0.1:45678".parse().unwrap();
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 1500];
+            while let Ok((len, from)) = server.recv_from(&mut buf).await {
+                let Ok(request) = StunMessage::decode(&buf[..len]) else {
+                    continue;
+                };
+                let mut response = StunMessage {
+                    message_type: request.message_type,
+                    message_class: StunMessageClass::SuccessResponse,
+                    transaction_id: request.transaction_id,
+                    attributes: Vec::new(),
+                };
+                response.add_attribute(StunAttribute::XorRelayedAddress(relayed));
+                response.add_attribute(StunAttribute::Lifetime(600));
+                let _ = server.send_to(&response.encode(), from).await;
+            }
+        });
+
+        // Credentials carry the realm up front, skipping the 401 roundtrip
+        let gatherer =
+            IceGatherer::with_stun_servers(Vec::new()).with_turn_servers(vec![TurnServerConfig {
+                address: server_addr,
+                auth: StunAuthentication::new("user", "pass", Some("wraith.test".to_string())),
+            }]);
+
+        let local: SocketAddr = "127.0.0.1:7000".parse().unwrap();
+        let candidates = gatherer.gather(local).await.unwrap();
+        let relay = candidates
+            .iter()
+            .find(|c| c.candidate_type == CandidateType::Relay)
+            .expect("relayed candidate gathered");
+        assert_eq!(relay.address, relayed);
+
+        // The client behind the candidate stays alive until released
+        assert_eq!(gatherer.turn_allocations.lock().await.len(), 1);
+        gatherer.release_relay_allocations().await;
+        assert!(gatherer.turn_allocations.lock().await.is_empty());
+    }
+
     #[test]
     fn test_host_candidate() {
         let addr: SocketAddr = "192.168.1.100:5000".parse().unwrap();
@@ -375,7 +544,7 @@ mod tests {
     #[test]
     fn test_ice_gatherer_creation() {
         let gatherer = IceGatherer::new();
-        assert_eq!(gatherer.stun_servers.len(), 2);
+        assert_eq!(gatherer.stun_servers.len(), 4);
 
         let custom_servers = vec!["1.1.1.1:3478".parse().unwrap()];
         let gatherer = IceGatherer::with_stun_servers(custom_servers);
@@ -461,7 +630,7 @@ mod tests {
     #[test]
     fn test_ice_gatherer_default() {
         let gatherer = IceGatherer::default();
-        assert_eq!(gatherer.stun_servers.len(), 2);
+        assert_eq!(gatherer.stun_servers.len(), 4);
     }
 
     #[test]
@@ -513,6 +682,26 @@ mod tests {
         assert!(sdp.contains("5000"));
     }
 
+    #[test]
+    fn test_ipv6_preferred_within_type() {
+        let v6: SocketAddr = "[2001:db8::1]:5000".parse().unwrap();
+        let v4: SocketAddr = "192.168.1.100:5000".parse().unwrap();
+
+        // A v6 host candidate outranks a v4 host candidate, but candidate
+        // type still dominates: a v4 host beats a v6 srflx
+        assert!(IceCandidate::host(v6).priority > IceCandidate::host(v4).priority);
+        assert!(IceCandidate::host(v4).priority > IceCandidate::server_reflexive(v6, v6).priority);
+    }
+
+    #[test]
+    fn test_local_interfaces_dual_stack() {
+        let gatherer = IceGatherer::new();
+        let interfaces = gatherer.get_local_interfaces().unwrap();
+
+        assert!(interfaces.iter().any(SocketAddr::is_ipv4));
+        assert!(interfaces.iter().any(SocketAddr::is_ipv6));
+    }
+
     #[test]
     fn test_foundation_length() {
         let addr: SocketAddr = "192.168.1.100:5000".parse().unwrap();
@@ -0,0 +1,244 @@
+//! Adaptive NAT Keepalive
+//!
+//! Instead of a fixed keepalive interval, this module probes the NAT binding
+//! timeout by progressively lengthening the idle gap between keepalives until
+//! a keepalive fails to traverse the binding, then settles just below the
+//! longest gap known to work. This minimizes wakeups and cover packets on
+//! mobile devices while still keeping NAT bindings alive.
+//!
+//! The controller is a pure state machine: the caller sends the actual
+//! keepalive packet after each interval and reports whether the binding
+//! survived via [`AdaptiveKeepalive::on_probe_success`] or
+//! [`AdaptiveKeepalive::on_probe_failure`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use wraith_discovery::nat::AdaptiveKeepalive;
+//! use std::time::Duration;
+//!
+//! let mut keepalive = AdaptiveKeepalive::default();
+//!
+//! // Sleep for `next_interval()`, send a keepalive, then report the outcome.
+//! let gap = keepalive.next_interval();
+//! keepalive.on_probe_success(); // binding survived, try a longer gap
+//! assert!(keepalive.next_interval() > gap);
+//! ```
+
+use std::time::Duration;
+
+/// Multiplier applied to the probe interval after each successful probe
+const PROBE_GROWTH_FACTOR: f64 = 1.5;
+
+/// Fraction of the longest confirmed interval used once settled
+///
+/// Settling below the observed binding lifetime leaves margin for NAT
+/// timers that are close to, but not exactly, the probed gap.
+const SETTLE_SAFETY_FACTOR: f64 = 0.8;
+
+/// Adaptive keepalive controller that discovers the NAT binding lifetime.
+///
+/// Starts from a conservative interval and grows the idle gap geometrically
+/// while keepalives keep traversing the binding. On the first failure (or on
+/// reaching the configured maximum), it settles at a safety margin below the
+/// longest confirmed gap.
+#[derive(Debug, Clone)]
+pub struct AdaptiveKeepalive {
+    /// Shortest interval ever used (also the post-failure floor)
+    min_interval: Duration,
+    /// Longest interval worth probing
+    max_interval: Duration,
+    /// Longest interval confirmed to keep the binding alive
+    confirmed: Duration,
+    /// Interval currently being probed
+    probe: Duration,
+    /// Whether probing has finished
+    settled: bool,
+}
+
+impl AdaptiveKeepalive {
+    /// Create a new controller probing between `min_interval` and `max_interval`
+    ///
+    /// Probing starts at `min_interval`; common NAT binding lifetimes range
+    /// from ~30 seconds (aggressive carrier-grade NAT) to several minutes.
+    #[must_use]
+    pub fn new(min_interval: Duration, max_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            max_interval: max_interval.max(min_interval),
+            confirmed: min_interval,
+            probe: min_interval,
+            settled: false,
+        }
+    }
+
+    /// Get the idle gap to wait before sending the next keepalive
+    #[must_use]
+    pub fn next_interval(&self) -> Duration {
+        if self.settled {
+            self.settled_interval()
+        } else {
+            self.probe
+        }
+    }
+
+    /// Report that the binding survived the last idle gap
+    ///
+    /// Confirms the probed interval and lengthens the next probe. When the
+    /// probe reaches the configured maximum, the controller settles there.
+    pub fn on_probe_success(&mut self) {
+        if self.settled {
+            return;
+        }
+
+        self.confirmed = self.confirmed.max(self.probe);
+
+        if self.probe >= self.max_interval {
+            // Binding outlives anything we care to probe - settle at max
+            self.settled = true;
+            return;
+        }
+
+        self.probe = self
+            .probe
+            .mul_f64(PROBE_GROWTH_FACTOR)
+            .min(self.max_interval);
+    }
+
+    /// Report that a keepalive failed to traverse after the last idle gap
+    ///
+    /// The binding expired somewhere between the confirmed interval and the
+    /// probed one, so settle just below the confirmed lifetime. Failures
+    /// after settling (e.g. transient loss) reset probing from the confirmed
+    /// interval so a genuinely shortened lifetime is re-learned.
+    pub fn on_probe_failure(&mut self) {
+        if self.settled {
+            // Re-probe from the last known-good interval
+            self.settled = false;
+            self.probe = self.confirmed;
+            self.confirmed = self.min_interval;
+            return;
+        }
+
+        self.settled = true;
+    }
+
+    /// Whether probing has converged on a binding lifetime
+    #[must_use]
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+
+    /// The interval used once settled (safety margin below confirmed lifetime)
+    #[must_use]
+    pub fn settled_interval(&self) -> Duration {
+        self.confirmed
+            .mul_f64(SETTLE_SAFETY_FACTOR)
+            .max(self.min_interval)
+    }
+
+    /// Longest idle gap confirmed to keep the binding alive
+    #[must_use]
+    pub fn confirmed_interval(&self) -> Duration {
+        self.confirmed
+    }
+}
+
+impl Default for AdaptiveKeepalive {
+    fn default() -> Self {
+        // 15s floor covers aggressive CGNAT; 10min ceiling covers home routers
+        Self::new(Duration::from_secs(15), Duration::from_secs(600))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_starts_at_min_interval() {
+        let keepalive = AdaptiveKeepalive::new(Duration::from_secs(10), Duration::from_secs(300));
+
+        assert_eq!(keepalive.next_interval(), Duration::from_secs(10));
+        assert!(!keepalive.is_settled());
+    }
+
+    #[test]
+    fn test_probe_grows_on_success() {
+        let mut keepalive =
+            AdaptiveKeepalive::new(Duration::from_secs(10), Duration::from_secs(300));
+
+        keepalive.on_probe_success();
+        assert_eq!(keepalive.next_interval(), Duration::from_secs(15));
+
+        keepalive.on_probe_success();
+        assert!(keepalive.next_interval() > Duration::from_secs(15));
+    }
+
+    #[test]
+    fn test_settles_below_confirmed_on_failure() {
+        let mut keepalive =
+            AdaptiveKeepalive::new(Duration::from_secs(10), Duration::from_secs(300));
+
+        // 10s and 15s gaps survive, 22.5s gap kills the binding
+        keepalive.on_probe_success();
+        keepalive.on_probe_success();
+        keepalive.on_probe_failure();
+
+        assert!(keepalive.is_settled());
+        // Settled interval must be below the last confirmed gap (15s)
+        assert!(keepalive.settled_interval() <= Duration::from_secs(15));
+        // But never below the configured floor
+        assert!(keepalive.settled_interval() >= Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_settles_at_max_interval() {
+        let mut keepalive =
+            AdaptiveKeepalive::new(Duration::from_secs(10), Duration::from_secs(30));
+
+        // Keep succeeding until the probe is clamped at max
+        for _ in 0..10 {
+            keepalive.on_probe_success();
+        }
+
+        assert!(keepalive.is_settled());
+        assert_eq!(keepalive.confirmed_interval(), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_failure_while_settled_restarts_probing() {
+        let mut keepalive =
+            AdaptiveKeepalive::new(Duration::from_secs(10), Duration::from_secs(300));
+
+        keepalive.on_probe_success();
+        keepalive.on_probe_failure();
+        assert!(keepalive.is_settled());
+
+        // Network conditions changed - binding now expires faster
+        keepalive.on_probe_failure();
+        assert!(!keepalive.is_settled());
+
+        // Probing resumes from the previously confirmed interval (10s)
+        assert_eq!(keepalive.next_interval(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_immediate_failure_settles_at_floor() {
+        let mut keepalive =
+            AdaptiveKeepalive::new(Duration::from_secs(10), Duration::from_secs(300));
+
+        // Even the minimum gap failed - settle at the floor
+        keepalive.on_probe_failure();
+
+        assert!(keepalive.is_settled());
+        assert_eq!(keepalive.settled_interval(), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_default_bounds() {
+        let keepalive = AdaptiveKeepalive::default();
+
+        assert_eq!(keepalive.next_interval(), Duration::from_secs(15));
+    }
+}
@@ -7,6 +7,7 @@
 //!
 //! - **NAT Type Detection**: Identifies the type of NAT device (Full Cone, Restricted Cone, etc.)
 //! - **STUN Client**: Implements STUN protocol (RFC 5389) for server reflexive address discovery
+//! - **TURN Client**: Implements TURN protocol (RFC 8656) for relayed paths behind symmetric NAT
 //! - **ICE Candidate Gathering**: Collects host, server reflexive, and relay candidates
 //! - **UDP Hole Punching**: Establishes direct connections through NAT using simultaneous open
 //!
@@ -42,16 +43,24 @@
 //! # }
 //! ```
 
+pub mod coordination;
+pub mod environment;
 pub mod hole_punch;
 pub mod ice;
+pub mod keepalive;
 pub mod stun;
+pub mod turn;
 pub mod types;
 
 // Re-exports
+pub use coordination::{ClockSync, PunchSchedule};
+pub use environment::{EnvironmentProbe, NatEnvironment, TraversalStrategy};
 pub use hole_punch::{HolePuncher, PunchError};
-pub use ice::{Candidate, CandidateType, IceCandidate, IceGatherer};
+pub use ice::{Candidate, CandidateType, IceCandidate, IceGatherer, TurnServerConfig};
+pub use keepalive::AdaptiveKeepalive;
 pub use stun::{
     StunAttribute, StunAuthentication, StunClient, StunError, StunMessage, StunMessageClass,
     StunMessageType, StunRateLimiter,
 };
+pub use turn::{Allocation, TurnClient, TurnError};
 pub use types::{NatDetector, NatError, NatType};
@@ -66,6 +66,18 @@ pub enum StunMessageClass {
 pub enum StunMessageType {
     /// Binding request/response
     Binding,
+    /// TURN Allocate (RFC 8656 Section 7)
+    Allocate,
+    /// TURN Refresh (RFC 8656 Section 8)
+    Refresh,
+    /// TURN Send indication (RFC 8656 Section 11.1)
+    Send,
+    /// TURN Data indication (RFC 8656 Section 11.5)
+    Data,
+    /// TURN CreatePermission (RFC 8656 Section 9)
+    CreatePermission,
+    /// TURN ChannelBind (RFC 8656 Section 12)
+    ChannelBind,
 }
 
 impl StunMessageType {
@@ -83,6 +95,12 @@ impl StunMessageType {
     fn encode(&self, class: StunMessageClass) -> u16 {
         let method = match self {
             Self::Binding => 0x0001,
+            Self::Allocate => 0x0003,
+            Self::Refresh => 0x0004,
+            Self::Send => 0x0006,
+            Self::Data => 0x0007,
+            Self::CreatePermission => 0x0008,
+            Self::ChannelBind => 0x0009,
         };
 
         let class_bits = match class {
@@ -155,6 +173,19 @@ impl StunAuthentication {
         }
     }
 
+    /// Derive credentials with a realm learned from a server challenge
+    ///
+    /// Used by the TURN client when a 401 response supplies the realm
+    /// for long-term credentials.
+    #[must_use]
+    pub fn with_realm(&self, realm: String) -> Self {
+        Self {
+            username: self.username.clone(),
+            password: self.password.clone(),
+            realm: Some(realm),
+        }
+    }
+
     /// Derive HMAC key for MESSAGE-INTEGRITY
     ///
     /// For long-term credentials: MD5(username:realm:password)
@@ -279,6 +310,24 @@ pub enum StunAttribute {
     Username(String),
     /// Message integrity (0x0008) - HMAC-SHA1
     MessageIntegrity([u8; 20]),
+    /// Error code (0x0009) - class * 100 + number, with reason phrase
+    ErrorCode(u16, String),
+    /// Channel number (0x000C) - TURN channel binding (RFC 8656)
+    ChannelNumber(u16),
+    /// Lifetime (0x000D) - TURN allocation lifetime in seconds (RFC 8656)
+    Lifetime(u32),
+    /// XOR-Peer address (0x0012) - TURN peer address (RFC 8656)
+    XorPeerAddress(SocketAddr),
+    /// Data (0x0013) - TURN relayed application data (RFC 8656)
+    Data(Vec<u8>),
+    /// Realm (0x0014) - long-term credential realm
+    Realm(String),
+    /// Nonce (0x0015) - server-provided nonce for long-term credentials
+    Nonce(Vec<u8>),
+    /// XOR-Relayed address (0x0016) - TURN relayed transport address (RFC 8656)
+    XorRelayedAddress(SocketAddr),
+    /// Requested transport (0x0019) - TURN allocation transport protocol
+    RequestedTransport(u8),
     /// Software identifier (0x8022)
     Software(String),
     /// Fingerprint (0x8028) - CRC-32
@@ -294,6 +343,15 @@ impl StunAttribute {
             Self::MappedAddress(_) => 0x0001,
             Self::Username(_) => 0x0006,
             Self::MessageIntegrity(_) => 0x0008,
+            Self::ErrorCode(_, _) => 0x0009,
+            Self::ChannelNumber(_) => 0x000C,
+            Self::Lifetime(_) => 0x000D,
+            Self::XorPeerAddress(_) => 0x0012,
+            Self::Data(_) => 0x0013,
+            Self::Realm(_) => 0x0014,
+            Self::Nonce(_) => 0x0015,
+            Self::XorRelayedAddress(_) => 0x0016,
+            Self::RequestedTransport(_) => 0x0019,
             Self::XorMappedAddress(_) => 0x0020,
             Self::Software(_) => 0x8022,
             Self::Fingerprint(_) => 0x8028,
@@ -326,38 +384,28 @@ impl StunAttribute {
 
     fn encode_value(&self, transaction_id: &[u8; 12]) -> Vec<u8> {
         match self {
-            Self::XorMappedAddress(addr) => {
-                let mut value = Vec::new();
-                value.push(0); // Reserved
-                value.push(if addr.is_ipv4() { 0x01 } else { 0x02 });
-
-                // XOR port with most significant 16 bits of magic cookie
-                let xor_port = addr.port() ^ (MAGIC_COOKIE >> 16) as u16;
-                value.extend_from_slice(&xor_port.to_be_bytes());
-
-                // XOR address with magic cookie (+ transaction ID for IPv6)
-                match addr.ip() {
-                    std::net::IpAddr::V4(ipv4) => {
-                        let ip_bytes = ipv4.octets();
-                        let magic_bytes = MAGIC_COOKIE.to_be_bytes();
-                        for i in 0..4 {
-                            value.push(ip_bytes[i] ^ magic_bytes[i]);
-                        }
-                    }
-                    std::net::IpAddr::V6(ipv6) => {
-                        let ip_bytes = ipv6.octets();
-                        let mut xor_key = MAGIC_COOKIE.to_be_bytes().to_vec();
-                        xor_key.extend_from_slice(transaction_id);
-                        for i in 0..16 {
-                            value.push(ip_bytes[i] ^ xor_key[i]);
-                        }
-                    }
-                }
-
-                value
-            }
+            Self::XorMappedAddress(addr)
+            | Self::XorPeerAddress(addr)
+            | Self::XorRelayedAddress(addr) => encode_xor_address(*addr, transaction_id),
             Self::Username(u) => u.as_bytes().to_vec(),
             Self::MessageIntegrity(hmac) => hmac.to_vec(),
+            Self::ErrorCode(code, reason) => {
+                let mut value = vec![0, 0]; // Reserved
+                value.push((code / 100) as u8); // Class
+                value.push((code % 100) as u8); // Number
+                value.extend_from_slice(reason.as_bytes());
+                value
+            }
+            Self::ChannelNumber(n) => {
+                let mut value = n.to_be_bytes().to_vec();
+                value.extend_from_slice(&[0, 0]); // RFFU
+                value
+            }
+            Self::Lifetime(secs) => secs.to_be_bytes().to_vec(),
+            Self::Data(data) => data.clone(),
+            Self::Realm(r) => r.as_bytes().to_vec(),
+            Self::Nonce(n) => n.clone(),
+            Self::RequestedTransport(proto) => vec![*proto, 0, 0, 0],
             Self::Software(s) => s.as_bytes().to_vec(),
             Self::Fingerprint(f) => f.to_be_bytes().to_vec(),
             Self::Unknown(_, data) => data.clone(),
@@ -382,44 +430,63 @@ impl StunAttribute {
                 hmac.copy_from_slice(value);
                 Ok(Self::MessageIntegrity(hmac))
             }
-            0x0020 => {
-                // XOR-MAPPED-ADDRESS
+            0x0009 => {
+                // ERROR-CODE
                 if value.len() < 4 {
                     return Err(StunError::InvalidAttribute);
                 }
-
-                let family = value[1];
-                let xor_port = u16::from_be_bytes([value[2], value[3]]);
-                let port = xor_port ^ (MAGIC_COOKIE >> 16) as u16;
-
-                let addr = if family == 0x01 {
-                    // IPv4
-                    if value.len() < 8 {
-                        return Err(StunError::InvalidAttribute);
-                    }
-                    let magic_bytes = MAGIC_COOKIE.to_be_bytes();
-                    let mut ip_bytes = [0u8; 4];
-                    for i in 0..4 {
-                        ip_bytes[i] = value[4 + i] ^ magic_bytes[i];
-                    }
-                    let ip = std::net::Ipv4Addr::from(ip_bytes);
-                    SocketAddr::new(ip.into(), port)
-                } else {
-                    // IPv6
-                    if value.len() < 20 {
-                        return Err(StunError::InvalidAttribute);
-                    }
-                    let mut xor_key = MAGIC_COOKIE.to_be_bytes().to_vec();
-                    xor_key.extend_from_slice(transaction_id);
-                    let mut ip_bytes = [0u8; 16];
-                    for i in 0..16 {
-                        ip_bytes[i] = value[4 + i] ^ xor_key[i];
-                    }
-                    let ip = std::net::Ipv6Addr::from(ip_bytes);
-                    SocketAddr::new(ip.into(), port)
-                };
-
-                Ok(Self::XorMappedAddress(addr))
+                let code = u16::from(value[2] & 0x07) * 100 + u16::from(value[3]);
+                let reason = String::from_utf8_lossy(&value[4..]).to_string();
+                Ok(Self::ErrorCode(code, reason))
+            }
+            0x000C => {
+                // CHANNEL-NUMBER
+                if value.len() < 2 {
+                    return Err(StunError::InvalidAttribute);
+                }
+                Ok(Self::ChannelNumber(u16::from_be_bytes([
+                    value[0], value[1],
+                ])))
+            }
+            0x000D => {
+                // LIFETIME
+                if value.len() != 4 {
+                    return Err(StunError::InvalidAttribute);
+                }
+                let secs = u32::from_be_bytes([value[0], value[1], value[2], value[3]]);
+                Ok(Self::Lifetime(secs))
+            }
+            0x0012 => {
+                // XOR-PEER-ADDRESS
+                decode_xor_address(value, transaction_id).map(Self::XorPeerAddress)
+            }
+            0x0013 => {
+                // DATA
+                Ok(Self::Data(value.to_vec()))
+            }
+            0x0014 => {
+                // REALM
+                let s = String::from_utf8_lossy(value).to_string();
+                Ok(Self::Realm(s))
+            }
+            0x0015 => {
+                // NONCE
+                Ok(Self::Nonce(value.to_vec()))
+            }
+            0x0016 => {
+                // XOR-RELAYED-ADDRESS
+                decode_xor_address(value, transaction_id).map(Self::XorRelayedAddress)
+            }
+            0x0019 => {
+                // REQUESTED-TRANSPORT
+                if value.len() != 4 {
+                    return Err(StunError::InvalidAttribute);
+                }
+                Ok(Self::RequestedTransport(value[0]))
+            }
+            0x0020 => {
+                // XOR-MAPPED-ADDRESS
+                decode_xor_address(value, transaction_id).map(Self::XorMappedAddress)
             }
             0x8022 => {
                 // SOFTWARE
@@ -439,6 +506,78 @@ impl StunAttribute {
     }
 }
 
+/// Encode a socket address in the XOR'd format shared by XOR-MAPPED-ADDRESS,
+/// XOR-PEER-ADDRESS, and XOR-RELAYED-ADDRESS (RFC 5389 Section 15.2)
+fn encode_xor_address(addr: SocketAddr, transaction_id: &[u8; 12]) -> Vec<u8> {
+    let mut value = Vec::new();
+    value.push(0); // Reserved
+    value.push(if addr.is_ipv4() { 0x01 } else { 0x02 });
+
+    // XOR port with most significant 16 bits of magic cookie
+    let xor_port = addr.port() ^ (MAGIC_COOKIE >> 16) as u16;
+    value.extend_from_slice(&xor_port.to_be_bytes());
+
+    // XOR address with magic cookie (+ transaction ID for IPv6)
+    match addr.ip() {
+        std::net::IpAddr::V4(ipv4) => {
+            let ip_bytes = ipv4.octets();
+            let magic_bytes = MAGIC_COOKIE.to_be_bytes();
+            for i in 0..4 {
+                value.push(ip_bytes[i] ^ magic_bytes[i]);
+            }
+        }
+        std::net::IpAddr::V6(ipv6) => {
+            let ip_bytes = ipv6.octets();
+            let mut xor_key = MAGIC_COOKIE.to_be_bytes().to_vec();
+            xor_key.extend_from_slice(transaction_id);
+            for i in 0..16 {
+                value.push(ip_bytes[i] ^ xor_key[i]);
+            }
+        }
+    }
+
+    value
+}
+
+/// Decode a socket address from the XOR'd format shared by XOR-MAPPED-ADDRESS,
+/// XOR-PEER-ADDRESS, and XOR-RELAYED-ADDRESS (RFC 5389 Section 15.2)
+fn decode_xor_address(value: &[u8], transaction_id: &[u8; 12]) -> Result<SocketAddr, StunError> {
+    if value.len() < 4 {
+        return Err(StunError::InvalidAttribute);
+    }
+
+    let family = value[1];
+    let xor_port = u16::from_be_bytes([value[2], value[3]]);
+    let port = xor_port ^ (MAGIC_COOKIE >> 16) as u16;
+
+    if family == 0x01 {
+        // IPv4
+        if value.len() < 8 {
+            return Err(StunError::InvalidAttribute);
+        }
+        let magic_bytes = MAGIC_COOKIE.to_be_bytes();
+        let mut ip_bytes = [0u8; 4];
+        for i in 0..4 {
+            ip_bytes[i] = value[4 + i] ^ magic_bytes[i];
+        }
+        let ip = std::net::Ipv4Addr::from(ip_bytes);
+        Ok(SocketAddr::new(ip.into(), port))
+    } else {
+        // IPv6
+        if value.len() < 20 {
+            return Err(StunError::InvalidAttribute);
+        }
+        let mut xor_key = MAGIC_COOKIE.to_be_bytes().to_vec();
+        xor_key.extend_from_slice(transaction_id);
+        let mut ip_bytes = [0u8; 16];
+        for i in 0..16 {
+            ip_bytes[i] = value[4 + i] ^ xor_key[i];
+        }
+        let ip = std::net::Ipv6Addr::from(ip_bytes);
+        Ok(SocketAddr::new(ip.into(), port))
+    }
+}
+
 /// STUN message
 #[derive(Debug, Clone)]
 pub struct StunMessage {
@@ -456,13 +595,58 @@ impl StunMessage {
     /// Create a new STUN Binding Request
     #[must_use]
     pub fn binding_request() -> Self {
+        Self::request(StunMessageType::Binding)
+    }
+
+    /// Create a new request of any method with a fresh transaction ID
+    #[must_use]
+    pub fn request(message_type: StunMessageType) -> Self {
+        Self::with_class(message_type, StunMessageClass::Request)
+    }
+
+    /// Create a new indication of any method with a fresh transaction ID
+    #[must_use]
+    pub fn indication(message_type: StunMessageType) -> Self {
+        Self::with_class(message_type, StunMessageClass::Indication)
+    }
+
+    /// Create a Binding success response for a request (server side)
+    ///
+    /// Echoes the request's `transaction_id` and carries the observed
+    /// source address as XOR-MAPPED-ADDRESS, which is all a Binding
+    /// response needs (RFC 5389 Section 7.3.2).
+    #[must_use]
+    pub fn binding_response(transaction_id: [u8; 12], mapped: SocketAddr) -> Self {
+        let mut msg = Self {
+            message_type: StunMessageType::Binding,
+            message_class: StunMessageClass::SuccessResponse,
+            transaction_id,
+            attributes: Vec::new(),
+        };
+        msg.add_attribute(StunAttribute::XorMappedAddress(mapped));
+        msg
+    }
+
+    /// Quick check whether a datagram looks like a STUN message
+    ///
+    /// Uses the RFC 5389 Section 6 demultiplexing rule — two zero
+    /// leading bits plus the magic cookie — so STUN can share a socket
+    /// with another protocol without a full decode attempt.
+    #[must_use]
+    pub fn is_stun(bytes: &[u8]) -> bool {
+        bytes.len() >= HEADER_SIZE
+            && bytes[0] & 0xC0 == 0
+            && bytes[4..8] == MAGIC_COOKIE.to_be_bytes()
+    }
+
+    fn with_class(message_type: StunMessageType, message_class: StunMessageClass) -> Self {
         let mut transaction_id = [0u8; 12];
         use rand::RngCore;
         rand::thread_rng().fill_bytes(&mut transaction_id);
 
         Self {
-            message_type: StunMessageType::Binding,
-            message_class: StunMessageClass::Request,
+            message_type,
+            message_class,
             transaction_id,
             attributes: Vec::new(),
         }
@@ -585,6 +769,12 @@ impl StunMessage {
 
         let message_type = match method {
             0x0001 => StunMessageType::Binding,
+            0x0003 => StunMessageType::Allocate,
+            0x0004 => StunMessageType::Refresh,
+            0x0006 => StunMessageType::Send,
+            0x0007 => StunMessageType::Data,
+            0x0008 => StunMessageType::CreatePermission,
+            0x0009 => StunMessageType::ChannelBind,
             _ => return Err(StunError::InvalidMessageType),
         };
 
@@ -602,6 +792,69 @@ impl StunMessage {
         None
     }
 
+    /// Get XOR-RELAYED-ADDRESS attribute (TURN)
+    #[must_use]
+    pub fn xor_relayed_address(&self) -> Option<SocketAddr> {
+        self.attributes.iter().find_map(|attr| match attr {
+            StunAttribute::XorRelayedAddress(addr) => Some(*addr),
+            _ => None,
+        })
+    }
+
+    /// Get XOR-PEER-ADDRESS attribute (TURN)
+    #[must_use]
+    pub fn xor_peer_address(&self) -> Option<SocketAddr> {
+        self.attributes.iter().find_map(|attr| match attr {
+            StunAttribute::XorPeerAddress(addr) => Some(*addr),
+            _ => None,
+        })
+    }
+
+    /// Get LIFETIME attribute in seconds (TURN)
+    #[must_use]
+    pub fn lifetime(&self) -> Option<u32> {
+        self.attributes.iter().find_map(|attr| match attr {
+            StunAttribute::Lifetime(secs) => Some(*secs),
+            _ => None,
+        })
+    }
+
+    /// Get ERROR-CODE attribute as (code, reason)
+    #[must_use]
+    pub fn error_code(&self) -> Option<(u16, &str)> {
+        self.attributes.iter().find_map(|attr| match attr {
+            StunAttribute::ErrorCode(code, reason) => Some((*code, reason.as_str())),
+            _ => None,
+        })
+    }
+
+    /// Get REALM attribute
+    #[must_use]
+    pub fn realm(&self) -> Option<&str> {
+        self.attributes.iter().find_map(|attr| match attr {
+            StunAttribute::Realm(r) => Some(r.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Get NONCE attribute
+    #[must_use]
+    pub fn nonce(&self) -> Option<&[u8]> {
+        self.attributes.iter().find_map(|attr| match attr {
+            StunAttribute::Nonce(n) => Some(n.as_slice()),
+            _ => None,
+        })
+    }
+
+    /// Get DATA attribute (TURN)
+    #[must_use]
+    pub fn data(&self) -> Option<&[u8]> {
+        self.attributes.iter().find_map(|attr| match attr {
+            StunAttribute::Data(d) => Some(d.as_slice()),
+            _ => None,
+        })
+    }
+
     // ========================================================================
     // SEC-003: MESSAGE-INTEGRITY and FINGERPRINT Support
     // ========================================================================
@@ -1070,6 +1323,39 @@ mod tests {
         assert_eq!(MAGIC_COOKIE, 0x2112_A442);
     }
 
+    #[test]
+    fn test_binding_response_roundtrip() {
+        let mapped: SocketAddr = "198.51.100.7:41000".parse().unwrap();
+        let transaction_id = [3u8; 12];
+
+        let response = StunMessage::binding_response(transaction_id, mapped);
+        let decoded = StunMessage::decode(&response.encode()).unwrap();
+
+        assert_eq!(decoded.message_type, StunMessageType::Binding);
+        assert_eq!(decoded.message_class, StunMessageClass::SuccessResponse);
+        assert_eq!(decoded.transaction_id, transaction_id);
+        assert_eq!(decoded.xor_mapped_address(), Some(mapped));
+    }
+
+    #[test]
+    fn test_is_stun_demultiplexing() {
+        let request = StunMessage::binding_request().encode();
+        assert!(StunMessage::is_stun(&request));
+
+        // Too short
+        assert!(!StunMessage::is_stun(&request[..10]));
+
+        // Right length, wrong cookie
+        let mut not_stun = request.clone();
+        not_stun[4] ^= 0xFF;
+        assert!(!StunMessage::is_stun(&not_stun));
+
+        // Leading bits set (never STUN)
+        let mut wrong_bits = request;
+        wrong_bits[0] |= 0xC0;
+        assert!(!StunMessage::is_stun(&wrong_bits));
+    }
+
     // SEC-003: STUN Security Hardening Tests
     #[test]
     fn test_stun_authentication() {
@@ -0,0 +1,747 @@
+//! TURN Client Implementation (RFC 8656)
+//!
+//! TURN (Traversal Using Relays around NAT) provides a standards-based
+//! relayed path for peers behind symmetric NATs that cannot hole punch.
+//! Unlike the DERP-style relay in [`crate::relay`], TURN interoperates
+//! with public TURN infrastructure (e.g. coturn).
+//!
+//! # Protocol Flow
+//!
+//! 1. **Allocate**: the client requests a relayed transport address on
+//!    the server. Servers require long-term credentials, so the first
+//!    Allocate is answered with a 401 carrying REALM and NONCE; the
+//!    client retries with MESSAGE-INTEGRITY.
+//! 2. **CreatePermission**: before the relay forwards traffic from a
+//!    peer, the client must install a permission for that peer's IP.
+//! 3. **Send/Data indications**: unchannelled data relaying (36 bytes
+//!    of STUN overhead per packet).
+//! 4. **ChannelBind**: binds a 16-bit channel number to a peer so data
+//!    can flow as 4-byte-header ChannelData messages instead.
+//! 5. **Refresh**: keeps the allocation alive; lifetime 0 deallocates.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use wraith_discovery::nat::{StunAuthentication, TurnClient};
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let auth = StunAuthentication::new("user", "pass", None);
+//! let mut client = TurnClient::bind("0.0.0.0:0", "203.0.113.5:3478".parse()?, auth).await?;
+//!
+//! // Allocate a relayed address and tell the peer about it out-of-band
+//! let relayed = client.allocate().await?;
+//! println!("Relayed address: {relayed}");
+//!
+//! // Allow and reach the peer through the relay
+//! let peer = "198.51.100.7:41888".parse()?;
+//! client.create_permission(peer).await?;
+//! client.channel_bind(peer).await?;
+//! client.send_to_peer(peer, b"hello").await?;
+//! let (from, data) = client.recv_from_peer().await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::stun::{
+    StunAttribute, StunAuthentication, StunError, StunMessage, StunMessageClass, StunMessageType,
+};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use tokio::net::UdpSocket;
+
+/// Default TURN allocation lifetime requested (10 minutes)
+const DEFAULT_LIFETIME: u32 = 600;
+
+/// Default request timeout
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// UDP transport protocol number for REQUESTED-TRANSPORT
+const TRANSPORT_UDP: u8 = 17;
+
+/// First channel number in the valid range (RFC 8656 Section 12)
+const CHANNEL_MIN: u16 = 0x4000;
+
+/// Last channel number in the valid range
+const CHANNEL_MAX: u16 = 0x7FFF;
+
+/// Permission lifetime on the server (5 minutes, RFC 8656 Section 9)
+const PERMISSION_LIFETIME: Duration = Duration::from_secs(300);
+
+/// Channel binding lifetime on the server (10 minutes, RFC 8656 Section 12)
+const CHANNEL_LIFETIME: Duration = Duration::from_secs(600);
+
+/// TURN-specific errors
+#[derive(Debug)]
+pub enum TurnError {
+    /// Underlying STUN protocol error
+    Stun(StunError),
+    /// No active allocation (call `allocate` first)
+    NoAllocation,
+    /// Server rejected the request with an error code
+    ServerError(u16, String),
+    /// Server demanded authentication but provided no realm/nonce
+    MissingCredentialContext,
+    /// All channel numbers are in use
+    ChannelsExhausted,
+    /// Received ChannelData for an unbound channel
+    UnknownChannel(u16),
+    /// Relayed payload exceeds what a ChannelData message can carry
+    PayloadTooLarge,
+}
+
+impl std::fmt::Display for TurnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stun(e) => write!(f, "STUN error: {e}"),
+            Self::NoAllocation => write!(f, "No active TURN allocation"),
+            Self::ServerError(code, reason) => write!(f, "TURN server error {code}: {reason}"),
+            Self::MissingCredentialContext => {
+                write!(f, "TURN server sent 401 without realm/nonce")
+            }
+            Self::ChannelsExhausted => write!(f, "All TURN channel numbers in use"),
+            Self::UnknownChannel(n) => write!(f, "ChannelData for unbound channel {n:#06x}"),
+            Self::PayloadTooLarge => write!(f, "Payload too large for ChannelData message"),
+        }
+    }
+}
+
+impl std::error::Error for TurnError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Stun(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<StunError> for TurnError {
+    fn from(err: StunError) -> Self {
+        Self::Stun(err)
+    }
+}
+
+impl From<std::io::Error> for TurnError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Stun(StunError::Io(err))
+    }
+}
+
+/// State of an active TURN allocation
+#[derive(Debug, Clone)]
+pub struct Allocation {
+    /// Relayed transport address on the server
+    pub relayed_addr: SocketAddr,
+    /// Server reflexive address observed by the server
+    pub mapped_addr: Option<SocketAddr>,
+    /// Granted allocation lifetime
+    pub lifetime: Duration,
+    /// When the allocation was created or last refreshed
+    pub refreshed_at: Instant,
+}
+
+impl Allocation {
+    /// Whether the allocation has expired and needs re-allocation
+    #[must_use]
+    pub fn is_expired(&self) -> bool {
+        self.refreshed_at.elapsed() >= self.lifetime
+    }
+
+    /// Whether the allocation should be refreshed soon (past 80% of lifetime)
+    #[must_use]
+    pub fn needs_refresh(&self) -> bool {
+        self.refreshed_at.elapsed() >= self.lifetime.mul_f32(0.8)
+    }
+}
+
+/// One channel binding to a peer
+#[derive(Debug, Clone, Copy)]
+struct ChannelBinding {
+    peer: SocketAddr,
+    bound_at: Instant,
+}
+
+/// TURN client (RFC 8656) over UDP
+///
+/// Holds at most one allocation. Permissions and channel bindings are
+/// tracked with their server-side lifetimes so callers can tell when
+/// they need refreshing.
+pub struct TurnClient {
+    socket: UdpSocket,
+    server: SocketAddr,
+    auth: StunAuthentication,
+    timeout: Duration,
+    allocation: Option<Allocation>,
+    /// Server-provided nonce for long-term credentials
+    nonce: Option<Vec<u8>>,
+    /// Peer IPs with installed permissions and when they were installed
+    permissions: HashMap<std::net::IpAddr, Instant>,
+    /// Channel number to peer bindings
+    channels: HashMap<u16, ChannelBinding>,
+    /// Next channel number to try when binding
+    next_channel: u16,
+}
+
+impl TurnClient {
+    /// Bind a TURN client socket and associate it with a server
+    ///
+    /// Servers require long-term credentials; the realm is learned from
+    /// the server's 401 challenge, so `auth` may be created without one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the socket cannot be bound
+    pub async fn bind(
+        local_addr: &str,
+        server: SocketAddr,
+        auth: StunAuthentication,
+    ) -> Result<Self, std::io::Error> {
+        let socket = UdpSocket::bind(local_addr).await?;
+        Ok(Self {
+            socket,
+            server,
+            auth,
+            timeout: DEFAULT_TIMEOUT,
+            allocation: None,
+            nonce: None,
+            permissions: HashMap::new(),
+            channels: HashMap::new(),
+            next_channel: CHANNEL_MIN,
+        })
+    }
+
+    /// Set request timeout
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    /// Local socket address
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the local address cannot be determined
+    pub fn local_addr(&self) -> Result<SocketAddr, std::io::Error> {
+        self.socket.local_addr()
+    }
+
+    /// Current allocation, if any
+    #[must_use]
+    pub fn allocation(&self) -> Option<&Allocation> {
+        self.allocation.as_ref()
+    }
+
+    /// Request a relayed transport address from the server
+    ///
+    /// Performs the long-term credential dance: an unauthenticated
+    /// Allocate, a 401 challenge carrying realm and nonce, then an
+    /// authenticated retry. Returns the relayed address peers should
+    /// send to.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the exchange fails or the server refuses
+    /// the allocation
+    pub async fn allocate(&mut self) -> Result<SocketAddr, TurnError> {
+        let mut request = StunMessage::request(StunMessageType::Allocate);
+        request.add_attribute(StunAttribute::RequestedTransport(TRANSPORT_UDP));
+        request.add_attribute(StunAttribute::Lifetime(DEFAULT_LIFETIME));
+
+        let response = self.exchange_authenticated(request).await?;
+
+        let relayed_addr = response
+            .xor_relayed_address()
+            .ok_or(TurnError::Stun(StunError::MissingAttribute))?;
+        let lifetime = response.lifetime().unwrap_or(DEFAULT_LIFETIME);
+
+        self.allocation = Some(Allocation {
+            relayed_addr,
+            mapped_addr: response.xor_mapped_address(),
+            lifetime: Duration::from_secs(u64::from(lifetime)),
+            refreshed_at: Instant::now(),
+        });
+
+        tracing::debug!("TURN allocation created: relayed address {relayed_addr}");
+        Ok(relayed_addr)
+    }
+
+    /// Refresh the allocation, extending its lifetime
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnError::NoAllocation` without an allocation, or an
+    /// error if the server refuses the refresh
+    pub async fn refresh(&mut self) -> Result<(), TurnError> {
+        if self.allocation.is_none() {
+            return Err(TurnError::NoAllocation);
+        }
+
+        let mut request = StunMessage::request(StunMessageType::Refresh);
+        request.add_attribute(StunAttribute::Lifetime(DEFAULT_LIFETIME));
+
+        let response = self.exchange_authenticated(request).await?;
+        let lifetime = response.lifetime().unwrap_or(DEFAULT_LIFETIME);
+
+        if let Some(allocation) = &mut self.allocation {
+            allocation.lifetime = Duration::from_secs(u64::from(lifetime));
+            allocation.refreshed_at = Instant::now();
+        }
+        Ok(())
+    }
+
+    /// Release the allocation (Refresh with lifetime 0)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the exchange fails; local state is cleared
+    /// regardless so the client can re-allocate
+    pub async fn deallocate(&mut self) -> Result<(), TurnError> {
+        if self.allocation.is_none() {
+            return Ok(());
+        }
+
+        let mut request = StunMessage::request(StunMessageType::Refresh);
+        request.add_attribute(StunAttribute::Lifetime(0));
+
+        let result = self.exchange_authenticated(request).await;
+        self.allocation = None;
+        self.permissions.clear();
+        self.channels.clear();
+        result.map(|_| ())
+    }
+
+    /// Install a permission for a peer so the relay forwards its traffic
+    ///
+    /// Permissions are keyed by IP address on the server and expire
+    /// after five minutes; call again to refresh.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnError::NoAllocation` without an allocation, or an
+    /// error if the server refuses the permission
+    pub async fn create_permission(&mut self, peer: SocketAddr) -> Result<(), TurnError> {
+        if self.allocation.is_none() {
+            return Err(TurnError::NoAllocation);
+        }
+
+        let mut request = StunMessage::request(StunMessageType::CreatePermission);
+        request.add_attribute(StunAttribute::XorPeerAddress(peer));
+
+        self.exchange_authenticated(request).await?;
+        self.permissions.insert(peer.ip(), Instant::now());
+        Ok(())
+    }
+
+    /// Whether a fresh permission exists for a peer
+    #[must_use]
+    pub fn has_permission(&self, peer: &SocketAddr) -> bool {
+        self.permissions
+            .get(&peer.ip())
+            .is_some_and(|installed| installed.elapsed() < PERMISSION_LIFETIME)
+    }
+
+    /// Bind a channel number to a peer for low-overhead relaying
+    ///
+    /// Returns the bound channel number. Binding also installs a
+    /// permission for the peer on the server. Re-binding an existing
+    /// peer refreshes its channel.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnError::NoAllocation` without an allocation,
+    /// `TurnError::ChannelsExhausted` if no channel numbers remain, or
+    /// an error if the server refuses the binding
+    pub async fn channel_bind(&mut self, peer: SocketAddr) -> Result<u16, TurnError> {
+        if self.allocation.is_none() {
+            return Err(TurnError::NoAllocation);
+        }
+
+        let channel = self.channel_for(&peer).map_or_else(
+            || self.next_free_channel(),
+            Ok, // Refresh the existing binding
+        )?;
+
+        let mut request = StunMessage::request(StunMessageType::ChannelBind);
+        request.add_attribute(StunAttribute::ChannelNumber(channel));
+        request.add_attribute(StunAttribute::XorPeerAddress(peer));
+
+        self.exchange_authenticated(request).await?;
+
+        self.channels.insert(
+            channel,
+            ChannelBinding {
+                peer,
+                bound_at: Instant::now(),
+            },
+        );
+        self.permissions.insert(peer.ip(), Instant::now());
+        Ok(channel)
+    }
+
+    /// Channel number bound to a peer, if a fresh binding exists
+    #[must_use]
+    pub fn channel_for(&self, peer: &SocketAddr) -> Option<u16> {
+        self.channels.iter().find_map(|(channel, binding)| {
+            (binding.peer == *peer && binding.bound_at.elapsed() < CHANNEL_LIFETIME)
+                .then_some(*channel)
+        })
+    }
+
+    /// Send application data to a peer through the relay
+    ///
+    /// Uses ChannelData framing when a channel is bound to the peer
+    /// (4 bytes of overhead), otherwise a Send indication (36 bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns `TurnError::NoAllocation` without an allocation
+    pub async fn send_to_peer(&self, peer: SocketAddr, data: &[u8]) -> Result<(), TurnError> {
+        if self.allocation.is_none() {
+            return Err(TurnError::NoAllocation);
+        }
+
+        if let Some(channel) = self.channel_for(&peer) {
+            let framed = encode_channel_data(channel, data)?;
+            self.socket.send_to(&framed, self.server).await?;
+        } else {
+            let mut indication = StunMessage::indication(StunMessageType::Send);
+            indication.add_attribute(StunAttribute::XorPeerAddress(peer));
+            indication.add_attribute(StunAttribute::Data(data.to_vec()));
+            self.socket
+                .send_to(&indication.encode(), self.server)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Receive relayed application data from a peer
+    ///
+    /// Handles both ChannelData messages and Data indications; STUN
+    /// responses and unknown traffic are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error on I/O failure or a ChannelData message for a
+    /// channel that was never bound
+    pub async fn recv_from_peer(&self) -> Result<(SocketAddr, Vec<u8>), TurnError> {
+        let mut buf = vec![0u8; crate::relay::MAX_RELAY_PACKET_SIZE];
+        loop {
+            let (len, from) = self.socket.recv_from(&mut buf).await?;
+            if from != self.server {
+                continue;
+            }
+            let packet = &buf[..len];
+
+            // ChannelData messages start with a channel number in
+            // 0x4000..=0x7FFF; STUN messages start with 0b00 bits
+            if len >= 4 {
+                let leading = u16::from_be_bytes([packet[0], packet[1]]);
+                if (CHANNEL_MIN..=CHANNEL_MAX).contains(&leading) {
+                    let (channel, data) = decode_channel_data(packet)?;
+                    let binding = self
+                        .channels
+                        .get(&channel)
+                        .ok_or(TurnError::UnknownChannel(channel))?;
+                    return Ok((binding.peer, data));
+                }
+            }
+
+            if let Ok(msg) = StunMessage::decode(packet)
+                && msg.message_type == StunMessageType::Data
+                && msg.message_class == StunMessageClass::Indication
+                && let (Some(peer), Some(data)) = (msg.xor_peer_address(), msg.data())
+            {
+                return Ok((peer, data.to_vec()));
+            }
+        }
+    }
+
+    /// Lowest unused channel number in the valid range
+    fn next_free_channel(&mut self) -> Result<u16, TurnError> {
+        for _ in CHANNEL_MIN..=CHANNEL_MAX {
+            let candidate = self.next_channel;
+            self.next_channel = if self.next_channel == CHANNEL_MAX {
+                CHANNEL_MIN
+            } else {
+                self.next_channel + 1
+            };
+            if !self.channels.contains_key(&candidate) {
+                return Ok(candidate);
+            }
+        }
+        Err(TurnError::ChannelsExhausted)
+    }
+
+    /// Send a request, handling the long-term credential 401 challenge
+    ///
+    /// The first attempt is sent unauthenticated (or with stale
+    /// credentials); on a 401 or 438 (stale nonce) the realm and nonce
+    /// from the response are adopted and the request is retried with
+    /// MESSAGE-INTEGRITY.
+    async fn exchange_authenticated(
+        &mut self,
+        request: StunMessage,
+    ) -> Result<StunMessage, TurnError> {
+        for attempt_no in 0..2 {
+            // A fresh transaction ID per attempt, per RFC 5389
+            let mut attempt = StunMessage::request(request.message_type);
+            attempt.attributes = request.attributes.clone();
+
+            if self.auth.realm.is_some() {
+                attempt.add_attribute(StunAttribute::Username(self.auth.username.clone()));
+                if let Some(realm) = &self.auth.realm {
+                    attempt.add_attribute(StunAttribute::Realm(realm.clone()));
+                }
+                if let Some(nonce) = &self.nonce {
+                    attempt.add_attribute(StunAttribute::Nonce(nonce.clone()));
+                }
+                attempt.add_message_integrity(&self.auth);
+            }
+
+            let response = self.exchange(&attempt).await?;
+            match response.message_class {
+                StunMessageClass::SuccessResponse => return Ok(response),
+                StunMessageClass::ErrorResponse => {
+                    let (code, reason) = response
+                        .error_code()
+                        .map(|(c, r)| (c, r.to_string()))
+                        .unwrap_or((0, String::new()));
+
+                    // 401 Unauthorized / 438 Stale Nonce: adopt the
+                    // server's realm and nonce, then retry once with
+                    // MESSAGE-INTEGRITY
+                    if (code == 401 || code == 438) && attempt_no == 0 {
+                        if let Some(realm) = response.realm() {
+                            self.auth = self.auth.with_realm(realm.to_string());
+                        }
+                        if self.auth.realm.is_none() {
+                            return Err(TurnError::MissingCredentialContext);
+                        }
+                        if let Some(nonce) = response.nonce() {
+                            self.nonce = Some(nonce.to_vec());
+                        }
+                        continue;
+                    }
+
+                    return Err(TurnError::ServerError(code, reason));
+                }
+                _ => return Err(TurnError::Stun(StunError::InvalidMessageType)),
+            }
+        }
+
+        Err(TurnError::Stun(StunError::AuthenticationFailed))
+    }
+
+    /// Send one request and wait for the matching response
+    async fn exchange(&self, request: &StunMessage) -> Result<StunMessage, TurnError> {
+        let bytes = request.encode();
+        self.socket.send_to(&bytes, self.server).await?;
+
+        let mut buf = vec![0u8; crate::relay::MAX_RELAY_PACKET_SIZE];
+        loop {
+            let (len, from) = tokio::time::timeout(self.timeout, self.socket.recv_from(&mut buf))
+                .await
+                .map_err(|_| TurnError::Stun(StunError::Timeout))??;
+
+            if from != self.server {
+                continue;
+            }
+            let Ok(response) = StunMessage::decode(&buf[..len]) else {
+                continue;
+            };
+            if response.transaction_id != request.transaction_id {
+                continue;
+            }
+            return Ok(response);
+        }
+    }
+}
+
+/// Encode a ChannelData message (RFC 8656 Section 12.4)
+fn encode_channel_data(channel: u16, data: &[u8]) -> Result<Vec<u8>, TurnError> {
+    let len = u16::try_from(data.len()).map_err(|_| TurnError::PayloadTooLarge)?;
+    let mut framed = Vec::with_capacity(4 + data.len());
+    framed.extend_from_slice(&channel.to_be_bytes());
+    framed.extend_from_slice(&len.to_be_bytes());
+    framed.extend_from_slice(data);
+    Ok(framed)
+}
+
+/// Decode a ChannelData message into (channel, data)
+fn decode_channel_data(packet: &[u8]) -> Result<(u16, Vec<u8>), TurnError> {
+    if packet.len() < 4 {
+        return Err(TurnError::Stun(StunError::MessageTooShort));
+    }
+    let channel = u16::from_be_bytes([packet[0], packet[1]]);
+    let len = u16::from_be_bytes([packet[2], packet[3]]) as usize;
+    if packet.len() < 4 + len {
+        return Err(TurnError::Stun(StunError::MessageTooShort));
+    }
+    Ok((channel, packet[4..4 + len].to_vec()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_data_roundtrip() {
+        let framed = encode_channel_data(0x4001, b"hello").unwrap();
+        assert_eq!(framed.len(), 9);
+
+        let (channel, data) = decode_channel_data(&framed).unwrap();
+        assert_eq!(channel, 0x4001);
+        assert_eq!(data, b"hello");
+    }
+
+    #[test]
+    fn test_channel_data_ignores_trailing_padding() {
+        let mut framed = encode_channel_data(0x4abc, b"data").unwrap();
+        framed.extend_from_slice(&[0, 0, 0]); // Transport padding
+
+        let (channel, data) = decode_channel_data(&framed).unwrap();
+        assert_eq!(channel, 0x4abc);
+        assert_eq!(data, b"data");
+    }
+
+    #[test]
+    fn test_channel_data_truncated() {
+        assert!(decode_channel_data(&[0x40]).is_err());
+
+        let framed = encode_channel_data(0x4001, b"hello").unwrap();
+        assert!(decode_channel_data(&framed[..6]).is_err());
+    }
+
+    #[test]
+    fn test_allocate_request_attributes() {
+        let mut request = StunMessage::request(StunMessageType::Allocate);
+        request.add_attribute(StunAttribute::RequestedTransport(TRANSPORT_UDP));
+        request.add_attribute(StunAttribute::Lifetime(DEFAULT_LIFETIME));
+
+        let decoded = StunMessage::decode(&request.encode()).unwrap();
+        assert_eq!(decoded.message_type, StunMessageType::Allocate);
+        assert_eq!(decoded.message_class, StunMessageClass::Request);
+        assert_eq!(decoded.lifetime(), Some(DEFAULT_LIFETIME));
+        assert!(
+            decoded
+                .attributes
+                .iter()
+                .any(|attr| matches!(attr, StunAttribute::RequestedTransport(TRANSPORT_UDP)))
+        );
+    }
+
+    #[test]
+    fn test_send_indication_roundtrip() {
+        let peer: SocketAddr = "198.51.100.7:41888".parse().unwrap();
+        let mut indication = StunMessage::indication(StunMessageType::Send);
+        indication.add_attribute(StunAttribute::XorPeerAddress(peer));
+        indication.add_attribute(StunAttribute::Data(vec![1, 2, 3]));
+
+        let decoded = StunMessage::decode(&indication.encode()).unwrap();
+        assert_eq!(decoded.message_type, StunMessageType::Send);
+        assert_eq!(decoded.message_class, StunMessageClass::Indication);
+        assert_eq!(decoded.xor_peer_address(), Some(peer));
+        assert_eq!(decoded.data(), Some([1u8, 2, 3].as_slice()));
+    }
+
+    #[tokio::test]
+    async fn test_operations_require_allocation() {
+        let auth = StunAuthentication::new("user", "pass", None);
+        let server: SocketAddr = "203.0.113.5:3478".parse().unwrap();
+        let mut client = TurnClient::bind("127.0.0.1:0", server, auth).await.unwrap();
+
+        let peer: SocketAddr = "198.51.100.7:41888".parse().unwrap();
+        assert!(matches!(
+            client.refresh().await,
+            Err(TurnError::NoAllocation)
+        ));
+        assert!(matches!(
+            client.create_permission(peer).await,
+            Err(TurnError::NoAllocation)
+        ));
+        assert!(matches!(
+            client.channel_bind(peer).await,
+            Err(TurnError::NoAllocation)
+        ));
+        assert!(matches!(
+            client.send_to_peer(peer, b"data").await,
+            Err(TurnError::NoAllocation)
+        ));
+        // Deallocating without an allocation is a no-op
+        assert!(client.deallocate().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_channel_number_allocation() {
+        let auth = StunAuthentication::new("user", "pass", None);
+        let server: SocketAddr = "203.0.113.5:3478".parse().unwrap();
+        let mut client = TurnClient::bind("127.0.0.1:0", server, auth).await.unwrap();
+
+        assert_eq!(client.next_free_channel().unwrap(), CHANNEL_MIN);
+        assert_eq!(client.next_free_channel().unwrap(), CHANNEL_MIN + 1);
+
+        // A bound channel is skipped
+        client.channels.insert(
+            CHANNEL_MIN + 2,
+            ChannelBinding {
+                peer: "198.51.100.7:41888".parse().unwrap(),
+                bound_at: Instant::now(),
+            },
+        );
+        assert_eq!(client.next_free_channel().unwrap(), CHANNEL_MIN + 3);
+    }
+
+    #[tokio::test]
+    async fn test_channel_for_bound_peer() {
+        let auth = StunAuthentication::new("user", "pass", None);
+        let server: SocketAddr = "203.0.113.5:3478".parse().unwrap();
+        let mut client = TurnClient::bind("127.0.0.1:0", server, auth).await.unwrap();
+
+        let peer: SocketAddr = "198.51.100.7:41888".parse().unwrap();
+        assert_eq!(client.channel_for(&peer), None);
+
+        client.channels.insert(
+            0x4005,
+            ChannelBinding {
+                peer,
+                bound_at: Instant::now(),
+            },
+        );
+        assert_eq!(client.channel_for(&peer), Some(0x4005));
+        assert!(!client.has_permission(&peer));
+    }
+
+    #[test]
+    fn test_allocation_refresh_tracking() {
+        let allocation = Allocation {
+            relayed_addr: "203.0.113.5:49152".parse().unwrap(),
+            mapped_addr: None,
+            lifetime: Duration::from_secs(600),
+            refreshed_at: Instant::now(),
+        };
+        assert!(!allocation.is_expired());
+        assert!(!allocation.needs_refresh());
+
+        let stale = Allocation {
+            lifetime: Duration::ZERO,
+            ..allocation
+        };
+        assert!(stale.is_expired());
+        assert!(stale.needs_refresh());
+    }
+
+    #[test]
+    fn test_turn_error_display() {
+        assert_eq!(
+            TurnError::NoAllocation.to_string(),
+            "No active TURN allocation"
+        );
+        assert_eq!(
+            TurnError::ServerError(437, "Allocation Mismatch".to_string()).to_string(),
+            "TURN server error 437: Allocation Mismatch"
+        );
+        assert_eq!(
+            TurnError::UnknownChannel(0x4001).to_string(),
+            "ChannelData for unbound channel 0x4001"
+        );
+    }
+}
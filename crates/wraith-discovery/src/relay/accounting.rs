@@ -0,0 +1,327 @@
+//! Per-client bandwidth accounting and quotas for the relay server.
+//!
+//! Tracks bytes and packets forwarded for every registered client,
+//! enforces per-client quotas, and applies a fairness policy when the
+//! relay's aggregate budget is exhausted: clients consuming more than
+//! their fair share are throttled first, so a single heavy user cannot
+//! starve everyone else.
+//!
+//! Accounting is window-based. The just-completed window provides stable
+//! observed rates for load reporting; the current window drives verdicts.
+
+use super::protocol::NodeId;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Quota configuration for relay bandwidth accounting
+#[derive(Debug, Clone)]
+pub struct QuotaConfig {
+    /// Maximum bytes a single client may relay per window
+    pub client_bytes_per_window: u64,
+    /// Aggregate byte budget for the whole relay per window
+    pub total_bytes_per_window: u64,
+    /// Accounting window length
+    pub window: Duration,
+    /// A client is "heavy" above this multiple of its fair share
+    pub fairness_factor: f64,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            client_bytes_per_window: 5 * 1024 * 1024, // 5 MB/s per client
+            total_bytes_per_window: 125 * 1024 * 1024, // ~1 Gbps aggregate
+            window: Duration::from_secs(1),
+            fairness_factor: 2.0,
+        }
+    }
+}
+
+/// Verdict for one packet charged against the accounts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChargeVerdict {
+    /// Within quota; the packet was charged and may be forwarded
+    Allowed,
+    /// The client exhausted its own per-window quota
+    QuotaExceeded,
+    /// The relay is saturated and this client is over its fair share
+    Throttled,
+}
+
+/// Cumulative and windowed usage for one client
+#[derive(Debug, Clone, Default)]
+pub struct ClientUsage {
+    /// Total bytes forwarded since registration
+    pub bytes_forwarded: u64,
+    /// Total packets forwarded since registration
+    pub packets_forwarded: u64,
+    /// Packets rejected by quota or fairness policy
+    pub packets_rejected: u64,
+    /// Bytes charged in the current window
+    window_bytes: u64,
+    /// Packets charged in the current window
+    window_packets: u64,
+}
+
+/// Snapshot of relay load for admin queries and relay selection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RelayLoad {
+    /// Registered client count at snapshot time
+    pub client_count: usize,
+    /// Observed forwarded bytes per second (previous full window)
+    pub bytes_per_sec: u64,
+    /// Observed forwarded packets per second (previous full window)
+    pub packets_per_sec: u64,
+    /// Utilisation of the aggregate budget, clamped to `0.0..=1.0`
+    pub load: f32,
+}
+
+/// Windowed bandwidth accountant with per-client quotas and fairness
+#[derive(Debug)]
+pub struct BandwidthAccountant {
+    /// Quota configuration
+    config: QuotaConfig,
+    /// Usage per client
+    usage: HashMap<NodeId, ClientUsage>,
+    /// Start of the current accounting window
+    window_start: Instant,
+    /// Aggregate bytes charged in the current window
+    window_total_bytes: u64,
+    /// Aggregate packets charged in the current window
+    window_total_packets: u64,
+    /// Aggregate bytes of the previous completed window
+    last_window_bytes: u64,
+    /// Aggregate packets of the previous completed window
+    last_window_packets: u64,
+}
+
+impl BandwidthAccountant {
+    /// Create an accountant with the given quotas
+    #[must_use]
+    pub fn new(config: QuotaConfig) -> Self {
+        Self {
+            config,
+            usage: HashMap::new(),
+            window_start: Instant::now(),
+            window_total_bytes: 0,
+            window_total_packets: 0,
+            last_window_bytes: 0,
+            last_window_packets: 0,
+        }
+    }
+
+    /// Charge one packet to a client's account and return the verdict
+    ///
+    /// Rejected packets are counted in the client's usage but not charged
+    /// against window totals.
+    pub fn charge(&mut self, client: NodeId, bytes: usize) -> ChargeVerdict {
+        self.roll_window();
+
+        let bytes = bytes as u64;
+        let active_clients = self
+            .usage
+            .values()
+            .filter(|u| u.window_bytes > 0)
+            .count()
+            .max(1) as u64;
+
+        // Fair share of the aggregate budget among currently active
+        // clients; recomputed per packet so shares adapt as clients come
+        // and go within the window
+        let fair_share = (self.config.total_bytes_per_window / active_clients) as f64
+            * self.config.fairness_factor;
+
+        let usage = self.usage.entry(client).or_default();
+
+        if usage.window_bytes + bytes > self.config.client_bytes_per_window {
+            usage.packets_rejected += 1;
+            return ChargeVerdict::QuotaExceeded;
+        }
+
+        if self.window_total_bytes + bytes > self.config.total_bytes_per_window
+            && (usage.window_bytes + bytes) as f64 > fair_share
+        {
+            usage.packets_rejected += 1;
+            return ChargeVerdict::Throttled;
+        }
+
+        usage.window_bytes += bytes;
+        usage.window_packets += 1;
+        usage.bytes_forwarded += bytes;
+        usage.packets_forwarded += 1;
+        self.window_total_bytes += bytes;
+        self.window_total_packets += 1;
+
+        ChargeVerdict::Allowed
+    }
+
+    /// Current relay load snapshot
+    ///
+    /// Rates come from the previous completed window so a fresh window
+    /// does not make the relay look idle.
+    #[must_use]
+    pub fn load(&mut self, client_count: usize) -> RelayLoad {
+        self.roll_window();
+
+        let window_secs = self.config.window.as_secs_f64().max(f64::EPSILON);
+        let bytes_per_sec = (self.last_window_bytes as f64 / window_secs) as u64;
+        let packets_per_sec = (self.last_window_packets as f64 / window_secs) as u64;
+
+        let budget_per_sec = self.config.total_bytes_per_window as f64 / window_secs;
+        let load = (bytes_per_sec as f64 / budget_per_sec.max(1.0)).clamp(0.0, 1.0) as f32;
+
+        RelayLoad {
+            client_count,
+            bytes_per_sec,
+            packets_per_sec,
+            load,
+        }
+    }
+
+    /// Usage for one client, if it has been seen
+    #[must_use]
+    pub fn usage(&self, client: &NodeId) -> Option<&ClientUsage> {
+        self.usage.get(client)
+    }
+
+    /// Drop accounts for clients no longer registered
+    pub fn retain<F: FnMut(&NodeId) -> bool>(&mut self, mut keep: F) {
+        self.usage.retain(|id, _| keep(id));
+    }
+
+    /// Close the window if it has elapsed and start a new one
+    fn roll_window(&mut self) {
+        let elapsed = self.window_start.elapsed();
+        if elapsed < self.config.window {
+            return;
+        }
+
+        // If more than one full window passed idle, the previous window
+        // observed nothing
+        if elapsed >= self.config.window * 2 {
+            self.last_window_bytes = 0;
+            self.last_window_packets = 0;
+        } else {
+            self.last_window_bytes = self.window_total_bytes;
+            self.last_window_packets = self.window_total_packets;
+        }
+
+        self.window_total_bytes = 0;
+        self.window_total_packets = 0;
+        self.window_start = Instant::now();
+        for usage in self.usage.values_mut() {
+            usage.window_bytes = 0;
+            usage.window_packets = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_config() -> QuotaConfig {
+        QuotaConfig {
+            client_bytes_per_window: 1000,
+            total_bytes_per_window: 2500,
+            window: Duration::from_secs(1),
+            fairness_factor: 2.0,
+        }
+    }
+
+    #[test]
+    fn test_within_quota_is_allowed() {
+        let mut acct = BandwidthAccountant::new(test_config());
+        assert_eq!(acct.charge([1u8; 32], 500), ChargeVerdict::Allowed);
+        assert_eq!(acct.charge([1u8; 32], 400), ChargeVerdict::Allowed);
+    }
+
+    #[test]
+    fn test_client_quota_is_enforced() {
+        let mut acct = BandwidthAccountant::new(test_config());
+        assert_eq!(acct.charge([1u8; 32], 900), ChargeVerdict::Allowed);
+        assert_eq!(acct.charge([1u8; 32], 200), ChargeVerdict::QuotaExceeded);
+
+        let usage = acct.usage(&[1u8; 32]).unwrap();
+        assert_eq!(usage.bytes_forwarded, 900);
+        assert_eq!(usage.packets_rejected, 1);
+    }
+
+    #[test]
+    fn test_heavy_user_is_throttled_when_saturated() {
+        let mut acct = BandwidthAccountant::new(QuotaConfig {
+            client_bytes_per_window: 10_000,
+            total_bytes_per_window: 1500,
+            window: Duration::from_secs(1),
+            fairness_factor: 1.0,
+        });
+
+        // Two active clients: fair share is 750 each
+        assert_eq!(acct.charge([1u8; 32], 700), ChargeVerdict::Allowed);
+        assert_eq!(acct.charge([2u8; 32], 700), ChargeVerdict::Allowed);
+
+        // Budget exhausted: the client pushing past its share is cut off
+        assert_eq!(acct.charge([1u8; 32], 700), ChargeVerdict::Throttled);
+    }
+
+    #[test]
+    fn test_light_user_not_throttled_when_saturated() {
+        let mut acct = BandwidthAccountant::new(QuotaConfig {
+            client_bytes_per_window: 10_000,
+            total_bytes_per_window: 1000,
+            window: Duration::from_secs(1),
+            fairness_factor: 2.0,
+        });
+
+        // One heavy client saturates the relay
+        assert_eq!(acct.charge([1u8; 32], 950), ChargeVerdict::Allowed);
+
+        // A light newcomer still fits under its fair share
+        assert_eq!(acct.charge([2u8; 32], 100), ChargeVerdict::Allowed);
+    }
+
+    #[test]
+    fn test_window_roll_resets_quotas() {
+        let mut acct = BandwidthAccountant::new(QuotaConfig {
+            client_bytes_per_window: 1000,
+            total_bytes_per_window: 2500,
+            window: Duration::from_millis(10),
+            fairness_factor: 2.0,
+        });
+
+        assert_eq!(acct.charge([1u8; 32], 1000), ChargeVerdict::Allowed);
+        assert_eq!(acct.charge([1u8; 32], 1), ChargeVerdict::QuotaExceeded);
+
+        std::thread::sleep(Duration::from_millis(15));
+        assert_eq!(acct.charge([1u8; 32], 1000), ChargeVerdict::Allowed);
+    }
+
+    #[test]
+    fn test_load_reports_previous_window() {
+        let mut acct = BandwidthAccountant::new(QuotaConfig {
+            client_bytes_per_window: 10_000,
+            total_bytes_per_window: 10_000,
+            window: Duration::from_millis(10),
+            fairness_factor: 2.0,
+        });
+
+        acct.charge([1u8; 32], 5000);
+        std::thread::sleep(Duration::from_millis(15));
+
+        let load = acct.load(1);
+        assert_eq!(load.client_count, 1);
+        assert!(load.bytes_per_sec > 0);
+        assert!(load.load > 0.0 && load.load <= 1.0);
+    }
+
+    #[test]
+    fn test_retain_drops_departed_clients() {
+        let mut acct = BandwidthAccountant::new(test_config());
+        acct.charge([1u8; 32], 10);
+        acct.charge([2u8; 32], 10);
+
+        acct.retain(|id| id == &[1u8; 32]);
+        assert!(acct.usage(&[1u8; 32]).is_some());
+        assert!(acct.usage(&[2u8; 32]).is_none());
+    }
+}
@@ -1,6 +1,8 @@
 //! Relay client implementation for connecting to relay servers.
 
+use super::accounting::RelayLoad;
 use super::protocol::{NodeId, RelayError, RelayMessage};
+use crate::nat::coordination::ClockSync;
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -186,6 +188,91 @@ impl RelayClient {
         Ok(())
     }
 
+    /// Query the relay's current load
+    ///
+    /// Sends a `LoadQuery` and waits for the relay's `LoadReport`, so
+    /// selection can use observed load instead of a hardcoded value.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the query fails or times out.
+    pub async fn query_load(&self) -> Result<RelayLoad, RelayError> {
+        let msg = RelayMessage::LoadQuery;
+        let bytes = msg.to_bytes()?;
+        self.socket.send(&bytes).await?;
+
+        // Wait for LoadReport with timeout
+        let mut buf = vec![0u8; 65536];
+        let len = time::timeout(Duration::from_secs(10), self.socket.recv(&mut buf))
+            .await
+            .map_err(|_| RelayError::Timeout)??;
+
+        let response = RelayMessage::from_bytes(&buf[..len])?;
+
+        match response {
+            RelayMessage::LoadReport {
+                client_count,
+                bytes_per_sec,
+                packets_per_sec,
+                load,
+            } => Ok(RelayLoad {
+                client_count: client_count as usize,
+                bytes_per_sec,
+                packets_per_sec,
+                load,
+            }),
+            RelayMessage::Error { code, message: _ } => Err(code.into()),
+            _ => Err(RelayError::InvalidMessage),
+        }
+    }
+
+    /// Sync against the relay's clock for coordinated hole punching
+    ///
+    /// Performs `samples` `TimeQuery`/`TimeReport` exchanges and keeps
+    /// the one with the lowest round-trip time, whose NTP-style offset
+    /// has the tightest error bound (half its RTT). Both punch
+    /// participants sync against the same relay, so their converted
+    /// schedules agree even when their wall clocks do not — see
+    /// [`crate::nat::coordination`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if every exchange fails or times out.
+    pub async fn sync_time(&self, samples: usize) -> Result<ClockSync, RelayError> {
+        let mut best: Option<ClockSync> = None;
+        let mut buf = vec![0u8; 65536];
+
+        for _ in 0..samples.max(1) {
+            let sent_us = crate::nat::coordination::unix_micros();
+            let msg = RelayMessage::TimeQuery {
+                client_time_us: sent_us,
+            };
+            self.socket.send(&msg.to_bytes()?).await?;
+
+            let Ok(Ok(len)) =
+                time::timeout(Duration::from_secs(2), self.socket.recv(&mut buf)).await
+            else {
+                continue;
+            };
+            let received_us = crate::nat::coordination::unix_micros();
+
+            match RelayMessage::from_bytes(&buf[..len]) {
+                Ok(RelayMessage::TimeReport {
+                    client_time_us,
+                    relay_time_us,
+                }) if client_time_us == sent_us => {
+                    let sample = ClockSync::from_exchange(sent_us, relay_time_us, received_us);
+                    if best.is_none_or(|b| sample.rtt_us < b.rtt_us) {
+                        best = Some(sample);
+                    }
+                }
+                _ => continue,
+            }
+        }
+
+        best.ok_or(RelayError::Timeout)
+    }
+
     /// Disconnect from relay server
     ///
     /// # Errors
@@ -306,6 +393,30 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_sync_time_against_local_relay() {
+        use crate::relay::server::RelayServer;
+
+        let server = Arc::new(
+            RelayServer::bind("127.0.0.1:0".parse().unwrap())
+                .await
+                .unwrap(),
+        );
+        let server_addr = server.local_addr().unwrap();
+        let s = server.clone();
+        tokio::spawn(async move {
+            let _ = s.run().await;
+        });
+
+        let client = RelayClient::connect(server_addr, [7u8; 32]).await.unwrap();
+        let sync = client.sync_time(3).await.unwrap();
+
+        // Relay and client share the loopback host's clock, so the
+        // measured offset and RTT are both small
+        assert!(sync.rtt_us < 1_000_000);
+        assert!(sync.offset_us.unsigned_abs() < 1_000_000);
+    }
+
     #[test]
     fn test_relay_client_state_transitions() {
         assert_eq!(
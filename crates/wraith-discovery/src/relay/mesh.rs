@@ -0,0 +1,191 @@
+//! Relay mesh federation: routing between cooperating relay servers.
+//!
+//! Federated relays periodically announce their registered clients to
+//! each other (`MeshAnnounce`). Each relay keeps a routing table keyed
+//! by client node ID, so a packet for a client registered on a peer
+//! relay can be wrapped in a `MeshForward` and handed to that relay.
+//! Forwarding is single-hop: a relay only forwards to the relay that
+//! announced the destination, which keeps the mesh loop-free.
+
+use super::protocol::NodeId;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+/// Mesh federation configuration
+#[derive(Debug, Clone)]
+pub struct RelayMeshConfig {
+    /// Addresses of peer relay servers in the mesh
+    pub peers: Vec<SocketAddr>,
+    /// How often to announce registered clients to peers
+    pub announce_interval: Duration,
+    /// How long a learned route stays valid without re-announcement
+    pub route_ttl: Duration,
+}
+
+impl Default for RelayMeshConfig {
+    fn default() -> Self {
+        Self {
+            peers: Vec::new(),
+            announce_interval: Duration::from_secs(10),
+            route_ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RelayMeshConfig {
+    /// Whether federation is enabled (any peers configured)
+    #[must_use]
+    pub fn is_enabled(&self) -> bool {
+        !self.peers.is_empty()
+    }
+
+    /// Whether an address belongs to a configured mesh peer
+    #[must_use]
+    pub fn is_peer(&self, addr: &SocketAddr) -> bool {
+        self.peers.contains(addr)
+    }
+}
+
+/// One learned route to a client on a peer relay
+#[derive(Debug, Clone, Copy)]
+struct MeshRoute {
+    /// Relay that announced the client
+    relay_addr: SocketAddr,
+    /// When the route was learned or refreshed
+    learned_at: Instant,
+}
+
+/// Routing table keyed by client node ID
+///
+/// Routes are learned from `MeshAnnounce` messages and expire after the
+/// configured TTL so departed clients stop attracting traffic.
+#[derive(Debug, Default)]
+pub struct MeshRoutingTable {
+    routes: HashMap<NodeId, MeshRoute>,
+}
+
+impl MeshRoutingTable {
+    /// Create an empty routing table
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Learn (or refresh) a route to a client via a peer relay
+    pub fn learn(&mut self, client: NodeId, relay_addr: SocketAddr) {
+        self.routes.insert(
+            client,
+            MeshRoute {
+                relay_addr,
+                learned_at: Instant::now(),
+            },
+        );
+    }
+
+    /// Relay address for a client, if a fresh route is known
+    #[must_use]
+    pub fn route(&self, client: &NodeId, ttl: Duration) -> Option<SocketAddr> {
+        self.routes
+            .get(client)
+            .filter(|r| r.learned_at.elapsed() < ttl)
+            .map(|r| r.relay_addr)
+    }
+
+    /// Drop routes older than the TTL
+    pub fn prune(&mut self, ttl: Duration) {
+        self.routes.retain(|_, r| r.learned_at.elapsed() < ttl);
+    }
+
+    /// Drop all routes learned from one relay (e.g. it left the mesh)
+    pub fn forget_relay(&mut self, relay_addr: &SocketAddr) {
+        self.routes.retain(|_, r| r.relay_addr != *relay_addr);
+    }
+
+    /// Number of known routes
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.routes.len()
+    }
+
+    /// Whether no routes are known
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.routes.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn test_mesh_config_default_disabled() {
+        let config = RelayMeshConfig::default();
+        assert!(!config.is_enabled());
+        assert!(!config.is_peer(&"127.0.0.1:443".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_mesh_config_peer_check() {
+        let addr: SocketAddr = "10.0.0.1:443".parse().unwrap();
+        let config = RelayMeshConfig {
+            peers: vec![addr],
+            ..RelayMeshConfig::default()
+        };
+        assert!(config.is_enabled());
+        assert!(config.is_peer(&addr));
+        assert!(!config.is_peer(&"10.0.0.2:443".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_routing_table_learn_and_route() {
+        let mut table = MeshRoutingTable::new();
+        let relay: SocketAddr = "10.0.0.1:443".parse().unwrap();
+
+        assert!(table.route(&[1u8; 32], TTL).is_none());
+        table.learn([1u8; 32], relay);
+        assert_eq!(table.route(&[1u8; 32], TTL), Some(relay));
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn test_routing_table_expiry() {
+        let mut table = MeshRoutingTable::new();
+        let relay: SocketAddr = "10.0.0.1:443".parse().unwrap();
+        table.learn([1u8; 32], relay);
+
+        // A zero TTL makes every route stale immediately
+        assert!(table.route(&[1u8; 32], Duration::ZERO).is_none());
+        table.prune(Duration::ZERO);
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_routing_table_refresh_replaces_relay() {
+        let mut table = MeshRoutingTable::new();
+        let relay1: SocketAddr = "10.0.0.1:443".parse().unwrap();
+        let relay2: SocketAddr = "10.0.0.2:443".parse().unwrap();
+
+        table.learn([1u8; 32], relay1);
+        table.learn([1u8; 32], relay2);
+        assert_eq!(table.route(&[1u8; 32], TTL), Some(relay2));
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn test_routing_table_forget_relay() {
+        let mut table = MeshRoutingTable::new();
+        let relay1: SocketAddr = "10.0.0.1:443".parse().unwrap();
+        let relay2: SocketAddr = "10.0.0.2:443".parse().unwrap();
+
+        table.learn([1u8; 32], relay1);
+        table.learn([2u8; 32], relay2);
+        table.forget_relay(&relay1);
+
+        assert!(table.route(&[1u8; 32], TTL).is_none());
+        assert_eq!(table.route(&[2u8; 32], TTL), Some(relay2));
+    }
+}
@@ -63,12 +63,16 @@
 //! # }
 //! ```
 
+pub mod accounting;
 pub mod client;
+pub mod mesh;
 pub mod protocol;
 pub mod selection;
 pub mod server;
 
+pub use accounting::{BandwidthAccountant, ChargeVerdict, ClientUsage, QuotaConfig, RelayLoad};
 pub use client::RelayClient;
+pub use mesh::{MeshRoutingTable, RelayMeshConfig};
 pub use protocol::{RelayError, RelayErrorCode, RelayMessage};
 pub use selection::{RelayInfo, RelaySelector, SelectionStrategy};
 pub use server::{RelayServer, RelayServerConfig};
@@ -68,6 +68,56 @@ pub enum RelayMessage {
         /// Human-readable error message
         message: String,
     },
+
+    /// Admin query for the relay's current load
+    LoadQuery,
+
+    /// Relay load report (response to `LoadQuery`)
+    LoadReport {
+        /// Number of registered clients
+        client_count: u32,
+        /// Observed forwarded bytes per second
+        bytes_per_sec: u64,
+        /// Observed forwarded packets per second
+        packets_per_sec: u64,
+        /// Utilisation of the aggregate budget (`0.0..=1.0`)
+        load: f32,
+    },
+
+    /// Client requests the relay's clock for punch coordination
+    TimeQuery {
+        /// Client's local time when the query was sent (µs since the
+        /// Unix epoch), echoed back so the client can pair request and
+        /// response and measure round-trip time
+        client_time_us: u64,
+    },
+
+    /// Relay clock report (response to `TimeQuery`)
+    TimeReport {
+        /// The `client_time_us` from the query, echoed verbatim
+        client_time_us: u64,
+        /// Relay's local time when the query was handled (µs since the
+        /// Unix epoch)
+        relay_time_us: u64,
+    },
+
+    /// Mesh federation: a relay announces its registered clients
+    MeshAnnounce {
+        /// Announcing relay's identifier
+        relay_id: [u8; 32],
+        /// Node IDs of clients registered on that relay
+        clients: Vec<NodeId>,
+    },
+
+    /// Mesh federation: relay-to-relay packet forwarding (single hop)
+    MeshForward {
+        /// Originating client's node ID
+        src_id: NodeId,
+        /// Destination client's node ID
+        dest_id: NodeId,
+        /// Encrypted payload (relays cannot decrypt)
+        payload: Vec<u8>,
+    },
 }
 
 /// Relay error codes
@@ -87,6 +137,8 @@ pub enum RelayErrorCode {
     AuthFailed = 6,
     /// Internal server error
     InternalError = 7,
+    /// Per-client bandwidth quota exceeded
+    QuotaExceeded = 8,
 }
 
 impl RelayMessage {
@@ -112,6 +164,12 @@ impl RelayMessage {
             RelayMessage::Keepalive => "Keepalive",
             RelayMessage::Disconnect => "Disconnect",
             RelayMessage::Error { .. } => "Error",
+            RelayMessage::LoadQuery => "LoadQuery",
+            RelayMessage::LoadReport { .. } => "LoadReport",
+            RelayMessage::TimeQuery { .. } => "TimeQuery",
+            RelayMessage::TimeReport { .. } => "TimeReport",
+            RelayMessage::MeshAnnounce { .. } => "MeshAnnounce",
+            RelayMessage::MeshForward { .. } => "MeshForward",
         }
     }
 }
@@ -133,6 +191,8 @@ pub enum RelayError {
     PeerNotFound,
     /// Rate limited
     RateLimited,
+    /// Bandwidth quota exceeded
+    QuotaExceeded,
     /// Invalid message
     InvalidMessage,
     /// Server full
@@ -153,6 +213,7 @@ impl fmt::Display for RelayError {
             RelayError::NotRegistered => write!(f, "Client not registered"),
             RelayError::PeerNotFound => write!(f, "Peer not found"),
             RelayError::RateLimited => write!(f, "Rate limited"),
+            RelayError::QuotaExceeded => write!(f, "Bandwidth quota exceeded"),
             RelayError::InvalidMessage => write!(f, "Invalid message"),
             RelayError::ServerFull => write!(f, "Server at capacity"),
             RelayError::AuthFailed => write!(f, "Authentication failed"),
@@ -178,6 +239,7 @@ impl From<RelayErrorCode> for RelayError {
             RelayErrorCode::InvalidMessage => RelayError::InvalidMessage,
             RelayErrorCode::ServerFull => RelayError::ServerFull,
             RelayErrorCode::AuthFailed => RelayError::AuthFailed,
+            RelayErrorCode::QuotaExceeded => RelayError::QuotaExceeded,
             RelayErrorCode::InternalError => RelayError::Internal("Unknown error".to_string()),
         }
     }
@@ -293,6 +355,49 @@ mod tests {
         assert_eq!(msg, decoded);
     }
 
+    #[test]
+    fn test_message_serialization_time_query_report() {
+        let query = RelayMessage::TimeQuery {
+            client_time_us: 1_000_000,
+        };
+        let bytes = query.to_bytes().unwrap();
+        assert_eq!(query, RelayMessage::from_bytes(&bytes).unwrap());
+
+        let report = RelayMessage::TimeReport {
+            client_time_us: 1_000_000,
+            relay_time_us: 1_005_000,
+        };
+        let bytes = report.to_bytes().unwrap();
+        assert_eq!(report, RelayMessage::from_bytes(&bytes).unwrap());
+    }
+
+    #[test]
+    fn test_message_serialization_mesh_announce() {
+        let msg = RelayMessage::MeshAnnounce {
+            relay_id: [8u8; 32],
+            clients: vec![[1u8; 32], [2u8; 32]],
+        };
+
+        let bytes = msg.to_bytes().unwrap();
+        let decoded = RelayMessage::from_bytes(&bytes).unwrap();
+
+        assert_eq!(msg, decoded);
+    }
+
+    #[test]
+    fn test_message_serialization_mesh_forward() {
+        let msg = RelayMessage::MeshForward {
+            src_id: [1u8; 32],
+            dest_id: [2u8; 32],
+            payload: vec![10, 20, 30],
+        };
+
+        let bytes = msg.to_bytes().unwrap();
+        let decoded = RelayMessage::from_bytes(&bytes).unwrap();
+
+        assert_eq!(msg, decoded);
+    }
+
     #[test]
     fn test_message_type() {
         let msg = RelayMessage::Register {
@@ -1,7 +1,10 @@
 //! Relay server for forwarding packets between peers.
 
+use super::accounting::{BandwidthAccountant, ChargeVerdict, ClientUsage, QuotaConfig, RelayLoad};
+use super::mesh::{MeshRoutingTable, RelayMeshConfig};
 use super::protocol::{NodeId, RelayError, RelayErrorCode, RelayMessage};
-use std::collections::HashMap;
+use crate::nat::stun::{StunMessage, StunMessageClass, StunMessageType, StunRateLimiter};
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -101,6 +104,22 @@ pub struct RelayServerConfig {
     pub client_timeout: Duration,
     /// Cleanup interval
     pub cleanup_interval: Duration,
+    /// Bandwidth quotas and fairness policy
+    pub quotas: QuotaConfig,
+    /// Mesh federation with peer relays
+    pub mesh: RelayMeshConfig,
+    /// Registration ACL: only these node IDs may register (`None` = open relay)
+    ///
+    /// Used by embedded relays that serve a fixed set of trusted peers;
+    /// can be updated at runtime via [`RelayServer::set_allowed_clients`].
+    pub allowed_clients: Option<HashSet<NodeId>>,
+    /// Answer STUN Binding requests on the relay socket
+    ///
+    /// Lets peers discover their reflexive address from the relay itself
+    /// instead of a third-party STUN server. Requests share the relay
+    /// port (demultiplexed by the STUN magic cookie) and are rate-limited
+    /// per source IP at the relay's `rate_limit`.
+    pub stun_enabled: bool,
 }
 
 impl Default for RelayServerConfig {
@@ -110,6 +129,10 @@ impl Default for RelayServerConfig {
             rate_limit: 100,
             client_timeout: Duration::from_secs(60),
             cleanup_interval: Duration::from_secs(30),
+            quotas: QuotaConfig::default(),
+            mesh: RelayMeshConfig::default(),
+            allowed_clients: None,
+            stun_enabled: true,
         }
     }
 }
@@ -124,6 +147,14 @@ pub struct RelayServer {
     socket: Arc<UdpSocket>,
     /// Rate limiter
     rate_limiter: Arc<RwLock<RateLimiter>>,
+    /// Bandwidth accountant (quotas and fairness)
+    accountant: Arc<RwLock<BandwidthAccountant>>,
+    /// Routes to clients registered on peer relays
+    mesh_routes: Arc<RwLock<MeshRoutingTable>>,
+    /// Registration ACL (`None` = open relay)
+    allowed_clients: Arc<RwLock<Option<HashSet<NodeId>>>>,
+    /// Per-IP rate limiter for STUN Binding requests
+    stun_limiter: StunRateLimiter,
     /// Server configuration
     config: RelayServerConfig,
     /// Server relay ID
@@ -176,6 +207,10 @@ impl RelayServer {
                 config.rate_limit,
                 Duration::from_secs(1),
             ))),
+            accountant: Arc::new(RwLock::new(BandwidthAccountant::new(config.quotas.clone()))),
+            mesh_routes: Arc::new(RwLock::new(MeshRoutingTable::new())),
+            allowed_clients: Arc::new(RwLock::new(config.allowed_clients.clone())),
+            stun_limiter: StunRateLimiter::new(config.rate_limit),
             config,
             relay_id,
         })
@@ -198,6 +233,11 @@ impl RelayServer {
         // Spawn cleanup task
         self.spawn_cleanup_task();
 
+        // Spawn mesh announcements when federation is configured
+        if self.config.mesh.is_enabled() {
+            self.spawn_mesh_task();
+        }
+
         let mut buf = vec![0u8; 65536];
 
         loop {
@@ -205,6 +245,13 @@ impl RelayServer {
                 Ok((len, from)) => {
                     let packet = &buf[..len];
 
+                    // STUN shares the relay socket; the magic cookie keeps
+                    // it unambiguous against bincode relay messages
+                    if self.config.stun_enabled && StunMessage::is_stun(packet) {
+                        self.handle_stun_binding(packet, from).await;
+                        continue;
+                    }
+
                     if let Ok(msg) = RelayMessage::from_bytes(packet) {
                         self.handle_message(msg, from).await;
                     }
@@ -249,14 +296,158 @@ impl RelayServer {
                     clients.remove(&node_id);
                 }
             }
+            RelayMessage::LoadQuery => {
+                // Admin query; open to anyone so selectors can probe
+                // relays before registering
+                let load = self.load().await;
+                let report = RelayMessage::LoadReport {
+                    client_count: load.client_count as u32,
+                    bytes_per_sec: load.bytes_per_sec,
+                    packets_per_sec: load.packets_per_sec,
+                    load: load.load,
+                };
+                if let Ok(bytes) = report.to_bytes() {
+                    let _ = self.socket.send_to(&bytes, from).await;
+                }
+            }
+            RelayMessage::TimeQuery { client_time_us } => {
+                // Like LoadQuery, open to anyone: both punch participants
+                // must be able to sync against the same relay clock even
+                // if only one of them is registered here
+                let report = RelayMessage::TimeReport {
+                    client_time_us,
+                    relay_time_us: crate::nat::coordination::unix_micros(),
+                };
+                if let Ok(bytes) = report.to_bytes() {
+                    let _ = self.socket.send_to(&bytes, from).await;
+                }
+            }
+            RelayMessage::MeshAnnounce {
+                relay_id: _,
+                clients,
+            } => {
+                self.handle_mesh_announce(clients, from).await;
+            }
+            RelayMessage::MeshForward {
+                src_id,
+                dest_id,
+                payload,
+            } => {
+                self.handle_mesh_forward(src_id, dest_id, payload, from)
+                    .await;
+            }
             _ => {
                 // Ignore other message types
             }
         }
     }
 
+    /// Answer a STUN Binding request with the sender's reflexive address
+    ///
+    /// Open to unregistered senders, like `LoadQuery`: reflexive-address
+    /// discovery happens before a peer has anything to register for.
+    /// Non-Binding methods and non-request classes are ignored — the
+    /// relay is a STUN server only to the extent hole punching needs one.
+    async fn handle_stun_binding(&self, packet: &[u8], from: SocketAddr) {
+        if !self.stun_limiter.allow_request(from.ip()) {
+            return;
+        }
+
+        let Ok(request) = StunMessage::decode(packet) else {
+            return;
+        };
+        if request.message_type != StunMessageType::Binding
+            || request.message_class != StunMessageClass::Request
+        {
+            return;
+        }
+
+        let response = StunMessage::binding_response(request.transaction_id, from);
+        let _ = self.socket.send_to(&response.encode(), from).await;
+    }
+
+    /// Handle a client-list announcement from a peer relay
+    ///
+    /// Only accepted from relays listed in the mesh configuration.
+    /// Locally registered clients are skipped so a local registration
+    /// always wins over a remote route.
+    async fn handle_mesh_announce(&self, announced: Vec<NodeId>, from: SocketAddr) {
+        if !self.config.mesh.is_peer(&from) {
+            return;
+        }
+
+        let clients = self.clients.read().await;
+        let mut routes = self.mesh_routes.write().await;
+        for client in announced {
+            if !clients.contains_key(&client) {
+                routes.learn(client, from);
+            }
+        }
+    }
+
+    /// Handle a packet forwarded from a peer relay
+    ///
+    /// Delivered only to locally registered clients; forwarding is
+    /// single-hop so a misrouted packet is dropped rather than bounced
+    /// around the mesh.
+    async fn handle_mesh_forward(
+        &self,
+        src_id: NodeId,
+        dest_id: NodeId,
+        payload: Vec<u8>,
+        from: SocketAddr,
+    ) {
+        if !self.config.mesh.is_peer(&from) {
+            return;
+        }
+
+        let clients = self.clients.read().await;
+        if let Some(dest_client) = clients.get(&dest_id) {
+            let dest_addr = dest_client.addr;
+            drop(clients);
+
+            let forward = RelayMessage::RecvPacket { src_id, payload };
+            if let Ok(bytes) = forward.to_bytes() {
+                let _ = self.socket.send_to(&bytes, dest_addr).await;
+            }
+        }
+    }
+
+    /// Announce the current client list to every configured mesh peer
+    pub async fn announce_to_mesh(&self) {
+        if !self.config.mesh.is_enabled() {
+            return;
+        }
+
+        let clients: Vec<NodeId> = self.clients.read().await.keys().copied().collect();
+        let announce = RelayMessage::MeshAnnounce {
+            relay_id: self.relay_id,
+            clients,
+        };
+
+        if let Ok(bytes) = announce.to_bytes() {
+            for peer in &self.config.mesh.peers {
+                let _ = self.socket.send_to(&bytes, peer).await;
+            }
+        }
+    }
+
     /// Handle client registration
     async fn handle_register(&self, node_id: NodeId, public_key: [u8; 32], from: SocketAddr) {
+        // Enforce the registration ACL before anything else
+        {
+            let allowed = self.allowed_clients.read().await;
+            let denied = allowed
+                .as_ref()
+                .is_some_and(|allowed| !allowed.contains(&node_id));
+            drop(allowed);
+            if denied {
+                self.send_error(from, RelayErrorCode::AuthFailed, "Not an allowed client")
+                    .await;
+                return;
+            }
+        }
+
         let mut clients = self.clients.write().await;
 
         // Check if server is full
@@ -303,6 +494,34 @@ impl RelayServer {
             }
         }
 
+        // Charge bandwidth quota and fairness policy
+        {
+            let mut accountant = self.accountant.write().await;
+            let verdict = accountant.charge(src_id, payload.len());
+            drop(accountant);
+            match verdict {
+                ChargeVerdict::Allowed => {}
+                ChargeVerdict::QuotaExceeded => {
+                    self.send_error(
+                        from,
+                        RelayErrorCode::QuotaExceeded,
+                        "Bandwidth quota exceeded",
+                    )
+                    .await;
+                    return;
+                }
+                ChargeVerdict::Throttled => {
+                    self.send_error(
+                        from,
+                        RelayErrorCode::RateLimited,
+                        "Relay saturated; reduce rate",
+                    )
+                    .await;
+                    return;
+                }
+            }
+        }
+
         // Find destination client
         let clients = self.clients.read().await;
         if let Some(dest_client) = clients.get(&dest_id) {
@@ -317,6 +536,25 @@ impl RelayServer {
             }
         } else {
             drop(clients);
+
+            // Not local: try a mesh route to a federated relay
+            let mesh_route = self
+                .mesh_routes
+                .read()
+                .await
+                .route(&dest_id, self.config.mesh.route_ttl);
+            if let Some(relay_addr) = mesh_route {
+                let forward = RelayMessage::MeshForward {
+                    src_id,
+                    dest_id,
+                    payload,
+                };
+                if let Ok(bytes) = forward.to_bytes() {
+                    let _ = self.socket.send_to(&bytes, relay_addr).await;
+                }
+                return;
+            }
+
             self.send_error(from, RelayErrorCode::PeerNotFound, "Peer not found")
                 .await;
         }
@@ -349,6 +587,7 @@ impl RelayServer {
     fn spawn_cleanup_task(&self) {
         let clients = self.clients.clone();
         let rate_limiter = self.rate_limiter.clone();
+        let accountant = self.accountant.clone();
         let timeout = self.config.client_timeout;
         let interval = self.config.cleanup_interval;
 
@@ -369,10 +608,68 @@ impl RelayServer {
                     let mut limiter = rate_limiter.write().await;
                     limiter.cleanup();
                 }
+
+                // Drop accounts of departed clients
+                {
+                    let clients_guard = clients.read().await;
+                    let mut acct = accountant.write().await;
+                    acct.retain(|id| clients_guard.contains_key(id));
+                }
             }
         });
     }
 
+    /// Spawn periodic mesh announcements and route pruning
+    fn spawn_mesh_task(&self) {
+        let socket = self.socket.clone();
+        let clients = self.clients.clone();
+        let mesh_routes = self.mesh_routes.clone();
+        let mesh = self.config.mesh.clone();
+        let relay_id = self.relay_id;
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(mesh.announce_interval);
+
+            loop {
+                ticker.tick().await;
+
+                // Announce current registrations to every peer relay
+                let client_ids: Vec<NodeId> = clients.read().await.keys().copied().collect();
+                let announce = RelayMessage::MeshAnnounce {
+                    relay_id,
+                    clients: client_ids,
+                };
+                if let Ok(bytes) = announce.to_bytes() {
+                    for peer in &mesh.peers {
+                        let _ = socket.send_to(&bytes, peer).await;
+                    }
+                }
+
+                // Expire routes that were not re-announced
+                mesh_routes.write().await.prune(mesh.route_ttl);
+            }
+        });
+    }
+
+    /// Replace the registration ACL at runtime
+    ///
+    /// Passing `None` opens the relay to any client; passing a set
+    /// restricts new registrations to the listed node IDs and evicts any
+    /// currently registered client that is no longer allowed. Embedded
+    /// relays call this when their trust store changes.
+    pub async fn set_allowed_clients(&self, allowed: Option<HashSet<NodeId>>) {
+        if let Some(allowed) = allowed.as_ref() {
+            let mut clients = self.clients.write().await;
+            clients.retain(|node_id, _| allowed.contains(node_id));
+        }
+        *self.allowed_clients.write().await = allowed;
+    }
+
+    /// Number of routes learned from peer relays
+    pub async fn mesh_route_count(&self) -> usize {
+        self.mesh_routes.read().await.len()
+    }
+
     /// Get number of connected clients
     pub async fn client_count(&self) -> usize {
         self.clients.read().await.len()
@@ -383,6 +680,29 @@ impl RelayServer {
     pub fn relay_id(&self) -> [u8; 32] {
         self.relay_id
     }
+
+    /// Actual bound address (resolves port 0 binds)
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the socket address cannot be retrieved.
+    pub fn local_addr(&self) -> Result<SocketAddr, RelayError> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// Current relay load snapshot (admin API)
+    ///
+    /// The same numbers are served to remote `LoadQuery` messages, so
+    /// `RelaySelector` load values can reflect reality.
+    pub async fn load(&self) -> RelayLoad {
+        let client_count = self.client_count().await;
+        self.accountant.write().await.load(client_count)
+    }
+
+    /// Usage accounting for one client, if it has relayed anything
+    pub async fn client_usage(&self, client: &NodeId) -> Option<ClientUsage> {
+        self.accountant.read().await.usage(client).cloned()
+    }
 }
 
 #[cfg(test)]
@@ -410,6 +730,275 @@ mod tests {
         assert_eq!(config.rate_limit, 100);
     }
 
+    #[tokio::test]
+    async fn test_relay_server_load_starts_idle() {
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = RelayServer::bind(addr).await.unwrap();
+
+        let load = server.load().await;
+        assert_eq!(load.client_count, 0);
+        assert_eq!(load.bytes_per_sec, 0);
+        assert_eq!(load.load, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_relay_server_client_usage_unknown_client() {
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = RelayServer::bind(addr).await.unwrap();
+
+        assert!(server.client_usage(&[9u8; 32]).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_relay_server_mesh_disabled_by_default() {
+        let config = RelayServerConfig::default();
+        assert!(!config.mesh.is_enabled());
+
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = RelayServer::bind(addr).await.unwrap();
+        assert_eq!(server.mesh_route_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_mesh_forwarding_between_federated_relays() {
+        use std::sync::Arc;
+
+        let addr1: SocketAddr = "127.0.0.1:47311".parse().unwrap();
+        let addr2: SocketAddr = "127.0.0.1:47312".parse().unwrap();
+
+        let config1 = RelayServerConfig {
+            mesh: RelayMeshConfig {
+                peers: vec![addr2],
+                ..RelayMeshConfig::default()
+            },
+            ..RelayServerConfig::default()
+        };
+        let config2 = RelayServerConfig {
+            mesh: RelayMeshConfig {
+                peers: vec![addr1],
+                ..RelayMeshConfig::default()
+            },
+            ..RelayServerConfig::default()
+        };
+
+        let relay1 = Arc::new(RelayServer::bind_with_config(addr1, config1).await.unwrap());
+        let relay2 = Arc::new(RelayServer::bind_with_config(addr2, config2).await.unwrap());
+
+        let r1 = relay1.clone();
+        tokio::spawn(async move {
+            let _ = r1.run().await;
+        });
+        let r2 = relay2.clone();
+        tokio::spawn(async move {
+            let _ = r2.run().await;
+        });
+
+        // Client A registers on relay 1, client B on relay 2
+        let client_a = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        client_a.connect(addr1).await.unwrap();
+        let register_a = RelayMessage::Register {
+            node_id: [0xAA; 32],
+            public_key: [1u8; 32],
+        };
+        client_a
+            .send(&register_a.to_bytes().unwrap())
+            .await
+            .unwrap();
+
+        let client_b = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        client_b.connect(addr2).await.unwrap();
+        let register_b = RelayMessage::Register {
+            node_id: [0xBB; 32],
+            public_key: [2u8; 32],
+        };
+        client_b
+            .send(&register_b.to_bytes().unwrap())
+            .await
+            .unwrap();
+
+        // Consume the register acks
+        let mut buf = vec![0u8; 65536];
+        client_a.recv(&mut buf).await.unwrap();
+        client_b.recv(&mut buf).await.unwrap();
+
+        // Relay 2 announces its clients; relay 1 learns the route to B
+        relay2.announce_to_mesh().await;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(relay1.mesh_route_count().await, 1);
+
+        // A sends to B through relay 1 -> relay 2
+        let packet = RelayMessage::SendPacket {
+            dest_id: [0xBB; 32],
+            payload: vec![1, 2, 3, 4],
+        };
+        client_a.send(&packet.to_bytes().unwrap()).await.unwrap();
+
+        let len = tokio::time::timeout(Duration::from_secs(5), client_b.recv(&mut buf))
+            .await
+            .expect("timed out waiting for forwarded packet")
+            .unwrap();
+        let received = RelayMessage::from_bytes(&buf[..len]).unwrap();
+        assert_eq!(
+            received,
+            RelayMessage::RecvPacket {
+                src_id: [0xAA; 32],
+                payload: vec![1, 2, 3, 4],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_time_query_reports_relay_clock() {
+        use std::sync::Arc;
+
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = Arc::new(RelayServer::bind(addr).await.unwrap());
+        let server_addr = server.local_addr().unwrap();
+
+        let s = server.clone();
+        tokio::spawn(async move {
+            let _ = s.run().await;
+        });
+
+        // Unregistered clients can query, like LoadQuery
+        let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        client.connect(server_addr).await.unwrap();
+        let query = RelayMessage::TimeQuery {
+            client_time_us: 42_000_000,
+        };
+        client.send(&query.to_bytes().unwrap()).await.unwrap();
+
+        let mut buf = vec![0u8; 65536];
+        let len = tokio::time::timeout(Duration::from_secs(5), client.recv(&mut buf))
+            .await
+            .expect("timed out waiting for TimeReport")
+            .unwrap();
+
+        match RelayMessage::from_bytes(&buf[..len]).unwrap() {
+            RelayMessage::TimeReport {
+                client_time_us,
+                relay_time_us,
+            } => {
+                assert_eq!(client_time_us, 42_000_000);
+                assert!(relay_time_us > 0);
+            }
+            other => panic!("expected TimeReport, got {}", other.message_type()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_relay_answers_stun_binding() {
+        use std::sync::Arc;
+
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = Arc::new(RelayServer::bind(addr).await.unwrap());
+        let server_addr = server.local_addr().unwrap();
+        let s = server.clone();
+        tokio::spawn(async move {
+            let _ = s.run().await;
+        });
+
+        let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let client_addr = client.local_addr().unwrap();
+        let request = StunMessage::binding_request();
+        client
+            .send_to(&request.encode(), server_addr)
+            .await
+            .unwrap();
+
+        let mut buf = vec![0u8; 1500];
+        let (len, _) = tokio::time::timeout(Duration::from_secs(5), client.recv_from(&mut buf))
+            .await
+            .expect("timed out waiting for STUN response")
+            .unwrap();
+
+        let response = StunMessage::decode(&buf[..len]).unwrap();
+        assert_eq!(response.message_class, StunMessageClass::SuccessResponse);
+        assert_eq!(response.transaction_id, request.transaction_id);
+        // No NAT on loopback: the reflexive address is the socket itself
+        assert_eq!(response.xor_mapped_address(), Some(client_addr));
+    }
+
+    #[tokio::test]
+    async fn test_stun_disabled_ignores_binding() {
+        use std::sync::Arc;
+
+        let config = RelayServerConfig {
+            stun_enabled: false,
+            ..RelayServerConfig::default()
+        };
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = Arc::new(RelayServer::bind_with_config(addr, config).await.unwrap());
+        let server_addr = server.local_addr().unwrap();
+        let s = server.clone();
+        tokio::spawn(async move {
+            let _ = s.run().await;
+        });
+
+        let client = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let request = StunMessage::binding_request();
+        client
+            .send_to(&request.encode(), server_addr)
+            .await
+            .unwrap();
+
+        let mut buf = vec![0u8; 1500];
+        let result =
+            tokio::time::timeout(Duration::from_millis(300), client.recv_from(&mut buf)).await;
+        assert!(result.is_err(), "expected no STUN response");
+    }
+
+    #[tokio::test]
+    async fn test_acl_open_by_default() {
+        let config = RelayServerConfig::default();
+        assert!(config.allowed_clients.is_none());
+
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = RelayServer::bind_with_config(addr, config).await.unwrap();
+        server.handle_register([0xAA; 32], [1u8; 32], addr).await;
+        assert_eq!(server.client_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_acl_rejects_unlisted_client() {
+        let config = RelayServerConfig {
+            allowed_clients: Some(HashSet::from([[0xAA; 32]])),
+            ..RelayServerConfig::default()
+        };
+
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = RelayServer::bind_with_config(addr, config).await.unwrap();
+
+        server.handle_register([0xAA; 32], [1u8; 32], addr).await;
+        server.handle_register([0xBB; 32], [2u8; 32], addr).await;
+        assert_eq!(server.client_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_set_allowed_clients_evicts_removed_peers() {
+        let addr = "127.0.0.1:0".parse().unwrap();
+        let server = RelayServer::bind(addr).await.unwrap();
+
+        server.handle_register([0xAA; 32], [1u8; 32], addr).await;
+        server.handle_register([0xBB; 32], [2u8; 32], addr).await;
+        assert_eq!(server.client_count().await, 2);
+
+        // Tightening the ACL drops the no-longer-allowed client
+        server
+            .set_allowed_clients(Some(HashSet::from([[0xAA; 32]])))
+            .await;
+        assert_eq!(server.client_count().await, 1);
+
+        // And blocks it from re-registering
+        server.handle_register([0xBB; 32], [2u8; 32], addr).await;
+        assert_eq!(server.client_count().await, 1);
+
+        // Reopening restores open registration
+        server.set_allowed_clients(None).await;
+        server.handle_register([0xBB; 32], [2u8; 32], addr).await;
+        assert_eq!(server.client_count().await, 2);
+    }
+
     #[test]
     fn test_client_connection() {
         let addr = "127.0.0.1:8000".parse().unwrap();
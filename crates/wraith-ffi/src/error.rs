@@ -93,6 +93,10 @@ impl WraithError {
         Self::new(WraithErrorCode::InternalError, message)
     }
 
+    pub fn timeout(message: impl Into<String>) -> Self {
+        Self::new(WraithErrorCode::Timeout, message)
+    }
+
     /// Convert error to C-compatible error string
     pub fn to_c_string(&self) -> *mut c_char {
         CString::new(self.message.clone())
@@ -133,6 +137,12 @@ impl From<wraith_core::node::NodeError> for WraithError {
             NodeError::PeerNotFound(_) => {
                 Self::new(WraithErrorCode::SessionNotFound, err.to_string())
             }
+            NodeError::TrustViolation(_) => {
+                Self::new(WraithErrorCode::CryptoError, err.to_string())
+            }
+            NodeError::AuthorizationDenied(_) => {
+                Self::new(WraithErrorCode::InvalidArgument, err.to_string())
+            }
             NodeError::Migration(_) => Self::new(WraithErrorCode::InternalError, err.to_string()),
             NodeError::Obfuscation(_) => Self::new(WraithErrorCode::InternalError, err.to_string()),
             NodeError::InvalidConfig(_) => {
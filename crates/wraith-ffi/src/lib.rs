@@ -27,9 +27,10 @@
 // FFI code inherently requires unsafe operations within unsafe functions
 #![allow(unsafe_op_in_unsafe_fn)]
 
+use std::collections::HashMap;
 use std::ffi::{CStr, CString};
-use std::os::raw::{c_char, c_int};
-use std::sync::Arc;
+use std::os::raw::{c_char, c_int, c_void};
+use std::sync::{Arc, Mutex};
 
 use tokio::runtime::Runtime;
 use wraith_core::node::Node;
@@ -73,8 +74,65 @@ pub struct WraithConfig {
 pub(crate) struct NodeHandle {
     pub(crate) node: Node,
     pub(crate) runtime: Arc<Runtime>,
+    /// Per-transfer progress watcher tasks, keyed by transfer ID
+    pub(crate) progress_watchers: Mutex<HashMap<[u8; 32], tokio::task::JoinHandle<()>>>,
+    /// Node-wide event watcher task (at most one)
+    pub(crate) event_watcher: Mutex<Option<tokio::task::JoinHandle<()>>>,
 }
 
+impl Drop for NodeHandle {
+    fn drop(&mut self) {
+        // Abort watcher tasks before the runtime shuts down so no callback
+        // fires after wraith_node_free() returns.
+        if let Ok(mut watchers) = self.progress_watchers.lock() {
+            for (_, task) in watchers.drain() {
+                task.abort();
+            }
+        }
+        if let Ok(mut watcher) = self.event_watcher.lock()
+            && let Some(task) = watcher.take()
+        {
+            task.abort();
+        }
+    }
+}
+
+/// Run a blocking FFI operation on the runtime with an optional deadline
+///
+/// A `timeout_ms` of 0 means no deadline (wait indefinitely). When the
+/// deadline expires the underlying future is dropped (cancelling the
+/// operation) and a [`WraithErrorCode::Timeout`] error is returned, so the
+/// embedding application can abort hung handshakes or stalled transfers
+/// without killing the process.
+pub(crate) fn block_on_with_deadline<F>(
+    runtime: &Runtime,
+    timeout_ms: u64,
+    future: F,
+) -> Result<F::Output, WraithError>
+where
+    F: std::future::Future,
+{
+    if timeout_ms == 0 {
+        return Ok(runtime.block_on(future));
+    }
+
+    runtime
+        .block_on(async move {
+            tokio::time::timeout(std::time::Duration::from_millis(timeout_ms), future).await
+        })
+        .map_err(|_| WraithError::timeout(format!("Operation exceeded {timeout_ms} ms deadline")))
+}
+
+/// Caller-supplied opaque context pointer passed back to C callbacks
+///
+/// The pointer is never dereferenced by Rust; the host guarantees it stays
+/// valid (and thread-safe to use) for as long as the callback is registered.
+pub(crate) struct CallbackUserData(pub(crate) *mut c_void);
+
+// SAFETY: the pointer is treated as an opaque token and only handed back to
+// the caller's callback, which per the documented ABI must be thread-safe.
+unsafe impl Send for CallbackUserData {}
+
 /// Initialize the WRAITH FFI library
 ///
 /// Must be called before any other FFI functions.
@@ -110,6 +168,49 @@ pub extern "C" fn wraith_version() -> *const c_char {
     VERSION.as_ptr() as *const c_char
 }
 
+/// Get compiled features and detected platform capabilities as JSON
+///
+/// Returns a newly allocated null-terminated JSON string describing which
+/// acceleration features (AF_XDP, io_uring, AES-NI, SIMD) and algorithm
+/// families are usable on this system, so GUIs and support tooling can
+/// adapt without parsing human-readable output. The key set is a stable
+/// contract: keys are only added, never renamed or removed.
+///
+/// Caller must free the returned pointer with `wraith_free_string()`.
+///
+/// # Safety
+///
+/// The returned pointer must not be freed by any other allocator.
+#[unsafe(no_mangle)]
+pub extern "C" fn wraith_features_json() -> *mut c_char {
+    let transport = wraith_transport::capabilities::TransportCapabilities::detect();
+    let crypto = wraith_crypto::capabilities::CryptoCapabilities::detect();
+
+    let features = serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "platform": {
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+        },
+        "transport": {
+            "udp": transport.udp,
+            "af_xdp": transport.af_xdp,
+            "io_uring": transport.io_uring,
+            "bpf_fs": transport.bpf_fs,
+        },
+        "crypto": {
+            "aes_ni": crypto.aes_ni,
+            "avx2": crypto.avx2,
+            "sse41": crypto.sse41,
+            "neon": crypto.neon,
+            "pq_kem": crypto.pq_kem,
+        },
+        "compression": [],
+    });
+
+    to_c_string(features.to_string())
+}
+
 /// Free a string returned by WRAITH FFI functions
 ///
 /// # Safety
@@ -233,6 +334,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_wraith_features_json_is_valid_json() {
+        let c_str = wraith_features_json();
+
+        unsafe {
+            let json = from_c_string(c_str).unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+            assert_eq!(parsed["version"], env!("CARGO_PKG_VERSION"));
+            assert!(parsed["transport"]["udp"].as_bool().unwrap());
+            assert!(parsed["crypto"]["pq_kem"].is_boolean());
+            assert!(parsed["compression"].is_array());
+
+            wraith_free_string(c_str);
+        }
+    }
+
+    #[test]
+    fn test_block_on_with_deadline_zero_means_no_deadline() {
+        let runtime = Runtime::new().unwrap();
+        let result = block_on_with_deadline(&runtime, 0, async { 42 });
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[test]
+    fn test_block_on_with_deadline_completes_within_deadline() {
+        let runtime = Runtime::new().unwrap();
+        let result = block_on_with_deadline(&runtime, 5_000, async { 42 });
+        assert_eq!(result.unwrap(), 42);
+    }
+
+    #[test]
+    fn test_block_on_with_deadline_expires() {
+        let runtime = Runtime::new().unwrap();
+        let result = block_on_with_deadline(&runtime, 10, std::future::pending::<()>());
+
+        let err = result.unwrap_err();
+        assert_eq!(err.code, WraithErrorCode::Timeout);
+        assert!(err.message.contains("10 ms"));
+    }
+
     #[test]
     fn test_to_c_string_with_embedded_null() {
         // String with embedded null bytes should be handled gracefully
@@ -1,16 +1,20 @@
 //! Node API FFI
 
-use std::os::raw::{c_char, c_int};
+use std::os::raw::{c_char, c_int, c_void};
 use std::sync::Arc;
 
 use tokio::runtime::Runtime;
-use wraith_core::node::Node;
+use tokio::sync::broadcast;
 use wraith_core::node::config::NodeConfig;
+use wraith_core::node::{Node, NodeEvent};
 
 use crate::config::ConfigHandle;
 use crate::error::{WraithError, WraithErrorCode};
 use crate::types::*;
-use crate::{NodeHandle, WraithConfig, WraithNode, ffi_try, ffi_try_ptr};
+use crate::{
+    CallbackUserData, NodeHandle, WraithConfig, WraithNode, block_on_with_deadline, ffi_try,
+    ffi_try_ptr,
+};
 
 /// Create a new node with random identity
 ///
@@ -46,11 +50,39 @@ pub unsafe extern "C" fn wraith_node_new(
     let handle = Box::new(NodeHandle {
         node,
         runtime: Arc::new(runtime),
+        progress_watchers: Default::default(),
+        event_watcher: Default::default(),
     });
 
     Box::into_raw(handle) as *mut WraithNode
 }
 
+/// Create a new node isolated from every other node in the process
+///
+/// Identical in behavior to `wraith_node_new()`; this entry point exists to
+/// make the multi-tenant contract explicit for server applications hosting
+/// one node per tenant in a single process. Every handle owns its own
+/// identity, configuration, sockets, and Tokio runtime, and the library
+/// keeps no shared mutable state between handles — starting, stopping, or
+/// freeing one tenant's node never affects another's. The only process-wide
+/// state is the logging subscriber installed by `wraith_init()`.
+///
+/// Leave the configured listen port at 0 (the default) so each tenant binds
+/// its own ephemeral port.
+///
+/// # Safety
+///
+/// - `config` must be a valid configuration handle or null (uses default config)
+/// - `error_out` must be null or a valid pointer to receive error message
+/// - Caller must free the returned handle with `wraith_node_free()`
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_node_new_isolated(
+    config: *const WraithConfig,
+    error_out: *mut *mut c_char,
+) -> *mut WraithNode {
+    wraith_node_new(config, error_out)
+}
+
 /// Free a node handle
 ///
 /// This will stop the node if it's running and clean up all resources.
@@ -72,6 +104,7 @@ pub unsafe extern "C" fn wraith_node_free(node: *mut WraithNode) {
 /// Start the node
 ///
 /// This initializes the transport layer and begins listening for connections.
+/// Equivalent to `wraith_node_start_with_timeout()` with no deadline.
 ///
 /// # Safety
 ///
@@ -81,6 +114,25 @@ pub unsafe extern "C" fn wraith_node_free(node: *mut WraithNode) {
 pub unsafe extern "C" fn wraith_node_start(
     node: *mut WraithNode,
     error_out: *mut *mut c_char,
+) -> c_int {
+    wraith_node_start_with_timeout(node, 0, error_out)
+}
+
+/// Start the node, bounded by a deadline
+///
+/// A `timeout_ms` of 0 means no deadline. If the deadline expires before
+/// startup completes, the attempt is aborted and `WRAITH_ERROR_TIMEOUT` is
+/// returned; the node is left stopped and start may be retried.
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_node_start_with_timeout(
+    node: *mut WraithNode,
+    timeout_ms: u64,
+    error_out: *mut *mut c_char,
 ) -> c_int {
     if node.is_null() {
         if !error_out.is_null() {
@@ -93,12 +145,15 @@ pub unsafe extern "C" fn wraith_node_start(
     let node_clone = handle.node.clone();
     let runtime = handle.runtime.clone();
 
-    ffi_try!(
-        runtime
-            .block_on(async move { node_clone.start().await })
-            .map_err(WraithError::from),
+    let result = ffi_try!(
+        block_on_with_deadline(
+            &runtime,
+            timeout_ms,
+            async move { node_clone.start().await }
+        ),
         error_out
     );
+    ffi_try!(result.map_err(WraithError::from), error_out);
 
     WraithErrorCode::Success as c_int
 }
@@ -106,6 +161,7 @@ pub unsafe extern "C" fn wraith_node_start(
 /// Stop the node
 ///
 /// This gracefully shuts down the transport layer and closes all sessions.
+/// Equivalent to `wraith_node_stop_with_timeout()` with no deadline.
 ///
 /// # Safety
 ///
@@ -115,6 +171,26 @@ pub unsafe extern "C" fn wraith_node_start(
 pub unsafe extern "C" fn wraith_node_stop(
     node: *mut WraithNode,
     error_out: *mut *mut c_char,
+) -> c_int {
+    wraith_node_stop_with_timeout(node, 0, error_out)
+}
+
+/// Stop the node, bounded by a deadline
+///
+/// A `timeout_ms` of 0 means no deadline. If graceful shutdown does not
+/// finish within the deadline it is abandoned and `WRAITH_ERROR_TIMEOUT` is
+/// returned; the handle remains valid and may still be freed with
+/// `wraith_node_free()`.
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_node_stop_with_timeout(
+    node: *mut WraithNode,
+    timeout_ms: u64,
+    error_out: *mut *mut c_char,
 ) -> c_int {
     if node.is_null() {
         if !error_out.is_null() {
@@ -127,12 +203,11 @@ pub unsafe extern "C" fn wraith_node_stop(
     let node_clone = handle.node.clone();
     let runtime = handle.runtime.clone();
 
-    ffi_try!(
-        runtime
-            .block_on(async move { node_clone.stop().await })
-            .map_err(WraithError::from),
+    let result = ffi_try!(
+        block_on_with_deadline(&runtime, timeout_ms, async move { node_clone.stop().await }),
         error_out
     );
+    ffi_try!(result.map_err(WraithError::from), error_out);
 
     WraithErrorCode::Success as c_int
 }
@@ -186,6 +261,182 @@ pub unsafe extern "C" fn wraith_node_get_id(
     WraithErrorCode::Success as c_int
 }
 
+/// Estimate achievable throughput to a peer before starting a transfer
+///
+/// Answers from the node's passive knowledge (a fresh path estimate from
+/// a recent transfer, else persisted peer history). When neither exists
+/// and `allow_probe` is true, a short packet-train probe measures the
+/// path instead: a few kilobytes and one round trip, requiring the node
+/// to be started and the peer reachable. With `allow_probe` false the
+/// call never sends anything and fails for a never-measured peer.
+///
+/// Applications can divide a planned transfer's size by
+/// `estimate_out->bandwidth_bps` to predict its duration, or compare
+/// estimates across peers to pick a source.
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `peer_id` must be a valid pointer to a 32-byte peer ID
+/// - `estimate_out` must be a valid pointer to a WraithBandwidthEstimate
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_node_estimate_bandwidth(
+    node: *mut WraithNode,
+    peer_id: *const WraithNodeId,
+    allow_probe: bool,
+    estimate_out: *mut WraithBandwidthEstimate,
+    error_out: *mut *mut c_char,
+) -> c_int {
+    if node.is_null() {
+        if !error_out.is_null() {
+            *error_out = WraithError::invalid_argument("node is null").to_c_string();
+        }
+        return WraithErrorCode::InvalidArgument as c_int;
+    }
+
+    if peer_id.is_null() {
+        if !error_out.is_null() {
+            *error_out = WraithError::invalid_argument("peer_id is null").to_c_string();
+        }
+        return WraithErrorCode::InvalidArgument as c_int;
+    }
+
+    if estimate_out.is_null() {
+        if !error_out.is_null() {
+            *error_out = WraithError::invalid_argument("estimate_out is null").to_c_string();
+        }
+        return WraithErrorCode::InvalidArgument as c_int;
+    }
+
+    let handle = &mut *(node as *mut NodeHandle);
+    let node_clone = handle.node.clone();
+    let peer_id_bytes = (*peer_id).bytes;
+
+    let estimate = ffi_try!(
+        handle
+            .runtime
+            .block_on(async move {
+                match node_clone.estimate_bandwidth(&peer_id_bytes).await {
+                    Err(_) if allow_probe => node_clone.probe_bandwidth(&peer_id_bytes).await,
+                    result => result,
+                }
+            })
+            .map_err(WraithError::from),
+        error_out
+    );
+
+    *estimate_out = WraithBandwidthEstimate {
+        bandwidth_bps: estimate.bandwidth_bps,
+        rtt_us: estimate.rtt.map_or(0, |rtt| rtt.as_micros() as u64),
+        source: estimate.source.into(),
+    };
+
+    WraithErrorCode::Success as c_int
+}
+
+/// Register a node event callback
+///
+/// The callback receives push-based state change notifications: node
+/// start/stop transitions and transfers becoming active or finishing.
+/// Registering a new callback replaces the previous one; passing a null
+/// `callback` unregisters without replacement. At most one event callback
+/// is active per node.
+///
+/// Callback ABI rules (see [`WraithEventCallback`]):
+/// - Invoked from an internal runtime thread, never the registering thread
+/// - Must be thread-safe and return promptly
+/// - Must not call blocking WRAITH FFI functions on the same node or free it
+/// - `user_data` must remain valid until the callback is unregistered or the
+///   node is freed; no invocations occur after `wraith_node_free()` returns
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `callback`, if non-null, must satisfy the ABI rules above
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_node_set_event_callback(
+    node: *mut WraithNode,
+    callback: WraithEventCallback,
+    user_data: *mut c_void,
+    error_out: *mut *mut c_char,
+) -> c_int {
+    if node.is_null() {
+        if !error_out.is_null() {
+            *error_out = WraithError::invalid_argument("node is null").to_c_string();
+        }
+        return WraithErrorCode::InvalidArgument as c_int;
+    }
+
+    let handle = &*(node as *const NodeHandle);
+
+    // Replacing or clearing always stops the previous watcher first
+    let mut watcher = handle
+        .event_watcher
+        .lock()
+        .expect("event watcher lock poisoned");
+    if let Some(task) = watcher.take() {
+        task.abort();
+    }
+
+    let Some(callback) = callback else {
+        return WraithErrorCode::Success as c_int;
+    };
+
+    let mut events = handle.node.subscribe();
+    let user_data = CallbackUserData(user_data);
+
+    let task = handle.runtime.spawn(async move {
+        let user_data = user_data;
+
+        // SAFETY (applies to all invocations below): the caller guarantees
+        // the callback and user_data obey the documented ABI rules while the
+        // watcher is registered.
+        loop {
+            let event = match events.recv().await {
+                Ok(event) => event,
+                // Dropped events are unrecoverable for a diffing consumer,
+                // but the C event set is edge-triggered state changes, so
+                // just keep consuming
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+
+            match event {
+                NodeEvent::NodeStarted => unsafe {
+                    callback(
+                        WraithNodeEventType::NodeStarted,
+                        std::ptr::null(),
+                        user_data.0,
+                    );
+                },
+                NodeEvent::NodeStopped => unsafe {
+                    callback(
+                        WraithNodeEventType::NodeStopped,
+                        std::ptr::null(),
+                        user_data.0,
+                    );
+                },
+                NodeEvent::TransferStarted { transfer_id, .. } => {
+                    let id = WraithTransferId { bytes: transfer_id };
+                    unsafe { callback(WraithNodeEventType::TransferStarted, &id, user_data.0) };
+                }
+                NodeEvent::TransferCompleted { transfer_id } => {
+                    let id = WraithTransferId { bytes: transfer_id };
+                    unsafe { callback(WraithNodeEventType::TransferFinished, &id, user_data.0) };
+                }
+                // Not represented in the C event enum
+                _ => {}
+            }
+        }
+    });
+
+    *watcher = Some(task);
+
+    WraithErrorCode::Success as c_int
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -233,6 +484,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_node_new_isolated_nodes_are_independent() {
+        unsafe {
+            let a = wraith_node_new_isolated(ptr::null(), ptr::null_mut());
+            let b = wraith_node_new_isolated(ptr::null(), ptr::null_mut());
+            assert!(!a.is_null());
+            assert!(!b.is_null());
+
+            let mut id_a = WraithNodeId { bytes: [0u8; 32] };
+            let mut id_b = WraithNodeId { bytes: [0u8; 32] };
+            assert_eq!(wraith_node_get_id(a, &mut id_a, ptr::null_mut()), 0);
+            assert_eq!(wraith_node_get_id(b, &mut id_b, ptr::null_mut()), 0);
+            assert_ne!(id_a.bytes, id_b.bytes);
+
+            // Freeing one tenant's node leaves the other usable
+            wraith_node_free(a);
+            assert!(!wraith_node_is_running(b));
+            wraith_node_free(b);
+        }
+    }
+
     #[test]
     fn test_node_free_null() {
         unsafe {
@@ -291,6 +563,72 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_estimate_bandwidth_unknown_peer_without_probe() {
+        unsafe {
+            let node = wraith_node_new(ptr::null(), ptr::null_mut());
+            let peer = WraithNodeId { bytes: [5u8; 32] };
+            let mut estimate = WraithBandwidthEstimate {
+                bandwidth_bps: 0,
+                rtt_us: 0,
+                source: WraithEstimateSource::History,
+            };
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+
+            // No measurement exists and probing is disallowed
+            let result =
+                wraith_node_estimate_bandwidth(node, &peer, false, &mut estimate, &mut error_ptr);
+            assert_ne!(result, WraithErrorCode::Success as c_int);
+            if !error_ptr.is_null() {
+                crate::wraith_free_string(error_ptr);
+            }
+
+            wraith_node_free(node);
+        }
+    }
+
+    #[test]
+    fn test_estimate_bandwidth_null_arguments() {
+        unsafe {
+            let peer = WraithNodeId { bytes: [5u8; 32] };
+            let mut estimate = WraithBandwidthEstimate {
+                bandwidth_bps: 0,
+                rtt_us: 0,
+                source: WraithEstimateSource::History,
+            };
+
+            let result = wraith_node_estimate_bandwidth(
+                ptr::null_mut(),
+                &peer,
+                false,
+                &mut estimate,
+                ptr::null_mut(),
+            );
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+
+            let node = wraith_node_new(ptr::null(), ptr::null_mut());
+            let result = wraith_node_estimate_bandwidth(
+                node,
+                ptr::null(),
+                false,
+                &mut estimate,
+                ptr::null_mut(),
+            );
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+
+            let result = wraith_node_estimate_bandwidth(
+                node,
+                &peer,
+                false,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+
+            wraith_node_free(node);
+        }
+    }
+
     #[test]
     fn test_node_is_running_null() {
         unsafe {
@@ -368,6 +706,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_node_start_with_timeout_null() {
+        unsafe {
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+            let result = wraith_node_start_with_timeout(ptr::null_mut(), 1_000, &mut error_ptr);
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+
+            let error_msg = CStr::from_ptr(error_ptr).to_str().unwrap();
+            assert!(error_msg.contains("node is null"));
+            crate::wraith_free_string(error_ptr);
+        }
+    }
+
+    #[test]
+    fn test_node_stop_with_timeout_null() {
+        unsafe {
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+            let result = wraith_node_stop_with_timeout(ptr::null_mut(), 1_000, &mut error_ptr);
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+
+            let error_msg = CStr::from_ptr(error_ptr).to_str().unwrap();
+            assert!(error_msg.contains("node is null"));
+            crate::wraith_free_string(error_ptr);
+        }
+    }
+
     #[test]
     fn test_node_double_start() {
         unsafe {
@@ -402,6 +770,71 @@ mod tests {
         }
     }
 
+    unsafe extern "C" fn count_events(
+        _event: WraithNodeEventType,
+        _transfer_id: *const WraithTransferId,
+        user_data: *mut c_void,
+    ) {
+        unsafe {
+            let counter = &*(user_data as *const std::sync::atomic::AtomicU32);
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_set_event_callback_null_node() {
+        unsafe {
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+            let result = wraith_node_set_event_callback(
+                ptr::null_mut(),
+                Some(count_events),
+                ptr::null_mut(),
+                &mut error_ptr,
+            );
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+
+            let error_msg = CStr::from_ptr(error_ptr).to_str().unwrap();
+            assert!(error_msg.contains("node is null"));
+            crate::wraith_free_string(error_ptr);
+        }
+    }
+
+    #[test]
+    fn test_set_event_callback_register_replace_clear() {
+        unsafe {
+            let node = wraith_node_new(ptr::null(), ptr::null_mut());
+            let counter = std::sync::atomic::AtomicU32::new(0);
+            let user_data = &counter as *const _ as *mut c_void;
+
+            // Register
+            let result = wraith_node_set_event_callback(
+                node,
+                Some(count_events),
+                user_data,
+                ptr::null_mut(),
+            );
+            assert_eq!(result, WraithErrorCode::Success as c_int);
+
+            // Replace (previous watcher is stopped)
+            let result = wraith_node_set_event_callback(
+                node,
+                Some(count_events),
+                user_data,
+                ptr::null_mut(),
+            );
+            assert_eq!(result, WraithErrorCode::Success as c_int);
+
+            // Clear with null callback
+            let result =
+                wraith_node_set_event_callback(node, None, ptr::null_mut(), ptr::null_mut());
+            assert_eq!(result, WraithErrorCode::Success as c_int);
+
+            wraith_node_free(node);
+        }
+    }
+
     #[test]
     fn test_node_stop_before_start() {
         unsafe {
@@ -4,10 +4,12 @@ use std::os::raw::{c_char, c_int};
 
 use crate::error::{WraithError, WraithErrorCode};
 use crate::types::*;
-use crate::{NodeHandle, WraithNode, WraithSession, ffi_try};
+use crate::{NodeHandle, WraithNode, WraithSession, block_on_with_deadline, ffi_try};
 
 /// Establish a new session with a peer
 ///
+/// Equivalent to `wraith_session_establish_with_timeout()` with no deadline.
+///
 /// # Safety
 ///
 /// - `node` must be a valid node handle
@@ -20,6 +22,30 @@ pub unsafe extern "C" fn wraith_session_establish(
     peer_id: *const WraithNodeId,
     session_out: *mut *mut WraithSession,
     error_out: *mut *mut c_char,
+) -> c_int {
+    wraith_session_establish_with_timeout(node, peer_id, 0, session_out, error_out)
+}
+
+/// Establish a new session with a peer, bounded by a deadline
+///
+/// A `timeout_ms` of 0 means no deadline. If the deadline expires before the
+/// handshake completes, the attempt is aborted and `WRAITH_ERROR_TIMEOUT` is
+/// returned, so hosts can recover from hung handshakes without killing the
+/// process.
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `peer_id` must be a valid pointer to a WraithNodeId struct (32-byte peer ID)
+/// - `session_out` must be a valid pointer to receive the session handle
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_session_establish_with_timeout(
+    node: *mut WraithNode,
+    peer_id: *const WraithNodeId,
+    timeout_ms: u64,
+    session_out: *mut *mut WraithSession,
+    error_out: *mut *mut c_char,
 ) -> c_int {
     if node.is_null() {
         if !error_out.is_null() {
@@ -47,12 +73,13 @@ pub unsafe extern "C" fn wraith_session_establish(
     let node_clone = handle.node.clone();
     let runtime = handle.runtime.clone();
 
-    let _session_id = ffi_try!(
-        runtime
-            .block_on(async move { node_clone.establish_session(&peer_id_bytes).await })
-            .map_err(WraithError::from),
+    let result = ffi_try!(
+        block_on_with_deadline(&runtime, timeout_ms, async move {
+            node_clone.establish_session(&peer_id_bytes).await
+        }),
         error_out
     );
+    let _session_id = ffi_try!(result.map_err(WraithError::from), error_out);
 
     // Store peer_id in handle (needed for close_session which takes peer_id)
     let session_handle = Box::new(peer_id_bytes);
@@ -63,6 +90,8 @@ pub unsafe extern "C" fn wraith_session_establish(
 
 /// Close an active session
 ///
+/// Equivalent to `wraith_session_close_with_timeout()` with no deadline.
+///
 /// # Safety
 ///
 /// - `node` must be a valid node handle
@@ -74,6 +103,29 @@ pub unsafe extern "C" fn wraith_session_close(
     node: *mut WraithNode,
     session: *mut WraithSession,
     error_out: *mut *mut c_char,
+) -> c_int {
+    wraith_session_close_with_timeout(node, session, 0, error_out)
+}
+
+/// Close an active session, bounded by a deadline
+///
+/// A `timeout_ms` of 0 means no deadline. If the deadline expires the close
+/// is abandoned and `WRAITH_ERROR_TIMEOUT` is returned; the session handle
+/// is consumed either way, so an unresponsive peer cannot hold the caller
+/// hostage during teardown.
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `session` must be a valid session handle returned by `wraith_session_establish()`
+/// - `error_out` must be null or a valid pointer to receive error message
+/// - `session` must not be used after this call
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_session_close_with_timeout(
+    node: *mut WraithNode,
+    session: *mut WraithSession,
+    timeout_ms: u64,
+    error_out: *mut *mut c_char,
 ) -> c_int {
     if node.is_null() {
         if !error_out.is_null() {
@@ -97,12 +149,13 @@ pub unsafe extern "C" fn wraith_session_close(
     let node_clone = handle.node.clone();
     let runtime = handle.runtime.clone();
 
-    ffi_try!(
-        runtime
-            .block_on(async move { node_clone.close_session(&peer_id_bytes).await })
-            .map_err(WraithError::from),
+    let result = ffi_try!(
+        block_on_with_deadline(&runtime, timeout_ms, async move {
+            node_clone.close_session(&peer_id_bytes).await
+        }),
         error_out
     );
+    ffi_try!(result.map_err(WraithError::from), error_out);
 
     WraithErrorCode::Success as c_int
 }
@@ -169,6 +222,62 @@ pub unsafe extern "C" fn wraith_session_get_stats(
     }
 }
 
+/// Get the short authentication string for an established session
+///
+/// Writes two newly allocated NUL-terminated UTF-8 strings: a six-digit
+/// decimal code and a four-emoji sequence, both derived from the handshake
+/// transcript. Both users compare either rendering out-of-band to rule out
+/// a man-in-the-middle on first contact. Free both with
+/// `wraith_free_string()`.
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `session` must be a valid session handle
+/// - `digits_out` and `emoji_out` must be valid pointers to receive strings
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_session_get_sas(
+    node: *const WraithNode,
+    session: *const WraithSession,
+    digits_out: *mut *mut c_char,
+    emoji_out: *mut *mut c_char,
+    error_out: *mut *mut c_char,
+) -> c_int {
+    if node.is_null() || session.is_null() || digits_out.is_null() || emoji_out.is_null() {
+        if !error_out.is_null() {
+            *error_out = WraithError::invalid_argument("null argument").to_c_string();
+        }
+        return WraithErrorCode::InvalidArgument as c_int;
+    }
+
+    // Get peer_id from session handle
+    let peer_id_bytes = *(session as *const [u8; 32]);
+
+    let handle = &*(node as *const NodeHandle);
+    match handle.node.session_sas(&peer_id_bytes) {
+        Ok(sas) => {
+            let digits = std::ffi::CString::new(sas.digits())
+                .expect("SAS digits contain no NUL bytes")
+                .into_raw();
+            let emoji = std::ffi::CString::new(sas.emoji())
+                .expect("SAS emoji contain no NUL bytes")
+                .into_raw();
+            *digits_out = digits;
+            *emoji_out = emoji;
+            WraithErrorCode::Success as c_int
+        }
+        Err(e) => {
+            let err = WraithError::from(e);
+            let code = err.code;
+            if !error_out.is_null() {
+                *error_out = err.to_c_string();
+            }
+            code as c_int
+        }
+    }
+}
+
 /// Get the number of active sessions
 ///
 /// # Safety
@@ -337,6 +446,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_session_establish_with_timeout_null_node() {
+        unsafe {
+            let peer_id = WraithNodeId { bytes: [1u8; 32] };
+            let mut session_ptr: *mut WraithSession = ptr::null_mut();
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+
+            let result = wraith_session_establish_with_timeout(
+                ptr::null_mut(),
+                &peer_id,
+                1_000,
+                &mut session_ptr,
+                &mut error_ptr,
+            );
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+            crate::wraith_free_string(error_ptr);
+        }
+    }
+
+    #[test]
+    fn test_session_close_with_timeout_null_session() {
+        unsafe {
+            let node = crate::node::wraith_node_new(ptr::null(), ptr::null_mut());
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+
+            let result =
+                wraith_session_close_with_timeout(node, ptr::null_mut(), 1_000, &mut error_ptr);
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+
+            let error_msg = CStr::from_ptr(error_ptr).to_str().unwrap();
+            assert!(error_msg.contains("session is null"));
+            crate::wraith_free_string(error_ptr);
+
+            crate::node::wraith_node_free(node);
+        }
+    }
+
     #[test]
     fn test_session_get_stats_null_node() {
         unsafe {
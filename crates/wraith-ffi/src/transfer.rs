@@ -1,14 +1,37 @@
 //! Transfer API FFI
 
-use std::os::raw::{c_char, c_int};
+use std::os::raw::{c_char, c_int, c_void};
 use std::path::PathBuf;
+use std::time::Duration;
 
 use crate::error::{WraithError, WraithErrorCode};
 use crate::types::*;
-use crate::{NodeHandle, WraithNode, WraithTransfer, ffi_try, from_c_string};
+use crate::{
+    CallbackUserData, NodeHandle, WraithNode, WraithTransfer, block_on_with_deadline, ffi_try,
+    from_c_string,
+};
+
+/// Interval between progress callback invocations
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Convert core transfer progress to the FFI representation
+pub(crate) fn progress_to_ffi(
+    progress: &wraith_core::node::progress::TransferProgress,
+) -> WraithTransferProgress {
+    WraithTransferProgress {
+        total_bytes: progress.bytes_total,
+        transferred_bytes: progress.bytes_sent,
+        progress: (progress.progress_percent / 100.0) as f32, // Convert from 0-100 to 0-1
+        eta_seconds: progress.eta.map_or(0, |eta| eta.as_secs()),
+        rate_bytes_per_sec: progress.speed_bytes_per_sec as u64,
+        is_complete: progress.is_complete(),
+    }
+}
 
 /// Send a file to a peer
 ///
+/// Equivalent to `wraith_transfer_send_file_with_timeout()` with no deadline.
+///
 /// # Safety
 ///
 /// - `node` must be a valid node handle
@@ -23,6 +46,32 @@ pub unsafe extern "C" fn wraith_transfer_send_file(
     file_path: *const c_char,
     transfer_out: *mut *mut WraithTransfer,
     error_out: *mut *mut c_char,
+) -> c_int {
+    wraith_transfer_send_file_with_timeout(node, peer_id, file_path, 0, transfer_out, error_out)
+}
+
+/// Send a file to a peer, bounded by a deadline
+///
+/// The deadline covers transfer initiation (session setup and file
+/// registration), not the transfer itself; use `wraith_transfer_wait_with_timeout()`
+/// to bound completion. A `timeout_ms` of 0 means no deadline. If the deadline
+/// expires, initiation is aborted and `WRAITH_ERROR_TIMEOUT` is returned.
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `peer_id` must be a valid pointer to a 32-byte peer ID
+/// - `file_path` must be a valid null-terminated UTF-8 string
+/// - `transfer_out` must be a valid pointer to receive the transfer handle
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_transfer_send_file_with_timeout(
+    node: *mut WraithNode,
+    peer_id: *const WraithNodeId,
+    file_path: *const c_char,
+    timeout_ms: u64,
+    transfer_out: *mut *mut WraithTransfer,
+    error_out: *mut *mut c_char,
 ) -> c_int {
     if node.is_null() {
         if !error_out.is_null() {
@@ -57,12 +106,13 @@ pub unsafe extern "C" fn wraith_transfer_send_file(
     let node_clone = handle.node.clone();
     let runtime = handle.runtime.clone();
 
-    let transfer_id = ffi_try!(
-        runtime
-            .block_on(async move { node_clone.send_file(file_path_buf, &peer_id_bytes).await })
-            .map_err(WraithError::from),
+    let result = ffi_try!(
+        block_on_with_deadline(&runtime, timeout_ms, async move {
+            node_clone.send_file(file_path_buf, &peer_id_bytes).await
+        }),
         error_out
     );
+    let transfer_id = ffi_try!(result.map_err(WraithError::from), error_out);
 
     // Store transfer ID as handle
     let transfer_handle = Box::new(transfer_id);
@@ -74,6 +124,7 @@ pub unsafe extern "C" fn wraith_transfer_send_file(
 /// Wait for a file transfer to complete
 ///
 /// This is a blocking call that waits until the transfer finishes.
+/// Equivalent to `wraith_transfer_wait_with_timeout()` with no deadline.
 ///
 /// # Safety
 ///
@@ -85,6 +136,29 @@ pub unsafe extern "C" fn wraith_transfer_wait(
     node: *mut WraithNode,
     transfer: *const WraithTransfer,
     error_out: *mut *mut c_char,
+) -> c_int {
+    wraith_transfer_wait_with_timeout(node, transfer, 0, error_out)
+}
+
+/// Wait for a file transfer to complete, bounded by a deadline
+///
+/// This is a blocking call that waits until the transfer finishes or the
+/// deadline expires. A `timeout_ms` of 0 means wait indefinitely. On expiry
+/// `WRAITH_ERROR_TIMEOUT` is returned; the transfer keeps running in the
+/// background, so the caller can wait again or abort it with
+/// `wraith_transfer_cancel()`.
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `transfer` must be a valid transfer handle
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_transfer_wait_with_timeout(
+    node: *mut WraithNode,
+    transfer: *const WraithTransfer,
+    timeout_ms: u64,
+    error_out: *mut *mut c_char,
 ) -> c_int {
     if node.is_null() {
         if !error_out.is_null() {
@@ -105,12 +179,13 @@ pub unsafe extern "C" fn wraith_transfer_wait(
     let node_clone = handle.node.clone();
     let runtime = handle.runtime.clone();
 
-    ffi_try!(
-        runtime
-            .block_on(async move { node_clone.wait_for_transfer(transfer_id).await })
-            .map_err(WraithError::from),
+    let result = ffi_try!(
+        block_on_with_deadline(&runtime, timeout_ms, async move {
+            node_clone.wait_for_transfer(transfer_id).await
+        }),
         error_out
     );
+    ffi_try!(result.map_err(WraithError::from), error_out);
 
     WraithErrorCode::Success as c_int
 }
@@ -161,23 +236,7 @@ pub unsafe extern "C" fn wraith_transfer_get_progress(
 
     match progress_opt {
         Some(progress) => {
-            let is_complete = progress.is_complete();
-            let pct = (progress.progress_percent / 100.0) as f32; // Convert from 0-100 to 0-1
-
-            let eta_seconds = if let Some(eta) = progress.eta {
-                eta.as_secs()
-            } else {
-                0
-            };
-
-            *progress_out = WraithTransferProgress {
-                total_bytes: progress.bytes_total,
-                transferred_bytes: progress.bytes_sent,
-                progress: pct,
-                eta_seconds,
-                rate_bytes_per_sec: progress.speed_bytes_per_sec as u64,
-                is_complete,
-            };
+            *progress_out = progress_to_ffi(&progress);
         }
         None => {
             if !error_out.is_null() {
@@ -274,6 +333,99 @@ pub unsafe extern "C" fn wraith_transfer_cancel(
     WraithErrorCode::Success as c_int
 }
 
+/// Register a progress callback for a transfer
+///
+/// The callback is invoked roughly every 100ms with a fresh progress
+/// snapshot until the transfer completes or disappears, replacing the need
+/// to poll `wraith_transfer_get_progress()`. Registering a new callback for
+/// the same transfer replaces the previous one; passing a null `callback`
+/// unregisters without replacement.
+///
+/// Callback ABI rules (see [`WraithProgressCallback`]):
+/// - Invoked from an internal runtime thread, never the registering thread
+/// - Must be thread-safe and return promptly
+/// - Must not call blocking WRAITH FFI functions on the same node or free it
+/// - `user_data` must remain valid until the callback is unregistered, the
+///   transfer finishes, or the node is freed; no invocations occur after
+///   `wraith_node_free()` returns
+///
+/// # Safety
+///
+/// - `node` must be a valid node handle
+/// - `transfer` must be a valid transfer handle
+/// - `callback`, if non-null, must satisfy the ABI rules above
+/// - `error_out` must be null or a valid pointer to receive error message
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn wraith_transfer_set_progress_callback(
+    node: *mut WraithNode,
+    transfer: *const WraithTransfer,
+    callback: WraithProgressCallback,
+    user_data: *mut c_void,
+    error_out: *mut *mut c_char,
+) -> c_int {
+    if node.is_null() {
+        if !error_out.is_null() {
+            *error_out = WraithError::invalid_argument("node is null").to_c_string();
+        }
+        return WraithErrorCode::InvalidArgument as c_int;
+    }
+
+    if transfer.is_null() {
+        if !error_out.is_null() {
+            *error_out = WraithError::invalid_argument("transfer is null").to_c_string();
+        }
+        return WraithErrorCode::InvalidArgument as c_int;
+    }
+
+    let transfer_id = *(transfer as *const [u8; 32]);
+    let handle = &*(node as *const NodeHandle);
+
+    // Replacing or clearing always stops the previous watcher first
+    let mut watchers = handle
+        .progress_watchers
+        .lock()
+        .expect("progress watcher lock poisoned");
+    if let Some(task) = watchers.remove(&transfer_id) {
+        task.abort();
+    }
+
+    let Some(callback) = callback else {
+        return WraithErrorCode::Success as c_int;
+    };
+
+    let node_clone = handle.node.clone();
+    let user_data = CallbackUserData(user_data);
+
+    let task = handle.runtime.spawn(async move {
+        let user_data = user_data;
+        let mut ticker = tokio::time::interval(PROGRESS_POLL_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            let Some(progress) = node_clone.get_transfer_progress(&transfer_id).await else {
+                break;
+            };
+
+            let id = WraithTransferId { bytes: transfer_id };
+            let ffi_progress = progress_to_ffi(&progress);
+
+            // SAFETY: the caller guarantees the callback and user_data obey
+            // the documented ABI rules while the watcher is registered.
+            unsafe { callback(&id, &ffi_progress, user_data.0) };
+
+            if ffi_progress.is_complete {
+                break;
+            }
+        }
+    });
+
+    watchers.insert(transfer_id, task);
+
+    WraithErrorCode::Success as c_int
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -459,6 +611,70 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_transfer_send_file_with_timeout_null_node() {
+        unsafe {
+            let peer_id = WraithNodeId { bytes: [1u8; 32] };
+            let file_path = CString::new("/tmp/test.txt").unwrap();
+            let mut transfer_ptr: *mut WraithTransfer = ptr::null_mut();
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+
+            let result = wraith_transfer_send_file_with_timeout(
+                ptr::null_mut(),
+                &peer_id,
+                file_path.as_ptr(),
+                1_000,
+                &mut transfer_ptr,
+                &mut error_ptr,
+            );
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+            crate::wraith_free_string(error_ptr);
+        }
+    }
+
+    #[test]
+    fn test_transfer_wait_with_timeout_null_transfer() {
+        unsafe {
+            let node = crate::node::wraith_node_new(ptr::null(), ptr::null_mut());
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+
+            let result =
+                wraith_transfer_wait_with_timeout(node, ptr::null(), 1_000, &mut error_ptr);
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+
+            let error_msg = CStr::from_ptr(error_ptr).to_str().unwrap();
+            assert!(error_msg.contains("transfer is null"));
+            crate::wraith_free_string(error_ptr);
+
+            crate::node::wraith_node_free(node);
+        }
+    }
+
+    #[test]
+    fn test_transfer_wait_with_timeout_not_found() {
+        unsafe {
+            let node = crate::node::wraith_node_new(ptr::null(), ptr::null_mut());
+            let transfer_id = [42u8; 32];
+            let transfer = Box::into_raw(Box::new(transfer_id)) as *mut WraithTransfer;
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+
+            // An unknown transfer fails fast with TransferNotFound rather
+            // than waiting out the deadline
+            let result = wraith_transfer_wait_with_timeout(node, transfer, 10_000, &mut error_ptr);
+
+            assert_eq!(result, WraithErrorCode::TransferNotFound as c_int);
+            assert!(!error_ptr.is_null());
+            crate::wraith_free_string(error_ptr);
+
+            wraith_transfer_free(transfer);
+            crate::node::wraith_node_free(node);
+        }
+    }
+
     #[test]
     fn test_transfer_get_progress_null_node() {
         unsafe {
@@ -593,6 +809,103 @@ mod tests {
         }
     }
 
+    unsafe extern "C" fn count_progress(
+        _transfer_id: *const WraithTransferId,
+        _progress: *const WraithTransferProgress,
+        user_data: *mut c_void,
+    ) {
+        unsafe {
+            let counter = &*(user_data as *const std::sync::atomic::AtomicU32);
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_set_progress_callback_null_node() {
+        unsafe {
+            let transfer_id = [1u8; 32];
+            let transfer = Box::into_raw(Box::new(transfer_id)) as *mut WraithTransfer;
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+
+            let result = wraith_transfer_set_progress_callback(
+                ptr::null_mut(),
+                transfer,
+                Some(count_progress),
+                ptr::null_mut(),
+                &mut error_ptr,
+            );
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+
+            let error_msg = CStr::from_ptr(error_ptr).to_str().unwrap();
+            assert!(error_msg.contains("node is null"));
+            crate::wraith_free_string(error_ptr);
+
+            drop(Box::from_raw(transfer as *mut [u8; 32]));
+        }
+    }
+
+    #[test]
+    fn test_set_progress_callback_null_transfer() {
+        unsafe {
+            let node = crate::node::wraith_node_new(ptr::null(), ptr::null_mut());
+            let mut error_ptr: *mut c_char = ptr::null_mut();
+
+            let result = wraith_transfer_set_progress_callback(
+                node,
+                ptr::null(),
+                Some(count_progress),
+                ptr::null_mut(),
+                &mut error_ptr,
+            );
+
+            assert_eq!(result, WraithErrorCode::InvalidArgument as c_int);
+            assert!(!error_ptr.is_null());
+
+            let error_msg = CStr::from_ptr(error_ptr).to_str().unwrap();
+            assert!(error_msg.contains("transfer is null"));
+            crate::wraith_free_string(error_ptr);
+
+            crate::node::wraith_node_free(node);
+        }
+    }
+
+    #[test]
+    fn test_set_progress_callback_register_and_clear() {
+        unsafe {
+            let node = crate::node::wraith_node_new(ptr::null(), ptr::null_mut());
+            let transfer_id = [1u8; 32];
+            let transfer = Box::into_raw(Box::new(transfer_id)) as *mut WraithTransfer;
+            let counter = std::sync::atomic::AtomicU32::new(0);
+            let user_data = &counter as *const _ as *mut c_void;
+
+            // Register (the watcher exits on its own since the transfer
+            // does not exist, but registration itself succeeds)
+            let result = wraith_transfer_set_progress_callback(
+                node,
+                transfer,
+                Some(count_progress),
+                user_data,
+                ptr::null_mut(),
+            );
+            assert_eq!(result, WraithErrorCode::Success as c_int);
+
+            // Clear with null callback
+            let result = wraith_transfer_set_progress_callback(
+                node,
+                transfer,
+                None,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            );
+            assert_eq!(result, WraithErrorCode::Success as c_int);
+
+            drop(Box::from_raw(transfer as *mut [u8; 32]));
+            crate::node::wraith_node_free(node);
+        }
+    }
+
     #[test]
     fn test_transfer_cancel_null_node() {
         unsafe {
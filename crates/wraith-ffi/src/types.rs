@@ -1,5 +1,6 @@
 //! FFI-safe type definitions
 
+use std::os::raw::c_void;
 use std::time::Duration;
 
 /// Node ID (32 bytes - Ed25519 public key)
@@ -59,6 +60,30 @@ pub struct WraithTransferProgress {
     pub is_complete: bool,
 }
 
+/// Where a bandwidth estimate came from
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WraithEstimateSource {
+    /// Active probe measurement taken just now
+    Probe = 0,
+    /// Fresh path estimate recorded by a recent transfer
+    CurrentPath = 1,
+    /// Persisted history smoothed over past transfers
+    History = 2,
+}
+
+/// Pre-transfer bandwidth estimate toward a peer
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct WraithBandwidthEstimate {
+    /// Estimated achievable throughput in bytes per second
+    pub bandwidth_bps: u64,
+    /// Round-trip time in microseconds (0 if not measured)
+    pub rtt_us: u64,
+    /// Which source produced the estimate
+    pub source: WraithEstimateSource,
+}
+
 /// Transfer status
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -121,6 +146,52 @@ pub enum WraithMimicryMode {
     Doh = 3,
 }
 
+/// Node event kind delivered to a [`WraithEventCallback`]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WraithNodeEventType {
+    /// The node started listening
+    NodeStarted = 0,
+    /// The node stopped
+    NodeStopped = 1,
+    /// A new transfer became active
+    TransferStarted = 2,
+    /// An active transfer finished (completed, failed, or was cancelled)
+    TransferFinished = 3,
+}
+
+/// Transfer progress callback
+///
+/// Invoked from an internal runtime thread - never from the thread that
+/// registered it. `transfer_id` and `progress` point to stack temporaries
+/// that are only valid for the duration of the call; copy what you need.
+/// `user_data` is the pointer supplied at registration, passed back verbatim.
+///
+/// The callback must be thread-safe, must return promptly, and must not
+/// call back into blocking WRAITH FFI functions on the same node (deadlock)
+/// or free the node handle (use-after-free).
+pub type WraithProgressCallback = Option<
+    unsafe extern "C" fn(
+        transfer_id: *const WraithTransferId,
+        progress: *const WraithTransferProgress,
+        user_data: *mut c_void,
+    ),
+>;
+
+/// Node event callback
+///
+/// Invoked from an internal runtime thread with the same threading,
+/// reentrancy, and lifetime rules as [`WraithProgressCallback`].
+/// `transfer_id` is null for node-level events (`NodeStarted`/`NodeStopped`)
+/// and points to a call-scoped temporary for transfer events.
+pub type WraithEventCallback = Option<
+    unsafe extern "C" fn(
+        event: WraithNodeEventType,
+        transfer_id: *const WraithTransferId,
+        user_data: *mut c_void,
+    ),
+>;
+
 /// Log level
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -172,6 +243,16 @@ impl From<WraithTimingMode> for wraith_core::node::TimingMode {
     }
 }
 
+impl From<wraith_core::node::EstimateSource> for WraithEstimateSource {
+    fn from(source: wraith_core::node::EstimateSource) -> Self {
+        match source {
+            wraith_core::node::EstimateSource::Probe => WraithEstimateSource::Probe,
+            wraith_core::node::EstimateSource::CurrentPath => WraithEstimateSource::CurrentPath,
+            wraith_core::node::EstimateSource::History => WraithEstimateSource::History,
+        }
+    }
+}
+
 impl From<WraithMimicryMode> for wraith_core::node::MimicryMode {
     fn from(mode: WraithMimicryMode) -> Self {
         match mode {
@@ -276,6 +357,14 @@ mod tests {
         assert_eq!(WraithMimicryMode::Doh as i32, 3);
     }
 
+    #[test]
+    fn test_node_event_type_values() {
+        assert_eq!(WraithNodeEventType::NodeStarted as i32, 0);
+        assert_eq!(WraithNodeEventType::NodeStopped as i32, 1);
+        assert_eq!(WraithNodeEventType::TransferStarted as i32, 2);
+        assert_eq!(WraithNodeEventType::TransferFinished as i32, 3);
+    }
+
     #[test]
     fn test_log_level_values() {
         assert_eq!(WraithLogLevel::Trace as i32, 0);
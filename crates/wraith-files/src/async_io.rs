@@ -0,0 +1,734 @@
+//! Cross-platform async file I/O backends.
+//!
+//! [`crate::async_file`] delivers parallel chunk reads through io_uring, but
+//! only on Linux. This module closes the gap: the [`AsyncFileIo`] trait
+//! captures the same submit/wait request model, and [`open_platform`] /
+//! [`create_platform`] hand back the best backend for the running OS —
+//! io_uring on Linux, and a positional-I/O thread pool elsewhere (overlapped
+//! `seek_read`/`seek_write` on Windows, `pread`/`pwrite` on macOS and other
+//! Unixes), so cross-platform clients get parallel chunk reads without
+//! cfg-gated gaps.
+
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::JoinHandle;
+
+/// Identifier for an in-flight read or write request
+pub type RequestId = u64;
+
+/// Default worker count for the thread-pool backend
+pub const DEFAULT_POOL_WORKERS: usize = 4;
+
+/// Common interface over platform async file I/O backends
+///
+/// Mirrors the submit/wait model of [`crate::async_file`]: queue positional
+/// reads and writes, push them to the backend as a batch with
+/// [`submit`](Self::submit), then block on or poll completions by request ID.
+pub trait AsyncFileIo: Send {
+    /// Queue a positional read of `len` bytes at `offset`
+    ///
+    /// The read is not issued until [`submit`](Self::submit) is called.
+    fn read_at(&mut self, offset: u64, len: usize) -> io::Result<RequestId>;
+
+    /// Queue a positional write of `data` at `offset`
+    ///
+    /// The write is not issued until [`submit`](Self::submit) is called.
+    /// Writing through a handle opened read-only fails when the completion
+    /// is waited on, not here.
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> io::Result<RequestId>;
+
+    /// Issue all queued requests; returns the number issued
+    fn submit(&mut self) -> io::Result<usize>;
+
+    /// Block until the given read completes and return its data
+    ///
+    /// The buffer is truncated to the bytes actually read (short at end of
+    /// file). Fails with [`io::ErrorKind::NotFound`] if the request was
+    /// never submitted or was already consumed.
+    fn wait_read(&mut self, request_id: RequestId) -> io::Result<Vec<u8>>;
+
+    /// Block until the given write completes and return the bytes written
+    ///
+    /// Fails with [`io::ErrorKind::NotFound`] if the request was never
+    /// submitted or was already consumed.
+    fn wait_write(&mut self, request_id: RequestId) -> io::Result<usize>;
+
+    /// Number of submitted requests not yet completed
+    fn pending(&self) -> usize;
+
+    /// Wait for all submitted requests to complete and sync data to disk
+    ///
+    /// Completed results stay retrievable via the `wait_*` methods.
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+/// Open the platform's best async I/O backend for reading an existing file
+///
+/// io_uring on Linux; the positional-I/O thread pool elsewhere.
+/// `queue_depth` sizes the io_uring submission queue and is ignored by the
+/// thread-pool backend.
+pub fn open_platform<P: AsRef<Path>>(
+    path: P,
+    queue_depth: u32,
+) -> io::Result<Box<dyn AsyncFileIo>> {
+    #[cfg(target_os = "linux")]
+    {
+        Ok(Box::new(UringFileIo::open(path, queue_depth)?))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = queue_depth;
+        Ok(Box::new(ThreadPoolFileIo::open(
+            path,
+            DEFAULT_POOL_WORKERS,
+        )?))
+    }
+}
+
+/// Create (or truncate) a file and open the platform's best async I/O
+/// backend for reading and writing it
+pub fn create_platform<P: AsRef<Path>>(
+    path: P,
+    queue_depth: u32,
+) -> io::Result<Box<dyn AsyncFileIo>> {
+    #[cfg(target_os = "linux")]
+    {
+        Ok(Box::new(UringFileIo::create(path, queue_depth)?))
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = queue_depth;
+        Ok(Box::new(ThreadPoolFileIo::create(
+            path,
+            DEFAULT_POOL_WORKERS,
+        )?))
+    }
+}
+
+// ───────────────────────────────────────────────────────────────────────────
+// Thread-pool backend (Windows, macOS, and any other non-Linux platform)
+// ───────────────────────────────────────────────────────────────────────────
+
+enum Job {
+    Read {
+        id: RequestId,
+        offset: u64,
+        len: usize,
+    },
+    Write {
+        id: RequestId,
+        offset: u64,
+        data: Vec<u8>,
+    },
+}
+
+enum Outcome {
+    Read(io::Result<Vec<u8>>),
+    Write(io::Result<usize>),
+}
+
+struct PoolState {
+    results: HashMap<RequestId, Outcome>,
+    completed: u64,
+}
+
+struct PoolShared {
+    state: Mutex<PoolState>,
+    ready: Condvar,
+}
+
+/// Portable async file I/O backend built on a positional-read thread pool
+///
+/// Each worker issues positional reads and writes (`pread`/`pwrite` on Unix,
+/// overlapped `seek_read`/`seek_write` on Windows) against a shared file
+/// handle, so queued chunk requests are serviced in parallel without moving
+/// the file cursor. This is the backend [`open_platform`] selects on Windows
+/// and macOS; it also works on Linux, where it is mainly useful for tests
+/// and as a fallback when io_uring is unavailable.
+pub struct ThreadPoolFileIo {
+    file: Arc<File>,
+    shared: Arc<PoolShared>,
+    sender: Option<mpsc::Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+    queued: Vec<Job>,
+    next_id: RequestId,
+    submitted: u64,
+}
+
+impl ThreadPoolFileIo {
+    /// Open an existing file read-only with the given worker count
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use wraith_files::async_io::{AsyncFileIo, ThreadPoolFileIo};
+    ///
+    /// let mut io = ThreadPoolFileIo::open("/etc/hostname", 4).unwrap();
+    /// let req = io.read_at(0, 1024).unwrap();
+    /// io.submit().unwrap();
+    /// let data = io.wait_read(req).unwrap();
+    /// ```
+    pub fn open<P: AsRef<Path>>(path: P, workers: usize) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(Self::with_file(file, workers))
+    }
+
+    /// Create (or truncate) a file for reading and writing
+    pub fn create<P: AsRef<Path>>(path: P, workers: usize) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(Self::with_file(file, workers))
+    }
+
+    fn with_file(file: File, workers: usize) -> Self {
+        let file = Arc::new(file);
+        let shared = Arc::new(PoolShared {
+            state: Mutex::new(PoolState {
+                results: HashMap::new(),
+                completed: 0,
+            }),
+            ready: Condvar::new(),
+        });
+
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..workers.max(1))
+            .map(|_| {
+                let receiver = Arc::clone(&receiver);
+                let file = Arc::clone(&file);
+                let shared = Arc::clone(&shared);
+                std::thread::spawn(move || {
+                    loop {
+                        let job = {
+                            let receiver = receiver.lock().unwrap();
+                            receiver.recv()
+                        };
+                        let Ok(job) = job else {
+                            // All senders dropped: pool is shutting down
+                            break;
+                        };
+
+                        let (id, outcome) = match job {
+                            Job::Read { id, offset, len } => {
+                                (id, Outcome::Read(read_exact_at(&file, offset, len)))
+                            }
+                            Job::Write { id, offset, data } => {
+                                (id, Outcome::Write(write_all_at(&file, offset, &data)))
+                            }
+                        };
+
+                        let mut state = shared.state.lock().unwrap();
+                        state.results.insert(id, outcome);
+                        state.completed += 1;
+                        shared.ready.notify_all();
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            file,
+            shared,
+            sender: Some(sender),
+            workers,
+            queued: Vec::new(),
+            next_id: 0,
+            submitted: 0,
+        }
+    }
+
+    fn wait_outcome(&mut self, request_id: RequestId) -> io::Result<Outcome> {
+        let mut state = self.shared.state.lock().unwrap();
+        loop {
+            if let Some(outcome) = state.results.remove(&request_id) {
+                return Ok(outcome);
+            }
+            if self.submitted == state.completed {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "Request not found and no pending operations",
+                ));
+            }
+            state = self.shared.ready.wait(state).unwrap();
+        }
+    }
+}
+
+impl AsyncFileIo for ThreadPoolFileIo {
+    fn read_at(&mut self, offset: u64, len: usize) -> io::Result<RequestId> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.queued.push(Job::Read { id, offset, len });
+        Ok(id)
+    }
+
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> io::Result<RequestId> {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.queued.push(Job::Write {
+            id,
+            offset,
+            data: data.to_vec(),
+        });
+        Ok(id)
+    }
+
+    fn submit(&mut self) -> io::Result<usize> {
+        let sender = self
+            .sender
+            .as_ref()
+            .expect("sender only dropped in Drop")
+            .clone();
+        let count = self.queued.len();
+        for job in self.queued.drain(..) {
+            sender
+                .send(job)
+                .map_err(|_| io::Error::other("I/O worker pool shut down"))?;
+            self.submitted += 1;
+        }
+        Ok(count)
+    }
+
+    fn wait_read(&mut self, request_id: RequestId) -> io::Result<Vec<u8>> {
+        match self.wait_outcome(request_id)? {
+            Outcome::Read(result) => result,
+            Outcome::Write(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Request is a write, not a read",
+            )),
+        }
+    }
+
+    fn wait_write(&mut self, request_id: RequestId) -> io::Result<usize> {
+        match self.wait_outcome(request_id)? {
+            Outcome::Write(result) => result,
+            Outcome::Read(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Request is a read, not a write",
+            )),
+        }
+    }
+
+    fn pending(&self) -> usize {
+        let state = self.shared.state.lock().unwrap();
+        (self.submitted - state.completed) as usize
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut state = self.shared.state.lock().unwrap();
+        while state.completed < self.submitted {
+            state = self.shared.ready.wait(state).unwrap();
+        }
+        drop(state);
+        self.file.sync_all()
+    }
+}
+
+impl Drop for ThreadPoolFileIo {
+    fn drop(&mut self) {
+        // Dropping the sender ends the workers' recv loops
+        self.sender.take();
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+/// Positional read that fills `len` bytes, truncating only at end of file
+fn read_exact_at(file: &File, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+    let mut buffer = vec![0u8; len];
+    let mut filled = 0;
+    while filled < len {
+        match positional_read(file, &mut buffer[filled..], offset + filled as u64) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    buffer.truncate(filled);
+    Ok(buffer)
+}
+
+/// Positional write of the full buffer
+fn write_all_at(file: &File, offset: u64, data: &[u8]) -> io::Result<usize> {
+    let mut written = 0;
+    while written < data.len() {
+        match positional_write(file, &data[written..], offset + written as u64) {
+            Ok(0) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::WriteZero,
+                    "Failed to write whole buffer",
+                ));
+            }
+            Ok(n) => written += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(written)
+}
+
+#[cfg(unix)]
+fn positional_read(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    use std::os::unix::fs::FileExt;
+    file.read_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn positional_read(file: &File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+    use std::os::windows::fs::FileExt;
+    file.seek_read(buf, offset)
+}
+
+#[cfg(unix)]
+fn positional_write(file: &File, buf: &[u8], offset: u64) -> io::Result<usize> {
+    use std::os::unix::fs::FileExt;
+    file.write_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn positional_write(file: &File, buf: &[u8], offset: u64) -> io::Result<usize> {
+    use std::os::windows::fs::FileExt;
+    file.seek_write(buf, offset)
+}
+
+// ───────────────────────────────────────────────────────────────────────────
+// io_uring backend (Linux)
+// ───────────────────────────────────────────────────────────────────────────
+
+/// io_uring backend for [`AsyncFileIo`] (Linux-only)
+///
+/// Unifies the read and write halves of [`crate::async_file`] behind the
+/// portable trait so callers can hold one `Box<dyn AsyncFileIo>` on every
+/// platform. Prefer [`crate::async_file`] directly when the code path is
+/// Linux-only anyway.
+#[cfg(target_os = "linux")]
+pub struct UringFileIo {
+    engine: crate::io_uring::IoUringEngine,
+    file: File,
+    fd: std::os::unix::io::RawFd,
+    next_id: RequestId,
+    pending_reads: HashMap<RequestId, Vec<u8>>,
+    pending_writes: HashMap<RequestId, Vec<u8>>,
+    completed_reads: HashMap<RequestId, Vec<u8>>,
+    completed_writes: HashMap<RequestId, usize>,
+    completed_errors: HashMap<RequestId, i32>,
+}
+
+#[cfg(target_os = "linux")]
+impl UringFileIo {
+    /// Open an existing file read-only
+    pub fn open<P: AsRef<Path>>(path: P, queue_depth: u32) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Self::with_file(file, queue_depth)
+    }
+
+    /// Create (or truncate) a file for reading and writing
+    pub fn create<P: AsRef<Path>>(path: P, queue_depth: u32) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        Self::with_file(file, queue_depth)
+    }
+
+    fn with_file(file: File, queue_depth: u32) -> io::Result<Self> {
+        use std::os::unix::io::AsRawFd;
+
+        let fd = file.as_raw_fd();
+        let engine = crate::io_uring::IoUringEngine::new(queue_depth).map_err(io::Error::other)?;
+
+        Ok(Self {
+            engine,
+            file,
+            fd,
+            next_id: 0,
+            pending_reads: HashMap::new(),
+            pending_writes: HashMap::new(),
+            completed_reads: HashMap::new(),
+            completed_writes: HashMap::new(),
+            completed_errors: HashMap::new(),
+        })
+    }
+
+    fn record_completion(&mut self, comp: &crate::io_uring::Completion) {
+        if comp.result < 0 {
+            self.pending_reads.remove(&comp.user_data);
+            self.pending_writes.remove(&comp.user_data);
+            self.completed_errors.insert(comp.user_data, -comp.result);
+            return;
+        }
+
+        if let Some(mut buffer) = self.pending_reads.remove(&comp.user_data) {
+            buffer.truncate(comp.result as usize);
+            self.completed_reads.insert(comp.user_data, buffer);
+        } else if self.pending_writes.remove(&comp.user_data).is_some() {
+            self.completed_writes
+                .insert(comp.user_data, comp.result as usize);
+        }
+    }
+
+    /// Wait for completions until `done(self)` yields a result
+    fn wait_until<T>(
+        &mut self,
+        request_id: RequestId,
+        done: impl Fn(&mut Self) -> Option<io::Result<T>>,
+    ) -> io::Result<T> {
+        loop {
+            if let Some(result) = done(self) {
+                return result;
+            }
+            if let Some(errno) = self.completed_errors.remove(&request_id) {
+                return Err(io::Error::from_raw_os_error(errno));
+            }
+            if self.engine.pending() == 0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "Request not found and no pending operations",
+                ));
+            }
+            let completions = self.engine.wait(1).map_err(io::Error::other)?;
+            for comp in completions {
+                self.record_completion(&comp);
+            }
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl AsyncFileIo for UringFileIo {
+    fn read_at(&mut self, offset: u64, len: usize) -> io::Result<RequestId> {
+        let request_id = self.next_id;
+        self.next_id += 1;
+
+        let buffer = vec![0u8; len];
+
+        // SAFETY: Buffer pointer stays valid until completion since
+        // pending_reads owns the buffer until the read completes.
+        unsafe {
+            self.engine
+                .read(self.fd, offset, buffer.as_ptr() as *mut u8, len, request_id)
+                .map_err(io::Error::other)?;
+        }
+
+        self.pending_reads.insert(request_id, buffer);
+        Ok(request_id)
+    }
+
+    fn write_at(&mut self, offset: u64, data: &[u8]) -> io::Result<RequestId> {
+        let request_id = self.next_id;
+        self.next_id += 1;
+
+        let data_copy = data.to_vec();
+
+        // SAFETY: Buffer pointer stays valid until completion since
+        // pending_writes owns the buffer until the write completes.
+        unsafe {
+            self.engine
+                .write(
+                    self.fd,
+                    offset,
+                    data_copy.as_ptr(),
+                    data_copy.len(),
+                    request_id,
+                )
+                .map_err(io::Error::other)?;
+        }
+
+        self.pending_writes.insert(request_id, data_copy);
+        Ok(request_id)
+    }
+
+    fn submit(&mut self) -> io::Result<usize> {
+        self.engine.submit().map_err(io::Error::other)
+    }
+
+    fn wait_read(&mut self, request_id: RequestId) -> io::Result<Vec<u8>> {
+        self.wait_until(request_id, move |this| {
+            if this.completed_writes.contains_key(&request_id) {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Request is a write, not a read",
+                )));
+            }
+            this.completed_reads.remove(&request_id).map(Ok)
+        })
+    }
+
+    fn wait_write(&mut self, request_id: RequestId) -> io::Result<usize> {
+        self.wait_until(request_id, move |this| {
+            if this.completed_reads.contains_key(&request_id) {
+                return Some(Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "Request is a read, not a write",
+                )));
+            }
+            this.completed_writes.remove(&request_id).map(Ok)
+        })
+    }
+
+    fn pending(&self) -> usize {
+        self.engine.pending()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        while self.engine.pending() > 0 {
+            let completions = self.engine.wait(1).map_err(io::Error::other)?;
+            for comp in completions {
+                self.record_completion(&comp);
+            }
+        }
+        self.file.sync_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn temp_with(content: &[u8]) -> NamedTempFile {
+        let mut temp = NamedTempFile::new().unwrap();
+        temp.write_all(content).unwrap();
+        temp.flush().unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_pool_single_read() {
+        let temp = temp_with(b"Hello, thread pool!");
+        let mut io = ThreadPoolFileIo::open(temp.path(), 2).unwrap();
+
+        let req = io.read_at(0, 19).unwrap();
+        io.submit().unwrap();
+
+        let data = io.wait_read(req).unwrap();
+        assert_eq!(&data, b"Hello, thread pool!");
+    }
+
+    #[test]
+    fn test_pool_parallel_reads() {
+        let temp = temp_with(b"ABCDEFGHIJ");
+        let mut io = ThreadPoolFileIo::open(temp.path(), 4).unwrap();
+
+        let req1 = io.read_at(0, 5).unwrap();
+        let req2 = io.read_at(5, 5).unwrap();
+        assert_eq!(io.submit().unwrap(), 2);
+
+        // Completion order is arbitrary; wait in reverse submit order
+        assert_eq!(&io.wait_read(req2).unwrap(), b"FGHIJ");
+        assert_eq!(&io.wait_read(req1).unwrap(), b"ABCDE");
+    }
+
+    #[test]
+    fn test_pool_read_truncates_at_eof() {
+        let temp = temp_with(b"short");
+        let mut io = ThreadPoolFileIo::open(temp.path(), 2).unwrap();
+
+        let req = io.read_at(0, 1024).unwrap();
+        io.submit().unwrap();
+
+        assert_eq!(&io.wait_read(req).unwrap(), b"short");
+    }
+
+    #[test]
+    fn test_pool_write_roundtrip() {
+        let temp = NamedTempFile::new().unwrap();
+        let mut io = ThreadPoolFileIo::create(temp.path(), 2).unwrap();
+
+        let w1 = io.write_at(0, b"Hello ").unwrap();
+        let w2 = io.write_at(6, b"World!").unwrap();
+        io.submit().unwrap();
+
+        assert_eq!(io.wait_write(w1).unwrap(), 6);
+        assert_eq!(io.wait_write(w2).unwrap(), 6);
+        io.flush().unwrap();
+
+        let req = io.read_at(0, 12).unwrap();
+        io.submit().unwrap();
+        assert_eq!(&io.wait_read(req).unwrap(), b"Hello World!");
+    }
+
+    #[test]
+    fn test_pool_unknown_request_errors() {
+        let temp = temp_with(b"data");
+        let mut io = ThreadPoolFileIo::open(temp.path(), 2).unwrap();
+
+        let err = io.wait_read(42).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_pool_write_on_readonly_fails_at_wait() {
+        let temp = temp_with(b"data");
+        let mut io = ThreadPoolFileIo::open(temp.path(), 2).unwrap();
+
+        // Queuing and submitting succeed; the OS error surfaces on wait
+        let req = io.write_at(0, b"nope").unwrap();
+        io.submit().unwrap();
+        assert!(io.wait_write(req).is_err());
+    }
+
+    #[test]
+    fn test_pool_wait_read_on_write_request() {
+        let temp = NamedTempFile::new().unwrap();
+        let mut io = ThreadPoolFileIo::create(temp.path(), 2).unwrap();
+
+        let req = io.write_at(0, b"data").unwrap();
+        io.submit().unwrap();
+
+        let err = io.wait_read(req).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_platform_backend_read() {
+        let temp = temp_with(b"platform backend");
+        let mut io = open_platform(temp.path(), 128).unwrap();
+
+        let req = io.read_at(0, 16).unwrap();
+        io.submit().unwrap();
+
+        assert_eq!(&io.wait_read(req).unwrap(), b"platform backend");
+    }
+
+    #[test]
+    fn test_platform_backend_write_roundtrip() {
+        let temp = NamedTempFile::new().unwrap();
+        let mut io = create_platform(temp.path(), 128).unwrap();
+
+        let w = io.write_at(0, b"cross-platform").unwrap();
+        io.submit().unwrap();
+        assert_eq!(io.wait_write(w).unwrap(), 14);
+        io.flush().unwrap();
+
+        let content = std::fs::read(temp.path()).unwrap();
+        assert_eq!(&content, b"cross-platform");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_uring_backend_parallel_reads() {
+        let temp = temp_with(b"0123456789");
+        let mut io = UringFileIo::open(temp.path(), 128).unwrap();
+
+        let req1 = io.read_at(0, 5).unwrap();
+        let req2 = io.read_at(5, 5).unwrap();
+        io.submit().unwrap();
+
+        assert_eq!(&io.wait_read(req1).unwrap(), b"01234");
+        assert_eq!(&io.wait_read(req2).unwrap(), b"56789");
+    }
+}
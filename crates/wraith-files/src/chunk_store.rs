@@ -0,0 +1,542 @@
+//! Content-addressed local chunk store with garbage collection
+//!
+//! Stores chunks on disk keyed by their BLAKE3 hash, deduplicating
+//! identical chunks across transfers. Manifests (ordered chunk lists for
+//! a piece of content) hold references to chunks; reference counts track
+//! how many manifests need each chunk, a GC pass removes chunks no
+//! manifest references, and an optional cache size limit evicts the
+//! least-recently-requested seed data when the store grows too large.
+//!
+//! Layout on disk is one file per chunk under `<root>/chunks/`, named by
+//! the chunk's hex hash. Metadata (reference counts, access order) lives
+//! in memory; reopening a store rescans the chunk directory, and callers
+//! re-register their manifests to restore reference counts.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Subdirectory holding chunk files
+const CHUNKS_DIR: &str = "chunks";
+
+/// Chunk store configuration
+#[derive(Debug, Clone)]
+pub struct ChunkStoreConfig {
+    /// Root directory for the store
+    pub root: PathBuf,
+    /// Maximum on-disk size in bytes before LRU eviction (None = unlimited)
+    pub max_cache_bytes: Option<u64>,
+}
+
+impl ChunkStoreConfig {
+    /// Create a configuration with no cache limit
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            max_cache_bytes: None,
+        }
+    }
+
+    /// Set the cache size limit
+    #[must_use]
+    pub fn with_max_cache_bytes(mut self, max: u64) -> Self {
+        self.max_cache_bytes = Some(max);
+        self
+    }
+}
+
+/// Per-chunk metadata
+#[derive(Debug, Clone)]
+struct ChunkEntry {
+    /// Chunk size in bytes
+    size: u64,
+    /// Number of manifests referencing this chunk
+    refcount: u32,
+    /// Monotonic access counter value at last request (LRU ordering)
+    last_access: u64,
+}
+
+/// On-disk usage report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StoreUsage {
+    /// Total bytes stored across all chunks
+    pub total_bytes: u64,
+    /// Bytes in chunks referenced by at least one manifest
+    pub referenced_bytes: u64,
+    /// Bytes in unreferenced chunks (reclaimable by GC)
+    pub unreferenced_bytes: u64,
+    /// Number of stored chunks
+    pub chunk_count: usize,
+    /// Number of registered manifests
+    pub manifest_count: usize,
+}
+
+/// Result of a GC or eviction pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcStats {
+    /// Number of chunks removed
+    pub chunks_removed: usize,
+    /// Bytes reclaimed
+    pub bytes_reclaimed: u64,
+}
+
+/// Content-addressed chunk store with reference counting and GC
+#[derive(Debug)]
+pub struct ChunkStore {
+    config: ChunkStoreConfig,
+    /// Chunk metadata keyed by BLAKE3 hash
+    chunks: HashMap<[u8; 32], ChunkEntry>,
+    /// Manifest chunk lists keyed by manifest ID
+    manifests: HashMap<[u8; 32], Vec<[u8; 32]>>,
+    /// Monotonic counter backing LRU ordering
+    access_clock: u64,
+}
+
+impl ChunkStore {
+    /// Open a store, creating the directory layout if needed
+    ///
+    /// Existing chunk files are rescanned into the index with zero
+    /// reference counts; callers re-register manifests with
+    /// [`Self::add_manifest`] to restore references before running GC.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store directories cannot be created or read.
+    pub fn open(config: ChunkStoreConfig) -> io::Result<Self> {
+        let chunks_dir = config.root.join(CHUNKS_DIR);
+        fs::create_dir_all(&chunks_dir)?;
+
+        let mut chunks = HashMap::new();
+        for entry in fs::read_dir(&chunks_dir)? {
+            let entry = entry?;
+            let Some(hash) = parse_chunk_file_name(&entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+            let size = entry.metadata()?.len();
+            chunks.insert(
+                hash,
+                ChunkEntry {
+                    size,
+                    refcount: 0,
+                    last_access: 0,
+                },
+            );
+        }
+
+        Ok(Self {
+            config,
+            chunks,
+            manifests: HashMap::new(),
+            access_clock: 0,
+        })
+    }
+
+    /// Store a chunk, returning its BLAKE3 hash
+    ///
+    /// Identical chunks are deduplicated: re-storing existing data only
+    /// refreshes its access time.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk file cannot be written.
+    pub fn put_chunk(&mut self, data: &[u8]) -> io::Result<[u8; 32]> {
+        let hash = *blake3::hash(data).as_bytes();
+        self.access_clock += 1;
+
+        if let Some(entry) = self.chunks.get_mut(&hash) {
+            entry.last_access = self.access_clock;
+            return Ok(hash);
+        }
+
+        fs::write(self.chunk_path(&hash), data)?;
+        self.chunks.insert(
+            hash,
+            ChunkEntry {
+                size: data.len() as u64,
+                refcount: 0,
+                last_access: self.access_clock,
+            },
+        );
+        Ok(hash)
+    }
+
+    /// Read a chunk, refreshing its LRU position
+    ///
+    /// Returns `None` if the chunk is not stored (never stored, collected,
+    /// or evicted).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk file cannot be read.
+    pub fn get_chunk(&mut self, hash: &[u8; 32]) -> io::Result<Option<Vec<u8>>> {
+        if !self.chunks.contains_key(hash) {
+            return Ok(None);
+        }
+        let data = fs::read(self.chunk_path(hash))?;
+        self.access_clock += 1;
+        if let Some(entry) = self.chunks.get_mut(hash) {
+            entry.last_access = self.access_clock;
+        }
+        Ok(Some(data))
+    }
+
+    /// Whether a chunk is currently stored
+    #[must_use]
+    pub fn has_chunk(&self, hash: &[u8; 32]) -> bool {
+        self.chunks.contains_key(hash)
+    }
+
+    /// Register a manifest, incrementing reference counts on its chunks
+    ///
+    /// Re-registering an existing manifest ID first releases its previous
+    /// references, so updates do not leak counts. Chunks the manifest
+    /// lists but the store does not hold are skipped; register the
+    /// manifest again once they arrive via [`Self::put_chunk`].
+    pub fn add_manifest(&mut self, manifest_id: [u8; 32], chunk_hashes: &[[u8; 32]]) {
+        self.remove_manifest(&manifest_id);
+        for hash in chunk_hashes {
+            if let Some(entry) = self.chunks.get_mut(hash) {
+                entry.refcount += 1;
+            }
+        }
+        self.manifests.insert(manifest_id, chunk_hashes.to_vec());
+    }
+
+    /// Unregister a manifest, decrementing reference counts on its chunks
+    ///
+    /// Chunks reaching zero references stay on disk until the next
+    /// [`Self::collect_garbage`] pass.
+    pub fn remove_manifest(&mut self, manifest_id: &[u8; 32]) {
+        let Some(chunk_hashes) = self.manifests.remove(manifest_id) else {
+            return;
+        };
+        for hash in chunk_hashes {
+            if let Some(entry) = self.chunks.get_mut(&hash) {
+                entry.refcount = entry.refcount.saturating_sub(1);
+            }
+        }
+    }
+
+    /// Hash of the `index`-th chunk of a registered manifest
+    ///
+    /// Returns `None` for unknown manifests or out-of-range indices. The
+    /// chunk itself may still have been evicted; check [`Self::get_chunk`].
+    #[must_use]
+    pub fn manifest_chunk(&self, manifest_id: &[u8; 32], index: usize) -> Option<[u8; 32]> {
+        self.manifests.get(manifest_id)?.get(index).copied()
+    }
+
+    /// Remove all chunks no manifest references
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a chunk file cannot be deleted; chunks removed
+    /// before the failure stay removed.
+    pub fn collect_garbage(&mut self) -> io::Result<GcStats> {
+        let unreferenced: Vec<[u8; 32]> = self
+            .chunks
+            .iter()
+            .filter(|(_, entry)| entry.refcount == 0)
+            .map(|(hash, _)| *hash)
+            .collect();
+
+        let mut stats = GcStats::default();
+        for hash in unreferenced {
+            stats.bytes_reclaimed += self.delete_chunk(&hash)?;
+            stats.chunks_removed += 1;
+        }
+        Ok(stats)
+    }
+
+    /// Evict least-recently-requested chunks until under the cache limit
+    ///
+    /// Unreferenced chunks are evicted before referenced (seed) data; a
+    /// seed chunk that is evicted can be re-fetched from the original
+    /// content, so its manifest stays registered. No-op when no limit is
+    /// configured or usage is within it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a chunk file cannot be deleted.
+    pub fn enforce_cache_limit(&mut self) -> io::Result<GcStats> {
+        let Some(max_bytes) = self.config.max_cache_bytes else {
+            return Ok(GcStats::default());
+        };
+
+        let mut total: u64 = self.chunks.values().map(|entry| entry.size).sum();
+        if total <= max_bytes {
+            return Ok(GcStats::default());
+        }
+
+        // Oldest access first; unreferenced chunks sort before seed data
+        let mut candidates: Vec<([u8; 32], u64, bool)> = self
+            .chunks
+            .iter()
+            .map(|(hash, entry)| (*hash, entry.last_access, entry.refcount > 0))
+            .collect();
+        candidates.sort_by_key(|&(_, last_access, referenced)| (referenced, last_access));
+
+        let mut stats = GcStats::default();
+        for (hash, _, _) in candidates {
+            if total <= max_bytes {
+                break;
+            }
+            let freed = self.delete_chunk(&hash)?;
+            total -= freed;
+            stats.bytes_reclaimed += freed;
+            stats.chunks_removed += 1;
+        }
+        Ok(stats)
+    }
+
+    /// Report on-disk usage
+    #[must_use]
+    pub fn usage(&self) -> StoreUsage {
+        let mut usage = StoreUsage {
+            total_bytes: 0,
+            referenced_bytes: 0,
+            unreferenced_bytes: 0,
+            chunk_count: self.chunks.len(),
+            manifest_count: self.manifests.len(),
+        };
+        for entry in self.chunks.values() {
+            usage.total_bytes += entry.size;
+            if entry.refcount > 0 {
+                usage.referenced_bytes += entry.size;
+            } else {
+                usage.unreferenced_bytes += entry.size;
+            }
+        }
+        usage
+    }
+
+    /// Remove a chunk from disk and the index, returning its size
+    fn delete_chunk(&mut self, hash: &[u8; 32]) -> io::Result<u64> {
+        let Some(entry) = self.chunks.remove(hash) else {
+            return Ok(0);
+        };
+        match fs::remove_file(self.chunk_path(hash)) {
+            Ok(()) => Ok(entry.size),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(entry.size),
+            Err(e) => {
+                self.chunks.insert(*hash, entry);
+                Err(e)
+            }
+        }
+    }
+
+    /// Path of a chunk file
+    fn chunk_path(&self, hash: &[u8; 32]) -> PathBuf {
+        self.config
+            .root
+            .join(CHUNKS_DIR)
+            .join(encode_chunk_hash(hash))
+    }
+}
+
+/// Encode a chunk hash as its hex file name
+fn encode_chunk_hash(hash: &[u8; 32]) -> String {
+    hash.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Parse a chunk file name back into a hash (ignores foreign files)
+fn parse_chunk_file_name(name: &str) -> Option<[u8; 32]> {
+    if name.len() != 64 {
+        return None;
+    }
+    let mut hash = [0u8; 32];
+    for (i, byte) in hash.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&name[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn open_store(dir: &Path) -> ChunkStore {
+        ChunkStore::open(ChunkStoreConfig::new(dir)).unwrap()
+    }
+
+    #[test]
+    fn test_put_get_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let mut store = open_store(dir.path());
+
+        let hash = store.put_chunk(b"hello chunk").unwrap();
+        assert!(store.has_chunk(&hash));
+        assert_eq!(store.get_chunk(&hash).unwrap().unwrap(), b"hello chunk");
+        assert!(store.get_chunk(&[0u8; 32]).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_put_deduplicates() {
+        let dir = TempDir::new().unwrap();
+        let mut store = open_store(dir.path());
+
+        let h1 = store.put_chunk(b"same data").unwrap();
+        let h2 = store.put_chunk(b"same data").unwrap();
+        assert_eq!(h1, h2);
+        assert_eq!(store.usage().chunk_count, 1);
+    }
+
+    #[test]
+    fn test_manifest_chunk_lookup() {
+        let dir = TempDir::new().unwrap();
+        let mut store = open_store(dir.path());
+
+        let first = store.put_chunk(b"first").unwrap();
+        let second = store.put_chunk(b"second").unwrap();
+        store.add_manifest([1u8; 32], &[first, second]);
+
+        assert_eq!(store.manifest_chunk(&[1u8; 32], 0), Some(first));
+        assert_eq!(store.manifest_chunk(&[1u8; 32], 1), Some(second));
+        assert_eq!(store.manifest_chunk(&[1u8; 32], 2), None);
+        assert_eq!(store.manifest_chunk(&[9u8; 32], 0), None);
+    }
+
+    #[test]
+    fn test_gc_removes_only_unreferenced() {
+        let dir = TempDir::new().unwrap();
+        let mut store = open_store(dir.path());
+
+        let kept = store.put_chunk(b"referenced").unwrap();
+        let dropped = store.put_chunk(b"unreferenced").unwrap();
+        store.add_manifest([1u8; 32], &[kept]);
+
+        let stats = store.collect_garbage().unwrap();
+        assert_eq!(stats.chunks_removed, 1);
+        assert_eq!(stats.bytes_reclaimed, b"unreferenced".len() as u64);
+        assert!(store.has_chunk(&kept));
+        assert!(!store.has_chunk(&dropped));
+    }
+
+    #[test]
+    fn test_refcount_shared_chunk_survives_one_manifest_removal() {
+        let dir = TempDir::new().unwrap();
+        let mut store = open_store(dir.path());
+
+        let shared = store.put_chunk(b"shared").unwrap();
+        store.add_manifest([1u8; 32], &[shared]);
+        store.add_manifest([2u8; 32], &[shared]);
+
+        store.remove_manifest(&[1u8; 32]);
+        assert_eq!(store.collect_garbage().unwrap().chunks_removed, 0);
+        assert!(store.has_chunk(&shared));
+
+        store.remove_manifest(&[2u8; 32]);
+        assert_eq!(store.collect_garbage().unwrap().chunks_removed, 1);
+        assert!(!store.has_chunk(&shared));
+    }
+
+    #[test]
+    fn test_add_manifest_twice_does_not_leak_references() {
+        let dir = TempDir::new().unwrap();
+        let mut store = open_store(dir.path());
+
+        let chunk = store.put_chunk(b"data").unwrap();
+        store.add_manifest([1u8; 32], &[chunk]);
+        store.add_manifest([1u8; 32], &[chunk]);
+
+        store.remove_manifest(&[1u8; 32]);
+        assert_eq!(store.collect_garbage().unwrap().chunks_removed, 1);
+    }
+
+    #[test]
+    fn test_usage_reporting() {
+        let dir = TempDir::new().unwrap();
+        let mut store = open_store(dir.path());
+
+        let referenced = store.put_chunk(&[1u8; 100]).unwrap();
+        store.put_chunk(&[2u8; 50]).unwrap();
+        store.add_manifest([1u8; 32], &[referenced]);
+
+        let usage = store.usage();
+        assert_eq!(usage.total_bytes, 150);
+        assert_eq!(usage.referenced_bytes, 100);
+        assert_eq!(usage.unreferenced_bytes, 50);
+        assert_eq!(usage.chunk_count, 2);
+        assert_eq!(usage.manifest_count, 1);
+    }
+
+    #[test]
+    fn test_cache_limit_evicts_lru_first() {
+        let dir = TempDir::new().unwrap();
+        let config = ChunkStoreConfig::new(dir.path()).with_max_cache_bytes(250);
+        let mut store = ChunkStore::open(config).unwrap();
+
+        let oldest = store.put_chunk(&[1u8; 100]).unwrap();
+        let middle = store.put_chunk(&[2u8; 100]).unwrap();
+        let newest = store.put_chunk(&[3u8; 100]).unwrap();
+
+        // Touch the oldest chunk so `middle` becomes least recently used
+        store.get_chunk(&oldest).unwrap();
+
+        let stats = store.enforce_cache_limit().unwrap();
+        assert_eq!(stats.chunks_removed, 1);
+        assert!(!store.has_chunk(&middle));
+        assert!(store.has_chunk(&oldest));
+        assert!(store.has_chunk(&newest));
+    }
+
+    #[test]
+    fn test_cache_limit_prefers_evicting_unreferenced() {
+        let dir = TempDir::new().unwrap();
+        let config = ChunkStoreConfig::new(dir.path()).with_max_cache_bytes(150);
+        let mut store = ChunkStore::open(config).unwrap();
+
+        // Seed data is older but referenced; the newer unreferenced chunk
+        // is evicted first
+        let seed = store.put_chunk(&[1u8; 100]).unwrap();
+        let scratch = store.put_chunk(&[2u8; 100]).unwrap();
+        store.add_manifest([1u8; 32], &[seed]);
+
+        let stats = store.enforce_cache_limit().unwrap();
+        assert_eq!(stats.chunks_removed, 1);
+        assert!(store.has_chunk(&seed));
+        assert!(!store.has_chunk(&scratch));
+    }
+
+    #[test]
+    fn test_cache_limit_noop_within_budget() {
+        let dir = TempDir::new().unwrap();
+        let config = ChunkStoreConfig::new(dir.path()).with_max_cache_bytes(1000);
+        let mut store = ChunkStore::open(config).unwrap();
+
+        store.put_chunk(&[1u8; 100]).unwrap();
+        assert_eq!(store.enforce_cache_limit().unwrap(), GcStats::default());
+        assert_eq!(store.usage().chunk_count, 1);
+    }
+
+    #[test]
+    fn test_reopen_rescans_chunks() {
+        let dir = TempDir::new().unwrap();
+        let hash = {
+            let mut store = open_store(dir.path());
+            store.put_chunk(b"persisted").unwrap()
+        };
+
+        let mut reopened = open_store(dir.path());
+        assert!(reopened.has_chunk(&hash));
+        assert_eq!(reopened.get_chunk(&hash).unwrap().unwrap(), b"persisted");
+        // References are rebuilt by re-registering manifests
+        assert_eq!(reopened.usage().referenced_bytes, 0);
+    }
+
+    #[test]
+    fn test_reopen_ignores_foreign_files() {
+        let dir = TempDir::new().unwrap();
+        {
+            open_store(dir.path());
+        }
+        fs::write(dir.path().join(CHUNKS_DIR).join("not-a-chunk.tmp"), b"x").unwrap();
+
+        let store = open_store(dir.path());
+        assert_eq!(store.usage().chunk_count, 0);
+    }
+}
@@ -2,14 +2,35 @@
 //!
 //! This module provides file chunking and reassembly with optional buffer pool
 //! integration for reduced allocation overhead during high-throughput transfers.
+//!
+//! Two chunking strategies are supported: fixed-size chunks ([`FileChunker`])
+//! and content-defined chunking ([`CdcChunker`], FastCDC). Content-defined
+//! chunk boundaries follow the data rather than absolute offsets, so an
+//! insertion near the start of a file only changes the chunks around the
+//! edit instead of shifting every later chunk. Combined with a
+//! [`ChunkIndex`] keyed by BLAKE3 chunk hash, re-sending a slightly
+//! modified file only transfers the new or changed chunks.
 
 use crate::DEFAULT_CHUNK_SIZE;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs::{File, OpenOptions};
 use std::io::{self, Read, Seek, SeekFrom, Write};
 use std::path::Path;
 use wraith_transport::BufferPool;
 
+/// Submission queue depth for the batch-read I/O backend
+const BATCH_QUEUE_DEPTH: u32 = 128;
+
+/// Chunking strategy used to split a file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChunkingStrategy {
+    /// Fixed-size chunks at absolute offsets
+    #[default]
+    Fixed,
+    /// Content-defined chunk boundaries (FastCDC)
+    ContentDefined,
+}
+
 /// Chunk metadata
 #[derive(Debug, Clone)]
 pub struct ChunkInfo {
@@ -34,6 +55,11 @@ pub struct FileChunker {
     current_offset: u64,
     /// Optional buffer pool for chunk allocation
     buffer_pool: Option<BufferPool>,
+    /// Platform async I/O backend servicing batch reads in parallel
+    /// (io_uring on Linux, positional-read thread pool elsewhere); `None`
+    /// when the backend could not be opened, falling back to one large
+    /// sequential read per batch
+    async_io: Option<Box<dyn crate::async_io::AsyncFileIo>>,
 }
 
 impl FileChunker {
@@ -43,7 +69,7 @@ impl FileChunker {
     ///
     /// Returns an error if the file cannot be opened or metadata cannot be read.
     pub fn new<P: AsRef<Path>>(path: P, chunk_size: usize) -> io::Result<Self> {
-        let file = File::open(path)?;
+        let file = File::open(&path)?;
         let total_size = file.metadata()?.len();
 
         Ok(Self {
@@ -52,6 +78,7 @@ impl FileChunker {
             total_size,
             current_offset: 0,
             buffer_pool: None,
+            async_io: Self::open_batch_backend(&path),
         })
     }
 
@@ -84,7 +111,7 @@ impl FileChunker {
         chunk_size: usize,
         buffer_pool: BufferPool,
     ) -> io::Result<Self> {
-        let file = File::open(path)?;
+        let file = File::open(&path)?;
         let total_size = file.metadata()?.len();
 
         Ok(Self {
@@ -93,9 +120,22 @@ impl FileChunker {
             total_size,
             current_offset: 0,
             buffer_pool: Some(buffer_pool),
+            async_io: Self::open_batch_backend(&path),
         })
     }
 
+    /// Open the platform async I/O backend used for parallel batch reads
+    ///
+    /// Best-effort: batch reads fall back to a single sequential read when
+    /// the backend is unavailable (e.g. io_uring disabled by seccomp).
+    fn open_batch_backend<P: AsRef<Path>>(
+        path: P,
+    ) -> Option<Box<dyn crate::async_io::AsyncFileIo>> {
+        crate::async_io::open_platform(path, BATCH_QUEUE_DEPTH)
+            .inspect_err(|e| tracing::debug!("Batch read backend unavailable: {e}"))
+            .ok()
+    }
+
     /// Create a chunker with default chunk size
     ///
     /// # Errors
@@ -223,6 +263,66 @@ impl FileChunker {
             .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "Chunk not found"))
     }
 
+    /// Read a contiguous run of chunks as one batched I/O submission
+    ///
+    /// Issues one positional read per chunk through the platform async I/O
+    /// backend ([`crate::async_io::open_platform`]: io_uring on Linux, a
+    /// positional-read thread pool elsewhere) and submits them together,
+    /// so the chunks of a batch are read in parallel while per-chunk
+    /// hashes remain valid. The batch is truncated at end of file and the
+    /// final chunk may be short. Used by rate-adaptive senders to group
+    /// small logical chunks into larger disk submissions. Falls back to a
+    /// single sequential read when the backend is unavailable.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `start_index` is out of bounds, `count` is zero,
+    /// or reading fails.
+    pub fn read_chunk_batch(&mut self, start_index: u64, count: usize) -> io::Result<Vec<Vec<u8>>> {
+        if count == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Batch count must be nonzero",
+            ));
+        }
+
+        self.seek_to_chunk(start_index)?;
+
+        let remaining = (self.total_size - self.current_offset) as usize;
+        let batch_len = remaining.min(count.saturating_mul(self.chunk_size));
+
+        let chunks = match self.async_io.as_mut() {
+            Some(backend) => {
+                // Queue one read per fixed chunk boundary, then submit the
+                // whole batch in one go
+                let requests: Vec<_> = (0..batch_len)
+                    .step_by(self.chunk_size)
+                    .map(|chunk_start| {
+                        let len = self.chunk_size.min(batch_len - chunk_start);
+                        backend.read_at(self.current_offset + chunk_start as u64, len)
+                    })
+                    .collect::<io::Result<_>>()?;
+                backend.submit()?;
+                requests
+                    .into_iter()
+                    .map(|request| backend.wait_read(request))
+                    .collect::<io::Result<_>>()?
+            }
+            None => {
+                let mut buffer = vec![0u8; batch_len];
+                self.file.read_exact(&mut buffer)?;
+                // Split at the fixed hashed boundaries (the last piece may
+                // be short)
+                buffer.chunks(self.chunk_size).map(<[u8]>::to_vec).collect()
+            }
+        };
+        self.current_offset += batch_len as u64;
+        // Keep the sequential-read cursor in step with the batch
+        self.file.seek(SeekFrom::Start(self.current_offset))?;
+
+        Ok(chunks)
+    }
+
     /// Get chunk info for a specific index
     ///
     /// # Errors
@@ -250,6 +350,313 @@ impl FileChunker {
     }
 }
 
+/// FastCDC parameters
+///
+/// Chunk sizes are bounded by `min_size` and `max_size`; `avg_size` sets the
+/// expected chunk size and must be a power of two between the bounds.
+#[derive(Debug, Clone, Copy)]
+pub struct CdcParams {
+    /// Minimum chunk size in bytes (no cut points before this)
+    pub min_size: usize,
+    /// Target average chunk size in bytes (power of two)
+    pub avg_size: usize,
+    /// Maximum chunk size in bytes (forced cut at this length)
+    pub max_size: usize,
+}
+
+impl Default for CdcParams {
+    fn default() -> Self {
+        Self {
+            min_size: DEFAULT_CHUNK_SIZE / 4,
+            avg_size: DEFAULT_CHUNK_SIZE,
+            max_size: DEFAULT_CHUNK_SIZE * 4,
+        }
+    }
+}
+
+impl CdcParams {
+    /// Validate the parameter relationships
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the sizes are zero, out of order, or `avg_size`
+    /// is not a power of two.
+    pub fn validate(&self) -> io::Result<()> {
+        if self.min_size == 0 || !self.avg_size.is_power_of_two() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "avg_size must be a power of two and min_size non-zero",
+            ));
+        }
+        if self.min_size > self.avg_size || self.avg_size > self.max_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "CDC sizes must satisfy min <= avg <= max",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Gear hash table for FastCDC, generated deterministically (splitmix64)
+/// so both transfer endpoints compute identical chunk boundaries.
+const GEAR: [u64; 256] = {
+    let mut table = [0u64; 256];
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut i = 0;
+    while i < 256 {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+};
+
+/// Find the FastCDC cut point for `data`
+///
+/// Returns the length of the next chunk (at most `data.len()`). Uses
+/// normalized chunking: a stricter mask before the average size makes
+/// early cuts rare, a looser mask after it makes late cuts likely, pulling
+/// the chunk size distribution toward `avg_size`.
+#[must_use]
+pub fn cdc_cut_point(data: &[u8], params: &CdcParams) -> usize {
+    let len = data.len();
+    if len <= params.min_size {
+        return len;
+    }
+
+    let bits = params.avg_size.trailing_zeros();
+    let mask_strict: u64 = (1u64 << (bits + 2)) - 1;
+    let mask_loose: u64 = (1u64 << (bits - 2)) - 1;
+
+    let normal = len.min(params.avg_size);
+    let max = len.min(params.max_size);
+
+    let mut hash: u64 = 0;
+    let mut i = params.min_size;
+
+    while i < normal {
+        hash = (hash << 1).wrapping_add(GEAR[data[i] as usize]);
+        if hash & mask_strict == 0 {
+            return i + 1;
+        }
+        i += 1;
+    }
+
+    while i < max {
+        hash = (hash << 1).wrapping_add(GEAR[data[i] as usize]);
+        if hash & mask_loose == 0 {
+            return i + 1;
+        }
+        i += 1;
+    }
+
+    max
+}
+
+/// Split an in-memory buffer into content-defined chunks
+///
+/// Returns `(offset, size)` pairs covering the whole buffer in order.
+#[must_use]
+pub fn cdc_chunk_offsets(data: &[u8], params: &CdcParams) -> Vec<(u64, usize)> {
+    let mut offsets = Vec::new();
+    let mut pos = 0;
+
+    while pos < data.len() {
+        let size = cdc_cut_point(&data[pos..], params);
+        offsets.push((pos as u64, size));
+        pos += size;
+    }
+
+    offsets
+}
+
+/// Content-defined chunker over a file (FastCDC)
+///
+/// Streams the file through a lookahead buffer of `max_size` bytes, so
+/// memory use is bounded regardless of file size. Chunks come out in file
+/// order; concatenating them reproduces the file exactly.
+pub struct CdcChunker {
+    file: File,
+    params: CdcParams,
+    total_size: u64,
+    bytes_read: u64,
+    /// Lookahead buffer holding unconsumed file data
+    buffer: Vec<u8>,
+}
+
+impl CdcChunker {
+    /// Create a content-defined chunker for a file
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the parameters are invalid or the file cannot
+    /// be opened.
+    pub fn new<P: AsRef<Path>>(path: P, params: CdcParams) -> io::Result<Self> {
+        params.validate()?;
+        let file = File::open(path)?;
+        let total_size = file.metadata()?.len();
+
+        Ok(Self {
+            file,
+            params,
+            total_size,
+            bytes_read: 0,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Create a chunker with default FastCDC parameters
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened.
+    pub fn with_default_params<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::new(path, CdcParams::default())
+    }
+
+    /// Get total file size
+    #[must_use]
+    pub fn total_size(&self) -> u64 {
+        self.total_size
+    }
+
+    /// Get the chunking parameters
+    #[must_use]
+    pub fn params(&self) -> &CdcParams {
+        &self.params
+    }
+
+    /// Read the next content-defined chunk
+    ///
+    /// Returns `None` when the file is exhausted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the file fails.
+    pub fn read_chunk(&mut self) -> io::Result<Option<Vec<u8>>> {
+        // Top up the lookahead buffer to max_size so the cut point search
+        // always sees a full window (except at end of file)
+        while self.buffer.len() < self.params.max_size && self.bytes_read < self.total_size {
+            let want = (self.params.max_size - self.buffer.len())
+                .min((self.total_size - self.bytes_read) as usize);
+            let start = self.buffer.len();
+            self.buffer.resize(start + want, 0);
+            self.file.read_exact(&mut self.buffer[start..])?;
+            self.bytes_read += want as u64;
+        }
+
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+
+        let size = cdc_cut_point(&self.buffer, &self.params);
+        let chunk = self.buffer.drain(..size).collect();
+        Ok(Some(chunk))
+    }
+}
+
+/// Location of a chunk within its source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkRef {
+    /// Byte offset in the source file
+    pub offset: u64,
+    /// Chunk size in bytes
+    pub size: usize,
+}
+
+/// Chunk index keyed by BLAKE3 hash
+///
+/// Maps chunk hashes to their location in a local file, enabling
+/// deduplication: before requesting a chunk from a peer, the receiver
+/// checks the index built from a previous version of the file and copies
+/// matching chunks locally instead of transferring them.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkIndex {
+    chunks: HashMap<[u8; 32], ChunkRef>,
+}
+
+impl ChunkIndex {
+    /// Create an empty index
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build an index from a file using content-defined chunking
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read.
+    pub fn build<P: AsRef<Path>>(path: P, params: CdcParams) -> io::Result<Self> {
+        let mut chunker = CdcChunker::new(path, params)?;
+        let mut index = Self::new();
+        let mut offset = 0u64;
+
+        while let Some(chunk) = chunker.read_chunk()? {
+            let hash = blake3::hash(&chunk);
+            index.insert(
+                *hash.as_bytes(),
+                ChunkRef {
+                    offset,
+                    size: chunk.len(),
+                },
+            );
+            offset += chunk.len() as u64;
+        }
+
+        Ok(index)
+    }
+
+    /// Insert a chunk location
+    ///
+    /// Duplicate hashes keep the first location recorded.
+    pub fn insert(&mut self, hash: [u8; 32], chunk: ChunkRef) {
+        self.chunks.entry(hash).or_insert(chunk);
+    }
+
+    /// Check whether a chunk hash is present
+    #[must_use]
+    pub fn contains(&self, hash: &[u8; 32]) -> bool {
+        self.chunks.contains_key(hash)
+    }
+
+    /// Look up the location of a chunk by hash
+    #[must_use]
+    pub fn get(&self, hash: &[u8; 32]) -> Option<&ChunkRef> {
+        self.chunks.get(hash)
+    }
+
+    /// Number of distinct chunks in the index
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Check if the index is empty
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    /// Partition a chunk hash list into locally-available and missing
+    ///
+    /// Given the manifest of a file to receive, returns the hashes that
+    /// must be fetched from the sender; the rest can be copied from the
+    /// local file this index was built from.
+    #[must_use]
+    pub fn missing_from(&self, manifest: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        manifest
+            .iter()
+            .filter(|hash| !self.contains(hash))
+            .copied()
+            .collect()
+    }
+}
+
 /// File reassembler for receiving side
 ///
 /// Supports out-of-order chunk writing for parallel downloads with O(1) chunk
@@ -306,6 +713,54 @@ impl FileReassembler {
         })
     }
 
+    /// Reopen a partially written file, preserving already-received chunks
+    ///
+    /// Unlike [`FileReassembler::new`], the file is opened without
+    /// truncation so data written by a previous run survives. The caller
+    /// supplies the set of chunks known to be on disk (e.g. from persisted
+    /// resume state); those are treated as received and excluded from
+    /// [`missing_chunks`](FileReassembler::missing_chunks).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or pre-allocated.
+    pub fn resume<P: AsRef<Path>>(
+        path: P,
+        total_size: u64,
+        chunk_size: usize,
+        received: &HashSet<u64>,
+    ) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        // Ensure the file spans the full size even if the previous run
+        // never wrote the final chunk
+        file.set_len(total_size)?;
+
+        let total_chunks = total_size.div_ceil(chunk_size as u64);
+
+        let received_chunks: HashSet<u64> = received
+            .iter()
+            .copied()
+            .filter(|&i| i < total_chunks)
+            .collect();
+        let missing_chunks_set: HashSet<u64> = (0..total_chunks)
+            .filter(|i| !received_chunks.contains(i))
+            .collect();
+
+        Ok(Self {
+            file,
+            chunk_size,
+            total_chunks,
+            total_size,
+            received_chunks,
+            missing_chunks_set,
+        })
+    }
+
     /// Write chunk at specific index
     ///
     /// Supports out-of-order chunk writes for parallel downloads.
@@ -499,6 +954,56 @@ mod tests {
         assert_eq!(chunk.len(), DEFAULT_CHUNK_SIZE);
     }
 
+    #[test]
+    fn test_read_chunk_batch_matches_per_chunk_reads() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let data: Vec<u8> = (0..1000u32).flat_map(u32::to_le_bytes).collect(); // 4000 bytes
+        temp_file.write_all(&data).unwrap();
+        temp_file.flush().unwrap();
+
+        let mut chunker = FileChunker::new(temp_file.path(), 1024).unwrap();
+        assert_eq!(chunker.num_chunks(), 4);
+
+        // One batched read produces the same chunks (and hashes) as
+        // individual reads at the fixed boundaries
+        let batch = chunker.read_chunk_batch(0, 4).unwrap();
+        assert_eq!(batch.len(), 4);
+        for (index, chunk) in batch.iter().enumerate() {
+            let individual = chunker.read_chunk_at(index as u64).unwrap();
+            assert_eq!(chunk, &individual);
+            assert_eq!(blake3::hash(chunk), blake3::hash(&individual));
+        }
+
+        // Final chunk is short (4000 - 3 * 1024 = 928 bytes)
+        assert_eq!(batch[3].len(), 928);
+    }
+
+    #[test]
+    fn test_read_chunk_batch_truncates_at_eof() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&vec![0x55u8; 3000]).unwrap();
+        temp_file.flush().unwrap();
+
+        let mut chunker = FileChunker::new(temp_file.path(), 1024).unwrap();
+
+        // Asking for more chunks than remain yields only what exists
+        let batch = chunker.read_chunk_batch(2, 16).unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch[0].len(), 3000 - 2 * 1024);
+    }
+
+    #[test]
+    fn test_read_chunk_batch_invalid_input() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&[0u8; 100]).unwrap();
+        temp_file.flush().unwrap();
+
+        let mut chunker = FileChunker::new(temp_file.path(), 64).unwrap();
+
+        assert!(chunker.read_chunk_batch(0, 0).is_err());
+        assert!(chunker.read_chunk_batch(99, 1).is_err()); // Out of bounds
+    }
+
     #[test]
     fn test_out_of_order_reassembly() {
         let mut temp_file = NamedTempFile::new().unwrap();
@@ -588,6 +1093,45 @@ mod tests {
         assert!(reassembler.finalize().is_err());
     }
 
+    #[test]
+    fn test_reassembler_resume_preserves_existing_chunks() {
+        let output_file = NamedTempFile::new().unwrap();
+        let total_size = 4 * 1024u64;
+        let chunk_size = 1024usize;
+
+        // First run: write chunks 0 and 2, then "crash"
+        let mut reassembler =
+            FileReassembler::new(output_file.path(), total_size, chunk_size).unwrap();
+        reassembler.write_chunk(0, &vec![0xAA; chunk_size]).unwrap();
+        reassembler.write_chunk(2, &vec![0xCC; chunk_size]).unwrap();
+        drop(reassembler);
+
+        // Second run: resume with the persisted chunk set
+        let received: HashSet<u64> = [0, 2].into_iter().collect();
+        let mut resumed =
+            FileReassembler::resume(output_file.path(), total_size, chunk_size, &received).unwrap();
+
+        assert!(resumed.has_chunk(0));
+        assert!(resumed.has_chunk(2));
+        let mut missing = resumed.missing_chunks();
+        missing.sort_unstable();
+        assert_eq!(missing, vec![1, 3]);
+
+        // Only the missing chunks need to arrive
+        resumed.write_chunk(1, &vec![0xBB; chunk_size]).unwrap();
+        resumed.write_chunk(3, &vec![0xDD; chunk_size]).unwrap();
+        assert!(resumed.is_complete());
+        resumed.finalize().unwrap();
+
+        // Data from the first run survived the reopen
+        let data = std::fs::read(output_file.path()).unwrap();
+        assert_eq!(&data[..chunk_size], &vec![0xAA; chunk_size][..]);
+        assert_eq!(
+            &data[2 * chunk_size..3 * chunk_size],
+            &vec![0xCC; chunk_size][..]
+        );
+    }
+
     // Buffer pool integration tests
 
     #[test]
@@ -712,6 +1256,198 @@ mod tests {
         chunker.release_chunk(chunk);
     }
 
+    // Content-defined chunking tests
+
+    /// Small parameters so tests exercise multiple chunks cheaply
+    fn small_cdc() -> CdcParams {
+        CdcParams {
+            min_size: 1024,
+            avg_size: 4096,
+            max_size: 16384,
+        }
+    }
+
+    /// Pseudo-random but deterministic test data
+    fn patterned(len: usize, seed: u8) -> Vec<u8> {
+        (0..len)
+            .map(|i| ((i as u64).wrapping_mul(31).wrapping_add(seed as u64) % 251) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn test_cdc_params_validation() {
+        assert!(CdcParams::default().validate().is_ok());
+        assert!(small_cdc().validate().is_ok());
+
+        let bad_avg = CdcParams {
+            avg_size: 5000, // Not a power of two
+            ..small_cdc()
+        };
+        assert!(bad_avg.validate().is_err());
+
+        let bad_order = CdcParams {
+            min_size: 8192,
+            avg_size: 4096,
+            max_size: 16384,
+        };
+        assert!(bad_order.validate().is_err());
+    }
+
+    #[test]
+    fn test_cdc_cut_point_bounds() {
+        let params = small_cdc();
+        let data = patterned(100_000, 7);
+
+        let cut = cdc_cut_point(&data, &params);
+        assert!(cut > params.min_size);
+        assert!(cut <= params.max_size);
+    }
+
+    #[test]
+    fn test_cdc_cut_point_short_input() {
+        let params = small_cdc();
+
+        // Input at or below min_size is one chunk
+        assert_eq!(cdc_cut_point(&[0u8; 100], &params), 100);
+        assert_eq!(cdc_cut_point(&[0u8; 1024], &params), 1024);
+        assert_eq!(cdc_cut_point(&[], &params), 0);
+    }
+
+    #[test]
+    fn test_cdc_offsets_cover_input() {
+        let params = small_cdc();
+        let data = patterned(200_000, 3);
+
+        let offsets = cdc_chunk_offsets(&data, &params);
+        assert!(!offsets.is_empty());
+
+        let mut expected_offset = 0u64;
+        for (offset, size) in &offsets {
+            assert_eq!(*offset, expected_offset);
+            assert!(*size <= params.max_size);
+            expected_offset += *size as u64;
+        }
+        assert_eq!(expected_offset, data.len() as u64);
+    }
+
+    #[test]
+    fn test_cdc_chunker_roundtrip() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let data = patterned(150_000, 11);
+        temp_file.write_all(&data).unwrap();
+        temp_file.flush().unwrap();
+
+        let mut chunker = CdcChunker::new(temp_file.path(), small_cdc()).unwrap();
+        assert_eq!(chunker.total_size(), data.len() as u64);
+
+        let mut reconstructed = Vec::new();
+        let mut chunk_count = 0;
+        while let Some(chunk) = chunker.read_chunk().unwrap() {
+            assert!(chunk.len() <= small_cdc().max_size);
+            reconstructed.extend_from_slice(&chunk);
+            chunk_count += 1;
+        }
+
+        assert!(chunk_count > 1);
+        assert_eq!(reconstructed, data);
+    }
+
+    #[test]
+    fn test_cdc_boundaries_survive_insertion() {
+        let params = small_cdc();
+        let original = patterned(200_000, 5);
+
+        // Insert a few bytes near the start: fixed-size chunking would
+        // shift every later chunk, CDC boundaries resynchronize
+        let mut modified = original.clone();
+        for (i, b) in [0x01u8, 0x02, 0x03].iter().enumerate() {
+            modified.insert(5000 + i, *b);
+        }
+
+        let hash_chunks = |data: &[u8]| -> HashSet<[u8; 32]> {
+            cdc_chunk_offsets(data, &params)
+                .iter()
+                .map(|(offset, size)| {
+                    *blake3::hash(&data[*offset as usize..*offset as usize + size]).as_bytes()
+                })
+                .collect()
+        };
+
+        let original_hashes = hash_chunks(&original);
+        let modified_hashes = hash_chunks(&modified);
+
+        let shared = original_hashes.intersection(&modified_hashes).count();
+        // Most chunks should be unchanged; only those around the edit differ
+        assert!(
+            shared * 2 > modified_hashes.len(),
+            "expected majority of chunks shared, got {shared}/{}",
+            modified_hashes.len()
+        );
+    }
+
+    #[test]
+    fn test_chunk_index_build_and_lookup() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let data = patterned(100_000, 9);
+        temp_file.write_all(&data).unwrap();
+        temp_file.flush().unwrap();
+
+        let params = small_cdc();
+        let index = ChunkIndex::build(temp_file.path(), params).unwrap();
+        assert!(!index.is_empty());
+
+        // Every chunk of the file must be recoverable from its recorded
+        // location (duplicate chunks keep their first occurrence)
+        let offsets = cdc_chunk_offsets(&data, &params);
+        for (offset, size) in offsets {
+            let chunk = &data[offset as usize..offset as usize + size];
+            let hash = *blake3::hash(chunk).as_bytes();
+            let chunk_ref = index.get(&hash).expect("chunk missing from index");
+            assert_eq!(chunk_ref.size, size);
+            let stored =
+                &data[chunk_ref.offset as usize..chunk_ref.offset as usize + chunk_ref.size];
+            assert_eq!(stored, chunk);
+        }
+    }
+
+    #[test]
+    fn test_chunk_index_dedup_missing_from() {
+        let params = small_cdc();
+        let original = patterned(200_000, 5);
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        temp_file.write_all(&original).unwrap();
+        temp_file.flush().unwrap();
+
+        let index = ChunkIndex::build(temp_file.path(), params).unwrap();
+
+        // Manifest of a modified version of the same file
+        let mut modified = original.clone();
+        modified.insert(5000, 0xFF);
+        let manifest: Vec<[u8; 32]> = cdc_chunk_offsets(&modified, &params)
+            .iter()
+            .map(|(offset, size)| {
+                *blake3::hash(&modified[*offset as usize..*offset as usize + size]).as_bytes()
+            })
+            .collect();
+
+        let missing = index.missing_from(&manifest);
+        // Only the chunks around the edit need transferring
+        assert!(!missing.is_empty());
+        assert!(
+            missing.len() * 2 < manifest.len(),
+            "expected minority of chunks missing, got {}/{}",
+            missing.len(),
+            manifest.len()
+        );
+    }
+
+    #[test]
+    fn test_chunking_strategy_default() {
+        assert_eq!(ChunkingStrategy::default(), ChunkingStrategy::Fixed);
+        assert_ne!(ChunkingStrategy::Fixed, ChunkingStrategy::ContentDefined);
+    }
+
     #[test]
     fn test_chunker_buffer_pool_roundtrip() {
         use wraith_transport::BufferPool;
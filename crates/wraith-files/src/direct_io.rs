@@ -0,0 +1,532 @@
+//! O_DIRECT chunk reading with an aligned buffer pool.
+//!
+//! Streaming a very large file through the page cache evicts everything
+//! else on the machine for data that is read exactly once. This module
+//! reads chunks with `O_DIRECT`, bypassing the page cache entirely, using
+//! buffers from an [`AlignedBufferPool`] sized to the chunk size (direct
+//! I/O requires the buffer address, file offset, and read length to be
+//! block-aligned).
+//!
+//! Direct I/O is best-effort: when the filesystem rejects `O_DIRECT`
+//! (tmpfs, some network filesystems), the chunk size is not a multiple of
+//! [`DIRECT_IO_ALIGNMENT`], or the platform is not Linux, reads fall back
+//! to ordinary buffered I/O with the same API and results. Callers can
+//! check [`DirectChunker::is_direct`] but never need to branch on it.
+
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Buffer and offset alignment for direct I/O
+///
+/// 4096 covers both 512-byte and 4K-sector devices; the kernel only
+/// requires logical block size alignment, so this is conservative.
+pub const DIRECT_IO_ALIGNMENT: usize = 4096;
+
+/// One alignment-sized block; `Vec<AlignedBlock>` gives the pool aligned
+/// contiguous storage without manual allocator calls
+#[repr(C, align(4096))]
+#[derive(Clone, Copy)]
+struct AlignedBlock([u8; DIRECT_IO_ALIGNMENT]);
+
+/// Fixed-capacity byte buffer whose storage is aligned for direct I/O
+///
+/// Dereferences to the valid portion filled by the last read, like a
+/// `Vec<u8>` chunk from [`FileChunker`](crate::chunker::FileChunker).
+pub struct AlignedBuffer {
+    blocks: Vec<AlignedBlock>,
+    len: usize,
+}
+
+impl AlignedBuffer {
+    /// Allocate a zeroed buffer with at least `capacity` bytes of storage
+    fn with_capacity(capacity: usize) -> Self {
+        let blocks = capacity.div_ceil(DIRECT_IO_ALIGNMENT).max(1);
+        Self {
+            blocks: vec![AlignedBlock([0u8; DIRECT_IO_ALIGNMENT]); blocks],
+            len: 0,
+        }
+    }
+
+    /// Total aligned storage in bytes (a multiple of the alignment)
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.blocks.len() * DIRECT_IO_ALIGNMENT
+    }
+
+    /// Length of the valid data
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check if the buffer holds no data
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Valid data filled by the last read
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        // SAFETY: blocks is one contiguous allocation of plain bytes and
+        // len never exceeds capacity
+        unsafe { std::slice::from_raw_parts(self.blocks.as_ptr().cast(), self.len) }
+    }
+
+    /// Full aligned storage for the read syscall to fill
+    fn storage_mut(&mut self) -> &mut [u8] {
+        let capacity = self.capacity();
+        // SAFETY: blocks is one contiguous allocation of plain bytes
+        unsafe { std::slice::from_raw_parts_mut(self.blocks.as_mut_ptr().cast(), capacity) }
+    }
+
+    /// Mark `len` bytes as valid data
+    fn set_len(&mut self, len: usize) {
+        debug_assert!(len <= self.capacity());
+        self.len = len;
+    }
+}
+
+impl std::ops::Deref for AlignedBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl AsRef<[u8]> for AlignedBuffer {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+/// Pool of aligned buffers sized to the chunk size
+///
+/// Mirrors [`BufferPool`](wraith_transport::BufferPool) for aligned
+/// storage: buffers are pre-allocated up front, `acquire` falls back to a
+/// fresh allocation when the pool is empty, and `release` drops buffers
+/// beyond the configured pool size instead of growing without bound.
+#[derive(Clone)]
+pub struct AlignedBufferPool {
+    buffers: Arc<Mutex<Vec<AlignedBuffer>>>,
+    buffer_capacity: usize,
+    pool_size: usize,
+}
+
+impl AlignedBufferPool {
+    /// Create a pool of `pool_size` buffers, each holding at least
+    /// `buffer_capacity` bytes (rounded up to the alignment)
+    #[must_use]
+    pub fn new(buffer_capacity: usize, pool_size: usize) -> Self {
+        let buffers = (0..pool_size)
+            .map(|_| AlignedBuffer::with_capacity(buffer_capacity))
+            .collect();
+
+        Self {
+            buffers: Arc::new(Mutex::new(buffers)),
+            buffer_capacity,
+            pool_size,
+        }
+    }
+
+    /// Acquire a buffer, allocating a new one if the pool is exhausted
+    #[must_use]
+    pub fn acquire(&self) -> AlignedBuffer {
+        let recycled = self.buffers.lock().expect("pool lock poisoned").pop();
+        recycled.unwrap_or_else(|| AlignedBuffer::with_capacity(self.buffer_capacity))
+    }
+
+    /// Return a buffer to the pool for reuse
+    ///
+    /// Buffers beyond the configured pool size are dropped.
+    pub fn release(&self, mut buffer: AlignedBuffer) {
+        buffer.set_len(0);
+        let mut buffers = self.buffers.lock().expect("pool lock poisoned");
+        if buffers.len() < self.pool_size {
+            buffers.push(buffer);
+        }
+    }
+
+    /// Number of buffers currently available in the pool
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.buffers.lock().expect("pool lock poisoned").len()
+    }
+}
+
+/// Chunk reader bypassing the page cache for very large transfers
+///
+/// Same sequential-plus-seek surface as
+/// [`FileChunker`](crate::chunker::FileChunker), but chunks come back in
+/// pooled [`AlignedBuffer`]s and reads use `O_DIRECT` where the platform
+/// and filesystem allow it.
+pub struct DirectChunker {
+    file: File,
+    path: PathBuf,
+    /// Whether reads currently bypass the page cache
+    direct: bool,
+    pool: AlignedBufferPool,
+    chunk_size: usize,
+    total_size: u64,
+    current_offset: u64,
+}
+
+impl DirectChunker {
+    /// Open a file for direct chunk reading
+    ///
+    /// Tries `O_DIRECT` first and silently falls back to buffered I/O if
+    /// the chunk size is not a multiple of [`DIRECT_IO_ALIGNMENT`] or the
+    /// filesystem refuses direct opens. `pool_size` bounds how many chunk
+    /// buffers are kept alive for reuse.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be opened or metadata cannot
+    /// be read.
+    pub fn new<P: AsRef<Path>>(path: P, chunk_size: usize, pool_size: usize) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let (file, direct) = if chunk_size % DIRECT_IO_ALIGNMENT == 0 {
+            match open_direct(&path) {
+                Some(file) => (file, true),
+                None => (File::open(&path)?, false),
+            }
+        } else {
+            (File::open(&path)?, false)
+        };
+
+        let total_size = file.metadata()?.len();
+
+        Ok(Self {
+            file,
+            path,
+            direct,
+            pool: AlignedBufferPool::new(chunk_size, pool_size),
+            chunk_size,
+            total_size,
+            current_offset: 0,
+        })
+    }
+
+    /// Whether reads currently bypass the page cache
+    #[must_use]
+    pub fn is_direct(&self) -> bool {
+        self.direct
+    }
+
+    /// Get the buffer pool chunks are drawn from
+    #[must_use]
+    pub fn buffer_pool(&self) -> &AlignedBufferPool {
+        &self.pool
+    }
+
+    /// Get total number of chunks
+    #[must_use]
+    pub fn num_chunks(&self) -> u64 {
+        self.total_size.div_ceil(self.chunk_size as u64)
+    }
+
+    /// Get chunk size
+    #[must_use]
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Get total file size
+    #[must_use]
+    pub fn total_size(&self) -> u64 {
+        self.total_size
+    }
+
+    /// Read next chunk sequentially into a pooled aligned buffer
+    ///
+    /// Returns `None` when the file is exhausted. Release the buffer back
+    /// with [`release_chunk`](Self::release_chunk) after processing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if reading from the file fails.
+    pub fn read_chunk(&mut self) -> io::Result<Option<AlignedBuffer>> {
+        if self.current_offset >= self.total_size {
+            return Ok(None);
+        }
+
+        let remaining = self.total_size - self.current_offset;
+        let chunk_len = remaining.min(self.chunk_size as u64) as usize;
+
+        let mut buffer = self.pool.acquire();
+        match self.fill_buffer(&mut buffer, chunk_len) {
+            Ok(()) => {}
+            // The open succeeded but the filesystem rejects direct reads
+            // (seen on some network filesystems): reopen buffered and retry
+            Err(e) if self.direct && e.raw_os_error() == Some(libc_einval()) => {
+                self.fall_back_to_buffered()?;
+                self.fill_buffer(&mut buffer, chunk_len)?;
+            }
+            Err(e) => return Err(e),
+        }
+
+        buffer.set_len(chunk_len);
+        self.current_offset += chunk_len as u64;
+
+        Ok(Some(buffer))
+    }
+
+    /// Release a chunk buffer back to the pool
+    pub fn release_chunk(&self, buffer: AlignedBuffer) {
+        self.pool.release(buffer);
+    }
+
+    /// Seek to specific chunk
+    ///
+    /// Chunk offsets are multiples of the chunk size, so direct-mode
+    /// alignment is preserved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk index is out of bounds or seeking
+    /// fails.
+    pub fn seek_to_chunk(&mut self, chunk_index: u64) -> io::Result<()> {
+        let offset = chunk_index * self.chunk_size as u64;
+
+        if offset >= self.total_size {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Chunk index out of bounds",
+            ));
+        }
+
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.current_offset = offset;
+
+        Ok(())
+    }
+
+    /// Read specific chunk by index
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the chunk index is invalid or reading fails.
+    pub fn read_chunk_at(&mut self, chunk_index: u64) -> io::Result<AlignedBuffer> {
+        self.seek_to_chunk(chunk_index)?;
+        self.read_chunk()?
+            .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "Chunk not found"))
+    }
+
+    /// Read `chunk_len` bytes into the front of `buffer`
+    ///
+    /// In direct mode the requested length is rounded up to the alignment
+    /// (the kernel requires it); the final partial chunk then comes back
+    /// as a short read stopping at end of file, which is permitted.
+    fn fill_buffer(&mut self, buffer: &mut AlignedBuffer, chunk_len: usize) -> io::Result<()> {
+        let want = if self.direct {
+            chunk_len.div_ceil(DIRECT_IO_ALIGNMENT) * DIRECT_IO_ALIGNMENT
+        } else {
+            chunk_len
+        };
+
+        let storage = &mut buffer.storage_mut()[..want];
+        let mut filled = 0;
+        while filled < chunk_len {
+            match self.file.read(&mut storage[filled..])? {
+                0 => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "File truncated during chunk read",
+                    ));
+                }
+                n => filled += n,
+            }
+        }
+        Ok(())
+    }
+
+    /// Reopen the file buffered at the current position
+    fn fall_back_to_buffered(&mut self) -> io::Result<()> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.current_offset))?;
+        self.file = file;
+        self.direct = false;
+        Ok(())
+    }
+}
+
+/// Try to open a file with `O_DIRECT`
+///
+/// Filesystems without direct I/O support (tmpfs among them) fail the
+/// open with `EINVAL`, which selects the buffered fallback.
+#[cfg(target_os = "linux")]
+fn open_direct(path: &Path) -> Option<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+
+    std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(libc::O_DIRECT)
+        .open(path)
+        .ok()
+}
+
+/// Direct I/O is Linux-only; other platforms always use buffered reads
+#[cfg(not(target_os = "linux"))]
+fn open_direct(_path: &Path) -> Option<File> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn libc_einval() -> i32 {
+    libc::EINVAL
+}
+
+#[cfg(not(target_os = "linux"))]
+fn libc_einval() -> i32 {
+    22
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DEFAULT_CHUNK_SIZE;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    /// Pseudo-random but deterministic test data
+    fn patterned(len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|i| ((i as u64).wrapping_mul(37).wrapping_add(13) % 251) as u8)
+            .collect()
+    }
+
+    #[test]
+    fn test_aligned_buffer_alignment() {
+        let buffer = AlignedBuffer::with_capacity(DEFAULT_CHUNK_SIZE);
+        assert_eq!(buffer.capacity(), DEFAULT_CHUNK_SIZE);
+        assert_eq!(buffer.as_slice().as_ptr() as usize % DIRECT_IO_ALIGNMENT, 0);
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn test_aligned_buffer_rounds_capacity_up() {
+        let buffer = AlignedBuffer::with_capacity(1000);
+        assert_eq!(buffer.capacity(), DIRECT_IO_ALIGNMENT);
+
+        let buffer = AlignedBuffer::with_capacity(DIRECT_IO_ALIGNMENT + 1);
+        assert_eq!(buffer.capacity(), 2 * DIRECT_IO_ALIGNMENT);
+    }
+
+    #[test]
+    fn test_pool_acquire_release() {
+        let pool = AlignedBufferPool::new(DIRECT_IO_ALIGNMENT, 2);
+        assert_eq!(pool.available(), 2);
+
+        let a = pool.acquire();
+        let b = pool.acquire();
+        assert_eq!(pool.available(), 0);
+
+        // Exhausted pool allocates rather than blocking
+        let c = pool.acquire();
+        assert_eq!(c.capacity(), DIRECT_IO_ALIGNMENT);
+
+        pool.release(a);
+        pool.release(b);
+        assert_eq!(pool.available(), 2);
+
+        // Buffers beyond the pool size are dropped
+        pool.release(c);
+        assert_eq!(pool.available(), 2);
+    }
+
+    #[test]
+    fn test_direct_chunker_roundtrip() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let data = patterned(DEFAULT_CHUNK_SIZE * 2 + 1000);
+        temp_file.write_all(&data).unwrap();
+        temp_file.flush().unwrap();
+
+        // Whether direct mode engages depends on the filesystem backing
+        // the temp dir; the data must be identical either way
+        let mut chunker = DirectChunker::new(temp_file.path(), DEFAULT_CHUNK_SIZE, 4).unwrap();
+        assert_eq!(chunker.num_chunks(), 3);
+        assert_eq!(chunker.total_size(), data.len() as u64);
+
+        let mut reconstructed = Vec::new();
+        while let Some(chunk) = chunker.read_chunk().unwrap() {
+            reconstructed.extend_from_slice(&chunk);
+            chunker.release_chunk(chunk);
+        }
+
+        assert_eq!(reconstructed, data);
+    }
+
+    #[test]
+    fn test_direct_chunker_last_partial_chunk() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let data = patterned(DEFAULT_CHUNK_SIZE + 1000);
+        temp_file.write_all(&data).unwrap();
+        temp_file.flush().unwrap();
+
+        let mut chunker = DirectChunker::new(temp_file.path(), DEFAULT_CHUNK_SIZE, 2).unwrap();
+
+        let chunk1 = chunker.read_chunk().unwrap().unwrap();
+        assert_eq!(chunk1.len(), DEFAULT_CHUNK_SIZE);
+
+        let chunk2 = chunker.read_chunk().unwrap().unwrap();
+        assert_eq!(chunk2.len(), 1000);
+        assert_eq!(&chunk2[..], &data[DEFAULT_CHUNK_SIZE..]);
+
+        assert!(chunker.read_chunk().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_unaligned_chunk_size_falls_back() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let data = patterned(10_000);
+        temp_file.write_all(&data).unwrap();
+        temp_file.flush().unwrap();
+
+        // 1000 is not a multiple of the alignment, so direct mode must
+        // not engage regardless of filesystem support
+        let mut chunker = DirectChunker::new(temp_file.path(), 1000, 2).unwrap();
+        assert!(!chunker.is_direct());
+
+        let mut reconstructed = Vec::new();
+        while let Some(chunk) = chunker.read_chunk().unwrap() {
+            reconstructed.extend_from_slice(&chunk);
+            chunker.release_chunk(chunk);
+        }
+        assert_eq!(reconstructed, data);
+    }
+
+    #[test]
+    fn test_direct_chunker_seek_and_read_at() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let data = patterned(DEFAULT_CHUNK_SIZE * 3);
+        temp_file.write_all(&data).unwrap();
+        temp_file.flush().unwrap();
+
+        let mut chunker = DirectChunker::new(temp_file.path(), DEFAULT_CHUNK_SIZE, 2).unwrap();
+
+        let chunk = chunker.read_chunk_at(2).unwrap();
+        assert_eq!(&chunk[..], &data[2 * DEFAULT_CHUNK_SIZE..]);
+
+        assert!(chunker.seek_to_chunk(3).is_err());
+    }
+
+    #[test]
+    fn test_pool_reuse_across_chunks() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let data = patterned(DEFAULT_CHUNK_SIZE * 4);
+        temp_file.write_all(&data).unwrap();
+        temp_file.flush().unwrap();
+
+        let mut chunker = DirectChunker::new(temp_file.path(), DEFAULT_CHUNK_SIZE, 1).unwrap();
+
+        // Releasing after each read keeps the working set at one buffer
+        while let Some(chunk) = chunker.read_chunk().unwrap() {
+            chunker.release_chunk(chunk);
+            assert_eq!(chunker.buffer_pool().available(), 1);
+        }
+    }
+}
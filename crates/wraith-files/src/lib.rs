@@ -11,8 +11,12 @@
 #![warn(missing_docs)]
 #![warn(clippy::all)]
 
+pub mod async_io;
+pub mod chunk_store;
 pub mod chunker;
+pub mod direct_io;
 pub mod hasher;
+pub mod scrub;
 pub mod transfer;
 pub mod tree_hash;
 
@@ -25,6 +29,8 @@ pub mod io_uring;
 /// Default chunk size (256 KiB)
 pub const DEFAULT_CHUNK_SIZE: usize = 256 * 1024;
 
+pub use chunker::ChunkingStrategy;
+
 /// File metadata for transfers
 #[derive(Debug, Clone)]
 pub struct FileMetadata {
@@ -36,4 +42,6 @@ pub struct FileMetadata {
     pub hash: [u8; 32],
     /// Number of chunks
     pub chunk_count: u64,
+    /// How the file was split into chunks
+    pub strategy: ChunkingStrategy,
 }
@@ -0,0 +1,485 @@
+//! Background integrity scrubbing of seeded content
+//!
+//! Long-running seeders accumulate silent corruption: disks bit-rot, and a
+//! seeder that keeps announcing a damaged file poisons every peer that
+//! downloads from it. The [`Scrubber`] periodically re-reads seeded files
+//! and re-verifies every chunk against the tree hash recorded when the
+//! content was first registered. Content that fails verification is
+//! immediately demoted to not-announced and a [`ScrubEvent::CorruptionDetected`]
+//! is broadcast so the owning node can withdraw its DHT announcements.
+//!
+//! Scrubbing is rate-limited so a large seed library does not saturate
+//! disk bandwidth needed by active transfers.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+use crate::chunker::FileChunker;
+use crate::tree_hash::FileTreeHash;
+
+/// Broadcast channel capacity for scrub events
+const EVENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Configuration for background scrubbing
+#[derive(Debug, Clone)]
+pub struct ScrubConfig {
+    /// How often a full scrub pass over all seeded content starts
+    pub interval: Duration,
+    /// Disk read throughput cap in bytes per second (None = unlimited)
+    ///
+    /// Keeps scrubbing from competing with active transfers for disk
+    /// bandwidth. Enforced by sleeping between chunk reads.
+    pub max_read_bytes_per_sec: Option<u64>,
+}
+
+impl Default for ScrubConfig {
+    fn default() -> Self {
+        Self {
+            // Once a day is enough to catch bit-rot well before most
+            // content stops being requested
+            interval: Duration::from_secs(24 * 60 * 60),
+            // 16 MiB/s: a full pass over 100 GiB of seeds takes under
+            // two hours without noticeably loading the disk
+            max_read_bytes_per_sec: Some(16 * 1024 * 1024),
+        }
+    }
+}
+
+/// Events emitted by the scrubber
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScrubEvent {
+    /// A seeded file failed re-verification and is no longer announced
+    CorruptionDetected {
+        /// Root tree hash the content was registered under
+        root_hash: [u8; 32],
+        /// Path of the corrupted file
+        path: PathBuf,
+        /// Chunk indices that failed verification (empty when the file
+        /// could not be read at all)
+        corrupt_chunks: Vec<u64>,
+    },
+    /// A full scrub pass finished
+    PassCompleted {
+        /// Number of seeded files verified this pass
+        files_verified: usize,
+        /// Number of files that failed verification this pass
+        files_corrupted: usize,
+    },
+}
+
+/// Summary of one scrub pass
+#[derive(Debug, Clone, Default)]
+pub struct ScrubReport {
+    /// Files that were verified clean
+    pub files_verified: usize,
+    /// Files that failed verification and were demoted
+    pub files_corrupted: usize,
+    /// Files skipped because they were already demoted
+    pub files_skipped: usize,
+    /// Total bytes read from disk
+    pub bytes_read: u64,
+}
+
+/// A registered piece of seeded content
+#[derive(Debug)]
+struct SeedEntry {
+    path: PathBuf,
+    tree: FileTreeHash,
+    chunk_size: usize,
+    /// Whether this content should still be announced to the swarm
+    announced: bool,
+}
+
+/// Re-verifies seeded content against recorded tree hashes
+///
+/// Cloning is cheap and shares the seed registry, so one clone can be
+/// moved into the background task spawned by [`Scrubber::spawn`] while
+/// the node keeps another for registering and querying seeds.
+#[derive(Debug, Clone)]
+pub struct Scrubber {
+    config: ScrubConfig,
+    seeds: Arc<Mutex<HashMap<[u8; 32], SeedEntry>>>,
+    events: broadcast::Sender<ScrubEvent>,
+}
+
+impl Scrubber {
+    /// Create a scrubber with the given configuration
+    pub fn new(config: ScrubConfig) -> Self {
+        let (events, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+        Self {
+            config,
+            seeds: Arc::new(Mutex::new(HashMap::new())),
+            events,
+        }
+    }
+
+    /// Register seeded content for periodic verification
+    ///
+    /// `tree` must be the tree hash computed over `path` with
+    /// `chunk_size` when the content was registered for seeding. The
+    /// content starts out announced. Returns the root hash used as the
+    /// registry key.
+    pub fn add_seed<P: AsRef<Path>>(
+        &self,
+        path: P,
+        tree: FileTreeHash,
+        chunk_size: usize,
+    ) -> [u8; 32] {
+        let root_hash = tree.root;
+        let entry = SeedEntry {
+            path: path.as_ref().to_path_buf(),
+            tree,
+            chunk_size,
+            announced: true,
+        };
+        self.seeds.lock().unwrap().insert(root_hash, entry);
+        root_hash
+    }
+
+    /// Remove seeded content from the registry
+    pub fn remove_seed(&self, root_hash: &[u8; 32]) -> bool {
+        self.seeds.lock().unwrap().remove(root_hash).is_some()
+    }
+
+    /// Whether the content is still clean and should be announced
+    ///
+    /// Returns `false` for unknown content.
+    pub fn is_announced(&self, root_hash: &[u8; 32]) -> bool {
+        self.seeds
+            .lock()
+            .unwrap()
+            .get(root_hash)
+            .is_some_and(|e| e.announced)
+    }
+
+    /// Root hashes of all content that is still announced
+    pub fn announced_content(&self) -> Vec<[u8; 32]> {
+        self.seeds
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(_, e)| e.announced)
+            .map(|(hash, _)| *hash)
+            .collect()
+    }
+
+    /// Subscribe to scrub events emitted after this call
+    pub fn subscribe(&self) -> broadcast::Receiver<ScrubEvent> {
+        self.events.subscribe()
+    }
+
+    /// Run one rate-limited verification pass over all announced seeds
+    ///
+    /// Each announced file is re-read chunk by chunk and checked against
+    /// its recorded tree hash. Files that fail verification (or can no
+    /// longer be read) are demoted to not-announced and reported via
+    /// [`ScrubEvent::CorruptionDetected`]. Already-demoted content is
+    /// skipped until re-registered.
+    pub async fn scrub_once(&self) -> ScrubReport {
+        let targets: Vec<[u8; 32]> = {
+            let seeds = self.seeds.lock().unwrap();
+            seeds.keys().copied().collect()
+        };
+
+        let mut report = ScrubReport::default();
+        for root_hash in targets {
+            // Re-read the entry each iteration: it may have been removed
+            // or demoted while we were verifying earlier files
+            let (path, tree, chunk_size) = {
+                let seeds = self.seeds.lock().unwrap();
+                match seeds.get(&root_hash) {
+                    Some(entry) if entry.announced => {
+                        (entry.path.clone(), entry.tree.clone(), entry.chunk_size)
+                    }
+                    Some(_) => {
+                        report.files_skipped += 1;
+                        continue;
+                    }
+                    None => continue,
+                }
+            };
+
+            match self
+                .verify_file(&path, &tree, chunk_size, &mut report)
+                .await
+            {
+                Ok(corrupt_chunks) if corrupt_chunks.is_empty() => {
+                    debug!("Scrub verified {} clean", path.display());
+                    report.files_verified += 1;
+                }
+                Ok(corrupt_chunks) => {
+                    warn!(
+                        "Scrub found {} corrupt chunk(s) in {}, withdrawing from announce set",
+                        corrupt_chunks.len(),
+                        path.display()
+                    );
+                    self.demote(root_hash, path, corrupt_chunks);
+                    report.files_corrupted += 1;
+                }
+                Err(e) => {
+                    warn!(
+                        "Scrub could not read {}: {e}, withdrawing from announce set",
+                        path.display()
+                    );
+                    self.demote(root_hash, path, Vec::new());
+                    report.files_corrupted += 1;
+                }
+            }
+        }
+
+        info!(
+            "Scrub pass complete: {} verified, {} corrupted, {} skipped, {} bytes read",
+            report.files_verified, report.files_corrupted, report.files_skipped, report.bytes_read
+        );
+        let _ = self.events.send(ScrubEvent::PassCompleted {
+            files_verified: report.files_verified,
+            files_corrupted: report.files_corrupted,
+        });
+        report
+    }
+
+    /// Verify every chunk of one file, throttled to the configured rate
+    async fn verify_file(
+        &self,
+        path: &Path,
+        tree: &FileTreeHash,
+        chunk_size: usize,
+        report: &mut ScrubReport,
+    ) -> io::Result<Vec<u64>> {
+        let mut chunker = FileChunker::new(path, chunk_size)?;
+        if chunker.num_chunks() != tree.chunk_count() as u64 {
+            // Truncated or grown file: every recorded chunk is suspect
+            return Ok((0..tree.chunk_count() as u64).collect());
+        }
+
+        let mut corrupt = Vec::new();
+        let mut index = 0u64;
+        while let Some(chunk) = chunker.read_chunk()? {
+            report.bytes_read += chunk.len() as u64;
+            if !tree.verify_chunk(index as usize, &chunk) {
+                corrupt.push(index);
+            }
+            self.throttle(chunk.len()).await;
+            index += 1;
+        }
+        Ok(corrupt)
+    }
+
+    /// Sleep long enough that sustained reads stay under the rate cap
+    async fn throttle(&self, bytes_read: usize) {
+        if let Some(rate) = self.config.max_read_bytes_per_sec {
+            let nanos = (bytes_read as u64).saturating_mul(1_000_000_000) / rate.max(1);
+            if nanos > 0 {
+                tokio::time::sleep(Duration::from_nanos(nanos)).await;
+            }
+        }
+    }
+
+    /// Stop announcing corrupted content and broadcast the event
+    fn demote(&self, root_hash: [u8; 32], path: PathBuf, corrupt_chunks: Vec<u64>) {
+        if let Some(entry) = self.seeds.lock().unwrap().get_mut(&root_hash) {
+            entry.announced = false;
+        }
+        let _ = self.events.send(ScrubEvent::CorruptionDetected {
+            root_hash,
+            path,
+            corrupt_chunks,
+        });
+    }
+
+    /// Spawn the periodic scrub loop as a background tokio task
+    ///
+    /// Runs one pass per configured interval until aborted via the
+    /// returned handle. The first pass starts after one full interval so
+    /// node startup is not front-loaded with disk reads.
+    #[must_use]
+    pub fn spawn(&self) -> JoinHandle<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(this.config.interval);
+            tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            // interval fires immediately; skip so the first pass waits
+            tick.tick().await;
+            loop {
+                tick.tick().await;
+                this.scrub_once().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tree_hash::compute_tree_hash;
+    use std::fs;
+    use std::io::{Seek, SeekFrom, Write};
+    use tempfile::TempDir;
+
+    const CHUNK_SIZE: usize = 1024;
+
+    fn seed_file(dir: &TempDir, name: &str, len: usize) -> (PathBuf, FileTreeHash) {
+        let path = dir.path().join(name);
+        let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        fs::write(&path, &data).unwrap();
+        let tree = compute_tree_hash(&path, CHUNK_SIZE).unwrap();
+        (path, tree)
+    }
+
+    fn unthrottled() -> ScrubConfig {
+        ScrubConfig {
+            interval: Duration::from_secs(3600),
+            max_read_bytes_per_sec: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clean_seed_stays_announced() {
+        let dir = TempDir::new().unwrap();
+        let (path, tree) = seed_file(&dir, "clean.bin", 4096);
+
+        let scrubber = Scrubber::new(unthrottled());
+        let root = scrubber.add_seed(&path, tree, CHUNK_SIZE);
+
+        let report = scrubber.scrub_once().await;
+        assert_eq!(report.files_verified, 1);
+        assert_eq!(report.files_corrupted, 0);
+        assert_eq!(report.bytes_read, 4096);
+        assert!(scrubber.is_announced(&root));
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_seed_is_demoted() {
+        let dir = TempDir::new().unwrap();
+        let (path, tree) = seed_file(&dir, "rotted.bin", 4096);
+
+        let scrubber = Scrubber::new(unthrottled());
+        let root = scrubber.add_seed(&path, tree, CHUNK_SIZE);
+
+        // Flip a byte in the third chunk
+        let mut file = fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.seek(SeekFrom::Start(2 * CHUNK_SIZE as u64 + 10))
+            .unwrap();
+        file.write_all(&[0xFF]).unwrap();
+        drop(file);
+
+        let mut events = scrubber.subscribe();
+        let report = scrubber.scrub_once().await;
+        assert_eq!(report.files_corrupted, 1);
+        assert!(!scrubber.is_announced(&root));
+
+        match events.recv().await.unwrap() {
+            ScrubEvent::CorruptionDetected {
+                root_hash,
+                corrupt_chunks,
+                ..
+            } => {
+                assert_eq!(root_hash, root);
+                assert_eq!(corrupt_chunks, vec![2]);
+            }
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_file_is_demoted() {
+        let dir = TempDir::new().unwrap();
+        let (path, tree) = seed_file(&dir, "gone.bin", 2048);
+
+        let scrubber = Scrubber::new(unthrottled());
+        let root = scrubber.add_seed(&path, tree, CHUNK_SIZE);
+        fs::remove_file(&path).unwrap();
+
+        let report = scrubber.scrub_once().await;
+        assert_eq!(report.files_corrupted, 1);
+        assert!(!scrubber.is_announced(&root));
+    }
+
+    #[tokio::test]
+    async fn test_truncated_file_is_demoted() {
+        let dir = TempDir::new().unwrap();
+        let (path, tree) = seed_file(&dir, "truncated.bin", 4096);
+
+        let scrubber = Scrubber::new(unthrottled());
+        let root = scrubber.add_seed(&path, tree, CHUNK_SIZE);
+
+        let file = fs::OpenOptions::new().write(true).open(&path).unwrap();
+        file.set_len(1024).unwrap();
+        drop(file);
+
+        let report = scrubber.scrub_once().await;
+        assert_eq!(report.files_corrupted, 1);
+        assert!(!scrubber.is_announced(&root));
+    }
+
+    #[tokio::test]
+    async fn test_demoted_seed_is_skipped_on_later_passes() {
+        let dir = TempDir::new().unwrap();
+        let (path, tree) = seed_file(&dir, "rotted.bin", 2048);
+
+        let scrubber = Scrubber::new(unthrottled());
+        scrubber.add_seed(&path, tree, CHUNK_SIZE);
+        fs::write(&path, vec![0u8; 2048]).unwrap();
+
+        let first = scrubber.scrub_once().await;
+        assert_eq!(first.files_corrupted, 1);
+
+        let second = scrubber.scrub_once().await;
+        assert_eq!(second.files_corrupted, 0);
+        assert_eq!(second.files_skipped, 1);
+        assert_eq!(second.bytes_read, 0);
+    }
+
+    #[tokio::test]
+    async fn test_announced_content_excludes_demoted() {
+        let dir = TempDir::new().unwrap();
+        let (clean_path, clean_tree) = seed_file(&dir, "clean.bin", 1024);
+        let (bad_path, bad_tree) = seed_file(&dir, "bad.bin", 2048);
+
+        let scrubber = Scrubber::new(unthrottled());
+        let clean_root = scrubber.add_seed(&clean_path, clean_tree, CHUNK_SIZE);
+        scrubber.add_seed(&bad_path, bad_tree, CHUNK_SIZE);
+        fs::remove_file(&bad_path).unwrap();
+
+        scrubber.scrub_once().await;
+        assert_eq!(scrubber.announced_content(), vec![clean_root]);
+    }
+
+    #[tokio::test]
+    async fn test_remove_seed() {
+        let dir = TempDir::new().unwrap();
+        let (path, tree) = seed_file(&dir, "seed.bin", 1024);
+
+        let scrubber = Scrubber::new(unthrottled());
+        let root = scrubber.add_seed(&path, tree, CHUNK_SIZE);
+        assert!(scrubber.remove_seed(&root));
+        assert!(!scrubber.remove_seed(&root));
+        assert!(!scrubber.is_announced(&root));
+    }
+
+    #[tokio::test]
+    async fn test_pass_completed_event() {
+        let dir = TempDir::new().unwrap();
+        let (path, tree) = seed_file(&dir, "seed.bin", 1024);
+
+        let scrubber = Scrubber::new(unthrottled());
+        scrubber.add_seed(&path, tree, CHUNK_SIZE);
+
+        let mut events = scrubber.subscribe();
+        scrubber.scrub_once().await;
+        assert_eq!(
+            events.recv().await.unwrap(),
+            ScrubEvent::PassCompleted {
+                files_verified: 1,
+                files_corrupted: 0,
+            }
+        );
+    }
+}
@@ -307,6 +307,148 @@ impl IncrementalTreeHasher {
     }
 }
 
+/// Outcome of verifying a single received chunk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkVerdict {
+    /// Chunk matches its expected hash
+    Verified,
+    /// Chunk does not match and must be retransmitted
+    Corrupt,
+    /// No per-chunk hashes are known yet; the chunk hash was recorded
+    /// for root verification once all chunks have arrived
+    Deferred,
+}
+
+/// Incremental verifier for chunks arriving out of order
+///
+/// The receiving side of a transfer knows the root hash from the (signed)
+/// offer before any data arrives. This verifier validates each chunk as it
+/// is received instead of only after `finalize()`:
+///
+/// - With a chunk-hash manifest (validated against the root via
+///   [`TreeHashVerifier::set_manifest`]), every chunk is checked the moment
+///   it arrives; corrupt chunks are rejected immediately and queued for
+///   retransmission.
+/// - With only the root, chunk hashes are recorded as they arrive and the
+///   root is checked as soon as the last chunk lands — still strictly
+///   earlier than a separate full-file pass.
+pub struct TreeHashVerifier {
+    expected_root: [u8; 32],
+    /// Per-chunk expected hashes, once proven to match the root
+    manifest: Option<Vec<[u8; 32]>>,
+    /// Hashes of chunks received so far (by index)
+    received: Vec<Option<[u8; 32]>>,
+    /// Indices rejected as corrupt and awaiting retransmission
+    pending_retransmit: Vec<u64>,
+}
+
+impl TreeHashVerifier {
+    /// Create a verifier from the root hash announced in the offer
+    #[must_use]
+    pub fn new(expected_root: [u8; 32], total_chunks: u64) -> Self {
+        Self {
+            expected_root,
+            manifest: None,
+            received: vec![None; total_chunks as usize],
+            pending_retransmit: Vec::new(),
+        }
+    }
+
+    /// Install a per-chunk hash manifest, enabling immediate rejection
+    ///
+    /// The manifest is only accepted if its Merkle root equals the root
+    /// this verifier was created with — that check extends whatever trust
+    /// the root carries (e.g. a signature on the offer) to every leaf.
+    /// Chunks that arrived before the manifest are re-checked against it;
+    /// any mismatch is queued for retransmission.
+    ///
+    /// Returns `false` (and installs nothing) if the manifest does not
+    /// match the expected root or chunk count.
+    pub fn set_manifest(&mut self, chunks: Vec<[u8; 32]>) -> bool {
+        if chunks.len() != self.received.len() || compute_merkle_root(&chunks) != self.expected_root
+        {
+            return false;
+        }
+
+        for (index, slot) in self.received.iter_mut().enumerate() {
+            if let Some(hash) = slot
+                && *hash != chunks[index]
+            {
+                *slot = None;
+                self.pending_retransmit.push(index as u64);
+            }
+        }
+
+        self.manifest = Some(chunks);
+        true
+    }
+
+    /// Whether a per-chunk manifest has been installed
+    #[must_use]
+    pub fn has_manifest(&self) -> bool {
+        self.manifest.is_some()
+    }
+
+    /// Verify a chunk as it arrives
+    ///
+    /// With a manifest the chunk is checked immediately; a corrupt chunk is
+    /// not recorded, so a later (correct) retransmission of the same index
+    /// can still verify. Without a manifest the hash is recorded and the
+    /// verdict deferred to [`TreeHashVerifier::root_valid`].
+    pub fn verify_chunk(&mut self, chunk_index: u64, chunk_data: &[u8]) -> ChunkVerdict {
+        let Some(slot) = self.received.get_mut(chunk_index as usize) else {
+            return ChunkVerdict::Corrupt;
+        };
+
+        let hash = *blake3::hash(chunk_data).as_bytes();
+
+        match &self.manifest {
+            Some(manifest) => {
+                if manifest[chunk_index as usize] == hash {
+                    *slot = Some(hash);
+                    ChunkVerdict::Verified
+                } else {
+                    self.pending_retransmit.push(chunk_index);
+                    ChunkVerdict::Corrupt
+                }
+            }
+            None => {
+                *slot = Some(hash);
+                ChunkVerdict::Deferred
+            }
+        }
+    }
+
+    /// Drain the indices rejected as corrupt since the last call
+    ///
+    /// The caller is expected to re-request exactly these chunks.
+    pub fn take_retransmit_requests(&mut self) -> Vec<u64> {
+        std::mem::take(&mut self.pending_retransmit)
+    }
+
+    /// Number of chunks received (and, with a manifest, verified)
+    #[must_use]
+    pub fn received_count(&self) -> usize {
+        self.received.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Whether every chunk has been received
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.received.iter().all(Option::is_some)
+    }
+
+    /// Check the assembled tree against the expected root
+    ///
+    /// Returns `None` while chunks are still outstanding, `Some(true)` once
+    /// all chunks are present and their Merkle root matches the offer.
+    #[must_use]
+    pub fn root_valid(&self) -> Option<bool> {
+        let leaves: Vec<[u8; 32]> = self.received.iter().copied().collect::<Option<Vec<_>>>()?;
+        Some(compute_merkle_root(&leaves) == self.expected_root)
+    }
+}
+
 /// Compute tree hash from in-memory data
 ///
 /// # Example
@@ -464,6 +606,85 @@ mod tests {
         assert!(tree_with_hash.verify_chunk(0, &chunk_data));
     }
 
+    fn verifier_fixture() -> (TreeHashVerifier, FileTreeHash, Vec<Vec<u8>>) {
+        let chunks: Vec<Vec<u8>> = vec![vec![0xAA; 1024], vec![0xBB; 1024], vec![0xCC; 512]];
+        let data: Vec<u8> = chunks.concat();
+        let tree = compute_tree_hash_from_data(&data, 1024);
+        let verifier = TreeHashVerifier::new(tree.root, tree.chunk_count() as u64);
+        (verifier, tree, chunks)
+    }
+
+    #[test]
+    fn test_verifier_with_manifest_rejects_corrupt_immediately() {
+        let (mut verifier, tree, chunks) = verifier_fixture();
+        assert!(verifier.set_manifest(tree.chunks.clone()));
+
+        assert_eq!(verifier.verify_chunk(0, &chunks[0]), ChunkVerdict::Verified);
+        assert_eq!(
+            verifier.verify_chunk(1, &[0xEE; 1024]),
+            ChunkVerdict::Corrupt
+        );
+        assert_eq!(verifier.take_retransmit_requests(), vec![1]);
+
+        // The retransmitted correct copy still verifies
+        assert_eq!(verifier.verify_chunk(1, &chunks[1]), ChunkVerdict::Verified);
+        assert_eq!(verifier.verify_chunk(2, &chunks[2]), ChunkVerdict::Verified);
+        assert_eq!(verifier.root_valid(), Some(true));
+        assert!(verifier.take_retransmit_requests().is_empty());
+    }
+
+    #[test]
+    fn test_verifier_rejects_bad_manifest() {
+        let (mut verifier, tree, _) = verifier_fixture();
+
+        let mut tampered = tree.chunks.clone();
+        tampered[0] = [0u8; 32];
+        assert!(!verifier.set_manifest(tampered));
+        assert!(!verifier.has_manifest());
+
+        // Wrong chunk count is also rejected
+        assert!(!verifier.set_manifest(tree.chunks[..2].to_vec()));
+    }
+
+    #[test]
+    fn test_verifier_root_only_defers_then_detects() {
+        let (mut verifier, _, chunks) = verifier_fixture();
+
+        assert_eq!(verifier.verify_chunk(0, &chunks[0]), ChunkVerdict::Deferred);
+        assert_eq!(verifier.root_valid(), None);
+
+        assert_eq!(
+            verifier.verify_chunk(1, &[0xEE; 1024]),
+            ChunkVerdict::Deferred
+        );
+        assert_eq!(verifier.verify_chunk(2, &chunks[2]), ChunkVerdict::Deferred);
+
+        // Root mismatch is detected the moment the last chunk lands
+        assert_eq!(verifier.root_valid(), Some(false));
+    }
+
+    #[test]
+    fn test_verifier_late_manifest_rechecks_received() {
+        let (mut verifier, tree, chunks) = verifier_fixture();
+
+        assert_eq!(verifier.verify_chunk(0, &chunks[0]), ChunkVerdict::Deferred);
+        assert_eq!(
+            verifier.verify_chunk(1, &[0xEE; 1024]),
+            ChunkVerdict::Deferred
+        );
+
+        // Manifest arrives after the corrupt chunk: it is flagged retroactively
+        assert!(verifier.set_manifest(tree.chunks.clone()));
+        assert_eq!(verifier.take_retransmit_requests(), vec![1]);
+        assert_eq!(verifier.received_count(), 1);
+    }
+
+    #[test]
+    fn test_verifier_out_of_range_index() {
+        let (mut verifier, _, chunks) = verifier_fixture();
+        assert_eq!(verifier.verify_chunk(99, &chunks[0]), ChunkVerdict::Corrupt);
+    }
+
     #[test]
     fn test_incremental_hasher_buffering() {
         let mut hasher = IncrementalTreeHasher::new(1024);
@@ -1,9 +1,21 @@
 //! DNS-over-HTTPS tunneling for traffic obfuscation.
 //!
 //! Encodes WRAITH traffic as DNS queries and responses,
-//! allowing it to blend with legitimate DoH traffic.
+//! allowing it to blend with legitimate DoH traffic. Control frames can be
+//! shaped as complete RFC 8484 HTTP exchanges - POST requests with
+//! `application/dns-message` bodies and matching 200 responses - for
+//! low-rate signaling when UDP is blocked.
 
 use base64::{Engine, engine::general_purpose::URL_SAFE_NO_PAD};
+use std::time::Duration;
+
+use crate::transport::{ObfuscationTransport, TransportError};
+
+/// Maximum payload carried in one DoH-shaped message
+///
+/// Matches the common EDNS buffer size so message lengths stay plausible;
+/// the DoH path is meant for handshake and control frames, not bulk data.
+pub const MAX_DOH_PAYLOAD: usize = 1232;
 
 /// DNS-over-HTTPS tunnel
 ///
@@ -209,6 +221,115 @@ impl DohTunnel {
         Ok(response[offset..offset + payload_len].to_vec())
     }
 
+    /// Encode a control frame as a DoH POST request (RFC 8484)
+    ///
+    /// Produces a complete HTTP/1.1 request with the headers a real DoH
+    /// client sends - `Content-Type: application/dns-message` and an exact
+    /// `Content-Length` - carrying the payload inside a DNS query message.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DohError::PayloadTooLarge` if the payload exceeds
+    /// [`MAX_DOH_PAYLOAD`].
+    pub fn encode_post_request(&self, payload: &[u8]) -> Result<Vec<u8>, DohError> {
+        if payload.len() > MAX_DOH_PAYLOAD {
+            return Err(DohError::PayloadTooLarge);
+        }
+
+        let body = self.create_dns_query("wraith.local", payload);
+        let (host, path) = self.host_and_path();
+
+        let mut request = format!(
+            "POST {path} HTTP/1.1\r\n\
+             Host: {host}\r\n\
+             Content-Type: application/dns-message\r\n\
+             Accept: application/dns-message\r\n\
+             Content-Length: {}\r\n\
+             Connection: keep-alive\r\n\
+             \r\n",
+            body.len()
+        )
+        .into_bytes();
+        request.extend_from_slice(&body);
+
+        Ok(request)
+    }
+
+    /// Encode a control frame as a DoH 200 response (RFC 8484)
+    ///
+    /// # Errors
+    ///
+    /// Returns `DohError::PayloadTooLarge` if the payload exceeds
+    /// [`MAX_DOH_PAYLOAD`].
+    pub fn encode_post_response(&self, payload: &[u8]) -> Result<Vec<u8>, DohError> {
+        if payload.len() > MAX_DOH_PAYLOAD {
+            return Err(DohError::PayloadTooLarge);
+        }
+
+        let body = self.create_dns_query("wraith.local", payload);
+
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\n\
+             Content-Type: application/dns-message\r\n\
+             Content-Length: {}\r\n\
+             Cache-Control: max-age=300\r\n\
+             Connection: keep-alive\r\n\
+             \r\n",
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(&body);
+
+        Ok(response)
+    }
+
+    /// Decode a DoH-shaped POST request or 200 response back to its payload
+    ///
+    /// # Errors
+    ///
+    /// Returns `DohError::InvalidResponse` if the HTTP framing or DNS body
+    /// is malformed.
+    pub fn decode_post(&self, message: &[u8]) -> Result<Vec<u8>, DohError> {
+        // Find the end of the HTTP headers
+        let header_end = message
+            .windows(4)
+            .position(|w| w == b"\r\n\r\n")
+            .ok_or(DohError::InvalidResponse)?;
+
+        let headers =
+            std::str::from_utf8(&message[..header_end]).map_err(|_| DohError::InvalidResponse)?;
+        if !headers.starts_with("POST ") && !headers.starts_with("HTTP/1.1 200") {
+            return Err(DohError::InvalidResponse);
+        }
+
+        self.parse_dns_response(&message[header_end + 4..])
+    }
+
+    /// Pick a plausible resolver response delay
+    ///
+    /// Public resolvers typically answer within tens of milliseconds; a
+    /// reply generated instantly from local state would stand out. Callers
+    /// should sleep this long before sending an encoded response.
+    #[must_use]
+    pub fn response_delay(&self) -> Duration {
+        use rand::Rng;
+        Duration::from_millis(rand::thread_rng().gen_range(8..=60))
+    }
+
+    /// Split the resolver URL into Host header value and request path
+    fn host_and_path(&self) -> (&str, &str) {
+        let without_scheme = self
+            .resolver_url
+            .strip_prefix("https://")
+            .or_else(|| self.resolver_url.strip_prefix("http://"))
+            .unwrap_or(&self.resolver_url);
+
+        match without_scheme.find('/') {
+            Some(idx) => (&without_scheme[..idx], &without_scheme[idx..]),
+            None => (without_scheme, "/dns-query"),
+        }
+    }
+
     /// Get the resolver URL
     #[must_use]
     pub fn resolver_url(&self) -> &str {
@@ -227,6 +348,34 @@ impl Default for DohTunnel {
     }
 }
 
+impl ObfuscationTransport for DohTunnel {
+    fn name(&self) -> &'static str {
+        "doh"
+    }
+
+    fn wrap(&mut self, payload: &[u8]) -> Result<Vec<u8>, TransportError> {
+        self.encode_post_request(payload)
+            .map_err(|e| TransportError::Malformed(e.to_string()))
+    }
+
+    fn unwrap(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, TransportError> {
+        self.decode_post(data)
+            .map(Some)
+            .map_err(|e| TransportError::Malformed(e.to_string()))
+    }
+
+    fn max_overhead(&self) -> usize {
+        // HTTP request line and headers plus DNS message framing; payloads
+        // above MAX_DOH_PAYLOAD are rejected by `wrap` regardless
+        512
+    }
+
+    fn next_send_delay(&mut self) -> Duration {
+        // Pace like a resolver answering queries
+        self.response_delay()
+    }
+}
+
 /// DoH error types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DohError {
@@ -234,6 +383,8 @@ pub enum DohError {
     DecodeFailed,
     /// Invalid DNS response
     InvalidResponse,
+    /// Payload exceeds the DoH message size budget
+    PayloadTooLarge,
 }
 
 impl std::fmt::Display for DohError {
@@ -241,6 +392,7 @@ impl std::fmt::Display for DohError {
         match self {
             Self::DecodeFailed => write!(f, "Failed to decode base64 response"),
             Self::InvalidResponse => write!(f, "Invalid DNS response"),
+            Self::PayloadTooLarge => write!(f, "Payload too large for DoH message"),
         }
     }
 }
@@ -464,6 +616,107 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_doh_post_request_roundtrip() {
+        let tunnel = DohTunnel::new("https://dns.example.com/dns-query".to_string());
+        let payload = b"handshake frame";
+
+        let request = tunnel.encode_post_request(payload).unwrap();
+        let decoded = tunnel.decode_post(&request).unwrap();
+
+        assert_eq!(decoded, payload);
+    }
+
+    #[test]
+    fn test_doh_post_response_roundtrip() {
+        let tunnel = DohTunnel::new("https://dns.example.com/dns-query".to_string());
+        let payload = b"ack frame";
+
+        let response = tunnel.encode_post_response(payload).unwrap();
+        let decoded = tunnel.decode_post(&response).unwrap();
+
+        assert_eq!(decoded, payload);
+    }
+
+    #[test]
+    fn test_doh_post_request_headers() {
+        let tunnel = DohTunnel::new("https://dns.example.com/dns-query".to_string());
+        let request = tunnel.encode_post_request(b"data").unwrap();
+
+        let header_end = request.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+        let headers = std::str::from_utf8(&request[..header_end]).unwrap();
+        let body_len = request.len() - header_end - 4;
+
+        assert!(headers.starts_with("POST /dns-query HTTP/1.1\r\n"));
+        assert!(headers.contains("Host: dns.example.com\r\n"));
+        assert!(headers.contains("Content-Type: application/dns-message\r\n"));
+        // Content-Length matches the actual body exactly
+        assert!(headers.contains(&format!("Content-Length: {body_len}\r\n")));
+    }
+
+    #[test]
+    fn test_doh_post_response_headers() {
+        let tunnel = DohTunnel::default();
+        let response = tunnel.encode_post_response(b"data").unwrap();
+
+        let text = String::from_utf8_lossy(&response);
+        assert!(text.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(text.contains("Content-Type: application/dns-message\r\n"));
+    }
+
+    #[test]
+    fn test_doh_post_payload_too_large() {
+        let tunnel = DohTunnel::default();
+        let oversized = vec![0u8; MAX_DOH_PAYLOAD + 1];
+
+        assert!(matches!(
+            tunnel.encode_post_request(&oversized),
+            Err(DohError::PayloadTooLarge)
+        ));
+        assert!(matches!(
+            tunnel.encode_post_response(&oversized),
+            Err(DohError::PayloadTooLarge)
+        ));
+    }
+
+    #[test]
+    fn test_doh_decode_post_rejects_non_doh() {
+        let tunnel = DohTunnel::default();
+
+        // No header terminator
+        assert!(matches!(
+            tunnel.decode_post(b"POST /dns-query HTTP/1.1\r\n"),
+            Err(DohError::InvalidResponse)
+        ));
+
+        // Wrong method/status line
+        assert!(matches!(
+            tunnel.decode_post(b"GET / HTTP/1.1\r\n\r\n"),
+            Err(DohError::InvalidResponse)
+        ));
+    }
+
+    #[test]
+    fn test_doh_host_and_path_without_path() {
+        let tunnel = DohTunnel::new("https://1.1.1.1".to_string());
+        let request = tunnel.encode_post_request(b"x").unwrap();
+        let text = String::from_utf8_lossy(&request);
+
+        assert!(text.starts_with("POST /dns-query HTTP/1.1\r\n"));
+        assert!(text.contains("Host: 1.1.1.1\r\n"));
+    }
+
+    #[test]
+    fn test_doh_response_delay_plausible() {
+        let tunnel = DohTunnel::default();
+
+        for _ in 0..50 {
+            let delay = tunnel.response_delay();
+            assert!(delay >= Duration::from_millis(8));
+            assert!(delay <= Duration::from_millis(60));
+        }
+    }
+
     #[test]
     fn test_doh_special_characters() {
         let tunnel = DohTunnel::new("https://dns.example.com/dns-query".to_string());
@@ -17,12 +17,14 @@ pub mod doh_tunnel;
 pub mod padding;
 pub mod timing;
 pub mod tls_mimicry;
+pub mod transport;
 pub mod websocket_mimicry;
 
 pub use adaptive::{MimicryMode, ObfuscationProfile, ThreatLevel};
 pub use cover::{CoverTrafficGenerator, TrafficDistribution};
-pub use doh_tunnel::{DohError, DohTunnel};
+pub use doh_tunnel::{DohError, DohTunnel, MAX_DOH_PAYLOAD};
 pub use padding::{PaddingEngine, PaddingMode};
 pub use timing::{TimingMode, TimingObfuscator, TrafficShaper};
 pub use tls_mimicry::{TlsError, TlsRecordWrapper, TlsSessionMimicry};
-pub use websocket_mimicry::{WebSocketFrameWrapper, WsError};
+pub use transport::{ObfuscationTransport, TransportError, TransportStack};
+pub use websocket_mimicry::{WebSocketFrameWrapper, WebSocketMimicry, WsError, WsMessage};
@@ -3,9 +3,11 @@
 //! This module provides a comprehensive padding engine with multiple modes
 //! to obscure message sizes and defeat traffic analysis attacks.
 
-use rand::Rng;
+use rand::{Rng, SeedableRng};
 use rand_distr::{Distribution, Geometric};
 
+use crate::transport::{ObfuscationTransport, TransportError};
+
 /// Packet padding modes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PaddingMode {
@@ -40,7 +42,7 @@ const SIZE_CLASSES: &[usize] = &[128, 512, 1024, 4096, 8192, 16384];
 /// ```
 pub struct PaddingEngine {
     mode: PaddingMode,
-    rng: rand::rngs::ThreadRng,
+    rng: rand::rngs::StdRng,
 }
 
 impl PaddingEngine {
@@ -57,7 +59,7 @@ impl PaddingEngine {
     pub fn new(mode: PaddingMode) -> Self {
         Self {
             mode,
-            rng: rand::thread_rng(),
+            rng: rand::rngs::StdRng::from_entropy(),
         }
     }
 
@@ -219,6 +221,59 @@ impl Default for PaddingEngine {
     }
 }
 
+/// Reference payload size used for MTU overhead reporting
+const MTU_PAYLOAD: usize = 1500;
+
+impl ObfuscationTransport for PaddingEngine {
+    fn name(&self) -> &'static str {
+        "padding"
+    }
+
+    /// Wraps with a 2-byte length prefix so the padded blob is
+    /// self-delimiting, then pads to the mode's target size.
+    fn wrap(&mut self, payload: &[u8]) -> Result<Vec<u8>, TransportError> {
+        let framed_len = payload.len() + 2;
+        let Ok(len) = u16::try_from(payload.len()) else {
+            return Err(TransportError::Malformed(
+                "payload exceeds 65535 bytes".to_string(),
+            ));
+        };
+
+        let mut buffer = Vec::with_capacity(framed_len);
+        buffer.extend_from_slice(&len.to_be_bytes());
+        buffer.extend_from_slice(payload);
+
+        let target = self.padded_size(framed_len);
+        self.pad(&mut buffer, target);
+        Ok(buffer)
+    }
+
+    fn unwrap(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, TransportError> {
+        if data.len() < 2 {
+            return Err(TransportError::Malformed(
+                "missing length prefix".to_string(),
+            ));
+        }
+        let len = u16::from_be_bytes([data[0], data[1]]) as usize;
+        if data.len() < 2 + len {
+            return Err(TransportError::Malformed(
+                "length prefix exceeds buffer".to_string(),
+            ));
+        }
+        Ok(Some(self.unpad(&data[2..], len).to_vec()))
+    }
+
+    fn max_overhead(&self) -> usize {
+        // Length prefix plus worst-case padding for an MTU-sized payload
+        let worst_padded = match self.mode() {
+            // Statistical padding is unbounded by distribution but clamped
+            PaddingMode::Statistical => *SIZE_CLASSES.last().unwrap(),
+            _ => self.padded_size_const(MTU_PAYLOAD + 2),
+        };
+        worst_padded.saturating_sub(MTU_PAYLOAD)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
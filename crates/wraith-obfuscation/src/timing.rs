@@ -3,9 +3,12 @@
 //! Provides various timing strategies to add jitter and prevent
 //! timing correlation attacks.
 
+use rand::SeedableRng;
 use rand_distr::{Distribution, Exp, Normal};
 use std::time::{Duration, Instant};
 
+use crate::transport::{ObfuscationTransport, TransportError};
+
 /// Timing obfuscation modes
 #[derive(Debug, Clone, Copy)]
 pub enum TimingMode {
@@ -54,7 +57,7 @@ pub enum TimingMode {
 /// ```
 pub struct TimingObfuscator {
     mode: TimingMode,
-    rng: rand::rngs::ThreadRng,
+    rng: rand::rngs::StdRng,
 }
 
 impl TimingObfuscator {
@@ -72,7 +75,7 @@ impl TimingObfuscator {
     pub fn new(mode: TimingMode) -> Self {
         Self {
             mode,
-            rng: rand::thread_rng(),
+            rng: rand::rngs::StdRng::from_entropy(),
         }
     }
 
@@ -175,6 +178,29 @@ impl Default for TimingObfuscator {
     }
 }
 
+impl ObfuscationTransport for TimingObfuscator {
+    fn name(&self) -> &'static str {
+        "timing"
+    }
+
+    /// Timing obfuscation does not alter payloads; it only delays sends
+    fn wrap(&mut self, payload: &[u8]) -> Result<Vec<u8>, TransportError> {
+        Ok(payload.to_vec())
+    }
+
+    fn unwrap(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, TransportError> {
+        Ok(Some(data.to_vec()))
+    }
+
+    fn max_overhead(&self) -> usize {
+        0
+    }
+
+    fn next_send_delay(&mut self) -> Duration {
+        self.next_delay()
+    }
+}
+
 /// Traffic shaping to mimic specific patterns
 ///
 /// Enforces a target packet rate by introducing delays between sends.
@@ -3,8 +3,12 @@
 //! Wraps WRAITH packets to look like TLS 1.3 application data,
 //! allowing traffic to pass DPI inspection and blend with HTTPS traffic.
 
+use std::collections::VecDeque;
+
 use rand::Rng;
 
+use crate::transport::{ObfuscationTransport, TransportError};
+
 /// TLS content type: Application Data
 const TLS_CONTENT_TYPE_APPLICATION_DATA: u8 = 23;
 /// TLS content type: Handshake
@@ -147,6 +151,8 @@ impl Default for TlsRecordWrapper {
 pub struct TlsSessionMimicry {
     handshake_complete: bool,
     wrapper: TlsRecordWrapper,
+    /// Generated handshake messages not yet handed to the transport layer
+    pending_handshake: VecDeque<Vec<u8>>,
 }
 
 impl TlsSessionMimicry {
@@ -156,6 +162,7 @@ impl TlsSessionMimicry {
         Self {
             handshake_complete: false,
             wrapper: TlsRecordWrapper::new(),
+            pending_handshake: VecDeque::new(),
         }
     }
 
@@ -341,6 +348,7 @@ impl TlsSessionMimicry {
     /// Reset the session
     pub fn reset(&mut self) {
         self.handshake_complete = false;
+        self.pending_handshake.clear();
         self.wrapper.reset();
     }
 }
@@ -351,6 +359,62 @@ impl Default for TlsSessionMimicry {
     }
 }
 
+impl ObfuscationTransport for TlsSessionMimicry {
+    fn name(&self) -> &'static str {
+        "tls"
+    }
+
+    fn requires_handshake(&self) -> bool {
+        true
+    }
+
+    fn is_established(&self) -> bool {
+        self.handshake_complete
+    }
+
+    fn next_handshake_message(&mut self) -> Option<Vec<u8>> {
+        if !self.handshake_complete && self.pending_handshake.is_empty() {
+            let messages = self.generate_handshake();
+            self.pending_handshake.extend(messages);
+        }
+        self.pending_handshake.pop_front()
+    }
+
+    fn process_handshake_message(
+        &mut self,
+        message: &[u8],
+    ) -> Result<Option<Vec<u8>>, TransportError> {
+        if message.first() != Some(&TLS_CONTENT_TYPE_HANDSHAKE) {
+            return Err(TransportError::HandshakeFailed(
+                "expected TLS handshake record".to_string(),
+            ));
+        }
+        // The handshake is theater; any handshake record completes it
+        self.handshake_complete = true;
+        Ok(None)
+    }
+
+    fn wrap(&mut self, payload: &[u8]) -> Result<Vec<u8>, TransportError> {
+        self.wrap_application_data(payload).map_err(|e| match e {
+            TlsError::HandshakeNotComplete => TransportError::HandshakeIncomplete,
+            other => TransportError::Malformed(other.to_string()),
+        })
+    }
+
+    fn unwrap(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, TransportError> {
+        self.unwrap_application_data(data)
+            .map(Some)
+            .map_err(|e| match e {
+                TlsError::HandshakeNotComplete => TransportError::HandshakeIncomplete,
+                other => TransportError::Malformed(other.to_string()),
+            })
+    }
+
+    fn max_overhead(&self) -> usize {
+        5 // TLS record header
+    }
+}
+
 /// TLS error types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TlsError {
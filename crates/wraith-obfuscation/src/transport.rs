@@ -0,0 +1,403 @@
+//! Pluggable obfuscation transport abstraction.
+//!
+//! The [`ObfuscationTransport`] trait is the extension point for wire-level
+//! obfuscation. The built-in TLS, WebSocket, and DoH mimicry layers, the
+//! padding engine, and the timing obfuscator all implement it, and third
+//! parties can ship custom transports (e.g. obfs4-style) without patching
+//! the core pipeline. [`TransportStack`] composes several layers into one.
+
+use std::time::Duration;
+
+/// Errors surfaced by pluggable transports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransportError {
+    /// The transport requires a handshake that has not completed yet
+    HandshakeIncomplete,
+    /// A handshake message was rejected
+    HandshakeFailed(String),
+    /// Wire data could not be decoded by this transport
+    Malformed(String),
+}
+
+impl std::fmt::Display for TransportError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HandshakeIncomplete => write!(f, "Transport handshake not complete"),
+            Self::HandshakeFailed(reason) => write!(f, "Transport handshake failed: {reason}"),
+            Self::Malformed(reason) => write!(f, "Malformed transport data: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for TransportError {}
+
+/// A wire-level obfuscation layer that WRAITH packets pass through
+///
+/// Implementations transform already-encrypted packets into a cover shape
+/// (a TLS record, a WebSocket frame, a DoH exchange, a padded blob) and
+/// back. Transports are layered: `wrap` is applied innermost-first on send
+/// and `unwrap` in reverse on receive.
+///
+/// # Examples
+///
+/// ```
+/// use wraith_obfuscation::transport::ObfuscationTransport;
+/// use wraith_obfuscation::tls_mimicry::TlsSessionMimicry;
+///
+/// let mut transport = TlsSessionMimicry::new();
+/// while let Some(_msg) = transport.next_handshake_message() {
+///     // deliver handshake messages to the peer
+/// }
+/// let wrapped = transport.wrap(b"packet").unwrap();
+/// assert_eq!(transport.unwrap(&wrapped).unwrap(), Some(b"packet".to_vec()));
+/// ```
+pub trait ObfuscationTransport: Send {
+    /// Short identifier for logging and configuration (e.g. "tls", "doh")
+    fn name(&self) -> &'static str;
+
+    /// Whether this transport needs a handshake before carrying data
+    fn requires_handshake(&self) -> bool {
+        false
+    }
+
+    /// Whether the transport is ready to carry application data
+    fn is_established(&self) -> bool {
+        true
+    }
+
+    /// Next outbound handshake message, or `None` when nothing is pending
+    fn next_handshake_message(&mut self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Process an inbound handshake message, optionally producing a reply
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransportError::HandshakeFailed` if the message is rejected.
+    fn process_handshake_message(
+        &mut self,
+        _message: &[u8],
+    ) -> Result<Option<Vec<u8>>, TransportError> {
+        Ok(None)
+    }
+
+    /// Wrap an already-encrypted packet for the wire
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransportError::HandshakeIncomplete` before the handshake
+    /// finishes, or `TransportError::Malformed` if the payload cannot be
+    /// carried by this transport.
+    fn wrap(&mut self, payload: &[u8]) -> Result<Vec<u8>, TransportError>;
+
+    /// Unwrap wire data back into a packet
+    ///
+    /// Returns `Ok(None)` when the data was consumed by the transport
+    /// itself (e.g. a WebSocket ping) and carries no application payload.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransportError::Malformed` if the data cannot be decoded.
+    fn unwrap(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, TransportError>;
+
+    /// Upper bound on bytes added by `wrap` for a payload of up to one MTU
+    ///
+    /// Used by the pipeline to budget inner frame sizes so wrapped packets
+    /// stay below the path MTU.
+    fn max_overhead(&self) -> usize;
+
+    /// Recommended delay before the next send, for timing-aware transports
+    fn next_send_delay(&mut self) -> Duration {
+        Duration::ZERO
+    }
+}
+
+/// An ordered stack of obfuscation transports applied as one
+///
+/// Layer 0 is innermost: `wrap` applies layers in push order, `unwrap` in
+/// reverse. The stack itself implements [`ObfuscationTransport`], so stacks
+/// can be nested or passed anywhere a single transport is expected.
+///
+/// # Examples
+///
+/// ```
+/// use wraith_obfuscation::padding::{PaddingEngine, PaddingMode};
+/// use wraith_obfuscation::tls_mimicry::TlsSessionMimicry;
+/// use wraith_obfuscation::transport::{ObfuscationTransport, TransportStack};
+///
+/// let mut stack = TransportStack::new();
+/// stack.push(Box::new(PaddingEngine::new(PaddingMode::SizeClasses)));
+/// stack.push(Box::new(TlsSessionMimicry::new()));
+/// while let Some(_msg) = stack.next_handshake_message() {}
+///
+/// let wrapped = stack.wrap(b"packet").unwrap();
+/// assert_eq!(stack.unwrap(&wrapped).unwrap(), Some(b"packet".to_vec()));
+/// ```
+#[derive(Default)]
+pub struct TransportStack {
+    layers: Vec<Box<dyn ObfuscationTransport>>,
+}
+
+impl TransportStack {
+    /// Create an empty transport stack
+    #[must_use]
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Add a layer on top of the stack (applied last on send)
+    pub fn push(&mut self, layer: Box<dyn ObfuscationTransport>) {
+        self.layers.push(layer);
+    }
+
+    /// Number of layers in the stack
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether the stack has no layers
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+}
+
+impl ObfuscationTransport for TransportStack {
+    fn name(&self) -> &'static str {
+        "stack"
+    }
+
+    fn requires_handshake(&self) -> bool {
+        self.layers.iter().any(|l| l.requires_handshake())
+    }
+
+    fn is_established(&self) -> bool {
+        self.layers.iter().all(|l| l.is_established())
+    }
+
+    fn next_handshake_message(&mut self) -> Option<Vec<u8>> {
+        // Layers with a finished handshake simply return None
+        self.layers
+            .iter_mut()
+            .find_map(|l| l.next_handshake_message())
+    }
+
+    fn process_handshake_message(
+        &mut self,
+        message: &[u8],
+    ) -> Result<Option<Vec<u8>>, TransportError> {
+        match self.layers.iter_mut().find(|l| !l.is_established()) {
+            Some(layer) => layer.process_handshake_message(message),
+            None => Ok(None),
+        }
+    }
+
+    fn wrap(&mut self, payload: &[u8]) -> Result<Vec<u8>, TransportError> {
+        let mut data = payload.to_vec();
+        for layer in &mut self.layers {
+            data = layer.wrap(&data)?;
+        }
+        Ok(data)
+    }
+
+    fn unwrap(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, TransportError> {
+        let mut current = data.to_vec();
+        for layer in self.layers.iter_mut().rev() {
+            match layer.unwrap(&current)? {
+                Some(inner) => current = inner,
+                None => return Ok(None),
+            }
+        }
+        Ok(Some(current))
+    }
+
+    fn max_overhead(&self) -> usize {
+        self.layers.iter().map(|l| l.max_overhead()).sum()
+    }
+
+    fn next_send_delay(&mut self) -> Duration {
+        self.layers
+            .iter_mut()
+            .map(|l| l.next_send_delay())
+            .max()
+            .unwrap_or(Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::padding::{PaddingEngine, PaddingMode};
+    use crate::timing::{TimingMode, TimingObfuscator};
+    use crate::tls_mimicry::TlsSessionMimicry;
+    use crate::websocket_mimicry::WebSocketMimicry;
+
+    /// Minimal third-party transport: XORs every byte with a key
+    struct XorTransport {
+        key: u8,
+    }
+
+    impl ObfuscationTransport for XorTransport {
+        fn name(&self) -> &'static str {
+            "xor"
+        }
+
+        fn wrap(&mut self, payload: &[u8]) -> Result<Vec<u8>, TransportError> {
+            Ok(payload.iter().map(|b| b ^ self.key).collect())
+        }
+
+        fn unwrap(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, TransportError> {
+            Ok(Some(data.iter().map(|b| b ^ self.key).collect()))
+        }
+
+        fn max_overhead(&self) -> usize {
+            0
+        }
+    }
+
+    #[test]
+    fn test_custom_transport_roundtrip() {
+        let mut transport = XorTransport { key: 0x5a };
+        let wrapped = transport.wrap(b"secret").unwrap();
+        assert_ne!(wrapped, b"secret");
+        assert_eq!(
+            transport.unwrap(&wrapped).unwrap(),
+            Some(b"secret".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_stack_wrap_unwrap_order() {
+        let mut stack = TransportStack::new();
+        stack.push(Box::new(XorTransport { key: 0x11 }));
+        stack.push(Box::new(TlsSessionMimicry::new()));
+        while stack.next_handshake_message().is_some() {}
+
+        let wrapped = stack.wrap(b"payload").unwrap();
+        // Outermost layer is TLS: application data content type
+        assert_eq!(wrapped[0], 23);
+        assert_eq!(stack.unwrap(&wrapped).unwrap(), Some(b"payload".to_vec()));
+    }
+
+    #[test]
+    fn test_stack_overhead_sums() {
+        let mut stack = TransportStack::new();
+        stack.push(Box::new(XorTransport { key: 1 }));
+        stack.push(Box::new(TlsSessionMimicry::new()));
+        assert_eq!(stack.max_overhead(), 5); // 0 + 5-byte TLS record header
+    }
+
+    #[test]
+    fn test_stack_handshake_state() {
+        let mut stack = TransportStack::new();
+        stack.push(Box::new(XorTransport { key: 1 }));
+        assert!(!stack.requires_handshake());
+        assert!(stack.is_established());
+
+        stack.push(Box::new(TlsSessionMimicry::new()));
+        assert!(stack.requires_handshake());
+        assert!(!stack.is_established());
+
+        let mut messages = 0;
+        while stack.next_handshake_message().is_some() {
+            messages += 1;
+        }
+        assert_eq!(messages, 3);
+        assert!(stack.is_established());
+    }
+
+    #[test]
+    fn test_empty_stack_is_identity() {
+        let mut stack = TransportStack::new();
+        assert!(stack.is_empty());
+        assert_eq!(stack.wrap(b"data").unwrap(), b"data");
+        assert_eq!(stack.unwrap(b"data").unwrap(), Some(b"data".to_vec()));
+        assert_eq!(stack.max_overhead(), 0);
+    }
+
+    #[test]
+    fn test_tls_through_trait() {
+        let mut transport: Box<dyn ObfuscationTransport> = Box::new(TlsSessionMimicry::new());
+        assert_eq!(transport.name(), "tls");
+        assert!(transport.requires_handshake());
+        assert!(matches!(
+            transport.wrap(b"early"),
+            Err(TransportError::HandshakeIncomplete)
+        ));
+
+        while transport.next_handshake_message().is_some() {}
+        assert!(transport.is_established());
+
+        let wrapped = transport.wrap(b"data").unwrap();
+        assert_eq!(transport.unwrap(&wrapped).unwrap(), Some(b"data".to_vec()));
+    }
+
+    #[test]
+    fn test_websocket_handshake_through_trait() {
+        let mut client: Box<dyn ObfuscationTransport> =
+            Box::new(WebSocketMimicry::client("example.com", "/chat"));
+        let mut server: Box<dyn ObfuscationTransport> = Box::new(WebSocketMimicry::server());
+
+        let request = client.next_handshake_message().unwrap();
+        let response = server.process_handshake_message(&request).unwrap().unwrap();
+        assert!(
+            client
+                .process_handshake_message(&response)
+                .unwrap()
+                .is_none()
+        );
+
+        assert!(client.is_established());
+        assert!(server.is_established());
+
+        let wrapped = client.wrap(b"hello").unwrap();
+        assert_eq!(server.unwrap(&wrapped).unwrap(), Some(b"hello".to_vec()));
+    }
+
+    #[test]
+    fn test_padding_through_trait() {
+        let mut transport: Box<dyn ObfuscationTransport> =
+            Box::new(PaddingEngine::new(PaddingMode::SizeClasses));
+        assert!(!transport.requires_handshake());
+
+        let wrapped = transport.wrap(b"short").unwrap();
+        assert_eq!(wrapped.len(), 128); // smallest size class
+        assert_eq!(transport.unwrap(&wrapped).unwrap(), Some(b"short".to_vec()));
+    }
+
+    #[test]
+    fn test_timing_through_trait() {
+        let mut transport: Box<dyn ObfuscationTransport> =
+            Box::new(TimingObfuscator::new(TimingMode::Uniform {
+                min: Duration::from_micros(100),
+                max: Duration::from_micros(200),
+            }));
+        assert_eq!(transport.max_overhead(), 0);
+
+        let delay = transport.next_send_delay();
+        assert!(delay >= Duration::from_micros(100));
+        assert!(delay <= Duration::from_micros(200));
+
+        // Payloads pass through unchanged
+        let wrapped = transport.wrap(b"data").unwrap();
+        assert_eq!(wrapped, b"data");
+    }
+
+    #[test]
+    fn test_transport_error_display() {
+        assert_eq!(
+            format!("{}", TransportError::HandshakeIncomplete),
+            "Transport handshake not complete"
+        );
+        assert_eq!(
+            format!("{}", TransportError::HandshakeFailed("bad key".to_string())),
+            "Transport handshake failed: bad key"
+        );
+        assert_eq!(
+            format!("{}", TransportError::Malformed("truncated".to_string())),
+            "Malformed transport data: truncated"
+        );
+    }
+}
@@ -1,13 +1,29 @@
 //! WebSocket protocol mimicry.
 //!
 //! Wraps WRAITH packets in WebSocket binary frames to blend with
-//! WebSocket traffic and evade DPI.
+//! WebSocket traffic and evade DPI. [`WebSocketMimicry`] additionally
+//! performs a realistic HTTP/1.1 Upgrade handshake (RFC 6455) and answers
+//! ping/pong keepalives so middleboxes see a plausible wss:// connection.
+
+use base64::{Engine, engine::general_purpose::STANDARD};
+use sha1::{Digest, Sha1};
+
+use crate::transport::{ObfuscationTransport, TransportError};
 
 /// WebSocket opcode for binary frame
 const WEBSOCKET_OPCODE_BINARY: u8 = 0x02;
+/// WebSocket opcode for connection close
+const WEBSOCKET_OPCODE_CLOSE: u8 = 0x08;
+/// WebSocket opcode for ping
+const WEBSOCKET_OPCODE_PING: u8 = 0x09;
+/// WebSocket opcode for pong
+const WEBSOCKET_OPCODE_PONG: u8 = 0x0A;
 /// WebSocket FIN bit
 const WEBSOCKET_FIN_BIT: u8 = 0x80;
 
+/// Handshake GUID from RFC 6455 section 1.3
+const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
 /// WebSocket frame wrapper
 ///
 /// Wraps WRAITH packets in WebSocket binary frames with optional masking.
@@ -63,48 +79,7 @@ impl WebSocketFrameWrapper {
     /// assert_eq!(frame[0] & 0x0F, 0x02); // Binary opcode
     /// ```
     pub fn wrap(&self, payload: &[u8]) -> Vec<u8> {
-        let mut frame = Vec::new();
-
-        // Byte 1: FIN + RSV + OPCODE
-        frame.push(WEBSOCKET_FIN_BIT | WEBSOCKET_OPCODE_BINARY);
-
-        // Byte 2: MASK + Payload length
-        let mask_bit = if self.client_to_server { 0x80 } else { 0x00 };
-
-        if payload.len() < 126 {
-            frame.push(mask_bit | payload.len() as u8);
-        } else if payload.len() < 65536 {
-            frame.push(mask_bit | 126);
-            frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
-        } else {
-            frame.push(mask_bit | 127);
-            frame.extend_from_slice(&(payload.len() as u64).to_be_bytes());
-        }
-
-        // Masking key (if client)
-        let masking_key = if self.client_to_server {
-            use rand::Rng;
-            let mut rng = rand::thread_rng();
-            let key: [u8; 4] = rng.r#gen();
-            frame.extend_from_slice(&key);
-            Some(key)
-        } else {
-            None
-        };
-
-        // Payload (masked if client)
-        if let Some(key) = masking_key {
-            let masked: Vec<u8> = payload
-                .iter()
-                .enumerate()
-                .map(|(i, &byte)| byte ^ key[i % 4])
-                .collect();
-            frame.extend_from_slice(&masked);
-        } else {
-            frame.extend_from_slice(payload);
-        }
-
-        frame
+        build_frame(WEBSOCKET_OPCODE_BINARY, payload, self.client_to_server)
     }
 
     /// Unwrap WebSocket frame to get payload
@@ -129,70 +104,12 @@ impl WebSocketFrameWrapper {
     /// assert_eq!(unwrapped, original);
     /// ```
     pub fn unwrap(&self, frame: &[u8]) -> Result<Vec<u8>, WsError> {
-        if frame.len() < 2 {
-            return Err(WsError::TooShort);
-        }
-
-        let _fin = (frame[0] & 0x80) != 0;
-        let opcode = frame[0] & 0x0F;
+        let (opcode, payload) = parse_frame(frame)?;
 
         if opcode != WEBSOCKET_OPCODE_BINARY {
             return Err(WsError::InvalidOpcode);
         }
 
-        let masked = (frame[1] & 0x80) != 0;
-        let mut payload_len = (frame[1] & 0x7F) as usize;
-        let mut offset = 2;
-
-        // Extended payload length
-        if payload_len == 126 {
-            if frame.len() < 4 {
-                return Err(WsError::TooShort);
-            }
-            payload_len = u16::from_be_bytes([frame[2], frame[3]]) as usize;
-            offset = 4;
-        } else if payload_len == 127 {
-            if frame.len() < 10 {
-                return Err(WsError::TooShort);
-            }
-            payload_len = u64::from_be_bytes([
-                frame[2], frame[3], frame[4], frame[5], frame[6], frame[7], frame[8], frame[9],
-            ]) as usize;
-            offset = 10;
-        }
-
-        // Masking key
-        let masking_key = if masked {
-            if frame.len() < offset + 4 {
-                return Err(WsError::TooShort);
-            }
-            let key = [
-                frame[offset],
-                frame[offset + 1],
-                frame[offset + 2],
-                frame[offset + 3],
-            ];
-            offset += 4;
-            Some(key)
-        } else {
-            None
-        };
-
-        // Payload
-        if frame.len() < offset + payload_len {
-            return Err(WsError::IncompleteFrame);
-        }
-
-        let payload = if let Some(key) = masking_key {
-            frame[offset..offset + payload_len]
-                .iter()
-                .enumerate()
-                .map(|(i, &byte)| byte ^ key[i % 4])
-                .collect()
-        } else {
-            frame[offset..offset + payload_len].to_vec()
-        };
-
         Ok(payload)
     }
 
@@ -209,6 +126,426 @@ impl Default for WebSocketFrameWrapper {
     }
 }
 
+/// Build a WebSocket frame with the given opcode, masked when `mask` is set
+fn build_frame(opcode: u8, payload: &[u8], mask: bool) -> Vec<u8> {
+    let mut frame = Vec::new();
+
+    // Byte 1: FIN + RSV + OPCODE
+    frame.push(WEBSOCKET_FIN_BIT | opcode);
+
+    // Byte 2: MASK + Payload length
+    let mask_bit = if mask { 0x80 } else { 0x00 };
+
+    if payload.len() < 126 {
+        frame.push(mask_bit | payload.len() as u8);
+    } else if payload.len() < 65536 {
+        frame.push(mask_bit | 126);
+        frame.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    } else {
+        frame.push(mask_bit | 127);
+        frame.extend_from_slice(&(payload.len() as u64).to_be_bytes());
+    }
+
+    // Masking key (clients must mask)
+    let masking_key = if mask {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+        let key: [u8; 4] = rng.r#gen();
+        frame.extend_from_slice(&key);
+        Some(key)
+    } else {
+        None
+    };
+
+    // Payload (masked if key present)
+    if let Some(key) = masking_key {
+        let masked: Vec<u8> = payload
+            .iter()
+            .enumerate()
+            .map(|(i, &byte)| byte ^ key[i % 4])
+            .collect();
+        frame.extend_from_slice(&masked);
+    } else {
+        frame.extend_from_slice(payload);
+    }
+
+    frame
+}
+
+/// Parse a WebSocket frame, returning its opcode and unmasked payload
+fn parse_frame(frame: &[u8]) -> Result<(u8, Vec<u8>), WsError> {
+    if frame.len() < 2 {
+        return Err(WsError::TooShort);
+    }
+
+    let _fin = (frame[0] & 0x80) != 0;
+    let opcode = frame[0] & 0x0F;
+
+    let masked = (frame[1] & 0x80) != 0;
+    let mut payload_len = (frame[1] & 0x7F) as usize;
+    let mut offset = 2;
+
+    // Extended payload length
+    if payload_len == 126 {
+        if frame.len() < 4 {
+            return Err(WsError::TooShort);
+        }
+        payload_len = u16::from_be_bytes([frame[2], frame[3]]) as usize;
+        offset = 4;
+    } else if payload_len == 127 {
+        if frame.len() < 10 {
+            return Err(WsError::TooShort);
+        }
+        payload_len = u64::from_be_bytes([
+            frame[2], frame[3], frame[4], frame[5], frame[6], frame[7], frame[8], frame[9],
+        ]) as usize;
+        offset = 10;
+    }
+
+    // Masking key
+    let masking_key = if masked {
+        if frame.len() < offset + 4 {
+            return Err(WsError::TooShort);
+        }
+        let key = [
+            frame[offset],
+            frame[offset + 1],
+            frame[offset + 2],
+            frame[offset + 3],
+        ];
+        offset += 4;
+        Some(key)
+    } else {
+        None
+    };
+
+    // Payload
+    if frame.len() < offset + payload_len {
+        return Err(WsError::IncompleteFrame);
+    }
+
+    let payload = if let Some(key) = masking_key {
+        frame[offset..offset + payload_len]
+            .iter()
+            .enumerate()
+            .map(|(i, &byte)| byte ^ key[i % 4])
+            .collect()
+    } else {
+        frame[offset..offset + payload_len].to_vec()
+    };
+
+    Ok((opcode, payload))
+}
+
+/// Compute the `Sec-WebSocket-Accept` value for a handshake key (RFC 6455)
+fn compute_accept(key: &str) -> String {
+    let mut hasher = Sha1::new();
+    hasher.update(key.as_bytes());
+    hasher.update(WEBSOCKET_GUID.as_bytes());
+    STANDARD.encode(hasher.finalize())
+}
+
+/// A decoded incoming WebSocket message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WsMessage {
+    /// Binary data frame carrying a WRAITH packet
+    Data(Vec<u8>),
+    /// Ping keepalive; reply with [`WebSocketMimicry::pong`] echoing the payload
+    Ping(Vec<u8>),
+    /// Pong keepalive answering an earlier ping
+    Pong(Vec<u8>),
+    /// Connection close
+    Close,
+}
+
+/// Full WebSocket session mimicry
+///
+/// Drives a realistic wss:// connection: an HTTP/1.1 Upgrade handshake with
+/// a correctly computed `Sec-WebSocket-Accept`, masked binary data frames,
+/// and ping/pong keepalives. Data transfer is rejected until the handshake
+/// completes, matching what a middlebox would expect to observe.
+///
+/// # Examples
+///
+/// ```
+/// use wraith_obfuscation::websocket_mimicry::{WebSocketMimicry, WsMessage};
+///
+/// let mut client = WebSocketMimicry::client("cdn.example.com", "/socket");
+/// let mut server = WebSocketMimicry::server();
+///
+/// let request = client.handshake_request();
+/// let response = server.process_handshake_request(&request).unwrap();
+/// client.process_handshake_response(&response).unwrap();
+///
+/// let frame = client.wrap(b"wraith packet").unwrap();
+/// let message = server.decode(&frame).unwrap();
+/// assert_eq!(message, WsMessage::Data(b"wraith packet".to_vec()));
+/// ```
+pub struct WebSocketMimicry {
+    /// Client frames are masked, server frames are not
+    is_client: bool,
+    /// Host header for the upgrade request
+    host: String,
+    /// Request path for the upgrade request
+    path: String,
+    /// Handshake key awaiting the server's accept (client only)
+    pending_key: Option<String>,
+    /// Whether the upgrade handshake has completed
+    established: bool,
+}
+
+impl WebSocketMimicry {
+    /// Create a client-side session targeting `host` and `path`
+    #[must_use]
+    pub fn client(host: &str, path: &str) -> Self {
+        Self {
+            is_client: true,
+            host: host.to_string(),
+            path: path.to_string(),
+            pending_key: None,
+            established: false,
+        }
+    }
+
+    /// Create a server-side session
+    #[must_use]
+    pub fn server() -> Self {
+        Self {
+            is_client: false,
+            host: String::new(),
+            path: String::new(),
+            pending_key: None,
+            established: false,
+        }
+    }
+
+    /// Whether the upgrade handshake has completed
+    #[must_use]
+    pub fn is_established(&self) -> bool {
+        self.established
+    }
+
+    /// Generate the HTTP/1.1 Upgrade request (client side)
+    ///
+    /// Produces a browser-plausible request with a fresh random
+    /// `Sec-WebSocket-Key`, which is remembered so the server's accept
+    /// value can be verified.
+    pub fn handshake_request(&mut self) -> Vec<u8> {
+        let mut key_bytes = [0u8; 16];
+        if getrandom::getrandom(&mut key_bytes).is_err() {
+            // Fall back to the thread RNG; the key only needs to be unpredictable
+            use rand::Rng;
+            rand::thread_rng().fill(&mut key_bytes);
+        }
+        let key = STANDARD.encode(key_bytes);
+
+        let request = format!(
+            "GET {} HTTP/1.1\r\n\
+             Host: {}\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Key: {}\r\n\
+             Sec-WebSocket-Version: 13\r\n\
+             User-Agent: Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36\r\n\
+             Origin: https://{}\r\n\
+             \r\n",
+            self.path, self.host, key, self.host
+        );
+
+        self.pending_key = Some(key);
+        request.into_bytes()
+    }
+
+    /// Process a client's upgrade request and produce the 101 response (server side)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WsError::HandshakeFailed`] if the request is not a valid
+    /// WebSocket upgrade.
+    pub fn process_handshake_request(&mut self, request: &[u8]) -> Result<Vec<u8>, WsError> {
+        let text = std::str::from_utf8(request).map_err(|_| WsError::HandshakeFailed)?;
+
+        if !text.starts_with("GET ") {
+            return Err(WsError::HandshakeFailed);
+        }
+
+        let upgrade_ok =
+            header_value(text, "Upgrade").is_some_and(|v| v.eq_ignore_ascii_case("websocket"));
+        let key = header_value(text, "Sec-WebSocket-Key").ok_or(WsError::HandshakeFailed)?;
+
+        if !upgrade_ok {
+            return Err(WsError::HandshakeFailed);
+        }
+
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\n\
+             Upgrade: websocket\r\n\
+             Connection: Upgrade\r\n\
+             Sec-WebSocket-Accept: {}\r\n\
+             \r\n",
+            compute_accept(key)
+        );
+
+        self.established = true;
+        Ok(response.into_bytes())
+    }
+
+    /// Process the server's 101 response and complete the handshake (client side)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WsError::HandshakeFailed`] if the response is not a 101 or
+    /// the `Sec-WebSocket-Accept` value does not match the key we sent.
+    pub fn process_handshake_response(&mut self, response: &[u8]) -> Result<(), WsError> {
+        let text = std::str::from_utf8(response).map_err(|_| WsError::HandshakeFailed)?;
+
+        if !text.starts_with("HTTP/1.1 101") {
+            return Err(WsError::HandshakeFailed);
+        }
+
+        let key = self.pending_key.take().ok_or(WsError::HandshakeFailed)?;
+        let accept = header_value(text, "Sec-WebSocket-Accept").ok_or(WsError::HandshakeFailed)?;
+
+        if accept != compute_accept(&key) {
+            return Err(WsError::HandshakeFailed);
+        }
+
+        self.established = true;
+        Ok(())
+    }
+
+    /// Wrap a WRAITH packet as a binary data frame
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WsError::HandshakeIncomplete`] if called before the
+    /// upgrade handshake finished.
+    pub fn wrap(&self, payload: &[u8]) -> Result<Vec<u8>, WsError> {
+        if !self.established {
+            return Err(WsError::HandshakeIncomplete);
+        }
+        Ok(build_frame(
+            WEBSOCKET_OPCODE_BINARY,
+            payload,
+            self.is_client,
+        ))
+    }
+
+    /// Decode an incoming frame into data or a control message
+    ///
+    /// Ping frames should be answered with [`pong`](Self::pong) echoing the
+    /// ping payload, as RFC 6455 requires.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WsError::HandshakeIncomplete`] before the handshake, or a
+    /// parse error for malformed frames.
+    pub fn decode(&self, frame: &[u8]) -> Result<WsMessage, WsError> {
+        if !self.established {
+            return Err(WsError::HandshakeIncomplete);
+        }
+
+        let (opcode, payload) = parse_frame(frame)?;
+        match opcode {
+            WEBSOCKET_OPCODE_BINARY => Ok(WsMessage::Data(payload)),
+            WEBSOCKET_OPCODE_PING => Ok(WsMessage::Ping(payload)),
+            WEBSOCKET_OPCODE_PONG => Ok(WsMessage::Pong(payload)),
+            WEBSOCKET_OPCODE_CLOSE => Ok(WsMessage::Close),
+            _ => Err(WsError::InvalidOpcode),
+        }
+    }
+
+    /// Build a ping keepalive frame
+    #[must_use]
+    pub fn ping(&self, payload: &[u8]) -> Vec<u8> {
+        build_frame(WEBSOCKET_OPCODE_PING, payload, self.is_client)
+    }
+
+    /// Build a pong frame answering a ping, echoing its payload
+    #[must_use]
+    pub fn pong(&self, ping_payload: &[u8]) -> Vec<u8> {
+        build_frame(WEBSOCKET_OPCODE_PONG, ping_payload, self.is_client)
+    }
+
+    /// Build a close frame
+    #[must_use]
+    pub fn close(&self) -> Vec<u8> {
+        build_frame(WEBSOCKET_OPCODE_CLOSE, &[], self.is_client)
+    }
+}
+
+impl ObfuscationTransport for WebSocketMimicry {
+    fn name(&self) -> &'static str {
+        "websocket"
+    }
+
+    fn requires_handshake(&self) -> bool {
+        true
+    }
+
+    fn is_established(&self) -> bool {
+        self.established
+    }
+
+    fn next_handshake_message(&mut self) -> Option<Vec<u8>> {
+        // Only the client initiates; one request per handshake
+        if self.is_client && !self.established && self.pending_key.is_none() {
+            Some(self.handshake_request())
+        } else {
+            None
+        }
+    }
+
+    fn process_handshake_message(
+        &mut self,
+        message: &[u8],
+    ) -> Result<Option<Vec<u8>>, TransportError> {
+        if self.is_client {
+            self.process_handshake_response(message)
+                .map_err(|e| TransportError::HandshakeFailed(e.to_string()))?;
+            Ok(None)
+        } else {
+            self.process_handshake_request(message)
+                .map(Some)
+                .map_err(|e| TransportError::HandshakeFailed(e.to_string()))
+        }
+    }
+
+    fn wrap(&mut self, payload: &[u8]) -> Result<Vec<u8>, TransportError> {
+        WebSocketMimicry::wrap(self, payload).map_err(|e| match e {
+            WsError::HandshakeIncomplete => TransportError::HandshakeIncomplete,
+            other => TransportError::Malformed(other.to_string()),
+        })
+    }
+
+    fn unwrap(&mut self, data: &[u8]) -> Result<Option<Vec<u8>>, TransportError> {
+        match self.decode(data) {
+            Ok(WsMessage::Data(payload)) => Ok(Some(payload)),
+            // Control frames carry no application payload
+            Ok(WsMessage::Ping(_) | WsMessage::Pong(_) | WsMessage::Close) => Ok(None),
+            Err(WsError::HandshakeIncomplete) => Err(TransportError::HandshakeIncomplete),
+            Err(other) => Err(TransportError::Malformed(other.to_string())),
+        }
+    }
+
+    fn max_overhead(&self) -> usize {
+        // 2-byte header + 8-byte extended length + 4-byte client mask
+        14
+    }
+}
+
+/// Extract an HTTP header value (case-insensitive name) from raw header text
+fn header_value<'a>(text: &'a str, name: &str) -> Option<&'a str> {
+    text.lines().find_map(|line| {
+        let (header, value) = line.split_once(':')?;
+        if header.trim().eq_ignore_ascii_case(name) {
+            Some(value.trim())
+        } else {
+            None
+        }
+    })
+}
+
 /// WebSocket error types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum WsError {
@@ -218,6 +555,10 @@ pub enum WsError {
     InvalidOpcode,
     /// Incomplete frame
     IncompleteFrame,
+    /// Upgrade handshake was invalid or could not be verified
+    HandshakeFailed,
+    /// Data exchanged before the upgrade handshake completed
+    HandshakeIncomplete,
 }
 
 impl std::fmt::Display for WsError {
@@ -226,6 +567,8 @@ impl std::fmt::Display for WsError {
             Self::TooShort => write!(f, "WebSocket frame too short"),
             Self::InvalidOpcode => write!(f, "Invalid WebSocket opcode"),
             Self::IncompleteFrame => write!(f, "Incomplete WebSocket frame"),
+            Self::HandshakeFailed => write!(f, "WebSocket upgrade handshake failed"),
+            Self::HandshakeIncomplete => write!(f, "WebSocket handshake not complete"),
         }
     }
 }
@@ -486,6 +829,135 @@ mod tests {
         );
     }
 
+    fn established_pair() -> (WebSocketMimicry, WebSocketMimicry) {
+        let mut client = WebSocketMimicry::client("cdn.example.com", "/socket");
+        let mut server = WebSocketMimicry::server();
+
+        let request = client.handshake_request();
+        let response = server.process_handshake_request(&request).unwrap();
+        client.process_handshake_response(&response).unwrap();
+
+        (client, server)
+    }
+
+    #[test]
+    fn test_mimicry_handshake() {
+        let (client, server) = established_pair();
+        assert!(client.is_established());
+        assert!(server.is_established());
+    }
+
+    #[test]
+    fn test_mimicry_handshake_request_format() {
+        let mut client = WebSocketMimicry::client("cdn.example.com", "/socket");
+        let request = String::from_utf8(client.handshake_request()).unwrap();
+
+        assert!(request.starts_with("GET /socket HTTP/1.1\r\n"));
+        assert!(request.contains("Host: cdn.example.com\r\n"));
+        assert!(request.contains("Upgrade: websocket\r\n"));
+        assert!(request.contains("Sec-WebSocket-Version: 13\r\n"));
+        assert!(request.contains("Sec-WebSocket-Key: "));
+        assert!(request.ends_with("\r\n\r\n"));
+    }
+
+    #[test]
+    fn test_mimicry_accept_value() {
+        // Example from RFC 6455 section 1.3
+        assert_eq!(
+            compute_accept("dGhlIHNhbXBsZSBub25jZQ=="),
+            "s3pPLMBiTxaQ9kYGzzhZRbK+xOo="
+        );
+    }
+
+    #[test]
+    fn test_mimicry_rejects_bad_accept() {
+        let mut client = WebSocketMimicry::client("cdn.example.com", "/socket");
+        let _ = client.handshake_request();
+
+        let forged = b"HTTP/1.1 101 Switching Protocols\r\n\
+                       Upgrade: websocket\r\n\
+                       Connection: Upgrade\r\n\
+                       Sec-WebSocket-Accept: AAAAAAAAAAAAAAAAAAAAAAAAAAA=\r\n\
+                       \r\n";
+        assert_eq!(
+            client.process_handshake_response(forged),
+            Err(WsError::HandshakeFailed)
+        );
+        assert!(!client.is_established());
+    }
+
+    #[test]
+    fn test_mimicry_rejects_non_upgrade_request() {
+        let mut server = WebSocketMimicry::server();
+        let plain_get = b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\n";
+
+        assert_eq!(
+            server.process_handshake_request(plain_get),
+            Err(WsError::HandshakeFailed)
+        );
+    }
+
+    #[test]
+    fn test_mimicry_data_roundtrip() {
+        let (client, server) = established_pair();
+
+        let frame = client.wrap(b"wraith packet").unwrap();
+        assert_eq!(
+            server.decode(&frame).unwrap(),
+            WsMessage::Data(b"wraith packet".to_vec())
+        );
+
+        let reply = server.wrap(b"response").unwrap();
+        assert_eq!(
+            client.decode(&reply).unwrap(),
+            WsMessage::Data(b"response".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_mimicry_data_before_handshake_rejected() {
+        let client = WebSocketMimicry::client("cdn.example.com", "/socket");
+
+        assert_eq!(client.wrap(b"early"), Err(WsError::HandshakeIncomplete));
+        assert_eq!(
+            client.decode(&[0x82, 0x00]),
+            Err(WsError::HandshakeIncomplete)
+        );
+    }
+
+    #[test]
+    fn test_mimicry_ping_pong_keepalive() {
+        let (client, server) = established_pair();
+
+        // Server pings, client decodes and echoes the payload back
+        let ping = server.ping(b"keepalive");
+        let WsMessage::Ping(payload) = client.decode(&ping).unwrap() else {
+            panic!("expected ping");
+        };
+
+        let pong = client.pong(&payload);
+        assert_eq!(
+            server.decode(&pong).unwrap(),
+            WsMessage::Pong(b"keepalive".to_vec())
+        );
+    }
+
+    #[test]
+    fn test_mimicry_close() {
+        let (client, server) = established_pair();
+
+        let close = client.close();
+        assert_eq!(server.decode(&close).unwrap(), WsMessage::Close);
+    }
+
+    #[test]
+    fn test_mimicry_client_frames_are_masked() {
+        let (client, _server) = established_pair();
+
+        let frame = client.wrap(b"payload").unwrap();
+        assert_eq!(frame[1] & 0x80, 0x80);
+    }
+
     #[test]
     fn test_websocket_masking_different_each_time() {
         let wrapper = WebSocketFrameWrapper::new(true);
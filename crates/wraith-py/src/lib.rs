@@ -0,0 +1,385 @@
+//! # wraith-py - Python bindings for WRAITH Protocol
+//!
+//! Exposes the Node API as Python classes with asyncio integration:
+//! [`Node`], [`Session`], and [`Transfer`] wrap their Rust counterparts,
+//! and every blocking operation (start/stop, session establishment, file
+//! sends, transfer waits) is an awaitable coroutine driven by a shared
+//! tokio runtime.
+//!
+//! Peer and transfer identifiers cross the boundary as 64-character hex
+//! strings rather than byte buffers, matching how the CLI prints them.
+//! Protocol failures raise the `wraith.WraithError` Python exception;
+//! malformed arguments raise `ValueError`.
+//!
+//! Receiving needs no explicit call: a started node accepts incoming
+//! transfers and writes completed files to its configured download
+//! directory.
+
+use std::path::PathBuf;
+
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+
+use wraith_core::node::Node as CoreNode;
+use wraith_core::node::config::NodeConfig;
+
+create_exception!(
+    wraith,
+    WraithError,
+    PyException,
+    "A WRAITH protocol operation failed."
+);
+
+/// Convert a Node API error into the Python-side exception
+fn protocol_err(e: wraith_core::node::NodeError) -> PyErr {
+    WraithError::new_err(e.to_string())
+}
+
+/// Encode a 32-byte identifier as lowercase hex
+fn encode_id(id: &[u8; 32]) -> String {
+    id.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Parse a 64-character hex string into a 32-byte identifier
+fn parse_id(s: &str) -> Result<[u8; 32], String> {
+    if s.len() != 64 {
+        return Err(format!("expected 64 hex characters, got {}", s.len()));
+    }
+    let mut id = [0u8; 32];
+    for (i, byte) in id.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16)
+            .map_err(|_| format!("invalid hex at position {}", i * 2))?;
+    }
+    Ok(id)
+}
+
+/// Parse a peer/transfer identifier argument, raising `ValueError` on failure
+fn parse_id_arg(name: &str, s: &str) -> PyResult<[u8; 32]> {
+    parse_id(s).map_err(|e| PyValueError::new_err(format!("invalid {name}: {e}")))
+}
+
+/// Snapshot of a transfer's progress
+#[pyclass(get_all)]
+#[derive(Clone)]
+struct TransferProgress {
+    /// Total bytes in the transfer
+    total_bytes: u64,
+    /// Bytes transferred so far
+    transferred_bytes: u64,
+    /// Completion percentage (0.0 - 100.0)
+    percent: f64,
+    /// Current transfer rate in bytes per second
+    rate_bytes_per_sec: f64,
+    /// Estimated seconds to completion, or None if unknown
+    eta_seconds: Option<u64>,
+    /// Whether the transfer has finished
+    is_complete: bool,
+}
+
+#[pymethods]
+impl TransferProgress {
+    fn __repr__(&self) -> String {
+        format!(
+            "TransferProgress(transferred_bytes={}, total_bytes={}, percent={:.1}, is_complete={})",
+            self.transferred_bytes, self.total_bytes, self.percent, self.is_complete
+        )
+    }
+}
+
+impl TransferProgress {
+    fn from_core(progress: &wraith_core::node::progress::TransferProgress) -> Self {
+        Self {
+            total_bytes: progress.bytes_total,
+            transferred_bytes: progress.bytes_sent,
+            percent: progress.progress_percent,
+            rate_bytes_per_sec: progress.speed_bytes_per_sec,
+            eta_seconds: progress.eta.map(|eta| eta.as_secs()),
+            is_complete: progress.is_complete(),
+        }
+    }
+}
+
+/// An established session with a peer
+///
+/// Returned by `Node.establish_session()`; hold it to close the session
+/// explicitly. Dropping it does not close the underlying session.
+#[pyclass]
+struct Session {
+    node: CoreNode,
+    peer_id: [u8; 32],
+}
+
+#[pymethods]
+impl Session {
+    /// The peer's node ID as a hex string
+    #[getter]
+    fn peer_id(&self) -> String {
+        encode_id(&self.peer_id)
+    }
+
+    /// Close the session (coroutine)
+    fn close<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.node.clone();
+        let peer_id = self.peer_id;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            node.close_session(&peer_id).await.map_err(protocol_err)
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Session(peer_id=\"{}\")", encode_id(&self.peer_id))
+    }
+}
+
+/// A file transfer in progress
+///
+/// Returned by `Node.send_file()`. Await `wait()` for completion, poll
+/// `progress()` for status, or `cancel()` to abort.
+#[pyclass]
+struct Transfer {
+    node: CoreNode,
+    transfer_id: [u8; 32],
+}
+
+#[pymethods]
+impl Transfer {
+    /// The transfer ID as a hex string
+    #[getter]
+    fn id(&self) -> String {
+        encode_id(&self.transfer_id)
+    }
+
+    /// Wait for the transfer to complete (coroutine)
+    fn wait<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.node.clone();
+        let transfer_id = self.transfer_id;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            node.wait_for_transfer(transfer_id)
+                .await
+                .map_err(protocol_err)
+        })
+    }
+
+    /// Get a progress snapshot, or None if the transfer is unknown (coroutine)
+    fn progress<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.node.clone();
+        let transfer_id = self.transfer_id;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let progress = node.get_transfer_progress(&transfer_id).await;
+            Ok(progress.as_ref().map(TransferProgress::from_core))
+        })
+    }
+
+    /// Cancel the transfer (coroutine)
+    fn cancel<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.node.clone();
+        let transfer_id = self.transfer_id;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            node.cancel_transfer(&transfer_id)
+                .await
+                .map_err(protocol_err)
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Transfer(id=\"{}\")", encode_id(&self.transfer_id))
+    }
+}
+
+/// A WRAITH node
+///
+/// Created with a fresh random identity. Start it before establishing
+/// sessions or sending files; a started node also accepts incoming
+/// transfers into `download_dir`.
+#[pyclass]
+struct Node {
+    inner: CoreNode,
+}
+
+#[pymethods]
+impl Node {
+    /// Create a new node with a random identity
+    ///
+    /// `listen_addr` is a `"host:port"` string (port 0 auto-selects);
+    /// `download_dir` is where completed incoming transfers are written.
+    /// Both default to the standard node configuration.
+    #[new]
+    #[pyo3(signature = (listen_addr=None, download_dir=None))]
+    fn new(
+        py: Python<'_>,
+        listen_addr: Option<&str>,
+        download_dir: Option<PathBuf>,
+    ) -> PyResult<Self> {
+        let mut config = NodeConfig::default();
+        if let Some(addr) = listen_addr {
+            config.listen_addr = addr
+                .parse()
+                .map_err(|e| PyValueError::new_err(format!("invalid listen_addr: {e}")))?;
+        }
+        if let Some(dir) = download_dir {
+            config.transfer.download_dir = dir;
+        }
+
+        let inner = py
+            .allow_threads(|| {
+                pyo3_async_runtimes::tokio::get_runtime()
+                    .block_on(CoreNode::new_with_config(config))
+            })
+            .map_err(protocol_err)?;
+
+        Ok(Self { inner })
+    }
+
+    /// The node's ID (Ed25519 public key) as a hex string
+    #[getter]
+    fn node_id(&self) -> String {
+        encode_id(self.inner.node_id())
+    }
+
+    /// Whether the node is currently running
+    #[getter]
+    fn is_running(&self) -> bool {
+        self.inner.is_running()
+    }
+
+    /// Start the node (coroutine)
+    fn start<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.inner.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            node.start().await.map_err(protocol_err)
+        })
+    }
+
+    /// Stop the node (coroutine)
+    fn stop<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.inner.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            node.stop().await.map_err(protocol_err)
+        })
+    }
+
+    /// The node's bound listen address as a string (coroutine)
+    fn listen_addr<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.inner.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            node.listen_addr()
+                .await
+                .map(|addr| addr.to_string())
+                .map_err(protocol_err)
+        })
+    }
+
+    /// Establish a session with a peer, returning a `Session` (coroutine)
+    fn establish_session<'py>(
+        &self,
+        py: Python<'py>,
+        peer_id: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.inner.clone();
+        let peer_id = parse_id_arg("peer_id", peer_id)?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            node.establish_session(&peer_id)
+                .await
+                .map_err(protocol_err)?;
+            Ok(Session { node, peer_id })
+        })
+    }
+
+    /// Send a file to a peer, returning a `Transfer` (coroutine)
+    ///
+    /// Returns as soon as the transfer is registered; await
+    /// `Transfer.wait()` for completion.
+    fn send_file<'py>(
+        &self,
+        py: Python<'py>,
+        path: PathBuf,
+        peer_id: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.inner.clone();
+        let peer_id = parse_id_arg("peer_id", peer_id)?;
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let transfer_id = node.send_file(path, &peer_id).await.map_err(protocol_err)?;
+            Ok(Transfer { node, transfer_id })
+        })
+    }
+
+    /// Hex IDs of all active transfers (coroutine)
+    fn active_transfers<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.inner.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let ids: Vec<String> = node
+                .active_transfers()
+                .await
+                .iter()
+                .map(encode_id)
+                .collect();
+            Ok(ids)
+        })
+    }
+
+    /// Hex IDs of all peers with active sessions (coroutine)
+    fn active_sessions<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let node = self.inner.clone();
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            let ids: Vec<String> = node.active_sessions().await.iter().map(encode_id).collect();
+            Ok(ids)
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Node(node_id=\"{}\", running={})",
+            encode_id(self.inner.node_id()),
+            self.inner.is_running()
+        )
+    }
+}
+
+/// Python module definition
+#[pymodule]
+fn wraith(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Node>()?;
+    m.add_class::<Session>()?;
+    m.add_class::<Transfer>()?;
+    m.add_class::<TransferProgress>()?;
+    m.add("WraithError", m.py().get_type::<WraithError>())?;
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_parse_roundtrip() {
+        let id = [0xabu8; 32];
+        let hex = encode_id(&id);
+        assert_eq!(hex.len(), 64);
+        assert_eq!(parse_id(&hex).unwrap(), id);
+    }
+
+    #[test]
+    fn test_parse_id_rejects_bad_input() {
+        assert!(parse_id("").is_err());
+        assert!(parse_id("abcd").is_err());
+        assert!(parse_id(&"g".repeat(64)).is_err());
+        // Uppercase hex is accepted
+        assert_eq!(parse_id(&"AB".repeat(32)).unwrap(), [0xabu8; 32]);
+    }
+
+    #[test]
+    fn test_encode_id_is_lowercase() {
+        let id = {
+            let mut id = [0u8; 32];
+            id[0] = 0xff;
+            id[31] = 0x0a;
+            id
+        };
+        let hex = encode_id(&id);
+        assert!(hex.starts_with("ff"));
+        assert!(hex.ends_with("0a"));
+        assert_eq!(hex, hex.to_lowercase());
+    }
+}
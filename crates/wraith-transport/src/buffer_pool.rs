@@ -110,6 +110,48 @@ impl BufferPool {
         Self { pool, buffer_size }
     }
 
+    /// Create a buffer pool whose pages live on a specific NUMA node
+    ///
+    /// Pre-allocation runs on a thread pinned to a CPU of `node`, so the
+    /// kernel's first-touch policy places the buffer pages in that node's
+    /// memory. Workers pinned to the same node (typically the NIC's, see
+    /// [`get_numa_node_for_nic`](crate::numa::get_numa_node_for_nic)) then
+    /// access packet buffers without cross-node memory traffic.
+    ///
+    /// Falls back to [`BufferPool::new`] when the node has no CPUs or on
+    /// platforms without NUMA support.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wraith_transport::BufferPool;
+    ///
+    /// // Allocate MTU-sized buffers on NUMA node 0
+    /// let pool = BufferPool::new_on_node(1472, 256, 0);
+    /// assert_eq!(pool.available(), 256);
+    /// ```
+    pub fn new_on_node(buffer_size: usize, pool_size: usize, node: usize) -> Self {
+        #[cfg(target_os = "linux")]
+        if let Some(&cpu) = crate::numa::cpus_on_node(node).first() {
+            let allocated = std::thread::spawn(move || {
+                if let Err(e) = crate::numa::pin_current_thread(cpu) {
+                    tracing::warn!("Failed to pin pool allocation to CPU {}: {}", cpu, e);
+                }
+                Self::new(buffer_size, pool_size)
+            })
+            .join();
+
+            if let Ok(pool) = allocated {
+                return pool;
+            }
+        }
+
+        #[cfg(not(target_os = "linux"))]
+        let _ = node;
+
+        Self::new(buffer_size, pool_size)
+    }
+
     /// Acquire a buffer from the pool
     ///
     /// Returns a `Vec<u8>` of size `buffer_size` from the pool. If the pool is empty,
@@ -428,6 +470,25 @@ mod tests {
         assert_eq!(pool.available(), 2);
     }
 
+    #[test]
+    fn test_buffer_pool_new_on_node() {
+        let pool = BufferPool::new_on_node(1024, 8, 0);
+        assert_eq!(pool.buffer_size(), 1024);
+        assert_eq!(pool.available(), 8);
+
+        let buf = pool.acquire();
+        assert_eq!(buf.len(), 1024);
+        pool.release(buf);
+        assert_eq!(pool.available(), 8);
+    }
+
+    #[test]
+    fn test_buffer_pool_new_on_node_missing_node_falls_back() {
+        let pool = BufferPool::new_on_node(512, 4, 4096);
+        assert_eq!(pool.buffer_size(), 512);
+        assert_eq!(pool.available(), 4);
+    }
+
     #[test]
     fn test_buffer_pool_zero_size() {
         let pool = BufferPool::new(0, 10);
@@ -0,0 +1,173 @@
+//! Runtime platform capability detection
+//!
+//! Probes which transport acceleration features the running kernel and
+//! hardware actually support, as opposed to what this binary was compiled
+//! with. Support tooling and GUIs use this to decide what to enable
+//! without parsing human-readable output.
+//!
+//! All probes are cheap (a socket or ring creation that is immediately
+//! torn down) and safe to call repeatedly.
+
+/// Snapshot of platform transport capabilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransportCapabilities {
+    /// AF_XDP sockets can be created (kernel 5.3+, Linux only)
+    pub af_xdp: bool,
+    /// io_uring rings can be created (kernel 5.1+, Linux only)
+    pub io_uring: bool,
+    /// The BPF filesystem is mounted, required for pinning XDP programs
+    pub bpf_fs: bool,
+    /// A privileged XDP loader helper is listening on its socket,
+    /// allowing an unprivileged daemon to receive map descriptors
+    pub xdp_helper: bool,
+    /// UDP sockets are usable (always true on supported platforms)
+    pub udp: bool,
+    /// UDP_SEGMENT (GSO) send offload works (kernel 4.18+, Linux only)
+    pub udp_gso: bool,
+    /// UDP_GRO receive coalescing works (kernel 5.0+, Linux only)
+    pub udp_gro: bool,
+}
+
+impl TransportCapabilities {
+    /// Probe the running system for transport capabilities
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wraith_transport::capabilities::TransportCapabilities;
+    ///
+    /// let caps = TransportCapabilities::detect();
+    /// assert!(caps.udp);
+    /// ```
+    #[must_use]
+    pub fn detect() -> Self {
+        Self {
+            af_xdp: af_xdp_available(),
+            io_uring: io_uring_available(),
+            bpf_fs: bpf_fs_mounted(),
+            xdp_helper: xdp_helper_available(),
+            udp: true,
+            udp_gso: crate::gso::udp_gso_available(),
+            udp_gro: crate::gso::udp_gro_available(),
+        }
+    }
+
+    /// Whether any kernel-bypass acceleration is available
+    #[must_use]
+    pub fn has_acceleration(&self) -> bool {
+        self.af_xdp || self.io_uring
+    }
+}
+
+/// Check whether AF_XDP sockets can be created
+///
+/// Creates and immediately closes an AF_XDP socket. Fails on kernels
+/// without XDP support and on non-Linux platforms.
+#[must_use]
+pub fn af_xdp_available() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        // SAFETY: socket() and close() with no shared state; the fd is
+        // closed before returning
+        unsafe {
+            let fd = libc::socket(libc::AF_XDP, libc::SOCK_RAW, 0);
+            if fd < 0 {
+                return false;
+            }
+            libc::close(fd);
+            true
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
+/// Check whether io_uring rings can be created
+///
+/// Creates and immediately drops a minimal ring. Fails on kernels older
+/// than 5.1, under restrictive seccomp policies, and on non-Linux
+/// platforms.
+#[must_use]
+pub fn io_uring_available() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        ::io_uring::IoUring::new(2).is_ok()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
+/// Check whether the BPF filesystem is mounted at its standard path
+///
+/// XDP program pinning requires bpffs; its absence means XDP programs
+/// cannot persist across process restarts.
+#[must_use]
+pub fn bpf_fs_mounted() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        std::path::Path::new("/sys/fs/bpf").is_dir()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
+/// Check whether the privileged XDP loader helper is reachable
+///
+/// An unprivileged daemon can still use XDP when the split-process
+/// helper is running: it receives the BPF map descriptors over the
+/// helper's Unix socket instead of loading the program itself (see
+/// [`crate::xdp_loader`]).
+#[must_use]
+pub fn xdp_helper_available() -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        std::path::Path::new(crate::xdp_loader::DEFAULT_LOADER_SOCKET).exists()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_does_not_panic() {
+        let caps = TransportCapabilities::detect();
+        assert!(caps.udp);
+    }
+
+    #[test]
+    fn test_detect_is_stable() {
+        // Probes have no side effects, so repeated detection agrees
+        assert_eq!(
+            TransportCapabilities::detect(),
+            TransportCapabilities::detect()
+        );
+    }
+
+    #[test]
+    fn test_has_acceleration_consistency() {
+        let caps = TransportCapabilities::detect();
+        assert_eq!(caps.has_acceleration(), caps.af_xdp || caps.io_uring);
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    #[test]
+    fn test_non_linux_has_no_kernel_features() {
+        let caps = TransportCapabilities::detect();
+        assert!(!caps.af_xdp);
+        assert!(!caps.io_uring);
+        assert!(!caps.bpf_fs);
+        assert!(!caps.udp_gso);
+        assert!(!caps.udp_gro);
+    }
+}
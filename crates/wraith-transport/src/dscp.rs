@@ -0,0 +1,198 @@
+//! DSCP (Differentiated Services Code Point) marking for outgoing packets.
+//!
+//! Enterprise networks classify and prioritize traffic by the DSCP field in
+//! the IP header (the upper six bits of the IPv4 TOS byte / IPv6 traffic
+//! class). Marking WRAITH traffic lets operators place bulk transfers in an
+//! assured-forwarding class (e.g. AF21) or latency-sensitive control traffic
+//! in expedited forwarding (EF) without deep packet inspection.
+//!
+//! Marking is applied per socket via `IP_TOS` / `IPV6_TCLASS`, so all packets
+//! sent from one transport share a single code point. Per-frame marking (e.g.
+//! DATA frames as AF21 and ACK/CONTROL frames as EF on the same socket) would
+//! require per-message ancillary data (`sendmsg` with a TOS cmsg) and is not
+//! yet supported; the AF_XDP path will stamp the TOS byte directly into
+//! packet templates once it constructs full IP headers.
+
+use std::fmt;
+use std::io;
+
+/// A DSCP code point (0-63).
+///
+/// Use the named constants for the standard classes or [`Dscp::new`] /
+/// [`Dscp::from_name`] for arbitrary or configured values.
+///
+/// # Examples
+/// ```
+/// use wraith_transport::dscp::Dscp;
+///
+/// assert_eq!(Dscp::AF21.code_point(), 18);
+/// assert_eq!(Dscp::EF.tos(), 46 << 2);
+/// assert_eq!(Dscp::from_name("af21"), Some(Dscp::AF21));
+/// assert_eq!(Dscp::new(64), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Dscp(u8);
+
+impl Dscp {
+    /// Class selector 0 - best effort (default forwarding)
+    pub const CS0: Dscp = Dscp(0);
+    /// Class selector 1 - low-priority / scavenger
+    pub const CS1: Dscp = Dscp(8);
+    /// Assured forwarding 11 - high-throughput data, low drop precedence
+    pub const AF11: Dscp = Dscp(10);
+    /// Assured forwarding 21 - bulk data transfers
+    pub const AF21: Dscp = Dscp(18);
+    /// Assured forwarding 31 - multimedia streaming
+    pub const AF31: Dscp = Dscp(26);
+    /// Assured forwarding 41 - interactive multimedia
+    pub const AF41: Dscp = Dscp(34);
+    /// Class selector 5 - signaling
+    pub const CS5: Dscp = Dscp(40);
+    /// Expedited forwarding - low-latency control traffic
+    pub const EF: Dscp = Dscp(46);
+
+    /// Create from a raw code point, rejecting values outside 0-63
+    #[must_use]
+    pub const fn new(code_point: u8) -> Option<Self> {
+        if code_point < 64 {
+            Some(Dscp(code_point))
+        } else {
+            None
+        }
+    }
+
+    /// The raw 6-bit code point
+    #[must_use]
+    pub const fn code_point(self) -> u8 {
+        self.0
+    }
+
+    /// The full TOS / traffic-class byte (code point shifted left of the
+    /// two ECN bits, which are left zero)
+    #[must_use]
+    pub const fn tos(self) -> u8 {
+        self.0 << 2
+    }
+
+    /// Parse a class name ("AF21", "ef", "cs0") or a decimal code point
+    ///
+    /// Names are case-insensitive. Decimal values must be in 0-63.
+    #[must_use]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "cs0" | "be" | "default" => Some(Self::CS0),
+            "cs1" => Some(Self::CS1),
+            "af11" => Some(Self::AF11),
+            "af21" => Some(Self::AF21),
+            "af31" => Some(Self::AF31),
+            "af41" => Some(Self::AF41),
+            "cs5" => Some(Self::CS5),
+            "ef" => Some(Self::EF),
+            other => other.parse::<u8>().ok().and_then(Self::new),
+        }
+    }
+}
+
+impl fmt::Display for Dscp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::CS0 => write!(f, "CS0"),
+            Self::CS1 => write!(f, "CS1"),
+            Self::AF11 => write!(f, "AF11"),
+            Self::AF21 => write!(f, "AF21"),
+            Self::AF31 => write!(f, "AF31"),
+            Self::AF41 => write!(f, "AF41"),
+            Self::CS5 => write!(f, "CS5"),
+            Self::EF => write!(f, "EF"),
+            Dscp(code) => write!(f, "DSCP {code}"),
+        }
+    }
+}
+
+/// Apply a DSCP marking to a bound socket.
+///
+/// Sets `IP_TOS` for IPv4 sockets and `IPV6_TCLASS` for IPv6 sockets (the
+/// latter is Unix-only; other platforms report `Unsupported` for IPv6).
+pub fn set_socket_dscp<'a, S>(socket: &'a S, is_ipv4: bool, dscp: Dscp) -> io::Result<()>
+where
+    socket2::SockRef<'a>: From<&'a S>,
+{
+    let socket = socket2::SockRef::from(socket);
+    if is_ipv4 {
+        socket.set_tos_v4(u32::from(dscp.tos()))
+    } else {
+        #[cfg(unix)]
+        {
+            socket.set_tclass_v6(u32::from(dscp.tos()))
+        }
+        #[cfg(not(unix))]
+        {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "IPv6 traffic-class marking is only supported on Unix",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_named_code_points() {
+        assert_eq!(Dscp::CS0.code_point(), 0);
+        assert_eq!(Dscp::AF21.code_point(), 18);
+        assert_eq!(Dscp::EF.code_point(), 46);
+    }
+
+    #[test]
+    fn test_tos_shifts_past_ecn_bits() {
+        assert_eq!(Dscp::AF21.tos(), 0x48);
+        assert_eq!(Dscp::EF.tos(), 0xB8);
+        assert_eq!(Dscp::CS0.tos(), 0);
+    }
+
+    #[test]
+    fn test_new_rejects_out_of_range() {
+        assert_eq!(Dscp::new(63), Some(Dscp(63)));
+        assert_eq!(Dscp::new(64), None);
+        assert_eq!(Dscp::new(255), None);
+    }
+
+    #[test]
+    fn test_from_name() {
+        assert_eq!(Dscp::from_name("AF21"), Some(Dscp::AF21));
+        assert_eq!(Dscp::from_name("ef"), Some(Dscp::EF));
+        assert_eq!(Dscp::from_name("be"), Some(Dscp::CS0));
+        assert_eq!(Dscp::from_name("46"), Some(Dscp::EF));
+        assert_eq!(Dscp::from_name("64"), None);
+        assert_eq!(Dscp::from_name("platinum"), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Dscp::AF21.to_string(), "AF21");
+        assert_eq!(Dscp::EF.to_string(), "EF");
+        assert_eq!(Dscp::new(13).unwrap().to_string(), "DSCP 13");
+    }
+
+    #[test]
+    fn test_set_socket_dscp_ipv4() {
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        set_socket_dscp(&socket, true, Dscp::AF21).unwrap();
+
+        let sock = socket2::SockRef::from(&socket);
+        assert_eq!(sock.tos_v4().unwrap(), u32::from(Dscp::AF21.tos()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_set_socket_dscp_ipv6() {
+        let socket = std::net::UdpSocket::bind("[::1]:0").unwrap();
+        set_socket_dscp(&socket, false, Dscp::EF).unwrap();
+
+        let sock = socket2::SockRef::from(&socket);
+        assert_eq!(sock.tclass_v6().unwrap(), u32::from(Dscp::EF.tos()));
+    }
+}
@@ -3,6 +3,7 @@
 //! This module provides a factory pattern for creating different transport
 //! implementations based on configuration.
 
+use crate::dscp::Dscp;
 use crate::quic::QuicTransport;
 use crate::transport::{Transport, TransportResult};
 use crate::udp_async::AsyncUdpTransport;
@@ -30,6 +31,8 @@ pub struct TransportFactoryConfig {
     pub recv_buffer_size: Option<usize>,
     /// Send buffer size (bytes)
     pub send_buffer_size: Option<usize>,
+    /// DSCP marking for outgoing packets (None = leave at OS default)
+    pub dscp: Option<Dscp>,
 }
 
 impl TransportFactoryConfig {
@@ -54,6 +57,7 @@ impl TransportFactoryConfig {
             bind_addr,
             recv_buffer_size: None,
             send_buffer_size: None,
+            dscp: None,
         }
     }
 
@@ -104,6 +108,17 @@ impl TransportFactoryConfig {
         self.send_buffer_size = Some(send_size);
         self
     }
+
+    /// Set the DSCP marking for outgoing packets.
+    ///
+    /// # Arguments
+    /// * `dscp` - Code point to write into the IP TOS / traffic-class byte
+    ///   (e.g. [`Dscp::AF21`] for bulk transfers)
+    #[must_use]
+    pub fn with_dscp(mut self, dscp: Dscp) -> Self {
+        self.dscp = Some(dscp);
+        self
+    }
 }
 
 /// Default bind address for transports (0.0.0.0:0 = any interface, OS-assigned port)
@@ -118,6 +133,7 @@ impl Default for TransportFactoryConfig {
             bind_addr: DEFAULT_BIND_ADDR,
             recv_buffer_size: None,
             send_buffer_size: None,
+            dscp: None,
         }
     }
 }
@@ -172,6 +188,9 @@ impl TransportFactory {
         match config.transport_type {
             TransportType::Udp => {
                 let transport = AsyncUdpTransport::bind(config.bind_addr).await?;
+                if let Some(dscp) = config.dscp {
+                    transport.set_dscp(dscp)?;
+                }
                 Ok(Arc::new(transport))
             }
             TransportType::Quic => {
@@ -272,6 +291,15 @@ mod tests {
         assert_ne!(bound_addr.port(), 0);
     }
 
+    #[tokio::test]
+    async fn test_factory_create_udp_with_dscp() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let config = TransportFactoryConfig::udp(addr).with_dscp(Dscp::AF21);
+
+        let transport = TransportFactory::create(config).await.unwrap();
+        assert!(transport.local_addr().unwrap().is_ipv4());
+    }
+
     #[tokio::test]
     async fn test_factory_create_udp_shorthand() {
         let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
@@ -1,13 +1,19 @@
-//! io_uring-based asynchronous file I/O for WRAITH Protocol
+//! io_uring-based asynchronous I/O for WRAITH Protocol
 //!
-//! This module provides high-performance async file I/O using Linux io_uring.
-//! It supports batched operations with zero-copy reads/writes and registered buffers.
+//! This module provides high-performance async I/O using Linux io_uring.
+//! One ring carries both file operations (read/write/fsync) and network
+//! datagram operations (sendmsg/recvmsg), so a worker drains a single
+//! completion queue for disk and network instead of splitting its time
+//! between a uring for files and epoll for sockets.
 //!
 //! ## Features
 //!
 //! - Batched submission (submit multiple ops in one syscall)
 //! - Completion polling (process multiple completions at once)
 //! - Registered buffers for zero-copy I/O
+//! - Unified disk + network completions on one ring per worker
+//! - Multishot receive: one SQE yields a completion per datagram
+//! - Provided buffer groups backing multishot receives
 //! - SQE (Submission Queue Entry) management
 //! - CQE (Completion Queue Entry) processing
 //!
@@ -48,6 +54,7 @@
 #[cfg(target_os = "linux")]
 use std::collections::HashMap;
 use std::io;
+use std::net::SocketAddr;
 use thiserror::Error;
 
 // Platform-specific RawFd type
@@ -90,12 +97,18 @@ pub struct PendingOp {
     pub id: u64,
     /// Operation type
     pub op_type: OpType,
-    /// File descriptor
+    /// File or socket descriptor
     pub fd: RawFd,
-    /// Offset in file
+    /// Offset in file (0 for network operations)
     pub offset: u64,
     /// Buffer length
     pub len: usize,
+    /// Destination address (sendmsg only)
+    pub peer: Option<SocketAddr>,
+    /// Provided buffer group backing the receive (multishot recvmsg only)
+    pub buf_group: Option<u16>,
+    /// Whether the operation re-arms itself after each completion
+    pub multishot: bool,
 }
 
 /// Operation types
@@ -107,6 +120,18 @@ pub enum OpType {
     Write,
     /// Fsync operation
     Fsync,
+    /// Datagram send (IORING_OP_SENDMSG)
+    SendMsg,
+    /// Datagram receive (IORING_OP_RECVMSG, optionally multishot)
+    RecvMsg,
+}
+
+impl OpType {
+    /// Whether this is a network (socket) operation
+    #[must_use]
+    pub fn is_network(&self) -> bool {
+        matches!(self, Self::SendMsg | Self::RecvMsg)
+    }
 }
 
 /// Completion result
@@ -131,6 +156,8 @@ pub struct IoUringContext {
     pending: HashMap<u64, PendingOp>,
     /// Registered buffers
     buffers: Vec<Vec<u8>>,
+    /// Provided buffer groups for multishot receives (group id -> buffers)
+    buffer_groups: HashMap<u16, Vec<Vec<u8>>>,
 }
 
 #[cfg(target_os = "linux")]
@@ -159,6 +186,7 @@ impl IoUringContext {
             next_id: 0,
             pending: HashMap::new(),
             buffers: Vec::new(),
+            buffer_groups: HashMap::new(),
         })
     }
 
@@ -183,6 +211,9 @@ impl IoUringContext {
             fd,
             offset,
             len,
+            peer: None,
+            buf_group: None,
+            multishot: false,
         };
 
         // In production, would create SQE and submit to ring
@@ -217,6 +248,9 @@ impl IoUringContext {
             fd,
             offset,
             len: data.len(),
+            peer: None,
+            buf_group: None,
+            multishot: false,
         };
 
         // In production, would create SQE and submit to ring
@@ -244,6 +278,9 @@ impl IoUringContext {
             fd,
             offset: 0,
             len: 0,
+            peer: None,
+            buf_group: None,
+            multishot: false,
         };
 
         // In production, would create SQE and submit to ring
@@ -252,6 +289,130 @@ impl IoUringContext {
         Ok(id)
     }
 
+    /// Submit a datagram send to a peer
+    ///
+    /// # Arguments
+    ///
+    /// * `fd` - UDP socket descriptor
+    /// * `data` - Datagram payload
+    /// * `peer` - Destination address
+    ///
+    /// # Returns
+    ///
+    /// Operation ID for tracking completion
+    pub fn submit_sendmsg(
+        &mut self,
+        fd: RawFd,
+        data: &[u8],
+        peer: SocketAddr,
+    ) -> Result<u64, IoUringError> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let op = PendingOp {
+            id,
+            op_type: OpType::SendMsg,
+            fd,
+            offset: 0,
+            len: data.len(),
+            peer: Some(peer),
+            buf_group: None,
+            multishot: false,
+        };
+
+        // In production, would build a msghdr SQE (IORING_OP_SENDMSG)
+        self.pending.insert(id, op);
+
+        Ok(id)
+    }
+
+    /// Submit a single-shot datagram receive
+    ///
+    /// # Arguments
+    ///
+    /// * `fd` - UDP socket descriptor
+    /// * `len` - Maximum datagram size to accept
+    ///
+    /// # Returns
+    ///
+    /// Operation ID for tracking completion
+    pub fn submit_recvmsg(&mut self, fd: RawFd, len: usize) -> Result<u64, IoUringError> {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let op = PendingOp {
+            id,
+            op_type: OpType::RecvMsg,
+            fd,
+            offset: 0,
+            len,
+            peer: None,
+            buf_group: None,
+            multishot: false,
+        };
+
+        // In production, would build a msghdr SQE (IORING_OP_RECVMSG)
+        self.pending.insert(id, op);
+
+        Ok(id)
+    }
+
+    /// Submit a multishot datagram receive backed by a provided buffer group
+    ///
+    /// A single SQE that yields one completion per incoming datagram and
+    /// re-arms itself until cancelled or the buffer group is exhausted,
+    /// eliminating the submit-per-packet overhead of single-shot receives.
+    /// The group must have been registered with
+    /// [`IoUringContext::register_buffer_group`] first.
+    ///
+    /// # Arguments
+    ///
+    /// * `fd` - UDP socket descriptor
+    /// * `buf_group` - Registered provided-buffer group to receive into
+    ///
+    /// # Returns
+    ///
+    /// Operation ID; completions reference this ID until the op is
+    /// cancelled via [`IoUringContext::cancel`]
+    pub fn submit_recvmsg_multishot(
+        &mut self,
+        fd: RawFd,
+        buf_group: u16,
+    ) -> Result<u64, IoUringError> {
+        let group = self.buffer_groups.get(&buf_group).ok_or_else(|| {
+            IoUringError::Submission(format!("Buffer group {buf_group} not registered"))
+        })?;
+        let buf_len = group.first().map_or(0, Vec::len);
+
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let op = PendingOp {
+            id,
+            op_type: OpType::RecvMsg,
+            fd,
+            offset: 0,
+            len: buf_len,
+            peer: None,
+            buf_group: Some(buf_group),
+            multishot: true,
+        };
+
+        // In production, would set IORING_RECV_MULTISHOT and IOSQE_BUFFER_SELECT
+        self.pending.insert(id, op);
+
+        Ok(id)
+    }
+
+    /// Cancel a pending operation
+    ///
+    /// Primarily used to stop a multishot receive when a worker shuts
+    /// down. Returns `true` if the operation was pending.
+    pub fn cancel(&mut self, id: u64) -> bool {
+        // In production, would submit IORING_OP_ASYNC_CANCEL
+        self.pending.remove(&id).is_some()
+    }
+
     /// Wait for completions
     ///
     /// Blocks until at least `min_complete` operations have completed.
@@ -274,19 +435,28 @@ impl IoUringContext {
         let to_complete: Vec<u64> = self.pending.keys().copied().take(min_complete).collect();
 
         for id in to_complete {
-            if let Some(op) = self.pending.remove(&id) {
-                // Simulate successful completion
-                let result = match op.op_type {
-                    OpType::Read | OpType::Write => op.len as i32,
-                    OpType::Fsync => 0,
-                };
-
-                completions.push(Completion {
-                    id,
-                    result,
-                    op_type: op.op_type,
-                });
+            let Some(op) = self.pending.get(&id) else {
+                continue;
+            };
+
+            // Simulate successful completion
+            let result = match op.op_type {
+                OpType::Read | OpType::Write | OpType::SendMsg | OpType::RecvMsg => op.len as i32,
+                OpType::Fsync => 0,
+            };
+            let op_type = op.op_type;
+
+            // Multishot ops re-arm themselves: the CQE carries IORING_CQE_F_MORE
+            // and the SQE stays live, so only single-shot ops leave the pending set
+            if !op.multishot {
+                self.pending.remove(&id);
             }
+
+            completions.push(Completion {
+                id,
+                result,
+                op_type,
+            });
         }
 
         Ok(completions)
@@ -327,6 +497,32 @@ impl IoUringContext {
         Ok(())
     }
 
+    /// Register a provided buffer group for multishot receives
+    ///
+    /// # Arguments
+    ///
+    /// * `group` - Buffer group ID (referenced by multishot SQEs)
+    /// * `count` - Number of buffers in the group
+    /// * `buf_len` - Size of each buffer (max datagram size)
+    pub fn register_buffer_group(
+        &mut self,
+        group: u16,
+        count: usize,
+        buf_len: usize,
+    ) -> Result<(), IoUringError> {
+        if count == 0 || buf_len == 0 {
+            return Err(IoUringError::Submission(
+                "Buffer group must have at least one non-empty buffer".to_string(),
+            ));
+        }
+
+        // In production, would submit IORING_REGISTER_PBUF_RING
+        let buffers = vec![vec![0u8; buf_len]; count];
+        self.buffer_groups.insert(group, buffers);
+
+        Ok(())
+    }
+
     /// Get queue depth
     #[must_use]
     pub fn queue_depth(&self) -> u32 {
@@ -405,6 +601,41 @@ impl IoUringContext {
         Ok(id)
     }
 
+    /// Submit a datagram send (synchronous fallback)
+    pub fn submit_sendmsg(
+        &mut self,
+        _fd: RawFd,
+        _data: &[u8],
+        _peer: SocketAddr,
+    ) -> Result<u64, IoUringError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    /// Submit a datagram receive (synchronous fallback)
+    pub fn submit_recvmsg(&mut self, _fd: RawFd, _len: usize) -> Result<u64, IoUringError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    /// Submit a multishot datagram receive (synchronous fallback)
+    pub fn submit_recvmsg_multishot(
+        &mut self,
+        _fd: RawFd,
+        _buf_group: u16,
+    ) -> Result<u64, IoUringError> {
+        let id = self.next_id;
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    /// Cancel a pending operation (no-op on non-Linux)
+    pub fn cancel(&mut self, _id: u64) -> bool {
+        false
+    }
+
     /// Wait for completions (synchronous fallback)
     pub fn wait_completions(
         &mut self,
@@ -423,6 +654,16 @@ impl IoUringContext {
         Ok(())
     }
 
+    /// Register a provided buffer group (no-op on non-Linux)
+    pub fn register_buffer_group(
+        &mut self,
+        _group: u16,
+        _count: usize,
+        _buf_len: usize,
+    ) -> Result<(), IoUringError> {
+        Ok(())
+    }
+
     /// Get queue depth
     #[must_use]
     pub fn queue_depth(&self) -> u32 {
@@ -583,6 +824,7 @@ mod tests {
                     OpType::Read => assert!(completion.result > 0),
                     OpType::Write => assert!(completion.result > 0),
                     OpType::Fsync => assert_eq!(completion.result, 0),
+                    OpType::SendMsg | OpType::RecvMsg => unreachable!("no network ops submitted"),
                 }
             }
         }
@@ -691,6 +933,9 @@ mod tests {
             fd: 10,
             offset: 4096,
             len: 1024,
+            peer: None,
+            buf_group: None,
+            multishot: false,
         };
 
         assert_eq!(op.id, 42);
@@ -698,6 +943,8 @@ mod tests {
         assert_eq!(op.fd, 10);
         assert_eq!(op.offset, 4096);
         assert_eq!(op.len, 1024);
+        assert!(op.peer.is_none());
+        assert!(!op.multishot);
     }
 
     #[test]
@@ -722,6 +969,130 @@ mod tests {
 
         assert_ne!(OpType::Read, OpType::Write);
         assert_ne!(OpType::Write, OpType::Fsync);
+        assert_ne!(OpType::SendMsg, OpType::RecvMsg);
+    }
+
+    #[test]
+    fn test_op_type_is_network() {
+        assert!(OpType::SendMsg.is_network());
+        assert!(OpType::RecvMsg.is_network());
+        assert!(!OpType::Read.is_network());
+        assert!(!OpType::Write.is_network());
+        assert!(!OpType::Fsync.is_network());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_submit_sendmsg() {
+        let mut ctx = IoUringContext::new(64).unwrap();
+
+        let peer: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        let id = ctx.submit_sendmsg(5, &[0u8; 1200], peer).unwrap();
+
+        assert_eq!(id, 0);
+        assert_eq!(ctx.pending_count(), 1);
+
+        let completions = ctx.wait_completions(1).unwrap();
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].op_type, OpType::SendMsg);
+        assert_eq!(completions[0].result, 1200);
+        assert_eq!(ctx.pending_count(), 0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_submit_recvmsg() {
+        let mut ctx = IoUringContext::new(64).unwrap();
+
+        let id = ctx.submit_recvmsg(5, 2048).unwrap();
+        assert_eq!(ctx.pending_count(), 1);
+
+        let completions = ctx.wait_completions(1).unwrap();
+        assert_eq!(completions.len(), 1);
+        assert_eq!(completions[0].id, id);
+        assert_eq!(completions[0].op_type, OpType::RecvMsg);
+        assert_eq!(ctx.pending_count(), 0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_mixed_disk_and_network_completions() {
+        let mut ctx = IoUringContext::new(64).unwrap();
+
+        // A single ring carries both disk and network operations
+        let peer: SocketAddr = "127.0.0.1:9000".parse().unwrap();
+        ctx.submit_read(1, 0, 4096).unwrap();
+        ctx.submit_sendmsg(5, &[0u8; 1200], peer).unwrap();
+        ctx.submit_recvmsg(5, 2048).unwrap();
+        ctx.submit_fsync(1).unwrap();
+
+        let completions = ctx.wait_completions(4).unwrap();
+        assert_eq!(completions.len(), 4);
+
+        let network = completions
+            .iter()
+            .filter(|c| c.op_type.is_network())
+            .count();
+        assert_eq!(network, 2);
+        assert_eq!(ctx.pending_count(), 0);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_buffer_group_registration() {
+        let mut ctx = IoUringContext::new(64).unwrap();
+
+        assert!(ctx.register_buffer_group(0, 256, 2048).is_ok());
+
+        // Empty groups are rejected
+        assert!(ctx.register_buffer_group(1, 0, 2048).is_err());
+        assert!(ctx.register_buffer_group(1, 256, 0).is_err());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_multishot_requires_registered_group() {
+        let mut ctx = IoUringContext::new(64).unwrap();
+
+        let result = ctx.submit_recvmsg_multishot(5, 7);
+        assert!(result.is_err());
+
+        ctx.register_buffer_group(7, 64, 2048).unwrap();
+        assert!(ctx.submit_recvmsg_multishot(5, 7).is_ok());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_multishot_persists_across_completions() {
+        let mut ctx = IoUringContext::new(64).unwrap();
+
+        ctx.register_buffer_group(0, 64, 2048).unwrap();
+        let id = ctx.submit_recvmsg_multishot(5, 0).unwrap();
+
+        // Each wait yields a completion, but the op stays armed
+        for _ in 0..3 {
+            let completions = ctx.wait_completions(1).unwrap();
+            assert_eq!(completions.len(), 1);
+            assert_eq!(completions[0].id, id);
+            assert_eq!(completions[0].op_type, OpType::RecvMsg);
+            assert_eq!(ctx.pending_count(), 1);
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cancel_multishot() {
+        let mut ctx = IoUringContext::new(64).unwrap();
+
+        ctx.register_buffer_group(0, 64, 2048).unwrap();
+        let id = ctx.submit_recvmsg_multishot(5, 0).unwrap();
+        assert_eq!(ctx.pending_count(), 1);
+
+        assert!(ctx.cancel(id));
+        assert_eq!(ctx.pending_count(), 0);
+
+        // Cancelling again is a no-op
+        assert!(!ctx.cancel(id));
     }
 
     #[test]
@@ -18,17 +18,35 @@
 // Transport trait and implementations
 pub mod factory;
 pub mod quic;
+pub mod switchable;
 pub mod transport;
 pub mod udp_async;
 
 // Legacy sync UDP transport
 pub mod udp;
 
+// Platform capability probing
+pub mod capabilities;
+
+// DSCP/QoS packet marking
+pub mod dscp;
+
+// UDP segmentation offload (GSO) and receive coalescing (GRO)
+pub mod gso;
+
+// LAN one-to-many distribution over UDP multicast
+pub mod multicast;
+
+// Local address change detection (drives session migration)
+pub mod netwatch;
+
 // Kernel bypass and async I/O
 pub mod buffer_pool;
 pub mod io_uring;
+pub mod io_uring_udp;
 pub mod mtu;
 pub mod numa;
+pub mod steering;
 pub mod worker;
 
 // Re-export BufferPool at crate root for convenience
@@ -38,6 +56,10 @@ pub use buffer_pool::BufferPool;
 #[cfg(target_os = "linux")]
 pub mod af_xdp;
 
+// Privileged XDP loader handoff (split-process startup)
+#[cfg(target_os = "linux")]
+pub mod xdp_loader;
+
 /// Transport configuration
 #[derive(Debug, Clone)]
 pub struct TransportConfig {
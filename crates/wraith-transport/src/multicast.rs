@@ -0,0 +1,522 @@
+//! UDP multicast LAN distribution with NACK-based repair.
+//!
+//! Delivers the same chunked payload to many machines on one network
+//! segment (labs, render farms) in a single pass: the sender multicasts
+//! every chunk once, then marks the end of the round with a DONE packet.
+//! Receivers reply with unicast NACKs listing the chunks they missed, and
+//! the sender re-multicasts only those chunks in further repair rounds.
+//!
+//! Repair is bounded by [`MulticastConfig::repair_rounds`]. A receiver that
+//! is still missing chunks after the final round reports them via
+//! [`MulticastReceiveResult::missing`], so the caller can fetch the
+//! remainder over the ordinary unicast transfer path instead of holding the
+//! whole group hostage to one lossy NIC.
+//!
+//! Datagrams are unauthenticated and unencrypted: this mode is for trusted
+//! LAN segments, and callers are expected to verify the reassembled payload
+//! against a known content hash before use.
+
+use std::collections::BTreeSet;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::time::Duration;
+use tokio::net::UdpSocket;
+use tokio::time::timeout;
+
+/// Magic prefix on every multicast distribution datagram
+const MAGIC: &[u8; 4] = b"WMC1";
+
+/// Datagram type: chunk payload
+const TYPE_DATA: u8 = 0x01;
+
+/// Datagram type: unicast repair request from a receiver
+const TYPE_NACK: u8 = 0x02;
+
+/// Datagram type: end of a send round
+const TYPE_DONE: u8 = 0x03;
+
+/// Fixed header: magic + type + 8-byte session ID
+const HEADER_SIZE: usize = 4 + 1 + 8;
+
+/// Maximum chunk indexes carried in one NACK (fits a 1500-byte MTU)
+const MAX_NACK_INDEXES: usize = 300;
+
+/// Configuration for multicast distribution
+#[derive(Debug, Clone)]
+pub struct MulticastConfig {
+    /// Multicast group address (administratively scoped by default)
+    pub group: Ipv4Addr,
+
+    /// UDP port the group listens on
+    pub port: u16,
+
+    /// Local interface to send/join on (unspecified = routing default)
+    pub interface: Ipv4Addr,
+
+    /// Multicast TTL (1 = do not leave the local segment)
+    pub ttl: u32,
+
+    /// Maximum chunk payload per datagram (keep under the segment MTU)
+    pub chunk_payload: usize,
+
+    /// NACK-based repair rounds after the initial pass
+    pub repair_rounds: u32,
+
+    /// How long the sender waits for NACKs after each round
+    pub nack_window: Duration,
+}
+
+impl Default for MulticastConfig {
+    fn default() -> Self {
+        Self {
+            group: Ipv4Addr::new(239, 87, 82, 84), // 239.x "WRT"
+            port: 8421,
+            interface: Ipv4Addr::UNSPECIFIED,
+            ttl: 1,
+            chunk_payload: 1200,
+            repair_rounds: 3,
+            nack_window: Duration::from_millis(250),
+        }
+    }
+}
+
+impl MulticastConfig {
+    /// Destination address for outgoing datagrams
+    fn group_addr(&self) -> SocketAddr {
+        SocketAddr::V4(SocketAddrV4::new(self.group, self.port))
+    }
+}
+
+/// Outcome of one multicast distribution from the sender's side
+#[derive(Debug, Clone, Default)]
+pub struct DistributionReport {
+    /// Chunks sent in the initial pass
+    pub chunks_sent: usize,
+
+    /// Chunks re-multicast in repair rounds
+    pub repairs_sent: usize,
+
+    /// Rounds used, including the initial pass
+    pub rounds_used: u32,
+}
+
+/// Sender side of a multicast distribution
+pub struct MulticastSender {
+    socket: UdpSocket,
+    config: MulticastConfig,
+}
+
+impl MulticastSender {
+    /// Create a sender for the configured group
+    ///
+    /// Binds an ephemeral port; NACKs come back to it as unicast replies.
+    ///
+    /// # Errors
+    /// Returns an error if the socket cannot be created or configured.
+    pub async fn new(config: MulticastConfig) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(SocketAddrV4::new(config.interface, 0)).await?;
+        socket.set_multicast_ttl_v4(config.ttl)?;
+        socket.set_multicast_loop_v4(true)?;
+        Ok(Self { socket, config })
+    }
+
+    /// Distribute a chunked payload to the group with NACK repair
+    ///
+    /// Multicasts every chunk, then alternates DONE markers and NACK
+    /// windows, re-multicasting only the chunks any receiver reported
+    /// missing. Returns after a NACK window passes with no repair requests
+    /// or when the configured rounds are exhausted.
+    ///
+    /// # Errors
+    /// Returns an error if a send fails (e.g. no multicast route).
+    pub async fn distribute(
+        &self,
+        session_id: [u8; 8],
+        chunks: &[Vec<u8>],
+    ) -> std::io::Result<DistributionReport> {
+        let total =
+            u32::try_from(chunks.len()).map_err(|_| std::io::Error::other("too many chunks"))?;
+        let mut report = DistributionReport {
+            chunks_sent: chunks.len(),
+            ..Default::default()
+        };
+
+        // Initial pass: every chunk once
+        for (index, chunk) in chunks.iter().enumerate() {
+            self.send_chunk(session_id, index as u32, total, chunk)
+                .await?;
+        }
+
+        for round in 0..=self.config.repair_rounds {
+            report.rounds_used = round + 1;
+            self.send_done(session_id, total).await?;
+
+            let missing = self.collect_nacks(session_id).await;
+            if missing.is_empty() {
+                break;
+            }
+            if round == self.config.repair_rounds {
+                tracing::debug!(
+                    "Multicast repair rounds exhausted with {} chunks still NACKed",
+                    missing.len()
+                );
+                break;
+            }
+
+            for index in missing {
+                if let Some(chunk) = chunks.get(index as usize) {
+                    self.send_chunk(session_id, index, total, chunk).await?;
+                    report.repairs_sent += 1;
+                }
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Multicast one chunk
+    async fn send_chunk(
+        &self,
+        session_id: [u8; 8],
+        index: u32,
+        total: u32,
+        chunk: &[u8],
+    ) -> std::io::Result<()> {
+        debug_assert!(chunk.len() <= self.config.chunk_payload);
+        let mut packet = Vec::with_capacity(HEADER_SIZE + 8 + chunk.len());
+        packet.extend_from_slice(MAGIC);
+        packet.push(TYPE_DATA);
+        packet.extend_from_slice(&session_id);
+        packet.extend_from_slice(&index.to_be_bytes());
+        packet.extend_from_slice(&total.to_be_bytes());
+        packet.extend_from_slice(chunk);
+        self.socket
+            .send_to(&packet, self.config.group_addr())
+            .await?;
+        Ok(())
+    }
+
+    /// Multicast an end-of-round marker
+    async fn send_done(&self, session_id: [u8; 8], total: u32) -> std::io::Result<()> {
+        let mut packet = Vec::with_capacity(HEADER_SIZE + 4);
+        packet.extend_from_slice(MAGIC);
+        packet.push(TYPE_DONE);
+        packet.extend_from_slice(&session_id);
+        packet.extend_from_slice(&total.to_be_bytes());
+        self.socket
+            .send_to(&packet, self.config.group_addr())
+            .await?;
+        Ok(())
+    }
+
+    /// Gather NACKed chunk indexes during one NACK window
+    async fn collect_nacks(&self, session_id: [u8; 8]) -> BTreeSet<u32> {
+        let mut missing = BTreeSet::new();
+        let mut buf = vec![0u8; 65536];
+        let deadline = tokio::time::Instant::now() + self.config.nack_window;
+
+        loop {
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if remaining.is_zero() {
+                break;
+            }
+            match timeout(remaining, self.socket.recv_from(&mut buf)).await {
+                Ok(Ok((size, _from))) => {
+                    if let Some((kind, session, body)) = parse_header(&buf[..size])
+                        && kind == TYPE_NACK
+                        && session == session_id
+                    {
+                        missing.extend(parse_nack_indexes(body));
+                    }
+                }
+                Ok(Err(_)) | Err(_) => break,
+            }
+        }
+
+        missing
+    }
+}
+
+/// Result of receiving one multicast distribution
+#[derive(Debug, Clone)]
+pub struct MulticastReceiveResult {
+    /// Received chunks by index (`None` where repair also failed)
+    pub chunks: Vec<Option<Vec<u8>>>,
+}
+
+impl MulticastReceiveResult {
+    /// Whether every chunk arrived
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.chunks.iter().all(Option::is_some)
+    }
+
+    /// Indexes still missing, for unicast fallback
+    #[must_use]
+    pub fn missing(&self) -> Vec<u32> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.is_none())
+            .map(|(i, _)| i as u32)
+            .collect()
+    }
+
+    /// Fraction of chunks that never arrived (0.0 to 1.0)
+    #[must_use]
+    pub fn loss_rate(&self) -> f64 {
+        if self.chunks.is_empty() {
+            return 0.0;
+        }
+        self.missing().len() as f64 / self.chunks.len() as f64
+    }
+}
+
+/// Receiver side of a multicast distribution
+pub struct MulticastReceiver {
+    socket: UdpSocket,
+}
+
+impl MulticastReceiver {
+    /// Join the configured multicast group
+    ///
+    /// Binds the group port with address reuse so several receivers can
+    /// coexist on one machine.
+    ///
+    /// # Errors
+    /// Returns an error if the socket cannot be bound or the group joined.
+    pub fn join(config: MulticastConfig) -> std::io::Result<Self> {
+        let socket = socket2::Socket::new(
+            socket2::Domain::IPV4,
+            socket2::Type::DGRAM,
+            Some(socket2::Protocol::UDP),
+        )?;
+        socket.set_reuse_address(true)?;
+        #[cfg(unix)]
+        socket.set_reuse_port(true)?;
+        socket.bind(&SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, config.port).into())?;
+        socket.join_multicast_v4(&config.group, &config.interface)?;
+        socket.set_nonblocking(true)?;
+
+        let socket = UdpSocket::from_std(socket.into())?;
+        Ok(Self { socket })
+    }
+
+    /// Receive one distribution, NACKing missed chunks after each round
+    ///
+    /// Returns when all chunks arrived, when no datagram is seen for
+    /// `idle_timeout` (sender gone or repair rounds exhausted), or when the
+    /// sender stops answering NACKs. Check
+    /// [`MulticastReceiveResult::missing`] and fetch the rest via unicast
+    /// when the result is incomplete.
+    ///
+    /// # Errors
+    /// Returns an error if receiving or NACKing fails at the socket level.
+    pub async fn receive(
+        &self,
+        session_id: [u8; 8],
+        idle_timeout: Duration,
+    ) -> std::io::Result<MulticastReceiveResult> {
+        let mut chunks: Vec<Option<Vec<u8>>> = Vec::new();
+        let mut buf = vec![0u8; 65536];
+
+        loop {
+            let packet = match timeout(idle_timeout, self.socket.recv_from(&mut buf)).await {
+                Ok(result) => result?,
+                // Sender gone: report whatever arrived
+                Err(_) => break,
+            };
+            let (size, from) = packet;
+            let Some((kind, session, body)) = parse_header(&buf[..size]) else {
+                continue;
+            };
+            if session != session_id {
+                continue;
+            }
+
+            match kind {
+                TYPE_DATA => {
+                    if body.len() < 8 {
+                        continue;
+                    }
+                    let index = u32::from_be_bytes(body[..4].try_into().unwrap()) as usize;
+                    let total = u32::from_be_bytes(body[4..8].try_into().unwrap()) as usize;
+                    if index >= total {
+                        continue;
+                    }
+                    if chunks.len() < total {
+                        chunks.resize(total, None);
+                    }
+                    if chunks[index].is_none() {
+                        chunks[index] = Some(body[8..].to_vec());
+                    }
+                }
+                TYPE_DONE => {
+                    if body.len() < 4 {
+                        continue;
+                    }
+                    let total = u32::from_be_bytes(body[..4].try_into().unwrap()) as usize;
+                    if chunks.len() < total {
+                        chunks.resize(total, None);
+                    }
+                    let result = MulticastReceiveResult {
+                        chunks: chunks.clone(),
+                    };
+                    if result.is_complete() {
+                        break;
+                    }
+                    self.send_nacks(session_id, &result.missing(), from).await?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(MulticastReceiveResult { chunks })
+    }
+
+    /// Send unicast NACKs for the given chunk indexes to the sender
+    async fn send_nacks(
+        &self,
+        session_id: [u8; 8],
+        missing: &[u32],
+        sender: SocketAddr,
+    ) -> std::io::Result<()> {
+        for batch in missing.chunks(MAX_NACK_INDEXES) {
+            let mut packet = Vec::with_capacity(HEADER_SIZE + 2 + 4 * batch.len());
+            packet.extend_from_slice(MAGIC);
+            packet.push(TYPE_NACK);
+            packet.extend_from_slice(&session_id);
+            packet.extend_from_slice(&(batch.len() as u16).to_be_bytes());
+            for index in batch {
+                packet.extend_from_slice(&index.to_be_bytes());
+            }
+            self.socket.send_to(&packet, sender).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Split a datagram into (type, session ID, body), rejecting foreign traffic
+fn parse_header(packet: &[u8]) -> Option<(u8, [u8; 8], &[u8])> {
+    if packet.len() < HEADER_SIZE || &packet[..4] != MAGIC {
+        return None;
+    }
+    let kind = packet[4];
+    let session = packet[5..13].try_into().unwrap();
+    Some((kind, session, &packet[HEADER_SIZE..]))
+}
+
+/// Decode the chunk indexes from a NACK body
+fn parse_nack_indexes(body: &[u8]) -> Vec<u32> {
+    if body.len() < 2 {
+        return Vec::new();
+    }
+    let count = u16::from_be_bytes([body[0], body[1]]) as usize;
+    body[2..]
+        .chunks_exact(4)
+        .take(count)
+        .map(|c| u32::from_be_bytes(c.try_into().unwrap()))
+        .collect()
+}
+
+/// Split a payload into multicast-sized chunks
+#[must_use]
+pub fn chunk_payload(data: &[u8], chunk_size: usize) -> Vec<Vec<u8>> {
+    data.chunks(chunk_size.max(1)).map(<[u8]>::to_vec).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_defaults() {
+        let config = MulticastConfig::default();
+        assert!(config.group.is_multicast());
+        assert_eq!(config.ttl, 1);
+        assert!(config.chunk_payload <= 1400);
+    }
+
+    #[test]
+    fn test_chunk_payload_split() {
+        let data = vec![7u8; 2500];
+        let chunks = chunk_payload(&data, 1000);
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].len(), 1000);
+        assert_eq!(chunks[2].len(), 500);
+    }
+
+    #[test]
+    fn test_parse_header_rejects_foreign_packets() {
+        assert!(parse_header(b"short").is_none());
+        assert!(parse_header(&[0u8; 32]).is_none());
+
+        let mut packet = Vec::new();
+        packet.extend_from_slice(MAGIC);
+        packet.push(TYPE_DATA);
+        packet.extend_from_slice(&[9u8; 8]);
+        packet.extend_from_slice(b"body");
+        let (kind, session, body) = parse_header(&packet).unwrap();
+        assert_eq!(kind, TYPE_DATA);
+        assert_eq!(session, [9u8; 8]);
+        assert_eq!(body, b"body");
+    }
+
+    #[test]
+    fn test_nack_indexes_roundtrip() {
+        let mut body = Vec::new();
+        body.extend_from_slice(&3u16.to_be_bytes());
+        for index in [5u32, 17, 4096] {
+            body.extend_from_slice(&index.to_be_bytes());
+        }
+        assert_eq!(parse_nack_indexes(&body), vec![5, 17, 4096]);
+        assert!(parse_nack_indexes(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_receive_result_missing_and_loss() {
+        let result = MulticastReceiveResult {
+            chunks: vec![Some(vec![1]), None, Some(vec![3]), None],
+        };
+        assert!(!result.is_complete());
+        assert_eq!(result.missing(), vec![1, 3]);
+        assert!((result.loss_rate() - 0.5).abs() < f64::EPSILON);
+
+        let complete = MulticastReceiveResult {
+            chunks: vec![Some(vec![1])],
+        };
+        assert!(complete.is_complete());
+        assert_eq!(complete.loss_rate(), 0.0);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a multicast-capable network interface"]
+    async fn test_multicast_distribute_receive() {
+        let config = MulticastConfig {
+            port: 18421,
+            ..MulticastConfig::default()
+        };
+        let session_id = [42u8; 8];
+        let data: Vec<u8> = (0..5000u32).map(|i| i as u8).collect();
+        let chunks = chunk_payload(&data, config.chunk_payload);
+
+        let receiver = MulticastReceiver::join(config.clone()).unwrap();
+        let receive_task = tokio::spawn(async move {
+            receiver
+                .receive(session_id, Duration::from_secs(2))
+                .await
+                .unwrap()
+        });
+
+        // Give the receiver a moment to join the group
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let sender = MulticastSender::new(config).await.unwrap();
+        let report = sender.distribute(session_id, &chunks).await.unwrap();
+        assert_eq!(report.chunks_sent, chunks.len());
+
+        let result = receive_task.await.unwrap();
+        assert!(result.is_complete());
+        let reassembled: Vec<u8> = result.chunks.into_iter().flatten().flatten().collect();
+        assert_eq!(reassembled, data);
+    }
+}
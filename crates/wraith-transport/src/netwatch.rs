@@ -0,0 +1,310 @@
+//! Local network change detection.
+//!
+//! A mobile host that hops from Wi-Fi to LTE gets a new source address, and
+//! every established session must re-validate its path before traffic flows
+//! again. This module watches the local address set and reports changes so
+//! the session layer can drive migration.
+//!
+//! [`NetworkWatcher`] runs a background thread. On Linux it blocks on a
+//! `NETLINK_ROUTE` socket subscribed to link and address multicast groups,
+//! so a change is noticed the moment the kernel announces it; any netlink
+//! message simply triggers a re-enumeration and diff of the address set. On
+//! other platforms (macOS included, until a SystemConfiguration binding is
+//! warranted) the watcher falls back to diffing the address set on a poll
+//! interval, which catches the same transitions a few seconds later.
+
+use std::collections::BTreeSet;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+/// A change in the local address set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkChange {
+    /// Addresses that appeared since the last snapshot
+    pub added: Vec<IpAddr>,
+    /// Addresses that disappeared since the last snapshot
+    pub removed: Vec<IpAddr>,
+}
+
+/// Enumerate the host's non-loopback unicast addresses
+///
+/// Link-local IPv6 addresses are excluded: they churn with interface state
+/// but are never used as transfer endpoints. Returns an empty set on
+/// platforms without `getifaddrs`.
+#[must_use]
+pub fn local_unicast_addrs() -> BTreeSet<IpAddr> {
+    #[cfg(unix)]
+    {
+        local_unicast_addrs_unix()
+    }
+    #[cfg(not(unix))]
+    {
+        BTreeSet::new()
+    }
+}
+
+#[cfg(unix)]
+fn local_unicast_addrs_unix() -> BTreeSet<IpAddr> {
+    use std::net::{Ipv4Addr, Ipv6Addr};
+
+    let mut addrs = BTreeSet::new();
+    let mut ifap: *mut libc::ifaddrs = std::ptr::null_mut();
+
+    // SAFETY: getifaddrs allocates the list into ifap on success; each node
+    // is walked read-only via its ifa_next link and the list is released
+    // with freeifaddrs before returning.
+    unsafe {
+        if libc::getifaddrs(&raw mut ifap) != 0 {
+            return addrs;
+        }
+
+        let mut cursor = ifap;
+        while !cursor.is_null() {
+            let ifa = &*cursor;
+            if !ifa.ifa_addr.is_null() {
+                match i32::from((*ifa.ifa_addr).sa_family) {
+                    libc::AF_INET => {
+                        let sin = &*(ifa.ifa_addr.cast::<libc::sockaddr_in>());
+                        let ip = Ipv4Addr::from(u32::from_be(sin.sin_addr.s_addr));
+                        if !ip.is_loopback() {
+                            addrs.insert(IpAddr::V4(ip));
+                        }
+                    }
+                    libc::AF_INET6 => {
+                        let sin6 = &*(ifa.ifa_addr.cast::<libc::sockaddr_in6>());
+                        let ip = Ipv6Addr::from(sin6.sin6_addr.s6_addr);
+                        let link_local = (ip.segments()[0] & 0xffc0) == 0xfe80;
+                        if !ip.is_loopback() && !link_local {
+                            addrs.insert(IpAddr::V6(ip));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            cursor = ifa.ifa_next;
+        }
+        libc::freeifaddrs(ifap);
+    }
+
+    addrs
+}
+
+/// Background watcher for local address changes
+///
+/// Spawn with [`NetworkWatcher::spawn`], then drain changes with
+/// [`try_recv`](Self::try_recv) from whatever loop owns migration. The
+/// watcher thread stops when the watcher is dropped.
+pub struct NetworkWatcher {
+    events: mpsc::Receiver<NetworkChange>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl NetworkWatcher {
+    /// Start watching for address changes
+    ///
+    /// `poll_interval` bounds detection latency on platforms without a
+    /// kernel notification source; on Linux it only paces the periodic
+    /// re-check that backs up the netlink subscription.
+    #[must_use]
+    pub fn spawn(poll_interval: Duration) -> Self {
+        let (sender, events) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+
+        let handle = std::thread::Builder::new()
+            .name("wraith-netwatch".to_string())
+            .spawn(move || watch_loop(&sender, &thread_stop, poll_interval))
+            .expect("failed to spawn network watcher thread");
+
+        Self {
+            events,
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    /// Take the next pending change, if any (non-blocking)
+    #[must_use]
+    pub fn try_recv(&self) -> Option<NetworkChange> {
+        self.events.try_recv().ok()
+    }
+}
+
+impl Drop for NetworkWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn watch_loop(sender: &mpsc::Sender<NetworkChange>, stop: &AtomicBool, poll_interval: Duration) {
+    let netlink = NetlinkSocket::open(poll_interval);
+    let mut snapshot = local_unicast_addrs();
+
+    while !stop.load(Ordering::Acquire) {
+        // Block until the kernel reports a change or the interval elapses;
+        // without netlink, just sleep out the interval.
+        match &netlink {
+            Some(socket) => socket.wait_for_event(),
+            None => std::thread::sleep(poll_interval),
+        }
+        if stop.load(Ordering::Acquire) {
+            break;
+        }
+
+        let current = local_unicast_addrs();
+        if current == snapshot {
+            continue;
+        }
+
+        let change = NetworkChange {
+            added: current.difference(&snapshot).copied().collect(),
+            removed: snapshot.difference(&current).copied().collect(),
+        };
+        snapshot = current;
+
+        if sender.send(change).is_err() {
+            // Receiver dropped without the stop flag: shut down anyway
+            break;
+        }
+    }
+}
+
+/// `NETLINK_ROUTE` subscription to link and address multicast groups
+///
+/// Message contents are not parsed: any message on these groups means the
+/// address set may have changed, and re-enumerating is cheap.
+#[cfg(target_os = "linux")]
+struct NetlinkSocket {
+    fd: std::os::fd::OwnedFd,
+}
+
+#[cfg(target_os = "linux")]
+impl NetlinkSocket {
+    const RTMGRP_LINK: u32 = 0x1;
+    const RTMGRP_IPV4_IFADDR: u32 = 0x10;
+    const RTMGRP_IPV6_IFADDR: u32 = 0x100;
+
+    fn open(recv_timeout: Duration) -> Option<Self> {
+        use std::os::fd::FromRawFd;
+
+        // SAFETY: socket/bind/setsockopt are called with a properly zeroed
+        // sockaddr_nl and matching lengths; the fd is wrapped in OwnedFd
+        // immediately after creation so every exit path closes it.
+        unsafe {
+            let fd = libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::NETLINK_ROUTE,
+            );
+            if fd < 0 {
+                return None;
+            }
+            let fd = std::os::fd::OwnedFd::from_raw_fd(fd);
+
+            let mut addr: libc::sockaddr_nl = std::mem::zeroed();
+            addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+            addr.nl_groups =
+                Self::RTMGRP_LINK | Self::RTMGRP_IPV4_IFADDR | Self::RTMGRP_IPV6_IFADDR;
+
+            use std::os::fd::AsRawFd;
+            if libc::bind(
+                fd.as_raw_fd(),
+                (&raw const addr).cast::<libc::sockaddr>(),
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            ) < 0
+            {
+                return None;
+            }
+
+            // Receive timeout doubles as the periodic re-check interval and
+            // lets the watch loop observe its stop flag.
+            let timeout = libc::timeval {
+                tv_sec: recv_timeout.as_secs() as libc::time_t,
+                tv_usec: libc::suseconds_t::from(recv_timeout.subsec_micros()),
+            };
+            if libc::setsockopt(
+                fd.as_raw_fd(),
+                libc::SOL_SOCKET,
+                libc::SO_RCVTIMEO,
+                (&raw const timeout).cast::<libc::c_void>(),
+                std::mem::size_of::<libc::timeval>() as libc::socklen_t,
+            ) < 0
+            {
+                return None;
+            }
+
+            Some(Self { fd })
+        }
+    }
+
+    /// Block until a routing message arrives or the receive timeout expires
+    fn wait_for_event(&self) {
+        use std::os::fd::AsRawFd;
+
+        let mut buf = [0u8; 4096];
+        // SAFETY: buf is a valid writable buffer of the stated length for
+        // the lifetime of the call; the result (message, timeout, or error)
+        // is irrelevant beyond having waited.
+        unsafe {
+            libc::recv(
+                self.fd.as_raw_fd(),
+                buf.as_mut_ptr().cast::<libc::c_void>(),
+                buf.len(),
+                0,
+            );
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+struct NetlinkSocket;
+
+#[cfg(not(target_os = "linux"))]
+impl NetlinkSocket {
+    fn open(_recv_timeout: Duration) -> Option<Self> {
+        None
+    }
+
+    fn wait_for_event(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_unicast_addrs_excludes_loopback() {
+        let addrs = local_unicast_addrs();
+        assert!(addrs.iter().all(|addr| !addr.is_loopback()));
+    }
+
+    #[test]
+    fn test_watcher_spawn_and_drop() {
+        let watcher = NetworkWatcher::spawn(Duration::from_millis(50));
+        // No interface change happens during the test: no events
+        assert!(watcher.try_recv().is_none());
+        drop(watcher);
+    }
+
+    #[test]
+    fn test_watcher_quiet_network_stays_silent() {
+        let watcher = NetworkWatcher::spawn(Duration::from_millis(20));
+        std::thread::sleep(Duration::from_millis(80));
+        assert!(watcher.try_recv().is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_netlink_socket_opens() {
+        // Plain netlink route sockets need no privileges
+        assert!(NetlinkSocket::open(Duration::from_millis(10)).is_some());
+    }
+}
@@ -84,6 +84,114 @@ pub fn get_numa_node_count() -> usize {
     1
 }
 
+/// Get the NUMA node a network interface's device sits on (Linux only)
+///
+/// Reads `/sys/class/net/<interface>/device/numa_node`. Returns None when
+/// the interface does not exist, has no underlying PCI device (loopback,
+/// virtual interfaces), or the kernel reports -1 (no NUMA affinity).
+#[cfg(target_os = "linux")]
+pub fn get_numa_node_for_nic(interface: &str) -> Option<usize> {
+    let path = format!("/sys/class/net/{interface}/device/numa_node");
+    let node: i64 = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    if node < 0 {
+        debug!("NIC {} has no NUMA affinity", interface);
+        return None;
+    }
+    debug!("NIC {} is on NUMA node {}", interface, node);
+    Some(node as usize)
+}
+
+/// Get the NUMA node a network interface's device sits on (non-Linux)
+///
+/// Always returns None on non-Linux platforms.
+#[cfg(not(target_os = "linux"))]
+pub fn get_numa_node_for_nic(_interface: &str) -> Option<usize> {
+    None
+}
+
+/// List the CPU cores on a NUMA node (Linux only)
+///
+/// Parses `/sys/devices/system/node/node<N>/cpulist`. Returns an empty
+/// list if the node does not exist.
+#[cfg(target_os = "linux")]
+pub fn cpus_on_node(node: usize) -> Vec<usize> {
+    let path = format!("/sys/devices/system/node/node{node}/cpulist");
+    match fs::read_to_string(path) {
+        Ok(list) => parse_cpu_list(&list),
+        Err(_) => Vec::new(),
+    }
+}
+
+/// List the CPU cores on a NUMA node (non-Linux)
+///
+/// Always returns an empty list on non-Linux platforms.
+#[cfg(not(target_os = "linux"))]
+pub fn cpus_on_node(_node: usize) -> Vec<usize> {
+    Vec::new()
+}
+
+/// Parse a sysfs cpulist string (e.g. "0-3,8,10-11") into CPU IDs
+#[cfg(target_os = "linux")]
+fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+    for part in list.trim().split(',') {
+        if part.is_empty() {
+            continue;
+        }
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.parse::<usize>(), end.parse::<usize>()) {
+                    cpus.extend(start..=end);
+                }
+            }
+            None => {
+                if let Ok(cpu) = part.parse() {
+                    cpus.push(cpu);
+                }
+            }
+        }
+    }
+    cpus
+}
+
+/// Pin the current thread to a single CPU core (Linux only)
+///
+/// # Errors
+/// Returns an error string if `sched_setaffinity` fails
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpu: usize) -> Result<(), String> {
+    // SAFETY: sched_setaffinity is a standard Linux syscall. cpu_set_t is
+    // properly zero-initialized via mem::zeroed(), and CPU_ZERO/CPU_SET are
+    // standard libc macros. Passing 0 for pid means current thread, and
+    // size is correct for cpu_set_t.
+    unsafe {
+        let mut cpuset: libc::cpu_set_t = std::mem::zeroed();
+        libc::CPU_ZERO(&mut cpuset);
+        libc::CPU_SET(cpu, &mut cpuset);
+
+        let ret = libc::sched_setaffinity(
+            0, // Current thread
+            std::mem::size_of::<libc::cpu_set_t>(),
+            &cpuset,
+        );
+
+        if ret != 0 {
+            Err(format!("sched_setaffinity failed with code {ret}"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Pin the current thread to a single CPU core (non-Linux)
+///
+/// # Errors
+/// Always fails on non-Linux platforms.
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_cpu: usize) -> Result<(), String> {
+    Err("CPU pinning is not supported on this platform".to_string())
+}
+
 /// Allocate memory on a specific NUMA node (Linux only)
 ///
 /// # Arguments
@@ -504,6 +612,57 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_parse_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3"), vec![0, 1, 2, 3]);
+        assert_eq!(parse_cpu_list("0-1,4-5"), vec![0, 1, 4, 5]);
+        assert_eq!(parse_cpu_list("7"), vec![7]);
+        assert_eq!(parse_cpu_list("0,2,4\n"), vec![0, 2, 4]);
+        assert_eq!(parse_cpu_list(""), Vec::<usize>::new());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_cpus_on_node() {
+        // Node 0 exists on NUMA systems; a nonexistent node yields an
+        // empty list rather than an error
+        for cpu in cpus_on_node(0) {
+            assert!(cpu < 4096);
+        }
+        assert!(cpus_on_node(4096).is_empty());
+    }
+
+    #[test]
+    fn test_get_numa_node_for_nic_missing() {
+        assert_eq!(get_numa_node_for_nic("wraith-no-such-nic"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_get_numa_node_for_nic_loopback() {
+        // Loopback has no PCI device, so it reports no NUMA affinity
+        assert_eq!(get_numa_node_for_nic("lo"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_pin_current_thread() {
+        // Pin a scratch thread so the test runner's affinity is untouched
+        std::thread::spawn(|| pin_current_thread(0))
+            .join()
+            .unwrap()
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg(not(target_os = "linux"))]
+    fn test_non_linux_placement_helpers() {
+        assert_eq!(get_numa_node_for_nic("eth0"), None);
+        assert!(cpus_on_node(0).is_empty());
+        assert!(pin_current_thread(0).is_err());
+    }
+
     #[test]
     fn test_numa_allocator_null_handling() {
         let allocator = NumaAllocator::new();
@@ -0,0 +1,484 @@
+//! Receive-side scaling coordination for WRAITH Protocol
+//!
+//! Keeps NIC packet steering aligned with session ownership: each session is
+//! pinned to one worker (thread-per-core model), and this module programs the
+//! hardware so packets for that session's connection ID land on the RX queue
+//! drained by exactly that worker, eliminating cross-core handoffs in the
+//! hot path.
+//!
+//! ## Backends
+//!
+//! - **RSS/ntuple** - ethtool flow steering rules (via ethtool netlink) that
+//!   direct a UDP flow to a specific RX queue
+//! - **XDP map** - a `cid -> queue` BPF map consulted by the XDP steering
+//!   program before redirecting into the AF_XDP socket for that queue
+//! - **Software** - no hardware support; packets are re-dispatched between
+//!   workers in userspace (the cost this module exists to avoid)
+//!
+//! The coordinator tracks which worker owns each connection ID, emits the
+//! corresponding steering rules, and counts misdirected packets so the
+//! effectiveness of the rules is observable.
+
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Raw 8-byte connection ID as it appears in the outer packet header
+pub type RawCid = [u8; 8];
+
+/// Shard a connection ID across `num_workers` workers (FNV-1a)
+///
+/// Deterministic and dependency-free so the XDP steering program,
+/// userspace dispatch, and the worker pool all compute the same owner
+/// for an unpinned flow without coordination.
+///
+/// # Panics
+/// Panics if `num_workers` is zero.
+#[must_use]
+pub fn shard_for_cid(cid: &RawCid, num_workers: usize) -> usize {
+    let mut hash: u64 = 0xCBF2_9CE4_8422_2325;
+    for byte in cid {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01B3);
+    }
+    (hash % num_workers as u64) as usize
+}
+
+/// Steering errors
+#[derive(Debug, Error)]
+pub enum SteeringError {
+    /// Worker index out of range
+    #[error("invalid worker index: {index} (have {num_workers} workers)")]
+    InvalidWorker {
+        /// Requested worker index
+        index: usize,
+        /// Number of workers available
+        num_workers: usize,
+    },
+
+    /// Coordinator has no workers
+    #[error("cannot steer with zero workers")]
+    NoWorkers,
+
+    /// Rule installation failed
+    #[error("failed to install steering rule: {0}")]
+    RuleInstallation(String),
+}
+
+/// Hardware steering backend
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteeringBackend {
+    /// ethtool ntuple/flow-steering rules (RSS)
+    Rss,
+    /// XDP steering map (cid -> queue) consulted before AF_XDP redirect
+    XdpMap,
+    /// No hardware steering; userspace re-dispatch between workers
+    Software,
+}
+
+/// An installed steering rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SteeringRule {
+    /// Connection ID the rule matches
+    pub cid: RawCid,
+    /// RX queue / worker the flow is directed to
+    pub queue: usize,
+    /// Backend the rule was installed through
+    pub backend: SteeringBackend,
+}
+
+/// Per-worker steering statistics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkerSteeringStats {
+    /// Connection IDs currently pinned to this worker
+    pub assigned_cids: usize,
+    /// Packets that arrived on the correct worker
+    pub steered_packets: u64,
+    /// Packets that arrived on the wrong worker (cross-core handoff)
+    pub misdirected_packets: u64,
+}
+
+/// Receive-side scaling coordinator
+///
+/// Owns the `cid -> worker` assignment table and mirrors it into the
+/// configured steering backend. Assignment is deterministic (hash of the
+/// CID) unless a session is explicitly pinned, so both the kernel steering
+/// program and userspace dispatch agree on ownership without coordination.
+pub struct SteeringCoordinator {
+    backend: SteeringBackend,
+    num_workers: usize,
+    /// cid -> owning worker
+    assignments: HashMap<RawCid, usize>,
+    /// Per-worker statistics
+    stats: Vec<WorkerSteeringStats>,
+}
+
+impl SteeringCoordinator {
+    /// Create a coordinator for `num_workers` workers
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SteeringError::NoWorkers`] if `num_workers` is zero.
+    pub fn new(backend: SteeringBackend, num_workers: usize) -> Result<Self, SteeringError> {
+        if num_workers == 0 {
+            return Err(SteeringError::NoWorkers);
+        }
+
+        Ok(Self {
+            backend,
+            num_workers,
+            assignments: HashMap::new(),
+            stats: vec![WorkerSteeringStats::default(); num_workers],
+        })
+    }
+
+    /// Get the steering backend
+    #[must_use]
+    pub fn backend(&self) -> SteeringBackend {
+        self.backend
+    }
+
+    /// Get the number of workers
+    #[must_use]
+    pub fn num_workers(&self) -> usize {
+        self.num_workers
+    }
+
+    /// Default worker for a connection ID (FNV-1a hash of the CID)
+    ///
+    /// Deterministic so the XDP program and userspace compute the same
+    /// answer for unpinned flows.
+    #[must_use]
+    pub fn default_worker(&self, cid: &RawCid) -> usize {
+        shard_for_cid(cid, self.num_workers)
+    }
+
+    /// Assign a connection ID to its default worker and install the rule
+    ///
+    /// Returns the worker index that owns the session. Idempotent: an
+    /// already-assigned CID keeps its worker (including explicit pins).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if rule installation fails.
+    pub fn assign(&mut self, cid: RawCid) -> Result<usize, SteeringError> {
+        if let Some(&worker) = self.assignments.get(&cid) {
+            return Ok(worker);
+        }
+
+        let worker = self.default_worker(&cid);
+        self.install_rule(cid, worker)?;
+        self.assignments.insert(cid, worker);
+        self.stats[worker].assigned_cids += 1;
+
+        Ok(worker)
+    }
+
+    /// Pin a connection ID to a specific worker
+    ///
+    /// Overrides the hash-based default, e.g. after a session migrates to
+    /// a less loaded core. Replaces any existing rule for the CID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the worker index is out of range or rule
+    /// installation fails.
+    pub fn pin(&mut self, cid: RawCid, worker: usize) -> Result<(), SteeringError> {
+        if worker >= self.num_workers {
+            return Err(SteeringError::InvalidWorker {
+                index: worker,
+                num_workers: self.num_workers,
+            });
+        }
+
+        self.install_rule(cid, worker)?;
+
+        if let Some(previous) = self.assignments.insert(cid, worker) {
+            self.stats[previous].assigned_cids -= 1;
+        }
+        self.stats[worker].assigned_cids += 1;
+
+        Ok(())
+    }
+
+    /// Get the worker that owns a connection ID
+    #[must_use]
+    pub fn worker_for(&self, cid: &RawCid) -> Option<usize> {
+        self.assignments.get(cid).copied()
+    }
+
+    /// Remove a connection ID and its steering rule
+    ///
+    /// Returns the worker it was assigned to, if any.
+    pub fn remove(&mut self, cid: &RawCid) -> Option<usize> {
+        // In production, would delete the ntuple rule / XDP map entry
+        let worker = self.assignments.remove(cid)?;
+        self.stats[worker].assigned_cids -= 1;
+        Some(worker)
+    }
+
+    /// Record a received packet and report whether it was steered correctly
+    ///
+    /// `rx_worker` is the worker whose queue the packet arrived on.
+    /// Returns `true` if that worker owns the session; `false` means a
+    /// cross-core handoff is required (counted as misdirected). Unknown
+    /// CIDs are attributed to the receiving worker.
+    pub fn record_rx(&mut self, cid: &RawCid, rx_worker: usize) -> bool {
+        let owner = self.assignments.get(cid).copied().unwrap_or(rx_worker);
+        let correct = owner == rx_worker;
+
+        if rx_worker < self.num_workers {
+            if correct {
+                self.stats[rx_worker].steered_packets += 1;
+            } else {
+                self.stats[rx_worker].misdirected_packets += 1;
+            }
+        }
+
+        correct
+    }
+
+    /// Rebuild all assignments for a new worker count
+    ///
+    /// Called when the worker pool is resized. Explicit pins are reduced
+    /// modulo the new worker count; hash-assigned flows move to their new
+    /// default worker. Returns the number of flows whose worker changed
+    /// (each requires a rule update).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the new worker count is zero or a rule update
+    /// fails.
+    pub fn rebalance(&mut self, num_workers: usize) -> Result<usize, SteeringError> {
+        if num_workers == 0 {
+            return Err(SteeringError::NoWorkers);
+        }
+
+        self.num_workers = num_workers;
+        let mut stats = vec![WorkerSteeringStats::default(); num_workers];
+        let mut moved = 0;
+
+        let cids: Vec<(RawCid, usize)> = self.assignments.iter().map(|(c, w)| (*c, *w)).collect();
+        for (cid, old_worker) in cids {
+            let new_worker = if old_worker < num_workers {
+                old_worker
+            } else {
+                self.default_worker(&cid)
+            };
+
+            if new_worker != old_worker {
+                self.install_rule(cid, new_worker)?;
+                moved += 1;
+            }
+
+            self.assignments.insert(cid, new_worker);
+            stats[new_worker].assigned_cids += 1;
+        }
+
+        self.stats = stats;
+        Ok(moved)
+    }
+
+    /// List currently installed steering rules
+    #[must_use]
+    pub fn rules(&self) -> Vec<SteeringRule> {
+        self.assignments
+            .iter()
+            .map(|(cid, queue)| SteeringRule {
+                cid: *cid,
+                queue: *queue,
+                backend: self.backend,
+            })
+            .collect()
+    }
+
+    /// Get per-worker steering statistics
+    #[must_use]
+    pub fn stats(&self) -> &[WorkerSteeringStats] {
+        &self.stats
+    }
+
+    /// Number of tracked connection IDs
+    #[must_use]
+    pub fn assigned_count(&self) -> usize {
+        self.assignments.len()
+    }
+
+    /// Install a steering rule in the backend
+    fn install_rule(&self, _cid: RawCid, _queue: usize) -> Result<(), SteeringError> {
+        match self.backend {
+            SteeringBackend::Rss => {
+                // In production, would program an ethtool ntuple rule via
+                // ethtool netlink: match the flow's UDP 4-tuple, action =
+                // direct to RX queue `_queue`
+                Ok(())
+            }
+            SteeringBackend::XdpMap => {
+                // In production, would update the steering BPF map
+                // (key = cid, value = queue) consulted by the XDP program
+                // before XDP_REDIRECT into the AF_XDP socket
+                Ok(())
+            }
+            SteeringBackend::Software => {
+                // No hardware rule; dispatch happens in userspace
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cid(byte: u8) -> RawCid {
+        [byte; 8]
+    }
+
+    #[test]
+    fn test_coordinator_creation() {
+        let coordinator = SteeringCoordinator::new(SteeringBackend::Rss, 4).unwrap();
+        assert_eq!(coordinator.num_workers(), 4);
+        assert_eq!(coordinator.backend(), SteeringBackend::Rss);
+        assert_eq!(coordinator.assigned_count(), 0);
+    }
+
+    #[test]
+    fn test_zero_workers_rejected() {
+        assert!(matches!(
+            SteeringCoordinator::new(SteeringBackend::Rss, 0),
+            Err(SteeringError::NoWorkers)
+        ));
+    }
+
+    #[test]
+    fn test_default_worker_deterministic() {
+        let coordinator = SteeringCoordinator::new(SteeringBackend::XdpMap, 8).unwrap();
+
+        for byte in 0..32u8 {
+            let c = cid(byte);
+            let first = coordinator.default_worker(&c);
+            assert!(first < 8);
+            assert_eq!(first, coordinator.default_worker(&c));
+        }
+    }
+
+    #[test]
+    fn test_shard_for_cid_matches_default_worker() {
+        let coordinator = SteeringCoordinator::new(SteeringBackend::Software, 6).unwrap();
+
+        for byte in 0..32u8 {
+            let c = cid(byte);
+            assert_eq!(shard_for_cid(&c, 6), coordinator.default_worker(&c));
+        }
+    }
+
+    #[test]
+    fn test_assign_is_idempotent() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::Rss, 4).unwrap();
+
+        let worker = coordinator.assign(cid(1)).unwrap();
+        assert_eq!(coordinator.assign(cid(1)).unwrap(), worker);
+        assert_eq!(coordinator.assigned_count(), 1);
+        assert_eq!(coordinator.worker_for(&cid(1)), Some(worker));
+    }
+
+    #[test]
+    fn test_pin_overrides_default() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::XdpMap, 4).unwrap();
+
+        coordinator.assign(cid(1)).unwrap();
+        coordinator.pin(cid(1), 3).unwrap();
+
+        assert_eq!(coordinator.worker_for(&cid(1)), Some(3));
+        // Assign after pin keeps the pinned worker
+        assert_eq!(coordinator.assign(cid(1)).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_pin_invalid_worker() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::Rss, 2).unwrap();
+        assert!(matches!(
+            coordinator.pin(cid(1), 5),
+            Err(SteeringError::InvalidWorker {
+                index: 5,
+                num_workers: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn test_remove_clears_assignment() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::Rss, 4).unwrap();
+
+        let worker = coordinator.assign(cid(7)).unwrap();
+        assert_eq!(coordinator.remove(&cid(7)), Some(worker));
+        assert_eq!(coordinator.worker_for(&cid(7)), None);
+        assert_eq!(coordinator.remove(&cid(7)), None);
+        assert_eq!(coordinator.stats()[worker].assigned_cids, 0);
+    }
+
+    #[test]
+    fn test_record_rx_counts_misdirected() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::Software, 4).unwrap();
+
+        coordinator.pin(cid(1), 2).unwrap();
+
+        assert!(coordinator.record_rx(&cid(1), 2));
+        assert!(!coordinator.record_rx(&cid(1), 0));
+        assert!(!coordinator.record_rx(&cid(1), 1));
+
+        assert_eq!(coordinator.stats()[2].steered_packets, 1);
+        assert_eq!(coordinator.stats()[0].misdirected_packets, 1);
+        assert_eq!(coordinator.stats()[1].misdirected_packets, 1);
+    }
+
+    #[test]
+    fn test_record_rx_unknown_cid() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::Rss, 4).unwrap();
+
+        // Unknown CID is attributed to whichever worker received it
+        assert!(coordinator.record_rx(&cid(99), 1));
+        assert_eq!(coordinator.stats()[1].steered_packets, 1);
+    }
+
+    #[test]
+    fn test_rules_reflect_assignments() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::XdpMap, 4).unwrap();
+
+        coordinator.pin(cid(1), 0).unwrap();
+        coordinator.pin(cid(2), 3).unwrap();
+
+        let rules = coordinator.rules();
+        assert_eq!(rules.len(), 2);
+        assert!(rules.iter().all(|r| r.backend == SteeringBackend::XdpMap));
+        assert!(rules.iter().any(|r| r.cid == cid(1) && r.queue == 0));
+        assert!(rules.iter().any(|r| r.cid == cid(2) && r.queue == 3));
+    }
+
+    #[test]
+    fn test_rebalance_shrink_moves_out_of_range_flows() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::Rss, 8).unwrap();
+
+        coordinator.pin(cid(1), 7).unwrap();
+        coordinator.pin(cid(2), 1).unwrap();
+
+        let moved = coordinator.rebalance(2).unwrap();
+        assert_eq!(moved, 1); // Only the flow on worker 7 had to move
+
+        let new_worker = coordinator.worker_for(&cid(1)).unwrap();
+        assert!(new_worker < 2);
+        assert_eq!(coordinator.worker_for(&cid(2)), Some(1));
+
+        let total_assigned: usize = coordinator.stats().iter().map(|s| s.assigned_cids).sum();
+        assert_eq!(total_assigned, 2);
+    }
+
+    #[test]
+    fn test_rebalance_zero_workers_rejected() {
+        let mut coordinator = SteeringCoordinator::new(SteeringBackend::Rss, 4).unwrap();
+        assert!(matches!(
+            coordinator.rebalance(0),
+            Err(SteeringError::NoWorkers)
+        ));
+    }
+}
@@ -0,0 +1,439 @@
+//! Runtime-switchable transport with a fast path and a fallback.
+//!
+//! Wraps two [`Transport`] backends — an accelerated fast path (typically
+//! AF_XDP) and a plain UDP fallback — behind a single `Transport` handle
+//! that sessions hold for their lifetime. The active backend can be
+//! switched at any time without restarting transfers: each send/receive
+//! reads the active path with a single atomic load, so in-flight sessions
+//! migrate transparently on their next I/O operation.
+//!
+//! Switches happen two ways:
+//! - **Manually** via [`SwitchableTransport::switch_to_fallback`] and
+//!   [`SwitchableTransport::switch_to_fast_path`], e.g. from an admin
+//!   command or a capability re-probe.
+//! - **Automatically** when a fast-path operation fails (the XDP program
+//!   was detached, the NIC driver reset): the transport demotes itself to
+//!   the fallback and retries the failed operation there, so the caller
+//!   never observes the fast-path error.
+//!
+//! All transitions are counted and surfaced through both
+//! [`SwitchStats`] and the `path_switches` field of
+//! [`TransportStats`](crate::transport::TransportStats).
+
+use crate::transport::{Transport, TransportError, TransportResult, TransportStats};
+use async_trait::async_trait;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+/// Which backend a [`SwitchableTransport`] is currently using
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportPath {
+    /// The accelerated backend (AF_XDP or similar)
+    FastPath,
+    /// The plain UDP backend
+    Fallback,
+}
+
+/// Counters describing fast-path/fallback transitions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SwitchStats {
+    /// Transitions from the fast path to the fallback (manual + automatic)
+    pub to_fallback: u64,
+    /// Transitions from the fallback back to the fast path
+    pub to_fast_path: u64,
+    /// Automatic demotions triggered by a fast-path I/O failure
+    pub auto_demotions: u64,
+}
+
+impl SwitchStats {
+    /// Total number of path transitions in either direction
+    #[must_use]
+    pub fn total_switches(&self) -> u64 {
+        self.to_fallback + self.to_fast_path
+    }
+}
+
+/// Transport that toggles between a fast path and a fallback at runtime.
+///
+/// Sessions hold an `Arc<SwitchableTransport>` (or `Arc<dyn Transport>`)
+/// and never need to know which backend is active; the hot path pays one
+/// relaxed atomic load per operation, never a lock.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wraith_transport::switchable::SwitchableTransport;
+/// use wraith_transport::transport::Transport;
+/// use wraith_transport::udp_async::AsyncUdpTransport;
+/// use std::net::SocketAddr;
+/// use std::sync::Arc;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let addr: SocketAddr = "127.0.0.1:0".parse()?;
+/// let fast = Arc::new(AsyncUdpTransport::bind(addr).await?);
+/// let fallback = Arc::new(AsyncUdpTransport::bind(addr).await?);
+/// let transport = SwitchableTransport::new(fast, fallback);
+///
+/// // Admin detached the XDP program: drop to UDP without restarting
+/// transport.switch_to_fallback();
+/// transport.send_to(b"still flowing", "127.0.0.1:50000".parse()?).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct SwitchableTransport {
+    fast: Arc<dyn Transport>,
+    fallback: Arc<dyn Transport>,
+    /// True while the fast path is active
+    on_fast_path: AtomicBool,
+    to_fallback: AtomicU64,
+    to_fast_path: AtomicU64,
+    auto_demotions: AtomicU64,
+}
+
+impl SwitchableTransport {
+    /// Create a switchable transport starting on the fast path.
+    ///
+    /// # Arguments
+    /// * `fast` - The accelerated backend, active initially
+    /// * `fallback` - The backend to fall back to (typically plain UDP)
+    #[must_use]
+    pub fn new(fast: Arc<dyn Transport>, fallback: Arc<dyn Transport>) -> Self {
+        Self {
+            fast,
+            fallback,
+            on_fast_path: AtomicBool::new(true),
+            to_fallback: AtomicU64::new(0),
+            to_fast_path: AtomicU64::new(0),
+            auto_demotions: AtomicU64::new(0),
+        }
+    }
+
+    /// Which backend is currently active
+    #[must_use]
+    pub fn active_path(&self) -> TransportPath {
+        if self.on_fast_path.load(Ordering::Relaxed) {
+            TransportPath::FastPath
+        } else {
+            TransportPath::Fallback
+        }
+    }
+
+    /// Switch active I/O to the fallback backend.
+    ///
+    /// Returns `true` if the path changed, `false` if the fallback was
+    /// already active. Safe to call concurrently with in-flight I/O;
+    /// operations that already snapshot the fast path complete there.
+    pub fn switch_to_fallback(&self) -> bool {
+        let changed = self
+            .on_fast_path
+            .compare_exchange(true, false, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok();
+        if changed {
+            self.to_fallback.fetch_add(1, Ordering::Relaxed);
+        }
+        changed
+    }
+
+    /// Switch active I/O back to the fast path.
+    ///
+    /// Returns `true` if the path changed, `false` if the fast path was
+    /// already active. Callers should re-probe fast-path health (e.g.
+    /// re-attach the XDP program) before promoting.
+    pub fn switch_to_fast_path(&self) -> bool {
+        let changed = self
+            .on_fast_path
+            .compare_exchange(false, true, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok();
+        if changed {
+            self.to_fast_path.fetch_add(1, Ordering::Relaxed);
+        }
+        changed
+    }
+
+    /// Snapshot the transition counters
+    #[must_use]
+    pub fn switch_stats(&self) -> SwitchStats {
+        SwitchStats {
+            to_fallback: self.to_fallback.load(Ordering::Relaxed),
+            to_fast_path: self.to_fast_path.load(Ordering::Relaxed),
+            auto_demotions: self.auto_demotions.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Snapshot the active backend for one operation.
+    ///
+    /// Returns the backend and whether it is the fast path, so failures
+    /// can be attributed to the right side.
+    fn active(&self) -> (Arc<dyn Transport>, bool) {
+        if self.on_fast_path.load(Ordering::Relaxed) {
+            (Arc::clone(&self.fast), true)
+        } else {
+            (Arc::clone(&self.fallback), false)
+        }
+    }
+
+    /// Demote to the fallback after a fast-path failure.
+    ///
+    /// Counts the demotion even when another task already flipped the
+    /// path, so operators can see how many operations hit a dead fast
+    /// path before the switch settled.
+    fn demote(&self, error: &TransportError) {
+        self.auto_demotions.fetch_add(1, Ordering::Relaxed);
+        if self.switch_to_fallback() {
+            tracing::warn!("Fast path failed ({error}); switching to fallback transport");
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for SwitchableTransport {
+    async fn send_to(&self, buf: &[u8], addr: SocketAddr) -> TransportResult<usize> {
+        let (transport, is_fast) = self.active();
+        match transport.send_to(buf, addr).await {
+            Ok(sent) => Ok(sent),
+            Err(e) if is_fast => {
+                // Fast path died mid-transfer: demote and retry once on
+                // the fallback so the session never sees the failure
+                self.demote(&e);
+                self.fallback.send_to(buf, addr).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn recv_from(&self, buf: &mut [u8]) -> TransportResult<(usize, SocketAddr)> {
+        let (transport, is_fast) = self.active();
+        match transport.recv_from(buf).await {
+            Ok(received) => Ok(received),
+            Err(e) if is_fast => {
+                self.demote(&e);
+                self.fallback.recv_from(buf).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn send_batch(&self, packets: &[(&[u8], SocketAddr)]) -> TransportResult<usize> {
+        let (transport, is_fast) = self.active();
+        match transport.send_batch(packets).await {
+            Ok(sent) => Ok(sent),
+            Err(e) if is_fast => {
+                self.demote(&e);
+                self.fallback.send_batch(packets).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn recv_batch(&self, bufs: &mut [Vec<u8>]) -> TransportResult<Vec<(usize, SocketAddr)>> {
+        let (transport, is_fast) = self.active();
+        match transport.recv_batch(bufs).await {
+            Ok(results) => Ok(results),
+            Err(e) if is_fast => {
+                self.demote(&e);
+                self.fallback.recv_batch(bufs).await
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    fn local_addr(&self) -> TransportResult<SocketAddr> {
+        self.active().0.local_addr()
+    }
+
+    async fn close(&self) -> TransportResult<()> {
+        // Close both sides: a later switch must not resurrect I/O
+        let fast_result = self.fast.close().await;
+        let fallback_result = self.fallback.close().await;
+        fast_result.and(fallback_result)
+    }
+
+    fn is_closed(&self) -> bool {
+        self.fast.is_closed() && self.fallback.is_closed()
+    }
+
+    fn stats(&self) -> TransportStats {
+        // Aggregate both backends: bytes sent before a switch still count
+        let fast = self.fast.stats();
+        let fallback = self.fallback.stats();
+        let switches = self.switch_stats();
+        TransportStats {
+            bytes_sent: fast.bytes_sent + fallback.bytes_sent,
+            bytes_received: fast.bytes_received + fallback.bytes_received,
+            packets_sent: fast.packets_sent + fallback.packets_sent,
+            packets_received: fast.packets_received + fallback.packets_received,
+            send_errors: fast.send_errors + fallback.send_errors,
+            recv_errors: fast.recv_errors + fallback.recv_errors,
+            path_switches: switches.total_switches(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::udp_async::AsyncUdpTransport;
+    use std::time::Duration;
+    use tokio::time::timeout;
+
+    async fn udp_pair() -> (Arc<AsyncUdpTransport>, Arc<AsyncUdpTransport>) {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let fast = Arc::new(AsyncUdpTransport::bind(addr).await.unwrap());
+        let fallback = Arc::new(AsyncUdpTransport::bind(addr).await.unwrap());
+        (fast, fallback)
+    }
+
+    #[tokio::test]
+    async fn test_starts_on_fast_path() {
+        let (fast, fallback) = udp_pair().await;
+        let fast_addr = fast.local_addr().unwrap();
+        let transport = SwitchableTransport::new(fast, fallback);
+
+        assert_eq!(transport.active_path(), TransportPath::FastPath);
+        assert_eq!(transport.local_addr().unwrap(), fast_addr);
+        assert_eq!(transport.switch_stats(), SwitchStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_manual_switch_changes_sending_socket() {
+        let (fast, fallback) = udp_pair().await;
+        let fast_addr = fast.local_addr().unwrap();
+        let fallback_addr = fallback.local_addr().unwrap();
+        let transport = SwitchableTransport::new(fast, fallback);
+
+        let peer = AsyncUdpTransport::bind("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let peer_addr = peer.local_addr().unwrap();
+        let mut buf = vec![0u8; 64];
+
+        transport.send_to(b"via fast", peer_addr).await.unwrap();
+        let (_, from) = timeout(Duration::from_secs(1), peer.recv_from(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(from, fast_addr);
+
+        assert!(transport.switch_to_fallback());
+        assert_eq!(transport.active_path(), TransportPath::Fallback);
+
+        transport.send_to(b"via fallback", peer_addr).await.unwrap();
+        let (_, from) = timeout(Duration::from_secs(1), peer.recv_from(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(from, fallback_addr);
+    }
+
+    #[tokio::test]
+    async fn test_recv_follows_active_path() {
+        let (fast, fallback) = udp_pair().await;
+        let fallback_addr = fallback.local_addr().unwrap();
+        let transport = SwitchableTransport::new(fast, fallback);
+        transport.switch_to_fallback();
+
+        let peer = AsyncUdpTransport::bind("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        peer.send_to(b"hello", fallback_addr).await.unwrap();
+
+        let mut buf = vec![0u8; 64];
+        let (size, _) = timeout(Duration::from_secs(1), transport.recv_from(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&buf[..size], b"hello");
+    }
+
+    #[tokio::test]
+    async fn test_switch_is_idempotent() {
+        let (fast, fallback) = udp_pair().await;
+        let transport = SwitchableTransport::new(fast, fallback);
+
+        assert!(transport.switch_to_fallback());
+        assert!(!transport.switch_to_fallback());
+        assert!(transport.switch_to_fast_path());
+        assert!(!transport.switch_to_fast_path());
+
+        let stats = transport.switch_stats();
+        assert_eq!(stats.to_fallback, 1);
+        assert_eq!(stats.to_fast_path, 1);
+        assert_eq!(stats.auto_demotions, 0);
+        assert_eq!(stats.total_switches(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_auto_demotion_on_fast_path_failure() {
+        let (fast, fallback) = udp_pair().await;
+        // Simulate the XDP program being detached: the fast path errors
+        fast.close().await.unwrap();
+        let transport = SwitchableTransport::new(fast, fallback);
+
+        let peer = AsyncUdpTransport::bind("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let peer_addr = peer.local_addr().unwrap();
+
+        // The send succeeds despite the dead fast path
+        transport.send_to(b"rerouted", peer_addr).await.unwrap();
+        assert_eq!(transport.active_path(), TransportPath::Fallback);
+
+        let mut buf = vec![0u8; 64];
+        let (size, _) = timeout(Duration::from_secs(1), peer.recv_from(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&buf[..size], b"rerouted");
+
+        let stats = transport.switch_stats();
+        assert_eq!(stats.auto_demotions, 1);
+        assert_eq!(stats.to_fallback, 1);
+    }
+
+    #[tokio::test]
+    async fn test_fallback_failure_is_not_retried() {
+        let (fast, fallback) = udp_pair().await;
+        fallback.close().await.unwrap();
+        let transport = SwitchableTransport::new(fast, fallback);
+        transport.switch_to_fallback();
+
+        let peer_addr: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+        let result = transport.send_to(b"nowhere", peer_addr).await;
+        assert!(matches!(result, Err(TransportError::Closed)));
+        assert_eq!(transport.switch_stats().auto_demotions, 0);
+    }
+
+    #[tokio::test]
+    async fn test_stats_aggregate_both_paths() {
+        let (fast, fallback) = udp_pair().await;
+        let transport = SwitchableTransport::new(fast, fallback);
+
+        let peer = AsyncUdpTransport::bind("127.0.0.1:0".parse::<SocketAddr>().unwrap())
+            .await
+            .unwrap();
+        let peer_addr = peer.local_addr().unwrap();
+
+        transport.send_to(b"one", peer_addr).await.unwrap();
+        transport.switch_to_fallback();
+        transport.send_to(b"two", peer_addr).await.unwrap();
+
+        let stats = transport.stats();
+        assert_eq!(stats.packets_sent, 2);
+        assert_eq!(stats.bytes_sent, 6);
+        assert_eq!(stats.path_switches, 1);
+    }
+
+    #[tokio::test]
+    async fn test_close_closes_both_backends() {
+        let (fast, fallback) = udp_pair().await;
+        let transport =
+            SwitchableTransport::new(Arc::clone(&fast) as _, Arc::clone(&fallback) as _);
+
+        assert!(!transport.is_closed());
+        transport.close().await.unwrap();
+        assert!(transport.is_closed());
+        assert!(fast.is_closed());
+        assert!(fallback.is_closed());
+    }
+}
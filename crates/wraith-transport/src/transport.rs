@@ -114,6 +114,48 @@ pub trait Transport: Send + Sync {
     /// Check if the transport is closed.
     fn is_closed(&self) -> bool;
 
+    /// Send a batch of datagrams.
+    ///
+    /// The default implementation loops over [`send_to`](Self::send_to);
+    /// transports with kernel batching (`sendmmsg`) submit the whole
+    /// batch in one syscall. Returns the number of packets sent, which
+    /// may be less than `packets.len()` on a partial send — callers
+    /// resubmit the tail.
+    ///
+    /// # Errors
+    /// Returns `TransportError` only when no packet of the batch was sent
+    async fn send_batch(&self, packets: &[(&[u8], SocketAddr)]) -> TransportResult<usize> {
+        let mut sent = 0;
+        for (buf, addr) in packets {
+            match self.send_to(buf, *addr).await {
+                Ok(_) => sent += 1,
+                Err(e) if sent == 0 => return Err(e),
+                Err(_) => break,
+            }
+        }
+        Ok(sent)
+    }
+
+    /// Receive a batch of datagrams, one per buffer in `bufs`.
+    ///
+    /// Waits for at least one datagram, then returns as many as are
+    /// immediately available (up to `bufs.len()`), as `(length, sender)`
+    /// pairs matching the filled buffer order. The default implementation
+    /// receives a single datagram; transports with kernel batching
+    /// (`recvmmsg`) drain the socket queue in one syscall.
+    ///
+    /// # Errors
+    /// Returns `TransportError` if the receive fails
+    async fn recv_batch(&self, bufs: &mut [Vec<u8>]) -> TransportResult<Vec<(usize, SocketAddr)>> {
+        match bufs.first_mut() {
+            None => Ok(Vec::new()),
+            Some(buf) => {
+                let (size, addr) = self.recv_from(buf).await?;
+                Ok(vec![(size, addr)])
+            }
+        }
+    }
+
     /// Get transport statistics (optional).
     ///
     /// Returns transport-specific statistics like bytes sent/received,
@@ -138,6 +180,8 @@ pub struct TransportStats {
     pub send_errors: u64,
     /// Receive errors
     pub recv_errors: u64,
+    /// Fast-path/fallback transitions (switchable transports only)
+    pub path_switches: u64,
 }
 
 impl TransportStats {
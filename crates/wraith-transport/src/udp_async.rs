@@ -46,6 +46,12 @@ pub struct AsyncUdpTransport {
     packets_received: Arc<AtomicU64>,
     send_errors: Arc<AtomicU64>,
     recv_errors: Arc<AtomicU64>,
+    /// Configured GSO segment size (0 = segmentation not configured)
+    gso_segment: Arc<AtomicU64>,
+    /// Kernel accepted UDP_SEGMENT: large sends are split by the kernel
+    gso_offload: Arc<AtomicBool>,
+    /// Kernel accepted UDP_GRO: receives may be coalesced
+    gro_enabled: Arc<AtomicBool>,
 }
 
 impl AsyncUdpTransport {
@@ -84,6 +90,14 @@ impl AsyncUdpTransport {
             socket2::Socket::new(domain, socket2::Type::DGRAM, Some(socket2::Protocol::UDP))
                 .map_err(|e| TransportError::BindFailed(e.to_string()))?;
 
+        // IPv6 binds are dual-stack: clear IPV6_V6ONLY so a single socket
+        // bound to [::] also receives IPv4 traffic (as v4-mapped addresses)
+        if addr.is_ipv6() {
+            socket2
+                .set_only_v6(false)
+                .map_err(|e| TransportError::BindFailed(e.to_string()))?;
+        }
+
         // Set buffer sizes for high-throughput operation
         socket2
             .set_recv_buffer_size(2 * 1024 * 1024)
@@ -114,9 +128,27 @@ impl AsyncUdpTransport {
             packets_received: Arc::new(AtomicU64::new(0)),
             send_errors: Arc::new(AtomicU64::new(0)),
             recv_errors: Arc::new(AtomicU64::new(0)),
+            gso_segment: Arc::new(AtomicU64::new(0)),
+            gso_offload: Arc::new(AtomicBool::new(false)),
+            gro_enabled: Arc::new(AtomicBool::new(false)),
         })
     }
 
+    /// Set the DSCP marking for outgoing packets.
+    ///
+    /// Writes the code point into the IP TOS / traffic-class byte so QoS
+    /// infrastructure can classify WRAITH traffic (e.g. AF21 for bulk
+    /// transfers, EF for latency-sensitive control traffic). Applies to
+    /// every packet sent from this socket.
+    ///
+    /// # Errors
+    /// Returns `TransportError` if the socket option cannot be set
+    pub fn set_dscp(&self, dscp: crate::dscp::Dscp) -> TransportResult<()> {
+        let is_ipv4 = self.socket.local_addr()?.is_ipv4();
+        crate::dscp::set_socket_dscp(&*self.socket, is_ipv4, dscp)?;
+        Ok(())
+    }
+
     /// Create from an existing Tokio UdpSocket.
     ///
     /// # Arguments
@@ -132,8 +164,171 @@ impl AsyncUdpTransport {
             packets_received: Arc::new(AtomicU64::new(0)),
             send_errors: Arc::new(AtomicU64::new(0)),
             recv_errors: Arc::new(AtomicU64::new(0)),
+            gso_segment: Arc::new(AtomicU64::new(0)),
+            gso_offload: Arc::new(AtomicBool::new(false)),
+            gro_enabled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Enable UDP segmentation offload (GSO) for this socket.
+    ///
+    /// After this, [`send_segmented`](Self::send_segmented) moves up to
+    /// 64KB per syscall: the kernel splits the buffer into
+    /// `segment_size`-byte datagrams. Returns whether the kernel
+    /// accepted the offload; on older kernels (`Ok(false)`) the segment
+    /// size is still recorded and `send_segmented` degrades to one
+    /// syscall per segment.
+    ///
+    /// # Errors
+    /// Returns `TransportError` if `segment_size` is zero or the socket
+    /// option fails for a reason other than missing kernel support
+    pub fn enable_gso(&self, segment_size: u16) -> TransportResult<bool> {
+        if segment_size == 0 {
+            return Err(TransportError::InvalidConfig(
+                "GSO segment size must be non-zero".to_string(),
+            ));
+        }
+        self.gso_segment
+            .store(u64::from(segment_size), Ordering::Relaxed);
+
+        #[cfg(target_os = "linux")]
+        {
+            match crate::gso::set_udp_segment(&*self.socket, segment_size) {
+                Ok(()) => {
+                    self.gso_offload.store(true, Ordering::Relaxed);
+                    Ok(true)
+                }
+                Err(e) if crate::gso::is_unsupported(&e) => Ok(false),
+                Err(e) => Err(TransportError::Io(e)),
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Ok(false)
+        }
+    }
+
+    /// Enable UDP receive coalescing (GRO) for this socket.
+    ///
+    /// After this, [`recv_segmented`](Self::recv_segmented) may return a
+    /// single buffer holding several coalesced datagrams together with
+    /// their segment size. Returns whether the kernel accepted the
+    /// option; `Ok(false)` means receives stay per-datagram.
+    ///
+    /// # Errors
+    /// Returns `TransportError` if the socket option fails for a reason
+    /// other than missing kernel support
+    pub fn enable_gro(&self) -> TransportResult<bool> {
+        #[cfg(target_os = "linux")]
+        {
+            match crate::gso::set_udp_gro(&*self.socket, true) {
+                Ok(()) => {
+                    self.gro_enabled.store(true, Ordering::Relaxed);
+                    Ok(true)
+                }
+                Err(e) if crate::gso::is_unsupported(&e) => Ok(false),
+                Err(e) => Err(TransportError::Io(e)),
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Ok(false)
+        }
+    }
+
+    /// The configured GSO segment size, if [`enable_gso`](Self::enable_gso)
+    /// has been called
+    #[must_use]
+    pub fn gso_segment_size(&self) -> Option<u16> {
+        match self.gso_segment.load(Ordering::Relaxed) {
+            0 => None,
+            size => Some(size as u16),
         }
     }
+
+    /// Send a buffer as a train of equal-size datagrams.
+    ///
+    /// With GSO offload active, the whole buffer (up to
+    /// [`MAX_GSO_DATAGRAM`](crate::gso::MAX_GSO_DATAGRAM) bytes) goes to
+    /// the kernel in one syscall and is split there. Without offload the
+    /// buffer is split in userspace and sent segment by segment, so
+    /// callers get identical on-the-wire behavior either way. Buffers no
+    /// larger than one segment — and sockets with no GSO configured —
+    /// use a plain [`send_to`](Transport::send_to).
+    ///
+    /// # Errors
+    /// Returns `TransportError` if the buffer exceeds the GSO limit or a
+    /// send fails
+    pub async fn send_segmented(&self, buf: &[u8], addr: SocketAddr) -> TransportResult<usize> {
+        let segment = self.gso_segment.load(Ordering::Relaxed) as usize;
+        if segment == 0 || buf.len() <= segment {
+            return self.send_to(buf, addr).await;
+        }
+        if buf.len() > crate::gso::MAX_GSO_DATAGRAM {
+            return Err(TransportError::InvalidConfig(format!(
+                "GSO send of {} bytes exceeds the {} byte limit",
+                buf.len(),
+                crate::gso::MAX_GSO_DATAGRAM
+            )));
+        }
+
+        if self.gso_offload.load(Ordering::Relaxed) {
+            self.send_to(buf, addr).await
+        } else {
+            let mut total = 0;
+            for chunk in buf.chunks(segment) {
+                total += self.send_to(chunk, addr).await?;
+            }
+            Ok(total)
+        }
+    }
+
+    /// Receive one buffer that may hold several GRO-coalesced datagrams.
+    ///
+    /// Returns the byte count, the segment size when the kernel
+    /// coalesced multiple datagrams (`None` for a single datagram), and
+    /// the sender address. Without GRO enabled this behaves exactly like
+    /// [`recv_from`](Transport::recv_from).
+    ///
+    /// # Errors
+    /// Returns `TransportError` if the receive fails or the transport is
+    /// closed
+    pub async fn recv_segmented(
+        &self,
+        buf: &mut [u8],
+    ) -> TransportResult<(usize, Option<usize>, SocketAddr)> {
+        #[cfg(target_os = "linux")]
+        if self.gro_enabled.load(Ordering::Relaxed) {
+            if self.closed.load(Ordering::Relaxed) {
+                return Err(TransportError::Closed);
+            }
+            let fd = {
+                use std::os::fd::AsRawFd;
+                self.socket.as_raw_fd()
+            };
+            let result = self
+                .socket
+                .async_io(tokio::io::Interest::READABLE, || {
+                    crate::gso::recvmsg_gro(fd, buf)
+                })
+                .await;
+            return match result {
+                Ok((size, segment, addr)) => {
+                    self.bytes_received
+                        .fetch_add(size as u64, Ordering::Relaxed);
+                    self.packets_received.fetch_add(1, Ordering::Relaxed);
+                    Ok((size, segment, addr))
+                }
+                Err(e) => {
+                    self.recv_errors.fetch_add(1, Ordering::Relaxed);
+                    Err(TransportError::Io(e))
+                }
+            };
+        }
+
+        let (size, addr) = self.recv_from(buf).await?;
+        Ok((size, None, addr))
+    }
 }
 
 #[async_trait]
@@ -175,6 +370,98 @@ impl Transport for AsyncUdpTransport {
         }
     }
 
+    async fn send_batch(&self, packets: &[(&[u8], SocketAddr)]) -> TransportResult<usize> {
+        if self.closed.load(Ordering::Relaxed) {
+            return Err(TransportError::Closed);
+        }
+        if packets.is_empty() {
+            return Ok(0);
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            let fd = {
+                use std::os::fd::AsRawFd;
+                self.socket.as_raw_fd()
+            };
+            let result = self
+                .socket
+                .async_io(tokio::io::Interest::WRITABLE, || {
+                    crate::udp::mmsg::send_batch(fd, packets)
+                })
+                .await;
+            match result {
+                Ok(sent) => {
+                    for (buf, _) in &packets[..sent] {
+                        self.bytes_sent
+                            .fetch_add(buf.len() as u64, Ordering::Relaxed);
+                    }
+                    self.packets_sent.fetch_add(sent as u64, Ordering::Relaxed);
+                    Ok(sent)
+                }
+                Err(e) => {
+                    self.send_errors.fetch_add(1, Ordering::Relaxed);
+                    Err(TransportError::Io(e))
+                }
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let mut sent = 0;
+            for (buf, addr) in packets {
+                match self.send_to(buf, *addr).await {
+                    Ok(_) => sent += 1,
+                    Err(e) if sent == 0 => return Err(e),
+                    Err(_) => break,
+                }
+            }
+            Ok(sent)
+        }
+    }
+
+    async fn recv_batch(&self, bufs: &mut [Vec<u8>]) -> TransportResult<Vec<(usize, SocketAddr)>> {
+        if bufs.is_empty() {
+            return Ok(Vec::new());
+        }
+        if self.closed.load(Ordering::Relaxed) {
+            return Err(TransportError::Closed);
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            let fd = {
+                use std::os::fd::AsRawFd;
+                self.socket.as_raw_fd()
+            };
+            let result = self
+                .socket
+                .async_io(tokio::io::Interest::READABLE, || {
+                    crate::udp::mmsg::recv_batch(fd, bufs)
+                })
+                .await;
+            match result {
+                Ok(results) => {
+                    for (size, _) in &results {
+                        self.bytes_received
+                            .fetch_add(*size as u64, Ordering::Relaxed);
+                    }
+                    self.packets_received
+                        .fetch_add(results.len() as u64, Ordering::Relaxed);
+                    Ok(results)
+                }
+                Err(e) => {
+                    self.recv_errors.fetch_add(1, Ordering::Relaxed);
+                    Err(TransportError::Io(e))
+                }
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let (size, addr) = self.recv_from(&mut bufs[0]).await?;
+            Ok(vec![(size, addr)])
+        }
+    }
+
     fn local_addr(&self) -> TransportResult<SocketAddr> {
         self.socket.local_addr().map_err(TransportError::Io)
     }
@@ -196,6 +483,7 @@ impl Transport for AsyncUdpTransport {
             packets_received: self.packets_received.load(Ordering::Relaxed),
             send_errors: self.send_errors.load(Ordering::Relaxed),
             recv_errors: self.recv_errors.load(Ordering::Relaxed),
+            path_switches: 0,
         }
     }
 }
@@ -243,6 +531,37 @@ mod tests {
         assert_eq!(from, client.local_addr().unwrap());
     }
 
+    #[tokio::test]
+    async fn test_udp_dual_stack_recv() {
+        // An IPv6 wildcard bind also receives IPv4 traffic
+        let addr: SocketAddr = "[::]:0".parse().unwrap();
+        let Ok(server) = AsyncUdpTransport::bind(addr).await else {
+            // Host without IPv6 support
+            return;
+        };
+        let server_port = server.local_addr().unwrap().port();
+
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let client = AsyncUdpTransport::bind(addr).await.unwrap();
+        client
+            .send_to(
+                b"dual-stack",
+                format!("127.0.0.1:{server_port}").parse().unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let mut buf = vec![0u8; 1500];
+        let (size, from) = timeout(Duration::from_secs(1), server.recv_from(&mut buf))
+            .await
+            .expect("Timeout")
+            .unwrap();
+
+        assert_eq!(&buf[..size], b"dual-stack");
+        // IPv4 senders appear as v4-mapped IPv6 addresses
+        assert_eq!(from.port(), client.local_addr().unwrap().port());
+    }
+
     #[tokio::test]
     async fn test_udp_stats() {
         let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
@@ -484,4 +803,160 @@ mod tests {
         let result = transport.recv_from(&mut buf).await;
         assert!(matches!(result, Err(TransportError::Closed)));
     }
+
+    #[tokio::test]
+    async fn test_async_batch_roundtrip() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let sender = AsyncUdpTransport::bind(addr).await.unwrap();
+        let receiver = AsyncUdpTransport::bind(addr).await.unwrap();
+        let receiver_addr = receiver.local_addr().unwrap();
+
+        let payloads: Vec<Vec<u8>> = (0..4).map(|i| format!("Async {i}").into_bytes()).collect();
+        let packets: Vec<(&[u8], SocketAddr)> = payloads
+            .iter()
+            .map(|p| (p.as_slice(), receiver_addr))
+            .collect();
+
+        let sent = sender.send_batch(&packets).await.unwrap();
+        assert_eq!(sent, 4);
+        assert_eq!(sender.stats().packets_sent, 4);
+
+        let mut received = Vec::new();
+        let mut bufs = vec![vec![0u8; 1500]; 8];
+        while received.len() < 4 {
+            let results = timeout(Duration::from_secs(1), receiver.recv_batch(&mut bufs))
+                .await
+                .expect("Timeout waiting for batch")
+                .unwrap();
+            assert!(!results.is_empty());
+            for (i, (size, _)) in results.iter().enumerate() {
+                received.push(bufs[i][..*size].to_vec());
+            }
+        }
+
+        for payload in &payloads {
+            assert!(received.contains(payload));
+        }
+        assert_eq!(receiver.stats().packets_received, 4);
+    }
+
+    #[tokio::test]
+    async fn test_batch_after_close() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let transport = AsyncUdpTransport::bind(addr).await.unwrap();
+        transport.close().await.unwrap();
+
+        let result = transport
+            .send_batch(&[(b"late".as_slice(), "127.0.0.1:50000".parse().unwrap())])
+            .await;
+        assert!(matches!(result, Err(TransportError::Closed)));
+
+        let mut bufs = vec![vec![0u8; 64]];
+        let result = transport.recv_batch(&mut bufs).await;
+        assert!(matches!(result, Err(TransportError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn test_enable_gso_rejects_zero_segment() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let transport = AsyncUdpTransport::bind(addr).await.unwrap();
+
+        assert!(matches!(
+            transport.enable_gso(0),
+            Err(TransportError::InvalidConfig(_))
+        ));
+        assert_eq!(transport.gso_segment_size(), None);
+    }
+
+    #[tokio::test]
+    async fn test_send_segmented_without_gso_is_plain_send() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let sender = AsyncUdpTransport::bind(addr).await.unwrap();
+        let receiver = AsyncUdpTransport::bind(addr).await.unwrap();
+        let receiver_addr = receiver.local_addr().unwrap();
+
+        let sent = sender
+            .send_segmented(b"unsegmented", receiver_addr)
+            .await
+            .unwrap();
+        assert_eq!(sent, 11);
+
+        let mut buf = vec![0u8; 1500];
+        let (size, _) = timeout(Duration::from_secs(1), receiver.recv_from(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&buf[..size], b"unsegmented");
+        assert_eq!(sender.stats().packets_sent, 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_segmented_splits_into_datagrams() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let sender = AsyncUdpTransport::bind(addr).await.unwrap();
+        let receiver = AsyncUdpTransport::bind(addr).await.unwrap();
+        let receiver_addr = receiver.local_addr().unwrap();
+
+        sender.enable_gso(1200).unwrap();
+        assert_eq!(sender.gso_segment_size(), Some(1200));
+
+        // 3 full segments: arrives as 3 datagrams whether the kernel or
+        // the software fallback does the splitting
+        let payload = vec![0xABu8; 3600];
+        let sent = sender
+            .send_segmented(&payload, receiver_addr)
+            .await
+            .unwrap();
+        assert_eq!(sent, 3600);
+
+        let mut buf = vec![0u8; 1500];
+        let mut received = 0;
+        for _ in 0..3 {
+            let (size, _) = timeout(Duration::from_secs(1), receiver.recv_from(&mut buf))
+                .await
+                .expect("Timeout waiting for segment")
+                .unwrap();
+            assert_eq!(size, 1200);
+            received += size;
+        }
+        assert_eq!(received, 3600);
+    }
+
+    #[tokio::test]
+    async fn test_send_segmented_rejects_oversized_buffer() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let sender = AsyncUdpTransport::bind(addr).await.unwrap();
+        sender.enable_gso(1200).unwrap();
+
+        let payload = vec![0u8; crate::gso::MAX_GSO_DATAGRAM + 1];
+        let result = sender
+            .send_segmented(&payload, "127.0.0.1:50000".parse().unwrap())
+            .await;
+        assert!(matches!(result, Err(TransportError::InvalidConfig(_))));
+    }
+
+    #[tokio::test]
+    async fn test_recv_segmented_single_datagram() {
+        let addr: SocketAddr = "127.0.0.1:0".parse().unwrap();
+        let sender = AsyncUdpTransport::bind(addr).await.unwrap();
+        let receiver = AsyncUdpTransport::bind(addr).await.unwrap();
+        let receiver_addr = receiver.local_addr().unwrap();
+        let sender_addr = sender.local_addr().unwrap();
+
+        // GRO enabled (where supported) or not, a single small datagram
+        // arrives uncoalesced with no segment size
+        let _ = receiver.enable_gro().unwrap();
+        sender.send_to(b"solo", receiver_addr).await.unwrap();
+
+        let mut buf = vec![0u8; 1500];
+        let (size, segment, from) =
+            timeout(Duration::from_secs(1), receiver.recv_segmented(&mut buf))
+                .await
+                .unwrap()
+                .unwrap();
+        assert_eq!(&buf[..size], b"solo");
+        assert_eq!(segment, None);
+        assert_eq!(from, sender_addr);
+        assert_eq!(receiver.stats().packets_received, 1);
+    }
 }
@@ -10,13 +10,119 @@
 //! Target: >95% CPU utilization, scales to 16+ cores
 
 use crate::buffer_pool::BufferPool;
-use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, bounded};
+use crossbeam_channel::{Receiver, RecvTimeoutError, Sender, TryRecvError, bounded};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 use tracing::{debug, error, info, warn};
 
+/// Polling strategy configuration for the worker event loop
+///
+/// Workers run a hybrid loop: at high packet rates they busy-poll the task
+/// queue to avoid wakeup latency, and when traffic dies down they fall back
+/// to event-driven sleep waits so idle workers do not burn CPU. Transitions
+/// use a hysteresis band (`busy_enter_pps` > `busy_exit_pps`) so bursty
+/// traffic near a single threshold does not cause mode flapping.
+#[derive(Debug, Clone)]
+pub struct PollingConfig {
+    /// Enable adaptive busy-polling (false = always sleep-wait)
+    pub adaptive: bool,
+    /// Task arrival rate (tasks/sec) above which a worker enters busy-polling
+    pub busy_enter_pps: f64,
+    /// Task arrival rate (tasks/sec) below which a busy worker returns to sleeping
+    pub busy_exit_pps: f64,
+    /// How often the arrival rate is re-evaluated
+    pub sample_interval: Duration,
+    /// Sleep-wait timeout when idle (bounds shutdown latency)
+    pub idle_timeout: Duration,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            adaptive: true,
+            busy_enter_pps: 50_000.0,
+            busy_exit_pps: 5_000.0,
+            sample_interval: Duration::from_millis(10),
+            idle_timeout: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Current polling mode of a worker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollMode {
+    /// Event-driven sleep waits (low CPU, higher wakeup latency)
+    Sleeping,
+    /// Busy-polling the task queue (full CPU, minimal latency)
+    Busy,
+}
+
+/// Adaptive polling state machine
+///
+/// Tracks the task arrival rate over a sampling window and decides when a
+/// worker should switch between [`PollMode::Sleeping`] and [`PollMode::Busy`].
+/// The decision logic is separate from the worker loop so it can be tested
+/// deterministically with synthetic timestamps.
+pub struct AdaptivePoller {
+    config: PollingConfig,
+    mode: PollMode,
+    window_start: Instant,
+    window_tasks: u64,
+}
+
+impl AdaptivePoller {
+    /// Create a new poller starting in sleeping mode
+    #[must_use]
+    pub fn new(config: PollingConfig) -> Self {
+        Self {
+            config,
+            mode: PollMode::Sleeping,
+            window_start: Instant::now(),
+            window_tasks: 0,
+        }
+    }
+
+    /// Get the current polling mode
+    #[must_use]
+    pub fn mode(&self) -> PollMode {
+        self.mode
+    }
+
+    /// Record a task arrival in the current sampling window
+    pub fn record_task(&mut self) {
+        self.window_tasks += 1;
+    }
+
+    /// Re-evaluate the polling mode at `now`
+    ///
+    /// If a full sampling window has elapsed, computes the arrival rate and
+    /// applies the hysteresis thresholds; otherwise the mode is unchanged.
+    /// Returns the (possibly new) mode.
+    pub fn update(&mut self, now: Instant) -> PollMode {
+        let elapsed = now.saturating_duration_since(self.window_start);
+        if elapsed < self.config.sample_interval {
+            return self.mode;
+        }
+
+        let rate = self.window_tasks as f64 / elapsed.as_secs_f64();
+        match self.mode {
+            PollMode::Sleeping if self.config.adaptive && rate >= self.config.busy_enter_pps => {
+                self.mode = PollMode::Busy;
+            }
+            PollMode::Busy if rate < self.config.busy_exit_pps => {
+                self.mode = PollMode::Sleeping;
+            }
+            _ => {}
+        }
+
+        self.window_start = now;
+        self.window_tasks = 0;
+        self.mode
+    }
+}
+
 /// Worker pool configuration
 #[derive(Debug, Clone)]
 pub struct WorkerConfig {
@@ -28,11 +134,20 @@ pub struct WorkerConfig {
     pub pin_to_cpu: bool,
     /// Enable NUMA-aware allocation (Linux only)
     pub numa_aware: bool,
+    /// Restrict CPU pinning to this NUMA node (typically the NIC's node)
+    ///
+    /// When set, workers are pinned round-robin onto the node's CPUs
+    /// instead of worker-index == CPU-index, keeping packet processing on
+    /// the socket the NIC delivers into. `None` keeps index-based pinning.
+    /// Ignored when `pin_to_cpu` is false.
+    pub numa_node: Option<usize>,
     /// Optional buffer pool for packet buffer recycling
     ///
     /// When provided, packet buffers are returned to the pool after processing
     /// instead of being dropped, reducing allocation overhead significantly.
     pub buffer_pool: Option<BufferPool>,
+    /// Polling strategy for the worker event loop
+    pub polling: PollingConfig,
 }
 
 impl Default for WorkerConfig {
@@ -42,7 +157,9 @@ impl Default for WorkerConfig {
             queue_capacity: 10000,
             pin_to_cpu: cfg!(target_os = "linux"),
             numa_aware: cfg!(target_os = "linux"),
+            numa_node: None,
             buffer_pool: None,
+            polling: PollingConfig::default(),
         }
     }
 }
@@ -71,6 +188,30 @@ impl WorkerConfig {
             ..Default::default()
         }
     }
+
+    /// Create a WorkerConfig pinned to the NUMA node of a network interface
+    ///
+    /// Looks up the NIC's NUMA node via sysfs and restricts worker pinning
+    /// to that node's CPUs. Falls back to index-based pinning when the
+    /// interface has no NUMA affinity (virtual devices, non-NUMA systems,
+    /// non-Linux platforms). Combine with
+    /// [`BufferPool::new_on_node`] to keep packet buffers on the same node.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use wraith_transport::worker::WorkerConfig;
+    ///
+    /// // numa_node is Some(..) only when eth0 exists and reports a node
+    /// let config = WorkerConfig::for_nic("eth0");
+    /// ```
+    #[must_use]
+    pub fn for_nic(interface: &str) -> Self {
+        Self {
+            numa_node: crate::numa::get_numa_node_for_nic(interface),
+            ..Default::default()
+        }
+    }
 }
 
 /// Work item to be processed by a worker
@@ -118,6 +259,10 @@ pub struct WorkerStats {
     pub bytes_processed: AtomicU64,
     /// Total errors encountered
     pub errors: AtomicU64,
+    /// Nanoseconds spent busy-polling the task queue
+    pub busy_poll_nanos: AtomicU64,
+    /// Nanoseconds spent in event-driven sleep waits
+    pub sleep_nanos: AtomicU64,
 }
 
 /// Pool-wide statistics
@@ -162,6 +307,36 @@ impl PoolStats {
             .sum()
     }
 
+    /// Get total time spent busy-polling across all workers (nanoseconds)
+    pub fn total_busy_poll_nanos(&self) -> u64 {
+        self.workers
+            .iter()
+            .map(|w| w.busy_poll_nanos.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Get total time spent in sleep waits across all workers (nanoseconds)
+    pub fn total_sleep_nanos(&self) -> u64 {
+        self.workers
+            .iter()
+            .map(|w| w.sleep_nanos.load(Ordering::Relaxed))
+            .sum()
+    }
+
+    /// Fraction of loop time spent busy-polling (0.0 - 1.0)
+    ///
+    /// Approximates CPU actively burned by the hybrid event loop: time in
+    /// busy-poll spins counts as consumed CPU, time parked in sleep waits
+    /// does not. Returns 0.0 when no loop time has been recorded yet.
+    pub fn busy_poll_ratio(&self) -> f64 {
+        let busy = self.total_busy_poll_nanos();
+        let total = busy + self.total_sleep_nanos();
+        if total == 0 {
+            return 0.0;
+        }
+        busy as f64 / total as f64
+    }
+
     /// Get throughput in packets per second
     pub fn packets_per_second(&self) -> f64 {
         if let Some(start) = self.start_time {
@@ -222,6 +397,14 @@ impl WorkerPool {
         let (task_tx, task_rx) = bounded(config.queue_capacity * num_workers);
         let shutdown = Arc::new(AtomicBool::new(false));
 
+        // CPUs eligible for pinning: the configured NUMA node's cores when
+        // one is set (round-robin for worker counts beyond the node's
+        // size), otherwise worker index == CPU index
+        let node_cpus = config
+            .numa_node
+            .map(crate::numa::cpus_on_node)
+            .filter(|cpus| !cpus.is_empty());
+
         let mut workers = Vec::with_capacity(num_workers);
         let mut worker_stats = Vec::with_capacity(num_workers);
 
@@ -229,14 +412,20 @@ impl WorkerPool {
             let stats = Arc::new(WorkerStats::default());
             worker_stats.push(stats.clone());
 
+            let pin_cpu = config.pin_to_cpu.then(|| match &node_cpus {
+                Some(cpus) => cpus[id % cpus.len()],
+                None => id,
+            });
+
             let worker = Worker::spawn(
                 id,
                 task_rx.clone(),
                 shutdown.clone(),
                 stats,
-                config.pin_to_cpu,
+                pin_cpu,
                 config.numa_aware,
                 config.buffer_pool.clone(),
+                config.polling.clone(),
             );
             workers.push(worker);
         }
@@ -352,6 +541,18 @@ impl WorkerPool {
         self.workers.len()
     }
 
+    /// Get the worker that owns a connection ID
+    ///
+    /// Sessions are sharded across workers by an FNV-1a hash of the
+    /// connection ID — the same shard function used by
+    /// [`SteeringCoordinator`](crate::steering::SteeringCoordinator) —
+    /// so userspace dispatch and hardware steering agree on ownership
+    /// without coordination.
+    #[must_use]
+    pub fn worker_for_cid(&self, cid: &crate::steering::RawCid) -> usize {
+        crate::steering::shard_for_cid(cid, self.workers.len())
+    }
+
     /// Initiate graceful shutdown of all workers
     ///
     /// This method signals all workers to shut down and waits for them to finish.
@@ -386,14 +587,16 @@ struct Worker {
 
 impl Worker {
     #[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+    #[allow(clippy::too_many_arguments)]
     fn spawn(
         id: usize,
         task_rx: Receiver<Task>,
         shutdown: Arc<AtomicBool>,
         stats: Arc<WorkerStats>,
-        pin_to_cpu: bool,
+        pin_cpu: Option<usize>,
         numa_aware: bool,
         buffer_pool: Option<BufferPool>,
+        polling: PollingConfig,
     ) -> Self {
         let handle = thread::Builder::new()
             .name(format!("wraith-worker-{id}"))
@@ -402,57 +605,92 @@ impl Worker {
 
                 // Pin to CPU core if enabled
                 #[cfg(target_os = "linux")]
-                if pin_to_cpu {
-                    if let Err(e) = Self::pin_to_cpu(id) {
-                        warn!("Failed to pin worker {} to CPU: {}", id, e);
+                if let Some(cpu) = pin_cpu {
+                    if let Err(e) = crate::numa::pin_current_thread(cpu) {
+                        warn!("Failed to pin worker {} to CPU {}: {}", id, cpu, e);
                     } else {
-                        debug!("Worker {} pinned to CPU {}", id, id);
+                        debug!("Worker {} pinned to CPU {}", id, cpu);
                     }
                 }
 
                 // Set up NUMA-aware allocation if enabled
                 #[cfg(target_os = "linux")]
                 if numa_aware {
-                    if let Some(node) = crate::numa::get_numa_node_for_cpu(id) {
+                    if let Some(node) = crate::numa::get_numa_node_for_cpu(pin_cpu.unwrap_or(id)) {
                         debug!("Worker {} on NUMA node {}", id, node);
                     }
                 }
 
-                // Worker event loop
+                // Hybrid event loop: busy-poll under load, sleep-wait when idle
+                let mut poller = AdaptivePoller::new(polling);
+
                 while !shutdown.load(Ordering::Acquire) {
-                    match task_rx.recv_timeout(Duration::from_millis(100)) {
-                        Ok(task) => {
-                            stats.tasks_processed.fetch_add(1, Ordering::Relaxed);
-
-                            match task {
-                                Task::ProcessPacket { data, source } => {
-                                    Self::process_packet(&data, source, &stats);
-                                    // Release buffer back to pool if configured
-                                    if let Some(ref pool) = buffer_pool {
-                                        pool.release(data);
-                                    }
-                                }
-                                Task::SendPacket { data, destination } => {
-                                    Self::send_packet(&data, destination, &stats);
-                                    // Release buffer back to pool if configured
-                                    if let Some(ref pool) = buffer_pool {
-                                        pool.release(data);
-                                    }
-                                }
-                                Task::Shutdown => {
-                                    debug!("Worker {} received shutdown signal", id);
+                    let iter_start = Instant::now();
+                    let mode = poller.mode();
+
+                    let received = match mode {
+                        PollMode::Busy => match task_rx.try_recv() {
+                            Ok(task) => Some(task),
+                            Err(TryRecvError::Empty) => {
+                                std::hint::spin_loop();
+                                None
+                            }
+                            Err(TryRecvError::Disconnected) => {
+                                warn!("Worker {} task channel disconnected", id);
+                                break;
+                            }
+                        },
+                        PollMode::Sleeping => {
+                            match task_rx.recv_timeout(poller.config.idle_timeout) {
+                                Ok(task) => Some(task),
+                                Err(RecvTimeoutError::Timeout) => None,
+                                Err(RecvTimeoutError::Disconnected) => {
+                                    warn!("Worker {} task channel disconnected", id);
                                     break;
                                 }
                             }
                         }
-                        Err(RecvTimeoutError::Disconnected) => {
-                            warn!("Worker {} task channel disconnected", id);
-                            break;
+                    };
+
+                    // CPU accounting: busy-poll time is consumed CPU, sleep
+                    // waits are parked time
+                    let nanos = iter_start.elapsed().as_nanos() as u64;
+                    match mode {
+                        PollMode::Busy => {
+                            stats.busy_poll_nanos.fetch_add(nanos, Ordering::Relaxed);
+                        }
+                        PollMode::Sleeping => {
+                            stats.sleep_nanos.fetch_add(nanos, Ordering::Relaxed);
                         }
-                        Err(RecvTimeoutError::Timeout) => {
-                            // No task available, continue loop
+                    }
+
+                    if let Some(task) = received {
+                        poller.record_task();
+                        stats.tasks_processed.fetch_add(1, Ordering::Relaxed);
+
+                        match task {
+                            Task::ProcessPacket { data, source } => {
+                                Self::process_packet(&data, source, &stats);
+                                // Release buffer back to pool if configured
+                                if let Some(ref pool) = buffer_pool {
+                                    pool.release(data);
+                                }
+                            }
+                            Task::SendPacket { data, destination } => {
+                                Self::send_packet(&data, destination, &stats);
+                                // Release buffer back to pool if configured
+                                if let Some(ref pool) = buffer_pool {
+                                    pool.release(data);
+                                }
+                            }
+                            Task::Shutdown => {
+                                debug!("Worker {} received shutdown signal", id);
+                                break;
+                            }
                         }
                     }
+
+                    poller.update(Instant::now());
                 }
 
                 info!(
@@ -468,32 +706,6 @@ impl Worker {
         Self { id, handle }
     }
 
-    #[cfg(target_os = "linux")]
-    fn pin_to_cpu(core_id: usize) -> Result<(), String> {
-        use std::mem;
-
-        // SAFETY: sched_setaffinity is a standard Linux syscall. cpu_set_t is properly
-        // zero-initialized via mem::zeroed(), and CPU_ZERO/CPU_SET are standard libc macros.
-        // Passing 0 for pid means current thread, and size is correct for cpu_set_t.
-        unsafe {
-            let mut cpuset: libc::cpu_set_t = mem::zeroed();
-            libc::CPU_ZERO(&mut cpuset);
-            libc::CPU_SET(core_id, &mut cpuset);
-
-            let ret = libc::sched_setaffinity(
-                0, // Current thread
-                mem::size_of::<libc::cpu_set_t>(),
-                &cpuset,
-            );
-
-            if ret != 0 {
-                Err(format!("sched_setaffinity failed with code {ret}"))
-            } else {
-                Ok(())
-            }
-        }
-    }
-
     fn process_packet(data: &[u8], _source: usize, stats: &WorkerStats) {
         // Placeholder for packet processing logic
         // In a real implementation, this would:
@@ -538,6 +750,163 @@ pub enum WorkerError {
 mod tests {
     use super::*;
 
+    fn fast_polling() -> PollingConfig {
+        PollingConfig {
+            adaptive: true,
+            busy_enter_pps: 1000.0,
+            busy_exit_pps: 100.0,
+            sample_interval: Duration::from_millis(10),
+            idle_timeout: Duration::from_millis(10),
+        }
+    }
+
+    #[test]
+    fn test_polling_config_default() {
+        let config = PollingConfig::default();
+        assert!(config.adaptive);
+        assert!(config.busy_enter_pps > config.busy_exit_pps); // Hysteresis band
+        assert_eq!(config.sample_interval, Duration::from_millis(10));
+        assert_eq!(config.idle_timeout, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_poller_starts_sleeping() {
+        let poller = AdaptivePoller::new(PollingConfig::default());
+        assert_eq!(poller.mode(), PollMode::Sleeping);
+    }
+
+    #[test]
+    fn test_poller_enters_busy_on_high_rate() {
+        let mut poller = AdaptivePoller::new(fast_polling());
+
+        // 100 tasks in a 10ms window = 10,000 tasks/sec, above enter threshold
+        for _ in 0..100 {
+            poller.record_task();
+        }
+        let mode = poller.update(poller.window_start + Duration::from_millis(10));
+        assert_eq!(mode, PollMode::Busy);
+    }
+
+    #[test]
+    fn test_poller_stays_sleeping_below_threshold() {
+        let mut poller = AdaptivePoller::new(fast_polling());
+
+        // 5 tasks in 10ms = 500 tasks/sec, below enter threshold
+        for _ in 0..5 {
+            poller.record_task();
+        }
+        let mode = poller.update(poller.window_start + Duration::from_millis(10));
+        assert_eq!(mode, PollMode::Sleeping);
+    }
+
+    #[test]
+    fn test_poller_hysteresis_band() {
+        let mut poller = AdaptivePoller::new(fast_polling());
+
+        // Enter busy mode
+        for _ in 0..100 {
+            poller.record_task();
+        }
+        poller.update(poller.window_start + Duration::from_millis(10));
+        assert_eq!(poller.mode(), PollMode::Busy);
+
+        // 5 tasks in 10ms = 500/sec: between exit (100) and enter (1000)
+        // thresholds, so the worker stays busy instead of flapping
+        for _ in 0..5 {
+            poller.record_task();
+        }
+        let mode = poller.update(poller.window_start + Duration::from_millis(10));
+        assert_eq!(mode, PollMode::Busy);
+
+        // Zero tasks: below exit threshold, fall back to sleeping
+        let mode = poller.update(poller.window_start + Duration::from_millis(10));
+        assert_eq!(mode, PollMode::Sleeping);
+    }
+
+    #[test]
+    fn test_poller_no_transition_mid_window() {
+        let mut poller = AdaptivePoller::new(fast_polling());
+
+        for _ in 0..100 {
+            poller.record_task();
+        }
+        // Sample window has not elapsed yet: mode must not change
+        let mode = poller.update(poller.window_start + Duration::from_millis(1));
+        assert_eq!(mode, PollMode::Sleeping);
+    }
+
+    #[test]
+    fn test_poller_disabled_never_busy() {
+        let config = PollingConfig {
+            adaptive: false,
+            ..fast_polling()
+        };
+        let mut poller = AdaptivePoller::new(config);
+
+        for _ in 0..10_000 {
+            poller.record_task();
+        }
+        let mode = poller.update(poller.window_start + Duration::from_millis(10));
+        assert_eq!(mode, PollMode::Sleeping);
+    }
+
+    #[test]
+    fn test_pool_stats_busy_poll_ratio() {
+        let worker1 = Arc::new(WorkerStats::default());
+        let worker2 = Arc::new(WorkerStats::default());
+
+        worker1.busy_poll_nanos.store(750, Ordering::Relaxed);
+        worker1.sleep_nanos.store(250, Ordering::Relaxed);
+        worker2.busy_poll_nanos.store(0, Ordering::Relaxed);
+        worker2.sleep_nanos.store(1000, Ordering::Relaxed);
+
+        let pool_stats = PoolStats {
+            workers: vec![worker1, worker2],
+            start_time: Some(Instant::now()),
+        };
+
+        assert_eq!(pool_stats.total_busy_poll_nanos(), 750);
+        assert_eq!(pool_stats.total_sleep_nanos(), 1250);
+        assert!((pool_stats.busy_poll_ratio() - 0.375).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_pool_stats_busy_poll_ratio_empty() {
+        let pool_stats = PoolStats {
+            workers: vec![],
+            start_time: None,
+        };
+
+        assert_eq!(pool_stats.busy_poll_ratio(), 0.0);
+    }
+
+    #[test]
+    fn test_worker_loop_accounts_sleep_time() {
+        let config = WorkerConfig {
+            num_workers: 1,
+            queue_capacity: 10,
+            pin_to_cpu: false,
+            numa_aware: false,
+            numa_node: None,
+            buffer_pool: None,
+            polling: PollingConfig {
+                idle_timeout: Duration::from_millis(5),
+                ..PollingConfig::default()
+            },
+        };
+
+        let pool = WorkerPool::new(config);
+
+        // Idle worker should accumulate sleep time, not busy-poll time
+        std::thread::sleep(Duration::from_millis(30));
+
+        let stats = pool.stats();
+        assert!(stats.total_sleep_nanos() > 0);
+        assert_eq!(stats.total_busy_poll_nanos(), 0);
+
+        pool.shutdown();
+    }
+
     #[test]
     fn test_worker_config_default() {
         let config = WorkerConfig::default();
@@ -562,7 +931,9 @@ mod tests {
             queue_capacity: 100,
             pin_to_cpu: false,
             numa_aware: false,
+            numa_node: None,
             buffer_pool: None,
+            polling: PollingConfig::default(),
         };
 
         let pool = WorkerPool::new(config);
@@ -576,7 +947,9 @@ mod tests {
             queue_capacity: 10,
             pin_to_cpu: false,
             numa_aware: false,
+            numa_node: None,
             buffer_pool: None,
+            polling: PollingConfig::default(),
         };
 
         let pool = WorkerPool::new(config);
@@ -602,7 +975,9 @@ mod tests {
             queue_capacity: 10,
             pin_to_cpu: false,
             numa_aware: false,
+            numa_node: None,
             buffer_pool: None,
+            polling: PollingConfig::default(),
         };
 
         let pool = WorkerPool::new(config);
@@ -630,7 +1005,9 @@ mod tests {
             queue_capacity: 100,
             pin_to_cpu: false,
             numa_aware: false,
+            numa_node: None,
             buffer_pool: None,
+            polling: PollingConfig::default(),
         };
 
         let pool = WorkerPool::new(config);
@@ -660,7 +1037,9 @@ mod tests {
             queue_capacity: 5,
             pin_to_cpu: false,
             numa_aware: false,
+            numa_node: None,
             buffer_pool: None,
+            polling: PollingConfig::default(),
         };
 
         let pool = WorkerPool::new(config);
@@ -722,7 +1101,9 @@ mod tests {
             queue_capacity: 100,
             pin_to_cpu: false,
             numa_aware: false,
+            numa_node: None,
             buffer_pool: Some(BufferPool::new(1024, 64)),
+            polling: PollingConfig::default(),
         };
 
         let pool = WorkerPool::new(config);
@@ -761,7 +1142,9 @@ mod tests {
             queue_capacity: 50,
             pin_to_cpu: false,
             numa_aware: false,
+            numa_node: None,
             buffer_pool: Some(buffer_pool.clone()),
+            polling: PollingConfig::default(),
         };
 
         let pool = WorkerPool::new(config);
@@ -938,6 +1321,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_worker_config_for_nic_missing_interface() {
+        let config = WorkerConfig::for_nic("wraith-test-no-such-nic");
+        assert_eq!(config.numa_node, None);
+        assert_eq!(config.num_workers, 0); // Rest of the config keeps defaults
+    }
+
+    #[test]
+    fn test_worker_for_cid_matches_steering() {
+        use crate::steering::{SteeringBackend, SteeringCoordinator};
+
+        let config = WorkerConfig {
+            num_workers: 4,
+            queue_capacity: 10,
+            pin_to_cpu: false,
+            numa_aware: false,
+            numa_node: None,
+            buffer_pool: None,
+            polling: PollingConfig::default(),
+        };
+
+        let pool = WorkerPool::new(config);
+        let coordinator = SteeringCoordinator::new(SteeringBackend::Software, 4).unwrap();
+
+        for byte in 0..16u8 {
+            let cid = [byte; 8];
+            assert_eq!(pool.worker_for_cid(&cid), coordinator.default_worker(&cid));
+        }
+
+        pool.shutdown();
+    }
+
+    #[test]
+    fn test_worker_pool_numa_node_pinning() {
+        // Pinning onto a node (present or not) must not break pool startup
+        let config = WorkerConfig {
+            num_workers: 2,
+            queue_capacity: 10,
+            pin_to_cpu: true,
+            numa_aware: true,
+            numa_node: Some(0),
+            buffer_pool: None,
+            polling: PollingConfig::default(),
+        };
+
+        let pool = WorkerPool::new(config);
+
+        pool.submit(Task::ProcessPacket {
+            data: vec![1, 2, 3],
+            source: 0,
+        })
+        .unwrap();
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(pool.stats().total_tasks() > 0);
+
+        pool.shutdown();
+    }
+
+    #[test]
+    fn test_worker_pool_pinning_disabled() {
+        // pin_to_cpu=false disables affinity even with a NUMA node set
+        let config = WorkerConfig {
+            num_workers: 2,
+            queue_capacity: 10,
+            pin_to_cpu: false,
+            numa_aware: false,
+            numa_node: Some(0),
+            buffer_pool: None,
+            polling: PollingConfig::default(),
+        };
+
+        let pool = WorkerPool::new(config);
+        assert_eq!(pool.num_workers(), 2);
+        pool.shutdown();
+    }
+
     #[test]
     fn test_worker_pool_submit_after_shutdown() {
         let config = WorkerConfig {
@@ -945,7 +1405,9 @@ mod tests {
             queue_capacity: 10,
             pin_to_cpu: false,
             numa_aware: false,
+            numa_node: None,
             buffer_pool: None,
+            polling: PollingConfig::default(),
         };
 
         let pool = WorkerPool::new(config);
@@ -0,0 +1,544 @@
+//! Privileged XDP loader handoff over a Unix socket (Linux-only).
+//!
+//! Loading and attaching an XDP program requires `CAP_BPF` and
+//! `CAP_NET_ADMIN`, but nothing else in the node does. This module
+//! implements a split-process architecture: a small privileged helper
+//! (the loader side lives in `wraith-xdp`) loads and attaches the
+//! program, then passes the BPF map file descriptors to the unprivileged
+//! daemon over a Unix domain socket using `SCM_RIGHTS`. The daemon side
+//! calls [`request_xdp_handles`] during transport startup and can then
+//! create AF_XDP sockets and read statistics without ever holding the
+//! capabilities itself.
+//!
+//! The wire format is deliberately tiny. Request: `u16` length prefix,
+//! `u8` interface name length, interface bytes, `u32` queue ID (all
+//! big-endian). Response: a single message whose first byte is a status
+//! (`0` ok, `1` error); on success it carries one role byte per file
+//! descriptor with the descriptors themselves in an `SCM_RIGHTS` control
+//! message, on error a `u16`-prefixed UTF-8 message from the helper.
+
+use std::io::{self, Read, Write};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use thiserror::Error;
+
+/// Default rendezvous socket created by the privileged helper
+pub const DEFAULT_LOADER_SOCKET: &str = "/run/wraith/xdp-loader.sock";
+
+/// Upper bound on descriptors in one response (program + maps)
+const MAX_RESPONSE_FDS: usize = 8;
+
+/// XDP loader handoff errors
+#[derive(Debug, Error)]
+pub enum XdpLoaderError {
+    /// Socket I/O failed
+    #[error("Loader socket I/O failed: {0}")]
+    Io(#[from] io::Error),
+
+    /// Malformed message on the loader socket
+    #[error("Loader protocol violation: {0}")]
+    Protocol(String),
+
+    /// The privileged helper reported a load/attach failure
+    #[error("XDP helper failed: {0}")]
+    Helper(String),
+}
+
+/// Request from the unprivileged daemon to the privileged helper
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct XdpLoadRequest {
+    /// Network interface to attach to (e.g., "eth0")
+    pub interface: String,
+
+    /// RX queue the AF_XDP socket will bind to
+    pub queue_id: u32,
+}
+
+impl XdpLoadRequest {
+    /// Serialize to the wire format (without the length prefix)
+    fn encode(&self) -> Vec<u8> {
+        let name = self.interface.as_bytes();
+        let mut buf = Vec::with_capacity(1 + name.len() + 4);
+        buf.push(name.len() as u8);
+        buf.extend_from_slice(name);
+        buf.extend_from_slice(&self.queue_id.to_be_bytes());
+        buf
+    }
+
+    /// Parse from the wire format (without the length prefix)
+    fn decode(data: &[u8]) -> Result<Self, XdpLoaderError> {
+        if data.is_empty() {
+            return Err(XdpLoaderError::Protocol("Empty request".into()));
+        }
+        let name_len = data[0] as usize;
+        if data.len() != 1 + name_len + 4 {
+            return Err(XdpLoaderError::Protocol(format!(
+                "Request length {} does not match name length {name_len}",
+                data.len()
+            )));
+        }
+        let interface = std::str::from_utf8(&data[1..1 + name_len])
+            .map_err(|_| XdpLoaderError::Protocol("Interface name is not UTF-8".into()))?
+            .to_string();
+        let queue_id = u32::from_be_bytes(
+            data[1 + name_len..]
+                .try_into()
+                .expect("length checked above"),
+        );
+
+        Ok(Self {
+            interface,
+            queue_id,
+        })
+    }
+}
+
+/// Role of a file descriptor passed back by the helper
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum XdpFdRole {
+    /// `xsks_map` for steering packets to AF_XDP sockets
+    XsksMap = 0,
+    /// Per-CPU statistics map
+    StatsMap = 1,
+}
+
+impl XdpFdRole {
+    fn from_byte(byte: u8) -> Result<Self, XdpLoaderError> {
+        match byte {
+            0 => Ok(Self::XsksMap),
+            1 => Ok(Self::StatsMap),
+            other => Err(XdpLoaderError::Protocol(format!(
+                "Unknown descriptor role {other}"
+            ))),
+        }
+    }
+}
+
+/// File descriptors received from the privileged helper
+///
+/// Owning these keeps the kernel-side maps alive even if the helper
+/// exits; the XDP program itself stays attached via its BPF link.
+#[derive(Debug)]
+pub struct XdpHandles {
+    fds: Vec<(XdpFdRole, OwnedFd)>,
+}
+
+impl XdpHandles {
+    /// Number of descriptors received
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.fds.len()
+    }
+
+    /// Check if no descriptors were received
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.fds.is_empty()
+    }
+
+    /// Take ownership of the descriptor with the given role
+    pub fn take(&mut self, role: XdpFdRole) -> Option<OwnedFd> {
+        let pos = self.fds.iter().position(|(r, _)| *r == role)?;
+        Some(self.fds.remove(pos).1)
+    }
+}
+
+/// Request XDP map descriptors from the privileged helper
+///
+/// Connects to the helper's socket, asks it to load and attach the XDP
+/// program for `interface`/`queue_id` (a no-op if already attached), and
+/// returns the passed descriptors. Called during transport startup when
+/// the daemon lacks `CAP_BPF`; callers should fall back to plain UDP if
+/// the helper socket does not exist.
+///
+/// # Errors
+///
+/// Returns an error if the socket is unreachable, the helper reports a
+/// load failure, or the response is malformed.
+pub fn request_xdp_handles<P: AsRef<Path>>(
+    socket: P,
+    interface: &str,
+    queue_id: u32,
+) -> Result<XdpHandles, XdpLoaderError> {
+    let mut stream = UnixStream::connect(socket)?;
+
+    let request = XdpLoadRequest {
+        interface: interface.to_string(),
+        queue_id,
+    };
+    let body = request.encode();
+    stream.write_all(&(body.len() as u16).to_be_bytes())?;
+    stream.write_all(&body)?;
+
+    // The response is one sendmsg on the helper side, so a single recvmsg
+    // with a generous buffer receives it whole along with its descriptors
+    let mut buf = [0u8; 1024];
+    let (len, mut fds) = recv_with_fds(&stream, &mut buf, MAX_RESPONSE_FDS)?;
+    let data = &buf[..len];
+
+    if data.is_empty() {
+        return Err(XdpLoaderError::Protocol("Empty response".into()));
+    }
+
+    match data[0] {
+        0 => {
+            if data.len() < 2 {
+                return Err(XdpLoaderError::Protocol("Truncated response".into()));
+            }
+            let count = data[1] as usize;
+            if data.len() != 2 + count || fds.len() != count {
+                return Err(XdpLoaderError::Protocol(format!(
+                    "Expected {count} descriptors, received {}",
+                    fds.len()
+                )));
+            }
+            let mut handles = Vec::with_capacity(count);
+            for role_byte in &data[2..2 + count] {
+                let role = XdpFdRole::from_byte(*role_byte)?;
+                handles.push((role, fds.remove(0)));
+            }
+            Ok(XdpHandles { fds: handles })
+        }
+        1 => {
+            if data.len() < 3 {
+                return Err(XdpLoaderError::Protocol("Truncated error response".into()));
+            }
+            let msg_len = u16::from_be_bytes([data[1], data[2]]) as usize;
+            let msg = data
+                .get(3..3 + msg_len)
+                .ok_or_else(|| XdpLoaderError::Protocol("Truncated error message".into()))?;
+            Err(XdpLoaderError::Helper(
+                String::from_utf8_lossy(msg).into_owned(),
+            ))
+        }
+        other => Err(XdpLoaderError::Protocol(format!(
+            "Unknown response status {other}"
+        ))),
+    }
+}
+
+/// Bind the helper's rendezvous socket, replacing any stale one
+///
+/// The helper should place the socket in a directory only the daemon's
+/// user can reach; the socket itself is additionally restricted to
+/// owner and group.
+///
+/// # Errors
+///
+/// Returns an error if the socket cannot be bound or its permissions
+/// cannot be set.
+pub fn bind_loader_socket<P: AsRef<Path>>(path: P) -> io::Result<UnixListener> {
+    let path = path.as_ref();
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    let listener = UnixListener::bind(path)?;
+
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o660))?;
+
+    Ok(listener)
+}
+
+/// Serve loader requests until the listener fails
+///
+/// Runs in the privileged helper. `loader` performs the actual load and
+/// attach (in production, `wraith-xdp`'s `XdpProgram`) and returns the
+/// role-tagged descriptors to pass; its error string is forwarded to the
+/// daemon verbatim. Per-connection failures are logged and do not stop
+/// the serve loop.
+///
+/// # Errors
+///
+/// Returns an error only if accepting connections fails.
+pub fn serve<F>(listener: &UnixListener, loader: F) -> Result<(), XdpLoaderError>
+where
+    F: Fn(&XdpLoadRequest) -> Result<Vec<(XdpFdRole, OwnedFd)>, String>,
+{
+    loop {
+        let (stream, _) = listener.accept()?;
+        if let Err(e) = serve_connection(&stream, &loader) {
+            tracing::warn!("XDP loader request failed: {e}");
+        }
+    }
+}
+
+/// Handle a single loader request on an accepted connection
+///
+/// # Errors
+///
+/// Returns an error if the request is malformed or the response cannot
+/// be sent.
+pub fn serve_connection<F>(mut stream: &UnixStream, loader: &F) -> Result<(), XdpLoaderError>
+where
+    F: Fn(&XdpLoadRequest) -> Result<Vec<(XdpFdRole, OwnedFd)>, String>,
+{
+    let mut len_buf = [0u8; 2];
+    stream.read_exact(&mut len_buf)?;
+    let len = u16::from_be_bytes(len_buf) as usize;
+
+    let mut body = vec![0u8; len];
+    stream.read_exact(&mut body)?;
+    let request = XdpLoadRequest::decode(&body)?;
+
+    match loader(&request) {
+        Ok(handles) => {
+            let mut response = Vec::with_capacity(2 + handles.len());
+            response.push(0u8);
+            response.push(handles.len() as u8);
+            for (role, _) in &handles {
+                response.push(*role as u8);
+            }
+            let raw_fds: Vec<RawFd> = handles.iter().map(|(_, fd)| fd.as_raw_fd()).collect();
+            send_with_fds(stream, &response, &raw_fds)?;
+        }
+        Err(msg) => {
+            let msg = msg.as_bytes();
+            let mut response = Vec::with_capacity(3 + msg.len());
+            response.push(1u8);
+            response.extend_from_slice(&(msg.len() as u16).to_be_bytes());
+            response.extend_from_slice(msg);
+            send_with_fds(stream, &response, &[])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Send `data` with file descriptors attached via `SCM_RIGHTS`
+fn send_with_fds(stream: &UnixStream, data: &[u8], fds: &[RawFd]) -> io::Result<()> {
+    let mut iov = libc::iovec {
+        iov_base: data.as_ptr() as *mut libc::c_void,
+        iov_len: data.len(),
+    };
+
+    let fd_bytes = std::mem::size_of_val(fds);
+    // SAFETY: CMSG_SPACE is a pure size computation
+    let cmsg_space = unsafe { libc::CMSG_SPACE(fd_bytes as u32) } as usize;
+    let mut cmsg_buf = vec![0u8; cmsg_space];
+
+    // SAFETY: msghdr is plain-old-data; all pointers remain valid for the
+    // duration of the sendmsg call below
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+
+    if !fds.is_empty() {
+        msg.msg_control = cmsg_buf.as_mut_ptr().cast();
+        msg.msg_controllen = cmsg_space;
+
+        // SAFETY: cmsg_buf is large enough for one SCM_RIGHTS header plus
+        // the descriptor array, per the CMSG_SPACE computation above
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = libc::SOL_SOCKET;
+            (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(fd_bytes as u32) as usize;
+            std::ptr::copy_nonoverlapping(
+                fds.as_ptr().cast::<u8>(),
+                libc::CMSG_DATA(cmsg),
+                fd_bytes,
+            );
+        }
+    }
+
+    // SAFETY: msg and everything it points into are valid for this call
+    let sent = unsafe { libc::sendmsg(stream.as_raw_fd(), &msg, 0) };
+    if sent < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    if sent as usize != data.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::WriteZero,
+            "Short write on loader socket",
+        ));
+    }
+    Ok(())
+}
+
+/// Receive data and any attached file descriptors from the socket
+///
+/// Returns the number of data bytes read and the descriptors, wrapped
+/// for ownership so they are closed on drop.
+fn recv_with_fds(
+    stream: &UnixStream,
+    buf: &mut [u8],
+    max_fds: usize,
+) -> io::Result<(usize, Vec<OwnedFd>)> {
+    let mut iov = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+
+    let fd_bytes = max_fds * std::mem::size_of::<RawFd>();
+    // SAFETY: CMSG_SPACE is a pure size computation
+    let cmsg_space = unsafe { libc::CMSG_SPACE(fd_bytes as u32) } as usize;
+    let mut cmsg_buf = vec![0u8; cmsg_space];
+
+    // SAFETY: msghdr is plain-old-data; all pointers remain valid for the
+    // duration of the recvmsg call below
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+    msg.msg_control = cmsg_buf.as_mut_ptr().cast();
+    msg.msg_controllen = cmsg_space;
+
+    // SAFETY: msg and everything it points into are valid for this call
+    let received = unsafe { libc::recvmsg(stream.as_raw_fd(), &mut msg, libc::MSG_CMSG_CLOEXEC) };
+    if received < 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut fds = Vec::new();
+    // SAFETY: CMSG_FIRSTHDR/CMSG_NXTHDR walk the control buffer the
+    // kernel just filled; bounds come from msg_controllen
+    unsafe {
+        let mut cmsg = libc::CMSG_FIRSTHDR(&msg);
+        while !cmsg.is_null() {
+            if (*cmsg).cmsg_level == libc::SOL_SOCKET && (*cmsg).cmsg_type == libc::SCM_RIGHTS {
+                let data_len = (*cmsg).cmsg_len - libc::CMSG_LEN(0) as usize;
+                let count = data_len / std::mem::size_of::<RawFd>();
+                let data = libc::CMSG_DATA(cmsg).cast::<RawFd>();
+                for i in 0..count {
+                    let fd = std::ptr::read_unaligned(data.add(i));
+                    // SAFETY: the kernel installed this descriptor into
+                    // our process for us to own
+                    fds.push(OwnedFd::from_raw_fd(fd));
+                }
+            }
+            cmsg = libc::CMSG_NXTHDR(&msg, cmsg);
+        }
+    }
+
+    Ok((received as usize, fds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    /// Create a connected pipe as a stand-in for BPF map descriptors
+    fn test_pipe() -> (OwnedFd, OwnedFd) {
+        let mut fds = [0 as RawFd; 2];
+        // SAFETY: pipe fills the two-element array on success
+        let rc = unsafe { libc::pipe(fds.as_mut_ptr()) };
+        assert_eq!(rc, 0);
+        // SAFETY: both descriptors were just created and are unowned
+        unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) }
+    }
+
+    #[test]
+    fn test_request_roundtrip() {
+        let request = XdpLoadRequest {
+            interface: "eth0".to_string(),
+            queue_id: 3,
+        };
+        let decoded = XdpLoadRequest::decode(&request.encode()).unwrap();
+        assert_eq!(decoded, request);
+    }
+
+    #[test]
+    fn test_request_decode_rejects_malformed() {
+        assert!(XdpLoadRequest::decode(&[]).is_err());
+        // Name length claims more bytes than present
+        assert!(XdpLoadRequest::decode(&[10, b'e']).is_err());
+        // Trailing garbage
+        let mut encoded = XdpLoadRequest {
+            interface: "eth0".to_string(),
+            queue_id: 0,
+        }
+        .encode();
+        encoded.push(0);
+        assert!(XdpLoadRequest::decode(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_fd_handoff_end_to_end() {
+        let dir = std::env::temp_dir().join(format!("wraith-xdp-loader-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("loader.sock");
+
+        let listener = bind_loader_socket(&socket_path).unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            serve_connection(&stream, &|request: &XdpLoadRequest| {
+                assert_eq!(request.interface, "eth0");
+                assert_eq!(request.queue_id, 1);
+                // Pass both ends of a pipe in place of real map fds
+                let (read_end, write_end) = test_pipe();
+                Ok(vec![
+                    (XdpFdRole::XsksMap, read_end),
+                    (XdpFdRole::StatsMap, write_end),
+                ])
+            })
+            .unwrap();
+        });
+
+        let mut handles = request_xdp_handles(&socket_path, "eth0", 1).unwrap();
+        server.join().unwrap();
+        assert_eq!(handles.len(), 2);
+
+        // The descriptors must be live in this process: data written to
+        // the passed write end comes out of the passed read end
+        let read_end = handles.take(XdpFdRole::XsksMap).unwrap();
+        let write_end = handles.take(XdpFdRole::StatsMap).unwrap();
+        assert!(handles.is_empty());
+
+        let mut writer = File::from(write_end);
+        writer.write_all(b"ping").unwrap();
+        drop(writer);
+
+        let mut reader = File::from(read_end);
+        let mut received = Vec::new();
+        reader.read_to_end(&mut received).unwrap();
+        assert_eq!(received, b"ping");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_helper_error_is_forwarded() {
+        let dir =
+            std::env::temp_dir().join(format!("wraith-xdp-loader-err-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("loader.sock");
+
+        let listener = bind_loader_socket(&socket_path).unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            serve_connection(&stream, &|_request: &XdpLoadRequest| {
+                Err("no such interface".to_string())
+            })
+            .unwrap();
+        });
+
+        let result = request_xdp_handles(&socket_path, "nope0", 0);
+        server.join().unwrap();
+
+        match result {
+            Err(XdpLoaderError::Helper(msg)) => assert_eq!(msg, "no such interface"),
+            other => panic!("expected helper error, got {other:?}"),
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_bind_replaces_stale_socket() {
+        let dir =
+            std::env::temp_dir().join(format!("wraith-xdp-loader-stale-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket_path = dir.join("loader.sock");
+
+        let first = bind_loader_socket(&socket_path).unwrap();
+        drop(first);
+        // The socket file is left behind; rebinding must replace it
+        assert!(socket_path.exists());
+        let _second = bind_loader_socket(&socket_path).unwrap();
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -3,13 +3,15 @@
 //! Linux-specific XDP (eXpress Data Path) packet filtering using eBPF programs.
 //!
 //! This crate provides:
-//! - XDP program loading and attachment
+//! - XDP program loading and attachment (via BPF links)
+//! - Atomic in-place program replacement for filter upgrades
 //! - BPF map access for statistics and configuration
+//! - Per-CPU and per-RX-queue statistics with derived rates
 //! - High-performance packet steering to AF_XDP sockets
 //!
 //! ## Requirements
 //!
-//! - Linux kernel 5.3+ with XDP support
+//! - Linux kernel 5.7+ with XDP BPF link support
 //! - libbpf-dev (Ubuntu/Debian) or libbpf-devel (Fedora/RHEL)
 //! - clang and LLVM for compiling eBPF programs
 //!
@@ -30,7 +32,7 @@
 //! use wraith_xdp::{XdpProgram, XdpFlags};
 //!
 //! // Load XDP program from compiled object file
-//! let prog = XdpProgram::load("target/xdp/xdp_filter.o").unwrap();
+//! let mut prog = XdpProgram::load("target/xdp/xdp_filter.o").unwrap();
 //!
 //! // Attach to network interface
 //! prog.attach("eth0", XdpFlags::DRV_MODE).unwrap();
@@ -38,6 +40,9 @@
 //! // Read statistics
 //! let stats = prog.read_stats().unwrap();
 //! println!("RX packets: {}", stats.rx_packets);
+//!
+//! // Atomically upgrade the filter without detaching (sessions keep flowing)
+//! prog.replace("target/xdp/xdp_filter_v2.o").unwrap();
 //! # }
 //! ```
 
@@ -47,6 +52,8 @@
 
 use std::ffi::NulError;
 use std::fmt;
+use std::net::IpAddr;
+use std::time::Duration;
 use thiserror::Error;
 
 #[cfg(feature = "libbpf")]
@@ -57,6 +64,22 @@ use std::ffi::CString;
 pub const WRAITH_PORT_MIN: u16 = 40000;
 /// WRAITH port range maximum
 pub const WRAITH_PORT_MAX: u16 = 50000;
+/// Maximum number of allowlisted peers (mirrors MAX_ALLOWED_PEERS in xdp_filter.c)
+pub const MAX_ALLOWED_PEERS: u32 = 1024;
+/// Maximum number of RX queues tracked (mirrors MAX_SOCKETS in xdp_filter.c)
+pub const MAX_QUEUES: u32 = 64;
+
+/// Allowlist map key for a peer address
+///
+/// IPv4 addresses are stored as IPv4-mapped IPv6 (`::ffff:a.b.c.d`) so the
+/// `allowed_peers` map uses a single 16-byte key type for both families,
+/// matching the key layout built by `peer_key_v4()` in `xdp_filter.c`.
+pub fn peer_allowlist_key(addr: IpAddr) -> [u8; 16] {
+    match addr {
+        IpAddr::V4(v4) => v4.to_ipv6_mapped().octets(),
+        IpAddr::V6(v6) => v6.octets(),
+    }
+}
 
 /// XDP attachment flags
 #[derive(Debug, Clone, Copy)]
@@ -91,6 +114,36 @@ pub struct XdpStats {
     pub redirected: u64,
 }
 
+impl XdpStats {
+    /// Accumulate another counter set into this one
+    #[cfg_attr(not(feature = "libbpf"), allow(dead_code))]
+    fn accumulate(&mut self, other: &XdpStats) {
+        self.rx_packets += other.rx_packets;
+        self.rx_bytes += other.rx_bytes;
+        self.dropped += other.dropped;
+        self.redirected += other.redirected;
+    }
+
+    /// Derive rates from an earlier snapshot over a sampling window
+    ///
+    /// Counter wrap or a program replacement resetting counters yields zero
+    /// rates rather than nonsense, as does a zero-length window.
+    pub fn rates_since(&self, earlier: &XdpStats, elapsed: Duration) -> XdpStatsRates {
+        let secs = elapsed.as_secs_f64();
+        if secs <= 0.0 {
+            return XdpStatsRates::default();
+        }
+
+        let delta = |now: u64, then: u64| now.saturating_sub(then) as f64 / secs;
+        XdpStatsRates {
+            rx_pps: delta(self.rx_packets, earlier.rx_packets),
+            rx_bps: delta(self.rx_bytes, earlier.rx_bytes) * 8.0,
+            dropped_pps: delta(self.dropped, earlier.dropped),
+            redirected_pps: delta(self.redirected, earlier.redirected),
+        }
+    }
+}
+
 impl fmt::Display for XdpStats {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -101,6 +154,39 @@ impl fmt::Display for XdpStats {
     }
 }
 
+/// Rates derived from two [`XdpStats`] snapshots over a sampling window
+#[derive(Debug, Default, Clone, Copy)]
+pub struct XdpStatsRates {
+    /// Received packets per second
+    pub rx_pps: f64,
+    /// Received bits per second
+    pub rx_bps: f64,
+    /// Dropped packets per second
+    pub dropped_pps: f64,
+    /// Redirected packets per second
+    pub redirected_pps: f64,
+}
+
+/// Statistics broken down by CPU and RX queue
+#[derive(Debug, Default, Clone)]
+pub struct XdpDetailedStats {
+    /// Aggregate counters across all CPUs
+    pub total: XdpStats,
+    /// Per-CPU counters, indexed by CPU id
+    pub per_cpu: Vec<XdpStats>,
+    /// Packets redirected per RX queue, indexed by queue id
+    pub per_queue: Vec<u64>,
+}
+
+/// One entry of the periodic statistics stream
+#[derive(Debug, Clone, Copy)]
+pub struct XdpStatsSample {
+    /// Counter snapshot at sample time
+    pub stats: XdpStats,
+    /// Rates over the window since the previous sample
+    pub rates: XdpStatsRates,
+}
+
 /// XDP program errors
 #[derive(Debug, Error)]
 pub enum XdpError {
@@ -132,6 +218,10 @@ pub enum XdpError {
     #[error("Null byte in string: {0}")]
     Nul(#[from] NulError),
 
+    /// Invalid runtime configuration value
+    #[error("Invalid XDP configuration: {0}")]
+    InvalidConfig(String),
+
     /// Feature not available
     #[error("XDP/libbpf feature not enabled - recompile with --features libbpf")]
     FeatureNotEnabled,
@@ -143,13 +233,132 @@ mod libbpf_impl {
     use super::*;
     use std::os::raw::c_int;
     use std::ptr;
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+
+    // config_map indices (mirror enum config_key in xdp_filter.c)
+    const CFG_PORT_MIN: u32 = 0;
+    const CFG_PORT_MAX: u32 = 1;
+    const CFG_ALLOWLIST: u32 = 2;
+
+    /// File descriptors of the maps shared between filter generations
+    #[derive(Clone, Copy)]
+    struct MapFds {
+        xsks: c_int,
+        stats: c_int,
+        config: c_int,
+        allowed_peers: c_int,
+        queue_stats: c_int,
+    }
 
     /// XDP program handle (with libbpf support)
     pub struct XdpProgram {
         obj: *mut libbpf_sys::bpf_object,
         prog: *mut libbpf_sys::bpf_program,
-        xsks_map_fd: c_int,
-        stats_map_fd: c_int,
+        maps: MapFds,
+        /// BPF link fd pinning the program to an interface (-1 when detached)
+        link_fd: c_int,
+        /// Entries currently in the allowlist; enforcement follows occupancy
+        allowed_peer_count: usize,
+    }
+
+    /// Open, optionally rewire maps, and load a BPF object file
+    ///
+    /// When `reuse` is given, the new object's maps are pointed at the
+    /// existing kernel maps *before* the object is loaded, so AF_XDP socket
+    /// registrations, statistics, and runtime configuration survive a
+    /// program swap.
+    ///
+    /// # Safety
+    /// Caller must close the returned object with `bpf_object__close`.
+    unsafe fn open_and_load(
+        path: &str,
+        reuse: Option<MapFds>,
+    ) -> Result<
+        (
+            *mut libbpf_sys::bpf_object,
+            *mut libbpf_sys::bpf_program,
+            MapFds,
+        ),
+        XdpError,
+    > {
+        let path_c = CString::new(path)?;
+
+        // SAFETY: libbpf FFI calls with valid CString pointers that outlive the calls.
+        // BPF object and program pointers are checked for null before dereferencing.
+        // File descriptors from libbpf are valid kernel handles.
+        unsafe {
+            // Open BPF object file (not yet loaded into the kernel)
+            let obj = libbpf_sys::bpf_object__open(path_c.as_ptr());
+            if obj.is_null() {
+                return Err(XdpError::LoadFailed("Failed to open BPF object".into()));
+            }
+
+            // Locate the maps before load so they can be rewired to existing fds
+            let mut map_ptrs = [ptr::null_mut(); 5];
+            for (ptr_slot, name) in map_ptrs.iter_mut().zip([
+                "xsks_map",
+                "stats_map",
+                "config_map",
+                "allowed_peers",
+                "queue_stats_map",
+            ]) {
+                let map_name = CString::new(name)?;
+                let map = libbpf_sys::bpf_object__find_map_by_name(obj, map_name.as_ptr());
+                if map.is_null() {
+                    libbpf_sys::bpf_object__close(obj);
+                    return Err(XdpError::NotFound(format!("{name} not found")));
+                }
+                *ptr_slot = map;
+            }
+
+            if let Some(reuse) = reuse {
+                let reuse_fds = [
+                    reuse.xsks,
+                    reuse.stats,
+                    reuse.config,
+                    reuse.allowed_peers,
+                    reuse.queue_stats,
+                ];
+                for (map, fd) in map_ptrs.iter().zip(reuse_fds) {
+                    if libbpf_sys::bpf_map__reuse_fd(*map, fd) != 0 {
+                        libbpf_sys::bpf_object__close(obj);
+                        return Err(XdpError::LoadFailed(
+                            "Failed to reuse existing BPF maps".into(),
+                        ));
+                    }
+                }
+            }
+
+            // Load BPF object into kernel
+            if libbpf_sys::bpf_object__load(obj) != 0 {
+                libbpf_sys::bpf_object__close(obj);
+                return Err(XdpError::LoadFailed(
+                    "Failed to load BPF object into kernel".into(),
+                ));
+            }
+
+            // Find XDP program by name
+            let prog_name = CString::new("xdp_wraith_filter")?;
+            let prog = libbpf_sys::bpf_object__find_program_by_name(obj, prog_name.as_ptr());
+            if prog.is_null() {
+                libbpf_sys::bpf_object__close(obj);
+                return Err(XdpError::NotFound(
+                    "xdp_wraith_filter program not found".into(),
+                ));
+            }
+
+            let maps = MapFds {
+                xsks: libbpf_sys::bpf_map__fd(map_ptrs[0]),
+                stats: libbpf_sys::bpf_map__fd(map_ptrs[1]),
+                config: libbpf_sys::bpf_map__fd(map_ptrs[2]),
+                allowed_peers: libbpf_sys::bpf_map__fd(map_ptrs[3]),
+                queue_stats: libbpf_sys::bpf_map__fd(map_ptrs[4]),
+            };
+
+            Ok((obj, prog, maps))
+        }
     }
 
     impl XdpProgram {
@@ -161,68 +370,33 @@ mod libbpf_impl {
         /// # Errors
         /// Returns an error if the file cannot be loaded or required maps are missing
         pub fn load(path: &str) -> Result<Self, XdpError> {
-            let path_c = CString::new(path)?;
-
-            // SAFETY: libbpf FFI calls with valid CString pointers that outlive the calls.
-            // BPF object and program pointers are checked for null before dereferencing.
-            // File descriptors from libbpf are valid kernel handles.
-            unsafe {
-                // Open BPF object file
-                let obj = libbpf_sys::bpf_object__open(path_c.as_ptr());
-                if obj.is_null() {
-                    return Err(XdpError::LoadFailed("Failed to open BPF object".into()));
-                }
+            // SAFETY: ownership of the returned object is taken by Self, whose Drop
+            // closes it with bpf_object__close.
+            let (obj, prog, maps) = unsafe { open_and_load(path, None)? };
 
-                // Load BPF object into kernel
-                if libbpf_sys::bpf_object__load(obj) != 0 {
-                    libbpf_sys::bpf_object__close(obj);
-                    return Err(XdpError::LoadFailed("Failed to load BPF object into kernel".into()));
-                }
-
-                // Find XDP program by name
-                let prog_name = CString::new("xdp_wraith_filter")?;
-                let prog = libbpf_sys::bpf_object__find_program_by_name(obj, prog_name.as_ptr());
-                if prog.is_null() {
-                    libbpf_sys::bpf_object__close(obj);
-                    return Err(XdpError::NotFound("xdp_wraith_filter program not found".into()));
-                }
-
-                // Get xsks_map file descriptor
-                let xsks_map_name = CString::new("xsks_map")?;
-                let xsks_map = libbpf_sys::bpf_object__find_map_by_name(obj, xsks_map_name.as_ptr());
-                let xsks_map_fd = if !xsks_map.is_null() {
-                    libbpf_sys::bpf_map__fd(xsks_map)
-                } else {
-                    return Err(XdpError::NotFound("xsks_map not found".into()));
-                };
-
-                // Get stats_map file descriptor
-                let stats_map_name = CString::new("stats_map")?;
-                let stats_map = libbpf_sys::bpf_object__find_map_by_name(obj, stats_map_name.as_ptr());
-                let stats_map_fd = if !stats_map.is_null() {
-                    libbpf_sys::bpf_map__fd(stats_map)
-                } else {
-                    return Err(XdpError::NotFound("stats_map not found".into()));
-                };
-
-                Ok(Self {
-                    obj,
-                    prog,
-                    xsks_map_fd,
-                    stats_map_fd,
-                })
-            }
+            Ok(Self {
+                obj,
+                prog,
+                maps,
+                link_fd: -1,
+                allowed_peer_count: 0,
+            })
         }
 
         /// Attach XDP program to a network interface
         ///
+        /// Attachment uses an XDP BPF link (`BPF_LINK_CREATE`) rather than the
+        /// legacy netlink-based `bpf_set_link_xdp_fd`, so the attachment can
+        /// later be upgraded in place with [`replace`](Self::replace) and is
+        /// automatically torn down if the holding process dies.
+        ///
         /// # Arguments
         /// * `ifname` - Interface name (e.g., "eth0", "ens160")
         /// * `flags` - XDP attachment flags (SKB, DRV, or HW mode)
         ///
         /// # Errors
         /// Returns an error if the interface doesn't exist or attachment fails
-        pub fn attach(&self, ifname: &str, flags: XdpFlags) -> Result<(), XdpError> {
+        pub fn attach(&mut self, ifname: &str, flags: XdpFlags) -> Result<(), XdpError> {
             let ifname_c = CString::new(ifname)?;
             // SAFETY: if_nametoindex is a standard libc function that accepts a valid null-terminated
             // C string pointer. The CString ensures the string is properly null-terminated and valid.
@@ -232,42 +406,105 @@ mod libbpf_impl {
                 return Err(XdpError::InvalidInterface(ifname.to_string()));
             }
 
-            // SAFETY: bpf_program__fd and bpf_set_link_xdp_fd are valid libbpf FFI calls.
-            // prog pointer is valid (checked during load), ifindex is valid (checked above).
+            // SAFETY: bpf_program__fd and bpf_link_create are valid libbpf FFI calls.
+            // prog pointer is valid (checked during load), ifindex is valid (checked
+            // above), and the opts struct is zero-initialized with its size set as
+            // libbpf's forward-compatibility convention requires.
             unsafe {
                 let prog_fd = libbpf_sys::bpf_program__fd(self.prog);
-                let ret = libbpf_sys::bpf_set_link_xdp_fd(ifindex as i32, prog_fd, flags.bits());
-                if ret != 0 {
-                    return Err(XdpError::AttachFailed(format!("errno: {}", -ret)));
+                let mut opts: libbpf_sys::bpf_link_create_opts = std::mem::zeroed();
+                opts.sz = std::mem::size_of::<libbpf_sys::bpf_link_create_opts>() as _;
+                opts.flags = flags.bits();
+
+                let link_fd = libbpf_sys::bpf_link_create(
+                    prog_fd,
+                    ifindex as c_int,
+                    libbpf_sys::BPF_XDP,
+                    &opts,
+                );
+                if link_fd < 0 {
+                    return Err(XdpError::AttachFailed(format!("errno: {}", -link_fd)));
                 }
+                self.link_fd = link_fd;
             }
 
             Ok(())
         }
 
-        /// Detach XDP program from a network interface
+        /// Detach XDP program from its interface
         ///
-        /// # Arguments
-        /// * `ifname` - Interface name
-        pub fn detach(&self, ifname: &str) -> Result<(), XdpError> {
-            let ifname_c = CString::new(ifname)?;
-            // SAFETY: if_nametoindex is a standard libc function that accepts a valid null-terminated
-            // C string pointer. The CString ensures the string is properly null-terminated and valid.
-            let ifindex = unsafe { libc::if_nametoindex(ifname_c.as_ptr()) };
+        /// Destroys the BPF link created by [`attach`](Self::attach); the
+        /// kernel removes the program from the interface when the link closes.
+        pub fn detach(&mut self) -> Result<(), XdpError> {
+            if self.link_fd < 0 {
+                return Err(XdpError::DetachFailed("program is not attached".into()));
+            }
 
-            if ifindex == 0 {
-                return Err(XdpError::InvalidInterface(ifname.to_string()));
+            // SAFETY: link_fd is a valid BPF link file descriptor obtained from
+            // bpf_link_create. Closing it destroys the link and detaches the program.
+            let ret = unsafe { libc::close(self.link_fd) };
+            self.link_fd = -1;
+            if ret != 0 {
+                return Err(XdpError::DetachFailed(format!(
+                    "errno: {}",
+                    std::io::Error::last_os_error()
+                )));
             }
 
-            // SAFETY: bpf_set_link_xdp_fd is a valid libbpf FFI call. Passing -1 as fd detaches
-            // the current XDP program. ifindex is valid (checked above).
-            unsafe {
-                let ret = libbpf_sys::bpf_set_link_xdp_fd(ifindex as i32, -1, 0);
-                if ret != 0 {
-                    return Err(XdpError::DetachFailed(format!("errno: {}", -ret)));
+            Ok(())
+        }
+
+        /// Atomically replace the running XDP program with a new object file
+        ///
+        /// Loads `path`, rewires its maps (`xsks_map`, `stats_map`,
+        /// `config_map`, `allowed_peers`) to those of the currently loaded
+        /// program (preserving AF_XDP socket registrations, accumulated
+        /// statistics, and runtime filter configuration), then swaps the
+        /// program on the existing BPF link via `BPF_LINK_UPDATE`. The swap is atomic from the
+        /// dataplane's point of view: every packet is processed by exactly one
+        /// of the two programs and no window exists where the interface has no
+        /// filter, so established sessions are not dropped.
+        ///
+        /// If the program is not currently attached, the new object simply
+        /// replaces the loaded one and a later [`attach`](Self::attach) will
+        /// use it.
+        ///
+        /// # Errors
+        /// Returns an error if the new object cannot be loaded, its maps are
+        /// incompatible with the existing ones, or the link update fails. On
+        /// error the old program remains loaded and attached.
+        pub fn replace(&mut self, path: &str) -> Result<(), XdpError> {
+            // SAFETY: on success ownership of new_obj transfers to self (closed in
+            // Drop); on any failure below it is closed before returning.
+            let (new_obj, new_prog, maps) = unsafe { open_and_load(path, Some(self.maps))? };
+
+            if self.link_fd >= 0 {
+                // SAFETY: bpf_program__fd and bpf_link_update are valid libbpf FFI
+                // calls. link_fd is a valid BPF link fd, new_prog was checked
+                // non-null by open_and_load, and NULL opts selects the defaults.
+                unsafe {
+                    let new_prog_fd = libbpf_sys::bpf_program__fd(new_prog);
+                    let ret = libbpf_sys::bpf_link_update(self.link_fd, new_prog_fd, ptr::null());
+                    if ret != 0 {
+                        libbpf_sys::bpf_object__close(new_obj);
+                        return Err(XdpError::AttachFailed(format!(
+                            "link update failed: errno: {}",
+                            -ret
+                        )));
+                    }
                 }
             }
 
+            // SAFETY: old object is no longer referenced by the link; closing it
+            // releases the old program while the shared maps stay alive through
+            // the fds held by the new object.
+            unsafe {
+                libbpf_sys::bpf_object__close(self.obj);
+            }
+            self.obj = new_obj;
+            self.prog = new_prog;
+            self.maps = maps;
+
             Ok(())
         }
 
@@ -275,61 +512,250 @@ mod libbpf_impl {
         ///
         /// This map is used to register AF_XDP sockets for packet redirection
         pub fn xsks_map_fd(&self) -> c_int {
-            self.xsks_map_fd
+            self.maps.xsks
         }
 
         /// Get the stats_map file descriptor
         pub fn stats_map_fd(&self) -> c_int {
-            self.stats_map_fd
+            self.maps.stats
+        }
+
+        /// Set the UDP destination port range accepted by the filter
+        ///
+        /// Takes effect immediately for subsequent packets; no reload or
+        /// re-attach is required. Ports outside the range fall through to the
+        /// kernel network stack.
+        ///
+        /// # Errors
+        /// Returns [`XdpError::InvalidConfig`] if `min` is zero or exceeds
+        /// `max`.
+        pub fn set_port_range(&self, min: u16, max: u16) -> Result<(), XdpError> {
+            if min == 0 || min > max {
+                return Err(XdpError::InvalidConfig(format!(
+                    "invalid port range {min}-{max}"
+                )));
+            }
+
+            self.update_config(CFG_PORT_MIN, u32::from(min))?;
+            self.update_config(CFG_PORT_MAX, u32::from(max))
+        }
+
+        /// Add a peer source address to the allowlist
+        ///
+        /// Adding the first entry enables enforcement: from then on only
+        /// allowlisted peers reach the AF_XDP sockets, everything else on the
+        /// WRAITH port range is dropped. An empty allowlist admits every peer.
+        pub fn add_allowed_peer(&mut self, addr: IpAddr) -> Result<(), XdpError> {
+            let key = peer_allowlist_key(addr);
+            let value: u8 = 1;
+
+            // SAFETY: bpf_map_update_elem is a valid libbpf FFI call. The map fd is
+            // valid (obtained during load) and key/value pointers reference
+            // stack-allocated buffers matching the map's key/value sizes.
+            let ret = unsafe {
+                libbpf_sys::bpf_map_update_elem(
+                    self.maps.allowed_peers,
+                    key.as_ptr() as *const _,
+                    &value as *const u8 as *const _,
+                    u64::from(libbpf_sys::BPF_NOEXIST),
+                )
+            };
+
+            match -ret {
+                0 => {
+                    self.allowed_peer_count += 1;
+                    if self.allowed_peer_count == 1 {
+                        self.update_config(CFG_ALLOWLIST, 1)?;
+                    }
+                    Ok(())
+                }
+                libc::EEXIST => Ok(()), // already allowlisted
+                errno => Err(std::io::Error::from_raw_os_error(errno).into()),
+            }
+        }
+
+        /// Remove a peer source address from the allowlist
+        ///
+        /// Removing the last entry disables enforcement, reverting to
+        /// admitting every peer. Removing an address that is not allowlisted
+        /// is a no-op.
+        pub fn remove_allowed_peer(&mut self, addr: IpAddr) -> Result<(), XdpError> {
+            let key = peer_allowlist_key(addr);
+
+            // SAFETY: bpf_map_delete_elem is a valid libbpf FFI call. The map fd is
+            // valid and the key pointer references a stack buffer of the map's key size.
+            let ret = unsafe {
+                libbpf_sys::bpf_map_delete_elem(self.maps.allowed_peers, key.as_ptr() as *const _)
+            };
+
+            match -ret {
+                0 => {
+                    self.allowed_peer_count = self.allowed_peer_count.saturating_sub(1);
+                    if self.allowed_peer_count == 0 {
+                        self.update_config(CFG_ALLOWLIST, 0)?;
+                    }
+                    Ok(())
+                }
+                libc::ENOENT => Ok(()), // was not allowlisted
+                errno => Err(std::io::Error::from_raw_os_error(errno).into()),
+            }
+        }
+
+        /// Write one entry of the runtime configuration map
+        fn update_config(&self, key: u32, value: u32) -> Result<(), XdpError> {
+            // SAFETY: bpf_map_update_elem is a valid libbpf FFI call. The map fd is
+            // valid and key/value pointers reference stack-allocated u32 values
+            // matching the map's key/value sizes.
+            let ret = unsafe {
+                libbpf_sys::bpf_map_update_elem(
+                    self.maps.config,
+                    &key as *const u32 as *const _,
+                    &value as *const u32 as *const _,
+                    u64::from(libbpf_sys::BPF_ANY),
+                )
+            };
+
+            if ret != 0 {
+                return Err(std::io::Error::from_raw_os_error(-ret).into());
+            }
+            Ok(())
         }
 
         /// Read statistics from the XDP program
         ///
         /// Aggregates per-CPU statistics into a single XdpStats structure.
         pub fn read_stats(&self) -> Result<XdpStats, XdpError> {
-            let mut stats = XdpStats::default();
-            let num_cpus = num_cpus::get() as u32;
+            Ok(self.read_detailed_stats()?.total)
+        }
+
+        /// Read statistics broken down by CPU and RX queue
+        ///
+        /// The stats and queue maps are per-CPU arrays: a single lookup
+        /// returns one value slot per possible CPU, which is exposed both
+        /// individually and summed into the aggregate.
+        pub fn read_detailed_stats(&self) -> Result<XdpDetailedStats, XdpError> {
+            // SAFETY: libbpf_num_possible_cpus performs no pointer access.
+            let ncpus = unsafe { libbpf_sys::libbpf_num_possible_cpus() };
+            if ncpus <= 0 {
+                return Err(XdpError::Io(std::io::Error::other(
+                    "cannot determine possible CPU count",
+                )));
+            }
+            let ncpus = ncpus as usize;
 
-            // SAFETY: bpf_map_lookup_elem is a valid libbpf FFI call. stats_map_fd is a valid
-            // BPF map file descriptor (obtained during load). Pointers to stat_type and value
-            // are valid stack-allocated variables with correct lifetime.
+            let mut per_cpu = vec![XdpStats::default(); ncpus];
+            let mut values = vec![0u64; ncpus];
+
+            // SAFETY: bpf_map_lookup_elem is a valid libbpf FFI call. The map fds
+            // are valid (obtained during load), the key points to a stack u32, and
+            // the value buffer holds one u64 slot per possible CPU as per-CPU map
+            // lookups require.
             unsafe {
                 for stat_type in 0..4u32 {
-                    let mut total = 0u64;
-
-                    for cpu in 0..num_cpus {
-                        let mut value = 0u64;
-                        let ret = libbpf_sys::bpf_map_lookup_elem(
-                            self.stats_map_fd,
-                            &stat_type as *const u32 as *const _,
-                            &mut value as *mut u64 as *mut _,
-                        );
-
-                        if ret == 0 {
-                            total += value;
+                    let ret = libbpf_sys::bpf_map_lookup_elem(
+                        self.maps.stats,
+                        &stat_type as *const u32 as *const _,
+                        values.as_mut_ptr() as *mut _,
+                    );
+                    if ret != 0 {
+                        continue;
+                    }
+
+                    for (cpu, value) in values.iter().enumerate() {
+                        match stat_type {
+                            0 => per_cpu[cpu].rx_packets = *value,
+                            1 => per_cpu[cpu].rx_bytes = *value,
+                            2 => per_cpu[cpu].dropped = *value,
+                            3 => per_cpu[cpu].redirected = *value,
+                            _ => {}
                         }
                     }
+                }
+            }
+
+            let mut total = XdpStats::default();
+            for stats in &per_cpu {
+                total.accumulate(stats);
+            }
 
-                    match stat_type {
-                        0 => stats.rx_packets = total,
-                        1 => stats.rx_bytes = total,
-                        2 => stats.dropped = total,
-                        3 => stats.redirected = total,
-                        _ => {}
+            let mut per_queue = vec![0u64; MAX_QUEUES as usize];
+            // SAFETY: as above; queue_stats is a per-CPU array keyed by queue id.
+            unsafe {
+                for (queue_id, slot) in per_queue.iter_mut().enumerate() {
+                    let key = queue_id as u32;
+                    let ret = libbpf_sys::bpf_map_lookup_elem(
+                        self.maps.queue_stats,
+                        &key as *const u32 as *const _,
+                        values.as_mut_ptr() as *mut _,
+                    );
+                    if ret == 0 {
+                        *slot = values.iter().sum();
                     }
                 }
             }
 
-            Ok(stats)
+            Ok(XdpDetailedStats {
+                total,
+                per_cpu,
+                per_queue,
+            })
+        }
+
+        /// Periodic statistics snapshots as an async stream
+        ///
+        /// Samples the counters every `interval` and yields each snapshot
+        /// together with rates derived over the window since the previous
+        /// sample. The stream ends when the receiver is dropped or a map
+        /// read fails. Must be called from within a tokio runtime.
+        pub fn stats_stream(
+            self: &Arc<Self>,
+            interval: Duration,
+        ) -> ReceiverStream<XdpStatsSample> {
+            let (tx, rx) = mpsc::channel(16);
+            let prog = Arc::clone(self);
+
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(interval);
+                ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+                ticker.tick().await; // first tick completes immediately
+
+                let mut prev = prog.read_stats().unwrap_or_default();
+                let mut prev_at = std::time::Instant::now();
+
+                loop {
+                    ticker.tick().await;
+                    let stats = match prog.read_stats() {
+                        Ok(stats) => stats,
+                        Err(e) => {
+                            tracing::warn!("Failed to read XDP stats: {}", e);
+                            break;
+                        }
+                    };
+                    let now = std::time::Instant::now();
+                    let rates = stats.rates_since(&prev, now - prev_at);
+                    prev = stats;
+                    prev_at = now;
+
+                    if tx.send(XdpStatsSample { stats, rates }).await.is_err() {
+                        break;
+                    }
+                }
+            });
+
+            ReceiverStream::new(rx)
         }
     }
 
     impl Drop for XdpProgram {
         fn drop(&mut self) {
-            // SAFETY: bpf_object__close is a valid libbpf FFI call that safely handles
-            // cleanup of BPF resources. obj pointer is either null (checked) or a valid
-            // pointer obtained from bpf_object__open.
+            // SAFETY: link_fd (when set) is a valid BPF link fd from bpf_link_create;
+            // closing it detaches the program. bpf_object__close is a valid libbpf FFI
+            // call that safely handles cleanup of BPF resources. obj pointer is either
+            // null (checked) or a valid pointer obtained from bpf_object__open.
             unsafe {
+                if self.link_fd >= 0 {
+                    libc::close(self.link_fd);
+                }
                 if !self.obj.is_null() {
                     libbpf_sys::bpf_object__close(self.obj);
                 }
@@ -348,7 +774,8 @@ mod libbpf_impl {
     // SAFETY: `XdpProgram` is Sync because:
     // - BPF file descriptors are kernel-managed resources with atomic refcounting
     // - libbpf map operations (bpf_map_lookup_elem, etc.) are thread-safe
-    // - All methods take &self and operate on kernel resources that are synchronized by the kernel
+    // - Shared-access methods take &self and operate on kernel resources that are synchronized
+    //   by the kernel; mutating methods (attach/detach/replace) require &mut self
     // - No interior mutability without synchronization
     // - Concurrent BPF map access is safely handled by the kernel's BPF subsystem
     unsafe impl Sync for XdpProgram {}
@@ -372,12 +799,17 @@ mod stub_impl {
         }
 
         /// Attach XDP program - stub implementation
-        pub fn attach(&self, _ifname: &str, _flags: XdpFlags) -> Result<(), XdpError> {
+        pub fn attach(&mut self, _ifname: &str, _flags: XdpFlags) -> Result<(), XdpError> {
             Err(XdpError::FeatureNotEnabled)
         }
 
         /// Detach XDP program - stub implementation
-        pub fn detach(&self, _ifname: &str) -> Result<(), XdpError> {
+        pub fn detach(&mut self) -> Result<(), XdpError> {
+            Err(XdpError::FeatureNotEnabled)
+        }
+
+        /// Atomically replace the loaded program - stub implementation
+        pub fn replace(&mut self, _path: &str) -> Result<(), XdpError> {
             Err(XdpError::FeatureNotEnabled)
         }
 
@@ -391,10 +823,39 @@ mod stub_impl {
             -1
         }
 
+        /// Set accepted port range - stub implementation
+        pub fn set_port_range(&self, _min: u16, _max: u16) -> Result<(), XdpError> {
+            Err(XdpError::FeatureNotEnabled)
+        }
+
+        /// Add peer to allowlist - stub implementation
+        pub fn add_allowed_peer(&mut self, _addr: IpAddr) -> Result<(), XdpError> {
+            Err(XdpError::FeatureNotEnabled)
+        }
+
+        /// Remove peer from allowlist - stub implementation
+        pub fn remove_allowed_peer(&mut self, _addr: IpAddr) -> Result<(), XdpError> {
+            Err(XdpError::FeatureNotEnabled)
+        }
+
         /// Read statistics - stub implementation
         pub fn read_stats(&self) -> Result<XdpStats, XdpError> {
             Err(XdpError::FeatureNotEnabled)
         }
+
+        /// Read per-CPU/per-queue statistics - stub implementation
+        pub fn read_detailed_stats(&self) -> Result<XdpDetailedStats, XdpError> {
+            Err(XdpError::FeatureNotEnabled)
+        }
+
+        /// Periodic statistics stream - stub implementation (yields nothing)
+        pub fn stats_stream(
+            self: &std::sync::Arc<Self>,
+            _interval: Duration,
+        ) -> tokio_stream::wrappers::ReceiverStream<XdpStatsSample> {
+            let (_tx, rx) = tokio::sync::mpsc::channel(1);
+            tokio_stream::wrappers::ReceiverStream::new(rx)
+        }
     }
 }
 
@@ -405,6 +866,9 @@ pub use libbpf_impl::*;
 #[cfg(not(feature = "libbpf"))]
 pub use stub_impl::*;
 
+// Privileged loader helper for split-process startup
+pub mod loader;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -454,4 +918,126 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), XdpError::FeatureNotEnabled));
     }
+
+    #[test]
+    #[cfg(not(feature = "libbpf"))]
+    fn test_stub_replace_returns_error() {
+        let mut prog = XdpProgram;
+        let result = prog.replace("test_v2.o");
+        assert!(matches!(result.unwrap_err(), XdpError::FeatureNotEnabled));
+    }
+
+    #[test]
+    fn test_peer_allowlist_key_v4_mapped() {
+        let key = peer_allowlist_key("192.0.2.1".parse().unwrap());
+        let mut expected = [0u8; 16];
+        expected[10] = 0xff;
+        expected[11] = 0xff;
+        expected[12..].copy_from_slice(&[192, 0, 2, 1]);
+        assert_eq!(key, expected);
+    }
+
+    #[test]
+    fn test_peer_allowlist_key_v6_passthrough() {
+        let addr: IpAddr = "2001:db8::1".parse().unwrap();
+        let key = peer_allowlist_key(addr);
+        match addr {
+            IpAddr::V6(v6) => assert_eq!(key, v6.octets()),
+            IpAddr::V4(_) => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_rates_since() {
+        let earlier = XdpStats {
+            rx_packets: 1000,
+            rx_bytes: 100_000,
+            dropped: 10,
+            redirected: 900,
+        };
+        let now = XdpStats {
+            rx_packets: 3000,
+            rx_bytes: 300_000,
+            dropped: 10,
+            redirected: 2800,
+        };
+
+        let rates = now.rates_since(&earlier, Duration::from_secs(2));
+        assert_eq!(rates.rx_pps, 1000.0);
+        assert_eq!(rates.rx_bps, 800_000.0);
+        assert_eq!(rates.dropped_pps, 0.0);
+        assert_eq!(rates.redirected_pps, 950.0);
+    }
+
+    #[test]
+    fn test_rates_since_zero_window() {
+        let stats = XdpStats {
+            rx_packets: 1000,
+            ..Default::default()
+        };
+        let rates = stats.rates_since(&XdpStats::default(), Duration::ZERO);
+        assert_eq!(rates.rx_pps, 0.0);
+        assert_eq!(rates.rx_bps, 0.0);
+    }
+
+    #[test]
+    fn test_rates_since_counter_reset() {
+        // Counters reset (e.g. after replace()) must not produce negative rates
+        let earlier = XdpStats {
+            rx_packets: 5000,
+            rx_bytes: 500_000,
+            dropped: 50,
+            redirected: 4950,
+        };
+        let rates = XdpStats::default().rates_since(&earlier, Duration::from_secs(1));
+        assert_eq!(rates.rx_pps, 0.0);
+        assert_eq!(rates.rx_bps, 0.0);
+        assert_eq!(rates.dropped_pps, 0.0);
+        assert_eq!(rates.redirected_pps, 0.0);
+    }
+
+    #[test]
+    fn test_queue_constant() {
+        assert_eq!(MAX_QUEUES, 64);
+    }
+
+    #[test]
+    #[cfg(not(feature = "libbpf"))]
+    fn test_stub_detailed_stats_returns_error() {
+        let prog = XdpProgram;
+        assert!(matches!(
+            prog.read_detailed_stats().unwrap_err(),
+            XdpError::FeatureNotEnabled
+        ));
+    }
+
+    #[tokio::test]
+    #[cfg(not(feature = "libbpf"))]
+    async fn test_stub_stats_stream_is_empty() {
+        use tokio_stream::StreamExt;
+
+        let prog = std::sync::Arc::new(XdpProgram);
+        let mut stream = prog.stats_stream(Duration::from_millis(10));
+        assert!(stream.next().await.is_none());
+    }
+
+    #[test]
+    #[cfg(not(feature = "libbpf"))]
+    fn test_stub_allowlist_returns_error() {
+        let mut prog = XdpProgram;
+        assert!(matches!(
+            prog.set_port_range(42000, 43000).unwrap_err(),
+            XdpError::FeatureNotEnabled
+        ));
+        assert!(matches!(
+            prog.add_allowed_peer("192.0.2.1".parse().unwrap())
+                .unwrap_err(),
+            XdpError::FeatureNotEnabled
+        ));
+        assert!(matches!(
+            prog.remove_allowed_peer("192.0.2.1".parse().unwrap())
+                .unwrap_err(),
+            XdpError::FeatureNotEnabled
+        ));
+    }
 }
@@ -0,0 +1,72 @@
+//! Privileged loader helper for split-process startup.
+//!
+//! Loading and attaching an XDP program needs `CAP_BPF` and
+//! `CAP_NET_ADMIN`, but the data plane should not run privileged. This
+//! module is the privileged half: a small process that loads and
+//! attaches the filter, then hands duplicated BPF map descriptors to the
+//! unprivileged daemon over the Unix socket protocol defined in
+//! `wraith_transport::xdp_loader`. Programs stay attached for the
+//! helper's lifetime; the daemon owns its descriptor copies and keeps
+//! the maps alive independently.
+
+use crate::{XdpFlags, XdpProgram};
+use std::collections::HashMap;
+use std::os::fd::{FromRawFd, OwnedFd, RawFd};
+use std::path::Path;
+use std::sync::Mutex;
+use wraith_transport::xdp_loader::{self, XdpFdRole, XdpLoadRequest, XdpLoaderError};
+
+/// Duplicate a borrowed map descriptor for passing to the daemon
+fn dup_fd(fd: RawFd) -> Result<OwnedFd, String> {
+    // SAFETY: F_DUPFD_CLOEXEC on a valid descriptor yields a fresh one
+    let dup = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+    if dup < 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    // SAFETY: the descriptor was just created and is unowned
+    Ok(unsafe { OwnedFd::from_raw_fd(dup) })
+}
+
+/// Serve loader requests from the unprivileged daemon
+///
+/// Binds the rendezvous socket and serves until accepting fails. The
+/// first request for an interface loads `object_path` and attaches it;
+/// later requests (other queues, daemon restarts) reuse the attached
+/// program, so the data plane can restart without a reattach. Load and
+/// attach failures are reported to the requesting daemon rather than
+/// stopping the helper.
+///
+/// # Errors
+///
+/// Returns an error if the socket cannot be bound or accepting
+/// connections fails.
+pub fn run_helper<P: AsRef<Path>>(
+    socket_path: P,
+    object_path: &str,
+    flags: XdpFlags,
+) -> Result<(), XdpLoaderError> {
+    let listener = xdp_loader::bind_loader_socket(socket_path)?;
+    let programs: Mutex<HashMap<String, XdpProgram>> = Mutex::new(HashMap::new());
+
+    xdp_loader::serve(&listener, move |request: &XdpLoadRequest| {
+        let mut programs = programs.lock().expect("loader lock poisoned");
+
+        if !programs.contains_key(&request.interface) {
+            let mut program = XdpProgram::load(object_path).map_err(|e| e.to_string())?;
+            program
+                .attach(&request.interface, flags)
+                .map_err(|e| e.to_string())?;
+            tracing::info!(
+                interface = %request.interface,
+                "XDP program attached by privileged helper"
+            );
+            programs.insert(request.interface.clone(), program);
+        }
+
+        let program = programs.get(&request.interface).expect("inserted above");
+        Ok(vec![
+            (XdpFdRole::XsksMap, dup_fd(program.xsks_map_fd())?),
+            (XdpFdRole::StatsMap, dup_fd(program.stats_map_fd())?),
+        ])
+    })
+}
@@ -21,13 +21,16 @@
 #include <bpf/bpf_helpers.h>
 #include <bpf/bpf_endian.h>
 
-/* WRAITH port range */
+/* WRAITH port range (compiled-in defaults, overridable via config_map) */
 #define WRAITH_PORT_MIN 40000
 #define WRAITH_PORT_MAX 50000
 
 /* Maximum number of AF_XDP sockets */
 #define MAX_SOCKETS 64
 
+/* Maximum number of allowlisted peers */
+#define MAX_ALLOWED_PEERS 1024
+
 /*
  * Map for AF_XDP socket file descriptors
  * Key: Queue ID
@@ -59,6 +62,48 @@ enum stat_type {
     STAT_REDIRECTED = 3,
 };
 
+/*
+ * Per-CPU, per-RX-queue redirect counters
+ * Key: RX queue index
+ * Value: packets redirected from that queue
+ */
+struct {
+    __uint(type, BPF_MAP_TYPE_PERCPU_ARRAY);
+    __uint(key_size, sizeof(__u32));
+    __uint(value_size, sizeof(__u64));
+    __uint(max_entries, MAX_SOCKETS);
+} queue_stats_map SEC(".maps");
+
+/*
+ * Runtime configuration map, written from userspace.
+ * Zero/missing entries fall back to the compiled-in defaults.
+ */
+struct {
+    __uint(type, BPF_MAP_TYPE_ARRAY);
+    __uint(key_size, sizeof(__u32));
+    __uint(value_size, sizeof(__u32));
+    __uint(max_entries, 3);
+} config_map SEC(".maps");
+
+/* Configuration indices */
+enum config_key {
+    CFG_PORT_MIN = 0,
+    CFG_PORT_MAX = 1,
+    CFG_ALLOWLIST = 2,  /* non-zero: only allowlisted peers pass */
+};
+
+/*
+ * Peer allowlist, written from userspace.
+ * Key: 16-byte source address (IPv4 stored as IPv4-mapped IPv6)
+ * Value: unused (presence means allowed)
+ */
+struct {
+    __uint(type, BPF_MAP_TYPE_HASH);
+    __uint(key_size, 16);
+    __uint(value_size, sizeof(__u8));
+    __uint(max_entries, MAX_ALLOWED_PEERS);
+} allowed_peers SEC(".maps");
+
 /*
  * Update statistics counter
  */
@@ -69,6 +114,16 @@ static __always_inline void update_stat(__u32 type, __u64 delta)
         __sync_fetch_and_add(value, delta);
 }
 
+/*
+ * Update per-queue redirect counter
+ */
+static __always_inline void update_queue_stat(__u32 queue_id)
+{
+    __u64 *value = bpf_map_lookup_elem(&queue_stats_map, &queue_id);
+    if (value)
+        __sync_fetch_and_add(value, 1);
+}
+
 /*
  * Parse Ethernet header
  * Returns 0 on success, -1 on error
@@ -165,11 +220,44 @@ static __always_inline int parse_udphdr_v6(void *data, void *data_end,
 }
 
 /*
- * Check if destination port is in WRAITH range
+ * Check if destination port is in the configured WRAITH range
  */
 static __always_inline int is_wraith_port(__u16 dport)
 {
-    return (dport >= WRAITH_PORT_MIN && dport <= WRAITH_PORT_MAX);
+    __u32 key_min = CFG_PORT_MIN;
+    __u32 key_max = CFG_PORT_MAX;
+    __u32 *cfg_min = bpf_map_lookup_elem(&config_map, &key_min);
+    __u32 *cfg_max = bpf_map_lookup_elem(&config_map, &key_max);
+    __u16 port_min = (cfg_min && *cfg_min) ? *cfg_min : WRAITH_PORT_MIN;
+    __u16 port_max = (cfg_max && *cfg_max) ? *cfg_max : WRAITH_PORT_MAX;
+
+    return (dport >= port_min && dport <= port_max);
+}
+
+/*
+ * Check the source address against the peer allowlist.
+ * When enforcement is disabled (the default), all peers are allowed.
+ */
+static __always_inline int is_peer_allowed(const __u8 key[16])
+{
+    __u32 cfg_key = CFG_ALLOWLIST;
+    __u32 *enforced = bpf_map_lookup_elem(&config_map, &cfg_key);
+
+    if (!enforced || *enforced == 0)
+        return 1;
+
+    return bpf_map_lookup_elem(&allowed_peers, key) != NULL;
+}
+
+/*
+ * Build an allowlist key from an IPv4 source address (IPv4-mapped IPv6)
+ */
+static __always_inline void peer_key_v4(__be32 saddr, __u8 key[16])
+{
+    __builtin_memset(key, 0, 16);
+    key[10] = 0xff;
+    key[11] = 0xff;
+    __builtin_memcpy(&key[12], &saddr, 4);
 }
 
 /*
@@ -190,6 +278,7 @@ int xdp_wraith_filter(struct xdp_md *ctx)
     __u16 eth_proto;
     __u16 dport;
     __u32 queue_id;
+    __u8 peer_key[16];
     int ret;
 
     /* Parse Ethernet header */
@@ -216,6 +305,13 @@ int xdp_wraith_filter(struct xdp_md *ctx)
         if (!is_wraith_port(dport))
             goto pass;
 
+        /* Check source against peer allowlist */
+        peer_key_v4(ip->saddr, peer_key);
+        if (!is_peer_allowed(peer_key)) {
+            update_stat(STAT_DROPPED, 1);
+            return XDP_DROP;
+        }
+
         /* Update statistics */
         update_stat(STAT_RX_PACKETS, 1);
         update_stat(STAT_RX_BYTES, data_end - data);
@@ -225,6 +321,7 @@ int xdp_wraith_filter(struct xdp_md *ctx)
         ret = bpf_redirect_map(&xsks_map, queue_id, 0);
         if (ret == XDP_REDIRECT) {
             update_stat(STAT_REDIRECTED, 1);
+            update_queue_stat(queue_id);
             return XDP_REDIRECT;
         }
 
@@ -250,6 +347,13 @@ int xdp_wraith_filter(struct xdp_md *ctx)
         if (!is_wraith_port(dport))
             goto pass;
 
+        /* Check source against peer allowlist */
+        __builtin_memcpy(peer_key, &ipv6->saddr, 16);
+        if (!is_peer_allowed(peer_key)) {
+            update_stat(STAT_DROPPED, 1);
+            return XDP_DROP;
+        }
+
         /* Update statistics */
         update_stat(STAT_RX_PACKETS, 1);
         update_stat(STAT_RX_BYTES, data_end - data);
@@ -259,6 +363,7 @@ int xdp_wraith_filter(struct xdp_md *ctx)
         ret = bpf_redirect_map(&xsks_map, queue_id, 0);
         if (ret == XDP_REDIRECT) {
             update_stat(STAT_REDIRECTED, 1);
+            update_queue_stat(queue_id);
             return XDP_REDIRECT;
         }
 
@@ -617,7 +617,8 @@ fn test_file_chunking_integration() {
     // Create test file
     let temp_dir = TempDir::new().unwrap();
     let test_file = temp_dir.path().join("test.dat");
-    let test_data = vec![0xAA; 1024 * 1024]; // 1 MB
+    let test_data =
+        wraith_integration_tests::test_data::TestDataSpec::text_like(1).generate(1024 * 1024); // 1 MB
     std::fs::write(&test_file, &test_data).unwrap();
 
     // Chunk file
@@ -649,7 +650,8 @@ fn test_file_chunking_integration() {
 fn test_tree_hash_verification_integration() {
     let temp_dir = TempDir::new().unwrap();
     let test_file = temp_dir.path().join("test.dat");
-    let test_data = vec![0xBB; 512 * 1024]; // 512 KB
+    let test_data =
+        wraith_integration_tests::test_data::TestDataSpec::text_like(2).generate(512 * 1024); // 512 KB
     std::fs::write(&test_file, &test_data).unwrap();
 
     // Compute tree hash
@@ -2263,6 +2265,73 @@ async fn test_end_to_end_file_transfer() {
     receiver.stop().await.unwrap();
 }
 
+/// Test byte-stream pipe over an established session
+///
+/// Opens a WraithStream from one node to another, pushes bytes through
+/// it in both directions, and verifies StreamClose surfaces as EOF.
+#[tokio::test]
+async fn test_byte_stream_pipe() {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use wraith_core::node::Node;
+
+    let initiator = Node::new_random_with_port(0).await.unwrap();
+    let acceptor = Node::new_random_with_port(0).await.unwrap();
+
+    initiator.start().await.unwrap();
+    acceptor.start().await.unwrap();
+
+    let acceptor_addr = acceptor.listen_addr().await.unwrap();
+    initiator
+        .establish_session_with_addr(acceptor.node_id(), acceptor_addr)
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    // Open pipe from initiator; sessions are keyed by X25519 keys
+    let mut outbound = initiator
+        .open_stream(acceptor.x25519_public_key())
+        .await
+        .unwrap();
+
+    let mut inbound =
+        tokio::time::timeout(std::time::Duration::from_secs(5), acceptor.accept_stream())
+            .await
+            .expect("accept timed out")
+            .expect("accept queue closed");
+
+    assert_eq!(outbound.stream_id(), inbound.stream_id());
+
+    // Initiator -> acceptor
+    outbound.write_all(b"hello through the pipe").await.unwrap();
+    outbound.flush().await.unwrap();
+
+    let mut buf = vec![0u8; 64];
+    let n = tokio::time::timeout(std::time::Duration::from_secs(5), inbound.read(&mut buf))
+        .await
+        .expect("read timed out")
+        .unwrap();
+    assert_eq!(&buf[..n], b"hello through the pipe");
+
+    // Acceptor -> initiator (duplex)
+    inbound.write_all(b"ack").await.unwrap();
+    let n = tokio::time::timeout(std::time::Duration::from_secs(5), outbound.read(&mut buf))
+        .await
+        .expect("read timed out")
+        .unwrap();
+    assert_eq!(&buf[..n], b"ack");
+
+    // Shutdown propagates as EOF on the remote reader
+    outbound.shutdown().await.unwrap();
+    let n = tokio::time::timeout(std::time::Duration::from_secs(5), inbound.read(&mut buf))
+        .await
+        .expect("EOF timed out")
+        .unwrap();
+    assert_eq!(n, 0);
+
+    initiator.stop().await.unwrap();
+    acceptor.stop().await.unwrap();
+}
+
 /// Test connection establishment with Noise handshake
 ///
 /// Tests session establishment between two nodes:
@@ -2657,3 +2726,246 @@ async fn test_concurrent_transfers_node_api() {
     receiver2.stop().await.unwrap();
     receiver3.stop().await.unwrap();
 }
+
+/// Test multi-tenant embedding: isolated Node instances in one process
+///
+/// Server applications host one node per tenant inside a single process.
+/// Verify that nodes with separate identities, configs, and ports share no
+/// state: identities and ports are distinct, transfers between different
+/// tenant pairs complete concurrently, and stopping one tenant's nodes
+/// leaves the other tenant fully operational.
+#[tokio::test]
+async fn test_multi_tenant_isolated_nodes() {
+    use std::collections::HashSet;
+    use std::fs;
+    use tempfile::TempDir;
+    use wraith_core::node::{Node, NodeConfig, TransferConfig};
+
+    let temp_dir = TempDir::new().unwrap();
+
+    // Two tenants with deliberately different configurations
+    let config_a = NodeConfig {
+        listen_addr: "127.0.0.1:0".parse().unwrap(),
+        transfer: TransferConfig {
+            chunk_size: 32 * 1024,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let config_b = NodeConfig {
+        listen_addr: "127.0.0.1:0".parse().unwrap(),
+        transfer: TransferConfig {
+            chunk_size: 16 * 1024,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let sender_a = Node::new_with_config(config_a.clone()).await.unwrap();
+    let receiver_a = Node::new_with_config(config_a).await.unwrap();
+    let sender_b = Node::new_with_config(config_b.clone()).await.unwrap();
+    let receiver_b = Node::new_with_config(config_b).await.unwrap();
+
+    let tenants = [&sender_a, &receiver_a, &sender_b, &receiver_b];
+    for node in tenants {
+        node.start().await.unwrap();
+    }
+
+    // Every instance has its own identity and its own port
+    let ids: HashSet<String> = tenants.iter().map(|n| hex::encode(n.node_id())).collect();
+    assert_eq!(ids.len(), tenants.len(), "Identities must be distinct");
+
+    let mut addrs = HashSet::new();
+    for node in tenants {
+        addrs.insert(node.listen_addr().await.unwrap());
+    }
+    assert_eq!(addrs.len(), tenants.len(), "Ports must be distinct");
+
+    // One file per tenant
+    let data_a = vec![0xA1; 256 * 1024];
+    let path_a = temp_dir.path().join("tenant_a.bin");
+    fs::write(&path_a, &data_a).unwrap();
+
+    let data_b = vec![0xB2; 256 * 1024];
+    let path_b = temp_dir.path().join("tenant_b.bin");
+    fs::write(&path_b, &data_b).unwrap();
+
+    // Establish each tenant's session and start both transfers
+    let receiver_a_addr = receiver_a.listen_addr().await.unwrap();
+    let receiver_b_addr = receiver_b.listen_addr().await.unwrap();
+
+    sender_a
+        .establish_session_with_addr(receiver_a.node_id(), receiver_a_addr)
+        .await
+        .unwrap();
+    sender_b
+        .establish_session_with_addr(receiver_b.node_id(), receiver_b_addr)
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    let transfer_a = sender_a
+        .send_file(&path_a, receiver_a.x25519_public_key())
+        .await
+        .unwrap();
+    let transfer_b = sender_b
+        .send_file(&path_b, receiver_b.x25519_public_key())
+        .await
+        .unwrap();
+
+    // Both tenants' transfers complete concurrently
+    let (result_a, result_b) = tokio::join!(
+        tokio::time::timeout(
+            std::time::Duration::from_secs(30),
+            sender_a.wait_for_transfer(transfer_a),
+        ),
+        tokio::time::timeout(
+            std::time::Duration::from_secs(30),
+            sender_b.wait_for_transfer(transfer_b),
+        ),
+    );
+    result_a.expect("Tenant A timed out").unwrap();
+    result_b.expect("Tenant B timed out").unwrap();
+
+    // Stopping tenant A must not disturb tenant B: a fresh session and a
+    // second transfer still work afterwards
+    sender_a.stop().await.unwrap();
+    receiver_a.stop().await.unwrap();
+
+    let data_b2 = vec![0xB3; 128 * 1024];
+    let path_b2 = temp_dir.path().join("tenant_b_second.bin");
+    fs::write(&path_b2, &data_b2).unwrap();
+
+    let transfer_b2 = sender_b
+        .send_file(&path_b2, receiver_b.x25519_public_key())
+        .await
+        .unwrap();
+    tokio::time::timeout(
+        std::time::Duration::from_secs(30),
+        sender_b.wait_for_transfer(transfer_b2),
+    )
+    .await
+    .expect("Tenant B second transfer timed out")
+    .unwrap();
+
+    sender_b.stop().await.unwrap();
+    receiver_b.stop().await.unwrap();
+
+    // The receivers reassemble into the file names from the metadata,
+    // relative to the working directory - remove their copies
+    for name in ["tenant_a.bin", "tenant_b.bin", "tenant_b_second.bin"] {
+        fs::remove_file(name).ok();
+    }
+}
+
+/// Test multipath striping end-to-end over a second validated path
+///
+/// A UDP forwarder acts as the session's alternate address, counting
+/// packets before relaying them to the receiver. After `add_session_path`
+/// validates the forwarder address, a transfer must both complete and
+/// leave a portion of its frames through the forwarder — proving DATA
+/// frames are really striped onto the second path on the wire, not just
+/// registered in the scheduler.
+#[tokio::test]
+async fn test_multipath_stripes_data_over_second_path() {
+    use std::fs;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+    use tokio::net::UdpSocket;
+    use wraith_core::node::{Node, NodeConfig, TransferConfig};
+
+    let config = NodeConfig {
+        listen_addr: "127.0.0.1:0".parse().unwrap(),
+        transfer: TransferConfig {
+            chunk_size: 16 * 1024,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let sender = Node::new_with_config(config.clone()).await.unwrap();
+    let receiver = Node::new_with_config(config).await.unwrap();
+    sender.start().await.unwrap();
+    receiver.start().await.unwrap();
+
+    let receiver_addr = receiver.listen_addr().await.unwrap();
+
+    // Forwarder socket: the receiver's "second address"
+    let relay = Arc::new(UdpSocket::bind("127.0.0.1:0").await.unwrap());
+    let relay_addr = relay.local_addr().unwrap();
+    let relayed = Arc::new(AtomicUsize::new(0));
+    {
+        let relay = Arc::clone(&relay);
+        let relayed = Arc::clone(&relayed);
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 65536];
+            while let Ok((len, from)) = relay.recv_from(&mut buf).await {
+                // Only forward sender -> receiver; anything the receiver
+                // sends here would otherwise loop back to it
+                if from == receiver_addr {
+                    continue;
+                }
+                relayed.fetch_add(1, Ordering::Relaxed);
+                let _ = relay.send_to(&buf[..len], receiver_addr).await;
+            }
+        });
+    }
+
+    sender
+        .establish_session_with_addr(receiver.node_id(), receiver_addr)
+        .await
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+
+    // Validate the forwarder address as an additional path; the challenge
+    // and its response cross the wire through the forwarder
+    sender
+        .add_session_path(receiver.x25519_public_key(), relay_addr)
+        .await
+        .unwrap();
+    let after_validation = relayed.load(Ordering::Relaxed);
+    assert!(
+        after_validation >= 1,
+        "PATH_CHALLENGE must use the new path"
+    );
+
+    // Transfer enough data that the scheduler has many frames to stripe
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("multipath_striped.bin");
+    fs::write(&path, vec![0x5A; 512 * 1024]).unwrap();
+
+    let transfer_id = sender
+        .send_file(&path, receiver.x25519_public_key())
+        .await
+        .unwrap();
+    match tokio::time::timeout(
+        std::time::Duration::from_secs(30),
+        sender.wait_for_transfer(transfer_id),
+    )
+    .await
+    {
+        Ok(r) => r.unwrap(),
+        Err(_) => {
+            let progress = sender.get_transfer_progress(&transfer_id).await;
+            panic!(
+                "Striped transfer timed out. Progress: {:?}, relayed: {}",
+                progress,
+                relayed.load(Ordering::Relaxed)
+            );
+        }
+    }
+
+    let after_transfer = relayed.load(Ordering::Relaxed);
+    assert!(
+        after_transfer > after_validation,
+        "DATA frames must leave on the second validated path (relayed {after_validation} -> {after_transfer})"
+    );
+
+    sender.stop().await.unwrap();
+    receiver.stop().await.unwrap();
+
+    // The receiver reassembles into the file name from the metadata,
+    // relative to the working directory - remove its copy
+    fs::remove_file("multipath_striped.bin").ok();
+}
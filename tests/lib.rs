@@ -3,4 +3,5 @@
 //! Provides shared test fixtures and utilities for integration testing.
 
 pub mod fixtures;
+pub mod test_data;
 pub mod test_helpers;
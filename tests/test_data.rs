@@ -0,0 +1,261 @@
+//! Deterministic test data generation for benchmarks and integration tests
+//!
+//! Benchmarks that hash, compress, or deduplicate are only comparable
+//! across machines (and across runs) if they see byte-identical input.
+//! `vec![0xAA; n]` is reproducible but pathological — it compresses to
+//! nothing and dedupes to one block — while `rand::random` is realistic
+//! but different every run. [`TestDataSpec`] produces data that is both:
+//! a fixed seed drives a self-contained SplitMix64 generator, and the
+//! compressibility and duplication knobs shape the data to the profile a
+//! scenario needs (incompressible media, text-like documents, VM images
+//! with repeated blocks).
+//!
+//! Files of any size are generated block-by-block, so multi-GB corpora
+//! never need to fit in memory:
+//!
+//! ```no_run
+//! use wraith_integration_tests::test_data::TestDataSpec;
+//!
+//! TestDataSpec::text_like(42).write_file("corpus.dat", 4 << 30)?;
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+/// Generation granularity: profiles are applied per block
+pub const BLOCK_SIZE: usize = 4096;
+
+/// How many distinct blocks the duplication pool holds
+const POOL_CAP: usize = 64;
+
+/// Specification for a deterministic test data stream
+///
+/// Two machines constructing the same spec get byte-identical output for
+/// the same length, independent of platform or allocator behavior.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestDataSpec {
+    /// Seed for the internal SplitMix64 generator
+    pub seed: u64,
+    /// Fraction of each fresh block filled with a constant run (0.0..=1.0)
+    ///
+    /// 0.0 yields incompressible random bytes; higher values give
+    /// compressors proportionally more to work with.
+    pub compressibility: f64,
+    /// Probability that a block repeats an earlier block (0.0..=1.0)
+    ///
+    /// Models duplicate content (VM images, log archives) for
+    /// deduplication and content-addressing scenarios.
+    pub duplication: f64,
+}
+
+impl TestDataSpec {
+    /// Incompressible, non-duplicated data (encrypted/compressed media)
+    #[must_use]
+    pub fn random(seed: u64) -> Self {
+        Self {
+            seed,
+            compressibility: 0.0,
+            duplication: 0.0,
+        }
+    }
+
+    /// Moderately compressible with occasional repeats (documents, source)
+    #[must_use]
+    pub fn text_like(seed: u64) -> Self {
+        Self {
+            seed,
+            compressibility: 0.6,
+            duplication: 0.1,
+        }
+    }
+
+    /// Heavily duplicated blocks (VM images, backup archives)
+    #[must_use]
+    pub fn duplicated(seed: u64) -> Self {
+        Self {
+            seed,
+            compressibility: 0.3,
+            duplication: 0.5,
+        }
+    }
+
+    /// Generate `len` bytes in memory
+    #[must_use]
+    pub fn generate(&self, len: usize) -> Vec<u8> {
+        let mut out = Vec::with_capacity(len);
+        let mut stream = BlockStream::new(self);
+        let mut block = [0u8; BLOCK_SIZE];
+        while out.len() < len {
+            stream.next_block(&mut block);
+            let take = BLOCK_SIZE.min(len - out.len());
+            out.extend_from_slice(&block[..take]);
+        }
+        out
+    }
+
+    /// Stream `len` bytes to a file without holding them in memory
+    ///
+    /// Produces exactly the bytes [`TestDataSpec::generate`] would for the
+    /// same spec and length.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    pub fn write_file<P: AsRef<Path>>(&self, path: P, len: u64) -> io::Result<()> {
+        let mut writer = BufWriter::new(std::fs::File::create(path)?);
+        let mut stream = BlockStream::new(self);
+        let mut block = [0u8; BLOCK_SIZE];
+        let mut remaining = len;
+        while remaining > 0 {
+            stream.next_block(&mut block);
+            let take = (BLOCK_SIZE as u64).min(remaining) as usize;
+            writer.write_all(&block[..take])?;
+            remaining -= take as u64;
+        }
+        writer.flush()
+    }
+}
+
+/// SplitMix64: tiny, fast, identical on every platform
+///
+/// Deliberately self-contained so the corpus does not change when a
+/// `rand` upgrade alters algorithm defaults.
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in [0, 1)
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn fill(&mut self, buf: &mut [u8]) {
+        for chunk in buf.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+/// Produces the block sequence for a spec
+struct BlockStream {
+    rng: SplitMix64,
+    compressibility: f64,
+    duplication: f64,
+    /// Recently generated fresh blocks, candidates for duplication
+    pool: Vec<[u8; BLOCK_SIZE]>,
+    next_pool_slot: usize,
+}
+
+impl BlockStream {
+    fn new(spec: &TestDataSpec) -> Self {
+        Self {
+            rng: SplitMix64::new(spec.seed),
+            compressibility: spec.compressibility.clamp(0.0, 1.0),
+            duplication: spec.duplication.clamp(0.0, 1.0),
+            pool: Vec::new(),
+            next_pool_slot: 0,
+        }
+    }
+
+    fn next_block(&mut self, out: &mut [u8; BLOCK_SIZE]) {
+        // Duplicate an earlier block?
+        if !self.pool.is_empty() && self.rng.next_f64() < self.duplication {
+            let index = (self.rng.next_u64() as usize) % self.pool.len();
+            out.copy_from_slice(&self.pool[index]);
+            return;
+        }
+
+        // Fresh block: a constant run (what compressors exploit) followed
+        // by random bytes, split per the compressibility fraction
+        let run_len = (BLOCK_SIZE as f64 * self.compressibility) as usize;
+        let filler = self.rng.next_u64() as u8;
+        out[..run_len].fill(filler);
+        self.rng.fill(&mut out[run_len..]);
+
+        // Remember it for future duplication (bounded ring)
+        if self.pool.len() < POOL_CAP {
+            self.pool.push(*out);
+        } else {
+            self.pool[self.next_pool_slot] = *out;
+            self.next_pool_slot = (self.next_pool_slot + 1) % POOL_CAP;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Fraction of positions where a byte equals its predecessor — a
+    /// cheap stand-in for how much a run-length-aware compressor saves
+    fn repeat_ratio(data: &[u8]) -> f64 {
+        let repeats = data.windows(2).filter(|w| w[0] == w[1]).count();
+        repeats as f64 / (data.len() - 1) as f64
+    }
+
+    fn distinct_blocks(data: &[u8]) -> usize {
+        data.chunks(BLOCK_SIZE).collect::<HashSet<_>>().len()
+    }
+
+    #[test]
+    fn test_same_seed_same_bytes() {
+        let a = TestDataSpec::text_like(7).generate(256 * 1024);
+        let b = TestDataSpec::text_like(7).generate(256 * 1024);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_different_seed_different_bytes() {
+        let a = TestDataSpec::random(1).generate(4096);
+        let b = TestDataSpec::random(2).generate(4096);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_compressibility_profiles_differ() {
+        let random = TestDataSpec::random(9).generate(1024 * 1024);
+        let text = TestDataSpec::text_like(9).generate(1024 * 1024);
+
+        assert!(repeat_ratio(&random) < 0.05);
+        assert!(repeat_ratio(&text) > 0.5);
+    }
+
+    #[test]
+    fn test_duplication_profile_repeats_blocks() {
+        let random = TestDataSpec::random(3).generate(1024 * 1024);
+        let duplicated = TestDataSpec::duplicated(3).generate(1024 * 1024);
+
+        let total = 1024 * 1024 / BLOCK_SIZE;
+        assert_eq!(distinct_blocks(&random), total);
+        assert!(distinct_blocks(&duplicated) < total / 2);
+    }
+
+    #[test]
+    fn test_write_file_matches_generate() {
+        let spec = TestDataSpec::duplicated(11);
+        // Off-block-boundary length to cover the partial final block
+        let len = 3 * BLOCK_SIZE + 1234;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("corpus.dat");
+        spec.write_file(&path, len as u64).unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), spec.generate(len));
+    }
+}
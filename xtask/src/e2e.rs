@@ -0,0 +1,558 @@
+//! End-to-end test orchestration with Linux network namespaces
+//!
+//! `cargo xtask e2e` builds a small virtual network out of namespaces,
+//! veth pairs, nftables NAT, and tc netem link impairment, then runs
+//! transfer scenarios between `wraith` instances placed in it:
+//!
+//! - `direct` - both peers on the public segment, no NAT
+//! - `lossy` - direct topology with netem delay/jitter/loss applied
+//! - `punched` - both peers behind masquerading NATs; the receiver's NAT
+//!   carries a pre-established UDP mapping, modelling the state a
+//!   successful hole punch creates
+//! - `relayed` - a `wraith daemon --relay` instance on the public
+//!   segment, reached by clients from behind their NATs
+//!
+//! Topology (addresses are fixed so failures are reproducible):
+//!
+//! ```text
+//!   wr-a (10.11.1.2) ── wr-nata ──┐                ┌── wr-da (192.168.100.10)
+//!                                 ├─ wr-wan bridge ┤
+//!   wr-b (10.11.2.2) ── wr-natb ──┘ 192.168.100.0  └── wr-db (192.168.100.11)
+//! ```
+//!
+//! Requires root and the `ip`, `nft`, and `tc` binaries. Namespaces are
+//! torn down on exit unless `--keep` is given. `--ignored` additionally
+//! runs the workspace's `#[ignore]`d integration tests inside the wan
+//! namespace, giving them an isolated CI-like network.
+
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::time::Duration;
+
+/// Public segment carrying the bridge, direct peers, and NAT uplinks
+const WAN_NS: &str = "wr-wan";
+/// Bridge device inside the wan namespace
+const BRIDGE: &str = "wr-br0";
+/// UDP port used by receivers in every scenario
+const TRANSFER_PORT: u16 = 41888;
+/// Per-transfer wall-clock limit in seconds
+const TRANSFER_TIMEOUT_SECS: u32 = 60;
+
+/// One namespace with its address and, for NATed clients, its gateway
+struct Endpoint {
+    ns: &'static str,
+    addr: &'static str,
+}
+
+/// Direct peers sit on the public segment
+const DIRECT_A: Endpoint = Endpoint {
+    ns: "wr-da",
+    addr: "192.168.100.10",
+};
+const DIRECT_B: Endpoint = Endpoint {
+    ns: "wr-db",
+    addr: "192.168.100.11",
+};
+/// NATed peers; their public identity is the NAT uplink address
+const NAT_A: Endpoint = Endpoint {
+    ns: "wr-a",
+    addr: "10.11.1.2",
+};
+const NAT_B: Endpoint = Endpoint {
+    ns: "wr-b",
+    addr: "10.11.2.2",
+};
+/// Public addresses of the two NAT gateways
+const NAT_A_PUBLIC: &str = "192.168.100.2";
+const NAT_B_PUBLIC: &str = "192.168.100.3";
+/// Relay daemon address on the public segment (wan namespace itself)
+const RELAY_ADDR: &str = "192.168.100.1";
+
+/// Run the e2e orchestrator
+///
+/// `scenarios` filters which scenarios run (all when empty), `keep`
+/// leaves the namespaces behind for debugging, and `ignored` also runs
+/// the ignored integration tests inside the wan namespace.
+pub fn run(scenarios: Vec<String>, keep: bool, ignored: bool) -> anyhow::Result<()> {
+    let all = ["direct", "lossy", "punched", "relayed"];
+    for s in &scenarios {
+        if !all.contains(&s.as_str()) {
+            anyhow::bail!(
+                "unknown scenario '{s}' (expected one of: {})",
+                all.join(", ")
+            );
+        }
+    }
+    preflight()?;
+    let selected: Vec<&str> = if scenarios.is_empty() {
+        all.to_vec()
+    } else {
+        all.iter()
+            .copied()
+            .filter(|s| scenarios.iter().any(|w| w == s))
+            .collect()
+    };
+
+    println!("Building wraith binary...");
+    crate::run_command("cargo", &["build", "-p", "wraith-cli"])?;
+    let wraith = std::fs::canonicalize("target/debug/wraith")?;
+
+    let lab = Lab::create(keep)?;
+
+    let mut failures = Vec::new();
+    for scenario in &selected {
+        println!("--- scenario: {scenario} ---");
+        let result = match *scenario {
+            "direct" => run_transfer(&wraith, &DIRECT_A, &DIRECT_B, DIRECT_B.addr),
+            "lossy" => {
+                lab.with_impairment(|| run_transfer(&wraith, &DIRECT_A, &DIRECT_B, DIRECT_B.addr))
+            }
+            "punched" => run_transfer(&wraith, &NAT_A, &NAT_B, NAT_B_PUBLIC),
+            "relayed" => run_relayed(&wraith),
+            _ => unreachable!(),
+        };
+        match result {
+            Ok(()) => println!("scenario {scenario}: PASS"),
+            Err(e) => {
+                eprintln!("scenario {scenario}: FAIL ({e})");
+                failures.push((*scenario).to_string());
+            }
+        }
+    }
+
+    if ignored {
+        println!("--- ignored integration tests (in {WAN_NS}) ---");
+        let status = Command::new("ip")
+            .args([
+                "netns",
+                "exec",
+                WAN_NS,
+                "cargo",
+                "test",
+                "-p",
+                "wraith-integration-tests",
+                "--",
+                "--ignored",
+                "--test-threads=1",
+            ])
+            .status()?;
+        if !status.success() {
+            failures.push("ignored-tests".to_string());
+        }
+    }
+
+    drop(lab);
+
+    if failures.is_empty() {
+        println!("All scenarios passed");
+        Ok(())
+    } else {
+        anyhow::bail!("failed: {}", failures.join(", "))
+    }
+}
+
+/// Verify root and required tooling before touching the network stack
+fn preflight() -> anyhow::Result<()> {
+    if !cfg!(target_os = "linux") {
+        anyhow::bail!("e2e orchestration requires Linux network namespaces");
+    }
+    let uid = crate::command_output("id", &["-u"])?;
+    if uid != "0" {
+        anyhow::bail!("e2e orchestration must run as root (namespaces, nftables, tc)");
+    }
+    for tool in ["ip", "nft", "tc"] {
+        if Command::new(tool).arg("--version").output().is_err() {
+            anyhow::bail!("required tool '{tool}' not found in PATH");
+        }
+    }
+    Ok(())
+}
+
+/// The virtual network; tears itself down on drop unless kept
+struct Lab {
+    keep: bool,
+    namespaces: Vec<&'static str>,
+}
+
+impl Lab {
+    /// Build the full topology: bridge, direct peers, and two NATs
+    fn create(keep: bool) -> anyhow::Result<Self> {
+        let mut lab = Self {
+            keep,
+            namespaces: Vec::new(),
+        };
+
+        println!("Creating network namespaces...");
+        for ns in [
+            WAN_NS,
+            DIRECT_A.ns,
+            DIRECT_B.ns,
+            "wr-nata",
+            "wr-natb",
+            NAT_A.ns,
+            NAT_B.ns,
+        ] {
+            // A stale namespace from an interrupted run is replaced
+            let _ = Command::new("ip").args(["netns", "del", ns]).output();
+            sh("ip", &["netns", "add", ns])?;
+            lab.namespaces.push(ns);
+            in_ns(ns, "ip", &["link", "set", "lo", "up"])?;
+        }
+
+        // Public segment: bridge with the relay address
+        in_ns(WAN_NS, "ip", &["link", "add", BRIDGE, "type", "bridge"])?;
+        in_ns(
+            WAN_NS,
+            "ip",
+            &["addr", "add", &format!("{RELAY_ADDR}/24"), "dev", BRIDGE],
+        )?;
+        in_ns(WAN_NS, "ip", &["link", "set", BRIDGE, "up"])?;
+
+        // Direct peers straight onto the bridge
+        lab.attach_to_bridge(DIRECT_A.ns, "da", &format!("{}/24", DIRECT_A.addr))?;
+        lab.attach_to_bridge(DIRECT_B.ns, "db", &format!("{}/24", DIRECT_B.addr))?;
+
+        // NATed sides: client <-> gateway <-> bridge
+        lab.build_nat("wr-nata", NAT_A.ns, NAT_A_PUBLIC, "10.11.1.1", NAT_A.addr)?;
+        lab.build_nat("wr-natb", NAT_B.ns, NAT_B_PUBLIC, "10.11.2.1", NAT_B.addr)?;
+
+        // Punched scenario: pre-established inbound mapping on NAT B,
+        // standing in for the conntrack state a hole punch creates
+        in_ns(
+            "wr-natb",
+            "nft",
+            &[
+                "add",
+                "rule",
+                "ip",
+                "nat",
+                "prerouting",
+                "iifname",
+                "pub0",
+                "udp",
+                "dport",
+                &TRANSFER_PORT.to_string(),
+                "dnat",
+                "to",
+                &format!("{}:{}", NAT_B.addr, TRANSFER_PORT),
+            ],
+        )?;
+
+        Ok(lab)
+    }
+
+    /// Attach a namespace to the wan bridge with a veth pair
+    fn attach_to_bridge(&self, ns: &str, tag: &str, cidr: &str) -> anyhow::Result<()> {
+        let host_side = format!("wrv-{tag}");
+        sh(
+            "ip",
+            &[
+                "link", "add", &host_side, "type", "veth", "peer", "name", "eth0",
+            ],
+        )?;
+        sh("ip", &["link", "set", &host_side, "netns", WAN_NS])?;
+        sh("ip", &["link", "set", "eth0", "netns", ns])?;
+        in_ns(WAN_NS, "ip", &["link", "set", &host_side, "master", BRIDGE])?;
+        in_ns(WAN_NS, "ip", &["link", "set", &host_side, "up"])?;
+        in_ns(ns, "ip", &["addr", "add", cidr, "dev", "eth0"])?;
+        in_ns(ns, "ip", &["link", "set", "eth0", "up"])?;
+        Ok(())
+    }
+
+    /// Build one masquerading NAT gateway and the client behind it
+    fn build_nat(
+        &self,
+        nat_ns: &str,
+        client_ns: &str,
+        public_addr: &str,
+        gateway_addr: &str,
+        client_addr: &str,
+    ) -> anyhow::Result<()> {
+        // Uplink: gateway's pub0 onto the bridge
+        let tag = &nat_ns[3..]; // "nata" / "natb"
+        let host_side = format!("wrv-{tag}");
+        sh(
+            "ip",
+            &[
+                "link", "add", &host_side, "type", "veth", "peer", "name", "pub0",
+            ],
+        )?;
+        sh("ip", &["link", "set", &host_side, "netns", WAN_NS])?;
+        sh("ip", &["link", "set", "pub0", "netns", nat_ns])?;
+        in_ns(WAN_NS, "ip", &["link", "set", &host_side, "master", BRIDGE])?;
+        in_ns(WAN_NS, "ip", &["link", "set", &host_side, "up"])?;
+        in_ns(
+            nat_ns,
+            "ip",
+            &["addr", "add", &format!("{public_addr}/24"), "dev", "pub0"],
+        )?;
+        in_ns(nat_ns, "ip", &["link", "set", "pub0", "up"])?;
+
+        // Downlink: gateway's lan0 to the client's eth0
+        sh(
+            "ip",
+            &[
+                "link", "add", "lan0", "type", "veth", "peer", "name", "eth0",
+            ],
+        )?;
+        sh("ip", &["link", "set", "lan0", "netns", nat_ns])?;
+        sh("ip", &["link", "set", "eth0", "netns", client_ns])?;
+        in_ns(
+            nat_ns,
+            "ip",
+            &["addr", "add", &format!("{gateway_addr}/24"), "dev", "lan0"],
+        )?;
+        in_ns(nat_ns, "ip", &["link", "set", "lan0", "up"])?;
+        in_ns(
+            client_ns,
+            "ip",
+            &["addr", "add", &format!("{client_addr}/24"), "dev", "eth0"],
+        )?;
+        in_ns(client_ns, "ip", &["link", "set", "eth0", "up"])?;
+        in_ns(
+            client_ns,
+            "ip",
+            &["route", "add", "default", "via", gateway_addr],
+        )?;
+
+        // Forwarding and masquerade on the gateway
+        in_ns(nat_ns, "sysctl", &["-qw", "net.ipv4.ip_forward=1"])?;
+        in_ns(nat_ns, "nft", &["add", "table", "ip", "nat"])?;
+        in_ns(
+            nat_ns,
+            "nft",
+            &[
+                "add",
+                "chain",
+                "ip",
+                "nat",
+                "postrouting",
+                "{",
+                "type",
+                "nat",
+                "hook",
+                "postrouting",
+                "priority",
+                "100",
+                ";",
+                "}",
+            ],
+        )?;
+        in_ns(
+            nat_ns,
+            "nft",
+            &[
+                "add",
+                "chain",
+                "ip",
+                "nat",
+                "prerouting",
+                "{",
+                "type",
+                "nat",
+                "hook",
+                "prerouting",
+                "priority",
+                "-100",
+                ";",
+                "}",
+            ],
+        )?;
+        in_ns(
+            nat_ns,
+            "nft",
+            &[
+                "add",
+                "rule",
+                "ip",
+                "nat",
+                "postrouting",
+                "oifname",
+                "pub0",
+                "masquerade",
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Run a closure with netem impairment on the direct peers' links
+    fn with_impairment<F: FnOnce() -> anyhow::Result<()>>(&self, f: F) -> anyhow::Result<()> {
+        for ns in [DIRECT_A.ns, DIRECT_B.ns] {
+            in_ns(
+                ns,
+                "tc",
+                &[
+                    "qdisc", "add", "dev", "eth0", "root", "netem", "delay", "20ms", "5ms", "loss",
+                    "3%",
+                ],
+            )?;
+        }
+        let result = f();
+        for ns in [DIRECT_A.ns, DIRECT_B.ns] {
+            let _ = in_ns(ns, "tc", &["qdisc", "del", "dev", "eth0", "root"]);
+        }
+        result
+    }
+}
+
+impl Drop for Lab {
+    fn drop(&mut self) {
+        if self.keep {
+            println!("Keeping namespaces: {}", self.namespaces.join(", "));
+            return;
+        }
+        // Deleting a namespace removes its links, addresses, and rules
+        for ns in &self.namespaces {
+            let _ = Command::new("ip").args(["netns", "del", ns]).output();
+        }
+    }
+}
+
+/// One transfer: receiver in `to`, sender in `from` targeting `dest_addr`
+///
+/// Writes a pseudo-random test file, runs `wraith receive` and
+/// `wraith send`, then compares checksums of source and received file.
+fn run_transfer(
+    wraith: &Path,
+    from: &Endpoint,
+    to: &Endpoint,
+    dest_addr: &str,
+) -> anyhow::Result<()> {
+    let work = std::env::temp_dir().join(format!("wraith-e2e-{}-{}", from.ns, to.ns));
+    let _ = std::fs::remove_dir_all(&work);
+    let outbox = work.join("out");
+    std::fs::create_dir_all(&outbox)?;
+
+    // 4 MiB of deterministic, incompressible-enough test data
+    let source = work.join("payload.bin");
+    let mut data = vec![0u8; 4 * 1024 * 1024];
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    for chunk in data.chunks_mut(8) {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1);
+        let bytes = state.to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+    std::fs::write(&source, &data)?;
+
+    let mut receiver = spawn_in_ns(
+        to.ns,
+        wraith,
+        &[
+            "--quiet",
+            "receive",
+            "--output",
+            outbox.to_str().unwrap_or_default(),
+            "--bind",
+            &format!("0.0.0.0:{TRANSFER_PORT}"),
+            "--auto-accept",
+        ],
+    )?;
+    std::thread::sleep(Duration::from_secs(1));
+
+    let send_result = in_ns_with_timeout(
+        from.ns,
+        TRANSFER_TIMEOUT_SECS,
+        wraith,
+        &[
+            "--quiet",
+            "send",
+            source.to_str().unwrap_or_default(),
+            &format!("{dest_addr}:{TRANSFER_PORT}"),
+        ],
+    );
+    let _ = receiver.kill();
+    let _ = receiver.wait();
+    send_result?;
+
+    let received = outbox.join("payload.bin");
+    if !received.exists() {
+        anyhow::bail!("receiver produced no output file");
+    }
+    let sent_sum = crate::command_output("sha256sum", &[source.to_str().unwrap_or_default()])?;
+    let recv_sum = crate::command_output("sha256sum", &[received.to_str().unwrap_or_default()])?;
+    let sent_hash = sent_sum.split_whitespace().next().unwrap_or_default();
+    let recv_hash = recv_sum.split_whitespace().next().unwrap_or_default();
+    if sent_hash != recv_hash {
+        anyhow::bail!("checksum mismatch: sent {sent_hash}, received {recv_hash}");
+    }
+    Ok(())
+}
+
+/// Relayed scenario: relay daemon on the public segment, clients behind NAT
+///
+/// Verifies both NATed clients can complete a handshake with the relay
+/// (`wraith ping` through the masquerade). Full relay-mediated transfers
+/// follow once `wraith send` accepts peer IDs with relay fallback.
+fn run_relayed(wraith: &Path) -> anyhow::Result<()> {
+    let mut relay = spawn_in_ns(
+        WAN_NS,
+        wraith,
+        &[
+            "--quiet",
+            "daemon",
+            "--bind",
+            &format!("{RELAY_ADDR}:{TRANSFER_PORT}"),
+            "--relay",
+        ],
+    )?;
+    std::thread::sleep(Duration::from_secs(1));
+
+    let mut result = Ok(());
+    for client in [&NAT_A, &NAT_B] {
+        let ping = in_ns_with_timeout(
+            client.ns,
+            TRANSFER_TIMEOUT_SECS,
+            wraith,
+            &[
+                "--quiet",
+                "ping",
+                &format!("{RELAY_ADDR}:{TRANSFER_PORT}"),
+                "--count",
+                "3",
+            ],
+        );
+        if let Err(e) = ping {
+            result = Err(anyhow::anyhow!("{} could not reach relay: {e}", client.ns));
+            break;
+        }
+    }
+
+    let _ = relay.kill();
+    let _ = relay.wait();
+    result
+}
+
+/// Run a command to completion, failing on non-zero exit
+fn sh(program: &str, args: &[&str]) -> anyhow::Result<()> {
+    crate::run_command(program, args)
+}
+
+/// Run a command inside a namespace to completion
+fn in_ns(ns: &str, program: &str, args: &[&str]) -> anyhow::Result<()> {
+    let mut full = vec!["netns", "exec", ns, program];
+    full.extend_from_slice(args);
+    crate::run_command("ip", &full)
+}
+
+/// Run a binary inside a namespace under a wall-clock limit
+fn in_ns_with_timeout(ns: &str, secs: u32, binary: &Path, args: &[&str]) -> anyhow::Result<()> {
+    let limit = secs.to_string();
+    let bin = binary.to_str().unwrap_or_default();
+    let mut full = vec!["netns", "exec", ns, "timeout", &limit, bin];
+    full.extend_from_slice(args);
+    crate::run_command("ip", &full)
+}
+
+/// Spawn a long-running binary inside a namespace
+fn spawn_in_ns(ns: &str, binary: &Path, args: &[&str]) -> anyhow::Result<Child> {
+    let child = Command::new("ip")
+        .args(["netns", "exec", ns])
+        .arg(binary)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+    Ok(child)
+}
@@ -10,9 +10,15 @@
 //! - `ci` - Run all CI checks
 //! - `coverage` - Generate code coverage report
 //! - `doc` - Generate documentation
-//! - `build-xdp` - Build XDP program (requires root)
+//! - `build-xdp` - Build XDP program object (requires clang)
+//! - `dist` - Build release binaries and packaged archives
+//! - `profiles` - Generate AppArmor and SELinux profiles for the daemon
+//! - `e2e` - Run end-to-end scenarios in network namespaces (root, Linux)
+
+mod e2e;
 
 use clap::{Parser, Subcommand};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 #[derive(Parser)]
@@ -37,8 +43,52 @@ enum Commands {
     /// Run all CI checks
     Ci,
 
-    /// Build XDP program (requires root)
-    BuildXdp,
+    /// Build XDP program object (requires clang with BPF target)
+    BuildXdp {
+        /// Output path for the compiled object
+        #[arg(long, default_value = "target/xdp/xdp_filter.o")]
+        output: String,
+    },
+
+    /// Build release binaries and packaged archives
+    Dist {
+        /// Target triples to build (repeatable; defaults to the host)
+        ///
+        /// Cross targets need their std component installed
+        /// (`rustup target add <triple>`) and a suitable linker.
+        #[arg(long = "target")]
+        targets: Vec<String>,
+
+        /// Also build the XDP program object (bundled with Linux targets)
+        #[arg(long)]
+        xdp: bool,
+
+        /// Output directory for staged artifacts and archives
+        #[arg(long, default_value = "target/dist")]
+        out_dir: String,
+    },
+
+    /// Generate AppArmor and SELinux profiles for the daemon
+    Profiles {
+        /// Output directory for the generated profiles
+        #[arg(long, default_value = "target/dist/security")]
+        out_dir: String,
+    },
+
+    /// Run end-to-end scenarios in network namespaces (requires root)
+    E2e {
+        /// Scenarios to run: direct, lossy, punched, relayed (default all)
+        #[arg(long = "scenario")]
+        scenarios: Vec<String>,
+
+        /// Keep namespaces and NAT rules after the run for debugging
+        #[arg(long)]
+        keep: bool,
+
+        /// Also run the ignored integration tests inside the wan namespace
+        #[arg(long)]
+        ignored: bool,
+    },
 
     /// Generate documentation
     Doc,
@@ -79,10 +129,25 @@ fn main() -> anyhow::Result<()> {
             run_command("cargo", &["test", "--all-features", "--workspace"])?;
             println!("All CI checks passed!");
         }
-        Commands::BuildXdp => {
-            println!("Building XDP program...");
-            println!("Note: This requires the wraith-xdp crate and eBPF toolchain");
-            // TODO: Implement XDP build
+        Commands::BuildXdp { output } => {
+            build_xdp(Path::new(&output))?;
+        }
+        Commands::Dist {
+            targets,
+            xdp,
+            out_dir,
+        } => {
+            dist(targets, xdp, Path::new(&out_dir))?;
+        }
+        Commands::Profiles { out_dir } => {
+            security_profiles(Path::new(&out_dir))?;
+        }
+        Commands::E2e {
+            scenarios,
+            keep,
+            ignored,
+        } => {
+            e2e::run(scenarios, keep, ignored)?;
         }
         Commands::Doc => {
             run_command("cargo", &["doc", "--workspace", "--no-deps", "--open"])?;
@@ -173,3 +238,402 @@ fn run_command(program: &str, args: &[&str]) -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Capture a command's stdout as a trimmed string
+fn command_output(program: &str, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new(program).args(args).output()?;
+    if !output.status.success() {
+        anyhow::bail!("{program} {args:?} failed");
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Workspace version from the root Cargo.toml `[workspace.package]` table
+fn workspace_version() -> anyhow::Result<String> {
+    let manifest = std::fs::read_to_string("Cargo.toml")?;
+    let mut in_workspace_package = false;
+
+    for line in manifest.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_workspace_package = line == "[workspace.package]";
+        } else if in_workspace_package {
+            if let Some(value) = line
+                .strip_prefix("version")
+                .and_then(|rest| rest.trim_start().strip_prefix('='))
+            {
+                return Ok(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+
+    anyhow::bail!("workspace version not found in Cargo.toml")
+}
+
+/// Build the XDP packet filter object with clang
+///
+/// Compiles `crates/wraith-xdp/src/xdp_filter.c` for the BPF target. The
+/// object can then be attached with `ip link set dev <if> xdp obj ...` or
+/// loaded through the wraith-xdp crate.
+fn build_xdp(output: &Path) -> anyhow::Result<()> {
+    let source = "crates/wraith-xdp/src/xdp_filter.c";
+
+    if Command::new("clang").arg("--version").output().is_err() {
+        anyhow::bail!(
+            "clang not found; install clang and llvm (and libbpf headers) to build XDP programs"
+        );
+    }
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    println!("Building XDP program: {source} -> {}", output.display());
+    run_command(
+        "clang",
+        &[
+            "-O2",
+            "-g",
+            "-target",
+            "bpf",
+            "-c",
+            source,
+            "-o",
+            output.to_str().unwrap_or_default(),
+        ],
+    )?;
+    println!("XDP object built: {}", output.display());
+
+    Ok(())
+}
+
+/// Build release binaries for the requested targets and package them
+///
+/// For each target: builds `wraith` in release mode with the current
+/// commit embedded, stages the binary with docs, shell completions, and
+/// the man page, then produces a `.tar.gz` (Unix) or `.zip` (Windows)
+/// archive. A `SHA256SUMS` file covering all archives is written last.
+fn dist(targets: Vec<String>, xdp: bool, out_dir: &Path) -> anyhow::Result<()> {
+    let version = workspace_version()?;
+    let commit = command_output("git", &["rev-parse", "--short", "HEAD"])
+        .unwrap_or_else(|_| "unknown".to_string());
+    let host = command_output("rustc", &["-vV"])?
+        .lines()
+        .find_map(|l| l.strip_prefix("host: ").map(str::to_string))
+        .ok_or_else(|| anyhow::anyhow!("could not determine host triple"))?;
+
+    let targets = if targets.is_empty() {
+        vec![host.clone()]
+    } else {
+        targets
+    };
+
+    println!(
+        "Packaging wraith {version} ({commit}) for {} target(s)",
+        targets.len()
+    );
+    std::fs::create_dir_all(out_dir)?;
+
+    // Completions and the man page are target-independent; generate them
+    // once from a host build
+    println!("Building host binary for artifact generation...");
+    build_release_binary(&host, &commit, &host)?;
+    let host_binary = release_binary_path(&host, &host);
+
+    let completions_dir = out_dir.join("completions");
+    std::fs::create_dir_all(&completions_dir)?;
+    for shell in ["bash", "zsh", "fish", "powershell"] {
+        let ext = match shell {
+            "bash" => "wraith.bash",
+            "zsh" => "_wraith",
+            "fish" => "wraith.fish",
+            _ => "wraith.ps1",
+        };
+        let output = Command::new(&host_binary)
+            .args(["completions", shell])
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!("completion generation for {shell} failed");
+        }
+        std::fs::write(completions_dir.join(ext), output.stdout)?;
+    }
+
+    let man_page = Command::new(&host_binary).arg("manpage").output()?;
+    if !man_page.status.success() {
+        anyhow::bail!("man page generation failed");
+    }
+    std::fs::write(out_dir.join("wraith.1"), &man_page.stdout)?;
+
+    // Optional XDP object, bundled into Linux archives
+    let xdp_object = out_dir.join("xdp_filter.o");
+    if xdp {
+        build_xdp(&xdp_object)?;
+    }
+
+    // MAC profiles, bundled into Linux archives
+    let security_dir = out_dir.join("security");
+    security_profiles(&security_dir)?;
+
+    let mut archives = Vec::new();
+
+    for target in &targets {
+        println!("Building for {target}...");
+        build_release_binary(target, &commit, &host)?;
+
+        let is_windows = target.contains("windows");
+        let stage_name = format!("wraith-{version}-{target}");
+        let stage = out_dir.join(&stage_name);
+        if stage.exists() {
+            std::fs::remove_dir_all(&stage)?;
+        }
+        std::fs::create_dir_all(&stage)?;
+
+        // Binary
+        let binary = release_binary_path(target, &host);
+        let binary_name = if is_windows { "wraith.exe" } else { "wraith" };
+        std::fs::copy(&binary, stage.join(binary_name))?;
+
+        // Docs and licensing
+        for doc in ["README.md", "LICENSE", "CHANGELOG.md"] {
+            std::fs::copy(doc, stage.join(doc))?;
+        }
+
+        // Completions and man page
+        copy_dir(&completions_dir, &stage.join("completions"))?;
+        if !is_windows {
+            std::fs::create_dir_all(stage.join("man"))?;
+            std::fs::copy(out_dir.join("wraith.1"), stage.join("man/wraith.1"))?;
+        }
+
+        // XDP object and MAC profiles only make sense on Linux
+        if target.contains("linux") {
+            if xdp {
+                std::fs::copy(&xdp_object, stage.join("xdp_filter.o"))?;
+            }
+            copy_dir(&security_dir, &stage.join("security"))?;
+        }
+
+        // Archive: zip for Windows consumers, tar.gz elsewhere
+        let archive_name = if is_windows {
+            format!("{stage_name}.zip")
+        } else {
+            format!("{stage_name}.tar.gz")
+        };
+        let out_dir_str = out_dir.to_str().unwrap_or_default();
+        if is_windows {
+            run_command(
+                "zip",
+                &["-qr", &archive_name, &stage_name, "-x", "*.DS_Store"],
+            )
+            .or_else(|_| {
+                // Plain tar.gz fallback when zip is not installed
+                run_command(
+                    "tar",
+                    &["-czf", &archive_name, "-C", out_dir_str, &stage_name],
+                )
+            })?;
+        } else {
+            run_command(
+                "tar",
+                &["-czf", &archive_name, "-C", out_dir_str, &stage_name],
+            )?;
+        }
+
+        // Archives land in the CWD with the commands above; move them in
+        if Path::new(&archive_name).exists() {
+            std::fs::rename(&archive_name, out_dir.join(&archive_name))?;
+        }
+        println!("  {}", out_dir.join(&archive_name).display());
+        archives.push(archive_name);
+    }
+
+    // Checksums over all archives
+    let mut checksums = String::new();
+    for archive in &archives {
+        let sum = command_output(
+            "sha256sum",
+            &[out_dir.join(archive).to_str().unwrap_or_default()],
+        )?;
+        // sha256sum prints "<hash>  <path>"; keep just the file name
+        let hash = sum.split_whitespace().next().unwrap_or_default();
+        checksums.push_str(&format!("{hash}  {archive}\n"));
+    }
+    std::fs::write(out_dir.join("SHA256SUMS"), &checksums)?;
+
+    println!(
+        "Checksums written to {}",
+        out_dir.join("SHA256SUMS").display()
+    );
+    println!("Distribution complete: {} archive(s)", archives.len());
+
+    Ok(())
+}
+
+/// Generate AppArmor and SELinux profiles for the daemon
+///
+/// The profiles confine the daemon from the outside, complementing the
+/// in-process `wraith daemon --harden` mode: network and eBPF access for
+/// startup, config under the user's home, and nothing else. They are
+/// starting points — sites with a dedicated download directory or a
+/// non-default binary path should adjust before loading.
+fn security_profiles(out_dir: &Path) -> anyhow::Result<()> {
+    let version = workspace_version()?;
+    std::fs::create_dir_all(out_dir)?;
+
+    let apparmor = format!(
+        r#"# AppArmor profile for the WRAITH daemon (wraith {version})
+#
+# Install:
+#   cp wraith.apparmor /etc/apparmor.d/usr.local.bin.wraith
+#   apparmor_parser -r /etc/apparmor.d/usr.local.bin.wraith
+#
+# Adjust the binary path and download locations for your deployment,
+# then run the daemon with --harden so in-process capability dropping
+# and seccomp back this profile up.
+
+abi <abi/3.0>,
+
+#include <tunables/global>
+
+/usr/local/bin/wraith {{
+  #include <abstractions/base>
+  #include <abstractions/nameservice>
+
+  # UDP transport, TCP metrics endpoint, netlink for XDP attach
+  network inet dgram,
+  network inet6 dgram,
+  network inet stream,
+  network inet6 stream,
+  network netlink raw,
+
+  # Startup-only privileges (socket options, XDP/eBPF load); the daemon
+  # drops these itself once listeners are up when run with --harden
+  capability net_admin,
+  capability net_raw,
+  capability bpf,
+  capability perfmon,
+
+  # Binary, config, identity and trust store
+  /usr/local/bin/wraith mr,
+  owner @{{HOME}}/.wraith/ rw,
+  owner @{{HOME}}/.wraith/** rwk,
+
+  # Received files and sidecar manifests (narrow this to the site's
+  # download directory where one is enforced)
+  owner @{{HOME}}/** rw,
+
+  # XDP object shipped alongside the binary
+  /usr/local/share/wraith/xdp_filter.o r,
+
+  # No shelling out, ever
+  deny /usr/bin/** x,
+  deny /bin/** x,
+  deny @{{PROC}}/*/mem rw,
+}}
+"#
+    );
+    std::fs::write(out_dir.join("wraith.apparmor"), apparmor)?;
+
+    let selinux_te = format!(
+        r#"# SELinux policy module for the WRAITH daemon (wraith {version})
+#
+# Build and install (requires selinux-policy-devel):
+#   checkmodule -M -m -o wraith.mod wraith.te
+#   semodule_package -o wraith.pp -m wraith.mod -f wraith.fc
+#   semodule -i wraith.pp
+#   restorecon -v /usr/local/bin/wraith
+#
+# Review before deploying; the file rules assume config and downloads
+# live under the invoking user's home directory.
+
+policy_module(wraith, {version})
+
+type wraith_t;
+type wraith_exec_t;
+init_daemon_domain(wraith_t, wraith_exec_t)
+
+# UDP transport and the TCP metrics endpoint
+allow wraith_t self:udp_socket create_socket_perms;
+allow wraith_t self:tcp_socket create_stream_socket_perms;
+corenet_udp_bind_generic_node(wraith_t)
+corenet_tcp_bind_generic_node(wraith_t)
+corenet_udp_bind_all_unreserved_ports(wraith_t)
+corenet_tcp_bind_all_unreserved_ports(wraith_t)
+
+# XDP/eBPF program load at startup (dropped in-process with --harden)
+allow wraith_t self:netlink_generic_socket create_socket_perms;
+allow wraith_t self:capability {{ net_admin net_raw }};
+allow wraith_t self:capability2 {{ bpf perfmon }};
+allow wraith_t self:bpf {{ map_create map_read map_write prog_load prog_run }};
+
+# Config, identity and received files in the user's home
+userdom_manage_user_home_content_files(wraith_t)
+userdom_manage_user_home_content_dirs(wraith_t)
+
+# DNS for bootstrap node resolution
+sysnet_dns_name_resolve(wraith_t)
+"#
+    );
+    std::fs::write(out_dir.join("wraith.te"), selinux_te)?;
+
+    let selinux_fc = r#"# File contexts for the WRAITH daemon SELinux module
+/usr/local/bin/wraith    --    gen_context(system_u:object_r:wraith_exec_t,s0)
+"#;
+    std::fs::write(out_dir.join("wraith.fc"), selinux_fc)?;
+
+    println!("Security profiles written to {}:", out_dir.display());
+    for name in ["wraith.apparmor", "wraith.te", "wraith.fc"] {
+        println!("  {}", out_dir.join(name).display());
+    }
+
+    Ok(())
+}
+
+/// Build the release `wraith` binary for one target with commit metadata
+fn build_release_binary(target: &str, commit: &str, host: &str) -> anyhow::Result<()> {
+    let mut args = vec!["build", "--release", "-p", "wraith-cli"];
+    if target != host {
+        args.extend(["--target", target]);
+    }
+
+    let status = Command::new("cargo")
+        .args(&args)
+        .env("WRAITH_BUILD_COMMIT", commit)
+        .status()?;
+    if !status.success() {
+        anyhow::bail!("release build for {target} failed");
+    }
+    Ok(())
+}
+
+/// Path of the release binary for a target (host builds skip the triple dir)
+fn release_binary_path(target: &str, host: &str) -> PathBuf {
+    let binary = if target.contains("windows") {
+        "wraith.exe"
+    } else {
+        "wraith"
+    };
+    if target == host {
+        Path::new("target/release").join(binary)
+    } else {
+        Path::new("target")
+            .join(target)
+            .join("release")
+            .join(binary)
+    }
+}
+
+/// Recursively copy a directory
+fn copy_dir(from: &Path, to: &Path) -> anyhow::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let dest = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &dest)?;
+        } else {
+            std::fs::copy(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}